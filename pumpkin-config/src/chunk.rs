@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+/// How chunks are saved to the region files
+pub struct ChunkConfig {
+    /// The format the world's chunks are stored in
+    pub format: ChunkFormat,
+    pub compression: ChunkCompression,
+    /// Seconds between saving the chunks which changed, unloaded chunks stay in memory until then
+    pub autosave_interval: u64,
+    pub pregen: PregenConfig,
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        Self {
+            format: ChunkFormat::Anvil,
+            compression: ChunkCompression::default(),
+            autosave_interval: 30,
+            pregen: PregenConfig::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[serde(default)]
+/// Limits for `/pregen`, so generating chunks ahead of time doesn't slow down the players
+pub struct PregenConfig {
+    /// How many chunks are generated before the progress is saved
+    pub batch_size: usize,
+    /// The most chunks generated per second, 0 for no limit
+    pub chunks_per_second: u32,
+}
+
+impl Default for PregenConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 64,
+            chunks_per_second: 100,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[serde(default)]
+pub struct ChunkCompression {
+    pub algorithm: Compression,
+    /// A value between 0..9, not used by LZ4 and uncompressed chunks
+    /// 1 = Optimize for the best speed of encoding.
+    /// 9 = Optimize for the size of data being encoded.
+    pub level: u32,
+}
+
+impl Default for ChunkCompression {
+    fn default() -> Self {
+        Self {
+            algorithm: Compression::ZLib,
+            level: 6,
+        }
+    }
+}
+
+/// The compression schemes of region files, vanilla uses ZLib by default
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    GZip,
+    ZLib,
+    None,
+    /// Faster than ZLib but the files are larger, supported since 1.20.5
+    LZ4,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChunkFormat {
+    /// The region files vanilla uses, compressed chunk by chunk
+    Anvil,
+    /// Region files compressed as a whole with zstd, much smaller but vanilla can't read them
+    Linear,
+    /// Chunks are only kept in memory and lost on restart, useful for tests and minigames
+    Memory,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+use crate::LevelType;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(default)]
+/// A world next to the dimensions, known to players as `pumpkin:<name>`
+pub struct CustomWorldConfig {
+    pub name: String,
+    /// The dimension the world is generated and shown like
+    pub dimension: CustomWorldDimension,
+    /// The seed for world generation, empty uses the seed of the basic config
+    pub seed: String,
+    /// The world preset, only used by overworlds
+    pub level_type: LevelType,
+    /// The layers of flat worlds, like the generator settings of the server
+    pub generator_settings: String,
+}
+
+impl Default for CustomWorldConfig {
+    fn default() -> Self {
+        Self {
+            name: "world".to_string(),
+            dimension: CustomWorldDimension::Overworld,
+            seed: "".to_string(),
+            level_type: LevelType::Normal,
+            generator_settings: "".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomWorldDimension {
+    Overworld,
+    TheNether,
+    TheEnd,
+}
@@ -19,16 +19,22 @@ pub mod query;
 pub mod resource_pack;
 
 pub use auth::AuthenticationConfig;
+pub use chunk::ChunkConfig;
 pub use commands::CommandsConfig;
 pub use compression::CompressionConfig;
+pub use custom_world::{CustomWorldConfig, CustomWorldDimension};
 pub use lan_broadcast::LANBroadcastConfig;
+pub use moderation::ModerationConfig;
 pub use pvp::PVPConfig;
 pub use rcon::RCONConfig;
 pub use server_links::ServerLinksConfig;
 
+pub mod chunk;
 mod commands;
 pub mod compression;
+mod custom_world;
 mod lan_broadcast;
+mod moderation;
 pub mod op;
 mod pvp;
 mod rcon;
@@ -63,6 +69,8 @@ pub struct AdvancedConfiguration {
     pub query: QueryConfig,
     pub server_links: ServerLinksConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub chunk: ChunkConfig,
+    pub moderation: ModerationConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -72,6 +80,11 @@ pub struct BasicConfiguration {
     pub server_address: SocketAddr,
     /// The seed for world generation.
     pub seed: String,
+    /// The world preset the overworld is generated with.
+    pub level_type: LevelType,
+    /// The layers of flat worlds, as a superflat preset string or its JSON form. Empty uses the
+    /// classic flat preset.
+    pub generator_settings: String,
     /// The maximum number of players allowed on the server. Specifying `0` disables the limit.
     pub max_players: u32,
     /// The maximum view distance for players.
@@ -82,8 +95,16 @@ pub struct BasicConfiguration {
     pub default_difficulty: Difficulty,
     /// The op level assign by the /op command
     pub op_permission_level: PermissionLvl,
+    /// Whether only players on the whitelist and operators may join.
+    pub white_list: bool,
+    /// Whether players who aren't on the whitelist anymore are kicked when it changes.
+    pub enforce_whitelist: bool,
     /// Whether the Nether dimension is enabled.
     pub allow_nether: bool,
+    /// Whether the End dimension is enabled.
+    pub allow_end: bool,
+    /// Additional worlds with their own generator, known to players as `pumpkin:<name>`.
+    pub custom_worlds: Vec<CustomWorldConfig>,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
@@ -108,12 +129,18 @@ impl Default for BasicConfiguration {
         Self {
             server_address: SocketAddr::new(Ipv4Addr::new(0, 0, 0, 0).into(), 25565),
             seed: "".to_string(),
+            level_type: LevelType::Normal,
+            generator_settings: "".to_string(),
             max_players: 100000,
             view_distance: NonZeroU8::new(10).unwrap(),
             simulation_distance: NonZeroU8::new(10).unwrap(),
             default_difficulty: Difficulty::Normal,
             op_permission_level: PermissionLvl::Four,
+            white_list: false,
+            enforce_whitelist: false,
             allow_nether: true,
+            allow_end: true,
+            custom_worlds: Vec::new(),
             hardcore: false,
             online_mode: true,
             encryption: true,
@@ -127,6 +154,19 @@ impl Default for BasicConfiguration {
     }
 }
 
+/// The world presets of vanilla's `level-type`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelType {
+    Normal,
+    /// Layers of blocks described by the generator settings
+    Flat,
+    /// Every biome covers a larger area
+    LargeBiomes,
+    /// Taller and steeper terrain
+    Amplified,
+}
+
 trait LoadConfiguration {
     fn load() -> Self
     where
@@ -183,6 +223,31 @@ impl LoadConfiguration for AdvancedConfiguration {
     }
 }
 
+impl BasicConfiguration {
+    /// Saves whether the whitelist is on into the configuration file, like vanilla's
+    /// `setUsingWhitelist`. The configuration loaded on startup stays the same
+    pub fn save_white_list(white_list: bool) {
+        let path = env::current_dir()
+            .unwrap()
+            .join(CONFIG_ROOT_FOLDER)
+            .join(Self::get_path());
+        let result = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| toml::from_str::<Self>(&content).map_err(|err| err.to_string()))
+            .and_then(|mut config| {
+                config.white_list = white_list;
+                let content = toml::to_string(&config).map_err(|err| err.to_string())?;
+                fs::write(&path, content).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            warn!(
+                "Couldn't save the whitelist to {:?}. Reason: {}",
+                &path, err
+            );
+        }
+    }
+}
+
 impl LoadConfiguration for BasicConfiguration {
     fn get_path() -> &'static Path {
         Path::new("configuration.toml")
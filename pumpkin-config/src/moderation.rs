@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct ModerationConfig {
+    /// Shown to players who aren't on the whitelist while it is on
+    pub not_whitelisted_message: String,
+    /// Shown to banned players, `{reason}` is replaced by the reason of the ban
+    pub banned_message: String,
+    /// Shown to players whose IP address is banned, `{reason}` is replaced by the reason of the ban
+    pub ip_banned_message: String,
+    /// Added on a new line for bans which expire, `{expiration}` is replaced by the date
+    pub ban_expiration_message: String,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            not_whitelisted_message: "You are not white-listed on this server!".to_string(),
+            banned_message: "You are banned from this server.\nReason: {reason}".to_string(),
+            ip_banned_message: "Your IP address is banned from this server.\nReason: {reason}"
+                .to_string(),
+            ban_expiration_message: "Your ban will be removed on {expiration}".to_string(),
+        }
+    }
+}
@@ -7,6 +7,8 @@ pub struct Op {
     pub uuid: Uuid,
     pub name: String,
     pub level: PermissionLvl,
+    /// Saved like vanilla does, older files used the snake case name
+    #[serde(rename = "bypassesPlayerLimit", alias = "bypasses_player_limit")]
     pub bypasses_player_limit: bool,
 }
 
@@ -9,7 +9,7 @@ pub use permission::PermissionLvl;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Serialize, Deserialize, Clone)]
+#[derive(PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Debug)]
 pub enum Difficulty {
     Peaceful,
     Easy,
@@ -1,6 +1,6 @@
 use super::{position::WorldPosition, vector3::Vector3};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BoundingBox {
     pub min_x: f64,
     pub min_y: f64,
@@ -50,6 +50,42 @@ impl BoundingBox {
         }
     }
 
+    /// The box moved by the offset
+    pub fn offset(&self, offset: Vector3<f64>) -> Self {
+        Self {
+            min_x: self.min_x + offset.x,
+            min_y: self.min_y + offset.y,
+            min_z: self.min_z + offset.z,
+            max_x: self.max_x + offset.x,
+            max_y: self.max_y + offset.y,
+            max_z: self.max_z + offset.z,
+        }
+    }
+
+    /// The box stretched in the direction of the motion, covering everything it passes
+    pub fn expand_towards(&self, motion: Vector3<f64>) -> Self {
+        Self {
+            min_x: self.min_x + motion.x.min(0.0),
+            min_y: self.min_y + motion.y.min(0.0),
+            min_z: self.min_z + motion.z.min(0.0),
+            max_x: self.max_x + motion.x.max(0.0),
+            max_y: self.max_y + motion.y.max(0.0),
+            max_z: self.max_z + motion.z.max(0.0),
+        }
+    }
+
+    /// The box grown by the amount on every side, negative amounts shrink it
+    pub fn inflate(&self, amount: f64) -> Self {
+        Self {
+            min_x: self.min_x - amount,
+            min_y: self.min_y - amount,
+            min_z: self.min_z - amount,
+            max_x: self.max_x + amount,
+            max_y: self.max_y + amount,
+            max_z: self.max_z + amount,
+        }
+    }
+
     pub fn intersects(&self, other: &BoundingBox) -> bool {
         self.min_x < other.max_x
             && self.max_x > other.min_x
@@ -59,6 +95,33 @@ impl BoundingBox {
             && self.max_z > other.min_z
     }
 
+    /// How far along the line from `from` to `to` it enters the box, from 0 at `from` to 1 at
+    /// `to`. `None` if the line misses the box, lines starting inside of it hit it right away
+    pub fn clip(&self, from: Vector3<f64>, to: Vector3<f64>) -> Option<f64> {
+        let direction = to.sub(&from);
+        let mut enter: f64 = 0.0;
+        let mut exit: f64 = 1.0;
+        for (start, delta, min, max) in [
+            (from.x, direction.x, self.min_x, self.max_x),
+            (from.y, direction.y, self.min_y, self.max_y),
+            (from.z, direction.z, self.min_z, self.max_z),
+        ] {
+            if delta == 0.0 {
+                if start < min || start > max {
+                    return None;
+                }
+                continue;
+            }
+            let (a, b) = ((min - start) / delta, (max - start) / delta);
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+            if enter > exit {
+                return None;
+            }
+        }
+        Some(enter)
+    }
+
     pub fn squared_magnitude(&self, pos: Vector3<f64>) -> f64 {
         let d = f64::max(f64::max(self.min_x - pos.x, pos.x - self.max_x), 0.0);
         let e = f64::max(f64::max(self.min_y - pos.y, pos.y - self.max_y), 0.0);
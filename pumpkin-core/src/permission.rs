@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use num_derive::{FromPrimitive, ToPrimitive};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -44,6 +46,7 @@ impl<'de> Deserialize<'de> for PermissionLvl {
         let value = u8::deserialize(deserializer)?;
         match value {
             0 => Ok(PermissionLvl::Zero),
+            1 => Ok(PermissionLvl::One),
             2 => Ok(PermissionLvl::Two),
             3 => Ok(PermissionLvl::Three),
             4 => Ok(PermissionLvl::Four),
@@ -54,3 +57,165 @@ impl<'de> Deserialize<'de> for PermissionLvl {
         }
     }
 }
+
+/// The level everyone needs for nodes nobody registered, unless they were granted explicitly
+const UNREGISTERED_NODE_LVL: PermissionLvl = PermissionLvl::Four;
+
+/// The permission nodes known to the server, like `minecraft.command.gamemode`, each with the
+/// level that grants it by default
+#[derive(Default)]
+pub struct PermissionRegistry {
+    defaults: HashMap<String, PermissionLvl>,
+}
+
+impl PermissionRegistry {
+    /// Registers the node, replacing its default level if it was registered before
+    pub fn register(&mut self, node: &str, default: PermissionLvl) {
+        self.defaults.insert(node.to_string(), default);
+    }
+
+    /// The level that grants the node by default, none if it isn't registered
+    #[must_use]
+    pub fn default_lvl(&self, node: &str) -> Option<PermissionLvl> {
+        self.defaults.get(node).copied()
+    }
+
+    /// Every registered node
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.defaults.keys().map(String::as_str)
+    }
+
+    /// Whether someone with the level and the explicit grants has the node. Grants override the
+    /// level, the most specific one wins
+    #[must_use]
+    pub fn has_permission(
+        &self,
+        lvl: PermissionLvl,
+        grants: &PermissionGrants,
+        node: &str,
+    ) -> bool {
+        grants
+            .get(node)
+            .unwrap_or_else(|| lvl >= self.default_lvl(node).unwrap_or(UNREGISTERED_NODE_LVL))
+    }
+}
+
+/// Permission nodes granted or revoked for a single player, regardless of their level. A node
+/// ending in `.*` covers every node below it, `*` covers all nodes
+#[derive(Default, Clone)]
+pub struct PermissionGrants {
+    nodes: HashMap<String, bool>,
+}
+
+impl PermissionGrants {
+    /// Grants or revokes the node
+    pub fn set(&mut self, node: &str, value: bool) {
+        self.nodes.insert(node.to_string(), value);
+    }
+
+    /// Goes back to what the level grants
+    pub fn unset(&mut self, node: &str) {
+        self.nodes.remove(node);
+    }
+
+    /// Whether the node is granted, from the node itself or the closest wildcard above it. None if
+    /// nothing was set for it
+    #[must_use]
+    pub fn get(&self, node: &str) -> Option<bool> {
+        if let Some(value) = self.nodes.get(node) {
+            return Some(*value);
+        }
+        let mut parent = node;
+        while let Some((prefix, _)) = parent.rsplit_once('.') {
+            if let Some(value) = self.nodes.get(&format!("{prefix}.*")) {
+                return Some(*value);
+            }
+            parent = prefix;
+        }
+        self.nodes.get("*").copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PermissionGrants, PermissionLvl, PermissionRegistry};
+
+    #[test]
+    fn grants_are_unset_by_default() {
+        let grants = PermissionGrants::default();
+        assert_eq!(grants.get("minecraft.command.gamemode"), None);
+    }
+
+    #[test]
+    fn wildcards_cover_nodes_below_them() {
+        let mut grants = PermissionGrants::default();
+        grants.set("minecraft.command.*", true);
+        assert_eq!(grants.get("minecraft.command.gamemode"), Some(true));
+        assert_eq!(grants.get("minecraft.command.gamemode.other"), Some(true));
+        assert_eq!(grants.get("minecraft.selector"), None);
+        // The wildcard covers the nodes below it, not the node itself
+        assert_eq!(grants.get("minecraft.command"), None);
+
+        grants.set("*", false);
+        assert_eq!(grants.get("minecraft.selector"), Some(false));
+        assert_eq!(grants.get("minecraft.command.gamemode"), Some(true));
+    }
+
+    #[test]
+    fn most_specific_grant_wins() {
+        let mut grants = PermissionGrants::default();
+        grants.set("*", true);
+        grants.set("minecraft.*", false);
+        grants.set("minecraft.command.*", true);
+        grants.set("minecraft.command.stop", false);
+        assert_eq!(grants.get("minecraft.command.stop"), Some(false));
+        assert_eq!(grants.get("minecraft.command.kill"), Some(true));
+        assert_eq!(grants.get("minecraft.selector"), Some(false));
+        assert_eq!(grants.get("pumpkin.plugins"), Some(true));
+
+        grants.unset("minecraft.command.stop");
+        assert_eq!(grants.get("minecraft.command.stop"), Some(true));
+    }
+
+    #[test]
+    fn levels_grant_registered_nodes() {
+        let mut registry = PermissionRegistry::default();
+        registry.register("minecraft.command.gamemode", PermissionLvl::Two);
+        let grants = PermissionGrants::default();
+        assert!(!registry.has_permission(
+            PermissionLvl::One,
+            &grants,
+            "minecraft.command.gamemode"
+        ));
+        assert!(registry.has_permission(PermissionLvl::Two, &grants, "minecraft.command.gamemode"));
+        assert!(registry.has_permission(
+            PermissionLvl::Four,
+            &grants,
+            "minecraft.command.gamemode"
+        ));
+    }
+
+    #[test]
+    fn unregistered_nodes_need_the_highest_level() {
+        let registry = PermissionRegistry::default();
+        let grants = PermissionGrants::default();
+        assert!(!registry.has_permission(PermissionLvl::Three, &grants, "plugin.secret"));
+        assert!(registry.has_permission(PermissionLvl::Four, &grants, "plugin.secret"));
+    }
+
+    #[test]
+    fn grants_override_the_level() {
+        let mut registry = PermissionRegistry::default();
+        registry.register("minecraft.command.gamemode", PermissionLvl::Two);
+        registry.register("minecraft.command.help", PermissionLvl::Zero);
+        let mut grants = PermissionGrants::default();
+        grants.set("minecraft.command.gamemode", true);
+        grants.set("minecraft.command.help", false);
+        assert!(registry.has_permission(
+            PermissionLvl::Zero,
+            &grants,
+            "minecraft.command.gamemode"
+        ));
+        assert!(!registry.has_permission(PermissionLvl::Four, &grants, "minecraft.command.help"));
+    }
+}
@@ -47,6 +47,18 @@ impl<'a> TextComponent<'a> {
         }
     }
 
+    /// Text the client translates, the components fill in the placeholders of the translation
+    pub fn translate(key: impl Into<Cow<'a, str>>, with: Vec<TextComponent<'a>>) -> Self {
+        Self {
+            content: TextContent::Translate {
+                translate: key.into(),
+                with,
+            },
+            style: Style::default(),
+            extra: vec![],
+        }
+    }
+
     pub fn add_child(mut self, child: TextComponent<'a>) -> Self {
         self.extra.push(child);
         self
@@ -184,30 +196,40 @@ impl<'a> TextComponent<'a> {
         #[serde(rename_all = "camelCase")]
         struct TempStruct<'a> {
             #[serde(flatten)]
-            text: &'a TextContent<'a>,
+            text: TempContent<'a>,
             #[serde(flatten)]
             style: &'a Style<'a>,
             #[serde(default, skip_serializing_if = "Vec::is_empty")]
             #[serde(rename = "extra")]
             extra: Vec<TempStruct<'a>>,
         }
-        fn convert_extra<'a>(extra: &'a [TextComponent<'a>]) -> Vec<TempStruct<'a>> {
-            extra
-                .iter()
-                .map(|x| TempStruct {
-                    text: &x.content,
-                    style: &x.style,
-                    extra: convert_extra(&x.extra),
-                })
-                .collect()
+        // The components filling in translations have to be encoded like the others
+        #[derive(serde::Serialize)]
+        #[serde(untagged)]
+        enum TempContent<'a> {
+            Translate {
+                translate: &'a str,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                with: Vec<TempStruct<'a>>,
+            },
+            Other(&'a TextContent<'a>),
+        }
+        fn convert<'a>(component: &'a TextComponent<'a>) -> TempStruct<'a> {
+            let text = match &component.content {
+                TextContent::Translate { translate, with } => TempContent::Translate {
+                    translate,
+                    with: with.iter().map(convert).collect(),
+                },
+                content => TempContent::Other(content),
+            };
+            TempStruct {
+                text,
+                style: &component.style,
+                extra: component.extra.iter().map(convert).collect(),
+            }
         }
 
-        let temp_extra = convert_extra(&self.extra);
-        let astruct = TempStruct {
-            text: &self.content,
-            style: &self.style,
-            extra: temp_extra,
-        };
+        let astruct = convert(self);
         // dbg!(&serde_json::to_string(&astruct));
         // dbg!(pumpkin_nbt::serializer::to_bytes_unnamed(&astruct).unwrap().to_vec());
 
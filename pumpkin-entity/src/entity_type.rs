@@ -152,3 +152,470 @@ pub enum EntityType {
     Player = 147,
     FishingBobber = 148,
 }
+
+impl EntityType {
+    /// Every entity type, in registry order
+    pub const ALL: [Self; 149] = [
+        Self::AcaciaBoat,
+        Self::AcaciaChestBoat,
+        Self::Allay,
+        Self::AreaEffectCloud,
+        Self::Armadillo,
+        Self::ArmorStand,
+        Self::Arrow,
+        Self::Axolotl,
+        Self::BambooChestRaft,
+        Self::BambooRaft,
+        Self::Bat,
+        Self::Bee,
+        Self::BirchBoat,
+        Self::BirchChestBoat,
+        Self::Blaze,
+        Self::BlockDisplay,
+        Self::Bogged,
+        Self::Breeze,
+        Self::BreezeWindCharge,
+        Self::Camel,
+        Self::Cat,
+        Self::CaveSpider,
+        Self::CherryBoat,
+        Self::CherryChestBoat,
+        Self::ChestMinecart,
+        Self::Chicken,
+        Self::Cod,
+        Self::CommandBlockMinecart,
+        Self::Cow,
+        Self::Creaking,
+        Self::Creeper,
+        Self::DarkOakBoat,
+        Self::DarkOakChestBoat,
+        Self::Dolphin,
+        Self::Donkey,
+        Self::DragonFireball,
+        Self::Drowned,
+        Self::Egg,
+        Self::ElderGuardian,
+        Self::Enderman,
+        Self::Endermite,
+        Self::EnderDragon,
+        Self::EnderPearl,
+        Self::EndCrystal,
+        Self::Evoker,
+        Self::EvokerFangs,
+        Self::ExperienceBottle,
+        Self::ExperienceOrb,
+        Self::EyeOfEnder,
+        Self::FallingBlock,
+        Self::Fireball,
+        Self::FireworkRocket,
+        Self::Fox,
+        Self::Frog,
+        Self::FurnaceMinecart,
+        Self::Ghast,
+        Self::Giant,
+        Self::GlowItemFrame,
+        Self::GlowSquid,
+        Self::Goat,
+        Self::Guardian,
+        Self::Hoglin,
+        Self::HopperMinecart,
+        Self::Horse,
+        Self::Husk,
+        Self::Illusioner,
+        Self::Interaction,
+        Self::IronGolem,
+        Self::Item,
+        Self::ItemDisplay,
+        Self::ItemFrame,
+        Self::JungleBoat,
+        Self::JungleChestBoat,
+        Self::LeashKnot,
+        Self::LightningBolt,
+        Self::Llama,
+        Self::LlamaSpit,
+        Self::MagmaCube,
+        Self::MangroveBoat,
+        Self::MangroveChestBoat,
+        Self::Marker,
+        Self::Minecart,
+        Self::Mooshroom,
+        Self::Mule,
+        Self::OakBoat,
+        Self::OakChestBoat,
+        Self::Ocelot,
+        Self::OminousItemSpawner,
+        Self::Painting,
+        Self::PaleOakBoat,
+        Self::PaleOakChestBoat,
+        Self::Panda,
+        Self::Parrot,
+        Self::Phantom,
+        Self::Pig,
+        Self::Piglin,
+        Self::PiglinBrute,
+        Self::Pillager,
+        Self::PolarBear,
+        Self::Potion,
+        Self::Pufferfish,
+        Self::Rabbit,
+        Self::Ravager,
+        Self::Salmon,
+        Self::Sheep,
+        Self::Shulker,
+        Self::ShulkerBullet,
+        Self::Silverfish,
+        Self::Skeleton,
+        Self::SkeletonHorse,
+        Self::Slime,
+        Self::SmallFireball,
+        Self::Sniffer,
+        Self::Snowball,
+        Self::SnowGolem,
+        Self::SpawnerMinecart,
+        Self::SpectralArrow,
+        Self::Spider,
+        Self::SpruceBoat,
+        Self::SpruceChestBoat,
+        Self::Squid,
+        Self::Stray,
+        Self::Strider,
+        Self::Tadpole,
+        Self::TextDisplay,
+        Self::Tnt,
+        Self::TntMinecart,
+        Self::TraderLlama,
+        Self::Trident,
+        Self::TropicalFish,
+        Self::Turtle,
+        Self::Vex,
+        Self::Villager,
+        Self::Vindicator,
+        Self::WanderingTrader,
+        Self::Warden,
+        Self::WindCharge,
+        Self::Witch,
+        Self::Wither,
+        Self::WitherSkeleton,
+        Self::WitherSkull,
+        Self::Wolf,
+        Self::Zoglin,
+        Self::Zombie,
+        Self::ZombieHorse,
+        Self::ZombieVillager,
+        Self::ZombifiedPiglin,
+        Self::Player,
+        Self::FishingBobber,
+    ];
+
+    /// The id of the entity type without the `minecraft:` namespace, e.g. `zombie`
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::AcaciaBoat => "acacia_boat",
+            Self::AcaciaChestBoat => "acacia_chest_boat",
+            Self::Allay => "allay",
+            Self::AreaEffectCloud => "area_effect_cloud",
+            Self::Armadillo => "armadillo",
+            Self::ArmorStand => "armor_stand",
+            Self::Arrow => "arrow",
+            Self::Axolotl => "axolotl",
+            Self::BambooChestRaft => "bamboo_chest_raft",
+            Self::BambooRaft => "bamboo_raft",
+            Self::Bat => "bat",
+            Self::Bee => "bee",
+            Self::BirchBoat => "birch_boat",
+            Self::BirchChestBoat => "birch_chest_boat",
+            Self::Blaze => "blaze",
+            Self::BlockDisplay => "block_display",
+            Self::Bogged => "bogged",
+            Self::Breeze => "breeze",
+            Self::BreezeWindCharge => "breeze_wind_charge",
+            Self::Camel => "camel",
+            Self::Cat => "cat",
+            Self::CaveSpider => "cave_spider",
+            Self::CherryBoat => "cherry_boat",
+            Self::CherryChestBoat => "cherry_chest_boat",
+            Self::ChestMinecart => "chest_minecart",
+            Self::Chicken => "chicken",
+            Self::Cod => "cod",
+            Self::CommandBlockMinecart => "command_block_minecart",
+            Self::Cow => "cow",
+            Self::Creaking => "creaking",
+            Self::Creeper => "creeper",
+            Self::DarkOakBoat => "dark_oak_boat",
+            Self::DarkOakChestBoat => "dark_oak_chest_boat",
+            Self::Dolphin => "dolphin",
+            Self::Donkey => "donkey",
+            Self::DragonFireball => "dragon_fireball",
+            Self::Drowned => "drowned",
+            Self::Egg => "egg",
+            Self::ElderGuardian => "elder_guardian",
+            Self::Enderman => "enderman",
+            Self::Endermite => "endermite",
+            Self::EnderDragon => "ender_dragon",
+            Self::EnderPearl => "ender_pearl",
+            Self::EndCrystal => "end_crystal",
+            Self::Evoker => "evoker",
+            Self::EvokerFangs => "evoker_fangs",
+            Self::ExperienceBottle => "experience_bottle",
+            Self::ExperienceOrb => "experience_orb",
+            Self::EyeOfEnder => "eye_of_ender",
+            Self::FallingBlock => "falling_block",
+            Self::Fireball => "fireball",
+            Self::FireworkRocket => "firework_rocket",
+            Self::Fox => "fox",
+            Self::Frog => "frog",
+            Self::FurnaceMinecart => "furnace_minecart",
+            Self::Ghast => "ghast",
+            Self::Giant => "giant",
+            Self::GlowItemFrame => "glow_item_frame",
+            Self::GlowSquid => "glow_squid",
+            Self::Goat => "goat",
+            Self::Guardian => "guardian",
+            Self::Hoglin => "hoglin",
+            Self::HopperMinecart => "hopper_minecart",
+            Self::Horse => "horse",
+            Self::Husk => "husk",
+            Self::Illusioner => "illusioner",
+            Self::Interaction => "interaction",
+            Self::IronGolem => "iron_golem",
+            Self::Item => "item",
+            Self::ItemDisplay => "item_display",
+            Self::ItemFrame => "item_frame",
+            Self::JungleBoat => "jungle_boat",
+            Self::JungleChestBoat => "jungle_chest_boat",
+            Self::LeashKnot => "leash_knot",
+            Self::LightningBolt => "lightning_bolt",
+            Self::Llama => "llama",
+            Self::LlamaSpit => "llama_spit",
+            Self::MagmaCube => "magma_cube",
+            Self::MangroveBoat => "mangrove_boat",
+            Self::MangroveChestBoat => "mangrove_chest_boat",
+            Self::Marker => "marker",
+            Self::Minecart => "minecart",
+            Self::Mooshroom => "mooshroom",
+            Self::Mule => "mule",
+            Self::OakBoat => "oak_boat",
+            Self::OakChestBoat => "oak_chest_boat",
+            Self::Ocelot => "ocelot",
+            Self::OminousItemSpawner => "ominous_item_spawner",
+            Self::Painting => "painting",
+            Self::PaleOakBoat => "pale_oak_boat",
+            Self::PaleOakChestBoat => "pale_oak_chest_boat",
+            Self::Panda => "panda",
+            Self::Parrot => "parrot",
+            Self::Phantom => "phantom",
+            Self::Pig => "pig",
+            Self::Piglin => "piglin",
+            Self::PiglinBrute => "piglin_brute",
+            Self::Pillager => "pillager",
+            Self::PolarBear => "polar_bear",
+            Self::Potion => "potion",
+            Self::Pufferfish => "pufferfish",
+            Self::Rabbit => "rabbit",
+            Self::Ravager => "ravager",
+            Self::Salmon => "salmon",
+            Self::Sheep => "sheep",
+            Self::Shulker => "shulker",
+            Self::ShulkerBullet => "shulker_bullet",
+            Self::Silverfish => "silverfish",
+            Self::Skeleton => "skeleton",
+            Self::SkeletonHorse => "skeleton_horse",
+            Self::Slime => "slime",
+            Self::SmallFireball => "small_fireball",
+            Self::Sniffer => "sniffer",
+            Self::Snowball => "snowball",
+            Self::SnowGolem => "snow_golem",
+            Self::SpawnerMinecart => "spawner_minecart",
+            Self::SpectralArrow => "spectral_arrow",
+            Self::Spider => "spider",
+            Self::SpruceBoat => "spruce_boat",
+            Self::SpruceChestBoat => "spruce_chest_boat",
+            Self::Squid => "squid",
+            Self::Stray => "stray",
+            Self::Strider => "strider",
+            Self::Tadpole => "tadpole",
+            Self::TextDisplay => "text_display",
+            Self::Tnt => "tnt",
+            Self::TntMinecart => "tnt_minecart",
+            Self::TraderLlama => "trader_llama",
+            Self::Trident => "trident",
+            Self::TropicalFish => "tropical_fish",
+            Self::Turtle => "turtle",
+            Self::Vex => "vex",
+            Self::Villager => "villager",
+            Self::Vindicator => "vindicator",
+            Self::WanderingTrader => "wandering_trader",
+            Self::Warden => "warden",
+            Self::WindCharge => "wind_charge",
+            Self::Witch => "witch",
+            Self::Wither => "wither",
+            Self::WitherSkeleton => "wither_skeleton",
+            Self::WitherSkull => "wither_skull",
+            Self::Wolf => "wolf",
+            Self::Zoglin => "zoglin",
+            Self::Zombie => "zombie",
+            Self::ZombieHorse => "zombie_horse",
+            Self::ZombieVillager => "zombie_villager",
+            Self::ZombifiedPiglin => "zombified_piglin",
+            Self::Player => "player",
+            Self::FishingBobber => "fishing_bobber",
+        }
+    }
+
+    /// The entity type with the id, e.g. `minecraft:zombie`. The namespace is optional
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Some(match name {
+            "acacia_boat" => Self::AcaciaBoat,
+            "acacia_chest_boat" => Self::AcaciaChestBoat,
+            "allay" => Self::Allay,
+            "area_effect_cloud" => Self::AreaEffectCloud,
+            "armadillo" => Self::Armadillo,
+            "armor_stand" => Self::ArmorStand,
+            "arrow" => Self::Arrow,
+            "axolotl" => Self::Axolotl,
+            "bamboo_chest_raft" => Self::BambooChestRaft,
+            "bamboo_raft" => Self::BambooRaft,
+            "bat" => Self::Bat,
+            "bee" => Self::Bee,
+            "birch_boat" => Self::BirchBoat,
+            "birch_chest_boat" => Self::BirchChestBoat,
+            "blaze" => Self::Blaze,
+            "block_display" => Self::BlockDisplay,
+            "bogged" => Self::Bogged,
+            "breeze" => Self::Breeze,
+            "breeze_wind_charge" => Self::BreezeWindCharge,
+            "camel" => Self::Camel,
+            "cat" => Self::Cat,
+            "cave_spider" => Self::CaveSpider,
+            "cherry_boat" => Self::CherryBoat,
+            "cherry_chest_boat" => Self::CherryChestBoat,
+            "chest_minecart" => Self::ChestMinecart,
+            "chicken" => Self::Chicken,
+            "cod" => Self::Cod,
+            "command_block_minecart" => Self::CommandBlockMinecart,
+            "cow" => Self::Cow,
+            "creaking" => Self::Creaking,
+            "creeper" => Self::Creeper,
+            "dark_oak_boat" => Self::DarkOakBoat,
+            "dark_oak_chest_boat" => Self::DarkOakChestBoat,
+            "dolphin" => Self::Dolphin,
+            "donkey" => Self::Donkey,
+            "dragon_fireball" => Self::DragonFireball,
+            "drowned" => Self::Drowned,
+            "egg" => Self::Egg,
+            "elder_guardian" => Self::ElderGuardian,
+            "enderman" => Self::Enderman,
+            "endermite" => Self::Endermite,
+            "ender_dragon" => Self::EnderDragon,
+            "ender_pearl" => Self::EnderPearl,
+            "end_crystal" => Self::EndCrystal,
+            "evoker" => Self::Evoker,
+            "evoker_fangs" => Self::EvokerFangs,
+            "experience_bottle" => Self::ExperienceBottle,
+            "experience_orb" => Self::ExperienceOrb,
+            "eye_of_ender" => Self::EyeOfEnder,
+            "falling_block" => Self::FallingBlock,
+            "fireball" => Self::Fireball,
+            "firework_rocket" => Self::FireworkRocket,
+            "fox" => Self::Fox,
+            "frog" => Self::Frog,
+            "furnace_minecart" => Self::FurnaceMinecart,
+            "ghast" => Self::Ghast,
+            "giant" => Self::Giant,
+            "glow_item_frame" => Self::GlowItemFrame,
+            "glow_squid" => Self::GlowSquid,
+            "goat" => Self::Goat,
+            "guardian" => Self::Guardian,
+            "hoglin" => Self::Hoglin,
+            "hopper_minecart" => Self::HopperMinecart,
+            "horse" => Self::Horse,
+            "husk" => Self::Husk,
+            "illusioner" => Self::Illusioner,
+            "interaction" => Self::Interaction,
+            "iron_golem" => Self::IronGolem,
+            "item" => Self::Item,
+            "item_display" => Self::ItemDisplay,
+            "item_frame" => Self::ItemFrame,
+            "jungle_boat" => Self::JungleBoat,
+            "jungle_chest_boat" => Self::JungleChestBoat,
+            "leash_knot" => Self::LeashKnot,
+            "lightning_bolt" => Self::LightningBolt,
+            "llama" => Self::Llama,
+            "llama_spit" => Self::LlamaSpit,
+            "magma_cube" => Self::MagmaCube,
+            "mangrove_boat" => Self::MangroveBoat,
+            "mangrove_chest_boat" => Self::MangroveChestBoat,
+            "marker" => Self::Marker,
+            "minecart" => Self::Minecart,
+            "mooshroom" => Self::Mooshroom,
+            "mule" => Self::Mule,
+            "oak_boat" => Self::OakBoat,
+            "oak_chest_boat" => Self::OakChestBoat,
+            "ocelot" => Self::Ocelot,
+            "ominous_item_spawner" => Self::OminousItemSpawner,
+            "painting" => Self::Painting,
+            "pale_oak_boat" => Self::PaleOakBoat,
+            "pale_oak_chest_boat" => Self::PaleOakChestBoat,
+            "panda" => Self::Panda,
+            "parrot" => Self::Parrot,
+            "phantom" => Self::Phantom,
+            "pig" => Self::Pig,
+            "piglin" => Self::Piglin,
+            "piglin_brute" => Self::PiglinBrute,
+            "pillager" => Self::Pillager,
+            "polar_bear" => Self::PolarBear,
+            "potion" => Self::Potion,
+            "pufferfish" => Self::Pufferfish,
+            "rabbit" => Self::Rabbit,
+            "ravager" => Self::Ravager,
+            "salmon" => Self::Salmon,
+            "sheep" => Self::Sheep,
+            "shulker" => Self::Shulker,
+            "shulker_bullet" => Self::ShulkerBullet,
+            "silverfish" => Self::Silverfish,
+            "skeleton" => Self::Skeleton,
+            "skeleton_horse" => Self::SkeletonHorse,
+            "slime" => Self::Slime,
+            "small_fireball" => Self::SmallFireball,
+            "sniffer" => Self::Sniffer,
+            "snowball" => Self::Snowball,
+            "snow_golem" => Self::SnowGolem,
+            "spawner_minecart" => Self::SpawnerMinecart,
+            "spectral_arrow" => Self::SpectralArrow,
+            "spider" => Self::Spider,
+            "spruce_boat" => Self::SpruceBoat,
+            "spruce_chest_boat" => Self::SpruceChestBoat,
+            "squid" => Self::Squid,
+            "stray" => Self::Stray,
+            "strider" => Self::Strider,
+            "tadpole" => Self::Tadpole,
+            "text_display" => Self::TextDisplay,
+            "tnt" => Self::Tnt,
+            "tnt_minecart" => Self::TntMinecart,
+            "trader_llama" => Self::TraderLlama,
+            "trident" => Self::Trident,
+            "tropical_fish" => Self::TropicalFish,
+            "turtle" => Self::Turtle,
+            "vex" => Self::Vex,
+            "villager" => Self::Villager,
+            "vindicator" => Self::Vindicator,
+            "wandering_trader" => Self::WanderingTrader,
+            "warden" => Self::Warden,
+            "wind_charge" => Self::WindCharge,
+            "witch" => Self::Witch,
+            "wither" => Self::Wither,
+            "wither_skeleton" => Self::WitherSkeleton,
+            "wither_skull" => Self::WitherSkull,
+            "wolf" => Self::Wolf,
+            "zoglin" => Self::Zoglin,
+            "zombie" => Self::Zombie,
+            "zombie_horse" => Self::ZombieHorse,
+            "zombie_villager" => Self::ZombieVillager,
+            "zombified_piglin" => Self::ZombifiedPiglin,
+            "player" => Self::Player,
+            "fishing_bobber" => Self::FishingBobber,
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,286 @@
+use crate::crafting::check_ingredient_type;
+use pumpkin_registry::{are_enchantments_compatible, get_enchantment, Enchantment, IngredientType};
+use pumpkin_world::item::item_registry::{get_item, get_item_by_id};
+use pumpkin_world::item::ItemStack;
+
+/// Survival players can't pay this many levels or more
+pub const TOO_EXPENSIVE_COST: i32 = 40;
+/// Names typed into the anvil are cut off after this many characters
+pub const MAX_NAME_LENGTH: usize = 50;
+
+pub struct AnvilResult {
+    pub output: ItemStack,
+    /// Experience levels needed to take the output
+    pub cost: i32,
+    /// How many items of the second input a repair uses up, `None` if the whole stack is used
+    pub repair_item_count: Option<u8>,
+}
+
+fn is_enchanted_book(item: &ItemStack) -> bool {
+    get_item("minecraft:enchanted_book").is_some_and(|book| book.id == item.item_id)
+}
+
+/// Enchanted books keep their enchantments in a separate component, so they aren't active on the book itself
+fn crafting_enchantments(item: &ItemStack) -> &Vec<(String, i32)> {
+    if is_enchanted_book(item) {
+        &item.components.stored_enchantments
+    } else {
+        &item.components.enchantments
+    }
+}
+
+fn to_ingredient_type(name: &str) -> IngredientType {
+    match name.strip_prefix('#') {
+        Some(tag) => IngredientType::Tag(tag.to_string()),
+        None => IngredientType::Item(name.to_string()),
+    }
+}
+
+fn can_enchant(enchantment: &Enchantment, item: &ItemStack) -> bool {
+    check_ingredient_type(&to_ingredient_type(&enchantment.supported_items), item)
+}
+
+fn max_damage(item: &ItemStack) -> Option<i32> {
+    get_item_by_id(item.item_id)?
+        .components
+        .max_damage
+        .map(i32::from)
+}
+
+fn damage(item: &ItemStack) -> i32 {
+    item.components.damage.unwrap_or(0)
+}
+
+/// Whether the material repairs the item, e.g. diamonds repair diamond tools
+fn is_repair_item(item: &ItemStack, material: &ItemStack) -> bool {
+    get_item_by_id(item.item_id)
+        .and_then(|item| item.components.repairable.as_ref())
+        .is_some_and(|repairable| {
+            check_ingredient_type(&to_ingredient_type(&repairable.items), material)
+        })
+}
+
+/// Computes what the anvil makes out of its two inputs with vanilla's cost rules,
+/// returns `None` if the inputs can't be combined
+pub fn get_anvil_result(
+    input: &ItemStack,
+    sacrifice: Option<&ItemStack>,
+    item_name: &str,
+) -> Option<AnvilResult> {
+    let mut output = input.clone();
+    let mut enchantments = crafting_enchantments(input).clone();
+    // Every time an item goes through an anvil it gets more expensive to work on
+    let prior_work_penalty = input.components.repair_cost.unwrap_or(0)
+        + sacrifice.map_or(0, |sacrifice| sacrifice.components.repair_cost.unwrap_or(0));
+    let mut cost = 0;
+    let mut repair_item_count = None;
+
+    if let Some(sacrifice) = sacrifice {
+        let is_book = !sacrifice.components.stored_enchantments.is_empty();
+        let max_damage = max_damage(input);
+        if let Some(max_damage) = max_damage.filter(|_| is_repair_item(input, sacrifice)) {
+            // Each material repairs a quarter of the durability
+            let mut new_damage = damage(input);
+            let mut repaired = new_damage.min(max_damage / 4);
+            if repaired <= 0 {
+                return None;
+            }
+            let mut used = 0;
+            while repaired > 0 && used < sacrifice.item_count {
+                new_damage -= repaired;
+                cost += 1;
+                used += 1;
+                repaired = new_damage.min(max_damage / 4);
+            }
+            output.components.damage = Some(new_damage).filter(|damage| *damage > 0);
+            repair_item_count = Some(used);
+        } else {
+            if !is_book && (input.item_id != sacrifice.item_id || max_damage.is_none()) {
+                return None;
+            }
+            if let Some(max_damage) = max_damage.filter(|_| !is_book) {
+                // Combining two damaged items adds their durability with a 12% bonus
+                let durability = max_damage - damage(input) + max_damage - damage(sacrifice);
+                let new_damage = (max_damage - durability - max_damage * 12 / 100).max(0);
+                if new_damage < damage(input) {
+                    output.components.damage = Some(new_damage).filter(|damage| *damage > 0);
+                    cost += 2;
+                }
+            }
+
+            let mut any_applied = false;
+            let mut any_rejected = false;
+            for (name, level) in crafting_enchantments(sacrifice) {
+                let Some(enchantment) = get_enchantment(name) else {
+                    continue;
+                };
+                let mut applicable = is_enchanted_book(input) || can_enchant(enchantment, input);
+                // Each enchantment already on the item which excludes this one costs a level
+                for (other, _) in &enchantments {
+                    if other != name && !are_enchantments_compatible(name, other) {
+                        applicable = false;
+                        cost += 1;
+                    }
+                }
+                if !applicable {
+                    any_rejected = true;
+                    continue;
+                }
+                any_applied = true;
+
+                let current = enchantments.iter_mut().find(|(current, _)| current == name);
+                let new_level = match &current {
+                    // Two equal levels make the next level
+                    Some((_, current)) if current == level => level + 1,
+                    Some((_, current)) => *current.max(level),
+                    None => *level,
+                }
+                .min(enchantment.max_level);
+                match current {
+                    Some((_, current)) => *current = new_level,
+                    None => enchantments.push((name.clone(), new_level)),
+                }
+
+                let anvil_cost = if is_book {
+                    (enchantment.anvil_cost / 2).max(1)
+                } else {
+                    enchantment.anvil_cost
+                };
+                cost += anvil_cost * new_level;
+                if input.item_count > 1 {
+                    cost = TOO_EXPENSIVE_COST;
+                }
+            }
+            if any_rejected && !any_applied {
+                return None;
+            }
+        }
+    }
+
+    let mut rename_cost = 0;
+    if !item_name.trim().is_empty() {
+        if input.components.custom_name.as_deref() != Some(item_name) {
+            rename_cost = 1;
+            output.components.custom_name = Some(item_name.to_string());
+        }
+    } else if input.components.custom_name.is_some() {
+        // An empty name resets the name of the item
+        rename_cost = 1;
+        output.components.custom_name = None;
+    }
+    cost += rename_cost;
+    if cost <= 0 {
+        return None;
+    }
+
+    let mut total_cost = prior_work_penalty.saturating_add(cost).max(0);
+    let only_renaming = rename_cost == cost;
+    if only_renaming {
+        // Renaming is always possible
+        total_cost = total_cost.min(TOO_EXPENSIVE_COST - 1);
+    }
+
+    let mut repair_cost = output
+        .components
+        .repair_cost
+        .unwrap_or(0)
+        .max(sacrifice.map_or(0, |sacrifice| sacrifice.components.repair_cost.unwrap_or(0)));
+    if !only_renaming {
+        repair_cost = repair_cost.saturating_mul(2).saturating_add(1);
+    }
+    output.components.repair_cost = Some(repair_cost).filter(|cost| *cost > 0);
+    if is_enchanted_book(&output) {
+        output.components.stored_enchantments = enchantments;
+    } else {
+        output.components.enchantments = enchantments;
+    }
+
+    Some(AnvilResult {
+        output,
+        cost: total_cost,
+        repair_item_count,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::{item_registry::get_item, ItemStack};
+
+    use super::{get_anvil_result, TOO_EXPENSIVE_COST};
+
+    fn sword(damage: Option<i32>, repair_cost: Option<i32>) -> ItemStack {
+        let mut sword = ItemStack::new(1, get_item("minecraft:diamond_sword").unwrap().id);
+        sword.components.damage = damage;
+        sword.components.repair_cost = repair_cost;
+        sword
+    }
+
+    #[test]
+    fn renaming_costs_a_level() {
+        let result = get_anvil_result(&sword(None, None), None, "Blade").unwrap();
+        assert_eq!(result.cost, 1);
+        assert_eq!(
+            result.output.components.custom_name.as_deref(),
+            Some("Blade")
+        );
+        // Only renaming doesn't make the next use more expensive
+        assert_eq!(result.output.components.repair_cost, None);
+
+        let mut named = sword(None, None);
+        named.components.custom_name = Some("Blade".to_string());
+        assert!(get_anvil_result(&named, None, "Blade").is_none());
+        let reset = get_anvil_result(&named, None, "").unwrap();
+        assert_eq!(reset.cost, 1);
+        assert_eq!(reset.output.components.custom_name, None);
+    }
+
+    #[test]
+    fn prior_work_adds_to_the_cost() {
+        let result = get_anvil_result(&sword(None, Some(3)), None, "Blade").unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.output.components.repair_cost, Some(3));
+
+        // Combining two worn swords costs 2 and the prior work of both
+        let worn = sword(Some(1000), Some(1));
+        let result = get_anvil_result(&worn, Some(&worn), "").unwrap();
+        assert_eq!(result.cost, 4);
+        assert!(result.output.components.damage < worn.components.damage);
+        assert_eq!(result.output.components.repair_cost, Some(3));
+    }
+
+    #[test]
+    fn incompatible_enchantments_are_rejected() {
+        let mut sharp = sword(None, None);
+        sharp.components.enchantments = vec![("sharpness".to_string(), 1)];
+        let book = |enchantments: &[(&str, i32)]| {
+            let mut book = ItemStack::new(1, get_item("minecraft:enchanted_book").unwrap().id);
+            book.components.stored_enchantments = enchantments
+                .iter()
+                .map(|(name, level)| (name.to_string(), *level))
+                .collect();
+            book
+        };
+
+        assert!(get_anvil_result(&sharp, Some(&book(&[("smite", 1)])), "").is_none());
+
+        // The rejected enchantment still costs a level
+        let result =
+            get_anvil_result(&sharp, Some(&book(&[("smite", 1), ("unbreaking", 1)])), "").unwrap();
+        assert_eq!(
+            result.output.components.enchantments,
+            vec![("sharpness".to_string(), 1), ("unbreaking".to_string(), 1)]
+        );
+        assert_eq!(result.cost, 2);
+    }
+
+    #[test]
+    fn renaming_stays_below_too_expensive() {
+        let result = get_anvil_result(&sword(None, Some(50)), None, "Blade").unwrap();
+        assert_eq!(result.cost, TOO_EXPENSIVE_COST - 1);
+
+        // Everything else is too expensive for survival players then
+        let worn = sword(Some(1000), Some(50));
+        let result = get_anvil_result(&worn, Some(&sword(Some(1000), None)), "").unwrap();
+        assert!(result.cost >= TOO_EXPENSIVE_COST);
+    }
+}
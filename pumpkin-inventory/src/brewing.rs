@@ -0,0 +1,69 @@
+use pumpkin_registry::BREWING_MIXES;
+use pumpkin_world::block::block_entity::BrewingRecipes;
+use pumpkin_world::item::item_registry::{get_item, get_item_name_by_id};
+use pumpkin_world::item::ItemStack;
+
+const BREWING_FUEL: &str = "minecraft:blaze_powder";
+
+fn item_name(item: &ItemStack) -> Option<String> {
+    get_item_name_by_id(item.item_id).map(|name| format!("minecraft:{name}"))
+}
+
+pub fn is_brewing_fuel(item: &ItemStack) -> bool {
+    item_name(item).is_some_and(|name| name == BREWING_FUEL)
+}
+
+/// Whether the item can be put into one of the three bottle slots
+pub fn is_potion_container(item: &ItemStack) -> bool {
+    item_name(item).is_some_and(|name| BREWING_MIXES.is_container(&name))
+}
+
+pub fn is_brewing_ingredient(item: &ItemStack) -> bool {
+    item_name(item).is_some_and(|name| BREWING_MIXES.is_ingredient(&name))
+}
+
+/// Returns what the potion turns into when brewed with the ingredient,
+/// or `None` if the ingredient has no effect on it
+pub fn get_brewing_result(potion: &ItemStack, ingredient: &ItemStack) -> Option<ItemStack> {
+    let container = item_name(potion)?;
+    let ingredient = item_name(ingredient)?;
+
+    // Changing the container keeps the potion, e.g. a potion of healing becomes a splash potion of healing
+    if let Some(result) = BREWING_MIXES.container_mix(&container, &ingredient) {
+        let mut result_stack = potion.clone();
+        result_stack.item_id = get_item(result)?.id;
+        return Some(result_stack);
+    }
+
+    let from = format!("minecraft:{}", potion.components.potion.as_ref()?);
+    let result = BREWING_MIXES.potion_mix(&from, &ingredient)?;
+    let mut result_stack = potion.clone();
+    result_stack.components.potion = Some(
+        result
+            .strip_prefix("minecraft:")
+            .unwrap_or(result)
+            .to_string(),
+    );
+    Some(result_stack)
+}
+
+/// The potion mixes brewing stand block entities brew with
+pub struct BrewingStandRecipes;
+
+impl BrewingRecipes for BrewingStandRecipes {
+    fn brewing_result(&self, potion: &ItemStack, ingredient: &ItemStack) -> Option<ItemStack> {
+        get_brewing_result(potion, ingredient)
+    }
+
+    fn is_potion_container(&self, item: &ItemStack) -> bool {
+        is_potion_container(item)
+    }
+
+    fn is_ingredient(&self, item: &ItemStack) -> bool {
+        is_brewing_ingredient(item)
+    }
+
+    fn is_fuel(&self, item: &ItemStack) -> bool {
+        is_brewing_fuel(item)
+    }
+}
@@ -0,0 +1,41 @@
+use pumpkin_world::item::components::MapPostProcessing;
+use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::ItemStack;
+
+fn is_item(item: &ItemStack, name: &str) -> bool {
+    get_item(name).is_some_and(|expected| expected.id == item.item_id)
+}
+
+pub fn is_filled_map(item: &ItemStack) -> bool {
+    is_item(item, "minecraft:filled_map")
+}
+
+/// Whether the item can be put into the second slot to change a map
+pub fn is_cartography_material(item: &ItemStack) -> bool {
+    ["minecraft:paper", "minecraft:map", "minecraft:glass_pane"]
+        .into_iter()
+        .any(|name| is_item(item, name))
+}
+
+/// Computes what the cartography table makes out of the map:
+/// paper zooms it out, glass panes lock it and an empty map clones it
+pub fn get_cartography_result(map: &ItemStack, additional: &ItemStack) -> Option<ItemStack> {
+    // Maps without an id have no saved data which could be changed
+    if !is_filled_map(map) || map.components.map_id.is_none() {
+        return None;
+    }
+    // TODO: Locked maps and maps which are zoomed out all the way can't be changed, this needs the map's saved data
+    if is_item(additional, "minecraft:paper") {
+        let mut result = map.with_count(1);
+        result.components.map_post_processing = Some(MapPostProcessing::Scale);
+        Some(result)
+    } else if is_item(additional, "minecraft:glass_pane") {
+        let mut result = map.with_count(1);
+        result.components.map_post_processing = Some(MapPostProcessing::Lock);
+        Some(result)
+    } else if is_item(additional, "minecraft:map") {
+        Some(map.with_count(2))
+    } else {
+        None
+    }
+}
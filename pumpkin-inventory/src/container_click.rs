@@ -18,7 +18,7 @@ impl Click {
                 click_type: ClickType::CreativePickItem,
                 slot: Slot::Normal(slot.try_into().or(Err(InventoryError::InvalidSlot))?),
             }),
-            SlotActionType::Throw => Self::new_drop_item(button),
+            SlotActionType::Throw => Self::new_drop_item(button, slot),
             SlotActionType::QuickCraft => Self::new_drag_item(button, slot),
             SlotActionType::PickupAll => Ok(Self {
                 click_type: ClickType::DoubleClick,
@@ -66,15 +66,19 @@ impl Click {
         })
     }
 
-    fn new_drop_item(button: i8) -> Result<Self, InventoryError> {
+    fn new_drop_item(button: i8, slot: i16) -> Result<Self, InventoryError> {
         let drop_type = match button {
             0 => DropType::SingleItem,
             1 => DropType::FullStack,
             _ => Err(InventoryError::InvalidPacket)?,
         };
+        let slot = match slot {
+            -999 => Slot::OutsideInventory,
+            _ => Slot::Normal(slot.try_into().or(Err(InventoryError::InvalidSlot))?),
+        };
         Ok(Self {
             click_type: ClickType::DropType(drop_type),
-            slot: Slot::OutsideInventory,
+            slot,
         })
     }
 
@@ -143,5 +147,5 @@ pub enum MouseDragState {
 
 pub enum ItemChange {
     Remove { slot: usize },
-    Add { slot: usize, item: ItemStack },
+    Add { slot: usize, item: Box<ItemStack> },
 }
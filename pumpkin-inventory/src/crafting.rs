@@ -1,69 +1,229 @@
+use std::sync::LazyLock;
+
 use pumpkin_registry::{
-    flatten_3x3, get_tag_values, IngredientSlot, IngredientType, RecipeResult, TagCategory, RECIPES,
+    is_in_tag, IngredientSlot, IngredientType, Recipe, RecipeResult, TagCategory, RECIPES,
 };
-use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::item_registry::{get_item, get_item_name_by_id};
 use pumpkin_world::item::ItemStack;
 use rayon::prelude::*;
 
 #[inline(always)]
-fn check_ingredient_type(ingredient_type: &IngredientType, input: ItemStack) -> bool {
+pub(crate) fn check_ingredient_type(ingredient_type: &IngredientType, input: &ItemStack) -> bool {
     match ingredient_type {
-        IngredientType::Tag(tag) => {
-            let items = match get_tag_values(TagCategory::Item, tag) {
-                Some(items) => items,
-                None => return false,
-            };
-            items
-                .iter()
-                .any(|tag| check_ingredient_type(&tag.to_ingredient_type(), input))
-        }
+        IngredientType::Tag(tag) => get_item_name_by_id(input.item_id)
+            .is_some_and(|name| is_in_tag(TagCategory::Item, name, tag)),
         IngredientType::Item(item) => get_item(item).is_some_and(|item| item.id == input.item_id),
     }
 }
 
-pub fn check_if_matches_crafting(input: [[Option<ItemStack>; 3]; 3]) -> Option<ItemStack> {
-    let input = flatten_3x3(input);
+/// The shaped and shapeless crafting recipes, in the order of the recipe file. Like vanilla's
+/// `RecipeManager`, the first matching recipe wins
+static CRAFTING_RECIPES: LazyLock<Vec<&'static Recipe>> = LazyLock::new(|| {
     RECIPES
-        .par_iter()
-        .find_any(|recipe| {
-            let patterns = recipe.pattern();
-            if patterns
-                .iter()
-                .flatten()
-                .flatten()
-                .all(|slot| slot.is_none())
-            {
-                false
-            } else if recipe.recipe_type.is_shapeless() {
-                shapeless_crafting_match(input, recipe.pattern())
-            } else {
-                patterns.par_iter().any(|pattern| {
-                    pattern.iter().enumerate().all(|(i, row)| {
-                        row.iter()
-                            .enumerate()
-                            .all(|(j, item)| match (item, input[i][j]) {
-                                (Some(item), Some(input)) => ingredient_slot_check(item, input),
-                                (None, None) => true,
-                                (Some(_), None) | (None, Some(_)) => false,
-                            })
-                    })
+        .iter()
+        .filter(|recipe| recipe.implemented())
+        .collect()
+});
+
+/// The items of a crafting grid trimmed to the smallest rectangle around them, like vanilla's
+/// `CraftingInput`. This way recipes smaller than the grid match wherever they are placed
+struct CraftingInput<'a> {
+    width: usize,
+    height: usize,
+    items: Vec<Option<&'a ItemStack>>,
+}
+
+impl<'a> CraftingInput<'a> {
+    fn new(grid: &'a [[Option<ItemStack>; 3]; 3]) -> Self {
+        let filled = |row: &[Option<ItemStack>; 3]| row.iter().any(Option::is_some);
+        let (Some(top), Some(bottom)) =
+            (grid.iter().position(filled), grid.iter().rposition(filled))
+        else {
+            return Self {
+                width: 0,
+                height: 0,
+                items: Vec::new(),
+            };
+        };
+        let left = (0..3)
+            .find(|&column| grid.iter().any(|row| row[column].is_some()))
+            .unwrap_or(0);
+        let right = (0..3)
+            .rfind(|&column| grid.iter().any(|row| row[column].is_some()))
+            .unwrap_or(2);
+        let items = grid[top..=bottom]
+            .iter()
+            .flat_map(|row| row[left..=right].iter().map(Option::as_ref))
+            .collect();
+        Self {
+            width: right - left + 1,
+            height: bottom - top + 1,
+            items,
+        }
+    }
+
+    fn get(&self, row: usize, column: usize) -> Option<&'a ItemStack> {
+        self.items[row * self.width + column]
+    }
+
+    /// Whether the shaped recipe matches the input as it is or mirrored left to right
+    fn matches_shaped(&self, recipe: &Recipe) -> bool {
+        let Some(pattern) = recipe.pattern().first() else {
+            return false;
+        };
+        if recipe.shape() != Some((self.width, self.height)) {
+            return false;
+        }
+        [false, true].into_iter().any(|mirrored| {
+            (0..self.height).all(|row| {
+                (0..self.width).all(|column| {
+                    let input_column = if mirrored {
+                        self.width - 1 - column
+                    } else {
+                        column
+                    };
+                    match (&pattern[row][column], self.get(row, input_column)) {
+                        (Some(ingredient), Some(input)) => ingredient_slot_check(ingredient, input),
+                        (None, None) => true,
+                        (Some(_), None) | (None, Some(_)) => false,
+                    }
                 })
-            }
+            })
+        })
+    }
+
+    /// Whether every item goes into another ingredient of the shapeless recipe and no ingredient is
+    /// left, like vanilla's `StackedContents`. Items matching several ingredients, e.g. through
+    /// tags, are reassigned until everything fits
+    fn matches_shapeless(&self, recipe: &Recipe) -> bool {
+        let ingredients: Vec<&IngredientSlot> = recipe.ingredients().collect();
+        let items: Vec<&ItemStack> = self.items.iter().flatten().copied().collect();
+        if ingredients.is_empty() || ingredients.len() != items.len() {
+            return false;
+        }
+        let fits: Vec<Vec<bool>> = items
+            .iter()
+            .map(|item| {
+                ingredients
+                    .iter()
+                    .map(|ingredient| ingredient_slot_check(ingredient, item))
+                    .collect()
+            })
+            .collect();
+        let mut assigned_item = vec![None; ingredients.len()];
+        (0..items.len()).all(|item| {
+            let mut visited = vec![false; ingredients.len()];
+            assign_ingredient(item, &fits, &mut assigned_item, &mut visited)
         })
-        .map(|recipe| match recipe.result() {
-            RecipeResult::Single { id, .. } => Some(ItemStack {
-                item_id: get_item(id).unwrap().id,
-                item_count: 1,
-            }),
-            RecipeResult::Many { id, count, .. } => Some(ItemStack {
-                item_id: get_item(id).unwrap().id,
-                item_count: *count,
-            }),
-            RecipeResult::Special => None,
-        })?
+    }
+}
+
+/// Finds an ingredient for the item, moving already assigned items to other ingredients if needed
+fn assign_ingredient(
+    item: usize,
+    fits: &[Vec<bool>],
+    assigned_item: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for ingredient in 0..assigned_item.len() {
+        if !fits[item][ingredient] || visited[ingredient] {
+            continue;
+        }
+        visited[ingredient] = true;
+        let free = match assigned_item[ingredient] {
+            None => true,
+            Some(other) => assign_ingredient(other, fits, assigned_item, visited),
+        };
+        if free {
+            assigned_item[ingredient] = Some(item);
+            return true;
+        }
+    }
+    false
+}
+
+/// The output of the first crafting recipe matching the grid. 2x2 grids are passed as the top
+/// left corner of a 3x3 grid
+pub fn check_if_matches_crafting(input: [[Option<ItemStack>; 3]; 3]) -> Option<ItemStack> {
+    let input = CraftingInput::new(&input);
+    if input.items.is_empty() {
+        return None;
+    }
+    let recipe = CRAFTING_RECIPES.par_iter().find_first(|recipe| {
+        if recipe.recipe_type.is_shapeless() {
+            input.matches_shapeless(recipe)
+        } else {
+            input.matches_shaped(recipe)
+        }
+    })?;
+    let (id, count) = match recipe.result() {
+        RecipeResult::Single { id, .. } => (id, 1),
+        RecipeResult::Many { id, count, .. } => (id, *count),
+        RecipeResult::Special => return None,
+    };
+    Some(ItemStack::new(count, get_item(id)?.id))
 }
 
-fn ingredient_slot_check(recipe_item: &IngredientSlot, input: ItemStack) -> bool {
+/// What an ingredient leaves behind in the crafting grid, like vanilla's `craftRemainder`
+fn crafting_remainder(ingredient: &ItemStack) -> Option<ItemStack> {
+    let remainder = match get_item_name_by_id(ingredient.item_id)? {
+        "water_bucket" | "lava_bucket" | "milk_bucket" | "powder_snow_bucket" => "bucket",
+        "honey_bottle" | "dragon_breath" => "glass_bottle",
+        _ => return None,
+    };
+    get_item(remainder).map(|item| ItemStack::new(1, item.id))
+}
+
+/// Uses up one item of each ingredient of the taken output. Ingredients like buckets leave their
+/// remainder in the grid, remainders of stacked ingredients don't fit and are returned
+pub(crate) fn use_ingredients<'a>(
+    grid: impl IntoIterator<Item = &'a mut Option<ItemStack>>,
+) -> Vec<ItemStack> {
+    let mut leftovers = Vec::new();
+    for slot in grid {
+        let Some(ingredient) = slot.as_mut() else {
+            continue;
+        };
+        let remainder = crafting_remainder(ingredient);
+        if ingredient.item_count > 1 {
+            ingredient.item_count -= 1;
+            leftovers.extend(remainder);
+        } else {
+            *slot = remainder;
+        }
+    }
+    leftovers
+}
+
+/// Lays the recipe out in a square crafting grid of the given width, like the recipe book places it.
+/// Returns `None` if the recipe doesn't fit into the grid
+pub(crate) fn recipe_grid_layout(
+    recipe: &Recipe,
+    grid_width: usize,
+) -> Option<Vec<Option<&IngredientSlot>>> {
+    let mut layout = vec![None; grid_width * grid_width];
+    if let Some((width, height)) = recipe.shape() {
+        if width > grid_width || height > grid_width {
+            return None;
+        }
+        for (i, row) in recipe.pattern().first()?.iter().take(height).enumerate() {
+            for (j, ingredient) in row.iter().take(width).enumerate() {
+                layout[i * grid_width + j] = ingredient.as_ref();
+            }
+        }
+    } else {
+        let ingredients: Vec<_> = recipe.ingredients().collect();
+        if ingredients.is_empty() || ingredients.len() > layout.len() {
+            return None;
+        }
+        for (slot, ingredient) in layout.iter_mut().zip(ingredients) {
+            *slot = Some(ingredient);
+        }
+    }
+    Some(layout)
+}
+
+pub(crate) fn ingredient_slot_check(recipe_item: &IngredientSlot, input: &ItemStack) -> bool {
     match recipe_item {
         IngredientSlot::Single(ingredient) => check_ingredient_type(ingredient, input),
         IngredientSlot::Many(ingredients) => ingredients
@@ -71,29 +231,58 @@ fn ingredient_slot_check(recipe_item: &IngredientSlot, input: ItemStack) -> bool
             .any(|ingredient| check_ingredient_type(ingredient, input)),
     }
 }
-fn shapeless_crafting_match(
-    input: [[Option<ItemStack>; 3]; 3],
-    pattern: &[[[Option<IngredientSlot>; 3]; 3]],
-) -> bool {
-    let mut pattern: Vec<IngredientSlot> = pattern
-        .iter()
-        .flatten()
-        .flatten()
-        .flatten()
-        .cloned()
-        .collect();
-    for item in input.into_iter().flatten().flatten() {
-        if let Some(index) = pattern.iter().enumerate().find_map(|(i, recipe_item)| {
-            if ingredient_slot_check(recipe_item, item) {
-                Some(i)
-            } else {
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::{check_if_matches_crafting, use_ingredients};
+
+    #[test]
+    fn recipes_match_anywhere_in_the_grid() {
+        let mut grid = [const { [const { None }; 3] }; 3];
+        grid[1][2] = Some(ItemStack::of("minecraft:oak_planks", 1));
+        grid[2][2] = Some(ItemStack::of("minecraft:oak_planks", 1));
+        assert_eq!(
+            check_if_matches_crafting(grid),
+            Some(ItemStack::of("minecraft:stick", 4))
+        );
+    }
+
+    #[test]
+    fn recipes_match_mirrored() {
+        let planks = || Some(ItemStack::of("minecraft:oak_planks", 1));
+        let stick = || Some(ItemStack::of("minecraft:stick", 1));
+        let axe = [
+            [planks(), planks(), None],
+            [stick(), planks(), None],
+            [stick(), None, None],
+        ];
+        assert_eq!(
+            check_if_matches_crafting(axe),
+            Some(ItemStack::of("minecraft:wooden_axe", 1))
+        );
+    }
+
+    #[test]
+    fn ingredients_leave_their_remainder() {
+        let mut grid = [
+            Some(ItemStack::of("minecraft:water_bucket", 1)),
+            Some(ItemStack::of("minecraft:honey_bottle", 2)),
+            None,
+        ];
+        // The bottle of the stacked honey doesn't fit into the grid
+        assert_eq!(
+            use_ingredients(&mut grid),
+            vec![ItemStack::of("minecraft:glass_bottle", 1)]
+        );
+        assert_eq!(
+            grid,
+            [
+                Some(ItemStack::of("minecraft:bucket", 1)),
+                Some(ItemStack::of("minecraft:honey_bottle", 1)),
                 None
-            }
-        }) {
-            pattern.remove(index);
-        } else {
-            return false;
-        }
+            ]
+        );
     }
-    pattern.is_empty()
 }
@@ -1,6 +1,5 @@
 use crate::container_click::MouseDragType;
 use crate::{Container, InventoryError};
-use num_traits::Euclid;
 use pumpkin_world::item::ItemStack;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -66,16 +65,18 @@ impl DragHandler {
         let Some((_, drag)) = drags.remove_entry(container_id) else {
             Err(InventoryError::OutOfOrderDragging)?
         };
-        let drag = drag.lock().await;
+        let mut drag = drag.lock().await;
 
         if player != drag.player {
             Err(InventoryError::MultiplePlayersDragging)?
         }
+        if let Some(carried_item) = maybe_carried_item {
+            drag.slots
+                .retain(|slot| container.is_item_allowed(*slot, carried_item));
+        }
         let mut slots = container.all_slots();
-        let slots_cloned: Vec<Option<ItemStack>> = slots
-            .iter()
-            .map(|stack| stack.map(|item| item.to_owned()))
-            .collect();
+        let slots_cloned: Vec<Option<ItemStack>> =
+            slots.iter().map(|stack| (**stack).clone()).collect();
         let Some(carried_item) = maybe_carried_item else {
             return Ok(());
         };
@@ -84,27 +85,26 @@ impl DragHandler {
             // Checked in any function that uses this function.
             MouseDragType::Middle => {
                 for slot in &drag.slots {
-                    *slots[*slot] = *maybe_carried_item;
+                    *slots[*slot] = maybe_carried_item.clone();
                 }
             }
             MouseDragType::Right => {
-                let mut single_item = *carried_item;
-                single_item.item_count = 1;
+                let max_stack_size = carried_item.max_stack_size();
+                let single_item = carried_item.with_count(1);
 
-                let changing_slots =
-                    drag.possibly_changing_slots(&slots_cloned, carried_item.item_id);
-                changing_slots.for_each(|slot| {
+                let changing_slots: Vec<usize> = drag
+                    .possibly_changing_slots(&slots_cloned, carried_item)
+                    .collect();
+                changing_slots.into_iter().for_each(|slot| {
                     if carried_item.item_count != 0 {
-                        carried_item.item_count -= 1;
                         if let Some(stack) = &mut slots[slot] {
-                            // TODO: Check for stack max here
-                            if stack.item_count + 1 < 64 {
+                            if stack.item_count < max_stack_size {
                                 stack.item_count += 1;
-                            } else {
-                                carried_item.item_count += 1;
+                                carried_item.item_count -= 1;
                             }
                         } else {
-                            *slots[slot] = Some(single_item)
+                            *slots[slot] = Some(single_item.clone());
+                            carried_item.item_count -= 1;
                         }
                     }
                 });
@@ -114,25 +114,23 @@ impl DragHandler {
                 }
             }
             MouseDragType::Left => {
-                // TODO: Handle dragging a stack with greater amount than item allows as max unstackable
-                // In that specific case, follow MouseDragType::Right behaviours instead!
-
-                let changing_slots =
-                    drag.possibly_changing_slots(&slots_cloned, carried_item.item_id);
-                let amount_of_slots = changing_slots.clone().count();
-                let (amount_per_slot, remainder) = if amount_of_slots == 0 {
-                    // TODO: please work lol
-                    (1, 0)
-                } else {
-                    (carried_item.item_count as usize).div_rem_euclid(&amount_of_slots)
-                };
-                let mut item_in_each_slot = *carried_item;
-                item_in_each_slot.item_count = amount_per_slot as u8;
-                changing_slots.for_each(|slot| *slots[slot] = Some(item_in_each_slot));
-
-                if remainder > 0 {
-                    carried_item.item_count = remainder as u8;
-                } else {
+                let max_stack_size = carried_item.max_stack_size();
+                let changing_slots: Vec<usize> = drag
+                    .possibly_changing_slots(&slots_cloned, carried_item)
+                    .collect();
+                let amount_of_slots = changing_slots.len();
+                let amount_per_slot = (carried_item.item_count as usize)
+                    .checked_div(amount_of_slots)
+                    .unwrap_or(0) as u8;
+                for slot in changing_slots {
+                    let current = slots[slot].as_ref().map_or(0, |stack| stack.item_count);
+                    // Slots that would overflow keep the rest on the cursor
+                    let new_count = (current + amount_per_slot).min(max_stack_size.max(current));
+                    carried_item.item_count -= new_count - current;
+                    *slots[slot] = Some(carried_item.with_count(new_count));
+                }
+
+                if carried_item.item_count == 0 {
                     *maybe_carried_item = None
                 }
             }
@@ -151,14 +149,14 @@ impl Drag {
     fn possibly_changing_slots<'a>(
         &'a self,
         slots: &'a [Option<ItemStack>],
-        carried_item_id: u16,
-    ) -> impl Iterator<Item = usize> + 'a + Clone {
+        carried_item: &'a ItemStack,
+    ) -> impl Iterator<Item = usize> + 'a {
         self.slots.iter().filter_map(move |slot_index| {
             let slot = &slots[*slot_index];
 
             match slot {
                 Some(item_slot) => {
-                    if item_slot.item_id == carried_item_id {
+                    if item_slot.is_stackable_with(carried_item) {
                         Some(*slot_index)
                     } else {
                         None
@@ -169,3 +167,65 @@ impl Drag {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::DragHandler;
+    use crate::{container_click::MouseDragType, Chest, Container};
+
+    async fn drag(
+        chest: &mut Chest,
+        carried: &mut Option<ItemStack>,
+        drag_type: MouseDragType,
+        slots: &[usize],
+    ) {
+        let handler = DragHandler::new();
+        handler.new_drag(0, 1, drag_type).await.unwrap();
+        for slot in slots {
+            handler.add_slot(0, 1, *slot).await.unwrap();
+        }
+        handler.apply_drag(carried, chest, &0, 1).await.unwrap();
+    }
+
+    fn counts(chest: &Chest, slots: &[usize]) -> Vec<u8> {
+        let all_slots = chest.all_slots_ref();
+        slots
+            .iter()
+            .map(|slot| all_slots[*slot].map_or(0, |stack| stack.item_count))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn left_drag_keeps_the_overflow_on_the_cursor() {
+        let mut chest = Chest::new();
+        *chest.all_slots()[0] = Some(ItemStack::of("minecraft:stone", 60));
+        let mut carried = Some(ItemStack::of("minecraft:stone", 64));
+
+        drag(&mut chest, &mut carried, MouseDragType::Left, &[0, 1, 2]).await;
+
+        // 21 items per slot, but the first slot only has room for 4
+        assert_eq!(counts(&chest, &[0, 1, 2]), [64, 21, 21]);
+        assert_eq!(carried.map(|stack| stack.item_count), Some(18));
+    }
+
+    #[tokio::test]
+    async fn right_drag_skips_full_stacks() {
+        let mut chest = Chest::new();
+        *chest.all_slots()[0] = Some(ItemStack::of("minecraft:ender_pearl", 16));
+        *chest.all_slots()[3] = Some(ItemStack::of("minecraft:stone", 1));
+        let mut carried = Some(ItemStack::of("minecraft:ender_pearl", 2));
+
+        drag(
+            &mut chest,
+            &mut carried,
+            MouseDragType::Right,
+            &[0, 3, 1, 2],
+        )
+        .await;
+
+        assert_eq!(counts(&chest, &[0, 1, 2, 3]), [16, 1, 1, 1]);
+        assert_eq!(carried, None);
+    }
+}
@@ -16,4 +16,6 @@ pub enum InventoryError {
     InvalidPacket,
     #[error("Player does not have enough permissions")]
     PermissionError,
+    #[error("Invalid item stack")]
+    InvalidItem,
 }
@@ -0,0 +1,146 @@
+use pumpkin_registry::{get_enchantment, get_tag_values, TagCategory, TagType};
+use pumpkin_world::item::item_registry::{get_item, get_item_by_id};
+use pumpkin_world::item::ItemStack;
+
+/// Durability bonus in percent when two damaged items are combined
+const REPAIR_BONUS: i32 = 5;
+
+fn is_enchanted_book(item: &ItemStack) -> bool {
+    get_item("minecraft:enchanted_book").is_some_and(|book| book.id == item.item_id)
+}
+
+/// Curses stay on the item, every other enchantment is removed
+fn is_curse(enchantment: &str) -> bool {
+    get_tag_values(TagCategory::Enchantment, "curse").is_some_and(|curses| {
+        curses.iter().any(|curse| match curse {
+            TagType::Item(curse) => {
+                curse.strip_prefix("minecraft:").unwrap_or(curse) == enchantment
+            }
+            TagType::Tag(_) => false,
+        })
+    })
+}
+
+fn crafting_enchantments(item: &ItemStack) -> &Vec<(String, i32)> {
+    if is_enchanted_book(item) {
+        &item.components.stored_enchantments
+    } else {
+        &item.components.enchantments
+    }
+}
+
+fn crafting_enchantments_mut(item: &mut ItemStack) -> &mut Vec<(String, i32)> {
+    if is_enchanted_book(item) {
+        &mut item.components.stored_enchantments
+    } else {
+        &mut item.components.enchantments
+    }
+}
+
+fn max_damage(item: &ItemStack) -> Option<i32> {
+    get_item_by_id(item.item_id)?
+        .components
+        .max_damage
+        .map(i32::from)
+        .filter(|_| !item.components.unbreakable)
+}
+
+/// Computes what the grindstone makes out of its inputs, returns `None` if they can't be ground
+pub fn get_grindstone_result(
+    input: Option<&ItemStack>,
+    additional: Option<&ItemStack>,
+) -> Option<ItemStack> {
+    // Only single items can be ground
+    if [input, additional]
+        .into_iter()
+        .flatten()
+        .any(|item| item.item_count > 1)
+    {
+        return None;
+    }
+    let result = match (input, additional) {
+        (Some(input), Some(additional)) => merge_items(input, additional)?,
+        (Some(item), None) | (None, Some(item)) => {
+            if crafting_enchantments(item).is_empty() {
+                return None;
+            }
+            item.clone()
+        }
+        (None, None) => return None,
+    };
+    Some(remove_non_curses(result))
+}
+
+/// Combines two items of the same kind, adding up their durability like crafting does
+fn merge_items(input: &ItemStack, additional: &ItemStack) -> Option<ItemStack> {
+    if input.item_id != additional.item_id {
+        return None;
+    }
+    let mut result = match max_damage(input) {
+        Some(max_damage) => {
+            let damage = |item: &ItemStack| item.components.damage.unwrap_or(0);
+            let durability = max_damage - damage(input) + max_damage - damage(additional)
+                + max_damage * REPAIR_BONUS / 100;
+            let mut result = input.with_count(1);
+            result.components.damage =
+                Some((max_damage - durability).max(0)).filter(|damage| *damage > 0);
+            result
+        }
+        None => {
+            // Items without durability are only merged if they are the same and stack
+            if input.max_stack_size() < 2 || !input.is_stackable_with(additional) {
+                return None;
+            }
+            input.with_count(2)
+        }
+    };
+    // Only curses are kept, so these are the only ones worth taking over
+    let curses: Vec<_> = crafting_enchantments(additional)
+        .iter()
+        .filter(|(name, _)| is_curse(name))
+        .cloned()
+        .collect();
+    let enchantments = crafting_enchantments_mut(&mut result);
+    for (name, level) in curses {
+        match enchantments
+            .iter_mut()
+            .find(|(current, _)| *current == name)
+        {
+            Some((_, current)) => *current = (*current).max(level),
+            None => enchantments.push((name, level)),
+        }
+    }
+    Some(result)
+}
+
+fn remove_non_curses(mut item: ItemStack) -> ItemStack {
+    let enchantments = crafting_enchantments_mut(&mut item);
+    enchantments.retain(|(name, _)| is_curse(name));
+    let remaining = enchantments.len();
+    if remaining == 0 && is_enchanted_book(&item) {
+        if let Some(book) = get_item("minecraft:book") {
+            item.item_id = book.id;
+        }
+    }
+    // Every remaining enchantment counts as one anvil use
+    let repair_cost = (0..remaining).fold(0, |cost: i32, _| cost.saturating_mul(2) + 1);
+    item.components.repair_cost = Some(repair_cost).filter(|cost| *cost > 0);
+    item
+}
+
+/// The experience points the removed enchantments are worth, the player gets between this and twice as many
+pub fn get_grindstone_experience(input: Option<&ItemStack>, additional: Option<&ItemStack>) -> i32 {
+    let total: i32 = [input, additional]
+        .into_iter()
+        .flatten()
+        .flat_map(crafting_enchantments)
+        .filter(|(name, _)| !is_curse(name))
+        .filter_map(|(name, level)| Some(get_enchantment(name)?.min_cost.at_level(*level)))
+        .sum();
+    (total + 1) / 2
+}
+
+/// Only items with durability or enchantments can be put into the grindstone
+pub fn is_grindable(item: &ItemStack) -> bool {
+    max_damage(item).is_some() || !crafting_enchantments(item).is_empty()
+}
@@ -0,0 +1,92 @@
+use crate::crafting::check_ingredient_type;
+use pumpkin_registry::IngredientType;
+use pumpkin_world::item::item_registry::get_item_name_by_id;
+use pumpkin_world::item::ItemStack;
+
+/// Donkeys and mules always carry this many chest columns
+const CHESTED_HORSE_COLUMNS: u8 = 5;
+/// Llamas carry as many chest columns as they are strong, at most this many
+pub const MAX_LLAMA_STRENGTH: u8 = 5;
+
+/// The kinds of animals with a horse inventory, they differ in what they can be equipped with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorseType {
+    Horse,
+    /// Donkeys and mules
+    Chested,
+    /// Llamas and trader llamas, the strength decides how much fits into their chest
+    Llama {
+        strength: u8,
+    },
+    /// Skeleton and zombie horses
+    Undead,
+}
+
+impl HorseType {
+    pub fn can_have_chest(&self) -> bool {
+        matches!(self, Self::Chested | Self::Llama { .. })
+    }
+
+    /// Columns of the chest the window shows, each column has three rows
+    pub fn chest_columns(&self) -> u8 {
+        match self {
+            Self::Chested => CHESTED_HORSE_COLUMNS,
+            Self::Llama { strength } => (*strength).clamp(1, MAX_LLAMA_STRENGTH),
+            Self::Horse | Self::Undead => 0,
+        }
+    }
+
+    pub fn is_saddle(&self, item: &ItemStack) -> bool {
+        // Llamas are led, not ridden with a saddle
+        !matches!(self, Self::Llama { .. })
+            && get_item_name_by_id(item.item_id).is_some_and(|name| name == "saddle")
+    }
+
+    /// Horses wear horse armor and llamas carpets, the others have nothing to put into the armor slot
+    pub fn is_armor(&self, item: &ItemStack) -> bool {
+        match self {
+            Self::Horse => {
+                get_item_name_by_id(item.item_id).is_some_and(|name| name.ends_with("_horse_armor"))
+            }
+            Self::Llama { .. } => {
+                check_ingredient_type(&IngredientType::Tag("wool_carpets".to_string()), item)
+            }
+            Self::Chested | Self::Undead => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::HorseType;
+
+    #[test]
+    fn llama_chests_grow_with_strength() {
+        assert_eq!(HorseType::Horse.chest_columns(), 0);
+        assert_eq!(HorseType::Chested.chest_columns(), 5);
+        assert_eq!(HorseType::Llama { strength: 0 }.chest_columns(), 1);
+        assert_eq!(HorseType::Llama { strength: 3 }.chest_columns(), 3);
+        assert_eq!(HorseType::Llama { strength: 9 }.chest_columns(), 5);
+        assert!(!HorseType::Undead.can_have_chest());
+    }
+
+    #[test]
+    fn equipment_depends_on_the_animal() {
+        let saddle = ItemStack::of("minecraft:saddle", 1);
+        let armor = ItemStack::of("minecraft:iron_horse_armor", 1);
+        let carpet = ItemStack::of("minecraft:red_carpet", 1);
+        assert!(HorseType::Horse.is_saddle(&saddle));
+        assert!(HorseType::Horse.is_armor(&armor));
+        assert!(!HorseType::Horse.is_armor(&carpet));
+
+        let llama = HorseType::Llama { strength: 3 };
+        assert!(!llama.is_saddle(&saddle));
+        assert!(llama.is_armor(&carpet));
+        assert!(!llama.is_armor(&armor));
+
+        assert!(HorseType::Chested.is_saddle(&saddle));
+        assert!(!HorseType::Chested.is_armor(&armor));
+    }
+}
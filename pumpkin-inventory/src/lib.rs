@@ -1,19 +1,45 @@
-use crate::container_click::MouseClick;
+use crate::container_click::{Click, DropType, KeyClick, MouseClick};
+use crate::crafting::{ingredient_slot_check, recipe_grid_layout};
 use crate::player::PlayerInventory;
 use num_derive::FromPrimitive;
 use pumpkin_macros::screen;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_registry::{IngredientSlot, Recipe};
+use pumpkin_world::block::block_entity::BlockEntity;
 use pumpkin_world::item::ItemStack;
+use std::ops::Range;
 
+mod anvil;
+mod brewing;
+mod cartography;
 pub mod container_click;
 mod crafting;
 pub mod drag_handler;
 mod error;
+mod grindstone;
+mod horse;
+mod loom;
+mod merchant;
 mod open_container;
 pub mod player;
+pub mod recipe_book;
+mod smelting;
+mod smithing;
+mod stonecutter;
+mod virtual_container;
 pub mod window_property;
 
+pub use anvil::TOO_EXPENSIVE_COST;
+pub use brewing::BrewingStandRecipes;
 pub use error::InventoryError;
+pub use horse::{HorseType, MAX_LLAMA_STRENGTH};
+pub use merchant::MerchantOffer;
 pub use open_container::*;
+pub use smelting::{cooking_result, smelting_result, FurnaceRecipes};
+pub use stonecutter::{stonecutter_recipes, stonecutter_results};
+pub use virtual_container::{
+    ClickCallback, VirtualClick, VirtualContainer, VirtualContainerBuilder,
+};
 
 #[derive(Debug, FromPrimitive, Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
@@ -69,6 +95,11 @@ pub trait Container: Sync + Send {
         mouse_click: MouseClick,
         taking_crafted: bool,
     ) -> Result<(), InventoryError> {
+        if let Some(carried) = carried_item {
+            if !taking_crafted && !self.is_item_allowed(slot, carried) {
+                return Ok(());
+            }
+        }
         let mut all_slots = self.all_slots();
         if slot > all_slots.len() {
             Err(InventoryError::InvalidSlot)?
@@ -76,7 +107,7 @@ pub trait Container: Sync + Send {
         if taking_crafted {
             match (all_slots[slot].as_mut(), carried_item.as_mut()) {
                 (Some(s1), Some(s2)) => {
-                    if s1.item_id == s2.item_id {
+                    if s1.is_stackable_with(s2) {
                         handle_item_change(all_slots[slot], carried_item, mouse_click);
                     }
                 }
@@ -128,10 +159,90 @@ pub trait Container: Sync + Send {
     fn crafted_item_slot(&self) -> Option<ItemStack> {
         self.all_slots_ref()
             .get(self.crafting_output_slot()?)?
-            .copied()
+            .cloned()
     }
 
     fn recipe_used(&mut self) {}
+
+    /// Experience levels the player has to pay for taking the crafting output
+    fn experience_cost(&self) -> i32 {
+        0
+    }
+
+    /// Called when the player types a new name for the item, e.g. in an anvil
+    fn rename_item(&mut self, _name: &str) {}
+
+    /// Selects a merchant trade, returns the items it costs
+    fn select_trade(&mut self, _index: usize) -> Option<(ItemStack, Option<ItemStack>)> {
+        None
+    }
+
+    /// Called when the player clicks a button in the container, e.g. a stonecutter recipe or a loom pattern.
+    /// Returns whether the button exists
+    fn handle_button_click(&mut self, _button: i32) -> bool {
+        false
+    }
+
+    /// Experience points the player gets for taking the crafting output, a random amount out of the range
+    fn experience_reward(&self) -> Range<i32> {
+        0..0
+    }
+
+    /// Takes the experience stored for the items of the slot, like a furnace hands out the
+    /// experience of the smelted items once its output is taken
+    fn take_experience(&mut self, _slot: usize) -> f32 {
+        0.0
+    }
+
+    /// Takes the remainders of used ingredients which didn't fit back into the crafting grid, like
+    /// the glass bottles of stacked honey bottles. The player gets them
+    fn take_crafting_remainders(&mut self) -> Vec<ItemStack> {
+        Vec::new()
+    }
+
+    /// Whether the item is allowed to be put into the given slot
+    fn is_item_allowed(&self, _slot: usize, _item: &ItemStack) -> bool {
+        true
+    }
+
+    /// Called before a click is handled, returns true if the container handled the click itself.
+    /// The click is then undone on the client instead of moving any items
+    fn intercept_click(&mut self, _player_id: i32, _click: &Click) -> bool {
+        false
+    }
+
+    /// Writes the contents of the container into the block entity NBT
+    fn write_nbt(&self, _nbt: &mut NbtCompound) {}
+
+    /// Restores the contents of the container from the block entity NBT
+    fn read_nbt(&mut self, _nbt: &NbtCompound) {}
+
+    /// Advances the container by one server tick, returns whether any slot changed
+    fn tick(&mut self) -> bool {
+        false
+    }
+
+    /// Whether players changed the container since it copied the block entity it shows, only
+    /// then its contents are copied back
+    fn block_entity_changed(&self) -> bool {
+        false
+    }
+
+    /// Copies the contents into the block entity the container shows, returns false if the
+    /// container doesn't show a block entity of that type
+    fn save_block_entity(&self, _block_entity: &mut dyn BlockEntity) -> bool {
+        false
+    }
+
+    /// Copies the state of the block entity the container shows, returns whether any slot changed
+    fn load_block_entity(&mut self, _block_entity: &dyn BlockEntity) -> bool {
+        false
+    }
+
+    /// The current window properties as `(property, value)` pairs
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        vec![]
+    }
 }
 
 pub struct EmptyContainer;
@@ -170,19 +281,21 @@ pub fn handle_item_take(
     let Some(item) = item_slot else {
         return;
     };
-    let mut new_item = *item;
 
     match mouse_click {
         MouseClick::Left => {
-            *item_slot = None;
+            *carried_item = item_slot.take();
         }
         MouseClick::Right => {
-            let half = item.item_count / 2;
+            // The cursor gets the bigger half
+            let half = item.item_count.div_ceil(2);
             item.item_count -= half;
-            new_item.item_count = half;
+            *carried_item = Some(item.with_count(half));
+            if item.item_count == 0 {
+                *item_slot = None;
+            }
         }
     }
-    *carried_item = Some(new_item);
 }
 pub fn handle_item_change(
     carried_slot: &mut Option<ItemStack>,
@@ -192,25 +305,23 @@ pub fn handle_item_change(
     match (current_slot.as_mut(), carried_slot.as_mut()) {
         // Swap or combine current and carried
         (Some(current), Some(carried)) => {
-            if current.item_id == carried.item_id {
+            if current.is_stackable_with(carried) {
                 combine_stacks(carried_slot, current, mouse_click);
             } else if mouse_click == MouseClick::Left {
-                let carried = *carried;
-                *carried_slot = Some(current.to_owned());
-                *current_slot = Some(carried.to_owned());
+                std::mem::swap(carried_slot, current_slot);
             }
         }
         // Put held stack into empty slot
         (None, Some(carried)) => match mouse_click {
             MouseClick::Left => {
-                *current_slot = Some(carried.to_owned());
-                *carried_slot = None;
+                *current_slot = carried_slot.take();
             }
             MouseClick::Right => {
                 carried.item_count -= 1;
-                let mut new = *carried;
-                new.item_count = 1;
-                *current_slot = Some(new);
+                *current_slot = Some(carried.with_count(1));
+                if carried.item_count == 0 {
+                    *carried_slot = None;
+                }
             }
         },
         // Take stack into carried
@@ -233,17 +344,77 @@ pub fn combine_stacks(
         MouseClick::Right => 1,
     };
 
-    // TODO: Check for item stack max size here
-    if slot.item_count + carried_change <= 64 {
-        slot.item_count += carried_change;
-        carried_item.item_count -= carried_change;
-        if carried_item.item_count == 0 {
-            *carried_slot = None;
+    let max_stack_size = slot.max_stack_size();
+    if slot.item_count >= max_stack_size {
+        return;
+    }
+    let moved = carried_change.min(max_stack_size - slot.item_count);
+    slot.item_count += moved;
+    carried_item.item_count -= moved;
+    if carried_item.item_count == 0 {
+        *carried_slot = None;
+    }
+}
+
+/// Writes the slots as a vanilla `Items` list, skipping empty slots
+pub fn write_items_nbt(slots: &[Option<ItemStack>], nbt: &mut NbtCompound) {
+    write_items_list_nbt(slots, nbt, "Items");
+}
+
+/// Writes the slots as a list with the given name, like the `EnderItems` of players
+pub fn write_items_list_nbt(slots: &[Option<ItemStack>], nbt: &mut NbtCompound, name: &str) {
+    let items = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, item)| {
+            let item = item.as_ref()?;
+            let mut compound = NbtCompound::new();
+            compound.put("Slot".to_string(), NbtTag::Byte(slot as i8));
+            item.write_item_nbt(&mut compound);
+            Some(NbtTag::Compound(compound))
+        })
+        .collect();
+    nbt.put(name.to_string(), NbtTag::List(items));
+}
+
+/// Fills the slots from a vanilla `Items` list, slots which are not in the list are cleared
+pub fn read_items_nbt(slots: &mut [Option<ItemStack>], nbt: &NbtCompound) {
+    read_items_list_nbt(slots, nbt, "Items");
+}
+
+/// Fills the slots from the list with the given name, slots which are not in the list are cleared
+pub fn read_items_list_nbt(slots: &mut [Option<ItemStack>], nbt: &NbtCompound, name: &str) {
+    slots.iter_mut().for_each(|slot| *slot = None);
+    let Some(items) = nbt.get_list(name) else {
+        return;
+    };
+    for compound in items.iter().filter_map(NbtTag::extract_compound) {
+        let Some(slot) = compound.get_byte("Slot") else {
+            continue;
+        };
+        if let Some(slot) = slots.get_mut(slot as usize) {
+            *slot = ItemStack::read_item_nbt(compound);
+        }
+    }
+}
+
+/// What a click on or with a bundle did, players hear a different sound for each
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleClick {
+    Inserted,
+    /// The bundle is full or the item can't be put into bundles
+    InsertFailed,
+    RemovedOne,
+    Unchanged,
+}
+
+impl BundleClick {
+    fn inserted(moved: u8) -> Self {
+        if moved > 0 {
+            Self::Inserted
+        } else {
+            Self::InsertFailed
         }
-    } else {
-        let left_over = slot.item_count + carried_change - 64;
-        slot.item_count = 64;
-        carried_item.item_count = left_over;
     }
 }
 
@@ -267,6 +438,419 @@ impl<'a, 'b> OptionallyCombinedContainer<'a, 'b> {
     }
 }
 
+impl<'a> OptionallyCombinedContainer<'a, 'a> {
+    /// Moves the stack in the slot to the other side of the window, like vanilla does on shift clicks.
+    /// Existing stacks of the same item are filled up first, the rest goes into empty slots
+    pub fn quick_move(&mut self, slot: usize) -> Result<(), InventoryError> {
+        let Some(item) = self
+            .all_slots_ref()
+            .get(slot)
+            .ok_or(InventoryError::InvalidSlot)?
+            .cloned()
+        else {
+            return Ok(());
+        };
+        let targets: Vec<usize> = self
+            .quick_move_targets(slot)
+            .into_iter()
+            .filter(|target| *target != slot && self.is_item_allowed(*target, &item))
+            .collect();
+
+        let max_stack_size = item.max_stack_size();
+        let mut remaining = item.item_count;
+        let mut all_slots = self.all_slots();
+        for target in &targets {
+            if remaining == 0 {
+                break;
+            }
+            if let Some(stack) = all_slots[*target].as_mut() {
+                if stack.is_stackable_with(&item) && stack.item_count < max_stack_size {
+                    let moved = remaining.min(max_stack_size - stack.item_count);
+                    stack.item_count += moved;
+                    remaining -= moved;
+                }
+            }
+        }
+        for target in &targets {
+            if remaining == 0 {
+                break;
+            }
+            if all_slots[*target].is_none() {
+                let moved = remaining.min(max_stack_size);
+                *all_slots[*target] = Some(item.with_count(moved));
+                remaining -= moved;
+            }
+        }
+        *all_slots[slot] = (remaining > 0).then(|| item.with_count(remaining));
+        Ok(())
+    }
+
+    /// Gathers stacks of the carried item from all slots onto the cursor, like vanilla does on double clicks.
+    /// Partial stacks are taken before full ones and crafting outputs are never touched
+    pub fn collect_to_cursor(&mut self, carried_item: &mut Option<ItemStack>) {
+        let Some(carried) = carried_item.as_mut() else {
+            return;
+        };
+        let max_stack_size = carried.max_stack_size();
+        let crafting_output_slot = self.crafting_output_slot();
+        let mut all_slots = self.all_slots();
+        for take_full_stacks in [false, true] {
+            for (index, slot) in all_slots.iter_mut().enumerate() {
+                if carried.item_count >= max_stack_size {
+                    return;
+                }
+                if crafting_output_slot == Some(index) {
+                    continue;
+                }
+                let Some(stack) = slot.as_mut() else {
+                    continue;
+                };
+                if !stack.is_stackable_with(carried)
+                    || (stack.item_count >= max_stack_size) != take_full_stacks
+                {
+                    continue;
+                }
+                let taken = stack.item_count.min(max_stack_size - carried.item_count);
+                stack.item_count -= taken;
+                carried.item_count += taken;
+                if stack.item_count == 0 {
+                    **slot = None;
+                }
+            }
+        }
+    }
+
+    /// Lets bundles take in and give out items, like vanilla does on clicks. A carried bundle takes the
+    /// stack it is left-clicked on and puts its last item into empty slots on right-clicks. A bundle in
+    /// the slot takes the carried stack and hands out its last item on right-clicks with an empty cursor.
+    /// Returns `None` for regular clicks
+    pub fn bundle_click(
+        &mut self,
+        carried_item: &mut Option<ItemStack>,
+        slot: usize,
+        mouse_click: &MouseClick,
+    ) -> Result<Option<BundleClick>, InventoryError> {
+        let slot_is_empty = self
+            .all_slots_ref()
+            .get(slot)
+            .ok_or(InventoryError::InvalidSlot)?
+            .is_none();
+        if let Some(bundle) = carried_item.as_mut().filter(|item| item.is_bundle()) {
+            match mouse_click {
+                MouseClick::Left if !slot_is_empty => {
+                    let mut all_slots = self.all_slots();
+                    let Some(stack) = all_slots[slot].as_mut() else {
+                        return Ok(None);
+                    };
+                    let moved = bundle.bundle_insert(stack);
+                    if stack.item_count == 0 {
+                        *all_slots[slot] = None;
+                    }
+                    return Ok(Some(BundleClick::inserted(moved)));
+                }
+                MouseClick::Right if slot_is_empty => {
+                    let Some(mut removed) = bundle.bundle_remove_one() else {
+                        return Ok(Some(BundleClick::Unchanged));
+                    };
+                    if !self.is_item_allowed(slot, &removed) {
+                        // The item goes back into the bundle
+                        bundle.bundle_insert(&mut removed);
+                        return Ok(Some(BundleClick::Unchanged));
+                    }
+                    *self.all_slots()[slot] = Some(removed);
+                    return Ok(Some(BundleClick::RemovedOne));
+                }
+                _ => (),
+            }
+        }
+
+        let mut all_slots = self.all_slots();
+        let Some(bundle) = all_slots[slot].as_mut().filter(|item| item.is_bundle()) else {
+            return Ok(None);
+        };
+        match (carried_item.as_mut(), mouse_click) {
+            (Some(carried), MouseClick::Left) => {
+                let moved = bundle.bundle_insert(carried);
+                if carried.item_count == 0 {
+                    *carried_item = None;
+                }
+                Ok(Some(BundleClick::inserted(moved)))
+            }
+            (None, MouseClick::Right) => {
+                *carried_item = bundle.bundle_remove_one();
+                Ok(Some(if carried_item.is_some() {
+                    BundleClick::RemovedOne
+                } else {
+                    BundleClick::Unchanged
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Selects a merchant trade and moves the items it costs from the inventory into the payment slots,
+    /// like vanilla does when clicking on a trade
+    pub fn select_trade(&mut self, index: usize) -> Result<(), InventoryError> {
+        let Some((cost_a, cost_b)) = self
+            .container
+            .as_mut()
+            .and_then(|container| container.select_trade(index))
+        else {
+            return Ok(());
+        };
+        // Whatever is in the payment slots goes back first
+        self.quick_move(0)?;
+        self.quick_move(1)?;
+        let all_slots = self.all_slots_ref();
+        if all_slots[0].is_some() || all_slots[1].is_some() {
+            return Ok(());
+        }
+        self.fill_payment_slot(0, &cost_a);
+        if let Some(cost_b) = cost_b {
+            self.fill_payment_slot(1, &cost_b);
+        }
+        self.craft();
+        Ok(())
+    }
+
+    fn fill_payment_slot(&mut self, payment_slot: usize, cost: &ItemStack) {
+        let container_slots = self
+            .container
+            .as_ref()
+            .map_or(0, |container| container.all_slots_ref().len());
+        let mut all_slots = self.all_slots();
+        let (container, inventory) = all_slots.split_at_mut(container_slots);
+        let payment = &mut container[payment_slot];
+        for slot in inventory {
+            let Some(stack) = slot.as_mut() else {
+                continue;
+            };
+            if stack.item_id != cost.item_id
+                || payment
+                    .as_ref()
+                    .is_some_and(|payment| !payment.is_stackable_with(stack))
+            {
+                continue;
+            }
+            let max_stack_size = stack.max_stack_size();
+            let current = payment.as_ref().map_or(0, |payment| payment.item_count);
+            let moved = (max_stack_size - current).min(stack.item_count);
+            **payment = Some(stack.with_count(current + moved));
+            stack.item_count -= moved;
+            if stack.item_count == 0 {
+                **slot = None;
+            }
+            if current + moved >= max_stack_size {
+                break;
+            }
+        }
+    }
+
+    /// Moves the ingredients of the recipe from the inventory into the crafting grid, like vanilla does
+    /// when clicking on a recipe in the recipe book. Returns false if the player is missing ingredients
+    pub fn place_recipe(
+        &mut self,
+        recipe: &Recipe,
+        make_all: bool,
+    ) -> Result<bool, InventoryError> {
+        let Some((grid_start, grid_width)) = self.crafting_grid() else {
+            return Ok(false);
+        };
+        let Some(layout) = recipe_grid_layout(recipe, grid_width) else {
+            return Ok(false);
+        };
+        let grid = grid_start..grid_start + layout.len();
+        // Whatever is in the grid goes back first
+        for slot in grid.clone() {
+            self.quick_move(slot)?;
+        }
+        let inventory = self.inventory_slots();
+        let all_slots = self.all_slots_ref();
+        if all_slots[grid.clone()].iter().any(Option::is_some) {
+            self.craft();
+            return Ok(false);
+        }
+        let items: Vec<Option<ItemStack>> = all_slots[inventory.clone()]
+            .iter()
+            .map(|item| item.cloned())
+            .collect();
+        let crafts = if make_all {
+            // Being able to craft n times means being able to craft fewer times
+            (1..=64).collect::<Vec<u8>>().partition_point(|crafts| {
+                take_ingredients(items.clone(), &layout, *crafts).is_some()
+            })
+        } else {
+            1
+        };
+        let Some((remaining, placed)) = take_ingredients(items, &layout, crafts as u8) else {
+            self.craft();
+            return Ok(false);
+        };
+        let mut all_slots = self.all_slots();
+        for (slot, item) in inventory.zip(remaining).chain(grid.zip(placed)) {
+            *all_slots[slot] = item;
+        }
+        self.craft();
+        Ok(true)
+    }
+
+    /// The first slot and the width of the window's crafting grid
+    fn crafting_grid(&self) -> Option<(usize, usize)> {
+        match &self.container {
+            Some(container) => {
+                (*container.window_type() == WindowType::CraftingTable).then_some((1, 3))
+            }
+            None => Some((1, 2)),
+        }
+    }
+
+    /// The main inventory and hotbar slots of the window
+    fn inventory_slots(&self) -> std::ops::Range<usize> {
+        match &self.container {
+            Some(container) => {
+                let container_slots = container.all_slots_ref().len();
+                container_slots..container_slots + 36
+            }
+            None => 9..45,
+        }
+    }
+
+    /// Takes the items the drop key throws out of the slot, a single item or the whole stack.
+    /// Crafting outputs are always taken as a whole
+    pub fn take_for_drop(
+        &mut self,
+        slot: usize,
+        drop_type: &DropType,
+        taking_crafted: bool,
+    ) -> Result<Option<ItemStack>, InventoryError> {
+        let mut all_slots = self.all_slots();
+        let slot = all_slots.get_mut(slot).ok_or(InventoryError::InvalidSlot)?;
+        let Some(stack) = slot.as_mut() else {
+            return Ok(None);
+        };
+        if taking_crafted || matches!(drop_type, DropType::FullStack) || stack.item_count == 1 {
+            return Ok(slot.take());
+        }
+        stack.item_count -= 1;
+        Ok(Some(stack.with_count(1)))
+    }
+
+    /// Swaps the stack in the slot with the hotbar slot or the offhand selected by the key
+    pub fn swap_with_key(
+        &mut self,
+        slot: usize,
+        key_click: &KeyClick,
+        taking_crafted: bool,
+    ) -> Result<(), InventoryError> {
+        let inventory_slot = match key_click {
+            KeyClick::Slot(hotbar_slot) => 36 + usize::from(*hotbar_slot),
+            KeyClick::Offhand => 45,
+        };
+        let clicked_item = self
+            .all_slots_ref()
+            .get(slot)
+            .ok_or(InventoryError::InvalidSlot)?
+            .cloned();
+        let key_item = self.inventory.get_slot(inventory_slot)?.clone();
+        if let Some(key_item) = &key_item {
+            // Crafting outputs can only be taken out
+            if taking_crafted || !self.is_item_allowed(slot, key_item) {
+                return Ok(());
+            }
+        }
+        *self.all_slots()[slot] = key_item;
+        *self.inventory.get_slot(inventory_slot)? = clicked_item;
+        Ok(())
+    }
+
+    /// The slots a quick move from the given slot tries to put the stack into, in order
+    fn quick_move_targets(&self, slot: usize) -> Vec<usize> {
+        match &self.container {
+            Some(container) => {
+                let container_slots = container.all_slots_ref().len();
+                // Main inventory followed by the hotbar
+                let main = container_slots..container_slots + 27;
+                let hotbar = container_slots + 27..container_slots + 36;
+                if slot < container_slots {
+                    (container_slots..container_slots + 36).rev().collect()
+                } else if container.crafting_output_slot().is_some() {
+                    // Vanilla doesn't move items into the crafting grid
+                    if hotbar.contains(&slot) {
+                        main.collect()
+                    } else {
+                        hotbar.collect()
+                    }
+                } else {
+                    (0..container_slots).collect()
+                }
+            }
+            None => match slot {
+                0 => (9..45).rev().collect(),
+                1..=8 | 45 => (9..45).collect(),
+                // Armor is equipped first
+                9..=35 => (5..=8).chain(36..45).collect(),
+                _ => (5..=8).chain(9..36).collect(),
+            },
+        }
+    }
+}
+
+/// Takes the ingredients for crafting the recipe the given number of times out of the items.
+/// Returns the remaining items and what goes into each grid slot, or `None` if ingredients are missing
+#[allow(clippy::type_complexity)]
+fn take_ingredients(
+    mut items: Vec<Option<ItemStack>>,
+    layout: &[Option<&IngredientSlot>],
+    crafts: u8,
+) -> Option<(Vec<Option<ItemStack>>, Vec<Option<ItemStack>>)> {
+    if crafts == 0 {
+        return None;
+    }
+    let mut grid = Vec::with_capacity(layout.len());
+    for ingredient in layout {
+        let Some(ingredient) = ingredient else {
+            grid.push(None);
+            continue;
+        };
+        // A grid slot holds a single stack, so there has to be enough of one item
+        let placed = items
+            .iter()
+            .flatten()
+            .find(|stack| {
+                ingredient_slot_check(ingredient, stack)
+                    && crafts <= stack.max_stack_size()
+                    && items
+                        .iter()
+                        .flatten()
+                        .filter(|other| other.is_stackable_with(stack))
+                        .map(|other| u32::from(other.item_count))
+                        .sum::<u32>()
+                        >= u32::from(crafts)
+            })?
+            .with_count(crafts);
+        let mut missing = crafts;
+        for slot in &mut items {
+            if missing == 0 {
+                break;
+            }
+            let Some(stack) = slot.as_mut() else {
+                continue;
+            };
+            if !stack.is_stackable_with(&placed) {
+                continue;
+            }
+            let taken = missing.min(stack.item_count);
+            stack.item_count -= taken;
+            missing -= taken;
+            if stack.item_count == 0 {
+                *slot = None;
+            }
+        }
+        grid.push(Some(placed));
+    }
+    Some((items, grid))
+}
+
 impl<'a> Container for OptionallyCombinedContainer<'a, 'a> {
     fn window_type(&self) -> &'static WindowType {
         if let Some(container) = &self.container {
@@ -337,4 +921,176 @@ impl<'a> Container for OptionallyCombinedContainer<'a, 'a> {
             None => self.inventory.recipe_used(),
         }
     }
+
+    fn take_crafting_remainders(&mut self) -> Vec<ItemStack> {
+        match &mut self.container {
+            Some(container) => container.take_crafting_remainders(),
+            None => self.inventory.take_crafting_remainders(),
+        }
+    }
+
+    fn experience_cost(&self) -> i32 {
+        self.container
+            .as_ref()
+            .map_or(0, |container| container.experience_cost())
+    }
+
+    fn experience_reward(&self) -> Range<i32> {
+        self.container
+            .as_ref()
+            .map_or(0..0, |container| container.experience_reward())
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match &self.container {
+            // Slots after the container belong to the players inventory
+            Some(container) if slot < container.all_slots_ref().len() => {
+                container.is_item_allowed(slot, item)
+            }
+            Some(_) => true,
+            None => self.inventory.is_item_allowed(slot, item),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::{combine_stacks, Container, OptionallyCombinedContainer};
+    use crate::{
+        container_click::{KeyClick, MouseClick},
+        player::PlayerInventory,
+        Chest,
+    };
+
+    fn pearls(count: u8) -> ItemStack {
+        ItemStack::of("minecraft:ender_pearl", count)
+    }
+
+    #[test]
+    fn combining_stops_at_the_max_stack_size() {
+        let mut carried = Some(pearls(10));
+        let mut slot = pearls(10);
+        combine_stacks(&mut carried, &mut slot, MouseClick::Left);
+        assert_eq!(slot.item_count, 16);
+        assert_eq!(carried, Some(pearls(4)));
+
+        // A full stack takes nothing
+        combine_stacks(&mut carried, &mut slot, MouseClick::Left);
+        combine_stacks(&mut carried, &mut slot, MouseClick::Right);
+        assert_eq!(slot.item_count, 16);
+        assert_eq!(carried, Some(pearls(4)));
+    }
+
+    #[test]
+    fn right_click_moves_one_item() {
+        let mut carried = Some(pearls(2));
+        let mut slot = pearls(15);
+        combine_stacks(&mut carried, &mut slot, MouseClick::Right);
+        assert_eq!(slot.item_count, 16);
+        assert_eq!(carried, Some(pearls(1)));
+
+        let mut slot = pearls(3);
+        combine_stacks(&mut carried, &mut slot, MouseClick::Right);
+        assert_eq!(slot.item_count, 4);
+        assert_eq!(carried, None);
+    }
+    #[test]
+    fn quick_move_fills_stacks_before_empty_slots() {
+        let mut inventory = PlayerInventory::new();
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, None);
+        *window.all_slots()[9] = Some(ItemStack::of("minecraft:stone", 10));
+        *window.all_slots()[40] = Some(ItemStack::of("minecraft:stone", 60));
+
+        // Stone can't be worn, so it goes to the hotbar
+        window.quick_move(9).unwrap();
+        let slots = window.all_slots_ref();
+        assert_eq!(slots[9], None);
+        assert_eq!(slots[40], Some(&ItemStack::of("minecraft:stone", 64)));
+        assert_eq!(slots[36], Some(&ItemStack::of("minecraft:stone", 6)));
+    }
+
+    #[test]
+    fn quick_move_equips_armor_and_leaves_the_container_from_the_back() {
+        let mut inventory = PlayerInventory::new();
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, None);
+        *window.all_slots()[9] = Some(ItemStack::of("minecraft:iron_helmet", 1));
+        window.quick_move(9).unwrap();
+        assert_eq!(
+            window.all_slots_ref()[5],
+            Some(&ItemStack::of("minecraft:iron_helmet", 1))
+        );
+
+        let mut chest: Box<dyn Container> = Box::new(Chest::new());
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, Some(&mut chest));
+        *window.all_slots()[0] = Some(ItemStack::of("minecraft:stone", 10));
+        window.quick_move(0).unwrap();
+        let slots = window.all_slots_ref();
+        assert_eq!(slots[0], None);
+        // The last hotbar slot comes first
+        assert_eq!(slots[27 + 35], Some(&ItemStack::of("minecraft:stone", 10)));
+    }
+
+    #[test]
+    fn number_keys_swap_with_the_hotbar_and_offhand() {
+        let mut inventory = PlayerInventory::new();
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, None);
+        *window.all_slots()[9] = Some(ItemStack::of("minecraft:stone", 5));
+        *window.all_slots()[37] = Some(ItemStack::of("minecraft:ender_pearl", 3));
+
+        window.swap_with_key(9, &KeyClick::Slot(1), false).unwrap();
+        assert_eq!(
+            window.all_slots_ref()[9],
+            Some(&ItemStack::of("minecraft:ender_pearl", 3))
+        );
+        assert_eq!(
+            window.all_slots_ref()[37],
+            Some(&ItemStack::of("minecraft:stone", 5))
+        );
+
+        window.swap_with_key(9, &KeyClick::Offhand, false).unwrap();
+        assert_eq!(window.all_slots_ref()[9], None);
+        assert_eq!(
+            window.all_slots_ref()[45],
+            Some(&ItemStack::of("minecraft:ender_pearl", 3))
+        );
+    }
+
+    #[test]
+    fn number_keys_respect_slot_rules() {
+        let mut inventory = PlayerInventory::new();
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, None);
+        *window.all_slots()[36] = Some(ItemStack::of("minecraft:stone", 5));
+
+        // Stone is no helmet
+        window.swap_with_key(5, &KeyClick::Slot(0), false).unwrap();
+        assert_eq!(window.all_slots_ref()[5], None);
+        // Crafting outputs can only be taken out
+        window.swap_with_key(0, &KeyClick::Slot(0), true).unwrap();
+        assert_eq!(
+            window.all_slots_ref()[36],
+            Some(&ItemStack::of("minecraft:stone", 5))
+        );
+    }
+
+    #[test]
+    fn double_click_takes_partial_stacks_first() {
+        let mut inventory = PlayerInventory::new();
+        let mut chest: Box<dyn Container> = Box::new(Chest::new());
+        let mut window = OptionallyCombinedContainer::new(&mut inventory, Some(&mut chest));
+        *window.all_slots()[0] = Some(ItemStack::of("minecraft:stone", 64));
+        *window.all_slots()[1] = Some(ItemStack::of("minecraft:stone", 5));
+        *window.all_slots()[2] = Some(ItemStack::of("minecraft:ender_pearl", 5));
+        *window.all_slots()[30] = Some(ItemStack::of("minecraft:stone", 30));
+        let mut carried = Some(ItemStack::of("minecraft:stone", 10));
+
+        window.collect_to_cursor(&mut carried);
+        assert_eq!(carried, Some(ItemStack::of("minecraft:stone", 64)));
+        let slots = window.all_slots_ref();
+        assert_eq!(slots[0], Some(&ItemStack::of("minecraft:stone", 45)));
+        assert_eq!(slots[1], None);
+        assert_eq!(slots[2], Some(&ItemStack::of("minecraft:ender_pearl", 5)));
+        assert_eq!(slots[30], None);
+    }
 }
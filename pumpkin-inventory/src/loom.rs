@@ -0,0 +1,72 @@
+use pumpkin_registry::{get_tag_values, TagCategory, TagType};
+use pumpkin_world::item::components::DYE_COLORS;
+use pumpkin_world::item::item_registry::get_item_name_by_id;
+use pumpkin_world::item::ItemStack;
+
+/// Banners can't have more layers than this
+pub const MAX_PATTERNS: usize = 6;
+
+fn item_name(item: &ItemStack) -> Option<&'static str> {
+    get_item_name_by_id(item.item_id)
+}
+
+pub fn is_banner(item: &ItemStack) -> bool {
+    item_name(item)
+        .and_then(|name| name.strip_suffix("_banner"))
+        .is_some_and(|color| DYE_COLORS.contains(&color))
+}
+
+pub fn dye_color(item: &ItemStack) -> Option<&'static str> {
+    let color = item_name(item)?.strip_suffix("_dye")?;
+    DYE_COLORS.iter().copied().find(|dye| *dye == color)
+}
+
+/// The banner pattern tag of a banner pattern item, e.g. `pattern_item/globe` for the globe banner pattern
+fn provided_patterns_tag(item: &ItemStack) -> Option<String> {
+    let pattern = item_name(item)?.strip_suffix("_banner_pattern")?;
+    Some(format!("pattern_item/{pattern}"))
+}
+
+pub fn is_banner_pattern(item: &ItemStack) -> bool {
+    provided_patterns_tag(item).is_some()
+}
+
+/// The patterns the player can choose from, the index of a pattern is the button the player clicks for it.
+/// Some patterns can be applied without a pattern item
+pub fn selectable_patterns(pattern_item: Option<&ItemStack>) -> Vec<String> {
+    let tag = match pattern_item {
+        Some(item) => match provided_patterns_tag(item) {
+            Some(tag) => tag,
+            None => return vec![],
+        },
+        None => "no_item_required".to_string(),
+    };
+    get_tag_values(TagCategory::BannerPattern, &tag)
+        .into_iter()
+        .flatten()
+        .filter_map(|pattern| match pattern {
+            TagType::Item(pattern) => Some(
+                pattern
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(pattern)
+                    .to_string(),
+            ),
+            TagType::Tag(_) => None,
+        })
+        .collect()
+}
+
+/// Adds a layer with the pattern in the color of the dye on top of the banner,
+/// returns `None` if the banner is full
+pub fn get_loom_result(banner: &ItemStack, dye: &ItemStack, pattern: &str) -> Option<ItemStack> {
+    let color = dye_color(dye)?;
+    if banner.components.banner_patterns.len() >= MAX_PATTERNS {
+        return None;
+    }
+    let mut result = banner.with_count(1);
+    result
+        .components
+        .banner_patterns
+        .push((pattern.to_string(), color.to_string()));
+    Some(result)
+}
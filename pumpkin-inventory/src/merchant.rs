@@ -0,0 +1,127 @@
+use pumpkin_world::item::ItemStack;
+
+/// A trade offered by a villager or wandering trader
+#[derive(Clone, Debug)]
+pub struct MerchantOffer {
+    pub cost_a: ItemStack,
+    pub cost_b: Option<ItemStack>,
+    pub result: ItemStack,
+    pub uses: i32,
+    pub max_uses: i32,
+    /// Experience the merchant gains from the trade
+    pub experience: i32,
+    /// Price adjustment, e.g. from the player's reputation
+    pub special_price: i32,
+    pub price_multiplier: f32,
+    /// Raises the price of trades which are used a lot
+    pub demand: i32,
+}
+
+impl MerchantOffer {
+    pub fn new(
+        cost_a: ItemStack,
+        cost_b: Option<ItemStack>,
+        result: ItemStack,
+        max_uses: i32,
+        experience: i32,
+        price_multiplier: f32,
+    ) -> Self {
+        Self {
+            cost_a,
+            cost_b,
+            result,
+            uses: 0,
+            max_uses,
+            experience,
+            special_price: 0,
+            price_multiplier,
+            demand: 0,
+        }
+    }
+
+    pub fn is_out_of_stock(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+
+    /// The first cost with the demand and special price applied
+    pub fn adjusted_cost_a(&self) -> ItemStack {
+        let count = i32::from(self.cost_a.item_count);
+        let demand_bonus = ((count * self.demand) as f32 * self.price_multiplier)
+            .floor()
+            .max(0.0) as i32;
+        let adjusted_count = (count + demand_bonus + self.special_price)
+            .clamp(1, i32::from(self.cost_a.max_stack_size()));
+        self.cost_a.with_count(adjusted_count as u8)
+    }
+
+    /// Whether the items in the two payment slots pay for this trade
+    pub fn is_paid_by(&self, a: Option<&ItemStack>, b: Option<&ItemStack>) -> bool {
+        pays(&self.adjusted_cost_a(), a)
+            && match &self.cost_b {
+                Some(cost_b) => pays(cost_b, b),
+                None => b.is_none(),
+            }
+    }
+}
+
+fn pays(cost: &ItemStack, item: Option<&ItemStack>) -> bool {
+    item.is_some_and(|item| item.item_id == cost.item_id && item.item_count >= cost.item_count)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::MerchantOffer;
+
+    fn bread_for_emeralds() -> MerchantOffer {
+        MerchantOffer::new(
+            ItemStack::of("minecraft:emerald", 10),
+            None,
+            ItemStack::of("minecraft:bread", 6),
+            12,
+            1,
+            0.05,
+        )
+    }
+
+    #[test]
+    fn demand_and_special_prices_change_the_cost() {
+        let mut offer = bread_for_emeralds();
+        assert_eq!(offer.adjusted_cost_a().item_count, 10);
+        // 10 * 5 * 0.05 rounds down to 2
+        offer.demand = 5;
+        assert_eq!(offer.adjusted_cost_a().item_count, 12);
+        offer.special_price = -3;
+        assert_eq!(offer.adjusted_cost_a().item_count, 9);
+        // Prices stay between one item and a full stack
+        offer.special_price = -100;
+        assert_eq!(offer.adjusted_cost_a().item_count, 1);
+        offer.special_price = 100;
+        assert_eq!(offer.adjusted_cost_a().item_count, 64);
+    }
+
+    #[test]
+    fn payment_slots_must_cover_the_cost() {
+        let mut offer = bread_for_emeralds();
+        let emeralds = ItemStack::of("minecraft:emerald", 12);
+        assert!(offer.is_paid_by(Some(&emeralds), None));
+        assert!(!offer.is_paid_by(Some(&ItemStack::of("minecraft:emerald", 9)), None));
+        assert!(!offer.is_paid_by(Some(&ItemStack::of("minecraft:diamond", 12)), None));
+        // Trades with one cost want the second slot empty
+        assert!(!offer.is_paid_by(Some(&emeralds), Some(&emeralds)));
+
+        offer.cost_b = Some(ItemStack::of("minecraft:book", 1));
+        assert!(!offer.is_paid_by(Some(&emeralds), None));
+        assert!(offer.is_paid_by(Some(&emeralds), Some(&ItemStack::of("minecraft:book", 1))));
+    }
+
+    #[test]
+    fn offers_run_out_of_stock() {
+        let mut offer = bread_for_emeralds();
+        offer.uses = 11;
+        assert!(!offer.is_out_of_stock());
+        offer.uses = 12;
+        assert!(offer.is_out_of_stock());
+    }
+}
@@ -1,8 +1,30 @@
-use crate::crafting::check_if_matches_crafting;
-use crate::{Container, WindowType};
+use crate::anvil::{self, get_anvil_result};
+use crate::brewing::{is_brewing_fuel, is_brewing_ingredient, is_potion_container};
+use crate::cartography::{get_cartography_result, is_cartography_material, is_filled_map};
+use crate::container_click::MouseClick;
+use crate::crafting::{check_if_matches_crafting, use_ingredients};
+use crate::grindstone::{get_grindstone_experience, get_grindstone_result, is_grindable};
+use crate::horse::{HorseType, MAX_LLAMA_STRENGTH};
+use crate::loom::{dye_color, get_loom_result, is_banner, is_banner_pattern, selectable_patterns};
+use crate::merchant::MerchantOffer;
+use crate::smelting::get_fuel_burn_time;
+use crate::smithing::{get_smithing_result, is_smithing_input};
+use crate::stonecutter::stonecutter_results;
+use crate::window_property::{self, WindowProperty};
+use crate::{
+    handle_item_change, read_items_list_nbt, read_items_nbt, write_items_list_nbt, write_items_nbt,
+    Container, InventoryError, WindowType,
+};
 use pumpkin_core::math::position::WorldPosition;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::block::block_entity::brewing_stand::{self, BrewingStandBlockEntity};
+use pumpkin_world::block::block_entity::furnace::{FurnaceBlockEntity, FUEL_SLOT, OUTPUT_SLOT};
+use pumpkin_world::block::block_entity::BlockEntity;
 use pumpkin_world::block::block_registry::Block;
 use pumpkin_world::item::ItemStack;
+use pumpkin_world::level::Level;
+use std::marker::PhantomData;
+use std::ops::Range;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -12,7 +34,11 @@ pub struct OpenContainer {
     players: Vec<i32>,
     container: Arc<Mutex<Box<dyn Container>>>,
     location: Option<WorldPosition>,
+    /// The other half of containers spanning two blocks, like large chests
+    second_location: Option<WorldPosition>,
     block: Option<Block>,
+    /// The entity the container belongs to, like a horse
+    entity: Option<i32>,
 }
 
 impl OpenContainer {
@@ -52,18 +78,51 @@ impl OpenContainer {
             players: vec![player_id],
             container: Arc::new(Mutex::new(Box::new(C::default()))),
             location,
+            second_location: None,
             block,
+            entity: None,
         }
     }
 
-    pub fn is_location(&self, try_position: WorldPosition) -> bool {
-        if let Some(location) = self.location {
-            location == try_position
-        } else {
-            false
+    /// Opens an already filled container which doesn't belong to a block, like a villager's trades
+    pub fn new_without_block(player_id: i32, container: Box<dyn Container>) -> Self {
+        Self {
+            players: vec![player_id],
+            container: Arc::new(Mutex::new(container)),
+            location: None,
+            second_location: None,
+            block: None,
+            entity: None,
+        }
+    }
+
+    /// Opens the inventory of an entity, the container is shared with the entity which keeps it after closing
+    pub fn new_for_entity(
+        player_id: i32,
+        entity_id: i32,
+        container: Arc<Mutex<Box<dyn Container>>>,
+    ) -> Self {
+        Self {
+            players: vec![player_id],
+            container,
+            location: None,
+            second_location: None,
+            block: None,
+            entity: Some(entity_id),
         }
     }
 
+    /// Ties the container to a block, so it gets closed once the block is gone or out of reach
+    pub fn with_block(mut self, location: WorldPosition, block: Block) -> Self {
+        self.location = Some(location);
+        self.block = Some(block);
+        self
+    }
+
+    pub fn is_location(&self, try_position: WorldPosition) -> bool {
+        self.location == Some(try_position) || self.second_location == Some(try_position)
+    }
+
     pub async fn clear_all_slots(&self) {
         self.container.lock().await.clear_all_slots();
     }
@@ -88,16 +147,75 @@ impl OpenContainer {
         self.location = location;
     }
 
+    pub fn get_second_location(&self) -> Option<WorldPosition> {
+        self.second_location
+    }
+
+    pub fn set_second_location(&mut self, second_location: Option<WorldPosition>) {
+        self.second_location = second_location;
+    }
+
     pub fn get_block(&self) -> Option<Block> {
         self.block.clone()
     }
+
+    /// Changes the block the container belongs to, e.g. when an anvil gets damaged
+    pub fn set_block(&mut self, block: Block) {
+        self.block = Some(block);
+    }
+
+    pub fn get_entity(&self) -> Option<i32> {
+        self.entity
+    }
+
+    pub fn get_container(&self) -> Arc<Mutex<Box<dyn Container>>> {
+        self.container.clone()
+    }
+
+    pub async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.container.lock().await.write_nbt(nbt);
+    }
+
+    pub async fn read_nbt(&self, nbt: &NbtCompound) {
+        self.container.lock().await.read_nbt(nbt);
+    }
+
+    /// Copies the contents into the block entity the container shows, returns false if there is
+    /// none to keep them
+    pub async fn save_block_entity(&self, level: &Level) -> bool {
+        let Some(location) = self.location else {
+            return false;
+        };
+        let container = self.container.lock().await;
+        level
+            .update_block_entity(location, |block_entity| {
+                container.save_block_entity(block_entity)
+            })
+            .await
+    }
 }
+/// Whether a recomputed crafting output differs from the previous one
+pub(crate) fn output_changed(old: &Option<ItemStack>, new: &Option<ItemStack>) -> bool {
+    old != new
+}
+
+/// Uses up one item of the stack in the slot
+fn remove_one(slot: &mut Option<ItemStack>) {
+    if let Some(item) = slot {
+        if item.item_count > 1 {
+            item.item_count -= 1;
+        } else {
+            *slot = None;
+        }
+    }
+}
+
 #[derive(Default)]
 pub struct Chest([Option<ItemStack>; 27]);
 
 impl Chest {
     pub fn new() -> Self {
-        Self([None; 27])
+        Self([const { None }; 27])
     }
 }
 impl Container for Chest {
@@ -115,12 +233,130 @@ impl Container for Chest {
     fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
         self.0.iter().map(|slot| slot.as_ref()).collect()
     }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items_nbt(&self.0, nbt);
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        read_items_nbt(&mut self.0, nbt);
+    }
+}
+
+pub struct LargeChest([Option<ItemStack>; 54]);
+
+impl LargeChest {
+    pub fn new() -> Self {
+        Self([const { None }; 54])
+    }
+}
+
+impl Default for LargeChest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Container for LargeChest {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x6
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Large Chest"
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+/// Every player has their own ender chest, all ender chest blocks open the same one
+#[derive(Default)]
+pub struct EnderChestContainer([Option<ItemStack>; 27]);
+
+impl EnderChestContainer {
+    pub fn new() -> Self {
+        Self([const { None }; 27])
+    }
+}
+
+impl Container for EnderChestContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Ender Chest"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    // Stored in the player data instead of a block entity
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items_list_nbt(&self.0, nbt, "EnderItems");
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        read_items_list_nbt(&mut self.0, nbt, "EnderItems");
+    }
+}
+
+#[derive(Default)]
+pub struct ShulkerBoxContainer([Option<ItemStack>; 27]);
+
+impl ShulkerBoxContainer {
+    pub fn new() -> Self {
+        Self([const { None }; 27])
+    }
+}
+
+impl Container for ShulkerBoxContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::ShulkerBox
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Shulker Box"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn is_item_allowed(&self, _slot: usize, item: &ItemStack) -> bool {
+        // Shulker boxes can't be nested
+        !item.is_shulker_box()
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items_nbt(&self.0, nbt);
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        read_items_nbt(&mut self.0, nbt);
+    }
 }
 
 #[derive(Default)]
 pub struct CraftingTable {
     input: [[Option<ItemStack>; 3]; 3],
     output: Option<ItemStack>,
+    /// Remainders of the used ingredients which didn't fit back into the crafting grid
+    remainders: Vec<ItemStack>,
 }
 
 impl Container for CraftingTable {
@@ -158,8 +394,8 @@ impl Container for CraftingTable {
     }
 
     fn craft(&mut self) -> bool {
-        let old_output = self.output;
-        self.output = check_if_matches_crafting(self.input);
+        let old_output = self.output.take();
+        self.output = check_if_matches_crafting(self.input.clone());
         old_output != self.output
             || self.input.iter().flatten().any(|s| s.is_some())
             || self.output.is_some()
@@ -173,44 +409,1083 @@ impl Container for CraftingTable {
         (1..10).contains(slot)
     }
     fn recipe_used(&mut self) {
-        self.input.iter_mut().flatten().for_each(|slot| {
-            if let Some(item) = slot {
-                if item.item_count > 1 {
-                    item.item_count -= 1;
-                } else {
-                    *slot = None;
-                }
-            }
-        })
+        let leftovers = use_ingredients(self.input.iter_mut().flatten());
+        self.remainders.extend(leftovers);
+    }
+
+    fn take_crafting_remainders(&mut self) -> Vec<ItemStack> {
+        std::mem::take(&mut self.remainders)
+    }
+}
+
+/// Which block entity a furnace-like block has and which screen it opens
+pub trait FurnaceKind: Default + Send + Sync + 'static {
+    const BLOCK_ENTITY: &'static str;
+    const WINDOW_TYPE: &'static WindowType;
+    const NAME: &'static str;
+}
+
+#[derive(Default)]
+pub struct Smelting;
+
+impl FurnaceKind for Smelting {
+    const BLOCK_ENTITY: &'static str = "minecraft:furnace";
+    const WINDOW_TYPE: &'static WindowType = &WindowType::Furnace;
+    const NAME: &'static str = "Furnace";
+}
+
+/// Only smelts ores and armor, twice as fast as a furnace
+#[derive(Default)]
+pub struct Blasting;
+
+impl FurnaceKind for Blasting {
+    const BLOCK_ENTITY: &'static str = "minecraft:blast_furnace";
+    const WINDOW_TYPE: &'static WindowType = &WindowType::BlastFurnace;
+    const NAME: &'static str = "Blast Furnace";
+}
+
+/// Only cooks food, twice as fast as a furnace
+#[derive(Default)]
+pub struct Smoking;
+
+impl FurnaceKind for Smoking {
+    const BLOCK_ENTITY: &'static str = "minecraft:smoker";
+    const WINDOW_TYPE: &'static WindowType = &WindowType::Smoker;
+    const NAME: &'static str = "Smoker";
+}
+
+pub type BlastFurnaceContainer = FurnaceContainer<Blasting>;
+pub type SmokerContainer = FurnaceContainer<Smoking>;
+
+/// Shows the furnace block entity, which cooks while nobody looks at it. The server copies the
+/// clicks into the block entity before the world ticks and its progress back afterwards, clicks
+/// wait until both are done
+pub struct FurnaceContainer<K: FurnaceKind = Smelting> {
+    kind: PhantomData<K>,
+    furnace: FurnaceBlockEntity,
+    /// The block entity as it was last copied, clicks change the furnace away from it
+    copied: FurnaceBlockEntity,
+}
+
+impl<K: FurnaceKind> Default for FurnaceContainer<K> {
+    fn default() -> Self {
+        let furnace = FurnaceBlockEntity::from_nbt(K::BLOCK_ENTITY, &NbtCompound::new());
+        Self {
+            kind: PhantomData,
+            copied: furnace.clone(),
+            furnace,
+        }
+    }
+}
+
+impl<K: FurnaceKind> FurnaceContainer<K> {
+    pub fn is_burning(&self) -> bool {
+        self.furnace.is_lit()
+    }
+}
+
+impl<K: FurnaceKind> Container for FurnaceContainer<K> {
+    fn window_type(&self) -> &'static WindowType {
+        K::WINDOW_TYPE
+    }
+
+    fn window_name(&self) -> &'static str {
+        K::NAME
+    }
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.furnace.items.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.furnace.items.iter().map(Option::as_ref).collect()
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match slot {
+            FUEL_SLOT => get_fuel_burn_time(item).is_some(),
+            OUTPUT_SLOT => false,
+            _ => true,
+        }
+    }
+
+    fn take_experience(&mut self, slot: usize) -> f32 {
+        if slot == OUTPUT_SLOT {
+            std::mem::take(&mut self.furnace.experience)
+        } else {
+            0.0
+        }
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.furnace.write_nbt(nbt);
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.furnace = FurnaceBlockEntity::from_nbt(K::BLOCK_ENTITY, nbt);
+        self.copied.clone_from(&self.furnace);
+    }
+
+    fn block_entity_changed(&self) -> bool {
+        self.furnace != self.copied
+    }
+
+    fn save_block_entity(&self, block_entity: &mut dyn BlockEntity) -> bool {
+        let Some(furnace) = block_entity
+            .as_any_mut()
+            .downcast_mut::<FurnaceBlockEntity>()
+        else {
+            return false;
+        };
+        furnace.clone_from(&self.furnace);
+        true
+    }
+
+    fn load_block_entity(&mut self, block_entity: &dyn BlockEntity) -> bool {
+        let Some(furnace) = block_entity.as_any().downcast_ref::<FurnaceBlockEntity>() else {
+            return false;
+        };
+        let slots_changed = furnace.items != self.furnace.items;
+        self.furnace.clone_from(furnace);
+        self.copied.clone_from(furnace);
+        slots_changed
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        vec![
+            WindowProperty::new(
+                window_property::Furnace::FireIcon,
+                self.furnace.lit_time_remaining,
+            )
+            .into_tuple(),
+            WindowProperty::new(
+                window_property::Furnace::MaximumFuelBurnTime,
+                self.furnace.lit_total_time,
+            )
+            .into_tuple(),
+            WindowProperty::new(
+                window_property::Furnace::ProgressArrow,
+                self.furnace.cooking_time_spent,
+            )
+            .into_tuple(),
+            WindowProperty::new(
+                window_property::Furnace::MaximumProgress,
+                self.furnace.cooking_total_time,
+            )
+            .into_tuple(),
+        ]
+    }
+}
+
+/// Shows the brewing stand block entity, which brews while nobody looks at it. It is copied
+/// back and forth like the furnace
+pub struct BrewingStandContainer {
+    stand: BrewingStandBlockEntity,
+    /// The block entity as it was last copied, clicks change the stand away from it
+    copied: BrewingStandBlockEntity,
+}
+
+impl Default for BrewingStandContainer {
+    fn default() -> Self {
+        let stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        Self {
+            copied: stand.clone(),
+            stand,
+        }
+    }
+}
+
+impl Container for BrewingStandContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::BrewingStand
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Brewing Stand"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.stand.items.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.stand.items.iter().map(Option::as_ref).collect()
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match slot {
+            // Only potions, as they don't stack the bottle slots can't hold more than one item
+            0..=2 => is_potion_container(item),
+            brewing_stand::INGREDIENT_SLOT => is_brewing_ingredient(item),
+            brewing_stand::FUEL_SLOT => is_brewing_fuel(item),
+            _ => false,
+        }
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        vec![
+            WindowProperty::new(
+                window_property::BrewingStand::BrewTime,
+                self.stand.brew_time as i16,
+            )
+            .into_tuple(),
+            WindowProperty::new(
+                window_property::BrewingStand::FuelTime,
+                i16::from(self.stand.fuel),
+            )
+            .into_tuple(),
+        ]
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.stand.write_nbt(nbt);
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.stand = BrewingStandBlockEntity::from_nbt(nbt);
+        self.copied.clone_from(&self.stand);
+    }
+
+    fn block_entity_changed(&self) -> bool {
+        self.stand != self.copied
+    }
+
+    fn save_block_entity(&self, block_entity: &mut dyn BlockEntity) -> bool {
+        let Some(stand) = block_entity
+            .as_any_mut()
+            .downcast_mut::<BrewingStandBlockEntity>()
+        else {
+            return false;
+        };
+        stand.clone_from(&self.stand);
+        true
+    }
+
+    fn load_block_entity(&mut self, block_entity: &dyn BlockEntity) -> bool {
+        let Some(stand) = block_entity
+            .as_any()
+            .downcast_ref::<BrewingStandBlockEntity>()
+        else {
+            return false;
+        };
+        let slots_changed = stand.items != self.stand.items;
+        self.stand.clone_from(stand);
+        self.copied.clone_from(stand);
+        slots_changed
     }
 }
 
 #[derive(Default)]
-pub struct Furnace {
-    cook: Option<ItemStack>,
-    fuel: Option<ItemStack>,
+pub struct AnvilContainer {
+    input: Option<ItemStack>,
+    /// The item combined into the input, e.g. an enchanted book or repair material
+    sacrifice: Option<ItemStack>,
     output: Option<ItemStack>,
+    /// The name typed into the text field
+    item_name: String,
+    cost: i32,
+    repair_item_count: Option<u8>,
 }
 
-impl Container for Furnace {
+impl Container for AnvilContainer {
     fn window_type(&self) -> &'static WindowType {
-        &WindowType::Furnace
+        &WindowType::Anvil
     }
 
     fn window_name(&self) -> &'static str {
-        "Furnace"
+        "Repair & Name"
     }
+
     fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
-        let mut slots = vec![&mut self.cook];
-        slots.push(&mut self.fuel);
-        slots.push(&mut self.output);
-        slots
+        vec![&mut self.input, &mut self.sacrifice, &mut self.output]
     }
 
     fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
-        let mut slots = vec![self.cook.as_ref()];
-        slots.push(self.fuel.as_ref());
-        slots.push(self.output.as_ref());
-        slots
+        vec![
+            self.input.as_ref(),
+            self.sacrifice.as_ref(),
+            self.output.as_ref(),
+        ]
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![self.input.as_ref(), self.sacrifice.as_ref()]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.input, &mut self.sacrifice]
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        let old_cost = self.cost;
+        let result = self
+            .input
+            .as_ref()
+            .and_then(|input| get_anvil_result(input, self.sacrifice.as_ref(), &self.item_name));
+        (self.output, self.cost, self.repair_item_count) = match result {
+            Some(result) => (Some(result.output), result.cost, result.repair_item_count),
+            None => (None, 0, None),
+        };
+        output_changed(&old_output, &self.output) || old_cost != self.cost
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..2).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        self.input = None;
+        self.sacrifice = match (self.sacrifice.take(), self.repair_item_count) {
+            (Some(sacrifice), Some(used)) if sacrifice.item_count > used => {
+                Some(sacrifice.with_count(sacrifice.item_count - used))
+            }
+            _ => None,
+        };
+        self.cost = 0;
+    }
+
+    fn is_item_allowed(&self, slot: usize, _item: &ItemStack) -> bool {
+        slot != 2
+    }
+
+    fn experience_cost(&self) -> i32 {
+        self.cost
+    }
+
+    fn rename_item(&mut self, name: &str) {
+        self.item_name = name.chars().take(anvil::MAX_NAME_LENGTH).collect();
+        self.craft();
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        vec![WindowProperty::new(
+            window_property::Anvil::RepairCost,
+            self.cost.clamp(0, i16::MAX.into()) as i16,
+        )
+        .into_tuple()]
+    }
+}
+
+pub struct MerchantContainer {
+    payment: [Option<ItemStack>; 2],
+    output: Option<ItemStack>,
+    offers: Vec<MerchantOffer>,
+    /// The trade the player clicked on in the list
+    selected_offer: Option<usize>,
+    /// The trade the output belongs to, and whether it is paid with the payment slots swapped
+    active_offer: Option<(usize, bool)>,
+    experience: i32,
+}
+
+impl MerchantContainer {
+    pub fn new(offers: Vec<MerchantOffer>, experience: i32) -> Self {
+        Self {
+            payment: [None, None],
+            output: None,
+            offers,
+            selected_offer: None,
+            active_offer: None,
+            experience,
+        }
+    }
+
+    fn find_offer(&self, index: usize) -> Option<(usize, bool)> {
+        let offer = self.offers.get(index)?;
+        let [a, b] = &self.payment;
+        if offer.is_paid_by(a.as_ref(), b.as_ref()) {
+            Some((index, false))
+        } else if offer.is_paid_by(b.as_ref(), a.as_ref()) {
+            Some((index, true))
+        } else {
+            None
+        }
+    }
+}
+
+impl Container for MerchantContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Merchant
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Villager"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        let mut slots: Vec<_> = self.payment.iter_mut().collect();
+        slots.push(&mut self.output);
+        slots
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        let mut slots: Vec<_> = self.payment.iter().map(|slot| slot.as_ref()).collect();
+        slots.push(self.output.as_ref());
+        slots
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        self.payment.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.payment.iter_mut().collect()
+    }
+
+    fn craft(&mut self) -> bool {
+        // The selected trade is preferred if multiple trades cost the same items
+        let active_offer = self
+            .selected_offer
+            .and_then(|index| self.find_offer(index))
+            .or_else(|| (0..self.offers.len()).find_map(|index| self.find_offer(index)));
+        self.active_offer =
+            active_offer.filter(|(index, _)| !self.offers[*index].is_out_of_stock());
+
+        let old_output = self.output.take();
+        self.output = self
+            .active_offer
+            .map(|(index, _)| self.offers[index].result.clone());
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..2).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        let Some((index, swapped)) = self.active_offer.take() else {
+            return;
+        };
+        let offer = &mut self.offers[index];
+        let costs = [Some(offer.adjusted_cost_a()), offer.cost_b.clone()];
+        offer.uses += 1;
+        self.experience += offer.experience;
+
+        let (a, b) = if swapped { (1, 0) } else { (0, 1) };
+        for (slot, cost) in [a, b].into_iter().zip(costs) {
+            let Some(cost) = cost else {
+                continue;
+            };
+            if let Some(payment) = self.payment[slot].as_mut() {
+                payment.item_count = payment.item_count.saturating_sub(cost.item_count);
+                if payment.item_count == 0 {
+                    self.payment[slot] = None;
+                }
+            }
+        }
+    }
+
+    fn is_item_allowed(&self, slot: usize, _item: &ItemStack) -> bool {
+        slot != 2
+    }
+
+    fn select_trade(&mut self, index: usize) -> Option<(ItemStack, Option<ItemStack>)> {
+        let offer = self.offers.get(index)?;
+        let costs = (offer.adjusted_cost_a(), offer.cost_b.clone());
+        self.selected_offer = Some(index);
+        Some(costs)
+    }
+}
+
+#[derive(Default)]
+pub struct StonecutterContainer {
+    input: Option<ItemStack>,
+    output: Option<ItemStack>,
+    /// What the input can be cut into, in the order the client lists them
+    results: Vec<ItemStack>,
+    /// The index of the result the player clicked on
+    selected: Option<usize>,
+    /// The item the results were looked up for
+    input_item: Option<u16>,
+}
+
+impl Container for StonecutterContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Stonecutter
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Stonecutter"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.input, &mut self.output]
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        vec![self.input.as_ref(), self.output.as_ref()]
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![self.input.as_ref()]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.input]
+    }
+
+    fn craft(&mut self) -> bool {
+        let input_item = self.input.as_ref().map(|input| input.item_id);
+        if input_item != self.input_item {
+            // A different input has different results, so the selection doesn't apply anymore
+            self.input_item = input_item;
+            self.selected = None;
+            self.results = self
+                .input
+                .as_ref()
+                .map(stonecutter_results)
+                .unwrap_or_default();
+        }
+        let old_output = self.output.take();
+        self.output = self
+            .selected
+            .and_then(|index| self.results.get(index))
+            .cloned();
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(1)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        *slot == 0
+    }
+
+    fn recipe_used(&mut self) {
+        remove_one(&mut self.input);
+    }
+
+    fn is_item_allowed(&self, slot: usize, _item: &ItemStack) -> bool {
+        slot != 1
+    }
+
+    fn handle_button_click(&mut self, button: i32) -> bool {
+        let Ok(index) = usize::try_from(button) else {
+            return false;
+        };
+        if index >= self.results.len() {
+            return false;
+        }
+        self.selected = Some(index);
+        self.craft();
+        true
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        vec![WindowProperty::new(
+            window_property::Stonecutter::SelectedRecipe,
+            self.selected.map_or(-1, |index| index as i16),
+        )
+        .into_tuple()]
+    }
+}
+
+#[derive(Default)]
+pub struct LoomContainer {
+    banner: Option<ItemStack>,
+    dye: Option<ItemStack>,
+    /// An optional banner pattern item, which unlocks more patterns
+    pattern: Option<ItemStack>,
+    output: Option<ItemStack>,
+    /// The name of the pattern the player clicked on
+    selected: Option<String>,
+}
+
+impl Container for LoomContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Loom
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Loom"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![
+            &mut self.banner,
+            &mut self.dye,
+            &mut self.pattern,
+            &mut self.output,
+        ]
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        vec![
+            self.banner.as_ref(),
+            self.dye.as_ref(),
+            self.pattern.as_ref(),
+            self.output.as_ref(),
+        ]
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![
+            self.banner.as_ref(),
+            self.dye.as_ref(),
+            self.pattern.as_ref(),
+        ]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.banner, &mut self.dye, &mut self.pattern]
+    }
+
+    fn craft(&mut self) -> bool {
+        let patterns = selectable_patterns(self.pattern.as_ref());
+        if patterns.len() == 1 {
+            // A pattern item with a single pattern doesn't need to be chosen
+            self.selected = patterns.into_iter().next();
+        } else if !self
+            .selected
+            .as_ref()
+            .is_some_and(|selected| patterns.contains(selected))
+        {
+            self.selected = None;
+        }
+
+        let old_output = self.output.take();
+        self.output = match (&self.banner, &self.dye, &self.selected) {
+            (Some(banner), Some(dye), Some(pattern)) => get_loom_result(banner, dye, pattern),
+            _ => None,
+        };
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(3)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..3).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        // The pattern item can be used again
+        remove_one(&mut self.banner);
+        remove_one(&mut self.dye);
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match slot {
+            0 => is_banner(item),
+            1 => dye_color(item).is_some(),
+            2 => is_banner_pattern(item),
+            _ => false,
+        }
+    }
+
+    fn handle_button_click(&mut self, button: i32) -> bool {
+        // TODO: The client only lists the patterns once it knows the banner pattern tags
+        let patterns = selectable_patterns(self.pattern.as_ref());
+        let Some(pattern) = usize::try_from(button)
+            .ok()
+            .and_then(|index| patterns.get(index))
+        else {
+            return false;
+        };
+        self.selected = Some(pattern.clone());
+        self.craft();
+        true
+    }
+
+    fn window_properties(&self) -> Vec<(i16, i16)> {
+        let selected = self.selected.as_ref().and_then(|selected| {
+            selectable_patterns(self.pattern.as_ref())
+                .iter()
+                .position(|pattern| pattern == selected)
+        });
+        vec![WindowProperty::new(
+            window_property::Loom::SelectedPattern,
+            selected.map_or(-1, |index| index as i16),
+        )
+        .into_tuple()]
+    }
+}
+
+#[derive(Default)]
+pub struct GrindstoneContainer {
+    input: Option<ItemStack>,
+    additional: Option<ItemStack>,
+    output: Option<ItemStack>,
+}
+
+impl Container for GrindstoneContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Grindstone
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Repair & Disenchant"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.input, &mut self.additional, &mut self.output]
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        vec![
+            self.input.as_ref(),
+            self.additional.as_ref(),
+            self.output.as_ref(),
+        ]
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![self.input.as_ref(), self.additional.as_ref()]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.input, &mut self.additional]
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        self.output = get_grindstone_result(self.input.as_ref(), self.additional.as_ref());
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..2).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        self.input = None;
+        self.additional = None;
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        slot != 2 && is_grindable(item)
+    }
+
+    fn experience_reward(&self) -> Range<i32> {
+        let experience = get_grindstone_experience(self.input.as_ref(), self.additional.as_ref());
+        experience..experience * 2
+    }
+}
+
+#[derive(Default)]
+pub struct CartographyTableContainer {
+    map: Option<ItemStack>,
+    /// Paper, an empty map or a glass pane
+    additional: Option<ItemStack>,
+    output: Option<ItemStack>,
+}
+
+impl Container for CartographyTableContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::CartographyTable
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Cartography Table"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.map, &mut self.additional, &mut self.output]
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        vec![
+            self.map.as_ref(),
+            self.additional.as_ref(),
+            self.output.as_ref(),
+        ]
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![self.map.as_ref(), self.additional.as_ref()]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![&mut self.map, &mut self.additional]
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        self.output = match (&self.map, &self.additional) {
+            (Some(map), Some(additional)) => get_cartography_result(map, additional),
+            _ => None,
+        };
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..2).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        remove_one(&mut self.map);
+        remove_one(&mut self.additional);
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match slot {
+            0 => is_filled_map(item),
+            1 => is_cartography_material(item),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SmithingTableContainer {
+    /// The template, base item and addition
+    input: [Option<ItemStack>; 3],
+    output: Option<ItemStack>,
+}
+
+impl Container for SmithingTableContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::SmithingTable
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Upgrade Gear"
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter_mut().collect();
+        slots.push(&mut self.output);
+        slots
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        let mut slots: Vec<_> = self.input.iter().map(|slot| slot.as_ref()).collect();
+        slots.push(self.output.as_ref());
+        slots
+    }
+
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        self.input.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.input.iter_mut().collect()
+    }
+
+    fn craft(&mut self) -> bool {
+        let old_output = self.output.take();
+        let [template, base, addition] = &self.input;
+        self.output = get_smithing_result(template.as_ref(), base.as_ref(), addition.as_ref());
+        output_changed(&old_output, &self.output)
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(3)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (0..3).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        self.input.iter_mut().for_each(remove_one);
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        slot < 3 && is_smithing_input(slot, item)
+    }
+}
+
+/// The inventory of a horse, donkey, mule or llama. It belongs to the entity and is saved with it
+pub struct HorseContainer {
+    horse_type: HorseType,
+    saddle: Option<ItemStack>,
+    /// Horse armor or the carpet of a llama
+    armor: Option<ItemStack>,
+    /// Empty if the animal carries no chest
+    chest: Vec<Option<ItemStack>>,
+}
+
+impl HorseContainer {
+    pub fn new(horse_type: HorseType) -> Self {
+        Self {
+            horse_type,
+            saddle: None,
+            armor: None,
+            chest: vec![],
+        }
+    }
+
+    fn has_chest(&self) -> bool {
+        !self.chest.is_empty()
+    }
+
+    /// Gives the animal a chest, returns false if it can't carry one or already has one
+    fn add_chest(&mut self) -> bool {
+        if !self.horse_type.can_have_chest() || self.has_chest() {
+            return false;
+        }
+        self.chest = vec![None; usize::from(self.horse_type.chest_columns()) * 3];
+        true
+    }
+}
+
+impl Container for HorseContainer {
+    fn window_type(&self) -> &'static WindowType {
+        // Horse windows are opened with their own packet, so this is never sent
+        &WindowType::Generic9x1
+    }
+
+    fn window_name(&self) -> &'static str {
+        // The client titles the window with the entity's name
+        ""
+    }
+
+    fn handle_item_change(
+        &mut self,
+        carried_item: &mut Option<ItemStack>,
+        slot: usize,
+        mouse_click: MouseClick,
+        _taking_crafted: bool,
+    ) -> Result<(), InventoryError> {
+        let mut mouse_click = mouse_click;
+        if let Some(carried) = carried_item {
+            if !self.is_item_allowed(slot, &carried.with_count(1)) {
+                return Ok(());
+            }
+            // The saddle and armor slots only hold a single item
+            if slot < 2 && carried.item_count > 1 {
+                if self.all_slots_ref()[slot].is_some() {
+                    return Ok(());
+                }
+                mouse_click = MouseClick::Right;
+            }
+        }
+        let mut all_slots = self.all_slots();
+        let current_slot = all_slots.get_mut(slot).ok_or(InventoryError::InvalidSlot)?;
+        handle_item_change(carried_item, current_slot, mouse_click);
+        Ok(())
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        let mut slots = vec![&mut self.saddle, &mut self.armor];
+        slots.extend(self.chest.iter_mut());
+        slots
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        let mut slots = vec![self.saddle.as_ref(), self.armor.as_ref()];
+        slots.extend(self.chest.iter().map(|slot| slot.as_ref()));
+        slots
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        match slot {
+            0 => item.item_count == 1 && self.horse_type.is_saddle(item),
+            1 => item.item_count == 1 && self.horse_type.is_armor(item),
+            _ => true,
+        }
+    }
+
+    /// Uses the names of vanilla's horse NBT, the chest items are numbered from zero
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut put_item = |name: &str, item: &Option<ItemStack>| {
+            if let Some(item) = item {
+                let mut compound = NbtCompound::new();
+                item.write_item_nbt(&mut compound);
+                nbt.put(name.to_string(), NbtTag::Compound(compound));
+            }
+        };
+        put_item("SaddleItem", &self.saddle);
+        put_item("body_armor_item", &self.armor);
+        if let HorseType::Llama { strength } = self.horse_type {
+            nbt.put("Strength".to_string(), NbtTag::Int(i32::from(strength)));
+        }
+        if self.horse_type.can_have_chest() {
+            nbt.put(
+                "ChestedHorse".to_string(),
+                NbtTag::Byte(i8::from(self.has_chest())),
+            );
+            if self.has_chest() {
+                write_items_nbt(&self.chest, nbt);
+            }
+        }
+    }
+
+    fn read_nbt(&mut self, nbt: &NbtCompound) {
+        let get_item = |name: &str| ItemStack::read_item_nbt(nbt.get_compound(name)?);
+        self.saddle = get_item("SaddleItem");
+        self.armor = get_item("body_armor_item");
+        if let (HorseType::Llama { .. }, Some(strength)) =
+            (self.horse_type, nbt.get_int("Strength"))
+        {
+            let strength = strength.clamp(1, i32::from(MAX_LLAMA_STRENGTH)) as u8;
+            self.horse_type = HorseType::Llama { strength };
+        }
+        self.chest = vec![];
+        if nbt.get_bool("ChestedHorse").unwrap_or(false) && self.add_chest() {
+            read_items_nbt(&mut self.chest, nbt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_world::block::block_registry::get_block;
+    use pumpkin_world::item::{item_registry::get_item, ItemStack};
+
+    use pumpkin_world::block::block_entity::{block_entity_from_nbt, furnace::INPUT_SLOT};
+
+    use super::{EnderChestContainer, FurnaceContainer, OpenContainer, Smelting};
+    use crate::Container;
+
+    #[test]
+    fn containers_remember_their_block() {
+        let location = WorldPosition(Vector3::new(1, 64, -3));
+        let chest = get_block("minecraft:chest").unwrap().clone();
+        let container = OpenContainer::new_without_block(1, Box::new(EnderChestContainer::new()))
+            .with_block(location, chest.clone());
+        assert_eq!(container.get_location(), Some(location));
+        assert_eq!(container.get_block().map(|block| block.id), Some(chest.id));
+        assert!(container.is_location(location));
+        assert!(!container.is_location(WorldPosition(Vector3::new(1, 65, -3))));
+    }
+    #[test]
+    fn ender_chests_are_saved_with_the_player() {
+        let mut ender_chest = EnderChestContainer::new();
+        ender_chest.0[26] = Some(ItemStack::new(5, get_item("minecraft:diamond").unwrap().id));
+        let mut nbt = NbtCompound::new();
+        ender_chest.write_nbt(&mut nbt);
+        assert!(nbt.get_list("Items").is_none());
+        assert_eq!(nbt.get_list("EnderItems").map(Vec::len), Some(1));
+
+        let mut loaded = EnderChestContainer::new();
+        loaded.0[0] = Some(ItemStack::new(1, get_item("minecraft:stone").unwrap().id));
+        loaded.read_nbt(&nbt);
+        assert_eq!(loaded.0, ender_chest.0);
+    }
+
+    #[test]
+    fn furnaces_are_only_copied_back_after_clicks() {
+        let mut block_entity = block_entity_from_nbt("minecraft:furnace", &NbtCompound::new());
+        let mut furnace = FurnaceContainer::<Smelting>::default();
+        assert!(!furnace.load_block_entity(block_entity.as_ref()));
+        assert!(!furnace.block_entity_changed());
+
+        *furnace.all_slots()[INPUT_SLOT] =
+            Some(ItemStack::new(3, get_item("minecraft:beef").unwrap().id));
+        assert!(furnace.block_entity_changed());
+        assert!(furnace.save_block_entity(block_entity.as_mut()));
+        assert!(!furnace.load_block_entity(block_entity.as_ref()));
+        assert!(!furnace.block_entity_changed());
     }
 }
@@ -1,6 +1,7 @@
 use crate::container_click::MouseClick;
-use crate::crafting::check_if_matches_crafting;
+use crate::crafting::{check_if_matches_crafting, use_ingredients};
 use crate::{handle_item_change, Container, InventoryError, WindowType};
+use pumpkin_world::item::item_registry::{get_item_by_id, EquipmentSlot};
 use pumpkin_world::item::ItemStack;
 use std::iter::Chain;
 use std::slice::IterMut;
@@ -9,6 +10,8 @@ pub struct PlayerInventory {
     // Main Inventory + Hotbar
     crafting: [Option<ItemStack>; 4],
     crafting_output: Option<ItemStack>,
+    /// Remainders of the used ingredients which didn't fit back into the crafting grid
+    crafting_remainders: Vec<ItemStack>,
     items: [Option<ItemStack>; 36],
     armor: [Option<ItemStack>; 4],
     offhand: Option<ItemStack>,
@@ -30,10 +33,11 @@ impl PlayerInventory {
 
     pub fn new() -> Self {
         Self {
-            crafting: [None; 4],
+            crafting: [const { None }; 4],
             crafting_output: None,
-            items: [None; 36],
-            armor: [None; 4],
+            crafting_remainders: Vec::new(),
+            items: [const { None }; 36],
+            armor: [const { None }; 4],
             offhand: None,
             // TODO: What when player spawns in with an different index ?
             selected: 0,
@@ -80,6 +84,7 @@ impl PlayerInventory {
         }
 
         Ok(Box::new(match slot {
+            // The offhand takes any item, shields are just the ones the client renders there
             0..=4 | 9..=45 => |_| true,
             5 => |item: &ItemStack| item.is_helmet(),
             6 => |item: &ItemStack| item.is_chestplate(),
@@ -101,6 +106,63 @@ impl PlayerInventory {
             _ => Err(InventoryError::InvalidSlot),
         }
     }
+    /// The armor slot the item is worn in, `None` for items which can't be worn
+    pub fn armor_slot_for(item: &ItemStack) -> Option<usize> {
+        match item.equipment_slot()? {
+            EquipmentSlot::Head => Some(5),
+            EquipmentSlot::Chest => Some(6),
+            EquipmentSlot::Legs => Some(7),
+            EquipmentSlot::Feet => Some(8),
+            EquipmentSlot::MainHand | EquipmentSlot::OffHand | EquipmentSlot::Body => None,
+        }
+    }
+
+    /// The worn armor, from the helmet to the boots
+    pub fn armor_mut(&mut self) -> &mut [Option<ItemStack>; 4] {
+        &mut self.armor
+    }
+
+    /// The items in both hands and the worn armor
+    pub fn equipment_mut(&mut self) -> impl Iterator<Item = &mut Option<ItemStack>> {
+        let held = &mut self.items[self.selected + 36 - 9];
+        std::iter::once(held)
+            .chain(std::iter::once(&mut self.offhand))
+            .chain(self.armor.iter_mut())
+    }
+
+    /// Whether the slot holds armor with Curse of Binding, which only players in creative can take off
+    pub fn is_bound_armor(&self, slot: usize) -> bool {
+        (5..=8).contains(&slot)
+            && self.armor[slot - 5]
+                .as_ref()
+                .is_some_and(ItemStack::has_binding_curse)
+    }
+
+    /// Swaps the armor in the hand with the worn one, like right-clicking armor does.
+    /// Returns whether anything was equipped
+    pub fn equip_from_hand(&mut self, offhand: bool, can_remove_bound: bool) -> bool {
+        let hand = if offhand {
+            &mut self.offhand
+        } else {
+            &mut self.items[self.selected + 36 - 9]
+        };
+        let Some(item) = hand.as_ref() else {
+            return false;
+        };
+        let swappable = get_item_by_id(item.item_id)
+            .and_then(|item| item.components.equippable.as_ref())
+            .is_some_and(|equippable| equippable.swappable);
+        let Some(slot) = Self::armor_slot_for(item).filter(|_| swappable) else {
+            return false;
+        };
+        let armor = &mut self.armor[slot - 5];
+        if armor.as_ref().is_some_and(ItemStack::has_binding_curse) && !can_remove_bound {
+            return false;
+        }
+        std::mem::swap(hand, armor);
+        true
+    }
+
     pub fn set_selected(&mut self, slot: usize) {
         assert!((0..9).contains(&slot));
         self.selected = slot;
@@ -116,6 +178,33 @@ impl PlayerInventory {
         &mut self.items[self.selected + 36 - 9]
     }
 
+    /// The item in the main hand or the offhand, depending on which hand a packet names
+    pub fn hand_item_mut(&mut self, offhand: bool) -> &mut Option<ItemStack> {
+        if offhand {
+            &mut self.offhand
+        } else {
+            self.held_item_mut()
+        }
+    }
+
+    /// The slot of the first item the predicate accepts, the offhand and the held item come before
+    /// the rest of the inventory like the arrows vanilla's `Player.getProjectile` finds
+    pub fn find_item_mut(
+        &mut self,
+        predicate: impl Fn(&ItemStack) -> bool,
+    ) -> Option<&mut Option<ItemStack>> {
+        let held = self.selected + 36 - 9;
+        if self.offhand.as_ref().is_some_and(&predicate) {
+            return Some(&mut self.offhand);
+        }
+        if self.items[held].as_ref().is_some_and(&predicate) {
+            return Some(&mut self.items[held]);
+        }
+        self.items
+            .iter_mut()
+            .find(|slot| slot.as_ref().is_some_and(&predicate))
+    }
+
     pub fn get_slot_with_item(&self, item_id: u16) -> Option<usize> {
         for slot in 9..=44 {
             match &self.items[slot - 9] {
@@ -154,6 +243,16 @@ impl PlayerInventory {
         slots
     }
 
+    /// The items the player carries in the main inventory, the armor slots and the offhand,
+    /// without the crafting grid
+    pub fn stored_items(&self) -> impl Iterator<Item = &ItemStack> {
+        self.items
+            .iter()
+            .chain(&self.armor)
+            .chain(std::iter::once(&self.offhand))
+            .flatten()
+    }
+
     pub fn slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
         let mut slots = vec![&mut self.crafting_output];
         slots.extend(self.crafting.iter_mut());
@@ -173,6 +272,51 @@ impl PlayerInventory {
         let (items, hotbar) = self.items.split_at_mut(27);
         hotbar.iter_mut().chain(items)
     }
+
+    /// Puts the stack into the inventory like vanilla does when picking up items. Matching stacks are filled up first,
+    /// starting with the held item and the offhand, then empty hotbar slots and the main inventory are used.
+    /// Returns what didn't fit
+    pub fn insert_stack(&mut self, mut stack: ItemStack) -> Option<ItemStack> {
+        let held = self.selected + 36 - 9;
+        if merge_into(&mut self.items[held], &mut stack)
+            || merge_into(&mut self.offhand, &mut stack)
+        {
+            return None;
+        }
+        // The hotbar is at the end of the items
+        for slot in (27..36).chain(0..27).filter(|slot| *slot != held) {
+            if merge_into(&mut self.items[slot], &mut stack) {
+                return None;
+            }
+        }
+        let max_stack_size = stack.max_stack_size();
+        for slot in self.slots_with_hotbar_first() {
+            if slot.is_none() {
+                let moved = stack.item_count.min(max_stack_size);
+                *slot = Some(stack.with_count(moved));
+                stack.item_count -= moved;
+                if stack.item_count == 0 {
+                    return None;
+                }
+            }
+        }
+        Some(stack)
+    }
+}
+
+/// Moves as much of the stack onto the stack in the slot as fits, returns whether all of it was moved
+fn merge_into(slot: &mut Option<ItemStack>, stack: &mut ItemStack) -> bool {
+    let Some(existing) = slot.as_mut() else {
+        return false;
+    };
+    let max_stack_size = stack.max_stack_size();
+    if !existing.is_stackable_with(stack) || existing.item_count >= max_stack_size {
+        return false;
+    }
+    let moved = stack.item_count.min(max_stack_size - existing.item_count);
+    existing.item_count += moved;
+    stack.item_count -= moved;
+    stack.item_count == 0
 }
 
 impl Container for PlayerInventory {
@@ -229,9 +373,9 @@ impl Container for PlayerInventory {
     }
 
     fn craft(&mut self) -> bool {
-        let v1 = [self.crafting[0], self.crafting[1], None];
-        let v2 = [self.crafting[2], self.crafting[3], None];
-        let v3 = [None; 3];
+        let v1 = [self.crafting[0].clone(), self.crafting[1].clone(), None];
+        let v2 = [self.crafting[2].clone(), self.crafting[3].clone(), None];
+        let v3 = [const { None }; 3];
         let together = [v1, v2, v3];
 
         self.crafting_output = check_if_matches_crafting(together);
@@ -245,4 +389,132 @@ impl Container for PlayerInventory {
     fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
         (1..=4).contains(slot)
     }
+
+    fn recipe_used(&mut self) {
+        let leftovers = use_ingredients(&mut self.crafting);
+        self.crafting_remainders.extend(leftovers);
+    }
+
+    fn take_crafting_remainders(&mut self) -> Vec<ItemStack> {
+        std::mem::take(&mut self.crafting_remainders)
+    }
+
+    fn is_item_allowed(&self, slot: usize, item: &ItemStack) -> bool {
+        // Nothing can be put into the crafting output
+        slot != 0
+            && self
+                .slot_condition(slot)
+                .is_ok_and(|slot_condition| slot_condition(item))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::PlayerInventory;
+    use crate::Container;
+
+    #[test]
+    fn pickups_fill_the_hands_first() {
+        let mut inventory = PlayerInventory::new();
+        inventory.set_selected(2);
+        // The main inventory comes before the hotbar slots
+        inventory.items[0] = Some(ItemStack::of("minecraft:stone", 10));
+        inventory.items[29] = Some(ItemStack::of("minecraft:stone", 60));
+        inventory.offhand = Some(ItemStack::of("minecraft:stone", 50));
+
+        assert_eq!(
+            inventory.insert_stack(ItemStack::of("minecraft:stone", 20)),
+            None
+        );
+        assert_eq!(
+            inventory.items[29],
+            Some(ItemStack::of("minecraft:stone", 64))
+        );
+        assert_eq!(
+            inventory.offhand,
+            Some(ItemStack::of("minecraft:stone", 64))
+        );
+        assert_eq!(
+            inventory.items[0],
+            Some(ItemStack::of("minecraft:stone", 12))
+        );
+    }
+
+    #[test]
+    fn pickups_prefer_empty_hotbar_slots() {
+        let mut inventory = PlayerInventory::new();
+        inventory.items[27] = Some(ItemStack::of("minecraft:dirt", 1));
+
+        assert_eq!(
+            inventory.insert_stack(ItemStack::of("minecraft:stone", 70)),
+            None
+        );
+        assert_eq!(
+            inventory.items[28],
+            Some(ItemStack::of("minecraft:stone", 64))
+        );
+        assert_eq!(
+            inventory.items[29],
+            Some(ItemStack::of("minecraft:stone", 6))
+        );
+
+        // What doesn't fit is left over
+        let mut inventory = PlayerInventory::new();
+        for slot in &mut inventory.items {
+            *slot = Some(ItemStack::of("minecraft:stone", 63));
+        }
+        assert_eq!(
+            inventory.insert_stack(ItemStack::of("minecraft:stone", 40)),
+            Some(ItemStack::of("minecraft:stone", 4))
+        );
+    }
+
+    #[test]
+    fn armor_slots_only_take_matching_armor() {
+        let inventory = PlayerInventory::new();
+        let helmet = ItemStack::of("minecraft:iron_helmet", 1);
+        assert!(inventory.is_item_allowed(5, &helmet));
+        assert!(!inventory.is_item_allowed(6, &helmet));
+        assert!(!inventory.is_item_allowed(5, &ItemStack::of("minecraft:stone", 1)));
+        assert!(inventory.is_item_allowed(45, &ItemStack::of("minecraft:stone", 1)));
+        // Nothing can be put into the crafting output
+        assert!(!inventory.is_item_allowed(0, &ItemStack::of("minecraft:stone", 1)));
+    }
+
+    #[test]
+    fn bound_armor_stays_on() {
+        let mut inventory = PlayerInventory::new();
+        let mut cursed = ItemStack::of("minecraft:iron_helmet", 1);
+        cursed.components.enchantments = vec![("binding_curse".to_string(), 1)];
+        inventory.armor[0] = Some(cursed.clone());
+        *inventory.held_item_mut() = Some(ItemStack::of("minecraft:diamond_helmet", 1));
+        assert!(inventory.is_bound_armor(5));
+        assert!(!inventory.is_bound_armor(6));
+
+        assert!(!inventory.equip_from_hand(false, false));
+        assert_eq!(inventory.armor[0], Some(cursed.clone()));
+        // Players in creative can take it off
+        assert!(inventory.equip_from_hand(false, true));
+        assert_eq!(
+            inventory.armor[0],
+            Some(ItemStack::of("minecraft:diamond_helmet", 1))
+        );
+        assert_eq!(inventory.held_item(), Some(&cursed));
+    }
+
+    #[test]
+    fn only_armor_is_equipped_from_the_hand() {
+        let mut inventory = PlayerInventory::new();
+        inventory.offhand = Some(ItemStack::of("minecraft:stone", 1));
+        assert!(!inventory.equip_from_hand(true, false));
+        inventory.offhand = Some(ItemStack::of("minecraft:golden_boots", 1));
+        assert!(inventory.equip_from_hand(true, false));
+        assert_eq!(
+            inventory.armor[3],
+            Some(ItemStack::of("minecraft:golden_boots", 1))
+        );
+        assert_eq!(inventory.offhand, None);
+    }
 }
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use num_derive::FromPrimitive;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+#[derive(Debug, FromPrimitive, Clone, Copy, Eq, PartialEq)]
+pub enum RecipeBookType {
+    Crafting,
+    Furnace,
+    BlastFurnace,
+    Smoker,
+}
+
+impl RecipeBookType {
+    const ALL: [Self; 4] = [
+        Self::Crafting,
+        Self::Furnace,
+        Self::BlastFurnace,
+        Self::Smoker,
+    ];
+
+    /// The vanilla player data keys for whether the book is open and filtering
+    const fn nbt_keys(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Crafting => ("isGuiOpen", "isFilteringCraftable"),
+            Self::Furnace => ("isFurnaceGuiOpen", "isFurnaceFilteringCraftable"),
+            Self::BlastFurnace => (
+                "isBlastingFurnaceGuiOpen",
+                "isBlastingFurnaceFilteringCraftable",
+            ),
+            Self::Smoker => ("isSmokerGuiOpen", "isSmokerFilteringCraftable"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RecipeBookSettings {
+    pub open: bool,
+    /// Only shows recipes the player has the ingredients for
+    pub filtering: bool,
+}
+
+/// The recipes a player has unlocked and how their recipe books look.
+/// Recipes are identified by their index in `RECIPES`
+#[derive(Debug, Default)]
+pub struct RecipeBook {
+    known: BTreeSet<usize>,
+    /// Recipes which are highlighted until the player looks at them
+    highlighted: BTreeSet<usize>,
+    settings: [RecipeBookSettings; 4],
+}
+
+impl RecipeBook {
+    pub fn is_known(&self, recipe: usize) -> bool {
+        self.known.contains(&recipe)
+    }
+
+    pub fn is_highlighted(&self, recipe: usize) -> bool {
+        self.highlighted.contains(&recipe)
+    }
+
+    pub fn known(&self) -> impl Iterator<Item = usize> + '_ {
+        self.known.iter().copied()
+    }
+
+    /// Unlocks the recipes and highlights the new ones, returns the recipes which were not known before
+    pub fn unlock(&mut self, recipes: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        let unlocked: Vec<usize> = recipes
+            .into_iter()
+            .filter(|recipe| self.known.insert(*recipe))
+            .collect();
+        self.highlighted.extend(&unlocked);
+        unlocked
+    }
+
+    pub fn remove_highlight(&mut self, recipe: usize) {
+        self.highlighted.remove(&recipe);
+    }
+
+    pub fn settings(&self, book: RecipeBookType) -> RecipeBookSettings {
+        self.settings[book as usize]
+    }
+
+    pub fn set_settings(&mut self, book: RecipeBookType, settings: RecipeBookSettings) {
+        self.settings[book as usize] = settings;
+    }
+
+    /// Whether each book is open and filtering, in the order the client expects
+    pub fn all_settings(&self) -> [(bool, bool); 4] {
+        self.settings
+            .map(|settings| (settings.open, settings.filtering))
+    }
+
+    /// Writes the recipe book in the format of the vanilla `recipeBook` player data
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        // Vanilla stores recipe names, but our recipes have none so we store their index
+        nbt.put(
+            "recipes".to_string(),
+            NbtTag::IntArray(self.known.iter().map(|recipe| *recipe as i32).collect()),
+        );
+        nbt.put(
+            "toBeDisplayed".to_string(),
+            NbtTag::IntArray(
+                self.highlighted
+                    .iter()
+                    .map(|recipe| *recipe as i32)
+                    .collect(),
+            ),
+        );
+        for book in RecipeBookType::ALL {
+            let (open_key, filtering_key) = book.nbt_keys();
+            let settings = self.settings(book);
+            nbt.put(open_key.to_string(), NbtTag::Byte(i8::from(settings.open)));
+            nbt.put(
+                filtering_key.to_string(),
+                NbtTag::Byte(i8::from(settings.filtering)),
+            );
+        }
+    }
+
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        let read_recipes = |key| -> BTreeSet<usize> {
+            nbt.get_int_array(key)
+                .into_iter()
+                .flatten()
+                .filter_map(|recipe| usize::try_from(*recipe).ok())
+                .collect()
+        };
+        self.known = read_recipes("recipes");
+        self.highlighted = read_recipes("toBeDisplayed");
+        for book in RecipeBookType::ALL {
+            let (open_key, filtering_key) = book.nbt_keys();
+            self.set_settings(
+                book,
+                RecipeBookSettings {
+                    open: nbt.get_bool(open_key).unwrap_or(false),
+                    filtering: nbt.get_bool(filtering_key).unwrap_or(false),
+                },
+            );
+        }
+    }
+}
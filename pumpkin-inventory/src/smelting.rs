@@ -0,0 +1,132 @@
+use crate::crafting::{check_ingredient_type, ingredient_slot_check};
+use pumpkin_registry::{IngredientType, RecipeResult, RecipeType, RECIPES};
+use pumpkin_world::block::block_entity::CookingRecipes;
+use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::ItemStack;
+
+/// Burn times in ticks, entries starting with `#` are item tags
+const FUELS: &[(&str, u16)] = &[
+    ("minecraft:lava_bucket", 20000),
+    ("minecraft:coal_block", 16000),
+    ("minecraft:dried_kelp_block", 4001),
+    ("minecraft:blaze_rod", 2400),
+    ("minecraft:coal", 1600),
+    ("minecraft:charcoal", 1600),
+    ("#boats", 1200),
+    ("#chest_boats", 1200),
+    ("#hanging_signs", 800),
+    ("#logs_that_burn", 300),
+    ("#bamboo_blocks", 300),
+    ("#planks", 300),
+    ("minecraft:bamboo_mosaic", 300),
+    ("#wooden_stairs", 300),
+    ("minecraft:bamboo_mosaic_stairs", 300),
+    ("#wooden_trapdoors", 300),
+    ("#wooden_pressure_plates", 300),
+    ("#wooden_fences", 300),
+    ("#fence_gates", 300),
+    ("#banners", 300),
+    ("minecraft:note_block", 300),
+    ("minecraft:bookshelf", 300),
+    ("minecraft:chiseled_bookshelf", 300),
+    ("minecraft:lectern", 300),
+    ("minecraft:jukebox", 300),
+    ("minecraft:chest", 300),
+    ("minecraft:trapped_chest", 300),
+    ("minecraft:crafting_table", 300),
+    ("minecraft:daylight_detector", 300),
+    ("minecraft:bow", 300),
+    ("minecraft:crossbow", 300),
+    ("minecraft:fishing_rod", 300),
+    ("minecraft:ladder", 300),
+    ("minecraft:mangrove_roots", 300),
+    ("#signs", 200),
+    ("#wooden_doors", 200),
+    ("minecraft:wooden_shovel", 200),
+    ("minecraft:wooden_sword", 200),
+    ("minecraft:wooden_hoe", 200),
+    ("minecraft:wooden_axe", 200),
+    ("minecraft:wooden_pickaxe", 200),
+    ("#wooden_slabs", 150),
+    ("minecraft:bamboo_mosaic_slab", 150),
+    ("#wool", 100),
+    ("#wooden_buttons", 100),
+    ("#saplings", 100),
+    ("minecraft:stick", 100),
+    ("minecraft:bowl", 100),
+    ("minecraft:dead_bush", 100),
+    ("minecraft:azalea", 100),
+    ("minecraft:flowering_azalea", 100),
+    ("#wool_carpets", 67),
+    ("minecraft:bamboo", 50),
+    ("minecraft:scaffolding", 50),
+];
+
+fn to_ingredient_type(name: &str) -> IngredientType {
+    match name.strip_prefix('#') {
+        Some(tag) => IngredientType::Tag(tag.to_string()),
+        None => IngredientType::Item(name.to_string()),
+    }
+}
+
+/// Returns how many ticks the item keeps a furnace burning, or `None` if it is not a fuel
+pub fn get_fuel_burn_time(item: &ItemStack) -> Option<u16> {
+    // Nether wood is part of some of the wooden tags, but does not burn
+    if check_ingredient_type(&to_ingredient_type("#non_flammable_wood"), item) {
+        return None;
+    }
+    FUELS
+        .iter()
+        .find(|(name, _)| check_ingredient_type(&to_ingredient_type(name), item))
+        .map(|(_, burn_time)| *burn_time)
+}
+
+/// Finds the cooking recipe of the given type for the input, like vanilla's `RecipeManager`
+/// lookups for furnaces, blast furnaces, smokers and campfires. Returns the resulting item
+/// together with the cooking time in ticks and the experience it gives
+pub fn cooking_result(recipe_type: RecipeType, input: &ItemStack) -> Option<(ItemStack, u16, f32)> {
+    let recipe = RECIPES.iter().find(|recipe| {
+        recipe.recipe_type == recipe_type
+            && recipe
+                .ingredients()
+                .next()
+                .is_some_and(|ingredient| ingredient_slot_check(ingredient, input))
+    })?;
+    let result = match recipe.result() {
+        RecipeResult::Single { id, .. } => ItemStack::new(1, get_item(id)?.id),
+        RecipeResult::Many { id, count, .. } => ItemStack::new(*count, get_item(id)?.id),
+        RecipeResult::Special => return None,
+    };
+    Some((
+        result,
+        recipe.cooking_time()?,
+        recipe.experience().unwrap_or(0.0),
+    ))
+}
+
+/// What the input turns into in a furnace
+pub fn smelting_result(input: &ItemStack) -> Option<(ItemStack, u16, f32)> {
+    cooking_result(RecipeType::Smelting, input)
+}
+
+/// The recipes furnace, blast furnace and smoker block entities cook with
+pub struct FurnaceRecipes;
+
+impl CookingRecipes for FurnaceRecipes {
+    fn cooking_result(
+        &self,
+        block_entity: &str,
+        input: &ItemStack,
+    ) -> Option<(ItemStack, u16, f32)> {
+        let recipe_type = match block_entity {
+            "minecraft:blast_furnace" => RecipeType::Blasting,
+            "minecraft:smoker" => RecipeType::Smoking,
+            _ => RecipeType::Smelting,
+        };
+        cooking_result(recipe_type, input)
+    }
+
+    fn fuel_burn_time(&self, fuel: &ItemStack) -> Option<u16> {
+        get_fuel_burn_time(fuel)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::crafting::ingredient_slot_check;
+use pumpkin_registry::{IngredientSlot, Recipe, RecipeResult, RecipeType, SmithingType, RECIPES};
+use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::ItemStack;
+
+/// The template, base and addition a smithing recipe takes, in the order of the smithing table slots
+fn smithing_inputs(recipe: &Recipe) -> Option<&[Option<IngredientSlot>; 3]> {
+    if !matches!(recipe.recipe_type, RecipeType::Smithing(_)) {
+        return None;
+    }
+    recipe.pattern().first().map(|pattern| &pattern[0])
+}
+
+fn matches_input(ingredient: &Option<IngredientSlot>, item: Option<&ItemStack>) -> bool {
+    match (ingredient, item) {
+        (Some(ingredient), Some(item)) => ingredient_slot_check(ingredient, item),
+        (None, None) => true,
+        (Some(_), None) | (None, Some(_)) => false,
+    }
+}
+
+/// Whether any smithing recipe takes the item in the given input slot
+pub fn is_smithing_input(slot: usize, item: &ItemStack) -> bool {
+    RECIPES.iter().filter_map(smithing_inputs).any(|inputs| {
+        inputs
+            .get(slot)
+            .is_some_and(|ingredient| matches_input(ingredient, Some(item)))
+    })
+}
+
+/// Finds the smithing recipe for the inputs and applies it to the base item,
+/// returns `None` if no recipe matches
+pub fn get_smithing_result(
+    template: Option<&ItemStack>,
+    base: Option<&ItemStack>,
+    addition: Option<&ItemStack>,
+) -> Option<ItemStack> {
+    let recipe = RECIPES.iter().find(|recipe| {
+        smithing_inputs(recipe).is_some_and(|inputs| {
+            inputs
+                .iter()
+                .zip([template, base, addition])
+                .all(|(ingredient, item)| matches_input(ingredient, item))
+        })
+    })?;
+    match (recipe.recipe_type, recipe.result()) {
+        // The upgraded item keeps the enchantments, name and damage of the base
+        (RecipeType::Smithing(SmithingType::Transform), RecipeResult::Single { id, .. }) => {
+            let mut result = base?.with_count(1);
+            result.item_id = get_item(id)?.id;
+            Some(result)
+        }
+        (RecipeType::Smithing(SmithingType::Transform), RecipeResult::Many { id, count, .. }) => {
+            let mut result = base?.with_count(*count);
+            result.item_id = get_item(id)?.id;
+            Some(result)
+        }
+        // TODO: Armor trims need the trim component and the trim registries synced to the client
+        _ => None,
+    }
+}
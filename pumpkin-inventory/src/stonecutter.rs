@@ -0,0 +1,28 @@
+use crate::crafting::ingredient_slot_check;
+use pumpkin_registry::{Recipe, RecipeResult, RecipeType, RECIPES};
+use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::ItemStack;
+
+/// All stonecutting recipes, the client lists them in the same order
+pub fn stonecutter_recipes() -> impl Iterator<Item = &'static Recipe> {
+    RECIPES
+        .iter()
+        .filter(|recipe| recipe.recipe_type == RecipeType::StoneCutting)
+}
+
+/// The items the input can be cut into, the index of a result is the button the player clicks for it
+pub fn stonecutter_results(input: &ItemStack) -> Vec<ItemStack> {
+    stonecutter_recipes()
+        .filter(|recipe| {
+            recipe
+                .ingredients()
+                .next()
+                .is_some_and(|ingredient| ingredient_slot_check(ingredient, input))
+        })
+        .filter_map(|recipe| match recipe.result() {
+            RecipeResult::Single { id, .. } => Some(ItemStack::new(1, get_item(id)?.id)),
+            RecipeResult::Many { id, count, .. } => Some(ItemStack::new(*count, get_item(id)?.id)),
+            RecipeResult::Special => None,
+        })
+        .collect()
+}
@@ -0,0 +1,252 @@
+use crate::container_click::{self, Click, ClickType};
+use crate::open_container::output_changed;
+use crate::{Container, WindowType};
+use pumpkin_world::item::ItemStack;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Called when a player clicks the slot of a virtual container, it may change the container's items
+pub type ClickCallback = Arc<dyn Fn(&mut VirtualContainer, &VirtualClick) + Send + Sync>;
+
+/// A click on a slot of a virtual container
+pub struct VirtualClick<'a> {
+    /// The entity id of the player who clicked
+    pub player_id: i32,
+    pub slot: usize,
+    pub click_type: &'a ClickType,
+}
+
+/// A chest-like menu made by a plugin, e.g. a server selector, opened with `Player::open_custom_container`.
+/// Players can't move items in or out of it, clicking a slot calls its callback instead.
+/// Changes to the items are sent to the viewers on the next tick
+pub struct VirtualContainer {
+    window_type: &'static WindowType,
+    title: &'static str,
+    slots: Vec<Option<ItemStack>>,
+    callbacks: HashMap<usize, ClickCallback>,
+    /// The items the viewers have seen last
+    synced_slots: Vec<Option<ItemStack>>,
+}
+
+impl VirtualContainer {
+    /// Starts a menu with 9 slots per row, it has between 1 and 6 rows
+    pub fn builder(rows: u8, title: &'static str) -> VirtualContainerBuilder {
+        let rows = rows.clamp(1, 6);
+        VirtualContainerBuilder {
+            rows,
+            title,
+            slots: vec![None; usize::from(rows) * 9],
+            callbacks: HashMap::new(),
+        }
+    }
+
+    pub fn get_item(&self, slot: usize) -> Option<&ItemStack> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// Replaces the item in the slot, slots outside of the menu are ignored
+    pub fn set_item(&mut self, slot: usize, item: Option<ItemStack>) {
+        if let Some(current) = self.slots.get_mut(slot) {
+            *current = item;
+        }
+    }
+
+    pub fn set_callback(&mut self, slot: usize, callback: ClickCallback) {
+        self.callbacks.insert(slot, callback);
+    }
+}
+
+pub struct VirtualContainerBuilder {
+    rows: u8,
+    title: &'static str,
+    slots: Vec<Option<ItemStack>>,
+    callbacks: HashMap<usize, ClickCallback>,
+}
+
+impl VirtualContainerBuilder {
+    /// Puts an item into the slot which does nothing when clicked
+    pub fn with_item(mut self, slot: usize, item: ItemStack) -> Self {
+        if let Some(current) = self.slots.get_mut(slot) {
+            *current = Some(item);
+        }
+        self
+    }
+
+    /// Puts an item into the slot which calls the callback when clicked
+    pub fn with_button(
+        self,
+        slot: usize,
+        item: ItemStack,
+        callback: impl Fn(&mut VirtualContainer, &VirtualClick) + Send + Sync + 'static,
+    ) -> Self {
+        self.with_item(slot, item).on_click(slot, callback)
+    }
+
+    /// Calls the callback when the slot is clicked, even if it is empty
+    pub fn on_click(
+        mut self,
+        slot: usize,
+        callback: impl Fn(&mut VirtualContainer, &VirtualClick) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.insert(slot, Arc::new(callback));
+        self
+    }
+
+    pub fn build(self) -> VirtualContainer {
+        let window_type = match self.rows {
+            1 => &WindowType::Generic9x1,
+            2 => &WindowType::Generic9x2,
+            3 => &WindowType::Generic9x3,
+            4 => &WindowType::Generic9x4,
+            5 => &WindowType::Generic9x5,
+            _ => &WindowType::Generic9x6,
+        };
+        VirtualContainer {
+            window_type,
+            title: self.title,
+            synced_slots: self.slots.clone(),
+            slots: self.slots,
+            callbacks: self.callbacks,
+        }
+    }
+}
+
+impl Container for VirtualContainer {
+    fn window_type(&self) -> &'static WindowType {
+        self.window_type
+    }
+
+    fn window_name(&self) -> &'static str {
+        self.title
+    }
+
+    fn all_slots(&mut self) -> Vec<&mut Option<ItemStack>> {
+        self.slots.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Vec<Option<&ItemStack>> {
+        self.slots.iter().map(|slot| slot.as_ref()).collect()
+    }
+
+    // The items belong to the menu, so they are neither collected nor given to the player on closing
+    fn all_combinable_slots(&self) -> Vec<Option<&ItemStack>> {
+        vec![]
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Vec<&mut Option<ItemStack>> {
+        vec![]
+    }
+
+    fn intercept_click(&mut self, player_id: i32, click: &Click) -> bool {
+        if let container_click::Slot::Normal(slot) = click.slot {
+            if slot < self.slots.len() {
+                // Slots only get added to drags, which aren't clicks on their own
+                if !matches!(click.click_type, ClickType::MouseDrag { .. }) {
+                    if let Some(callback) = self.callbacks.get(&slot).cloned() {
+                        let click = VirtualClick {
+                            player_id,
+                            slot,
+                            click_type: &click.click_type,
+                        };
+                        callback(self, &click);
+                    }
+                }
+                return true;
+            }
+        }
+        // Clicks in the player's inventory are fine as long as they can't move items into the menu
+        matches!(
+            click.click_type,
+            ClickType::ShiftClick | ClickType::DoubleClick | ClickType::MouseDrag { .. }
+        )
+    }
+
+    fn is_item_allowed(&self, _slot: usize, _item: &ItemStack) -> bool {
+        false
+    }
+
+    fn tick(&mut self) -> bool {
+        let changed = self
+            .slots
+            .iter()
+            .zip(&self.synced_slots)
+            .any(|(new, old)| output_changed(old, new));
+        if changed {
+            self.synced_slots.clone_from(&self.slots);
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use pumpkin_world::item::ItemStack;
+
+    use super::VirtualContainer;
+    use crate::container_click::{Click, ClickType, MouseClick, Slot};
+    use crate::{Container, WindowType};
+
+    fn click(slot: usize, click_type: ClickType) -> Click {
+        Click {
+            slot: Slot::Normal(slot),
+            click_type,
+        }
+    }
+
+    #[test]
+    fn menus_have_one_to_six_rows() {
+        let menu = VirtualContainer::builder(0, "Menu").build();
+        assert!(*menu.window_type() == WindowType::Generic9x1);
+        assert_eq!(menu.all_slots_ref().len(), 9);
+        let menu = VirtualContainer::builder(9, "Menu")
+            .with_item(53, ItemStack::of("compass", 1))
+            .with_item(54, ItemStack::of("compass", 1))
+            .build();
+        assert!(*menu.window_type() == WindowType::Generic9x6);
+        assert_eq!(menu.all_slots_ref().len(), 54);
+        assert_eq!(menu.get_item(53), Some(&ItemStack::of("compass", 1)));
+    }
+
+    #[test]
+    fn buttons_call_their_callback() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let mut menu = VirtualContainer::builder(1, "Menu")
+            .with_button(4, ItemStack::of("compass", 1), move |menu, click| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                menu.set_item(click.slot, Some(ItemStack::of("compass", 2)));
+            })
+            .build();
+
+        assert!(menu.intercept_click(1, &click(4, ClickType::MouseClick(MouseClick::Left))));
+        assert_eq!(clicks.load(Ordering::Relaxed), 1);
+        assert_eq!(menu.get_item(4), Some(&ItemStack::of("compass", 2)));
+        // Slots without a button are still protected
+        assert!(menu.intercept_click(1, &click(0, ClickType::ShiftClick)));
+        assert_eq!(clicks.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn items_cant_be_moved_in_from_the_inventory() {
+        let mut menu = VirtualContainer::builder(1, "Menu").build();
+        // Slot 9 is the first slot of the player's inventory
+        assert!(menu.intercept_click(1, &click(9, ClickType::ShiftClick)));
+        assert!(menu.intercept_click(1, &click(9, ClickType::DoubleClick)));
+        assert!(!menu.intercept_click(1, &click(9, ClickType::MouseClick(MouseClick::Left))));
+        assert!(!menu.is_item_allowed(0, &ItemStack::of("compass", 1)));
+    }
+
+    #[test]
+    fn changes_are_synced_once() {
+        let mut menu = VirtualContainer::builder(1, "Menu").build();
+        assert!(!menu.tick());
+        menu.set_item(0, Some(ItemStack::of("compass", 1)));
+        assert!(menu.tick());
+        assert!(!menu.tick());
+    }
+}
@@ -65,6 +65,52 @@ impl NbtCompound {
         }
     }
 
+    /// Like [`Self::put`], but replaces the tag if the name is already taken
+    pub fn insert(&mut self, name: String, value: impl Into<NbtTag>) {
+        match self.get_mut(&name) {
+            Some(tag) => *tag = value.into(),
+            None => self.child_tags.push((name, value.into())),
+        }
+    }
+
+    /// Copies the tags of the other compound into this one, compounds in both are merged too.
+    /// Like vanilla's `CompoundTag.merge`
+    pub fn merge(&mut self, other: &NbtCompound) {
+        for (name, tag) in &other.child_tags {
+            match (self.get_mut(name), tag) {
+                (Some(NbtTag::Compound(compound)), NbtTag::Compound(other)) => {
+                    compound.merge(other);
+                }
+                _ => self.insert(name.clone(), tag.clone()),
+            }
+        }
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut NbtTag> {
+        self.child_tags
+            .iter_mut()
+            .find(|(key, _)| key.as_str() == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Removes the tag, returning it if there was one
+    pub fn remove(&mut self, name: &str) -> Option<NbtTag> {
+        let index = self
+            .child_tags
+            .iter()
+            .position(|(key, _)| key.as_str() == name)?;
+        Some(self.child_tags.remove(index).1)
+    }
+
+    /// Whether the compound has everything the expected one has, it may have more entries and its
+    /// lists more elements, like vanilla's `NbtUtils.compareNbt` with partial lists
+    pub fn contains(&self, expected: &NbtCompound) -> bool {
+        expected.child_tags.iter().all(|(name, expected)| {
+            self.get(name)
+                .is_some_and(|actual| tag_contains(actual, expected))
+        })
+    }
+
     pub fn get_byte(&self, name: &str) -> Option<i8> {
         self.get(name).and_then(|tag| tag.extract_byte())
     }
@@ -140,6 +186,17 @@ impl FromIterator<(String, NbtTag)> for NbtCompound {
     }
 }
 
+fn tag_contains(actual: &NbtTag, expected: &NbtTag) -> bool {
+    match (actual, expected) {
+        (NbtTag::Compound(actual), NbtTag::Compound(expected)) => actual.contains(expected),
+        // Empty lists only match empty lists
+        (NbtTag::List(actual), NbtTag::List(expected)) if !expected.is_empty() => expected
+            .iter()
+            .all(|expected| actual.iter().any(|actual| tag_contains(actual, expected))),
+        _ => actual == expected,
+    }
+}
+
 impl IntoIterator for NbtCompound {
     type Item = (String, NbtTag);
     type IntoIter = IntoIter<(String, NbtTag)>;
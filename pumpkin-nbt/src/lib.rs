@@ -14,7 +14,9 @@ use thiserror::Error;
 
 pub mod compound;
 pub mod deserializer;
+pub mod path;
 pub mod serializer;
+pub mod snbt;
 pub mod tag;
 
 // This NBT crate is inspired from CrabNBT
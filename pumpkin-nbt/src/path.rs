@@ -0,0 +1,464 @@
+//! Paths to tags inside NBT like commands write them, e.g. `Inventory[{Slot:0b}].components`
+
+use std::fmt::Display;
+
+use thiserror::Error;
+
+use crate::{
+    compound::NbtCompound,
+    snbt::{SnbtError, SnbtReader},
+    tag::NbtTag,
+};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NbtPathError {
+    #[error("Invalid NBT path element at position {0}")]
+    InvalidNode(usize),
+    #[error("{0}")]
+    Snbt(#[from] SnbtError),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum NbtPathNode {
+    /// `{...}`, the root if it contains the compound
+    MatchRoot(NbtCompound),
+    /// `name`
+    Key(String),
+    /// `name{...}`, the named tag if it contains the compound
+    MatchKey(String, NbtCompound),
+    /// `[index]`, negative indices count from the end
+    Index(i32),
+    /// `[]`
+    AllElements,
+    /// `[{...}]`, the elements which contain the compound
+    MatchElement(NbtCompound),
+}
+
+/// A path to tags inside a compound, like vanilla's `NbtPathArgument.NbtPath`. A path can point
+/// at many tags, e.g. every element of a list
+#[derive(Clone, Debug, PartialEq)]
+pub struct NbtPath {
+    nodes: Vec<NbtPathNode>,
+    source: String,
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn matches_filter(tag: &NbtTag, filter: &NbtCompound) -> bool {
+    matches!(tag, NbtTag::Compound(compound) if compound.contains(filter))
+}
+
+/// The index in a list of the length, negative indices count from the end
+fn list_index(len: usize, index: i32) -> Option<usize> {
+    let index = if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)?
+    } else {
+        index as usize
+    };
+    (index < len).then_some(index)
+}
+
+/// Whether the tag may be put into the list, the elements of a list all have the same type
+fn fits_into(list: &[NbtTag], tag: &NbtTag) -> bool {
+    list.first()
+        .is_none_or(|first| first.get_type_id() == tag.get_type_id())
+}
+
+impl NbtPathNode {
+    fn get<'a>(&self, tag: &'a NbtTag, found: &mut Vec<&'a NbtTag>) {
+        match (self, tag) {
+            (Self::MatchRoot(filter), tag) if matches_filter(tag, filter) => found.push(tag),
+            (Self::Key(name), NbtTag::Compound(compound)) => found.extend(compound.get(name)),
+            (Self::MatchKey(name, filter), NbtTag::Compound(compound)) => {
+                found.extend(compound.get(name).filter(|tag| matches_filter(tag, filter)))
+            }
+            (Self::Index(index), NbtTag::List(list)) => {
+                found.extend(list_index(list.len(), *index).map(|index| &list[index]));
+            }
+            (Self::AllElements, NbtTag::List(list)) => found.extend(list),
+            (Self::MatchElement(filter), NbtTag::List(list)) => {
+                found.extend(list.iter().filter(|tag| matches_filter(tag, filter)));
+            }
+            _ => {}
+        }
+    }
+
+    fn get_mut<'a>(&self, tag: &'a mut NbtTag, found: &mut Vec<&'a mut NbtTag>) {
+        match (self, tag) {
+            (Self::MatchRoot(filter), tag) if matches_filter(tag, filter) => found.push(tag),
+            (Self::Key(name), NbtTag::Compound(compound)) => found.extend(compound.get_mut(name)),
+            (Self::MatchKey(name, filter), NbtTag::Compound(compound)) => found.extend(
+                compound
+                    .get_mut(name)
+                    .filter(|tag| matches_filter(tag, filter)),
+            ),
+            (Self::Index(index), NbtTag::List(list)) => {
+                if let Some(index) = list_index(list.len(), *index) {
+                    found.push(&mut list[index]);
+                }
+            }
+            (Self::AllElements, NbtTag::List(list)) => found.extend(list.iter_mut()),
+            (Self::MatchElement(filter), NbtTag::List(list)) => {
+                found.extend(list.iter_mut().filter(|tag| matches_filter(tag, filter)));
+            }
+            _ => {}
+        }
+    }
+
+    /// Like `get_mut`, missing tags are created as `next` so the next node finds something
+    fn get_or_create<'a>(
+        &self,
+        tag: &'a mut NbtTag,
+        next: &NbtTag,
+        found: &mut Vec<&'a mut NbtTag>,
+    ) {
+        match (self, tag) {
+            (Self::MatchRoot(filter), tag) if matches_filter(tag, filter) => found.push(tag),
+            (Self::Key(name), NbtTag::Compound(compound)) => {
+                if compound.get(name).is_none() {
+                    compound.insert(name.clone(), next.clone());
+                }
+                found.extend(compound.get_mut(name));
+            }
+            (Self::MatchKey(name, filter), NbtTag::Compound(compound)) => {
+                if compound.get(name).is_none() {
+                    compound.insert(name.clone(), NbtTag::Compound(filter.clone()));
+                }
+                found.extend(
+                    compound
+                        .get_mut(name)
+                        .filter(|tag| matches_filter(tag, filter)),
+                );
+            }
+            (Self::Index(index), NbtTag::List(list)) => {
+                if let Some(index) = list_index(list.len(), *index) {
+                    found.push(&mut list[index]);
+                }
+            }
+            (Self::AllElements, NbtTag::List(list)) => found.extend(list.iter_mut()),
+            (Self::MatchElement(filter), NbtTag::List(list)) => {
+                if !list.iter().any(|tag| matches_filter(tag, filter))
+                    && fits_into(list, &NbtTag::Compound(NbtCompound::new()))
+                {
+                    list.push(NbtTag::Compound(filter.clone()));
+                }
+                found.extend(list.iter_mut().filter(|tag| matches_filter(tag, filter)));
+            }
+            _ => {}
+        }
+    }
+
+    /// What to create when the node finds nothing
+    fn empty_parent(&self) -> NbtTag {
+        match self {
+            Self::Index(_) | Self::AllElements | Self::MatchElement(_) => NbtTag::List(Vec::new()),
+            Self::MatchRoot(_) | Self::Key(_) | Self::MatchKey(..) => {
+                NbtTag::Compound(NbtCompound::new())
+            }
+        }
+    }
+
+    /// Puts the value where the node points at in the parent, returns how many tags changed
+    fn set(&self, parent: &mut NbtTag, value: &NbtTag) -> usize {
+        let replace = |tag: &mut NbtTag| {
+            if tag == value {
+                0
+            } else {
+                *tag = value.clone();
+                1
+            }
+        };
+        match (self, parent) {
+            (Self::Key(name), NbtTag::Compound(compound)) => match compound.get_mut(name) {
+                Some(tag) => replace(tag),
+                None => {
+                    compound.insert(name.clone(), value.clone());
+                    1
+                }
+            },
+            (Self::MatchKey(name, filter), NbtTag::Compound(compound)) => compound
+                .get_mut(name)
+                .filter(|tag| matches_filter(tag, filter))
+                .map_or(0, replace),
+            (Self::Index(index), NbtTag::List(list)) if fits_into(list, value) => {
+                list_index(list.len(), *index).map_or(0, |index| replace(&mut list[index]))
+            }
+            (Self::AllElements, NbtTag::List(list)) if fits_into(list, value) => {
+                if list.is_empty() {
+                    list.push(value.clone());
+                    return 1;
+                }
+                list.iter_mut().map(replace).sum()
+            }
+            (Self::MatchElement(filter), NbtTag::List(list)) if fits_into(list, value) => list
+                .iter_mut()
+                .filter(|tag| matches_filter(tag, filter))
+                .map(replace)
+                .sum(),
+            _ => 0,
+        }
+    }
+
+    /// Removes what the node points at from the parent, returns how many tags were removed
+    fn remove(&self, parent: &mut NbtTag) -> usize {
+        match (self, parent) {
+            (Self::Key(name), NbtTag::Compound(compound)) => {
+                usize::from(compound.remove(name).is_some())
+            }
+            (Self::MatchKey(name, filter), NbtTag::Compound(compound))
+                if compound
+                    .get(name)
+                    .is_some_and(|tag| matches_filter(tag, filter)) =>
+            {
+                compound.remove(name);
+                1
+            }
+            (Self::Index(index), NbtTag::List(list)) => {
+                list_index(list.len(), *index).map_or(0, |index| {
+                    list.remove(index);
+                    1
+                })
+            }
+            (Self::AllElements, NbtTag::List(list)) => std::mem::take(list).len(),
+            (Self::MatchElement(filter), NbtTag::List(list)) => {
+                let len = list.len();
+                list.retain(|tag| !matches_filter(tag, filter));
+                len - list.len()
+            }
+            _ => 0,
+        }
+    }
+}
+
+impl NbtPath {
+    /// Parses the path, like vanilla's `NbtPathArgument.parse`
+    pub fn parse(input: &str) -> Result<Self, NbtPathError> {
+        let mut reader = SnbtReader { input, pos: 0 };
+        let mut nodes = Vec::new();
+        while reader.peek().is_some() {
+            nodes.push(Self::parse_node(&mut reader, nodes.is_empty())?);
+            if !matches!(reader.peek(), None | Some('[' | '{')) {
+                reader.expect('.')?;
+            }
+        }
+        if nodes.is_empty() {
+            return Err(NbtPathError::InvalidNode(0));
+        }
+        Ok(Self {
+            nodes,
+            source: input.to_string(),
+        })
+    }
+
+    fn parse_node(reader: &mut SnbtReader, root: bool) -> Result<NbtPathNode, NbtPathError> {
+        let start = reader.pos;
+        let key = match reader.peek() {
+            Some('{') if root => return Ok(NbtPathNode::MatchRoot(reader.read_compound()?)),
+            Some('[') => {
+                reader.next();
+                let node = match reader.peek() {
+                    Some('{') => NbtPathNode::MatchElement(reader.read_compound()?),
+                    Some(']') => NbtPathNode::AllElements,
+                    _ => NbtPathNode::Index(
+                        reader
+                            .read_unquoted()
+                            .parse()
+                            .map_err(|_| NbtPathError::InvalidNode(start))?,
+                    ),
+                };
+                reader.expect(']')?;
+                return Ok(node);
+            }
+            Some(quote @ ('"' | '\'')) => reader.read_quoted(quote)?,
+            _ => {
+                while reader
+                    .peek()
+                    .is_some_and(|char| !" \"'[]{}.".contains(char))
+                {
+                    reader.next();
+                }
+                reader.input[start..reader.pos].to_string()
+            }
+        };
+        if key.is_empty() {
+            return Err(NbtPathError::InvalidNode(start));
+        }
+        if reader.peek() == Some('{') {
+            return Ok(NbtPathNode::MatchKey(key, reader.read_compound()?));
+        }
+        Ok(NbtPathNode::Key(key))
+    }
+
+    /// The tags the path points at in the compound
+    pub fn get(&self, root: &NbtCompound) -> Vec<NbtTag> {
+        let root = NbtTag::Compound(root.clone());
+        let mut tags = vec![&root];
+        for node in &self.nodes {
+            let mut found = Vec::new();
+            for tag in tags {
+                node.get(tag, &mut found);
+            }
+            tags = found;
+        }
+        tags.into_iter().cloned().collect()
+    }
+
+    /// Runs `f` on the tags the path points at. Missing compounds and lists on the way are
+    /// created, and the last tag is created with `default` if it is missing
+    pub fn modify(
+        &self,
+        root: &mut NbtCompound,
+        default: impl Fn() -> NbtTag,
+        mut f: impl FnMut(&mut NbtTag) -> usize,
+    ) -> usize {
+        let mut root_tag = NbtTag::Compound(std::mem::take(root));
+        let mut tags = vec![&mut root_tag];
+        for (index, node) in self.nodes.iter().enumerate() {
+            let next = match self.nodes.get(index + 1) {
+                Some(next) => next.empty_parent(),
+                None => default(),
+            };
+            let mut found = Vec::new();
+            for tag in tags {
+                node.get_or_create(tag, &next, &mut found);
+            }
+            tags = found;
+        }
+        let changed = tags.into_iter().map(&mut f).sum();
+        if let NbtTag::Compound(compound) = root_tag {
+            *root = compound;
+        }
+        changed
+    }
+
+    /// Runs `f` on the tags holding the last tag of the path, creating them like `modify`
+    fn modify_parents(
+        &self,
+        root: &mut NbtCompound,
+        mut f: impl FnMut(&NbtPathNode, &mut NbtTag) -> usize,
+    ) -> usize {
+        let (last, parents) = self.nodes.split_last().expect("Paths are never empty");
+        if parents.is_empty() {
+            let mut root_tag = NbtTag::Compound(std::mem::take(root));
+            let changed = f(last, &mut root_tag);
+            if let NbtTag::Compound(compound) = root_tag {
+                *root = compound;
+            }
+            return changed;
+        }
+        let parent_path = Self {
+            nodes: parents.to_vec(),
+            source: String::new(),
+        };
+        parent_path.modify(root, || last.empty_parent(), |parent| f(last, parent))
+    }
+
+    /// Puts the value everywhere the path points at, missing compounds and lists on the way are
+    /// created. Returns how many tags changed
+    pub fn set(&self, root: &mut NbtCompound, value: &NbtTag) -> usize {
+        self.modify_parents(root, |last, parent| last.set(parent, value))
+    }
+
+    /// Removes the tags the path points at, returns how many were removed
+    pub fn remove(&self, root: &mut NbtCompound) -> usize {
+        let (last, parents) = self.nodes.split_last().expect("Paths are never empty");
+        let mut root_tag = NbtTag::Compound(std::mem::take(root));
+        let mut tags = vec![&mut root_tag];
+        for node in parents {
+            let mut found = Vec::new();
+            for tag in tags {
+                node.get_mut(tag, &mut found);
+            }
+            tags = found;
+        }
+        let removed = tags.into_iter().map(|tag| last.remove(tag)).sum();
+        if let NbtTag::Compound(compound) = root_tag {
+            *root = compound;
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{snbt::compound_from_snbt, snbt::from_snbt, tag::NbtTag};
+
+    use super::{NbtPath, NbtPathError};
+
+    fn path(input: &str) -> NbtPath {
+        NbtPath::parse(input).unwrap()
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(NbtPath::parse(""), Err(NbtPathError::InvalidNode(0)));
+        assert_eq!(NbtPath::parse("a..b"), Err(NbtPathError::InvalidNode(2)));
+        assert_eq!(NbtPath::parse("a[x]"), Err(NbtPathError::InvalidNode(1)));
+        assert!(NbtPath::parse("a.b{c:1}[0].\"d e\"").is_ok());
+    }
+
+    #[test]
+    fn get() {
+        let nbt = compound_from_snbt(
+            r#"{Pos:[1.0d,2.0d,3.0d],Inventory:[{Slot:0b,id:"stone"},{Slot:1b,id:"dirt"}],a:{b:{c:7}}}"#,
+        )
+        .unwrap();
+        assert_eq!(path("a.b.c").get(&nbt), vec![NbtTag::Int(7)]);
+        assert_eq!(path("Pos[-1]").get(&nbt), vec![NbtTag::Double(3.0)]);
+        assert_eq!(path("Pos[]").get(&nbt).len(), 3);
+        assert_eq!(
+            path("Inventory[{Slot:1b}].id").get(&nbt),
+            vec![NbtTag::String("dirt".to_string())]
+        );
+        assert_eq!(path("a{b:{c:7}}.b.c").get(&nbt), vec![NbtTag::Int(7)]);
+        assert_eq!(path("{a:{}}.a.b.c").get(&nbt), vec![NbtTag::Int(7)]);
+        assert!(path("a{b:{c:8}}").get(&nbt).is_empty());
+        assert!(path("Pos[3]").get(&nbt).is_empty());
+    }
+
+    #[test]
+    fn set_and_remove() {
+        let mut nbt = compound_from_snbt(r#"{Tags:["a","b"],x:1}"#).unwrap();
+        assert_eq!(path("x").set(&mut nbt, &NbtTag::Int(1)), 0);
+        assert_eq!(path("new.deep").set(&mut nbt, &NbtTag::Int(2)), 1);
+        assert_eq!(path("Tags[0]").set(&mut nbt, &NbtTag::Int(2)), 0);
+        assert_eq!(
+            path("Tags[]").set(&mut nbt, &NbtTag::String("c".to_string())),
+            2
+        );
+        assert_eq!(
+            nbt,
+            compound_from_snbt(r#"{Tags:["c","c"],x:1,new:{deep:2}}"#).unwrap()
+        );
+
+        assert_eq!(path("Tags[-1]").remove(&mut nbt), 1);
+        assert_eq!(path("new.deep").remove(&mut nbt), 1);
+        assert_eq!(path("missing.deep").remove(&mut nbt), 0);
+        assert_eq!(
+            nbt,
+            compound_from_snbt(r#"{Tags:["c"],x:1,new:{}}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn modify_creates_missing_tags() {
+        let mut nbt = compound_from_snbt("{}").unwrap();
+        let changed = path("list").modify(
+            &mut nbt,
+            || NbtTag::List(Vec::new()),
+            |tag| match tag {
+                NbtTag::List(list) => {
+                    list.push(from_snbt("1").unwrap());
+                    1
+                }
+                _ => 0,
+            },
+        );
+        assert_eq!(changed, 1);
+        assert_eq!(nbt, compound_from_snbt("{list:[1]}").unwrap());
+    }
+}
@@ -0,0 +1,423 @@
+//! Stringified NBT like commands write it, e.g. `{Health:20f,Tags:["boss"]}`
+
+use bytes::Bytes;
+use thiserror::Error;
+
+use crate::{compound::NbtCompound, tag::NbtTag};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SnbtError {
+    #[error("Expected value at position {0}")]
+    ExpectedValue(usize),
+    #[error("Expected key at position {0}")]
+    ExpectedKey(usize),
+    #[error("Expected '{0}' at position {1}")]
+    Expected(char, usize),
+    #[error("Invalid array type '{0}' at position {1}")]
+    InvalidArrayType(char, usize),
+    #[error("Invalid escape sequence at position {0}")]
+    InvalidEscape(usize),
+    #[error("Can't mix element types in a list or array at position {0}")]
+    MixedElements(usize),
+    #[error("Unexpected trailing data at position {0}")]
+    TrailingData(usize),
+}
+
+/// Parses a single tag, like vanilla's `TagParser.parseTag`
+pub fn from_snbt(input: &str) -> Result<NbtTag, SnbtError> {
+    let mut reader = SnbtReader { input, pos: 0 };
+    let tag = reader.read_value()?;
+    reader.expect_end()?;
+    Ok(tag)
+}
+
+/// Parses a compound, which is all commands accept in most places
+pub fn compound_from_snbt(input: &str) -> Result<NbtCompound, SnbtError> {
+    let mut reader = SnbtReader { input, pos: 0 };
+    reader.skip_whitespace();
+    if reader.peek() != Some('{') {
+        return Err(SnbtError::Expected('{', reader.pos));
+    }
+    let compound = reader.read_compound()?;
+    reader.expect_end()?;
+    Ok(compound)
+}
+
+/// Writes the tag like vanilla's `StringTagVisitor`, `from_snbt` reads it back
+pub fn to_snbt(tag: &NbtTag) -> String {
+    let mut snbt = String::new();
+    write_snbt(tag, &mut snbt);
+    snbt
+}
+
+fn write_snbt(tag: &NbtTag, snbt: &mut String) {
+    let join = |snbt: &mut String, prefix: &str, values: Vec<String>| {
+        snbt.push_str(prefix);
+        snbt.push_str(&values.join(","));
+        snbt.push(']');
+    };
+    match tag {
+        NbtTag::End => {}
+        NbtTag::Byte(byte) => snbt.push_str(&format!("{byte}b")),
+        NbtTag::Short(short) => snbt.push_str(&format!("{short}s")),
+        NbtTag::Int(int) => snbt.push_str(&int.to_string()),
+        NbtTag::Long(long) => snbt.push_str(&format!("{long}L")),
+        NbtTag::Float(float) => snbt.push_str(&format!("{float:?}f")),
+        NbtTag::Double(double) => snbt.push_str(&format!("{double:?}d")),
+        NbtTag::String(string) => snbt.push_str(&quote(string)),
+        NbtTag::ByteArray(bytes) => join(
+            snbt,
+            "[B;",
+            bytes
+                .iter()
+                .map(|byte| format!("{}B", *byte as i8))
+                .collect(),
+        ),
+        NbtTag::IntArray(ints) => join(snbt, "[I;", ints.iter().map(i32::to_string).collect()),
+        NbtTag::LongArray(longs) => join(
+            snbt,
+            "[L;",
+            longs.iter().map(|long| format!("{long}L")).collect(),
+        ),
+        NbtTag::List(list) => join(snbt, "[", list.iter().map(to_snbt).collect()),
+        NbtTag::Compound(compound) => {
+            snbt.push('{');
+            for (index, (key, value)) in compound.child_tags.iter().enumerate() {
+                if index > 0 {
+                    snbt.push(',');
+                }
+                if !key.is_empty() && key.chars().all(is_unquoted_char) {
+                    snbt.push_str(key);
+                } else {
+                    snbt.push_str(&quote(key));
+                }
+                snbt.push(':');
+                write_snbt(value, snbt);
+            }
+            snbt.push('}');
+        }
+    }
+}
+
+/// Quotes the string with the quotes it doesn't contain, double quotes if possible
+fn quote(string: &str) -> String {
+    let quote = if string.contains('"') && !string.contains('\'') {
+        '\''
+    } else {
+        '"'
+    };
+    let mut quoted = String::from(quote);
+    for char in string.chars() {
+        if char == quote || char == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(char);
+    }
+    quoted.push(quote);
+    quoted
+}
+
+pub(crate) fn is_unquoted_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || "_-.+".contains(char)
+}
+
+pub(crate) struct SnbtReader<'a> {
+    pub(crate) input: &'a str,
+    pub(crate) pos: usize,
+}
+
+impl<'a> SnbtReader<'a> {
+    pub(crate) fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    pub(crate) fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.pos += char.len_utf8();
+        Some(char)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    /// Skips whitespace and the char, returns whether it was there
+    pub(crate) fn skip(&mut self, char: char) -> bool {
+        self.skip_whitespace();
+        let found = self.peek() == Some(char);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    pub(crate) fn expect(&mut self, char: char) -> Result<(), SnbtError> {
+        if self.skip(char) {
+            Ok(())
+        } else {
+            Err(SnbtError::Expected(char, self.pos))
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), SnbtError> {
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            return Err(SnbtError::TrailingData(self.pos));
+        }
+        Ok(())
+    }
+
+    fn read_value(&mut self) -> Result<NbtTag, SnbtError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.read_compound().map(NbtTag::Compound),
+            Some('[') => self.read_list_or_array(),
+            Some(quote @ ('"' | '\'')) => self.read_quoted(quote).map(NbtTag::String),
+            _ => {
+                let start = self.pos;
+                let token = self.read_unquoted();
+                if token.is_empty() {
+                    return Err(SnbtError::ExpectedValue(start));
+                }
+                Ok(typed_value(token))
+            }
+        }
+    }
+
+    pub(crate) fn read_unquoted(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(is_unquoted_char) {
+            self.next();
+        }
+        &self.input[start..self.pos]
+    }
+
+    pub(crate) fn read_quoted(&mut self, quote: char) -> Result<String, SnbtError> {
+        let start = self.pos;
+        self.next();
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some(escaped @ ('\\' | '"' | '\'')) => string.push(escaped),
+                    _ => return Err(SnbtError::InvalidEscape(self.pos)),
+                },
+                Some(char) if char == quote => return Ok(string),
+                Some(char) => string.push(char),
+                None => return Err(SnbtError::Expected(quote, start)),
+            }
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, SnbtError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let key = match self.peek() {
+            Some(quote @ ('"' | '\'')) => self.read_quoted(quote)?,
+            _ => self.read_unquoted().to_string(),
+        };
+        if key.is_empty() {
+            return Err(SnbtError::ExpectedKey(start));
+        }
+        Ok(key)
+    }
+
+    pub(crate) fn read_compound(&mut self) -> Result<NbtCompound, SnbtError> {
+        self.expect('{')?;
+        let mut compound = NbtCompound::new();
+        while !self.skip('}') {
+            let key = self.read_key()?;
+            self.expect(':')?;
+            let value = self.read_value()?;
+            compound.put(key, value);
+            if !self.skip(',') {
+                self.expect('}')?;
+                break;
+            }
+        }
+        Ok(compound)
+    }
+
+    fn read_list_or_array(&mut self) -> Result<NbtTag, SnbtError> {
+        self.expect('[')?;
+        let rest = &self.input[self.pos..];
+        let mut chars = rest.chars();
+        if let (Some(kind), Some(';')) = (chars.next(), chars.next()) {
+            if !rest.starts_with('"') && !rest.starts_with('\'') {
+                let start = self.pos;
+                self.pos += 2;
+                return match kind {
+                    'B' => self
+                        .read_array(|tag| match tag {
+                            NbtTag::Byte(byte) => Some(byte as u8),
+                            _ => None,
+                        })
+                        .map(|bytes| NbtTag::ByteArray(Bytes::from(bytes))),
+                    'I' => self
+                        .read_array(|tag| match tag {
+                            NbtTag::Int(int) => Some(int),
+                            _ => None,
+                        })
+                        .map(NbtTag::IntArray),
+                    'L' => self
+                        .read_array(|tag| match tag {
+                            NbtTag::Long(long) => Some(long),
+                            _ => None,
+                        })
+                        .map(NbtTag::LongArray),
+                    _ => Err(SnbtError::InvalidArrayType(kind, start)),
+                };
+            }
+        }
+        let mut list: Vec<NbtTag> = Vec::new();
+        while !self.skip(']') {
+            self.skip_whitespace();
+            let start = self.pos;
+            let value = self.read_value()?;
+            if list
+                .first()
+                .is_some_and(|first| first.get_type_id() != value.get_type_id())
+            {
+                return Err(SnbtError::MixedElements(start));
+            }
+            list.push(value);
+            if !self.skip(',') {
+                self.expect(']')?;
+                break;
+            }
+        }
+        Ok(NbtTag::List(list))
+    }
+
+    fn read_array<T>(
+        &mut self,
+        element: impl Fn(NbtTag) -> Option<T>,
+    ) -> Result<Vec<T>, SnbtError> {
+        let mut array = Vec::new();
+        while !self.skip(']') {
+            self.skip_whitespace();
+            let start = self.pos;
+            let value = element(self.read_value()?).ok_or(SnbtError::MixedElements(start))?;
+            array.push(value);
+            if !self.skip(',') {
+                self.expect(']')?;
+                break;
+            }
+        }
+        Ok(array)
+    }
+}
+
+/// Whether the token is a number with an optional sign, like `-12` or `1.5e3`
+fn is_number(token: &str, decimal: bool) -> bool {
+    let digits = token.strip_prefix(['-', '+']).unwrap_or(token);
+    digits.starts_with(|char: char| char.is_ascii_digit() || (decimal && char == '.'))
+        && digits
+            .chars()
+            .all(|char| char.is_ascii_digit() || (decimal && ".eE-+".contains(char)))
+}
+
+/// Unquoted tokens are numbers with a suffix for their type, booleans or strings, like vanilla's
+/// `TagParser.type`. Numbers out of range stay strings
+fn typed_value(token: &str) -> NbtTag {
+    let (number, suffix) = token.split_at(token.len() - 1);
+    let tag = match suffix {
+        "b" | "B" if is_number(number, false) => number.parse().ok().map(NbtTag::Byte),
+        "s" | "S" if is_number(number, false) => number.parse().ok().map(NbtTag::Short),
+        "l" | "L" if is_number(number, false) => number.parse().ok().map(NbtTag::Long),
+        "f" | "F" if is_number(number, true) => number.parse().ok().map(NbtTag::Float),
+        "d" | "D" if is_number(number, true) => number.parse().ok().map(NbtTag::Double),
+        _ if is_number(token, false) => token.parse().ok().map(NbtTag::Int),
+        _ if is_number(token, true) && token.contains('.') => {
+            token.parse().ok().map(NbtTag::Double)
+        }
+        _ => match token {
+            "true" => Some(NbtTag::Byte(1)),
+            "false" => Some(NbtTag::Byte(0)),
+            _ => None,
+        },
+    };
+    tag.unwrap_or_else(|| NbtTag::String(token.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compound::NbtCompound, tag::NbtTag};
+
+    use super::{compound_from_snbt, from_snbt, to_snbt, SnbtError};
+
+    #[test]
+    fn primitives() {
+        assert_eq!(from_snbt("3b"), Ok(NbtTag::Byte(3)));
+        assert_eq!(from_snbt("-7s"), Ok(NbtTag::Short(-7)));
+        assert_eq!(from_snbt("42"), Ok(NbtTag::Int(42)));
+        assert_eq!(from_snbt("9000000000L"), Ok(NbtTag::Long(9_000_000_000)));
+        assert_eq!(from_snbt("1.5f"), Ok(NbtTag::Float(1.5)));
+        assert_eq!(from_snbt("2.5"), Ok(NbtTag::Double(2.5)));
+        assert_eq!(from_snbt("true"), Ok(NbtTag::Byte(1)));
+        // Numbers out of range and words stay strings
+        assert_eq!(from_snbt("300b"), Ok(NbtTag::String("300b".to_string())));
+        assert_eq!(
+            from_snbt("zombie"),
+            Ok(NbtTag::String("zombie".to_string()))
+        );
+        assert_eq!(
+            from_snbt(r#""say \"hi\"""#),
+            Ok(NbtTag::String("say \"hi\"".to_string()))
+        );
+    }
+
+    #[test]
+    fn compounds_and_lists() {
+        let compound =
+            compound_from_snbt(r#"{ Health: 20f, Tags: ["boss", 'a b'], Pos: [I; 1, 2] }"#)
+                .unwrap();
+        assert_eq!(compound.get_float("Health"), Some(20.0));
+        assert_eq!(
+            compound.get_list("Tags"),
+            Some(&vec![
+                NbtTag::String("boss".to_string()),
+                NbtTag::String("a b".to_string())
+            ])
+        );
+        assert_eq!(compound.get_int_array("Pos"), Some(&vec![1, 2]));
+        assert_eq!(compound_from_snbt("{}"), Ok(NbtCompound::new()));
+
+        assert_eq!(from_snbt("[1, 2b]"), Err(SnbtError::MixedElements(4)));
+        assert_eq!(from_snbt("[I; 1b]"), Err(SnbtError::MixedElements(4)));
+        assert_eq!(compound_from_snbt("{a:1"), Err(SnbtError::Expected('}', 4)));
+        assert_eq!(
+            compound_from_snbt("{a:1} b"),
+            Err(SnbtError::TrailingData(6))
+        );
+        assert_eq!(compound_from_snbt("[]"), Err(SnbtError::Expected('{', 0)));
+    }
+
+    #[test]
+    fn partial_matches() {
+        let entity =
+            compound_from_snbt(r#"{Health:20f,Tags:["a","b"],Item:{id:"stone",count:2}}"#).unwrap();
+        let matches = |expected| entity.contains(&compound_from_snbt(expected).unwrap());
+        assert!(matches("{}"));
+        assert!(matches("{Tags:[\"b\"]}"));
+        assert!(matches("{Item:{id:\"stone\"}}"));
+        assert!(!matches("{Health:20}"));
+        assert!(!matches("{Tags:[]}"));
+        assert!(!matches("{Item:{id:\"dirt\"}}"));
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let snbt =
+            r#"{Health:20.0f,Tags:["boss","say \"hi\""],Pos:[I;1,-2],"a b":[B;1B],UUID:7L,x:{}}"#;
+        let tag = from_snbt(snbt).unwrap();
+        assert_eq!(
+            to_snbt(&tag),
+            r#"{Health:20.0f,Tags:["boss",'say "hi"'],Pos:[I;1,-2],"a b":[B;1B],UUID:7L,x:{}}"#
+        );
+        assert_eq!(from_snbt(&to_snbt(&tag)), Ok(tag));
+        assert_eq!(to_snbt(&NbtTag::Double(0.5)), "0.5d");
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 use bytes::{Buf, BufMut};
 
-mod deserializer;
+pub(crate) mod deserializer;
 use thiserror::Error;
 pub mod packet_id;
 mod serializer;
@@ -0,0 +1,40 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, codec::identifier::Identifier, ClientPacket, VarInt};
+
+/// Tells the client which entries are in which tags, e.g. which blocks are `mineable/pickaxe`
+#[client_packet("config:update_tags")]
+pub struct CUpdateTags<'a> {
+    registries: &'a [RegistryTags],
+}
+
+/// The tags of a registry
+pub struct RegistryTags {
+    pub registry_id: Identifier,
+    pub tags: Vec<TagEntries>,
+}
+
+pub struct TagEntries {
+    pub tag_id: Identifier,
+    /// The ids of the entries in the registry
+    pub entries: Vec<VarInt>,
+}
+
+impl<'a> CUpdateTags<'a> {
+    pub fn new(registries: &'a [RegistryTags]) -> Self {
+        Self { registries }
+    }
+}
+
+impl ClientPacket for CUpdateTags<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list::<RegistryTags>(self.registries, |p, registry| {
+            p.put_identifier(&registry.registry_id);
+            p.put_list::<TagEntries>(&registry.tags, |p, tag| {
+                p.put_identifier(&tag.tag_id);
+                p.put_list::<VarInt>(&tag.entries, |p, entry| p.put_var_int(entry));
+            });
+        });
+    }
+}
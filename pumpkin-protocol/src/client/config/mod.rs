@@ -8,6 +8,7 @@ mod c_registry_data;
 mod c_server_links;
 mod c_store_cookie;
 mod c_transfer;
+mod c_update_tags;
 
 pub use c_add_resource_pack::*;
 pub use c_config_disconnect::*;
@@ -19,3 +20,4 @@ pub use c_registry_data::*;
 pub use c_server_links::*;
 pub use c_store_cookie::*;
 pub use c_transfer::*;
+pub use c_update_tags::*;
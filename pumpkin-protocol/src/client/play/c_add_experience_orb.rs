@@ -0,0 +1,28 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Spawns an experience orb, orbs don't use the generic spawn entity packet
+#[derive(Serialize)]
+#[client_packet("play:add_experience_orb")]
+pub struct CAddExperienceOrb {
+    entity_id: VarInt,
+    x: f64,
+    y: f64,
+    z: f64,
+    /// The experience points the orb gives, the client picks the orb's size from it
+    count: i16,
+}
+
+impl CAddExperienceOrb {
+    pub fn new(entity_id: VarInt, x: f64, y: f64, z: f64, count: i16) -> Self {
+        Self {
+            entity_id,
+            x,
+            y,
+            z,
+            count,
+        }
+    }
+}
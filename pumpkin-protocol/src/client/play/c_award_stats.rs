@@ -0,0 +1,34 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// The statistics of the player, sent when they open the statistics screen
+#[client_packet("play:award_stats")]
+pub struct CAwardStats<'a> {
+    stats: &'a [AwardedStat],
+}
+
+impl<'a> CAwardStats<'a> {
+    pub fn new(stats: &'a [AwardedStat]) -> Self {
+        Self { stats }
+    }
+}
+
+/// A statistic by the id of its type and the registry id of what it counts, like the block for
+/// `mined`
+pub struct AwardedStat {
+    pub category_id: VarInt,
+    pub stat_id: VarInt,
+    pub value: VarInt,
+}
+
+impl ClientPacket for CAwardStats<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list(self.stats, |p, stat| {
+            p.put_var_int(&stat.category_id);
+            p.put_var_int(&stat.stat_id);
+            p.put_var_int(&stat.value);
+        });
+    }
+}
@@ -0,0 +1,37 @@
+use bytes::BufMut;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::client_packet;
+use pumpkin_nbt::{compound::NbtCompound, Nbt};
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// Updates the data players render a block entity from, like the mob spinning in a spawner
+#[client_packet("play:block_entity_data")]
+pub struct CBlockEntityData<'a> {
+    location: &'a WorldPosition,
+    block_entity_type: VarInt,
+    nbt: NbtCompound,
+}
+
+impl<'a> CBlockEntityData<'a> {
+    pub fn new(location: &'a WorldPosition, block_entity_type: VarInt, nbt: NbtCompound) -> Self {
+        Self {
+            location,
+            block_entity_type,
+            nbt,
+        }
+    }
+}
+
+impl ClientPacket for CBlockEntityData<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        let position = self.location.0;
+        bytebuf.put_i64(
+            ((i64::from(position.x) & 0x3FF_FFFF) << 38)
+                | ((i64::from(position.z) & 0x3FF_FFFF) << 12)
+                | (i64::from(position.y) & 0xFFF),
+        );
+        bytebuf.put_var_int(&self.block_entity_type);
+        bytebuf.put_slice(&Nbt::new(String::new(), self.nbt.clone()).write_unnamed());
+    }
+}
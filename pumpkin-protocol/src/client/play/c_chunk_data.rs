@@ -1,8 +1,13 @@
-use crate::{bytebuf::ByteBufMut, codec::bit_set::BitSet, ClientPacket, VarInt};
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+use super::c_light_update::write_light_data;
 
 use bytes::{BufMut, BytesMut};
 use pumpkin_macros::client_packet;
-use pumpkin_world::{chunk::ChunkData, DIRECT_PALETTE_BITS};
+use pumpkin_nbt::{Nbt, END_ID};
+use pumpkin_world::{
+    block::block_entity::block_entity_type_id, chunk::ChunkData, DIRECT_PALETTE_BITS,
+};
 
 #[client_packet("play:level_chunk_with_light")]
 pub struct CChunkData<'a>(pub &'a ChunkData);
@@ -107,37 +112,32 @@ impl ClientPacket for CChunkData<'_> {
         // Data
         buf.put_slice(&data_buf);
 
-        // TODO: block entities
-        buf.put_var_int(&VarInt(0));
-
-        // Sky Light Mask
-        // All of the chunks, this is not optimal and uses way more data than needed but will be
-        // overhauled with full lighting system.
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0b01111111111111111111111110]));
-        // Block Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
-        // Empty Sky Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0b0]));
-        // Empty Block Light Mask
-        buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
-
-        buf.put_var_int(&VarInt(self.0.blocks.subchunks_len() as i32));
-        self.0.blocks.iter_subchunks().for_each(|chunk| {
-            let mut chunk_light = [0u8; 2048];
-            for (i, _) in chunk.iter().enumerate() {
-                // if !block .is_air() {
-                //     continue;
-                // }
-                let index = i / 2;
-                let mask = if i % 2 == 1 { 0xF0 } else { 0x0F };
-                chunk_light[index] |= mask;
+        // Block entities of types players don't know are left out
+        let block_entities: Vec<_> = self
+            .0
+            .block_entities
+            .iter()
+            .filter_map(|(position, block_entity)| {
+                let type_id = block_entity_type_id(block_entity.id())?;
+                Some((position, type_id, block_entity.update_nbt()))
+            })
+            .collect();
+        buf.put_var_int(&VarInt(block_entities.len() as i32));
+        for (position, type_id, nbt) in block_entities {
+            let (_, relative) = position.chunk_and_chunk_relative_position();
+            // Packed XZ
+            buf.put_u8(((relative.x & 15) << 4 | (relative.z & 15)) as u8);
+            buf.put_i16(position.0.y as i16);
+            buf.put_var_int(&VarInt(type_id));
+            match nbt {
+                Some(nbt) => buf.put_slice(&Nbt::new(String::new(), nbt).write_unnamed()),
+                None => buf.put_u8(END_ID),
             }
+        }
 
-            buf.put_var_int(&VarInt(chunk_light.len() as i32));
-            buf.put_slice(&chunk_light);
-        });
-
-        // Block Lighting
-        buf.put_var_int(&VarInt(0));
+        // Light sections start one section below the world, light is sent for every section in the world
+        let sections =
+            (1..=self.0.blocks.subchunks_len()).fold(0u64, |mask, index| mask | 1 << index);
+        write_light_data(buf, &self.0.light, sections);
     }
 }
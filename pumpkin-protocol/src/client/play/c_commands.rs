@@ -29,6 +29,8 @@ impl ClientPacket for CCommands<'_> {
 
 pub struct ProtoNode<'a> {
     pub children: Vec<VarInt>,
+    /// The node parsing continues at after this one, like after the subcommands of `/execute`
+    pub redirect: Option<VarInt>,
     pub node_type: ProtoNodeType<'a>,
 }
 
@@ -54,7 +56,7 @@ impl ProtoNode<'_> {
 
     pub fn write_to(&self, bytebuf: &mut impl BufMut) {
         // flags
-        let flags = match self.node_type {
+        let mut flags = match self.node_type {
             ProtoNodeType::Root => 0,
             ProtoNodeType::Literal {
                 name: _,
@@ -82,14 +84,17 @@ impl ProtoNode<'_> {
                 n
             }
         };
+        if self.redirect.is_some() {
+            flags |= Self::FLAG_HAS_REDIRECT;
+        }
         bytebuf.put_u8(flags);
 
         // child count + children
         bytebuf.put_list(&self.children, |bytebuf, child| bytebuf.put_var_int(child));
 
         // redirect node
-        if flags & Self::FLAG_HAS_REDIRECT != 0 {
-            bytebuf.put_var_int(&1.into());
+        if let Some(redirect) = &self.redirect {
+            bytebuf.put_var_int(redirect);
         }
 
         // name
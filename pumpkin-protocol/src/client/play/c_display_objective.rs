@@ -19,6 +19,7 @@ impl<'a> CDisplayObjective<'a> {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum DisplaySlot {
     List,
@@ -1,18 +1,20 @@
+use pumpkin_core::{math::position::WorldPosition, text::TextComponent};
 use pumpkin_macros::client_packet;
-use serde::Serialize;
+use serde::{ser::SerializeTuple, Serialize, Serializer};
 
-use crate::VarInt;
+use crate::{codec::slot::Slot, VarInt};
 
+/// Changes metadata values of an entity, only the values which changed have to be sent
 #[derive(Serialize)]
 #[client_packet("play:set_entity_data")]
-pub struct CSetEntityMetadata<T> {
+pub struct CSetEntityMetadata {
     entity_id: VarInt,
-    metadata: Metadata<T>,
+    metadata: Vec<Metadata>,
     end: u8,
 }
 
-impl<T> CSetEntityMetadata<T> {
-    pub fn new(entity_id: VarInt, metadata: Metadata<T>) -> Self {
+impl CSetEntityMetadata {
+    pub fn new(entity_id: VarInt, metadata: Vec<Metadata>) -> Self {
         Self {
             entity_id,
             metadata,
@@ -22,14 +24,70 @@ impl<T> CSetEntityMetadata<T> {
 }
 
 #[derive(Serialize)]
-pub struct Metadata<T> {
+pub struct Metadata {
     index: u8,
-    typ: VarInt,
-    value: T,
+    value: MetadataValue,
 }
 
-impl<T> Metadata<T> {
-    pub fn new(index: u8, typ: VarInt, value: T) -> Self {
-        Self { index, typ, value }
+impl Metadata {
+    pub fn new(index: u8, value: MetadataValue) -> Self {
+        Self { index, value }
+    }
+}
+
+/// A metadata value together with its type, like vanilla's `EntityDataSerializers`
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    TextComponent(TextComponent<'static>),
+    OptionalTextComponent(Option<TextComponent<'static>>),
+    Slot(Slot),
+    Boolean(bool),
+    OptionalBlockPosition(Option<WorldPosition>),
+    Pose(i32),
+}
+
+impl MetadataValue {
+    /// The id the client knows the type of the value by
+    pub const fn type_id(&self) -> i32 {
+        match self {
+            Self::Byte(_) => 0,
+            Self::VarInt(_) => 1,
+            Self::Float(_) => 3,
+            Self::String(_) => 4,
+            Self::TextComponent(_) => 5,
+            Self::OptionalTextComponent(_) => 6,
+            Self::Slot(_) => 7,
+            Self::Boolean(_) => 8,
+            Self::OptionalBlockPosition(_) => 11,
+            Self::Pose(_) => 21,
+        }
+    }
+}
+
+impl Serialize for MetadataValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&VarInt(self.type_id()))?;
+        match self {
+            Self::Byte(value) => tuple.serialize_element(value)?,
+            Self::VarInt(value) | Self::Pose(value) => {
+                tuple.serialize_element(&VarInt(*value))?;
+            }
+            Self::Float(value) => tuple.serialize_element(value)?,
+            Self::String(value) => tuple.serialize_element(value)?,
+            Self::TextComponent(value) => tuple.serialize_element(value)?,
+            Self::OptionalTextComponent(value) => tuple.serialize_element(value)?,
+            Self::Slot(value) => tuple.serialize_element(value)?,
+            Self::Boolean(value) => tuple.serialize_element(value)?,
+            Self::OptionalBlockPosition(value) => tuple.serialize_element(value)?,
+        }
+        tuple.end()
     }
 }
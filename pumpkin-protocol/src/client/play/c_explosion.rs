@@ -0,0 +1,48 @@
+use bytes::BufMut;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// Shows an explosion, the blocks it destroys are sent separately. Each player gets their own
+/// knockback
+#[client_packet("play:explode")]
+pub struct CExplosion {
+    center: Vector3<f64>,
+    player_knockback: Option<Vector3<f64>>,
+    particle_id: VarInt,
+    sound_id: VarInt,
+}
+
+impl CExplosion {
+    pub fn new(
+        center: Vector3<f64>,
+        player_knockback: Option<Vector3<f64>>,
+        particle_id: VarInt,
+        sound_id: VarInt,
+    ) -> Self {
+        Self {
+            center,
+            player_knockback,
+            particle_id,
+            sound_id,
+        }
+    }
+}
+
+impl ClientPacket for CExplosion {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_f64(self.center.x);
+        bytebuf.put_f64(self.center.y);
+        bytebuf.put_f64(self.center.z);
+        bytebuf.put_option(&self.player_knockback, |p, v| {
+            p.put_f64(v.x);
+            p.put_f64(v.y);
+            p.put_f64(v.z);
+        });
+        // The explosion particles have no options
+        bytebuf.put_var_int(&self.particle_id);
+        // Registered sounds are sent plus one, zero is followed by an inline sound event
+        bytebuf.put_var_int(&VarInt(self.sound_id.0 + 1));
+    }
+}
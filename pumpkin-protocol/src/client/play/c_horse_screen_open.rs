@@ -0,0 +1,24 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Opens the inventory of a horse, donkey, mule or llama
+#[derive(Serialize)]
+#[client_packet("play:horse_screen_open")]
+pub struct CHorseScreenOpen {
+    window_id: VarInt,
+    /// Columns of the chest, each column has three slots
+    inventory_columns: VarInt,
+    entity_id: i32,
+}
+
+impl CHorseScreenOpen {
+    pub fn new(window_id: VarInt, inventory_columns: VarInt, entity_id: i32) -> Self {
+        Self {
+            window_id,
+            inventory_columns,
+            entity_id,
+        }
+    }
+}
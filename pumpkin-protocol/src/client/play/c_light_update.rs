@@ -0,0 +1,71 @@
+use crate::{bytebuf::ByteBufMut, codec::bit_set::BitSet, ClientPacket, VarInt};
+
+use bytes::BufMut;
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_macros::client_packet;
+use pumpkin_world::chunk::{ChunkLight, LIGHT_SECTION_SIZE};
+
+/// Sends the light of some sections of a chunk after it changed
+#[client_packet("play:light_update")]
+pub struct CLightUpdate {
+    position: Vector2<i32>,
+    light: ChunkLight,
+    /// The light sections which are sent, starting with the one right below the world
+    sections: u64,
+}
+
+impl CLightUpdate {
+    pub fn new(position: Vector2<i32>, light: ChunkLight, sections: u64) -> Self {
+        Self {
+            position,
+            light,
+            sections,
+        }
+    }
+}
+
+impl ClientPacket for CLightUpdate {
+    fn write(&self, buf: &mut impl BufMut) {
+        buf.put_var_int(&VarInt(self.position.x));
+        buf.put_var_int(&VarInt(self.position.z));
+        write_light_data(buf, &self.light, self.sections);
+    }
+}
+
+fn section(storage: &[Option<Box<[u8]>>], index: usize) -> Option<&[u8]> {
+    storage.get(index).and_then(Option::as_deref)
+}
+
+/// Writes the light data shared by the chunk and light update packets for the sections in the mask.
+/// Sky light sections without data are fully lit, block light sections without data are dark
+pub(crate) fn write_light_data(buf: &mut impl BufMut, light: &ChunkLight, sections: u64) {
+    let sent = (0..64).filter(|index| sections & (1 << index) != 0);
+    let block_light_mask = sent
+        .clone()
+        .filter(|index| section(&light.block_light, *index).is_some())
+        .fold(0i64, |mask, index| mask | 1 << index);
+    let empty_block_light_mask = sections as i64 & !block_light_mask;
+
+    // Sky Light Mask
+    buf.put_bit_set(&BitSet(VarInt(1), vec![sections as i64]));
+    // Block Light Mask
+    buf.put_bit_set(&BitSet(VarInt(1), vec![block_light_mask]));
+    // Empty Sky Light Mask
+    buf.put_bit_set(&BitSet(VarInt(1), vec![0]));
+    // Empty Block Light Mask
+    buf.put_bit_set(&BitSet(VarInt(1), vec![empty_block_light_mask]));
+
+    buf.put_var_int(&VarInt(sent.clone().count() as i32));
+    let full_light = [0xFFu8; LIGHT_SECTION_SIZE];
+    for index in sent.clone() {
+        let section_light = section(&light.sky_light, index).unwrap_or(&full_light);
+        buf.put_var_int(&VarInt(section_light.len() as i32));
+        buf.put_slice(section_light);
+    }
+
+    buf.put_var_int(&VarInt(block_light_mask.count_ones() as i32));
+    for section_light in sent.filter_map(|index| section(&light.block_light, index)) {
+        buf.put_var_int(&VarInt(section_light.len() as i32));
+        buf.put_slice(section_light);
+    }
+}
@@ -0,0 +1,84 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, codec::slot::Slot, ClientPacket, VarInt};
+
+#[client_packet("play:merchant_offers")]
+pub struct CMerchantOffers<'a> {
+    window_id: VarInt,
+    trades: &'a [MerchantTrade],
+    villager_level: VarInt,
+    experience: VarInt,
+    /// Only regular villagers show their level and experience
+    is_regular_villager: bool,
+    can_restock: bool,
+}
+
+pub struct MerchantTrade {
+    pub input_1: TradeItem,
+    pub output: Slot,
+    pub input_2: Option<TradeItem>,
+    /// Out of stock trades are shown as locked
+    pub disabled: bool,
+    pub uses: i32,
+    pub max_uses: i32,
+    pub experience: i32,
+    pub special_price: i32,
+    pub price_multiplier: f32,
+    pub demand: i32,
+}
+
+/// An item a trade costs, any components are accepted
+pub struct TradeItem {
+    pub item_id: VarInt,
+    pub item_count: VarInt,
+}
+
+impl<'a> CMerchantOffers<'a> {
+    pub fn new(
+        window_id: VarInt,
+        trades: &'a [MerchantTrade],
+        villager_level: VarInt,
+        experience: VarInt,
+        is_regular_villager: bool,
+        can_restock: bool,
+    ) -> Self {
+        Self {
+            window_id,
+            trades,
+            villager_level,
+            experience,
+            is_regular_villager,
+            can_restock,
+        }
+    }
+}
+
+fn put_trade_item(bytebuf: &mut impl BufMut, item: &TradeItem) {
+    bytebuf.put_var_int(&item.item_id);
+    bytebuf.put_var_int(&item.item_count);
+    // No component predicates
+    bytebuf.put_var_int(&VarInt(0));
+}
+
+impl ClientPacket for CMerchantOffers<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&self.window_id);
+        bytebuf.put_list::<MerchantTrade>(self.trades, |p, trade| {
+            put_trade_item(p, &trade.input_1);
+            trade.output.write(p);
+            p.put_option(&trade.input_2, |p, item| put_trade_item(p, item));
+            p.put_bool(trade.disabled);
+            p.put_i32(trade.uses);
+            p.put_i32(trade.max_uses);
+            p.put_i32(trade.experience);
+            p.put_i32(trade.special_price);
+            p.put_f32(trade.price_multiplier);
+            p.put_i32(trade.demand);
+        });
+        bytebuf.put_var_int(&self.villager_level);
+        bytebuf.put_var_int(&self.experience);
+        bytebuf.put_bool(self.is_regular_villager);
+        bytebuf.put_bool(self.can_restock);
+    }
+}
@@ -0,0 +1,26 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+use super::RecipeDisplay;
+
+/// Shows the recipe in the crafting grid when the player is missing ingredients
+#[client_packet("play:place_ghost_recipe")]
+pub struct CPlaceGhostRecipe<'a> {
+    window_id: VarInt,
+    recipe: &'a RecipeDisplay,
+}
+
+impl<'a> CPlaceGhostRecipe<'a> {
+    pub fn new(window_id: VarInt, recipe: &'a RecipeDisplay) -> Self {
+        Self { window_id, recipe }
+    }
+}
+
+impl ClientPacket for CPlaceGhostRecipe<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&self.window_id);
+        self.recipe.write(bytebuf);
+    }
+}
@@ -0,0 +1,44 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket};
+
+use super::RecipeDisplayEntry;
+
+#[client_packet("play:recipe_book_add")]
+pub struct CRecipeBookAdd<'a> {
+    recipes: &'a [RecipeBookEntry],
+    /// Replaces all recipes the client knows, used when joining
+    replace: bool,
+}
+
+pub struct RecipeBookEntry {
+    pub display: RecipeDisplayEntry,
+    /// Shows the "new recipes unlocked" toast
+    pub show_notification: bool,
+    /// Highlights the recipe in the book until it is seen
+    pub highlight: bool,
+}
+
+impl<'a> CRecipeBookAdd<'a> {
+    pub fn new(recipes: &'a [RecipeBookEntry], replace: bool) -> Self {
+        Self { recipes, replace }
+    }
+}
+
+impl ClientPacket for CRecipeBookAdd<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list::<RecipeBookEntry>(self.recipes, |p, entry| {
+            entry.display.write(p);
+            let mut flags = 0;
+            if entry.show_notification {
+                flags |= 0x01;
+            }
+            if entry.highlight {
+                flags |= 0x02;
+            }
+            p.put_u8(flags);
+        });
+        bytebuf.put_bool(self.replace);
+    }
+}
@@ -0,0 +1,26 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket};
+
+#[client_packet("play:recipe_book_settings")]
+pub struct CRecipeBookSettings {
+    /// Whether the book is open and filters for craftable recipes,
+    /// for the crafting, furnace, blast furnace and smoker books
+    books: [(bool, bool); 4],
+}
+
+impl CRecipeBookSettings {
+    pub fn new(books: [(bool, bool); 4]) -> Self {
+        Self { books }
+    }
+}
+
+impl ClientPacket for CRecipeBookSettings {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        for (open, filtering) in self.books {
+            bytebuf.put_bool(open);
+            bytebuf.put_bool(filtering);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use bytes::BufMut;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_macros::client_packet;
+
+use crate::{
+    bytebuf::ByteBufMut,
+    codec::{var_long::VarLong, Codec},
+    ClientPacket, VarInt,
+};
+
+/// Changes several blocks of one chunk section at once
+#[client_packet("play:section_blocks_update")]
+pub struct CSectionBlocksUpdate {
+    /// The chunk x, the section y and the chunk z
+    section: Vector3<i32>,
+    /// The changed blocks and their new states, all inside the section
+    blocks: Vec<(WorldPosition, u16)>,
+}
+
+impl CSectionBlocksUpdate {
+    pub fn new(section: Vector3<i32>, blocks: Vec<(WorldPosition, u16)>) -> Self {
+        Self { section, blocks }
+    }
+}
+
+impl ClientPacket for CSectionBlocksUpdate {
+    fn write(&self, buf: &mut impl BufMut) {
+        let section = ((i64::from(self.section.x) & 0x3FFFFF) << 42)
+            | ((i64::from(self.section.z) & 0x3FFFFF) << 20)
+            | (i64::from(self.section.y) & 0xFFFFF);
+        buf.put_i64(section);
+        buf.put_var_int(&VarInt(self.blocks.len() as i32));
+        for (position, state_id) in &self.blocks {
+            let relative =
+                ((position.0.x & 15) << 8) | ((position.0.z & 15) << 4) | (position.0.y & 15);
+            VarLong((i64::from(*state_id) << 12) | i64::from(relative)).encode(buf);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::{codec::slot::Slot, VarInt};
+
+/// Shows the items an entity holds and wears, like the armor of an armor stand
+#[derive(Serialize)]
+#[client_packet("play:set_equipment")]
+pub struct CSetEquipment {
+    entity_id: VarInt,
+    equipment: Vec<EquipmentEntry>,
+}
+
+#[derive(Serialize)]
+pub struct EquipmentEntry {
+    slot: u8,
+    item: Slot,
+}
+
+impl CSetEquipment {
+    /// The slots are 0 for the main hand, 1 for the offhand, 2 to 5 for boots, leggings, chestplate and helmet
+    /// and 6 for the body of animals. There has to be at least one entry
+    pub fn new(entity_id: VarInt, equipment: Vec<(u8, Slot)>) -> Self {
+        let last = equipment.len().saturating_sub(1);
+        let equipment = equipment
+            .into_iter()
+            .enumerate()
+            .map(|(index, (slot, item))| EquipmentEntry {
+                // The top bit tells whether another entry follows
+                slot: if index == last { slot } else { slot | 0x80 },
+                item,
+            })
+            .collect();
+        Self {
+            entity_id,
+            equipment,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use bytes::BufMut;
+use pumpkin_core::text::TextComponent;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+/// The color a team without a color is sent with
+pub const TEAM_COLOR_RESET: i32 = 21;
+
+/// Friendly flag bits of a team
+pub const FRIENDLY_FIRE: u8 = 0x01;
+pub const SEE_FRIENDLY_INVISIBLES: u8 = 0x02;
+
+#[client_packet("play:set_player_team")]
+pub struct CSetPlayerTeam<'a> {
+    team_name: &'a str,
+    method: TeamMethod<'a>,
+}
+
+impl<'a> CSetPlayerTeam<'a> {
+    pub fn new(team_name: &'a str, method: TeamMethod<'a>) -> Self {
+        Self { team_name, method }
+    }
+}
+
+/// How a team is shown to the client, the rules are strings like `always` or `pushOwnTeam`
+pub struct TeamInfo<'a> {
+    pub display_name: TextComponent<'a>,
+    pub friendly_flags: u8,
+    pub name_tag_visibility: &'a str,
+    pub collision_rule: &'a str,
+    pub color: VarInt,
+    pub prefix: TextComponent<'a>,
+    pub suffix: TextComponent<'a>,
+}
+
+pub enum TeamMethod<'a> {
+    Create(TeamInfo<'a>, &'a [String]),
+    Remove,
+    Update(TeamInfo<'a>),
+    AddEntities(&'a [String]),
+    RemoveEntities(&'a [String]),
+}
+
+impl TeamMethod<'_> {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Create(..) => 0,
+            Self::Remove => 1,
+            Self::Update(_) => 2,
+            Self::AddEntities(_) => 3,
+            Self::RemoveEntities(_) => 4,
+        }
+    }
+}
+
+fn put_info(bytebuf: &mut impl BufMut, info: &TeamInfo) {
+    bytebuf.put_slice(&info.display_name.encode());
+    bytebuf.put_u8(info.friendly_flags);
+    bytebuf.put_string(info.name_tag_visibility);
+    bytebuf.put_string(info.collision_rule);
+    bytebuf.put_var_int(&info.color);
+    bytebuf.put_slice(&info.prefix.encode());
+    bytebuf.put_slice(&info.suffix.encode());
+}
+
+impl ClientPacket for CSetPlayerTeam<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_string(self.team_name);
+        bytebuf.put_u8(self.method.id());
+        match &self.method {
+            TeamMethod::Create(info, entities) => {
+                put_info(bytebuf, info);
+                bytebuf.put_list(entities, |p, entity| p.put_string(entity));
+            }
+            TeamMethod::Remove => {}
+            TeamMethod::Update(info) => put_info(bytebuf, info),
+            TeamMethod::AddEntities(entities) | TeamMethod::RemoveEntities(entities) => {
+                bytebuf.put_list(entities, |p, entity| p.put_string(entity));
+            }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Plays the animation of an entity picking up an item entity, it doesn't remove the item entity
+#[derive(Serialize)]
+#[client_packet("play:take_item_entity")]
+pub struct CTakeItemEntity {
+    collected_entity_id: VarInt,
+    collector_entity_id: VarInt,
+    pickup_item_count: VarInt,
+}
+
+impl CTakeItemEntity {
+    pub fn new(
+        collected_entity_id: VarInt,
+        collector_entity_id: VarInt,
+        pickup_item_count: VarInt,
+    ) -> Self {
+        Self {
+            collected_entity_id,
+            collector_entity_id,
+            pickup_item_count,
+        }
+    }
+}
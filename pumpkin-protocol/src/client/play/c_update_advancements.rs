@@ -0,0 +1,96 @@
+use bytes::BufMut;
+use pumpkin_core::text::TextComponent;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, codec::slot::Slot, ClientPacket, VarInt};
+
+/// Display flag bits of an advancement
+pub const ADVANCEMENT_HAS_BACKGROUND: i32 = 0x01;
+pub const ADVANCEMENT_SHOW_TOAST: i32 = 0x02;
+pub const ADVANCEMENT_HIDDEN: i32 = 0x04;
+
+#[client_packet("play:update_advancements")]
+pub struct CUpdateAdvancements<'a> {
+    /// Forgets all advancements the client knows, used when joining
+    reset: bool,
+    added: &'a [AdvancementEntry<'a>],
+    removed: &'a [String],
+    progress: &'a [AdvancementProgressEntry<'a>],
+}
+
+impl<'a> CUpdateAdvancements<'a> {
+    pub fn new(
+        reset: bool,
+        added: &'a [AdvancementEntry<'a>],
+        removed: &'a [String],
+        progress: &'a [AdvancementProgressEntry<'a>],
+    ) -> Self {
+        Self {
+            reset,
+            added,
+            removed,
+            progress,
+        }
+    }
+}
+
+pub struct AdvancementEntry<'a> {
+    pub id: &'a str,
+    pub parent: Option<&'a str>,
+    pub display: Option<AdvancementDisplayEntry<'a>>,
+    pub requirements: Vec<Vec<String>>,
+    pub sends_telemetry: bool,
+}
+
+/// Where and how the advancement is shown in the advancement screen, the frame is task 0,
+/// challenge 1 and goal 2
+pub struct AdvancementDisplayEntry<'a> {
+    pub title: TextComponent<'a>,
+    pub description: TextComponent<'a>,
+    pub icon: Slot,
+    pub frame: VarInt,
+    pub flags: i32,
+    pub background: Option<&'a str>,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The criteria of an advancement with the time they were done at in milliseconds since the epoch
+pub struct AdvancementProgressEntry<'a> {
+    pub id: &'a str,
+    pub criteria: Vec<(&'a str, Option<i64>)>,
+}
+
+impl ClientPacket for CUpdateAdvancements<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_bool(self.reset);
+        bytebuf.put_list(self.added, |p, entry| {
+            p.put_string(entry.id);
+            p.put_option(&entry.parent, |p, parent| p.put_string(parent));
+            p.put_option(&entry.display, |p, display| {
+                p.put_slice(&display.title.encode());
+                p.put_slice(&display.description.encode());
+                display.icon.write(p);
+                p.put_var_int(&display.frame);
+                p.put_i32(display.flags);
+                if let Some(background) = display.background {
+                    p.put_string(background);
+                }
+                p.put_f32(display.x);
+                p.put_f32(display.y);
+            });
+            p.put_list(&entry.requirements, |p, group| {
+                p.put_list(group, |p, criterion| p.put_string(criterion));
+            });
+            p.put_bool(entry.sends_telemetry);
+        });
+        bytebuf.put_list(self.removed, |p, id| p.put_string(id));
+        bytebuf.put_list(self.progress, |p, entry| {
+            p.put_string(entry.id);
+            p.put_list(&entry.criteria, |p, (criterion, date)| {
+                p.put_string(criterion);
+                p.put_option(date, |p, date| p.put_i64(*date));
+            });
+        });
+    }
+}
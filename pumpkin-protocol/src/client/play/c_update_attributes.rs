@@ -0,0 +1,49 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, ClientPacket, VarInt};
+
+#[client_packet("play:update_attributes")]
+pub struct CUpdateAttributes<'a> {
+    entity_id: VarInt,
+    attributes: &'a [AttributeProperty],
+}
+
+/// An attribute of an entity with the modifiers changing its value
+pub struct AttributeProperty {
+    /// The id of the attribute in the `attribute` registry
+    pub attribute_id: VarInt,
+    pub base: f64,
+    pub modifiers: Vec<AttributeModifier>,
+}
+
+pub struct AttributeModifier {
+    pub id: String,
+    pub amount: f64,
+    /// 0 adds the amount, 1 multiplies the base and 2 multiplies the total by one plus the amount
+    pub operation: u8,
+}
+
+impl<'a> CUpdateAttributes<'a> {
+    pub fn new(entity_id: VarInt, attributes: &'a [AttributeProperty]) -> Self {
+        Self {
+            entity_id,
+            attributes,
+        }
+    }
+}
+
+impl ClientPacket for CUpdateAttributes<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&self.entity_id);
+        bytebuf.put_list::<AttributeProperty>(self.attributes, |p, attribute| {
+            p.put_var_int(&attribute.attribute_id);
+            p.put_f64(attribute.base);
+            p.put_list::<AttributeModifier>(&attribute.modifiers, |p, modifier| {
+                p.put_string(&modifier.id);
+                p.put_f64(modifier.amount);
+                p.put_u8(modifier.operation);
+            });
+        });
+    }
+}
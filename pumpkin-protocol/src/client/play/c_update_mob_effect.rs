@@ -0,0 +1,34 @@
+use pumpkin_macros::client_packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Shows an effect an entity has, the flags say whether it is ambient (0x01), shows particles
+/// (0x02) and shows its icon (0x04)
+#[derive(Serialize)]
+#[client_packet("play:update_mob_effect")]
+pub struct CUpdateMobEffect {
+    entity_id: VarInt,
+    effect_id: VarInt,
+    amplifier: VarInt,
+    duration: VarInt,
+    flags: i8,
+}
+
+impl CUpdateMobEffect {
+    pub fn new(
+        entity_id: VarInt,
+        effect_id: VarInt,
+        amplifier: VarInt,
+        duration: VarInt,
+        flags: i8,
+    ) -> Self {
+        Self {
+            entity_id,
+            effect_id,
+            amplifier,
+            duration,
+            flags,
+        }
+    }
+}
@@ -65,6 +65,7 @@ pub enum Mode {
     Update,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
 pub enum RenderType {
     Integer,
@@ -0,0 +1,54 @@
+use bytes::BufMut;
+use pumpkin_macros::client_packet;
+
+use crate::{bytebuf::ByteBufMut, codec::identifier::Identifier, ClientPacket, VarInt};
+
+use super::SlotDisplay;
+
+/// Sends the recipe data the client needs for containers which don't use the recipe book
+#[client_packet("play:update_recipes")]
+pub struct CUpdateRecipes<'a> {
+    /// Sets of items the client allows in certain slots, e.g. `smithing_template`
+    property_sets: &'a [PropertySet],
+    stonecutter_recipes: &'a [StonecutterRecipe],
+}
+
+pub struct PropertySet {
+    pub id: Identifier,
+    pub items: Vec<VarInt>,
+}
+
+pub struct StonecutterRecipe {
+    /// The items the recipe accepts as input
+    pub ingredient: Vec<VarInt>,
+    pub result: SlotDisplay,
+}
+
+impl<'a> CUpdateRecipes<'a> {
+    pub fn new(
+        property_sets: &'a [PropertySet],
+        stonecutter_recipes: &'a [StonecutterRecipe],
+    ) -> Self {
+        Self {
+            property_sets,
+            stonecutter_recipes,
+        }
+    }
+}
+
+impl ClientPacket for CUpdateRecipes<'_> {
+    fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_list::<PropertySet>(self.property_sets, |p, set| {
+            p.put_identifier(&set.id);
+            p.put_list::<VarInt>(&set.items, |p, item| p.put_var_int(item));
+        });
+        bytebuf.put_list::<StonecutterRecipe>(self.stonecutter_recipes, |p, recipe| {
+            // Holder sets are written as a tag when the length is zero, so the length is shifted by one
+            p.put_var_int(&VarInt(recipe.ingredient.len() as i32 + 1));
+            for item in &recipe.ingredient {
+                p.put_var_int(item);
+            }
+            recipe.result.write(p);
+        });
+    }
+}
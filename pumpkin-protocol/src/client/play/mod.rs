@@ -1,7 +1,10 @@
 mod bossevent_action;
 mod c_acknowledge_block;
 mod c_actionbar;
+mod c_add_experience_orb;
+mod c_award_stats;
 mod c_block_destroy_stage;
+mod c_block_entity_data;
 mod c_block_event;
 mod c_block_update;
 mod c_boss_event;
@@ -21,25 +24,34 @@ mod c_entity_metadata;
 mod c_entity_sound_effect;
 mod c_entity_status;
 mod c_entity_velocity;
+mod c_explosion;
 mod c_game_event;
 mod c_head_rot;
+mod c_horse_screen_open;
 mod c_hurt_animation;
 mod c_initialize_world_border;
 mod c_keep_alive;
 mod c_level_event;
+mod c_light_update;
 mod c_login;
+mod c_merchant_offers;
 mod c_open_screen;
 mod c_particle;
 mod c_ping_response;
+mod c_place_ghost_recipe;
 mod c_play_disconnect;
 mod c_player_abilities;
 mod c_player_chat_message;
 mod c_player_info_update;
 mod c_player_position;
 mod c_player_remove;
+mod c_recipe_book_add;
+mod c_recipe_book_settings;
 mod c_remove_entities;
+mod c_remove_mob_effect;
 mod c_reset_score;
 mod c_respawn;
+mod c_section_blocks_update;
 mod c_server_links;
 mod c_set_border_center;
 mod c_set_border_lerp_size;
@@ -49,8 +61,11 @@ mod c_set_border_warning_distance;
 mod c_set_container_content;
 mod c_set_container_property;
 mod c_set_container_slot;
+mod c_set_equipment;
+mod c_set_experience;
 mod c_set_health;
 mod c_set_held_item;
+mod c_set_player_team;
 mod c_set_time;
 mod c_set_title;
 mod c_sound_effect;
@@ -58,21 +73,30 @@ mod c_spawn_entity;
 mod c_store_cookie;
 mod c_subtitle;
 mod c_system_chat_message;
+mod c_take_item_entity;
 mod c_teleport_entity;
 mod c_transfer;
 mod c_unload_chunk;
+mod c_update_advancements;
+mod c_update_attributes;
 mod c_update_entity_pos;
 mod c_update_entity_pos_rot;
 mod c_update_entity_rot;
+mod c_update_mob_effect;
 mod c_update_objectives;
+mod c_update_recipes;
 mod c_update_score;
 mod c_worldevent;
 mod player_action;
+mod recipe_display;
 
 pub use bossevent_action::*;
 pub use c_acknowledge_block::*;
 pub use c_actionbar::*;
+pub use c_add_experience_orb::*;
+pub use c_award_stats::*;
 pub use c_block_destroy_stage::*;
+pub use c_block_entity_data::*;
 pub use c_block_event::*;
 pub use c_block_update::*;
 pub use c_boss_event::*;
@@ -92,25 +116,34 @@ pub use c_entity_metadata::*;
 pub use c_entity_sound_effect::*;
 pub use c_entity_status::*;
 pub use c_entity_velocity::*;
+pub use c_explosion::*;
 pub use c_game_event::*;
 pub use c_head_rot::*;
+pub use c_horse_screen_open::*;
 pub use c_hurt_animation::*;
 pub use c_initialize_world_border::*;
 pub use c_keep_alive::*;
 pub use c_level_event::*;
+pub use c_light_update::*;
 pub use c_login::*;
+pub use c_merchant_offers::*;
 pub use c_open_screen::*;
 pub use c_particle::*;
 pub use c_ping_response::*;
+pub use c_place_ghost_recipe::*;
 pub use c_play_disconnect::*;
 pub use c_player_abilities::*;
 pub use c_player_chat_message::*;
 pub use c_player_info_update::*;
 pub use c_player_position::*;
 pub use c_player_remove::*;
+pub use c_recipe_book_add::*;
+pub use c_recipe_book_settings::*;
 pub use c_remove_entities::*;
+pub use c_remove_mob_effect::*;
 pub use c_reset_score::*;
 pub use c_respawn::*;
+pub use c_section_blocks_update::*;
 pub use c_server_links::*;
 pub use c_set_border_center::*;
 pub use c_set_border_lerp_size::*;
@@ -120,8 +153,11 @@ pub use c_set_border_warning_distance::*;
 pub use c_set_container_content::*;
 pub use c_set_container_property::*;
 pub use c_set_container_slot::*;
+pub use c_set_equipment::*;
+pub use c_set_experience::*;
 pub use c_set_health::*;
 pub use c_set_held_item::*;
+pub use c_set_player_team::*;
 pub use c_set_time::*;
 pub use c_set_title::*;
 pub use c_sound_effect::*;
@@ -129,13 +165,19 @@ pub use c_spawn_entity::*;
 pub use c_store_cookie::*;
 pub use c_subtitle::*;
 pub use c_system_chat_message::*;
+pub use c_take_item_entity::*;
 pub use c_teleport_entity::*;
 pub use c_transfer::*;
 pub use c_unload_chunk::*;
+pub use c_update_advancements::*;
+pub use c_update_attributes::*;
 pub use c_update_entity_pos::*;
 pub use c_update_entity_pos_rot::*;
 pub use c_update_entity_rot::*;
+pub use c_update_mob_effect::*;
 pub use c_update_objectives::*;
+pub use c_update_recipes::*;
 pub use c_update_score::*;
 pub use c_worldevent::*;
 pub use player_action::*;
+pub use recipe_display::*;
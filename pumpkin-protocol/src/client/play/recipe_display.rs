@@ -0,0 +1,108 @@
+use bytes::BufMut;
+
+use crate::{bytebuf::ByteBufMut, codec::slot::Slot, VarInt};
+
+/// How the client shows a single slot of a recipe
+pub enum SlotDisplay {
+    Empty,
+    Item(VarInt),
+    ItemStack(Box<Slot>),
+    /// Cycles through the displays, used for ingredients accepting several items
+    Composite(Vec<SlotDisplay>),
+}
+
+impl SlotDisplay {
+    pub fn write(&self, bytebuf: &mut impl BufMut) {
+        match self {
+            Self::Empty => bytebuf.put_var_int(&VarInt(0)),
+            Self::Item(item_id) => {
+                bytebuf.put_var_int(&VarInt(2));
+                bytebuf.put_var_int(item_id);
+            }
+            Self::ItemStack(slot) => {
+                bytebuf.put_var_int(&VarInt(3));
+                slot.write(bytebuf);
+            }
+            Self::Composite(contents) => {
+                bytebuf.put_var_int(&VarInt(7));
+                bytebuf.put_list::<SlotDisplay>(contents, |p, display| display.write(p));
+            }
+        }
+    }
+}
+
+/// How the client shows a recipe in the recipe book
+pub enum RecipeDisplay {
+    CraftingShapeless {
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+    CraftingShaped {
+        width: VarInt,
+        height: VarInt,
+        /// Row major, empty slots are `SlotDisplay::Empty`
+        ingredients: Vec<SlotDisplay>,
+        result: SlotDisplay,
+        crafting_station: SlotDisplay,
+    },
+}
+
+impl RecipeDisplay {
+    pub fn write(&self, bytebuf: &mut impl BufMut) {
+        match self {
+            Self::CraftingShapeless {
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                bytebuf.put_var_int(&VarInt(0));
+                bytebuf.put_list::<SlotDisplay>(ingredients, |p, display| display.write(p));
+                result.write(bytebuf);
+                crafting_station.write(bytebuf);
+            }
+            Self::CraftingShaped {
+                width,
+                height,
+                ingredients,
+                result,
+                crafting_station,
+            } => {
+                bytebuf.put_var_int(&VarInt(1));
+                bytebuf.put_var_int(width);
+                bytebuf.put_var_int(height);
+                bytebuf.put_list::<SlotDisplay>(ingredients, |p, display| display.write(p));
+                result.write(bytebuf);
+                crafting_station.write(bytebuf);
+            }
+        }
+    }
+}
+
+pub struct RecipeDisplayEntry {
+    pub id: VarInt,
+    pub display: RecipeDisplay,
+    pub group: Option<VarInt>,
+    pub category: VarInt,
+    /// The items each ingredient accepts, the client uses these to filter for craftable recipes
+    pub crafting_requirements: Option<Vec<Vec<VarInt>>>,
+}
+
+impl RecipeDisplayEntry {
+    pub fn write(&self, bytebuf: &mut impl BufMut) {
+        bytebuf.put_var_int(&self.id);
+        self.display.write(bytebuf);
+        // Optional var ints are shifted by one, zero means absent
+        bytebuf.put_var_int(&VarInt(self.group.as_ref().map_or(0, |group| group.0 + 1)));
+        bytebuf.put_var_int(&self.category);
+        bytebuf.put_option(&self.crafting_requirements, |p, requirements| {
+            p.put_list::<Vec<VarInt>>(requirements, |p, items| {
+                // Holder sets are written as a tag when the length is zero, so the length is shifted by one
+                p.put_var_int(&VarInt(items.len() as i32 + 1));
+                for item in items {
+                    p.put_var_int(item);
+                }
+            });
+        });
+    }
+}
@@ -1,19 +1,40 @@
+use crate::bytebuf::ByteBufMut;
 use crate::VarInt;
+use bytes::{BufMut, BytesMut};
+use pumpkin_nbt::{
+    tag::NbtTag, BYTE_ARRAY_ID, BYTE_ID, COMPOUND_ID, DOUBLE_ID, END_ID, FLOAT_ID, INT_ARRAY_ID,
+    INT_ID, LIST_ID, LONG_ARRAY_ID, LONG_ID, SHORT_ID, STRING_ID,
+};
+use pumpkin_world::item::components::{
+    get_banner_pattern_id, get_dye_color_id, get_enchantment_id, get_potion_id, ItemComponents,
+    MapPostProcessing, BANNER_PATTERNS, DYE_COLORS, ENCHANTMENTS, MAX_CONTAINER_SLOTS, POTIONS,
+};
 use pumpkin_world::item::ItemStack;
-use serde::ser::SerializeSeq;
 use serde::{
     de::{self, SeqAccess},
     Deserialize, Serialize, Serializer,
 };
 
-#[derive(Debug, Clone)]
+// Protocol ids of the data component types we support
+const CUSTOM_DATA: i32 = 0;
+const DAMAGE: i32 = 3;
+const UNBREAKABLE: i32 = 4;
+const CUSTOM_NAME: i32 = 5;
+const ENCHANTMENTS_COMPONENT: i32 = 10;
+const REPAIR_COST: i32 = 17;
+const STORED_ENCHANTMENTS: i32 = 33;
+const MAP_ID: i32 = 36;
+const MAP_POST_PROCESSING: i32 = 38;
+const BUNDLE_CONTENTS: i32 = 40;
+const POTION_CONTENTS: i32 = 41;
+const BANNER_PATTERNS_COMPONENT: i32 = 59;
+const CONTAINER: i32 = 62;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Slot {
     item_count: VarInt,
     item_id: Option<VarInt>,
-    num_components_to_add: Option<VarInt>,
-    num_components_to_remove: Option<VarInt>,
-    components_to_add: Option<Vec<(VarInt, ())>>, // The second type depends on the varint
-    components_to_remove: Option<Vec<VarInt>>,
+    components: ItemComponents,
 }
 
 impl<'de> Deserialize<'de> for Slot {
@@ -33,41 +54,86 @@ impl<'de> Deserialize<'de> for Slot {
             where
                 A: SeqAccess<'de>,
             {
-                let item_count = seq
-                    .next_element::<VarInt>()?
-                    .ok_or(de::Error::custom("Failed to decode VarInt"))?;
+                let item_count = next_var_int(&mut seq)?;
                 if item_count.0 == 0 {
-                    return Ok(Slot {
-                        item_count: 0.into(),
-                        item_id: None,
-                        num_components_to_add: None,
-                        num_components_to_remove: None,
-                        components_to_add: None,
-                        components_to_remove: None,
-                    });
+                    return Ok(Slot::empty());
                 }
-                let item_id = seq
-                    .next_element::<VarInt>()?
-                    .ok_or(de::Error::custom("Failed to decode VarInt"))?;
-                let num_components_to_add = seq
-                    .next_element::<VarInt>()?
-                    .ok_or(de::Error::custom("Failed to decode VarInt"))?;
-                let num_components_to_remove = seq
-                    .next_element::<VarInt>()?
-                    .ok_or(de::Error::custom("Failed to decode VarInt"))?;
-                if num_components_to_add.0 != 0 || num_components_to_remove.0 != 0 {
-                    return Err(de::Error::custom(
-                        "Slot components are currently unsupported",
-                    ));
+                let item_id = next_var_int(&mut seq)?;
+                let num_components_to_add = next_var_int(&mut seq)?;
+                let num_components_to_remove = next_var_int(&mut seq)?;
+
+                let mut components = ItemComponents::default();
+                for _ in 0..num_components_to_add.0 {
+                    let component = next_var_int(&mut seq)?;
+                    match component.0 {
+                        CUSTOM_DATA => match next_network_nbt(&mut seq)? {
+                            NbtTag::Compound(custom_data) => {
+                                components.custom_data = Some(custom_data);
+                            }
+                            _ => return Err(de::Error::custom("Custom data must be a compound")),
+                        },
+                        DAMAGE => components.damage = Some(next_var_int(&mut seq)?.0),
+                        UNBREAKABLE => {
+                            // Whether it's shown in the tooltip
+                            next_bool(&mut seq)?;
+                            components.unbreakable = true;
+                        }
+                        CUSTOM_NAME => match next_network_nbt(&mut seq)? {
+                            NbtTag::String(custom_name) => {
+                                components.custom_name = Some(custom_name);
+                            }
+                            _ => {
+                                return Err(de::Error::custom(
+                                    "Styled custom names are currently unsupported",
+                                ))
+                            }
+                        },
+                        ENCHANTMENTS_COMPONENT => {
+                            components.enchantments = next_enchantments(&mut seq)?;
+                        }
+                        REPAIR_COST => components.repair_cost = Some(next_var_int(&mut seq)?.0),
+                        STORED_ENCHANTMENTS => {
+                            components.stored_enchantments = next_enchantments(&mut seq)?;
+                        }
+                        MAP_ID => components.map_id = Some(next_var_int(&mut seq)?.0),
+                        MAP_POST_PROCESSING => {
+                            components.map_post_processing =
+                                Some(match next_var_int(&mut seq)?.0 {
+                                    0 => MapPostProcessing::Lock,
+                                    1 => MapPostProcessing::Scale,
+                                    _ => {
+                                        return Err(de::Error::custom(
+                                            "Unknown map post processing",
+                                        ))
+                                    }
+                                });
+                        }
+                        BUNDLE_CONTENTS => {
+                            components.bundle_contents = next_bundle_contents(&mut seq)?;
+                        }
+                        POTION_CONTENTS => components.potion = next_potion(&mut seq)?,
+                        BANNER_PATTERNS_COMPONENT => {
+                            components.banner_patterns = next_banner_patterns(&mut seq)?;
+                        }
+                        CONTAINER => components.container = next_container(&mut seq)?,
+                        // We can't skip components we don't know the size of
+                        _ => {
+                            return Err(de::Error::custom(format!(
+                                "Slot component {} is currently unsupported",
+                                component.0
+                            )))
+                        }
+                    }
+                }
+                // We don't track default components, so there is nothing to remove them from
+                for _ in 0..num_components_to_remove.0 {
+                    next_var_int(&mut seq)?;
                 }
 
                 Ok(Slot {
                     item_count,
                     item_id: Some(item_id),
-                    num_components_to_add: Some(num_components_to_add),
-                    num_components_to_remove: Some(num_components_to_remove),
-                    components_to_add: None,
-                    components_to_remove: None,
+                    components,
                 })
             }
         }
@@ -76,102 +142,464 @@ impl<'de> Deserialize<'de> for Slot {
     }
 }
 
+fn next_var_int<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<VarInt, A::Error> {
+    seq.next_element::<VarInt>()?
+        .ok_or(de::Error::custom("Failed to decode VarInt"))
+}
+
+fn next_bool<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<bool, A::Error> {
+    seq.next_element::<bool>()?
+        .ok_or(de::Error::custom("Failed to decode bool"))
+}
+
+fn next_u8<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<u8, A::Error> {
+    seq.next_element::<u8>()?
+        .ok_or(de::Error::custom("Failed to decode byte"))
+}
+
+fn next_i32<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<i32, A::Error> {
+    seq.next_element::<i32>()?
+        .ok_or(de::Error::custom("Failed to decode int"))
+}
+
+/// Network NBT has no name for the root tag, see `put_network_nbt`
+fn next_network_nbt<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<NbtTag, A::Error> {
+    let tag_id = next_u8(seq)?;
+    let mut data = BytesMut::new();
+    copy_nbt_data(seq, tag_id, &mut data)?;
+    NbtTag::deserialize_data(&mut data.freeze(), tag_id).map_err(de::Error::custom)
+}
+
+/// Copies the bytes of a tag's payload, so the NBT crate can parse it
+fn copy_nbt_data<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+    tag_id: u8,
+    data: &mut BytesMut,
+) -> Result<(), A::Error> {
+    match tag_id {
+        END_ID => {}
+        BYTE_ID => copy_bytes(seq, 1, data)?,
+        SHORT_ID => copy_bytes(seq, 2, data)?,
+        INT_ID | FLOAT_ID => copy_bytes(seq, 4, data)?,
+        LONG_ID | DOUBLE_ID => copy_bytes(seq, 8, data)?,
+        BYTE_ARRAY_ID => copy_array(seq, 1, data)?,
+        INT_ARRAY_ID => copy_array(seq, 4, data)?,
+        LONG_ARRAY_ID => copy_array(seq, 8, data)?,
+        STRING_ID => copy_string(seq, data)?,
+        LIST_ID => {
+            let element_id = next_u8(seq)?;
+            let len = next_i32(seq)?;
+            data.put_u8(element_id);
+            data.put_i32(len);
+            for _ in 0..len {
+                copy_nbt_data(seq, element_id, data)?;
+            }
+        }
+        COMPOUND_ID => loop {
+            let child_id = next_u8(seq)?;
+            data.put_u8(child_id);
+            if child_id == END_ID {
+                break;
+            }
+            copy_string(seq, data)?;
+            copy_nbt_data(seq, child_id, data)?;
+        },
+        _ => return Err(de::Error::custom(format!("Unknown NBT tag id {tag_id}"))),
+    }
+    Ok(())
+}
+
+fn copy_bytes<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+    len: usize,
+    data: &mut BytesMut,
+) -> Result<(), A::Error> {
+    for _ in 0..len {
+        data.put_u8(next_u8(seq)?);
+    }
+    Ok(())
+}
+
+fn copy_array<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+    element_size: usize,
+    data: &mut BytesMut,
+) -> Result<(), A::Error> {
+    let len = next_i32(seq)?;
+    let len = usize::try_from(len).map_err(|_| de::Error::custom("Negative NBT array length"))?;
+    data.put_i32(len as i32);
+    copy_bytes(seq, len * element_size, data)
+}
+
+fn copy_string<'de, A: SeqAccess<'de>>(seq: &mut A, data: &mut BytesMut) -> Result<(), A::Error> {
+    let len = seq
+        .next_element::<u16>()?
+        .ok_or(de::Error::custom("Failed to decode string length"))?;
+    data.put_u16(len);
+    copy_bytes(seq, len.into(), data)
+}
+
+fn next_enchantments<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Vec<(String, i32)>, A::Error> {
+    let count = next_var_int(seq)?;
+    let mut enchantments = Vec::new();
+    for _ in 0..count.0 {
+        let id = next_var_int(seq)?;
+        let level = next_var_int(seq)?;
+        let name = usize::try_from(id.0)
+            .ok()
+            .and_then(|id| ENCHANTMENTS.get(id))
+            .ok_or(de::Error::custom("Unknown enchantment"))?;
+        enchantments.push((name.to_string(), level.0));
+    }
+    // Whether they are shown in the tooltip
+    next_bool(seq)?;
+    Ok(enchantments)
+}
+
+fn next_bundle_contents<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Vec<ItemStack>, A::Error> {
+    let count = next_var_int(seq)?;
+    let mut items = Vec::new();
+    for _ in 0..count.0 {
+        let item = seq
+            .next_element::<Slot>()?
+            .and_then(Slot::to_item)
+            .ok_or(de::Error::custom("Invalid item in bundle"))?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+fn next_container<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Vec<Option<ItemStack>>, A::Error> {
+    let count = next_var_int(seq)?;
+    if !usize::try_from(count.0).is_ok_and(|count| count <= MAX_CONTAINER_SLOTS) {
+        return Err(de::Error::custom("Too many container slots"));
+    }
+    let mut items = Vec::new();
+    for _ in 0..count.0 {
+        // Empty slots are sent as empty stacks
+        let slot = seq
+            .next_element::<Slot>()?
+            .ok_or(de::Error::custom("Missing container slot"))?;
+        items.push(slot.to_item());
+    }
+    Ok(items)
+}
+
+fn next_potion<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Option<String>, A::Error> {
+    let potion = if next_bool(seq)? {
+        let id = next_var_int(seq)?;
+        let name = usize::try_from(id.0)
+            .ok()
+            .and_then(|id| POTIONS.get(id))
+            .ok_or(de::Error::custom("Unknown potion"))?;
+        Some(name.to_string())
+    } else {
+        None
+    };
+    let has_custom_color = next_bool(seq)?;
+    let custom_effects = next_var_int(seq)?;
+    if has_custom_color || custom_effects.0 != 0 {
+        return Err(de::Error::custom(
+            "Custom potion colors and effects are currently unsupported",
+        ));
+    }
+    // Custom name, which is only set for potions which aren't brewable
+    if next_bool(seq)? {
+        return Err(de::Error::custom(
+            "Custom potion names are currently unsupported",
+        ));
+    }
+    Ok(potion)
+}
+
+fn next_banner_patterns<'de, A: SeqAccess<'de>>(
+    seq: &mut A,
+) -> Result<Vec<(String, String)>, A::Error> {
+    let count = next_var_int(seq)?;
+    let mut layers = Vec::new();
+    for _ in 0..count.0 {
+        // Holders are shifted by one, zero means the pattern is given inline
+        let holder = next_var_int(seq)?;
+        let pattern = usize::try_from(holder.0 - 1)
+            .ok()
+            .and_then(|id| BANNER_PATTERNS.get(id))
+            .ok_or(de::Error::custom("Inline or unknown banner pattern"))?;
+        let color = usize::try_from(next_var_int(seq)?.0)
+            .ok()
+            .and_then(|id| DYE_COLORS.get(id))
+            .ok_or(de::Error::custom("Unknown dye color"))?;
+        layers.push((pattern.to_string(), color.to_string()));
+    }
+    Ok(layers)
+}
+
 impl Serialize for Slot {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        if self.item_count == 0.into() {
-            let mut s = serializer.serialize_seq(Some(1))?;
-            s.serialize_element(&self.item_count)?;
-            s.end()
-        } else {
-            match (&self.num_components_to_add, &self.num_components_to_remove) {
-                (Some(to_add), Some(to_remove)) => {
-                    let mut s = serializer.serialize_seq(Some(6))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(to_add)?;
-                    s.serialize_element(to_remove)?;
-                    s.serialize_element(self.components_to_add.as_ref().unwrap())?;
-                    s.serialize_element(self.components_to_remove.as_ref().unwrap())?;
-                    s.end()
-                }
-                (None, Some(to_remove)) => {
-                    let mut s = serializer.serialize_seq(Some(5))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(to_remove)?;
-                    s.serialize_element(self.components_to_remove.as_ref().unwrap())?;
-                    s.end()
-                }
-                (Some(to_add), None) => {
-                    let mut s = serializer.serialize_seq(Some(5))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(to_add)?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(self.components_to_add.as_ref().unwrap())?;
-                    s.end()
-                }
-                (None, None) => {
-                    let mut s = serializer.serialize_seq(Some(4))?;
-                    s.serialize_element(&self.item_count)?;
-                    s.serialize_element(&self.item_id.as_ref().unwrap())?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.serialize_element(&VarInt(0))?;
-                    s.end()
+        let mut buf = BytesMut::new();
+        self.write(&mut buf);
+        serializer.serialize_bytes(&buf)
+    }
+}
+
+impl Slot {
+    /// Writes the slot for packets which are written by hand
+    pub fn write(&self, buf: &mut impl BufMut) {
+        buf.put_var_int(&self.item_count);
+        if let Some(item_id) = self.item_id.as_ref().filter(|_| self.item_count.0 != 0) {
+            buf.put_var_int(item_id);
+            self.write_components(buf);
+        }
+    }
+
+    fn write_components(&self, buf: &mut impl BufMut) {
+        let components = &self.components;
+        let mut added: i32 = 0;
+        let mut data = BytesMut::new();
+        if let Some(custom_data) = &components.custom_data {
+            data.put_var_int(&CUSTOM_DATA.into());
+            put_network_nbt(&mut data, &NbtTag::Compound(custom_data.clone()));
+            added += 1;
+        }
+        if let Some(damage) = components.damage {
+            data.put_var_int(&DAMAGE.into());
+            data.put_var_int(&damage.into());
+            added += 1;
+        }
+        if components.unbreakable {
+            data.put_var_int(&UNBREAKABLE.into());
+            data.put_bool(true);
+            added += 1;
+        }
+        if let Some(custom_name) = &components.custom_name {
+            // A plain string is a valid text component
+            data.put_var_int(&CUSTOM_NAME.into());
+            put_network_nbt(&mut data, &NbtTag::String(custom_name.clone()));
+            added += 1;
+        }
+        if !components.enchantments.is_empty() {
+            data.put_var_int(&ENCHANTMENTS_COMPONENT.into());
+            put_enchantments(&mut data, &components.enchantments);
+            added += 1;
+        }
+        if let Some(repair_cost) = components.repair_cost {
+            data.put_var_int(&REPAIR_COST.into());
+            data.put_var_int(&repair_cost.into());
+            added += 1;
+        }
+        if !components.stored_enchantments.is_empty() {
+            data.put_var_int(&STORED_ENCHANTMENTS.into());
+            put_enchantments(&mut data, &components.stored_enchantments);
+            added += 1;
+        }
+        if !components.bundle_contents.is_empty() {
+            data.put_var_int(&BUNDLE_CONTENTS.into());
+            data.put_var_int(&(components.bundle_contents.len() as i32).into());
+            for item in &components.bundle_contents {
+                Slot::from(item).write(&mut data);
+            }
+            added += 1;
+        }
+        if let Some(potion) = components.potion.as_deref().and_then(get_potion_id) {
+            data.put_var_int(&POTION_CONTENTS.into());
+            data.put_bool(true);
+            data.put_var_int(&(potion as i32).into());
+            // No custom color, effects or name
+            data.put_bool(false);
+            data.put_var_int(&0.into());
+            data.put_bool(false);
+            added += 1;
+        }
+        if let Some(map_id) = components.map_id {
+            data.put_var_int(&MAP_ID.into());
+            data.put_var_int(&map_id.into());
+            added += 1;
+        }
+        if let Some(post_processing) = components.map_post_processing {
+            data.put_var_int(&MAP_POST_PROCESSING.into());
+            data.put_var_int(&(post_processing as i32).into());
+            added += 1;
+        }
+        if !components.banner_patterns.is_empty() {
+            data.put_var_int(&BANNER_PATTERNS_COMPONENT.into());
+            put_banner_patterns(&mut data, &components.banner_patterns);
+            added += 1;
+        }
+        if !components.container.is_empty() {
+            data.put_var_int(&CONTAINER.into());
+            data.put_var_int(&(components.container.len() as i32).into());
+            for item in &components.container {
+                match item {
+                    Some(item) => Slot::from(item).write(&mut data),
+                    None => Slot::empty().write(&mut data),
                 }
             }
+            added += 1;
         }
+        buf.put_var_int(&added.into());
+        buf.put_var_int(&0.into());
+        buf.put(data);
     }
-}
 
-impl Slot {
+    /// Returns `None` for empty slots and for ids or counts which don't fit into an `ItemStack`
     pub fn to_item(self) -> Option<ItemStack> {
-        let item_id = self.item_id?.0.try_into().unwrap();
+        let item_id = self.item_id?.0.try_into().ok()?;
         Some(ItemStack {
             item_id,
-            item_count: self.item_count.0.try_into().unwrap(),
+            item_count: self.item_count.0.try_into().ok()?,
+            components: self.components,
         })
     }
 
-    pub const fn empty() -> Self {
+    pub fn is_empty(&self) -> bool {
+        self.item_count.0 == 0
+    }
+
+    pub fn empty() -> Self {
         Slot {
             item_count: VarInt(0),
             item_id: None,
-            num_components_to_add: None,
-            num_components_to_remove: None,
-            components_to_add: None,
-            components_to_remove: None,
+            components: ItemComponents::default(),
         }
     }
 }
 
+/// Network NBT has no name for the root tag
+fn put_network_nbt(buf: &mut BytesMut, tag: &NbtTag) {
+    buf.put_u8(tag.get_type_id());
+    buf.put(tag.serialize_data());
+}
+
+fn put_enchantments(buf: &mut BytesMut, enchantments: &[(String, i32)]) {
+    let known: Vec<(usize, i32)> = enchantments
+        .iter()
+        .filter_map(|(name, level)| Some((get_enchantment_id(name)?, *level)))
+        .collect();
+    buf.put_var_int(&(known.len() as i32).into());
+    for (id, level) in known {
+        buf.put_var_int(&(id as i32).into());
+        buf.put_var_int(&level.into());
+    }
+    // Show in tooltip
+    buf.put_bool(true);
+}
+
+fn put_banner_patterns(buf: &mut BytesMut, layers: &[(String, String)]) {
+    let known: Vec<(usize, usize)> = layers
+        .iter()
+        .filter_map(|(pattern, color)| {
+            Some((get_banner_pattern_id(pattern)?, get_dye_color_id(color)?))
+        })
+        .collect();
+    buf.put_var_int(&(known.len() as i32).into());
+    for (pattern, color) in known {
+        buf.put_var_int(&(pattern as i32 + 1).into());
+        buf.put_var_int(&(color as i32).into());
+    }
+}
+
 impl From<&ItemStack> for Slot {
     fn from(item: &ItemStack) -> Self {
         Slot {
             item_count: item.item_count.into(),
             item_id: Some(VarInt(item.item_id as i32)),
-            // TODO: add these
-            num_components_to_add: None,
-            num_components_to_remove: None,
-            components_to_add: None,
-            components_to_remove: None,
+            components: item.components.clone(),
         }
     }
 }
 
 impl From<Option<&ItemStack>> for Slot {
     fn from(item: Option<&ItemStack>) -> Self {
-        item.map(Slot::from).unwrap_or(Slot::empty())
+        item.map_or_else(Slot::empty, Slot::from)
     }
 }
 
 impl From<&Option<ItemStack>> for Slot {
     fn from(item: &Option<ItemStack>) -> Self {
-        item.map(|stack| Self::from(&stack))
-            .unwrap_or(Slot::empty())
+        Self::from(item.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bytes::{Bytes, BytesMut};
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+    use pumpkin_world::item::{
+        components::{ItemComponents, MapPostProcessing},
+        ItemStack,
+    };
+    use serde::Deserialize;
+
+    use crate::{bytebuf::deserializer::Deserializer, VarInt};
+
+    use super::Slot;
+
+    fn reserialize(slot: &Slot) -> Slot {
+        let mut bytes = BytesMut::new();
+        slot.write(&mut bytes);
+        let mut bytes = Bytes::from(bytes);
+        let slot = Slot::deserialize(Deserializer::new(&mut bytes)).unwrap();
+        assert!(bytes.is_empty(), "The whole slot should be read");
+        slot
+    }
+
+    #[test]
+    fn empty_slot_reserialize() {
+        assert_eq!(reserialize(&Slot::empty()), Slot::empty());
+    }
+
+    #[test]
+    fn every_component_reserialize() {
+        let mut custom_data = NbtCompound::new();
+        custom_data.put("byte".to_string(), NbtTag::Byte(1));
+        custom_data.put("name".to_string(), NbtTag::String("Pümpkin".to_string()));
+        custom_data.put(
+            "list".to_string(),
+            NbtTag::List(vec![NbtTag::Int(1), NbtTag::Int(2)]),
+        );
+        custom_data.put("longs".to_string(), NbtTag::LongArray(vec![i64::MIN, 0]));
+        let mut nested = NbtCompound::new();
+        nested.put("double".to_string(), NbtTag::Double(0.5));
+        custom_data.put("nested".to_string(), NbtTag::Compound(nested));
+
+        let mut item = ItemStack::new(3, 1);
+        item.components = ItemComponents {
+            custom_data: Some(custom_data),
+            damage: Some(12),
+            unbreakable: true,
+            custom_name: Some("Named".to_string()),
+            enchantments: vec![("sharpness".to_string(), 5)],
+            repair_cost: Some(3),
+            stored_enchantments: vec![("mending".to_string(), 1)],
+            potion: Some("water".to_string()),
+            map_id: Some(7),
+            map_post_processing: Some(MapPostProcessing::Scale),
+            banner_patterns: vec![("border".to_string(), "orange".to_string())],
+            bundle_contents: vec![ItemStack::new(2, 1), ItemStack::new(1, 2)],
+            bundle_selected_item: None,
+            container: vec![None, Some(ItemStack::new(4, 3))],
+        };
+
+        let slot = Slot::from(&item);
+        let reserialized = reserialize(&slot);
+        assert_eq!(reserialized, slot);
+        assert_eq!(reserialized.to_item(), Some(item));
+    }
+    #[test]
+    fn only_valid_slots_have_an_item() {
+        let slot = |item_count, item_id| Slot {
+            item_count: VarInt(item_count),
+            item_id: Some(VarInt(item_id)),
+            components: ItemComponents::default(),
+        };
+        assert!(Slot::empty().is_empty());
+        assert_eq!(Slot::empty().to_item(), None);
+        assert_eq!(slot(5, 1).to_item(), Some(ItemStack::new(5, 1)));
+        // Counts and ids a client can send but no item stack can hold
+        assert_eq!(slot(300, 1).to_item(), None);
+        assert_eq!(slot(1, -1).to_item(), None);
+        assert_eq!(slot(1, 70_000).to_item(), None);
     }
 }
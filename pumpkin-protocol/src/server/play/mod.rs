@@ -1,3 +1,4 @@
+mod s_bundle_item_selected;
 mod s_chat_command;
 mod s_chat_message;
 mod s_click_container;
@@ -7,11 +8,13 @@ mod s_client_tick_end;
 mod s_close_container;
 mod s_command_suggestion;
 mod s_confirm_teleport;
+mod s_container_button_click;
 mod s_cookie_response;
 mod s_interact;
 mod s_keep_alive;
 mod s_pick_item;
 mod s_ping_request;
+mod s_place_recipe;
 mod s_player_abilities;
 mod s_player_action;
 mod s_player_command;
@@ -20,12 +23,17 @@ mod s_player_input;
 mod s_player_position;
 mod s_player_position_rotation;
 mod s_player_rotation;
+mod s_recipe_book_change_settings;
+mod s_recipe_book_seen_recipe;
+mod s_rename_item;
+mod s_select_trade;
 mod s_set_creative_slot;
 mod s_set_held_item;
 mod s_swing_arm;
 mod s_use_item;
 mod s_use_item_on;
 
+pub use s_bundle_item_selected::*;
 pub use s_chat_command::*;
 pub use s_chat_message::*;
 pub use s_click_container::*;
@@ -35,11 +43,13 @@ pub use s_client_tick_end::*;
 pub use s_close_container::*;
 pub use s_command_suggestion::*;
 pub use s_confirm_teleport::*;
+pub use s_container_button_click::*;
 pub use s_cookie_response::*;
 pub use s_interact::*;
 pub use s_keep_alive::*;
 pub use s_pick_item::*;
 pub use s_ping_request::*;
+pub use s_place_recipe::*;
 pub use s_player_abilities::*;
 pub use s_player_action::*;
 pub use s_player_command::*;
@@ -48,6 +58,10 @@ pub use s_player_input::*;
 pub use s_player_position::*;
 pub use s_player_position_rotation::*;
 pub use s_player_rotation::*;
+pub use s_recipe_book_change_settings::*;
+pub use s_recipe_book_seen_recipe::*;
+pub use s_rename_item::*;
+pub use s_select_trade::*;
 pub use s_set_creative_slot::*;
 pub use s_set_held_item::*;
 pub use s_swing_arm::*;
@@ -0,0 +1,12 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent when the player scrolls through the items of a bundle in their open window
+#[derive(serde::Deserialize)]
+#[server_packet("play:bundle_item_selected")]
+pub struct SBundleItemSelected {
+    pub slot_id: VarInt,
+    /// The index of the hovered item, -1 if none is selected
+    pub selected_item_index: VarInt,
+}
@@ -0,0 +1,11 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent when the player clicks a button in a container, e.g. a stonecutter recipe or a loom pattern
+#[derive(serde::Deserialize)]
+#[server_packet("play:container_button_click")]
+pub struct SContainerButtonClick {
+    pub window_id: VarInt,
+    pub button_id: VarInt,
+}
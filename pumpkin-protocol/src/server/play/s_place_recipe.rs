@@ -0,0 +1,13 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent when the player clicks on a recipe in the recipe book
+#[derive(serde::Deserialize)]
+#[server_packet("play:place_recipe")]
+pub struct SPlaceRecipe {
+    pub window_id: VarInt,
+    pub recipe_id: VarInt,
+    /// Shift clicking places as many crafts as possible
+    pub make_all: bool,
+}
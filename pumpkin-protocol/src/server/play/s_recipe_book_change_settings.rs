@@ -0,0 +1,12 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+#[derive(serde::Deserialize)]
+#[server_packet("play:recipe_book_change_settings")]
+pub struct SRecipeBookChangeSettings {
+    /// 0: crafting, 1: furnace, 2: blast furnace, 3: smoker
+    pub book_type: VarInt,
+    pub book_open: bool,
+    pub filter_active: bool,
+}
@@ -0,0 +1,10 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent when the player looks at a highlighted recipe
+#[derive(serde::Deserialize)]
+#[server_packet("play:recipe_book_seen_recipe")]
+pub struct SRecipeBookSeenRecipe {
+    pub recipe_id: VarInt,
+}
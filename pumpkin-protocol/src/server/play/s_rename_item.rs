@@ -0,0 +1,8 @@
+use pumpkin_macros::server_packet;
+
+/// Sent while the player types a name into an anvil
+#[derive(serde::Deserialize)]
+#[server_packet("play:rename_item")]
+pub struct SRenameItem {
+    pub item_name: String,
+}
@@ -0,0 +1,10 @@
+use pumpkin_macros::server_packet;
+
+use crate::VarInt;
+
+/// Sent when the player clicks on a trade in the merchant screen
+#[derive(serde::Deserialize)]
+#[server_packet("play:select_trade")]
+pub struct SSelectTrade {
+    pub selected_slot: VarInt,
+}
@@ -0,0 +1,231 @@
+use std::sync::LazyLock;
+
+use indexmap::IndexMap;
+use pumpkin_core::text::TextComponent;
+use serde::Deserialize;
+
+use crate::{is_in_tag, TagCategory};
+
+/// The advancements by their id, parents come before their children
+pub static ADVANCEMENTS: LazyLock<IndexMap<String, Advancement>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../assets/advancements.json"))
+        .expect("Could not parse advancements.json registry.")
+});
+
+pub fn get_advancement(id: &str) -> Option<&'static Advancement> {
+    ADVANCEMENTS
+        .get(id)
+        .or_else(|| ADVANCEMENTS.get(&format!("minecraft:{id}")))
+}
+
+/// An advancement like vanilla loads it from its JSON file
+#[derive(Deserialize)]
+pub struct Advancement {
+    pub parent: Option<String>,
+    pub display: Option<AdvancementDisplay>,
+    pub criteria: IndexMap<String, Criterion>,
+    /// Every group needs one of its criteria, all criteria are needed if there are no groups
+    #[serde(default)]
+    requirements: Vec<Vec<String>>,
+    #[serde(default)]
+    pub rewards: AdvancementRewards,
+}
+
+impl Advancement {
+    /// The groups of criteria, every group needs one of its criteria to be done
+    pub fn requirements(&self) -> Vec<Vec<String>> {
+        if self.requirements.is_empty() {
+            self.criteria
+                .keys()
+                .map(|criterion| vec![criterion.clone()])
+                .collect()
+        } else {
+            self.requirements.clone()
+        }
+    }
+
+    /// Whether the criteria which are done complete the advancement
+    pub fn is_done(&self, done: impl Fn(&str) -> bool) -> bool {
+        !self.criteria.is_empty()
+            && self
+                .requirements()
+                .iter()
+                .all(|group| group.iter().any(|criterion| done(criterion)))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AdvancementDisplay {
+    pub icon: AdvancementIcon,
+    pub title: TextComponent<'static>,
+    pub description: TextComponent<'static>,
+    #[serde(default)]
+    pub frame: AdvancementFrame,
+    pub background: Option<String>,
+    #[serde(default = "default_true")]
+    pub show_toast: bool,
+    #[serde(default = "default_true")]
+    pub announce_to_chat: bool,
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+pub struct AdvancementIcon {
+    pub id: String,
+    #[serde(default = "default_count")]
+    pub count: u8,
+}
+
+const fn default_count() -> u8 {
+    1
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvancementFrame {
+    #[default]
+    Task,
+    Challenge,
+    Goal,
+}
+
+impl AdvancementFrame {
+    /// The name of the frame in translation keys, like `chat.type.advancement.task`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Task => "task",
+            Self::Challenge => "challenge",
+            Self::Goal => "goal",
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+pub struct AdvancementRewards {
+    #[serde(default)]
+    pub experience: i32,
+}
+
+#[derive(Deserialize)]
+pub struct Criterion {
+    pub trigger: String,
+    #[serde(default)]
+    pub conditions: CriterionConditions,
+}
+
+/// The conditions of the triggers the server fires, conditions of other triggers are ignored
+#[derive(Deserialize, Default)]
+pub struct CriterionConditions {
+    /// Each of the items has to be in the inventory, `inventory_changed`
+    #[serde(default)]
+    pub items: Vec<ItemPredicate>,
+    /// Where the block was placed, `placed_block`
+    #[serde(default)]
+    pub location: Vec<LocationCondition>,
+    /// The entity which was killed, `player_killed_entity`
+    #[serde(default)]
+    pub entity: Vec<EntityCondition>,
+}
+
+#[derive(Deserialize)]
+pub struct ItemPredicate {
+    /// An item or a tag of items starting with `#`
+    pub items: Option<String>,
+}
+
+impl ItemPredicate {
+    pub fn matches(&self, item: &str) -> bool {
+        match self.items.as_deref() {
+            Some(expected) => match expected.strip_prefix('#') {
+                Some(tag) => is_in_tag(TagCategory::Item, item, tag),
+                None => same_id(expected, item),
+            },
+            None => true,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct LocationCondition {
+    pub block: Option<String>,
+}
+
+impl LocationCondition {
+    pub fn matches(&self, block: &str) -> bool {
+        self.block
+            .as_deref()
+            .is_none_or(|expected| same_id(expected, block))
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EntityCondition {
+    pub predicate: Option<EntityPredicate>,
+}
+
+#[derive(Deserialize)]
+pub struct EntityPredicate {
+    #[serde(rename = "type")]
+    pub entity_type: Option<String>,
+}
+
+impl EntityCondition {
+    pub fn matches(&self, entity_type: &str) -> bool {
+        self.predicate
+            .as_ref()
+            .and_then(|predicate| predicate.entity_type.as_deref())
+            .is_none_or(|expected| match expected.strip_prefix('#') {
+                Some(tag) => is_in_tag(TagCategory::Entity, entity_type, tag),
+                None => same_id(expected, entity_type),
+            })
+    }
+}
+
+/// Compares ids with or without the `minecraft:` namespace
+fn same_id(a: &str, b: &str) -> bool {
+    a.strip_prefix("minecraft:").unwrap_or(a) == b.strip_prefix("minecraft:").unwrap_or(b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_advancement, ADVANCEMENTS};
+
+    #[test]
+    fn parents_and_requirements() {
+        for (id, advancement) in ADVANCEMENTS.iter() {
+            if let Some(parent) = &advancement.parent {
+                let parent = ADVANCEMENTS.get_index_of(parent.as_str()).unwrap();
+                assert!(
+                    parent < ADVANCEMENTS.get_index_of(id.as_str()).unwrap(),
+                    "{id}"
+                );
+            }
+            for group in advancement.requirements() {
+                for criterion in group {
+                    assert!(advancement.criteria.contains_key(&criterion), "{id}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn criteria() {
+        let mine_stone = get_advancement("story/mine_stone").unwrap();
+        let conditions = &mine_stone.criteria["get_stone"].conditions;
+        assert!(conditions.items[0].matches("minecraft:cobblestone"));
+        assert!(!conditions.items[0].matches("minecraft:dirt"));
+
+        let armor = get_advancement("minecraft:story/obtain_armor").unwrap();
+        assert!(armor.is_done(|criterion| criterion == "iron_boots"));
+        assert!(!armor.is_done(|_| false));
+
+        let kill = get_advancement("adventure/kill_a_mob").unwrap();
+        assert!(kill.criteria["minecraft:zombie"].conditions.entity[0].matches("zombie"));
+        assert!(!kill.criteria["minecraft:zombie"].conditions.entity[0].matches("pig"));
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+pub static BREWING_MIXES: LazyLock<BrewingMixes> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../assets/brewing.json"))
+        .expect("Could not parse brewing.json registry.")
+});
+
+#[derive(Deserialize)]
+pub struct BrewingMixes {
+    /// Items which can hold a potion and be put into the bottle slots
+    pub containers: Vec<String>,
+    /// Mixes changing the item, e.g. potion to splash potion
+    pub container_mixes: Vec<BrewingMix>,
+    /// Mixes changing the potion the item holds
+    pub potion_mixes: Vec<BrewingMix>,
+}
+
+#[derive(Deserialize)]
+pub struct BrewingMix {
+    pub from: String,
+    pub ingredient: String,
+    pub to: String,
+}
+
+impl BrewingMixes {
+    pub fn is_container(&self, item: &str) -> bool {
+        self.containers.iter().any(|container| container == item)
+    }
+
+    /// Whether the item can be put into the ingredient slot
+    pub fn is_ingredient(&self, item: &str) -> bool {
+        self.container_mixes
+            .iter()
+            .chain(&self.potion_mixes)
+            .any(|mix| mix.ingredient == item)
+    }
+
+    pub fn container_mix(&self, container: &str, ingredient: &str) -> Option<&str> {
+        find_mix(&self.container_mixes, container, ingredient)
+    }
+
+    pub fn potion_mix(&self, potion: &str, ingredient: &str) -> Option<&str> {
+        find_mix(&self.potion_mixes, potion, ingredient)
+    }
+}
+
+fn find_mix<'a>(mixes: &'a [BrewingMix], from: &str, ingredient: &str) -> Option<&'a str> {
+    mixes
+        .iter()
+        .find(|mix| mix.from == from && mix.ingredient == ingredient)
+        .map(|mix| mix.to.as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::BREWING_MIXES;
+
+    #[test]
+    fn mixes() {
+        assert_eq!(
+            BREWING_MIXES.potion_mix("minecraft:water", "minecraft:nether_wart"),
+            Some("minecraft:awkward")
+        );
+        assert_eq!(
+            BREWING_MIXES.container_mix("minecraft:potion", "minecraft:gunpowder"),
+            Some("minecraft:splash_potion")
+        );
+        assert!(BREWING_MIXES.is_ingredient("minecraft:dragon_breath"));
+        assert!(!BREWING_MIXES.is_ingredient("minecraft:dirt"));
+    }
+}
@@ -4,4 +4,27 @@ use serde::{Deserialize, Serialize};
 pub struct Enchantment {
     // TODO: Add things :D
     //  description: Text<'static>,
+    pub max_level: i32,
+    /// Experience levels an anvil charges per level of this enchantment
+    pub anvil_cost: i32,
+    /// Item tag of the items this enchantment can be applied to
+    pub supported_items: String,
+    /// The lowest enchanting power this enchantment can be rolled with
+    pub min_cost: EnchantmentCost,
+    /// Enchantment tag of the enchantments which can't be on the same item, e.g. Sharpness and
+    /// Smite
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_set: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EnchantmentCost {
+    pub base: i32,
+    pub per_level_above_first: i32,
+}
+
+impl EnchantmentCost {
+    pub fn at_level(&self, level: i32) -> i32 {
+        self.base + self.per_level_above_first * (level - 1)
+    }
 }
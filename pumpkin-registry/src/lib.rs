@@ -1,27 +1,34 @@
 use std::sync::LazyLock;
 
+pub use advancement::{
+    get_advancement, Advancement, AdvancementDisplay, AdvancementFrame, Criterion, ADVANCEMENTS,
+};
 use banner_pattern::BannerPattern;
 use biome::Biome;
+pub use brewing::{BrewingMix, BrewingMixes, BREWING_MIXES};
 use chat_type::ChatType;
 use damage_type::DamageType;
 use dimension::Dimension;
-use enchantment::Enchantment;
+pub use enchantment::{Enchantment, EnchantmentCost};
 use indexmap::IndexMap;
 use instrument::Instrument;
 use jukebox_song::JukeboxSong;
 use paint::Painting;
 use pumpkin_protocol::{client::config::RegistryEntry, codec::identifier::Identifier};
 pub use recipe::{
-    flatten_3x3, IngredientSlot, IngredientType, Recipe, RecipeResult, RecipeType, RECIPES,
+    flatten_3x3, IngredientSlot, IngredientType, Recipe, RecipeResult, RecipeType, SmithingType,
+    RECIPES,
 };
 use serde::{Deserialize, Serialize};
-pub use tags::{get_tag_values, TagCategory, TagType};
+pub use tags::{get_tag_values, get_tags, is_in_tag, TagCategory, TagType};
 use trim_material::TrimMaterial;
 use trim_pattern::TrimPattern;
 use wolf::WolfVariant;
 
+mod advancement;
 mod banner_pattern;
 mod biome;
+mod brewing;
 mod chat_type;
 mod damage_type;
 mod dimension;
@@ -40,6 +47,42 @@ pub static SYNCED_REGISTRIES: LazyLock<SyncedRegistry> = LazyLock::new(|| {
         .expect("Could not parse synced_registries.json registry.")
 });
 
+/// Looks up an enchantment, the name is given without the `minecraft:` prefix
+pub fn get_enchantment(name: &str) -> Option<&'static Enchantment> {
+    SYNCED_REGISTRIES.enchantment.get(name)
+}
+
+/// The id players know the entry of a synced registry by, its index in the registry data. Only
+/// registries which are sent to players have ids
+pub fn synced_registry_entry_id(tag_category: TagCategory, name: &str) -> Option<i32> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    let index = match tag_category {
+        TagCategory::WorldGenBiome => SYNCED_REGISTRIES.biome.get_index_of(name),
+        TagCategory::Painting => SYNCED_REGISTRIES.painting_variant.get_index_of(name),
+        TagCategory::DamageType => SYNCED_REGISTRIES.damage_type.get_index_of(name),
+        TagCategory::BannerPattern => SYNCED_REGISTRIES.banner_pattern.get_index_of(name),
+        _ => None,
+    }?;
+    Some(index as i32)
+}
+
+/// Whether two different enchantments can be on the same item, like vanilla's
+/// `Enchantment.areCompatible`. Names are given without the `minecraft:` prefix
+pub fn are_enchantments_compatible(first: &str, second: &str) -> bool {
+    let excludes = |name: &str, other: &str| {
+        get_enchantment(name)
+            .and_then(|enchantment| enchantment.exclusive_set.as_deref())
+            .is_some_and(|set| {
+                is_in_tag(
+                    TagCategory::Enchantment,
+                    other,
+                    set.strip_prefix('#').unwrap_or(set),
+                )
+            })
+    };
+    first != second && !excludes(first, second) && !excludes(second, first)
+}
+
 pub struct Registry {
     pub registry_id: Identifier,
     pub registry_entries: Vec<RegistryEntry>,
@@ -266,3 +309,17 @@ impl Registry {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::are_enchantments_compatible;
+
+    #[test]
+    fn exclusive_enchantments() {
+        assert!(!are_enchantments_compatible("sharpness", "smite"));
+        assert!(!are_enchantments_compatible("silk_touch", "fortune"));
+        // Only infinity names the set with mending
+        assert!(!are_enchantments_compatible("mending", "infinity"));
+        assert!(are_enchantments_compatible("sharpness", "unbreaking"));
+    }
+}
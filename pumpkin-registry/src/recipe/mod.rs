@@ -3,6 +3,7 @@ mod recipe_formats;
 
 pub use read::{
     ingredients::IngredientSlot, ingredients::IngredientType, Recipe, RecipeResult, RecipeType,
+    SmithingType,
 };
 use std::sync::LazyLock;
 pub fn flatten_3x3<T: Clone>(input: [[Option<T>; 3]; 3]) -> [[Option<T>; 3]; 3] {
@@ -46,7 +47,7 @@ pub static RECIPES: LazyLock<Vec<Recipe>> =
 #[cfg(test)]
 mod test {
     use super::flatten_3x3;
-    use crate::RECIPES;
+    use crate::{RecipeResult, RecipeType, SmithingType, RECIPES};
 
     #[test]
     fn row_flatten() {
@@ -89,4 +90,58 @@ mod test {
     fn check_parsing() {
         assert!(!RECIPES.is_empty())
     }
+
+    #[test]
+    fn cooking_recipes() {
+        let baked_potato = RECIPES
+            .iter()
+            .find(|recipe| {
+                recipe.recipe_type == RecipeType::Smelting
+                    && recipe.result().id() == "minecraft:baked_potato"
+            })
+            .expect("Smelting recipe for baked potatoes exists");
+        assert_eq!(baked_potato.cooking_time(), Some(200));
+        assert_eq!(baked_potato.experience(), Some(0.35));
+        assert!(baked_potato.pattern()[0][0][0].is_some());
+    }
+
+    #[test]
+    fn recipe_shape() {
+        let boat = RECIPES
+            .iter()
+            .find(|recipe| recipe.result().id() == "minecraft:acacia_boat")
+            .expect("Crafting recipe for acacia boats exists");
+        assert_eq!(boat.shape(), Some((3, 2)));
+        assert_eq!(boat.ingredients().count(), 5);
+        assert_eq!(boat.category(), Some("misc"));
+
+        let button = RECIPES
+            .iter()
+            .find(|recipe| recipe.result().id() == "minecraft:acacia_button")
+            .expect("Crafting recipe for acacia buttons exists");
+        assert_eq!(button.shape(), None);
+        assert_eq!(button.ingredients().count(), 1);
+    }
+
+    #[test]
+    fn stonecutting_and_smithing_recipes() {
+        let slab = RECIPES
+            .iter()
+            .find(|recipe| {
+                recipe.recipe_type == RecipeType::StoneCutting
+                    && recipe.result().id() == "minecraft:andesite_slab"
+            })
+            .expect("Stonecutting recipe for andesite slabs exists");
+        assert!(matches!(slab.result(), RecipeResult::Many { count: 2, .. }));
+        assert_eq!(slab.ingredients().count(), 1);
+
+        let axe = RECIPES
+            .iter()
+            .find(|recipe| {
+                recipe.recipe_type == RecipeType::Smithing(SmithingType::Transform)
+                    && recipe.result().id() == "minecraft:netherite_axe"
+            })
+            .expect("Smithing recipe for netherite axes exists");
+        assert_eq!(axe.ingredients().count(), 3);
+    }
 }
@@ -4,7 +4,9 @@ use crate::recipe::read::SpecialCraftingType::{
     ArmorDye, BannerDuplicate, BookCloning, Firework, RepairItem, ShieldDecoration,
     ShulkerboxColoring, SuspiciousStew, TippedArrow,
 };
-use crate::recipe::recipe_formats::{ShapedCrafting, ShapelessCrafting};
+use crate::recipe::recipe_formats::{
+    CookingRecipe, ShapedCrafting, ShapelessCrafting, SmithingRecipe, StonecuttingRecipe,
+};
 use serde::de::{Error, MapAccess, Visitor};
 use serde::{de, Deserialize, Deserializer};
 use std::collections::HashMap;
@@ -425,11 +427,13 @@ impl<'de> Deserialize<'de> for Recipe {
                 let result = match recipe_type {
                     RecipeType::Crafting(CraftingType::Special(_))
                     | RecipeType::Crafting(CraftingType::DecoratedPot)
-                    | RecipeType::Smithing(_) => RecipeResult::Special,
+                    | RecipeType::Smithing(SmithingType::Normal | SmithingType::Trim) => {
+                        RecipeResult::Special
+                    }
                     _ => result.ok_or_else(|| de::Error::missing_field("result"))?,
                 };
 
-                match recipe_type {
+                let recipe: Result<Recipe, A::Error> = match recipe_type {
                     RecipeType::Crafting(CraftingType::Shaped) => {
                         let mut rows = [[None; 3], [None; 3], [None; 3]];
                         pattern
@@ -469,15 +473,44 @@ impl<'de> Deserialize<'de> for Recipe {
                             result,
                         }))
                     }
-                    RecipeType::Smithing(_) => Ok(Recipe::from(Test {
-                        recipe_type,
-                        result: RecipeResult::Special,
-                    })),
-                    _ => Ok(Recipe::from(Test {
-                        recipe_type,
-                        result,
-                    })),
-                }
+                    RecipeType::Smithing(_) => {
+                        let template =
+                            template.ok_or_else(|| de::Error::missing_field("template"))?;
+                        let base = base.ok_or_else(|| de::Error::missing_field("base"))?;
+                        let addition =
+                            addition.ok_or_else(|| de::Error::missing_field("addition"))?;
+                        Ok(Recipe::from(SmithingRecipe::new(
+                            recipe_type,
+                            template,
+                            base,
+                            addition,
+                            result,
+                        )))
+                    }
+                    RecipeType::StoneCutting => {
+                        let ingredient =
+                            ingredient.ok_or_else(|| de::Error::missing_field("ingredient"))?;
+                        Ok(Recipe::from(StonecuttingRecipe::new(ingredient, result)))
+                    }
+                    RecipeType::Smelting
+                    | RecipeType::Blasting
+                    | RecipeType::Smoking
+                    | RecipeType::CampfireCooking => {
+                        let ingredient =
+                            ingredient.ok_or_else(|| de::Error::missing_field("ingredient"))?;
+                        // Vanilla falls back to 200 ticks when no cooking time is given
+                        Ok(Recipe::from(CookingRecipe::new(
+                            recipe_type,
+                            ingredient,
+                            cookingtime.unwrap_or(200),
+                            experience.unwrap_or(0.0),
+                            result,
+                        )))
+                    }
+                };
+                let mut recipe = recipe?;
+                recipe.category = category.map(str::to_string);
+                Ok(recipe)
             }
         }
 
@@ -523,6 +556,9 @@ pub struct Recipe {
     pub recipe_type: RecipeType,
     pattern: Vec<[[Option<IngredientSlot>; 3]; 3]>,
     result: RecipeResult,
+    cooking_time: Option<u16>,
+    experience: Option<f32>,
+    category: Option<String>,
 }
 
 impl Recipe {
@@ -534,6 +570,45 @@ impl Recipe {
         &self.result
     }
 
+    /// The time in ticks it takes to cook the input, only present for cooking recipes
+    pub fn cooking_time(&self) -> Option<u16> {
+        self.cooking_time
+    }
+
+    /// The experience a single cooked item gives, only present for cooking recipes
+    pub fn experience(&self) -> Option<f32> {
+        self.experience
+    }
+
+    /// The recipe book category, e.g. `building` or `redstone` for crafting recipes
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    /// All ingredients of the recipe, in row major order for shaped recipes
+    pub fn ingredients(&self) -> impl Iterator<Item = &IngredientSlot> {
+        self.pattern.iter().flatten().flatten().flatten()
+    }
+
+    /// The width and height of a shaped recipe's pattern
+    pub fn shape(&self) -> Option<(usize, usize)> {
+        if self.recipe_type != RecipeType::Crafting(CraftingType::Shaped) {
+            return None;
+        }
+        let pattern = self.pattern.first()?;
+        // Patterns are flattened, so they start in the top left corner
+        let width = pattern
+            .iter()
+            .filter_map(|row| row.iter().rposition(Option::is_some))
+            .max()?
+            + 1;
+        let height = pattern
+            .iter()
+            .rposition(|row| row.iter().any(Option::is_some))?
+            + 1;
+        Some((width, height))
+    }
+
     pub fn implemented(&self) -> bool {
         match self.recipe_type {
             RecipeType::Crafting(crafting_type) => {
@@ -577,11 +652,22 @@ pub trait RecipeTrait: Sized {
 
     fn result(self) -> RecipeResult;
 
+    fn cooking_time(&self) -> Option<u16> {
+        None
+    }
+
+    fn experience(&self) -> Option<f32> {
+        None
+    }
+
     fn to_recipe(self) -> Recipe {
         Recipe {
             recipe_type: self.recipe_type(),
             pattern: self.pattern().into_iter().map(flatten_3x3).collect(),
+            cooking_time: self.cooking_time(),
+            experience: self.experience(),
             result: self.result(),
+            category: None,
         }
     }
 }
@@ -90,3 +90,135 @@ impl RecipeTrait for ShapelessCrafting {
         self.output
     }
 }
+
+pub struct CookingRecipe {
+    recipe_type: RecipeType,
+    ingredient: IngredientSlot,
+    cooking_time: u16,
+    experience: f32,
+    output: RecipeResult,
+}
+
+impl CookingRecipe {
+    pub(crate) fn new(
+        recipe_type: RecipeType,
+        ingredient: IngredientSlot,
+        cooking_time: u16,
+        experience: f32,
+        output: RecipeResult,
+    ) -> Self {
+        Self {
+            recipe_type,
+            ingredient,
+            cooking_time,
+            experience,
+            output,
+        }
+    }
+}
+
+impl RecipeTrait for CookingRecipe {
+    fn recipe_type(&self) -> RecipeType {
+        self.recipe_type
+    }
+
+    // Cooking recipes only have a single input, so it is stored in the top left slot
+    fn pattern(&self) -> Vec<[[Option<IngredientSlot>; 3]; 3]> {
+        vec![[
+            [Some(self.ingredient.clone()), None, None],
+            [const { None }; 3],
+            [const { None }; 3],
+        ]]
+    }
+
+    fn cooking_time(&self) -> Option<u16> {
+        Some(self.cooking_time)
+    }
+
+    fn experience(&self) -> Option<f32> {
+        Some(self.experience)
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
+
+pub struct StonecuttingRecipe {
+    ingredient: IngredientSlot,
+    output: RecipeResult,
+}
+
+impl StonecuttingRecipe {
+    pub(crate) fn new(ingredient: IngredientSlot, output: RecipeResult) -> Self {
+        Self { ingredient, output }
+    }
+}
+
+impl RecipeTrait for StonecuttingRecipe {
+    fn recipe_type(&self) -> RecipeType {
+        RecipeType::StoneCutting
+    }
+
+    // Like cooking recipes the single input is stored in the top left slot
+    fn pattern(&self) -> Vec<[[Option<IngredientSlot>; 3]; 3]> {
+        vec![[
+            [Some(self.ingredient.clone()), None, None],
+            [const { None }; 3],
+            [const { None }; 3],
+        ]]
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
+
+pub struct SmithingRecipe {
+    recipe_type: RecipeType,
+    template: IngredientSlot,
+    base: IngredientSlot,
+    addition: IngredientSlot,
+    output: RecipeResult,
+}
+
+impl SmithingRecipe {
+    pub(crate) fn new(
+        recipe_type: RecipeType,
+        template: IngredientSlot,
+        base: IngredientSlot,
+        addition: IngredientSlot,
+        output: RecipeResult,
+    ) -> Self {
+        Self {
+            recipe_type,
+            template,
+            base,
+            addition,
+            output,
+        }
+    }
+}
+
+impl RecipeTrait for SmithingRecipe {
+    fn recipe_type(&self) -> RecipeType {
+        self.recipe_type
+    }
+
+    // The inputs are stored in the first row in the order of the smithing table slots
+    fn pattern(&self) -> Vec<[[Option<IngredientSlot>; 3]; 3]> {
+        vec![[
+            [
+                Some(self.template.clone()),
+                Some(self.base.clone()),
+                Some(self.addition.clone()),
+            ],
+            [const { None }; 3],
+            [const { None }; 3],
+        ]]
+    }
+
+    fn result(self) -> RecipeResult {
+        self.output
+    }
+}
@@ -6,7 +6,7 @@ use std::sync::LazyLock;
 
 use crate::IngredientType;
 
-#[derive(Deserialize, Eq, PartialEq, Hash)]
+#[derive(Deserialize, Eq, PartialEq, Hash, Clone, Copy, Debug)]
 pub enum TagCategory {
     #[serde(rename = "instrument")]
     Instrument,
@@ -36,6 +36,43 @@ pub enum TagCategory {
     GameEvent,
 }
 
+impl TagCategory {
+    pub const ALL: [Self; 13] = [
+        Self::Instrument,
+        Self::WorldGenBiome,
+        Self::PointOfInterest,
+        Self::Entity,
+        Self::DamageType,
+        Self::BannerPattern,
+        Self::Block,
+        Self::Fluid,
+        Self::Enchantment,
+        Self::Cat,
+        Self::Painting,
+        Self::Item,
+        Self::GameEvent,
+    ];
+
+    /// The registry the tags are about, without the `minecraft:` namespace
+    pub const fn registry(self) -> &'static str {
+        match self {
+            Self::Instrument => "instrument",
+            Self::WorldGenBiome => "worldgen/biome",
+            Self::PointOfInterest => "point_of_interest_type",
+            Self::Entity => "entity_type",
+            Self::DamageType => "damage_type",
+            Self::BannerPattern => "banner_pattern",
+            Self::Block => "block",
+            Self::Fluid => "fluid",
+            Self::Enchantment => "enchantment",
+            Self::Cat => "cat_variant",
+            Self::Painting => "painting_variant",
+            Self::Item => "item",
+            Self::GameEvent => "game_event",
+        }
+    }
+}
+
 pub static TAGS: LazyLock<HashMap<TagCategory, HashMap<String, Vec<TagType>>>> =
     LazyLock::new(|| {
         let mut map = HashMap::new();
@@ -51,7 +88,29 @@ pub static TAGS: LazyLock<HashMap<TagCategory, HashMap<String, Vec<TagType>>>> =
 pub fn get_tag_values(tag_category: TagCategory, tag: &str) -> Option<&Vec<TagType>> {
     TAGS.get(&tag_category)
         .expect("Should deserialize all tag categories")
-        .get(tag)
+        .get(tag.strip_prefix("minecraft:").unwrap_or(tag))
+}
+
+/// Whether the entry of the registry is in the tag, like vanilla's `Holder.is(TagKey)`. Both
+/// may be given with or without the `minecraft:` namespace
+pub fn is_in_tag(tag_category: TagCategory, id: &str, tag: &str) -> bool {
+    let id = id.strip_prefix("minecraft:").unwrap_or(id);
+    get_tag_values(tag_category, tag).is_some_and(|values| {
+        values.iter().any(|value| match value {
+            TagType::Item(entry) => entry == id,
+            TagType::Tag(tag) => is_in_tag(tag_category, id, tag),
+        })
+    })
+}
+
+/// The tags of the category together with their entries
+pub fn get_tags(
+    tag_category: TagCategory,
+) -> impl Iterator<Item = (&'static str, &'static [TagType])> {
+    TAGS.get(&tag_category)
+        .into_iter()
+        .flatten()
+        .map(|(tag, values)| (tag.as_str(), values.as_slice()))
 }
 
 #[derive(Deserialize)]
@@ -103,11 +162,27 @@ impl<'de> Deserialize<'de> for TagType {
 
 #[cfg(test)]
 mod test {
-    use crate::tags::TAGS;
+    use crate::tags::{get_tags, is_in_tag, TagCategory, TAGS};
 
     #[test]
     // This test assures that all tags that exist are loaded into the tags registry
     fn load_tags() {
-        assert!(!TAGS.is_empty())
+        assert!(!TAGS.is_empty());
+        for category in TagCategory::ALL {
+            assert!(get_tags(category).next().is_some(), "{category:?}");
+        }
+    }
+
+    #[test]
+    fn tag_lookups() {
+        assert!(is_in_tag(
+            TagCategory::Block,
+            "minecraft:stone",
+            "mineable/pickaxe"
+        ));
+        assert!(is_in_tag(TagCategory::Item, "oak_log", "minecraft:logs"));
+        assert!(is_in_tag(TagCategory::Fluid, "flowing_water", "water"));
+        assert!(!is_in_tag(TagCategory::Block, "dirt", "mineable/pickaxe"));
+        assert!(!is_in_tag(TagCategory::Item, "oak_log", "unknown"));
     }
 }
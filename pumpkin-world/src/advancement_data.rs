@@ -0,0 +1,204 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{chunk::WORLD_DATA_VERSION, write_atomically};
+
+const ADVANCEMENTS_FOLDER: &str = "advancements";
+
+#[derive(Error, Debug)]
+pub enum AdvancementDataError {
+    #[error("Io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("Json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+}
+
+/// The criteria a player has done of each advancement, by the advancement id and then the
+/// criterion name. The times are milliseconds since the epoch
+pub type AdvancementProgressData = BTreeMap<String, BTreeMap<String, i64>>;
+
+#[derive(Serialize, Deserialize)]
+struct AdvancementsFile {
+    #[serde(flatten)]
+    advancements: BTreeMap<String, AdvancementFileEntry>,
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AdvancementFileEntry {
+    criteria: BTreeMap<String, String>,
+    done: bool,
+}
+
+/// Reads and writes the `advancements/<uuid>.json` files of a world, like vanilla's
+/// `PlayerAdvancements`
+pub struct AdvancementDataStorage {
+    folder: PathBuf,
+}
+
+impl AdvancementDataStorage {
+    pub fn new(root_folder: &Path) -> Self {
+        Self {
+            folder: root_folder.join(ADVANCEMENTS_FOLDER),
+        }
+    }
+
+    fn path(&self, uuid: &str) -> PathBuf {
+        self.folder.join(format!("{uuid}.json"))
+    }
+
+    /// Loads the progress of the player, which is empty if the player has never joined the world
+    pub fn load(&self, uuid: &str) -> Result<AdvancementProgressData, AdvancementDataError> {
+        let path = self.path(uuid);
+        if !path.exists() {
+            return Ok(AdvancementProgressData::new());
+        }
+        let file: AdvancementsFile = serde_json::from_slice(&fs::read(path)?)?;
+        file.advancements
+            .into_iter()
+            .map(|(id, entry)| {
+                let criteria = entry
+                    .criteria
+                    .into_iter()
+                    .map(|(criterion, date)| Ok((criterion, parse_date(&date)?)))
+                    .collect::<Result<_, AdvancementDataError>>()?;
+                Ok((id, criteria))
+            })
+            .collect()
+    }
+
+    /// Saves the progress of the player, `done` tells which advancements are complete
+    pub fn save(
+        &self,
+        uuid: &str,
+        progress: &AdvancementProgressData,
+        done: impl Fn(&str) -> bool,
+    ) -> Result<(), AdvancementDataError> {
+        fs::create_dir_all(&self.folder)?;
+        let file = AdvancementsFile {
+            advancements: progress
+                .iter()
+                .filter(|(_, criteria)| !criteria.is_empty())
+                .map(|(id, criteria)| {
+                    let entry = AdvancementFileEntry {
+                        criteria: criteria
+                            .iter()
+                            .map(|(criterion, date)| (criterion.clone(), format_date(*date)))
+                            .collect(),
+                        done: done(id),
+                    };
+                    (id.clone(), entry)
+                })
+                .collect(),
+            data_version: WORLD_DATA_VERSION,
+        };
+
+        write_atomically(&self.path(uuid), serde_json::to_vec_pretty(&file)?)?;
+        Ok(())
+    }
+}
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Formats the time like vanilla saves it, `yyyy-MM-dd HH:mm:ss Z` in UTC
+fn format_date(millis: i64) -> String {
+    let days = millis.div_euclid(MILLIS_PER_DAY);
+    let seconds = millis.rem_euclid(MILLIS_PER_DAY) / 1000;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} +0000",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Parses a date in the format vanilla saves them in, with any zone offset
+fn parse_date(date: &str) -> Result<i64, AdvancementDataError> {
+    let invalid = || AdvancementDataError::InvalidDate(date.to_string());
+    let number = |part: &str| part.parse::<i64>().map_err(|_| invalid());
+
+    let mut parts = date.split(' ');
+    let (Some(day), Some(time), Some(offset), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let day: Vec<_> = day.split('-').map(number).collect::<Result<_, _>>()?;
+    let time: Vec<_> = time.split(':').map(number).collect::<Result<_, _>>()?;
+    let (&[year, month, day], &[hours, minutes, seconds]) = (&day[..], &time[..]) else {
+        return Err(invalid());
+    };
+    if offset.len() != 5 || !offset.is_ascii() {
+        return Err(invalid());
+    }
+    let offset_minutes = number(&offset[1..3])? * 60 + number(&offset[3..])?;
+    let offset_minutes = match &offset[..1] {
+        "+" => offset_minutes,
+        "-" => -offset_minutes,
+        _ => return Err(invalid()),
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hours * 3600 + minutes * 60
+        - offset_minutes * 60
+        + seconds;
+    Ok(seconds * 1000)
+}
+
+/// The days since the epoch of a date, from Howard Hinnant's `days_from_civil`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date of the days since the epoch, from Howard Hinnant's `civil_from_days`
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_date, parse_date};
+
+    #[test]
+    fn dates() {
+        assert_eq!(format_date(0), "1970-01-01 00:00:00 +0000");
+        assert_eq!(format_date(1_709_251_199_000), "2024-02-29 23:59:59 +0000");
+        assert_eq!(
+            parse_date("2024-02-29 23:59:59 +0000").unwrap(),
+            1_709_251_199_000
+        );
+        assert_eq!(
+            parse_date("2024-03-01 01:59:59 +0200").unwrap(),
+            1_709_251_199_000
+        );
+        assert!(parse_date("2024-02-29").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use enum_dispatch::enum_dispatch;
-use serde::{Deserialize, Serialize};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
 
 use crate::generation::{
     multi_noise_sampler::{BiomeEntries, MultiNoiseSampler},
@@ -9,13 +9,61 @@ use crate::generation::{
 // TODO make this work with the protocol
 // Send by the registry
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum Biome {
     Plains,
     SnowyTiga,
+    Desert,
+    Badlands,
+    Beach,
+    Ocean,
+    NetherWastes,
+    SoulSandValley,
+    CrimsonForest,
+    WarpedForest,
+    BasaltDeltas,
+    TheEnd,
+    EndHighlands,
+    EndMidlands,
+    SmallEndIslands,
+    EndBarrens,
     // TODO list all Biomes
 }
 
+impl Biome {
+    pub const ALL: [Self; 16] = [
+        Self::Plains,
+        Self::SnowyTiga,
+        Self::Desert,
+        Self::Badlands,
+        Self::Beach,
+        Self::Ocean,
+        Self::NetherWastes,
+        Self::SoulSandValley,
+        Self::CrimsonForest,
+        Self::WarpedForest,
+        Self::BasaltDeltas,
+        Self::TheEnd,
+        Self::EndHighlands,
+        Self::EndMidlands,
+        Self::SmallEndIslands,
+        Self::EndBarrens,
+    ];
+
+    /// The namespaced id, the inverse of [`Self::from_id`]
+    pub fn id(self) -> String {
+        let name = serde_json::to_value(self).expect("Biomes serialize to their name");
+        format!("minecraft:{}", name.as_str().unwrap_or_default())
+    }
+
+    /// The biome with the namespaced id vanilla saves, `None` for biomes we don't know yet
+    pub fn from_id(id: &str) -> Option<Self> {
+        let name = id.strip_prefix("minecraft:").unwrap_or(id);
+        Self::deserialize(IntoDeserializer::<serde::de::value::Error>::into_deserializer(name)).ok()
+    }
+}
+
 #[derive(Clone)]
 #[enum_dispatch(BiomeSupplierImpl)]
 pub enum BiomeSupplier {
@@ -0,0 +1,308 @@
+use std::any::Any;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{
+    block::BlockFace,
+    item::{item_registry::get_item, ItemStack},
+};
+
+use super::{
+    other_tags, read_items, write_items, BlockEntity, BrewingRecipes, TickContext, TickResult,
+};
+
+const KNOWN_TAGS: [&str; 3] = ["Items", "BrewTime", "Fuel"];
+
+pub const INGREDIENT_SLOT: usize = 3;
+pub const FUEL_SLOT: usize = 4;
+
+/// How many brews a single blaze powder lasts
+pub const FUEL_PER_ITEM: u8 = 20;
+/// Ticks it takes to finish a brew
+pub const BREW_TIME: u16 = 400;
+
+/// Brewing stands. They brew the potions in their bottle slots with the ingredient while
+/// fuel is left, like vanilla's `BrewingStandBlockEntity.serverTick`
+#[derive(Clone, PartialEq)]
+pub struct BrewingStandBlockEntity {
+    /// The three bottle slots, followed by the ingredient and the fuel
+    pub items: [Option<ItemStack>; 5],
+    /// Remaining ticks of the current brew, 0 if nothing is brewing
+    pub brew_time: u16,
+    /// How many more brews the consumed fuel lasts
+    pub fuel: u8,
+    /// The ingredient the current brew was started with, vanilla doesn't save it either
+    ingredient: Option<u16>,
+    /// Custom names and locks, which are not handled by the block entity
+    other: NbtCompound,
+}
+
+impl BrewingStandBlockEntity {
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut items = [const { None }; 5];
+        read_items(nbt, &mut items);
+        Self {
+            ingredient: items[INGREDIENT_SLOT].as_ref().map(|item| item.item_id),
+            items,
+            brew_time: nbt.get_short("BrewTime").unwrap_or(0).max(0) as u16,
+            fuel: nbt.get_byte("Fuel").unwrap_or(0).max(0) as u8,
+            other: other_tags(nbt, &KNOWN_TAGS),
+        }
+    }
+
+    fn can_brew(&self, recipes: &dyn BrewingRecipes) -> bool {
+        let Some(ingredient) = &self.items[INGREDIENT_SLOT] else {
+            return false;
+        };
+        self.items[..INGREDIENT_SLOT]
+            .iter()
+            .flatten()
+            .any(|bottle| recipes.brewing_result(bottle, ingredient).is_some())
+    }
+
+    fn refuel(&mut self, recipes: &dyn BrewingRecipes) {
+        let Some(fuel) = self.items[FUEL_SLOT].as_mut() else {
+            return;
+        };
+        if !recipes.is_fuel(fuel) {
+            return;
+        }
+        self.fuel = FUEL_PER_ITEM;
+        if fuel.item_count > 1 {
+            fuel.item_count -= 1;
+        } else {
+            self.items[FUEL_SLOT] = None;
+        }
+    }
+
+    /// Brews the potions, returns the bottle dragon's breath leaves behind if it doesn't fit
+    /// into the ingredient slot
+    fn brew(&mut self, recipes: &dyn BrewingRecipes) -> Option<ItemStack> {
+        let ingredient = self.items[INGREDIENT_SLOT].take()?;
+        for bottle in &mut self.items[..INGREDIENT_SLOT] {
+            if let Some(result) = bottle
+                .as_ref()
+                .and_then(|bottle| recipes.brewing_result(bottle, &ingredient))
+            {
+                *bottle = Some(result);
+            }
+        }
+        let remainder = get_item("minecraft:dragon_breath")
+            .filter(|dragon_breath| dragon_breath.id == ingredient.item_id)
+            .and_then(|_| get_item("minecraft:glass_bottle"))
+            .map(|bottle| ItemStack::new(1, bottle.id));
+        if ingredient.item_count > 1 {
+            self.items[INGREDIENT_SLOT] = Some(ingredient.with_count(ingredient.item_count - 1));
+            // Like vanilla, the bottle drops out of the stand if ingredients are left
+            remainder
+        } else {
+            self.items[INGREDIENT_SLOT] = remainder;
+            None
+        }
+    }
+}
+
+fn is_glass_bottle(item: &ItemStack) -> bool {
+    get_item("minecraft:glass_bottle").is_some_and(|bottle| bottle.id == item.item_id)
+}
+
+impl BlockEntity for BrewingStandBlockEntity {
+    fn id(&self) -> &str {
+        "minecraft:brewing_stand"
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items(&self.items, nbt);
+        nbt.put("BrewTime".to_string(), NbtTag::Short(self.brew_time as i16));
+        nbt.put("Fuel".to_string(), NbtTag::Byte(self.fuel as i8));
+        nbt.extend(self.other.child_tags.iter().cloned());
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, context: &TickContext) -> TickResult {
+        if self.fuel == 0 {
+            self.refuel(context.brewing);
+        }
+        let can_brew = self.can_brew(context.brewing);
+        let ingredient = self.items[INGREDIENT_SLOT]
+            .as_ref()
+            .map(|ingredient| ingredient.item_id);
+        let mut dropped = Vec::new();
+        if self.brew_time > 0 {
+            self.brew_time -= 1;
+            if self.brew_time == 0 && can_brew {
+                dropped.extend(self.brew(context.brewing));
+            } else if !can_brew || ingredient != self.ingredient {
+                // The brew is cancelled when the ingredient or the potions are taken out
+                self.brew_time = 0;
+            }
+        } else if can_brew && self.fuel > 0 {
+            self.fuel -= 1;
+            self.brew_time = BREW_TIME;
+            self.ingredient = ingredient;
+        }
+        TickResult {
+            dropped,
+            ..Default::default()
+        }
+    }
+
+    fn items_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    /// Ingredients go in from above, potions and fuel from the sides, like vanilla's
+    /// `getSlotsForFace`
+    fn can_insert(
+        &self,
+        slot: usize,
+        item: &ItemStack,
+        face: BlockFace,
+        context: &TickContext,
+    ) -> bool {
+        match (face, slot) {
+            (BlockFace::Top, INGREDIENT_SLOT) => context.brewing.is_ingredient(item),
+            (BlockFace::Top | BlockFace::Bottom, _) => false,
+            (_, FUEL_SLOT) => context.brewing.is_fuel(item),
+            // Potions don't stack, each bottle slot holds one
+            (_, 0..=2) => self.items[slot].is_none() && context.brewing.is_potion_container(item),
+            _ => false,
+        }
+    }
+
+    /// Hoppers take the potions and the bottles dragon's breath leaves behind
+    fn can_extract(&self, slot: usize, item: &ItemStack) -> bool {
+        slot < INGREDIENT_SLOT || (slot == INGREDIENT_SLOT && is_glass_bottle(item))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_nbt::compound::NbtCompound;
+
+    use crate::{
+        block::{
+            block_entity::{
+                block_entity_data,
+                tests::{TestBlockEntities, TestRecipes},
+                BlockEntity, TickContext,
+            },
+            BlockFace,
+        },
+        item::ItemStack,
+    };
+
+    use super::{BrewingStandBlockEntity, BREW_TIME, FUEL_PER_ITEM, FUEL_SLOT, INGREDIENT_SLOT};
+
+    fn context(block_entities: &TestBlockEntities) -> TickContext<'_> {
+        TickContext {
+            position: WorldPosition(Vector3::new(0, 0, 0)),
+            state_id: 0,
+            players: &[],
+            recipes: &TestRecipes,
+            brewing: &TestRecipes,
+            block_entities,
+        }
+    }
+
+    /// Ticks the stand until the brew started on the first tick is done, returns what dropped
+    fn brew(stand: &mut BrewingStandBlockEntity) -> Vec<ItemStack> {
+        let block_entities = TestBlockEntities::default();
+        let context = context(&block_entities);
+        (0..=BREW_TIME)
+            .flat_map(|_| stand.tick(&context).dropped)
+            .collect()
+    }
+
+    #[test]
+    fn brews_with_fuel() {
+        let mut stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        stand.items[0] = Some(ItemStack::of("potion", 1));
+        stand.items[INGREDIENT_SLOT] = Some(ItemStack::of("nether_wart", 2));
+        stand.items[FUEL_SLOT] = Some(ItemStack::of("blaze_powder", 1));
+
+        assert!(brew(&mut stand).is_empty());
+        assert_eq!(stand.items[0], Some(ItemStack::of("splash_potion", 1)));
+        assert_eq!(
+            stand.items[INGREDIENT_SLOT],
+            Some(ItemStack::of("nether_wart", 1))
+        );
+        assert_eq!(stand.items[FUEL_SLOT], None);
+        assert_eq!(stand.fuel, FUEL_PER_ITEM - 1);
+        assert_eq!(stand.brew_time, 0);
+    }
+
+    #[test]
+    fn stops_when_the_ingredient_is_taken_out() {
+        let mut stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        stand.items[0] = Some(ItemStack::of("potion", 1));
+        stand.items[INGREDIENT_SLOT] = Some(ItemStack::of("nether_wart", 1));
+        stand.fuel = 1;
+        let block_entities = TestBlockEntities::default();
+        let context = context(&block_entities);
+        stand.tick(&context);
+        assert_eq!(stand.brew_time, BREW_TIME);
+
+        stand.items[INGREDIENT_SLOT] = None;
+        stand.tick(&context);
+        assert_eq!(stand.brew_time, 0);
+        assert_eq!(stand.items[0], Some(ItemStack::of("potion", 1)));
+    }
+
+    #[test]
+    fn drops_the_dragon_breath_bottle_if_ingredients_are_left() {
+        let mut stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        stand.items[0] = Some(ItemStack::of("potion", 1));
+        stand.items[INGREDIENT_SLOT] = Some(ItemStack::of("dragon_breath", 2));
+        stand.fuel = 2;
+        assert_eq!(brew(&mut stand), vec![ItemStack::of("glass_bottle", 1)]);
+
+        // The last bottle stays in the stand
+        stand.items[0] = Some(ItemStack::of("potion", 1));
+        assert!(brew(&mut stand).is_empty());
+        assert_eq!(
+            stand.items[INGREDIENT_SLOT],
+            Some(ItemStack::of("glass_bottle", 1))
+        );
+    }
+
+    #[test]
+    fn hoppers_fill_the_slots_through_their_faces() {
+        let stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        let block_entities = TestBlockEntities::default();
+        let context = context(&block_entities);
+        let wart = ItemStack::of("nether_wart", 1);
+        let powder = ItemStack::of("blaze_powder", 1);
+        let potion = ItemStack::of("potion", 1);
+        assert!(stand.can_insert(INGREDIENT_SLOT, &wart, BlockFace::Top, &context));
+        assert!(!stand.can_insert(INGREDIENT_SLOT, &wart, BlockFace::North, &context));
+        assert!(stand.can_insert(FUEL_SLOT, &powder, BlockFace::North, &context));
+        assert!(stand.can_insert(1, &potion, BlockFace::East, &context));
+        assert!(!stand.can_insert(1, &potion, BlockFace::Top, &context));
+    }
+
+    #[test]
+    fn keeps_its_progress() {
+        let mut stand = BrewingStandBlockEntity::from_nbt(&NbtCompound::new());
+        stand.items[INGREDIENT_SLOT] = Some(ItemStack::of("nether_wart", 1));
+        stand.brew_time = 100;
+        stand.fuel = 5;
+        let nbt = block_entity_data(&stand);
+        assert_eq!(nbt.get_short("BrewTime"), Some(100));
+        let loaded = BrewingStandBlockEntity::from_nbt(&nbt);
+        assert_eq!(loaded.items, stand.items);
+        assert_eq!(block_entity_data(&loaded), nbt);
+    }
+}
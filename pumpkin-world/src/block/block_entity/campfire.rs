@@ -0,0 +1,202 @@
+use std::any::Any;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{
+    block::{block_registry::get_block_by_state_id, random_tick::property},
+    item::ItemStack,
+};
+
+use super::{other_tags, read_items, write_items, BlockEntity, TickContext, TickResult};
+
+const KNOWN_TAGS: [&str; 3] = ["Items", "CookingTimes", "CookingTotalTimes"];
+
+/// Campfires cook up to four items at once
+pub const CAMPFIRE_SLOTS: usize = 4;
+
+/// Campfires and soul campfires. They cook the food put on them while they are lit and cool it
+/// down while they are out, like vanilla's `CampfireBlockEntity`
+#[derive(Default)]
+pub struct CampfireBlockEntity {
+    pub items: [Option<ItemStack>; CAMPFIRE_SLOTS],
+    /// Ticks each item was cooked for
+    pub cooking_times: [i32; CAMPFIRE_SLOTS],
+    /// Ticks each item needs
+    pub cooking_total_times: [i32; CAMPFIRE_SLOTS],
+    /// Custom names and other data, which are not handled by the block entity
+    other: NbtCompound,
+}
+
+impl CampfireBlockEntity {
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut campfire = Self {
+            other: other_tags(nbt, &KNOWN_TAGS),
+            ..Default::default()
+        };
+        read_items(nbt, &mut campfire.items);
+        read_times(nbt, "CookingTimes", &mut campfire.cooking_times);
+        read_times(nbt, "CookingTotalTimes", &mut campfire.cooking_total_times);
+        campfire
+    }
+
+    /// Puts a single item of the stack on the first free spot, like vanilla's
+    /// `CampfireBlockEntity.placeFood`. Returns false if the campfire is full
+    pub fn place_food(&mut self, stack: &ItemStack, cooking_time: i32) -> bool {
+        let Some(slot) = self.items.iter().position(Option::is_none) else {
+            return false;
+        };
+        self.items[slot] = Some(stack.with_count(1));
+        self.cooking_times[slot] = 0;
+        self.cooking_total_times[slot] = cooking_time;
+        true
+    }
+}
+
+fn read_times(nbt: &NbtCompound, name: &str, times: &mut [i32; CAMPFIRE_SLOTS]) {
+    if let Some(saved) = nbt.get_int_array(name) {
+        for (time, saved) in times.iter_mut().zip(saved) {
+            *time = *saved;
+        }
+    }
+}
+
+impl BlockEntity for CampfireBlockEntity {
+    fn id(&self) -> &str {
+        "minecraft:campfire"
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items(&self.items, nbt);
+        nbt.put(
+            "CookingTimes".to_string(),
+            NbtTag::IntArray(self.cooking_times.to_vec()),
+        );
+        nbt.put(
+            "CookingTotalTimes".to_string(),
+            NbtTag::IntArray(self.cooking_total_times.to_vec()),
+        );
+        nbt.extend(self.other.child_tags.iter().cloned());
+    }
+
+    /// Players see the food on the campfire
+    fn update_nbt(&self) -> Option<NbtCompound> {
+        let mut nbt = NbtCompound::new();
+        write_items(&self.items, &mut nbt);
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, context: &TickContext) -> TickResult {
+        let lit = get_block_by_state_id(context.state_id)
+            .and_then(|block| property(block, context.state_id, "lit"))
+            .is_some_and(|lit| lit == "true");
+        if !lit {
+            for time in &mut self.cooking_times {
+                *time = (*time - 2).max(0);
+            }
+            return TickResult::default();
+        }
+        let mut cooked = Vec::new();
+        let slots = self
+            .items
+            .iter_mut()
+            .zip(&mut self.cooking_times)
+            .zip(&self.cooking_total_times);
+        for ((item, time), total_time) in slots {
+            if item.is_none() {
+                continue;
+            }
+            *time += 1;
+            if *time >= *total_time {
+                cooked.extend(item.take());
+            }
+        }
+        TickResult {
+            block_entity_update: (!cooked.is_empty()).then(|| self.update_nbt()).flatten(),
+            cooked,
+            ..Default::default()
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use crate::{
+        block::{
+            block_entity::{
+                block_entity_data,
+                tests::{TestBlockEntities, TestRecipes},
+                BlockEntity, TickContext,
+            },
+            block_registry::get_block,
+            random_tick::with_property,
+        },
+        item::{item_registry::get_item, ItemStack},
+    };
+
+    use super::CampfireBlockEntity;
+
+    #[test]
+    fn cooks_while_lit() {
+        let block = get_block("minecraft:campfire").unwrap();
+        let lit = with_property(block, block.default_state_id, "lit", "true");
+        let out = with_property(block, block.default_state_id, "lit", "false");
+        let beef = ItemStack::new(3, get_item("beef").unwrap().id);
+        let mut campfire = CampfireBlockEntity::default();
+        assert!(campfire.place_food(&beef, 3));
+        assert_eq!(campfire.items[0].as_ref().unwrap().item_count, 1);
+        let mut tick = |state_id| {
+            campfire.tick(&TickContext {
+                position: WorldPosition(Vector3::new(0, 0, 0)),
+                state_id,
+                players: &[],
+                recipes: &TestRecipes,
+                brewing: &TestRecipes,
+                block_entities: &TestBlockEntities::default(),
+            })
+        };
+
+        assert!(tick(lit).cooked.is_empty());
+        // Out campfires cool the food down
+        assert!(tick(out).cooked.is_empty());
+        assert!(tick(lit).cooked.is_empty());
+        assert!(tick(lit).cooked.is_empty());
+        let result = tick(lit);
+        let [cooked] = result.cooked.as_slice() else {
+            panic!("One item should be done cooking");
+        };
+        assert_eq!(cooked.item_id, beef.item_id);
+        assert_eq!(cooked.item_count, 1);
+        assert!(result.block_entity_update.is_some());
+        assert!(campfire.items[0].is_none());
+    }
+
+    #[test]
+    fn saves_food() {
+        let beef = ItemStack::new(1, get_item("beef").unwrap().id);
+        let mut campfire = CampfireBlockEntity::default();
+        for _ in 0..4 {
+            assert!(campfire.place_food(&beef, 600));
+        }
+        assert!(!campfire.place_food(&beef, 600));
+        campfire.cooking_times[2] = 100;
+
+        let loaded = CampfireBlockEntity::from_nbt(&block_entity_data(&campfire));
+        assert!(loaded.items.iter().all(Option::is_some));
+        assert_eq!(loaded.cooking_times, [0, 0, 100, 0]);
+        assert_eq!(loaded.cooking_total_times, [600; 4]);
+    }
+}
@@ -0,0 +1,356 @@
+use std::any::Any;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{
+    block::{
+        block_registry::get_block_by_state_id,
+        random_tick::{property, with_property},
+        BlockFace,
+    },
+    item::{item_registry::get_item, ItemStack},
+};
+
+use super::{
+    other_tags, read_items, write_items, BlockEntity, CookingRecipes, TickContext, TickResult,
+};
+
+/// Vanilla saves the used recipes and works out the experience from them
+const EXPERIENCE_TAG: &str = "pumpkin:experience";
+
+const KNOWN_TAGS: [&str; 6] = [
+    "Items",
+    EXPERIENCE_TAG,
+    "lit_time_remaining",
+    "lit_total_time",
+    "cooking_time_spent",
+    "cooking_total_time",
+];
+
+pub const INPUT_SLOT: usize = 0;
+pub const FUEL_SLOT: usize = 1;
+pub const OUTPUT_SLOT: usize = 2;
+
+/// Furnaces, smokers and blast furnaces. They burn fuel to cook their input and lose their
+/// cooking progress while they are out, like vanilla's `AbstractFurnaceBlockEntity.serverTick`
+#[derive(Clone, PartialEq)]
+pub struct FurnaceBlockEntity {
+    id: String,
+    /// The input, the fuel and the output
+    pub items: [Option<ItemStack>; 3],
+    /// Ticks until the current fuel burnt down
+    pub lit_time_remaining: i16,
+    /// Ticks the current fuel burns in total
+    pub lit_total_time: i16,
+    /// Ticks the item being cooked was cooked for
+    pub cooking_time_spent: i16,
+    /// Ticks the item being cooked needs
+    pub cooking_total_time: i16,
+    /// Experience of the cooked items, the player taking the output gets it
+    pub experience: f32,
+    /// Used recipes and custom names, which are not handled by the block entity
+    other: NbtCompound,
+}
+
+impl FurnaceBlockEntity {
+    pub fn from_nbt(id: &str, nbt: &NbtCompound) -> Self {
+        let mut items = [const { None }; 3];
+        read_items(nbt, &mut items);
+        Self {
+            id: id.to_string(),
+            items,
+            lit_time_remaining: nbt.get_short("lit_time_remaining").unwrap_or(0),
+            lit_total_time: nbt.get_short("lit_total_time").unwrap_or(0),
+            cooking_time_spent: nbt.get_short("cooking_time_spent").unwrap_or(0),
+            cooking_total_time: nbt.get_short("cooking_total_time").unwrap_or(0),
+            experience: nbt.get_float(EXPERIENCE_TAG).unwrap_or(0.0),
+            other: other_tags(nbt, &KNOWN_TAGS),
+        }
+    }
+
+    pub fn is_lit(&self) -> bool {
+        self.lit_time_remaining > 0
+    }
+
+    /// The state of the furnace block if it doesn't show whether the furnace burns
+    fn lit_state(&self, state_id: u16) -> Option<u16> {
+        let block = get_block_by_state_id(state_id)?;
+        let lit = self.is_lit().to_string();
+        (property(block, state_id, "lit")? != lit)
+            .then(|| with_property(block, state_id, "lit", &lit))
+    }
+
+    /// Whether the cooked item fits into the output slot
+    fn can_output(&self, result: &ItemStack) -> bool {
+        match &self.items[OUTPUT_SLOT] {
+            None => true,
+            Some(output) => {
+                output.is_stackable_with(result)
+                    && u16::from(output.item_count) + u16::from(result.item_count)
+                        <= u16::from(output.max_stack_size())
+            }
+        }
+    }
+
+    /// Lights the furnace with one item of the fuel
+    fn burn_fuel(&mut self, recipes: &dyn CookingRecipes) {
+        let Some(fuel) = self.items[FUEL_SLOT].as_mut() else {
+            return;
+        };
+        let Some(burn_time) = recipes.fuel_burn_time(fuel) else {
+            return;
+        };
+        self.lit_time_remaining = i16::try_from(burn_time).unwrap_or(i16::MAX);
+        self.lit_total_time = self.lit_time_remaining;
+        if fuel.item_count > 1 {
+            fuel.item_count -= 1;
+        } else {
+            // Lava buckets leave their bucket behind
+            self.items[FUEL_SLOT] = get_item("minecraft:lava_bucket")
+                .filter(|lava_bucket| lava_bucket.id == fuel.item_id)
+                .and_then(|_| get_item("minecraft:bucket"))
+                .map(|bucket| ItemStack::new(1, bucket.id));
+        }
+    }
+
+    fn cook(&mut self, result: ItemStack, experience: f32) {
+        self.experience += experience;
+        match &mut self.items[OUTPUT_SLOT] {
+            Some(output) => output.item_count += result.item_count,
+            output => *output = Some(result),
+        }
+        let input = &mut self.items[INPUT_SLOT];
+        if let Some(cooked) = input {
+            if cooked.item_count > 1 {
+                cooked.item_count -= 1;
+            } else {
+                *input = None;
+            }
+        }
+    }
+
+    fn cool_down(&mut self) {
+        self.cooking_time_spent = (self.cooking_time_spent - 2).clamp(0, self.cooking_total_time);
+    }
+}
+
+fn is_bucket(item: &ItemStack) -> bool {
+    get_item("minecraft:bucket").is_some_and(|bucket| bucket.id == item.item_id)
+}
+
+impl BlockEntity for FurnaceBlockEntity {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items(&self.items, nbt);
+        nbt.put(
+            "lit_time_remaining".to_string(),
+            NbtTag::Short(self.lit_time_remaining),
+        );
+        nbt.put(
+            "lit_total_time".to_string(),
+            NbtTag::Short(self.lit_total_time),
+        );
+        nbt.put(
+            "cooking_time_spent".to_string(),
+            NbtTag::Short(self.cooking_time_spent),
+        );
+        nbt.put(
+            "cooking_total_time".to_string(),
+            NbtTag::Short(self.cooking_total_time),
+        );
+        if self.experience > 0.0 {
+            nbt.put(EXPERIENCE_TAG.to_string(), NbtTag::Float(self.experience));
+        }
+        nbt.extend(self.other.child_tags.iter().cloned());
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, context: &TickContext) -> TickResult {
+        if self.is_lit() {
+            self.lit_time_remaining -= 1;
+        }
+        let recipe = self.items[INPUT_SLOT]
+            .as_ref()
+            .and_then(|input| context.recipes.cooking_result(&self.id, input))
+            .filter(|(result, _, _)| self.can_output(result));
+        match recipe {
+            Some((result, cooking_total_time, experience)) => {
+                if !self.is_lit() {
+                    self.burn_fuel(context.recipes);
+                }
+                if self.is_lit() {
+                    self.cooking_total_time = i16::try_from(cooking_total_time).unwrap_or(i16::MAX);
+                    self.cooking_time_spent += 1;
+                    if self.cooking_time_spent >= self.cooking_total_time {
+                        self.cooking_time_spent = 0;
+                        self.cook(result, experience);
+                    }
+                } else {
+                    self.cool_down();
+                }
+            }
+            // Nothing to cook
+            None if self.is_lit() => self.cooking_time_spent = 0,
+            None => self.cool_down(),
+        }
+        TickResult {
+            state_id: self.lit_state(context.state_id),
+            ..Default::default()
+        }
+    }
+
+    fn items_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    /// Items go in from above, fuel from the sides, like vanilla's `getSlotsForFace`
+    fn can_insert(
+        &self,
+        slot: usize,
+        item: &ItemStack,
+        face: BlockFace,
+        context: &TickContext,
+    ) -> bool {
+        match face {
+            BlockFace::Top => slot == INPUT_SLOT,
+            BlockFace::Bottom => false,
+            _ => {
+                slot == FUEL_SLOT
+                    && (context.recipes.fuel_burn_time(item).is_some()
+                        || (is_bucket(item)
+                            && !self.items[FUEL_SLOT].as_ref().is_some_and(is_bucket)))
+            }
+        }
+    }
+
+    /// Hoppers take the output and the buckets lava leaves behind
+    fn can_extract(&self, slot: usize, item: &ItemStack) -> bool {
+        slot == OUTPUT_SLOT || (slot == FUEL_SLOT && is_bucket(item))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use crate::{
+        block::{
+            block_entity::{
+                block_entity_data,
+                tests::{TestBlockEntities, TestRecipes},
+                BlockEntity, TickContext,
+            },
+            block_registry::get_block,
+            random_tick::with_property,
+        },
+        item::ItemStack,
+    };
+
+    use super::{FurnaceBlockEntity, FUEL_SLOT, INPUT_SLOT, OUTPUT_SLOT};
+
+    fn tick(furnace: &mut FurnaceBlockEntity, state_id: u16) -> Option<u16> {
+        furnace
+            .tick(&TickContext {
+                position: WorldPosition(Vector3::new(0, 0, 0)),
+                state_id,
+                players: &[],
+                recipes: &TestRecipes,
+                brewing: &TestRecipes,
+                block_entities: &TestBlockEntities::default(),
+            })
+            .state_id
+    }
+
+    #[test]
+    fn goes_out_when_the_fuel_burnt_down() {
+        let block = get_block("minecraft:furnace").unwrap();
+        let lit = with_property(block, block.default_state_id, "lit", "true");
+        let mut nbt = NbtCompound::new();
+        nbt.put("lit_time_remaining".to_string(), NbtTag::Short(2));
+        nbt.put("cooking_time_spent".to_string(), NbtTag::Short(50));
+        nbt.put("cooking_total_time".to_string(), NbtTag::Short(200));
+        let mut furnace = FurnaceBlockEntity::from_nbt("minecraft:furnace", &nbt);
+
+        assert_eq!(tick(&mut furnace, lit), None);
+        assert_eq!(tick(&mut furnace, lit), Some(block.default_state_id));
+        // Nothing was cooking
+        assert_eq!(furnace.cooking_time_spent, 0);
+        furnace.cooking_time_spent = 50;
+        // Without fuel the cooking progress goes back
+        assert_eq!(tick(&mut furnace, block.default_state_id), None);
+        assert_eq!(furnace.cooking_time_spent, 48);
+    }
+
+    #[test]
+    fn cooks_with_fuel() {
+        let block = get_block("minecraft:furnace").unwrap();
+        let lit = with_property(block, block.default_state_id, "lit", "true");
+        let mut furnace = FurnaceBlockEntity::from_nbt("minecraft:furnace", &NbtCompound::new());
+        furnace.items[INPUT_SLOT] = Some(ItemStack::of("beef", 2));
+        furnace.items[FUEL_SLOT] = Some(ItemStack::of("coal", 1));
+
+        assert_eq!(tick(&mut furnace, block.default_state_id), Some(lit));
+        assert_eq!(furnace.items[FUEL_SLOT], None);
+        assert_eq!(furnace.lit_total_time, 4);
+        tick(&mut furnace, lit);
+        tick(&mut furnace, lit);
+        assert_eq!(
+            furnace.items[OUTPUT_SLOT],
+            Some(ItemStack::of("cooked_beef", 1))
+        );
+        assert_eq!(furnace.items[INPUT_SLOT], Some(ItemStack::of("beef", 1)));
+        assert_eq!(furnace.cooking_time_spent, 0);
+        assert!((furnace.experience - 0.35).abs() < f32::EPSILON);
+        // The fuel ran out before the second beef was done
+        tick(&mut furnace, lit);
+        assert_eq!(tick(&mut furnace, lit), Some(block.default_state_id));
+        assert_eq!(furnace.cooking_time_spent, 0);
+        assert_eq!(
+            furnace.items[OUTPUT_SLOT],
+            Some(ItemStack::of("cooked_beef", 1))
+        );
+    }
+
+    #[test]
+    fn waits_for_space_in_the_output() {
+        let mut furnace = FurnaceBlockEntity::from_nbt("minecraft:furnace", &NbtCompound::new());
+        furnace.items[INPUT_SLOT] = Some(ItemStack::of("beef", 1));
+        furnace.items[FUEL_SLOT] = Some(ItemStack::of("coal", 1));
+        furnace.items[OUTPUT_SLOT] = Some(ItemStack::of("stone", 1));
+        tick(&mut furnace, 0);
+        // The fuel isn't wasted
+        assert!(!furnace.is_lit());
+        assert_eq!(furnace.items[FUEL_SLOT], Some(ItemStack::of("coal", 1)));
+    }
+
+    #[test]
+    fn keeps_items() {
+        let mut furnace = FurnaceBlockEntity::from_nbt("minecraft:smoker", &NbtCompound::new());
+        furnace.items[FUEL_SLOT] = Some(ItemStack::of("coal", 3));
+        furnace.lit_total_time = 1600;
+        furnace.experience = 0.7;
+        let saved = block_entity_data(&furnace);
+        assert_eq!(saved.get_list("Items").unwrap().len(), 1);
+        assert_eq!(saved.get_short("lit_total_time"), Some(1600));
+        let loaded = FurnaceBlockEntity::from_nbt("minecraft:smoker", &saved);
+        assert_eq!(loaded.items[FUEL_SLOT], Some(ItemStack::of("coal", 3)));
+        assert_eq!(loaded.lit_total_time, 1600);
+        assert!((loaded.experience - 0.7).abs() < f32::EPSILON);
+    }
+}
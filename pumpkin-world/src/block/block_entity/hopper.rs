@@ -0,0 +1,409 @@
+use std::any::Any;
+
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{
+    block::{block_registry::get_block_by_state_id, random_tick::property, BlockFace},
+    item::ItemStack,
+};
+
+use super::{other_tags, read_items, write_items, BlockEntity, TickContext, TickResult};
+
+const KNOWN_TAGS: [&str; 2] = ["Items", "TransferCooldown"];
+
+/// Hoppers wait this many ticks after moving an item
+pub const TRANSFER_COOLDOWN: i32 = 8;
+
+pub const HOPPER_SLOTS: usize = 5;
+
+/// Hoppers, which push an item into the container they face and pull one out of the container
+/// above them, like vanilla's `HopperBlockEntity.pushItemsTick`. Hoppers without a container
+/// above them collect the items lying on them instead
+pub struct HopperBlockEntity {
+    pub items: [Option<ItemStack>; HOPPER_SLOTS],
+    /// Ticks until the hopper moves items again
+    pub transfer_cooldown: i32,
+    /// Custom names and other data, which are not handled by the block entity
+    other: NbtCompound,
+}
+
+impl HopperBlockEntity {
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut items = [const { None }; HOPPER_SLOTS];
+        read_items(nbt, &mut items);
+        Self {
+            items,
+            transfer_cooldown: nbt.get_int("TransferCooldown").unwrap_or(-1),
+            other: other_tags(nbt, &KNOWN_TAGS),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.items.iter().all(|slot| {
+            slot.as_ref()
+                .is_some_and(|item| item.item_count >= item.max_stack_size())
+        })
+    }
+
+    /// Puts as many items of the stack into the hopper as fit, returns whether any did
+    pub fn collect(&mut self, stack: &mut ItemStack) -> bool {
+        let count = stack.item_count;
+        for slot in &mut self.items {
+            if stack.item_count == 0 {
+                break;
+            }
+            match slot {
+                None => {
+                    *slot = Some(stack.clone());
+                    stack.item_count = 0;
+                }
+                Some(item) if item.is_stackable_with(stack) => {
+                    let moved = (item.max_stack_size().saturating_sub(item.item_count))
+                        .min(stack.item_count);
+                    item.item_count += moved;
+                    stack.item_count -= moved;
+                }
+                Some(_) => {}
+            }
+        }
+        stack.item_count < count
+    }
+
+    /// Pushes an item into the container the hopper faces
+    fn push(&mut self, context: &TickContext, facing: BlockFace) -> bool {
+        let target = WorldPosition(context.position.0 + facing.to_offset());
+        for slot in &mut self.items {
+            let Some(item) = slot.as_ref().map(|item| item.with_count(1)) else {
+                continue;
+            };
+            let inserted = context
+                .block_entities
+                .update(&target, &mut |block_entity, _| {
+                    insert(block_entity, &item, facing.opposite(), context)
+                });
+            if inserted == Some(true) {
+                take_one(slot);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Pulls an item out of the container above the hopper. `None` if there is no container
+    /// above it
+    fn pull(&mut self, context: &TickContext) -> Option<bool> {
+        let source = WorldPosition(context.position.0 + BlockFace::Top.to_offset());
+        let mut is_container = false;
+        let pulled = context
+            .block_entities
+            .update(&source, &mut |block_entity, _| {
+                let Some(slots) = block_entity.items_mut().map(|items| items.len()) else {
+                    return false;
+                };
+                is_container = true;
+                for slot in 0..slots {
+                    let Some(item) = block_entity
+                        .items_mut()
+                        .and_then(|items| items[slot].as_ref())
+                        .map(|item| item.with_count(1))
+                    else {
+                        continue;
+                    };
+                    if !block_entity.can_extract(slot, &item) || !self.collect(&mut item.clone()) {
+                        continue;
+                    }
+                    if let Some(items) = block_entity.items_mut() {
+                        take_one(&mut items[slot]);
+                    }
+                    return true;
+                }
+                false
+            });
+        is_container.then_some(pulled == Some(true))
+    }
+}
+
+/// Puts the item into the first slot of the block entity which takes it, like vanilla's
+/// `HopperBlockEntity.addItem`
+fn insert(
+    block_entity: &mut dyn BlockEntity,
+    item: &ItemStack,
+    face: BlockFace,
+    context: &TickContext,
+) -> bool {
+    let Some(slots) = block_entity.items_mut().map(|items| items.len()) else {
+        return false;
+    };
+    let was_empty = block_entity
+        .items_mut()
+        .is_some_and(|items| items.iter().all(Option::is_none));
+    for slot in 0..slots {
+        if !block_entity.can_insert(slot, item, face, context) {
+            continue;
+        }
+        let Some(items) = block_entity.items_mut() else {
+            return false;
+        };
+        match &mut items[slot] {
+            None => items[slot] = Some(item.clone()),
+            Some(stack)
+                if stack.is_stackable_with(item)
+                    && stack.item_count + item.item_count <= stack.max_stack_size() =>
+            {
+                stack.item_count += item.item_count;
+            }
+            Some(_) => continue,
+        }
+        // Hoppers which just got their first item wait before passing it on
+        if let Some(hopper) = block_entity
+            .as_any_mut()
+            .downcast_mut::<HopperBlockEntity>()
+        {
+            if was_empty {
+                hopper.transfer_cooldown = TRANSFER_COOLDOWN;
+            }
+        }
+        return true;
+    }
+    false
+}
+
+fn take_one(slot: &mut Option<ItemStack>) {
+    if let Some(item) = slot {
+        if item.item_count > 1 {
+            item.item_count -= 1;
+        } else {
+            *slot = None;
+        }
+    }
+}
+
+impl BlockEntity for HopperBlockEntity {
+    fn id(&self) -> &str {
+        "minecraft:hopper"
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        write_items(&self.items, nbt);
+        nbt.put(
+            "TransferCooldown".to_string(),
+            NbtTag::Int(self.transfer_cooldown),
+        );
+        nbt.extend(self.other.child_tags.iter().cloned());
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, context: &TickContext) -> TickResult {
+        self.transfer_cooldown -= 1;
+        if self.transfer_cooldown > 0 {
+            return TickResult::default();
+        }
+        self.transfer_cooldown = 0;
+        let Some(block) = get_block_by_state_id(context.state_id) else {
+            return TickResult::default();
+        };
+        // Powered hoppers are locked
+        if property(block, context.state_id, "enabled") == Some("false") {
+            return TickResult::default();
+        }
+        let facing = property(block, context.state_id, "facing")
+            .and_then(BlockFace::from_name)
+            .unwrap_or(BlockFace::Bottom);
+        let mut moved = false;
+        if self.items.iter().any(Option::is_some) {
+            moved = self.push(context, facing);
+        }
+        let mut collect_items = false;
+        if !self.is_full() {
+            match self.pull(context) {
+                Some(pulled) => moved |= pulled,
+                None => collect_items = true,
+            }
+        }
+        if moved {
+            self.transfer_cooldown = TRANSFER_COOLDOWN;
+        }
+        TickResult {
+            collect_items,
+            ..Default::default()
+        }
+    }
+
+    fn items_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        Some(&mut self.items)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_nbt::compound::NbtCompound;
+
+    use crate::{
+        block::{
+            block_entity::{
+                block_entity_from_nbt,
+                furnace::{FurnaceBlockEntity, FUEL_SLOT, INPUT_SLOT},
+                tests::{TestBlockEntities, TestRecipes},
+                BlockEntity, TickContext, TickResult,
+            },
+            block_registry::get_block,
+            random_tick::with_property,
+        },
+        item::ItemStack,
+    };
+
+    use super::{HopperBlockEntity, TRANSFER_COOLDOWN};
+
+    const HOPPER: WorldPosition = WorldPosition(Vector3::new(0, 64, 0));
+
+    fn hopper_state(facing: &str, enabled: bool) -> u16 {
+        let block = get_block("minecraft:hopper").unwrap();
+        let state = with_property(block, block.default_state_id, "facing", facing);
+        with_property(block, state, "enabled", &enabled.to_string())
+    }
+
+    fn tick(
+        hopper: &mut HopperBlockEntity,
+        state_id: u16,
+        block_entities: &TestBlockEntities,
+    ) -> TickResult {
+        hopper.tick(&TickContext {
+            position: HOPPER,
+            state_id,
+            players: &[],
+            recipes: &TestRecipes,
+            brewing: &TestRecipes,
+            block_entities,
+        })
+    }
+
+    fn furnace(block_entities: &TestBlockEntities, position: WorldPosition) -> FurnaceBlockEntity {
+        block_entities.0.borrow()[&position]
+            .0
+            .as_any()
+            .downcast_ref::<FurnaceBlockEntity>()
+            .unwrap()
+            .clone()
+    }
+
+    fn with_block_entity(position: WorldPosition, id: &str) -> TestBlockEntities {
+        let block_entities = TestBlockEntities::default();
+        block_entities.0.borrow_mut().insert(
+            position,
+            (block_entity_from_nbt(id, &NbtCompound::new()), 0),
+        );
+        block_entities
+    }
+
+    #[test]
+    fn pushes_items_into_the_furnace_below() {
+        let below = WorldPosition(Vector3::new(0, 63, 0));
+        let block_entities = with_block_entity(below, "minecraft:furnace");
+        let mut hopper = HopperBlockEntity::from_nbt(&NbtCompound::new());
+        hopper.items[2] = Some(ItemStack::of("beef", 2));
+
+        tick(&mut hopper, hopper_state("down", true), &block_entities);
+        assert_eq!(
+            furnace(&block_entities, below).items[INPUT_SLOT],
+            Some(ItemStack::of("beef", 1))
+        );
+        assert_eq!(hopper.items[2], Some(ItemStack::of("beef", 1)));
+        assert_eq!(hopper.transfer_cooldown, TRANSFER_COOLDOWN);
+
+        // Nothing moves while the hopper cools down
+        for _ in 1..TRANSFER_COOLDOWN {
+            tick(&mut hopper, hopper_state("down", true), &block_entities);
+        }
+        assert_eq!(hopper.items[2], Some(ItemStack::of("beef", 1)));
+        tick(&mut hopper, hopper_state("down", true), &block_entities);
+        assert_eq!(hopper.items[2], None);
+    }
+
+    #[test]
+    fn furnaces_take_fuel_from_the_sides() {
+        let east = WorldPosition(Vector3::new(1, 64, 0));
+        let block_entities = with_block_entity(east, "minecraft:furnace");
+        let mut hopper = HopperBlockEntity::from_nbt(&NbtCompound::new());
+        hopper.items[0] = Some(ItemStack::of("beef", 1));
+        hopper.items[1] = Some(ItemStack::of("coal", 1));
+
+        tick(&mut hopper, hopper_state("east", true), &block_entities);
+        let furnace = furnace(&block_entities, east);
+        assert_eq!(furnace.items[INPUT_SLOT], None);
+        assert_eq!(furnace.items[FUEL_SLOT], Some(ItemStack::of("coal", 1)));
+        assert_eq!(hopper.items[0], Some(ItemStack::of("beef", 1)));
+    }
+
+    #[test]
+    fn pulls_items_out_of_the_chest_above() {
+        let above = WorldPosition(Vector3::new(0, 65, 0));
+        let block_entities = with_block_entity(above, "minecraft:chest");
+        block_entities
+            .0
+            .borrow_mut()
+            .get_mut(&above)
+            .unwrap()
+            .0
+            .items_mut()
+            .unwrap()[26] = Some(ItemStack::of("stone", 3));
+        let mut hopper = HopperBlockEntity::from_nbt(&NbtCompound::new());
+
+        let result = tick(&mut hopper, hopper_state("down", true), &block_entities);
+        assert!(!result.collect_items);
+        assert_eq!(hopper.items[0], Some(ItemStack::of("stone", 1)));
+        let mut chest = block_entities.0.borrow_mut();
+        assert_eq!(
+            chest.get_mut(&above).unwrap().0.items_mut().unwrap()[26],
+            Some(ItemStack::of("stone", 2))
+        );
+    }
+
+    #[test]
+    fn locked_hoppers_keep_their_items() {
+        let below = WorldPosition(Vector3::new(0, 63, 0));
+        let block_entities = with_block_entity(below, "minecraft:furnace");
+        let mut hopper = HopperBlockEntity::from_nbt(&NbtCompound::new());
+        hopper.items[0] = Some(ItemStack::of("beef", 1));
+
+        let result = tick(&mut hopper, hopper_state("down", false), &block_entities);
+        assert!(!result.collect_items);
+        assert_eq!(hopper.items[0], Some(ItemStack::of("beef", 1)));
+    }
+
+    #[test]
+    fn hoppers_without_a_container_above_collect_items() {
+        let mut hopper = HopperBlockEntity::from_nbt(&NbtCompound::new());
+        let result = tick(
+            &mut hopper,
+            hopper_state("down", true),
+            &TestBlockEntities::default(),
+        );
+        assert!(result.collect_items);
+
+        let mut lying = ItemStack::of("stone", 64);
+        hopper.items = [
+            Some(ItemStack::of("stone", 63)),
+            Some(ItemStack::of("dirt", 1)),
+            None,
+            None,
+            None,
+        ];
+        assert!(hopper.collect(&mut lying));
+        assert_eq!(hopper.items[0], Some(ItemStack::of("stone", 64)));
+        assert_eq!(hopper.items[2], Some(ItemStack::of("stone", 63)));
+        assert_eq!(lying.item_count, 0);
+    }
+}
@@ -0,0 +1,496 @@
+use std::{any::Any, collections::HashMap, sync::LazyLock};
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::item::ItemStack;
+
+use super::{
+    block_registry::{get_block_entity_type, BLOCKS},
+    BlockFace,
+};
+
+pub mod brewing_stand;
+pub mod campfire;
+pub mod furnace;
+pub mod hopper;
+pub mod spawner;
+
+use brewing_stand::BrewingStandBlockEntity;
+use campfire::CampfireBlockEntity;
+use furnace::FurnaceBlockEntity;
+use hopper::HopperBlockEntity;
+use spawner::{MobSpawnerBlockEntity, SpawnAttempt};
+
+/// What a ticking block entity knows about the world around it
+pub struct TickContext<'a> {
+    pub position: WorldPosition,
+    /// The state of the block the block entity belongs to
+    pub state_id: u16,
+    /// The positions of the players in the world
+    pub players: &'a [Vector3<f64>],
+    pub recipes: &'a dyn CookingRecipes,
+    pub brewing: &'a dyn BrewingRecipes,
+    /// The other block entities of the world, hoppers move items in and out of them
+    pub block_entities: &'a dyn NearbyBlockEntities,
+}
+
+/// The block entities a ticking block entity reaches
+pub trait NearbyBlockEntities {
+    /// Changes the block entity at the position, which gets the state of its block. Returns
+    /// whether the change changed something, `None` if there is no block entity or it is busy
+    fn update(
+        &self,
+        position: &WorldPosition,
+        update: &mut dyn FnMut(&mut dyn BlockEntity, u16) -> bool,
+    ) -> Option<bool>;
+}
+
+/// The cooking recipes and fuels of furnaces, which are loaded with the other recipes outside
+/// of the world
+pub trait CookingRecipes: Sync {
+    /// What the input cooks into in the block entity type, with the cooking time in ticks and
+    /// the experience it gives
+    fn cooking_result(
+        &self,
+        block_entity: &str,
+        input: &ItemStack,
+    ) -> Option<(ItemStack, u16, f32)>;
+
+    /// How many ticks the item keeps a furnace burning, `None` if it is no fuel
+    fn fuel_burn_time(&self, fuel: &ItemStack) -> Option<u16>;
+}
+
+/// The potion mixes of brewing stands, which are loaded with the other recipes outside of the
+/// world
+pub trait BrewingRecipes: Sync {
+    /// What the potion turns into when brewed with the ingredient, `None` if the ingredient has
+    /// no effect on it
+    fn brewing_result(&self, potion: &ItemStack, ingredient: &ItemStack) -> Option<ItemStack>;
+
+    /// Whether the item goes into one of the three bottle slots
+    fn is_potion_container(&self, item: &ItemStack) -> bool;
+
+    fn is_ingredient(&self, item: &ItemStack) -> bool;
+
+    fn is_fuel(&self, item: &ItemStack) -> bool;
+}
+
+/// What a block entity did on its tick, the world applies it once the chunk is unlocked
+#[derive(Default)]
+pub struct TickResult {
+    /// The state the block changes to
+    pub state_id: Option<u16>,
+    /// The mobs a spawner tries to spawn
+    pub spawn: Option<SpawnAttempt>,
+    /// The items a campfire finished cooking, the world drops what they cook into
+    pub cooked: Vec<ItemStack>,
+    /// The items the block entity pushed out, they drop at its block
+    pub dropped: Vec<ItemStack>,
+    /// The data players need to render the block entity again
+    pub block_entity_update: Option<NbtCompound>,
+    /// Whether a hopper collects the items lying on it
+    pub collect_items: bool,
+}
+
+/// The data of a block which doesn't fit in its state, like the items in a furnace. Block
+/// entities are kept per chunk and saved with it
+pub trait BlockEntity: Send + Sync {
+    /// The id of the block entity type, e.g. `minecraft:furnace`
+    fn id(&self) -> &str;
+
+    /// Writes the data vanilla saves, without the id and the position
+    fn write_nbt(&self, nbt: &mut NbtCompound);
+
+    /// The data players need to render the block entity, sent with the chunk. Most block
+    /// entities are rendered from their block alone
+    fn update_nbt(&self) -> Option<NbtCompound> {
+        None
+    }
+
+    /// Whether the block entity runs every tick while its chunk is near a player
+    fn is_ticking(&self) -> bool {
+        false
+    }
+
+    /// Runs a tick of the block entity
+    fn tick(&mut self, _context: &TickContext) -> TickResult {
+        TickResult::default()
+    }
+
+    /// The item slots of block entities which keep items, hoppers move items through them
+    fn items_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        None
+    }
+
+    /// Whether a hopper may put the item into the slot through the face of the block, like
+    /// furnaces only take fuel through their sides
+    fn can_insert(
+        &self,
+        _slot: usize,
+        _item: &ItemStack,
+        _face: BlockFace,
+        _context: &TickContext,
+    ) -> bool {
+        true
+    }
+
+    /// Whether a hopper below may take the item out of the slot
+    fn can_extract(&self, _slot: usize, _item: &ItemStack) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Creates a block entity of a type from its saved data
+type BlockEntityFactory = fn(&str, &NbtCompound) -> Box<dyn BlockEntity>;
+
+/// The block entity types with their own data, the others keep the NBT they were loaded with
+static BLOCK_ENTITY_TYPES: LazyLock<HashMap<&'static str, BlockEntityFactory>> =
+    LazyLock::new(|| {
+        let furnace: BlockEntityFactory = |id, nbt| Box::new(FurnaceBlockEntity::from_nbt(id, nbt));
+        let hopper: BlockEntityFactory = |_, nbt| Box::new(HopperBlockEntity::from_nbt(nbt));
+        let spawner: BlockEntityFactory = |_, nbt| Box::new(MobSpawnerBlockEntity::from_nbt(nbt));
+        let campfire: BlockEntityFactory = |_, nbt| Box::new(CampfireBlockEntity::from_nbt(nbt));
+        let brewing_stand: BlockEntityFactory =
+            |_, nbt| Box::new(BrewingStandBlockEntity::from_nbt(nbt));
+        HashMap::from([
+            ("minecraft:furnace", furnace),
+            ("minecraft:smoker", furnace),
+            ("minecraft:blast_furnace", furnace),
+            ("minecraft:hopper", hopper),
+            ("minecraft:mob_spawner", spawner),
+            ("minecraft:campfire", campfire),
+            ("minecraft:brewing_stand", brewing_stand),
+        ])
+    });
+
+/// The block entity types which keep items without a type of their own, with their slots
+const CONTAINER_SLOTS: [(&str, usize); 6] = [
+    ("minecraft:chest", 27),
+    ("minecraft:trapped_chest", 27),
+    ("minecraft:barrel", 27),
+    ("minecraft:shulker_box", 27),
+    ("minecraft:dispenser", 9),
+    ("minecraft:dropper", 9),
+];
+
+/// A block entity without its own type, its NBT is kept as it was loaded. The items of
+/// containers are read, so hoppers can move them
+pub struct GenericBlockEntity {
+    id: String,
+    /// The slots of containers, empty for other block entities
+    items: Vec<Option<ItemStack>>,
+    nbt: NbtCompound,
+}
+
+impl GenericBlockEntity {
+    fn from_nbt(id: &str, nbt: &NbtCompound) -> Self {
+        let Some((_, slots)) = CONTAINER_SLOTS
+            .iter()
+            .find(|(container, _)| *container == id)
+        else {
+            return Self {
+                id: id.to_string(),
+                items: Vec::new(),
+                nbt: nbt.clone(),
+            };
+        };
+        let mut items = vec![None; *slots];
+        read_items(nbt, &mut items);
+        Self {
+            id: id.to_string(),
+            items,
+            nbt: nbt
+                .child_tags
+                .iter()
+                .filter(|(name, _)| name != "Items")
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl BlockEntity for GenericBlockEntity {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        // Containers which were never filled have no items saved
+        if self.items.iter().any(Option::is_some) {
+            write_items(&self.items, nbt);
+        }
+        nbt.extend(self.nbt.child_tags.iter().cloned());
+    }
+
+    fn items_mut(&mut self) -> Option<&mut [Option<ItemStack>]> {
+        (!self.items.is_empty()).then_some(self.items.as_mut_slice())
+    }
+
+    fn can_insert(
+        &self,
+        _slot: usize,
+        item: &ItemStack,
+        _face: BlockFace,
+        _context: &TickContext,
+    ) -> bool {
+        // Shulker boxes don't fit into shulker boxes
+        self.id != "minecraft:shulker_box" || !item.is_shulker_box()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// The block entity of the type with the id, read from its saved data
+pub fn block_entity_from_nbt(id: &str, nbt: &NbtCompound) -> Box<dyn BlockEntity> {
+    match BLOCK_ENTITY_TYPES.get(id) {
+        Some(factory) => factory(id, nbt),
+        None => Box::new(GenericBlockEntity::from_nbt(id, nbt)),
+    }
+}
+
+/// A new block entity for blocks in the state, if their block entity type has its own data.
+/// Other block entities are created by the blocks which use them
+pub fn new_block_entity(state_id: u16) -> Option<Box<dyn BlockEntity>> {
+    let id = get_block_entity_type(state_id)?;
+    let factory = BLOCK_ENTITY_TYPES.get(id.as_str())?;
+    Some(factory(&id, &NbtCompound::new()))
+}
+
+/// The id the block entity type has in the registry sent to players
+pub fn block_entity_type_id(id: &str) -> Option<i32> {
+    let name = id.strip_prefix("minecraft:").unwrap_or(id);
+    BLOCKS
+        .block_entity_types
+        .iter()
+        .position(|known| known == name)
+        .map(|index| index as i32)
+}
+
+/// The block entity like vanilla saves it in the chunk, with its id and position
+pub fn block_entity_to_nbt(
+    block_entity: &dyn BlockEntity,
+    position: &WorldPosition,
+) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    if !block_entity.id().is_empty() {
+        nbt.put(
+            "id".to_string(),
+            NbtTag::String(block_entity.id().to_string()),
+        );
+    }
+    nbt.put("x".to_string(), NbtTag::Int(position.0.x));
+    nbt.put("y".to_string(), NbtTag::Int(position.0.y));
+    nbt.put("z".to_string(), NbtTag::Int(position.0.z));
+    nbt.put("keepPacked".to_string(), NbtTag::Byte(0));
+    block_entity.write_nbt(&mut nbt);
+    nbt
+}
+
+/// The saved data of the block entity, without its id and position
+pub fn block_entity_data(block_entity: &dyn BlockEntity) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    block_entity.write_nbt(&mut nbt);
+    nbt
+}
+
+/// Copies the tags which the block entity doesn't read itself, so data of features which are
+/// not implemented yet, like custom names, survives saving
+pub(crate) fn other_tags(nbt: &NbtCompound, known: &[&str]) -> NbtCompound {
+    nbt.child_tags
+        .iter()
+        .filter(|(name, _)| {
+            !known.contains(&name.as_str())
+                && !matches!(name.as_str(), "x" | "y" | "z" | "id" | "keepPacked")
+        })
+        .cloned()
+        .collect()
+}
+
+/// Fills the slots from a vanilla `Items` list
+pub(crate) fn read_items(nbt: &NbtCompound, slots: &mut [Option<ItemStack>]) {
+    for compound in nbt
+        .get_list("Items")
+        .into_iter()
+        .flatten()
+        .filter_map(NbtTag::extract_compound)
+    {
+        let Some(slot) = compound.get_byte("Slot") else {
+            continue;
+        };
+        if let Some(slot) = slots.get_mut(slot as usize) {
+            *slot = ItemStack::read_item_nbt(compound);
+        }
+    }
+}
+
+/// Writes the slots as a vanilla `Items` list, skipping empty slots
+pub(crate) fn write_items(slots: &[Option<ItemStack>], nbt: &mut NbtCompound) {
+    let items = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, item)| {
+            let item = item.as_ref()?;
+            let mut compound = NbtCompound::new();
+            compound.put("Slot".to_string(), NbtTag::Byte(slot as i8));
+            item.write_item_nbt(&mut compound);
+            Some(NbtTag::Compound(compound))
+        })
+        .collect();
+    nbt.put("Items".to_string(), NbtTag::List(items));
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+    use crate::{
+        block::block_registry::get_block,
+        item::{item_registry::get_item, ItemStack},
+    };
+
+    use super::{
+        block_entity_data, block_entity_from_nbt, block_entity_to_nbt, block_entity_type_id,
+        new_block_entity, BlockEntity, BrewingRecipes, CookingRecipes, HopperBlockEntity,
+        NearbyBlockEntities,
+    };
+
+    /// Block entities around a ticking block entity, with the state of their block
+    #[derive(Default)]
+    pub(crate) struct TestBlockEntities(
+        pub(crate) RefCell<HashMap<WorldPosition, (Box<dyn BlockEntity>, u16)>>,
+    );
+
+    impl NearbyBlockEntities for TestBlockEntities {
+        fn update(
+            &self,
+            position: &WorldPosition,
+            update: &mut dyn FnMut(&mut dyn BlockEntity, u16) -> bool,
+        ) -> Option<bool> {
+            let mut block_entities = self.0.borrow_mut();
+            let (block_entity, state_id) = block_entities.get_mut(position)?;
+            Some(update(block_entity.as_mut(), *state_id))
+        }
+    }
+
+    /// Furnaces cook beef in 3 ticks, coal burns for 4 ticks. Nether wart turns potions into
+    /// splash potions and dragon's breath into lingering potions
+    pub(crate) struct TestRecipes;
+
+    impl CookingRecipes for TestRecipes {
+        fn cooking_result(
+            &self,
+            _block_entity: &str,
+            input: &ItemStack,
+        ) -> Option<(ItemStack, u16, f32)> {
+            (input.item_id == get_item("beef")?.id)
+                .then(|| Some((ItemStack::new(1, get_item("cooked_beef")?.id), 3, 0.35)))?
+        }
+
+        fn fuel_burn_time(&self, fuel: &ItemStack) -> Option<u16> {
+            (fuel.item_id == get_item("coal")?.id).then_some(4)
+        }
+    }
+
+    impl BrewingRecipes for TestRecipes {
+        fn brewing_result(&self, potion: &ItemStack, ingredient: &ItemStack) -> Option<ItemStack> {
+            if potion.item_id != get_item("potion")?.id {
+                return None;
+            }
+            let result = if ingredient.item_id == get_item("nether_wart")?.id {
+                "splash_potion"
+            } else if ingredient.item_id == get_item("dragon_breath")?.id {
+                "lingering_potion"
+            } else {
+                return None;
+            };
+            Some(ItemStack::new(1, get_item(result)?.id))
+        }
+
+        fn is_potion_container(&self, item: &ItemStack) -> bool {
+            get_item("potion").is_some_and(|potion| potion.id == item.item_id)
+        }
+
+        fn is_ingredient(&self, item: &ItemStack) -> bool {
+            ["nether_wart", "dragon_breath"]
+                .into_iter()
+                .filter_map(get_item)
+                .any(|ingredient| ingredient.id == item.item_id)
+        }
+
+        fn is_fuel(&self, item: &ItemStack) -> bool {
+            get_item("blaze_powder").is_some_and(|powder| powder.id == item.item_id)
+        }
+    }
+
+    #[test]
+    fn unknown_types_keep_their_nbt() {
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "CustomName".to_string(),
+            NbtTag::String("\"Box\"".to_string()),
+        );
+        let block_entity = block_entity_from_nbt("minecraft:barrel", &nbt);
+        assert_eq!(block_entity.id(), "minecraft:barrel");
+        assert_eq!(block_entity_data(block_entity.as_ref()), nbt);
+    }
+
+    #[test]
+    fn containers_keep_their_items() {
+        let mut nbt = NbtCompound::new();
+        let mut slots = [const { None }; 27];
+        slots[4] = Some(ItemStack::new(2, get_item("stone").unwrap().id));
+        super::write_items(&slots, &mut nbt);
+        let mut chest = block_entity_from_nbt("minecraft:chest", &nbt);
+        assert_eq!(chest.items_mut().unwrap(), slots.as_slice());
+        assert_eq!(block_entity_data(chest.as_ref()), nbt);
+        // Other block entities have no slots
+        assert!(block_entity_from_nbt("minecraft:sign", &nbt)
+            .items_mut()
+            .is_none());
+    }
+
+    #[test]
+    fn saved_with_id_and_position() {
+        let block_entity = block_entity_from_nbt("minecraft:hopper", &NbtCompound::new());
+        let nbt = block_entity_to_nbt(
+            block_entity.as_ref(),
+            &WorldPosition(Vector3::new(1, -2, 3)),
+        );
+        assert_eq!(nbt.get_string("id").unwrap(), "minecraft:hopper");
+        assert_eq!(nbt.get_int("y"), Some(-2));
+        assert!(block_entity
+            .as_any()
+            .downcast_ref::<HopperBlockEntity>()
+            .is_some());
+    }
+
+    #[test]
+    fn created_for_ticking_blocks() {
+        let furnace = get_block("minecraft:furnace").unwrap().default_state_id;
+        assert_eq!(new_block_entity(furnace).unwrap().id(), "minecraft:furnace");
+        // Chests keep their items themselves
+        let chest = get_block("minecraft:chest").unwrap().default_state_id;
+        assert!(new_block_entity(chest).is_none());
+    }
+
+    #[test]
+    fn network_ids() {
+        assert_eq!(block_entity_type_id("minecraft:furnace"), Some(0));
+        assert_eq!(block_entity_type_id("minecraft:mob_spawner"), Some(9));
+        assert_eq!(block_entity_type_id("minecraft:unknown"), None);
+    }
+}
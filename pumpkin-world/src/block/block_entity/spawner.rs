@@ -0,0 +1,466 @@
+use std::{any::Any, ops::RangeInclusive};
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use rand::Rng;
+
+use crate::{
+    block::{block_registry::get_state_by_state_id, random_tick::BlockAccess},
+    chunk::light::LightType,
+};
+
+use super::{other_tags, BlockEntity, TickContext, TickResult};
+
+const KNOWN_TAGS: [&str; 9] = [
+    "Delay",
+    "MinSpawnDelay",
+    "MaxSpawnDelay",
+    "SpawnCount",
+    "MaxNearbyEntities",
+    "RequiredPlayerRange",
+    "SpawnRange",
+    "SpawnData",
+    "SpawnPotentials",
+];
+
+/// The light levels in which a spawner spawns its mob, from the `custom_spawn_rules` of the
+/// spawn data. Without them spawners spawn in any light
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightLimits {
+    pub block: RangeInclusive<u8>,
+    pub sky: RangeInclusive<u8>,
+}
+
+impl LightLimits {
+    fn read_nbt(nbt: &NbtCompound) -> Self {
+        Self {
+            block: read_light_limit(nbt.get("block_light_limit")),
+            sky: read_light_limit(nbt.get("sky_light_limit")),
+        }
+    }
+}
+
+/// Limits are saved as the highest level or as a range
+fn read_light_limit(tag: Option<&NbtTag>) -> RangeInclusive<u8> {
+    let clamp = |level: i32| level.clamp(0, 15) as u8;
+    match tag {
+        Some(NbtTag::Int(max)) => 0..=clamp(*max),
+        Some(NbtTag::Compound(range)) => {
+            clamp(range.get_int("min_inclusive").unwrap_or(0))
+                ..=clamp(range.get_int("max_inclusive").unwrap_or(15))
+        }
+        _ => 0..=15,
+    }
+}
+
+/// The mobs a spawner tries to spawn once its delay ran out. The world spawns them where there
+/// is space and resets the spawner if any mob spawned
+#[derive(Debug, Clone)]
+pub struct SpawnAttempt {
+    /// The id of the entity type, e.g. `minecraft:zombie`
+    pub entity_id: String,
+    /// Where the mobs would spawn, the bottom center of each mob
+    pub positions: Vec<Vector3<f64>>,
+    pub light_limits: Option<LightLimits>,
+    /// The spawner stops spawning while this many mobs of its type are around it
+    pub max_nearby_entities: i32,
+    /// How far around the spawner mobs count as nearby
+    pub spawn_range: i32,
+}
+
+impl SpawnAttempt {
+    /// Whether a mob of the size fits at the position without touching blocks, and the light
+    /// limits of the spawner allow it
+    pub fn allows(
+        &self,
+        blocks: &impl BlockAccess,
+        position: Vector3<f64>,
+        width: f64,
+        height: f64,
+    ) -> bool {
+        let block = WorldPosition(Vector3::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        ));
+        if let Some(limits) = &self.light_limits {
+            let block_light = blocks.light(LightType::Block, &block).unwrap_or(0);
+            let sky_light = blocks.light(LightType::Sky, &block).unwrap_or(0);
+            if !limits.block.contains(&block_light) || !limits.sky.contains(&sky_light) {
+                return false;
+            }
+        }
+
+        // Blocks with any collision count as full blocks
+        let min_x = (position.x - width / 2.0).floor() as i32;
+        let max_x = (position.x + width / 2.0).ceil() as i32;
+        let min_y = position.y.floor() as i32;
+        let max_y = (position.y + height).ceil() as i32;
+        let min_z = (position.z - width / 2.0).floor() as i32;
+        let max_z = (position.z + width / 2.0).ceil() as i32;
+        for x in min_x..max_x {
+            for y in min_y..max_y {
+                for z in min_z..max_z {
+                    let position = WorldPosition(Vector3::new(x, y, z));
+                    let Some(state_id) = blocks.block_state(&position) else {
+                        return false;
+                    };
+                    if get_state_by_state_id(state_id)
+                        .is_some_and(|state| !state.collision_shapes.is_empty())
+                    {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Monster spawners, they spawn their mob around them while a player is close, like vanilla's
+/// `BaseSpawner.serverTick`
+pub struct MobSpawnerBlockEntity {
+    /// Ticks until the next spawn, -1 picks a new delay on the next tick
+    pub delay: i16,
+    pub min_spawn_delay: i16,
+    pub max_spawn_delay: i16,
+    /// How many mobs the spawner tries to spawn at once
+    pub spawn_count: i16,
+    pub max_nearby_entities: i16,
+    /// Players closer than this activate the spawner
+    pub required_player_range: i16,
+    /// How far from the spawner mobs spawn
+    pub spawn_range: i16,
+    /// The mob spawned next with its spawn rules, players see it spinning in the spawner
+    pub spawn_data: NbtCompound,
+    /// The weighted spawn data the next spawn data is picked from after every spawn
+    pub spawn_potentials: Vec<(i32, NbtCompound)>,
+    /// Custom names and other tags, which are not handled by the block entity
+    other: NbtCompound,
+}
+
+impl MobSpawnerBlockEntity {
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let spawn_potentials = nbt
+            .get_list("SpawnPotentials")
+            .map(|potentials| {
+                potentials
+                    .iter()
+                    .filter_map(NbtTag::extract_compound)
+                    .filter_map(|potential| {
+                        Some((
+                            potential.get_int("weight").unwrap_or(1),
+                            potential.get_compound("data")?.clone(),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            delay: nbt.get_short("Delay").unwrap_or(20),
+            min_spawn_delay: nbt.get_short("MinSpawnDelay").unwrap_or(200),
+            max_spawn_delay: nbt.get_short("MaxSpawnDelay").unwrap_or(800),
+            spawn_count: nbt.get_short("SpawnCount").unwrap_or(4),
+            max_nearby_entities: nbt.get_short("MaxNearbyEntities").unwrap_or(6),
+            required_player_range: nbt.get_short("RequiredPlayerRange").unwrap_or(16),
+            spawn_range: nbt.get_short("SpawnRange").unwrap_or(4),
+            spawn_data: nbt.get_compound("SpawnData").cloned().unwrap_or_default(),
+            spawn_potentials,
+            other: other_tags(nbt, &KNOWN_TAGS),
+        }
+    }
+
+    /// The id of the entity type the spawner spawns next, empty spawners spawn nothing
+    pub fn entity_id(&self) -> Option<&str> {
+        self.spawn_data
+            .get_compound("entity")?
+            .get_string("id")
+            .map(String::as_str)
+    }
+
+    /// Sets the mob the spawner spawns, e.g. when a player uses a spawn egg on it
+    pub fn set_entity_id(&mut self, id: &str) {
+        let mut entity = NbtCompound::new();
+        entity.put("id".to_string(), NbtTag::String(id.to_string()));
+        let mut spawn_data = NbtCompound::new();
+        spawn_data.put("entity".to_string(), NbtTag::Compound(entity));
+        self.spawn_data = spawn_data;
+        self.spawn_potentials.clear();
+    }
+
+    fn is_near_player(&self, position: &WorldPosition, players: &[Vector3<f64>]) -> bool {
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        let range = f64::from(self.required_player_range);
+        players
+            .iter()
+            .any(|player| player.sub(&center).length_squared() < range * range)
+    }
+
+    /// Waits a random delay until the next spawn and picks the next mob from the spawn potentials
+    pub fn reset_delay(&mut self, rng: &mut impl Rng) {
+        self.delay = if self.max_spawn_delay <= self.min_spawn_delay {
+            self.min_spawn_delay
+        } else {
+            self.min_spawn_delay + rng.gen_range(0..self.max_spawn_delay - self.min_spawn_delay)
+        };
+
+        let total_weight: i32 = self
+            .spawn_potentials
+            .iter()
+            .map(|(weight, _)| (*weight).max(0))
+            .sum();
+        if total_weight > 0 {
+            let mut picked = rng.gen_range(0..total_weight);
+            for (weight, data) in &self.spawn_potentials {
+                picked -= (*weight).max(0);
+                if picked < 0 {
+                    self.spawn_data = data.clone();
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Random positions around the spawner, one for every mob it spawns at once
+    fn spawn_positions(&self, position: &WorldPosition, rng: &mut impl Rng) -> Vec<Vector3<f64>> {
+        let range = f64::from(self.spawn_range);
+        (0..self.spawn_count)
+            .map(|_| {
+                let x =
+                    f64::from(position.0.x) + (rng.gen::<f64>() - rng.gen::<f64>()) * range + 0.5;
+                let y = f64::from(position.0.y + rng.gen_range(0..3) - 1);
+                let z =
+                    f64::from(position.0.z) + (rng.gen::<f64>() - rng.gen::<f64>()) * range + 0.5;
+                Vector3::new(x, y, z)
+            })
+            .collect()
+    }
+}
+
+impl BlockEntity for MobSpawnerBlockEntity {
+    fn id(&self) -> &str {
+        "minecraft:mob_spawner"
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put("Delay".to_string(), NbtTag::Short(self.delay));
+        nbt.put(
+            "MinSpawnDelay".to_string(),
+            NbtTag::Short(self.min_spawn_delay),
+        );
+        nbt.put(
+            "MaxSpawnDelay".to_string(),
+            NbtTag::Short(self.max_spawn_delay),
+        );
+        nbt.put("SpawnCount".to_string(), NbtTag::Short(self.spawn_count));
+        nbt.put(
+            "MaxNearbyEntities".to_string(),
+            NbtTag::Short(self.max_nearby_entities),
+        );
+        nbt.put(
+            "RequiredPlayerRange".to_string(),
+            NbtTag::Short(self.required_player_range),
+        );
+        nbt.put("SpawnRange".to_string(), NbtTag::Short(self.spawn_range));
+        nbt.put(
+            "SpawnData".to_string(),
+            NbtTag::Compound(self.spawn_data.clone()),
+        );
+        let spawn_potentials = self
+            .spawn_potentials
+            .iter()
+            .map(|(weight, data)| {
+                let mut potential = NbtCompound::new();
+                potential.put("weight".to_string(), NbtTag::Int(*weight));
+                potential.put("data".to_string(), NbtTag::Compound(data.clone()));
+                NbtTag::Compound(potential)
+            })
+            .collect();
+        nbt.put(
+            "SpawnPotentials".to_string(),
+            NbtTag::List(spawn_potentials),
+        );
+        nbt.extend(self.other.child_tags.iter().cloned());
+    }
+
+    fn update_nbt(&self) -> Option<NbtCompound> {
+        // Players render the mob of the spawn data spinning inside of the spawner
+        let mut nbt = NbtCompound::new();
+        self.write_nbt(&mut nbt);
+        nbt.remove("SpawnPotentials");
+        Some(nbt)
+    }
+
+    fn is_ticking(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, context: &TickContext) -> TickResult {
+        if !self.is_near_player(&context.position, context.players) {
+            return TickResult::default();
+        }
+        let mut rng = rand::thread_rng();
+        if self.delay == -1 {
+            self.reset_delay(&mut rng);
+        }
+        if self.delay > 0 {
+            self.delay -= 1;
+            return TickResult::default();
+        }
+
+        let Some(entity_id) = self.entity_id().map(str::to_string) else {
+            self.reset_delay(&mut rng);
+            return TickResult::default();
+        };
+        TickResult {
+            spawn: Some(SpawnAttempt {
+                entity_id,
+                positions: self.spawn_positions(&context.position, &mut rng),
+                light_limits: self
+                    .spawn_data
+                    .get_compound("custom_spawn_rules")
+                    .map(LightLimits::read_nbt),
+                max_nearby_entities: i32::from(self.max_nearby_entities),
+                spawn_range: i32::from(self.spawn_range),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::block::{
+        block_entity::{
+            tests::{TestBlockEntities, TestRecipes},
+            BlockEntity, TickContext,
+        },
+        random_tick::tests::TestBlocks,
+    };
+
+    use super::{LightLimits, MobSpawnerBlockEntity};
+
+    fn zombie_spawner() -> MobSpawnerBlockEntity {
+        let mut spawner = MobSpawnerBlockEntity::from_nbt(&NbtCompound::new());
+        spawner.set_entity_id("minecraft:zombie");
+        spawner
+    }
+
+    fn tick(spawner: &mut MobSpawnerBlockEntity, player: Vector3<f64>) -> bool {
+        spawner
+            .tick(&TickContext {
+                position: WorldPosition(Vector3::new(0, 64, 0)),
+                state_id: 0,
+                players: &[player],
+                recipes: &TestRecipes,
+                brewing: &TestRecipes,
+                block_entities: &TestBlockEntities::default(),
+            })
+            .spawn
+            .is_some()
+    }
+
+    #[test]
+    fn spawns_after_delay_near_players() {
+        let mut spawner = zombie_spawner();
+        let near = Vector3::new(5.0, 64.0, 5.0);
+        let far = Vector3::new(50.0, 64.0, 0.0);
+        // Far away players don't count the delay down
+        for _ in 0..100 {
+            assert!(!tick(&mut spawner, far));
+        }
+        assert_eq!(spawner.delay, 20);
+        for _ in 0..20 {
+            assert!(!tick(&mut spawner, near));
+        }
+        assert!(tick(&mut spawner, near));
+    }
+
+    #[test]
+    fn spawn_positions_stay_in_range() {
+        let mut spawner = zombie_spawner();
+        spawner.delay = 0;
+        let attempt = spawner
+            .tick(&TickContext {
+                position: WorldPosition(Vector3::new(10, 64, -10)),
+                state_id: 0,
+                players: &[Vector3::new(10.0, 64.0, -10.0)],
+                recipes: &TestRecipes,
+                brewing: &TestRecipes,
+                block_entities: &TestBlockEntities::default(),
+            })
+            .spawn
+            .unwrap();
+        assert_eq!(attempt.entity_id, "minecraft:zombie");
+        assert_eq!(attempt.positions.len(), 4);
+        for position in attempt.positions {
+            assert!((position.x - 10.5).abs() <= 4.0);
+            assert!((63.0..=65.0).contains(&position.y));
+            assert!((position.z + 9.5).abs() <= 4.0);
+        }
+    }
+
+    #[test]
+    fn reset_picks_from_potentials() {
+        let mut spawner = zombie_spawner();
+        let mut skeleton = MobSpawnerBlockEntity::from_nbt(&NbtCompound::new());
+        skeleton.set_entity_id("minecraft:skeleton");
+        spawner.spawn_potentials = vec![(1, skeleton.spawn_data)];
+        spawner.reset_delay(&mut StdRng::seed_from_u64(0));
+        assert_eq!(spawner.entity_id(), Some("minecraft:skeleton"));
+        assert!((200..800).contains(&spawner.delay));
+    }
+
+    #[test]
+    fn space_and_light_checks() {
+        let mut spawner = zombie_spawner();
+        spawner.delay = 0;
+        let mut attempt = spawner
+            .tick(&TickContext {
+                position: WorldPosition(Vector3::new(0, 0, 0)),
+                state_id: 0,
+                players: &[Vector3::new(0.0, 0.0, 0.0)],
+                recipes: &TestRecipes,
+                brewing: &TestRecipes,
+                block_entities: &TestBlockEntities::default(),
+            })
+            .spawn
+            .unwrap();
+        let mut world = TestBlocks::new(0, 0);
+        let position = Vector3::new(0.5, 1.0, 0.5);
+        assert!(attempt.allows(&world, position, 0.6, 1.95));
+        world.set(0, 2, 0, "stone");
+        assert!(!attempt.allows(&world, position, 0.6, 1.95));
+
+        let lit = TestBlocks::new(15, 0);
+        let mut rules = NbtCompound::new();
+        rules.put("sky_light_limit".to_string(), NbtTag::Int(7));
+        attempt.light_limits = Some(LightLimits::read_nbt(&rules));
+        assert!(!attempt.allows(&lit, position, 0.6, 1.95));
+        assert!(attempt.allows(&TestBlocks::new(7, 0), position, 0.6, 1.95));
+    }
+
+    #[test]
+    fn saved_like_vanilla() {
+        let spawner = zombie_spawner();
+        let read = MobSpawnerBlockEntity::from_nbt(&spawner.update_nbt().unwrap());
+        assert_eq!(read.entity_id(), Some("minecraft:zombie"));
+        assert_eq!(read.max_nearby_entities, 6);
+    }
+}
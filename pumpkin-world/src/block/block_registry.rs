@@ -82,11 +82,61 @@ pub fn get_block_by_item<'a>(item_id: u16) -> Option<&'a Block> {
     let block_id = BLOCK_ID_BY_ITEM_ID.get(&item_id)?;
     BLOCKS_BY_ID.get(block_id)
 }
-#[expect(dead_code)]
+
+/// The id of the block entity type blocks in the state have, e.g. `minecraft:chest`
+pub fn get_block_entity_type(state_id: u16) -> Option<String> {
+    let type_id = get_state_by_state_id(state_id)?.block_entity_type?;
+    let name = BLOCKS.block_entity_types.get(type_id as usize)?;
+    Some(format!("minecraft:{name}"))
+}
+
+impl Block {
+    /// The property values of one of the block's states, in the order the block lists its properties.
+    /// States are numbered like the combinations of the property values, the last property changes first
+    pub fn state_properties(&self, state_id: u16) -> Vec<(&str, &str)> {
+        let first_state = self.states.first().map_or(state_id, |state| state.id);
+        let mut index = usize::from(state_id.saturating_sub(first_state));
+        let mut properties: Vec<(&str, &str)> = self
+            .properties
+            .iter()
+            .rev()
+            .map(|property| {
+                let value = &property.values[index % property.values.len()];
+                index /= property.values.len();
+                (property.name.as_str(), value.as_str())
+            })
+            .collect();
+        properties.reverse();
+        properties
+    }
+
+    /// The state with the given property values, missing or unknown values are taken from the default state
+    pub fn state_with_properties(&self, properties: &HashMap<String, String>) -> u16 {
+        let defaults = self.state_properties(self.default_state_id);
+        let index =
+            self.properties
+                .iter()
+                .zip(defaults)
+                .fold(0, |index, (property, (_, default))| {
+                    let value = properties
+                        .get(&property.name)
+                        .and_then(|value| property.values.iter().position(|known| known == value))
+                        .or_else(|| property.values.iter().position(|known| known == default))
+                        .unwrap_or(0);
+                    index * property.values.len() + value
+                });
+        let first_state = self
+            .states
+            .first()
+            .map_or(self.default_state_id, |state| state.id);
+        first_state + index as u16
+    }
+}
 #[derive(Deserialize, Clone, Debug)]
 pub struct TopLevel {
-    block_entity_types: Vec<String>,
-    shapes: Vec<Shape>,
+    pub block_entity_types: Vec<String>,
+    /// The boxes blocks are made of, states refer to them by their index
+    pub shapes: Vec<Shape>,
     pub blocks: Vec<Block>,
 }
 #[derive(Deserialize, Clone, Debug)]
@@ -101,11 +151,10 @@ pub struct Block {
     pub default_state_id: u16,
     pub states: Vec<State>,
 }
-#[expect(dead_code)]
 #[derive(Deserialize, Clone, Debug)]
 pub struct Property {
-    name: String,
-    values: Vec<String>,
+    pub name: String,
+    pub values: Vec<String>,
 }
 #[derive(Deserialize, Clone, Debug)]
 pub struct State {
@@ -118,9 +167,9 @@ pub struct State {
     pub collision_shapes: Vec<u16>,
     pub block_entity_type: Option<u32>,
 }
-#[expect(dead_code)]
+/// A box inside of a block, from 0 to 1 on each axis. Some blocks like fences reach above it
 #[derive(Deserialize, Clone, Debug)]
-struct Shape {
-    min: [f32; 3],
-    max: [f32; 3],
+pub struct Shape {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
 }
@@ -1,4 +1,12 @@
-use super::block_registry::{get_block, get_state_by_state_id};
+use std::collections::HashMap;
+
+use pumpkin_nbt::{
+    compound::NbtCompound,
+    snbt::{compound_from_snbt, SnbtError},
+};
+use thiserror::Error;
+
+use super::block_registry::{get_block, get_block_by_state_id, get_state_by_state_id};
 
 #[derive(Clone, Copy, Debug, Eq)]
 pub struct BlockState {
@@ -42,9 +50,143 @@ impl BlockState {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum BlockStateParseError {
+    #[error("Unknown block type '{0}'")]
+    UnknownBlock(String),
+    #[error("Block {block} does not have property '{property}'")]
+    UnknownProperty { block: String, property: String },
+    #[error("Block {block} does not accept '{value}' for {property} property")]
+    InvalidValue {
+        block: String,
+        property: String,
+        value: String,
+    },
+    #[error("Property '{0}' can only be set once")]
+    DuplicateProperty(String),
+    #[error("Expected value for property '{0}'")]
+    ExpectedValue(String),
+    #[error("Expected closing ] for block state properties")]
+    UnclosedProperties,
+    #[error("Unexpected '{0}' after the block")]
+    TrailingData(String),
+    #[error("Invalid block entity data: {0}")]
+    InvalidNbt(#[from] SnbtError),
+}
+
+/// The parts of a block given in a command, like `minecraft:chest[facing=north]{Items:[]}`. The
+/// name may be a tag, the properties and block entity data are optional
+pub struct BlockInputParts<'a> {
+    pub name: &'a str,
+    pub properties: Vec<(&'a str, &'a str)>,
+    pub nbt: Option<NbtCompound>,
+}
+
+impl<'a> BlockInputParts<'a> {
+    /// Splits the input into its parts, like vanilla's `BlockStateParser` does before looking up
+    /// the block
+    pub fn split(input: &'a str) -> Result<Self, BlockStateParseError> {
+        let name_end = input.find(['[', '{']).unwrap_or(input.len());
+        let (name, mut rest) = input.split_at(name_end);
+
+        let mut properties: Vec<(&str, &str)> = Vec::new();
+        if let Some(inner) = rest.strip_prefix('[') {
+            let Some((list, after)) = inner.split_once(']') else {
+                return Err(BlockStateParseError::UnclosedProperties);
+            };
+            for property in list.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                let Some((key, value)) = property.split_once('=') else {
+                    return Err(BlockStateParseError::ExpectedValue(property.to_string()));
+                };
+                let (key, value) = (key.trim(), value.trim());
+                if properties.iter().any(|(known, _)| *known == key) {
+                    return Err(BlockStateParseError::DuplicateProperty(key.to_string()));
+                }
+                properties.push((key, value));
+            }
+            rest = after;
+        }
+
+        let nbt = match rest {
+            "" => None,
+            nbt if nbt.starts_with('{') => Some(compound_from_snbt(nbt)?),
+            other => return Err(BlockStateParseError::TrailingData(other.to_string())),
+        };
+        Ok(Self {
+            name,
+            properties,
+            nbt,
+        })
+    }
+}
+
+/// A block state given in a command, properties which aren't given have their default value.
+/// Like vanilla's `BlockInput`
+pub struct BlockStateInput {
+    pub block_id: u16,
+    pub state_id: u16,
+    /// The property values which were given
+    pub properties: Vec<(String, String)>,
+    /// The block entity data to place with the block
+    pub nbt: Option<NbtCompound>,
+}
+
+impl BlockStateInput {
+    pub fn parse(input: &str) -> Result<Self, BlockStateParseError> {
+        let BlockInputParts {
+            name,
+            properties,
+            nbt,
+        } = BlockInputParts::split(input)?;
+        let block =
+            get_block(name).ok_or_else(|| BlockStateParseError::UnknownBlock(name.to_string()))?;
+
+        let mut values = HashMap::new();
+        for (key, value) in properties {
+            let Some(property) = block.properties.iter().find(|p| p.name == key) else {
+                return Err(BlockStateParseError::UnknownProperty {
+                    block: name.to_string(),
+                    property: key.to_string(),
+                });
+            };
+            if !property.values.iter().any(|known| known == value) {
+                return Err(BlockStateParseError::InvalidValue {
+                    block: name.to_string(),
+                    property: key.to_string(),
+                    value: value.to_string(),
+                });
+            }
+            values.insert(key.to_string(), value.to_string());
+        }
+        Ok(Self {
+            block_id: block.id,
+            state_id: block.state_with_properties(&values),
+            properties: values.into_iter().collect(),
+            nbt,
+        })
+    }
+
+    /// Whether the state is of the block and has the given property values, like vanilla's
+    /// `BlockInput.test`. Block entity data isn't compared
+    pub fn matches(&self, state_id: u16) -> bool {
+        let Some(block) = get_block_by_state_id(state_id) else {
+            return false;
+        };
+        if block.id != self.block_id {
+            return false;
+        }
+        let state = block.state_properties(state_id);
+        self.properties.iter().all(|(key, value)| {
+            state
+                .iter()
+                .any(|(known, known_value)| *known == key && *known_value == value)
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::BlockState;
+    use super::{BlockState, BlockStateInput, BlockStateParseError};
 
     #[test]
     fn not_existing() {
@@ -57,4 +199,34 @@ mod tests {
         let result = BlockState::new("dirt");
         assert!(result.is_some());
     }
+
+    #[test]
+    fn parse_input() {
+        let log = BlockStateInput::parse("minecraft:oak_log[axis=x]").unwrap();
+        assert!(log.matches(log.state_id));
+        let default = BlockStateInput::parse("oak_log").unwrap();
+        assert_ne!(log.state_id, default.state_id);
+        assert!(default.matches(log.state_id));
+        assert!(!log.matches(default.state_id));
+
+        let chest = BlockStateInput::parse("chest{Items:[]}").unwrap();
+        assert!(chest.nbt.is_some());
+
+        assert!(matches!(
+            BlockStateInput::parse("oak_log[axis=w]"),
+            Err(BlockStateParseError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            BlockStateInput::parse("oak_log[color=red]"),
+            Err(BlockStateParseError::UnknownProperty { .. })
+        ));
+        assert!(matches!(
+            BlockStateInput::parse("oak_log[axis=x"),
+            Err(BlockStateParseError::UnclosedProperties)
+        ));
+        assert!(matches!(
+            BlockStateInput::parse("not_a_block"),
+            Err(BlockStateParseError::UnknownBlock(_))
+        ));
+    }
 }
@@ -0,0 +1,36 @@
+use std::ops::RangeInclusive;
+
+/// The experience points mining the block drops, like vanilla's `DropExperienceBlock`s. Blocks
+/// mined with Silk Touch drop none
+pub fn block_experience(name: &str) -> Option<RangeInclusive<i32>> {
+    let name = name.strip_prefix("deepslate_").unwrap_or(name);
+    match name {
+        "coal_ore" => Some(0..=2),
+        "diamond_ore" | "emerald_ore" => Some(3..=7),
+        "lapis_ore" | "nether_quartz_ore" => Some(2..=5),
+        "redstone_ore" => Some(1..=5),
+        "nether_gold_ore" => Some(0..=1),
+        // Vanilla adds two random amounts of up to 14 points
+        "spawner" => Some(15..=43),
+        "sculk" => Some(1..=1),
+        "sculk_sensor" | "calibrated_sculk_sensor" | "sculk_catalyst" | "sculk_shrieker" => {
+            Some(5..=5)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::block_experience;
+
+    #[test]
+    fn ores_drop_experience() {
+        assert_eq!(block_experience("diamond_ore"), Some(3..=7));
+        assert_eq!(block_experience("deepslate_diamond_ore"), Some(3..=7));
+        assert_eq!(block_experience("coal_ore"), Some(0..=2));
+        // Metal ores drop raw metal, which gives experience once smelted
+        assert_eq!(block_experience("iron_ore"), None);
+        assert_eq!(block_experience("stone"), None);
+    }
+}
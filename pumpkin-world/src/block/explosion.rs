@@ -0,0 +1,442 @@
+use std::collections::HashSet;
+
+use pumpkin_core::math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3};
+use rand::Rng;
+
+use super::{
+    block_registry::{get_block_and_state_by_state_id, get_state_by_state_id},
+    fluid::fluid_state,
+    random_tick::BlockAccess,
+};
+
+/// The power of primed TNT and TNT minecarts
+pub const TNT_POWER: f32 = 4.0;
+/// The power of creepers, charged creepers explode with twice the power
+pub const CREEPER_POWER: f32 = 3.0;
+/// The power of beds used outside of the overworld
+pub const BED_POWER: f32 = 5.0;
+/// The power of charged respawn anchors used outside of the nether
+pub const RESPAWN_ANCHOR_POWER: f32 = 5.0;
+
+/// Explosions with less power, or which don't break blocks, show the small explosion particle
+pub const LARGE_EXPLOSION_POWER: f32 = 2.0;
+
+/// The blast resistance of blocks which no explosion breaks, like bedrock
+const INDESTRUCTIBLE: f32 = 3_600_000.0;
+/// The blast resistance of water and lava, which shields blocks in them
+const FLUID_RESISTANCE: f32 = 100.0;
+
+/// Rays start at the surface of a 16 x 16 x 16 cube around the explosion
+const RAY_GRID: u8 = 16;
+/// Distance rays travel every step
+const RAY_STEP: f64 = 0.3;
+/// Strength rays lose every step, even through air
+const RAY_DECAY: f32 = 0.225_000_01;
+
+const UNBREAKABLE_BLOCKS: [&str; 13] = [
+    "bedrock",
+    "barrier",
+    "command_block",
+    "chain_command_block",
+    "repeating_command_block",
+    "structure_block",
+    "jigsaw",
+    "end_portal",
+    "end_portal_frame",
+    "end_gateway",
+    "light",
+    "moving_piston",
+    "piston_head",
+];
+
+const BLAST_PROOF_BLOCKS: [&str; 10] = [
+    "obsidian",
+    "crying_obsidian",
+    "netherite_block",
+    "ancient_debris",
+    "respawn_anchor",
+    "reinforced_deepslate",
+    "enchanting_table",
+    "anvil",
+    "chipped_anvil",
+    "damaged_anvil",
+];
+
+/// Parts of the names of stone and metal blocks, which resist explosions better than their
+/// hardness suggests
+const STONE_LIKE: [&str; 16] = [
+    "stone",
+    "cobble",
+    "brick",
+    "deepslate",
+    "andesite",
+    "diorite",
+    "granite",
+    "tuff",
+    "prismarine",
+    "purpur",
+    "iron_",
+    "gold_block",
+    "diamond_block",
+    "emerald_block",
+    "copper",
+    "grindstone",
+];
+
+/// How well the block resists explosions. The block data has no blast resistance, so it is
+/// derived from the name for the blocks where it differs from the hardness
+pub fn blast_resistance(state_id: u16) -> f32 {
+    let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+        return 0.0;
+    };
+    let name = block.name.as_str();
+    let resistance = if UNBREAKABLE_BLOCKS.contains(&name) {
+        INDESTRUCTIBLE
+    } else if BLAST_PROOF_BLOCKS.contains(&name) {
+        1200.0
+    } else if name == "ender_chest" {
+        600.0
+    } else if name.contains("end_stone") {
+        9.0
+    } else if name.ends_with("_ore") {
+        3.0
+    } else if name.contains("sandstone") {
+        0.8
+    } else if name == "basalt" || (name.ends_with("terracotta") && !name.contains("glazed")) {
+        4.2
+    } else if block.hardness >= 1.5
+        && !name.contains("dripstone")
+        && !matches!(name, "stonecutter" | "lodestone" | "mud_bricks")
+        && STONE_LIKE.iter().any(|part| name.contains(part))
+    {
+        6.0
+    } else {
+        block.hardness.max(0.0)
+    };
+    if fluid_state(state_id).is_some() {
+        resistance.max(FLUID_RESISTANCE)
+    } else {
+        resistance
+    }
+}
+
+fn block_at(position: Vector3<f64>) -> WorldPosition {
+    WorldPosition(Vector3::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    ))
+}
+
+/// The blocks the explosion reaches, like vanilla's `calculateExplodedPositions`. Rays go out in
+/// every direction and lose strength with distance and the blast resistance of the blocks they
+/// pass through. Air is included, explosions which cause fires set it alight
+pub fn exploded_blocks(
+    world: &impl BlockAccess,
+    center: Vector3<f64>,
+    power: f32,
+    rng: &mut impl Rng,
+) -> HashSet<WorldPosition> {
+    let mut exploded = HashSet::new();
+    let edge = |n: u8| n == 0 || n == RAY_GRID - 1;
+    let scale = f64::from(RAY_GRID - 1);
+    for x in 0..RAY_GRID {
+        for y in 0..RAY_GRID {
+            for z in 0..RAY_GRID {
+                if !(edge(x) || edge(y) || edge(z)) {
+                    continue;
+                }
+                let direction = Vector3::new(
+                    f64::from(x) / scale * 2.0 - 1.0,
+                    f64::from(y) / scale * 2.0 - 1.0,
+                    f64::from(z) / scale * 2.0 - 1.0,
+                )
+                .normalize();
+                let mut strength = power * rng.gen_range(0.7..1.3);
+                let mut position = center;
+                while strength > 0.0 {
+                    let block = block_at(position);
+                    // Rays stop at the edge of the world and of the loaded chunks
+                    let Some(state_id) = world.block_state(&block) else {
+                        break;
+                    };
+                    let air = get_state_by_state_id(state_id).is_none_or(|state| state.air);
+                    if !air {
+                        strength -= (blast_resistance(state_id) + 0.3) * 0.3;
+                    }
+                    if strength > 0.0 {
+                        exploded.insert(block);
+                    }
+                    position = position + direction * RAY_STEP;
+                    strength -= RAY_DECAY;
+                }
+            }
+        }
+    }
+    exploded
+}
+
+/// Whether a block between the two points stops the explosion from reaching an entity. Blocks
+/// with any collision count as full blocks
+fn ray_blocked(world: &impl BlockAccess, from: Vector3<f64>, to: Vector3<f64>) -> bool {
+    let from = [from.x, from.y, from.z];
+    let to = [to.x, to.y, to.z];
+    let mut block = from.map(|n| n.floor() as i32);
+    let end = to.map(|n| n.floor() as i32);
+    let direction = [to[0] - from[0], to[1] - from[1], to[2] - from[2]];
+    // How far along the ray the next block boundary on each axis is, and the distance between them
+    let mut next = [f64::INFINITY; 3];
+    let mut delta = [f64::INFINITY; 3];
+    for axis in 0..3 {
+        if direction[axis] > 0.0 {
+            next[axis] = (f64::from(block[axis]) + 1.0 - from[axis]) / direction[axis];
+            delta[axis] = 1.0 / direction[axis];
+        } else if direction[axis] < 0.0 {
+            next[axis] = (from[axis] - f64::from(block[axis])) / -direction[axis];
+            delta[axis] = -1.0 / direction[axis];
+        }
+    }
+    loop {
+        let position = WorldPosition(Vector3::new(block[0], block[1], block[2]));
+        let solid = world
+            .block_state(&position)
+            .and_then(get_state_by_state_id)
+            .is_some_and(|state| !state.collision_shapes.is_empty());
+        if solid {
+            return true;
+        }
+        if block == end {
+            return false;
+        }
+        let axis = (0..3)
+            .min_by(|a, b| next[*a].total_cmp(&next[*b]))
+            .unwrap_or(0);
+        if next[axis] > 1.0 {
+            return false;
+        }
+        block[axis] += if direction[axis] > 0.0 { 1 } else { -1 };
+        next[axis] += delta[axis];
+    }
+}
+
+/// The share of points spread over the bounding box which the explosion reaches without a block
+/// in the way, from 0 to 1. Like vanilla's `getSeenPercent`
+pub fn exposure(world: &impl BlockAccess, center: Vector3<f64>, bounding_box: &BoundingBox) -> f32 {
+    let step_x = 1.0 / ((bounding_box.max_x - bounding_box.min_x) * 2.0 + 1.0);
+    let step_y = 1.0 / ((bounding_box.max_y - bounding_box.min_y) * 2.0 + 1.0);
+    let step_z = 1.0 / ((bounding_box.max_z - bounding_box.min_z) * 2.0 + 1.0);
+    if step_x < 0.0 || step_y < 0.0 || step_z < 0.0 {
+        return 0.0;
+    }
+    // Centers the points on the horizontal axes
+    let offset_x = (1.0 - (1.0 / step_x).floor() * step_x) / 2.0;
+    let offset_z = (1.0 - (1.0 / step_z).floor() * step_z) / 2.0;
+    let lerp = |delta: f64, min: f64, max: f64| delta.mul_add(max - min, min);
+
+    let mut reached = 0u32;
+    let mut points = 0u32;
+    let mut x = 0.0;
+    while x <= 1.0 {
+        let mut y = 0.0;
+        while y <= 1.0 {
+            let mut z = 0.0;
+            while z <= 1.0 {
+                let point = Vector3::new(
+                    lerp(x, bounding_box.min_x, bounding_box.max_x) + offset_x,
+                    lerp(y, bounding_box.min_y, bounding_box.max_y),
+                    lerp(z, bounding_box.min_z, bounding_box.max_z) + offset_z,
+                );
+                if !ray_blocked(world, point, center) {
+                    reached += 1;
+                }
+                points += 1;
+                z += step_z;
+            }
+            y += step_y;
+        }
+        x += step_x;
+    }
+    if points == 0 {
+        0.0
+    } else {
+        reached as f32 / points as f32
+    }
+}
+
+/// What an explosion does to an entity in its reach
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExplosionHit {
+    pub damage: f32,
+    /// Added to the velocity of the entity, pushing it away from the explosion
+    pub knockback: Vector3<f64>,
+}
+
+/// How the explosion hits an entity standing at the position, `None` if it is out of reach.
+/// Explosions reach twice as far as their power, and push from the center towards `target`,
+/// the eyes of most entities. Like the entity part of vanilla's `explode`
+pub fn explosion_hit(
+    world: &impl BlockAccess,
+    center: Vector3<f64>,
+    power: f32,
+    position: Vector3<f64>,
+    target: Vector3<f64>,
+    bounding_box: &BoundingBox,
+) -> Option<ExplosionHit> {
+    let diameter = f64::from(power) * 2.0;
+    let distance = position.sub(&center).length() / diameter;
+    if distance > 1.0 {
+        return None;
+    }
+    let direction = target.sub(&center);
+    if direction.length_squared() == 0.0 {
+        return None;
+    }
+    let impact = (1.0 - distance) * f64::from(exposure(world, center, bounding_box));
+    let damage = (impact * impact + impact) / 2.0 * 7.0 * diameter + 1.0;
+    Some(ExplosionHit {
+        damage: damage as f32,
+        knockback: direction.normalize() * impact,
+    })
+}
+
+/// Whether a block broken by the explosion still drops its item. Stronger explosions destroy more
+/// of the drops, like the `explosion_decay` loot function
+pub fn survives_explosion(power: f32, rng: &mut impl Rng) -> bool {
+    rng.gen::<f32>() <= 1.0 / power
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::tests::{pos, TestBlocks},
+    };
+
+    use super::{
+        blast_resistance, exploded_blocks, explosion_hit, exposure, survives_explosion, TNT_POWER,
+    };
+
+    fn state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    /// A 9 x 9 x 9 cube of the block, with the center at 4.5, 4.5, 4.5
+    fn cube(name: &str) -> TestBlocks {
+        let mut world = TestBlocks::new(15, 0);
+        for x in 0..9 {
+            for y in 0..9 {
+                for z in 0..9 {
+                    world.set(x, y, z, name);
+                }
+            }
+        }
+        world
+    }
+
+    fn solid_blocks(
+        world: &TestBlocks,
+        exploded: &std::collections::HashSet<pumpkin_core::math::position::WorldPosition>,
+    ) -> usize {
+        exploded
+            .iter()
+            .filter(|position| world.blocks.get(position).is_some_and(|state| *state != 0))
+            .count()
+    }
+
+    #[test]
+    fn blast_resistances() {
+        assert!((blast_resistance(state("stone")) - 6.0).abs() < f32::EPSILON);
+        assert!((blast_resistance(state("cobblestone")) - 6.0).abs() < f32::EPSILON);
+        assert!((blast_resistance(state("dirt")) - 0.5).abs() < f32::EPSILON);
+        assert!((blast_resistance(state("obsidian")) - 1200.0).abs() < f32::EPSILON);
+        assert!((blast_resistance(state("water")) - 100.0).abs() < f32::EPSILON);
+        assert!((blast_resistance(state("iron_ore")) - 3.0).abs() < f32::EPSILON);
+        assert!(blast_resistance(state("bedrock")) > 1_000_000.0);
+        assert!(blast_resistance(state("stone_button")) < 1.0);
+    }
+
+    #[test]
+    fn tnt_breaks_dirt_but_not_obsidian() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let center = Vector3::new(4.5, 4.5, 4.5);
+
+        let mut world = cube("dirt");
+        world.blocks.insert(pos(4, 4, 4), 0);
+        let exploded = exploded_blocks(&world, center, TNT_POWER, &mut rng);
+        assert!(exploded.contains(&pos(4, 4, 4)));
+        assert!(exploded.contains(&pos(5, 4, 4)));
+        assert!(solid_blocks(&world, &exploded) > 20);
+        // Nothing reaches the edge of the cube
+        assert!(!exploded.contains(&pos(0, 4, 4)));
+
+        let mut world = cube("obsidian");
+        world.blocks.insert(pos(4, 4, 4), 0);
+        let exploded = exploded_blocks(&world, center, TNT_POWER, &mut rng);
+        assert_eq!(solid_blocks(&world, &exploded), 0);
+    }
+
+    #[test]
+    fn water_shields_blocks() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut world = cube("dirt");
+        world.set(4, 4, 4, "water");
+        let exploded = exploded_blocks(&world, Vector3::new(4.5, 4.5, 4.5), TNT_POWER, &mut rng);
+        assert_eq!(solid_blocks(&world, &exploded), 0);
+    }
+
+    #[test]
+    fn walls_block_exposure() {
+        let size = BoundingBoxSize {
+            width: 0.6,
+            height: 1.8,
+        };
+        let bounding_box = BoundingBox::new_from_pos(4.5, 0.0, 0.5, &size);
+        let center = Vector3::new(0.5, 0.5, 0.5);
+        let mut world = TestBlocks::new(15, 0);
+        assert!((exposure(&world, center, &bounding_box) - 1.0).abs() < f32::EPSILON);
+
+        for y in 0..4 {
+            for z in -2..3 {
+                world.set(2, y, z, "stone");
+            }
+        }
+        assert!(exposure(&world, center, &bounding_box) < f32::EPSILON);
+    }
+
+    #[test]
+    fn hits_fall_off_with_distance() {
+        let world = TestBlocks::new(15, 0);
+        let size = BoundingBoxSize {
+            width: 0.6,
+            height: 1.8,
+        };
+        let center = Vector3::new(0.5, 0.5, 0.5);
+        let hit_at = |x: f64| {
+            let position = Vector3::new(x, 0.0, 0.5);
+            let bounding_box = BoundingBox::new_from_pos(x, 0.0, 0.5, &size);
+            let target = Vector3::new(x, 1.62, 0.5);
+            explosion_hit(&world, center, TNT_POWER, position, target, &bounding_box)
+        };
+        let near = hit_at(2.5).unwrap();
+        let far = hit_at(6.5).unwrap();
+        assert!(near.damage > far.damage);
+        assert!(near.knockback.x > far.knockback.x && far.knockback.x > 0.0);
+        assert!(hit_at(9.5).is_none());
+    }
+
+    #[test]
+    fn drops_decimate_with_power() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!((0..100).all(|_| survives_explosion(1.0, &mut rng)));
+        let survived = (0..1000)
+            .filter(|_| survives_explosion(TNT_POWER, &mut rng))
+            .count();
+        assert!((150..350).contains(&survived));
+    }
+}
@@ -0,0 +1,157 @@
+use pumpkin_core::math::position::WorldPosition;
+
+use crate::WORLD_LOWEST_Y;
+
+use super::{
+    block_registry::{get_block_and_state_by_state_id, get_state_by_state_id},
+    fluid::{fluid_state, Fluid},
+    random_tick::{default_state, offset, BlockAccess},
+};
+
+/// Blocks which fall down when there is nothing below them
+const FALLING_BLOCKS: [&str; 3] = ["sand", "red_sand", "gravel"];
+
+/// Whether the block falls down as an entity when there is nothing below it
+pub fn is_falling_block(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id).is_some_and(|(block, _)| {
+        FALLING_BLOCKS.contains(&block.name.as_str()) || block.name.ends_with("_concrete_powder")
+    })
+}
+
+/// Whether falling blocks fall through the block and can land in its place, like air, fluids
+/// and short grass
+pub fn can_fall_through(state_id: u16) -> bool {
+    get_state_by_state_id(state_id).is_some_and(|state| state.air || state.replaceable)
+}
+
+/// The concrete concrete powder turns into in water, `None` for other blocks
+pub fn concrete(state_id: u16) -> Option<u16> {
+    let (block, _) = get_block_and_state_by_state_id(state_id)?;
+    let color = block.name.strip_suffix("_concrete_powder")?;
+    Some(default_state(&format!("{color}_concrete")))
+}
+
+fn is_water(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(fluid_state)
+        .is_some_and(|fluid| fluid.fluid == Fluid::Water)
+}
+
+/// Concrete powder in water or next to it, except below, hardens. Returns the concrete, `None`
+/// if the block stays as it is
+pub fn harden(world: &impl BlockAccess, position: WorldPosition, state_id: u16) -> Option<u16> {
+    let concrete = concrete(state_id)?;
+    let wet = is_water(world, &position)
+        || [(0, 1, 0), (-1, 0, 0), (1, 0, 0), (0, 0, -1), (0, 0, 1)]
+            .iter()
+            .any(|(x, y, z)| is_water(world, &offset(&position, *x, *y, *z)));
+    wet.then_some(concrete)
+}
+
+/// The state of the falling block at the position if it should start falling. Blocks don't fall
+/// out of the world or into chunks which are not loaded
+pub fn start_falling(world: &impl BlockAccess, position: WorldPosition) -> Option<u16> {
+    if position.0.y <= i32::from(WORLD_LOWEST_Y) {
+        return None;
+    }
+    let state_id = world.block_state(&position)?;
+    let below = world.block_state(&offset(&position, 0, -1, 0))?;
+    (is_falling_block(state_id) && can_fall_through(below)).then_some(state_id)
+}
+
+/// Whether the falling block stops in the block it is in instead of on top of the block below,
+/// concrete powder does that in water
+pub fn lands_in(world: &impl BlockAccess, position: WorldPosition, state_id: u16) -> bool {
+    concrete(state_id).is_some() && is_water(world, &position)
+}
+
+/// The block a falling block entity becomes when it lands at the position, `None` if it can't
+/// be placed there and drops as an item instead
+pub fn landing_state(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+    state_id: u16,
+) -> Option<u16> {
+    if lands_in(world, position, state_id) {
+        return concrete(state_id);
+    }
+    let target = world.block_state(&position)?;
+    let below = world.block_state(&offset(&position, 0, -1, 0))?;
+    if !can_fall_through(target) || can_fall_through(below) {
+        return None;
+    }
+    Some(harden(world, position, state_id).unwrap_or(state_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::tests::{pos, TestBlocks},
+    };
+
+    use super::{can_fall_through, harden, is_falling_block, landing_state, start_falling};
+
+    fn state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    #[test]
+    fn falling_blocks() {
+        assert!(is_falling_block(state("sand")));
+        assert!(is_falling_block(state("gravel")));
+        assert!(is_falling_block(state("lime_concrete_powder")));
+        assert!(!is_falling_block(state("lime_concrete")));
+        assert!(!is_falling_block(state("stone")));
+        assert!(can_fall_through(state("air")));
+        assert!(can_fall_through(state("water")));
+        assert!(can_fall_through(state("short_grass")));
+        assert!(!can_fall_through(state("torch")));
+    }
+
+    #[test]
+    fn sand_falls_without_support() {
+        let mut world = TestBlocks::new(15, 0);
+        let sand = world.set(0, 1, 0, "sand");
+        world.set(0, 0, 0, "stone");
+        assert_eq!(start_falling(&world, pos(0, 1, 0)), None);
+
+        world.set(0, 0, 0, "short_grass");
+        assert_eq!(start_falling(&world, pos(0, 1, 0)), Some(sand));
+
+        world.set(0, 5, 0, "stone");
+        assert_eq!(start_falling(&world, pos(0, 5, 0)), None);
+    }
+
+    #[test]
+    fn sand_lands_or_drops() {
+        let mut world = TestBlocks::new(15, 0);
+        let sand = state("sand");
+        world.set(0, 0, 0, "stone");
+        assert_eq!(landing_state(&world, pos(0, 1, 0), sand), Some(sand));
+
+        // Torches can't be replaced, the sand drops as an item
+        world.set(0, 1, 0, "torch");
+        assert_eq!(landing_state(&world, pos(0, 1, 0), sand), None);
+    }
+
+    #[test]
+    fn concrete_powder_hardens_in_water() {
+        let mut world = TestBlocks::new(15, 0);
+        let powder = state("red_concrete_powder");
+        let concrete = state("red_concrete");
+        world.set(0, 0, 0, "stone");
+        assert_eq!(harden(&world, pos(0, 1, 0), powder), None);
+        assert_eq!(landing_state(&world, pos(0, 1, 0), powder), Some(powder));
+
+        world.set(1, 1, 0, "water");
+        assert_eq!(harden(&world, pos(0, 1, 0), powder), Some(concrete));
+        assert_eq!(landing_state(&world, pos(0, 1, 0), powder), Some(concrete));
+
+        // Powder stops in the water instead of sinking
+        world.set(0, 3, 0, "water");
+        assert_eq!(landing_state(&world, pos(0, 3, 0), powder), Some(concrete));
+        assert_eq!(harden(&world, pos(0, 1, 0), state("sand")), None);
+    }
+}
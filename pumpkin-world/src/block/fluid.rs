@@ -0,0 +1,681 @@
+use std::sync::LazyLock;
+
+use pumpkin_core::math::position::WorldPosition;
+
+use super::{
+    block_registry::{get_block, get_block_and_state_by_state_id, Block, BLOCKS},
+    random_tick::{default_state, offset, property, with_property, BlockAccess},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+impl Fluid {
+    /// The name of the source fluid, also used for the ticks of the fluid
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Water => "minecraft:water",
+            Self::Lava => "minecraft:lava",
+        }
+    }
+
+    /// The name vanilla saves the ticks of flowing fluid with
+    pub fn flowing_name(self) -> &'static str {
+        match self {
+            Self::Water => "minecraft:flowing_water",
+            Self::Lava => "minecraft:flowing_lava",
+        }
+    }
+
+    /// Reads the name of a fluid, flowing or not
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name
+            .replace("minecraft:", "")
+            .replace("flowing_", "")
+            .as_str()
+        {
+            "water" => Some(Self::Water),
+            "lava" => Some(Self::Lava),
+            _ => None,
+        }
+    }
+
+    /// How many ticks the fluid waits before spreading, lava is faster in the nether
+    pub fn tick_delay(self, ultra_warm: bool) -> u32 {
+        match (self, ultra_warm) {
+            (Self::Water, _) => 5,
+            (Self::Lava, true) => 10,
+            (Self::Lava, false) => 30,
+        }
+    }
+
+    /// How much the fluid loses with each block it flows
+    fn drop_off(self, ultra_warm: bool) -> u8 {
+        match (self, ultra_warm) {
+            (Self::Lava, false) => 2,
+            _ => 1,
+        }
+    }
+
+    /// How far the fluid looks for a way down before choosing where to flow
+    fn slope_find_distance(self, ultra_warm: bool) -> u32 {
+        match (self, ultra_warm) {
+            (Self::Lava, false) => 2,
+            _ => 4,
+        }
+    }
+
+    /// Two water sources next to each other create a new source between them, lava doesn't
+    fn converts_to_source(self) -> bool {
+        self == Self::Water
+    }
+
+    fn block(self) -> &'static Block {
+        get_block(self.name()).expect("Fluid blocks are registered")
+    }
+}
+
+/// The fluid in a block, stored in the `level` of water and lava blocks. Waterlogged blocks hold
+/// a water source
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FluidState {
+    pub fluid: Fluid,
+    /// From 1 to 8, sources and falling fluid are full
+    pub amount: u8,
+    pub source: bool,
+    /// Fluid falling down is full and spreads sideways only as far as 7 blocks of flowing fluid
+    pub falling: bool,
+}
+
+impl FluidState {
+    pub fn source(fluid: Fluid) -> Self {
+        Self {
+            fluid,
+            amount: 8,
+            source: true,
+            falling: false,
+        }
+    }
+
+    pub fn flowing(fluid: Fluid, amount: u8, falling: bool) -> Self {
+        Self {
+            fluid,
+            amount,
+            source: false,
+            falling,
+        }
+    }
+
+    /// Reads the `level` of a fluid block, 0 is a source and levels from 8 are falling
+    fn from_level(fluid: Fluid, level: u8) -> Self {
+        match level {
+            0 => Self::source(fluid),
+            1..=7 => Self::flowing(fluid, 8 - level, false),
+            _ => Self::flowing(fluid, 8, true),
+        }
+    }
+
+    /// The state of the water or lava block holding the fluid
+    pub fn state_id(self) -> u16 {
+        let level = if self.source {
+            0
+        } else {
+            8 - self.amount.min(8) + if self.falling { 8 } else { 0 }
+        };
+        let block = self.fluid.block();
+        with_property(block, block.default_state_id, "level", &level.to_string())
+    }
+}
+
+/// Blocks which are always full of water, they can't be replaced by spreading fluids
+const WATER_PLANTS: [&str; 5] = [
+    "bubble_column",
+    "kelp",
+    "kelp_plant",
+    "seagrass",
+    "tall_seagrass",
+];
+
+/// The fluid of every block state, indexed by the state id
+static FLUID_STATES: LazyLock<Vec<Option<FluidState>>> = LazyLock::new(|| {
+    let mut fluids = Vec::new();
+    for block in &BLOCKS.blocks {
+        for state in &block.states {
+            let Some(fluid) = block_fluid(block, state.id) else {
+                continue;
+            };
+            let id = usize::from(state.id);
+            if fluids.len() <= id {
+                fluids.resize(id + 1, None);
+            }
+            fluids[id] = Some(fluid);
+        }
+    }
+    fluids
+});
+
+fn block_fluid(block: &Block, state_id: u16) -> Option<FluidState> {
+    if let Some(fluid) = Fluid::from_name(&block.name) {
+        let level = property(block, state_id, "level")?.parse().ok()?;
+        return Some(FluidState::from_level(fluid, level));
+    }
+    let waterlogged = property(block, state_id, "waterlogged") == Some("true");
+    (waterlogged || WATER_PLANTS.contains(&block.name.as_str()))
+        .then(|| FluidState::source(Fluid::Water))
+}
+
+/// The fluid in blocks of the state, `None` if there is none
+pub fn fluid_state(state_id: u16) -> Option<FluidState> {
+    FLUID_STATES.get(usize::from(state_id)).copied().flatten()
+}
+
+/// Whether the state is a water or lava block, not a block which only holds water
+fn is_fluid_block(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id)
+        .is_some_and(|(block, _)| Fluid::from_name(&block.name).is_some())
+}
+
+/// Whether fluid can flow into the block, replacing it. Solid blocks, signs, portals and blocks
+/// holding water keep fluids out
+fn can_hold_fluid(state_id: u16) -> bool {
+    if is_fluid_block(state_id) {
+        return true;
+    }
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return false;
+    };
+    let name = block.name.as_str();
+    state.collision_shapes.is_empty()
+        && fluid_state(state_id).is_none()
+        && !name.ends_with("_sign")
+        && !matches!(
+            name,
+            "sugar_cane" | "nether_portal" | "end_portal" | "end_gateway" | "structure_void"
+        )
+}
+
+fn is_solid(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id)
+        .is_some_and(|(_, state)| !state.collision_shapes.is_empty())
+}
+
+const HORIZONTAL: [(i32, i32); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// The blocks changed by a fluid tick
+#[derive(Default, Debug)]
+pub struct FluidUpdate {
+    /// The blocks which change and their new states, in the order they change
+    pub changes: Vec<(WorldPosition, u16)>,
+    /// Where lava met water and hardened, vanilla plays a fizz there
+    pub fizz: Vec<WorldPosition>,
+}
+
+/// Lets the fluid at the position flow like vanilla's `FlowingFluid.tick`. Flowing fluid without a
+/// source next to it dries up, fluid flows down first and otherwise towards the nearest way down.
+/// Lava touching water turns into obsidian, cobblestone or stone
+pub fn fluid_tick(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+    fluid: Fluid,
+    ultra_warm: bool,
+) -> FluidUpdate {
+    let mut tick = FluidTick {
+        world,
+        fluid,
+        ultra_warm,
+        update: FluidUpdate::default(),
+    };
+    tick.tick(position);
+    tick.update
+}
+
+struct FluidTick<'a, W> {
+    world: &'a W,
+    fluid: Fluid,
+    ultra_warm: bool,
+    update: FluidUpdate,
+}
+
+impl<W: BlockAccess> FluidTick<'_, W> {
+    /// Blocks which are not loaded keep fluids out
+    fn state_at(&self, position: &WorldPosition) -> Option<u16> {
+        self.update
+            .changes
+            .iter()
+            .rev()
+            .find_map(|(changed, state_id)| (changed == position).then_some(*state_id))
+            .or_else(|| self.world.block_state(position))
+    }
+
+    /// The fluid at the position if it is the ticked fluid
+    fn own_fluid(&self, position: &WorldPosition) -> Option<FluidState> {
+        self.state_at(position)
+            .and_then(fluid_state)
+            .filter(|state| state.fluid == self.fluid)
+    }
+
+    fn set(&mut self, position: WorldPosition, state_id: u16) {
+        self.update.changes.push((position, state_id));
+    }
+
+    fn harden(&mut self, position: WorldPosition, block: &str) {
+        self.set(position, default_state(block));
+        self.update.fizz.push(position);
+    }
+
+    fn tick(&mut self, position: WorldPosition) {
+        let Some(mut state) = self.own_fluid(&position) else {
+            return;
+        };
+        if self.fluid == Fluid::Lava && self.lava_meets_water(position, state) {
+            return;
+        }
+        if !state.source {
+            match self.new_fluid(&position) {
+                None => {
+                    self.set(position, 0);
+                    return;
+                }
+                Some(new_state) if new_state != state => {
+                    self.set(position, new_state.state_id());
+                    state = new_state;
+                }
+                Some(_) => {}
+            }
+        }
+        self.spread(position, state);
+    }
+
+    /// Lava next to or below water hardens, sources into obsidian and flowing lava into
+    /// cobblestone. Above soul soil lava next to blue ice turns into basalt
+    fn lava_meets_water(&mut self, position: WorldPosition, state: FluidState) -> bool {
+        let on_soul_soil = self.is_block(&offset(&position, 0, -1, 0), "soul_soil");
+        let neighbours = HORIZONTAL
+            .iter()
+            .map(|(x, z)| offset(&position, *x, 0, *z))
+            .chain([offset(&position, 0, 1, 0)]);
+        for neighbour in neighbours {
+            let state_id = self.state_at(&neighbour);
+            if state_id
+                .and_then(fluid_state)
+                .is_some_and(|fluid| fluid.fluid == Fluid::Water)
+            {
+                let block = if state.source {
+                    "obsidian"
+                } else {
+                    "cobblestone"
+                };
+                self.harden(position, block);
+                return true;
+            }
+            if on_soul_soil && self.is_block(&neighbour, "blue_ice") {
+                self.harden(position, "basalt");
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_block(&self, position: &WorldPosition, name: &str) -> bool {
+        self.state_at(position)
+            .and_then(get_block_and_state_by_state_id)
+            .is_some_and(|(block, _)| block.name == name)
+    }
+
+    /// What the fluid at the position would be judging by the fluid around it, like vanilla's
+    /// `getNewLiquid`
+    fn new_fluid(&self, position: &WorldPosition) -> Option<FluidState> {
+        let mut max_amount = 0;
+        let mut sources = 0;
+        for (x, z) in HORIZONTAL {
+            if let Some(neighbour) = self.own_fluid(&offset(position, x, 0, z)) {
+                if neighbour.source {
+                    sources += 1;
+                }
+                max_amount = max_amount.max(neighbour.amount);
+            }
+        }
+        if self.fluid.converts_to_source() && sources >= 2 {
+            let below = offset(position, 0, -1, 0);
+            if self.state_at(&below).is_some_and(is_solid)
+                || self.own_fluid(&below).is_some_and(|below| below.source)
+            {
+                return Some(FluidState::source(self.fluid));
+            }
+        }
+        if self.own_fluid(&offset(position, 0, 1, 0)).is_some() {
+            return Some(FluidState::flowing(self.fluid, 8, true));
+        }
+        let amount = max_amount.saturating_sub(self.fluid.drop_off(self.ultra_warm));
+        (amount > 0).then(|| FluidState::flowing(self.fluid, amount, false))
+    }
+
+    /// Whether the fluid can flow into the block, only lava can flow down into water
+    fn can_spread_to(&self, position: &WorldPosition, down: bool) -> bool {
+        let Some(state_id) = self.state_at(position) else {
+            return false;
+        };
+        match fluid_state(state_id) {
+            Some(existing) => down && self.fluid == Fluid::Lava && existing.fluid == Fluid::Water,
+            None => can_hold_fluid(state_id),
+        }
+    }
+
+    fn spread_to(&mut self, position: WorldPosition, state: FluidState, down: bool) {
+        let into_water = self
+            .state_at(&position)
+            .and_then(fluid_state)
+            .is_some_and(|existing| existing.fluid == Fluid::Water);
+        if down && self.fluid == Fluid::Lava && into_water {
+            self.harden(position, "stone");
+        } else {
+            self.set(position, state.state_id());
+        }
+    }
+
+    /// Whether fluid can pass through the block while looking for a way down
+    fn can_pass_through(&self, position: &WorldPosition) -> bool {
+        let Some(state_id) = self.state_at(position) else {
+            return false;
+        };
+        !self.own_fluid(position).is_some_and(|state| state.source) && can_hold_fluid(state_id)
+    }
+
+    /// Whether the fluid can fall into the block
+    fn is_hole(&self, position: &WorldPosition) -> bool {
+        self.own_fluid(position).is_some() || self.state_at(position).is_some_and(can_hold_fluid)
+    }
+
+    fn spread(&mut self, position: WorldPosition, state: FluidState) {
+        let below = offset(&position, 0, -1, 0);
+        if self.can_spread_to(&below, true) {
+            if let Some(new_state) = self.new_fluid(&below) {
+                self.spread_to(below, new_state, true);
+            }
+            // Fluid falling out of a pool still spreads sideways
+            let sources = HORIZONTAL
+                .iter()
+                .filter(|(x, z)| {
+                    self.own_fluid(&offset(&position, *x, 0, *z))
+                        .is_some_and(|state| state.source)
+                })
+                .count();
+            if sources >= 3 {
+                self.spread_to_sides(position, state);
+            }
+        } else if state.source || !self.is_hole(&below) {
+            self.spread_to_sides(position, state);
+        }
+    }
+
+    fn spread_to_sides(&mut self, position: WorldPosition, state: FluidState) {
+        let amount = if state.falling {
+            7
+        } else {
+            state
+                .amount
+                .saturating_sub(self.fluid.drop_off(self.ultra_warm))
+        };
+        if amount == 0 {
+            return;
+        }
+        for (neighbour, new_state) in self.spreads(&position) {
+            if self.can_spread_to(&neighbour, false) {
+                self.spread_to(neighbour, new_state, false);
+            }
+        }
+    }
+
+    /// The sides the fluid flows to, the ones closest to a way down
+    fn spreads(&self, position: &WorldPosition) -> Vec<(WorldPosition, FluidState)> {
+        let mut closest = u32::MAX;
+        let mut spreads = Vec::new();
+        for (index, (x, z)) in HORIZONTAL.iter().enumerate() {
+            let neighbour = offset(position, *x, 0, *z);
+            if !self.can_pass_through(&neighbour) {
+                continue;
+            }
+            let Some(new_state) = self.new_fluid(&neighbour) else {
+                continue;
+            };
+            let distance = if self.is_hole(&offset(&neighbour, 0, -1, 0)) {
+                0
+            } else {
+                self.slope_distance(&neighbour, 1, opposite(index))
+            };
+            if distance < closest {
+                spreads.clear();
+                closest = distance;
+            }
+            if distance == closest {
+                spreads.push((neighbour, new_state));
+            }
+        }
+        spreads
+    }
+
+    /// How many blocks away the closest way down is, searching up to the slope find distance
+    fn slope_distance(&self, position: &WorldPosition, depth: u32, from: usize) -> u32 {
+        let mut closest = u32::MAX;
+        for (index, (x, z)) in HORIZONTAL.iter().enumerate() {
+            if index == from {
+                continue;
+            }
+            let neighbour = offset(position, *x, 0, *z);
+            if !self.can_pass_through(&neighbour) {
+                continue;
+            }
+            if self.is_hole(&offset(&neighbour, 0, -1, 0)) {
+                return depth;
+            }
+            if depth < self.fluid.slope_find_distance(self.ultra_warm) {
+                closest = closest.min(self.slope_distance(&neighbour, depth + 1, opposite(index)));
+            }
+        }
+        closest
+    }
+}
+
+/// The index of the opposite direction in `HORIZONTAL`
+fn opposite(index: usize) -> usize {
+    index ^ 1
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::position::WorldPosition;
+
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::tests::{pos, TestBlocks},
+    };
+
+    use super::{fluid_state, fluid_tick, Fluid, FluidState, FluidUpdate};
+
+    fn set_fluid(world: &mut TestBlocks, position: WorldPosition, state: FluidState) {
+        world.blocks.insert(position, state.state_id());
+    }
+
+    fn floor(world: &mut TestBlocks, size: i32) {
+        for x in -size..=size {
+            for z in -size..=size {
+                world.set(x, -1, z, "stone");
+            }
+        }
+    }
+
+    fn changed_to(update: &FluidUpdate, position: WorldPosition) -> Option<u16> {
+        update
+            .changes
+            .iter()
+            .rev()
+            .find_map(|(changed, state_id)| (*changed == position).then_some(*state_id))
+    }
+
+    #[test]
+    fn fluid_states() {
+        let water = get_block("water").unwrap();
+        assert_eq!(
+            fluid_state(water.default_state_id),
+            Some(FluidState::source(Fluid::Water))
+        );
+        for state in [
+            FluidState::flowing(Fluid::Lava, 3, false),
+            FluidState::flowing(Fluid::Water, 8, true),
+        ] {
+            assert_eq!(fluid_state(state.state_id()), Some(state));
+        }
+        let stairs = get_block("oak_stairs").unwrap();
+        let waterlogged = stairs
+            .states
+            .iter()
+            .find(|state| {
+                stairs
+                    .state_properties(state.id)
+                    .contains(&("waterlogged", "true"))
+            })
+            .unwrap();
+        assert_eq!(
+            fluid_state(waterlogged.id),
+            Some(FluidState::source(Fluid::Water))
+        );
+        assert_eq!(fluid_state(0), None);
+        assert_eq!(
+            Fluid::from_name("minecraft:flowing_lava"),
+            Some(Fluid::Lava)
+        );
+    }
+
+    #[test]
+    fn water_spreads_on_flat_ground() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 3);
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Water));
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Water, false);
+        let flowing = FluidState::flowing(Fluid::Water, 7, false).state_id();
+        for position in [pos(1, 0, 0), pos(-1, 0, 0), pos(0, 0, 1), pos(0, 0, -1)] {
+            assert_eq!(changed_to(&update, position), Some(flowing));
+        }
+        assert_eq!(update.changes.len(), 4);
+    }
+
+    #[test]
+    fn water_flows_towards_holes() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 4);
+        world.blocks.remove(&pos(3, -1, 0));
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Water));
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Water, false);
+        let changed: Vec<_> = update
+            .changes
+            .iter()
+            .map(|(position, _)| *position)
+            .collect();
+        assert_eq!(changed, vec![pos(1, 0, 0)]);
+    }
+
+    #[test]
+    fn water_falls_down() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, -3, 0, "stone");
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Water));
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Water, false);
+        let falling = FluidState::flowing(Fluid::Water, 8, true).state_id();
+        assert_eq!(update.changes, vec![(pos(0, -1, 0), falling)]);
+    }
+
+    #[test]
+    fn flowing_water_dries_up() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 2);
+        set_fluid(
+            &mut world,
+            pos(0, 0, 0),
+            FluidState::flowing(Fluid::Water, 7, false),
+        );
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Water, false);
+        assert_eq!(update.changes, vec![(pos(0, 0, 0), 0)]);
+    }
+
+    #[test]
+    fn water_between_sources_becomes_a_source() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 2);
+        set_fluid(&mut world, pos(-1, 0, 0), FluidState::source(Fluid::Water));
+        set_fluid(&mut world, pos(1, 0, 0), FluidState::source(Fluid::Water));
+        set_fluid(
+            &mut world,
+            pos(0, 0, 0),
+            FluidState::flowing(Fluid::Water, 7, false),
+        );
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Water, false);
+        assert_eq!(
+            changed_to(&update, pos(0, 0, 0)),
+            Some(FluidState::source(Fluid::Water).state_id())
+        );
+    }
+
+    #[test]
+    fn lava_spreads_less_outside_the_nether() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 2);
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Lava));
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Lava, false);
+        assert_eq!(
+            changed_to(&update, pos(1, 0, 0)),
+            Some(FluidState::flowing(Fluid::Lava, 6, false).state_id())
+        );
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Lava, true);
+        assert_eq!(
+            changed_to(&update, pos(1, 0, 0)),
+            Some(FluidState::flowing(Fluid::Lava, 7, false).state_id())
+        );
+    }
+
+    #[test]
+    fn lava_hardens_next_to_water() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 2);
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Lava));
+        set_fluid(
+            &mut world,
+            pos(1, 0, 0),
+            FluidState::flowing(Fluid::Water, 7, false),
+        );
+        let obsidian = get_block("obsidian").unwrap().default_state_id;
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Lava, false);
+        assert_eq!(update.changes, vec![(pos(0, 0, 0), obsidian)]);
+        assert_eq!(update.fizz, vec![pos(0, 0, 0)]);
+
+        set_fluid(
+            &mut world,
+            pos(0, 0, 0),
+            FluidState::flowing(Fluid::Lava, 4, false),
+        );
+        let cobblestone = get_block("cobblestone").unwrap().default_state_id;
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Lava, false);
+        assert_eq!(update.changes, vec![(pos(0, 0, 0), cobblestone)]);
+    }
+
+    #[test]
+    fn lava_flowing_into_water_makes_stone() {
+        let mut world = TestBlocks::new(15, 0);
+        set_fluid(&mut world, pos(0, 0, 0), FluidState::source(Fluid::Lava));
+        set_fluid(&mut world, pos(0, -1, 0), FluidState::source(Fluid::Water));
+        let stone = get_block("stone").unwrap().default_state_id;
+
+        let update = fluid_tick(&world, pos(0, 0, 0), Fluid::Lava, false);
+        assert_eq!(update.changes, vec![(pos(0, -1, 0), stone)]);
+        assert_eq!(update.fizz, vec![pos(0, -1, 0)]);
+    }
+}
@@ -0,0 +1,98 @@
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use rand::Rng;
+
+use crate::{WORLD_LOWEST_Y, WORLD_MAX_Y};
+
+use super::{block_registry::get_state_by_state_id, random_tick::BlockAccess};
+
+/// How many times lightning tries to set the blocks around where it strikes alight
+const FIRE_SPREAD_ATTEMPTS: usize = 4;
+
+fn is_air(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(get_state_by_state_id)
+        .is_some_and(|state| state.air)
+}
+
+/// Where lightning strikes in the column, the air on top of its highest block like vanilla's
+/// `MOTION_BLOCKING` heightmap. Columns without blocks are never struck
+pub fn lightning_target(world: &impl BlockAccess, x: i32, z: i32) -> Option<WorldPosition> {
+    (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+        .rev()
+        .map(|y| WorldPosition(Vector3::new(x, y, z)))
+        .find(|position| !is_air(world, position))
+        .map(|position| WorldPosition(position.0 + Vector3::new(0, 1, 0)))
+}
+
+/// Whether fire can burn at the position, air on top of a solid block
+fn can_burn(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    let below = WorldPosition(position.0 + Vector3::new(0, -1, 0));
+    let on_solid = world
+        .block_state(&below)
+        .and_then(get_state_by_state_id)
+        .is_some_and(|state| !state.collision_shapes.is_empty());
+    is_air(world, position) && on_solid
+}
+
+/// The blocks lightning sets alight, where it strikes and a few random blocks next to it like
+/// vanilla's `LightningBolt.spawnFire`
+pub fn lightning_fire(
+    world: &impl BlockAccess,
+    strike: WorldPosition,
+    rng: &mut impl Rng,
+) -> Vec<WorldPosition> {
+    let mut fire = Vec::new();
+    if can_burn(world, &strike) {
+        fire.push(strike);
+    }
+    for _ in 0..FIRE_SPREAD_ATTEMPTS {
+        let offset = Vector3::new(
+            rng.gen_range(-1..=1),
+            rng.gen_range(-1..=1),
+            rng.gen_range(-1..=1),
+        );
+        let position = WorldPosition(strike.0 + offset);
+        if !fire.contains(&position) && can_burn(world, &position) {
+            fire.push(position);
+        }
+    }
+    fire
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::block::random_tick::tests::{pos, TestBlocks};
+
+    use super::{lightning_fire, lightning_target};
+
+    #[test]
+    fn lightning_strikes_the_highest_block() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, 60, 0, "stone");
+        world.set(0, 64, 0, "oak_leaves");
+        assert_eq!(lightning_target(&world, 0, 0), Some(pos(0, 65, 0)));
+        assert_eq!(lightning_target(&world, 1, 0), None);
+    }
+
+    #[test]
+    fn lightning_only_sets_air_on_solid_blocks_alight() {
+        let mut world = TestBlocks::new(15, 0);
+        for x in -1..=1 {
+            for z in -1..=1 {
+                world.set(x, 63, z, "stone");
+            }
+        }
+        world.set(0, 64, 0, "stone");
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let fire = lightning_fire(&world, pos(0, 65, 0), &mut rng);
+            assert!(fire
+                .iter()
+                .all(|position| *position == pos(0, 65, 0) || position.0.y == 64));
+            assert!(!fire.contains(&pos(0, 64, 0)));
+        }
+    }
+}
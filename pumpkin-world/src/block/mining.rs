@@ -0,0 +1,69 @@
+/// Clients finish breaking a bit early, vanilla accepts blocks broken this far along
+pub const MIN_FINISHED_PROGRESS: f32 = 0.7;
+
+/// The mining speed multiplier of Haste, like vanilla's `Player.getDestroySpeed`
+pub fn haste_multiplier(amplifier: u8) -> f32 {
+    1.0 + (f32::from(amplifier) + 1.0) * 0.2
+}
+
+/// The mining speed multiplier of Mining Fatigue, it gets drastically stronger each level
+pub const fn mining_fatigue_multiplier(amplifier: u8) -> f32 {
+    match amplifier {
+        0 => 0.3,
+        1 => 0.09,
+        2 => 0.0027,
+        _ => 8.1e-4,
+    }
+}
+
+/// The mining speed a tool gains from Efficiency, only tools which are fast on the block get it
+pub fn efficiency_bonus(speed: f32, level: i32) -> f32 {
+    if speed > 1.0 && level > 0 {
+        (level * level + 1) as f32
+    } else {
+        0.0
+    }
+}
+
+/// How much of the block is mined each tick, like vanilla's `BlockBehaviour.getDestroyProgress`.
+/// Blocks which need the right tool take over three times as long without it and unbreakable
+/// blocks have a negative hardness
+pub fn destroy_progress(hardness: f32, speed: f32, correct_tool: bool) -> f32 {
+    if hardness < 0.0 {
+        return 0.0;
+    }
+    let divisor = if correct_tool { 30.0 } else { 100.0 };
+    speed / hardness / divisor
+}
+
+/// The crack texture shown for the progress, from 0 to 9
+pub fn destroy_stage(progress: f32) -> u8 {
+    (progress * 10.0).clamp(0.0, 9.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{destroy_progress, destroy_stage, efficiency_bonus, mining_fatigue_multiplier};
+
+    #[test]
+    fn break_times() {
+        // Stone takes 7.5 seconds by hand and over a second with a wooden pickaxe
+        let hand = destroy_progress(1.5, 1.0, false);
+        assert!((hand * 150.0 - 1.0).abs() < 1e-4);
+        let pickaxe = destroy_progress(1.5, 2.0, true);
+        assert!((pickaxe * 22.5 - 1.0).abs() < 1e-4);
+        // Blocks without hardness break instantly, bedrock never does
+        assert!(destroy_progress(0.0, 1.0, true) >= 1.0);
+        assert!(destroy_progress(-1.0, 100.0, true) <= 0.0);
+    }
+
+    #[test]
+    fn speed_modifiers() {
+        assert!((efficiency_bonus(8.0, 5) - 26.0).abs() < f32::EPSILON);
+        assert!(efficiency_bonus(1.0, 5).abs() < f32::EPSILON);
+        assert!(mining_fatigue_multiplier(5) < mining_fatigue_multiplier(2));
+        assert_eq!(destroy_stage(0.05), 0);
+        assert_eq!(destroy_stage(0.55), 5);
+        assert_eq!(destroy_stage(1.5), 9);
+    }
+}
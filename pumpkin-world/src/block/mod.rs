@@ -1,13 +1,24 @@
 use num_derive::FromPrimitive;
 
+pub mod block_entity;
 pub mod block_registry;
 pub mod block_state;
+pub mod experience;
+pub mod explosion;
+pub mod falling;
+pub mod fluid;
+pub mod lightning;
+pub mod mining;
+pub mod piston;
+pub mod portal;
+pub mod random_tick;
+pub mod redstone;
 
 use pumpkin_core::math::vector3::Vector3;
 
 pub use block_state::BlockState;
 
-#[derive(FromPrimitive)]
+#[derive(FromPrimitive, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BlockFace {
     Bottom = 0,
     Top,
@@ -18,6 +29,59 @@ pub enum BlockFace {
 }
 
 impl BlockFace {
+    pub const ALL: [Self; 6] = [
+        Self::Bottom,
+        Self::Top,
+        Self::North,
+        Self::South,
+        Self::West,
+        Self::East,
+    ];
+
+    /// The face as used by the `facing` property of blocks
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockFace::Bottom => "down",
+            BlockFace::Top => "up",
+            BlockFace::North => "north",
+            BlockFace::South => "south",
+            BlockFace::West => "west",
+            BlockFace::East => "east",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|face| face.name() == name)
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            BlockFace::Bottom => BlockFace::Top,
+            BlockFace::Top => BlockFace::Bottom,
+            BlockFace::North => BlockFace::South,
+            BlockFace::South => BlockFace::North,
+            BlockFace::West => BlockFace::East,
+            BlockFace::East => BlockFace::West,
+        }
+    }
+
+    /// The horizontal face to the right of this one when looking down, like vanilla's
+    /// `getClockWise`. Vertical faces stay the same
+    pub fn clockwise(self) -> Self {
+        match self {
+            BlockFace::North => BlockFace::East,
+            BlockFace::East => BlockFace::South,
+            BlockFace::South => BlockFace::West,
+            BlockFace::West => BlockFace::North,
+            vertical => vertical,
+        }
+    }
+
+    /// Whether both faces are on the same axis, like up and down
+    pub fn same_axis(self, other: Self) -> bool {
+        self == other || self == other.opposite()
+    }
+
     pub fn to_offset(&self) -> Vector3<i32> {
         match self {
             BlockFace::Bottom => (0, -1, 0),
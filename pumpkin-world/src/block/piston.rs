@@ -0,0 +1,737 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{WORLD_LOWEST_Y, WORLD_MAX_Y};
+
+use super::{
+    block_registry::{get_block, get_block_and_state_by_state_id, get_block_by_state_id},
+    random_tick::{property, with_property, BlockAccess},
+    redstone::relative,
+    BlockFace,
+};
+
+/// The most blocks a piston can push or pull at once
+pub const MAX_PUSH: usize = 12;
+
+/// How a block reacts to pistons pushing it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PushReaction {
+    /// Moved by pistons pushing and pulling it
+    Normal,
+    /// Broken and dropped when a piston pushes it
+    Destroy,
+    /// Stops pistons
+    Block,
+    /// Pushed, but sticky pistons can't pull it
+    PushOnly,
+}
+
+/// Blocks which break when pushed even though they have collision
+const DESTROYED_BY_PISTONS: [&str; 13] = [
+    "cactus",
+    "pumpkin",
+    "carved_pumpkin",
+    "jack_o_lantern",
+    "melon",
+    "dragon_egg",
+    "cocoa",
+    "cake",
+    "lily_pad",
+    "bamboo",
+    "chorus_plant",
+    "chorus_flower",
+    "pointed_dripstone",
+];
+
+/// Blocks which stop pistons even though they can be broken
+const IMMOVABLE: [&str; 4] = [
+    "obsidian",
+    "crying_obsidian",
+    "respawn_anchor",
+    "reinforced_deepslate",
+];
+
+/// How the block reacts to pistons, like vanilla's `getPistonPushReaction`
+pub fn push_reaction(state_id: u16) -> PushReaction {
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return PushReaction::Block;
+    };
+    let name = block.name.as_str();
+    if state.air {
+        PushReaction::Normal
+    } else if IMMOVABLE.contains(&name)
+        || block.hardness < 0.0
+        || state.block_entity_type.is_some()
+        || matches!(name, "piston_head" | "moving_piston")
+    {
+        PushReaction::Block
+    } else if name.ends_with("_glazed_terracotta") {
+        PushReaction::PushOnly
+    } else if state.replaceable
+        || state.collision_shapes.is_empty()
+        || DESTROYED_BY_PISTONS.contains(&name)
+        || name.ends_with("_door")
+        || name.ends_with("_bed")
+    {
+        PushReaction::Destroy
+    } else {
+        PushReaction::Normal
+    }
+}
+
+fn is_piston(state_id: u16) -> bool {
+    get_block_by_state_id(state_id)
+        .is_some_and(|block| matches!(block.name.as_str(), "piston" | "sticky_piston"))
+}
+
+/// Slime and honey blocks pull the blocks next to them along
+fn is_sticky(state_id: u16) -> bool {
+    get_block_by_state_id(state_id)
+        .is_some_and(|block| matches!(block.name.as_str(), "slime_block" | "honey_block"))
+}
+
+/// Slime and honey blocks stick to everything except each other
+fn can_stick_to_each_other(first: u16, second: u16) -> bool {
+    let name = |state_id| get_block_by_state_id(state_id).map(|block| block.name.as_str());
+    let (first_name, second_name) = (name(first), name(second));
+    let slime_and_honey = matches!(
+        (first_name, second_name),
+        (Some("slime_block"), Some("honey_block")) | (Some("honey_block"), Some("slime_block"))
+    );
+    !slime_and_honey && (is_sticky(first) || is_sticky(second))
+}
+
+/// The direction the piston, its head or the moving block faces
+pub fn facing(state_id: u16) -> Option<BlockFace> {
+    let block = get_block_by_state_id(state_id)?;
+    BlockFace::from_name(property(block, state_id, "facing")?)
+}
+
+/// Whether the piston or piston head is sticky
+pub fn is_sticky_piston(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| {
+        block.name == "sticky_piston" || property(block, state_id, "type") == Some("sticky")
+    })
+}
+
+/// Whether the piston is extended
+pub fn is_extended(state_id: u16) -> bool {
+    get_block_by_state_id(state_id)
+        .is_some_and(|block| property(block, state_id, "extended") == Some("true"))
+}
+
+fn piston_state(name: &str, facing: BlockFace, properties: &[(&str, &str)]) -> u16 {
+    let Some(block) = get_block(name) else {
+        return 0;
+    };
+    let mut properties: HashMap<String, String> = properties
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    properties.insert("facing".to_string(), facing.name().to_string());
+    block.state_with_properties(&properties)
+}
+
+fn piston_type(sticky: bool) -> &'static str {
+    if sticky {
+        "sticky"
+    } else {
+        "normal"
+    }
+}
+
+/// Whether pistons can move the block, like vanilla's `PistonBaseBlock.isPushable`
+fn is_pushable(
+    state_id: u16,
+    position: &WorldPosition,
+    direction: BlockFace,
+    allow_destroy: bool,
+    piston_facing: BlockFace,
+) -> bool {
+    let y = position.0.y;
+    if y < i32::from(WORLD_LOWEST_Y)
+        || y >= i32::from(WORLD_MAX_Y)
+        || (direction == BlockFace::Bottom && y == i32::from(WORLD_LOWEST_Y))
+        || (direction == BlockFace::Top && y == i32::from(WORLD_MAX_Y) - 1)
+    {
+        return false;
+    }
+    if is_piston(state_id) {
+        return !is_extended(state_id);
+    }
+    match push_reaction(state_id) {
+        PushReaction::Normal => true,
+        PushReaction::Destroy => allow_destroy,
+        PushReaction::Block => false,
+        PushReaction::PushOnly => direction == piston_facing,
+    }
+}
+
+/// The blocks a piston moves and the ones it breaks
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PistonStructure {
+    /// The blocks which move, blocks further away from the piston come first
+    pub to_push: Vec<WorldPosition>,
+    pub to_destroy: Vec<WorldPosition>,
+}
+
+/// Finds the blocks a piston moves, a port of vanilla's `PistonStructureResolver`
+struct Resolver<'a, W> {
+    world: &'a W,
+    piston: WorldPosition,
+    piston_facing: BlockFace,
+    push_direction: BlockFace,
+    /// The head of a retracting piston, which is removed before the blocks are pulled
+    head: Option<WorldPosition>,
+    structure: PistonStructure,
+}
+
+impl<W: BlockAccess> Resolver<'_, W> {
+    /// Unloaded blocks stop pistons like bedrock does
+    fn state(&self, position: &WorldPosition) -> u16 {
+        if self.head.as_ref() == Some(position) {
+            return 0;
+        }
+        self.world.block_state(position).unwrap_or(u16::MAX)
+    }
+
+    fn is_air(&self, position: &WorldPosition) -> bool {
+        get_block_and_state_by_state_id(self.state(position)).is_some_and(|(_, state)| state.air)
+    }
+
+    fn resolve(&mut self, start: WorldPosition, extending: bool) -> bool {
+        let state_id = self.state(&start);
+        if !is_pushable(
+            state_id,
+            &start,
+            self.push_direction,
+            false,
+            self.piston_facing,
+        ) {
+            if extending && push_reaction(state_id) == PushReaction::Destroy {
+                self.structure.to_destroy.push(start);
+                return true;
+            }
+            return false;
+        }
+        if !self.add_block_line(start, self.push_direction) {
+            return false;
+        }
+        let mut i = 0;
+        while i < self.structure.to_push.len() {
+            let position = self.structure.to_push[i];
+            if is_sticky(self.state(&position)) && !self.add_branching_blocks(position) {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Adds the block and the line of blocks in front of it, and behind it for blocks stuck to it
+    fn add_block_line(&mut self, origin: WorldPosition, direction: BlockFace) -> bool {
+        let mut state_id = self.state(&origin);
+        if self.is_air(&origin)
+            || !is_pushable(state_id, &origin, self.push_direction, false, direction)
+            || origin == self.piston
+            || self.structure.to_push.contains(&origin)
+        {
+            return true;
+        }
+
+        // Blocks stuck to the back of the line move along
+        let mut count = 1;
+        if count + self.structure.to_push.len() > MAX_PUSH {
+            return false;
+        }
+        while is_sticky(state_id) {
+            let position = self.offset(&origin, self.push_direction.opposite(), count);
+            let previous = state_id;
+            state_id = self.state(&position);
+            if self.is_air(&position)
+                || !can_stick_to_each_other(previous, state_id)
+                || !is_pushable(
+                    state_id,
+                    &position,
+                    self.push_direction,
+                    false,
+                    self.push_direction.opposite(),
+                )
+                || position == self.piston
+            {
+                break;
+            }
+            count += 1;
+            if count + self.structure.to_push.len() > MAX_PUSH {
+                return false;
+            }
+        }
+
+        let mut added = 0;
+        for i in (0..count).rev() {
+            let position = self.offset(&origin, self.push_direction.opposite(), i);
+            self.structure.to_push.push(position);
+            added += 1;
+        }
+
+        // The blocks in front are pushed by the line
+        let mut distance = 1;
+        loop {
+            let position = self.offset(&origin, self.push_direction, distance);
+            if let Some(index) = self.structure.to_push.iter().position(|p| *p == position) {
+                self.reorder_at_collision(added, index);
+                for i in 0..=index + added {
+                    let position = self.structure.to_push[i];
+                    if is_sticky(self.state(&position)) && !self.add_branching_blocks(position) {
+                        return false;
+                    }
+                }
+                return true;
+            }
+            let state_id = self.state(&position);
+            if self.is_air(&position) {
+                return true;
+            }
+            if !is_pushable(
+                state_id,
+                &position,
+                self.push_direction,
+                true,
+                self.push_direction,
+            ) || position == self.piston
+            {
+                return false;
+            }
+            if push_reaction(state_id) == PushReaction::Destroy {
+                self.structure.to_destroy.push(position);
+                return true;
+            }
+            if self.structure.to_push.len() >= MAX_PUSH {
+                return false;
+            }
+            self.structure.to_push.push(position);
+            added += 1;
+            distance += 1;
+        }
+    }
+
+    /// Moves the last `added` blocks in front of the block at `index`, which they run into
+    fn reorder_at_collision(&mut self, added: usize, index: usize) {
+        let to_push = &mut self.structure.to_push;
+        let split = to_push.len() - added;
+        let mut reordered = to_push[..index].to_vec();
+        reordered.extend_from_slice(&to_push[split..]);
+        reordered.extend_from_slice(&to_push[index..split]);
+        *to_push = reordered;
+    }
+
+    /// Adds the blocks stuck to the sides of a slime or honey block
+    fn add_branching_blocks(&mut self, from: WorldPosition) -> bool {
+        let state_id = self.state(&from);
+        for direction in BlockFace::ALL {
+            if direction.same_axis(self.push_direction) {
+                continue;
+            }
+            let position = relative(&from, direction);
+            if can_stick_to_each_other(self.state(&position), state_id)
+                && !self.add_block_line(position, direction)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn offset(
+        &self,
+        position: &WorldPosition,
+        direction: BlockFace,
+        distance: usize,
+    ) -> WorldPosition {
+        let distance = i32::try_from(distance).unwrap_or(i32::MAX);
+        WorldPosition(position.0 + direction.to_offset() * distance)
+    }
+}
+
+/// The blocks the piston moves when it extends or a sticky piston retracts, `None` if it can't
+/// move them, e.g. when pushing more than 12 blocks or obsidian
+pub fn resolve_structure(
+    world: &impl BlockAccess,
+    piston: WorldPosition,
+    facing: BlockFace,
+    extending: bool,
+) -> Option<PistonStructure> {
+    let (push_direction, start) = if extending {
+        (facing, relative(&piston, facing))
+    } else {
+        (
+            facing.opposite(),
+            relative(&relative(&piston, facing), facing),
+        )
+    };
+    let mut resolver = Resolver {
+        world,
+        piston,
+        piston_facing: facing,
+        push_direction,
+        head: (!extending).then(|| relative(&piston, facing)),
+        structure: PistonStructure::default(),
+    };
+    resolver
+        .resolve(start, extending)
+        .then_some(resolver.structure)
+}
+
+/// A block being moved by a piston, stored in the block entity of the `moving_piston` block in its
+/// place until the piston finished moving
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MovingPiston {
+    /// The block which is placed once the piston finished moving
+    pub state_id: u16,
+    /// The direction the piston faces
+    pub facing: BlockFace,
+    pub extending: bool,
+    /// Whether the block is the piston head or the retracting piston itself
+    pub source: bool,
+}
+
+impl MovingPiston {
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        let mut block_state = NbtCompound::new();
+        if let Some(block) = get_block_by_state_id(self.state_id) {
+            block_state.put(
+                "Name".to_string(),
+                NbtTag::String(format!("minecraft:{}", block.name)),
+            );
+            let properties: NbtCompound = block
+                .state_properties(self.state_id)
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), NbtTag::String(value.to_string())))
+                .collect();
+            block_state.put("Properties".to_string(), NbtTag::Compound(properties));
+        }
+        nbt.put("blockState".to_string(), NbtTag::Compound(block_state));
+        nbt.put("facing".to_string(), NbtTag::Int(self.facing as i32));
+        nbt.put("progress".to_string(), NbtTag::Float(0.0));
+        nbt.put(
+            "extending".to_string(),
+            NbtTag::Byte(i8::from(self.extending)),
+        );
+        nbt.put("source".to_string(), NbtTag::Byte(i8::from(self.source)));
+        nbt
+    }
+
+    pub fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let block_state = nbt.get_compound("blockState")?;
+        let block = get_block(block_state.get_string("Name")?)?;
+        let state_id = match block_state.get_compound("Properties") {
+            Some(properties) => {
+                let properties: HashMap<String, String> = properties
+                    .child_tags
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.clone(), value.extract_string()?.clone()))
+                    })
+                    .collect();
+                block.state_with_properties(&properties)
+            }
+            None => block.default_state_id,
+        };
+        let facing = num_traits::FromPrimitive::from_i32(nbt.get_int("facing")?)?;
+        Some(Self {
+            state_id,
+            facing,
+            extending: nbt.get_bool("extending")?,
+            source: nbt.get_bool("source")?,
+        })
+    }
+}
+
+/// The blocks a piston changes when it starts moving
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PistonMove {
+    /// The blocks which break and drop, with their states
+    pub destroyed: Vec<(WorldPosition, u16)>,
+    /// The blocks which change and their new states, in the order they change
+    pub changes: Vec<(WorldPosition, u16)>,
+    /// The `moving_piston` blocks placed and the blocks they turn into once the piston finished
+    pub moving: Vec<(WorldPosition, MovingPiston)>,
+}
+
+impl PistonMove {
+    /// Turns the structure into moving blocks, leaving air behind
+    fn move_blocks(
+        &mut self,
+        world: &impl BlockAccess,
+        structure: &PistonStructure,
+        facing: BlockFace,
+        extending: bool,
+        sticky: bool,
+    ) {
+        let direction = if extending { facing } else { facing.opposite() };
+        for position in &structure.to_destroy {
+            self.destroyed
+                .push((*position, world.block_state(position).unwrap_or(0)));
+        }
+        let mut vacated: Vec<WorldPosition> = structure.to_push.clone();
+        vacated.extend_from_slice(&structure.to_destroy);
+        let mut moving = Vec::new();
+        for position in structure.to_push.iter().rev() {
+            let target = relative(position, direction);
+            vacated.retain(|vacated| *vacated != target);
+            let state_id = world.block_state(position).unwrap_or(0);
+            moving.push((
+                target,
+                MovingPiston {
+                    state_id,
+                    facing,
+                    extending,
+                    source: false,
+                },
+            ));
+        }
+        self.changes
+            .extend(vacated.into_iter().map(|position| (position, 0)));
+        self.add_moving(moving, sticky);
+    }
+
+    fn add_moving(&mut self, moving: Vec<(WorldPosition, MovingPiston)>, sticky: bool) {
+        for (position, block) in moving {
+            let state_id = piston_state(
+                "moving_piston",
+                block.facing,
+                &[("type", piston_type(sticky))],
+            );
+            self.changes.push((position, state_id));
+            self.moving.push((position, block));
+        }
+    }
+}
+
+/// What changes when the piston at the position extends, `None` if it can't push the blocks in
+/// front of it. Like vanilla's `PistonBaseBlock.moveBlocks`
+pub fn extend(world: &impl BlockAccess, piston: WorldPosition) -> Option<PistonMove> {
+    let state_id = world.block_state(&piston)?;
+    let facing = facing(state_id)?;
+    let sticky = is_sticky_piston(state_id);
+    let structure = resolve_structure(world, piston, facing, true)?;
+    let mut piston_move = PistonMove::default();
+    piston_move.move_blocks(world, &structure, facing, true, sticky);
+
+    let head = piston_state(
+        "piston_head",
+        facing,
+        &[("type", piston_type(sticky)), ("short", "false")],
+    );
+    let head_position = relative(&piston, facing);
+    piston_move
+        .changes
+        .retain(|(position, _)| *position != head_position);
+    piston_move.add_moving(
+        vec![(
+            head_position,
+            MovingPiston {
+                state_id: head,
+                facing,
+                extending: true,
+                source: true,
+            },
+        )],
+        sticky,
+    );
+    let block = get_block_by_state_id(state_id)?;
+    piston_move
+        .changes
+        .push((piston, with_property(block, state_id, "extended", "true")));
+    Some(piston_move)
+}
+
+/// What changes when the piston at the position retracts. Sticky pistons pull the block in front
+/// of the head along if they can, like vanilla's `PistonBaseBlock.triggerEvent`
+pub fn retract(world: &impl BlockAccess, piston: WorldPosition) -> Option<PistonMove> {
+    let state_id = world.block_state(&piston)?;
+    let facing = facing(state_id)?;
+    let sticky = is_sticky_piston(state_id);
+    let block = get_block_by_state_id(state_id)?;
+    let retracted = with_property(block, state_id, "extended", "false");
+
+    let mut piston_move = PistonMove::default();
+    piston_move.add_moving(
+        vec![(
+            piston,
+            MovingPiston {
+                state_id: retracted,
+                facing,
+                extending: false,
+                source: true,
+            },
+        )],
+        sticky,
+    );
+
+    let head = relative(&piston, facing);
+    let pulled = relative(&head, facing);
+    let structure = world.block_state(&pulled).and_then(|pulled_state| {
+        let pullable = !get_block_and_state_by_state_id(pulled_state)
+            .is_some_and(|(_, state)| state.air)
+            && is_pushable(pulled_state, &pulled, facing.opposite(), false, facing)
+            && (push_reaction(pulled_state) == PushReaction::Normal || is_piston(pulled_state));
+        (sticky && pullable)
+            .then(|| resolve_structure(world, piston, facing, false))
+            .flatten()
+    });
+    match structure {
+        Some(structure) => piston_move.move_blocks(world, &structure, facing, false, sticky),
+        None => piston_move.changes.push((head, 0)),
+    }
+    Some(piston_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_core::math::position::WorldPosition;
+
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::tests::{pos, TestBlocks},
+        BlockFace,
+    };
+
+    use super::{
+        extend, is_extended, piston_state, push_reaction, resolve_structure, retract, MovingPiston,
+        PushReaction,
+    };
+
+    fn state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    fn set_piston(world: &mut TestBlocks, position: WorldPosition, name: &str, extended: bool) {
+        let extended = if extended { "true" } else { "false" };
+        world.blocks.insert(
+            position,
+            piston_state(name, BlockFace::East, &[("extended", extended)]),
+        );
+    }
+
+    #[test]
+    fn push_reactions() {
+        assert_eq!(push_reaction(state("stone")), PushReaction::Normal);
+        assert_eq!(push_reaction(state("air")), PushReaction::Normal);
+        assert_eq!(push_reaction(state("obsidian")), PushReaction::Block);
+        assert_eq!(push_reaction(state("bedrock")), PushReaction::Block);
+        assert_eq!(push_reaction(state("chest")), PushReaction::Block);
+        assert_eq!(push_reaction(state("torch")), PushReaction::Destroy);
+        assert_eq!(push_reaction(state("water")), PushReaction::Destroy);
+        assert_eq!(push_reaction(state("oak_door")), PushReaction::Destroy);
+        assert_eq!(
+            push_reaction(state("red_glazed_terracotta")),
+            PushReaction::PushOnly
+        );
+    }
+
+    #[test]
+    fn pushes_at_most_twelve_blocks() {
+        let mut world = TestBlocks::new(15, 0);
+        set_piston(&mut world, pos(0, 0, 0), "piston", false);
+        for x in 1..=12 {
+            world.set(x, 0, 0, "stone");
+        }
+        let structure = resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true).unwrap();
+        assert_eq!(structure.to_push.len(), 12);
+        assert!(structure.to_destroy.is_empty());
+
+        world.set(13, 0, 0, "stone");
+        assert_eq!(
+            resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true),
+            None
+        );
+
+        // Obsidian stops the piston, torches break
+        world.blocks.clear();
+        world.set(1, 0, 0, "stone");
+        world.set(2, 0, 0, "obsidian");
+        assert_eq!(
+            resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true),
+            None
+        );
+        world.set(2, 0, 0, "torch");
+        let structure = resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true).unwrap();
+        assert_eq!(structure.to_push, vec![pos(1, 0, 0)]);
+        assert_eq!(structure.to_destroy, vec![pos(2, 0, 0)]);
+    }
+
+    #[test]
+    fn slime_pulls_neighbours_along() {
+        let mut world = TestBlocks::new(15, 0);
+        set_piston(&mut world, pos(0, 0, 0), "piston", false);
+        world.set(1, 0, 0, "slime_block");
+        world.set(1, 1, 0, "stone");
+        world.set(1, 0, 1, "honey_block");
+        world.set(0, 0, -1, "stone");
+        let structure = resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true).unwrap();
+        let mut to_push = structure.to_push.clone();
+        to_push.sort_by_key(|position| (position.0.x, position.0.y, position.0.z));
+        // Honey doesn't stick to slime, the stone next to the piston isn't touching the slime
+        assert_eq!(to_push, vec![pos(1, 0, 0), pos(1, 1, 0)]);
+
+        // Blocks stuck to the slime count towards the limit
+        for x in 2..=12 {
+            world.set(x, 0, 0, "stone");
+        }
+        assert_eq!(
+            resolve_structure(&world, pos(0, 0, 0), BlockFace::East, true),
+            None
+        );
+    }
+
+    #[test]
+    fn extend_and_retract() {
+        let mut world = TestBlocks::new(15, 0);
+        set_piston(&mut world, pos(0, 0, 0), "sticky_piston", false);
+        let stone = world.set(1, 0, 0, "stone");
+        let piston_move = extend(&world, pos(0, 0, 0)).unwrap();
+        let moving: HashMap<_, _> = piston_move.moving.iter().copied().collect();
+        assert_eq!(moving[&pos(2, 0, 0)].state_id, stone);
+        assert!(moving[&pos(1, 0, 0)].source);
+        let (position, base) = *piston_move.changes.last().unwrap();
+        assert_eq!(position, pos(0, 0, 0));
+        assert!(is_extended(base));
+
+        // The sticky piston pulls the stone back next to it
+        world.blocks.clear();
+        set_piston(&mut world, pos(0, 0, 0), "sticky_piston", true);
+        world.blocks.insert(
+            pos(1, 0, 0),
+            piston_state("piston_head", BlockFace::East, &[("type", "sticky")]),
+        );
+        world.set(2, 0, 0, "stone");
+        let piston_move = retract(&world, pos(0, 0, 0)).unwrap();
+        let moving: HashMap<_, _> = piston_move.moving.iter().copied().collect();
+        assert_eq!(moving[&pos(1, 0, 0)].state_id, stone);
+        assert!(!is_extended(moving[&pos(0, 0, 0)].state_id));
+        assert!(piston_move.changes.contains(&(pos(2, 0, 0), 0)));
+
+        // Normal pistons leave it
+        set_piston(&mut world, pos(0, 0, 0), "piston", true);
+        let piston_move = retract(&world, pos(0, 0, 0)).unwrap();
+        assert_eq!(piston_move.moving.len(), 1);
+        assert!(piston_move.changes.contains(&(pos(1, 0, 0), 0)));
+    }
+
+    #[test]
+    fn moving_piston_nbt() {
+        let moving = MovingPiston {
+            state_id: piston_state("piston_head", BlockFace::Top, &[("type", "sticky")]),
+            facing: BlockFace::Top,
+            extending: true,
+            source: true,
+        };
+        assert_eq!(MovingPiston::from_nbt(&moving.to_nbt()), Some(moving));
+    }
+}
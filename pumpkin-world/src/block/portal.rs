@@ -0,0 +1,586 @@
+use pumpkin_core::math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3};
+
+use super::{
+    block_registry::{get_block, get_block_and_state_by_state_id, get_block_by_state_id},
+    random_tick::{default_state, offset, property, with_property, BlockAccess},
+    BlockFace,
+};
+
+/// Nether portals are at most this wide and high inside of their frame
+const MAX_PORTAL_SIZE: i32 = 21;
+
+/// How far from where an entity comes out of a portal a new portal may be built
+pub const PORTAL_SITE_RADIUS: i32 = 16;
+
+/// Where players arrive in the end, on an obsidian platform
+pub const END_SPAWN: WorldPosition = WorldPosition(Vector3::new(100, 49, 0));
+
+/// The horizontal axis a nether portal stretches along
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortalAxis {
+    X,
+    Z,
+}
+
+impl PortalAxis {
+    /// The value of the `axis` property of nether portals
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::X => "x",
+            Self::Z => "z",
+        }
+    }
+
+    fn step(self) -> (i32, i32) {
+        match self {
+            Self::X => (1, 0),
+            Self::Z => (0, 1),
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            Self::X => Self::Z,
+            Self::Z => Self::X,
+        }
+    }
+}
+
+/// The inside of an obsidian frame, which is filled with portal blocks when it is lit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NetherPortalShape {
+    /// The lowest block inside of the frame, the others are in the positive direction of the axis
+    pub bottom_left: WorldPosition,
+    pub axis: PortalAxis,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl NetherPortalShape {
+    /// The blocks inside of the frame
+    pub fn blocks(&self) -> Vec<WorldPosition> {
+        let (step_x, step_z) = self.axis.step();
+        (0..self.height)
+            .flat_map(|y| {
+                (0..self.width).map(move |i| offset(&self.bottom_left, step_x * i, y, step_z * i))
+            })
+            .collect()
+    }
+
+    /// The portal block filling this shape
+    pub fn portal_state(&self) -> u16 {
+        nether_portal_state(self.axis)
+    }
+
+    /// Where entities come out of the portal, at the bottom of its middle
+    pub fn exit(&self) -> Vector3<f64> {
+        let (step_x, step_z) = self.axis.step();
+        let along = f64::from(self.width) / 2.0;
+        let x = f64::from(self.bottom_left.0.x);
+        let z = f64::from(self.bottom_left.0.z);
+        Vector3::new(
+            x + if step_x == 1 { along } else { 0.5 },
+            f64::from(self.bottom_left.0.y),
+            z + if step_z == 1 { along } else { 0.5 },
+        )
+    }
+}
+
+/// A portal an entity can stand in
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PortalKind {
+    /// A nether portal along the axis
+    Nether(PortalAxis),
+    End,
+}
+
+/// The portal the bounding box touches, end portals win over nether portals
+pub fn portal_in(world: &impl BlockAccess, bounding_box: &BoundingBox) -> Option<PortalKind> {
+    // Entities touching a portal from the outside are not in it
+    let min = |value: f64| (value + 1.0E-7).floor() as i32;
+    let max = |value: f64| (value - 1.0E-7).floor() as i32;
+    let mut portal = None;
+    for x in min(bounding_box.min_x)..=max(bounding_box.max_x) {
+        for y in min(bounding_box.min_y)..=max(bounding_box.max_y) {
+            for z in min(bounding_box.min_z)..=max(bounding_box.max_z) {
+                let Some(state) = world.block_state(&WorldPosition(Vector3::new(x, y, z))) else {
+                    continue;
+                };
+                if is_end_portal(state) {
+                    return Some(PortalKind::End);
+                }
+                if let Some(axis) = nether_portal_axis(state) {
+                    portal = Some(PortalKind::Nether(axis));
+                }
+            }
+        }
+    }
+    portal
+}
+
+/// The nether portal block along the axis
+pub fn nether_portal_state(axis: PortalAxis) -> u16 {
+    get_block("minecraft:nether_portal").map_or(0, |block| {
+        with_property(block, block.default_state_id, "axis", axis.name())
+    })
+}
+
+pub fn is_nether_portal(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| block.name == "nether_portal")
+}
+
+pub fn is_end_portal(state_id: u16) -> bool {
+    get_block_by_state_id(state_id).is_some_and(|block| block.name == "end_portal")
+}
+
+/// The axis of the nether portal block, `None` for other blocks
+pub fn nether_portal_axis(state_id: u16) -> Option<PortalAxis> {
+    let block = get_block_by_state_id(state_id).filter(|block| block.name == "nether_portal")?;
+    match property(block, state_id, "axis")? {
+        "z" => Some(PortalAxis::Z),
+        _ => Some(PortalAxis::X),
+    }
+}
+
+fn is_frame(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(get_block_by_state_id)
+        .is_some_and(|block| block.name == "obsidian")
+}
+
+/// Portals are lit in air and fire and keep the portal blocks they already have
+fn is_empty(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(get_block_and_state_by_state_id)
+        .is_some_and(|(block, state)| {
+            state.air || matches!(block.name.as_str(), "fire" | "soul_fire" | "nether_portal")
+        })
+}
+
+/// How far the empty blocks on top of the frame reach in the direction, 0 if they don't end at the
+/// frame
+fn distance_to_frame(world: &impl BlockAccess, start: &WorldPosition, step: (i32, i32)) -> i32 {
+    for i in 0..=MAX_PORTAL_SIZE {
+        let position = offset(start, step.0 * i, 0, step.1 * i);
+        if !is_empty(world, &position) {
+            return if is_frame(world, &position) { i } else { 0 };
+        }
+        if !is_frame(world, &offset(&position, 0, -1, 0)) {
+            return 0;
+        }
+    }
+    0
+}
+
+/// The frame around the position along the axis, like vanilla's `PortalShape`
+fn find_shape(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+    axis: PortalAxis,
+) -> Option<NetherPortalShape> {
+    let (step_x, step_z) = axis.step();
+    let mut bottom = position;
+    for _ in 0..MAX_PORTAL_SIZE {
+        if !is_empty(world, &offset(&bottom, 0, -1, 0)) {
+            break;
+        }
+        bottom = offset(&bottom, 0, -1, 0);
+    }
+
+    let left = distance_to_frame(world, &bottom, (-step_x, -step_z)) - 1;
+    if left < 0 {
+        return None;
+    }
+    let bottom_left = offset(&bottom, -step_x * left, 0, -step_z * left);
+    let width = distance_to_frame(world, &bottom_left, (step_x, step_z));
+    if !(2..=MAX_PORTAL_SIZE).contains(&width) {
+        return None;
+    }
+
+    let column = |i: i32, y: i32| offset(&bottom_left, step_x * i, y, step_z * i);
+    let height = (0..MAX_PORTAL_SIZE)
+        .find(|y| {
+            !is_frame(world, &column(-1, *y))
+                || !is_frame(world, &column(width, *y))
+                || (0..width).any(|i| !is_empty(world, &column(i, *y)))
+        })
+        .unwrap_or(MAX_PORTAL_SIZE);
+    let has_top = (0..width).all(|i| is_frame(world, &column(i, height)));
+    if height < 3 || !has_top {
+        return None;
+    }
+    Some(NetherPortalShape {
+        bottom_left,
+        axis,
+        width,
+        height,
+    })
+}
+
+/// The obsidian frame the position is in, along the x axis if it could be both
+pub fn find_nether_portal_shape(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+) -> Option<NetherPortalShape> {
+    find_shape(world, position, PortalAxis::X)
+        .or_else(|| find_shape(world, position, PortalAxis::Z))
+}
+
+/// Whether the nether portal block still has portal blocks or obsidian above, below and next to
+/// it along its axis. Portals break apart once a block of them or of their frame is gone
+pub fn nether_portal_survives(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+    state_id: u16,
+) -> bool {
+    let Some(axis) = nether_portal_axis(state_id) else {
+        return true;
+    };
+    let (step_x, step_z) = axis.step();
+    [
+        (0, -1, 0),
+        (0, 1, 0),
+        (step_x, 0, step_z),
+        (-step_x, 0, -step_z),
+    ]
+    .iter()
+    .all(|(x, y, z)| {
+        let neighbor = offset(&position, *x, *y, *z);
+        // Unloaded blocks don't break portals
+        world
+            .block_state(&neighbor)
+            .is_none_or(|state| is_nether_portal(state) || is_frame(world, &neighbor))
+    })
+}
+
+/// The inside of the nether portal the portal block is part of
+pub fn nether_portal_at(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+) -> Option<NetherPortalShape> {
+    let axis = nether_portal_axis(world.block_state(&position)?)?;
+    find_shape(world, position, axis)
+}
+
+fn is_solid(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(get_block_and_state_by_state_id)
+        .is_some_and(|(_, state)| !state.replaceable && !state.collision_shapes.is_empty())
+}
+
+fn is_air(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    world
+        .block_state(position)
+        .and_then(get_block_and_state_by_state_id)
+        .is_some_and(|(_, state)| state.air)
+}
+
+/// Whether a portal along the axis fits with its lowest inside block at the position. Its frame
+/// replaces the ground, and there has to be room to walk out of it on both sides
+fn fits_portal(world: &impl BlockAccess, position: &WorldPosition, axis: PortalAxis) -> bool {
+    let (step_x, step_z) = axis.step();
+    let (across_x, across_z) = axis.other().step();
+    (-1..=2).all(|along| {
+        (-1..=1).all(|across| {
+            let ground = offset(
+                position,
+                step_x * along + across_x * across,
+                -1,
+                step_z * along + across_z * across,
+            );
+            is_solid(world, &ground) && (1..=4).all(|y| is_air(world, &offset(&ground, 0, y, 0)))
+        })
+    })
+}
+
+/// The place closest to `center` within `radius` blocks where a new portal fits, like vanilla's
+/// `PortalForcer.createPortal`. Returns the lowest block inside of the new portal
+pub fn find_portal_site(
+    world: &impl BlockAccess,
+    center: WorldPosition,
+    axis: PortalAxis,
+    radius: i32,
+    min_y: i32,
+    max_y: i32,
+) -> Option<WorldPosition> {
+    let mut best: Option<(i64, WorldPosition)> = None;
+    for x in -radius..=radius {
+        for z in -radius..=radius {
+            for y in (min_y + 1..=max_y).rev() {
+                let position = WorldPosition(Vector3::new(center.0.x + x, y, center.0.z + z));
+                // Most places are ruled out by the column itself
+                if !is_solid(world, &offset(&position, 0, -1, 0))
+                    || !(0..4).all(|up| is_air(world, &offset(&position, 0, up, 0)))
+                    || !fits_portal(world, &position, axis)
+                {
+                    continue;
+                }
+                let dy = i64::from(y - center.0.y);
+                let distance = i64::from(x * x + z * z) + dy * dy;
+                if best.is_none_or(|(closest, _)| distance < closest) {
+                    best = Some((distance, position));
+                }
+            }
+        }
+    }
+    best.map(|(_, position)| position)
+}
+
+/// The blocks of a new lit portal with its lowest inside block at the position. A portal in the
+/// air stands on an obsidian platform with room to walk out on both sides
+pub fn build_nether_portal(
+    position: WorldPosition,
+    axis: PortalAxis,
+    platform: bool,
+) -> Vec<(WorldPosition, u16)> {
+    let obsidian = default_state("obsidian");
+    let (step_x, step_z) = axis.step();
+    let (across_x, across_z) = axis.other().step();
+    let mut blocks = Vec::new();
+    if platform {
+        for along in 0..2 {
+            for across in [-1, 1] {
+                let ground = offset(
+                    &position,
+                    step_x * along + across_x * across,
+                    -1,
+                    step_z * along + across_z * across,
+                );
+                blocks.push((ground, obsidian));
+                blocks.extend((1..=3).map(|y| (offset(&ground, 0, y, 0), 0)));
+            }
+        }
+    }
+    for along in -1..=2 {
+        for y in -1..=3 {
+            let block = offset(&position, step_x * along, y, step_z * along);
+            let inside = (0..2).contains(&along) && (0..3).contains(&y);
+            blocks.push((
+                block,
+                if inside {
+                    nether_portal_state(axis)
+                } else {
+                    obsidian
+                },
+            ));
+        }
+    }
+    blocks
+}
+
+/// The obsidian platform players arrive on in the end, with room above it
+pub fn end_platform() -> Vec<(WorldPosition, u16)> {
+    let obsidian = default_state("obsidian");
+    let mut blocks = Vec::new();
+    for x in -2..=2 {
+        for z in -2..=2 {
+            blocks.push((offset(&END_SPAWN, x, -1, z), obsidian));
+            blocks.extend((0..3).map(|y| (offset(&END_SPAWN, x, y, z), 0)));
+        }
+    }
+    blocks
+}
+
+/// Whether the state is an end portal frame with an eye in it
+fn frame_with_eye(world: &impl BlockAccess, position: &WorldPosition, facing: BlockFace) -> bool {
+    world
+        .block_state(position)
+        .and_then(|state| Some((get_block_by_state_id(state)?, state)))
+        .is_some_and(|(block, state)| {
+            block.name == "end_portal_frame"
+                && property(block, state, "eye") == Some("true")
+                && property(block, state, "facing") == Some(facing.name())
+        })
+}
+
+/// Whether the 12 frames around the 3x3 center are all complete and facing it
+fn is_complete_end_portal(world: &impl BlockAccess, center: &WorldPosition) -> bool {
+    (-1..=1).all(|i| {
+        frame_with_eye(world, &offset(center, i, 0, -2), BlockFace::South)
+            && frame_with_eye(world, &offset(center, i, 0, 2), BlockFace::North)
+            && frame_with_eye(world, &offset(center, -2, 0, i), BlockFace::East)
+            && frame_with_eye(world, &offset(center, 2, 0, i), BlockFace::West)
+    })
+}
+
+/// The middle of the end portal the frame completes, `None` if the portal is still missing eyes
+pub fn completed_end_portal(
+    world: &impl BlockAccess,
+    frame: WorldPosition,
+) -> Option<WorldPosition> {
+    // The frame is on one of the sides of the center, which is two blocks away
+    (-1..=1)
+        .flat_map(|i| {
+            [
+                offset(&frame, i, 0, 2),
+                offset(&frame, i, 0, -2),
+                offset(&frame, 2, 0, i),
+                offset(&frame, -2, 0, i),
+            ]
+        })
+        .find(|center| is_complete_end_portal(world, center))
+}
+
+/// The end portal blocks inside of a completed frame
+pub fn end_portal_blocks(center: WorldPosition) -> Vec<(WorldPosition, u16)> {
+    let end_portal = default_state("end_portal");
+    (-1..=1)
+        .flat_map(|x| (-1..=1).map(move |z| (offset(&center, x, 0, z), end_portal)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{boundingbox::BoundingBox, vector3::Vector3};
+
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::{
+            tests::{pos, TestBlocks},
+            with_property,
+        },
+        BlockFace,
+    };
+
+    use super::{
+        build_nether_portal, completed_end_portal, find_nether_portal_shape, find_portal_site,
+        nether_portal_state, nether_portal_survives, portal_in, NetherPortalShape, PortalAxis,
+        PortalKind,
+    };
+
+    /// An obsidian frame around a 2x3 inside, along the x axis starting at x = 0
+    fn build_frame(world: &mut TestBlocks) {
+        for x in -1..=2 {
+            world.set(x, 0, 0, "obsidian");
+            world.set(x, 4, 0, "obsidian");
+        }
+        for y in 1..=3 {
+            world.set(-1, y, 0, "obsidian");
+            world.set(2, y, 0, "obsidian");
+        }
+    }
+
+    #[test]
+    fn finds_frames() {
+        let mut world = TestBlocks::new(15, 0);
+        build_frame(&mut world);
+        let shape = NetherPortalShape {
+            bottom_left: pos(0, 1, 0),
+            axis: PortalAxis::X,
+            width: 2,
+            height: 3,
+        };
+        assert_eq!(find_nether_portal_shape(&world, pos(1, 2, 0)), Some(shape));
+        assert_eq!(find_nether_portal_shape(&world, pos(0, 1, 0)), Some(shape));
+        assert_eq!(shape.blocks().len(), 6);
+
+        // Fire inside of the frame doesn't matter
+        world.set(0, 1, 0, "fire");
+        assert_eq!(find_nether_portal_shape(&world, pos(0, 1, 0)), Some(shape));
+    }
+
+    #[test]
+    fn rejects_broken_frames() {
+        let mut world = TestBlocks::new(15, 0);
+        build_frame(&mut world);
+        world.set(2, 2, 0, "stone");
+        assert_eq!(find_nether_portal_shape(&world, pos(0, 1, 0)), None);
+
+        let mut world = TestBlocks::new(15, 0);
+        build_frame(&mut world);
+        world.set(1, 3, 0, "dirt");
+        assert_eq!(find_nether_portal_shape(&world, pos(0, 1, 0)), None);
+
+        // Corners are not needed
+        let mut world = TestBlocks::new(15, 0);
+        build_frame(&mut world);
+        world.blocks.remove(&pos(-1, 0, 0));
+        world.blocks.remove(&pos(2, 4, 0));
+        assert!(find_nether_portal_shape(&world, pos(0, 1, 0)).is_some());
+    }
+
+    #[test]
+    fn portals_break_with_their_frame() {
+        let mut world = TestBlocks::new(15, 0);
+        build_frame(&mut world);
+        let state = nether_portal_state(PortalAxis::X);
+        for x in 0..2 {
+            for y in 1..=3 {
+                world.blocks.insert(pos(x, y, 0), state);
+            }
+        }
+        assert!(nether_portal_survives(&world, pos(0, 1, 0), state));
+        world.set(-1, 1, 0, "air");
+        assert!(!nether_portal_survives(&world, pos(0, 1, 0), state));
+        // Blocks in front of and behind portals don't matter
+        assert!(nether_portal_survives(&world, pos(1, 1, 0), state));
+    }
+
+    #[test]
+    fn new_portals_stand_on_the_ground() {
+        let mut world = TestBlocks::new(15, 0);
+        for x in -5..=5 {
+            for z in -5..=5 {
+                world.set(x, 9, z, "stone");
+            }
+        }
+        let site = find_portal_site(&world, pos(0, 30, 0), PortalAxis::X, 3, 0, 40);
+        assert_eq!(site, Some(pos(0, 10, 0)));
+
+        let blocks = build_nether_portal(pos(0, 10, 0), PortalAxis::X, false);
+        let portal = nether_portal_state(PortalAxis::X);
+        assert_eq!(
+            blocks.iter().filter(|(_, state)| *state == portal).count(),
+            6
+        );
+        assert_eq!(blocks.len(), 20);
+    }
+
+    #[test]
+    fn entities_in_portals() {
+        let mut world = TestBlocks::new(15, 0);
+        world
+            .blocks
+            .insert(pos(0, 1, 0), nether_portal_state(PortalAxis::Z));
+        let inside = BoundingBox::new(Vector3::new(0.2, 1.0, 0.2), Vector3::new(0.8, 2.8, 0.8));
+        assert_eq!(
+            portal_in(&world, &inside),
+            Some(PortalKind::Nether(PortalAxis::Z))
+        );
+        let next_to = BoundingBox::new(Vector3::new(1.0, 1.0, 0.2), Vector3::new(1.6, 2.8, 0.8));
+        assert_eq!(portal_in(&world, &next_to), None);
+
+        world.set(1, 1, 0, "end_portal");
+        let between = BoundingBox::new(Vector3::new(0.7, 1.0, 0.2), Vector3::new(1.3, 2.8, 0.8));
+        assert_eq!(portal_in(&world, &between), Some(PortalKind::End));
+    }
+
+    fn place_frame(world: &mut TestBlocks, x: i32, z: i32, facing: BlockFace) {
+        let frame = get_block("end_portal_frame").unwrap();
+        let state = with_property(frame, frame.default_state_id, "facing", facing.name());
+        let state = with_property(frame, state, "eye", "true");
+        world.blocks.insert(pos(x, 0, z), state);
+    }
+
+    #[test]
+    fn eyes_complete_end_portals() {
+        let mut world = TestBlocks::new(15, 0);
+        for i in -1..=1 {
+            place_frame(&mut world, i, -2, BlockFace::South);
+            place_frame(&mut world, i, 2, BlockFace::North);
+            place_frame(&mut world, -2, i, BlockFace::East);
+        }
+        place_frame(&mut world, 2, -1, BlockFace::West);
+        place_frame(&mut world, 2, 0, BlockFace::West);
+        assert_eq!(completed_end_portal(&world, pos(2, 0, 0)), None);
+
+        place_frame(&mut world, 2, 1, BlockFace::West);
+        assert_eq!(
+            completed_end_portal(&world, pos(2, 0, 1)),
+            Some(pos(0, 0, 0))
+        );
+    }
+}
@@ -0,0 +1,695 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::LazyLock,
+};
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use rand::Rng;
+
+use crate::{
+    chunk::{light::LightType, ChunkData},
+    coordinates::ChunkRelativeBlockCoordinates,
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+
+use super::block_registry::{get_block, get_block_and_state_by_state_id, Block, BLOCKS};
+
+/// What happens to a block when it is randomly ticked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomTickBehavior {
+    /// Wheat, carrots, potatoes and beetroots grow one age in bright light, faster on wet farmland
+    Crop { max_age: u8 },
+    /// Grass blocks and mycelium spread onto dirt nearby and turn into dirt when covered
+    SpreadingDirt,
+    /// Ice melts in bright block light
+    Ice,
+    /// Leaves too far away from logs decay
+    Leaves,
+    /// Fire ages, burns out and spreads to flammable blocks
+    Fire,
+}
+
+/// The random tick behavior of every block state, indexed by the state id
+static RANDOM_TICKS: LazyLock<Vec<Option<RandomTickBehavior>>> = LazyLock::new(|| {
+    let mut behaviors = Vec::new();
+    for block in &BLOCKS.blocks {
+        let Some(behavior) = block_behavior(block) else {
+            continue;
+        };
+        for state in &block.states {
+            let id = usize::from(state.id);
+            if behaviors.len() <= id {
+                behaviors.resize(id + 1, None);
+            }
+            behaviors[id] = Some(behavior);
+        }
+    }
+    behaviors
+});
+
+fn block_behavior(block: &Block) -> Option<RandomTickBehavior> {
+    match block.name.as_str() {
+        "wheat" | "carrots" | "potatoes" => Some(RandomTickBehavior::Crop { max_age: 7 }),
+        "beetroots" => Some(RandomTickBehavior::Crop { max_age: 3 }),
+        "grass_block" | "mycelium" => Some(RandomTickBehavior::SpreadingDirt),
+        "ice" => Some(RandomTickBehavior::Ice),
+        "fire" => Some(RandomTickBehavior::Fire),
+        name if name.ends_with("_leaves") => Some(RandomTickBehavior::Leaves),
+        _ => None,
+    }
+}
+
+/// What happens to blocks of the state when they are randomly ticked, `None` if nothing happens
+pub fn random_tick_behavior(state_id: u16) -> Option<RandomTickBehavior> {
+    RANDOM_TICKS.get(usize::from(state_id)).copied().flatten()
+}
+
+/// The blocks around a randomly ticked block. Blocks in chunks which are not loaded are `None`
+pub trait BlockAccess {
+    fn block_state(&self, position: &WorldPosition) -> Option<u16>;
+
+    fn light(&self, kind: LightType, position: &WorldPosition) -> Option<u8>;
+
+    /// The brightest of the sky and block light, without the darkening at night
+    fn raw_brightness(&self, position: &WorldPosition) -> u8 {
+        let sky = self.light(LightType::Sky, position).unwrap_or(0);
+        let block = self.light(LightType::Block, position).unwrap_or(0);
+        sky.max(block)
+    }
+}
+
+/// Picks `speed` random blocks in every section of the chunk which do something when randomly ticked
+pub fn pick_random_ticks(
+    chunk: &ChunkData,
+    speed: u32,
+    rng: &mut impl Rng,
+) -> Vec<(WorldPosition, u16)> {
+    let mut picked = Vec::new();
+    for section in 0..WORLD_HEIGHT / 16 {
+        for _ in 0..speed {
+            let x = rng.gen_range(0..16);
+            let y = (section * 16) as i32 + rng.gen_range(0..16) + i32::from(WORLD_LOWEST_Y);
+            let z = rng.gen_range(0..16);
+            let relative = ChunkRelativeBlockCoordinates::from(Vector3::new(x, y, z));
+            let Some(state_id) = chunk.blocks.get_block(relative) else {
+                continue;
+            };
+            if random_tick_behavior(state_id).is_some() {
+                let position = WorldPosition(Vector3::new(
+                    chunk.position.x * 16 + x,
+                    y,
+                    chunk.position.z * 16 + z,
+                ));
+                picked.push((position, state_id));
+            }
+        }
+    }
+    picked
+}
+
+/// Randomly ticks the block, returns the blocks which change and their new states
+pub fn random_tick(
+    world: &impl BlockAccess,
+    position: WorldPosition,
+    state_id: u16,
+    ultra_warm: bool,
+    rng: &mut impl Rng,
+) -> Vec<(WorldPosition, u16)> {
+    let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+        return Vec::new();
+    };
+    let mut tick = RandomTick {
+        world,
+        block,
+        position,
+        state_id,
+        rng,
+        changes: Vec::new(),
+    };
+    match random_tick_behavior(state_id) {
+        Some(RandomTickBehavior::Crop { max_age }) => tick.crop(max_age),
+        Some(RandomTickBehavior::SpreadingDirt) => tick.spreading_dirt(),
+        Some(RandomTickBehavior::Ice) => tick.ice(ultra_warm),
+        Some(RandomTickBehavior::Leaves) => tick.leaves(),
+        Some(RandomTickBehavior::Fire) => tick.fire(ultra_warm),
+        None => {}
+    }
+    tick.changes
+}
+
+struct RandomTick<'a, W, R> {
+    world: &'a W,
+    block: &'a Block,
+    position: WorldPosition,
+    state_id: u16,
+    rng: &'a mut R,
+    changes: Vec<(WorldPosition, u16)>,
+}
+
+pub(crate) const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+pub(crate) fn offset(position: &WorldPosition, x: i32, y: i32, z: i32) -> WorldPosition {
+    WorldPosition(position.0 + Vector3::new(x, y, z))
+}
+
+pub(crate) fn property<'a>(block: &'a Block, state_id: u16, name: &str) -> Option<&'a str> {
+    block
+        .state_properties(state_id)
+        .into_iter()
+        .find_map(|(property, value)| (property == name).then_some(value))
+}
+
+pub(crate) fn with_property(block: &Block, state_id: u16, name: &str, value: &str) -> u16 {
+    let mut properties: HashMap<String, String> = block
+        .state_properties(state_id)
+        .into_iter()
+        .map(|(property, value)| (property.to_string(), value.to_string()))
+        .collect();
+    properties.insert(name.to_string(), value.to_string());
+    block.state_with_properties(&properties)
+}
+
+pub(crate) fn default_state(name: &str) -> u16 {
+    get_block(name).map_or(0, |block| block.default_state_id)
+}
+
+fn is_log(block: &Block) -> bool {
+    ["_log", "_wood", "_stem", "_hyphae"]
+        .iter()
+        .any(|suffix| block.name.ends_with(suffix))
+}
+
+/// The chances of a block to catch fire from fire next to it and to burn away, like vanilla's `FireBlock.bootStrap`
+fn flammability(state_id: u16) -> (u32, u32) {
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return (0, 0);
+    };
+    if !state.burnable {
+        return (0, 0);
+    }
+    let name = block.name.as_str();
+    if name.ends_with("_leaves") || name.ends_with("_wool") || name.ends_with("_carpet") {
+        (30, 60)
+    } else if is_log(block) {
+        (5, 5)
+    } else if name.ends_with("grass") || name.ends_with("fern") || name.ends_with("vine") {
+        (60, 100)
+    } else {
+        (5, 20)
+    }
+}
+
+/// Whether the top of the block is a full face, fire can burn on it forever
+fn is_full_block(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id)
+        .is_some_and(|(_, state)| state.collision_shapes == [0])
+}
+
+impl<W: BlockAccess, R: Rng> RandomTick<'_, W, R> {
+    fn block_at(&self, position: &WorldPosition) -> Option<&'static Block> {
+        let state_id = self.world.block_state(position)?;
+        get_block_and_state_by_state_id(state_id).map(|(block, _)| block)
+    }
+
+    fn is_block(&self, position: &WorldPosition, name: &str) -> bool {
+        self.block_at(position)
+            .is_some_and(|block| block.name == name)
+    }
+
+    fn set(&mut self, position: WorldPosition, state_id: u16) {
+        self.changes.push((position, state_id));
+    }
+
+    /// Vanilla's `CropBlock.randomTick`
+    fn crop(&mut self, max_age: u8) {
+        let Some(age) = property(self.block, self.state_id, "age")
+            .and_then(|age| age.parse::<u8>().ok())
+            .filter(|age| *age < max_age)
+        else {
+            return;
+        };
+        // Beetroots grow slower
+        if max_age == 3 && self.rng.gen_range(0..3) == 0 {
+            return;
+        }
+        if self.world.raw_brightness(&self.position) < 9 {
+            return;
+        }
+        let chance = (25.0 / self.growth_speed()) as u32 + 1;
+        if self.rng.gen_range(0..chance) == 0 {
+            let state = with_property(self.block, self.state_id, "age", &(age + 1).to_string());
+            self.set(self.position, state);
+        }
+    }
+
+    /// Farmland below and around speeds crops up, crops of the same kind next to each other slow them down
+    fn growth_speed(&self) -> f32 {
+        let mut speed = 1.0;
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let below = offset(&self.position, x, -1, z);
+                let Some(state_id) = self.world.block_state(&below) else {
+                    continue;
+                };
+                let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+                    continue;
+                };
+                if block.name != "farmland" {
+                    continue;
+                }
+                let mut farmland = if property(block, state_id, "moisture") == Some("0") {
+                    1.0
+                } else {
+                    3.0
+                };
+                if x != 0 || z != 0 {
+                    farmland /= 4.0;
+                }
+                speed += farmland;
+            }
+        }
+
+        let same = |x, z| self.is_block(&offset(&self.position, x, 0, z), &self.block.name);
+        let along_x = same(-1, 0) || same(1, 0);
+        let along_z = same(0, -1) || same(0, 1);
+        let diagonal = same(-1, -1) || same(1, -1) || same(1, 1) || same(-1, 1);
+        if (along_x && along_z) || diagonal {
+            speed /= 2.0;
+        }
+        speed
+    }
+
+    /// Whether grass can stay grass below the block above it
+    fn can_be_grass(&self, position: &WorldPosition) -> bool {
+        let above = offset(position, 0, 1, 0);
+        let Some(state_id) = self.world.block_state(&above) else {
+            return true;
+        };
+        let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+            return true;
+        };
+        match block.name.as_str() {
+            "snow" => property(block, state_id, "layers") == Some("1"),
+            "water" | "lava" => false,
+            _ => state.opacity.unwrap_or(0) < 15,
+        }
+    }
+
+    /// Vanilla's `SpreadingSnowyDirtBlock.randomTick`
+    fn spreading_dirt(&mut self) {
+        if !self.can_be_grass(&self.position) {
+            self.set(self.position, default_state("dirt"));
+            return;
+        }
+        if self.world.raw_brightness(&offset(&self.position, 0, 1, 0)) < 9 {
+            return;
+        }
+        for _ in 0..4 {
+            let target = offset(
+                &self.position,
+                self.rng.gen_range(-1..=1),
+                self.rng.gen_range(-3..=1),
+                self.rng.gen_range(-1..=1),
+            );
+            if !self.is_block(&target, "dirt") || !self.can_be_grass(&target) {
+                continue;
+            }
+            let above = offset(&target, 0, 1, 0);
+            if self.is_block(&above, "water") {
+                continue;
+            }
+            let snowy = self.is_block(&above, "snow") || self.is_block(&above, "snow_block");
+            let state = with_property(
+                self.block,
+                self.block.default_state_id,
+                "snowy",
+                if snowy { "true" } else { "false" },
+            );
+            self.set(target, state);
+        }
+    }
+
+    /// Vanilla's `IceBlock.randomTick`, ice melts to water, in the nether it evaporates
+    fn ice(&mut self, ultra_warm: bool) {
+        let light = self
+            .world
+            .light(LightType::Block, &self.position)
+            .unwrap_or(0);
+        if light <= 11 {
+            return;
+        }
+        let melted = if ultra_warm {
+            0
+        } else {
+            default_state("water")
+        };
+        self.set(self.position, melted);
+    }
+
+    /// Leaves which are not connected to a log through at most 6 leaves decay. Leaves only learn
+    /// their distance here, generated leaves all start out at the furthest distance
+    fn leaves(&mut self) {
+        if property(self.block, self.state_id, "persistent") != Some("false")
+            || property(self.block, self.state_id, "distance") != Some("7")
+        {
+            return;
+        }
+        match self.distance_to_log() {
+            Some(distance) => {
+                let state =
+                    with_property(self.block, self.state_id, "distance", &distance.to_string());
+                self.set(self.position, state);
+            }
+            None => self.set(self.position, 0),
+        }
+    }
+
+    fn distance_to_log(&self) -> Option<u8> {
+        let mut visited = HashSet::from([self.position]);
+        let mut queue = VecDeque::from([(self.position, 0)]);
+        while let Some((position, distance)) = queue.pop_front() {
+            for (x, y, z) in NEIGHBOURS {
+                let next = offset(&position, x, y, z);
+                if !visited.insert(next) {
+                    continue;
+                }
+                let Some(block) = self.block_at(&next) else {
+                    continue;
+                };
+                if is_log(block) {
+                    return Some(distance + 1);
+                }
+                if block.name.ends_with("_leaves") && distance + 1 < 6 {
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// Vanilla's `FireBlock.tick`, without rain putting it out
+    fn fire(&mut self, ultra_warm: bool) {
+        let below = offset(&self.position, 0, -1, 0);
+        let below_state = self.world.block_state(&below).unwrap_or(0);
+        let flammable_neighbour = NEIGHBOURS.iter().any(|(x, y, z)| {
+            let neighbour = offset(&self.position, *x, *y, *z);
+            self.world
+                .block_state(&neighbour)
+                .is_some_and(|state| flammability(state).0 > 0)
+        });
+        if !is_full_block(below_state) && !flammable_neighbour {
+            self.set(self.position, 0);
+            return;
+        }
+
+        let infiniburn = get_block_and_state_by_state_id(below_state).is_some_and(|(block, _)| {
+            matches!(block.name.as_str(), "netherrack" | "magma_block")
+                || (block.name == "soul_sand" && ultra_warm)
+        });
+        let age: u32 = property(self.block, self.state_id, "age")
+            .and_then(|age| age.parse().ok())
+            .unwrap_or(0);
+        let new_age = (age + self.rng.gen_range(0..3) / 2).min(15);
+        if !infiniburn {
+            if !flammable_neighbour {
+                if !is_full_block(below_state) || age > 3 {
+                    self.set(self.position, 0);
+                }
+                return;
+            }
+            if age == 15 && self.rng.gen_range(0..4) == 0 && flammability(below_state).0 == 0 {
+                self.set(self.position, 0);
+                return;
+            }
+        }
+        if new_age != age {
+            let state = with_property(self.block, self.state_id, "age", &new_age.to_string());
+            self.set(self.position, state);
+        }
+
+        for (x, y, z) in NEIGHBOURS {
+            let chance = if y == 0 { 300 } else { 250 };
+            self.burn_out(offset(&self.position, x, y, z), chance, new_age);
+        }
+
+        // Fire jumps to air next to flammable blocks, more easily upwards
+        for x in -1..=1 {
+            for z in -1..=1 {
+                for y in -1..=4 {
+                    if x == 0 && y == 0 && z == 0 {
+                        continue;
+                    }
+                    let target = offset(&self.position, x, y, z);
+                    if self.world.block_state(&target) != Some(0) {
+                        continue;
+                    }
+                    let ignite = NEIGHBOURS
+                        .iter()
+                        .filter_map(|(x, y, z)| {
+                            self.world.block_state(&offset(&target, *x, *y, *z))
+                        })
+                        .map(|state| flammability(state).0)
+                        .max()
+                        .unwrap_or(0);
+                    if ignite == 0 {
+                        continue;
+                    }
+                    let range = if y > 1 {
+                        100 + (y as u32 - 1) * 100
+                    } else {
+                        100
+                    };
+                    // Normal difficulty
+                    let odds = (ignite + 40 + 2 * 7) / (new_age + 30);
+                    if odds > 0 && self.rng.gen_range(0..range) <= odds {
+                        self.ignite(target, new_age);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Vanilla's `FireBlock.checkBurnOut`, the block either catches fire or burns away
+    fn burn_out(&mut self, position: WorldPosition, chance: u32, age: u32) {
+        let Some(state_id) = self.world.block_state(&position) else {
+            return;
+        };
+        if self.rng.gen_range(0..chance) >= flammability(state_id).1 {
+            return;
+        }
+        if self.rng.gen_range(0..age + 10) < 5 {
+            self.ignite(position, age);
+        } else {
+            self.set(position, 0);
+        }
+    }
+
+    fn ignite(&mut self, position: WorldPosition, age: u32) {
+        let age = (age + self.rng.gen_range(0..5) / 4).min(15);
+        let state = with_property(
+            self.block,
+            self.block.default_state_id,
+            "age",
+            &age.to_string(),
+        );
+        self.set(position, state);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{block::block_registry::get_block, chunk::light::LightType};
+
+    use super::{
+        random_tick, random_tick_behavior, with_property, BlockAccess, RandomTickBehavior,
+    };
+
+    /// Blocks which are not set are air, every block has the same light
+    pub(crate) struct TestBlocks {
+        pub(crate) blocks: HashMap<WorldPosition, u16>,
+        sky_light: u8,
+        block_light: u8,
+    }
+
+    impl TestBlocks {
+        pub(crate) fn new(sky_light: u8, block_light: u8) -> Self {
+            Self {
+                blocks: HashMap::new(),
+                sky_light,
+                block_light,
+            }
+        }
+
+        pub(crate) fn set(&mut self, x: i32, y: i32, z: i32, name: &str) -> u16 {
+            let state = get_block(name).unwrap().default_state_id;
+            self.blocks.insert(pos(x, y, z), state);
+            state
+        }
+    }
+
+    impl BlockAccess for TestBlocks {
+        fn block_state(&self, position: &WorldPosition) -> Option<u16> {
+            Some(self.blocks.get(position).copied().unwrap_or(0))
+        }
+
+        fn light(&self, kind: LightType, _position: &WorldPosition) -> Option<u8> {
+            Some(match kind {
+                LightType::Sky => self.sky_light,
+                LightType::Block => self.block_light,
+            })
+        }
+    }
+
+    pub(crate) fn pos(x: i32, y: i32, z: i32) -> WorldPosition {
+        WorldPosition(Vector3::new(x, y, z))
+    }
+
+    /// Ticks the block until it changes, returns the changes
+    fn tick_until_changed(
+        world: &TestBlocks,
+        position: WorldPosition,
+    ) -> Vec<(WorldPosition, u16)> {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10_000 {
+            let changes = random_tick(world, position, world.blocks[&position], false, &mut rng);
+            if !changes.is_empty() {
+                return changes;
+            }
+        }
+        Vec::new()
+    }
+
+    #[test]
+    fn registry() {
+        let wheat = get_block("wheat").unwrap();
+        assert_eq!(
+            random_tick_behavior(wheat.default_state_id),
+            Some(RandomTickBehavior::Crop { max_age: 7 })
+        );
+        let leaves = get_block("birch_leaves").unwrap();
+        assert!(leaves
+            .states
+            .iter()
+            .all(|state| random_tick_behavior(state.id) == Some(RandomTickBehavior::Leaves)));
+        assert_eq!(random_tick_behavior(0), None);
+        assert_eq!(
+            random_tick_behavior(get_block("stone").unwrap().default_state_id),
+            None
+        );
+    }
+
+    #[test]
+    fn crops_grow_in_light() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, -1, 0, "farmland");
+        let wheat = world.set(0, 0, 0, "wheat");
+        let block = get_block("wheat").unwrap();
+        assert_eq!(
+            tick_until_changed(&world, pos(0, 0, 0)),
+            vec![(pos(0, 0, 0), with_property(block, wheat, "age", "1"))]
+        );
+
+        let mut dark = TestBlocks::new(0, 0);
+        dark.set(0, -1, 0, "farmland");
+        dark.set(0, 0, 0, "wheat");
+        assert!(tick_until_changed(&dark, pos(0, 0, 0)).is_empty());
+
+        let ripe = with_property(block, wheat, "age", "7");
+        world.blocks.insert(pos(0, 0, 0), ripe);
+        assert!(tick_until_changed(&world, pos(0, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn grass_spreads_and_dies() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, 0, 0, "grass_block");
+        world.set(1, 0, 0, "dirt");
+        let changes = tick_until_changed(&world, pos(0, 0, 0));
+        assert!(!changes.is_empty());
+        assert!(changes
+            .iter()
+            .all(|(position, _)| *position == pos(1, 0, 0)));
+
+        world.set(0, 1, 0, "stone");
+        let dirt = get_block("dirt").unwrap().default_state_id;
+        assert_eq!(
+            tick_until_changed(&world, pos(0, 0, 0)),
+            vec![(pos(0, 0, 0), dirt)]
+        );
+    }
+
+    #[test]
+    fn ice_melts_next_to_light() {
+        let mut world = TestBlocks::new(15, 11);
+        world.set(0, 0, 0, "ice");
+        assert!(tick_until_changed(&world, pos(0, 0, 0)).is_empty());
+
+        world.block_light = 12;
+        let water = get_block("water").unwrap().default_state_id;
+        assert_eq!(
+            tick_until_changed(&world, pos(0, 0, 0)),
+            vec![(pos(0, 0, 0), water)]
+        );
+    }
+
+    #[test]
+    fn leaves_decay_away_from_logs() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, 0, 0, "oak_log");
+        for x in 1..=8 {
+            world.set(x, 0, 0, "oak_leaves");
+        }
+        let block = get_block("oak_leaves").unwrap();
+        let leaves = block.default_state_id;
+        assert_eq!(
+            tick_until_changed(&world, pos(3, 0, 0)),
+            vec![(pos(3, 0, 0), with_property(block, leaves, "distance", "3"))]
+        );
+        assert_eq!(
+            tick_until_changed(&world, pos(8, 0, 0)),
+            vec![(pos(8, 0, 0), 0)]
+        );
+
+        let persistent = with_property(block, leaves, "persistent", "true");
+        world.blocks.insert(pos(8, 0, 0), persistent);
+        assert!(tick_until_changed(&world, pos(8, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn fire_burns_out_without_fuel() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, 0, 0, "fire");
+        assert_eq!(
+            tick_until_changed(&world, pos(0, 0, 0)),
+            vec![(pos(0, 0, 0), 0)]
+        );
+    }
+
+    #[test]
+    fn fire_burns_planks() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, -1, 0, "stone");
+        world.set(0, 0, 0, "fire");
+        world.set(1, 0, 0, "oak_planks");
+        let mut rng = StdRng::seed_from_u64(1);
+        let burnt = (0..10_000).any(|_| {
+            let state = world.blocks[&pos(0, 0, 0)];
+            random_tick(&world, pos(0, 0, 0), state, false, &mut rng)
+                .iter()
+                .any(|(position, _)| *position == pos(1, 0, 0))
+        });
+        assert!(burnt);
+    }
+}
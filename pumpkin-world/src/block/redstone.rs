@@ -0,0 +1,257 @@
+use pumpkin_core::math::position::WorldPosition;
+
+use super::{
+    block_registry::{get_block_and_state_by_state_id, Block},
+    random_tick::{property, BlockAccess},
+    BlockFace,
+};
+
+/// The full signal strength, signals get weaker along redstone wire
+pub const MAX_SIGNAL: u8 = 15;
+
+/// The block next to the position in the direction
+pub fn relative(position: &WorldPosition, face: BlockFace) -> WorldPosition {
+    WorldPosition(position.0 + face.to_offset())
+}
+
+fn face_property(block: &Block, state_id: u16, name: &str) -> Option<BlockFace> {
+    BlockFace::from_name(property(block, state_id, name)?)
+}
+
+fn is_on(block: &Block, state_id: u16, name: &str) -> bool {
+    property(block, state_id, name) == Some("true")
+}
+
+fn level(block: &Block, state_id: u16) -> u8 {
+    property(block, state_id, "power")
+        .and_then(|power| power.parse().ok())
+        .unwrap_or(0)
+}
+
+/// The side of the block levers and buttons are attached to
+fn attached_face(block: &Block, state_id: u16) -> Option<BlockFace> {
+    match property(block, state_id, "face")? {
+        "floor" => Some(BlockFace::Bottom),
+        "ceiling" => Some(BlockFace::Top),
+        _ => Some(face_property(block, state_id, "facing")?.opposite()),
+    }
+}
+
+/// The signal the block sends out towards the block next to it in the direction, from 0 to 15.
+/// Like vanilla's `getSignal`, but with the direction pointing away from the signal source
+pub fn signal(state_id: u16, towards: BlockFace) -> u8 {
+    let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+        return 0;
+    };
+    let name = block.name.as_str();
+    let on = |condition: bool| if condition { MAX_SIGNAL } else { 0 };
+    match name {
+        "redstone_block" => MAX_SIGNAL,
+        "lever" | "detector_rail" | "tripwire_hook" => on(is_on(block, state_id, "powered")),
+        "redstone_torch" => on(is_on(block, state_id, "lit") && towards != BlockFace::Bottom),
+        "redstone_wall_torch" => on(is_on(block, state_id, "lit")
+            && face_property(block, state_id, "facing")
+                .is_some_and(|facing| towards != facing.opposite())),
+        // Wire powers the blocks it runs over and next to, but not the one above
+        "redstone_wire" if towards != BlockFace::Top => level(block, state_id),
+        // Repeaters and observers face their input, they power the block behind them
+        "repeater" | "observer" => on(is_on(block, state_id, "powered")
+            && face_property(block, state_id, "facing")
+                .is_some_and(|facing| towards == facing.opposite())),
+        "daylight_detector"
+        | "target"
+        | "light_weighted_pressure_plate"
+        | "heavy_weighted_pressure_plate" => level(block, state_id),
+        _ if name.ends_with("_button") || name.ends_with("_pressure_plate") => {
+            on(is_on(block, state_id, "powered"))
+        }
+        _ => 0,
+    }
+}
+
+/// The signal the block sends into the block in the direction, which passes it on to the blocks
+/// next to it if it is solid. Like vanilla's `getDirectSignal`
+pub fn direct_signal(state_id: u16, towards: BlockFace) -> u8 {
+    let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+        return 0;
+    };
+    let name = block.name.as_str();
+    let strong = match name {
+        "redstone_torch" | "redstone_wall_torch" => towards == BlockFace::Top,
+        "redstone_wire" | "repeater" | "observer" => true,
+        "lever" => attached_face(block, state_id) == Some(towards),
+        _ if name.ends_with("_button") => attached_face(block, state_id) == Some(towards),
+        _ if name.ends_with("_pressure_plate") => towards == BlockFace::Bottom,
+        _ => false,
+    };
+    if strong {
+        // Wire only powers the block it runs over this way
+        if name == "redstone_wire" && towards != BlockFace::Bottom {
+            return 0;
+        }
+        signal(state_id, towards)
+    } else {
+        0
+    }
+}
+
+/// Whether redstone passes through the block, solid blocks which are no redstone components
+fn is_conductor(state_id: u16) -> bool {
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return false;
+    };
+    state.collision_shapes == [0]
+        && !matches!(
+            block.name.as_str(),
+            "redstone_block" | "observer" | "target" | "piston" | "sticky_piston"
+        )
+}
+
+/// The signal the block at the position receives from the block next to it in the direction.
+/// Solid blocks pass on the direct signals they receive
+pub fn received_signal(world: &impl BlockAccess, position: &WorldPosition, from: BlockFace) -> u8 {
+    let neighbour = relative(position, from);
+    let Some(state_id) = world.block_state(&neighbour) else {
+        return 0;
+    };
+    let signal = signal(state_id, from.opposite());
+    if !is_conductor(state_id) {
+        return signal;
+    }
+    BlockFace::ALL
+        .into_iter()
+        .filter_map(|face| {
+            let state_id = world.block_state(&relative(&neighbour, face))?;
+            Some(direct_signal(state_id, face.opposite()))
+        })
+        .fold(signal, u8::max)
+}
+
+/// Whether the block at the position receives a signal from any side, like vanilla's
+/// `hasNeighborSignal` without the quasi-connectivity of pistons
+pub fn has_signal(world: &impl BlockAccess, position: &WorldPosition) -> bool {
+    BlockFace::ALL
+        .into_iter()
+        .any(|face| received_signal(world, position, face) > 0)
+}
+
+/// Whether a piston at the position facing the direction is powered. Like in vanilla, pistons are
+/// also powered by signals reaching the block above them
+pub fn has_neighbor_signal(
+    world: &impl BlockAccess,
+    position: &WorldPosition,
+    facing: BlockFace,
+) -> bool {
+    let above = relative(position, BlockFace::Top);
+    BlockFace::ALL
+        .into_iter()
+        .any(|face| face != facing && received_signal(world, position, face) > 0)
+        || BlockFace::ALL
+            .into_iter()
+            .any(|face| face != BlockFace::Bottom && received_signal(world, &above, face) > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::block::{
+        block_registry::get_block,
+        random_tick::tests::{pos, TestBlocks},
+        BlockFace,
+    };
+
+    use super::{has_neighbor_signal, has_signal, received_signal};
+
+    fn set_state(
+        world: &mut TestBlocks,
+        x: i32,
+        y: i32,
+        z: i32,
+        name: &str,
+        properties: &[(&str, &str)],
+    ) {
+        let block = get_block(name).unwrap();
+        let properties: HashMap<String, String> = properties
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        world
+            .blocks
+            .insert(pos(x, y, z), block.state_with_properties(&properties));
+    }
+
+    #[test]
+    fn redstone_block_powers_neighbours() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(1, 0, 0, "redstone_block");
+        assert_eq!(received_signal(&world, &pos(0, 0, 0), BlockFace::East), 15);
+        assert!(has_neighbor_signal(&world, &pos(0, 0, 0), BlockFace::North));
+        assert!(has_signal(&world, &pos(0, 0, 0)));
+        assert!(!has_signal(&world, &pos(0, 0, 2)));
+        // Pistons ignore signals from the block they push
+        assert!(!has_neighbor_signal(&world, &pos(0, 0, 0), BlockFace::East));
+        assert!(!has_neighbor_signal(
+            &world,
+            &pos(0, 0, 2),
+            BlockFace::North
+        ));
+    }
+
+    #[test]
+    fn levers_power_through_blocks() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(1, 0, 0, "stone");
+        set_state(
+            &mut world,
+            2,
+            0,
+            0,
+            "lever",
+            &[("face", "wall"), ("facing", "east"), ("powered", "false")],
+        );
+        assert!(!has_neighbor_signal(
+            &world,
+            &pos(0, 0, 0),
+            BlockFace::North
+        ));
+
+        set_state(
+            &mut world,
+            2,
+            0,
+            0,
+            "lever",
+            &[("face", "wall"), ("facing", "east"), ("powered", "true")],
+        );
+        assert!(has_neighbor_signal(&world, &pos(0, 0, 0), BlockFace::North));
+
+        // Levers only power the block they are attached to through it
+        set_state(
+            &mut world,
+            2,
+            0,
+            0,
+            "lever",
+            &[("face", "floor"), ("facing", "east"), ("powered", "true")],
+        );
+        assert!(!has_neighbor_signal(
+            &world,
+            &pos(0, 0, 0),
+            BlockFace::North
+        ));
+    }
+
+    #[test]
+    fn torches_and_quasi_connectivity() {
+        let mut world = TestBlocks::new(15, 0);
+        // A torch doesn't power the block it stands on
+        world.set(0, 1, 0, "redstone_torch");
+        assert_eq!(received_signal(&world, &pos(0, 0, 0), BlockFace::Top), 0);
+
+        // A redstone block two blocks above powers the piston through the block above it
+        world.blocks.clear();
+        world.set(0, 2, 0, "redstone_block");
+        assert!(has_neighbor_signal(&world, &pos(0, 0, 0), BlockFace::North));
+    }
+}
@@ -1,28 +1,32 @@
 use std::{
-    fs::OpenOptions,
-    io::{Read, Seek},
+    fs::{self, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use flate2::bufread::{GzDecoder, ZlibDecoder};
+use flate2::{
+    bufread::{GzDecoder, GzEncoder, ZlibDecoder, ZlibEncoder},
+    Compression as CompressionLevel,
+};
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_nbt::{compound::NbtCompound, Nbt};
 
 use crate::level::LevelFolder;
 
-use super::{ChunkData, ChunkReader, ChunkReadingError, CompressionError};
-
-#[derive(Clone)]
-pub struct AnvilChunkReader {}
-
-impl Default for AnvilChunkReader {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+use super::{
+    read_entities_nbt, write_entities_nbt, ChunkData, ChunkReadingError, ChunkStorage,
+    ChunkWritingError, CompressionError, PointOfInterest,
+};
 
-impl AnvilChunkReader {
-    pub fn new() -> Self {
-        Self {}
-    }
-}
+/// Region files are made of sectors, the first two hold the location and timestamp tables
+const SECTOR_SIZE: usize = 4096;
+/// The sector count of a location entry is a single byte, bigger chunks are stored in their own file
+const MAX_CHUNK_SECTORS: usize = 255;
+/// Set in the compression byte of chunks which are stored in a `.mcc` file next to the region
+const EXTERNAL_FLAG: u8 = 128;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Compression {
@@ -38,7 +42,28 @@ pub enum Compression {
     Custom,
 }
 
+impl From<pumpkin_config::chunk::Compression> for Compression {
+    fn from(value: pumpkin_config::chunk::Compression) -> Self {
+        match value {
+            pumpkin_config::chunk::Compression::GZip => Self::GZip,
+            pumpkin_config::chunk::Compression::ZLib => Self::ZLib,
+            pumpkin_config::chunk::Compression::None => Self::None,
+            pumpkin_config::chunk::Compression::LZ4 => Self::LZ4,
+        }
+    }
+}
+
 impl Compression {
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::GZip => 1,
+            Self::ZLib => 2,
+            Self::None => 3,
+            Self::LZ4 => 4,
+            Self::Custom => 127,
+        }
+    }
+
     pub fn from_byte(byte: u8) -> Option<Self> {
         match byte {
             1 => Some(Self::GZip),
@@ -82,81 +107,381 @@ impl Compression {
             Compression::Custom => todo!(),
         }
     }
-}
 
-impl ChunkReader for AnvilChunkReader {
-    fn read_chunk(
+    fn compress_data(
         &self,
+        uncompressed_data: &[u8],
+        level: u32,
+    ) -> Result<Vec<u8>, CompressionError> {
+        match self {
+            Compression::GZip => {
+                let mut encoder =
+                    GzEncoder::new(uncompressed_data, CompressionLevel::new(level.min(9)));
+                let mut chunk_data = Vec::new();
+                encoder
+                    .read_to_end(&mut chunk_data)
+                    .map_err(CompressionError::GZipError)?;
+                Ok(chunk_data)
+            }
+            Compression::ZLib => {
+                let mut encoder =
+                    ZlibEncoder::new(uncompressed_data, CompressionLevel::new(level.min(9)));
+                let mut chunk_data = Vec::new();
+                encoder
+                    .read_to_end(&mut chunk_data)
+                    .map_err(CompressionError::ZlibError)?;
+                Ok(chunk_data)
+            }
+            Compression::None => Ok(uncompressed_data.to_vec()),
+            Compression::LZ4 => {
+                let mut encoder = lz4::EncoderBuilder::new()
+                    .build(Vec::new())
+                    .map_err(CompressionError::LZ4Error)?;
+                encoder
+                    .write_all(uncompressed_data)
+                    .map_err(CompressionError::LZ4Error)?;
+                let (compressed_data, result) = encoder.finish();
+                result.map_err(CompressionError::LZ4Error)?;
+                Ok(compressed_data)
+            }
+            Compression::Custom => Err(CompressionError::UnknownCompression),
+        }
+    }
+}
+
+/// The region files in the folder with the given extension, along with the position of their regions
+pub(super) fn region_files(folder: &Path, extension: &str) -> Vec<(PathBuf, (i32, i32))> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let region = name
+                .to_str()?
+                .strip_prefix("r.")?
+                .strip_suffix(extension)?
+                .strip_suffix('.')?
+                .split_once('.')
+                .and_then(|(x, z)| Some((x.parse::<i32>().ok()?, z.parse::<i32>().ok()?)))?;
+            Some((entry.path(), region))
+        })
+        .collect()
+}
+
+/// The index of the chunk in the tables of its region
+pub(super) fn region_index(at: &Vector2<i32>) -> usize {
+    let modulus = |a: i32, b: i32| ((a % b) + b) % b;
+    (modulus(at.x, 32) + modulus(at.z, 32) * 32) as usize
+}
+
+/// The position of the chunk at the index of a region's tables
+pub(super) fn chunk_at_region_index(region: (i32, i32), index: usize) -> Vector2<i32> {
+    Vector2::new(
+        region.0 * 32 + (index % 32) as i32,
+        region.1 * 32 + (index / 32) as i32,
+    )
+}
+
+/// Stores chunks in region files, compressed like configured. Chunks bigger than a region allows
+/// are stored in their own `.mcc` file
+pub struct AnvilChunkStorage {
+    compression: Compression,
+    level: u32,
+    /// Chunks of the same region can't be written at the same time
+    write_lock: Mutex<()>,
+}
+
+impl Default for AnvilChunkStorage {
+    fn default() -> Self {
+        let config = &ADVANCED_CONFIG.chunk.compression;
+        Self::new(config.algorithm.into(), config.level)
+    }
+}
+
+impl AnvilChunkStorage {
+    pub fn new(compression: Compression, level: u32) -> Self {
+        Self {
+            compression,
+            level,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Reads the chunk's NBT out of a region file in the world's `entities` or `poi` folder,
+    /// returns None if there is none
+    fn read_extra_chunk_nbt(
         save_file: &LevelFolder,
-        at: &pumpkin_core::math::vector2::Vector2<i32>,
-    ) -> Result<super::ChunkData, ChunkReadingError> {
-        let region = (at.x >> 5, at.z >> 5);
+        folder: &str,
+        at: &Vector2<i32>,
+    ) -> Option<NbtCompound> {
+        let bytes = match read_region_chunk(&save_file.root_folder.join(folder), at) {
+            Ok(bytes) => bytes,
+            Err(ChunkReadingError::ChunkNotExist) => return None,
+            Err(err) => {
+                log::warn!("Failed to read {} of chunk {:?}: {}", folder, at, err);
+                return None;
+            }
+        };
+        Nbt::read(&mut &bytes[..])
+            .inspect_err(|err| log::warn!("Failed to parse {} of chunk {:?}: {}", folder, at, err))
+            .ok()
+            .map(|nbt| nbt.root_tag)
+    }
+
+    /// Writes the chunk's NBT into the region file of the folder, the region file is created if
+    /// there is none yet
+    fn write_region_chunk(
+        &self,
+        folder: &Path,
+        at: &Vector2<i32>,
+        chunk_data: &[u8],
+    ) -> Result<(), ChunkWritingError> {
+        let io_error = |err: std::io::Error| ChunkWritingError::IoError(err.kind());
+        let compressed = self
+            .compression
+            .compress_data(chunk_data, self.level)
+            .map_err(ChunkWritingError::Compression)?;
+
+        let _guard = self
+            .write_lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        fs::create_dir_all(folder).map_err(io_error)?;
 
+        // Length of the data including the compression byte, the compression byte and the data
+        let mut payload = Vec::with_capacity(compressed.len() + 5);
+        let external_path = external_chunk_path(folder, at);
+        if compressed.len() + 5 > MAX_CHUNK_SECTORS * SECTOR_SIZE {
+            fs::write(&external_path, &compressed).map_err(io_error)?;
+            payload.extend_from_slice(&1u32.to_be_bytes());
+            payload.push(self.compression.to_byte() | EXTERNAL_FLAG);
+        } else {
+            remove_if_exists(&external_path).map_err(io_error)?;
+            payload.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+            payload.push(self.compression.to_byte());
+            payload.extend_from_slice(&compressed);
+        }
+        let sectors = payload.len().div_ceil(SECTOR_SIZE);
+        payload.resize(sectors * SECTOR_SIZE, 0);
+
+        let region = (at.x >> 5, at.z >> 5);
         let mut region_file = OpenOptions::new()
             .read(true)
-            .open(
-                save_file
-                    .region_folder
-                    .join(format!("r.{}.{}.mca", region.0, region.1)),
-            )
-            .map_err(|err| match err.kind() {
-                std::io::ErrorKind::NotFound => ChunkReadingError::ChunkNotExist,
-                kind => ChunkReadingError::IoError(kind),
-            })?;
-
-        let mut location_table: [u8; 4096] = [0; 4096];
-        let mut timestamp_table: [u8; 4096] = [0; 4096];
-
-        // fill the location and timestamp tables
-        region_file
-            .read_exact(&mut location_table)
-            .map_err(|err| ChunkReadingError::IoError(err.kind()))?;
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(folder.join(format!("r.{}.{}.mca", region.0, region.1)))
+            .map_err(io_error)?;
+
+        let mut tables = [0u8; SECTOR_SIZE * 2];
+        if region_file.metadata().map_err(io_error)?.len() >= tables.len() as u64 {
+            region_file.read_exact(&mut tables).map_err(io_error)?;
+        }
+        let (location_table, timestamp_table) = tables.split_at_mut(SECTOR_SIZE);
+
+        let index = region_index(at);
+        let offset = Self::allocate_sectors(location_table, index, sectors);
         region_file
-            .read_exact(&mut timestamp_table)
-            .map_err(|err| ChunkReadingError::IoError(err.kind()))?;
-
-        let modulus = |a: i32, b: i32| ((a % b) + b) % b;
-        let chunk_x = modulus(at.x, 32) as u32;
-        let chunk_z = modulus(at.z, 32) as u32;
-        let table_entry = (chunk_x + chunk_z * 32) * 4;
-
-        let mut offset = vec![0u8];
-        offset.extend_from_slice(&location_table[table_entry as usize..table_entry as usize + 3]);
-        let offset = u32::from_be_bytes(offset.try_into().unwrap()) as u64 * 4096;
-        let size = location_table[table_entry as usize + 3] as usize * 4096;
-
-        if offset == 0 && size == 0 {
-            return Err(ChunkReadingError::ChunkNotExist);
-        }
-
-        // Read the file using the offset and size
-        let mut file_buf = {
-            region_file
-                .seek(std::io::SeekFrom::Start(offset))
-                .map_err(|_| ChunkReadingError::RegionIsInvalid)?;
-            let mut out = vec![0; size];
-            region_file
-                .read_exact(&mut out)
-                .map_err(|_| ChunkReadingError::RegionIsInvalid)?;
-            out
+            .seek(SeekFrom::Start((offset * SECTOR_SIZE) as u64))
+            .map_err(io_error)?;
+        region_file.write_all(&payload).map_err(io_error)?;
+
+        let offset = (offset as u32).to_be_bytes();
+        location_table[index * 4..index * 4 + 4].copy_from_slice(&[
+            offset[1],
+            offset[2],
+            offset[3],
+            sectors as u8,
+        ]);
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs() as u32);
+        timestamp_table[index * 4..index * 4 + 4].copy_from_slice(&timestamp.to_be_bytes());
+        region_file.rewind().map_err(io_error)?;
+        region_file.write_all(&tables).map_err(io_error)?;
+        Ok(())
+    }
+
+    /// Finds sectors for a chunk needing `needed` sectors. The chunk stays where it is if it still fits,
+    /// otherwise the first gap between the other chunks which is big enough is used or it's put at the end
+    fn allocate_sectors(location_table: &[u8], index: usize, needed: usize) -> usize {
+        let location = |entry: usize| {
+            let bytes = &location_table[entry * 4..entry * 4 + 4];
+            let offset = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize;
+            (offset, bytes[3] as usize)
         };
+        let (offset, count) = location(index);
+        if offset >= 2 && needed <= count {
+            return offset;
+        }
+
+        let mut used = vec![true, true];
+        for entry in (0..1024).filter(|entry| *entry != index) {
+            let (offset, count) = location(entry);
+            if offset < 2 || count == 0 {
+                continue;
+            }
+            if used.len() < offset + count {
+                used.resize(offset + count, false);
+            }
+            used[offset..offset + count].fill(true);
+        }
+
+        let mut start = 2;
+        for (sector, in_use) in used.iter().enumerate().skip(2) {
+            if *in_use {
+                start = sector + 1;
+            } else if sector + 1 - start >= needed {
+                return start;
+            }
+        }
+        start
+    }
+}
 
-        // TODO: check checksum to make sure chunk is not corrupted
-        let header: Vec<u8> = file_buf.drain(0..5).collect();
+/// Reads the decompressed NBT of a chunk out of the region files in the folder
+fn read_region_chunk(folder: &Path, at: &Vector2<i32>) -> Result<Vec<u8>, ChunkReadingError> {
+    let region = (at.x >> 5, at.z >> 5);
 
-        let compression = Compression::from_byte(header[4]).ok_or(
-            ChunkReadingError::Compression(CompressionError::UnknownCompression),
-        )?;
+    let mut region_file = OpenOptions::new()
+        .read(true)
+        .open(folder.join(format!("r.{}.{}.mca", region.0, region.1)))
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => ChunkReadingError::ChunkNotExist,
+            kind => ChunkReadingError::IoError(kind),
+        })?;
 
-        let size = u32::from_be_bytes(header[..4].try_into().unwrap());
+    let mut location_table: [u8; 4096] = [0; 4096];
+    let mut timestamp_table: [u8; 4096] = [0; 4096];
+
+    // fill the location and timestamp tables
+    region_file
+        .read_exact(&mut location_table)
+        .map_err(|err| ChunkReadingError::IoError(err.kind()))?;
+    region_file
+        .read_exact(&mut timestamp_table)
+        .map_err(|err| ChunkReadingError::IoError(err.kind()))?;
+
+    let table_entry = (region_index(at) * 4) as u32;
+
+    let mut offset = vec![0u8];
+    offset.extend_from_slice(&location_table[table_entry as usize..table_entry as usize + 3]);
+    let offset = u32::from_be_bytes(offset.try_into().unwrap()) as u64 * 4096;
+    let size = location_table[table_entry as usize + 3] as usize * 4096;
 
+    if offset == 0 && size == 0 {
+        return Err(ChunkReadingError::ChunkNotExist);
+    }
+
+    // Read the file using the offset and size
+    let mut file_buf = {
+        region_file
+            .seek(std::io::SeekFrom::Start(offset))
+            .map_err(|_| ChunkReadingError::RegionIsInvalid)?;
+        let mut out = vec![0; size];
+        region_file
+            .read_exact(&mut out)
+            .map_err(|_| ChunkReadingError::RegionIsInvalid)?;
+        out
+    };
+
+    // TODO: check checksum to make sure chunk is not corrupted
+    let header: Vec<u8> = file_buf.drain(0..5).collect();
+
+    let compression = Compression::from_byte(header[4] & !EXTERNAL_FLAG).ok_or(
+        ChunkReadingError::Compression(CompressionError::UnknownCompression),
+    )?;
+
+    let chunk_data = if header[4] & EXTERNAL_FLAG == 0 {
+        let size = u32::from_be_bytes(header[..4].try_into().unwrap());
         // size includes the compression scheme byte, so we need to subtract 1
-        let chunk_data = file_buf.drain(0..size as usize - 1).collect();
-        let decompressed_chunk = compression
-            .decompress_data(chunk_data)
-            .map_err(ChunkReadingError::Compression)?;
+        file_buf.drain(0..size as usize - 1).collect()
+    } else {
+        fs::read(external_chunk_path(folder, at))
+            .map_err(|err| ChunkReadingError::IoError(err.kind()))?
+    };
+    compression
+        .decompress_data(chunk_data)
+        .map_err(ChunkReadingError::Compression)
+}
+
+fn external_chunk_path(folder: &Path, at: &Vector2<i32>) -> std::path::PathBuf {
+    folder.join(format!("c.{}.{}.mcc", at.x, at.z))
+}
 
-        ChunkData::from_bytes(&decompressed_chunk, *at).map_err(ChunkReadingError::ParsingError)
+impl ChunkStorage for AnvilChunkStorage {
+    fn read_chunk_nbt(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+    ) -> Result<Vec<u8>, ChunkReadingError> {
+        read_region_chunk(&save_file.region_folder, at)
+    }
+
+    fn read_chunk(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+    ) -> Result<ChunkData, ChunkReadingError> {
+        let decompressed_chunk = self.read_chunk_nbt(save_file, at)?;
+        let mut chunk = ChunkData::from_bytes(&decompressed_chunk, *at)
+            .map_err(ChunkReadingError::ParsingError)?;
+        if let Some(entities) = Self::read_extra_chunk_nbt(save_file, "entities", at) {
+            chunk.entities = read_entities_nbt(&entities);
+        }
+        if let Some(poi) = Self::read_extra_chunk_nbt(save_file, "poi", at) {
+            chunk.points_of_interest = PointOfInterest::read_chunk_nbt(&poi);
+        }
+        Ok(chunk)
+    }
+
+    fn stored_chunks(&self, save_file: &LevelFolder) -> Vec<Vector2<i32>> {
+        let mut chunks = Vec::new();
+        for (path, region) in region_files(&save_file.region_folder, "mca") {
+            let mut location_table = [0u8; SECTOR_SIZE];
+            let read =
+                fs::File::open(&path).and_then(|mut file| file.read_exact(&mut location_table));
+            if let Err(err) = read {
+                log::warn!("Failed to read region file {:?}: {}", path, err);
+                continue;
+            }
+            chunks.extend(
+                location_table
+                    .chunks_exact(4)
+                    .enumerate()
+                    .filter(|(_, location)| location.iter().any(|byte| *byte != 0))
+                    .map(|(index, _)| chunk_at_region_index(region, index)),
+            );
+        }
+        chunks
+    }
+
+    fn write_chunk(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+        chunk_data: &[u8],
+    ) -> Result<(), ChunkWritingError> {
+        self.write_region_chunk(&save_file.region_folder, at, chunk_data)
+    }
+
+    fn write_entities(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+        entities: &[NbtCompound],
+    ) -> Result<(), ChunkWritingError> {
+        let nbt = Nbt::new(String::new(), write_entities_nbt(at, entities));
+        self.write_region_chunk(&save_file.root_folder.join("entities"), at, &nbt.write())
+    }
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
     }
 }
 
@@ -165,16 +490,43 @@ mod tests {
     use std::path::PathBuf;
 
     use pumpkin_core::math::vector2::Vector2;
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 
     use crate::{
-        chunk::{anvil::AnvilChunkReader, ChunkReader, ChunkReadingError},
+        chunk::{
+            anvil::{AnvilChunkStorage, Compression},
+            read_entities_nbt, ChunkReadingError, ChunkStorage,
+        },
         level::LevelFolder,
     };
 
+    fn test_folder(name: &str) -> LevelFolder {
+        let root_folder =
+            std::env::temp_dir().join(format!("pumpkin-anvil-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root_folder);
+        LevelFolder {
+            region_folder: root_folder.join("region"),
+            root_folder,
+        }
+    }
+
+    /// Data which doesn't compress well, so it takes up as many sectors as bytes it has
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
     #[test]
     fn not_existing() {
         let region_path = PathBuf::from("not_existing");
-        let result = AnvilChunkReader::new().read_chunk(
+        let result = AnvilChunkStorage::new(Compression::ZLib, 6).read_chunk(
             &LevelFolder {
                 root_folder: PathBuf::from(""),
                 region_folder: region_path,
@@ -183,4 +535,136 @@ mod tests {
         );
         assert!(matches!(result, Err(ChunkReadingError::ChunkNotExist)));
     }
+
+    #[test]
+    fn write_and_read_back() {
+        let folder = test_folder("roundtrip");
+        for compression in [
+            Compression::GZip,
+            Compression::ZLib,
+            Compression::None,
+            Compression::LZ4,
+        ] {
+            let storage = AnvilChunkStorage::new(compression, 6);
+            let data = format!("chunk data {compression:?}").into_bytes();
+            storage
+                .write_chunk(&folder, &Vector2::new(-1, 33), &data)
+                .unwrap();
+            assert_eq!(
+                storage
+                    .read_chunk_nbt(&folder, &Vector2::new(-1, 33))
+                    .unwrap(),
+                data
+            );
+        }
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+
+    #[test]
+    fn grown_chunks_move() {
+        let folder = test_folder("grown");
+        let storage = AnvilChunkStorage::new(Compression::None, 0);
+        let small = noise(100);
+        let large = noise(3 * 4096);
+        storage
+            .write_chunk(&folder, &Vector2::new(0, 0), &small)
+            .unwrap();
+        storage
+            .write_chunk(&folder, &Vector2::new(1, 0), &small)
+            .unwrap();
+        // Doesn't fit into its single sector anymore and must not overwrite the next chunk
+        storage
+            .write_chunk(&folder, &Vector2::new(0, 0), &large)
+            .unwrap();
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(0, 0))
+                .unwrap(),
+            large
+        );
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(1, 0))
+                .unwrap(),
+            small
+        );
+        // The freed sector is reused
+        storage
+            .write_chunk(&folder, &Vector2::new(2, 0), &small)
+            .unwrap();
+        let region_len = std::fs::metadata(folder.region_folder.join("r.0.0.mca"))
+            .unwrap()
+            .len();
+        assert_eq!(region_len, 8 * 4096);
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+
+    #[test]
+    fn oversized_chunks_are_external() {
+        let folder = test_folder("oversized");
+        let storage = AnvilChunkStorage::new(Compression::None, 0);
+        let huge = noise(256 * 4096);
+        storage
+            .write_chunk(&folder, &Vector2::new(5, 7), &huge)
+            .unwrap();
+        let external = folder.region_folder.join("c.5.7.mcc");
+        assert!(external.exists());
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(5, 7))
+                .unwrap(),
+            huge
+        );
+
+        // Once it fits again the external file goes away
+        storage
+            .write_chunk(&folder, &Vector2::new(5, 7), &[1, 2, 3])
+            .unwrap();
+        assert!(!external.exists());
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(5, 7))
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+
+    #[test]
+    fn stored_chunks() {
+        let folder = test_folder("stored");
+        let storage = AnvilChunkStorage::new(Compression::None, 0);
+        for at in [Vector2::new(0, 0), Vector2::new(-1, 40)] {
+            storage.write_chunk(&folder, &at, &[0]).unwrap();
+        }
+
+        let mut chunks = storage.stored_chunks(&folder);
+        chunks.sort_by_key(|chunk| (chunk.x, chunk.z));
+        assert_eq!(chunks, vec![Vector2::new(-1, 40), Vector2::new(0, 0)]);
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+
+    #[test]
+    fn entities_are_stored_apart() {
+        let folder = test_folder("entities");
+        let storage = AnvilChunkStorage::new(Compression::ZLib, 6);
+        let at = Vector2::new(2, -9);
+        let mut entity = NbtCompound::new();
+        entity.put(
+            "id".to_string(),
+            NbtTag::String("minecraft:item".to_string()),
+        );
+        storage
+            .write_entities(&folder, &at, &[entity.clone()])
+            .unwrap();
+
+        let read = AnvilChunkStorage::read_extra_chunk_nbt(&folder, "entities", &at).unwrap();
+        assert_eq!(read_entities_nbt(&read), vec![entity]);
+        // The chunk itself is not there
+        assert!(matches!(
+            storage.read_chunk_nbt(&folder, &at),
+            Err(ChunkReadingError::ChunkNotExist)
+        ));
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
 }
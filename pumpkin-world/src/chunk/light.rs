@@ -0,0 +1,547 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::LazyLock,
+};
+
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2};
+
+use crate::{block::block_registry::BLOCKS, WORLD_HEIGHT, WORLD_LOWEST_Y};
+
+use super::{ChunkData, ChunkLight, CHUNK_AREA, LIGHT_SECTION_COUNT, LIGHT_SECTION_SIZE};
+
+/// Opacity and luminance of every block state, indexed by the state id
+static STATE_LIGHT: LazyLock<Vec<(u8, u8)>> = LazyLock::new(|| {
+    let mut light = Vec::new();
+    for state in BLOCKS.blocks.iter().flat_map(|block| &block.states) {
+        let id = usize::from(state.id);
+        if light.len() <= id {
+            light.resize(id + 1, (0, 0));
+        }
+        light[id] = (
+            state.opacity.unwrap_or(0).min(15) as u8,
+            state.luminance.min(15),
+        );
+    }
+    light
+});
+
+fn opacity(state_id: u16) -> u8 {
+    STATE_LIGHT
+        .get(usize::from(state_id))
+        .map_or(0, |light| light.0)
+}
+
+fn luminance(state_id: u16) -> u8 {
+    STATE_LIGHT
+        .get(usize::from(state_id))
+        .map_or(0, |light| light.1)
+}
+
+/// Whether replacing the one block state with the other can change any light levels
+pub fn affects_light(old_state_id: u16, new_state_id: u16) -> bool {
+    opacity(old_state_id) != opacity(new_state_id)
+        || luminance(old_state_id) != luminance(new_state_id)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LightType {
+    Sky,
+    Block,
+}
+
+impl LightType {
+    /// The level of sections without stored data
+    fn default_level(self) -> u8 {
+        match self {
+            Self::Sky => 15,
+            Self::Block => 0,
+        }
+    }
+}
+
+/// The light section and the index in it of a block, `y` counts up from the bottom of the world
+fn light_index(x: usize, y: usize, z: usize) -> (usize, usize) {
+    (y / 16 + 1, (y % 16) * CHUNK_AREA + z * 16 + x)
+}
+
+impl ChunkLight {
+    fn storage(&self, kind: LightType) -> &Vec<Option<Box<[u8]>>> {
+        match kind {
+            LightType::Sky => &self.sky_light,
+            LightType::Block => &self.block_light,
+        }
+    }
+
+    fn storage_mut(&mut self, kind: LightType) -> &mut Vec<Option<Box<[u8]>>> {
+        match kind {
+            LightType::Sky => &mut self.sky_light,
+            LightType::Block => &mut self.block_light,
+        }
+    }
+
+    /// The light level of a block in the chunk, `y` counts up from the bottom of the world
+    pub fn get(&self, kind: LightType, x: usize, y: usize, z: usize) -> u8 {
+        let (section, index) = light_index(x, y, z);
+        match self.storage(kind).get(section).and_then(Option::as_deref) {
+            Some(data) => (data[index / 2] >> (4 * (index % 2))) & 0xF,
+            None => kind.default_level(),
+        }
+    }
+
+    /// Sets the light level of a block in the chunk, sections are allocated when needed
+    pub fn set(&mut self, kind: LightType, x: usize, y: usize, z: usize, level: u8) {
+        let (section, index) = light_index(x, y, z);
+        let storage = self.storage_mut(kind);
+        if storage.len() < LIGHT_SECTION_COUNT {
+            storage.resize(LIGHT_SECTION_COUNT, None);
+        }
+        let fill = kind.default_level() * 0x11;
+        let data = storage[section]
+            .get_or_insert_with(|| vec![fill; LIGHT_SECTION_SIZE].into_boxed_slice());
+        let shift = 4 * (index % 2);
+        data[index / 2] = (data[index / 2] & !(0xF << shift)) | ((level & 0xF) << shift);
+    }
+
+    /// A copy of the sections in the mask, the other sections are left out
+    pub fn copy_sections(&self, sections: u64) -> Self {
+        let copy = |storage: &Vec<Option<Box<[u8]>>>| {
+            storage
+                .iter()
+                .enumerate()
+                .map(|(index, data)| data.clone().filter(|_| sections & (1 << index) != 0))
+                .collect()
+        };
+        Self {
+            sky_light: copy(&self.sky_light),
+            block_light: copy(&self.block_light),
+        }
+    }
+
+    /// Drops the sections which only hold the default level, they don't need to be stored
+    fn drop_default_sections(&mut self) {
+        for kind in [LightType::Sky, LightType::Block] {
+            let fill = kind.default_level() * 0x11;
+            for section in self.storage_mut(kind) {
+                if section
+                    .as_ref()
+                    .is_some_and(|data| data.iter().all(|byte| *byte == fill))
+                {
+                    *section = None;
+                }
+            }
+        }
+    }
+}
+
+/// Blocks and light the propagation works on, positions are `(x, y, z)` with `y` counting up from the bottom of the world
+trait LightWorld {
+    /// The block state, `None` outside of the world or the loaded chunks
+    fn state(&self, pos: (i32, i32, i32)) -> Option<u16>;
+    fn light(&self, kind: LightType, pos: (i32, i32, i32)) -> u8;
+    fn set_light(&mut self, kind: LightType, pos: (i32, i32, i32), level: u8);
+}
+
+const DIRECTIONS: [(i32, i32, i32); 6] = [
+    (0, -1, 0),
+    (0, 1, 0),
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+fn offset(pos: (i32, i32, i32), direction: (i32, i32, i32)) -> (i32, i32, i32) {
+    (
+        pos.0 + direction.0,
+        pos.1 + direction.1,
+        pos.2 + direction.2,
+    )
+}
+
+/// The light a block gets from a neighbour with the given level. Full sky light travels down
+/// through transparent blocks without getting weaker, every other step takes away at least one level
+fn attenuate(kind: LightType, level: u8, downwards: bool, opacity: u8) -> u8 {
+    if kind == LightType::Sky && downwards && level == 15 && opacity == 0 {
+        15
+    } else {
+        level.saturating_sub(opacity.max(1))
+    }
+}
+
+/// Spreads the light of the queued blocks to their neighbours
+fn propagate_increase(
+    world: &mut impl LightWorld,
+    kind: LightType,
+    queue: &mut VecDeque<(i32, i32, i32)>,
+) {
+    while let Some(pos) = queue.pop_front() {
+        let level = world.light(kind, pos);
+        if level <= 1 {
+            continue;
+        }
+        for direction in DIRECTIONS {
+            let neighbour = offset(pos, direction);
+            let Some(state) = world.state(neighbour) else {
+                continue;
+            };
+            let new_level = attenuate(kind, level, direction.1 < 0, opacity(state));
+            if new_level > world.light(kind, neighbour) {
+                world.set_light(kind, neighbour, new_level);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+}
+
+/// Darkens the blocks which were lit by the queued blocks, which were already set to 0 and are queued
+/// along with their old level. Blocks which are lit from somewhere else are queued to spread their light again
+fn propagate_decrease(
+    world: &mut impl LightWorld,
+    kind: LightType,
+    queue: &mut VecDeque<((i32, i32, i32), u8)>,
+    increase: &mut VecDeque<(i32, i32, i32)>,
+) {
+    while let Some((pos, level)) = queue.pop_front() {
+        for direction in DIRECTIONS {
+            let neighbour = offset(pos, direction);
+            let Some(state) = world.state(neighbour) else {
+                continue;
+            };
+            let neighbour_level = world.light(kind, neighbour);
+            if neighbour_level == 0 {
+                continue;
+            }
+            let lit_by_pos = neighbour_level < level
+                || (kind == LightType::Sky && direction.1 < 0 && level == 15);
+            if !lit_by_pos {
+                increase.push_back(neighbour);
+                continue;
+            }
+            world.set_light(kind, neighbour, 0);
+            queue.push_back((neighbour, neighbour_level));
+            // Light sources keep their own light
+            let emitted = luminance(state);
+            if kind == LightType::Block && emitted > 0 {
+                world.set_light(kind, neighbour, emitted);
+                increase.push_back(neighbour);
+            }
+        }
+    }
+}
+
+impl LightWorld for ChunkData {
+    fn state(&self, (x, y, z): (i32, i32, i32)) -> Option<u16> {
+        if !(0..16).contains(&x) || !(0..16).contains(&z) || !(0..WORLD_HEIGHT as i32).contains(&y)
+        {
+            return None;
+        }
+        Some(self.blocks.blocks[y as usize * CHUNK_AREA + z as usize * 16 + x as usize])
+    }
+
+    fn light(&self, kind: LightType, (x, y, z): (i32, i32, i32)) -> u8 {
+        self.light.get(kind, x as usize, y as usize, z as usize)
+    }
+
+    fn set_light(&mut self, kind: LightType, (x, y, z): (i32, i32, i32), level: u8) {
+        self.light
+            .set(kind, x as usize, y as usize, z as usize, level);
+    }
+}
+
+/// Calculates the light of a chunk from scratch, replacing its stored light.
+/// Only the blocks of the chunk are taken into account, light from neighbouring chunks doesn't reach into it
+pub fn light_chunk(chunk: &mut ChunkData) {
+    let zeroed = || Some(vec![0; LIGHT_SECTION_SIZE].into_boxed_slice());
+    chunk.light = ChunkLight {
+        sky_light: (0..LIGHT_SECTION_COUNT).map(|_| zeroed()).collect(),
+        block_light: vec![None; LIGHT_SECTION_COUNT],
+    };
+    // Everything from the sky down to the first block which isn't fully transparent gets full sky light
+    let mut heights = [[WORLD_HEIGHT as i32; 16]; 16];
+    for x in 0..16 {
+        for z in 0..16 {
+            let mut y = WORLD_HEIGHT as i32;
+            while chunk
+                .state((x, y - 1, z))
+                .is_some_and(|state| opacity(state) == 0)
+            {
+                y -= 1;
+            }
+            heights[x as usize][z as usize] = y;
+            for y in y..WORLD_HEIGHT as i32 {
+                chunk.set_light(LightType::Sky, (x, y, z), 15);
+            }
+        }
+    }
+    // From there it only has to spread sideways and into the shadows of neighbouring columns
+    let mut queue = VecDeque::new();
+    for x in 0..16 {
+        for z in 0..16 {
+            let height = heights[x as usize][z as usize];
+            let shadowed = DIRECTIONS[2..]
+                .iter()
+                .filter_map(|direction| {
+                    heights
+                        .get((x + direction.0) as usize)?
+                        .get((z + direction.2) as usize)
+                })
+                .copied()
+                .max()
+                .unwrap_or(height);
+            for y in height..shadowed.max(height + 1).min(WORLD_HEIGHT as i32) {
+                queue.push_back((x, y, z));
+            }
+        }
+    }
+    propagate_increase(chunk, LightType::Sky, &mut queue);
+
+    for y in 0..WORLD_HEIGHT as i32 {
+        for z in 0..16 {
+            for x in 0..16 {
+                let emitted = chunk.state((x, y, z)).map_or(0, luminance);
+                if emitted > 0 {
+                    chunk.set_light(LightType::Block, (x, y, z), emitted);
+                    queue.push_back((x, y, z));
+                }
+            }
+        }
+    }
+    propagate_increase(chunk, LightType::Block, &mut queue);
+    chunk.light.drop_default_sections();
+}
+
+/// Updates the light around changed blocks across the given chunks.
+/// Chunks which aren't given are treated like the edge of the world, light stops there
+pub struct LightUpdater<'a> {
+    chunks: HashMap<Vector2<i32>, &'a mut ChunkData>,
+    /// The light sections which changed in every chunk
+    changed: HashMap<Vector2<i32>, u64>,
+}
+
+impl<'a> LightUpdater<'a> {
+    pub fn new(chunks: impl IntoIterator<Item = &'a mut ChunkData>) -> Self {
+        Self {
+            chunks: chunks
+                .into_iter()
+                .map(|chunk| (chunk.position, chunk))
+                .collect(),
+            changed: HashMap::new(),
+        }
+    }
+
+    fn chunk_position((x, _, z): (i32, i32, i32)) -> Vector2<i32> {
+        Vector2::new(x >> 4, z >> 4)
+    }
+
+    /// Recalculates the light around a block after it was changed
+    pub fn update_block(&mut self, position: WorldPosition) {
+        let pos = (
+            position.0.x,
+            position.0.y - i32::from(WORLD_LOWEST_Y),
+            position.0.z,
+        );
+        let Some(state) = self.state(pos) else {
+            return;
+        };
+        for kind in [LightType::Sky, LightType::Block] {
+            let mut decrease = VecDeque::new();
+            let mut increase = VecDeque::new();
+            let old_level = self.light(kind, pos);
+            if old_level > 0 {
+                self.set_light(kind, pos, 0);
+                decrease.push_back((pos, old_level));
+            }
+            propagate_decrease(self, kind, &mut decrease, &mut increase);
+
+            let own_level = match kind {
+                LightType::Block => luminance(state),
+                // The sky is right above the top of the world
+                LightType::Sky if pos.1 == WORLD_HEIGHT as i32 - 1 => {
+                    attenuate(kind, 15, true, opacity(state))
+                }
+                LightType::Sky => 0,
+            };
+            if own_level > self.light(kind, pos) {
+                self.set_light(kind, pos, own_level);
+                increase.push_back(pos);
+            }
+            // Neighbours may be able to shine through the block now
+            for direction in DIRECTIONS {
+                let neighbour = offset(pos, direction);
+                if self.light(kind, neighbour) > 0 {
+                    increase.push_back(neighbour);
+                }
+            }
+            propagate_increase(self, kind, &mut increase);
+        }
+    }
+
+    /// The chunks whose light changed, along with a mask of the changed light sections
+    pub fn into_changed_sections(self) -> HashMap<Vector2<i32>, u64> {
+        self.changed
+    }
+}
+
+impl LightWorld for LightUpdater<'_> {
+    fn state(&self, pos: (i32, i32, i32)) -> Option<u16> {
+        let chunk = self.chunks.get(&Self::chunk_position(pos))?;
+        chunk.state((pos.0 & 15, pos.1, pos.2 & 15))
+    }
+
+    fn light(&self, kind: LightType, pos: (i32, i32, i32)) -> u8 {
+        if self.state(pos).is_none() {
+            return 0;
+        }
+        self.chunks[&Self::chunk_position(pos)].light(kind, (pos.0 & 15, pos.1, pos.2 & 15))
+    }
+
+    fn set_light(&mut self, kind: LightType, pos: (i32, i32, i32), level: u8) {
+        let chunk_position = Self::chunk_position(pos);
+        let Some(chunk) = self.chunks.get_mut(&chunk_position) else {
+            return;
+        };
+        chunk.set_light(kind, (pos.0 & 15, pos.1, pos.2 & 15), level);
+        chunk.dirty = true;
+        let (section, _) = light_index(0, pos.1 as usize, 0);
+        *self.changed.entry(chunk_position).or_default() |= 1 << section;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        block::block_registry::get_block,
+        chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::structure::ChunkStructures,
+        WORLD_LOWEST_Y,
+    };
+
+    use super::{light_chunk, LightType, LightUpdater};
+
+    fn state(name: &str) -> u16 {
+        get_block(name).unwrap().default_state_id
+    }
+
+    fn empty_chunk(x: i32, z: i32) -> ChunkData {
+        ChunkData {
+            blocks: ChunkBlocks::default(),
+            position: Vector2::new(x, z),
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: ChunkStructures::default(),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: false,
+        }
+    }
+
+    fn set_block(chunk: &mut ChunkData, x: i32, y: i32, z: i32, state_id: u16) {
+        chunk.blocks.set_block(
+            ChunkRelativeBlockCoordinates::from(Vector3::new(x, y, z)),
+            state_id,
+        );
+    }
+
+    /// The light level at a world height
+    fn light(chunk: &ChunkData, kind: LightType, x: usize, y: i32, z: usize) -> u8 {
+        chunk
+            .light
+            .get(kind, x, (y - i32::from(WORLD_LOWEST_Y)) as usize, z)
+    }
+
+    #[test]
+    fn sky_light_under_roof() {
+        let mut chunk = empty_chunk(0, 0);
+        let stone = state("stone");
+        for x in 0..16 {
+            for z in 0..16 {
+                set_block(&mut chunk, x, 0, z, stone);
+            }
+        }
+        // A roof with a single hole
+        for x in 0..8 {
+            for z in 0..8 {
+                if (x, z) != (4, 4) {
+                    set_block(&mut chunk, x, 10, z, stone);
+                }
+            }
+        }
+        light_chunk(&mut chunk);
+
+        assert_eq!(light(&chunk, LightType::Sky, 12, 5, 12), 15);
+        assert_eq!(light(&chunk, LightType::Sky, 4, 5, 4), 15);
+        assert_eq!(light(&chunk, LightType::Sky, 3, 5, 4), 14);
+        assert_eq!(light(&chunk, LightType::Sky, 1, 5, 1), 9);
+        assert_eq!(light(&chunk, LightType::Sky, 4, 0, 4), 0);
+        assert_eq!(light(&chunk, LightType::Sky, 4, -10, 4), 0);
+    }
+
+    #[test]
+    fn block_light_spreads_and_is_removed() {
+        let mut chunk = empty_chunk(0, 0);
+        let glowstone = state("glowstone");
+        set_block(&mut chunk, 8, 20, 8, glowstone);
+        light_chunk(&mut chunk);
+        assert_eq!(light(&chunk, LightType::Block, 8, 20, 8), 15);
+        assert_eq!(light(&chunk, LightType::Block, 8, 20, 3), 10);
+
+        set_block(&mut chunk, 8, 20, 8, 0);
+        let mut updater = LightUpdater::new([&mut chunk]);
+        updater.update_block(WorldPosition(Vector3::new(8, 20, 8)));
+        let changed = updater.into_changed_sections();
+        assert!(changed.contains_key(&Vector2::new(0, 0)));
+        assert_eq!(light(&chunk, LightType::Block, 8, 20, 8), 0);
+        assert_eq!(light(&chunk, LightType::Block, 8, 20, 3), 0);
+        assert!(chunk.dirty);
+    }
+
+    #[test]
+    fn light_crosses_chunk_borders() {
+        let mut left = empty_chunk(-1, 0);
+        let mut right = empty_chunk(0, 0);
+        light_chunk(&mut left);
+        light_chunk(&mut right);
+
+        let glowstone = state("glowstone");
+        set_block(&mut right, 1, 30, 5, glowstone);
+        LightUpdater::new([&mut left, &mut right])
+            .update_block(WorldPosition(Vector3::new(1, 30, 5)));
+        assert_eq!(light(&right, LightType::Block, 1, 30, 5), 15);
+        assert_eq!(light(&left, LightType::Block, 15, 30, 5), 13);
+        assert_eq!(light(&left, LightType::Block, 5, 30, 5), 3);
+    }
+
+    #[test]
+    fn placed_blocks_cast_shadows() {
+        let mut chunk = empty_chunk(0, 0);
+        let stone = state("stone");
+        for x in 0..16 {
+            for z in 0..16 {
+                set_block(&mut chunk, x, 0, z, stone);
+            }
+        }
+        light_chunk(&mut chunk);
+        assert_eq!(light(&chunk, LightType::Sky, 8, 1, 8), 15);
+
+        set_block(&mut chunk, 8, 50, 8, stone);
+        LightUpdater::new([&mut chunk]).update_block(WorldPosition(Vector3::new(8, 50, 8)));
+        // Sky light comes in from the sides now
+        assert_eq!(light(&chunk, LightType::Sky, 8, 49, 8), 14);
+        assert_eq!(light(&chunk, LightType::Sky, 8, 1, 8), 14);
+        assert_eq!(light(&chunk, LightType::Sky, 8, 50, 8), 0);
+
+        set_block(&mut chunk, 8, 50, 8, 0);
+        LightUpdater::new([&mut chunk]).update_block(WorldPosition(Vector3::new(8, 50, 8)));
+        assert_eq!(light(&chunk, LightType::Sky, 8, 1, 8), 15);
+    }
+}
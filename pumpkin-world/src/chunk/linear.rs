@@ -0,0 +1,358 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::vector2::Vector2;
+
+use crate::{level::LevelFolder, write_atomically};
+
+use super::{
+    anvil::{chunk_at_region_index, region_files, region_index},
+    ChunkReadingError, ChunkStorage, ChunkWritingError, CompressionError,
+};
+
+/// Starts and ends every linear region file
+const SIGNATURE: u64 = 0xc3ff_1318_3cca_9d9a;
+const VERSION: u8 = 1;
+/// Signature, version, newest timestamp, compression level, chunk count, data length and 8 reserved bytes
+const HEADER_SIZE: usize = 8 + 1 + 8 + 1 + 2 + 4 + 8;
+const FOOTER_SIZE: usize = 8;
+const REGION_CHUNKS: usize = 1024;
+/// Regions without unsaved chunks are dropped from the cache once it holds more than this many
+const MAX_CACHED_REGIONS: usize = 64;
+
+type RegionKey = (PathBuf, (i32, i32));
+
+#[derive(Clone)]
+struct LinearChunk {
+    data: Vec<u8>,
+    timestamp: u32,
+}
+
+/// A region held in memory, it is written to disk as a whole
+struct LinearRegion {
+    chunks: Vec<Option<LinearChunk>>,
+    /// Whether chunks were written since the region was last saved
+    dirty: bool,
+}
+
+impl LinearRegion {
+    fn empty() -> Self {
+        Self {
+            chunks: vec![None; REGION_CHUNKS],
+            dirty: false,
+        }
+    }
+
+    fn read(path: &Path) -> Result<Self, ChunkReadingError> {
+        let file = match fs::read(path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::empty()),
+            Err(err) => return Err(ChunkReadingError::IoError(err.kind())),
+        };
+        if file.len() < HEADER_SIZE + FOOTER_SIZE
+            || file[..8] != SIGNATURE.to_be_bytes()
+            || file[file.len() - FOOTER_SIZE..] != SIGNATURE.to_be_bytes()
+            || file[8] != VERSION
+        {
+            return Err(ChunkReadingError::RegionIsInvalid);
+        }
+        let data_length = u32::from_be_bytes(file[20..24].try_into().unwrap()) as usize;
+        let compressed = file
+            .get(HEADER_SIZE..HEADER_SIZE + data_length)
+            .ok_or(ChunkReadingError::RegionIsInvalid)?;
+        let data = zstd::decode_all(compressed)
+            .map_err(|err| ChunkReadingError::Compression(CompressionError::ZstdError(err)))?;
+
+        // The sizes and timestamps of every chunk are followed by the chunks in the same order
+        let table_size = REGION_CHUNKS * 8;
+        if data.len() < table_size {
+            return Err(ChunkReadingError::RegionIsInvalid);
+        }
+        let mut chunks = Vec::with_capacity(REGION_CHUNKS);
+        let mut offset = table_size;
+        for entry in data[..table_size].chunks_exact(8) {
+            let size = u32::from_be_bytes(entry[..4].try_into().unwrap()) as usize;
+            let timestamp = u32::from_be_bytes(entry[4..].try_into().unwrap());
+            if size == 0 {
+                chunks.push(None);
+                continue;
+            }
+            let data = data
+                .get(offset..offset + size)
+                .ok_or(ChunkReadingError::RegionIsInvalid)?;
+            chunks.push(Some(LinearChunk {
+                data: data.to_vec(),
+                timestamp,
+            }));
+            offset += size;
+        }
+        Ok(Self {
+            chunks,
+            dirty: false,
+        })
+    }
+
+    fn write(&self, path: &Path, level: i32) -> Result<(), ChunkWritingError> {
+        let io_error = |err: std::io::Error| ChunkWritingError::IoError(err.kind());
+        let stored = || self.chunks.iter().flatten();
+
+        let mut data = Vec::with_capacity(
+            REGION_CHUNKS * 8 + stored().map(|chunk| chunk.data.len()).sum::<usize>(),
+        );
+        for chunk in &self.chunks {
+            let (size, timestamp) = chunk
+                .as_ref()
+                .map_or((0, 0), |chunk| (chunk.data.len() as u32, chunk.timestamp));
+            data.extend_from_slice(&size.to_be_bytes());
+            data.extend_from_slice(&timestamp.to_be_bytes());
+        }
+        for chunk in stored() {
+            data.extend_from_slice(&chunk.data);
+        }
+        let compressed = zstd::encode_all(&data[..], level)
+            .map_err(|err| ChunkWritingError::Compression(CompressionError::ZstdError(err)))?;
+
+        let newest_timestamp = stored().map(|chunk| chunk.timestamp).max().unwrap_or(0);
+        let mut file = Vec::with_capacity(HEADER_SIZE + compressed.len() + FOOTER_SIZE);
+        file.extend_from_slice(&SIGNATURE.to_be_bytes());
+        file.push(VERSION);
+        file.extend_from_slice(&u64::from(newest_timestamp).to_be_bytes());
+        file.push(level as u8);
+        file.extend_from_slice(&(stored().count() as u16).to_be_bytes());
+        file.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        file.extend_from_slice(&0u64.to_be_bytes());
+        file.extend_from_slice(&compressed);
+        file.extend_from_slice(&SIGNATURE.to_be_bytes());
+
+        // A crash while writing must not leave a broken region behind
+        write_atomically(path, &file).map_err(io_error)
+    }
+}
+
+/// Stores chunks in linear region files, where the whole region is compressed at once with zstd.
+/// This makes them a lot smaller than Anvil regions, but vanilla can't read them.
+/// Regions are kept in memory, written chunks are only saved once the storage is flushed
+pub struct LinearChunkStorage {
+    level: i32,
+    /// Cached regions by their folder and position
+    regions: Mutex<HashMap<RegionKey, LinearRegion>>,
+}
+
+impl Default for LinearChunkStorage {
+    fn default() -> Self {
+        Self::new(ADVANCED_CONFIG.chunk.compression.level)
+    }
+}
+
+impl LinearChunkStorage {
+    /// zstd supports compression levels from 1 to 22
+    pub fn new(level: u32) -> Self {
+        Self {
+            level: level.clamp(1, 22) as i32,
+            regions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn region_key(save_file: &LevelFolder, at: &Vector2<i32>) -> RegionKey {
+        (save_file.region_folder.clone(), (at.x >> 5, at.z >> 5))
+    }
+
+    fn region_path((folder, region): &RegionKey) -> PathBuf {
+        folder.join(format!("r.{}.{}.linear", region.0, region.1))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<RegionKey, LinearRegion>> {
+        self.regions.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The cached region, it is read from disk if it is not cached yet
+    fn cached_region(
+        regions: &mut HashMap<RegionKey, LinearRegion>,
+        key: RegionKey,
+    ) -> Result<&mut LinearRegion, ChunkReadingError> {
+        if !regions.contains_key(&key) {
+            if regions.len() >= MAX_CACHED_REGIONS {
+                regions.retain(|_, region| region.dirty);
+            }
+            let region = LinearRegion::read(&Self::region_path(&key))?;
+            regions.insert(key.clone(), region);
+        }
+        Ok(regions.get_mut(&key).unwrap())
+    }
+}
+
+impl ChunkStorage for LinearChunkStorage {
+    fn read_chunk_nbt(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+    ) -> Result<Vec<u8>, ChunkReadingError> {
+        let mut regions = self.lock();
+        let region = Self::cached_region(&mut regions, Self::region_key(save_file, at))?;
+        region.chunks[region_index(at)]
+            .as_ref()
+            .map(|chunk| chunk.data.clone())
+            .ok_or(ChunkReadingError::ChunkNotExist)
+    }
+
+    fn write_chunk(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+        chunk_data: &[u8],
+    ) -> Result<(), ChunkWritingError> {
+        let mut regions = self.lock();
+        let region =
+            Self::cached_region(&mut regions, Self::region_key(save_file, at)).map_err(|err| {
+                match err {
+                    ChunkReadingError::IoError(kind) => ChunkWritingError::IoError(kind),
+                    ChunkReadingError::Compression(err) => ChunkWritingError::Compression(err),
+                    // Overwriting a broken region would lose the chunks which are still readable
+                    _ => ChunkWritingError::IoError(std::io::ErrorKind::InvalidData),
+                }
+            })?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs() as u32);
+        region.chunks[region_index(at)] = Some(LinearChunk {
+            data: chunk_data.to_vec(),
+            timestamp,
+        });
+        region.dirty = true;
+        Ok(())
+    }
+
+    fn flush(&self, save_file: &LevelFolder) -> Result<(), ChunkWritingError> {
+        let mut regions = self.lock();
+        fs::create_dir_all(&save_file.region_folder)
+            .map_err(|err| ChunkWritingError::IoError(err.kind()))?;
+        for (key, region) in regions
+            .iter_mut()
+            .filter(|(key, region)| region.dirty && key.0 == save_file.region_folder)
+        {
+            // Regions which failed to save stay dirty and are tried again with the next flush
+            region.write(&Self::region_path(key), self.level)?;
+            region.dirty = false;
+        }
+        Ok(())
+    }
+
+    fn stored_chunks(&self, save_file: &LevelFolder) -> Vec<Vector2<i32>> {
+        let mut regions = self.lock();
+        let mut positions: Vec<(i32, i32)> = region_files(&save_file.region_folder, "linear")
+            .into_iter()
+            .map(|(_, region)| region)
+            .collect();
+        // Regions which were never flushed don't have a file yet
+        for (folder, region) in regions.keys() {
+            if *folder == save_file.region_folder && !positions.contains(region) {
+                positions.push(*region);
+            }
+        }
+
+        let mut chunks = Vec::new();
+        for region_pos in positions {
+            let key = (save_file.region_folder.clone(), region_pos);
+            let region = match Self::cached_region(&mut regions, key) {
+                Ok(region) => region,
+                Err(err) => {
+                    log::warn!("Failed to read linear region {:?}: {}", region_pos, err);
+                    continue;
+                }
+            };
+            chunks.extend(
+                region
+                    .chunks
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, chunk)| chunk.is_some())
+                    .map(|(index, _)| chunk_at_region_index(region_pos, index)),
+            );
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use crate::{
+        chunk::{linear::LinearChunkStorage, ChunkReadingError, ChunkStorage},
+        level::LevelFolder,
+    };
+
+    fn test_folder(name: &str) -> LevelFolder {
+        let root_folder =
+            std::env::temp_dir().join(format!("pumpkin-linear-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root_folder);
+        LevelFolder {
+            region_folder: root_folder.join("region"),
+            root_folder,
+        }
+    }
+
+    #[test]
+    fn write_flush_and_read_back() {
+        let folder = test_folder("roundtrip");
+        let storage = LinearChunkStorage::new(3);
+        storage
+            .write_chunk(&folder, &Vector2::new(-1, 33), b"first")
+            .unwrap();
+        storage
+            .write_chunk(&folder, &Vector2::new(-32, 63), b"second")
+            .unwrap();
+        // Written chunks are buffered until the storage is flushed
+        let region = folder.region_folder.join("r.-1.1.linear");
+        assert!(!region.exists());
+        storage.flush(&folder).unwrap();
+        assert!(region.exists());
+
+        // A new storage doesn't have the region cached
+        let storage = LinearChunkStorage::new(3);
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(-1, 33))
+                .unwrap(),
+            b"first"
+        );
+        assert_eq!(
+            storage
+                .read_chunk_nbt(&folder, &Vector2::new(-32, 63))
+                .unwrap(),
+            b"second"
+        );
+        assert!(matches!(
+            storage.read_chunk_nbt(&folder, &Vector2::new(-2, 33)),
+            Err(ChunkReadingError::ChunkNotExist)
+        ));
+
+        let mut chunks = storage.stored_chunks(&folder);
+        chunks.sort_by_key(|chunk| (chunk.x, chunk.z));
+        assert_eq!(chunks, vec![Vector2::new(-32, 63), Vector2::new(-1, 33)]);
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+
+    #[test]
+    fn invalid_regions_are_rejected() {
+        let folder = test_folder("invalid");
+        std::fs::create_dir_all(&folder.region_folder).unwrap();
+        std::fs::write(folder.region_folder.join("r.0.0.linear"), [0; 64]).unwrap();
+        let storage = LinearChunkStorage::new(3);
+        assert!(matches!(
+            storage.read_chunk_nbt(&folder, &Vector2::new(0, 0)),
+            Err(ChunkReadingError::RegionIsInvalid)
+        ));
+        // The broken region is not overwritten
+        assert!(storage
+            .write_chunk(&folder, &Vector2::new(0, 0), b"chunk")
+            .is_err());
+        let _ = std::fs::remove_dir_all(&folder.root_folder);
+    }
+}
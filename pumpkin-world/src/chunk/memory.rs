@@ -0,0 +1,40 @@
+use dashmap::DashMap;
+use pumpkin_core::math::vector2::Vector2;
+
+use crate::level::LevelFolder;
+
+use super::{ChunkReadingError, ChunkStorage, ChunkWritingError};
+
+/// Keeps chunks in memory only, they are gone once the server stops.
+/// Useful for tests and worlds which are reset anyway
+#[derive(Default)]
+pub struct MemoryChunkStorage {
+    chunks: DashMap<Vector2<i32>, Vec<u8>>,
+}
+
+impl ChunkStorage for MemoryChunkStorage {
+    fn read_chunk_nbt(
+        &self,
+        _save_file: &LevelFolder,
+        at: &Vector2<i32>,
+    ) -> Result<Vec<u8>, ChunkReadingError> {
+        self.chunks
+            .get(at)
+            .map(|chunk| chunk.value().clone())
+            .ok_or(ChunkReadingError::ChunkNotExist)
+    }
+
+    fn write_chunk(
+        &self,
+        _save_file: &LevelFolder,
+        at: &Vector2<i32>,
+        chunk_data: &[u8],
+    ) -> Result<(), ChunkWritingError> {
+        self.chunks.insert(*at, chunk_data.to_vec());
+        Ok(())
+    }
+
+    fn stored_chunks(&self, _save_file: &LevelFolder) -> Vec<Vector2<i32>> {
+        self.chunks.iter().map(|chunk| *chunk.key()).collect()
+    }
+}
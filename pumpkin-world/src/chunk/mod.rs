@@ -1,30 +1,109 @@
+use anvil::AnvilChunkStorage;
 use fastnbt::LongArray;
-use pumpkin_core::math::vector2::Vector2;
+use linear::LinearChunkStorage;
+use memory::MemoryChunkStorage;
+use pumpkin_config::chunk::ChunkFormat;
+use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag, Nbt};
 use serde::{Deserialize, Serialize};
 use std::cmp::max;
 use std::collections::HashMap;
 use std::ops::Index;
 use thiserror::Error;
+use ticks::TickQueue;
+use upgrade::{upgrade_chunk, UpgradeError};
 
 use crate::{
-    block::BlockState,
+    biome::Biome,
+    block::{
+        block_entity::{block_entity_from_nbt, block_entity_to_nbt, BlockEntity},
+        block_registry::{get_block, get_block_by_state_id},
+        BlockState,
+    },
     coordinates::{ChunkRelativeBlockCoordinates, Height},
+    generation::{blender::BlendingData, structure::ChunkStructures},
     level::LevelFolder,
-    WORLD_HEIGHT,
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
 };
 
 pub mod anvil;
+pub mod light;
+pub mod linear;
+pub mod memory;
+pub mod ticks;
+pub mod upgrade;
 
 const CHUNK_AREA: usize = 16 * 16;
 const SUBCHUNK_VOLUME: usize = CHUNK_AREA * 16;
 const CHUNK_VOLUME: usize = CHUNK_AREA * WORLD_HEIGHT;
 
-pub trait ChunkReader: Sync + Send {
+/// The data version of the chunks we write, the one of 1.21.4
+pub const WORLD_DATA_VERSION: i32 = 4189;
+/// Light is also stored for the sections right below and above the world
+pub const LIGHT_SECTION_COUNT: usize = WORLD_HEIGHT / 16 + 2;
+/// The size of a light section, every block takes up half a byte
+pub const LIGHT_SECTION_SIZE: usize = SUBCHUNK_VOLUME / 2;
+
+/// Where the chunks of a world are stored. Chunks are written as serialized NBT, see `ChunkData::to_bytes`
+pub trait ChunkStorage: Sync + Send {
+    /// Reads the uncompressed NBT of the chunk
+    fn read_chunk_nbt(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+    ) -> Result<Vec<u8>, ChunkReadingError>;
+
     fn read_chunk(
         &self,
         save_file: &LevelFolder,
         at: &Vector2<i32>,
-    ) -> Result<ChunkData, ChunkReadingError>;
+    ) -> Result<ChunkData, ChunkReadingError> {
+        let chunk_data = self.read_chunk_nbt(save_file, at)?;
+        ChunkData::from_bytes(&chunk_data, *at).map_err(ChunkReadingError::ParsingError)
+    }
+
+    /// Stores the serialized chunk, it may only be written to disk once `flush` is called
+    fn write_chunk(
+        &self,
+        save_file: &LevelFolder,
+        at: &Vector2<i32>,
+        chunk_data: &[u8],
+    ) -> Result<(), ChunkWritingError>;
+
+    /// Stores the entities of the chunk, storages without an `entities` folder drop them
+    fn write_entities(
+        &self,
+        _save_file: &LevelFolder,
+        _at: &Vector2<i32>,
+        _entities: &[NbtCompound],
+    ) -> Result<(), ChunkWritingError> {
+        Ok(())
+    }
+
+    /// Writes the chunks which were only buffered to disk
+    fn flush(&self, _save_file: &LevelFolder) -> Result<(), ChunkWritingError> {
+        Ok(())
+    }
+
+    /// The positions of every stored chunk
+    fn stored_chunks(&self, save_file: &LevelFolder) -> Vec<Vector2<i32>>;
+}
+
+/// The storage of the format chosen in the config
+pub fn get_chunk_storage(format: ChunkFormat) -> Box<dyn ChunkStorage> {
+    match format {
+        ChunkFormat::Anvil => Box::new(AnvilChunkStorage::default()),
+        ChunkFormat::Linear => Box::new(LinearChunkStorage::default()),
+        ChunkFormat::Memory => Box::new(MemoryChunkStorage::default()),
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ChunkWritingError {
+    #[error("Io error: {0}")]
+    IoError(std::io::ErrorKind),
+    #[error("Compression error {0}")]
+    Compression(CompressionError),
 }
 
 #[derive(Error, Debug)]
@@ -51,11 +130,174 @@ pub enum CompressionError {
     GZipError(std::io::Error),
     #[error("Error while working with LZ4 compression: {0}")]
     LZ4Error(std::io::Error),
+    #[error("Error while working with zstd compression: {0}")]
+    ZstdError(std::io::Error),
 }
 
 pub struct ChunkData {
     pub blocks: ChunkBlocks,
     pub position: Vector2<i32>,
+    /// The block entities in this chunk, keyed by their absolute block position
+    pub block_entities: HashMap<WorldPosition, Box<dyn BlockEntity>>,
+    /// Raw NBT of the entities vanilla saved in the `entities` folder for this chunk
+    pub entities: Vec<NbtCompound>,
+    /// Whether `entities` changed or the saved entities may be outdated, then they are saved with
+    /// the next save even if the chunk itself didn't change
+    pub entities_dirty: bool,
+    /// Points of interest vanilla saved in the `poi` folder for this chunk
+    pub points_of_interest: Vec<PointOfInterest>,
+    pub light: ChunkLight,
+    /// The structures starting in and reaching into this chunk
+    pub structures: ChunkStructures,
+    /// The terrain along the borders of a chunk generated by an older version, new chunks next to
+    /// it blend into it
+    pub blending_data: Option<BlendingData>,
+    /// Block updates scheduled with a delay, e.g. by repeaters
+    pub block_ticks: TickQueue,
+    /// Fluid updates scheduled with a delay, e.g. by flowing water
+    pub fluid_ticks: TickQueue,
+    /// How many ticks players spent near the chunk, mobs get tougher in chunks players stay in
+    pub inhabited_time: i64,
+    /// Whether the chunk changed since it was last saved, newly generated chunks have to be saved too
+    pub dirty: bool,
+}
+
+/// Light levels as stored by vanilla, half a byte per block in the same order as the blocks.
+/// Chunks without any are lit when they are loaded, see `light::light_chunk`
+#[derive(Default, Clone)]
+pub struct ChunkLight {
+    /// Indexed by section, starting with the one right below the world. Sections without data are fully lit
+    pub sky_light: Vec<Option<Box<[u8]>>>,
+    /// Indexed like `sky_light`. Sections without data are dark
+    pub block_light: Vec<Option<Box<[u8]>>>,
+}
+
+impl ChunkLight {
+    pub fn is_empty(&self) -> bool {
+        self.sky_light
+            .iter()
+            .chain(&self.block_light)
+            .all(Option::is_none)
+    }
+
+    /// Reads the light out of the sections of a vanilla chunk, light-only sections are included
+    fn read_nbt(sections: &[NbtTag]) -> Self {
+        let mut light = Self {
+            sky_light: vec![None; LIGHT_SECTION_COUNT],
+            block_light: vec![None; LIGHT_SECTION_COUNT],
+        };
+        for section in sections.iter().filter_map(NbtTag::extract_compound) {
+            let Some(index) = section
+                .get_byte("Y")
+                .and_then(|y| light_section_index(i32::from(y)))
+            else {
+                continue;
+            };
+            let read = |name| {
+                section
+                    .get(name)
+                    .and_then(NbtTag::extract_byte_array)
+                    .filter(|data| data.len() == LIGHT_SECTION_SIZE)
+                    .map(|data| data.to_vec().into_boxed_slice())
+            };
+            light.sky_light[index] = read("SkyLight");
+            light.block_light[index] = read("BlockLight");
+        }
+        light
+    }
+}
+
+fn light_section_index(section_y: i32) -> Option<usize> {
+    usize::try_from(section_y - i32::from(WORLD_LOWEST_Y) / 16 + 1)
+        .ok()
+        .filter(|index| *index < LIGHT_SECTION_COUNT)
+}
+
+/// A point of interest vanilla keeps track of, like beds, job sites and nether portals
+#[derive(Clone, PartialEq, Eq)]
+pub struct PointOfInterest {
+    pub position: WorldPosition,
+    /// E.g. `minecraft:home`
+    pub kind: String,
+    /// How many more villagers can claim it
+    pub free_tickets: i32,
+}
+
+impl PointOfInterest {
+    /// Reads the points of interest out of the NBT of a `poi` region file chunk.
+    /// Sections vanilla marked as invalid are skipped, vanilla rebuilds them too
+    pub fn read_chunk_nbt(chunk: &NbtCompound) -> Vec<Self> {
+        let Some(sections) = chunk.get_compound("Sections") else {
+            return Vec::new();
+        };
+        sections
+            .child_tags
+            .iter()
+            .filter_map(|(_, section)| section.extract_compound())
+            .filter(|section| section.get_bool("Valid").unwrap_or(true))
+            .filter_map(|section| section.get_list("Records"))
+            .flatten()
+            .filter_map(NbtTag::extract_compound)
+            .filter_map(|record| {
+                let position = record.get_int_array("pos")?;
+                let [x, y, z] = position[..] else {
+                    return None;
+                };
+                Some(Self {
+                    position: WorldPosition(Vector3::new(x, y, z)),
+                    kind: record.get_string("type")?.clone(),
+                    free_tickets: record.get_int("free_tickets").unwrap_or(0),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads the entities out of the NBT of an `entities` region file chunk
+pub fn read_entities_nbt(chunk: &NbtCompound) -> Vec<NbtCompound> {
+    chunk
+        .get_list("Entities")
+        .into_iter()
+        .flatten()
+        .filter_map(NbtTag::extract_compound)
+        .cloned()
+        .collect()
+}
+
+/// Writes the entities like an `entities` region file chunk, which vanilla reads back
+pub fn write_entities_nbt(at: &Vector2<i32>, entities: &[NbtCompound]) -> NbtCompound {
+    let mut chunk = NbtCompound::new();
+    chunk.put("DataVersion".to_string(), NbtTag::Int(WORLD_DATA_VERSION));
+    chunk.put("Position".to_string(), NbtTag::IntArray(vec![at.x, at.z]));
+    chunk.put(
+        "Entities".to_string(),
+        NbtTag::List(entities.iter().cloned().map(NbtTag::Compound).collect()),
+    );
+    chunk
+}
+
+/// Reads the block entities of a vanilla chunk, keyed by their position.
+/// The position and id are dropped from the NBT, the id picks the type of the block entity
+fn read_block_entities_nbt(
+    block_entities: &[NbtTag],
+) -> HashMap<WorldPosition, Box<dyn BlockEntity>> {
+    block_entities
+        .iter()
+        .filter_map(NbtTag::extract_compound)
+        .filter_map(|nbt| {
+            let position = WorldPosition(Vector3::new(
+                nbt.get_int("x")?,
+                nbt.get_int("y")?,
+                nbt.get_int("z")?,
+            ));
+            let id = nbt.get_string("id").cloned().unwrap_or_default();
+            let mut nbt = nbt.clone();
+            nbt.child_tags.retain(|(name, _)| {
+                !matches!(name.as_str(), "x" | "y" | "z" | "id" | "keepPacked")
+            });
+            Some((position, block_entity_from_nbt(&id, &nbt)))
+        })
+        .collect()
 }
 pub struct ChunkBlocks {
     // TODO make this a Vec that doesn't store the upper layers that only contain air
@@ -72,7 +314,7 @@ pub struct ChunkBlocks {
 #[serde(rename_all = "PascalCase")]
 struct PaletteEntry {
     name: String,
-    _properties: Option<HashMap<String, String>>,
+    properties: Option<HashMap<String, String>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -92,7 +334,6 @@ pub struct ChunkHeightmaps {
 }
 
 #[derive(Deserialize, Debug)]
-#[expect(dead_code)]
 struct ChunkSection {
     #[serde(rename = "Y")]
     y: i32,
@@ -204,6 +445,19 @@ impl ChunkBlocks {
         std::mem::replace(&mut self.blocks[Self::convert_index(position)], block)
     }
 
+    /// The blocks matching the predicate, with absolute heights and x and z relative to the chunk
+    pub fn find_blocks(&self, matches: impl Fn(u16) -> bool) -> Vec<Vector3<i32>> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| matches(**state))
+            .map(|(index, _)| {
+                let y = (index / CHUNK_AREA) as i32 + i32::from(WORLD_LOWEST_Y);
+                Vector3::new((index % 16) as i32, y, (index / 16 % 16) as i32)
+            })
+            .collect()
+    }
+
     pub fn iter_subchunks(&self) -> impl Iterator<Item = &[u16; SUBCHUNK_VOLUME]> {
         self.blocks
             .chunks(SUBCHUNK_VOLUME)
@@ -232,7 +486,20 @@ impl Index<ChunkRelativeBlockCoordinates> for ChunkBlocks {
 }
 
 impl ChunkData {
+    /// Chunks saved by older versions are upgraded first, see `upgrade::upgrade_chunk`
     pub fn from_bytes(chunk_data: &[u8], at: Vector2<i32>) -> Result<Self, ChunkParsingError> {
+        // Block entities and light don't have a fixed layout, so they are read from the NBT tree
+        let mut nbt = Nbt::read(&mut &chunk_data[..])
+            .map_err(|e| ChunkParsingError::ErrorDeserializingChunk(e.to_string()))?;
+        let upgraded_data;
+        let chunk_data = if upgrade_chunk(&mut nbt.root_tag).map_err(ChunkParsingError::Upgrade)? {
+            upgraded_data = nbt.write();
+            &upgraded_data[..]
+        } else {
+            chunk_data
+        };
+        let root = nbt.root_tag;
+
         if fastnbt::from_bytes::<ChunkStatus>(chunk_data)
             .map_err(|_| ChunkParsingError::FailedReadStatus)?
             != ChunkStatus::Full
@@ -245,29 +512,49 @@ impl ChunkData {
 
         // this needs to be boxed, otherwise it will cause a stack-overflow
         let mut blocks = ChunkBlocks::empty_with_heightmap(chunk_data.heightmaps);
-        let mut block_index = 0; // which block we're currently at
 
         for section in chunk_data.sections.into_iter() {
-            let block_states = match section.block_states {
-                Some(states) => states,
-                None => continue, // TODO @lukas0008 this should instead fill all blocks with the only element of the palette
+            // Sections right below and above the world only hold light
+            let Some(block_states) = section.block_states else {
+                continue;
+            };
+            let Ok(section_index) = usize::try_from(section.y - i32::from(WORLD_LOWEST_Y) / 16)
+            else {
+                continue;
             };
+            if section_index >= blocks.subchunks_len() {
+                continue;
+            }
+            // which block we're currently at
+            let mut block_index = section_index * SUBCHUNK_VOLUME;
 
             let palette = block_states
                 .palette
                 .iter()
-                .map(|entry| match BlockState::new(&entry.name) {
+                .map(|entry| match get_block(&entry.name) {
                     // Block not found, Often the case when World has an newer or older version then block registry
                     None => BlockState::AIR,
-                    Some(state) => state,
+                    Some(block) => BlockState {
+                        state_id: entry
+                            .properties
+                            .as_ref()
+                            .map_or(block.default_state_id, |properties| {
+                                block.state_with_properties(properties)
+                            }),
+                        block_id: block.id,
+                    },
                 })
                 .collect::<Vec<_>>();
 
             let block_data = match block_states.data {
                 None => {
-                    // We skipped placing an empty subchunk.
-                    // We need to increase the y coordinate of the next subchunk being placed.
+                    // Sections made of a single block have no data, empty ones can be skipped
+                    let block = palette.first().map_or(0, BlockState::get_id);
+                    let start = block_index;
                     block_index += SUBCHUNK_VOLUME;
+                    if block != 0 {
+                        blocks.blocks[start..block_index].fill(block);
+                    }
                     continue;
                 }
                 Some(d) => d,
@@ -303,18 +590,228 @@ impl ChunkData {
 
                     // if `SUBCHUNK_VOLUME `is not divisible by `blocks_in_palette` the block_data
                     // can sometimes spill into other subchunks. We avoid that by aborting early
-                    if (block_index % SUBCHUNK_VOLUME) == 0 {
+                    if block_index.is_multiple_of(SUBCHUNK_VOLUME) {
                         break 'block_loop;
                     }
                 }
             }
         }
 
+        let sections = root.get_list("sections").map_or(&[][..], Vec::as_slice);
+        let blending_data = root
+            .get_compound("blending_data")
+            .and_then(BlendingData::read_nbt)
+            .map(|mut blending_data| {
+                blending_data.calculate(&blocks, |x, y, z| read_biome_nbt(sections, x, y, z));
+                blending_data
+            });
         Ok(ChunkData {
             blocks,
             position: at,
+            block_entities: root
+                .get_list("block_entities")
+                .map(|block_entities| read_block_entities_nbt(block_entities))
+                .unwrap_or_default(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::read_nbt(sections),
+            structures: root
+                .get_compound("structures")
+                .map(ChunkStructures::read_nbt)
+                .unwrap_or_default(),
+            blending_data,
+            block_ticks: root
+                .get_list("block_ticks")
+                .map(|ticks| TickQueue::read_nbt(ticks))
+                .unwrap_or_default(),
+            fluid_ticks: root
+                .get_list("fluid_ticks")
+                .map(|ticks| TickQueue::read_nbt(ticks))
+                .unwrap_or_default(),
+            inhabited_time: root.get_long("InhabitedTime").unwrap_or(0),
+            dirty: false,
         })
     }
+
+    /// Serializes the chunk into the NBT layout of vanilla region files
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let subchunks: Vec<_> = self.blocks.iter_subchunks().collect();
+        let sections = (0..LIGHT_SECTION_COUNT)
+            .filter_map(|light_index| {
+                let y = light_index as i32 - 1 + i32::from(WORLD_LOWEST_Y) / 16;
+                let mut section = light_index
+                    .checked_sub(1)
+                    .and_then(|index| subchunks.get(index))
+                    .map_or_else(NbtCompound::new, |subchunk| section_to_nbt(subchunk));
+                for (name, light) in [
+                    ("SkyLight", &self.light.sky_light),
+                    ("BlockLight", &self.light.block_light),
+                ] {
+                    if let Some(Some(data)) = light.get(light_index) {
+                        section.put(name.to_string(), NbtTag::ByteArray(data.to_vec().into()));
+                    }
+                }
+                if section.child_tags.is_empty() {
+                    return None;
+                }
+                section.put("Y".to_string(), NbtTag::Byte(y as i8));
+                Some(NbtTag::Compound(section))
+            })
+            .collect();
+
+        let mut heightmaps = NbtCompound::new();
+        heightmaps.put(
+            "MOTION_BLOCKING".to_string(),
+            NbtTag::LongArray(self.blocks.heightmap.motion_blocking.to_vec()),
+        );
+        heightmaps.put(
+            "WORLD_SURFACE".to_string(),
+            NbtTag::LongArray(self.blocks.heightmap.world_surface.to_vec()),
+        );
+
+        let block_entities = self
+            .block_entities
+            .iter()
+            .map(|(position, block_entity)| {
+                NbtTag::Compound(block_entity_to_nbt(block_entity.as_ref(), position))
+            })
+            .collect();
+
+        let mut chunk = NbtCompound::new();
+        chunk.put("DataVersion".to_string(), NbtTag::Int(WORLD_DATA_VERSION));
+        chunk.put("xPos".to_string(), NbtTag::Int(self.position.x));
+        chunk.put("zPos".to_string(), NbtTag::Int(self.position.z));
+        chunk.put(
+            "yPos".to_string(),
+            NbtTag::Int(i32::from(WORLD_LOWEST_Y) / 16),
+        );
+        chunk.put(
+            "Status".to_string(),
+            NbtTag::String("minecraft:full".to_string()),
+        );
+        // Without `isLightOn` vanilla relights the chunk, so the light is correct again after block changes
+        chunk.put("LastUpdate".to_string(), NbtTag::Long(0));
+        chunk.put(
+            "InhabitedTime".to_string(),
+            NbtTag::Long(self.inhabited_time),
+        );
+        chunk.put("sections".to_string(), NbtTag::List(sections));
+        chunk.put("Heightmaps".to_string(), NbtTag::Compound(heightmaps));
+        chunk.put("block_entities".to_string(), NbtTag::List(block_entities));
+        chunk.put(
+            "structures".to_string(),
+            NbtTag::Compound(self.structures.to_nbt()),
+        );
+        chunk.put(
+            "block_ticks".to_string(),
+            NbtTag::List(self.block_ticks.to_nbt()),
+        );
+        chunk.put(
+            "fluid_ticks".to_string(),
+            NbtTag::List(self.fluid_ticks.to_nbt()),
+        );
+        if let Some(blending_data) = &self.blending_data {
+            chunk.put(
+                "blending_data".to_string(),
+                NbtTag::Compound(blending_data.to_nbt()),
+            );
+        }
+        Nbt::new(String::new(), chunk).write().to_vec()
+    }
+}
+
+/// The biome at the block position relative to the chunk, biomes are stored every 4 blocks in the
+/// sections the same way as blocks are
+fn read_biome_nbt(sections: &[NbtTag], x: i32, y: i32, z: i32) -> Option<Biome> {
+    let section = sections
+        .iter()
+        .filter_map(NbtTag::extract_compound)
+        .find(|section| section.get_byte("Y").map(i32::from) == Some(y.div_euclid(16)))?;
+    let biomes = section.get_compound("biomes")?;
+    let palette = biomes.get_list("palette")?;
+    let index = match biomes.get_long_array("data") {
+        None => 0,
+        Some(data) => {
+            let bits = 64 - (palette.len() as u64 - 1).leading_zeros() as usize;
+            let per_long = 64 / bits;
+            let index = ((y.rem_euclid(16) / 4 * 4 + z / 4) * 4 + x / 4) as usize;
+            let long = *data.get(index / per_long)? as u64;
+            ((long >> (index % per_long * bits)) & ((1 << bits) - 1)) as usize
+        }
+    };
+    Biome::from_id(palette.get(index)?.extract_string()?)
+}
+
+/// Writes the blocks of a section as a palette and the indices into it, packed into longs.
+/// Sections with a single block have no data, we don't track biomes so every section is plains
+fn section_to_nbt(subchunk: &[u16; SUBCHUNK_VOLUME]) -> NbtCompound {
+    let mut palette: Vec<u16> = Vec::new();
+    let mut palette_indices: HashMap<u16, usize> = HashMap::new();
+    let indices: Vec<usize> = subchunk
+        .iter()
+        .map(|state_id| {
+            *palette_indices.entry(*state_id).or_insert_with(|| {
+                palette.push(*state_id);
+                palette.len() - 1
+            })
+        })
+        .collect();
+
+    let mut block_states = NbtCompound::new();
+    block_states.put(
+        "palette".to_string(),
+        NbtTag::List(palette.iter().map(|id| palette_entry_to_nbt(*id)).collect()),
+    );
+    if palette.len() > 1 {
+        // Same layout as when reading, entries never span two longs
+        let block_bit_size = max(4, 64 - (palette.len() as u64 - 1).leading_zeros()) as usize;
+        let blocks_in_long = 64 / block_bit_size;
+        let data = indices
+            .chunks(blocks_in_long)
+            .map(|blocks| {
+                blocks.iter().enumerate().fold(0u64, |long, (i, index)| {
+                    long | (*index as u64) << (i * block_bit_size)
+                }) as i64
+            })
+            .collect();
+        block_states.put("data".to_string(), NbtTag::LongArray(data));
+    }
+
+    let mut biomes = NbtCompound::new();
+    biomes.put(
+        "palette".to_string(),
+        NbtTag::List(vec![NbtTag::String("minecraft:plains".to_string())]),
+    );
+
+    let mut section = NbtCompound::new();
+    section.put("block_states".to_string(), NbtTag::Compound(block_states));
+    section.put("biomes".to_string(), NbtTag::Compound(biomes));
+    section
+}
+
+fn palette_entry_to_nbt(state_id: u16) -> NbtTag {
+    let mut entry = NbtCompound::new();
+    let Some(block) = get_block_by_state_id(state_id) else {
+        entry.put(
+            "Name".to_string(),
+            NbtTag::String("minecraft:air".to_string()),
+        );
+        return NbtTag::Compound(entry);
+    };
+    entry.put(
+        "Name".to_string(),
+        NbtTag::String(format!("minecraft:{}", block.name)),
+    );
+    let properties = block.state_properties(state_id);
+    if !properties.is_empty() {
+        let properties = properties
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), NbtTag::String(value.to_string())))
+            .collect();
+        entry.put("Properties".to_string(), NbtTag::Compound(properties));
+    }
+    NbtTag::Compound(entry)
 }
 
 #[derive(Error, Debug)]
@@ -325,4 +822,230 @@ pub enum ChunkParsingError {
     ChunkNotGenerated,
     #[error("Error deserializing chunk: {0}")]
     ErrorDeserializingChunk(String),
+    #[error("Failed to upgrade chunk: {0}")]
+    Upgrade(UpgradeError),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pumpkin_core::math::{position::WorldPosition, vector2::Vector2, vector3::Vector3};
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag, Nbt};
+
+    use crate::{
+        biome::Biome,
+        block::block_entity::{block_entity_data, block_entity_from_nbt},
+        generation::{
+            blender::{flat_blocks, BlendingData},
+            structure::{Structure, StructureGenerator},
+            Seed,
+        },
+    };
+
+    use super::{
+        read_biome_nbt, read_block_entities_nbt, read_entities_nbt, ticks::TickPriority,
+        write_entities_nbt, ChunkBlocks, ChunkData, ChunkLight, ChunkStructures, PointOfInterest,
+        TickQueue, LIGHT_SECTION_COUNT, LIGHT_SECTION_SIZE,
+    };
+
+    #[test]
+    fn block_entities_and_light_are_saved() {
+        let position = WorldPosition(Vector3::new(3, -60, 21));
+        let mut nbt = NbtCompound::new();
+        nbt.put("Items".to_string(), NbtTag::List(Vec::new()));
+        let mut light = ChunkLight {
+            sky_light: vec![None; LIGHT_SECTION_COUNT],
+            block_light: vec![None; LIGHT_SECTION_COUNT],
+        };
+        // The section below the world only holds light
+        light.sky_light[0] = Some(vec![0x77; LIGHT_SECTION_SIZE].into_boxed_slice());
+        light.block_light[5] = Some(vec![0x12; LIGHT_SECTION_SIZE].into_boxed_slice());
+        let chunk = ChunkData {
+            blocks: ChunkBlocks::default(),
+            position: Vector2::new(0, 1),
+            block_entities: HashMap::from([(
+                position,
+                block_entity_from_nbt("minecraft:furnace", &nbt),
+            )]),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: light.clone(),
+            structures: ChunkStructures::default(),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 72_000,
+            dirty: true,
+        };
+
+        let root = Nbt::read(&mut &chunk.to_bytes()[..]).unwrap().root_tag;
+        let block_entities = read_block_entities_nbt(root.get_list("block_entities").unwrap());
+        let furnace = &block_entities[&position];
+        assert_eq!(furnace.id(), "minecraft:furnace");
+        assert!(block_entity_data(furnace.as_ref())
+            .get_list("Items")
+            .is_some());
+        let read_light = ChunkLight::read_nbt(root.get_list("sections").unwrap());
+        assert_eq!(read_light.sky_light, light.sky_light);
+        assert_eq!(read_light.block_light, light.block_light);
+        assert_eq!(root.get_long("InhabitedTime"), Some(72_000));
+    }
+
+    #[test]
+    fn structures_are_saved() {
+        let generator = StructureGenerator::new(Seed(1));
+        let position = generator
+            .locate(Structure::Stronghold, &Vector2::new(0, 0))
+            .unwrap();
+        let structures = generator.chunk_structures(&position);
+        assert!(structures.starts.contains_key(&Structure::Stronghold));
+        let chunk = ChunkData {
+            blocks: ChunkBlocks::default(),
+            position,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: structures.clone(),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
+        };
+
+        let root = Nbt::read(&mut &chunk.to_bytes()[..]).unwrap().root_tag;
+        let read = ChunkStructures::read_nbt(root.get_compound("structures").unwrap());
+        assert_eq!(read, structures);
+    }
+
+    #[test]
+    fn blending_data_is_saved() {
+        let blocks = flat_blocks(70);
+        let mut blending_data = BlendingData::new(-4, 20);
+        // Sections are saved as plains
+        blending_data.calculate(&blocks, |_, _, _| Some(Biome::Plains));
+        let chunk = ChunkData {
+            blocks,
+            position: Vector2::new(2, -3),
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: ChunkStructures::default(),
+            blending_data: Some(blending_data.clone()),
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
+        };
+
+        let root = Nbt::read(&mut &chunk.to_bytes()[..]).unwrap().root_tag;
+        let mut read = BlendingData::read_nbt(root.get_compound("blending_data").unwrap()).unwrap();
+        let sections = root.get_list("sections").unwrap();
+        read.calculate(&chunk.blocks, |x, y, z| read_biome_nbt(sections, x, y, z));
+        assert_eq!(read, blending_data);
+    }
+
+    #[test]
+    fn scheduled_ticks_are_saved() {
+        let mut block_ticks = TickQueue::default();
+        block_ticks.schedule(
+            WorldPosition(Vector3::new(1, 64, 2)),
+            "minecraft:repeater",
+            4,
+            TickPriority::High,
+        );
+        let mut fluid_ticks = TickQueue::default();
+        fluid_ticks.schedule(
+            WorldPosition(Vector3::new(3, 10, 5)),
+            "minecraft:water",
+            5,
+            TickPriority::Normal,
+        );
+        let chunk = ChunkData {
+            blocks: ChunkBlocks::default(),
+            position: Vector2::new(0, 0),
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: ChunkStructures::default(),
+            blending_data: None,
+            block_ticks: block_ticks.clone(),
+            fluid_ticks: fluid_ticks.clone(),
+            inhabited_time: 0,
+            dirty: true,
+        };
+
+        let root = Nbt::read(&mut &chunk.to_bytes()[..]).unwrap().root_tag;
+        let read = TickQueue::read_nbt(root.get_list("block_ticks").unwrap());
+        assert_eq!(read.to_nbt(), block_ticks.to_nbt());
+        let read = TickQueue::read_nbt(root.get_list("fluid_ticks").unwrap());
+        assert_eq!(read.to_nbt(), fluid_ticks.to_nbt());
+    }
+
+    #[test]
+    fn points_of_interest() {
+        let record = |kind: &str, pos: Vec<i32>| {
+            let mut record = NbtCompound::new();
+            record.put("type".to_string(), NbtTag::String(kind.to_string()));
+            record.put("pos".to_string(), NbtTag::IntArray(pos));
+            record.put("free_tickets".to_string(), NbtTag::Int(1));
+            NbtTag::Compound(record)
+        };
+        let section = |valid: bool, records: Vec<NbtTag>| {
+            let mut section = NbtCompound::new();
+            section.put("Valid".to_string(), NbtTag::Byte(i8::from(valid)));
+            section.put("Records".to_string(), NbtTag::List(records));
+            NbtTag::Compound(section)
+        };
+        let mut sections = NbtCompound::new();
+        sections.put(
+            "4".to_string(),
+            section(true, vec![record("minecraft:home", vec![1, 70, 2])]),
+        );
+        sections.put(
+            "5".to_string(),
+            section(false, vec![record("minecraft:bell", vec![1, 80, 2])]),
+        );
+        let mut chunk = NbtCompound::new();
+        chunk.put("Sections".to_string(), NbtTag::Compound(sections));
+
+        let points = PointOfInterest::read_chunk_nbt(&chunk);
+        assert!(
+            points
+                == vec![PointOfInterest {
+                    position: WorldPosition(Vector3::new(1, 70, 2)),
+                    kind: "minecraft:home".to_string(),
+                    free_tickets: 1,
+                }]
+        );
+    }
+
+    #[test]
+    fn entities() {
+        let mut entity = NbtCompound::new();
+        entity.put(
+            "id".to_string(),
+            NbtTag::String("minecraft:armor_stand".to_string()),
+        );
+        let mut chunk = NbtCompound::new();
+        chunk.put(
+            "Entities".to_string(),
+            NbtTag::List(vec![NbtTag::Compound(entity.clone())]),
+        );
+
+        assert_eq!(read_entities_nbt(&chunk), vec![entity.clone()]);
+        assert!(read_entities_nbt(&NbtCompound::new()).is_empty());
+
+        let written = write_entities_nbt(&Vector2::new(-3, 7), &[entity.clone()]);
+        assert_eq!(written.get_int_array("Position"), Some(&vec![-3, 7]));
+        assert_eq!(read_entities_nbt(&written), vec![entity]);
+    }
 }
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, HashMap};
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+/// How urgent a scheduled tick is, ticks which are due at the same time run from the most to the
+/// least urgent. Saved as a number from -3 to 3
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TickPriority {
+    ExtremelyHigh = -3,
+    VeryHigh = -2,
+    High = -1,
+    #[default]
+    Normal = 0,
+    Low = 1,
+    VeryLow = 2,
+    ExtremelyLow = 3,
+}
+
+impl TickPriority {
+    const ALL: [Self; 7] = [
+        Self::ExtremelyHigh,
+        Self::VeryHigh,
+        Self::High,
+        Self::Normal,
+        Self::Low,
+        Self::VeryLow,
+        Self::ExtremelyLow,
+    ];
+
+    /// Values outside of the range are clamped
+    pub fn from_value(value: i32) -> Self {
+        Self::ALL[(value.clamp(-3, 3) + 3) as usize]
+    }
+
+    pub fn value(self) -> i32 {
+        self as i32
+    }
+}
+
+/// A block or fluid update which is due
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduledTick {
+    pub position: WorldPosition,
+    /// The block or fluid the tick is for, e.g. `minecraft:repeater`. Nothing happens if the block
+    /// was replaced in the meantime
+    pub target: String,
+    pub priority: TickPriority,
+}
+
+/// When a tick is due, ticks due at the same time are ordered by their priority and then by when
+/// they were scheduled
+type TickKey = (u64, TickPriority, u64);
+
+/// The block or fluid ticks scheduled in a chunk, saved as the `block_ticks` and `fluid_ticks`
+/// lists of vanilla chunks. Delays only count down while the chunk is ticked
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct TickQueue {
+    /// How often the queue was advanced since the chunk was loaded
+    time: u64,
+    ticks: BTreeMap<TickKey, ScheduledTick>,
+    /// A block or fluid has at most one tick scheduled per position
+    scheduled: HashMap<(WorldPosition, String), TickKey>,
+    scheduled_count: u64,
+}
+
+impl TickQueue {
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    pub fn is_scheduled(&self, position: &WorldPosition, target: &str) -> bool {
+        self.scheduled
+            .contains_key(&(*position, target.to_string()))
+    }
+
+    /// Schedules a tick in `delay` ticks of the chunk. Returns false if the target already has a
+    /// tick scheduled at the position, the earlier one is kept like in vanilla
+    pub fn schedule(
+        &mut self,
+        position: WorldPosition,
+        target: &str,
+        delay: u32,
+        priority: TickPriority,
+    ) -> bool {
+        let id = (position, target.to_string());
+        if self.scheduled.contains_key(&id) {
+            return false;
+        }
+        let key = (self.time + u64::from(delay), priority, self.scheduled_count);
+        self.scheduled_count += 1;
+        self.scheduled.insert(id, key);
+        self.ticks.insert(
+            key,
+            ScheduledTick {
+                position,
+                target: target.to_string(),
+                priority,
+            },
+        );
+        true
+    }
+
+    /// Counts down one tick and takes the ticks which are due, in the order they have to run
+    pub fn advance(&mut self) -> Vec<ScheduledTick> {
+        self.time += 1;
+        let mut due = Vec::new();
+        while let Some(entry) = self.ticks.first_entry() {
+            if entry.key().0 > self.time {
+                break;
+            }
+            let tick = entry.remove();
+            self.scheduled.remove(&(tick.position, tick.target.clone()));
+            due.push(tick);
+        }
+        due
+    }
+
+    /// Reads a `block_ticks` or `fluid_ticks` list, the ticks keep the order they were saved in
+    pub fn read_nbt(ticks: &[NbtTag]) -> Self {
+        let mut queue = Self::default();
+        for tick in ticks.iter().filter_map(NbtTag::extract_compound) {
+            let (Some(target), Some(x), Some(y), Some(z)) = (
+                tick.get_string("i"),
+                tick.get_int("x"),
+                tick.get_int("y"),
+                tick.get_int("z"),
+            ) else {
+                continue;
+            };
+            // Overdue ticks run right away
+            let delay = u32::try_from(tick.get_int("t").unwrap_or(0)).unwrap_or(0);
+            let priority = TickPriority::from_value(tick.get_int("p").unwrap_or(0));
+            queue.schedule(
+                WorldPosition(Vector3::new(x, y, z)),
+                target,
+                delay,
+                priority,
+            );
+        }
+        queue
+    }
+
+    pub fn to_nbt(&self) -> Vec<NbtTag> {
+        self.ticks
+            .iter()
+            .map(|((due, _, _), tick)| {
+                let mut nbt = NbtCompound::new();
+                nbt.put("i".to_string(), NbtTag::String(tick.target.clone()));
+                nbt.put("x".to_string(), NbtTag::Int(tick.position.0.x));
+                nbt.put("y".to_string(), NbtTag::Int(tick.position.0.y));
+                nbt.put("z".to_string(), NbtTag::Int(tick.position.0.z));
+                let delay = i32::try_from(due - self.time).unwrap_or(i32::MAX);
+                nbt.put("t".to_string(), NbtTag::Int(delay));
+                nbt.put("p".to_string(), NbtTag::Int(tick.priority.value()));
+                NbtTag::Compound(nbt)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use super::{TickPriority, TickQueue};
+
+    fn pos(x: i32) -> WorldPosition {
+        WorldPosition(Vector3::new(x, 64, 0))
+    }
+
+    fn targets(queue: &mut TickQueue) -> Vec<i32> {
+        queue
+            .advance()
+            .into_iter()
+            .map(|tick| tick.position.0.x)
+            .collect()
+    }
+
+    #[test]
+    fn ticks_run_after_their_delay() {
+        let mut queue = TickQueue::default();
+        queue.schedule(pos(0), "minecraft:repeater", 2, TickPriority::Normal);
+        queue.schedule(pos(1), "minecraft:water", 1, TickPriority::Normal);
+        assert_eq!(targets(&mut queue), vec![1]);
+        assert_eq!(targets(&mut queue), vec![0]);
+        assert!(queue.is_empty());
+        assert!(targets(&mut queue).is_empty());
+    }
+
+    #[test]
+    fn urgent_ticks_run_first() {
+        let mut queue = TickQueue::default();
+        queue.schedule(pos(0), "minecraft:repeater", 1, TickPriority::Normal);
+        queue.schedule(pos(1), "minecraft:repeater", 1, TickPriority::Low);
+        queue.schedule(pos(2), "minecraft:repeater", 1, TickPriority::Normal);
+        queue.schedule(pos(3), "minecraft:repeater", 1, TickPriority::ExtremelyHigh);
+        assert_eq!(targets(&mut queue), vec![3, 0, 2, 1]);
+    }
+
+    #[test]
+    fn one_tick_per_position() {
+        let mut queue = TickQueue::default();
+        assert!(queue.schedule(pos(0), "minecraft:water", 5, TickPriority::Normal));
+        assert!(!queue.schedule(pos(0), "minecraft:water", 1, TickPriority::Normal));
+        assert!(queue.schedule(pos(0), "minecraft:lava", 1, TickPriority::Normal));
+        assert!(queue.is_scheduled(&pos(0), "minecraft:water"));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(targets(&mut queue), vec![0]);
+        assert!(!queue.is_scheduled(&pos(0), "minecraft:lava"));
+        assert!(queue.schedule(pos(0), "minecraft:lava", 1, TickPriority::Normal));
+    }
+
+    #[test]
+    fn nbt_keeps_the_remaining_delay() {
+        let mut queue = TickQueue::default();
+        queue.schedule(pos(0), "minecraft:repeater", 3, TickPriority::High);
+        queue.schedule(pos(1), "minecraft:water", 5, TickPriority::Normal);
+        queue.advance();
+
+        let mut read = TickQueue::read_nbt(&queue.to_nbt());
+        assert_eq!(read.to_nbt(), queue.to_nbt());
+        assert!(targets(&mut read).is_empty());
+        assert_eq!(targets(&mut read), vec![0]);
+        read.advance();
+        assert_eq!(targets(&mut read), vec![1]);
+    }
+
+    #[test]
+    fn priorities_are_clamped() {
+        assert_eq!(TickPriority::from_value(-10), TickPriority::ExtremelyHigh);
+        assert_eq!(TickPriority::from_value(1), TickPriority::Low);
+        assert_eq!(TickPriority::from_value(3).value(), 3);
+    }
+}
@@ -0,0 +1,337 @@
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use thiserror::Error;
+
+use super::WORLD_DATA_VERSION;
+
+/// 1.18, older chunks store their sections in a different layout
+pub const MIN_UPGRADABLE_DATA_VERSION: i32 = 2860;
+
+/// Changes the chunk saved by versions before the data version to the layout of that version
+type Migration = fn(&mut NbtCompound);
+
+/// Applied in order to every chunk with a lower data version
+const MIGRATIONS: &[(i32, Migration)] = &[
+    // 1.20: statuses are namespaced and signs got a front and a back side
+    (3463, namespace_status),
+    (3463, upgrade_signs),
+    // 1.20.3: grass was renamed to short grass
+    (3698, rename_grass),
+    // 1.20.5: item stacks use components instead of tags
+    (3837, upgrade_container_items),
+];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UpgradeError {
+    #[error("Chunk has no data version")]
+    MissingDataVersion,
+    #[error("Chunks of data version {0} are too old to be upgraded, the oldest supported version is 1.18")]
+    TooOld(i32),
+}
+
+/// Upgrades chunk NBT written by an older version to the current layout.
+/// Returns whether anything had to be upgraded, chunks from newer versions are left as they are
+pub fn upgrade_chunk(chunk: &mut NbtCompound) -> Result<bool, UpgradeError> {
+    let data_version = chunk
+        .get_int("DataVersion")
+        .ok_or(UpgradeError::MissingDataVersion)?;
+    if data_version >= WORLD_DATA_VERSION {
+        return Ok(false);
+    }
+    if data_version < MIN_UPGRADABLE_DATA_VERSION {
+        return Err(UpgradeError::TooOld(data_version));
+    }
+    for (version, migration) in MIGRATIONS {
+        if data_version < *version {
+            migration(chunk);
+        }
+    }
+    if let Some(version) = chunk.get_mut("DataVersion") {
+        *version = NbtTag::Int(WORLD_DATA_VERSION);
+    }
+    Ok(true)
+}
+
+/// The compounds in the list, nothing if there is no such list
+fn compounds_mut<'a>(
+    compound: &'a mut NbtCompound,
+    name: &str,
+) -> impl Iterator<Item = &'a mut NbtCompound> {
+    let list = match compound.get_mut(name) {
+        Some(NbtTag::List(list)) => Some(list),
+        _ => None,
+    };
+    list.into_iter().flatten().filter_map(|tag| match tag {
+        NbtTag::Compound(compound) => Some(compound),
+        _ => None,
+    })
+}
+
+fn namespace_status(chunk: &mut NbtCompound) {
+    if let Some(NbtTag::String(status)) = chunk.get_mut("Status") {
+        if !status.contains(':') {
+            *status = format!("minecraft:{status}");
+        }
+    }
+}
+
+fn rename_grass(chunk: &mut NbtCompound) {
+    for section in compounds_mut(chunk, "sections") {
+        let Some(NbtTag::Compound(block_states)) = section.get_mut("block_states") else {
+            continue;
+        };
+        for entry in compounds_mut(block_states, "palette") {
+            if let Some(NbtTag::String(name)) = entry.get_mut("Name") {
+                if name == "minecraft:grass" {
+                    *name = "minecraft:short_grass".to_string();
+                }
+            }
+        }
+    }
+}
+
+/// Moves the four lines, the color and the glow of signs to the front text, the back stays empty
+fn upgrade_signs(chunk: &mut NbtCompound) {
+    let empty_line = || NbtTag::String("\"\"".to_string());
+    for block_entity in compounds_mut(chunk, "block_entities") {
+        if !matches!(
+            block_entity.get_string("id").map(String::as_str),
+            Some("minecraft:sign" | "minecraft:hanging_sign")
+        ) || block_entity.get("front_text").is_some()
+        {
+            continue;
+        }
+        let messages = ["Text1", "Text2", "Text3", "Text4"]
+            .iter()
+            .map(|line| block_entity.remove(line).unwrap_or_else(empty_line))
+            .collect();
+        let color = block_entity
+            .remove("Color")
+            .unwrap_or_else(|| NbtTag::String("black".to_string()));
+        let glowing = block_entity
+            .remove("GlowingText")
+            .unwrap_or(NbtTag::Byte(0));
+
+        let mut front_text = NbtCompound::new();
+        front_text.put("messages".to_string(), NbtTag::List(messages));
+        front_text.put("color".to_string(), color);
+        front_text.put("has_glowing_text".to_string(), glowing);
+        let mut back_text = NbtCompound::new();
+        back_text.put("messages".to_string(), NbtTag::List(vec![empty_line(); 4]));
+        back_text.put("color".to_string(), NbtTag::String("black".to_string()));
+        back_text.put("has_glowing_text".to_string(), NbtTag::Byte(0));
+        block_entity.put("front_text".to_string(), NbtTag::Compound(front_text));
+        block_entity.put("back_text".to_string(), NbtTag::Compound(back_text));
+        block_entity.put("is_waxed".to_string(), NbtTag::Byte(0));
+    }
+}
+
+fn upgrade_container_items(chunk: &mut NbtCompound) {
+    for block_entity in compounds_mut(chunk, "block_entities") {
+        for item in compounds_mut(block_entity, "Items") {
+            upgrade_item(item);
+        }
+    }
+}
+
+/// Turns the count into an int and moves the tags we know of into components, other tags are dropped
+fn upgrade_item(item: &mut NbtCompound) {
+    if let Some(count) = item.remove("Count").and_then(|count| count.extract_byte()) {
+        item.put("count".to_string(), NbtTag::Int(i32::from(count)));
+    }
+    let Some(NbtTag::Compound(tag)) = item.remove("tag") else {
+        return;
+    };
+    let mut components = NbtCompound::new();
+    if let Some(damage) = tag.get_int("Damage").filter(|damage| *damage > 0) {
+        components.put("minecraft:damage".to_string(), NbtTag::Int(damage));
+    }
+    if let Some(name) = tag
+        .get_compound("display")
+        .and_then(|display| display.get_string("Name"))
+    {
+        components.put(
+            "minecraft:custom_name".to_string(),
+            NbtTag::String(name.clone()),
+        );
+    }
+    if tag.get_bool("Unbreakable").unwrap_or(false) {
+        components.put(
+            "minecraft:unbreakable".to_string(),
+            NbtTag::Compound(NbtCompound::new()),
+        );
+    }
+    if let Some(repair_cost) = tag.get_int("RepairCost") {
+        components.put(
+            "minecraft:repair_cost".to_string(),
+            NbtTag::Int(repair_cost),
+        );
+    }
+    for (old, new) in [
+        ("Enchantments", "minecraft:enchantments"),
+        ("StoredEnchantments", "minecraft:stored_enchantments"),
+    ] {
+        let Some(enchantments) = tag.get_list(old) else {
+            continue;
+        };
+        let levels = enchantments
+            .iter()
+            .filter_map(NbtTag::extract_compound)
+            .filter_map(|enchantment| {
+                let level = enchantment
+                    .get_short("lvl")
+                    .map(i32::from)
+                    .or_else(|| enchantment.get_int("lvl"))?;
+                Some((enchantment.get_string("id")?.clone(), NbtTag::Int(level)))
+            })
+            .collect();
+        let mut compound = NbtCompound::new();
+        compound.put("levels".to_string(), NbtTag::Compound(levels));
+        components.put(new.to_string(), NbtTag::Compound(compound));
+    }
+    if !components.child_tags.is_empty() {
+        item.put("components".to_string(), NbtTag::Compound(components));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+    use super::{upgrade_chunk, UpgradeError};
+    use crate::chunk::WORLD_DATA_VERSION;
+
+    fn compound(tags: Vec<(&str, NbtTag)>) -> NbtCompound {
+        tags.into_iter()
+            .map(|(name, tag)| (name.to_string(), tag))
+            .collect()
+    }
+
+    fn string(value: &str) -> NbtTag {
+        NbtTag::String(value.to_string())
+    }
+
+    /// A chunk like 1.19 saved it, with grass, a sign and a chest
+    fn old_chunk() -> NbtCompound {
+        let palette = vec![
+            NbtTag::Compound(compound(vec![("Name", string("minecraft:air"))])),
+            NbtTag::Compound(compound(vec![("Name", string("minecraft:grass"))])),
+        ];
+        let block_states = compound(vec![("palette", NbtTag::List(palette))]);
+        let section = compound(vec![
+            ("Y", NbtTag::Byte(0)),
+            ("block_states", NbtTag::Compound(block_states)),
+        ]);
+        let sign = compound(vec![
+            ("id", string("minecraft:sign")),
+            ("Text1", string("\"Hello\"")),
+            ("Color", string("red")),
+        ]);
+        let enchantment = compound(vec![
+            ("id", string("minecraft:sharpness")),
+            ("lvl", NbtTag::Short(3)),
+        ]);
+        let tag = compound(vec![
+            ("Damage", NbtTag::Int(5)),
+            (
+                "Enchantments",
+                NbtTag::List(vec![NbtTag::Compound(enchantment)]),
+            ),
+        ]);
+        let item = compound(vec![
+            ("id", string("minecraft:iron_sword")),
+            ("Count", NbtTag::Byte(1)),
+            ("Slot", NbtTag::Byte(2)),
+            ("tag", NbtTag::Compound(tag)),
+        ]);
+        let chest = compound(vec![
+            ("id", string("minecraft:chest")),
+            ("Items", NbtTag::List(vec![NbtTag::Compound(item)])),
+        ]);
+        compound(vec![
+            ("DataVersion", NbtTag::Int(3120)),
+            ("Status", string("full")),
+            ("sections", NbtTag::List(vec![NbtTag::Compound(section)])),
+            (
+                "block_entities",
+                NbtTag::List(vec![NbtTag::Compound(sign), NbtTag::Compound(chest)]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn upgrades_old_chunks() {
+        let mut chunk = old_chunk();
+        assert_eq!(upgrade_chunk(&mut chunk), Ok(true));
+        assert_eq!(chunk.get_int("DataVersion"), Some(WORLD_DATA_VERSION));
+        assert_eq!(
+            chunk.get_string("Status").map(String::as_str),
+            Some("minecraft:full")
+        );
+
+        let section = chunk.get_list("sections").unwrap()[0]
+            .extract_compound()
+            .unwrap();
+        let palette = section
+            .get_compound("block_states")
+            .unwrap()
+            .get_list("palette")
+            .unwrap();
+        let grass = palette[1].extract_compound().unwrap();
+        assert_eq!(
+            grass.get_string("Name").map(String::as_str),
+            Some("minecraft:short_grass")
+        );
+
+        let block_entities = chunk.get_list("block_entities").unwrap();
+        let sign = block_entities[0].extract_compound().unwrap();
+        assert!(sign.get("Text1").is_none());
+        let front_text = sign.get_compound("front_text").unwrap();
+        assert_eq!(
+            front_text.get_string("color").map(String::as_str),
+            Some("red")
+        );
+        let messages = front_text.get_list("messages").unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(
+            messages[0].extract_string().map(String::as_str),
+            Some("\"Hello\"")
+        );
+        assert!(sign.get_compound("back_text").is_some());
+
+        let chest = block_entities[1].extract_compound().unwrap();
+        let item = chest.get_list("Items").unwrap()[0]
+            .extract_compound()
+            .unwrap();
+        assert_eq!(item.get_int("count"), Some(1));
+        assert_eq!(item.get_byte("Slot"), Some(2));
+        assert!(item.get("tag").is_none());
+        let components = item.get_compound("components").unwrap();
+        assert_eq!(components.get_int("minecraft:damage"), Some(5));
+        let levels = components
+            .get_compound("minecraft:enchantments")
+            .and_then(|enchantments| enchantments.get_compound("levels"))
+            .unwrap();
+        assert_eq!(levels.get_int("minecraft:sharpness"), Some(3));
+    }
+
+    #[test]
+    fn current_chunks_are_untouched() {
+        let mut chunk = compound(vec![
+            ("DataVersion", NbtTag::Int(WORLD_DATA_VERSION)),
+            ("Status", string("full")),
+        ]);
+        let before = chunk.clone();
+        assert_eq!(upgrade_chunk(&mut chunk), Ok(false));
+        assert!(chunk == before);
+    }
+
+    #[test]
+    fn too_old_chunks() {
+        let mut chunk = compound(vec![("DataVersion", NbtTag::Int(2586))]);
+        assert_eq!(upgrade_chunk(&mut chunk), Err(UpgradeError::TooOld(2586)));
+        assert_eq!(
+            upgrade_chunk(&mut NbtCompound::new()),
+            Err(UpgradeError::MissingDataVersion)
+        );
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+use dashmap::DashMap;
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_nbt::Nbt;
+use tokio::{
+    runtime::Handle,
+    sync::{mpsc, RwLock},
+};
+
+use crate::{
+    chunk::{light::light_chunk, ChunkData, ChunkParsingError, ChunkReadingError, ChunkStorage},
+    generation::{
+        blender::{Blender, BlendingData},
+        WorldGenerator,
+    },
+    level::LevelFolder,
+};
+
+/// Workers pause once this many chunks wait to be handed to the async runtime,
+/// so slow consumers don't pile up finished chunks in memory
+const MAX_PENDING_DELIVERIES: usize = 256;
+
+pub type ChunkSender = mpsc::Sender<Arc<RwLock<ChunkData>>>;
+
+/// Someone waiting for a chunk
+struct ChunkWaiter {
+    sender: ChunkSender,
+    rt: Handle,
+    /// Requests for players are dropped once no player watches the chunk anymore
+    cancellable: bool,
+}
+
+struct PendingChunk<W> {
+    priority: u64,
+    waiters: Vec<W>,
+}
+
+/// Chunks waiting for a worker, lower priorities come first and equal ones in request order.
+/// Requesting a queued chunk again adds the waiter to it, it only moves up if the new priority is lower
+struct ChunkQueue<W> {
+    heap: BinaryHeap<Reverse<(u64, u64, i32, i32)>>,
+    pending: HashMap<Vector2<i32>, PendingChunk<W>>,
+    sequence: u64,
+}
+
+impl<W> Default for ChunkQueue<W> {
+    fn default() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            pending: HashMap::new(),
+            sequence: 0,
+        }
+    }
+}
+
+impl<W> ChunkQueue<W> {
+    fn push(&mut self, pos: Vector2<i32>, priority: u64, waiter: W) {
+        let pending = self.pending.entry(pos).or_insert(PendingChunk {
+            priority: u64::MAX,
+            waiters: Vec::new(),
+        });
+        pending.waiters.push(waiter);
+        if priority < pending.priority {
+            pending.priority = priority;
+            self.sequence += 1;
+            self.heap
+                .push(Reverse((priority, self.sequence, pos.x, pos.z)));
+        }
+    }
+
+    fn pop(&mut self) -> Option<(Vector2<i32>, Vec<W>)> {
+        while let Some(Reverse((priority, _, x, z))) = self.heap.pop() {
+            let pos = Vector2::new(x, z);
+            // Chunks which moved up are in the heap twice, the outdated entry is skipped
+            if self
+                .pending
+                .get(&pos)
+                .is_some_and(|pending| pending.priority == priority)
+            {
+                return self
+                    .pending
+                    .remove(&pos)
+                    .map(|pending| (pos, pending.waiters));
+            }
+        }
+        None
+    }
+
+    fn len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    queue: ChunkQueue<ChunkWaiter>,
+    /// Chunks handed to the async runtime which were not received yet
+    deliveries: usize,
+    shutdown: bool,
+}
+
+struct Shared {
+    state: Mutex<PoolState>,
+    /// Wakes workers up when chunks get queued, deliveries finish or the pool shuts down
+    wake: Condvar,
+    loaded_chunks: Arc<DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>>,
+    chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    chunk_storage: Arc<dyn ChunkStorage>,
+    level_folder: LevelFolder,
+    world_gen: Arc<dyn WorldGenerator>,
+    /// The blending data of every chunk next to a generated one, `None` for chunks which are new
+    /// or don't exist. Chunks only become old by upgrading the world, so entries never change
+    blending_data: DashMap<Vector2<i32>, Option<Arc<BlendingData>>>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        // The state stays consistent even if a worker panicked while holding the lock
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Loads and generates chunks on dedicated threads, so neither the async runtime nor the rayon pool
+/// used by the tick loop is blocked by world generation.
+/// Chunks closest to the players requesting them are handled first
+pub struct ChunkWorkerPool {
+    shared: Arc<Shared>,
+}
+
+impl ChunkWorkerPool {
+    pub fn new(
+        loaded_chunks: Arc<DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>>,
+        chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+        chunk_storage: Arc<dyn ChunkStorage>,
+        level_folder: LevelFolder,
+        world_gen: Arc<dyn WorldGenerator>,
+    ) -> Self {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(PoolState::default()),
+            wake: Condvar::new(),
+            loaded_chunks,
+            chunk_watchers,
+            chunk_storage,
+            level_folder,
+            world_gen,
+            blending_data: DashMap::new(),
+        });
+        // One core is left for the tick loop
+        let workers = thread::available_parallelism()
+            .map_or(1, |cores| cores.get().saturating_sub(1))
+            .max(1);
+        for index in 0..workers {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name(format!("chunk-worker-{index}"))
+                .spawn(move || work(&shared))
+                .expect("Failed to spawn chunk worker");
+        }
+        Self { shared }
+    }
+
+    /// Queues the chunk, it is sent through the channel once it is loaded or generated.
+    /// Lower priorities are handled first, cancellable requests are dropped if no player watches the chunk
+    /// by the time a worker gets to it. Never blocks
+    pub fn request(
+        &self,
+        pos: Vector2<i32>,
+        priority: u64,
+        cancellable: bool,
+        sender: ChunkSender,
+        rt: &Handle,
+    ) {
+        if let Some(chunk) = self.shared.loaded_chunks.get(&pos) {
+            let chunk = chunk.value().clone();
+            rt.spawn(async move {
+                let _ = sender
+                    .send(chunk)
+                    .await
+                    .inspect_err(|err| log::error!("unable to send chunk to channel: {}", err));
+            });
+            return;
+        }
+        let waiter = ChunkWaiter {
+            sender,
+            rt: rt.clone(),
+            cancellable,
+        };
+        self.shared.lock().queue.push(pos, priority, waiter);
+        self.shared.wake.notify_one();
+    }
+
+    /// How many chunks wait for a worker
+    pub fn queued_chunks(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+}
+
+impl Drop for ChunkWorkerPool {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.wake.notify_all();
+    }
+}
+
+fn work(shared: &Arc<Shared>) {
+    loop {
+        let (pos, waiters) = {
+            let mut state = shared.lock();
+            loop {
+                if state.shutdown {
+                    return;
+                }
+                if state.deliveries < MAX_PENDING_DELIVERIES {
+                    if let Some(job) = state.queue.pop() {
+                        break job;
+                    }
+                }
+                state = shared
+                    .wake
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+        };
+
+        // Players which disconnected or moved away don't need the chunk anymore
+        let watched = shared.chunk_watchers.contains_key(&pos);
+        let waiters: Vec<ChunkWaiter> = waiters
+            .into_iter()
+            .filter(|waiter| !waiter.sender.is_closed() && (watched || !waiter.cancellable))
+            .collect();
+        if waiters.is_empty() {
+            log::trace!("Cancelled loading chunk {:?}", pos);
+            continue;
+        }
+
+        let chunk = load_or_generate(shared, pos);
+        shared.lock().deliveries += waiters.len();
+        for waiter in waiters {
+            let chunk = chunk.clone();
+            let shared = shared.clone();
+            waiter.rt.spawn(async move {
+                let _ = waiter
+                    .sender
+                    .send(chunk)
+                    .await
+                    .inspect_err(|err| log::error!("unable to send chunk to channel: {}", err));
+                shared.lock().deliveries -= 1;
+                shared.wake.notify_one();
+            });
+        }
+    }
+}
+
+fn load_or_generate(shared: &Shared, pos: Vector2<i32>) -> Arc<RwLock<ChunkData>> {
+    if let Some(chunk) = shared.loaded_chunks.get(&pos) {
+        return chunk.value().clone();
+    }
+    let mut chunk = match shared.chunk_storage.read_chunk(&shared.level_folder, &pos) {
+        Ok(chunk) => Some(chunk),
+        Err(
+            ChunkReadingError::ChunkNotExist
+            | ChunkReadingError::ParsingError(ChunkParsingError::ChunkNotGenerated),
+        ) => None,
+        Err(ChunkReadingError::ParsingError(ChunkParsingError::Upgrade(err))) => {
+            log::warn!("Can't upgrade chunk {:?} (regenerating): {}", pos, err);
+            // The old chunk stays on disk unless the generated one is changed
+            let mut chunk = shared.world_gen.generate_chunk(pos);
+            chunk.dirty = false;
+            Some(chunk)
+        }
+        Err(err) => {
+            log::error!("Failed to read chunk (regenerating) {:?}: {:?}", pos, err);
+            None
+        }
+    }
+    .unwrap_or_else(|| {
+        let blender = Blender::new(pos, |pos| blending_data(shared, pos));
+        shared.world_gen.generate_blended_chunk(pos, blender)
+    });
+    // Generated chunks and chunks saved without light are lit from scratch
+    if chunk.light.is_empty() {
+        light_chunk(&mut chunk);
+    }
+
+    // Another worker may have loaded the chunk for a request which came in while this one was working
+    shared
+        .loaded_chunks
+        .entry(pos)
+        .or_insert_with(|| Arc::new(RwLock::new(chunk)))
+        .value()
+        .clone()
+}
+
+/// The blending data of the chunk, saved chunks are only fully read if they have some
+fn blending_data(shared: &Shared, pos: Vector2<i32>) -> Option<Arc<BlendingData>> {
+    if let Some(data) = shared.blending_data.get(&pos) {
+        return data.clone();
+    }
+    let data = if let Some(chunk) = shared.loaded_chunks.get(&pos) {
+        chunk.blocking_read().blending_data.clone().map(Arc::new)
+    } else {
+        shared
+            .chunk_storage
+            .read_chunk_nbt(&shared.level_folder, &pos)
+            .ok()
+            .filter(|bytes| {
+                Nbt::read(&mut &bytes[..])
+                    .is_ok_and(|nbt| nbt.root_tag.get_compound("blending_data").is_some())
+            })
+            .and_then(|bytes| ChunkData::from_bytes(&bytes, pos).ok())
+            .and_then(|chunk| chunk.blending_data)
+            .map(Arc::new)
+    };
+    shared.blending_data.insert(pos, data.clone());
+    data
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::ChunkQueue;
+
+    #[test]
+    fn closest_chunks_first() {
+        let mut queue = ChunkQueue::default();
+        queue.push(Vector2::new(3, 0), 9, 'a');
+        queue.push(Vector2::new(1, 0), 1, 'b');
+        queue.push(Vector2::new(0, 2), 4, 'c');
+
+        assert_eq!(queue.pop(), Some((Vector2::new(1, 0), vec!['b'])));
+        assert_eq!(queue.pop(), Some((Vector2::new(0, 2), vec!['c'])));
+        assert_eq!(queue.pop(), Some((Vector2::new(3, 0), vec!['a'])));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn equal_priorities_in_request_order() {
+        let mut queue = ChunkQueue::default();
+        queue.push(Vector2::new(0, 1), 1, 'a');
+        queue.push(Vector2::new(1, 0), 1, 'b');
+
+        assert_eq!(queue.pop(), Some((Vector2::new(0, 1), vec!['a'])));
+        assert_eq!(queue.pop(), Some((Vector2::new(1, 0), vec!['b'])));
+    }
+
+    #[test]
+    fn requests_are_merged() {
+        let mut queue = ChunkQueue::default();
+        queue.push(Vector2::new(5, 5), 50, 'a');
+        queue.push(Vector2::new(2, 2), 8, 'b');
+        // A closer player moves the chunk up
+        queue.push(Vector2::new(5, 5), 0, 'c');
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.pop(), Some((Vector2::new(5, 5), vec!['a', 'c'])));
+        assert_eq!(queue.pop(), Some((Vector2::new(2, 2), vec!['b'])));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -0,0 +1,934 @@
+use pumpkin_core::{math::vector3::Vector3, text::TextComponent};
+use rand::Rng;
+
+use crate::{
+    block::{block_registry::get_block_by_state_id, random_tick::property},
+    item::{
+        item_registry::{get_item_by_id, Operation},
+        ItemStack,
+    },
+};
+
+/// Armor never takes away more than this share of damage out of 25
+const MAX_ARMOR: f32 = 20.0;
+/// Protection enchantments never take away more than this share of damage out of 25
+const MAX_ENCHANTMENT_PROTECTION: i32 = 20;
+/// Each level of Resistance takes away this share of damage out of 25
+const RESISTANCE_PER_LEVEL: f32 = 5.0;
+/// Entities fall this far without getting hurt
+const SAFE_FALL_DISTANCE: f64 = 3.0;
+/// The air entities have when they start holding their breath
+pub const MAX_AIR_SUPPLY: i32 = 300;
+/// Entities which run out of air drown whenever their air supply gets down to this
+const DROWNING_AIR_SUPPLY: i32 = -20;
+/// Breathing entities get back this much air each tick
+const AIR_REFILL: i32 = 4;
+pub const DROWNING_DAMAGE: f32 = 2.0;
+
+/// The kinds of damage, in the order of vanilla's `damage_type` registry which is also their id
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    Arrow,
+    BadRespawnPoint,
+    Cactus,
+    Campfire,
+    Cramming,
+    DragonBreath,
+    Drown,
+    DryOut,
+    EnderPearl,
+    Explosion,
+    Fall,
+    FallingAnvil,
+    FallingBlock,
+    FallingStalactite,
+    Fireball,
+    Fireworks,
+    FlyIntoWall,
+    Freeze,
+    Generic,
+    GenericKill,
+    HotFloor,
+    InFire,
+    InWall,
+    IndirectMagic,
+    Lava,
+    LightningBolt,
+    MaceSmash,
+    Magic,
+    MobAttack,
+    MobAttackNoAggro,
+    MobProjectile,
+    OnFire,
+    OutOfWorld,
+    OutsideBorder,
+    PlayerAttack,
+    PlayerExplosion,
+    SonicBoom,
+    Spit,
+    Stalagmite,
+    Starve,
+    Sting,
+    SweetBerryBush,
+    Thorns,
+    Thrown,
+    Trident,
+    UnattributedFireball,
+    WindCharge,
+    Wither,
+    WitherSkull,
+}
+
+impl DamageType {
+    pub const ALL: [Self; 49] = [
+        Self::Arrow,
+        Self::BadRespawnPoint,
+        Self::Cactus,
+        Self::Campfire,
+        Self::Cramming,
+        Self::DragonBreath,
+        Self::Drown,
+        Self::DryOut,
+        Self::EnderPearl,
+        Self::Explosion,
+        Self::Fall,
+        Self::FallingAnvil,
+        Self::FallingBlock,
+        Self::FallingStalactite,
+        Self::Fireball,
+        Self::Fireworks,
+        Self::FlyIntoWall,
+        Self::Freeze,
+        Self::Generic,
+        Self::GenericKill,
+        Self::HotFloor,
+        Self::InFire,
+        Self::InWall,
+        Self::IndirectMagic,
+        Self::Lava,
+        Self::LightningBolt,
+        Self::MaceSmash,
+        Self::Magic,
+        Self::MobAttack,
+        Self::MobAttackNoAggro,
+        Self::MobProjectile,
+        Self::OnFire,
+        Self::OutOfWorld,
+        Self::OutsideBorder,
+        Self::PlayerAttack,
+        Self::PlayerExplosion,
+        Self::SonicBoom,
+        Self::Spit,
+        Self::Stalagmite,
+        Self::Starve,
+        Self::Sting,
+        Self::SweetBerryBush,
+        Self::Thorns,
+        Self::Thrown,
+        Self::Trident,
+        Self::UnattributedFireball,
+        Self::WindCharge,
+        Self::Wither,
+        Self::WitherSkull,
+    ];
+
+    /// The id of the damage type in the `damage_type` registry, which the protocol sends
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// The name of the damage type without the `minecraft:` namespace
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Arrow => "arrow",
+            Self::BadRespawnPoint => "bad_respawn_point",
+            Self::Cactus => "cactus",
+            Self::Campfire => "campfire",
+            Self::Cramming => "cramming",
+            Self::DragonBreath => "dragon_breath",
+            Self::Drown => "drown",
+            Self::DryOut => "dry_out",
+            Self::EnderPearl => "ender_pearl",
+            Self::Explosion => "explosion",
+            Self::Fall => "fall",
+            Self::FallingAnvil => "falling_anvil",
+            Self::FallingBlock => "falling_block",
+            Self::FallingStalactite => "falling_stalactite",
+            Self::Fireball => "fireball",
+            Self::Fireworks => "fireworks",
+            Self::FlyIntoWall => "fly_into_wall",
+            Self::Freeze => "freeze",
+            Self::Generic => "generic",
+            Self::GenericKill => "generic_kill",
+            Self::HotFloor => "hot_floor",
+            Self::InFire => "in_fire",
+            Self::InWall => "in_wall",
+            Self::IndirectMagic => "indirect_magic",
+            Self::Lava => "lava",
+            Self::LightningBolt => "lightning_bolt",
+            Self::MaceSmash => "mace_smash",
+            Self::Magic => "magic",
+            Self::MobAttack => "mob_attack",
+            Self::MobAttackNoAggro => "mob_attack_no_aggro",
+            Self::MobProjectile => "mob_projectile",
+            Self::OnFire => "on_fire",
+            Self::OutOfWorld => "out_of_world",
+            Self::OutsideBorder => "outside_border",
+            Self::PlayerAttack => "player_attack",
+            Self::PlayerExplosion => "player_explosion",
+            Self::SonicBoom => "sonic_boom",
+            Self::Spit => "spit",
+            Self::Stalagmite => "stalagmite",
+            Self::Starve => "starve",
+            Self::Sting => "sting",
+            Self::SweetBerryBush => "sweet_berry_bush",
+            Self::Thorns => "thorns",
+            Self::Thrown => "thrown",
+            Self::Trident => "trident",
+            Self::UnattributedFireball => "unattributed_fireball",
+            Self::WindCharge => "wind_charge",
+            Self::Wither => "wither",
+            Self::WitherSkull => "wither_skull",
+        }
+    }
+
+    /// The damage type with the name, with or without the `minecraft:` namespace
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|damage_type| damage_type.name() == name)
+    }
+
+    /// The part of the death message translation keys for this damage type, like vanilla's
+    /// `message_id`
+    pub const fn message_id(self) -> &'static str {
+        match self {
+            Self::Arrow => "arrow",
+            Self::BadRespawnPoint => "badRespawnPoint",
+            Self::Cactus => "cactus",
+            Self::Campfire | Self::InFire => "inFire",
+            Self::Cramming => "cramming",
+            Self::DragonBreath => "dragonBreath",
+            Self::Drown => "drown",
+            Self::DryOut => "dryout",
+            Self::EnderPearl | Self::Fall => "fall",
+            Self::Explosion => "explosion",
+            Self::FallingAnvil => "anvil",
+            Self::FallingBlock => "fallingBlock",
+            Self::FallingStalactite => "fallingStalactite",
+            Self::Fireball => "fireball",
+            Self::Fireworks => "fireworks",
+            Self::FlyIntoWall => "flyIntoWall",
+            Self::Freeze => "freeze",
+            Self::Generic => "generic",
+            Self::GenericKill => "genericKill",
+            Self::HotFloor => "hotFloor",
+            Self::InWall => "inWall",
+            Self::IndirectMagic => "indirectMagic",
+            Self::Lava => "lava",
+            Self::LightningBolt => "lightningBolt",
+            Self::MaceSmash => "mace_smash",
+            Self::Magic => "magic",
+            Self::MobAttack
+            | Self::MobAttackNoAggro
+            | Self::MobProjectile
+            | Self::Spit
+            | Self::WindCharge => "mob",
+            Self::OnFire | Self::UnattributedFireball => "onFire",
+            Self::OutOfWorld => "outOfWorld",
+            Self::OutsideBorder => "outsideBorder",
+            Self::PlayerAttack => "player",
+            Self::PlayerExplosion => "explosion.player",
+            Self::SonicBoom => "sonic_boom",
+            Self::Stalagmite => "stalagmite",
+            Self::Starve => "starve",
+            Self::Sting => "sting",
+            Self::SweetBerryBush => "sweetBerryBush",
+            Self::Thorns => "thorns",
+            Self::Thrown => "thrown",
+            Self::Trident => "trident",
+            Self::Wither => "wither",
+            Self::WitherSkull => "witherSkull",
+        }
+    }
+
+    /// Armor doesn't protect from the damage, like the `bypasses_armor` tag
+    pub const fn bypasses_armor(self) -> bool {
+        matches!(
+            self,
+            Self::OnFire
+                | Self::InWall
+                | Self::Cramming
+                | Self::Drown
+                | Self::FlyIntoWall
+                | Self::Generic
+                | Self::Wither
+                | Self::DragonBreath
+                | Self::Starve
+                | Self::Fall
+                | Self::EnderPearl
+                | Self::Freeze
+                | Self::Stalagmite
+                | Self::Magic
+                | Self::IndirectMagic
+                | Self::OutOfWorld
+                | Self::GenericKill
+                | Self::SonicBoom
+                | Self::OutsideBorder
+        )
+    }
+
+    /// Shields don't block the damage, like the `bypasses_shield` tag
+    pub const fn bypasses_shield(self) -> bool {
+        self.bypasses_armor() || matches!(self, Self::FallingAnvil | Self::FallingStalactite)
+    }
+
+    /// The damage hurts even invulnerable entities and players in creative, like the
+    /// `bypasses_invulnerability` tag
+    pub const fn bypasses_invulnerability(self) -> bool {
+        matches!(self, Self::OutOfWorld | Self::GenericKill)
+    }
+
+    /// Resistance doesn't protect from the damage, like the `bypasses_resistance` tag
+    pub const fn bypasses_resistance(self) -> bool {
+        matches!(self, Self::OutOfWorld | Self::GenericKill)
+    }
+
+    /// No effect protects from the damage, like the `bypasses_effects` tag
+    pub const fn bypasses_effects(self) -> bool {
+        matches!(self, Self::Starve)
+    }
+
+    /// Protection enchantments don't protect from the damage, like the `bypasses_enchantments`
+    /// tag
+    pub const fn bypasses_enchantments(self) -> bool {
+        matches!(self, Self::SonicBoom)
+    }
+
+    /// Like the `is_fire` tag, Fire Resistance and Fire Protection protect from it
+    pub const fn is_fire(self) -> bool {
+        matches!(
+            self,
+            Self::InFire
+                | Self::Campfire
+                | Self::OnFire
+                | Self::Lava
+                | Self::HotFloor
+                | Self::UnattributedFireball
+                | Self::Fireball
+        )
+    }
+
+    /// Like the `is_projectile` tag, Projectile Protection protects from it
+    pub const fn is_projectile(self) -> bool {
+        matches!(
+            self,
+            Self::Arrow
+                | Self::Trident
+                | Self::MobProjectile
+                | Self::UnattributedFireball
+                | Self::Fireball
+                | Self::WitherSkull
+                | Self::Thrown
+                | Self::WindCharge
+        )
+    }
+
+    /// Like the `is_explosion` tag, Blast Protection protects from it
+    pub const fn is_explosion(self) -> bool {
+        matches!(
+            self,
+            Self::Fireworks | Self::Explosion | Self::PlayerExplosion | Self::BadRespawnPoint
+        )
+    }
+
+    /// Like the `is_fall` tag, Feather Falling protects from it
+    pub const fn is_fall(self) -> bool {
+        matches!(self, Self::Fall | Self::EnderPearl | Self::Stalagmite)
+    }
+
+    /// The exhaustion players get from taking the damage, like the `exhaustion` of the damage type
+    pub const fn exhaustion(self) -> f32 {
+        match self {
+            Self::Cramming
+            | Self::DragonBreath
+            | Self::Drown
+            | Self::EnderPearl
+            | Self::Fall
+            | Self::FlyIntoWall
+            | Self::Freeze
+            | Self::Generic
+            | Self::GenericKill
+            | Self::InWall
+            | Self::IndirectMagic
+            | Self::Magic
+            | Self::OnFire
+            | Self::OutOfWorld
+            | Self::OutsideBorder
+            | Self::SonicBoom
+            | Self::Stalagmite
+            | Self::Starve
+            | Self::Wither => 0.0,
+            _ => 0.1,
+        }
+    }
+
+    /// The death message of an entity killed by this damage, like vanilla's
+    /// `DamageSource.getLocalizedDeathMessage`. The attacker is the entity which caused the
+    /// damage, the shooter of a projectile or the projectile itself when nobody shot it
+    pub fn death_message<'a>(
+        self,
+        victim: TextComponent<'a>,
+        attacker: Option<TextComponent<'a>>,
+    ) -> TextComponent<'a> {
+        match (self, attacker) {
+            // Falls use vanilla's fall variants, which only know the generic accident here
+            (Self::Fall | Self::EnderPearl, None) => {
+                TextComponent::translate("death.fell.accident.generic", vec![victim])
+            }
+            (Self::BadRespawnPoint, _) => TextComponent::translate(
+                "death.attack.badRespawnPoint.message",
+                vec![
+                    victim,
+                    TextComponent::translate("death.attack.badRespawnPoint.link", vec![]),
+                ],
+            ),
+            (damage_type, None) => TextComponent::translate(
+                format!("death.attack.{}", damage_type.message_id()),
+                vec![victim],
+            ),
+            (damage_type, Some(attacker)) => TextComponent::translate(
+                format!("death.attack.{}", damage_type.message_id()),
+                vec![victim, attacker],
+            ),
+        }
+    }
+}
+
+/// What protects an entity from damage besides invulnerability and shields
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Protection {
+    pub armor: f32,
+    pub toughness: f32,
+    /// The protection factor from the enchantments of the armor, see `enchantment_protection`
+    pub enchantments: i32,
+    /// The amplifier of the Resistance effect of the entity
+    pub resistance: Option<u8>,
+}
+
+impl Protection {
+    /// How well the armor pieces protect from the damage type through their armor points and
+    /// enchantments
+    pub fn of_armor<'a>(
+        damage_type: DamageType,
+        armor: impl IntoIterator<Item = &'a ItemStack>,
+    ) -> Self {
+        let mut protection = Self::default();
+        for piece in armor {
+            let (points, toughness) = armor_points(piece);
+            protection.armor += points;
+            protection.toughness += toughness;
+            protection.enchantments += enchantment_protection(damage_type, piece);
+        }
+        protection
+    }
+}
+
+/// The armor points and the armor toughness of a piece of armor
+pub fn armor_points(piece: &ItemStack) -> (f32, f32) {
+    let Some(modifiers) =
+        get_item_by_id(piece.item_id).and_then(|item| item.components.attribute_modifiers.as_ref())
+    else {
+        return (0.0, 0.0);
+    };
+    let mut armor = 0.0;
+    let mut toughness = 0.0;
+    for modifier in &modifiers.modifiers {
+        if modifier.operation != Operation::AddValue {
+            continue;
+        }
+        match modifier.type_val.as_str() {
+            "minecraft:armor" => armor += modifier.amount as f32,
+            "minecraft:armor_toughness" => toughness += modifier.amount as f32,
+            _ => {}
+        }
+    }
+    (armor, toughness)
+}
+
+/// How much the protection enchantments of a piece of armor protect from the damage type, like
+/// the `damage_protection` effects of vanilla's enchantments
+pub fn enchantment_protection(damage_type: DamageType, piece: &ItemStack) -> i32 {
+    let mut protection = piece.enchantment_level("protection");
+    if damage_type.is_fire() {
+        protection += piece.enchantment_level("fire_protection") * 2;
+    }
+    if damage_type.is_explosion() {
+        protection += piece.enchantment_level("blast_protection") * 2;
+    }
+    if damage_type.is_projectile() {
+        protection += piece.enchantment_level("projectile_protection") * 2;
+    }
+    if damage_type.is_fall() {
+        protection += piece.enchantment_level("feather_falling") * 3;
+    }
+    protection
+}
+
+/// The damage the enchantments of a weapon add to an attack, like the `damage` effects of
+/// vanilla's Sharpness, Smite and Bane of Arthropods. Smite and Bane of Arthropods only hurt
+/// mobs which are sensitive to them
+pub fn enchantment_damage_bonus(
+    weapon: &ItemStack,
+    sensitive_to_smite: bool,
+    sensitive_to_bane_of_arthropods: bool,
+) -> f32 {
+    let mut bonus = 0.0;
+    let sharpness = weapon.enchantment_level("sharpness");
+    if sharpness > 0 {
+        bonus += 0.5 * sharpness as f32 + 0.5;
+    }
+    if sensitive_to_smite {
+        bonus += 2.5 * weapon.enchantment_level("smite") as f32;
+    }
+    if sensitive_to_bane_of_arthropods {
+        bonus += 2.5 * weapon.enchantment_level("bane_of_arthropods") as f32;
+    }
+    bonus
+}
+
+/// How many ticks an attack with the weapon sets the target on fire, Fire Aspect burns for four
+/// seconds per level
+pub fn fire_aspect_ticks(weapon: &ItemStack) -> i32 {
+    weapon.enchantment_level("fire_aspect") * 80
+}
+
+/// The damage left after armor, like vanilla's `CombatRules.getDamageAfterAbsorb`. Toughness
+/// keeps armor useful against strong hits
+pub fn damage_after_armor(amount: f32, armor: f32, toughness: f32) -> f32 {
+    let toughness_factor = 2.0 + toughness / 4.0;
+    let effective_armor = (armor - amount / toughness_factor).clamp(armor * 0.2, MAX_ARMOR);
+    amount * (1.0 - effective_armor / 25.0)
+}
+
+/// The damage left after protection enchantments, like vanilla's
+/// `CombatRules.getDamageAfterMagicAbsorb`
+pub fn damage_after_enchantments(amount: f32, protection: i32) -> f32 {
+    let protection = protection.clamp(0, MAX_ENCHANTMENT_PROTECTION);
+    amount * (1.0 - protection as f32 / 25.0)
+}
+
+/// The damage left after Resistance, like vanilla's `getDamageAfterMagicAbsorb`. Resistance V
+/// protects from everything
+pub fn damage_after_resistance(amount: f32, amplifier: u8) -> f32 {
+    let level = f32::from(amplifier) + 1.0;
+    (amount * (25.0 - level * RESISTANCE_PER_LEVEL) / 25.0).max(0.0)
+}
+
+/// The damage an entity takes after what protects it, like vanilla's `getDamageAfterArmorAbsorb`
+/// and `getDamageAfterMagicAbsorb` together
+pub fn damage_after_protection(
+    damage_type: DamageType,
+    amount: f32,
+    protection: &Protection,
+) -> f32 {
+    let mut amount = amount;
+    if !damage_type.bypasses_armor() {
+        amount = damage_after_armor(amount, protection.armor, protection.toughness);
+    }
+    if let Some(amplifier) = protection.resistance {
+        if !damage_type.bypasses_resistance() && !damage_type.bypasses_effects() {
+            amount = damage_after_resistance(amount, amplifier);
+        }
+    }
+    if amount <= 0.0 || damage_type.bypasses_enchantments() {
+        return amount.max(0.0);
+    }
+    damage_after_enchantments(amount, protection.enchantments)
+}
+
+/// How much durability each piece of armor loses from the damage, like vanilla's `hurtArmor`
+pub fn armor_durability_damage(amount: f32) -> i32 {
+    ((amount / 4.0) as i32).max(1)
+}
+
+/// Whether a raised shield blocks damage from the source, shields only block damage coming from
+/// in front of the entity like vanilla's `isDamageSourceBlocked`
+pub fn shield_blocks(
+    view_direction: Vector3<f64>,
+    position: Vector3<f64>,
+    source: Vector3<f64>,
+) -> bool {
+    let to_entity = position.sub(&source);
+    let horizontal = Vector3::new(to_entity.x, 0.0, to_entity.z);
+    if horizontal.length_squared() == 0.0 {
+        return false;
+    }
+    let horizontal = horizontal.normalize();
+    horizontal.x * view_direction.x + horizontal.z * view_direction.z < 0.0
+}
+
+/// The damage of a fall, like vanilla's `calculateFallDamage`. Jump Boost lets entities fall
+/// further without getting hurt and the multiplier comes from the block they land on
+pub fn fall_damage(fall_distance: f64, jump_boost: Option<u8>, multiplier: f32) -> f32 {
+    let safe_distance =
+        SAFE_FALL_DISTANCE + jump_boost.map_or(0.0, |amplifier| f64::from(amplifier) + 1.0);
+    ((fall_distance - safe_distance) as f32 * multiplier)
+        .ceil()
+        .max(0.0)
+}
+
+/// The damage of gliding into a wall, like vanilla's `handleFallFlyingCollisions`. Only losing
+/// a lot of horizontal speed at once hurts
+pub fn fly_into_wall_damage(speed_before: f64, speed_after: f64) -> f32 {
+    (((speed_before - speed_after) * 10.0 - 3.0) as f32).max(0.0)
+}
+
+/// How much landing on the block hurts, like vanilla's `Block.fallOn`. Hay bales and beds soften
+/// the fall and slime blocks bounce entities which don't sneak
+pub fn landing_damage_multiplier(block_name: &str, sneaking: bool) -> f32 {
+    match block_name {
+        "hay_block" | "honey_block" => 0.2,
+        "slime_block" if !sneaking => 0.0,
+        name if name.ends_with("_bed") => 0.5,
+        _ => 1.0,
+    }
+}
+
+/// Whether entities inside the block stop falling, like climbing a ladder or getting stuck in
+/// cobweb
+pub fn breaks_fall(block_name: &str) -> bool {
+    matches!(
+        block_name,
+        "ladder"
+            | "vine"
+            | "scaffolding"
+            | "cobweb"
+            | "sweet_berry_bush"
+            | "powder_snow"
+            | "weeping_vines"
+            | "weeping_vines_plant"
+            | "twisting_vines"
+            | "twisting_vines_plant"
+            | "cave_vines"
+            | "cave_vines_plant"
+    )
+}
+
+/// The damage a block does to living entities inside of it, like vanilla's `entityInside`. Sweet
+/// berry bushes only prick entities moving through them once they have grown
+pub fn block_contact_damage(state_id: u16, moving: bool) -> Option<(DamageType, f32)> {
+    let block = get_block_by_state_id(state_id)?;
+    match block.name.as_str() {
+        "cactus" => Some((DamageType::Cactus, 1.0)),
+        "sweet_berry_bush"
+            if moving && property(block, state_id, "age").is_some_and(|age| age != "0") =>
+        {
+            Some((DamageType::SweetBerryBush, 1.0))
+        }
+        "fire" => Some((DamageType::InFire, 1.0)),
+        "soul_fire" => Some((DamageType::InFire, 2.0)),
+        _ => None,
+    }
+}
+
+/// The air supply of an entity after a tick and whether it drowns, like vanilla's
+/// `LivingEntity.baseTick`. Entities lose air while they hold their breath under water and
+/// Respiration gives a chance to keep it
+pub fn tick_air_supply(
+    air_supply: i32,
+    holding_breath: bool,
+    respiration: i32,
+    rng: &mut impl Rng,
+) -> (i32, bool) {
+    if !holding_breath {
+        return ((air_supply + AIR_REFILL).min(MAX_AIR_SUPPLY), false);
+    }
+    let air_supply = if respiration > 0 && rng.gen_range(0..=respiration) > 0 {
+        air_supply
+    } else {
+        air_supply - 1
+    };
+    if air_supply <= DROWNING_AIR_SUPPLY {
+        (0, true)
+    } else {
+        (air_supply, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::{
+        math::vector3::Vector3,
+        text::{TextComponent, TextContent},
+    };
+
+    use crate::{
+        block::{
+            block_registry::get_block,
+            random_tick::{default_state, with_property},
+        },
+        item::{item_registry::get_item, ItemStack},
+    };
+
+    use super::{
+        armor_points, block_contact_damage, breaks_fall, damage_after_armor,
+        damage_after_enchantments, damage_after_protection, damage_after_resistance,
+        enchantment_damage_bonus, fall_damage, fire_aspect_ticks, fly_into_wall_damage,
+        landing_damage_multiplier, shield_blocks, tick_air_supply, DamageType, Protection,
+        MAX_AIR_SUPPLY,
+    };
+
+    fn item(name: &str) -> ItemStack {
+        ItemStack::new(1, get_item(name).unwrap().id)
+    }
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn ids_follow_registry_order() {
+        assert_eq!(DamageType::Arrow.id(), 0);
+        assert_eq!(DamageType::Fall.id(), 10);
+        assert_eq!(DamageType::InFire.id(), 21);
+        assert_eq!(DamageType::Magic.id(), 27);
+        assert_eq!(DamageType::MobAttack.id(), 28);
+        assert_eq!(DamageType::OnFire.id(), 31);
+        assert_eq!(DamageType::PlayerAttack.id(), 34);
+        assert_eq!(DamageType::Wither.id(), 47);
+        assert_eq!(DamageType::WitherSkull.id(), 48);
+        for (id, damage_type) in DamageType::ALL.into_iter().enumerate() {
+            assert_eq!(damage_type.id(), id as u8);
+        }
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(
+            DamageType::from_name("minecraft:generic_kill"),
+            Some(DamageType::GenericKill)
+        );
+        assert_eq!(
+            DamageType::from_name("mob_attack_no_aggro"),
+            Some(DamageType::MobAttackNoAggro)
+        );
+        assert_eq!(DamageType::from_name("minecraft:stone"), None);
+    }
+
+    #[test]
+    fn armor_reduces_damage() {
+        // Full diamond armor
+        assert!(close(
+            damage_after_armor(10.0, 20.0, 8.0),
+            10.0 * (1.0 - 17.5 / 25.0)
+        ));
+        // Strong hits get through more armor
+        assert!(close(
+            damage_after_armor(20.0, 10.0, 0.0),
+            20.0 * (1.0 - 2.0 / 25.0)
+        ));
+        assert!(close(damage_after_armor(5.0, 0.0, 0.0), 5.0));
+    }
+
+    #[test]
+    fn armor_points_of_items() {
+        assert_eq!(armor_points(&item("diamond_chestplate")), (8.0, 2.0));
+        assert_eq!(armor_points(&item("netherite_helmet")), (3.0, 3.0));
+        assert_eq!(armor_points(&item("stick")), (0.0, 0.0));
+    }
+
+    #[test]
+    fn enchantments_and_resistance_reduce_damage() {
+        assert!(close(
+            damage_after_enchantments(10.0, 4),
+            10.0 * 21.0 / 25.0
+        ));
+        // Protection stops at 80%
+        assert!(close(damage_after_enchantments(10.0, 64), 2.0));
+        assert!(close(damage_after_resistance(10.0, 0), 8.0));
+        assert!(close(damage_after_resistance(10.0, 4), 0.0));
+        assert!(close(damage_after_resistance(10.0, 255), 0.0));
+    }
+
+    #[test]
+    fn weapon_enchantments() {
+        let mut sword = item("iron_sword");
+        assert!(close(enchantment_damage_bonus(&sword, true, true), 0.0));
+        sword.components.enchantments = vec![
+            ("sharpness".to_string(), 5),
+            ("smite".to_string(), 2),
+            ("fire_aspect".to_string(), 2),
+        ];
+        assert!(close(enchantment_damage_bonus(&sword, false, false), 3.0));
+        // Smite only hurts undead
+        assert!(close(enchantment_damage_bonus(&sword, true, false), 8.0));
+        assert_eq!(fire_aspect_ticks(&sword), 160);
+    }
+
+    #[test]
+    fn protection_depends_on_damage_type() {
+        let mut boots = item("diamond_boots");
+        boots.components.enchantments = vec![("feather_falling".to_string(), 4)];
+        let fall = Protection::of_armor(DamageType::Fall, [&boots]);
+        assert_eq!(fall.enchantments, 12);
+        // Armor points don't help against falling
+        assert!(close(
+            damage_after_protection(DamageType::Fall, 10.0, &fall),
+            10.0 * 13.0 / 25.0
+        ));
+        let attack = Protection::of_armor(DamageType::MobAttack, [&boots]);
+        assert_eq!(attack.enchantments, 0);
+        assert!(close(
+            damage_after_protection(DamageType::MobAttack, 10.0, &attack),
+            damage_after_armor(10.0, 3.0, 2.0)
+        ));
+    }
+
+    #[test]
+    fn some_damage_ignores_protection() {
+        let protection = Protection {
+            armor: 20.0,
+            toughness: 12.0,
+            enchantments: 20,
+            resistance: Some(4),
+        };
+        assert!(close(
+            damage_after_protection(DamageType::GenericKill, 100.0, &protection),
+            100.0 * 5.0 / 25.0
+        ));
+        assert!(close(
+            damage_after_protection(DamageType::Starve, 1.0, &protection),
+            1.0 * 5.0 / 25.0
+        ));
+        assert!(close(
+            damage_after_protection(DamageType::MobAttack, 10.0, &protection),
+            0.0
+        ));
+    }
+
+    #[test]
+    fn shields_only_block_from_the_front() {
+        let position = Vector3::new(0.0, 64.0, 0.0);
+        let looking_north = Vector3::new(0.0, 0.0, -1.0);
+        assert!(shield_blocks(
+            looking_north,
+            position,
+            Vector3::new(0.0, 64.0, -5.0)
+        ));
+        assert!(shield_blocks(
+            looking_north,
+            position,
+            Vector3::new(3.0, 70.0, -1.0)
+        ));
+        assert!(!shield_blocks(
+            looking_north,
+            position,
+            Vector3::new(0.0, 64.0, 5.0)
+        ));
+    }
+
+    #[test]
+    fn death_messages() {
+        let message = DamageType::MobAttack.death_message(
+            TextComponent::text("Steve"),
+            Some(TextComponent::translate("entity.minecraft.zombie", vec![])),
+        );
+        let TextContent::Translate { translate, with } = message.content else {
+            panic!("death messages are translated");
+        };
+        assert_eq!(translate, "death.attack.mob");
+        assert_eq!(with.len(), 2);
+
+        let message = DamageType::Fall.death_message(TextComponent::text("Steve"), None);
+        let TextContent::Translate { translate, .. } = message.content else {
+            panic!("death messages are translated");
+        };
+        assert_eq!(translate, "death.fell.accident.generic");
+    }
+
+    #[test]
+    fn falls_hurt_past_three_blocks() {
+        assert!(close(fall_damage(3.0, None, 1.0), 0.0));
+        assert!(close(fall_damage(3.2, None, 1.0), 1.0));
+        assert!(close(fall_damage(10.0, None, 1.0), 7.0));
+        // Jump Boost II
+        assert!(close(fall_damage(10.0, Some(1), 1.0), 5.0));
+        assert!(close(
+            fall_damage(23.0, None, landing_damage_multiplier("hay_block", false)),
+            4.0
+        ));
+        assert!(close(
+            fall_damage(23.0, None, landing_damage_multiplier("red_bed", false)),
+            10.0
+        ));
+        assert!(close(
+            fall_damage(23.0, None, landing_damage_multiplier("slime_block", false)),
+            0.0
+        ));
+        assert!(close(
+            fall_damage(23.0, None, landing_damage_multiplier("slime_block", true)),
+            20.0
+        ));
+        assert!(breaks_fall("ladder"));
+        assert!(!breaks_fall("stone"));
+    }
+
+    #[test]
+    fn blocks_hurt_entities_inside() {
+        assert_eq!(
+            block_contact_damage(default_state("cactus"), false),
+            Some((DamageType::Cactus, 1.0))
+        );
+        assert_eq!(
+            block_contact_damage(default_state("soul_fire"), false),
+            Some((DamageType::InFire, 2.0))
+        );
+        assert_eq!(block_contact_damage(default_state("stone"), true), None);
+
+        let bush = get_block("sweet_berry_bush").unwrap();
+        let grown = with_property(bush, bush.default_state_id, "age", "1");
+        assert_eq!(block_contact_damage(bush.default_state_id, true), None);
+        assert_eq!(block_contact_damage(grown, false), None);
+        assert_eq!(
+            block_contact_damage(grown, true),
+            Some((DamageType::SweetBerryBush, 1.0))
+        );
+    }
+
+    #[test]
+    fn gliding_into_walls_hurts() {
+        assert!(close(fly_into_wall_damage(1.5, 1.49), 0.0));
+        assert!(close(fly_into_wall_damage(1.5, 0.0), 12.0));
+    }
+
+    #[test]
+    fn entities_drown_without_air() {
+        let mut rng = rand::thread_rng();
+        let mut air = MAX_AIR_SUPPLY;
+        let mut ticks = 0;
+        loop {
+            let (next, drowns) = tick_air_supply(air, true, 0, &mut rng);
+            ticks += 1;
+            air = next;
+            if drowns {
+                break;
+            }
+        }
+        assert_eq!(ticks, MAX_AIR_SUPPLY + 20);
+        assert_eq!(air, 0);
+
+        // Air comes back quickly
+        assert_eq!(tick_air_supply(0, false, 0, &mut rng), (4, false));
+        assert_eq!(
+            tick_air_supply(MAX_AIR_SUPPLY - 1, false, 0, &mut rng),
+            (MAX_AIR_SUPPLY, false)
+        );
+        // Respiration keeps air most of the time
+        let lost = (0..1000)
+            .filter(|_| tick_air_supply(100, true, 3, &mut rng).0 < 100)
+            .count();
+        assert!(lost < 500);
+    }
+}
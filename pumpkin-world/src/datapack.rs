@@ -0,0 +1,368 @@
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const DATAPACKS_FOLDER: &str = "datapacks";
+const PACK_METADATA: &str = "pack.mcmeta";
+
+/// The built in pack, it has no functions
+pub const VANILLA_PACK: &str = "vanilla";
+/// Packs in the `datapacks` folder are known by their folder name after this, like vanilla
+const FILE_PACK_PREFIX: &str = "file/";
+
+#[derive(Error, Debug)]
+pub enum DatapackError {
+    #[error("Io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("Json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Line {line}: {message}")]
+    InvalidFunction { line: usize, message: String },
+}
+
+/// The datapacks of the world in load order and the ones turned off, saved in `level.dat` like
+/// vanilla's `DataPackConfig`
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DataPacks {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+}
+
+impl Default for DataPacks {
+    fn default() -> Self {
+        Self {
+            enabled: vec![VANILLA_PACK.to_string()],
+            disabled: Vec::new(),
+        }
+    }
+}
+
+impl DataPacks {
+    /// Forgets the enabled packs which are gone and enables the new ones which weren't turned
+    /// off, like vanilla does when the server starts or reloads
+    pub fn refresh(&mut self, available: &[String]) {
+        self.enabled.retain(|pack| {
+            let found = available.contains(pack);
+            if !found {
+                log::warn!("Missing data pack {pack}");
+            }
+            found
+        });
+        for pack in available {
+            if !self.enabled.contains(pack) && !self.disabled.contains(pack) {
+                self.enabled.push(pack.clone());
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TagEntry {
+    Id(String),
+    Entry { id: String },
+}
+
+/// A function tag file, like vanilla's `TagFile`
+#[derive(Deserialize)]
+struct TagFile {
+    values: Vec<TagEntry>,
+    #[serde(default)]
+    replace: bool,
+}
+
+/// The id with the `minecraft` namespace if it has none
+fn namespaced(id: &str) -> Cow<'_, str> {
+    if id.contains(':') {
+        Cow::Borrowed(id)
+    } else {
+        Cow::Owned(format!("minecraft:{id}"))
+    }
+}
+
+/// The functions and function tags of the enabled datapacks, like vanilla's
+/// `ServerFunctionLibrary`
+#[derive(Default)]
+pub struct FunctionLibrary {
+    functions: BTreeMap<String, Vec<String>>,
+    /// The functions of each tag with the tags in it resolved
+    tags: BTreeMap<String, Vec<String>>,
+}
+
+impl FunctionLibrary {
+    /// The commands of the function, the namespace defaults to `minecraft`
+    pub fn function(&self, id: &str) -> Option<&[String]> {
+        self.functions
+            .get(namespaced(id).as_ref())
+            .map(Vec::as_slice)
+    }
+
+    /// The functions of the tag, the namespace defaults to `minecraft`
+    pub fn tag(&self, id: &str) -> Option<&[String]> {
+        self.tags.get(namespaced(id).as_ref()).map(Vec::as_slice)
+    }
+
+    pub fn function_ids(&self) -> impl Iterator<Item = &str> {
+        self.functions.keys().map(String::as_str)
+    }
+
+    pub fn tag_ids(&self) -> impl Iterator<Item = &str> {
+        self.tags.keys().map(String::as_str)
+    }
+}
+
+/// Reads the packs in the `datapacks` folder of a world. Only unzipped packs are supported
+pub struct DatapackStorage {
+    folder: PathBuf,
+}
+
+impl DatapackStorage {
+    pub fn new(root_folder: &Path) -> Self {
+        Self {
+            folder: root_folder.join(DATAPACKS_FOLDER),
+        }
+    }
+
+    /// The packs which can be enabled, the built in one and the folders with a `pack.mcmeta`
+    pub fn available(&self) -> Vec<String> {
+        let mut packs = vec![VANILLA_PACK.to_string()];
+        let Ok(entries) = fs::read_dir(&self.folder) else {
+            return packs;
+        };
+        let mut folders: Vec<_> = entries
+            .flatten()
+            .filter(|entry| entry.path().join(PACK_METADATA).is_file())
+            .map(|entry| format!("{FILE_PACK_PREFIX}{}", entry.file_name().to_string_lossy()))
+            .collect();
+        folders.sort();
+        packs.extend(folders);
+        packs
+    }
+
+    /// Loads the functions and tags of the packs in order. Functions of later packs replace the
+    /// ones of earlier packs and tags are merged unless they replace. Broken files are skipped
+    pub fn load(&self, packs: &[String]) -> FunctionLibrary {
+        let mut functions = BTreeMap::new();
+        let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for pack in packs {
+            let Some(name) = pack.strip_prefix(FILE_PACK_PREFIX) else {
+                continue;
+            };
+            let Ok(namespaces) = fs::read_dir(self.folder.join(name).join("data")) else {
+                continue;
+            };
+            for namespace in namespaces.flatten() {
+                let folder = namespace.path();
+                let namespace = namespace.file_name().to_string_lossy().into_owned();
+                for (path, file) in files(&folder.join("function"), "mcfunction") {
+                    let id = format!("{namespace}:{path}");
+                    let function = fs::read_to_string(&file)
+                        .map_err(DatapackError::from)
+                        .and_then(|source| parse_function(&source));
+                    match function {
+                        Ok(commands) => {
+                            functions.insert(id, commands);
+                        }
+                        Err(error) => log::error!("Failed to load function {id}: {error}"),
+                    }
+                }
+                for (path, file) in files(&folder.join("tags").join("function"), "json") {
+                    let id = format!("{namespace}:{path}");
+                    let tag = fs::read(&file)
+                        .map_err(DatapackError::from)
+                        .and_then(|data| Ok(serde_json::from_slice::<TagFile>(&data)?));
+                    match tag {
+                        Ok(tag) => {
+                            let values = tags.entry(id).or_default();
+                            if tag.replace {
+                                values.clear();
+                            }
+                            values.extend(tag.values.into_iter().map(|entry| match entry {
+                                TagEntry::Id(id) | TagEntry::Entry { id } => id,
+                            }));
+                        }
+                        Err(error) => log::error!("Failed to load function tag {id}: {error}"),
+                    }
+                }
+            }
+        }
+        let tags = resolve_tags(&tags, &functions);
+        FunctionLibrary { functions, tags }
+    }
+}
+
+/// The files with the extension in the folder and its subfolders by their path relative to it
+/// without the extension, like `foo/bar` for `foo/bar.mcfunction`
+fn files(folder: &Path, extension: &str) -> Vec<(String, PathBuf)> {
+    fn visit(folder: &Path, prefix: &str, extension: &str, files: &mut Vec<(String, PathBuf)>) {
+        let Ok(entries) = fs::read_dir(folder) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if path.is_dir() {
+                visit(&path, &format!("{prefix}{name}/"), extension, files);
+            } else if let Some(name) = name
+                .strip_suffix(extension)
+                .and_then(|name| name.strip_suffix('.'))
+            {
+                files.push((format!("{prefix}{name}"), path));
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    visit(folder, "", extension, &mut files);
+    files
+}
+
+/// Resolves the tags inside tags, functions which don't exist are left out
+fn resolve_tags(
+    tags: &BTreeMap<String, Vec<String>>,
+    functions: &BTreeMap<String, Vec<String>>,
+) -> BTreeMap<String, Vec<String>> {
+    fn resolve(
+        id: &str,
+        tags: &BTreeMap<String, Vec<String>>,
+        functions: &BTreeMap<String, Vec<String>>,
+        visiting: &mut Vec<String>,
+        resolved: &mut Vec<String>,
+    ) {
+        let Some(values) = tags.get(id) else {
+            log::warn!("Unknown function tag #{id}");
+            return;
+        };
+        if visiting.iter().any(|tag| tag == id) {
+            log::warn!("Function tag #{id} contains itself");
+            return;
+        }
+        visiting.push(id.to_string());
+        for value in values {
+            if let Some(tag) = value.strip_prefix('#') {
+                resolve(&namespaced(tag), tags, functions, visiting, resolved);
+                continue;
+            }
+            let function = namespaced(value);
+            if !functions.contains_key(function.as_ref()) {
+                log::warn!("Function tag #{id} contains unknown function {function}");
+            } else if !resolved.iter().any(|resolved| *resolved == function) {
+                resolved.push(function.into_owned());
+            }
+        }
+        visiting.pop();
+    }
+
+    tags.keys()
+        .map(|id| {
+            let mut resolved = Vec::new();
+            resolve(id, tags, functions, &mut Vec::new(), &mut resolved);
+            (id.clone(), resolved)
+        })
+        .collect()
+}
+
+/// The commands of a `.mcfunction` file, like vanilla's `CommandFunction.fromLines`. Empty lines
+/// and comments are skipped and lines ending with a backslash continue on the next line
+pub fn parse_function(source: &str) -> Result<Vec<String>, DatapackError> {
+    let mut commands = Vec::new();
+    let mut lines = source.lines().map(str::trim).enumerate();
+    while let Some((index, line)) = lines.next() {
+        let invalid = |message: String| DatapackError::InvalidFunction {
+            line: index + 1,
+            message,
+        };
+        let mut command = line.to_string();
+        while command.ends_with('\\') {
+            command.pop();
+            let Some((_, next)) = lines.next() else {
+                return Err(invalid("Line continuation at end of file".to_string()));
+            };
+            command.push_str(next);
+        }
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+        if command.starts_with("//") {
+            return Err(invalid(format!(
+                "Unknown or invalid command '{command}' (if you intended to make a comment, use '#' not '//')"
+            )));
+        }
+        if let Some(rest) = command.strip_prefix('/') {
+            return Err(invalid(format!(
+                "Unknown or invalid command '{command}' (did you mean '{rest}'? Do not use a preceding forwards slash.)"
+            )));
+        }
+        if command.starts_with('$') {
+            return Err(invalid("Macro lines are not supported".to_string()));
+        }
+        commands.push(command);
+    }
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{parse_function, resolve_tags, DataPacks};
+
+    #[test]
+    fn functions() {
+        let commands = parse_function(
+            "# A comment\n\nsay hello\n  give @s \\\n    minecraft:stone\ntime set day\n",
+        )
+        .unwrap();
+        assert_eq!(
+            commands,
+            ["say hello", "give @s minecraft:stone", "time set day"]
+        );
+        assert!(parse_function("/say hello").is_err());
+        assert!(parse_function("say \\").is_err());
+    }
+
+    #[test]
+    fn tags() {
+        let functions =
+            BTreeMap::from(["minecraft:a", "pack:b"].map(|id| (id.to_string(), Vec::new())));
+        let tags = BTreeMap::from([
+            (
+                "minecraft:tick".to_string(),
+                vec![
+                    "a".to_string(),
+                    "#pack:more".to_string(),
+                    "pack:c".to_string(),
+                ],
+            ),
+            (
+                "pack:more".to_string(),
+                vec![
+                    "pack:b".to_string(),
+                    "a".to_string(),
+                    "#pack:more".to_string(),
+                ],
+            ),
+        ]);
+        let resolved = resolve_tags(&tags, &functions);
+        assert_eq!(resolved["minecraft:tick"], ["minecraft:a", "pack:b"]);
+        assert_eq!(resolved["pack:more"], ["pack:b", "minecraft:a"]);
+    }
+
+    #[test]
+    fn refresh() {
+        let mut packs = DataPacks {
+            enabled: vec!["vanilla".to_string(), "file/gone".to_string()],
+            disabled: vec!["file/off".to_string()],
+        };
+        packs.refresh(&["vanilla", "file/new", "file/off"].map(str::to_string));
+        assert_eq!(packs.enabled, ["vanilla", "file/new"]);
+        assert_eq!(packs.disabled, ["file/off"]);
+    }
+}
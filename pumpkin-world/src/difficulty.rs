@@ -0,0 +1,208 @@
+use pumpkin_core::Difficulty;
+use rand::Rng;
+
+/// Ticks in a Minecraft day
+const DAY_TICKS: i64 = 24_000;
+
+/// How bright the moon is in each of its phases, starting with the full moon
+const MOON_BRIGHTNESS: [f32; 8] = [1.0, 0.75, 0.5, 0.25, 0.0, 0.25, 0.5, 0.75];
+
+/// The armor mobs spawn with, from the worst to the best, for the feet, legs, chest and head
+const ARMOR: [[&str; 4]; 5] = [
+    [
+        "leather_boots",
+        "leather_leggings",
+        "leather_chestplate",
+        "leather_helmet",
+    ],
+    [
+        "golden_boots",
+        "golden_leggings",
+        "golden_chestplate",
+        "golden_helmet",
+    ],
+    [
+        "chainmail_boots",
+        "chainmail_leggings",
+        "chainmail_chestplate",
+        "chainmail_helmet",
+    ],
+    [
+        "iron_boots",
+        "iron_leggings",
+        "iron_chestplate",
+        "iron_helmet",
+    ],
+    [
+        "diamond_boots",
+        "diamond_leggings",
+        "diamond_chestplate",
+        "diamond_helmet",
+    ],
+];
+
+/// How bright the moon is on the day, mobs get tougher around full moons
+#[must_use]
+pub fn moon_brightness(day_time: i64) -> f32 {
+    MOON_BRIGHTNESS[(day_time / DAY_TICKS).rem_euclid(8) as usize]
+}
+
+/// The difficulty at a place in the world, like vanilla's `DifficultyInstance`. It rises the
+/// longer the world exists and players stay in the chunk, and during bright moons
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LocalDifficulty {
+    pub base: Difficulty,
+    /// Between 0 on peaceful and 6.75 on hard
+    pub effective: f32,
+}
+
+impl LocalDifficulty {
+    #[must_use]
+    pub fn new(base: Difficulty, day_time: i64, inhabited_time: i64, moon_brightness: f32) -> Self {
+        let effective = if base == Difficulty::Peaceful {
+            0.0
+        } else {
+            let age = ((day_time as f32 - 72_000.0) / 1_440_000.0).clamp(0.0, 1.0) * 0.25;
+            let mut regional = (inhabited_time as f32 / 3_600_000.0).clamp(0.0, 1.0)
+                * if base == Difficulty::Hard { 1.0 } else { 0.75 };
+            regional += (moon_brightness * 0.25).clamp(0.0, age);
+            if base == Difficulty::Easy {
+                regional *= 0.5;
+            }
+            f32::from(base as u8) * (0.75 + age + regional)
+        };
+        Self { base, effective }
+    }
+
+    /// Whether the difficulty is above the value
+    #[must_use]
+    pub fn is_harder_than(&self, value: f32) -> bool {
+        self.effective > value
+    }
+
+    /// Between 0 and 1, how likely mobs spawn with equipment
+    #[must_use]
+    pub fn special_multiplier(&self) -> f32 {
+        if self.effective < 2.0 {
+            0.0
+        } else if self.effective > 4.0 {
+            1.0
+        } else {
+            (self.effective - 2.0) / 2.0
+        }
+    }
+}
+
+/// Scales damage to players which depends on the difficulty, like the damage of explosions and
+/// mobs in vanilla's `Player.hurt`
+#[must_use]
+pub fn scale_damage(difficulty: Difficulty, amount: f32) -> f32 {
+    match difficulty {
+        Difficulty::Peaceful => 0.0,
+        Difficulty::Easy => (amount / 2.0 + 1.0).min(amount),
+        Difficulty::Normal => amount,
+        Difficulty::Hard => amount * 1.5,
+    }
+}
+
+/// The armor a zombie or skeleton spawns with, for the feet, legs, chest and head like vanilla's
+/// `Mob.populateDefaultEquipmentSlots`. Mobs mostly spawn without armor, the pieces start at the
+/// feet and each one after is less likely
+pub fn mob_armor(local: &LocalDifficulty, rng: &mut impl Rng) -> [Option<&'static str>; 4] {
+    let mut armor = [None; 4];
+    if rng.gen::<f32>() >= 0.15 * local.special_multiplier() {
+        return armor;
+    }
+    let mut material = rng.gen_range(0..2);
+    for _ in 0..3 {
+        if rng.gen::<f32>() < 0.095 {
+            material += 1;
+        }
+    }
+    let stop_chance = if local.base == Difficulty::Hard {
+        0.1
+    } else {
+        0.25
+    };
+    for (slot, piece) in armor.iter_mut().enumerate() {
+        if slot > 0 && rng.gen::<f32>() < stop_chance {
+            break;
+        }
+        *piece = Some(ARMOR[material][slot]);
+    }
+    armor
+}
+
+/// The weapon a zombie rarely spawns with, like vanilla's `Zombie.populateDefaultEquipmentSlots`
+pub fn zombie_weapon(difficulty: Difficulty, rng: &mut impl Rng) -> Option<&'static str> {
+    let chance = if difficulty == Difficulty::Hard {
+        0.05
+    } else {
+        0.01
+    };
+    if rng.gen::<f32>() >= chance {
+        return None;
+    }
+    Some(if rng.gen_range(0..3) == 0 {
+        "iron_sword"
+    } else {
+        "iron_shovel"
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::Difficulty;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{mob_armor, moon_brightness, scale_damage, LocalDifficulty};
+
+    #[test]
+    fn moon_phases_repeat() {
+        assert_eq!(moon_brightness(0), 1.0);
+        assert_eq!(moon_brightness(4 * 24_000), 0.0);
+        assert_eq!(moon_brightness(8 * 24_000 + 12_000), 1.0);
+    }
+
+    #[test]
+    fn difficulty_rises_with_time_spent() {
+        let new = LocalDifficulty::new(Difficulty::Normal, 0, 0, 1.0);
+        assert_eq!(new.effective, 1.5);
+        assert_eq!(new.special_multiplier(), 0.0);
+
+        let old = LocalDifficulty::new(Difficulty::Hard, 2_000_000, 4_000_000, 1.0);
+        assert_eq!(old.effective, 6.75);
+        assert_eq!(old.special_multiplier(), 1.0);
+        assert!(old.is_harder_than(6.0));
+
+        let peaceful = LocalDifficulty::new(Difficulty::Peaceful, 2_000_000, 4_000_000, 1.0);
+        assert_eq!(peaceful.effective, 0.0);
+    }
+
+    #[test]
+    fn damage_scales_with_difficulty() {
+        assert_eq!(scale_damage(Difficulty::Peaceful, 10.0), 0.0);
+        assert_eq!(scale_damage(Difficulty::Easy, 10.0), 6.0);
+        assert_eq!(scale_damage(Difficulty::Easy, 1.0), 1.0);
+        assert_eq!(scale_damage(Difficulty::Normal, 10.0), 10.0);
+        assert_eq!(scale_damage(Difficulty::Hard, 10.0), 15.0);
+    }
+
+    #[test]
+    fn mobs_only_get_armor_in_hard_regions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let easy = LocalDifficulty::new(Difficulty::Easy, 0, 0, 0.0);
+        assert!((0..1000).all(|_| mob_armor(&easy, &mut rng) == [None; 4]));
+
+        let hard = LocalDifficulty::new(Difficulty::Hard, 2_000_000, 4_000_000, 1.0);
+        let armored: Vec<_> = (0..1000)
+            .map(|_| mob_armor(&hard, &mut rng))
+            .filter(|armor| armor[0].is_some())
+            .collect();
+        assert!((100..200).contains(&armored.len()));
+        // Pieces are only left out after the first missing one
+        assert!(armored.iter().all(|armor| armor
+            .windows(2)
+            .all(|pair| pair[0].is_some() || pair[1].is_none())));
+    }
+}
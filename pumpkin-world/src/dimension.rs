@@ -16,6 +16,6 @@ impl Dimension {
             Dimension::Nether => base_directory.push("DIM-1"),
             Dimension::End => base_directory.push("DIM1"),
         }
-        Level::from_root_folder(base_directory)
+        Level::from_root_folder(base_directory, *self)
     }
 }
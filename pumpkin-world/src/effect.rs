@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+
+/// Effects with this duration never run out, like vanilla's `INFINITE_DURATION`
+pub const INFINITE_DURATION: i32 = -1;
+/// How much faster each level of speed makes an entity
+const SPEED_PER_LEVEL: f64 = 0.2;
+/// How much slower each level of slowness makes an entity
+const SLOWNESS_PER_LEVEL: f64 = 0.15;
+/// Damage each level of strength adds to melee attacks
+const STRENGTH_PER_LEVEL: f32 = 3.0;
+/// Damage each level of weakness takes from melee attacks
+const WEAKNESS_PER_LEVEL: f32 = 4.0;
+
+/// The status effects, in the order of vanilla's `mob_effect` registry which is also their id
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusEffect {
+    Speed,
+    Slowness,
+    Haste,
+    MiningFatigue,
+    Strength,
+    InstantHealth,
+    InstantDamage,
+    JumpBoost,
+    Nausea,
+    Regeneration,
+    Resistance,
+    FireResistance,
+    WaterBreathing,
+    Invisibility,
+    Blindness,
+    NightVision,
+    Hunger,
+    Weakness,
+    Poison,
+    Wither,
+    HealthBoost,
+    Absorption,
+    Saturation,
+    Glowing,
+    Levitation,
+    Luck,
+    Unluck,
+    SlowFalling,
+    ConduitPower,
+    DolphinsGrace,
+    BadOmen,
+    HeroOfTheVillage,
+    Darkness,
+    TrialOmen,
+    RaidOmen,
+    WindCharged,
+    Weaving,
+    Oozing,
+    Infested,
+}
+
+impl StatusEffect {
+    pub const ALL: [Self; 39] = [
+        Self::Speed,
+        Self::Slowness,
+        Self::Haste,
+        Self::MiningFatigue,
+        Self::Strength,
+        Self::InstantHealth,
+        Self::InstantDamage,
+        Self::JumpBoost,
+        Self::Nausea,
+        Self::Regeneration,
+        Self::Resistance,
+        Self::FireResistance,
+        Self::WaterBreathing,
+        Self::Invisibility,
+        Self::Blindness,
+        Self::NightVision,
+        Self::Hunger,
+        Self::Weakness,
+        Self::Poison,
+        Self::Wither,
+        Self::HealthBoost,
+        Self::Absorption,
+        Self::Saturation,
+        Self::Glowing,
+        Self::Levitation,
+        Self::Luck,
+        Self::Unluck,
+        Self::SlowFalling,
+        Self::ConduitPower,
+        Self::DolphinsGrace,
+        Self::BadOmen,
+        Self::HeroOfTheVillage,
+        Self::Darkness,
+        Self::TrialOmen,
+        Self::RaidOmen,
+        Self::WindCharged,
+        Self::Weaving,
+        Self::Oozing,
+        Self::Infested,
+    ];
+
+    /// The id of the effect in the `mob_effect` registry, which the protocol sends
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// The name of the effect without the `minecraft:` namespace
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Speed => "speed",
+            Self::Slowness => "slowness",
+            Self::Haste => "haste",
+            Self::MiningFatigue => "mining_fatigue",
+            Self::Strength => "strength",
+            Self::InstantHealth => "instant_health",
+            Self::InstantDamage => "instant_damage",
+            Self::JumpBoost => "jump_boost",
+            Self::Nausea => "nausea",
+            Self::Regeneration => "regeneration",
+            Self::Resistance => "resistance",
+            Self::FireResistance => "fire_resistance",
+            Self::WaterBreathing => "water_breathing",
+            Self::Invisibility => "invisibility",
+            Self::Blindness => "blindness",
+            Self::NightVision => "night_vision",
+            Self::Hunger => "hunger",
+            Self::Weakness => "weakness",
+            Self::Poison => "poison",
+            Self::Wither => "wither",
+            Self::HealthBoost => "health_boost",
+            Self::Absorption => "absorption",
+            Self::Saturation => "saturation",
+            Self::Glowing => "glowing",
+            Self::Levitation => "levitation",
+            Self::Luck => "luck",
+            Self::Unluck => "unluck",
+            Self::SlowFalling => "slow_falling",
+            Self::ConduitPower => "conduit_power",
+            Self::DolphinsGrace => "dolphins_grace",
+            Self::BadOmen => "bad_omen",
+            Self::HeroOfTheVillage => "hero_of_the_village",
+            Self::Darkness => "darkness",
+            Self::TrialOmen => "trial_omen",
+            Self::RaidOmen => "raid_omen",
+            Self::WindCharged => "wind_charged",
+            Self::Weaving => "weaving",
+            Self::Oozing => "oozing",
+            Self::Infested => "infested",
+        }
+    }
+
+    /// The effect with the name, with or without the `minecraft:` namespace
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL.into_iter().find(|effect| effect.name() == name)
+    }
+
+    /// Whether the effect does everything at once when it is applied, like vanilla's
+    /// `InstantenousMobEffect`
+    pub const fn is_instant(self) -> bool {
+        matches!(
+            self,
+            Self::InstantHealth | Self::InstantDamage | Self::Saturation
+        )
+    }
+
+    /// Whether the effect does something this tick, like vanilla's `shouldApplyEffectTickThisTick`
+    pub const fn should_apply_tick(self, duration: i32, amplifier: u8) -> bool {
+        let interval = match self {
+            Self::Regeneration => 50,
+            Self::Poison => 25,
+            Self::Wither => 40,
+            Self::Hunger => 1,
+            effect if effect.is_instant() => return duration >= 1,
+            _ => return false,
+        };
+        // Higher levels apply more often, as often as every tick
+        let interval = match (interval as u32).checked_shr(amplifier as u32) {
+            Some(interval) => interval as i32,
+            None => 0,
+        };
+        interval == 0 || duration % interval == 0
+    }
+}
+
+/// An effect an entity has, like vanilla's `MobEffectInstance`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EffectInstance {
+    pub effect: StatusEffect,
+    /// The level of the effect minus one
+    pub amplifier: u8,
+    /// Ticks left until the effect runs out, `INFINITE_DURATION` for effects which never do
+    pub duration: i32,
+    /// Effects from beacons and conduits, their particles are fainter
+    pub ambient: bool,
+    pub show_particles: bool,
+    pub show_icon: bool,
+}
+
+impl EffectInstance {
+    pub const fn new(effect: StatusEffect, duration: i32, amplifier: u8) -> Self {
+        Self {
+            effect,
+            amplifier,
+            duration,
+            ambient: false,
+            show_particles: true,
+            show_icon: true,
+        }
+    }
+
+    pub const fn is_infinite(&self) -> bool {
+        self.duration == INFINITE_DURATION
+    }
+
+    /// Whether the effect takes the place of the same effect the entity already has, stronger
+    /// effects and the same effect lasting longer do
+    fn replaces(&self, old: &Self) -> bool {
+        if self.amplifier != old.amplifier {
+            return self.amplifier > old.amplifier;
+        }
+        !old.is_infinite() && (self.is_infinite() || self.duration > old.duration)
+    }
+}
+
+/// What the effects of an entity did in one tick
+#[derive(Debug, Default)]
+pub struct EffectTick {
+    /// The effects which do something this tick
+    pub applied: Vec<EffectInstance>,
+    /// The effects which ran out this tick
+    pub expired: Vec<StatusEffect>,
+}
+
+/// The effects an entity has, at most one of each
+#[derive(Debug, Default)]
+pub struct ActiveEffects {
+    effects: HashMap<StatusEffect, EffectInstance>,
+}
+
+impl ActiveEffects {
+    /// Gives the effect to the entity unless it already has a stronger or longer one of the same
+    /// effect, returns whether it did
+    pub fn add(&mut self, instance: EffectInstance) -> bool {
+        if self
+            .effects
+            .get(&instance.effect)
+            .is_some_and(|old| !instance.replaces(old))
+        {
+            return false;
+        }
+        self.effects.insert(instance.effect, instance);
+        true
+    }
+
+    pub fn remove(&mut self, effect: StatusEffect) -> Option<EffectInstance> {
+        self.effects.remove(&effect)
+    }
+
+    /// Removes every effect, returns the ones the entity had
+    pub fn clear(&mut self) -> Vec<EffectInstance> {
+        self.effects.drain().map(|(_, instance)| instance).collect()
+    }
+
+    pub fn get(&self, effect: StatusEffect) -> Option<&EffectInstance> {
+        self.effects.get(&effect)
+    }
+
+    pub fn has(&self, effect: StatusEffect) -> bool {
+        self.effects.contains_key(&effect)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EffectInstance> {
+        self.effects.values()
+    }
+
+    /// Counts the effects down by one tick, like vanilla's `tickEffects`. Effects apply before
+    /// they count down and run out once no ticks are left
+    pub fn tick(&mut self) -> EffectTick {
+        let mut tick = EffectTick::default();
+        self.effects.retain(|effect, instance| {
+            if instance
+                .effect
+                .should_apply_tick(instance.duration, instance.amplifier)
+            {
+                tick.applied.push(*instance);
+            }
+            if instance.is_infinite() {
+                return true;
+            }
+            instance.duration -= 1;
+            if instance.duration <= 0 {
+                tick.expired.push(*effect);
+                return false;
+            }
+            true
+        });
+        tick
+    }
+
+    /// The level of the effect, 0 when the entity doesn't have it
+    fn level(&self, effect: StatusEffect) -> u32 {
+        self.get(effect)
+            .map_or(0, |instance| u32::from(instance.amplifier) + 1)
+    }
+
+    /// Multiplies the movement speed of the entity, from its speed and slowness effects
+    pub fn movement_speed_multiplier(&self) -> f64 {
+        let speed = 1.0 + SPEED_PER_LEVEL * f64::from(self.level(StatusEffect::Speed));
+        let slowness = 1.0 - SLOWNESS_PER_LEVEL * f64::from(self.level(StatusEffect::Slowness));
+        (speed * slowness).max(0.0)
+    }
+
+    /// Damage added to melee attacks of the entity, from its strength and weakness effects
+    pub fn attack_damage_bonus(&self) -> f32 {
+        STRENGTH_PER_LEVEL * self.level(StatusEffect::Strength) as f32
+            - WEAKNESS_PER_LEVEL * self.level(StatusEffect::Weakness) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActiveEffects, EffectInstance, StatusEffect, INFINITE_DURATION};
+
+    #[test]
+    fn ids_follow_registry_order() {
+        assert_eq!(StatusEffect::Speed.id(), 0);
+        assert_eq!(StatusEffect::Regeneration.id(), 9);
+        assert_eq!(StatusEffect::Wither.id(), 19);
+        assert_eq!(StatusEffect::Infested.id(), 38);
+        for (id, effect) in StatusEffect::ALL.into_iter().enumerate() {
+            assert_eq!(effect.id(), id as i32);
+        }
+    }
+
+    #[test]
+    fn from_name() {
+        assert_eq!(
+            StatusEffect::from_name("minecraft:night_vision"),
+            Some(StatusEffect::NightVision)
+        );
+        assert_eq!(
+            StatusEffect::from_name("poison"),
+            Some(StatusEffect::Poison)
+        );
+        assert_eq!(StatusEffect::from_name("minecraft:stone"), None);
+    }
+
+    #[test]
+    fn regeneration_ticks_faster_with_higher_levels() {
+        let regeneration = StatusEffect::Regeneration;
+        assert!(regeneration.should_apply_tick(100, 0));
+        assert!(!regeneration.should_apply_tick(99, 0));
+        assert!(regeneration.should_apply_tick(75, 1));
+        assert!(!regeneration.should_apply_tick(76, 1));
+        // 50 >> 6 is 0, so regeneration VII heals every tick
+        assert!(regeneration.should_apply_tick(77, 6));
+        assert!(regeneration.should_apply_tick(77, 255));
+    }
+
+    #[test]
+    fn instant_effects_apply_once() {
+        let mut effects = ActiveEffects::default();
+        effects.add(EffectInstance::new(StatusEffect::InstantHealth, 1, 0));
+        let tick = effects.tick();
+        assert_eq!(tick.applied.len(), 1);
+        assert_eq!(tick.expired, vec![StatusEffect::InstantHealth]);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn effects_run_out() {
+        let mut effects = ActiveEffects::default();
+        effects.add(EffectInstance::new(StatusEffect::Speed, 2, 0));
+        assert!(effects.tick().expired.is_empty());
+        assert_eq!(effects.get(StatusEffect::Speed).unwrap().duration, 1);
+        assert_eq!(effects.tick().expired, vec![StatusEffect::Speed]);
+        assert!(!effects.has(StatusEffect::Speed));
+    }
+
+    #[test]
+    fn infinite_effects_never_run_out() {
+        let mut effects = ActiveEffects::default();
+        effects.add(EffectInstance::new(
+            StatusEffect::NightVision,
+            INFINITE_DURATION,
+            0,
+        ));
+        for _ in 0..1000 {
+            assert!(effects.tick().expired.is_empty());
+        }
+        assert!(effects
+            .get(StatusEffect::NightVision)
+            .unwrap()
+            .is_infinite());
+    }
+
+    #[test]
+    fn weaker_effects_dont_replace_stronger_ones() {
+        let mut effects = ActiveEffects::default();
+        assert!(effects.add(EffectInstance::new(StatusEffect::Strength, 100, 1)));
+        assert!(!effects.add(EffectInstance::new(StatusEffect::Strength, 1000, 0)));
+        assert!(!effects.add(EffectInstance::new(StatusEffect::Strength, 50, 1)));
+        assert!(effects.add(EffectInstance::new(StatusEffect::Strength, 200, 1)));
+        assert!(effects.add(EffectInstance::new(StatusEffect::Strength, 10, 2)));
+        let strength = effects.get(StatusEffect::Strength).unwrap();
+        assert_eq!((strength.amplifier, strength.duration), (2, 10));
+    }
+
+    #[test]
+    fn speed_and_strength_modifiers() {
+        let mut effects = ActiveEffects::default();
+        assert!((effects.movement_speed_multiplier() - 1.0).abs() < 1e-9);
+        effects.add(EffectInstance::new(StatusEffect::Speed, 100, 1));
+        assert!((effects.movement_speed_multiplier() - 1.4).abs() < 1e-9);
+        effects.add(EffectInstance::new(StatusEffect::Slowness, 100, 9));
+        assert!(effects.movement_speed_multiplier().abs() < 1e-9);
+
+        effects.add(EffectInstance::new(StatusEffect::Strength, 100, 0));
+        assert!((effects.attack_damage_bonus() - 3.0).abs() < 1e-6);
+        effects.add(EffectInstance::new(StatusEffect::Weakness, 100, 0));
+        assert!((effects.attack_damage_bonus() + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clear_removes_every_effect() {
+        let mut effects = ActiveEffects::default();
+        effects.add(EffectInstance::new(StatusEffect::Poison, 100, 0));
+        effects.add(EffectInstance::new(StatusEffect::Wither, 100, 0));
+        assert_eq!(effects.clear().len(), 2);
+        assert!(effects.is_empty());
+    }
+}
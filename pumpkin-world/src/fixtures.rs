@@ -0,0 +1,56 @@
+//! Generation entry points for the tests which compare against data extracted from vanilla,
+//! only built for tests and with the `test-fixtures` feature
+
+use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+use crate::{
+    biome::Biome,
+    coordinates::XZBlockCoordinates,
+    dimension::Dimension,
+    generation::{biome_generator, proto_chunk::ProtoChunk, surface::SurfaceSystem, Seed},
+};
+
+/// How far a chunk is generated before it is compared against a vanilla fixture
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FixtureStage {
+    /// Only the noise terrain, with aquifers and ore veins
+    Noise,
+    /// The noise terrain with surface rules, every column is plains like in a vanilla single
+    /// biome world
+    Surface,
+}
+
+/// The block state ids of the overworld chunk at `at` generated up to `stage`, in the order of
+/// the vanilla chunk fixtures: by x, then y from the bottom, then z
+pub fn generate_fixture_chunk(seed: u64, at: Vector2<i32>, stage: FixtureStage) -> Vec<u16> {
+    let mut chunk = ProtoChunk::new(at, seed);
+    chunk.populate_noise();
+    if stage == FixtureStage::Surface {
+        chunk.build_surface(&SurfaceSystem::new(Seed(seed)), &|_, _| Biome::Plains);
+    }
+
+    let mut states = Vec::with_capacity(16 * 16 * chunk.height() as usize);
+    for x in 0..16 {
+        for y in chunk.min_y()..chunk.min_y() + chunk.height() {
+            for z in 0..16 {
+                states.push(chunk.get_block_state(&Vector3::new(x, y, z)).state_id);
+            }
+        }
+    }
+    states
+}
+
+/// The biomes of the columns of the chunk at `at` in the dimension, in the order of the vanilla
+/// biome fixtures: by x, then z
+pub fn generate_fixture_biomes(seed: u64, dimension: Dimension, at: Vector2<i32>) -> Vec<Biome> {
+    let generator = biome_generator(Seed(seed), dimension);
+    (0..16)
+        .flat_map(|x| (0..16).map(move |z| (x, z)))
+        .map(|(x, z)| {
+            generator.generate_biome(XZBlockCoordinates {
+                x: at.x * 16 + x,
+                z: at.z * 16 + z,
+            })
+        })
+        .collect()
+}
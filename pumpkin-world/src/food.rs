@@ -0,0 +1,236 @@
+use pumpkin_core::Difficulty;
+
+pub const MAX_FOOD_LEVEL: i32 = 20;
+/// Players keep healing from food while their food level is at least this
+const REGENERATION_FOOD_LEVEL: i32 = 18;
+/// Exhaustion past this costs saturation or food
+const EXHAUSTION_PER_FOOD: f32 = 4.0;
+const MAX_EXHAUSTION: f32 = 40.0;
+/// Ticks between healing from saturation while the food bar is full
+const SATURATED_REGENERATION_TICKS: u32 = 10;
+/// Ticks between healing from food and between starving
+const REGENERATION_TICKS: u32 = 80;
+
+/// How exhausting actions are, like vanilla's `FoodConstants`
+pub const EXHAUSTION_SPRINT_PER_METER: f32 = 0.1;
+pub const EXHAUSTION_JUMP: f32 = 0.05;
+pub const EXHAUSTION_SPRINT_JUMP: f32 = 0.2;
+pub const EXHAUSTION_ATTACK: f32 = 0.1;
+pub const EXHAUSTION_MINE: f32 = 0.005;
+/// Exhaustion the Hunger effect adds every tick per level
+pub const EXHAUSTION_HUNGER_EFFECT: f32 = 0.005;
+
+/// What happened to the health of the player while their hunger ticked
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FoodTick {
+    Nothing,
+    /// The player heals by the amount
+    Heal(f32),
+    /// The player starves and takes a point of damage
+    Starve,
+}
+
+/// The hunger of a player, like vanilla's `FoodData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FoodData {
+    pub food_level: i32,
+    /// Lost before the food level, players with saturation heal quickly
+    pub saturation: f32,
+    /// Grows from actions like sprinting and jumping, costs saturation or food when large enough
+    pub exhaustion: f32,
+    /// Ticks since the player last healed or starved
+    pub tick_timer: u32,
+}
+
+impl Default for FoodData {
+    fn default() -> Self {
+        Self {
+            food_level: MAX_FOOD_LEVEL,
+            saturation: 5.0,
+            exhaustion: 0.0,
+            tick_timer: 0,
+        }
+    }
+}
+
+impl FoodData {
+    /// Eats food with the nutrition and saturation of its `food` component, saturation never
+    /// exceeds the food level
+    pub fn eat(&mut self, nutrition: i32, saturation: f32) {
+        self.food_level = (self.food_level + nutrition).clamp(0, MAX_FOOD_LEVEL);
+        self.saturation = (self.saturation + saturation).clamp(0.0, self.food_level as f32);
+    }
+
+    pub fn add_exhaustion(&mut self, exhaustion: f32) {
+        self.exhaustion = (self.exhaustion + exhaustion).min(MAX_EXHAUSTION);
+    }
+
+    /// Whether the player is hungry enough to eat food which can't always be eaten
+    pub const fn needs_food(&self) -> bool {
+        self.food_level < MAX_FOOD_LEVEL
+    }
+
+    /// Turns exhaustion into hunger and heals or starves the player, like vanilla's
+    /// `FoodData.tick`. Players only heal from food when natural regeneration is on and they are
+    /// hurt
+    pub fn tick(
+        &mut self,
+        difficulty: Difficulty,
+        natural_regeneration: bool,
+        hurt: bool,
+        health: f32,
+    ) -> FoodTick {
+        if self.exhaustion > EXHAUSTION_PER_FOOD {
+            self.exhaustion -= EXHAUSTION_PER_FOOD;
+            if self.saturation > 0.0 {
+                self.saturation = (self.saturation - 1.0).max(0.0);
+            } else if difficulty != Difficulty::Peaceful {
+                self.food_level = (self.food_level - 1).max(0);
+            }
+        }
+
+        let regenerates = natural_regeneration && hurt;
+        if regenerates && self.saturation > 0.0 && self.food_level >= MAX_FOOD_LEVEL {
+            self.tick_timer += 1;
+            if self.tick_timer >= SATURATED_REGENERATION_TICKS {
+                let amount = self.saturation.min(6.0);
+                self.add_exhaustion(amount);
+                self.tick_timer = 0;
+                return FoodTick::Heal(amount / 6.0);
+            }
+        } else if regenerates && self.food_level >= REGENERATION_FOOD_LEVEL {
+            self.tick_timer += 1;
+            if self.tick_timer >= REGENERATION_TICKS {
+                self.add_exhaustion(6.0);
+                self.tick_timer = 0;
+                return FoodTick::Heal(1.0);
+            }
+        } else if self.food_level <= 0 {
+            self.tick_timer += 1;
+            if self.tick_timer >= REGENERATION_TICKS {
+                self.tick_timer = 0;
+                // Starving only kills on hard, on normal it leaves half a heart
+                let starves = match difficulty {
+                    Difficulty::Hard => true,
+                    Difficulty::Normal => health > 1.0,
+                    Difficulty::Easy | Difficulty::Peaceful => health > 10.0,
+                };
+                if starves {
+                    return FoodTick::Starve;
+                }
+            }
+        } else {
+            self.tick_timer = 0;
+        }
+        FoodTick::Nothing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::Difficulty;
+
+    use super::{FoodData, FoodTick, MAX_FOOD_LEVEL};
+
+    #[test]
+    fn eating_caps_food_and_saturation() {
+        let mut food = FoodData {
+            food_level: 10,
+            saturation: 0.0,
+            ..FoodData::default()
+        };
+        // Cooked beef
+        food.eat(8, 12.8);
+        assert_eq!(food.food_level, 18);
+        assert!((food.saturation - 12.8).abs() < f32::EPSILON);
+        food.eat(8, 12.8);
+        assert_eq!(food.food_level, MAX_FOOD_LEVEL);
+        assert!((food.saturation - 20.0).abs() < f32::EPSILON);
+        assert!(!food.needs_food());
+    }
+
+    #[test]
+    fn exhaustion_costs_saturation_before_food() {
+        let mut food = FoodData {
+            saturation: 1.0,
+            exhaustion: 4.5,
+            ..FoodData::default()
+        };
+        food.tick(Difficulty::Normal, false, false, 20.0);
+        assert_eq!(food.food_level, MAX_FOOD_LEVEL);
+        assert!(food.saturation.abs() < f32::EPSILON);
+        assert!((food.exhaustion - 0.5).abs() < 1.0E-6);
+
+        food.add_exhaustion(4.0);
+        food.tick(Difficulty::Normal, false, false, 20.0);
+        assert_eq!(food.food_level, MAX_FOOD_LEVEL - 1);
+
+        // Peaceful never makes players hungry
+        food.add_exhaustion(4.0);
+        food.tick(Difficulty::Peaceful, false, false, 20.0);
+        assert_eq!(food.food_level, MAX_FOOD_LEVEL - 1);
+    }
+
+    #[test]
+    fn exhaustion_is_capped() {
+        let mut food = FoodData::default();
+        food.add_exhaustion(100.0);
+        assert!((food.exhaustion - 40.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn saturated_players_heal_quickly() {
+        let mut food = FoodData {
+            saturation: 3.0,
+            ..FoodData::default()
+        };
+        for _ in 0..9 {
+            assert_eq!(
+                food.tick(Difficulty::Normal, true, true, 10.0),
+                FoodTick::Nothing
+            );
+        }
+        assert_eq!(
+            food.tick(Difficulty::Normal, true, true, 10.0),
+            FoodTick::Heal(0.5)
+        );
+        assert!((food.exhaustion - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn fed_players_heal_slowly() {
+        let mut food = FoodData {
+            food_level: 18,
+            saturation: 0.0,
+            ..FoodData::default()
+        };
+        let heals = (0..80)
+            .map(|_| food.tick(Difficulty::Normal, true, true, 10.0))
+            .filter(|tick| *tick == FoodTick::Heal(1.0))
+            .count();
+        assert_eq!(heals, 1);
+
+        // Nothing heals without the game rule
+        let heals = (0..80)
+            .filter(|_| food.tick(Difficulty::Normal, false, true, 10.0) != FoodTick::Nothing)
+            .count();
+        assert_eq!(heals, 0);
+    }
+
+    #[test]
+    fn starving_depends_on_difficulty() {
+        let starve = |difficulty, health| {
+            let mut food = FoodData {
+                food_level: 0,
+                saturation: 0.0,
+                ..FoodData::default()
+            };
+            (0..80).any(|_| food.tick(difficulty, true, true, health) == FoodTick::Starve)
+        };
+        assert!(starve(Difficulty::Hard, 1.0));
+        assert!(starve(Difficulty::Normal, 2.0));
+        assert!(!starve(Difficulty::Normal, 1.0));
+        assert!(starve(Difficulty::Easy, 11.0));
+        assert!(!starve(Difficulty::Easy, 10.0));
+    }
+}
@@ -605,6 +605,17 @@ impl AquiferSamplerImpl for WorldAquiferSampler {
         let density = self.function.sample_mut(pos, state);
         self.apply_internal(pos, state, height_estimator, density)
     }
+
+    #[inline]
+    fn apply_with_density(
+        &mut self,
+        pos: &NoisePos,
+        state: &ChunkNoiseState,
+        height_estimator: &mut ChunkNoiseDensityFunctions,
+        density: f64,
+    ) -> Option<BlockState> {
+        self.apply_internal(pos, state, height_estimator, density)
+    }
 }
 
 pub struct SeaLevelAquiferSampler {
@@ -629,10 +640,20 @@ impl AquiferSamplerImpl for SeaLevelAquiferSampler {
         &mut self,
         pos: &NoisePos,
         state: &ChunkNoiseState,
-        _height_estimator: &mut ChunkNoiseDensityFunctions,
+        height_estimator: &mut ChunkNoiseDensityFunctions,
     ) -> Option<BlockState> {
         let sample = self.function.sample_mut(pos, state);
         //log::debug!("Aquifer sample {:?}: {}", &pos, sample);
+        self.apply_with_density(pos, state, height_estimator, sample)
+    }
+
+    fn apply_with_density(
+        &mut self,
+        pos: &NoisePos,
+        _state: &ChunkNoiseState,
+        _height_estimator: &mut ChunkNoiseDensityFunctions,
+        sample: f64,
+    ) -> Option<BlockState> {
         if sample > 0f64 {
             None
         } else {
@@ -653,6 +674,16 @@ pub trait AquiferSamplerImpl {
         state: &ChunkNoiseState,
         height_estimator: &mut ChunkNoiseDensityFunctions,
     ) -> Option<BlockState>;
+
+    /// Samples the aquifer with a known density instead of its own density function,
+    /// returns `None` where the aquifer keeps a solid barrier
+    fn apply_with_density(
+        &mut self,
+        pos: &NoisePos,
+        state: &ChunkNoiseState,
+        height_estimator: &mut ChunkNoiseDensityFunctions,
+        density: f64,
+    ) -> Option<BlockState>;
 }
 
 #[cfg(test)]
@@ -662,6 +693,7 @@ mod test {
     use crate::{
         block::BlockState,
         generation::{
+            blender::Blender,
             chunk_noise::{
                 BlockStateSampler, ChunkNoiseDensityFunctions, ChunkNoiseGenerator,
                 ChunkNoiseState, LAVA_BLOCK, WATER_BLOCK,
@@ -700,6 +732,7 @@ mod test {
             sampler,
             true,
             true,
+            Blender::NO_BLEND,
         );
         let sampler = match noise.state_sampler {
             BlockStateSampler::Chained(chained) => chained,
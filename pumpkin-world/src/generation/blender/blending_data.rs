@@ -0,0 +1,406 @@
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::{
+    biome::Biome,
+    block::block_registry::get_block_and_state_by_state_id,
+    chunk::ChunkBlocks,
+    coordinates::{ChunkRelativeBlockCoordinates, Height},
+    generation::biome_coords,
+};
+
+const QUARTS_PER_SECTION: i32 = 4;
+/// Cells are 8 blocks high
+const CELLS_PER_SECTION_Y: i32 = 2;
+const CELL_HORIZONTAL_MAX_INDEX_INSIDE: i32 = QUARTS_PER_SECTION - 1;
+const CELL_HORIZONTAL_MAX_INDEX_OUTSIDE: i32 = QUARTS_PER_SECTION;
+const CELL_COLUMN_INSIDE_COUNT: usize = 2 * CELL_HORIZONTAL_MAX_INDEX_INSIDE as usize + 1;
+const CELL_COLUMN_OUTSIDE_COUNT: usize = 2 * CELL_HORIZONTAL_MAX_INDEX_OUTSIDE as usize + 1;
+const CELL_COLUMN_COUNT: usize = CELL_COLUMN_INSIDE_COUNT + CELL_COLUMN_OUTSIDE_COUNT;
+/// Stored densities are scaled down by this when blending
+const BLENDING_DENSITY_FACTOR: f64 = 0.1;
+
+/// Blocks the height of an old chunk is measured at
+const SURFACE_BLOCKS: [&str; 11] = [
+    "podzol",
+    "gravel",
+    "grass_block",
+    "stone",
+    "coarse_dirt",
+    "sand",
+    "red_sand",
+    "mycelium",
+    "snow_block",
+    "terracotta",
+    "dirt",
+];
+
+/// The terrain of a chunk generated by an older version along its borders, new chunks next to it
+/// blend into it, see [`super::Blender`]. Values are kept for the columns of quart positions on
+/// the north and west border (inside) and on the south and east border (outside), columns without
+/// a value are `f64::MAX`
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlendingData {
+    min_section: i32,
+    max_section: i32,
+    heights: [f64; CELL_COLUMN_COUNT],
+    /// The density at every cell corner of a column, from the bottom up
+    densities: [Option<Vec<f64>>; CELL_COLUMN_COUNT],
+    biomes: [Option<Biome>; CELL_COLUMN_COUNT],
+}
+
+impl BlendingData {
+    pub fn new(min_section: i32, max_section: i32) -> Self {
+        Self {
+            min_section,
+            max_section,
+            heights: [f64::MAX; CELL_COLUMN_COUNT],
+            densities: Default::default(),
+            biomes: [None; CELL_COLUMN_COUNT],
+        }
+    }
+
+    /// Reads the `blending_data` vanilla saves, only heights are saved so the rest has to be
+    /// [calculated](Self::calculate)
+    pub fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let mut data = Self::new(nbt.get_int("min_section")?, nbt.get_int("max_section")?);
+        if let Some(heights) = nbt.get_list("heights") {
+            for (height, tag) in data.heights.iter_mut().zip(heights) {
+                *height = tag.extract_double().unwrap_or(f64::MAX);
+            }
+        }
+        Some(data)
+    }
+
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put("min_section".to_string(), NbtTag::Int(self.min_section));
+        nbt.put("max_section".to_string(), NbtTag::Int(self.max_section));
+        if self.heights.iter().any(|height| *height != f64::MAX) {
+            nbt.put(
+                "heights".to_string(),
+                NbtTag::List(self.heights.iter().copied().map(NbtTag::Double).collect()),
+            );
+        }
+        nbt
+    }
+
+    /// Measures heights, densities and biomes of every border column from the blocks of the old
+    /// chunk. Vanilla only does this for the borders facing new chunks, borders between two old
+    /// chunks are never close to generated terrain though
+    pub fn calculate(
+        &mut self,
+        blocks: &ChunkBlocks,
+        biome_at: impl Fn(i32, i32, i32) -> Option<Biome>,
+    ) {
+        for index in 0..CELL_COLUMN_COUNT {
+            // Outside columns lie in the next chunk, the last block of this one is used instead
+            let x = biome_coords::to_block(cell_x(index)).min(15);
+            let z = biome_coords::to_block(cell_z(index)).min(15);
+            if self.heights[index] == f64::MAX {
+                self.heights[index] = self.height_at(blocks, x, z) as f64;
+            }
+            let height = self.heights[index].floor() as i32;
+            self.densities[index] = Some(self.density_column(blocks, x, z, height));
+            self.biomes[index] = biome_at(x, height, z);
+        }
+    }
+
+    fn min_y(&self) -> i32 {
+        self.min_section * 16
+    }
+
+    fn max_y(&self) -> i32 {
+        self.max_section * 16
+    }
+
+    fn min_cell_y(&self) -> i32 {
+        self.min_section * CELLS_PER_SECTION_Y
+    }
+
+    fn cell_count(&self) -> usize {
+        ((self.max_section - self.min_section) * CELLS_PER_SECTION_Y) as usize
+    }
+
+    /// Densities start at the corner above the lowest one, which is always solid
+    fn cell_index(&self, cell_y: i32) -> i32 {
+        cell_y - self.min_cell_y() - 1
+    }
+
+    /// The y of the highest surface block
+    fn height_at(&self, blocks: &ChunkBlocks, x: i32, z: i32) -> i32 {
+        (self.min_y()..self.max_y())
+            .rev()
+            .find(|y| {
+                get_block_and_state_by_state_id(block_at(blocks, x, *y, z))
+                    .is_some_and(|(block, _)| SURFACE_BLOCKS.contains(&block.name.as_str()))
+            })
+            .unwrap_or(self.min_y())
+    }
+
+    /// Every value is the share of solid blocks in the 15 blocks around a cell corner, mapped to
+    /// -1..1. The corners around `height` are set so the surface is exactly at `height`
+    fn density_column(&self, blocks: &ChunkBlocks, x: i32, z: i32, height: i32) -> Vec<f64> {
+        let mut densities = vec![-1.0; self.cell_count()];
+        let mut y = self.max_y();
+        let mut read = |count: i32| {
+            (0..count)
+                .map(|_| {
+                    y -= 1;
+                    if is_ground(block_at(blocks, x, y, z)) {
+                        1.0
+                    } else {
+                        -1.0
+                    }
+                })
+                .sum::<f64>()
+        };
+
+        let mut above = read(7);
+        for index in (0..densities.len().saturating_sub(1)).rev() {
+            let corner = read(1);
+            let below = read(7);
+            densities[index] = (above + corner + below) / 15.0;
+            above = below;
+        }
+
+        let index = self.cell_index(height.div_euclid(8));
+        if index >= 0 && (index as usize) < densities.len().saturating_sub(1) {
+            let part = (height as f64 + 0.5).rem_euclid(8.0) / 8.0;
+            let ratio = (1.0 - part) / part;
+            let scale = ratio.max(1.0) * 0.25;
+            densities[index as usize + 1] = -ratio / scale;
+            densities[index as usize] = 1.0 / scale;
+        }
+        densities
+    }
+
+    /// The height of the column at the quart position relative to this chunk
+    pub fn height(&self, x: i32, z: i32) -> f64 {
+        if x == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE || z == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE {
+            self.heights[outside_index(x, z)]
+        } else if x == 0 || z == 0 {
+            self.heights[inside_index(x, z)]
+        } else {
+            f64::MAX
+        }
+    }
+
+    /// The density at the quart position relative to this chunk and the cell corner `cell_y`
+    pub fn density(&self, x: i32, cell_y: i32, z: i32) -> f64 {
+        if cell_y == self.min_cell_y() {
+            BLENDING_DENSITY_FACTOR
+        } else if x == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE || z == CELL_HORIZONTAL_MAX_INDEX_OUTSIDE {
+            self.column_density(outside_index(x, z), cell_y)
+        } else if x == 0 || z == 0 {
+            self.column_density(inside_index(x, z), cell_y)
+        } else {
+            f64::MAX
+        }
+    }
+
+    fn column_density(&self, index: usize, cell_y: i32) -> f64 {
+        let Some(densities) = &self.densities[index] else {
+            return f64::MAX;
+        };
+        usize::try_from(self.cell_index(cell_y))
+            .ok()
+            .and_then(|index| densities.get(index))
+            .map_or(f64::MAX, |density| density * BLENDING_DENSITY_FACTOR)
+    }
+
+    /// Calls `consumer` with the quart x and z and the height of every column with a height,
+    /// `quart_x` and `quart_z` are the first quart position of this chunk
+    pub fn iterate_heights(
+        &self,
+        quart_x: i32,
+        quart_z: i32,
+        mut consumer: impl FnMut(i32, i32, f64),
+    ) {
+        for (index, height) in self.heights.iter().enumerate() {
+            if *height != f64::MAX {
+                consumer(quart_x + cell_x(index), quart_z + cell_z(index), *height);
+            }
+        }
+    }
+
+    /// Calls `consumer` with the quart x, cell y, quart z and density of every cell corner
+    /// between `min_cell_y` and `max_cell_y`
+    pub fn iterate_densities(
+        &self,
+        quart_x: i32,
+        quart_z: i32,
+        min_cell_y: i32,
+        max_cell_y: i32,
+        mut consumer: impl FnMut(i32, i32, i32, f64),
+    ) {
+        let first_cell_y = self.min_cell_y() + 1;
+        for (index, densities) in self.densities.iter().enumerate() {
+            let Some(densities) = densities else {
+                continue;
+            };
+            let last_cell_y = first_cell_y + densities.len() as i32 - 1;
+            for cell_y in min_cell_y.max(first_cell_y)..=max_cell_y.min(last_cell_y) {
+                let density = densities[(cell_y - first_cell_y) as usize];
+                consumer(
+                    quart_x + cell_x(index),
+                    cell_y,
+                    quart_z + cell_z(index),
+                    density * BLENDING_DENSITY_FACTOR,
+                );
+            }
+        }
+    }
+
+    /// Calls `consumer` with the quart x and z and the surface biome of every column
+    pub fn iterate_biomes(
+        &self,
+        quart_x: i32,
+        quart_z: i32,
+        mut consumer: impl FnMut(i32, i32, Biome),
+    ) {
+        for (index, biome) in self.biomes.iter().enumerate() {
+            if let Some(biome) = biome {
+                consumer(quart_x + cell_x(index), quart_z + cell_z(index), *biome);
+            }
+        }
+    }
+}
+
+fn block_at(blocks: &ChunkBlocks, x: i32, y: i32, z: i32) -> u16 {
+    blocks
+        .get_block(ChunkRelativeBlockCoordinates {
+            x: (x as u8).into(),
+            y: Height(y as i16),
+            z: (z as u8).into(),
+        })
+        .unwrap_or_default()
+}
+
+/// Whether the block counts as terrain, trees and huge mushrooms don't
+fn is_ground(state_id: u16) -> bool {
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return false;
+    };
+    !state.air
+        && !state.collision_shapes.is_empty()
+        && !block.name.ends_with("_leaves")
+        && !block.name.ends_with("_log")
+        && !block.name.ends_with("_wood")
+        && !block.name.ends_with("_stem")
+        && !block.name.ends_with("_hyphae")
+        && !matches!(
+            block.name.as_str(),
+            "brown_mushroom_block" | "red_mushroom_block"
+        )
+}
+
+fn inside_index(x: i32, z: i32) -> usize {
+    (CELL_HORIZONTAL_MAX_INDEX_INSIDE - x + z) as usize
+}
+
+fn outside_index(x: i32, z: i32) -> usize {
+    CELL_COLUMN_INSIDE_COUNT + (x + CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - z) as usize
+}
+
+/// The quart x of the column, relative to the chunk
+fn cell_x(index: usize) -> i32 {
+    let index = index as i32;
+    if index < CELL_COLUMN_INSIDE_COUNT as i32 {
+        (CELL_HORIZONTAL_MAX_INDEX_INSIDE - index).max(0)
+    } else {
+        let index = index - CELL_COLUMN_INSIDE_COUNT as i32;
+        CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - (CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - index).max(0)
+    }
+}
+
+/// The quart z of the column, relative to the chunk
+fn cell_z(index: usize) -> i32 {
+    let index = index as i32;
+    if index < CELL_COLUMN_INSIDE_COUNT as i32 {
+        (index - CELL_HORIZONTAL_MAX_INDEX_INSIDE).max(0)
+    } else {
+        let index = index - CELL_COLUMN_INSIDE_COUNT as i32;
+        CELL_HORIZONTAL_MAX_INDEX_OUTSIDE - (index - CELL_HORIZONTAL_MAX_INDEX_OUTSIDE).max(0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use pumpkin_macros::block_state;
+
+    use crate::{
+        biome::Biome,
+        chunk::ChunkBlocks,
+        coordinates::{ChunkRelativeBlockCoordinates, Height},
+        WORLD_LOWEST_Y,
+    };
+
+    use super::{
+        cell_x, cell_z, inside_index, outside_index, BlendingData, CELL_COLUMN_COUNT,
+        CELL_COLUMN_INSIDE_COUNT,
+    };
+
+    /// Stone from the bottom of the world up to `height`
+    pub(crate) fn flat_blocks(height: i16) -> ChunkBlocks {
+        let mut blocks = ChunkBlocks::default();
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                for y in WORLD_LOWEST_Y..=height {
+                    blocks.set_block(
+                        ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: Height(y),
+                            z: z.into(),
+                        },
+                        block_state!("stone").state_id,
+                    );
+                }
+            }
+        }
+        blocks
+    }
+
+    #[test]
+    fn columns_are_indexed_by_position() {
+        for index in 0..CELL_COLUMN_COUNT {
+            let (x, z) = (cell_x(index), cell_z(index));
+            if index < CELL_COLUMN_INSIDE_COUNT {
+                assert_eq!(inside_index(x, z), index);
+            } else {
+                assert_eq!(outside_index(x, z), index);
+            }
+        }
+    }
+
+    #[test]
+    fn nbt_roundtrip() {
+        let mut data = BlendingData::new(-4, 20);
+        assert_eq!(BlendingData::read_nbt(&data.to_nbt()), Some(data.clone()));
+        data.heights[3] = 70.0;
+        assert_eq!(BlendingData::read_nbt(&data.to_nbt()), Some(data));
+    }
+
+    #[test]
+    fn calculate_flat_chunk() {
+        let mut data = BlendingData::new(-4, 20);
+        data.calculate(&flat_blocks(79), |_, _, _| Some(Biome::Desert));
+        for index in 0..CELL_COLUMN_COUNT {
+            assert_eq!(data.height(cell_x(index), cell_z(index)), 79.0);
+        }
+        // Columns inside the chunk are not kept
+        assert_eq!(data.height(2, 2), f64::MAX);
+
+        let mut biomes = 0;
+        data.iterate_biomes(0, 0, |_, _, biome| {
+            assert_eq!(biome, Biome::Desert);
+            biomes += 1;
+        });
+        assert_eq!(biomes, CELL_COLUMN_COUNT);
+
+        // Solid below the surface, open above it
+        assert!(data.density(0, 0, 1) > 0.0);
+        assert!(data.density(0, 5, 1) > 0.0);
+        assert!(data.density(0, 15, 1) < 0.0);
+        assert!(data.density(4, 30, 4) < 0.0);
+    }
+}
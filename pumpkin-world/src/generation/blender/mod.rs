@@ -1,10 +1,48 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
+
 use enum_dispatch::enum_dispatch;
+use pumpkin_core::{
+    math::vector2::Vector2,
+    random::{xoroshiro128::Xoroshiro, RandomGenerator, RandomImpl},
+};
+
+use crate::biome::Biome;
+
+use super::{
+    biome_coords,
+    noise::{
+        built_in_noise_params,
+        density::{NoisePos, NoisePosImpl},
+        lerp,
+        perlin::DoublePerlinNoiseSampler,
+    },
+};
+
+mod blending_data;
 
-use super::noise::density::NoisePos;
+#[cfg(test)]
+pub(crate) use blending_data::tests::flat_blocks;
+pub use blending_data::BlendingData;
+
+/// How far away from old chunks, in quarts, heights and biomes are blended
+const HEIGHT_BLENDING_RANGE_CELLS: i32 = 7 * 4 - 1;
+/// How far away from old chunks, in chunks, heights and biomes are blended
+pub const HEIGHT_BLENDING_RANGE_CHUNKS: i32 = (HEIGHT_BLENDING_RANGE_CELLS + 3) >> 2;
+/// How far away from old chunks, in chunks, densities are blended
+const DENSITY_BLENDING_RANGE_CHUNKS: i32 = 1;
+
+/// Displaces the border between old and new biomes
+static SHIFT_NOISE: LazyLock<DoublePerlinNoiseSampler> = LazyLock::new(|| {
+    let mut random = RandomGenerator::Xoroshiro(Xoroshiro::from_seed(42));
+    DoublePerlinNoiseSampler::new(&mut random, &built_in_noise_params::OFFSET, false)
+});
 
 pub struct BlendResult {
-    alpha: f64,
-    offset: f64,
+    pub alpha: f64,
+    pub offset: f64,
 }
 
 impl BlendResult {
@@ -13,13 +51,58 @@ impl BlendResult {
     }
 }
 
+#[derive(Clone, Debug)]
 #[enum_dispatch(BlenderImpl)]
 pub enum Blender {
     NoBlend(NoBlendBlender),
+    Blend(ChunkBlender),
 }
 
 impl Blender {
     pub const NO_BLEND: Self = Self::NoBlend(NoBlendBlender {});
+
+    /// Blends the chunk at `center` into the old chunks around it, `blending_data` gives the
+    /// blending data of a chunk if it is an old one
+    pub fn new(
+        center: Vector2<i32>,
+        blending_data: impl Fn(Vector2<i32>) -> Option<Arc<BlendingData>>,
+    ) -> Self {
+        let mut height_and_biome = HashMap::new();
+        let mut density = HashMap::new();
+        let range = HEIGHT_BLENDING_RANGE_CHUNKS;
+        for x in -range..=range {
+            for z in -range..=range {
+                if x * x + z * z > (range + 1) * (range + 1) {
+                    continue;
+                }
+                let pos = Vector2::new(center.x + x, center.z + z);
+                let Some(data) = blending_data(pos) else {
+                    continue;
+                };
+                if x.abs() <= DENSITY_BLENDING_RANGE_CHUNKS
+                    && z.abs() <= DENSITY_BLENDING_RANGE_CHUNKS
+                {
+                    density.insert(pos, data.clone());
+                }
+                height_and_biome.insert(pos, data);
+            }
+        }
+
+        if height_and_biome.is_empty() {
+            Self::NO_BLEND
+        } else {
+            Self::Blend(ChunkBlender(Arc::new(BlendingNeighbours {
+                height_and_biome,
+                density,
+            })))
+        }
+    }
+}
+
+impl Default for Blender {
+    fn default() -> Self {
+        Self::NO_BLEND
+    }
 }
 
 #[enum_dispatch]
@@ -28,19 +111,280 @@ pub trait BlenderImpl {
 
     fn apply_blend_density(&self, pos: &NoisePos, density: f64) -> f64;
 
-    fn get_biome_supplier(&self) {
-        todo!()
-    }
+    /// The biome of an old chunk this position lies in, `None` if the biome source decides
+    fn blend_biome(&self, block_x: i32, block_z: i32) -> Option<Biome>;
 }
 
+#[derive(Clone, Debug)]
 pub struct NoBlendBlender {}
 
 impl BlenderImpl for NoBlendBlender {
     fn calculate(&self, _block_x: i32, _block_z: i32) -> BlendResult {
-        BlendResult::new(1f64, 1f64)
+        BlendResult::new(1f64, 0f64)
     }
 
     fn apply_blend_density(&self, _pos: &NoisePos, density: f64) -> f64 {
         density
     }
+
+    fn blend_biome(&self, _block_x: i32, _block_z: i32) -> Option<Biome> {
+        None
+    }
+}
+
+#[derive(Debug)]
+struct BlendingNeighbours {
+    height_and_biome: HashMap<Vector2<i32>, Arc<BlendingData>>,
+    density: HashMap<Vector2<i32>, Arc<BlendingData>>,
+}
+
+/// Moves the terrain and biomes of a new chunk towards the old chunks around it, the closer to
+/// an old chunk the closer to its terrain
+#[derive(Clone, Debug)]
+pub struct ChunkBlender(Arc<BlendingNeighbours>);
+
+impl ChunkBlender {
+    /// The value of the old chunk at the quart position, columns on a chunk border may be kept by
+    /// the chunks north and west of it
+    fn blending_data_value(
+        &self,
+        x: i32,
+        z: i32,
+        getter: impl Fn(&BlendingData, i32, i32) -> f64,
+    ) -> f64 {
+        let chunk_x = biome_coords::to_chunk(x);
+        let chunk_z = biome_coords::to_chunk(z);
+        let on_x_border = x & 3 == 0;
+        let on_z_border = z & 3 == 0;
+        let value = |chunk_x: i32, chunk_z: i32| {
+            self.0
+                .height_and_biome
+                .get(&Vector2::new(chunk_x, chunk_z))
+                .map_or(f64::MAX, |data| {
+                    getter(
+                        data,
+                        x - biome_coords::from_chunk(chunk_x),
+                        z - biome_coords::from_chunk(chunk_z),
+                    )
+                })
+        };
+
+        let mut result = value(chunk_x, chunk_z);
+        if result == f64::MAX && on_x_border && on_z_border {
+            result = value(chunk_x - 1, chunk_z - 1);
+        }
+        if result == f64::MAX && on_x_border {
+            result = value(chunk_x - 1, chunk_z);
+        }
+        if result == f64::MAX && on_z_border {
+            result = value(chunk_x, chunk_z - 1);
+        }
+        result
+    }
+}
+
+/// The blend offset which moves the terrain surface to `height`
+fn height_to_offset(height: f64) -> f64 {
+    let y = height + 0.5;
+    let part = y.rem_euclid(8.0);
+    (32.0 * (y - 128.0) - 3.0 * (y - 120.0) * part + 3.0 * part * part)
+        / (128.0 * (32.0 - 3.0 * part))
+}
+
+/// Inverse distance weighting, returns the weighted value and the smallest distance
+#[derive(Default)]
+struct Weighted {
+    value: f64,
+    weights: f64,
+    closest: Option<f64>,
+}
+
+impl Weighted {
+    fn add(&mut self, distance: f64, value: f64) {
+        self.closest = Some(
+            self.closest
+                .map_or(distance, |closest| closest.min(distance)),
+        );
+        let weight = 1.0 / distance.powi(4);
+        self.value += value * weight;
+        self.weights += weight;
+    }
+
+    fn value(&self) -> f64 {
+        self.value / self.weights
+    }
+}
+
+impl BlenderImpl for ChunkBlender {
+    fn calculate(&self, block_x: i32, block_z: i32) -> BlendResult {
+        let x = biome_coords::from_block(block_x);
+        let z = biome_coords::from_block(block_z);
+        let height = self.blending_data_value(x, z, |data, x, z| data.height(x, z));
+        if height != f64::MAX {
+            return BlendResult::new(0.0, height_to_offset(height));
+        }
+
+        let mut weighted = Weighted::default();
+        for (chunk, data) in &self.0.height_and_biome {
+            data.iterate_heights(
+                biome_coords::from_chunk(chunk.x),
+                biome_coords::from_chunk(chunk.z),
+                |column_x, column_z, height| {
+                    let distance = ((x - column_x) as f64).hypot((z - column_z) as f64);
+                    if distance <= HEIGHT_BLENDING_RANGE_CELLS as f64 {
+                        weighted.add(distance, height);
+                    }
+                },
+            );
+        }
+
+        let Some(closest) = weighted.closest else {
+            return BlendResult::new(1.0, 0.0);
+        };
+        let alpha = (closest / (HEIGHT_BLENDING_RANGE_CELLS + 1) as f64).clamp(0.0, 1.0);
+        let alpha = 3.0 * alpha * alpha - 2.0 * alpha * alpha * alpha;
+        BlendResult::new(alpha, height_to_offset(weighted.value()))
+    }
+
+    fn apply_blend_density(&self, pos: &NoisePos, density: f64) -> f64 {
+        let x = biome_coords::from_block(pos.x());
+        let cell_y = pos.y() / 8;
+        let z = biome_coords::from_block(pos.z());
+        let old_density = self.blending_data_value(x, z, |data, x, z| data.density(x, cell_y, z));
+        if old_density != f64::MAX {
+            return old_density;
+        }
+
+        let mut weighted = Weighted::default();
+        for (chunk, data) in &self.0.density {
+            data.iterate_densities(
+                biome_coords::from_chunk(chunk.x),
+                biome_coords::from_chunk(chunk.z),
+                cell_y - 1,
+                cell_y + 1,
+                |column_x, column_y, column_z, density| {
+                    let distance = [x - column_x, (cell_y - column_y) * 2, z - column_z]
+                        .iter()
+                        .map(|delta| (delta * delta) as f64)
+                        .sum::<f64>()
+                        .sqrt();
+                    if distance <= 2.0 {
+                        weighted.add(distance, density);
+                    }
+                },
+            );
+        }
+
+        let Some(closest) = weighted.closest else {
+            return density;
+        };
+        lerp((closest / 3.0).clamp(0.0, 1.0), weighted.value(), density)
+    }
+
+    fn blend_biome(&self, block_x: i32, block_z: i32) -> Option<Biome> {
+        let x = biome_coords::from_block(block_x);
+        let z = biome_coords::from_block(block_z);
+        let mut closest: Option<(f64, Biome)> = None;
+        for (chunk, data) in &self.0.height_and_biome {
+            data.iterate_biomes(
+                biome_coords::from_chunk(chunk.x),
+                biome_coords::from_chunk(chunk.z),
+                |column_x, column_z, biome| {
+                    let distance = ((x - column_x) as f64).hypot((z - column_z) as f64);
+                    if distance <= HEIGHT_BLENDING_RANGE_CELLS as f64
+                        && closest.is_none_or(|(closest, _)| distance < closest)
+                    {
+                        closest = Some((distance, biome));
+                    }
+                },
+            );
+        }
+
+        let (distance, biome) = closest?;
+        let shift = SHIFT_NOISE.sample(x as f64, 0.0, z as f64) * 12.0;
+        let delta = ((distance + shift) / 12.0).clamp(0.0, 1.0);
+        (delta <= 0.5).then_some(biome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        biome::Biome,
+        generation::{proto_chunk::ProtoChunk, settings::OVERWORLD_SETTINGS},
+    };
+
+    use super::{flat_blocks, height_to_offset, Blender, BlenderImpl, BlendingData};
+
+    /// A blender for the chunk at `center` with a flat old chunk of desert at 0 0
+    fn flat_blender(center: Vector2<i32>, height: i16) -> Blender {
+        let mut data = BlendingData::new(-4, 20);
+        data.calculate(&flat_blocks(height), |_, _, _| Some(Biome::Desert));
+        let data = Arc::new(data);
+        Blender::new(center, |pos| {
+            (pos == Vector2::new(0, 0)).then(|| data.clone())
+        })
+    }
+
+    fn top_block(chunk: &ProtoChunk, x: i32, z: i32) -> i32 {
+        (chunk.min_y()..chunk.min_y() + chunk.height())
+            .rev()
+            .find(|y| !chunk.get_block_state(&Vector3::new(x, *y, z)).is_air())
+            .unwrap()
+    }
+
+    #[test]
+    fn no_blending_without_old_chunks() {
+        let blender = Blender::new(Vector2::new(0, 0), |_| None);
+        assert!(matches!(blender, Blender::NoBlend(_)));
+        let result = blender.calculate(3, 5);
+        assert_eq!((result.alpha, result.offset), (1.0, 0.0));
+    }
+
+    #[test]
+    fn alpha_grows_with_distance() {
+        let blender = flat_blender(Vector2::new(1, 0), 90);
+        let border = blender.calculate(16, 4);
+        assert_eq!(border.alpha, 0.0);
+        assert_eq!(border.offset, height_to_offset(90.0));
+
+        let mut last = 0.0;
+        for x in [20, 40, 60, 80] {
+            let alpha = blender.calculate(x, 4).alpha;
+            assert!(alpha > last, "{alpha} at {x}");
+            last = alpha;
+        }
+        assert_eq!(blender.calculate(16 * 8, 4).alpha, 1.0);
+    }
+
+    #[test]
+    fn biomes_of_old_chunks_spread() {
+        let blender = flat_blender(Vector2::new(1, 0), 70);
+        // The border is shifted by noise, so not every column next to the old chunk is desert
+        let desert = (0..16)
+            .filter(|z| blender.blend_biome(16, *z) == Some(Biome::Desert))
+            .count();
+        assert!(desert > 0);
+        assert!((0..16).all(|z| blender.blend_biome(16 * 7, z).is_none()));
+    }
+
+    #[test]
+    fn terrain_moves_to_old_chunks() {
+        let at = Vector2::new(1, 0);
+        let mut blended =
+            ProtoChunk::with_blender(at, 0, &OVERWORLD_SETTINGS, flat_blender(at, 110));
+        blended.populate_noise();
+        let mut unblended = ProtoChunk::new(at, 0);
+        unblended.populate_noise();
+
+        for z in 0..16 {
+            let height = top_block(&blended, 0, z);
+            assert!((height - 110).abs() <= 8, "{height} at {z}");
+            assert!((top_block(&unblended, 0, z) - 110).abs() > 8);
+        }
+    }
 }
@@ -0,0 +1,543 @@
+use std::f32::consts::PI;
+
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+use pumpkin_macros::block_state;
+
+use crate::block::BlockState;
+
+use super::{
+    chunk_noise::{LAVA_BLOCK, WATER_BLOCK},
+    positions::chunk_pos,
+    proto_chunk::ProtoChunk,
+};
+
+const BEDROCK_BLOCK: BlockState = block_state!("bedrock");
+
+/// How many chunks away a carver may start and still reach the carved chunk
+const CARVER_RANGE: i32 = 8;
+/// Blocks a tunnel travels at most from its start
+const MAX_TUNNEL_LENGTH: i32 = 112;
+
+/// Carvers applied after the base terrain, in vanilla order
+pub const OVERWORLD_CARVERS: [Carver; 3] = [
+    Carver::Cave(CaveCarverConfig {
+        probability: 0.15,
+        min_y: -56,
+        max_y: 180,
+        bound: 15,
+    }),
+    Carver::Cave(CaveCarverConfig {
+        probability: 0.07,
+        min_y: -56,
+        max_y: 47,
+        bound: 15,
+    }),
+    Carver::Canyon(CanyonCarverConfig {
+        probability: 0.01,
+        min_y: 10,
+        max_y: 67,
+    }),
+];
+
+pub struct CaveCarverConfig {
+    probability: f32,
+    min_y: i32,
+    max_y: i32,
+    /// Upper bound for the number of tunnel systems per chunk
+    bound: i32,
+}
+
+pub struct CanyonCarverConfig {
+    probability: f32,
+    min_y: i32,
+    max_y: i32,
+}
+
+pub enum Carver {
+    Cave(CaveCarverConfig),
+    Canyon(CanyonCarverConfig),
+}
+
+impl Carver {
+    fn probability(&self) -> f32 {
+        match self {
+            Self::Cave(config) => config.probability,
+            Self::Canyon(config) => config.probability,
+        }
+    }
+
+    fn carve(&self, context: &mut CarverContext, random: &mut LegacyRand, source: Vector2<i32>) {
+        match self {
+            Self::Cave(config) => carve_caves(config, context, random, source),
+            Self::Canyon(config) => carve_canyon(config, context, random, source),
+        }
+    }
+}
+
+/// Carves all `carvers` that start within range of the chunk into it.
+pub fn carve(chunk: &mut ProtoChunk, seed: u64, carvers: &[Carver]) {
+    let chunk_pos = chunk.chunk_pos();
+    let mut context = CarverContext { chunk };
+
+    for offset_x in -CARVER_RANGE..=CARVER_RANGE {
+        for offset_z in -CARVER_RANGE..=CARVER_RANGE {
+            let source = Vector2::new(chunk_pos.x + offset_x, chunk_pos.z + offset_z);
+            for (index, carver) in carvers.iter().enumerate() {
+                let mut random = carver_random(seed.wrapping_add(index as u64), &source);
+                if random.next_f32() <= carver.probability() {
+                    carver.carve(&mut context, &mut random, source);
+                }
+            }
+        }
+    }
+}
+
+/// The legacy large feature seed used by vanilla carvers
+pub(super) fn carver_random(seed: u64, source: &Vector2<i32>) -> LegacyRand {
+    let mut random = LegacyRand::from_seed(seed);
+    let a = random.next_i64();
+    let b = random.next_i64();
+    LegacyRand::from_seed(
+        ((source.x as i64).wrapping_mul(a) ^ (source.z as i64).wrapping_mul(b) ^ seed as i64)
+            as u64,
+    )
+}
+
+fn next_between(random: &mut LegacyRand, min: f32, max: f32) -> f32 {
+    random.next_f32() * (max - min) + min
+}
+
+struct CarverContext<'a> {
+    chunk: &'a mut ProtoChunk,
+}
+
+impl CarverContext<'_> {
+    fn min_y(&self) -> i32 {
+        self.chunk.min_y()
+    }
+
+    fn height(&self) -> i32 {
+        self.chunk.height()
+    }
+
+    /// Whether a tunnel at this position can still reach the carved chunk before it ends
+    fn can_reach(&self, x: f64, z: f64, branch_index: i32, branch_count: i32, width: f32) -> bool {
+        let chunk_pos = self.chunk.chunk_pos();
+        let dx = x - (chunk_pos::start_block_x(&chunk_pos) + 8) as f64;
+        let dz = z - (chunk_pos::start_block_z(&chunk_pos) + 8) as f64;
+        let remaining = (branch_count - branch_index) as f64;
+        let range = width as f64 + 2.0 + 16.0;
+        dx * dx + dz * dz - remaining * remaining <= range * range
+    }
+
+    /// Carves an ellipsoid, skipping every block `skip` rejects based on its
+    /// position relative to the center scaled by the radii
+    fn carve_ellipsoid(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: f64,
+        horizontal_radius: f64,
+        vertical_radius: f64,
+        skip: impl Fn(f64, f64, f64, i32) -> bool,
+    ) {
+        let chunk_pos = self.chunk.chunk_pos();
+        let start_x = chunk_pos::start_block_x(&chunk_pos);
+        let start_z = chunk_pos::start_block_z(&chunk_pos);
+        let reach = 16.0 + horizontal_radius * 2.0;
+        if (x - (start_x + 8) as f64).abs() > reach || (z - (start_z + 8) as f64).abs() > reach {
+            return;
+        }
+
+        let min_local_x = ((x - horizontal_radius).floor() as i32 - start_x - 1).max(0);
+        let max_local_x = ((x + horizontal_radius).floor() as i32 - start_x).min(15);
+        let min_y = ((y - vertical_radius).floor() as i32 - 1).max(self.min_y() + 1);
+        let max_y =
+            ((y + vertical_radius).floor() as i32 + 1).min(self.min_y() + self.height() - 8);
+        let min_local_z = ((z - horizontal_radius).floor() as i32 - start_z - 1).max(0);
+        let max_local_z = ((z + horizontal_radius).floor() as i32 - start_z).min(15);
+
+        for local_x in min_local_x..=max_local_x {
+            let scaled_x = ((start_x + local_x) as f64 + 0.5 - x) / horizontal_radius;
+            for local_z in min_local_z..=max_local_z {
+                let scaled_z = ((start_z + local_z) as f64 + 0.5 - z) / horizontal_radius;
+                if scaled_x * scaled_x + scaled_z * scaled_z >= 1.0 {
+                    continue;
+                }
+                for block_y in ((min_y + 1)..=max_y).rev() {
+                    let scaled_y = (block_y as f64 - 0.5 - y) / vertical_radius;
+                    if !skip(scaled_x, scaled_y, scaled_z, block_y) {
+                        self.carve_block(&Vector3::new(local_x, block_y, local_z));
+                    }
+                }
+            }
+        }
+    }
+
+    fn carve_block(&mut self, pos: &Vector3<i32>) {
+        let state = self.chunk.get_block_state(pos);
+        if !Self::can_replace(&state) {
+            return;
+        }
+        if let Some(carved) = self.carve_state(pos) {
+            self.chunk.set_block_state(pos, carved);
+        }
+    }
+
+    fn can_replace(state: &BlockState) -> bool {
+        !state.is_air() && !Self::is_fluid(state) && *state != BEDROCK_BLOCK
+    }
+
+    fn is_fluid(state: &BlockState) -> bool {
+        state.of_block(WATER_BLOCK.block_id) || state.of_block(LAVA_BLOCK.block_id)
+    }
+
+    /// Lava below the lava level, otherwise whatever the aquifer places there
+    fn carve_state(&mut self, pos: &Vector3<i32>) -> Option<BlockState> {
+        if pos.y <= self.chunk.lava_level() {
+            Some(LAVA_BLOCK)
+        } else {
+            self.chunk.sample_aquifer(pos)
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn carve_tunnel(
+        &mut self,
+        seed: i64,
+        mut x: f64,
+        mut y: f64,
+        mut z: f64,
+        horizontal_scale: f64,
+        vertical_scale: f64,
+        width: f32,
+        mut yaw: f32,
+        mut pitch: f32,
+        branch_start: i32,
+        branch_count: i32,
+        y_scale: f64,
+        floor_level: f64,
+    ) {
+        let mut random = LegacyRand::from_seed(seed as u64);
+        let split_index = random.next_bounded_i32(branch_count / 2) + branch_count / 4;
+        let steep = random.next_bounded_i32(6) == 0;
+        let mut yaw_change = 0.0f32;
+        let mut pitch_change = 0.0f32;
+
+        for branch_index in branch_start..branch_count {
+            let horizontal_radius =
+                1.5 + ((PI * branch_index as f32 / branch_count as f32).sin() * width) as f64;
+            let vertical_radius = horizontal_radius * y_scale;
+            let pitch_cos = pitch.cos();
+            x += (yaw.cos() * pitch_cos) as f64;
+            y += pitch.sin() as f64;
+            z += (yaw.sin() * pitch_cos) as f64;
+            pitch *= if steep { 0.92 } else { 0.7 };
+            pitch += pitch_change * 0.1;
+            yaw += yaw_change * 0.1;
+            pitch_change *= 0.9;
+            yaw_change *= 0.75;
+            pitch_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 2.0;
+            yaw_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 4.0;
+
+            if branch_index == split_index && width > 1.0 {
+                for side in [-1.0, 1.0] {
+                    let seed = random.next_i64();
+                    let width = random.next_f32() * 0.5 + 0.5;
+                    self.carve_tunnel(
+                        seed,
+                        x,
+                        y,
+                        z,
+                        horizontal_scale,
+                        vertical_scale,
+                        width,
+                        yaw + side * PI / 2.0,
+                        pitch / 3.0,
+                        branch_index,
+                        branch_count,
+                        1.0,
+                        floor_level,
+                    );
+                }
+                return;
+            }
+
+            if random.next_bounded_i32(4) != 0 {
+                if !self.can_reach(x, z, branch_index, branch_count, width) {
+                    return;
+                }
+                self.carve_ellipsoid(
+                    x,
+                    y,
+                    z,
+                    horizontal_radius * horizontal_scale,
+                    vertical_radius * vertical_scale,
+                    |dx, dy, dz, _| is_outside_cave(dx, dy, dz, floor_level),
+                );
+            }
+        }
+    }
+}
+
+/// Caves have a flattened floor below `floor_level`
+fn is_outside_cave(dx: f64, dy: f64, dz: f64, floor_level: f64) -> bool {
+    dy <= floor_level || dx * dx + dy * dy + dz * dz >= 1.0
+}
+
+fn carve_caves(
+    config: &CaveCarverConfig,
+    context: &mut CarverContext,
+    random: &mut LegacyRand,
+    source: Vector2<i32>,
+) {
+    let bound = random.next_bounded_i32(config.bound) + 1;
+    let bound = random.next_bounded_i32(bound) + 1;
+    let systems = random.next_bounded_i32(bound);
+
+    for _ in 0..systems {
+        let x = (chunk_pos::start_block_x(&source) + random.next_bounded_i32(16)) as f64;
+        let y = random.next_inbetween_i32(config.min_y, config.max_y) as f64;
+        let z = (chunk_pos::start_block_z(&source) + random.next_bounded_i32(16)) as f64;
+        let horizontal_scale = next_between(random, 0.7, 1.4) as f64;
+        let vertical_scale = next_between(random, 0.8, 1.3) as f64;
+        let floor_level = next_between(random, -1.0, -0.4) as f64;
+
+        let mut tunnels = 1;
+        if random.next_bounded_i32(4) == 0 {
+            let y_scale = next_between(random, 0.1, 0.9) as f64;
+            let room_width = 1.0 + random.next_f32() * 6.0;
+            let horizontal_radius = 1.5 + room_width as f64;
+            context.carve_ellipsoid(
+                x + 1.0,
+                y,
+                z,
+                horizontal_radius,
+                horizontal_radius * y_scale,
+                |dx, dy, dz, _| is_outside_cave(dx, dy, dz, floor_level),
+            );
+            tunnels += random.next_bounded_i32(4);
+        }
+
+        for _ in 0..tunnels {
+            let yaw = random.next_f32() * PI * 2.0;
+            let pitch = (random.next_f32() - 0.5) / 4.0;
+            let mut width = random.next_f32() * 2.0 + random.next_f32();
+            if random.next_bounded_i32(10) == 0 {
+                width *= random.next_f32() * random.next_f32() * 3.0 + 1.0;
+            }
+            let branch_count = MAX_TUNNEL_LENGTH - random.next_bounded_i32(MAX_TUNNEL_LENGTH / 4);
+            let seed = random.next_i64();
+            context.carve_tunnel(
+                seed,
+                x,
+                y,
+                z,
+                horizontal_scale,
+                vertical_scale,
+                width,
+                yaw,
+                pitch,
+                0,
+                branch_count,
+                1.0,
+                floor_level,
+            );
+        }
+    }
+}
+
+fn carve_canyon(
+    config: &CanyonCarverConfig,
+    context: &mut CarverContext,
+    random: &mut LegacyRand,
+    source: Vector2<i32>,
+) {
+    let mut x = (chunk_pos::start_block_x(&source) + random.next_bounded_i32(16)) as f64;
+    let mut y = random.next_inbetween_i32(config.min_y, config.max_y) as f64;
+    let mut z = (chunk_pos::start_block_z(&source) + random.next_bounded_i32(16)) as f64;
+    let mut yaw = random.next_f32() * PI * 2.0;
+    let mut pitch = next_between(random, -0.125, 0.125);
+    let y_scale = 3.0;
+    // Trapezoid between 0 and 6 with a plateau of 2
+    let width = random.next_f32() * 4.0 + random.next_f32() * 2.0;
+    let branch_count = (MAX_TUNNEL_LENGTH as f32 * next_between(random, 0.75, 1.0)) as i32;
+
+    let mut random = LegacyRand::from_seed(random.next_i64() as u64);
+
+    // Canyon walls get rougher or smoother depending on the height
+    let mut width_factors = vec![0.0f32; context.height() as usize];
+    let mut factor = 1.0f32;
+    for (index, width_factor) in width_factors.iter_mut().enumerate() {
+        if index == 0 || random.next_bounded_i32(3) == 0 {
+            factor = 1.0 + random.next_f32() * random.next_f32();
+        }
+        *width_factor = factor * factor;
+    }
+
+    let min_y = context.min_y();
+    let mut yaw_change = 0.0f32;
+    let mut pitch_change = 0.0f32;
+    for branch_index in 0..branch_count {
+        let mut horizontal_radius =
+            1.5 + ((branch_index as f32 * PI / branch_count as f32).sin() * width) as f64;
+        let mut vertical_radius = horizontal_radius * y_scale;
+        horizontal_radius *= next_between(&mut random, 0.75, 1.0) as f64;
+        vertical_radius *= next_between(&mut random, 0.75, 1.0) as f64;
+
+        let pitch_cos = pitch.cos();
+        x += (yaw.cos() * pitch_cos) as f64;
+        y += pitch.sin() as f64;
+        z += (yaw.sin() * pitch_cos) as f64;
+        pitch *= 0.7;
+        pitch += pitch_change * 0.05;
+        yaw += yaw_change * 0.05;
+        pitch_change *= 0.8;
+        yaw_change *= 0.5;
+        pitch_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 2.0;
+        yaw_change += (random.next_f32() - random.next_f32()) * random.next_f32() * 4.0;
+
+        if random.next_bounded_i32(4) != 0 {
+            if !context.can_reach(x, z, branch_index, branch_count, width) {
+                return;
+            }
+            context.carve_ellipsoid(
+                x,
+                y,
+                z,
+                horizontal_radius,
+                vertical_radius,
+                |dx, dy, dz, block_y| {
+                    let index = block_y - min_y;
+                    if index < 1 || index >= width_factors.len() as i32 {
+                        return true;
+                    }
+                    (dx * dx + dz * dz) * width_factors[index as usize - 1] as f64 + dy * dy / 6.0
+                        >= 1.0
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::generation::{chunk_noise::STONE_BLOCK as STONE_STATE, proto_chunk::ProtoChunk};
+
+    use super::{carve, Carver, CaveCarverConfig, LAVA_BLOCK, OVERWORLD_CARVERS, WATER_BLOCK};
+
+    fn solid_chunk(chunk_pos: Vector2<i32>) -> ProtoChunk {
+        let mut chunk = ProtoChunk::new(chunk_pos, 0);
+        chunk.fill(STONE_STATE);
+        chunk
+    }
+
+    fn states(chunk: &ProtoChunk) -> Vec<u16> {
+        let mut states = Vec::new();
+        for x in 0..16 {
+            for y in chunk.min_y()..chunk.min_y() + chunk.height() {
+                for z in 0..16 {
+                    states.push(chunk.get_block_state(&Vector3::new(x, y, z)).state_id);
+                }
+            }
+        }
+        states
+    }
+
+    #[test]
+    fn carving_is_deterministic() {
+        let mut first = solid_chunk(Vector2::new(3, -2));
+        let mut second = solid_chunk(Vector2::new(3, -2));
+        carve(&mut first, 1234, &OVERWORLD_CARVERS);
+        carve(&mut second, 1234, &OVERWORLD_CARVERS);
+        assert_eq!(states(&first), states(&second));
+    }
+
+    #[test]
+    fn carvers_open_caves() {
+        let carved = (0..8)
+            .map(|x| {
+                let mut chunk = solid_chunk(Vector2::new(x, 0));
+                carve(&mut chunk, 0, &OVERWORLD_CARVERS);
+                states(&chunk)
+                    .into_iter()
+                    .filter(|state| *state != STONE_STATE.state_id)
+                    .count()
+            })
+            .sum::<usize>();
+        assert!(carved > 0);
+    }
+
+    #[test]
+    fn lava_below_lava_level() {
+        // Caves only spawn at the bottom of the world so every opening can be checked
+        let carvers = [Carver::Cave(CaveCarverConfig {
+            probability: 1.0,
+            min_y: -60,
+            max_y: -58,
+            bound: 15,
+        })];
+        let mut chunk = solid_chunk(Vector2::new(0, 0));
+        carve(&mut chunk, 42, &carvers);
+
+        let mut carved = 0;
+        for x in 0..16 {
+            for y in chunk.min_y()..chunk.min_y() + chunk.height() {
+                for z in 0..16 {
+                    let state = chunk.get_block_state(&Vector3::new(x, y, z));
+                    if state == STONE_STATE {
+                        continue;
+                    }
+                    carved += 1;
+                    if y <= chunk.lava_level() {
+                        assert_eq!(state, LAVA_BLOCK);
+                    }
+                }
+            }
+        }
+        assert!(carved > 0);
+    }
+
+    #[test]
+    fn fluids_are_not_carved() {
+        let carvers = [Carver::Cave(CaveCarverConfig {
+            probability: 1.0,
+            min_y: 0,
+            max_y: 10,
+            bound: 15,
+        })];
+        let mut chunk = solid_chunk(Vector2::new(0, 0));
+        for x in 0..16 {
+            for z in 0..16 {
+                chunk.set_block_state(&Vector3::new(x, 8, z), WATER_BLOCK);
+            }
+        }
+        carve(&mut chunk, 42, &carvers);
+
+        for x in 0..16 {
+            for z in 0..16 {
+                assert_eq!(chunk.get_block_state(&Vector3::new(x, 8, z)), WATER_BLOCK);
+            }
+        }
+    }
+
+    #[test]
+    fn carved_blocks_come_from_aquifer() {
+        let mut chunk = ProtoChunk::new(Vector2::new(7, 4), 0);
+        chunk.populate_noise();
+        let populated = states(&chunk);
+        carve(&mut chunk, 0, &OVERWORLD_CARVERS);
+
+        let fluids = [0, WATER_BLOCK.state_id, LAVA_BLOCK.state_id];
+        for (before, after) in populated.into_iter().zip(states(&chunk)) {
+            if before != after {
+                assert!(fluids.contains(&after));
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash, mem, num::Wrapping, ops::AddAssign, sync::Arc};
+use std::{collections::HashMap, mem, num::Wrapping, ops::AddAssign, sync::Arc};
 
 use num_traits::Zero;
 use pumpkin_core::math::{floor_div, vector2::Vector2, vector3::Vector3};
@@ -19,7 +19,7 @@ use super::{
         WorldAquiferSampler,
     },
     biome_coords,
-    blender::Blender,
+    blender::{Blender, BlenderImpl},
     generation_shapes::GenerationShape,
     noise::{
         config::NoiseConfig,
@@ -143,6 +143,52 @@ impl<R: ComponentReference<ChunkNoiseState>> MutableComponentFunctionImpl<ChunkN
     }
 }
 
+/// The blend alpha or offset of the chunk's blender, also for positions sampled without the chunk
+#[derive(Clone, Copy)]
+pub enum ChunkBlendFunction {
+    Alpha,
+    Offset,
+}
+
+impl ComponentFunctionImpl for ChunkBlendFunction {}
+
+impl MutableComponentFunctionImpl<ChunkNoiseState> for ChunkBlendFunction {
+    fn sample_mut(&mut self, pos: &NoisePos, env: &ChunkNoiseState) -> f64 {
+        let result = env.blender.calculate(pos.x(), pos.z());
+        match self {
+            Self::Alpha => result.alpha,
+            Self::Offset => result.offset,
+        }
+    }
+
+    fn fill_mut(
+        &mut self,
+        arr: &mut [f64],
+        applier: &mut dyn EnvironmentApplierImpl<Env = ChunkNoiseState>,
+    ) {
+        applier.fill_mut(arr, self);
+    }
+
+    fn environment(&self) -> ConverterEnvironment<'_, ChunkNoiseState> {
+        ConverterEnvironment::ChunkNoise
+    }
+
+    fn into_environment(self: Box<Self>) -> OwnedConverterEnvironment<ChunkNoiseState> {
+        unreachable!()
+    }
+
+    fn convert(
+        self: Box<Self>,
+        _converter: &mut dyn ConverterImpl<ChunkNoiseState>,
+    ) -> ComponentReferenceImplementation<ChunkNoiseState> {
+        unreachable!()
+    }
+
+    fn clone_to_new_ref(&self) -> ComponentReferenceImplementation<ChunkNoiseState> {
+        MutableComponentReference(Box::new(*self)).into()
+    }
+}
+
 pub struct ChunkFlatCacheFunction<R: ComponentReference<ChunkNoiseState>> {
     delegate: R,
     cache: Box<[f64]>,
@@ -591,6 +637,12 @@ impl ConverterImpl<ChunkNoiseState> for ChunkNoiseWrappedFunctionConverter<'_> {
     ) -> ConversionResultPre<ChunkNoiseState> {
         match component {
             ConverterEnvironment::ChunkNoise => ConversionResultPre::NoChange,
+            ConverterEnvironment::BlendAlpha => ConversionResultPre::New(
+                MutableComponentReference(Box::new(ChunkBlendFunction::Alpha)).into(),
+            ),
+            ConverterEnvironment::BlendOffset => ConversionResultPre::New(
+                MutableComponentReference(Box::new(ChunkBlendFunction::Offset)).into(),
+            ),
             _ => ConversionResultPre::Default,
         }
     }
@@ -677,6 +729,7 @@ pub struct ChunkNoisePos {
     x: i32,
     y: i32,
     z: i32,
+    blender: Blender,
     //unique_id: UniqueChunkNoiseId,
 }
 
@@ -693,9 +746,8 @@ impl NoisePosImpl for ChunkNoisePos {
         self.z
     }
 
-    // TODO: implement blender
     fn get_blender(&self) -> Blender {
-        Blender::NO_BLEND
+        self.blender.clone()
     }
 }
 
@@ -848,7 +900,7 @@ impl ApplierImpl for ChunkNoiseApplier<'_> {
 
 pub const CHUNK_DIM: u8 = 16;
 
-#[derive(PartialEq, Eq, Clone, Hash, Default)]
+#[derive(Clone, Default)]
 pub struct ChunkNoiseState {
     cell_block_pos: Vector3<u8>,
     start_cell_pos: Vector2<i32>,
@@ -870,6 +922,8 @@ pub struct ChunkNoiseState {
     index: usize,
     cache_once_unique_index: Wrapping<u64>,
     sample_unique_index: Wrapping<u64>,
+
+    blender: Blender,
 }
 
 impl DensityFunctionEnvironment for ChunkNoiseState {}
@@ -889,11 +943,11 @@ impl ChunkNoiseState {
         let z = start_block_z + cell_block_z as i32;
 
         //log::debug!("Sampling pos {} {} {}", x, y, z);
-        // TODO: Add blender
         NoisePos::Chunk(ChunkNoisePos {
             x,
             y,
             z,
+            blender: self.blender.clone(),
             //unique_id: self.unique_id,
         })
     }
@@ -964,6 +1018,17 @@ impl BlockStateSampler {
             Self::Chained(chained) => chained.sample(pos, state, height_estimator),
         }
     }
+
+    fn aquifer_mut(&mut self) -> Option<&mut AquiferSampler> {
+        match self {
+            Self::Aquifer(aquifer) => Some(aquifer),
+            Self::Ore(_) => None,
+            Self::Chained(chained) => chained
+                .samplers
+                .iter_mut()
+                .find_map(|sampler| sampler.aquifer_mut()),
+        }
+    }
 }
 
 pub struct ChainedBlockStateSampler {
@@ -1029,6 +1094,7 @@ impl ChunkNoiseGenerator {
         level_sampler: FluidLevelSampler,
         aquifers: bool,
         ore_veins: bool,
+        blender: Blender,
     ) -> Self {
         let start_cell_pos = Vector2::new(
             floor_div(
@@ -1083,6 +1149,7 @@ impl ChunkNoiseGenerator {
             index,
             cache_once_unique_index,
             sample_unique_index,
+            blender,
         };
 
         let functions = CachedFunctions {
@@ -1212,7 +1279,7 @@ impl ChunkNoiseGenerator {
         self.shared.start_block_pos.x = current_x * self.horizontal_cell_block_count() as i32;
         self.shared.cell_block_pos.x = 0;
 
-        for cell_z in 0..=self.horizontal_cell_block_count() {
+        for cell_z in 0..=self.shared.horizontal_cell_count {
             let current_z = self.shared.start_cell_pos.z + cell_z as i32;
             self.shared.start_block_pos.z = current_z * self.horizontal_cell_block_count() as i32;
             self.shared.cell_block_pos.z = 0;
@@ -1354,6 +1421,24 @@ impl ChunkNoiseGenerator {
         )
     }
 
+    pub fn estimate_surface_height(&mut self, block_x: i32, block_z: i32) -> i32 {
+        self.density_functions
+            .estimate_surface_height(&self.shared, block_x, block_z)
+    }
+
+    /// Samples which fluid the aquifer places at an opened up position, like in carved
+    /// caves, `None` where the aquifer keeps a barrier
+    pub fn sample_aquifer(&mut self, x: i32, y: i32, z: i32) -> Option<BlockState> {
+        assert!(!self.shared.is_interpolating);
+        let pos = NoisePos::Unblended(UnblendedNoisePos::new(x, y, z));
+        match self.state_sampler.aquifer_mut() {
+            Some(aquifer) => {
+                aquifer.apply_with_density(&pos, &self.shared, &mut self.density_functions, 0f64)
+            }
+            None => Some(BlockState::AIR),
+        }
+    }
+
     pub fn horizontal_cell_block_count(&self) -> u8 {
         self.generation_shape.horizontal_cell_block_count()
     }
@@ -1381,6 +1466,7 @@ mod test {
 
     use crate::generation::{
         aquifer_sampler::{FluidLevel, FluidLevelSampler},
+        blender::Blender,
         generation_shapes::GenerationShape,
         noise::{config::NoiseConfig, router::OVERWORLD_NOISE_ROUTER},
         positions::chunk_pos,
@@ -1407,6 +1493,7 @@ mod test {
             sampler,
             true,
             true,
+            Blender::NO_BLEND,
         );
 
         let values = [
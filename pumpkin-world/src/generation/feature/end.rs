@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use pumpkin_core::{
+    math::vector3::Vector3,
+    random::{xoroshiro128::Xoroshiro, RandomImpl},
+};
+use pumpkin_macros::block_state;
+
+use crate::block::{block_registry::get_block, BlockState};
+
+use super::{block_at, set_block_at, ProtoChunk};
+
+const END_STONE: BlockState = block_state!("end_stone");
+const CHORUS_PLANT: BlockState = block_state!("chorus_plant");
+const CHORUS_FLOWER: BlockState = block_state!("chorus_flower");
+
+/// How far branches may grow away from the stem
+const CHORUS_SIZE: i32 = 8;
+/// Branches stop splitting after this many layers
+const CHORUS_MAX_LAYER: i32 = 4;
+
+const UP: Vector3<i32> = Vector3::new(0, 1, 0);
+const DOWN: Vector3<i32> = Vector3::new(0, -1, 0);
+/// North, east, south and west, in the order vanilla picks random horizontal directions
+const HORIZONTAL: [Vector3<i32>; 4] = [
+    Vector3::new(0, 0, -1),
+    Vector3::new(1, 0, 0),
+    Vector3::new(0, 0, 1),
+    Vector3::new(-1, 0, 0),
+];
+const CONNECTIONS: [(&str, Vector3<i32>); 6] = [
+    ("down", DOWN),
+    ("up", UP),
+    ("north", HORIZONTAL[0]),
+    ("east", HORIZONTAL[1]),
+    ("south", HORIZONTAL[2]),
+    ("west", HORIZONTAL[3]),
+];
+
+fn is_air(chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+    block_at(chunk, pos).is_some_and(|state| state.is_air())
+}
+
+fn is_chorus(state: BlockState) -> bool {
+    state.of_block(CHORUS_PLANT.block_id) || state.of_block(CHORUS_FLOWER.block_id)
+}
+
+/// Whether every horizontal neighbour except the one towards `except` is air
+fn is_surrounded_by_air(chunk: &ProtoChunk, pos: &Vector3<i32>, except: Option<usize>) -> bool {
+    HORIZONTAL
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| Some(*index) != except)
+        .all(|(_, direction)| is_air(chunk, &pos.add(direction)))
+}
+
+/// Grows a chorus plant on end stone, like a chorus flower which aged until it stopped
+/// branching
+pub(super) fn generate_chorus_plant(
+    chunk: &mut ProtoChunk,
+    random: &mut Xoroshiro,
+    origin: Vector3<i32>,
+) {
+    if !is_air(chunk, &origin) || block_at(chunk, &origin.add(&DOWN)) != Some(END_STONE) {
+        return;
+    }
+
+    let mut placed = vec![origin];
+    set_block_at(chunk, &origin, CHORUS_PLANT);
+    grow_chorus(chunk, random, origin, origin, 0, &mut placed);
+    connect_chorus(chunk, &placed);
+}
+
+fn grow_chorus(
+    chunk: &mut ProtoChunk,
+    random: &mut Xoroshiro,
+    pos: Vector3<i32>,
+    root: Vector3<i32>,
+    layer: i32,
+    placed: &mut Vec<Vector3<i32>>,
+) {
+    let mut height = random.next_bounded_i32(4) + 1;
+    if layer == 0 {
+        height += 1;
+    }
+    for offset in 1..=height {
+        let stem = pos.add(&Vector3::new(0, offset, 0));
+        if !is_surrounded_by_air(chunk, &stem, None) {
+            return;
+        }
+        set_block_at(chunk, &stem, CHORUS_PLANT);
+        placed.push(stem);
+    }
+
+    let top = pos.add(&Vector3::new(0, height, 0));
+    let mut branched = false;
+    if layer < CHORUS_MAX_LAYER {
+        let mut branches = random.next_bounded_i32(4);
+        if layer == 0 {
+            branches += 1;
+        }
+        for _ in 0..branches {
+            let direction = random.next_bounded_i32(4) as usize;
+            let branch = top.add(&HORIZONTAL[direction]);
+            if (branch.x - root.x).abs() < CHORUS_SIZE
+                && (branch.z - root.z).abs() < CHORUS_SIZE
+                && is_air(chunk, &branch)
+                && is_air(chunk, &branch.add(&DOWN))
+                && is_surrounded_by_air(chunk, &branch, Some((direction + 2) % 4))
+            {
+                branched = true;
+                set_block_at(chunk, &branch, CHORUS_PLANT);
+                placed.push(branch);
+                grow_chorus(chunk, random, branch, root, layer + 1, placed);
+            }
+        }
+    }
+
+    if !branched {
+        // A flower which won't grow any further
+        set_block_at(chunk, &top, chorus_flower(5));
+        placed.push(top);
+    }
+}
+
+fn chorus_flower(age: u8) -> BlockState {
+    let block = get_block("chorus_flower").expect("Chorus flowers are known blocks");
+    let properties = HashMap::from([("age".to_string(), age.to_string())]);
+    BlockState {
+        state_id: block.state_with_properties(&properties),
+        block_id: block.id,
+    }
+}
+
+/// Connects the chorus plant blocks to their neighbours once the plant is complete, plants
+/// also connect down to end stone
+fn connect_chorus(chunk: &mut ProtoChunk, placed: &[Vector3<i32>]) {
+    let block = get_block("chorus_plant").expect("Chorus plants are known blocks");
+    for pos in placed {
+        if !block_at(chunk, pos).is_some_and(|state| state.of_block(CHORUS_PLANT.block_id)) {
+            continue;
+        }
+        let properties = CONNECTIONS
+            .iter()
+            .map(|(name, direction)| {
+                let connected = block_at(chunk, &pos.add(direction)).is_some_and(|state| {
+                    is_chorus(state) || (*name == "down" && state == END_STONE)
+                });
+                (name.to_string(), connected.to_string())
+            })
+            .collect();
+        set_block_at(
+            chunk,
+            pos,
+            BlockState {
+                state_id: block.state_with_properties(&properties),
+                block_id: block.id,
+            },
+        );
+    }
+}
@@ -0,0 +1,555 @@
+use std::sync::LazyLock;
+
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{xoroshiro128::Xoroshiro, RandomImpl},
+};
+use pumpkin_macros::block_state;
+use serde::Deserialize;
+
+use crate::{biome::Biome, block::BlockState, WORLD_HEIGHT, WORLD_LOWEST_Y};
+
+use super::{
+    chunk_noise::{LAVA_BLOCK, WATER_BLOCK},
+    positions::chunk_pos,
+    proto_chunk::ProtoChunk,
+};
+
+const BEDROCK: BlockState = block_state!("bedrock");
+
+mod end;
+mod nether;
+mod ore;
+
+pub use nether::{DeltaFeature, ReplaceBlobsFeature};
+pub use ore::OreFeature;
+
+/// Vanilla places ores during the `underground_ores` generation step
+const UNDERGROUND_ORES_STEP: u64 = 6;
+
+pub static ORE_FEATURES: LazyLock<Vec<PlacedFeature>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../../assets/ore_features.json"))
+        .expect("Could not parse ore_features.json registry.")
+});
+
+pub static NETHER_FEATURES: LazyLock<Vec<PlacedFeature>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../../assets/nether_features.json"))
+        .expect("Could not parse nether_features.json registry.")
+});
+
+pub static END_FEATURES: LazyLock<Vec<PlacedFeature>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../../assets/end_features.json"))
+        .expect("Could not parse end_features.json registry.")
+});
+
+#[derive(Deserialize)]
+pub struct PlacedFeature {
+    pub name: String,
+    pub feature: Feature,
+    pub placement: Vec<PlacementModifier>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Feature {
+    Ore(OreFeature),
+    GlowstoneBlob,
+    BasaltPillar,
+    DeltaFeature(DeltaFeature),
+    NetherrackReplaceBlobs(ReplaceBlobsFeature),
+    ChorusPlant,
+}
+
+impl Feature {
+    /// Features which look at the terrain around them can only start in the decorated chunk,
+    /// the terrain of its neighbours is unknown
+    fn reads_terrain(&self) -> bool {
+        !matches!(self, Self::Ore(_))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PlacementModifier {
+    Count {
+        count: IntProvider,
+    },
+    RarityFilter {
+        chance: u32,
+    },
+    InSquare,
+    HeightRange {
+        height: HeightProvider,
+    },
+    /// Picks `count` columns and a position on every floor in them, until no column has
+    /// another floor
+    CountOnEveryLayer {
+        count: IntProvider,
+    },
+    /// Moves the position on top of the highest block of its column
+    Heightmap,
+    /// Vanilla checks the feature list of the biome, we list the biomes instead
+    Biome {
+        biomes: Vec<Biome>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum IntProvider {
+    Constant(i32),
+    Ranged(RangedIntProvider),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RangedIntProvider {
+    Uniform(UniformIntProvider),
+    /// Smaller values are more likely
+    BiasedToBottom(UniformIntProvider),
+}
+
+#[derive(Deserialize)]
+pub struct UniformIntProvider {
+    min_inclusive: i32,
+    max_inclusive: i32,
+}
+
+impl IntProvider {
+    fn sample(&self, random: &mut Xoroshiro) -> i32 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Ranged(RangedIntProvider::Uniform(uniform)) => {
+                random.next_inbetween_i32(uniform.min_inclusive, uniform.max_inclusive)
+            }
+            Self::Ranged(RangedIntProvider::BiasedToBottom(biased)) => {
+                let range =
+                    random.next_bounded_i32(biased.max_inclusive - biased.min_inclusive + 1);
+                biased.min_inclusive + random.next_bounded_i32(range + 1)
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YOffset {
+    Absolute(i32),
+    AboveBottom(i32),
+    BelowTop(i32),
+}
+
+impl YOffset {
+    pub fn get_y(&self) -> i32 {
+        match self {
+            Self::Absolute(y) => *y,
+            Self::AboveBottom(offset) => WORLD_LOWEST_Y as i32 + offset,
+            Self::BelowTop(offset) => WORLD_LOWEST_Y as i32 + WORLD_HEIGHT as i32 - 1 - offset,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HeightProvider {
+    Uniform {
+        min_inclusive: YOffset,
+        max_inclusive: YOffset,
+    },
+    Trapezoid {
+        min_inclusive: YOffset,
+        max_inclusive: YOffset,
+        #[serde(default)]
+        plateau: i32,
+    },
+}
+
+impl HeightProvider {
+    fn sample(&self, random: &mut Xoroshiro) -> i32 {
+        match self {
+            Self::Uniform {
+                min_inclusive,
+                max_inclusive,
+            } => random.next_inbetween_i32(min_inclusive.get_y(), max_inclusive.get_y()),
+            Self::Trapezoid {
+                min_inclusive,
+                max_inclusive,
+                plateau,
+            } => {
+                let min = min_inclusive.get_y();
+                let max = max_inclusive.get_y();
+                if min > max {
+                    return min;
+                }
+                let range = max - min;
+                if *plateau >= range {
+                    return random.next_inbetween_i32(min, max);
+                }
+                let slope = (range - plateau) / 2;
+                let rest = range - slope;
+                min + random.next_inbetween_i32(0, rest) + random.next_inbetween_i32(0, slope)
+            }
+        }
+    }
+}
+
+impl PlacementModifier {
+    fn positions(
+        &self,
+        chunk: &ProtoChunk,
+        biome_at: &dyn Fn(i32, i32) -> Biome,
+        random: &mut Xoroshiro,
+        pos: Vector3<i32>,
+    ) -> Vec<Vector3<i32>> {
+        match self {
+            Self::Count { count } => vec![pos; count.sample(random).max(0) as usize],
+            Self::RarityFilter { chance } => {
+                if random.next_f32() < 1.0 / *chance as f32 {
+                    vec![pos]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::InSquare => {
+                let x = random.next_bounded_i32(16) + pos.x;
+                let z = random.next_bounded_i32(16) + pos.z;
+                vec![Vector3::new(x, pos.y, z)]
+            }
+            Self::HeightRange { height } => {
+                vec![Vector3::new(pos.x, height.sample(random), pos.z)]
+            }
+            Self::CountOnEveryLayer { count } => {
+                let mut positions = Vec::new();
+                let mut layer = 0;
+                loop {
+                    let mut found = false;
+                    for _ in 0..count.sample(random) {
+                        let x = random.next_bounded_i32(16) + pos.x;
+                        let z = random.next_bounded_i32(16) + pos.z;
+                        if let Some(y) = Self::floor_on_layer(chunk, x, z, layer) {
+                            positions.push(Vector3::new(x, y, z));
+                            found = true;
+                        }
+                    }
+                    if !found {
+                        return positions;
+                    }
+                    layer += 1;
+                }
+            }
+            Self::Heightmap => {
+                let min_y = chunk.min_y();
+                let y = (min_y..min_y + chunk.height())
+                    .rev()
+                    .find(|y| {
+                        !chunk
+                            .get_block_state(&Vector3::new(pos.x, *y, pos.z))
+                            .is_air()
+                    })
+                    .map_or(min_y, |y| y + 1);
+                vec![Vector3::new(pos.x, y, pos.z)]
+            }
+            Self::Biome { biomes } => {
+                if biomes.contains(&biome_at(pos.x, pos.z)) {
+                    vec![pos]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
+    /// The y of the `layer`th floor from the top of the column, floors are solid blocks other
+    /// than bedrock with an open block above
+    fn floor_on_layer(chunk: &ProtoChunk, x: i32, z: i32, layer: usize) -> Option<i32> {
+        let is_open = |state: BlockState| {
+            state.is_air()
+                || state.of_block(WATER_BLOCK.block_id)
+                || state.of_block(LAVA_BLOCK.block_id)
+        };
+        let min_y = chunk.min_y();
+        let top_y = (min_y..min_y + chunk.height())
+            .rev()
+            .find(|y| !chunk.get_block_state(&Vector3::new(x, *y, z)).is_air())?
+            + 1;
+
+        let mut floors = 0;
+        let mut above = chunk.get_block_state(&Vector3::new(x, top_y, z));
+        for y in (min_y + 1..=top_y).rev() {
+            let below = chunk.get_block_state(&Vector3::new(x, y - 1, z));
+            if !is_open(below) && is_open(above) && !below.of_block(BEDROCK.block_id) {
+                if floors == layer {
+                    return Some(y - 1);
+                }
+                floors += 1;
+            }
+            above = below;
+        }
+        None
+    }
+}
+
+impl PlacedFeature {
+    /// Places the feature at every position the placement modifiers produce, modifiers are
+    /// applied depth first so every position consumes its randomness before the next one
+    fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        biome_at: &dyn Fn(i32, i32) -> Biome,
+        random: &mut Xoroshiro,
+        modifiers: &[PlacementModifier],
+        pos: Vector3<i32>,
+    ) {
+        match modifiers.split_first() {
+            Some((modifier, rest)) => {
+                for pos in modifier.positions(chunk, biome_at, random, pos) {
+                    self.generate(chunk, biome_at, random, rest, pos);
+                }
+            }
+            None => match &self.feature {
+                Feature::Ore(ore) => ore.generate(chunk, random, pos),
+                Feature::GlowstoneBlob => nether::generate_glowstone_blob(chunk, random, pos),
+                Feature::BasaltPillar => nether::generate_basalt_pillar(chunk, random, pos),
+                Feature::DeltaFeature(delta) => delta.generate(chunk, random, pos),
+                Feature::NetherrackReplaceBlobs(blobs) => blobs.generate(chunk, random, pos),
+                Feature::ChorusPlant => end::generate_chorus_plant(chunk, random, pos),
+            },
+        }
+    }
+}
+
+/// The population seed vanilla derives for a chunk from its first block position
+fn population_seed(seed: u64, block_x: i32, block_z: i32) -> u64 {
+    let mut random = Xoroshiro::from_seed(seed);
+    let a = random.next_i64() | 1;
+    let b = random.next_i64() | 1;
+    ((block_x as i64)
+        .wrapping_mul(a)
+        .wrapping_add((block_z as i64).wrapping_mul(b))
+        ^ seed as i64) as u64
+}
+
+/// The block at the world position `pos`, `None` outside of the chunk
+fn block_at(chunk: &ProtoChunk, pos: &Vector3<i32>) -> Option<BlockState> {
+    contains(chunk, pos).then(|| chunk.get_block_state(pos))
+}
+
+/// Sets the block at the world position `pos`, positions outside of the chunk are skipped
+fn set_block_at(chunk: &mut ProtoChunk, pos: &Vector3<i32>, state: BlockState) {
+    if contains(chunk, pos) {
+        chunk.set_block_state(pos, state);
+    }
+}
+
+fn contains(chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+    let chunk_pos = chunk.chunk_pos();
+    (chunk_pos::start_block_x(&chunk_pos)..=chunk_pos::end_block_x(&chunk_pos)).contains(&pos.x)
+        && (chunk_pos::start_block_z(&chunk_pos)..=chunk_pos::end_block_z(&chunk_pos))
+            .contains(&pos.z)
+        && (chunk.min_y()..chunk.min_y() + chunk.height()).contains(&pos.y)
+}
+
+/// The positions in the box of `range` around `center`, ordered by their manhattan distance
+/// to `center` like vanilla's `BlockPos.iterateOutwards`
+fn iterate_outwards(center: Vector3<i32>, range: Vector3<i32>) -> Vec<Vector3<i32>> {
+    let mut positions = Vec::new();
+    for distance in 0..=range.x + range.y + range.z {
+        let limit_x = range.x.min(distance);
+        for x in -limit_x..=limit_x {
+            let limit_y = range.y.min(distance - x.abs());
+            for y in -limit_y..=limit_y {
+                let z = distance - x.abs() - y.abs();
+                if z > range.z {
+                    continue;
+                }
+                positions.push(center.add(&Vector3::new(x, y, z)));
+                if z != 0 {
+                    positions.push(center.add(&Vector3::new(x, y, -z)));
+                }
+            }
+        }
+    }
+    positions
+}
+
+fn manhattan_distance(first: &Vector3<i32>, second: &Vector3<i32>) -> i32 {
+    (first.x - second.x).abs() + (first.y - second.y).abs() + (first.z - second.z).abs()
+}
+
+/// Places `features` from the chunk and its direct neighbours, every feature writes only into
+/// the given chunk so features crossing a chunk border line up on both sides. Features which
+/// read the terrain are cut off at the chunk border instead. `biome_at` gives the biome of a
+/// column in block coordinates
+pub fn generate_features(
+    chunk: &mut ProtoChunk,
+    seed: u64,
+    features: &[PlacedFeature],
+    biome_at: &dyn Fn(i32, i32) -> Biome,
+) {
+    let chunk_pos = chunk.chunk_pos();
+    for offset_x in -1..=1 {
+        for offset_z in -1..=1 {
+            let source = Vector2::new(chunk_pos.x + offset_x, chunk_pos.z + offset_z);
+            let block_x = chunk_pos::start_block_x(&source);
+            let block_z = chunk_pos::start_block_z(&source);
+            let population_seed = population_seed(seed, block_x, block_z);
+
+            for (index, feature) in features.iter().enumerate() {
+                if source != chunk_pos && feature.feature.reads_terrain() {
+                    continue;
+                }
+                let decorator_seed = population_seed
+                    .wrapping_add(index as u64)
+                    .wrapping_add(10000 * UNDERGROUND_ORES_STEP);
+                let mut random = Xoroshiro::from_seed(decorator_seed);
+                feature.generate(
+                    chunk,
+                    biome_at,
+                    &mut random,
+                    &feature.placement,
+                    Vector3::new(block_x, 0, block_z),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::{
+        biome::Biome,
+        block::BlockState,
+        generation::{
+            chunk_noise::STONE_BLOCK, proto_chunk::ProtoChunk, settings::NETHER_SETTINGS,
+        },
+    };
+
+    use super::{generate_features, NETHER_FEATURES, ORE_FEATURES};
+
+    fn plains(_: i32, _: i32) -> Biome {
+        Biome::Plains
+    }
+
+    fn stone_chunk(chunk_pos: Vector2<i32>) -> ProtoChunk {
+        let mut chunk = ProtoChunk::new(chunk_pos, 0);
+        chunk.fill(STONE_BLOCK);
+        chunk
+    }
+
+    /// Netherrack with open air in `open`
+    fn nether_chunk(chunk_pos: Vector2<i32>, open: impl Fn(i32) -> bool) -> ProtoChunk {
+        let mut chunk = ProtoChunk::with_settings(chunk_pos, 0, &NETHER_SETTINGS);
+        chunk.fill(BlockState::new("netherrack").unwrap());
+        for x in 0..16 {
+            for y in (0..128).filter(|y| open(*y)) {
+                for z in 0..16 {
+                    chunk.set_block_state(&Vector3::new(x, y, z), BlockState::AIR);
+                }
+            }
+        }
+        chunk
+    }
+
+    /// Netherrack with an open cave between y 40 and 100
+    fn nether_cave_chunk(chunk_pos: Vector2<i32>) -> ProtoChunk {
+        nether_chunk(chunk_pos, |y| (40..100).contains(&y))
+    }
+
+    fn count(chunk: &ProtoChunk, state: BlockState, y_range: std::ops::Range<i32>) -> usize {
+        let mut count = 0;
+        for x in 0..16 {
+            for y in y_range.clone() {
+                for z in 0..16 {
+                    if chunk.get_block_state(&Vector3::new(x, y, z)) == state {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn ores_are_placed() {
+        let mut chunk = stone_chunk(Vector2::new(2, 5));
+        generate_features(&mut chunk, 0, &ORE_FEATURES, &plains);
+
+        let coal = BlockState::new("coal_ore").unwrap();
+        let iron = BlockState::new("iron_ore").unwrap();
+        assert!(count(&chunk, coal, -64..320) > 0);
+        assert!(count(&chunk, iron, -64..320) > 0);
+    }
+
+    #[test]
+    fn ores_respect_height_ranges() {
+        let diamond = BlockState::new("diamond_ore").unwrap();
+        for x in 0..4 {
+            let mut chunk = stone_chunk(Vector2::new(x, 0));
+            generate_features(&mut chunk, 0, &ORE_FEATURES, &plains);
+            // The highest diamond blob is centered at y 16 and smaller than 12 blocks
+            assert_eq!(count(&chunk, diamond, 30..320), 0);
+        }
+    }
+
+    #[test]
+    fn ores_only_replace_stone() {
+        let mut chunk = ProtoChunk::new(Vector2::new(0, 0), 0);
+        generate_features(&mut chunk, 0, &ORE_FEATURES, &plains);
+        assert_eq!(count(&chunk, BlockState::AIR, -64..320), 16 * 16 * 384);
+    }
+
+    #[test]
+    fn features_are_deterministic() {
+        let mut first = stone_chunk(Vector2::new(-3, 7));
+        let mut second = stone_chunk(Vector2::new(-3, 7));
+        generate_features(&mut first, 99, &ORE_FEATURES, &plains);
+        generate_features(&mut second, 99, &ORE_FEATURES, &plains);
+        for x in 0..16 {
+            for y in -64..320 {
+                for z in 0..16 {
+                    let pos = Vector3::new(x, y, z);
+                    assert_eq!(first.get_block_state(&pos), second.get_block_state(&pos));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn glowstone_hangs_from_ceilings() {
+        let glowstone = BlockState::new("glowstone").unwrap();
+        // Every other layer is open so most positions are right below a ceiling
+        let mut chunk = nether_chunk(Vector2::new(0, 0), |y| y % 2 == 0);
+        generate_features(&mut chunk, 0, &NETHER_FEATURES, &|_, _| Biome::NetherWastes);
+        assert!(count(&chunk, glowstone, 0..128) > 0);
+
+        let mut chunk = nether_cave_chunk(Vector2::new(0, 0));
+        generate_features(&mut chunk, 0, &NETHER_FEATURES, &|_, _| Biome::NetherWastes);
+        // Blobs grow at most 12 blocks down from the ceiling
+        assert_eq!(count(&chunk, glowstone, 40..88), 0);
+    }
+
+    #[test]
+    fn basalt_deltas_are_decorated() {
+        let mut chunk = nether_cave_chunk(Vector2::new(1, 1));
+        generate_features(&mut chunk, 0, &NETHER_FEATURES, &|_, _| Biome::BasaltDeltas);
+        let lava = BlockState::new("lava").unwrap();
+        let basalt = BlockState::new("basalt").unwrap();
+        let blackstone = BlockState::new("blackstone").unwrap();
+        // Deltas are pools in the floor
+        assert!(count(&chunk, lava, 39..40) > 0);
+        assert!(count(&chunk, basalt, 0..128) > 0);
+        assert!(count(&chunk, blackstone, 0..128) > 0);
+    }
+
+    #[test]
+    fn biome_placement_filters_features() {
+        let mut chunk = nether_cave_chunk(Vector2::new(1, 1));
+        generate_features(&mut chunk, 0, &NETHER_FEATURES, &|_, _| {
+            Biome::CrimsonForest
+        });
+        let basalt = BlockState::new("basalt").unwrap();
+        assert_eq!(count(&chunk, basalt, 0..128), 0);
+    }
+}
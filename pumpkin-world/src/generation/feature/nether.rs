@@ -0,0 +1,286 @@
+use pumpkin_core::{
+    math::vector3::Vector3,
+    random::{xoroshiro128::Xoroshiro, RandomImpl},
+};
+use pumpkin_macros::block_state;
+use serde::Deserialize;
+
+use crate::block::BlockState;
+
+use super::{
+    block_at, iterate_outwards, manhattan_distance, ore::deserialize_state, set_block_at,
+    IntProvider, ProtoChunk, BEDROCK,
+};
+
+const GLOWSTONE: BlockState = block_state!("glowstone");
+const BASALT: BlockState = block_state!("basalt");
+
+const UP: Vector3<i32> = Vector3::new(0, 1, 0);
+const DOWN: Vector3<i32> = Vector3::new(0, -1, 0);
+/// North, south, west and east
+const HORIZONTAL: [Vector3<i32>; 4] = [
+    Vector3::new(0, 0, -1),
+    Vector3::new(0, 0, 1),
+    Vector3::new(-1, 0, 0),
+    Vector3::new(1, 0, 0),
+];
+const DIRECTIONS: [Vector3<i32>; 6] = [
+    DOWN,
+    UP,
+    HORIZONTAL[0],
+    HORIZONTAL[1],
+    HORIZONTAL[2],
+    HORIZONTAL[3],
+];
+
+fn is_air(chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+    block_at(chunk, pos).is_some_and(|state| state.is_air())
+}
+
+/// Grows a blob of glowstone down from a ceiling, every block touches exactly one other
+/// glowstone block when placed
+pub(super) fn generate_glowstone_blob(
+    chunk: &mut ProtoChunk,
+    random: &mut Xoroshiro,
+    origin: Vector3<i32>,
+) {
+    if !is_air(chunk, &origin) {
+        return;
+    }
+    let Some(above) = block_at(chunk, &origin.add(&UP)) else {
+        return;
+    };
+    if ![
+        block_state!("netherrack"),
+        BASALT,
+        block_state!("blackstone"),
+    ]
+    .iter()
+    .any(|ceiling| above.of_block(ceiling.block_id))
+    {
+        return;
+    }
+
+    set_block_at(chunk, &origin, GLOWSTONE);
+    for _ in 0..1500 {
+        let x = random.next_bounded_i32(8) - random.next_bounded_i32(8);
+        let y = -random.next_bounded_i32(12);
+        let z = random.next_bounded_i32(8) - random.next_bounded_i32(8);
+        let pos = origin.add(&Vector3::new(x, y, z));
+        if !is_air(chunk, &pos) {
+            continue;
+        }
+
+        let mut touching = 0;
+        for direction in DIRECTIONS {
+            if block_at(chunk, &pos.add(&direction)) == Some(GLOWSTONE) {
+                touching += 1;
+            }
+            if touching > 1 {
+                break;
+            }
+        }
+        if touching == 1 {
+            set_block_at(chunk, &pos, GLOWSTONE);
+        }
+    }
+}
+
+/// Hangs a column of basalt from a ceiling down to the floor and spreads a few basalt blocks
+/// around its foot
+pub(super) fn generate_basalt_pillar(
+    chunk: &mut ProtoChunk,
+    random: &mut Xoroshiro,
+    origin: Vector3<i32>,
+) {
+    if !is_air(chunk, &origin) || is_air(chunk, &origin.add(&UP)) {
+        return;
+    }
+
+    let mut pos = origin;
+    // Whether the thinner columns on each side still grow
+    let mut sides = [true; 4];
+    loop {
+        if pos.y < chunk.min_y() {
+            return;
+        }
+        if !is_air(chunk, &pos) {
+            break;
+        }
+        set_block_at(chunk, &pos, BASALT);
+        for (side, direction) in sides.iter_mut().zip(HORIZONTAL) {
+            *side = *side && {
+                // Stops the side column at a random height
+                let place = random.next_bounded_i32(10) != 0;
+                if place {
+                    set_block_at(chunk, &pos.add(&direction), BASALT);
+                }
+                place
+            };
+        }
+        pos = pos.add(&DOWN);
+    }
+
+    pos = pos.add(&UP);
+    for direction in HORIZONTAL {
+        if random.next_bool() {
+            set_block_at(chunk, &pos.add(&direction), BASALT);
+        }
+    }
+
+    pos = pos.add(&DOWN);
+    for x in -3..4 {
+        for z in -3..4 {
+            let corner: i32 = x * z;
+            if random.next_bounded_i32(10) >= 10 - corner.abs() {
+                continue;
+            }
+            let mut foot = pos.add(&Vector3::new(x, 0, z));
+            for _ in 0..3 {
+                if !is_air(chunk, &foot.add(&DOWN)) {
+                    break;
+                }
+                foot = foot.add(&DOWN);
+            }
+            if is_air(chunk, &foot.add(&DOWN)) {
+                continue;
+            }
+            set_block_at(chunk, &foot, BASALT);
+        }
+    }
+}
+
+/// A shallow pool of `contents` on a floor, optionally with a rim around it
+#[derive(Deserialize)]
+pub struct DeltaFeature {
+    #[serde(deserialize_with = "deserialize_state")]
+    contents: BlockState,
+    #[serde(deserialize_with = "deserialize_state")]
+    rim: BlockState,
+    size: IntProvider,
+    rim_size: IntProvider,
+}
+
+impl DeltaFeature {
+    /// Blocks a delta never replaces
+    const CANNOT_REPLACE: [BlockState; 7] = [
+        BEDROCK,
+        block_state!("nether_bricks"),
+        block_state!("nether_brick_fence"),
+        block_state!("nether_brick_stairs"),
+        block_state!("nether_wart"),
+        block_state!("chest"),
+        block_state!("spawner"),
+    ];
+
+    pub(super) fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut Xoroshiro,
+        origin: Vector3<i32>,
+    ) {
+        let with_rim = random.next_f64() < 0.9;
+        let rim_x = if with_rim {
+            self.rim_size.sample(random)
+        } else {
+            0
+        };
+        let rim_z = if with_rim {
+            self.rim_size.sample(random)
+        } else {
+            0
+        };
+        let has_rim = with_rim && rim_x != 0 && rim_z != 0;
+        let size_x = self.size.sample(random);
+        let size_z = self.size.sample(random);
+        let max_distance = size_x.max(size_z);
+
+        for pos in iterate_outwards(origin, Vector3::new(size_x, 0, size_z)) {
+            if manhattan_distance(&pos, &origin) > max_distance {
+                break;
+            }
+            if !self.can_place(chunk, &pos) {
+                continue;
+            }
+            if has_rim {
+                set_block_at(chunk, &pos, self.rim);
+            }
+            let inner = pos.add(&Vector3::new(rim_x, 0, rim_z));
+            if self.can_place(chunk, &inner) {
+                set_block_at(chunk, &inner, self.contents);
+            }
+        }
+    }
+
+    /// Only solid blocks with air above and solid blocks on every other side are replaced
+    fn can_place(&self, chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+        let Some(state) = block_at(chunk, pos) else {
+            return false;
+        };
+        if state.of_block(self.contents.block_id)
+            || Self::CANNOT_REPLACE
+                .iter()
+                .any(|block| state.of_block(block.block_id))
+        {
+            return false;
+        }
+        DIRECTIONS
+            .iter()
+            .all(|direction| is_air(chunk, &pos.add(direction)) == (*direction == UP))
+    }
+}
+
+/// Replaces the `target` blocks in a rough sphere with `state`
+#[derive(Deserialize)]
+pub struct ReplaceBlobsFeature {
+    #[serde(deserialize_with = "deserialize_state")]
+    target: BlockState,
+    #[serde(deserialize_with = "deserialize_state")]
+    state: BlockState,
+    radius: IntProvider,
+}
+
+impl ReplaceBlobsFeature {
+    fn is_target(&self, state: Option<BlockState>) -> bool {
+        state.is_some_and(|state| state.of_block(self.target.block_id))
+    }
+
+    pub(super) fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut Xoroshiro,
+        origin: Vector3<i32>,
+    ) {
+        let min_y = chunk.min_y() + 1;
+        let mut center = Vector3::new(
+            origin.x,
+            origin.y.clamp(min_y, chunk.min_y() + chunk.height() - 1),
+            origin.z,
+        );
+        // The blob starts at the first target block below the origin
+        loop {
+            if center.y <= min_y {
+                return;
+            }
+            if self.is_target(block_at(chunk, &center)) {
+                break;
+            }
+            center = center.add(&DOWN);
+        }
+
+        let radius = Vector3::new(
+            self.radius.sample(random),
+            self.radius.sample(random),
+            self.radius.sample(random),
+        );
+        let max_distance = radius.x.max(radius.y).max(radius.z);
+        for pos in iterate_outwards(center, radius) {
+            if manhattan_distance(&pos, &center) > max_distance {
+                break;
+            }
+            if self.is_target(block_at(chunk, &pos)) {
+                set_block_at(chunk, &pos, self.state);
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::f32::consts::PI;
+
+use pumpkin_core::{
+    math::vector3::Vector3,
+    random::{xoroshiro128::Xoroshiro, RandomImpl},
+};
+use serde::{Deserialize, Deserializer};
+
+use crate::{block::BlockState, generation::positions::chunk_pos};
+
+use super::ProtoChunk;
+
+#[derive(Deserialize)]
+pub struct OreFeature {
+    size: i32,
+    discard_chance_on_air_exposure: f32,
+    targets: Vec<OreTarget>,
+}
+
+#[derive(Deserialize)]
+struct OreTarget {
+    #[serde(deserialize_with = "deserialize_states")]
+    blocks: Vec<BlockState>,
+    #[serde(deserialize_with = "deserialize_state")]
+    state: BlockState,
+}
+
+pub(super) fn deserialize_state<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BlockState, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    BlockState::new(&name).ok_or_else(|| serde::de::Error::custom(format!("Unknown block {name}")))
+}
+
+fn deserialize_states<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<BlockState>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .into_iter()
+        .map(|name| {
+            BlockState::new(&name)
+                .ok_or_else(|| serde::de::Error::custom(format!("Unknown block {name}")))
+        })
+        .collect()
+}
+
+impl OreFeature {
+    /// Places a blob of ore along a short line through `origin`, only blocks inside the
+    /// chunk are written
+    pub(super) fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut Xoroshiro,
+        origin: Vector3<i32>,
+    ) {
+        let angle = random.next_f32() * PI;
+        let half_length = self.size as f32 / 8.0;
+        let (sin, cos) = (
+            (angle.sin() * half_length) as f64,
+            (angle.cos() * half_length) as f64,
+        );
+
+        let start_x = origin.x as f64 + sin;
+        let end_x = origin.x as f64 - sin;
+        let start_z = origin.z as f64 + cos;
+        let end_z = origin.z as f64 - cos;
+        let start_y = (origin.y + random.next_bounded_i32(3) - 2) as f64;
+        let end_y = (origin.y + random.next_bounded_i32(3) - 2) as f64;
+
+        let size = self.size as usize;
+        // x, y, z and radius of every sphere along the line
+        let mut spheres = Vec::with_capacity(size);
+        for index in 0..size {
+            let progress = index as f32 / size as f32;
+            let x = start_x + (end_x - start_x) * progress as f64;
+            let y = start_y + (end_y - start_y) * progress as f64;
+            let z = start_z + (end_z - start_z) * progress as f64;
+            let scale = random.next_f64() * self.size as f64 / 16.0;
+            let radius = (((PI * progress).sin() + 1.0) as f64 * scale + 1.0) / 2.0;
+            spheres.push((x, y, z, radius));
+        }
+
+        // Drop spheres that are fully contained in another one
+        for first in 0..size.saturating_sub(1) {
+            if spheres[first].3 <= 0.0 {
+                continue;
+            }
+            for second in first + 1..size {
+                if spheres[second].3 <= 0.0 {
+                    continue;
+                }
+                let dx = spheres[first].0 - spheres[second].0;
+                let dy = spheres[first].1 - spheres[second].1;
+                let dz = spheres[first].2 - spheres[second].2;
+                let dr = spheres[first].3 - spheres[second].3;
+                if dr * dr > dx * dx + dy * dy + dz * dz {
+                    if dr > 0.0 {
+                        spheres[second].3 = -1.0;
+                    } else {
+                        spheres[first].3 = -1.0;
+                    }
+                }
+            }
+        }
+
+        let chunk_pos = chunk.chunk_pos();
+        let chunk_x = chunk_pos::start_block_x(&chunk_pos);
+        let chunk_z = chunk_pos::start_block_z(&chunk_pos);
+        let min_y = chunk.min_y();
+        let max_y = min_y + chunk.height();
+
+        for (x, y, z, radius) in spheres {
+            if radius < 0.0 {
+                continue;
+            }
+            let from_x = ((x - radius).floor() as i32).max(chunk_x);
+            let to_x = ((x + radius).floor() as i32).min(chunk_x + 15);
+            let from_y = ((y - radius).floor() as i32).max(min_y);
+            let to_y = ((y + radius).floor() as i32).min(max_y - 1);
+            let from_z = ((z - radius).floor() as i32).max(chunk_z);
+            let to_z = ((z + radius).floor() as i32).min(chunk_z + 15);
+
+            for block_x in from_x..=to_x {
+                let scaled_x = (block_x as f64 + 0.5 - x) / radius;
+                if scaled_x * scaled_x >= 1.0 {
+                    continue;
+                }
+                for block_y in from_y..=to_y {
+                    let scaled_y = (block_y as f64 + 0.5 - y) / radius;
+                    if scaled_x * scaled_x + scaled_y * scaled_y >= 1.0 {
+                        continue;
+                    }
+                    for block_z in from_z..=to_z {
+                        let scaled_z = (block_z as f64 + 0.5 - z) / radius;
+                        if scaled_x * scaled_x + scaled_y * scaled_y + scaled_z * scaled_z >= 1.0 {
+                            continue;
+                        }
+                        let pos = Vector3::new(block_x - chunk_x, block_y, block_z - chunk_z);
+                        self.place(chunk, random, &pos);
+                    }
+                }
+            }
+        }
+    }
+
+    fn place(&self, chunk: &mut ProtoChunk, random: &mut Xoroshiro, pos: &Vector3<i32>) {
+        let state = chunk.get_block_state(pos);
+        // Ore already placed by an overlapping sphere is no longer a target
+        let Some(target) = self
+            .targets
+            .iter()
+            .find(|target| target.blocks.contains(&state))
+        else {
+            return;
+        };
+        if self.should_discard(random) && Self::is_exposed_to_air(chunk, pos) {
+            return;
+        }
+        chunk.set_block_state(pos, target.state);
+    }
+
+    fn should_discard(&self, random: &mut Xoroshiro) -> bool {
+        if self.discard_chance_on_air_exposure <= 0.0 {
+            false
+        } else if self.discard_chance_on_air_exposure >= 1.0 {
+            true
+        } else {
+            random.next_f32() < self.discard_chance_on_air_exposure
+        }
+    }
+
+    fn is_exposed_to_air(chunk: &ProtoChunk, pos: &Vector3<i32>) -> bool {
+        [
+            Vector3::new(pos.x - 1, pos.y, pos.z),
+            Vector3::new(pos.x + 1, pos.y, pos.z),
+            Vector3::new(pos.x, pos.y - 1, pos.z),
+            Vector3::new(pos.x, pos.y + 1, pos.z),
+            Vector3::new(pos.x, pos.y, pos.z - 1),
+            Vector3::new(pos.x, pos.y, pos.z + 1),
+        ]
+        .iter()
+        .any(|neighbor| {
+            (0..16).contains(&neighbor.x)
+                && (0..16).contains(&neighbor.z)
+                && chunk.get_block_state(neighbor).is_air()
+        })
+    }
+}
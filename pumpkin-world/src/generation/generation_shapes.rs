@@ -1,5 +1,6 @@
 use super::{biome_coords::to_block, height_limit::HeightLimitViewImpl};
 
+#[derive(Clone, Copy)]
 pub struct GenerationShape {
     min_y: i8,
     height: u16,
@@ -6,22 +6,96 @@ use crate::biome::Biome;
 use crate::block::block_state::BlockState;
 use crate::chunk::{ChunkBlocks, ChunkData};
 use crate::coordinates::{BlockCoordinates, ChunkRelativeBlockCoordinates, XZBlockCoordinates};
-use crate::generation::Seed;
+use crate::generation::{blender::Blender, preset::WorldPreset, structure::Structure, Seed};
 
 pub trait GeneratorInit {
     fn new(seed: Seed) -> Self;
+
+    /// Generators which don't depend on the preset generate like with [`Self::new`]
+    fn with_preset(seed: Seed, _preset: WorldPreset) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(seed)
+    }
 }
 
 pub trait WorldGenerator: Sync + Send {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData;
+
+    /// Generates the chunk so its terrain and biomes blend into the old chunks around it,
+    /// generators without blending support generate the chunk as usual
+    fn generate_blended_chunk(&self, at: Vector2<i32>, _blender: Blender) -> ChunkData {
+        self.generate_chunk(at)
+    }
+
+    /// The start chunk of the `structure` closest to the chunk `from`, `None` if the generator
+    /// has no structures of that type
+    fn locate_structure(
+        &self,
+        _structure: Structure,
+        _from: &Vector2<i32>,
+    ) -> Option<Vector2<i32>> {
+        None
+    }
+
+    /// The column with a biome matching `predicate` closest to `from`, `None` if there is none
+    /// within [`BIOME_SEARCH_RADIUS`] blocks
+    fn locate_biome(
+        &self,
+        _from: XZBlockCoordinates,
+        _predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        None
+    }
 }
 
+/// How far biomes are searched for, in blocks, like vanilla's `/locate biome`
+pub const BIOME_SEARCH_RADIUS: i32 = 6400;
+/// The distance between the columns sampled when searching for a biome
+const BIOME_SEARCH_STEP: i32 = 32;
+
 pub(crate) trait BiomeGenerator: Sync + Send {
     fn generate_biome(&self, at: XZBlockCoordinates) -> Biome;
 }
 
+/// Samples rings of columns around `from` until one has a matching biome, the closest column of
+/// that ring is returned. Like vanilla's `BiomeSource.findClosestBiome3d` at a single height
+pub(crate) fn locate_biome(
+    generator: &dyn BiomeGenerator,
+    from: XZBlockCoordinates,
+    predicate: &dyn Fn(Biome) -> bool,
+) -> Option<XZBlockCoordinates> {
+    let distance = |column: &XZBlockCoordinates| {
+        let x = i64::from(column.x - from.x);
+        let z = i64::from(column.z - from.z);
+        x * x + z * z
+    };
+    (0..=BIOME_SEARCH_RADIUS / BIOME_SEARCH_STEP).find_map(|ring| {
+        (-ring..=ring)
+            .flat_map(|x| (-ring..=ring).map(move |z| (x, z)))
+            .filter(|(x, z)| x.abs() == ring || z.abs() == ring)
+            .map(|(x, z)| XZBlockCoordinates {
+                x: from.x + x * BIOME_SEARCH_STEP,
+                z: from.z + z * BIOME_SEARCH_STEP,
+            })
+            .filter(|column| predicate(generator.generate_biome(*column)))
+            .min_by_key(distance)
+    })
+}
+
 pub(crate) trait TerrainGenerator: Sync + Send {
-    fn prepare_chunk(&self, at: &Vector2<i32>);
+    /// Whether caves and canyons are carved into the terrain after it is generated
+    fn carvers_enabled(&self) -> bool {
+        true
+    }
+
+    fn prepare_chunk(
+        &self,
+        at: &Vector2<i32>,
+        biome_generator: &dyn BiomeGenerator,
+        blender: &Blender,
+    );
 
     fn clean_chunk(&self, at: &Vector2<i32>);
 
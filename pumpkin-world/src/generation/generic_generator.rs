@@ -1,14 +1,17 @@
+use std::collections::HashMap;
+
 use noise::{NoiseFn, Perlin};
 use pumpkin_core::math::vector2::Vector2;
 
 use crate::{
-    chunk::{ChunkBlocks, ChunkData},
+    chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
     coordinates::{ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates},
     WORLD_LOWEST_Y,
 };
 
 use super::{
     generator::{BiomeGenerator, GeneratorInit, PerlinTerrainGenerator, WorldGenerator},
+    structure::ChunkStructures,
     Seed,
 };
 
@@ -75,6 +78,17 @@ impl<B: BiomeGenerator, T: PerlinTerrainGenerator> WorldGenerator for GenericGen
         ChunkData {
             blocks,
             position: at,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: ChunkStructures::default(),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
         }
     }
 }
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+use crate::{
+    biome::Biome,
+    chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
+    coordinates::{ChunkRelativeBlockCoordinates, XZBlockCoordinates},
+    generation::{
+        biome_coords,
+        feature::END_FEATURES,
+        generator::{locate_biome, BiomeGenerator, GeneratorInit},
+        noise::{
+            config::NoiseConfig,
+            density::{NoisePos, UnblendedNoisePos},
+        },
+        positions::chunk_pos,
+        proto_chunk::ProtoChunk,
+        section_coords,
+        settings::END_SETTINGS,
+        structure::{ChunkStructures, Structure, StructureGenerator, END_STRUCTURE_SETS},
+        Seed, WorldGenerator,
+    },
+};
+
+/// Sections closer to the origin than this belong to the main island
+const MAIN_ISLAND_SECTIONS: i64 = 64;
+
+/// Picks the end biomes like vanilla's end biome source, from how high the islands are
+pub(crate) struct EndBiomeGenerator {
+    config: NoiseConfig,
+}
+
+impl GeneratorInit for EndBiomeGenerator {
+    fn new(seed: Seed) -> Self {
+        Self {
+            config: NoiseConfig::new(seed.0, END_SETTINGS.router()),
+        }
+    }
+}
+
+impl BiomeGenerator for EndBiomeGenerator {
+    fn generate_biome(&self, at: XZBlockCoordinates) -> Biome {
+        // Biomes are sampled once every 4 blocks
+        let section_x = section_coords::block_to_section(biome_coords::to_block(
+            biome_coords::from_block(at.x),
+        ));
+        let section_z = section_coords::block_to_section(biome_coords::to_block(
+            biome_coords::from_block(at.z),
+        ));
+        if (section_x as i64).pow(2) + (section_z as i64).pow(2)
+            <= MAIN_ISLAND_SECTIONS * MAIN_ISLAND_SECTIONS
+        {
+            return Biome::TheEnd;
+        }
+
+        // The islands are sampled in the middle of every other section
+        let x = (section_x * 2 + 1) * 8;
+        let z = (section_z * 2 + 1) * 8;
+        let pos = NoisePos::Unblended(UnblendedNoisePos::new(x, 0, z));
+        let height = self.config.router().erosion.sample(&pos);
+        if height > 0.25 {
+            Biome::EndHighlands
+        } else if height >= -0.0625 {
+            Biome::EndMidlands
+        } else if height < -0.21875 {
+            Biome::SmallEndIslands
+        } else {
+            Biome::EndBarrens
+        }
+    }
+}
+
+/// Floating end stone islands, the main island around the origin is surrounded by a void
+/// until the outer islands start about 1000 blocks away
+pub struct EndGenerator {
+    biome_generator: EndBiomeGenerator,
+    structure_generator: StructureGenerator,
+    seed: Seed,
+}
+
+impl GeneratorInit for EndGenerator {
+    fn new(seed: Seed) -> Self {
+        Self {
+            biome_generator: EndBiomeGenerator::new(seed),
+            structure_generator: StructureGenerator::with_sets(seed, &END_STRUCTURE_SETS),
+            seed,
+        }
+    }
+}
+
+impl EndGenerator {
+    fn generate_proto_chunk(&self, at: Vector2<i32>) -> ProtoChunk {
+        let biome_at = |x, z| {
+            self.biome_generator
+                .generate_biome(XZBlockCoordinates { x, z })
+        };
+        let mut proto_chunk = ProtoChunk::with_settings(at, self.seed.0, &END_SETTINGS);
+        proto_chunk.populate_noise();
+        // The surface rule of the end only places end stone, which the noise already did
+        proto_chunk.generate_features(self.seed.0, &END_FEATURES, &biome_at);
+        proto_chunk
+    }
+
+    /// Vanilla only starts end cities in the highlands and midlands
+    fn can_start(&self, start: &Vector2<i32>) -> bool {
+        let biome = self.biome_generator.generate_biome(XZBlockCoordinates {
+            x: chunk_pos::start_block_x(start) + 8,
+            z: chunk_pos::start_block_z(start) + 8,
+        });
+        matches!(biome, Biome::EndHighlands | Biome::EndMidlands)
+    }
+
+    fn chunk_structures(&self, at: &Vector2<i32>) -> ChunkStructures {
+        let mut structures = self.structure_generator.chunk_structures(at);
+        structures
+            .starts
+            .retain(|_, start| self.can_start(&start.chunk));
+        for starts in structures.references.values_mut() {
+            starts.retain(|start| self.can_start(start));
+        }
+        structures.references.retain(|_, starts| !starts.is_empty());
+        structures
+    }
+}
+
+impl WorldGenerator for EndGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let proto_chunk = self.generate_proto_chunk(at);
+
+        let mut blocks = ChunkBlocks::default();
+        let min_y = proto_chunk.min_y() as i16;
+        let max_y = min_y + proto_chunk.height() as i16;
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                for y in (min_y..max_y).rev() {
+                    let state =
+                        proto_chunk.get_block_state(&Vector3::new(x.into(), y.into(), z.into()));
+                    blocks.set_block(
+                        ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: y.into(),
+                            z: z.into(),
+                        },
+                        state.state_id,
+                    );
+                }
+            }
+        }
+
+        ChunkData {
+            blocks,
+            position: at,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: self.chunk_structures(&at),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
+        }
+    }
+
+    fn locate_structure(&self, structure: Structure, from: &Vector2<i32>) -> Option<Vector2<i32>> {
+        self.structure_generator.locate(structure, from)
+    }
+
+    fn locate_biome(
+        &self,
+        from: XZBlockCoordinates,
+        predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        locate_biome(&self.biome_generator, from, predicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::{
+        biome::Biome,
+        coordinates::XZBlockCoordinates,
+        generation::{
+            generator::{BiomeGenerator, GeneratorInit},
+            proto_chunk::ProtoChunk,
+            settings::END_SETTINGS,
+            Seed,
+        },
+    };
+
+    use super::{EndBiomeGenerator, EndGenerator};
+
+    fn end_stone_count(chunk: &ProtoChunk) -> usize {
+        let end_stone = block_state!("end_stone");
+        let mut count = 0;
+        for x in 0..16 {
+            for y in 0..128 {
+                for z in 0..16 {
+                    if chunk.get_block_state(&Vector3::new(x, y, z)) == end_stone {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn main_island_at_origin() {
+        let mut chunk = ProtoChunk::with_settings(Vector2::new(0, 0), 0, &END_SETTINGS);
+        chunk.populate_noise();
+        assert!(end_stone_count(&chunk) > 0);
+        // Everything that isn't end stone is air, the end has no fluids
+        for x in 0..16 {
+            for y in 0..128 {
+                for z in 0..16 {
+                    let state = chunk.get_block_state(&Vector3::new(x, y, z));
+                    assert!(state == block_state!("end_stone") || state.is_air());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn void_around_main_island() {
+        // Between the main island and the outer islands
+        let mut chunk = ProtoChunk::with_settings(Vector2::new(40, 0), 0, &END_SETTINGS);
+        chunk.populate_noise();
+        assert_eq!(end_stone_count(&chunk), 0);
+    }
+
+    #[test]
+    fn main_island_is_the_end_biome() {
+        let generator = EndBiomeGenerator::new(Seed(0));
+        for (x, z) in [(0, 0), (500, -500), (-1000, 0)] {
+            assert_eq!(
+                generator.generate_biome(XZBlockCoordinates { x, z }),
+                Biome::TheEnd
+            );
+        }
+    }
+
+    #[test]
+    fn outer_biomes_generate() {
+        let generator = EndBiomeGenerator::new(Seed(0));
+        let mut found = Vec::new();
+        for x in -32..32 {
+            for z in -32..32 {
+                let biome = generator.generate_biome(XZBlockCoordinates {
+                    x: 2000 + x * 64,
+                    z: 2000 + z * 64,
+                });
+                if !found.contains(&biome) {
+                    found.push(biome);
+                }
+            }
+        }
+        for biome in [
+            Biome::EndHighlands,
+            Biome::EndMidlands,
+            Biome::SmallEndIslands,
+            Biome::EndBarrens,
+        ] {
+            assert!(found.contains(&biome), "{biome:?} was not generated");
+        }
+    }
+
+    #[test]
+    fn end_cities_start_in_highlands() {
+        let generator = EndGenerator::new(Seed(0));
+        for x in -4..4 {
+            for z in -4..4 {
+                let chunk = Vector2::new(100 + x * 20, 100 + z * 20);
+                for start in generator.chunk_structures(&chunk).starts.values() {
+                    let biome = generator
+                        .biome_generator
+                        .generate_biome(XZBlockCoordinates {
+                            x: start.chunk.x * 16 + 8,
+                            z: start.chunk.z * 16 + 8,
+                        });
+                    assert!(matches!(biome, Biome::EndHighlands | Biome::EndMidlands));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+
+use crate::{
+    biome::Biome,
+    chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
+    coordinates::{ChunkRelativeBlockCoordinates, XZBlockCoordinates},
+    generation::{
+        biome_coords,
+        feature::NETHER_FEATURES,
+        generator::{locate_biome, BiomeGenerator, GeneratorInit},
+        multi_noise_sampler::{BiomeEntries, NoiseValuePoint},
+        noise::{
+            config::NoiseConfig,
+            density::{NoisePos, UnblendedNoisePos},
+        },
+        proto_chunk::ProtoChunk,
+        settings::NETHER_SETTINGS,
+        structure::ChunkStructures,
+        surface::SurfaceSystem,
+        Seed, WorldGenerator,
+    },
+};
+
+/// The biome with its temperature, humidity and offset in the vanilla nether biome source
+const NETHER_BIOMES: [(Biome, f64, f64, f64); 5] = [
+    (Biome::NetherWastes, 0.0, 0.0, 0.0),
+    (Biome::SoulSandValley, 0.0, -0.5, 0.0),
+    (Biome::CrimsonForest, 0.4, 0.0, 0.0),
+    (Biome::WarpedForest, 0.0, 0.5, 0.375),
+    (Biome::BasaltDeltas, -0.5, 0.0, 0.175),
+];
+
+/// Picks the nether biomes from the temperature and vegetation noise of the nether router
+pub(crate) struct NetherBiomeGenerator {
+    config: NoiseConfig,
+    biome_entries: BiomeEntries,
+}
+
+impl GeneratorInit for NetherBiomeGenerator {
+    fn new(seed: Seed) -> Self {
+        let biome_entries = BiomeEntries::with_offsets(
+            NETHER_BIOMES
+                .iter()
+                .map(|(biome, temperature, humidity, offset)| {
+                    (
+                        *biome,
+                        NoiseValuePoint {
+                            temperature: *temperature,
+                            erosion: 0.0,
+                            depth: 0.0,
+                            continents: 0.0,
+                            weirdness: 0.0,
+                            humidity: *humidity,
+                        },
+                        *offset,
+                    )
+                })
+                .collect(),
+        );
+        Self {
+            config: NoiseConfig::new(seed.0, NETHER_SETTINGS.router()),
+            biome_entries,
+        }
+    }
+}
+
+impl BiomeGenerator for NetherBiomeGenerator {
+    fn generate_biome(&self, at: XZBlockCoordinates) -> Biome {
+        // Biomes are sampled once every 4 blocks
+        let x = biome_coords::to_block(biome_coords::from_block(at.x));
+        let z = biome_coords::to_block(biome_coords::from_block(at.z));
+        let pos = NoisePos::Unblended(UnblendedNoisePos::new(x, 0, z));
+        let router = self.config.router();
+        self.biome_entries.find_biome(&NoiseValuePoint {
+            temperature: router.temperature.sample(&pos),
+            erosion: 0.0,
+            depth: 0.0,
+            continents: 0.0,
+            weirdness: 0.0,
+            humidity: router.vegetation.sample(&pos),
+        })
+    }
+}
+
+/// Netherrack terrain between a bedrock floor and roof, with a lava sea below y 32
+pub struct NetherGenerator {
+    biome_generator: NetherBiomeGenerator,
+    surface: SurfaceSystem,
+    seed: Seed,
+}
+
+impl GeneratorInit for NetherGenerator {
+    fn new(seed: Seed) -> Self {
+        Self {
+            biome_generator: NetherBiomeGenerator::new(seed),
+            surface: SurfaceSystem::with_settings(seed, &NETHER_SETTINGS),
+            seed,
+        }
+    }
+}
+
+impl NetherGenerator {
+    fn generate_proto_chunk(&self, at: Vector2<i32>) -> ProtoChunk {
+        let biome_at = |x, z| {
+            self.biome_generator
+                .generate_biome(XZBlockCoordinates { x, z })
+        };
+        let mut proto_chunk = ProtoChunk::with_settings(at, self.seed.0, &NETHER_SETTINGS);
+        proto_chunk.populate_noise();
+        proto_chunk.build_surface(&self.surface, &biome_at);
+        proto_chunk.generate_features(self.seed.0, &NETHER_FEATURES, &biome_at);
+        proto_chunk
+    }
+}
+
+impl WorldGenerator for NetherGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let proto_chunk = self.generate_proto_chunk(at);
+
+        // Everything outside of the nether's height stays air
+        let mut blocks = ChunkBlocks::default();
+        let min_y = proto_chunk.min_y() as i16;
+        let max_y = min_y + proto_chunk.height() as i16;
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                for y in (min_y..max_y).rev() {
+                    let state =
+                        proto_chunk.get_block_state(&Vector3::new(x.into(), y.into(), z.into()));
+                    blocks.set_block(
+                        ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: y.into(),
+                            z: z.into(),
+                        },
+                        state.state_id,
+                    );
+                }
+            }
+        }
+
+        ChunkData {
+            blocks,
+            position: at,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: ChunkStructures::default(),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
+        }
+    }
+
+    fn locate_biome(
+        &self,
+        from: XZBlockCoordinates,
+        predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        locate_biome(&self.biome_generator, from, predicate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::{
+        biome::Biome,
+        coordinates::XZBlockCoordinates,
+        generation::{
+            chunk_noise::LAVA_BLOCK,
+            generator::{BiomeGenerator, GeneratorInit},
+            proto_chunk::ProtoChunk,
+            settings::NETHER_SETTINGS,
+            Seed, WorldGenerator,
+        },
+    };
+
+    use super::{NetherBiomeGenerator, NetherGenerator};
+
+    #[test]
+    fn lava_sea_below_32() {
+        let mut chunk = ProtoChunk::with_settings(Vector2::new(3, -2), 0, &NETHER_SETTINGS);
+        chunk.populate_noise();
+        let netherrack = block_state!("netherrack");
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in 0..128 {
+                    let state = chunk.get_block_state(&Vector3::new(x, y, z));
+                    if state == netherrack {
+                        continue;
+                    }
+                    if y < 32 {
+                        assert_eq!(state, LAVA_BLOCK, "{x} {y} {z}");
+                    } else {
+                        assert!(state.is_air(), "{x} {y} {z}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bedrock_floor_and_roof() {
+        let generator = NetherGenerator::new(Seed(0));
+        let chunk = generator.generate_proto_chunk(Vector2::new(0, 0));
+        let bedrock = block_state!("bedrock");
+        for x in 0..16 {
+            for z in 0..16 {
+                assert_eq!(chunk.get_block_state(&Vector3::new(x, 0, z)), bedrock);
+                assert_eq!(chunk.get_block_state(&Vector3::new(x, 127, z)), bedrock);
+                assert!(chunk.get_block_state(&Vector3::new(x, 128, z)).is_air());
+            }
+        }
+    }
+
+    #[test]
+    fn every_nether_biome_generates() {
+        let generator = NetherBiomeGenerator::new(Seed(0));
+        let mut found = Vec::new();
+        for x in -32..32 {
+            for z in -32..32 {
+                let biome = generator.generate_biome(XZBlockCoordinates {
+                    x: x * 128,
+                    z: z * 128,
+                });
+                if !found.contains(&biome) {
+                    found.push(biome);
+                }
+            }
+        }
+        for biome in [
+            Biome::NetherWastes,
+            Biome::SoulSandValley,
+            Biome::CrimsonForest,
+            Biome::WarpedForest,
+            Biome::BasaltDeltas,
+        ] {
+            assert!(found.contains(&biome), "{biome:?} was not generated");
+        }
+    }
+
+    #[test]
+    fn locates_the_nearest_biome() {
+        let generator = NetherGenerator::new(Seed(0));
+        let from = XZBlockCoordinates { x: 100, z: -50 };
+        let is_delta = |biome: Biome| biome == Biome::BasaltDeltas;
+        let delta = generator.locate_biome(from, &is_delta).unwrap();
+        assert_eq!(
+            generator.biome_generator.generate_biome(delta),
+            Biome::BasaltDeltas
+        );
+
+        let at_start = generator.biome_generator.generate_biome(from);
+        assert_eq!(
+            generator.locate_biome(from, &|biome: Biome| biome == at_start),
+            Some(from)
+        );
+        assert_eq!(
+            generator.locate_biome(from, &|biome: Biome| biome == Biome::Plains),
+            None
+        );
+    }
+}
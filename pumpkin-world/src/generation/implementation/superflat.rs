@@ -1,64 +1,444 @@
+use std::{collections::HashMap, str::FromStr};
+
 use pumpkin_core::math::vector2::Vector2;
+use serde::Deserialize;
+use thiserror::Error;
 
 use crate::{
     biome::Biome,
     block::block_state::BlockState,
-    coordinates::XZBlockCoordinates,
+    chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
+    coordinates::{ChunkRelativeBlockCoordinates, XZBlockCoordinates},
     generation::{
-        generator::{BiomeGenerator, GeneratorInit, TerrainGenerator},
-        generic_generator::GenericGenerator,
+        generator::{BiomeGenerator, GeneratorInit, WorldGenerator},
+        structure::{ChunkStructures, Structure, StructureGenerator},
         Seed,
     },
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
 };
 
-#[expect(dead_code)]
-pub type SuperflatGenerator = GenericGenerator<SuperflatBiomeGenerator, SuperflatTerrainGenerator>;
+/// The layers of the classic flat preset, which is used when no preset is configured
+const CLASSIC_FLAT: &str =
+    "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains";
 
-pub(crate) struct SuperflatBiomeGenerator {}
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SuperflatPresetError {
+    #[error("Unknown block {0}")]
+    UnknownBlock(String),
+    #[error("Invalid layer {0}")]
+    InvalidLayer(String),
+    #[error("Unknown biome {0}")]
+    UnknownBiome(String),
+    #[error("The layers are {0} blocks high, more than the world")]
+    TooHigh(u32),
+    #[error("Invalid preset JSON: {0}")]
+    Json(String),
+}
 
-impl GeneratorInit for SuperflatBiomeGenerator {
-    fn new(_: Seed) -> Self {
-        Self {}
+/// Blocks of the same type stacked on each other
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlatLayer {
+    pub block: BlockState,
+    pub height: u32,
+}
+
+/// What a flat world is made of, parsed from a vanilla preset string like
+/// `minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains` or from the JSON
+/// form vanilla uses for `generator-settings`
+#[derive(Debug, PartialEq)]
+pub struct SuperflatSettings {
+    /// From the bottom of the world up
+    pub layers: Vec<FlatLayer>,
+    pub biome: Biome,
+    /// The structures generated in the world
+    pub structures: Vec<Structure>,
+}
+
+impl Default for SuperflatSettings {
+    fn default() -> Self {
+        CLASSIC_FLAT
+            .parse()
+            .expect("The classic flat preset is valid")
     }
 }
 
+#[derive(Deserialize)]
+struct JsonPreset {
+    layers: Vec<JsonLayer>,
+    #[serde(default = "default_biome")]
+    biome: String,
+    /// Structure set ids, vanilla generates no structures without overrides
+    #[serde(default)]
+    structure_overrides: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    block: String,
+    height: u32,
+}
+
+fn default_biome() -> String {
+    "minecraft:plains".to_string()
+}
+
+impl SuperflatSettings {
+    fn new(
+        layers: Vec<FlatLayer>,
+        biome: &str,
+        structures: Vec<Structure>,
+    ) -> Result<Self, SuperflatPresetError> {
+        let height: u32 = layers.iter().map(|layer| layer.height).sum();
+        if height > WORLD_HEIGHT as u32 {
+            return Err(SuperflatPresetError::TooHigh(height));
+        }
+        Ok(Self {
+            layers,
+            biome: Biome::from_id(biome)
+                .ok_or_else(|| SuperflatPresetError::UnknownBiome(biome.to_string()))?,
+            structures,
+        })
+    }
+
+    fn parse_layer(layer: &str) -> Result<FlatLayer, SuperflatPresetError> {
+        let (height, block) = match layer.split_once('*') {
+            Some((height, block)) => (
+                height
+                    .trim()
+                    .parse()
+                    .map_err(|_| SuperflatPresetError::InvalidLayer(layer.to_string()))?,
+                block.trim(),
+            ),
+            None => (1, layer.trim()),
+        };
+        let block = BlockState::new(block)
+            .ok_or_else(|| SuperflatPresetError::UnknownBlock(block.to_string()))?;
+        Ok(FlatLayer { block, height })
+    }
+
+    /// Accepts structure ids, structure set ids and the names of the legacy preset format like
+    /// `village(size=1 distance=32)`. Structures we don't generate are skipped
+    fn parse_structure(name: &str) -> Option<Structure> {
+        let name = name.split('(').next().unwrap_or(name).trim();
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        match name {
+            "village" | "villages" => Some(Structure::VillagePlains),
+            "stronghold" | "strongholds" => Some(Structure::Stronghold),
+            _ => Structure::from_id(name),
+        }
+    }
+
+    fn from_json(json: &str) -> Result<Self, SuperflatPresetError> {
+        let preset: JsonPreset = serde_json::from_str(json)
+            .map_err(|error| SuperflatPresetError::Json(error.to_string()))?;
+        let layers = preset
+            .layers
+            .iter()
+            .map(|layer| {
+                let block = BlockState::new(&layer.block)
+                    .ok_or_else(|| SuperflatPresetError::UnknownBlock(layer.block.clone()))?;
+                Ok(FlatLayer {
+                    block,
+                    height: layer.height,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let structures = preset
+            .structure_overrides
+            .iter()
+            .filter_map(|name| Self::parse_structure(name))
+            .collect();
+        Self::new(layers, &preset.biome, structures)
+    }
+}
+
+impl FromStr for SuperflatSettings {
+    type Err = SuperflatPresetError;
+
+    fn from_str(preset: &str) -> Result<Self, Self::Err> {
+        let preset = preset.trim();
+        if preset.starts_with('{') {
+            return Self::from_json(preset);
+        }
+
+        let mut parts = preset.split(';');
+        let layers = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .filter(|layer| !layer.trim().is_empty())
+            .map(Self::parse_layer)
+            .collect::<Result<_, _>>()?;
+        let biome = parts.next().map_or("minecraft:plains", str::trim);
+        // Presets without structures keep the ones of the classic preset
+        let structures = match parts.next() {
+            Some(structures) => structures
+                .split(',')
+                .filter_map(Self::parse_structure)
+                .collect(),
+            None => vec![Structure::VillagePlains, Structure::Stronghold],
+        };
+        Self::new(layers, biome, structures)
+    }
+}
+
+pub(crate) struct SuperflatBiomeGenerator {
+    biome: Biome,
+}
+
 impl BiomeGenerator for SuperflatBiomeGenerator {
-    // TODO make generic over Biome and allow changing the Biome in the config.
     fn generate_biome(&self, _: XZBlockCoordinates) -> Biome {
-        Biome::Plains
+        self.biome
     }
 }
 
-pub(crate) struct SuperflatTerrainGenerator {}
+/// Layers of blocks from the bottom of the world up, with the same biome everywhere
+pub struct SuperflatGenerator {
+    biome_generator: SuperflatBiomeGenerator,
+    /// The block of every y level with a layer, starting at the bottom of the world
+    column: Vec<BlockState>,
+    structures: Vec<Structure>,
+    structure_generator: StructureGenerator,
+}
 
-impl GeneratorInit for SuperflatTerrainGenerator {
-    fn new(_: Seed) -> Self {
-        Self {}
+impl GeneratorInit for SuperflatGenerator {
+    fn new(seed: Seed) -> Self {
+        Self::with_settings(seed, SuperflatSettings::default())
     }
 }
 
-impl TerrainGenerator for SuperflatTerrainGenerator {
-    fn prepare_chunk(&self, _at: &Vector2<i32>) {}
-    fn clean_chunk(&self, _at: &Vector2<i32>) {}
+impl SuperflatGenerator {
+    pub fn with_settings(seed: Seed, settings: SuperflatSettings) -> Self {
+        let column = settings
+            .layers
+            .iter()
+            .flat_map(|layer| (0..layer.height).map(|_| layer.block))
+            .collect();
+        Self {
+            biome_generator: SuperflatBiomeGenerator {
+                biome: settings.biome,
+            },
+            column,
+            structures: settings.structures,
+            structure_generator: StructureGenerator::new(seed),
+        }
+    }
+
+    /// Villages only start in the biomes their houses are made for
+    fn can_generate(&self, structure: Structure) -> bool {
+        self.structures.contains(&structure)
+            && (structure != Structure::VillagePlains
+                || self
+                    .biome_generator
+                    .generate_biome(XZBlockCoordinates { x: 0, z: 0 })
+                    == Biome::Plains)
+    }
+
+    fn chunk_structures(&self, at: &Vector2<i32>) -> ChunkStructures {
+        let mut structures = self.structure_generator.chunk_structures(at);
+        structures
+            .starts
+            .retain(|structure, _| self.can_generate(*structure));
+        structures
+            .references
+            .retain(|structure, _| self.can_generate(*structure));
+        structures
+    }
+}
+
+impl WorldGenerator for SuperflatGenerator {
+    fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        let mut blocks = ChunkBlocks::default();
+        for x in 0..16u8 {
+            for z in 0..16u8 {
+                for (offset, state) in self.column.iter().enumerate() {
+                    if state.is_air() {
+                        continue;
+                    }
+                    blocks.set_block(
+                        ChunkRelativeBlockCoordinates {
+                            x: x.into(),
+                            y: (WORLD_LOWEST_Y + offset as i16).into(),
+                            z: z.into(),
+                        },
+                        state.state_id,
+                    );
+                }
+            }
+        }
 
-    // TODO allow specifying which blocks should be at which height in the config.
-    fn generate_block(
+        ChunkData {
+            blocks,
+            position: at,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: self.chunk_structures(&at),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
+        }
+    }
+
+    fn locate_structure(&self, structure: Structure, from: &Vector2<i32>) -> Option<Vector2<i32>> {
+        if !self.can_generate(structure) {
+            return None;
+        }
+        self.structure_generator.locate(structure, from)
+    }
+
+    /// The biome is the same everywhere, so it is either right where the search starts or nowhere
+    fn locate_biome(
         &self,
-        _chunk_pos: &Vector2<i32>,
-        _at: pumpkin_core::math::vector3::Vector3<i32>,
-        _biome: Biome,
-    ) -> BlockState {
-        todo!()
-    }
-
-    /*
-    fn generate_block(&self, at: BlockCoordinates, _: Biome) -> BlockState {
-        match *at.y {
-            -64 => block_state!("bedrock"),
-            -63..=-62 => block_state!("dirt"),
-            -61 => block_state!("grass_block"),
-            _ => BlockState::AIR,
+        from: XZBlockCoordinates,
+        predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        predicate(self.biome_generator.biome).then_some(from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use crate::{
+        biome::Biome,
+        block::block_state::BlockState,
+        coordinates::ChunkRelativeBlockCoordinates,
+        generation::{generator::WorldGenerator, structure::Structure, Seed},
+    };
+
+    use super::{FlatLayer, SuperflatGenerator, SuperflatPresetError, SuperflatSettings};
+
+    fn layer(block: &str, height: u32) -> FlatLayer {
+        FlatLayer {
+            block: BlockState::new(block).unwrap(),
+            height,
         }
     }
-    */
+
+    #[test]
+    fn parses_preset_strings() {
+        let settings: SuperflatSettings =
+            "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:desert"
+                .parse()
+                .unwrap();
+        assert_eq!(
+            settings.layers,
+            vec![
+                layer("bedrock", 1),
+                layer("dirt", 2),
+                layer("grass_block", 1)
+            ]
+        );
+        assert_eq!(settings.biome, Biome::Desert);
+        assert_eq!(
+            settings.structures,
+            vec![Structure::VillagePlains, Structure::Stronghold]
+        );
+    }
+
+    #[test]
+    fn parses_legacy_structure_options() {
+        let settings: SuperflatSettings =
+            "minecraft:bedrock,3*minecraft:stone;minecraft:plains;village(size=1 distance=32),decoration"
+                .parse()
+                .unwrap();
+        assert_eq!(settings.structures, vec![Structure::VillagePlains]);
+    }
+
+    #[test]
+    fn parses_json_presets() {
+        let settings: SuperflatSettings = r#"{
+            "layers": [
+                {"block": "minecraft:bedrock", "height": 1},
+                {"block": "minecraft:sandstone", "height": 10}
+            ],
+            "biome": "minecraft:desert",
+            "structure_overrides": ["minecraft:strongholds"]
+        }"#
+        .parse()
+        .unwrap();
+        assert_eq!(
+            settings.layers,
+            vec![layer("bedrock", 1), layer("sandstone", 10)]
+        );
+        assert_eq!(settings.biome, Biome::Desert);
+        assert_eq!(settings.structures, vec![Structure::Stronghold]);
+    }
+
+    #[test]
+    fn rejects_invalid_presets() {
+        assert_eq!(
+            "minecraft:bedrock,minecraft:not_a_block".parse::<SuperflatSettings>(),
+            Err(SuperflatPresetError::UnknownBlock(
+                "minecraft:not_a_block".to_string()
+            ))
+        );
+        assert_eq!(
+            "x*minecraft:dirt".parse::<SuperflatSettings>(),
+            Err(SuperflatPresetError::InvalidLayer(
+                "x*minecraft:dirt".to_string()
+            ))
+        );
+        assert_eq!(
+            "minecraft:dirt;minecraft:not_a_biome".parse::<SuperflatSettings>(),
+            Err(SuperflatPresetError::UnknownBiome(
+                "minecraft:not_a_biome".to_string()
+            ))
+        );
+        assert_eq!(
+            "400*minecraft:dirt".parse::<SuperflatSettings>(),
+            Err(SuperflatPresetError::TooHigh(400))
+        );
+    }
+
+    #[test]
+    fn layers_start_at_the_bottom() {
+        let generator = SuperflatGenerator::with_settings(Seed(0), SuperflatSettings::default());
+        let chunk = generator.generate_chunk(Vector2::new(3, -7));
+        let block_at = |y: i16| {
+            chunk.blocks.get_block(ChunkRelativeBlockCoordinates {
+                x: 5u8.into(),
+                y: y.into(),
+                z: 9u8.into(),
+            })
+        };
+        assert_eq!(
+            block_at(-64),
+            Some(BlockState::new("bedrock").unwrap().state_id)
+        );
+        assert_eq!(
+            block_at(-63),
+            Some(BlockState::new("dirt").unwrap().state_id)
+        );
+        assert_eq!(
+            block_at(-62),
+            Some(BlockState::new("dirt").unwrap().state_id)
+        );
+        assert_eq!(
+            block_at(-61),
+            Some(BlockState::new("grass_block").unwrap().state_id)
+        );
+        assert_eq!(block_at(-60), Some(BlockState::AIR.state_id));
+    }
+
+    #[test]
+    fn structures_follow_the_preset() {
+        let settings = "minecraft:bedrock;minecraft:plains;stronghold"
+            .parse()
+            .unwrap();
+        let generator = SuperflatGenerator::with_settings(Seed(0), settings);
+        let origin = Vector2::new(0, 0);
+        assert!(generator
+            .locate_structure(Structure::VillagePlains, &origin)
+            .is_none());
+        assert!(generator
+            .locate_structure(Structure::Stronghold, &origin)
+            .is_some());
+    }
 }
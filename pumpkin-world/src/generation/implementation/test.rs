@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     num::Wrapping,
     ops::{AddAssign, SubAssign},
 };
@@ -10,13 +11,20 @@ use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
 use crate::{
     biome::Biome,
     block::block_state::BlockState,
-    chunk::{ChunkBlocks, ChunkData},
+    chunk::{ticks::TickQueue, ChunkBlocks, ChunkData, ChunkLight},
     coordinates::{
         ChunkRelativeBlockCoordinates, ChunkRelativeXZBlockCoordinates, XZBlockCoordinates,
     },
     generation::{
-        generator::{BiomeGenerator, GeneratorInit, TerrainGenerator},
+        blender::{Blender, BlenderImpl},
+        carver::OVERWORLD_CARVERS,
+        feature::ORE_FEATURES,
+        generator::{locate_biome, BiomeGenerator, GeneratorInit, TerrainGenerator},
+        preset::WorldPreset,
         proto_chunk::ProtoChunk,
+        settings::GenerationSettings,
+        structure::{Structure, StructureGenerator},
+        surface::SurfaceSystem,
         Seed, WorldGenerator,
     },
     WORLD_LOWEST_Y, WORLD_MAX_Y,
@@ -25,33 +33,45 @@ use crate::{
 pub struct TestGenerator<B: BiomeGenerator, T: TerrainGenerator> {
     biome_generator: B,
     terrain_generator: T,
+    structure_generator: StructureGenerator,
 }
 
 impl<B: BiomeGenerator + GeneratorInit, T: TerrainGenerator + GeneratorInit> GeneratorInit
     for TestGenerator<B, T>
 {
     fn new(seed: Seed) -> Self {
+        Self::with_preset(seed, WorldPreset::Normal)
+    }
+
+    fn with_preset(seed: Seed, preset: WorldPreset) -> Self {
         Self {
-            biome_generator: B::new(seed),
-            terrain_generator: T::new(seed),
+            biome_generator: B::with_preset(seed, preset),
+            terrain_generator: T::with_preset(seed, preset),
+            structure_generator: StructureGenerator::new(seed),
         }
     }
 }
 
 impl<B: BiomeGenerator, T: TerrainGenerator> WorldGenerator for TestGenerator<B, T> {
     fn generate_chunk(&self, at: Vector2<i32>) -> ChunkData {
+        self.generate_blended_chunk(at, Blender::NO_BLEND)
+    }
+
+    fn generate_blended_chunk(&self, at: Vector2<i32>, blender: Blender) -> ChunkData {
         let mut blocks = ChunkBlocks::default();
-        self.terrain_generator.prepare_chunk(&at);
+        self.terrain_generator
+            .prepare_chunk(&at, &self.biome_generator, &blender);
 
         for x in 0..16u8 {
             for z in 0..16u8 {
-                let biome = self.biome_generator.generate_biome(
-                    ChunkRelativeXZBlockCoordinates {
-                        x: x.into(),
-                        z: z.into(),
-                    }
-                    .with_chunk_coordinates(at),
-                );
+                let column = ChunkRelativeXZBlockCoordinates {
+                    x: x.into(),
+                    z: z.into(),
+                }
+                .with_chunk_coordinates(at);
+                let biome = blender
+                    .blend_biome(column.x, column.z)
+                    .unwrap_or_else(|| self.biome_generator.generate_biome(column));
 
                 // TODO: This can be chunk specific
                 for y in (WORLD_LOWEST_Y..WORLD_MAX_Y).rev() {
@@ -77,8 +97,31 @@ impl<B: BiomeGenerator, T: TerrainGenerator> WorldGenerator for TestGenerator<B,
         ChunkData {
             blocks,
             position: at,
+            block_entities: HashMap::new(),
+            entities: Vec::new(),
+            entities_dirty: false,
+            points_of_interest: Vec::new(),
+            light: ChunkLight::default(),
+            structures: self.structure_generator.chunk_structures(&at),
+            blending_data: None,
+            block_ticks: TickQueue::default(),
+            fluid_ticks: TickQueue::default(),
+            inhabited_time: 0,
+            dirty: true,
         }
     }
+
+    fn locate_structure(&self, structure: Structure, from: &Vector2<i32>) -> Option<Vector2<i32>> {
+        self.structure_generator.locate(structure, from)
+    }
+
+    fn locate_biome(
+        &self,
+        from: XZBlockCoordinates,
+        predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        locate_biome(&self.biome_generator, from, predicate)
+    }
 }
 
 pub(crate) struct TestBiomeGenerator {}
@@ -98,28 +141,53 @@ impl BiomeGenerator for TestBiomeGenerator {
 
 pub(crate) struct TestTerrainGenerator {
     chunks: DashMap<Vector2<i32>, (ProtoChunk, Wrapping<u8>)>,
+    surface: SurfaceSystem,
+    settings: &'static GenerationSettings,
     seed: Seed,
 }
 
 impl GeneratorInit for TestTerrainGenerator {
     fn new(seed: Seed) -> Self {
+        Self::with_preset(seed, WorldPreset::Normal)
+    }
+
+    fn with_preset(seed: Seed, preset: WorldPreset) -> Self {
+        let settings = preset.overworld_settings();
         Self {
             chunks: DashMap::new(),
+            surface: SurfaceSystem::with_settings(seed, settings),
+            settings,
             seed,
         }
     }
 }
 
 impl TerrainGenerator for TestTerrainGenerator {
-    fn prepare_chunk(&self, at: &Vector2<i32>) {
+    fn prepare_chunk(
+        &self,
+        at: &Vector2<i32>,
+        biome_generator: &dyn BiomeGenerator,
+        blender: &Blender,
+    ) {
         let entry = self.chunks.entry(*at);
         match entry {
             Entry::Vacant(entry) => {
-                let mut proto_chunk = ProtoChunk::new(*at, self.seed.0);
+                let biome_at = |x, z| {
+                    blender.blend_biome(x, z).unwrap_or_else(|| {
+                        biome_generator.generate_biome(XZBlockCoordinates { x, z })
+                    })
+                };
+                let mut proto_chunk =
+                    ProtoChunk::with_blender(*at, self.seed.0, self.settings, blender.clone());
                 //let inst = std::time::Instant::now();
                 //println!("Populating chunk: {:?}", at);
                 proto_chunk.populate_noise();
                 //println!("Done populating chunk: {:?} ({:?})", at, inst.elapsed());
+                proto_chunk.build_surface(&self.surface, &biome_at);
+                if self.carvers_enabled() {
+                    proto_chunk.carve(self.seed.0, &OVERWORLD_CARVERS);
+                }
+                proto_chunk.generate_features(self.seed.0, &ORE_FEATURES, &biome_at);
                 entry.insert((proto_chunk, Wrapping(1)));
             }
             Entry::Occupied(mut entry) => {
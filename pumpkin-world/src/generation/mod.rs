@@ -1,8 +1,10 @@
 #![allow(dead_code)]
 
 pub mod aquifer_sampler;
-mod blender;
+pub mod blender;
+pub mod carver;
 pub mod chunk_noise;
+pub mod feature;
 pub mod generation_shapes;
 mod generator;
 mod generic_generator;
@@ -12,22 +14,95 @@ pub mod multi_noise_sampler;
 pub mod noise;
 pub mod ore_sampler;
 mod positions;
+pub mod preset;
 pub mod proto_chunk;
 mod seed;
+pub mod settings;
+pub mod structure;
+pub mod surface;
 
-pub use generator::WorldGenerator;
+use crate::dimension::Dimension;
+pub use generator::{WorldGenerator, BIOME_SEARCH_RADIUS};
 use implementation::{
+    end::EndGenerator,
+    nether::NetherGenerator,
     //overworld::biome::plains::PlainsGenerator,
+    superflat::{SuperflatGenerator, SuperflatSettings},
     test::{TestBiomeGenerator, TestGenerator, TestTerrainGenerator},
 };
+use preset::WorldPreset;
+use pumpkin_config::{LevelType, BASIC_CONFIG};
 pub use seed::Seed;
 
 use generator::GeneratorInit;
 
-pub fn get_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
-    // TODO decide which WorldGenerator to pick based on config.
+/// How a new level is generated, the dimensions of the server use the basic config while
+/// custom worlds bring their own
+#[derive(Clone)]
+pub struct GeneratorOptions {
+    /// The seed of new levels, existing levels keep the seed of their `level.dat`
+    pub seed: String,
+    pub level_type: LevelType,
+    pub generator_settings: String,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            seed: BASIC_CONFIG.seed.clone(),
+            level_type: BASIC_CONFIG.level_type,
+            generator_settings: BASIC_CONFIG.generator_settings.clone(),
+        }
+    }
+}
+
+pub fn get_world_gen(
+    seed: Seed,
+    dimension: Dimension,
+    options: &GeneratorOptions,
+) -> Box<dyn WorldGenerator> {
     //Box::new(PlainsGenerator::new(seed))
-    Box::new(TestGenerator::<TestBiomeGenerator, TestTerrainGenerator>::new(seed))
+    match dimension {
+        Dimension::Nether => Box::new(NetherGenerator::new(seed)),
+        Dimension::End => Box::new(EndGenerator::new(seed)),
+        Dimension::OverWorld => match WorldPreset::from(options.level_type) {
+            WorldPreset::Flat => Box::new(SuperflatGenerator::with_settings(
+                seed,
+                superflat_settings(&options.generator_settings),
+            )),
+            preset => Box::new(
+                TestGenerator::<TestBiomeGenerator, TestTerrainGenerator>::with_preset(
+                    seed, preset,
+                ),
+            ),
+        },
+    }
+}
+
+/// Picks the biomes of the default world of the dimension
+#[cfg(any(test, feature = "test-fixtures"))]
+pub(crate) fn biome_generator(
+    seed: Seed,
+    dimension: Dimension,
+) -> Box<dyn generator::BiomeGenerator> {
+    use implementation::{end::EndBiomeGenerator, nether::NetherBiomeGenerator};
+
+    match dimension {
+        Dimension::OverWorld => Box::new(TestBiomeGenerator::new(seed)),
+        Dimension::Nether => Box::new(NetherBiomeGenerator::new(seed)),
+        Dimension::End => Box::new(EndBiomeGenerator::new(seed)),
+    }
+}
+
+/// Falls back to the classic flat preset when the configured preset is invalid
+fn superflat_settings(preset: &str) -> SuperflatSettings {
+    if preset.trim().is_empty() {
+        return SuperflatSettings::default();
+    }
+    preset.parse().unwrap_or_else(|error| {
+        log::warn!("Invalid superflat preset, using the classic flat preset: {error}");
+        SuperflatSettings::default()
+    })
 }
 
 pub mod section_coords {
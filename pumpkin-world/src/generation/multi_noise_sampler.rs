@@ -46,14 +46,30 @@ pub struct BiomeEntries {
 pub struct SearchTreeNode {
     biome: Biome,
     center: NoiseValuePoint,
+    /// Moves the biome away from every sampled point, less likely biomes have a bigger offset
+    offset: f64,
 }
 
 impl BiomeEntries {
     /// Constructs a new search tree from a list of biomes and their corresponding noise value points.
     pub fn new(biomes: Vec<(Biome, NoiseValuePoint)>) -> Self {
+        Self::with_offsets(
+            biomes
+                .into_iter()
+                .map(|(biome, center)| (biome, center, 0.0))
+                .collect(),
+        )
+    }
+
+    /// Like [`Self::new`], with an offset for every biome
+    pub fn with_offsets(biomes: Vec<(Biome, NoiseValuePoint, f64)>) -> Self {
         let nodes = biomes
             .into_iter()
-            .map(|(biome, center)| SearchTreeNode { biome, center })
+            .map(|(biome, center, offset)| SearchTreeNode {
+                biome,
+                center,
+                offset,
+            })
             .collect();
 
         Self { nodes }
@@ -66,7 +82,7 @@ impl BiomeEntries {
         let mut min_distance = f64::MAX;
 
         for node in &self.nodes {
-            let distance = node.center.distance_squared(point);
+            let distance = node.center.distance_squared(point) + node.offset * node.offset;
             if distance < min_distance {
                 min_distance = distance;
                 closest_biome = Some(node.biome);
@@ -142,4 +158,31 @@ mod tests {
         let result = search_tree.find_biome(&query);
         assert_eq!(result, Biome::Plains);
     }
+
+    #[test]
+    fn test_biome_offset() {
+        let point = NoiseValuePoint {
+            temperature: 0.0,
+            erosion: 0.0,
+            depth: 0.0,
+            continents: 0.0,
+            weirdness: 0.0,
+            humidity: 0.0,
+        };
+        let biomes = vec![
+            (Biome::NetherWastes, point.clone(), 0.5),
+            (
+                Biome::WarpedForest,
+                NoiseValuePoint {
+                    humidity: 0.4,
+                    ..point
+                },
+                0.0,
+            ),
+        ];
+
+        // The offset pushes the closer biome further away than the other one
+        let search_tree = BiomeEntries::with_offsets(biomes);
+        assert_eq!(search_tree.find_biome(&point), Biome::WarpedForest);
+    }
 }
@@ -5,9 +5,9 @@ use crate::generation::{
         apply_blend_density,
         basic::RangeFunction,
         built_in_density_function::{
-            CAVES_ENTRANCES_OVERWORLD, CAVES_NOODLE_OVERWORLD, CONTINENTS_OVERWORLD,
-            CONTINENTS_OVERWORLD_LARGE_BIOME, EROSION_OVERWORLD, EROSION_OVERWORLD_LARGE_BIOME,
-            RIDGES_OVERWORLD, Y,
+            BASE_3D_NOISE_NETHER, CAVES_ENTRANCES_OVERWORLD, CAVES_NOODLE_OVERWORLD,
+            CONTINENTS_OVERWORLD, CONTINENTS_OVERWORLD_LARGE_BIOME, EROSION_OVERWORLD,
+            EROSION_OVERWORLD_LARGE_BIOME, RIDGES_OVERWORLD, Y,
         },
         vertical_range_choice,
     },
@@ -23,13 +23,14 @@ use super::{
             CAVES_SPAGHETTI_ROUGHNESS_FUNCTION_OVERWORLD, DEPTH_OVERWORLD,
             DEPTH_OVERWORLD_AMPLIFIED, DEPTH_OVERWORLD_LARGE_BIOME, FACTOR_OVERWORLD,
             FACTOR_OVERWORLD_AMPLIFIED, FACTOR_OVERWORLD_LARGE_BIOME, SHIFT_X, SHIFT_Z,
-            SLOPED_CHEESE_OVERWORLD, SLOPED_CHEESE_OVERWORLD_AMPLIFIED,
+            SLOPED_CHEESE_END, SLOPED_CHEESE_OVERWORLD, SLOPED_CHEESE_OVERWORLD_AMPLIFIED,
             SLOPED_CHEESE_OVERWORLD_LARGE_BIOME, ZERO,
         },
         component_functions::{
             ComponentReference, ComponentReferenceMap, ComponentReferenceMath, ConverterImpl,
             DensityFunctionEnvironment, NoEnvironment, SharedComponentReference,
         },
+        end::EndIslandFunction,
         lerp_density_static_start,
         noise::{InternalNoise, NoiseFunction, ShiftedNoiseFunction},
     },
@@ -41,6 +42,10 @@ pub static OVERWORLD_NOISE_ROUTER_LARGE: LazyLock<BaseRouter> =
     LazyLock::new(|| BaseRouter::create_surface_noise_router(true, false));
 pub static OVERWORLD_NOISE_ROUTER_AMPLIFIED: LazyLock<BaseRouter> =
     LazyLock::new(|| BaseRouter::create_surface_noise_router(false, true));
+pub static NETHER_NOISE_ROUTER: LazyLock<BaseRouter> =
+    LazyLock::new(BaseRouter::create_nether_noise_router);
+pub static END_NOISE_ROUTER: LazyLock<BaseRouter> =
+    LazyLock::new(BaseRouter::create_end_noise_router);
 
 pub struct BaseRouter {
     pub(crate) barrier: SharedComponentReference,
@@ -377,6 +382,108 @@ impl BaseRouter {
     }
 }
 
+impl BaseRouter {
+    /// The nether has no caves, aquifers or ore veins, only the temperature and vegetation
+    /// noises pick its biomes
+    pub fn create_nether_noise_router() -> Self {
+        let temperature = ShiftedNoiseFunction::<
+            NoEnvironment,
+            SharedComponentReference,
+            SharedComponentReference,
+            SharedComponentReference,
+        >::new(
+            SHIFT_X.clone(),
+            ZERO.clone(),
+            SHIFT_Z.clone(),
+            0.25f64,
+            0f64,
+            Arc::new(InternalNoise::new(
+                &built_in_noise_params::TEMPERATURE,
+                None,
+            )),
+        );
+
+        let vegetation = ShiftedNoiseFunction::<
+            NoEnvironment,
+            SharedComponentReference,
+            SharedComponentReference,
+            SharedComponentReference,
+        >::new(
+            SHIFT_X.clone(),
+            ZERO.clone(),
+            SHIFT_Z.clone(),
+            0.25f64,
+            0f64,
+            Arc::new(InternalNoise::new(&built_in_noise_params::VEGETATION, None)),
+        );
+
+        let final_density = apply_blend_density(apply_slides(
+            BASE_3D_NOISE_NETHER.clone(),
+            0,
+            128,
+            24,
+            0,
+            0.9375f64,
+            -8,
+            24,
+            2.5f64,
+        ));
+
+        Self {
+            barrier: ZERO.clone(),
+            fluid_level_floodedness: ZERO.clone(),
+            fluid_level_spread: ZERO.clone(),
+            lava: ZERO.clone(),
+            temperature: temperature.into(),
+            vegetation: vegetation.into(),
+            continents: ZERO.clone(),
+            erosion: ZERO.clone(),
+            depth: ZERO.clone(),
+            ridges: ZERO.clone(),
+            internal_density: ZERO.clone(),
+            final_density,
+            vein_toggle: ZERO.clone(),
+            vein_ridged: ZERO.clone(),
+            vein_gap: ZERO.clone(),
+        }
+    }
+}
+
+impl BaseRouter {
+    /// The end islands are shaped by the island function alone, which doubles as the erosion
+    /// the end biomes are picked by
+    pub fn create_end_noise_router() -> Self {
+        let islands: SharedComponentReference =
+            WrapperFunction::<NoEnvironment, SharedComponentReference>::new(
+                EndIslandFunction::new(0).into(),
+                WrapperType::Cache2D,
+            )
+            .into();
+
+        Self {
+            barrier: ZERO.clone(),
+            fluid_level_floodedness: ZERO.clone(),
+            fluid_level_spread: ZERO.clone(),
+            lava: ZERO.clone(),
+            temperature: ZERO.clone(),
+            vegetation: ZERO.clone(),
+            continents: ZERO.clone(),
+            erosion: islands.clone(),
+            depth: ZERO.clone(),
+            ridges: ZERO.clone(),
+            internal_density: apply_end_slides(islands.add_const(-0.703125f64)),
+            final_density: apply_blend_density(apply_end_slides(SLOPED_CHEESE_END.clone())),
+            vein_toggle: ZERO.clone(),
+            vein_ridged: ZERO.clone(),
+            vein_gap: ZERO.clone(),
+        }
+    }
+}
+
+fn apply_end_slides(density: SharedComponentReference) -> SharedComponentReference {
+    apply_slides(density, 0, 128, 72, -184, -23.4375f64, 4, 32, -0.234375f64)
+}
+
 fn apply_surface_slides(
     amplified: bool,
     density: SharedComponentReference,
@@ -0,0 +1,93 @@
+use pumpkin_config::LevelType;
+
+use super::settings::{
+    GenerationSettings, AMPLIFIED_SETTINGS, LARGE_BIOMES_SETTINGS, OVERWORLD_SETTINGS,
+};
+
+/// The world presets of vanilla's registry, which decide how the overworld is generated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorldPreset {
+    Normal,
+    Flat,
+    LargeBiomes,
+    Amplified,
+}
+
+impl WorldPreset {
+    pub const ALL: [WorldPreset; 4] =
+        [Self::Normal, Self::Flat, Self::LargeBiomes, Self::Amplified];
+
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::Normal => "minecraft:normal",
+            Self::Flat => "minecraft:flat",
+            Self::LargeBiomes => "minecraft:large_biomes",
+            Self::Amplified => "minecraft:amplified",
+        }
+    }
+
+    /// Accepts ids with and without the `minecraft` namespace
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.strip_prefix("minecraft:").unwrap_or(id);
+        Self::ALL
+            .into_iter()
+            .find(|preset| &preset.id()["minecraft:".len()..] == id)
+    }
+
+    /// The noise settings of the overworld, flat worlds are not generated from noise and use
+    /// the normal settings
+    pub fn overworld_settings(&self) -> &'static GenerationSettings {
+        match self {
+            Self::Normal | Self::Flat => &OVERWORLD_SETTINGS,
+            Self::LargeBiomes => &LARGE_BIOMES_SETTINGS,
+            Self::Amplified => &AMPLIFIED_SETTINGS,
+        }
+    }
+}
+
+impl From<LevelType> for WorldPreset {
+    fn from(level_type: LevelType) -> Self {
+        match level_type {
+            LevelType::Normal => Self::Normal,
+            LevelType::Flat => Self::Flat,
+            LevelType::LargeBiomes => Self::LargeBiomes,
+            LevelType::Amplified => Self::Amplified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr;
+
+    use crate::generation::settings::{AMPLIFIED_SETTINGS, LARGE_BIOMES_SETTINGS};
+
+    use super::WorldPreset;
+
+    #[test]
+    fn presets_by_id() {
+        for preset in WorldPreset::ALL {
+            assert_eq!(WorldPreset::from_id(preset.id()), Some(preset));
+        }
+        assert_eq!(
+            WorldPreset::from_id("large_biomes"),
+            Some(WorldPreset::LargeBiomes)
+        );
+        assert_eq!(
+            WorldPreset::from_id("minecraft:debug_all_block_states"),
+            None
+        );
+    }
+
+    #[test]
+    fn presets_pick_their_router() {
+        assert!(ptr::eq(
+            WorldPreset::Amplified.overworld_settings(),
+            &AMPLIFIED_SETTINGS
+        ));
+        assert!(ptr::eq(
+            WorldPreset::LargeBiomes.overworld_settings(),
+            &LARGE_BIOMES_SETTINGS
+        ));
+    }
+}
@@ -1,19 +1,24 @@
 use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
 
 use crate::{
+    biome::Biome,
     block::BlockState,
     generation::{
         chunk_noise::CHUNK_DIM,
-        generation_shapes::GenerationShape,
-        noise::{config::NoiseConfig, router::OVERWORLD_NOISE_ROUTER},
+        noise::config::NoiseConfig,
         positions::chunk_pos,
+        settings::{GenerationSettings, OVERWORLD_SETTINGS},
     },
 };
 
 use super::{
     aquifer_sampler::{FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl},
-    chunk_noise::{ChunkNoiseGenerator, LAVA_BLOCK, STONE_BLOCK, WATER_BLOCK},
+    blender::Blender,
+    carver::{self, Carver},
+    chunk_noise::{ChunkNoiseGenerator, LAVA_BLOCK},
+    feature::{self, PlacedFeature},
     positions::chunk_pos::{start_block_x, start_block_z},
+    surface::SurfaceSystem,
 };
 
 pub struct StandardChunkFluidLevelSampler {
@@ -50,23 +55,39 @@ pub struct ProtoChunk {
     sampler: ChunkNoiseGenerator,
     // These are local positions
     flat_block_map: Vec<BlockState>,
+    lava_level: i32,
+    default_block: BlockState,
     // may want to use chunk status
 }
 
 impl ProtoChunk {
     pub fn new(chunk_pos: Vector2<i32>, seed: u64) -> Self {
-        // TODO: Don't hardcode these
+        Self::with_settings(chunk_pos, seed, &OVERWORLD_SETTINGS)
+    }
 
-        let base_router = &OVERWORLD_NOISE_ROUTER;
+    pub fn with_settings(
+        chunk_pos: Vector2<i32>,
+        seed: u64,
+        settings: &GenerationSettings,
+    ) -> Self {
+        Self::with_blender(chunk_pos, seed, settings, Blender::NO_BLEND)
+    }
 
-        let generation_shape = GenerationShape::SURFACE;
-        let config = NoiseConfig::new(seed, base_router);
+    /// A chunk whose terrain is blended into the old chunks around it
+    pub fn with_blender(
+        chunk_pos: Vector2<i32>,
+        seed: u64,
+        settings: &GenerationSettings,
+        blender: Blender,
+    ) -> Self {
+        let generation_shape = settings.shape;
+        let config = NoiseConfig::new(seed, settings.router());
 
         let horizontal_cell_count = CHUNK_DIM / generation_shape.horizontal_cell_block_count();
 
-        // TODO: Customize these
+        // Below y -54 vanilla always fills open blocks with lava
         let sampler = FluidLevelSampler::Chunk(StandardChunkFluidLevelSampler::new(
-            FluidLevel::new(63, WATER_BLOCK),
+            FluidLevel::new(settings.sea_level, settings.default_fluid),
             FluidLevel::new(-54, LAVA_BLOCK),
         ));
 
@@ -78,17 +99,37 @@ impl ProtoChunk {
             generation_shape,
             &config,
             sampler,
-            true,
-            true,
+            settings.aquifers_enabled,
+            settings.ore_veins_enabled,
+            blender,
         );
 
         Self {
             chunk_pos,
             sampler,
             flat_block_map: vec![BlockState::AIR; CHUNK_DIM as usize * CHUNK_DIM as usize * height],
+            lava_level: settings.lava_level,
+            default_block: settings.default_block,
         }
     }
 
+    pub fn chunk_pos(&self) -> Vector2<i32> {
+        self.chunk_pos
+    }
+
+    pub fn min_y(&self) -> i32 {
+        self.sampler.min_y() as i32
+    }
+
+    pub fn height(&self) -> i32 {
+        self.sampler.height() as i32
+    }
+
+    /// Carved out blocks at or below this level are filled with lava
+    pub fn lava_level(&self) -> i32 {
+        self.lava_level
+    }
+
     #[inline]
     fn local_pos_to_index(&self, local_pos: &Vector3<i32>) -> usize {
         #[cfg(debug_assertions)]
@@ -116,6 +157,58 @@ impl ProtoChunk {
         }
     }
 
+    #[inline]
+    pub fn set_block_state(&mut self, local_pos: &Vector3<i32>, block_state: BlockState) {
+        let local_pos = Vector3::new(
+            local_pos.x & 15,
+            local_pos.y - self.sampler.min_y() as i32,
+            local_pos.z & 15,
+        );
+        if local_pos.y >= 0 && local_pos.y < self.sampler.height() as i32 {
+            let index = self.local_pos_to_index(&local_pos);
+            self.flat_block_map[index] = block_state;
+        }
+    }
+
+    /// What the aquifer fills an opened up block with, `None` where it would keep a barrier
+    pub fn sample_aquifer(&mut self, local_pos: &Vector3<i32>) -> Option<BlockState> {
+        self.sampler.sample_aquifer(
+            self.start_block_x() + (local_pos.x & 15),
+            local_pos.y,
+            self.start_block_z() + (local_pos.z & 15),
+        )
+    }
+
+    #[cfg(test)]
+    pub(crate) fn fill(&mut self, block_state: BlockState) {
+        self.flat_block_map.fill(block_state);
+    }
+
+    pub fn estimate_surface_height(&mut self, block_x: i32, block_z: i32) -> i32 {
+        self.sampler.estimate_surface_height(block_x, block_z)
+    }
+
+    /// Replaces the top of the terrain with biome dependent blocks, this must happen after
+    /// [`Self::populate_noise`]
+    pub fn build_surface(&mut self, surface: &SurfaceSystem, biome_at: &dyn Fn(i32, i32) -> Biome) {
+        surface.build_surface(self, biome_at);
+    }
+
+    /// Runs `carvers` over the populated noise, this must happen after [`Self::populate_noise`]
+    pub fn carve(&mut self, seed: u64, carvers: &[Carver]) {
+        carver::carve(self, seed, carvers);
+    }
+
+    /// Decorates the chunk with `features`, this must happen after carving
+    pub fn generate_features(
+        &mut self,
+        seed: u64,
+        features: &[PlacedFeature],
+        biome_at: &dyn Fn(i32, i32) -> Biome,
+    ) {
+        feature::generate_features(self, seed, features, biome_at);
+    }
+
     pub fn populate_noise(&mut self) {
         let horizontal_cell_block_count = self.sampler.horizontal_cell_block_count();
         let vertical_cell_block_count = self.sampler.vertical_cell_block_count();
@@ -161,9 +254,10 @@ impl ProtoChunk {
                                         local_z as f64 / horizontal_cell_block_count as f64;
                                     self.sampler.interpolate_z(block_z, delta_z);
 
-                                    // TODO: Change default block
-                                    let block_state =
-                                        self.sampler.sample_block_state().unwrap_or(STONE_BLOCK);
+                                    let block_state = self
+                                        .sampler
+                                        .sample_block_state()
+                                        .unwrap_or(self.default_block);
                                     //log::debug!("Sampled block state in {:?}", inst.elapsed());
 
                                     let local_pos = Vector3 {
@@ -216,9 +310,9 @@ impl ProtoChunk {
 mod test {
     use std::{fs, path::Path};
 
-    use pumpkin_core::math::vector2::Vector2;
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
 
-    use crate::read_data_from_file;
+    use crate::{generation::chunk_noise::LAVA_BLOCK, read_data_from_file};
 
     use super::ProtoChunk;
 
@@ -254,4 +348,16 @@ mod test {
                 .collect::<Vec<u16>>()
         );
     }
+
+    #[test]
+    fn aquifer_fills_lava_below_lava_level() {
+        let mut chunk = ProtoChunk::new(Vector2::new(7, 4), 0);
+        chunk.populate_noise();
+        for y in chunk.min_y()..chunk.lava_level() {
+            assert_eq!(
+                chunk.sample_aquifer(&Vector3::new(3, y, 12)),
+                Some(LAVA_BLOCK)
+            );
+        }
+    }
 }
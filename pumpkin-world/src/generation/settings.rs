@@ -0,0 +1,87 @@
+use std::sync::LazyLock;
+
+use pumpkin_core::random::RandomDeriver;
+use pumpkin_macros::block_state;
+
+use crate::block::BlockState;
+
+use super::{
+    chunk_noise::{LAVA_BLOCK, STONE_BLOCK, WATER_BLOCK},
+    generation_shapes::GenerationShape,
+    noise::router::{
+        BaseRouter, END_NOISE_ROUTER, NETHER_NOISE_ROUTER, OVERWORLD_NOISE_ROUTER,
+        OVERWORLD_NOISE_ROUTER_AMPLIFIED, OVERWORLD_NOISE_ROUTER_LARGE,
+    },
+    surface::{self, rules::MaterialRule},
+};
+
+/// How the terrain of a dimension is shaped and filled, like vanilla's noise settings
+pub struct GenerationSettings {
+    router: &'static LazyLock<BaseRouter>,
+    pub shape: GenerationShape,
+    /// The block the noise fills solid terrain with, surface rules only replace this block
+    pub default_block: BlockState,
+    /// The fluid filling everything open below the sea level
+    pub default_fluid: BlockState,
+    /// Open blocks below this y are filled with the default fluid
+    pub sea_level: i32,
+    /// Carved out blocks at or below this level are filled with lava
+    pub lava_level: i32,
+    pub aquifers_enabled: bool,
+    pub ore_veins_enabled: bool,
+    pub surface_rule: fn(&RandomDeriver) -> MaterialRule,
+}
+
+impl GenerationSettings {
+    pub fn router(&self) -> &BaseRouter {
+        self.router
+    }
+}
+
+/// The overworld presets only differ in their noise router
+const fn overworld(router: &'static LazyLock<BaseRouter>) -> GenerationSettings {
+    GenerationSettings {
+        router,
+        shape: GenerationShape::SURFACE,
+        default_block: STONE_BLOCK,
+        default_fluid: WATER_BLOCK,
+        sea_level: 63,
+        lava_level: -54,
+        aquifers_enabled: true,
+        ore_veins_enabled: true,
+        surface_rule: surface::create_overworld_surface_rule,
+    }
+}
+
+pub static OVERWORLD_SETTINGS: GenerationSettings = overworld(&OVERWORLD_NOISE_ROUTER);
+
+/// Continents and erosion are stretched, so every biome covers a larger area
+pub static LARGE_BIOMES_SETTINGS: GenerationSettings = overworld(&OVERWORLD_NOISE_ROUTER_LARGE);
+
+/// Taller and steeper terrain
+pub static AMPLIFIED_SETTINGS: GenerationSettings = overworld(&OVERWORLD_NOISE_ROUTER_AMPLIFIED);
+
+pub static NETHER_SETTINGS: GenerationSettings = GenerationSettings {
+    router: &NETHER_NOISE_ROUTER,
+    shape: GenerationShape::NETHER,
+    default_block: block_state!("netherrack"),
+    default_fluid: LAVA_BLOCK,
+    sea_level: 32,
+    lava_level: 10,
+    aquifers_enabled: false,
+    ore_veins_enabled: false,
+    surface_rule: surface::create_nether_surface_rule,
+};
+
+pub static END_SETTINGS: GenerationSettings = GenerationSettings {
+    router: &END_NOISE_ROUTER,
+    shape: GenerationShape::END,
+    default_block: block_state!("end_stone"),
+    default_fluid: BlockState::AIR,
+    sea_level: 0,
+    // Nothing carves the end
+    lava_level: i32::MIN,
+    aquifers_enabled: false,
+    ore_veins_enabled: false,
+    surface_rule: surface::create_end_surface_rule,
+};
@@ -0,0 +1,54 @@
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::generation::positions::chunk_pos;
+
+use super::{BlockBox, StructurePiece};
+
+/// Vanilla only starts cities on islands reaching at least this high, the terrain is unknown
+/// when placing the pieces so every city starts there
+const CITY_Y: i32 = 60;
+/// The floors of the first building from the bottom, with their size
+const BUILDING: [(&str, Vector3<i32>); 4] = [
+    ("base_floor", Vector3::new(10, 4, 10)),
+    ("second_floor_1", Vector3::new(12, 4, 12)),
+    ("third_floor_1", Vector3::new(14, 4, 14)),
+    ("third_roof", Vector3::new(16, 2, 16)),
+];
+const TOWER_BASE: (&str, Vector3<i32>) = ("tower_base", Vector3::new(7, 4, 7));
+const TOWER_PIECE: (&str, Vector3<i32>) = ("tower_piece", Vector3::new(7, 4, 7));
+const TOWER_TOP: (&str, Vector3<i32>) = ("tower_top", Vector3::new(9, 5, 9));
+/// A tower has up to this many pieces between its base and its top
+const MAX_TOWER_PIECES: i32 = 3;
+
+/// The building of an end city with a tower on its roof, every piece is centered on the
+/// start chunk and stacked on the one below it
+pub(super) fn generate(random: &mut LegacyRand, chunk: &Vector2<i32>) -> Vec<StructurePiece> {
+    let center_x = chunk_pos::start_block_x(chunk) + 8;
+    let center_z = chunk_pos::start_block_z(chunk) + 8;
+
+    let tower_pieces = random.next_bounded_i32(MAX_TOWER_PIECES) + 1;
+    let floors = BUILDING
+        .into_iter()
+        .chain([TOWER_BASE])
+        .chain((0..tower_pieces).map(|_| TOWER_PIECE))
+        .chain([TOWER_TOP]);
+
+    let mut pieces = Vec::new();
+    let mut y = CITY_Y;
+    for (depth, (name, size)) in floors.enumerate() {
+        let min = Vector3::new(center_x - size.x / 2, y, center_z - size.z / 2);
+        pieces.push(StructurePiece {
+            id: format!("minecraft:end_city/{name}"),
+            bounding_box: BlockBox::new(
+                min,
+                min.add(&Vector3::new(size.x - 1, size.y - 1, size.z - 1)),
+            ),
+            depth: depth as i32,
+        });
+        y += size.y;
+    }
+    pieces
+}
@@ -0,0 +1,440 @@
+use std::collections::VecDeque;
+
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{generation::positions::chunk_pos, WORLD_HEIGHT, WORLD_LOWEST_Y};
+
+use super::{shuffle, BlockBox, Facing, StructurePiece, SEA_LEVEL};
+
+const EMPTY: &str = "minecraft:empty";
+const STREET: &str = "minecraft:street";
+const BUILDING_ENTRANCE: &str = "minecraft:building_entrance";
+
+const VILLAGE_MAX_DEPTH: i32 = 6;
+/// How far pieces may be from the center of the first piece
+const VILLAGE_MAX_DISTANCE: i32 = 80;
+
+/// A connection point on the face of a template. A piece can be attached to it if the piece
+/// has a jigsaw facing the other way whose `name` is the `target` of this one
+struct Jigsaw {
+    pos: Vector3<i32>,
+    facing: Facing,
+    name: &'static str,
+    target: &'static str,
+    /// Where the pieces attached to this jigsaw come from
+    pool: &'static str,
+}
+
+/// The size and jigsaws of a vanilla structure template, the blocks are left out
+struct Template {
+    id: &'static str,
+    size: Vector3<i32>,
+    jigsaws: &'static [Jigsaw],
+}
+
+struct TemplatePool {
+    id: &'static str,
+    elements: &'static [(&'static Template, u32)],
+    /// Used when nothing from this pool fits or the maximum depth is reached
+    fallback: &'static str,
+}
+
+const fn jigsaw(
+    x: i32,
+    z: i32,
+    facing: Facing,
+    name: &'static str,
+    target: &'static str,
+    pool: &'static str,
+) -> Jigsaw {
+    Jigsaw {
+        pos: Vector3::new(x, 0, z),
+        facing,
+        name,
+        target,
+        pool,
+    }
+}
+
+const fn street(x: i32, z: i32, facing: Facing) -> Jigsaw {
+    jigsaw(
+        x,
+        z,
+        facing,
+        STREET,
+        STREET,
+        "minecraft:village/plains/streets",
+    )
+}
+
+const fn house_slot(x: i32, z: i32, facing: Facing) -> Jigsaw {
+    jigsaw(
+        x,
+        z,
+        facing,
+        EMPTY,
+        BUILDING_ENTRANCE,
+        "minecraft:village/plains/houses",
+    )
+}
+
+/// Houses are entered from the street on their north side
+const fn entrance(x: i32) -> Jigsaw {
+    jigsaw(x, 0, Facing::North, BUILDING_ENTRANCE, STREET, EMPTY)
+}
+
+const FOUNTAIN: Template = Template {
+    id: "minecraft:village/plains/town_centers/plains_fountain_01",
+    size: Vector3::new(9, 6, 9),
+    jigsaws: &[
+        street(4, 0, Facing::North),
+        street(8, 4, Facing::East),
+        street(4, 8, Facing::South),
+        street(0, 4, Facing::West),
+    ],
+};
+const MEETING_POINT: Template = Template {
+    id: "minecraft:village/plains/town_centers/plains_meeting_point_1",
+    size: Vector3::new(11, 7, 11),
+    jigsaws: &[
+        street(5, 0, Facing::North),
+        street(10, 5, Facing::East),
+        street(5, 10, Facing::South),
+        street(0, 5, Facing::West),
+    ],
+};
+
+const LONG_STREET: Template = Template {
+    id: "minecraft:village/plains/streets/straight_01",
+    size: Vector3::new(3, 3, 16),
+    jigsaws: &[
+        street(1, 0, Facing::North),
+        street(1, 15, Facing::South),
+        house_slot(0, 4, Facing::West),
+        house_slot(2, 4, Facing::East),
+        house_slot(0, 11, Facing::West),
+        house_slot(2, 11, Facing::East),
+    ],
+};
+const SHORT_STREET: Template = Template {
+    id: "minecraft:village/plains/streets/straight_02",
+    size: Vector3::new(3, 3, 9),
+    jigsaws: &[
+        street(1, 0, Facing::North),
+        street(1, 8, Facing::South),
+        house_slot(0, 4, Facing::West),
+        house_slot(2, 4, Facing::East),
+    ],
+};
+const CORNER: Template = Template {
+    id: "minecraft:village/plains/streets/corner_01",
+    size: Vector3::new(3, 3, 3),
+    jigsaws: &[street(1, 0, Facing::North), street(2, 1, Facing::East)],
+};
+const CROSSROAD: Template = Template {
+    id: "minecraft:village/plains/streets/crossroad_01",
+    size: Vector3::new(3, 3, 3),
+    jigsaws: &[
+        street(1, 0, Facing::North),
+        street(2, 1, Facing::East),
+        street(1, 2, Facing::South),
+        street(0, 1, Facing::West),
+    ],
+};
+const TERMINATOR: Template = Template {
+    id: "minecraft:village/common/terminators/terminator_01",
+    size: Vector3::new(3, 1, 1),
+    jigsaws: &[jigsaw(1, 0, Facing::North, STREET, EMPTY, EMPTY)],
+};
+
+const SMALL_HOUSE: Template = Template {
+    id: "minecraft:village/plains/houses/plains_small_house_1",
+    size: Vector3::new(5, 6, 5),
+    jigsaws: &[entrance(2)],
+};
+const MEDIUM_HOUSE: Template = Template {
+    id: "minecraft:village/plains/houses/plains_medium_house_1",
+    size: Vector3::new(7, 7, 9),
+    jigsaws: &[entrance(3)],
+};
+const BUTCHER_SHOP: Template = Template {
+    id: "minecraft:village/plains/houses/plains_butcher_shop_1",
+    size: Vector3::new(9, 7, 11),
+    jigsaws: &[entrance(4)],
+};
+const LIBRARY: Template = Template {
+    id: "minecraft:village/plains/houses/plains_library_1",
+    size: Vector3::new(9, 10, 9),
+    jigsaws: &[entrance(4)],
+};
+const FARM: Template = Template {
+    id: "minecraft:village/plains/houses/plains_small_farm_1",
+    size: Vector3::new(7, 2, 9),
+    jigsaws: &[entrance(3)],
+};
+
+/// A reduced version of the plains village pools of vanilla
+static VILLAGE_POOLS: [TemplatePool; 4] = [
+    TemplatePool {
+        id: "minecraft:village/plains/town_centers",
+        elements: &[(&FOUNTAIN, 50), (&MEETING_POINT, 50)],
+        fallback: EMPTY,
+    },
+    TemplatePool {
+        id: "minecraft:village/plains/streets",
+        elements: &[
+            (&LONG_STREET, 4),
+            (&SHORT_STREET, 4),
+            (&CORNER, 2),
+            (&CROSSROAD, 2),
+        ],
+        fallback: "minecraft:village/plains/terminators",
+    },
+    TemplatePool {
+        id: "minecraft:village/plains/terminators",
+        elements: &[(&TERMINATOR, 1)],
+        fallback: EMPTY,
+    },
+    TemplatePool {
+        id: "minecraft:village/plains/houses",
+        elements: &[
+            (&SMALL_HOUSE, 3),
+            (&MEDIUM_HOUSE, 2),
+            (&BUTCHER_SHOP, 1),
+            (&LIBRARY, 1),
+            (&FARM, 3),
+        ],
+        fallback: EMPTY,
+    },
+];
+
+fn get_pool(id: &str) -> Option<&'static TemplatePool> {
+    VILLAGE_POOLS.iter().find(|pool| pool.id == id)
+}
+
+impl TemplatePool {
+    /// Every element once, elements with a higher weight are more likely to come first
+    fn shuffled(&self, random: &mut LegacyRand) -> Vec<&'static Template> {
+        let mut elements: Vec<_> = self
+            .elements
+            .iter()
+            .flat_map(|(template, weight)| std::iter::repeat_n(*template, *weight as usize))
+            .collect();
+        shuffle(&mut elements, random);
+        let mut unique: Vec<&'static Template> = Vec::new();
+        for template in elements {
+            if !unique.iter().any(|other| other.id == template.id) {
+                unique.push(template);
+            }
+        }
+        unique
+    }
+
+    fn random_element(&self, random: &mut LegacyRand) -> Option<&'static Template> {
+        let total: u32 = self.elements.iter().map(|(_, weight)| weight).sum();
+        let mut index = random.next_bounded_i32(total as i32) as u32;
+        self.elements.iter().find_map(|(template, weight)| {
+            if index < *weight {
+                Some(*template)
+            } else {
+                index -= weight;
+                None
+            }
+        })
+    }
+}
+
+impl Template {
+    /// `rotation` is the amount of clockwise quarter turns
+    fn rotated_size(&self, rotation: u8) -> Vector3<i32> {
+        if rotation.is_multiple_of(2) {
+            self.size
+        } else {
+            Vector3::new(self.size.z, self.size.y, self.size.x)
+        }
+    }
+
+    /// The position inside the rotated template
+    fn rotate(&self, pos: Vector3<i32>, rotation: u8) -> Vector3<i32> {
+        let size = self.size;
+        match rotation % 4 {
+            0 => pos,
+            1 => Vector3::new(size.z - 1 - pos.z, pos.y, pos.x),
+            2 => Vector3::new(size.x - 1 - pos.x, pos.y, size.z - 1 - pos.z),
+            _ => Vector3::new(pos.z, pos.y, size.x - 1 - pos.x),
+        }
+    }
+
+    fn bounding_box(&self, min: Vector3<i32>, rotation: u8) -> BlockBox {
+        let size = self.rotated_size(rotation);
+        BlockBox::new(
+            min,
+            Vector3::new(min.x + size.x - 1, min.y + size.y - 1, min.z + size.z - 1),
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PlacedTemplate {
+    template: &'static Template,
+    rotation: u8,
+    bounding_box: BlockBox,
+    depth: i32,
+}
+
+/// Builds a structure from template pools by attaching templates to the free jigsaws of the
+/// ones placed before, breadth first
+struct JigsawAssembler<'a> {
+    random: &'a mut LegacyRand,
+    pieces: Vec<PlacedTemplate>,
+    max_depth: i32,
+    /// Every piece has to be inside
+    bounds: BlockBox,
+}
+
+impl JigsawAssembler<'_> {
+    fn assemble(&mut self) {
+        let mut queue = VecDeque::from([0]);
+        while let Some(index) = queue.pop_front() {
+            let PlacedTemplate {
+                template,
+                rotation,
+                bounding_box,
+                depth,
+            } = self.pieces[index];
+
+            let mut jigsaws: Vec<_> = template.jigsaws.iter().collect();
+            shuffle(&mut jigsaws, self.random);
+            for jigsaw in jigsaws {
+                let Some(pool) = get_pool(jigsaw.pool) else {
+                    continue;
+                };
+                let pos = template.rotate(jigsaw.pos, rotation).add(&bounding_box.min);
+                let facing = jigsaw.facing.rotate_clockwise(rotation);
+
+                let mut candidates = Vec::new();
+                if depth != self.max_depth {
+                    candidates.extend(pool.shuffled(self.random));
+                }
+                if let Some(fallback) = get_pool(pool.fallback) {
+                    candidates.extend(fallback.shuffled(self.random));
+                }
+
+                if let Some(piece) = self.attach(
+                    &candidates,
+                    jigsaw.target,
+                    pos.add(&facing.offset()),
+                    facing,
+                ) {
+                    self.pieces.push(PlacedTemplate {
+                        depth: depth + 1,
+                        ..piece
+                    });
+                    if depth < self.max_depth {
+                        queue.push_back(self.pieces.len() - 1);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The first candidate which can be connected to a jigsaw at `target_pos` without
+    /// overlapping other pieces
+    fn attach(
+        &mut self,
+        candidates: &[&'static Template],
+        target: &str,
+        target_pos: Vector3<i32>,
+        facing: Facing,
+    ) -> Option<PlacedTemplate> {
+        for template in candidates {
+            let mut rotations = [0, 1, 2, 3];
+            shuffle(&mut rotations, self.random);
+            for rotation in rotations {
+                for jigsaw in template.jigsaws {
+                    if jigsaw.name != target
+                        || jigsaw.facing.rotate_clockwise(rotation) != facing.opposite()
+                    {
+                        continue;
+                    }
+                    let min = target_pos.sub(&template.rotate(jigsaw.pos, rotation));
+                    let bounding_box = template.bounding_box(min, rotation);
+                    if self.bounds.contains(&bounding_box)
+                        && !self
+                            .pieces
+                            .iter()
+                            .any(|piece| piece.bounding_box.intersects(&bounding_box))
+                    {
+                        return Some(PlacedTemplate {
+                            template,
+                            rotation,
+                            bounding_box,
+                            depth: 0,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Assembles a plains village from its town center. There is no heightmap while structure starts
+/// are created, so the village is kept flat right above the sea level
+pub(super) fn assemble_village(
+    random: &mut LegacyRand,
+    chunk: &Vector2<i32>,
+) -> Vec<StructurePiece> {
+    let Some(start) = get_pool("minecraft:village/plains/town_centers")
+        .and_then(|pool| pool.random_element(random))
+    else {
+        return Vec::new();
+    };
+    let rotation = random.next_bounded_i32(4) as u8;
+    let min = Vector3::new(
+        chunk_pos::start_block_x(chunk),
+        SEA_LEVEL + 1,
+        chunk_pos::start_block_z(chunk),
+    );
+    let bounding_box = start.bounding_box(min, rotation);
+    let center_x = (bounding_box.min.x + bounding_box.max.x) / 2;
+    let center_z = (bounding_box.min.z + bounding_box.max.z) / 2;
+    let bottom = WORLD_LOWEST_Y as i32;
+
+    let mut assembler = JigsawAssembler {
+        random,
+        pieces: vec![PlacedTemplate {
+            template: start,
+            rotation,
+            bounding_box,
+            depth: 0,
+        }],
+        max_depth: VILLAGE_MAX_DEPTH,
+        bounds: BlockBox::new(
+            Vector3::new(
+                center_x - VILLAGE_MAX_DISTANCE,
+                bottom,
+                center_z - VILLAGE_MAX_DISTANCE,
+            ),
+            Vector3::new(
+                center_x + VILLAGE_MAX_DISTANCE + 1,
+                bottom + WORLD_HEIGHT as i32 - 1,
+                center_z + VILLAGE_MAX_DISTANCE + 1,
+            ),
+        ),
+    };
+    assembler.assemble();
+
+    assembler
+        .pieces
+        .into_iter()
+        .map(|piece| StructurePiece {
+            id: piece.template.id.to_string(),
+            bounding_box: piece.bounding_box,
+            depth: piece.depth,
+        })
+        .collect()
+}
@@ -0,0 +1,645 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use super::{carver::carver_random, positions::chunk_pos, Seed};
+
+mod end_city;
+mod jigsaw;
+pub mod placement;
+mod stronghold;
+
+use placement::{SpreadType, StructurePlacement};
+
+/// How many chunks away a structure may start and still reach into a chunk
+const REFERENCE_RANGE: i32 = 8;
+/// How many regions away from the origin `locate` searches for random spread structures
+const LOCATE_REGION_RADIUS: i32 = 100;
+const SEA_LEVEL: i32 = 63;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Structure {
+    VillagePlains,
+    Stronghold,
+    EndCity,
+}
+
+impl Structure {
+    pub const ALL: [Structure; 3] = [Self::VillagePlains, Self::Stronghold, Self::EndCity];
+
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::VillagePlains => "minecraft:village_plains",
+            Self::Stronghold => "minecraft:stronghold",
+            Self::EndCity => "minecraft:end_city",
+        }
+    }
+
+    /// Accepts ids with and without the `minecraft` namespace
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.strip_prefix("minecraft:").unwrap_or(id);
+        Self::ALL
+            .into_iter()
+            .find(|structure| &structure.id()["minecraft:".len()..] == id)
+    }
+}
+
+/// Structures which share a placement
+pub struct StructureSet {
+    pub structure: Structure,
+    pub placement: StructurePlacement,
+}
+
+pub const STRUCTURE_SETS: [StructureSet; 2] = [
+    StructureSet {
+        structure: Structure::VillagePlains,
+        placement: StructurePlacement::RandomSpread {
+            spacing: 34,
+            separation: 8,
+            spread_type: SpreadType::Linear,
+            salt: 10387312,
+        },
+    },
+    StructureSet {
+        structure: Structure::Stronghold,
+        placement: StructurePlacement::ConcentricRings {
+            distance: 32,
+            spread: 3,
+            count: 128,
+        },
+    },
+];
+
+pub const END_STRUCTURE_SETS: [StructureSet; 1] = [StructureSet {
+    structure: Structure::EndCity,
+    placement: StructurePlacement::RandomSpread {
+        spacing: 20,
+        separation: 11,
+        spread_type: SpreadType::Triangular,
+        salt: 10387313,
+    },
+}];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Facing {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Facing {
+    const ALL: [Facing; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    fn random(random: &mut LegacyRand) -> Self {
+        Self::ALL[random.next_bounded_i32(4) as usize]
+    }
+
+    fn rotate_clockwise(self, times: u8) -> Self {
+        Self::ALL[(self as usize + times as usize) % 4]
+    }
+
+    fn opposite(self) -> Self {
+        self.rotate_clockwise(2)
+    }
+
+    fn offset(self) -> Vector3<i32> {
+        match self {
+            Self::North => Vector3::new(0, 0, -1),
+            Self::East => Vector3::new(1, 0, 0),
+            Self::South => Vector3::new(0, 0, 1),
+            Self::West => Vector3::new(-1, 0, 0),
+        }
+    }
+}
+
+/// An inclusive box of blocks
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BlockBox {
+    pub min: Vector3<i32>,
+    pub max: Vector3<i32>,
+}
+
+impl BlockBox {
+    pub const fn new(min: Vector3<i32>, max: Vector3<i32>) -> Self {
+        Self { min, max }
+    }
+
+    /// A box of `size` which extends from `x, y, z` towards `facing`, `offset` is applied
+    /// before rotating so the same offset keeps a piece centered on its entrance
+    fn rotated(
+        pos: Vector3<i32>,
+        offset: Vector3<i32>,
+        size: Vector3<i32>,
+        facing: Facing,
+    ) -> Self {
+        let min_y = pos.y + offset.y;
+        let max_y = min_y + size.y - 1;
+        match facing {
+            Facing::North => Self::new(
+                Vector3::new(pos.x + offset.x, min_y, pos.z - size.z + 1 + offset.z),
+                Vector3::new(pos.x + size.x - 1 + offset.x, max_y, pos.z + offset.z),
+            ),
+            Facing::South => Self::new(
+                Vector3::new(pos.x + offset.x, min_y, pos.z + offset.z),
+                Vector3::new(
+                    pos.x + size.x - 1 + offset.x,
+                    max_y,
+                    pos.z + size.z - 1 + offset.z,
+                ),
+            ),
+            Facing::West => Self::new(
+                Vector3::new(pos.x - size.z + 1 + offset.z, min_y, pos.z + offset.x),
+                Vector3::new(pos.x + offset.z, max_y, pos.z + size.x - 1 + offset.x),
+            ),
+            Facing::East => Self::new(
+                Vector3::new(pos.x + offset.z, min_y, pos.z + offset.x),
+                Vector3::new(
+                    pos.x + size.z - 1 + offset.z,
+                    max_y,
+                    pos.z + size.x - 1 + offset.x,
+                ),
+            ),
+        }
+    }
+
+    pub fn intersects(&self, other: &BlockBox) -> bool {
+        self.max.x >= other.min.x
+            && self.min.x <= other.max.x
+            && self.max.y >= other.min.y
+            && self.min.y <= other.max.y
+            && self.max.z >= other.min.z
+            && self.min.z <= other.max.z
+    }
+
+    pub fn contains(&self, other: &BlockBox) -> bool {
+        self.min.x <= other.min.x
+            && self.min.y <= other.min.y
+            && self.min.z <= other.min.z
+            && self.max.x >= other.max.x
+            && self.max.y >= other.max.y
+            && self.max.z >= other.max.z
+    }
+
+    pub fn intersects_chunk(&self, chunk: &Vector2<i32>) -> bool {
+        self.max.x >= chunk_pos::start_block_x(chunk)
+            && self.min.x <= chunk_pos::end_block_x(chunk)
+            && self.max.z >= chunk_pos::start_block_z(chunk)
+            && self.min.z <= chunk_pos::end_block_z(chunk)
+    }
+
+    pub fn encompass(&self, other: &BlockBox) -> Self {
+        Self::new(
+            Vector3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            Vector3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        )
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    fn move_y(&mut self, offset: i32) {
+        self.min.y += offset;
+        self.max.y += offset;
+    }
+
+    fn to_nbt(self) -> NbtTag {
+        NbtTag::IntArray(vec![
+            self.min.x, self.min.y, self.min.z, self.max.x, self.max.y, self.max.z,
+        ])
+    }
+
+    fn from_nbt(values: &[i32]) -> Option<Self> {
+        let [min_x, min_y, min_z, max_x, max_y, max_z] = values[..] else {
+            return None;
+        };
+        Some(Self::new(
+            Vector3::new(min_x, min_y, min_z),
+            Vector3::new(max_x, max_y, max_z),
+        ))
+    }
+}
+
+/// A part of a structure. Pieces only know where they are, the blocks of the vanilla templates
+/// are not shipped with the server so nothing is placed for them yet
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StructurePiece {
+    /// The template of jigsaw pieces, the piece type otherwise
+    pub id: String,
+    pub bounding_box: BlockBox,
+    /// How many pieces away from the first piece this one is
+    pub depth: i32,
+}
+
+impl StructurePiece {
+    fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put("id".to_string(), NbtTag::String(self.id.clone()));
+        nbt.put("BB".to_string(), self.bounding_box.to_nbt());
+        nbt.put("GD".to_string(), NbtTag::Int(self.depth));
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        Some(Self {
+            id: nbt.get_string("id")?.clone(),
+            bounding_box: BlockBox::from_nbt(nbt.get_int_array("BB")?)?,
+            depth: nbt.get_int("GD").unwrap_or(0),
+        })
+    }
+}
+
+/// A structure generated from the chunk it starts in
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StructureStart {
+    pub structure: Structure,
+    pub chunk: Vector2<i32>,
+    pub pieces: Vec<StructurePiece>,
+}
+
+impl StructureStart {
+    /// The box around every piece
+    pub fn bounding_box(&self) -> Option<BlockBox> {
+        self.pieces
+            .iter()
+            .map(|piece| piece.bounding_box)
+            .reduce(|bounding_box, other| bounding_box.encompass(&other))
+    }
+
+    fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "id".to_string(),
+            NbtTag::String(self.structure.id().to_string()),
+        );
+        nbt.put("ChunkX".to_string(), NbtTag::Int(self.chunk.x));
+        nbt.put("ChunkZ".to_string(), NbtTag::Int(self.chunk.z));
+        nbt.put("references".to_string(), NbtTag::Int(0));
+        nbt.put(
+            "Children".to_string(),
+            NbtTag::List(
+                self.pieces
+                    .iter()
+                    .map(|piece| NbtTag::Compound(piece.to_nbt()))
+                    .collect(),
+            ),
+        );
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        Some(Self {
+            structure: Structure::from_id(nbt.get_string("id")?)?,
+            chunk: Vector2::new(nbt.get_int("ChunkX")?, nbt.get_int("ChunkZ")?),
+            pieces: nbt
+                .get_list("Children")?
+                .iter()
+                .filter_map(NbtTag::extract_compound)
+                .filter_map(StructurePiece::from_nbt)
+                .collect(),
+        })
+    }
+}
+
+/// The structures of a chunk as saved by vanilla
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ChunkStructures {
+    /// The structures starting in this chunk
+    pub starts: HashMap<Structure, StructureStart>,
+    /// The start chunks of the structures reaching into this chunk
+    pub references: HashMap<Structure, Vec<Vector2<i32>>>,
+}
+
+impl ChunkStructures {
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut starts = NbtCompound::new();
+        for (structure, start) in &self.starts {
+            starts.put(structure.id().to_string(), NbtTag::Compound(start.to_nbt()));
+        }
+        let mut references = NbtCompound::new();
+        for (structure, chunks) in &self.references {
+            references.put(
+                structure.id().to_string(),
+                NbtTag::LongArray(
+                    chunks
+                        .iter()
+                        .map(|chunk| chunk_pos::packed(chunk) as i64)
+                        .collect(),
+                ),
+            );
+        }
+
+        let mut nbt = NbtCompound::new();
+        nbt.put("starts".to_string(), NbtTag::Compound(starts));
+        nbt.put("References".to_string(), NbtTag::Compound(references));
+        nbt
+    }
+
+    /// Structures we don't generate are dropped
+    pub fn read_nbt(nbt: &NbtCompound) -> Self {
+        let starts = nbt
+            .get_compound("starts")
+            .into_iter()
+            .flat_map(|starts| &starts.child_tags)
+            .filter_map(|(_, start)| StructureStart::from_nbt(start.extract_compound()?))
+            .map(|start| (start.structure, start))
+            .collect();
+        let references = nbt
+            .get_compound("References")
+            .into_iter()
+            .flat_map(|references| &references.child_tags)
+            .filter_map(|(id, chunks)| {
+                let chunks = chunks
+                    .extract_long_array()?
+                    .iter()
+                    .map(|packed| {
+                        Vector2::new(
+                            chunk_pos::unpack_x(*packed as u64),
+                            chunk_pos::unpack_z(*packed as u64),
+                        )
+                    })
+                    .collect();
+                Some((Structure::from_id(id)?, chunks))
+            })
+            .collect();
+        Self { starts, references }
+    }
+}
+
+/// Decides where structures start and which pieces they have. Every possible start is generated,
+/// vanilla additionally drops starts in biomes the structure can't generate in
+pub struct StructureGenerator {
+    seed: u64,
+    sets: &'static [StructureSet],
+    stronghold_chunks: HashSet<Vector2<i32>>,
+}
+
+impl StructureGenerator {
+    /// Generates the structures of the overworld
+    pub fn new(seed: Seed) -> Self {
+        Self::with_sets(seed, &STRUCTURE_SETS)
+    }
+
+    pub fn with_sets(seed: Seed, sets: &'static [StructureSet]) -> Self {
+        Self {
+            seed: seed.0,
+            sets,
+            stronghold_chunks: sets
+                .iter()
+                .flat_map(|set| set.placement.ring_positions(seed.0))
+                .collect(),
+        }
+    }
+
+    fn is_start_chunk(&self, set: &StructureSet, chunk: &Vector2<i32>) -> bool {
+        match set.placement {
+            StructurePlacement::RandomSpread { .. } => {
+                set.placement.start_chunk(self.seed, chunk) == Some(*chunk)
+            }
+            StructurePlacement::ConcentricRings { .. } => self.stronghold_chunks.contains(chunk),
+        }
+    }
+
+    /// The structure of `set` starting in `chunk`
+    pub fn get_start(&self, set: &StructureSet, chunk: &Vector2<i32>) -> Option<StructureStart> {
+        if !self.is_start_chunk(set, chunk) {
+            return None;
+        }
+        let mut random = carver_random(self.seed, chunk);
+        let pieces = match set.structure {
+            Structure::VillagePlains => jigsaw::assemble_village(&mut random, chunk),
+            Structure::Stronghold => stronghold::generate(&mut random, chunk),
+            Structure::EndCity => end_city::generate(&mut random, chunk),
+        };
+        (!pieces.is_empty()).then_some(StructureStart {
+            structure: set.structure,
+            chunk: *chunk,
+            pieces,
+        })
+    }
+
+    /// The structures starting in and reaching into `chunk`
+    pub fn chunk_structures(&self, chunk: &Vector2<i32>) -> ChunkStructures {
+        let mut structures = ChunkStructures::default();
+        for set in self.sets {
+            for offset_x in -REFERENCE_RANGE..=REFERENCE_RANGE {
+                for offset_z in -REFERENCE_RANGE..=REFERENCE_RANGE {
+                    let source = Vector2::new(chunk.x + offset_x, chunk.z + offset_z);
+                    let Some(start) = self.get_start(set, &source) else {
+                        continue;
+                    };
+                    if start
+                        .bounding_box()
+                        .is_some_and(|bounding_box| bounding_box.intersects_chunk(chunk))
+                    {
+                        structures
+                            .references
+                            .entry(set.structure)
+                            .or_default()
+                            .push(source);
+                    }
+                    if source == *chunk {
+                        structures.starts.insert(set.structure, start);
+                    }
+                }
+            }
+        }
+        structures
+    }
+
+    /// The start chunk of the `structure` closest to `origin`
+    pub fn locate(&self, structure: Structure, origin: &Vector2<i32>) -> Option<Vector2<i32>> {
+        let set = self.sets.iter().find(|set| set.structure == structure)?;
+        let distance = |chunk: &Vector2<i32>| {
+            let x = (chunk.x - origin.x) as i64;
+            let z = (chunk.z - origin.z) as i64;
+            x * x + z * z
+        };
+
+        match set.placement {
+            StructurePlacement::RandomSpread { spacing, .. } => {
+                let region_x = origin.x.div_euclid(spacing);
+                let region_z = origin.z.div_euclid(spacing);
+                // The first ring of regions with a start, the closest start may still be in the
+                // next ring but vanilla accepts that too
+                (0..=LOCATE_REGION_RADIUS).find_map(|radius| {
+                    (-radius..=radius)
+                        .flat_map(|x| (-radius..=radius).map(move |z| (x, z)))
+                        .filter(|(x, z)| x.abs() == radius || z.abs() == radius)
+                        .filter_map(|(x, z)| {
+                            let region =
+                                Vector2::new((region_x + x) * spacing, (region_z + z) * spacing);
+                            set.placement.start_chunk(self.seed, &region)
+                        })
+                        .min_by_key(distance)
+                })
+            }
+            StructurePlacement::ConcentricRings { .. } => {
+                self.stronghold_chunks.iter().copied().min_by_key(distance)
+            }
+        }
+    }
+}
+
+/// Moves the pieces so the top of the structure is at least `top_penalty` blocks below `top_y`,
+/// at a random height above `bottom_y`
+fn shift_into(
+    pieces: &mut [StructurePiece],
+    random: &mut LegacyRand,
+    top_y: i32,
+    bottom_y: i32,
+    top_penalty: i32,
+) {
+    let Some(bounding_box) = pieces
+        .iter()
+        .map(|piece| piece.bounding_box)
+        .reduce(|bounding_box, other| bounding_box.encompass(&other))
+    else {
+        return;
+    };
+    let max_top = top_y - top_penalty;
+    let mut top = bounding_box.height() + bottom_y + 1;
+    if top < max_top {
+        top += random.next_bounded_i32(max_top - top);
+    }
+    let offset = top - bounding_box.max.y;
+    for piece in pieces {
+        piece.bounding_box.move_y(offset);
+    }
+}
+
+/// Shuffles like `Util.shuffle` of vanilla
+fn shuffle<T>(values: &mut [T], random: &mut LegacyRand) {
+    for index in (1..values.len()).rev() {
+        let other = random.next_bounded_i32(index as i32 + 1) as usize;
+        values.swap(index, other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::vector2::Vector2;
+
+    use crate::generation::{positions::chunk_pos, Seed};
+
+    use super::{
+        placement::StructurePlacement, Structure, StructureGenerator, SEA_LEVEL, STRUCTURE_SETS,
+    };
+
+    const SEED: u64 = 0x5eed;
+
+    fn first_start(generator: &StructureGenerator, structure: Structure) -> Vector2<i32> {
+        generator
+            .locate(structure, &Vector2::new(0, 0))
+            .expect("Every seed has a start near the origin")
+    }
+
+    #[test]
+    fn villages_are_spread_over_regions() {
+        let placement = &STRUCTURE_SETS[0].placement;
+        for region_x in -3..3 {
+            for region_z in -3..3 {
+                let chunk = Vector2::new(region_x * 34 + 5, region_z * 34 + 30);
+                let start = placement.start_chunk(SEED, &chunk).unwrap();
+                assert_eq!(start.x.div_euclid(34), region_x);
+                assert_eq!(start.z.div_euclid(34), region_z);
+                // Separation keeps the start away from the far edge of the region
+                assert!(start.x.rem_euclid(34) < 34 - 8);
+                assert!(start.z.rem_euclid(34) < 34 - 8);
+                assert_eq!(placement.start_chunk(SEED, &start), Some(start));
+            }
+        }
+    }
+
+    #[test]
+    fn strongholds_are_on_rings() {
+        let placement = &STRUCTURE_SETS[1].placement;
+        let StructurePlacement::ConcentricRings {
+            distance, count, ..
+        } = *placement
+        else {
+            unreachable!()
+        };
+        let positions = placement.ring_positions(SEED);
+        assert_eq!(positions.len(), count as usize);
+        assert_eq!(positions, placement.ring_positions(SEED));
+        // The first ring has 3 strongholds
+        for chunk in &positions[..3] {
+            let radius = ((chunk.x * chunk.x + chunk.z * chunk.z) as f64).sqrt();
+            let center = (4 * distance) as f64;
+            assert!((radius - center).abs() <= distance as f64 * 1.25 + 1.0);
+        }
+    }
+
+    #[test]
+    fn village_pieces_do_not_overlap() {
+        let generator = StructureGenerator::new(Seed(SEED));
+        let chunk = first_start(&generator, Structure::VillagePlains);
+        let start = generator.get_start(&STRUCTURE_SETS[0], &chunk).unwrap();
+        assert!(start.pieces.len() > 1);
+        for (index, piece) in start.pieces.iter().enumerate() {
+            for other in &start.pieces[index + 1..] {
+                assert!(!piece.bounding_box.intersects(&other.bounding_box));
+            }
+        }
+        let bounding_box = start.bounding_box().unwrap();
+        let center_x = chunk_pos::start_block_x(&chunk);
+        assert!(bounding_box.min.x >= center_x - 90 && bounding_box.max.x <= center_x + 100);
+    }
+
+    #[test]
+    fn strongholds_have_a_portal_room_underground() {
+        let generator = StructureGenerator::new(Seed(SEED));
+        let chunk = first_start(&generator, Structure::Stronghold);
+        let start = generator.get_start(&STRUCTURE_SETS[1], &chunk).unwrap();
+        assert!(start
+            .pieces
+            .iter()
+            .any(|piece| piece.id == "minecraft:shpr"));
+        assert!(start.bounding_box().unwrap().max.y <= SEA_LEVEL - 10);
+    }
+
+    #[test]
+    fn pieces_are_referenced_by_their_chunks() {
+        let generator = StructureGenerator::new(Seed(SEED));
+        let start_chunk = first_start(&generator, Structure::VillagePlains);
+        let structures = generator.chunk_structures(&start_chunk);
+        let start = &structures.starts[&Structure::VillagePlains];
+        assert_eq!(start.chunk, start_chunk);
+
+        for piece in &start.pieces {
+            let chunk = Vector2::new(piece.bounding_box.min.x >> 4, piece.bounding_box.min.z >> 4);
+            let references = &generator.chunk_structures(&chunk).references;
+            assert!(references[&Structure::VillagePlains].contains(&start_chunk));
+        }
+    }
+
+    #[test]
+    fn locate_finds_the_closest_start() {
+        let generator = StructureGenerator::new(Seed(SEED));
+        let origin = Vector2::new(300, -200);
+        let village = generator.locate(Structure::VillagePlains, &origin).unwrap();
+        assert_eq!(
+            STRUCTURE_SETS[0].placement.start_chunk(SEED, &village),
+            Some(village)
+        );
+        assert!((village.x - origin.x).abs() < 34 * 2);
+        assert!((village.z - origin.z).abs() < 34 * 2);
+
+        let stronghold = generator.locate(Structure::Stronghold, &origin).unwrap();
+        assert!(generator.stronghold_chunks.contains(&stronghold));
+    }
+}
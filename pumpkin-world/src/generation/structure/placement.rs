@@ -0,0 +1,118 @@
+use std::f64::consts::PI;
+
+use pumpkin_core::{
+    math::vector2::Vector2,
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+#[derive(Clone, Copy)]
+pub enum SpreadType {
+    Linear,
+    /// Averages two offsets so starts are more likely in the middle of their region
+    Triangular,
+}
+
+impl SpreadType {
+    fn offset(&self, random: &mut LegacyRand, bound: i32) -> i32 {
+        match self {
+            Self::Linear => random.next_bounded_i32(bound),
+            Self::Triangular => {
+                (random.next_bounded_i32(bound) + random.next_bounded_i32(bound)) / 2
+            }
+        }
+    }
+}
+
+/// Where the starts of the structures of a structure set may be
+pub enum StructurePlacement {
+    /// One start in every region of `spacing` chunks, at least `separation` chunks away from the
+    /// next region
+    RandomSpread {
+        spacing: i32,
+        separation: i32,
+        spread_type: SpreadType,
+        salt: i32,
+    },
+    /// A fixed amount of starts on rings around the world origin, `distance` is the distance
+    /// between rings in units of 3 chunks and `spread` the amount of starts on the first ring
+    ConcentricRings {
+        distance: i32,
+        spread: i32,
+        count: i32,
+    },
+}
+
+impl StructurePlacement {
+    /// The chunk of the only possible start in the region of `chunk`
+    pub fn start_chunk(&self, seed: u64, chunk: &Vector2<i32>) -> Option<Vector2<i32>> {
+        match self {
+            Self::RandomSpread {
+                spacing,
+                separation,
+                spread_type,
+                salt,
+            } => {
+                let region_x = chunk.x.div_euclid(*spacing);
+                let region_z = chunk.z.div_euclid(*spacing);
+                let mut random =
+                    LegacyRand::from_seed(region_seed(seed, region_x, region_z, *salt));
+                let bound = spacing - separation;
+                let offset_x = spread_type.offset(&mut random, bound);
+                let offset_z = spread_type.offset(&mut random, bound);
+                Some(Vector2::new(
+                    region_x * spacing + offset_x,
+                    region_z * spacing + offset_z,
+                ))
+            }
+            Self::ConcentricRings { .. } => None,
+        }
+    }
+
+    /// The chunks of every start on the rings, empty for other placements
+    pub fn ring_positions(&self, seed: u64) -> Vec<Vector2<i32>> {
+        let Self::ConcentricRings {
+            distance,
+            spread,
+            count,
+        } = self
+        else {
+            return Vec::new();
+        };
+
+        let mut positions = Vec::with_capacity(*count as usize);
+        let mut random = LegacyRand::from_seed(seed);
+        let mut angle = random.next_f64() * PI * 2.0;
+        let mut spread = *spread;
+        let mut on_ring = 0;
+        let mut ring = 0;
+
+        for index in 0..*count {
+            let radius = (4 * distance + distance * ring * 6) as f64
+                + (random.next_f64() - 0.5) * *distance as f64 * 2.5;
+            let x = (angle.cos() * radius).round() as i32;
+            let z = (angle.sin() * radius).round() as i32;
+            // Vanilla moves the start to a nearby preferred biome with this split random
+            let _ = random.split();
+            positions.push(Vector2::new(x, z));
+
+            angle += PI * 2.0 / spread as f64;
+            on_ring += 1;
+            if on_ring == spread {
+                ring += 1;
+                on_ring = 0;
+                spread += 2 * spread / (ring + 1);
+                spread = spread.min(count - index);
+                angle += random.next_f64() * PI * 2.0;
+            }
+        }
+        positions
+    }
+}
+
+fn region_seed(seed: u64, region_x: i32, region_z: i32, salt: i32) -> u64 {
+    (region_x as i64)
+        .wrapping_mul(341873128712)
+        .wrapping_add((region_z as i64).wrapping_mul(132897987541))
+        .wrapping_add(seed as i64)
+        .wrapping_add(salt as i64) as u64
+}
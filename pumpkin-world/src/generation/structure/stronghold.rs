@@ -0,0 +1,363 @@
+use pumpkin_core::{
+    math::{vector2::Vector2, vector3::Vector3},
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+
+use crate::{generation::positions::chunk_pos, WORLD_LOWEST_Y};
+
+use super::{shift_into, BlockBox, Facing, StructurePiece, SEA_LEVEL};
+
+/// Pieces further away from the start are not generated
+const MAX_CHAIN_LENGTH: i32 = 50;
+const MAX_DISTANCE: i32 = 112;
+/// Vanilla retries until the portal room was placed, which may take a few tries
+const MAX_ATTEMPTS: usize = 32;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PieceType {
+    Start,
+    Corridor,
+    PrisonHall,
+    LeftTurn,
+    RightTurn,
+    SquareRoom,
+    Stairs,
+    SpiralStaircase,
+    FiveWayCrossing,
+    ChestCorridor,
+    Library,
+    PortalRoom,
+}
+
+/// Where the next piece is attached, relative to the direction the piece is facing.
+/// `along` moves along the side and `up` raises the entrance of the next piece
+#[derive(Clone, Copy)]
+enum Exit {
+    Forward { along: i32, up: i32 },
+    Left { along: i32, up: i32 },
+    Right { along: i32, up: i32 },
+}
+
+const fn forward(along: i32, up: i32) -> Exit {
+    Exit::Forward { along, up }
+}
+
+const fn left(along: i32, up: i32) -> Exit {
+    Exit::Left { along, up }
+}
+
+const fn right(along: i32, up: i32) -> Exit {
+    Exit::Right { along, up }
+}
+
+/// The pieces which can be picked for a new opening
+const PICKABLE: [PieceType; 11] = [
+    PieceType::Corridor,
+    PieceType::PrisonHall,
+    PieceType::LeftTurn,
+    PieceType::RightTurn,
+    PieceType::SquareRoom,
+    PieceType::Stairs,
+    PieceType::SpiralStaircase,
+    PieceType::FiveWayCrossing,
+    PieceType::ChestCorridor,
+    PieceType::Library,
+    PieceType::PortalRoom,
+];
+
+impl PieceType {
+    const fn id(&self) -> &'static str {
+        match self {
+            Self::Start => "minecraft:shstart",
+            Self::Corridor => "minecraft:shs",
+            Self::PrisonHall => "minecraft:shph",
+            Self::LeftTurn => "minecraft:shlt",
+            Self::RightTurn => "minecraft:shrt",
+            Self::SquareRoom => "minecraft:shrc",
+            Self::Stairs => "minecraft:shssd",
+            Self::SpiralStaircase => "minecraft:shsd",
+            Self::FiveWayCrossing => "minecraft:sh5c",
+            Self::ChestCorridor => "minecraft:shcc",
+            Self::Library => "minecraft:shli",
+            Self::PortalRoom => "minecraft:shpr",
+        }
+    }
+
+    const fn weight(&self) -> i32 {
+        match self {
+            Self::Corridor => 40,
+            Self::LeftTurn | Self::RightTurn | Self::PortalRoom => 20,
+            Self::SquareRoom | Self::Library => 10,
+            _ => 5,
+        }
+    }
+
+    /// How often the piece may be generated, 0 is unlimited
+    const fn limit(&self) -> i32 {
+        match self {
+            Self::PrisonHall | Self::Stairs | Self::SpiralStaircase => 5,
+            Self::SquareRoom => 6,
+            Self::FiveWayCrossing | Self::ChestCorridor => 4,
+            Self::Library => 2,
+            Self::PortalRoom => 1,
+            _ => 0,
+        }
+    }
+
+    fn can_generate(&self, chain_length: i32) -> bool {
+        match self {
+            Self::Library => chain_length > 4,
+            Self::PortalRoom => chain_length > 5,
+            _ => true,
+        }
+    }
+
+    /// The offset from the entrance and the size of the piece when facing south
+    const fn shape(&self) -> (Vector3<i32>, Vector3<i32>) {
+        match self {
+            Self::Start => (Vector3::new(0, 0, 0), Vector3::new(5, 11, 5)),
+            Self::Corridor | Self::ChestCorridor => {
+                (Vector3::new(-1, -1, 0), Vector3::new(5, 5, 7))
+            }
+            Self::PrisonHall => (Vector3::new(-1, -1, 0), Vector3::new(9, 5, 11)),
+            Self::LeftTurn | Self::RightTurn => (Vector3::new(-1, -1, 0), Vector3::new(5, 5, 5)),
+            Self::SquareRoom => (Vector3::new(-4, -1, 0), Vector3::new(11, 7, 11)),
+            Self::Stairs => (Vector3::new(-1, -7, 0), Vector3::new(5, 11, 8)),
+            Self::SpiralStaircase => (Vector3::new(-1, -7, 0), Vector3::new(5, 11, 5)),
+            Self::FiveWayCrossing => (Vector3::new(-4, -3, 0), Vector3::new(10, 9, 11)),
+            Self::Library => (Vector3::new(-4, -1, 0), Vector3::new(14, 11, 15)),
+            Self::PortalRoom => (Vector3::new(-4, -1, 0), Vector3::new(11, 8, 16)),
+        }
+    }
+
+    fn exits(&self, random: &mut LegacyRand) -> Vec<Exit> {
+        match self {
+            Self::Start
+            | Self::PrisonHall
+            | Self::Stairs
+            | Self::SpiralStaircase
+            | Self::ChestCorridor => vec![forward(1, 1)],
+            Self::Corridor => {
+                let mut exits = vec![forward(1, 1)];
+                if random.next_bounded_i32(2) == 0 {
+                    exits.push(left(1, 2));
+                }
+                if random.next_bounded_i32(2) == 0 {
+                    exits.push(right(1, 2));
+                }
+                exits
+            }
+            Self::LeftTurn => vec![left(1, 1)],
+            Self::RightTurn => vec![right(1, 1)],
+            Self::SquareRoom => vec![forward(4, 1), left(4, 1), right(4, 1)],
+            Self::FiveWayCrossing => vec![
+                forward(5, 1),
+                left(3, 1),
+                right(3, 1),
+                left(5, 7),
+                right(5, 7),
+            ],
+            Self::Library | Self::PortalRoom => Vec::new(),
+        }
+    }
+}
+
+struct Piece {
+    piece_type: PieceType,
+    bounding_box: BlockBox,
+    facing: Facing,
+    chain_length: i32,
+    exits: Vec<Exit>,
+}
+
+impl Piece {
+    /// The entrance of the next piece and the direction it faces
+    fn exit_position(&self, exit: Exit) -> (Vector3<i32>, Facing) {
+        let min = self.bounding_box.min;
+        let max = self.bounding_box.max;
+        let along_x = matches!(self.facing, Facing::North | Facing::South);
+        match exit {
+            Exit::Forward { along, up } => match self.facing {
+                Facing::North => (
+                    Vector3::new(min.x + along, min.y + up, min.z - 1),
+                    Facing::North,
+                ),
+                Facing::South => (
+                    Vector3::new(min.x + along, min.y + up, max.z + 1),
+                    Facing::South,
+                ),
+                Facing::West => (
+                    Vector3::new(min.x - 1, min.y + up, min.z + along),
+                    Facing::West,
+                ),
+                Facing::East => (
+                    Vector3::new(max.x + 1, min.y + up, min.z + along),
+                    Facing::East,
+                ),
+            },
+            Exit::Left { along, up } if along_x => (
+                Vector3::new(min.x - 1, min.y + up, min.z + along),
+                Facing::West,
+            ),
+            Exit::Left { along, up } => (
+                Vector3::new(min.x + along, min.y + up, min.z - 1),
+                Facing::North,
+            ),
+            Exit::Right { along, up } if along_x => (
+                Vector3::new(max.x + 1, min.y + up, min.z + along),
+                Facing::East,
+            ),
+            Exit::Right { along, up } => (
+                Vector3::new(min.x + along, min.y + up, max.z + 1),
+                Facing::South,
+            ),
+        }
+    }
+}
+
+struct StrongholdGenerator<'a> {
+    random: &'a mut LegacyRand,
+    pieces: Vec<Piece>,
+    counts: [i32; PICKABLE.len()],
+    last_placed: Option<PieceType>,
+    has_portal_room: bool,
+}
+
+impl StrongholdGenerator<'_> {
+    fn generate(&mut self, chunk: &Vector2<i32>) {
+        let facing = Facing::random(self.random);
+        let x = chunk_pos::start_block_x(chunk) + 2;
+        let z = chunk_pos::start_block_z(chunk) + 2;
+        let (_, size) = PieceType::Start.shape();
+        let exits = PieceType::Start.exits(self.random);
+        self.pieces.push(Piece {
+            piece_type: PieceType::Start,
+            bounding_box: BlockBox::new(
+                Vector3::new(x, 64, z),
+                Vector3::new(x + size.x - 1, 64 + size.y - 1, z + size.z - 1),
+            ),
+            facing,
+            chain_length: 0,
+            exits,
+        });
+
+        let mut pending = vec![0];
+        while !pending.is_empty() {
+            let index = pending.remove(self.random.next_bounded_i32(pending.len() as i32) as usize);
+            let openings: Vec<_> = self.pieces[index]
+                .exits
+                .iter()
+                .map(|exit| self.pieces[index].exit_position(*exit))
+                .collect();
+            let chain_length = self.pieces[index].chain_length + 1;
+            for (pos, facing) in openings {
+                if let Some(piece) = self.place_next(pos, facing, chain_length) {
+                    pending.push(piece);
+                }
+            }
+        }
+    }
+
+    /// Picks a random piece for the opening at `pos`, pieces which don't fit are retried a few
+    /// times before the opening is left closed
+    fn place_next(
+        &mut self,
+        pos: Vector3<i32>,
+        facing: Facing,
+        chain_length: i32,
+    ) -> Option<usize> {
+        let start = self.pieces[0].bounding_box.min;
+        if chain_length > MAX_CHAIN_LENGTH
+            || (pos.x - start.x).abs() > MAX_DISTANCE
+            || (pos.z - start.z).abs() > MAX_DISTANCE
+        {
+            return None;
+        }
+
+        let available: Vec<_> = PICKABLE
+            .iter()
+            .enumerate()
+            .filter(|(index, piece_type)| {
+                piece_type.limit() == 0 || self.counts[*index] < piece_type.limit()
+            })
+            .map(|(index, piece_type)| (index, *piece_type))
+            .collect();
+        let total_weight: i32 = available
+            .iter()
+            .map(|(_, piece_type)| piece_type.weight())
+            .sum();
+        if total_weight <= 0 {
+            return None;
+        }
+
+        for _ in 0..5 {
+            let mut remaining = self.random.next_bounded_i32(total_weight);
+            for (index, piece_type) in &available {
+                remaining -= piece_type.weight();
+                if remaining >= 0 {
+                    continue;
+                }
+                if !piece_type.can_generate(chain_length) || self.last_placed == Some(*piece_type) {
+                    break;
+                }
+                let (offset, size) = piece_type.shape();
+                let bounding_box = BlockBox::rotated(pos, offset, size, facing);
+                if bounding_box.min.y <= 10
+                    || self
+                        .pieces
+                        .iter()
+                        .any(|piece| piece.bounding_box.intersects(&bounding_box))
+                {
+                    break;
+                }
+
+                let exits = piece_type.exits(self.random);
+                self.counts[*index] += 1;
+                self.last_placed = Some(*piece_type);
+                if *piece_type == PieceType::PortalRoom {
+                    self.has_portal_room = true;
+                }
+                self.pieces.push(Piece {
+                    piece_type: *piece_type,
+                    bounding_box,
+                    facing,
+                    chain_length,
+                    exits,
+                });
+                return Some(self.pieces.len() - 1);
+            }
+        }
+        None
+    }
+}
+
+/// Generates stronghold pieces starting with a spiral staircase in the given chunk, the whole
+/// stronghold is moved below the sea level afterwards
+pub(super) fn generate(random: &mut LegacyRand, chunk: &Vector2<i32>) -> Vec<StructurePiece> {
+    let mut pieces = Vec::new();
+    for _ in 0..MAX_ATTEMPTS {
+        let mut generator = StrongholdGenerator {
+            random: &mut *random,
+            pieces: Vec::new(),
+            counts: [0; PICKABLE.len()],
+            last_placed: None,
+            has_portal_room: false,
+        };
+        generator.generate(chunk);
+        let has_portal_room = generator.has_portal_room;
+        pieces = generator
+            .pieces
+            .into_iter()
+            .map(|piece| StructurePiece {
+                id: piece.piece_type.id().to_string(),
+                bounding_box: piece.bounding_box,
+                depth: piece.chain_length,
+            })
+            .collect();
+        if has_portal_room {
+            break;
+        }
+    }
+    shift_into(&mut pieces, random, SEA_LEVEL, WORLD_LOWEST_Y as i32, 10);
+    pieces
+}
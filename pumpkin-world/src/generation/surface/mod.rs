@@ -0,0 +1,389 @@
+use pumpkin_core::{
+    math::vector3::Vector3,
+    random::{xoroshiro128::Xoroshiro, RandomDeriver, RandomGenerator, RandomImpl},
+};
+use pumpkin_macros::block_state;
+
+use crate::{biome::Biome, block::BlockState};
+
+use super::{
+    chunk_noise::{LAVA_BLOCK, WATER_BLOCK},
+    noise::{
+        built_in_noise_params,
+        perlin::{DoublePerlinNoiseParameters, DoublePerlinNoiseSampler},
+    },
+    positions::chunk_pos,
+    proto_chunk::ProtoChunk,
+    settings::{GenerationSettings, OVERWORLD_SETTINGS},
+    Seed,
+};
+
+mod nether;
+mod overworld;
+pub mod rules;
+
+pub use nether::create_surface_rule as create_nether_surface_rule;
+pub use overworld::create_surface_rule as create_overworld_surface_rule;
+use rules::MaterialRule;
+
+/// The end is end stone all the way through
+pub fn create_end_surface_rule(_random_deriver: &RandomDeriver) -> MaterialRule {
+    MaterialRule::Block(block_state!("end_stone"))
+}
+
+/// The state of the block currently being replaced by the surface rules
+pub struct SurfaceContext<'a> {
+    terracotta_bands: &'a TerracottaBands,
+    pub biome: Biome,
+    pub block_x: i32,
+    pub block_y: i32,
+    pub block_z: i32,
+    /// How deep the surface layer of this column is
+    pub surface_depth: i32,
+    pub secondary_depth: f64,
+    /// Surface rules only run above this level
+    pub min_surface_level: i32,
+    /// Solid blocks from the last air block above, including this one
+    pub stone_depth_above: i32,
+    /// Blocks of the default block below this one, including this one
+    pub stone_depth_below: i32,
+    /// The y above the topmost fluid block of the current fluid body, `i32::MIN` if there is none
+    pub fluid_height: i32,
+}
+
+pub struct TerracottaBands {
+    bands: Vec<BlockState>,
+    offset_noise: DoublePerlinNoiseSampler,
+}
+
+impl TerracottaBands {
+    fn new(random_deriver: &RandomDeriver) -> Self {
+        let mut random = random_deriver.split_string("minecraft:clay_bands");
+        let mut bands = vec![block_state!("terracotta"); 192];
+
+        let mut index = 0;
+        while index < bands.len() {
+            index += random.next_bounded_i32(5) as usize + 1;
+            if index < bands.len() {
+                bands[index] = block_state!("orange_terracotta");
+            }
+            index += 1;
+        }
+
+        Self::add_bands(
+            &mut random,
+            &mut bands,
+            1,
+            block_state!("yellow_terracotta"),
+        );
+        Self::add_bands(&mut random, &mut bands, 2, block_state!("brown_terracotta"));
+        Self::add_bands(&mut random, &mut bands, 1, block_state!("red_terracotta"));
+
+        let white_bands = random.next_inbetween_i32(9, 15);
+        let mut index = 0;
+        let mut count = 0;
+        while count < white_bands && index < bands.len() {
+            bands[index] = block_state!("white_terracotta");
+            if index > 1 && random.next_bool() {
+                bands[index - 1] = block_state!("light_gray_terracotta");
+            }
+            if index + 1 < bands.len() && random.next_bool() {
+                bands[index + 1] = block_state!("light_gray_terracotta");
+            }
+            count += 1;
+            index += random.next_bounded_i32(16) as usize + 4;
+        }
+
+        Self {
+            bands,
+            offset_noise: create_noise(random_deriver, &built_in_noise_params::CLAY_BANDS_OFFSET),
+        }
+    }
+
+    fn add_bands(
+        random: &mut RandomGenerator,
+        bands: &mut [BlockState],
+        min_band_size: i32,
+        state: BlockState,
+    ) {
+        let count = random.next_inbetween_i32(6, 15);
+        for _ in 0..count {
+            let size = (min_band_size + random.next_bounded_i32(3)) as usize;
+            let start = random.next_bounded_i32(bands.len() as i32) as usize;
+            for band in bands.iter_mut().skip(start).take(size) {
+                *band = state;
+            }
+        }
+    }
+
+    fn get_block(&self, x: i32, y: i32, z: i32) -> BlockState {
+        let offset = (self.offset_noise.sample(x as f64, 0.0, z as f64) * 4.0).round() as i32;
+        let length = self.bands.len() as i32;
+        self.bands[(y + offset + length).rem_euclid(length) as usize]
+    }
+}
+
+fn create_noise(
+    random_deriver: &RandomDeriver,
+    params: &'static DoublePerlinNoiseParameters,
+) -> DoublePerlinNoiseSampler {
+    let mut random = random_deriver.split_string(params.id());
+    DoublePerlinNoiseSampler::new(&mut random, params, false)
+}
+
+/// Replaces the default block near the surface with biome dependent blocks
+pub struct SurfaceSystem {
+    random_deriver: RandomDeriver,
+    surface_noise: DoublePerlinNoiseSampler,
+    secondary_noise: DoublePerlinNoiseSampler,
+    terracotta_bands: TerracottaBands,
+    rule: MaterialRule,
+    default_block: BlockState,
+}
+
+impl SurfaceSystem {
+    pub fn new(seed: Seed) -> Self {
+        Self::with_settings(seed, &OVERWORLD_SETTINGS)
+    }
+
+    /// Uses the surface rule of `settings`, which replaces only its default block
+    pub fn with_settings(seed: Seed, settings: &GenerationSettings) -> Self {
+        let random_deriver = RandomDeriver::Xoroshiro(Xoroshiro::from_seed(seed.0).next_splitter());
+        Self {
+            surface_noise: create_noise(&random_deriver, &built_in_noise_params::SURFACE),
+            secondary_noise: create_noise(
+                &random_deriver,
+                &built_in_noise_params::SURFACE_SECONDARY,
+            ),
+            terracotta_bands: TerracottaBands::new(&random_deriver),
+            rule: (settings.surface_rule)(&random_deriver),
+            default_block: settings.default_block,
+            random_deriver,
+        }
+    }
+
+    fn is_fluid(state: &BlockState) -> bool {
+        state.of_block(WATER_BLOCK.block_id) || state.of_block(LAVA_BLOCK.block_id)
+    }
+
+    /// Applies the surface rules to every column of the chunk, `biome_at` gives the biome
+    /// of a column in block coordinates
+    pub fn build_surface(&self, chunk: &mut ProtoChunk, biome_at: &dyn Fn(i32, i32) -> Biome) {
+        let chunk_pos = chunk.chunk_pos();
+        let start_x = chunk_pos::start_block_x(&chunk_pos);
+        let start_z = chunk_pos::start_block_z(&chunk_pos);
+        let min_y = chunk.min_y();
+        let max_y = min_y + chunk.height() - 1;
+
+        let corner_heights = [
+            chunk.estimate_surface_height(start_x, start_z) as f64,
+            chunk.estimate_surface_height(start_x + 16, start_z) as f64,
+            chunk.estimate_surface_height(start_x, start_z + 16) as f64,
+            chunk.estimate_surface_height(start_x + 16, start_z + 16) as f64,
+        ];
+
+        for local_x in 0..16 {
+            for local_z in 0..16 {
+                let block_x = start_x + local_x;
+                let block_z = start_z + local_z;
+
+                let Some(top_y) = (min_y..=max_y).rev().find(|y| {
+                    !chunk
+                        .get_block_state(&Vector3::new(local_x, *y, local_z))
+                        .is_air()
+                }) else {
+                    continue;
+                };
+
+                let surface_depth = (self
+                    .surface_noise
+                    .sample(block_x as f64, 0.0, block_z as f64)
+                    * 2.75
+                    + 3.0
+                    + self
+                        .random_deriver
+                        .split_pos(block_x, 0, block_z)
+                        .next_f64()
+                        * 0.25) as i32;
+                let delta_x = local_x as f64 / 16.0;
+                let delta_z = local_z as f64 / 16.0;
+                let estimated_height = lerp(
+                    delta_z,
+                    lerp(delta_x, corner_heights[0], corner_heights[1]),
+                    lerp(delta_x, corner_heights[2], corner_heights[3]),
+                );
+
+                let mut context = SurfaceContext {
+                    terracotta_bands: &self.terracotta_bands,
+                    biome: biome_at(block_x, block_z),
+                    block_x,
+                    block_y: top_y,
+                    block_z,
+                    surface_depth,
+                    secondary_depth: self.secondary_noise.sample(
+                        block_x as f64,
+                        0.0,
+                        block_z as f64,
+                    ),
+                    // Terrain without an estimated surface, like the nether, has no preliminary
+                    // surface
+                    min_surface_level: (estimated_height.floor() as i32)
+                        .saturating_add(surface_depth - 8),
+                    stone_depth_above: 0,
+                    stone_depth_below: 0,
+                    fluid_height: i32::MIN,
+                };
+
+                let mut next_ceiling_y = i32::MAX;
+                for block_y in (min_y..=top_y).rev() {
+                    let pos = Vector3::new(local_x, block_y, local_z);
+                    let state = chunk.get_block_state(&pos);
+                    if state.is_air() {
+                        context.stone_depth_above = 0;
+                        context.fluid_height = i32::MIN;
+                        continue;
+                    }
+                    if Self::is_fluid(&state) {
+                        if context.fluid_height == i32::MIN {
+                            context.fluid_height = block_y + 1;
+                        }
+                        continue;
+                    }
+
+                    if next_ceiling_y >= block_y {
+                        // Everything below the world counts as air
+                        next_ceiling_y = min_y;
+                        for below_y in (min_y..block_y).rev() {
+                            if chunk.get_block_state(&Vector3::new(local_x, below_y, local_z))
+                                != self.default_block
+                            {
+                                next_ceiling_y = below_y + 1;
+                                break;
+                            }
+                        }
+                    }
+
+                    context.stone_depth_above += 1;
+                    context.stone_depth_below = block_y - next_ceiling_y + 1;
+                    context.block_y = block_y;
+
+                    if state == self.default_block {
+                        if let Some(new_state) = self.rule.try_apply(&context) {
+                            chunk.set_block_state(&pos, new_state);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lerp(delta: f64, start: f64, end: f64) -> f64 {
+    start + delta * (end - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{vector2::Vector2, vector3::Vector3};
+    use pumpkin_macros::block_state;
+
+    use crate::{
+        biome::Biome,
+        block::BlockState,
+        generation::{
+            chunk_noise::{STONE_BLOCK, WATER_BLOCK},
+            proto_chunk::ProtoChunk,
+            Seed,
+        },
+    };
+
+    use super::SurfaceSystem;
+
+    fn surface_chunk(chunk_pos: Vector2<i32>, biome: Biome) -> (ProtoChunk, ProtoChunk) {
+        let mut chunk = ProtoChunk::new(chunk_pos, 0);
+        chunk.populate_noise();
+        let mut surface = ProtoChunk::new(chunk_pos, 0);
+        surface.populate_noise();
+        SurfaceSystem::new(Seed(0)).build_surface(&mut surface, &|_, _| biome);
+        (chunk, surface)
+    }
+
+    /// The topmost solid block and whether it is below water
+    fn top_block(chunk: &ProtoChunk, x: i32, z: i32) -> (i32, bool) {
+        let mut y = chunk.min_y() + chunk.height() - 1;
+        loop {
+            let state = chunk.get_block_state(&Vector3::new(x, y, z));
+            if !state.is_air() && !SurfaceSystem::is_fluid(&state) {
+                let above = chunk.get_block_state(&Vector3::new(x, y + 1, z));
+                return (y, above.of_block(WATER_BLOCK.block_id));
+            }
+            y -= 1;
+        }
+    }
+
+    #[test]
+    fn bedrock_floor_and_deepslate() {
+        let (noise, chunk) = surface_chunk(Vector2::new(0, 0), Biome::Plains);
+        let bedrock = block_state!("bedrock");
+        let deepslate = block_state!("deepslate");
+        for x in 0..16 {
+            for z in 0..16 {
+                assert_eq!(chunk.get_block_state(&Vector3::new(x, -64, z)), bedrock);
+                let pos = Vector3::new(x, -10, z);
+                if noise.get_block_state(&pos) == STONE_BLOCK {
+                    assert_eq!(chunk.get_block_state(&pos), deepslate);
+                }
+            }
+        }
+    }
+
+    fn assert_surface(biome: Biome, on_land: BlockState, under_water: &[BlockState]) {
+        let (_, chunk) = surface_chunk(Vector2::new(7, 4), biome);
+        for x in 0..16 {
+            for z in 0..16 {
+                let (y, flooded) = top_block(&chunk, x, z);
+                let state = chunk.get_block_state(&Vector3::new(x, y, z));
+                if flooded {
+                    assert!(under_water.contains(&state), "{state:?} under water");
+                } else {
+                    assert_eq!(state, on_land);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn plains_surface() {
+        assert_surface(
+            Biome::Plains,
+            block_state!("grass_block"),
+            &[
+                block_state!("dirt"),
+                block_state!("gravel"),
+                block_state!("stone"),
+            ],
+        );
+    }
+
+    #[test]
+    fn desert_surface() {
+        assert_surface(
+            Biome::Desert,
+            block_state!("sand"),
+            &[block_state!("sand"), block_state!("sandstone")],
+        );
+    }
+
+    #[test]
+    fn badlands_surface() {
+        let (_, chunk) = surface_chunk(Vector2::new(7, 4), Biome::Badlands);
+        for x in 0..16 {
+            for z in 0..16 {
+                let (y, _) = top_block(&chunk, x, z);
+                let state = chunk.get_block_state(&Vector3::new(x, y, z));
+                assert_ne!(state, block_state!("grass_block"));
+                assert_ne!(state, block_state!("stone"));
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use pumpkin_core::random::RandomDeriver;
+use pumpkin_macros::block_state;
+
+use crate::{
+    biome::Biome,
+    generation::{
+        feature::YOffset,
+        noise::{
+            built_in_noise_params,
+            perlin::{DoublePerlinNoiseParameters, DoublePerlinNoiseSampler},
+        },
+    },
+};
+
+use super::{
+    create_noise,
+    rules::{
+        condition, floor, not, stone_depth, under_floor, vertical_gradient, y_above,
+        MaterialCondition::{self, *},
+        MaterialRule::{self, *},
+        VerticalSurfaceType,
+    },
+};
+
+/// The nether is 128 blocks high, so offsets from the world bottom and top don't apply
+const BOTTOM: i32 = 0;
+const TOP: i32 = 127;
+
+fn under_ceiling() -> MaterialCondition {
+    stone_depth(0, true, 0, VerticalSurfaceType::Ceiling)
+}
+
+fn noise(
+    random_deriver: &RandomDeriver,
+    params: &'static DoublePerlinNoiseParameters,
+) -> Arc<DoublePerlinNoiseSampler> {
+    Arc::new(create_noise(random_deriver, params))
+}
+
+fn noise_above(noise: &Arc<DoublePerlinNoiseSampler>, min_threshold: f64) -> MaterialCondition {
+    NoiseThreshold {
+        noise: noise.clone(),
+        min_threshold,
+        max_threshold: f64::MAX,
+    }
+}
+
+/// Nylium and wart blocks on the floors of the nether forests
+fn forest_floor(
+    netherrack: &Arc<DoublePerlinNoiseSampler>,
+    nether_wart: &Arc<DoublePerlinNoiseSampler>,
+    biome: Biome,
+    wart_block: MaterialRule,
+    nylium: MaterialRule,
+) -> MaterialRule {
+    condition(
+        MaterialCondition::Biome(vec![biome]),
+        condition(
+            not(noise_above(netherrack, 0.54)),
+            condition(
+                y_above(YOffset::Absolute(31), 0, false),
+                Sequence(vec![
+                    condition(noise_above(nether_wart, 1.17), wart_block),
+                    nylium,
+                ]),
+            ),
+        ),
+    )
+}
+
+/// The vanilla nether surface rules
+pub fn create_surface_rule(random_deriver: &RandomDeriver) -> MaterialRule {
+    let above_30 = || y_above(YOffset::Absolute(30), 0, true);
+    let not_above_35 = || not(y_above(YOffset::Absolute(35), 0, true));
+    let state_selector = noise(
+        random_deriver,
+        &built_in_noise_params::NETHER_STATE_SELECTOR,
+    );
+    let patch = noise(random_deriver, &built_in_noise_params::PATCH);
+    let netherrack = noise(random_deriver, &built_in_noise_params::NETHERRACK);
+    let nether_wart = noise(random_deriver, &built_in_noise_params::NETHER_WART);
+    let soul_sand_layer = noise(random_deriver, &built_in_noise_params::SOUL_SAND_LAYER);
+    let gravel_layer = noise(random_deriver, &built_in_noise_params::GRAVEL_LAYER);
+
+    let gravel_patch = || {
+        condition(
+            noise_above(&patch, -0.012),
+            condition(
+                above_30(),
+                condition(not_above_35(), Block(block_state!("gravel"))),
+            ),
+        )
+    };
+
+    Sequence(vec![
+        condition(
+            vertical_gradient(
+                random_deriver,
+                "minecraft:bedrock_floor",
+                YOffset::Absolute(BOTTOM),
+                YOffset::Absolute(BOTTOM + 5),
+            ),
+            Block(block_state!("bedrock")),
+        ),
+        condition(
+            not(vertical_gradient(
+                random_deriver,
+                "minecraft:bedrock_roof",
+                YOffset::Absolute(TOP - 5),
+                YOffset::Absolute(TOP),
+            )),
+            Block(block_state!("bedrock")),
+        ),
+        condition(
+            y_above(YOffset::Absolute(TOP - 5), 0, false),
+            Block(block_state!("netherrack")),
+        ),
+        condition(
+            MaterialCondition::Biome(vec![Biome::BasaltDeltas]),
+            Sequence(vec![
+                condition(under_ceiling(), Block(block_state!("basalt"))),
+                condition(
+                    under_floor(),
+                    Sequence(vec![
+                        gravel_patch(),
+                        condition(
+                            noise_above(&state_selector, 0.0),
+                            Block(block_state!("basalt")),
+                        ),
+                        Block(block_state!("blackstone")),
+                    ]),
+                ),
+            ]),
+        ),
+        condition(
+            MaterialCondition::Biome(vec![Biome::SoulSandValley]),
+            Sequence(vec![
+                condition(
+                    under_ceiling(),
+                    Sequence(vec![
+                        condition(
+                            noise_above(&state_selector, 0.0),
+                            Block(block_state!("soul_sand")),
+                        ),
+                        Block(block_state!("soul_soil")),
+                    ]),
+                ),
+                condition(
+                    under_floor(),
+                    Sequence(vec![
+                        gravel_patch(),
+                        condition(
+                            noise_above(&state_selector, 0.0),
+                            Block(block_state!("soul_sand")),
+                        ),
+                        Block(block_state!("soul_soil")),
+                    ]),
+                ),
+            ]),
+        ),
+        condition(
+            floor(),
+            Sequence(vec![
+                condition(
+                    not(y_above(YOffset::Absolute(32), 0, false)),
+                    condition(Hole, Block(block_state!("lava"))),
+                ),
+                forest_floor(
+                    &netherrack,
+                    &nether_wart,
+                    Biome::WarpedForest,
+                    Block(block_state!("warped_wart_block")),
+                    Block(block_state!("warped_nylium")),
+                ),
+                forest_floor(
+                    &netherrack,
+                    &nether_wart,
+                    Biome::CrimsonForest,
+                    Block(block_state!("nether_wart_block")),
+                    Block(block_state!("crimson_nylium")),
+                ),
+            ]),
+        ),
+        condition(
+            MaterialCondition::Biome(vec![Biome::NetherWastes]),
+            Sequence(vec![
+                condition(
+                    under_floor(),
+                    condition(
+                        noise_above(&soul_sand_layer, -0.012),
+                        Sequence(vec![
+                            condition(
+                                not(Hole),
+                                condition(
+                                    above_30(),
+                                    condition(not_above_35(), Block(block_state!("soul_sand"))),
+                                ),
+                            ),
+                            Block(block_state!("netherrack")),
+                        ]),
+                    ),
+                ),
+                condition(
+                    floor(),
+                    condition(
+                        y_above(YOffset::Absolute(31), 0, false),
+                        condition(
+                            not_above_35(),
+                            condition(
+                                noise_above(&gravel_layer, -0.012),
+                                Sequence(vec![
+                                    condition(
+                                        y_above(YOffset::Absolute(32), 0, false),
+                                        Block(block_state!("gravel")),
+                                    ),
+                                    condition(not(Hole), Block(block_state!("gravel"))),
+                                ]),
+                            ),
+                        ),
+                    ),
+                ),
+            ]),
+        ),
+        Block(block_state!("netherrack")),
+    ])
+}
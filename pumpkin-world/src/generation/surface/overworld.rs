@@ -0,0 +1,140 @@
+use pumpkin_core::random::RandomDeriver;
+use pumpkin_macros::block_state;
+
+use crate::{biome::Biome, generation::feature::YOffset};
+
+use super::rules::{
+    ceiling, condition, floor, not, stone_depth, under_floor, vertical_gradient, water, y_above,
+    MaterialCondition::{self, *},
+    MaterialRule::{self, *},
+    VerticalSurfaceType,
+};
+
+fn sandy() -> MaterialCondition {
+    MaterialCondition::Biome(vec![Biome::Desert, Biome::Beach])
+}
+
+/// Sand with sandstone below overhangs so it does not float
+fn sand() -> MaterialRule {
+    Sequence(vec![
+        condition(ceiling(), Block(block_state!("sandstone"))),
+        Block(block_state!("sand")),
+    ])
+}
+
+fn gravel() -> MaterialRule {
+    Sequence(vec![
+        condition(ceiling(), Block(block_state!("stone"))),
+        Block(block_state!("gravel")),
+    ])
+}
+
+fn badlands() -> MaterialRule {
+    condition(
+        MaterialCondition::Biome(vec![Biome::Badlands]),
+        Sequence(vec![
+            condition(
+                floor(),
+                Sequence(vec![
+                    condition(
+                        y_above(YOffset::Absolute(256), 0, false),
+                        Block(block_state!("orange_terracotta")),
+                    ),
+                    condition(y_above(YOffset::Absolute(74), 1, true), Bandlands),
+                    condition(
+                        water(-1, 0, false),
+                        Sequence(vec![
+                            condition(ceiling(), Block(block_state!("red_sandstone"))),
+                            Block(block_state!("red_sand")),
+                        ]),
+                    ),
+                    condition(not(Hole), Block(block_state!("orange_terracotta"))),
+                    condition(water(-6, -1, true), Block(block_state!("white_terracotta"))),
+                    gravel(),
+                ]),
+            ),
+            condition(
+                y_above(YOffset::Absolute(63), -1, true),
+                Sequence(vec![
+                    condition(
+                        y_above(YOffset::Absolute(63), 0, false),
+                        condition(
+                            not(y_above(YOffset::Absolute(74), 1, true)),
+                            Block(block_state!("orange_terracotta")),
+                        ),
+                    ),
+                    Bandlands,
+                ]),
+            ),
+            condition(
+                under_floor(),
+                condition(water(-6, -1, true), Block(block_state!("white_terracotta"))),
+            ),
+        ]),
+    )
+}
+
+fn surface() -> MaterialRule {
+    Sequence(vec![
+        badlands(),
+        // Dry land and the first block below a water surface
+        condition(
+            floor(),
+            condition(
+                water(-1, 0, false),
+                Sequence(vec![
+                    condition(sandy(), sand()),
+                    condition(water(0, 0, false), Block(block_state!("grass_block"))),
+                    Block(block_state!("dirt")),
+                ]),
+            ),
+        ),
+        condition(
+            water(-6, -1, true),
+            condition(
+                under_floor(),
+                Sequence(vec![
+                    condition(sandy(), sand()),
+                    Block(block_state!("dirt")),
+                ]),
+            ),
+        ),
+        condition(
+            sandy(),
+            condition(
+                stone_depth(0, true, 6, VerticalSurfaceType::Floor),
+                Block(block_state!("sandstone")),
+            ),
+        ),
+        // Deep water floors
+        condition(
+            floor(),
+            Sequence(vec![condition(sandy(), sand()), gravel()]),
+        ),
+    ])
+}
+
+/// A subset of the vanilla overworld surface rules covering the biomes we generate
+pub fn create_surface_rule(random_deriver: &RandomDeriver) -> MaterialRule {
+    Sequence(vec![
+        condition(
+            vertical_gradient(
+                random_deriver,
+                "minecraft:bedrock_floor",
+                YOffset::AboveBottom(0),
+                YOffset::AboveBottom(5),
+            ),
+            Block(block_state!("bedrock")),
+        ),
+        condition(AbovePreliminarySurface, surface()),
+        condition(
+            vertical_gradient(
+                random_deriver,
+                "minecraft:deepslate",
+                YOffset::Absolute(0),
+                YOffset::Absolute(8),
+            ),
+            Block(block_state!("deepslate")),
+        ),
+    ])
+}
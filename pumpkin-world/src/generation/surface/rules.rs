@@ -0,0 +1,250 @@
+use std::sync::Arc;
+
+use pumpkin_core::random::RandomDeriver;
+
+use crate::{
+    biome::Biome,
+    block::BlockState,
+    generation::{feature::YOffset, noise::perlin::DoublePerlinNoiseSampler},
+};
+
+use super::SurfaceContext;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum VerticalSurfaceType {
+    /// Counts the blocks above the current block, used for floors
+    Floor,
+    /// Counts the blocks below the current block, used for ceilings
+    Ceiling,
+}
+
+pub enum MaterialCondition {
+    Biome(Vec<Biome>),
+    NoiseThreshold {
+        noise: Arc<DoublePerlinNoiseSampler>,
+        min_threshold: f64,
+        max_threshold: f64,
+    },
+    VerticalGradient {
+        random_deriver: RandomDeriver,
+        true_at_and_below: YOffset,
+        false_at_and_above: YOffset,
+    },
+    YAbove {
+        anchor: YOffset,
+        surface_depth_multiplier: i32,
+        add_stone_depth: bool,
+    },
+    Water {
+        offset: i32,
+        surface_depth_multiplier: i32,
+        add_stone_depth: bool,
+    },
+    StoneDepth {
+        offset: i32,
+        add_surface_depth: bool,
+        secondary_depth_range: i32,
+        surface_type: VerticalSurfaceType,
+    },
+    Hole,
+    AbovePreliminarySurface,
+    Not(Box<MaterialCondition>),
+}
+
+impl MaterialCondition {
+    pub fn test(&self, context: &SurfaceContext) -> bool {
+        match self {
+            Self::Biome(biomes) => biomes.contains(&context.biome),
+            Self::NoiseThreshold {
+                noise,
+                min_threshold,
+                max_threshold,
+            } => {
+                let value = noise.sample(context.block_x as f64, 0.0, context.block_z as f64);
+                value >= *min_threshold && value <= *max_threshold
+            }
+            Self::VerticalGradient {
+                random_deriver,
+                true_at_and_below,
+                false_at_and_above,
+            } => {
+                let true_at_and_below = true_at_and_below.get_y();
+                let false_at_and_above = false_at_and_above.get_y();
+                let y = context.block_y;
+                if y <= true_at_and_below {
+                    return true;
+                }
+                if y >= false_at_and_above {
+                    return false;
+                }
+                let chance = (false_at_and_above - y) as f64
+                    / (false_at_and_above - true_at_and_below) as f64;
+                let mut random = random_deriver.split_pos(context.block_x, y, context.block_z);
+                (random.next_f32() as f64) < chance
+            }
+            Self::YAbove {
+                anchor,
+                surface_depth_multiplier,
+                add_stone_depth,
+            } => {
+                let stone_depth = if *add_stone_depth {
+                    context.stone_depth_above
+                } else {
+                    0
+                };
+                context.block_y + stone_depth
+                    >= anchor.get_y() + context.surface_depth * surface_depth_multiplier
+            }
+            Self::Water {
+                offset,
+                surface_depth_multiplier,
+                add_stone_depth,
+            } => {
+                if context.fluid_height == i32::MIN {
+                    return true;
+                }
+                let stone_depth = if *add_stone_depth {
+                    context.stone_depth_above
+                } else {
+                    0
+                };
+                context.block_y + stone_depth
+                    >= context.fluid_height
+                        + offset
+                        + context.surface_depth * surface_depth_multiplier
+            }
+            Self::StoneDepth {
+                offset,
+                add_surface_depth,
+                secondary_depth_range,
+                surface_type,
+            } => {
+                let stone_depth = match surface_type {
+                    VerticalSurfaceType::Floor => context.stone_depth_above,
+                    VerticalSurfaceType::Ceiling => context.stone_depth_below,
+                };
+                let surface_depth = if *add_surface_depth {
+                    context.surface_depth
+                } else {
+                    0
+                };
+                let secondary_depth = if *secondary_depth_range == 0 {
+                    0
+                } else {
+                    ((context.secondary_depth + 1.0) / 2.0 * *secondary_depth_range as f64) as i32
+                };
+                stone_depth <= 1 + offset + surface_depth + secondary_depth
+            }
+            Self::Hole => context.surface_depth <= 0,
+            Self::AbovePreliminarySurface => context.block_y >= context.min_surface_level,
+            Self::Not(condition) => !condition.test(context),
+        }
+    }
+}
+
+pub enum MaterialRule {
+    Block(BlockState),
+    Sequence(Vec<MaterialRule>),
+    Condition {
+        if_true: MaterialCondition,
+        then_run: Box<MaterialRule>,
+    },
+    /// Badlands terracotta strata
+    Bandlands,
+}
+
+impl MaterialRule {
+    /// Returns the block to place, `None` keeps the block as it is
+    pub fn try_apply(&self, context: &SurfaceContext) -> Option<BlockState> {
+        match self {
+            Self::Block(state) => Some(*state),
+            Self::Sequence(rules) => rules.iter().find_map(|rule| rule.try_apply(context)),
+            Self::Condition { if_true, then_run } => {
+                if if_true.test(context) {
+                    then_run.try_apply(context)
+                } else {
+                    None
+                }
+            }
+            Self::Bandlands => Some(context.terracotta_bands.get_block(
+                context.block_x,
+                context.block_y,
+                context.block_z,
+            )),
+        }
+    }
+}
+
+pub(super) fn condition(if_true: MaterialCondition, then_run: MaterialRule) -> MaterialRule {
+    MaterialRule::Condition {
+        if_true,
+        then_run: Box::new(then_run),
+    }
+}
+
+pub(super) fn not(condition: MaterialCondition) -> MaterialCondition {
+    MaterialCondition::Not(Box::new(condition))
+}
+
+pub(super) fn stone_depth(
+    offset: i32,
+    add_surface_depth: bool,
+    secondary_depth_range: i32,
+    surface_type: VerticalSurfaceType,
+) -> MaterialCondition {
+    MaterialCondition::StoneDepth {
+        offset,
+        add_surface_depth,
+        secondary_depth_range,
+        surface_type,
+    }
+}
+
+pub(super) fn floor() -> MaterialCondition {
+    stone_depth(0, false, 0, VerticalSurfaceType::Floor)
+}
+
+pub(super) fn ceiling() -> MaterialCondition {
+    stone_depth(0, false, 0, VerticalSurfaceType::Ceiling)
+}
+
+pub(super) fn under_floor() -> MaterialCondition {
+    stone_depth(0, true, 0, VerticalSurfaceType::Floor)
+}
+
+pub(super) fn water(
+    offset: i32,
+    surface_depth_multiplier: i32,
+    add_stone_depth: bool,
+) -> MaterialCondition {
+    MaterialCondition::Water {
+        offset,
+        surface_depth_multiplier,
+        add_stone_depth,
+    }
+}
+
+pub(super) fn y_above(
+    anchor: YOffset,
+    surface_depth_multiplier: i32,
+    add_stone_depth: bool,
+) -> MaterialCondition {
+    MaterialCondition::YAbove {
+        anchor,
+        surface_depth_multiplier,
+        add_stone_depth,
+    }
+}
+
+pub(super) fn vertical_gradient(
+    random_deriver: &RandomDeriver,
+    random_name: &str,
+    true_at_and_below: YOffset,
+    false_at_and_above: YOffset,
+) -> MaterialCondition {
+    MaterialCondition::VerticalGradient {
+        random_deriver: random_deriver.split_string(random_name).next_splitter(),
+        true_at_and_below,
+        false_at_and_above,
+    }
+}
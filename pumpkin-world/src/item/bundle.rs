@@ -0,0 +1,118 @@
+use crate::item::ItemStack;
+
+/// Bundles hold the weight of a single stack of 64 items, so every item weighs
+/// `64 / max stack size`. A bundle holds 64 items, 16 ender pearls or a single sword
+const BUNDLE_CAPACITY: u32 = 64;
+/// Bundles inside bundles weigh like a stack of 16 plus their contents
+const NESTED_BUNDLE_WEIGHT: u32 = 4;
+
+impl ItemStack {
+    /// How much of the bundle's capacity a single item of this stack uses up
+    fn bundle_item_weight(&self) -> u32 {
+        if self.is_bundle() {
+            return NESTED_BUNDLE_WEIGHT + self.bundle_weight();
+        }
+        // Items stacking beyond 64 still take up some space
+        (BUNDLE_CAPACITY / u32::from(self.max_stack_size().max(1))).max(1)
+    }
+
+    /// How much of the capacity the items in the bundle use up
+    pub fn bundle_weight(&self) -> u32 {
+        self.components
+            .bundle_contents
+            .iter()
+            .map(|item| u32::from(item.item_count) * item.bundle_item_weight())
+            .sum()
+    }
+
+    /// Whether the stack can be put into a bundle, shulker boxes have to stay outside
+    pub fn fits_in_bundle(&self) -> bool {
+        !self.is_shulker_box()
+    }
+
+    /// Moves as much of the stack into the bundle as fits, returns the amount of moved items.
+    /// Matching items are merged into one entry, which becomes the first one
+    pub fn bundle_insert(&mut self, stack: &mut ItemStack) -> u8 {
+        if !self.is_bundle() || !stack.fits_in_bundle() || stack.item_count == 0 {
+            return 0;
+        }
+        let free = BUNDLE_CAPACITY.saturating_sub(self.bundle_weight());
+        let fitting = free / stack.bundle_item_weight();
+        let moved =
+            u8::try_from(fitting).map_or(stack.item_count, |fitting| fitting.min(stack.item_count));
+        if moved == 0 {
+            return 0;
+        }
+        let contents = &mut self.components.bundle_contents;
+        let mut inserted = stack.with_count(moved);
+        if let Some(index) = contents
+            .iter()
+            .position(|item| item.is_stackable_with(stack))
+        {
+            inserted.item_count += contents.remove(index).item_count;
+        }
+        contents.insert(0, inserted);
+        self.components.bundle_selected_item = None;
+        stack.item_count -= moved;
+        moved
+    }
+
+    /// Takes the selected entry out of the bundle, the most recently inserted one if none is selected
+    pub fn bundle_remove_one(&mut self) -> Option<ItemStack> {
+        let contents = &mut self.components.bundle_contents;
+        if contents.is_empty() {
+            return None;
+        }
+        let index = self
+            .components
+            .bundle_selected_item
+            .take()
+            .filter(|index| *index < contents.len())
+            .unwrap_or(0);
+        Some(contents.remove(index))
+    }
+
+    /// Selects the entry the player hovers in the opened bundle, `None` deselects it
+    pub fn select_bundle_item(&mut self, index: Option<usize>) {
+        self.components.bundle_selected_item =
+            index.filter(|index| *index < self.components.bundle_contents.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::item::ItemStack;
+
+    #[test]
+    fn bundles_hold_a_stack_worth_of_items() {
+        let mut bundle = ItemStack::of("bundle", 1);
+        let mut pearls = ItemStack::of("ender_pearl", 20);
+        assert_eq!(bundle.bundle_insert(&mut pearls), 16);
+        assert_eq!(pearls.item_count, 4);
+        assert_eq!(bundle.bundle_weight(), 64);
+        assert_eq!(bundle.bundle_insert(&mut ItemStack::of("stone", 1)), 0);
+
+        let mut shulker_box = ItemStack::of("shulker_box", 1);
+        assert_eq!(
+            ItemStack::of("bundle", 1).bundle_insert(&mut shulker_box),
+            0
+        );
+    }
+
+    #[test]
+    fn bundles_give_out_the_last_inserted_item() {
+        let mut bundle = ItemStack::of("bundle", 1);
+        bundle.bundle_insert(&mut ItemStack::of("stone", 10));
+        bundle.bundle_insert(&mut ItemStack::of("dirt", 5));
+        bundle.bundle_insert(&mut ItemStack::of("stone", 3));
+        assert_eq!(
+            bundle.components.bundle_contents,
+            vec![ItemStack::of("stone", 13), ItemStack::of("dirt", 5)]
+        );
+
+        bundle.select_bundle_item(Some(1));
+        assert_eq!(bundle.bundle_remove_one(), Some(ItemStack::of("dirt", 5)));
+        assert_eq!(bundle.bundle_remove_one(), Some(ItemStack::of("stone", 13)));
+        assert_eq!(bundle.bundle_remove_one(), None);
+    }
+}
@@ -0,0 +1,667 @@
+use pumpkin_nbt::{
+    compound::NbtCompound,
+    snbt::{from_snbt, SnbtError},
+    tag::NbtTag,
+};
+use thiserror::Error;
+
+use super::ItemStack;
+
+/// The enchantment registry in network order, the index of an entry is its protocol id
+pub const ENCHANTMENTS: [&str; 42] = [
+    "aqua_affinity",
+    "bane_of_arthropods",
+    "binding_curse",
+    "blast_protection",
+    "breach",
+    "channeling",
+    "density",
+    "depth_strider",
+    "efficiency",
+    "feather_falling",
+    "fire_aspect",
+    "fire_protection",
+    "flame",
+    "fortune",
+    "frost_walker",
+    "impaling",
+    "infinity",
+    "knockback",
+    "looting",
+    "loyalty",
+    "luck_of_the_sea",
+    "lure",
+    "mending",
+    "multishot",
+    "piercing",
+    "power",
+    "projectile_protection",
+    "protection",
+    "punch",
+    "quick_charge",
+    "respiration",
+    "riptide",
+    "sharpness",
+    "silk_touch",
+    "smite",
+    "soul_speed",
+    "sweeping_edge",
+    "swift_sneak",
+    "thorns",
+    "unbreaking",
+    "vanishing_curse",
+    "wind_burst",
+];
+
+/// Returns the protocol id of the enchantment, the name is given without the `minecraft:` prefix
+pub fn get_enchantment_id(name: &str) -> Option<usize> {
+    ENCHANTMENTS
+        .iter()
+        .position(|enchantment| *enchantment == name)
+}
+
+/// The potion registry in network order, the index of an entry is its protocol id
+pub const POTIONS: [&str; 46] = [
+    "water",
+    "mundane",
+    "thick",
+    "awkward",
+    "night_vision",
+    "long_night_vision",
+    "invisibility",
+    "long_invisibility",
+    "leaping",
+    "long_leaping",
+    "strong_leaping",
+    "fire_resistance",
+    "long_fire_resistance",
+    "swiftness",
+    "long_swiftness",
+    "strong_swiftness",
+    "slowness",
+    "long_slowness",
+    "strong_slowness",
+    "turtle_master",
+    "long_turtle_master",
+    "strong_turtle_master",
+    "water_breathing",
+    "long_water_breathing",
+    "healing",
+    "strong_healing",
+    "harming",
+    "strong_harming",
+    "poison",
+    "long_poison",
+    "strong_poison",
+    "regeneration",
+    "long_regeneration",
+    "strong_regeneration",
+    "strength",
+    "long_strength",
+    "strong_strength",
+    "weakness",
+    "long_weakness",
+    "luck",
+    "slow_falling",
+    "long_slow_falling",
+    "wind_charged",
+    "weaving",
+    "oozing",
+    "infested",
+];
+
+/// Returns the protocol id of the potion, the name is given without the `minecraft:` prefix
+pub fn get_potion_id(name: &str) -> Option<usize> {
+    POTIONS.iter().position(|potion| *potion == name)
+}
+
+/// The banner pattern registry in network order, the index of an entry is its protocol id
+pub const BANNER_PATTERNS: [&str; 43] = [
+    "base",
+    "border",
+    "bricks",
+    "circle",
+    "creeper",
+    "cross",
+    "curly_border",
+    "diagonal_left",
+    "diagonal_right",
+    "diagonal_up_left",
+    "diagonal_up_right",
+    "flow",
+    "flower",
+    "globe",
+    "gradient",
+    "gradient_up",
+    "guster",
+    "half_horizontal",
+    "half_horizontal_bottom",
+    "half_vertical",
+    "half_vertical_right",
+    "mojang",
+    "piglin",
+    "rhombus",
+    "skull",
+    "small_stripes",
+    "square_bottom_left",
+    "square_bottom_right",
+    "square_top_left",
+    "square_top_right",
+    "straight_cross",
+    "stripe_bottom",
+    "stripe_center",
+    "stripe_downleft",
+    "stripe_downright",
+    "stripe_left",
+    "stripe_middle",
+    "stripe_right",
+    "stripe_top",
+    "triangle_bottom",
+    "triangle_top",
+    "triangles_bottom",
+    "triangles_top",
+];
+
+/// Returns the protocol id of the banner pattern, the name is given without the `minecraft:` prefix
+pub fn get_banner_pattern_id(name: &str) -> Option<usize> {
+    BANNER_PATTERNS.iter().position(|pattern| *pattern == name)
+}
+
+/// The dye colors in network order
+pub const DYE_COLORS: [&str; 16] = [
+    "white",
+    "orange",
+    "magenta",
+    "light_blue",
+    "yellow",
+    "lime",
+    "pink",
+    "gray",
+    "light_gray",
+    "cyan",
+    "purple",
+    "blue",
+    "brown",
+    "green",
+    "red",
+    "black",
+];
+
+pub fn get_dye_color_id(name: &str) -> Option<usize> {
+    DYE_COLORS.iter().position(|color| *color == name)
+}
+
+/// Vanilla's container component holds at most this many slots
+pub const MAX_CONTAINER_SLOTS: usize = 256;
+
+/// What happens to a map the next time it is in a player's inventory, set by cartography tables
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MapPostProcessing {
+    Lock,
+    Scale,
+}
+
+/// The components commands can set, without the `minecraft:` prefix
+pub const COMPONENT_NAMES: [&str; 12] = [
+    "custom_data",
+    "damage",
+    "unbreakable",
+    "custom_name",
+    "enchantments",
+    "repair_cost",
+    "stored_enchantments",
+    "potion_contents",
+    "map_id",
+    "banner_patterns",
+    "bundle_contents",
+    "container",
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ComponentParseError {
+    #[error("Unknown item component '{0}'")]
+    UnknownComponent(String),
+    #[error("Expected '=' after item component '{0}'")]
+    ExpectedValue(String),
+    #[error("Item component '{0}' was repeated, but only one value can be specified")]
+    RepeatedComponent(String),
+    #[error("Malformed '{0}' component: {1}")]
+    InvalidNbt(String, SnbtError),
+    #[error("Malformed '{0}' component")]
+    Malformed(String),
+}
+
+/// The data components which make a stack differ from the default item,
+/// stacks can only be merged if all of their components are equal
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemComponents {
+    /// Arbitrary data, e.g. set by commands or plugins
+    pub custom_data: Option<NbtCompound>,
+    /// How much durability was used up
+    pub damage: Option<i32>,
+    pub unbreakable: bool,
+    /// Plain text name given in an anvil
+    pub custom_name: Option<String>,
+    /// Enchantment name without the `minecraft:` prefix and level
+    pub enchantments: Vec<(String, i32)>,
+    /// Extra experience levels anvils charge for this item
+    pub repair_cost: Option<i32>,
+    /// Enchantments held by enchanted books, they are not active on the item itself
+    pub stored_enchantments: Vec<(String, i32)>,
+    /// Potion name without the `minecraft:` prefix, held by potions and tipped arrows
+    pub potion: Option<String>,
+    /// The id of the map's saved data
+    pub map_id: Option<i32>,
+    /// Only sent to the client, vanilla doesn't save it
+    pub map_post_processing: Option<MapPostProcessing>,
+    /// Pattern name without the `minecraft:` prefix and dye color of each layer, from bottom to top
+    pub banner_patterns: Vec<(String, String)>,
+    /// The items in a bundle, the most recently inserted one comes first
+    pub bundle_contents: Vec<ItemStack>,
+    /// The bundle item the player hovers, only tracked while the bundle is open and never saved
+    pub bundle_selected_item: Option<usize>,
+    /// The items of a shulker box item by their slot, the empty slots after the last item are
+    /// left out
+    pub container: Vec<Option<ItemStack>>,
+}
+
+impl ItemComponents {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Writes the components in the format used by vanilla world and player data
+    pub fn write_nbt(&self, compound: &mut NbtCompound) {
+        if let Some(custom_data) = &self.custom_data {
+            compound.put(
+                "minecraft:custom_data".to_string(),
+                NbtTag::Compound(custom_data.clone()),
+            );
+        }
+        if let Some(damage) = self.damage {
+            compound.put("minecraft:damage".to_string(), NbtTag::Int(damage));
+        }
+        if self.unbreakable {
+            compound.put(
+                "minecraft:unbreakable".to_string(),
+                NbtTag::Compound(NbtCompound::new()),
+            );
+        }
+        if let Some(custom_name) = &self.custom_name {
+            // Text components are stored as JSON
+            let json = serde_json::to_string(custom_name).unwrap_or_default();
+            compound.put("minecraft:custom_name".to_string(), NbtTag::String(json));
+        }
+        if !self.enchantments.is_empty() {
+            compound.put(
+                "minecraft:enchantments".to_string(),
+                enchantments_to_nbt(&self.enchantments),
+            );
+        }
+        if let Some(repair_cost) = self.repair_cost {
+            compound.put(
+                "minecraft:repair_cost".to_string(),
+                NbtTag::Int(repair_cost),
+            );
+        }
+        if !self.stored_enchantments.is_empty() {
+            compound.put(
+                "minecraft:stored_enchantments".to_string(),
+                enchantments_to_nbt(&self.stored_enchantments),
+            );
+        }
+        if let Some(potion) = &self.potion {
+            let mut potion_contents = NbtCompound::new();
+            potion_contents.put(
+                "potion".to_string(),
+                NbtTag::String(format!("minecraft:{potion}")),
+            );
+            compound.put(
+                "minecraft:potion_contents".to_string(),
+                NbtTag::Compound(potion_contents),
+            );
+        }
+        if let Some(map_id) = self.map_id {
+            compound.put("minecraft:map_id".to_string(), NbtTag::Int(map_id));
+        }
+        if !self.banner_patterns.is_empty() {
+            let layers = self
+                .banner_patterns
+                .iter()
+                .map(|(pattern, color)| {
+                    let mut layer = NbtCompound::new();
+                    layer.put(
+                        "pattern".to_string(),
+                        NbtTag::String(format!("minecraft:{pattern}")),
+                    );
+                    layer.put("color".to_string(), NbtTag::String(color.clone()));
+                    NbtTag::Compound(layer)
+                })
+                .collect();
+            compound.put(
+                "minecraft:banner_patterns".to_string(),
+                NbtTag::List(layers),
+            );
+        }
+        if !self.bundle_contents.is_empty() {
+            let items = self
+                .bundle_contents
+                .iter()
+                .map(|item| {
+                    let mut compound = NbtCompound::new();
+                    item.write_item_nbt(&mut compound);
+                    NbtTag::Compound(compound)
+                })
+                .collect();
+            compound.put("minecraft:bundle_contents".to_string(), NbtTag::List(items));
+        }
+        if !self.container.is_empty() {
+            let slots = self
+                .container
+                .iter()
+                .enumerate()
+                .filter_map(|(slot, item)| {
+                    let mut item_compound = NbtCompound::new();
+                    item.as_ref()?.write_item_nbt(&mut item_compound);
+                    let mut compound = NbtCompound::new();
+                    compound.put("slot".to_string(), NbtTag::Int(slot as i32));
+                    compound.put("item".to_string(), NbtTag::Compound(item_compound));
+                    Some(NbtTag::Compound(compound))
+                })
+                .collect();
+            compound.put("minecraft:container".to_string(), NbtTag::List(slots));
+        }
+    }
+
+    /// Reads the components from vanilla world and player data, unknown components are ignored
+    pub fn read_nbt(compound: &NbtCompound) -> Self {
+        Self {
+            custom_data: compound.get_compound("minecraft:custom_data").cloned(),
+            damage: compound.get_int("minecraft:damage"),
+            unbreakable: compound.get("minecraft:unbreakable").is_some(),
+            custom_name: compound
+                .get_string("minecraft:custom_name")
+                .and_then(|json| text_from_json(json)),
+            enchantments: compound
+                .get_compound("minecraft:enchantments")
+                .map(enchantments_from_nbt)
+                .unwrap_or_default(),
+            repair_cost: compound.get_int("minecraft:repair_cost"),
+            stored_enchantments: compound
+                .get_compound("minecraft:stored_enchantments")
+                .map(enchantments_from_nbt)
+                .unwrap_or_default(),
+            potion: compound
+                .get("minecraft:potion_contents")
+                .and_then(potion_from_nbt),
+            map_id: compound.get_int("minecraft:map_id"),
+            map_post_processing: None,
+            banner_patterns: compound
+                .get_list("minecraft:banner_patterns")
+                .map(|layers| layers.iter().filter_map(banner_layer_from_nbt).collect())
+                .unwrap_or_default(),
+            bundle_contents: compound
+                .get_list("minecraft:bundle_contents")
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(NbtTag::extract_compound)
+                        .filter_map(ItemStack::read_item_nbt)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            bundle_selected_item: None,
+            container: compound
+                .get_list("minecraft:container")
+                .map(|slots| container_from_nbt(slots))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn container_from_nbt(slots: &[NbtTag]) -> Vec<Option<ItemStack>> {
+    let mut container = Vec::new();
+    for compound in slots.iter().filter_map(NbtTag::extract_compound) {
+        let Some(slot) = compound
+            .get_int("slot")
+            .and_then(|slot| usize::try_from(slot).ok())
+            .filter(|slot| *slot < MAX_CONTAINER_SLOTS)
+        else {
+            continue;
+        };
+        let Some(item) = compound
+            .get_compound("item")
+            .and_then(ItemStack::read_item_nbt)
+        else {
+            continue;
+        };
+        if container.len() <= slot {
+            container.resize(slot + 1, None);
+        }
+        container[slot] = Some(item);
+    }
+    container
+}
+
+fn enchantments_to_nbt(enchantments: &[(String, i32)]) -> NbtTag {
+    let levels = enchantments
+        .iter()
+        .map(|(name, level)| (format!("minecraft:{name}"), NbtTag::Int(*level)))
+        .collect();
+    let mut compound = NbtCompound::new();
+    compound.put("levels".to_string(), NbtTag::Compound(levels));
+    NbtTag::Compound(compound)
+}
+
+fn enchantments_from_nbt(compound: &NbtCompound) -> Vec<(String, i32)> {
+    // The levels can also be given directly, without the wrapping compound
+    let levels = compound.get_compound("levels").unwrap_or(compound);
+    levels
+        .child_tags
+        .iter()
+        .filter_map(|(name, level)| {
+            let NbtTag::Int(level) = level else {
+                return None;
+            };
+            let name = name.strip_prefix("minecraft:").unwrap_or(name);
+            Some((name.to_string(), *level))
+        })
+        .collect()
+}
+
+fn potion_from_nbt(tag: &NbtTag) -> Option<String> {
+    // Potion contents with only a potion can also be given as the plain potion name
+    let name = match tag {
+        NbtTag::String(name) => name,
+        NbtTag::Compound(compound) => compound.get_string("potion")?,
+        _ => return None,
+    };
+    Some(name.strip_prefix("minecraft:").unwrap_or(name).to_string())
+}
+
+fn banner_layer_from_nbt(tag: &NbtTag) -> Option<(String, String)> {
+    let NbtTag::Compound(layer) = tag else {
+        return None;
+    };
+    let pattern = layer.get_string("pattern")?;
+    let pattern = pattern.strip_prefix("minecraft:").unwrap_or(pattern);
+    Some((pattern.to_string(), layer.get_string("color")?.clone()))
+}
+
+/// Extracts the plain text of a JSON text component, only literal text is supported
+fn text_from_json(json: &str) -> Option<String> {
+    match serde_json::from_str(json).ok()? {
+        serde_json::Value::String(text) => Some(text),
+        serde_json::Value::Object(object) => object.get("text")?.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Splits the components of the command syntax at the commas between them, commas inside of
+/// values are skipped
+fn split_components(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, char) in input.char_indices() {
+        if let Some(open) = quote {
+            if escaped {
+                escaped = false;
+            } else if char == '\\' {
+                escaped = true;
+            } else if char == open {
+                quote = None;
+            }
+            continue;
+        }
+        match char {
+            '"' | '\'' => quote = Some(char),
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// The component name with the `minecraft:` prefix, if commands can set the component
+fn component_key(name: &str) -> Result<String, ComponentParseError> {
+    let name = name.trim();
+    let short = name.strip_prefix("minecraft:").unwrap_or(name);
+    if COMPONENT_NAMES.contains(&short) {
+        Ok(format!("minecraft:{short}"))
+    } else {
+        Err(ComponentParseError::UnknownComponent(name.to_string()))
+    }
+}
+
+/// The components of a `name=value` list with the values as SNBT, like
+/// `damage=5,unbreakable={}` in `diamond_sword[damage=5,unbreakable={}]`. Like vanilla's
+/// `ItemParser`, values which don't fit the component are rejected
+pub fn parse_components(input: &str) -> Result<ItemComponents, ComponentParseError> {
+    let mut compound = NbtCompound::new();
+    for part in split_components(input) {
+        let Some((name, value)) = part.split_once('=') else {
+            return Err(ComponentParseError::ExpectedValue(part.to_string()));
+        };
+        let key = component_key(name)?;
+        if compound.get(&key).is_some() {
+            return Err(ComponentParseError::RepeatedComponent(key));
+        }
+        let value = from_snbt(value.trim())
+            .map_err(|error| ComponentParseError::InvalidNbt(key.clone(), error))?;
+        compound.put(key, value);
+    }
+    let components = ItemComponents::read_nbt(&compound);
+
+    // Components which couldn't be read are missing when written again
+    let mut written = NbtCompound::new();
+    components.write_nbt(&mut written);
+    if let Some((key, _)) = compound
+        .child_tags
+        .iter()
+        .find(|(key, _)| written.get(key).is_none())
+    {
+        return Err(ComponentParseError::Malformed(key.clone()));
+    }
+    Ok(components)
+}
+
+/// Tests on the components of a stack, like `damage=0,!enchantments` in
+/// `*[damage=0,!enchantments]`. A component has to be missing or equal the value exactly, like
+/// vanilla's `DataComponentPredicate`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComponentPredicate {
+    /// The components by their key and the value they must have, none if they must be missing
+    tests: Vec<(String, Option<NbtTag>)>,
+}
+
+impl ComponentPredicate {
+    pub fn parse(input: &str) -> Result<Self, ComponentParseError> {
+        let mut tests = Vec::new();
+        for part in split_components(input) {
+            if let Some(name) = part.strip_prefix('!') {
+                tests.push((component_key(name)?, None));
+                continue;
+            }
+            // The value is written like the stack would write it, so both compare equal
+            let components = parse_components(part)?;
+            let mut written = NbtCompound::new();
+            components.write_nbt(&mut written);
+            tests.extend(
+                written
+                    .child_tags
+                    .into_iter()
+                    .map(|(key, value)| (key, Some(value))),
+            );
+        }
+        Ok(Self { tests })
+    }
+
+    pub fn matches(&self, components: &ItemComponents) -> bool {
+        if self.tests.is_empty() {
+            return true;
+        }
+        let mut written = NbtCompound::new();
+        components.write_nbt(&mut written);
+        self.tests
+            .iter()
+            .all(|(key, value)| written.get(key) == value.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_components, ComponentParseError, ComponentPredicate, ItemComponents};
+
+    #[test]
+    fn parse() {
+        let components = parse_components(
+            "damage=5,minecraft:unbreakable={},enchantments={levels:{sharpness:2,unbreaking:1}}",
+        )
+        .unwrap();
+        assert_eq!(components.damage, Some(5));
+        assert!(components.unbreakable);
+        assert_eq!(components.enchantments.len(), 2);
+        assert!(components
+            .enchantments
+            .contains(&("sharpness".to_string(), 2)));
+        assert_eq!(
+            parse_components("colour=1"),
+            Err(ComponentParseError::UnknownComponent("colour".to_string()))
+        );
+        assert_eq!(
+            parse_components("damage=\"a,b\""),
+            Err(ComponentParseError::Malformed(
+                "minecraft:damage".to_string()
+            ))
+        );
+        assert!(parse_components("damage=1,damage=2").is_err());
+    }
+
+    #[test]
+    fn predicate() {
+        let damaged = ItemComponents {
+            damage: Some(3),
+            ..Default::default()
+        };
+        let predicate = ComponentPredicate::parse("damage=3,!unbreakable").unwrap();
+        assert!(predicate.matches(&damaged));
+        assert!(!predicate.matches(&ItemComponents::default()));
+        assert!(ComponentPredicate::parse("!damage")
+            .unwrap()
+            .matches(&ItemComponents::default()));
+        assert!(ComponentPredicate::default().matches(&damaged));
+    }
+}
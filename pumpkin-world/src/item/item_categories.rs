@@ -1,3 +1,4 @@
+use crate::item::item_registry::{get_item_by_id, get_item_name_by_id, EquipmentSlot};
 use crate::item::ItemStack;
 
 impl ItemStack {
@@ -13,57 +14,65 @@ impl ItemStack {
         .contains(&self.item_id)
     }
 
+    /// The slot the item is worn in, `None` if it can't be equipped
+    pub fn equipment_slot(&self) -> Option<EquipmentSlot> {
+        get_item_by_id(self.item_id)?
+            .components
+            .equippable
+            .as_ref()
+            .map(|equippable| equippable.slot)
+    }
+
+    /// Helmets, turtle shells, carved pumpkins and mob heads
     pub fn is_helmet(&self) -> bool {
-        [
-            // Leather
-            856, // Netherite
-            876, // Turtle helmet
-            794, // Chainmail
-            860, // Diamond
-            868, // Gold
-            872, // Iron
-            864,
-        ]
-        .contains(&self.item_id)
+        self.equipment_slot() == Some(EquipmentSlot::Head)
     }
 
+    /// Chestplates and elytras
     pub fn is_chestplate(&self) -> bool {
-        [
-            // Leather
-            857, // Netherite
-            877, // Chainmail
-            861, // Diamond
-            869, // Gold
-            873, // Iron
-            865, // Elytra
-            773,
-        ]
-        .contains(&self.item_id)
+        self.equipment_slot() == Some(EquipmentSlot::Chest)
     }
 
     pub fn is_leggings(&self) -> bool {
-        [
-            // Leather
-            858, // Netherite
-            878, // Chainmail
-            862, // Diamond
-            870, // Gold
-            874, // Iron
-            866,
-        ]
-        .contains(&self.item_id)
+        self.equipment_slot() == Some(EquipmentSlot::Legs)
     }
 
     pub fn is_boots(&self) -> bool {
-        [
-            // Leather
-            859, // Netherite
-            879, // Chainmail
-            863, // Diamond
-            871, // Gold
-            875, // Iron
-            867,
-        ]
-        .contains(&self.item_id)
+        self.equipment_slot() == Some(EquipmentSlot::Feet)
+    }
+
+    /// Armor with Curse of Binding can't be taken off once it is worn
+    pub fn has_binding_curse(&self) -> bool {
+        self.components
+            .enchantments
+            .iter()
+            .any(|(enchantment, _)| enchantment == "binding_curse")
+    }
+
+    /// The level of the enchantment on the item, 0 if the item isn't enchanted with it
+    pub fn enchantment_level(&self, enchantment: &str) -> i32 {
+        self.components
+            .enchantments
+            .iter()
+            .find(|(name, _)| name == enchantment)
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Bundles of every color
+    pub fn is_bundle(&self) -> bool {
+        get_item_by_id(self.item_id).is_some_and(|item| item.components.bundle_contents.is_some())
+    }
+
+    pub fn is_shulker_box(&self) -> bool {
+        // Undyed shulker box followed by the 16 dyed variants
+        (545..=561).contains(&self.item_id)
+    }
+
+    /// Netherite items and ancient debris, dropped they survive fire and lava
+    pub fn is_fire_resistant(&self) -> bool {
+        get_item_name_by_id(self.item_id).is_some_and(|name| {
+            name == "ancient_debris"
+                || (name.starts_with("netherite_") && !name.ends_with("_smithing_template"))
+        })
     }
 }
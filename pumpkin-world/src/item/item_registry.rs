@@ -21,6 +21,13 @@ pub fn get_item_by_id<'a>(id: u16) -> Option<&'a Item> {
     None
 }
 
+pub fn get_item_name_by_id(id: u16) -> Option<&'static str> {
+    ITEMS
+        .iter()
+        .find(|(_, item)| item.id == id)
+        .map(|(name, _)| name.as_str())
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct Item {
     pub id: u16,
@@ -39,6 +46,22 @@ pub struct ItemComponents {
     pub max_damage: Option<u16>,
     #[serde(rename = "minecraft:attribute_modifiers")]
     pub attribute_modifiers: Option<AttributeModifiers>,
+    #[serde(rename = "minecraft:repairable")]
+    pub repairable: Option<Repairable>,
+    #[serde(rename = "minecraft:equippable")]
+    pub equippable: Option<Equippable>,
+    #[serde(rename = "minecraft:food")]
+    pub food: Option<Food>,
+    #[serde(rename = "minecraft:consumable")]
+    pub consumable: Option<Consumable>,
+    /// What is left in the hand after using up the item, like the bowl of a stew
+    #[serde(rename = "minecraft:use_remainder")]
+    pub use_remainder: Option<UseRemainder>,
+    /// Only present on bundles, their default contents are always empty
+    #[serde(rename = "minecraft:bundle_contents")]
+    pub bundle_contents: Option<serde::de::IgnoredAny>,
+    #[serde(rename = "minecraft:tool")]
+    pub tool: Option<Tool>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -46,6 +69,228 @@ pub struct JukeboxPlayable {
     pub song: String,
 }
 
+/// How filling an item is to eat
+#[derive(Deserialize, Clone, Debug)]
+pub struct Food {
+    pub nutrition: i32,
+    pub saturation: f32,
+    /// Players eat it even when they are not hungry, like golden apples
+    #[serde(default)]
+    pub can_always_eat: bool,
+}
+
+/// How an item is eaten or drunk
+#[derive(Deserialize, Clone, Debug)]
+pub struct Consumable {
+    #[serde(default = "default_consume_seconds")]
+    pub consume_seconds: f32,
+    #[serde(default)]
+    pub animation: UseAnimation,
+    #[serde(default)]
+    pub on_consume_effects: Vec<ConsumeEffect>,
+}
+
+const fn default_consume_seconds() -> f32 {
+    1.6
+}
+
+impl Consumable {
+    /// Ticks the item has to be used before it is consumed
+    pub fn consume_ticks(&self) -> u32 {
+        (self.consume_seconds * 20.0) as u32
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UseAnimation {
+    #[default]
+    Eat,
+    Drink,
+    #[serde(other)]
+    Other,
+}
+
+/// What consuming an item does besides feeding
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum ConsumeEffect {
+    /// Gives the effects with the probability
+    #[serde(rename = "minecraft:apply_effects")]
+    ApplyEffects {
+        effects: Vec<ConsumeEffectInstance>,
+        #[serde(default = "default_probability")]
+        probability: f32,
+    },
+    #[serde(rename = "minecraft:remove_effects")]
+    RemoveEffects { effects: EffectIds },
+    #[serde(rename = "minecraft:clear_all_effects")]
+    ClearAllEffects,
+    /// Teleporting randomly and playing sounds
+    #[serde(other)]
+    Other,
+}
+
+const fn default_probability() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ConsumeEffectInstance {
+    pub id: String,
+    #[serde(default)]
+    pub amplifier: u8,
+    pub duration: i32,
+}
+
+/// A single effect or a list of them
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum EffectIds {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl EffectIds {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        match self {
+            Self::One(id) => std::slice::from_ref(id).iter(),
+            Self::Many(ids) => ids.iter(),
+        }
+        .map(String::as_str)
+    }
+}
+
+/// How fast an item mines blocks and which blocks it harvests, like vanilla's `Tool`
+#[derive(Deserialize, Clone, Debug)]
+pub struct Tool {
+    pub rules: Vec<ToolRule>,
+    #[serde(default = "default_mining_speed")]
+    pub default_mining_speed: f32,
+    /// Durability used up per block mined, swords and maces use 2
+    #[serde(default = "default_damage_per_block")]
+    pub damage_per_block: i32,
+}
+
+const fn default_mining_speed() -> f32 {
+    1.0
+}
+
+const fn default_damage_per_block() -> i32 {
+    1
+}
+
+/// The mining speed and whether drops are harvested for some blocks, the first matching rule
+/// which sets a value wins
+#[derive(Deserialize, Clone, Debug)]
+pub struct ToolRule {
+    /// Block ids and block tags starting with `#`
+    pub blocks: BlockIds,
+    pub speed: Option<f32>,
+    pub correct_for_drops: Option<bool>,
+}
+
+/// A single block or block tag, or a list of blocks
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum BlockIds {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl BlockIds {
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        match self {
+            Self::One(id) => std::slice::from_ref(id).iter(),
+            Self::Many(ids) => ids.iter(),
+        }
+        .map(String::as_str)
+    }
+}
+
+impl Tool {
+    /// How fast the tool mines the block, `in_tag` tells whether the block is in a block tag
+    pub fn mining_speed(&self, block: &str, in_tag: impl Fn(&str) -> bool) -> f32 {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(block, &in_tag))
+            .find_map(|rule| rule.speed)
+            .unwrap_or(self.default_mining_speed)
+    }
+
+    /// Whether blocks which need the right tool drop anything mined with this one
+    pub fn is_correct_for_drops(&self, block: &str, in_tag: impl Fn(&str) -> bool) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(block, &in_tag))
+            .find_map(|rule| rule.correct_for_drops)
+            .unwrap_or(false)
+    }
+}
+
+impl ToolRule {
+    fn matches(&self, block: &str, in_tag: impl Fn(&str) -> bool) -> bool {
+        self.blocks.iter().any(|id| match id.strip_prefix('#') {
+            Some(tag) => in_tag(tag),
+            None => id.strip_prefix("minecraft:").unwrap_or(id) == block,
+        })
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct UseRemainder {
+    pub id: String,
+    pub count: u8,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Repairable {
+    /// The item or item tag which repairs this item in an anvil
+    pub items: String,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Equippable {
+    /// The slot the item is worn in
+    pub slot: EquipmentSlot,
+    /// Whether using the item swaps it with the equipped one, carved pumpkins and heads can't be equipped that way
+    #[serde(default = "default_swappable")]
+    pub swappable: bool,
+}
+
+const fn default_swappable() -> bool {
+    true
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EquipmentSlot {
+    MainHand,
+    OffHand,
+    Head,
+    Chest,
+    Legs,
+    Feet,
+    /// The body of animals, e.g. horse armor
+    Body,
+}
+
+impl EquipmentSlot {
+    /// The entity status which shows players that the item in the slot broke, like vanilla's
+    /// `LivingEntity.entityEventForEquipmentBreak`
+    pub const fn break_status(self) -> i8 {
+        match self {
+            Self::MainHand => 47,
+            Self::OffHand => 48,
+            Self::Head => 49,
+            Self::Chest => 50,
+            Self::Legs => 51,
+            Self::Feet => 52,
+            Self::Body => 65,
+        }
+    }
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct AttributeModifiers {
     pub modifiers: Vec<Modifier>,
@@ -69,3 +314,100 @@ pub enum Operation {
     AddMultipliedBase,
     AddMultipliedTotal,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_item, ConsumeEffect, UseAnimation};
+
+    #[test]
+    fn tools() {
+        let pickaxe = get_item("diamond_pickaxe").unwrap();
+        let tool = pickaxe.components.tool.as_ref().unwrap();
+        assert_eq!(tool.damage_per_block, 1);
+        let mineable = |tag: &str| tag == "minecraft:mineable/pickaxe";
+        assert!((tool.mining_speed("stone", mineable) - 8.0).abs() < f32::EPSILON);
+        assert!(tool.is_correct_for_drops("stone", mineable));
+        assert!((tool.mining_speed("dirt", |_| false) - 1.0).abs() < f32::EPSILON);
+        assert!(!tool.is_correct_for_drops("dirt", |_| false));
+        // The incorrect tag comes first, so it wins over the mineable one
+        assert!(!tool.is_correct_for_drops("obsidian", |_| true));
+
+        let sword = get_item("diamond_sword").unwrap();
+        let tool = sword.components.tool.as_ref().unwrap();
+        assert_eq!(tool.damage_per_block, 2);
+        assert!(tool.is_correct_for_drops("cobweb", |_| false));
+        assert!((tool.mining_speed("cobweb", |_| false) - 15.0).abs() < f32::EPSILON);
+
+        assert!(get_item("stick").unwrap().components.tool.is_none());
+    }
+
+    #[test]
+    fn food_and_consumables() {
+        let bread = get_item("bread").unwrap();
+        let food = bread.components.food.as_ref().unwrap();
+        assert_eq!(food.nutrition, 5);
+        assert!(!food.can_always_eat);
+        let consumable = bread.components.consumable.as_ref().unwrap();
+        assert_eq!(consumable.consume_ticks(), 32);
+        assert_eq!(consumable.animation, UseAnimation::Eat);
+        assert!(bread.components.use_remainder.is_none());
+
+        let golden_apple = get_item("golden_apple").unwrap();
+        assert!(
+            golden_apple
+                .components
+                .food
+                .as_ref()
+                .unwrap()
+                .can_always_eat
+        );
+        let effects = &golden_apple
+            .components
+            .consumable
+            .as_ref()
+            .unwrap()
+            .on_consume_effects;
+        assert!(matches!(
+            &effects[..],
+            [ConsumeEffect::ApplyEffects { effects, probability }]
+                if effects.len() == 2 && (*probability - 1.0).abs() < f32::EPSILON
+        ));
+
+        let honey = get_item("honey_bottle").unwrap();
+        let consumable = honey.components.consumable.as_ref().unwrap();
+        assert_eq!(consumable.consume_ticks(), 40);
+        assert_eq!(consumable.animation, UseAnimation::Drink);
+        assert!(matches!(
+            &consumable.on_consume_effects[..],
+            [ConsumeEffect::RemoveEffects { effects }]
+                if effects.iter().eq(["minecraft:poison"])
+        ));
+        assert_eq!(
+            honey.components.use_remainder.as_ref().unwrap().id,
+            "minecraft:glass_bottle"
+        );
+
+        let milk = get_item("milk_bucket").unwrap();
+        assert!(milk.components.food.is_none());
+        assert!(matches!(
+            &milk
+                .components
+                .consumable
+                .as_ref()
+                .unwrap()
+                .on_consume_effects[..],
+            [ConsumeEffect::ClearAllEffects]
+        ));
+
+        let chorus_fruit = get_item("chorus_fruit").unwrap();
+        assert!(matches!(
+            &chorus_fruit
+                .components
+                .consumable
+                .as_ref()
+                .unwrap()
+                .on_consume_effects[..],
+            [ConsumeEffect::Other]
+        ));
+    }
+}
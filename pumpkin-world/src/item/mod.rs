@@ -1,3 +1,10 @@
+use components::ItemComponents;
+use item_registry::{get_item, get_item_by_id, get_item_name_by_id};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use rand::Rng;
+
+mod bundle;
+pub mod components;
 mod item_categories;
 pub mod item_registry;
 pub use item_registry::ITEMS;
@@ -11,17 +18,20 @@ pub enum Rarity {
     Epic,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct ItemStack {
     pub item_count: u8,
     // This ID is the numerical protocol ID, not the usual minecraft::block ID.
     pub item_id: u16,
-    // TODO: Add Item Components
+    pub components: ItemComponents,
 }
 
+/// Stacks are only equal if they have the same amount, see `is_stackable_with` for merging
 impl PartialEq for ItemStack {
     fn eq(&self, other: &Self) -> bool {
         self.item_id == other.item_id
+            && self.item_count == other.item_count
+            && self.components == other.components
     }
 }
 
@@ -30,6 +40,145 @@ impl ItemStack {
         Self {
             item_count,
             item_id,
+            components: ItemComponents::default(),
+        }
+    }
+
+    /// A stack of the named item, the name may leave out the `minecraft:` namespace
+    #[cfg(any(test, feature = "test-fixtures"))]
+    pub fn of(name: &str, item_count: u8) -> Self {
+        let item = get_item(name).unwrap_or_else(|| panic!("unknown item {name}"));
+        Self::new(item_count, item.id)
+    }
+
+    /// Whether both stacks can be merged into one, which requires the same item and components
+    pub fn is_stackable_with(&self, other: &Self) -> bool {
+        self.item_id == other.item_id && self.components == other.components
+    }
+
+    /// A copy of this stack with a different amount of items
+    pub fn with_count(&self, item_count: u8) -> Self {
+        Self {
+            item_count,
+            ..self.clone()
+        }
+    }
+
+    /// The maximum amount of items which fit into a single stack of this item
+    pub fn max_stack_size(&self) -> u8 {
+        get_item_by_id(self.item_id).map_or(64, |item| item.components.max_stack_size)
+    }
+
+    /// Uses up durability of the item, each point has a chance to be spared by Unbreaking like
+    /// vanilla's `ItemStack.hurtAndBreak`. Returns true once the item broke
+    pub fn damage_item(&mut self, amount: i32) -> bool {
+        let Some(max_damage) =
+            get_item_by_id(self.item_id).and_then(|item| item.components.max_damage)
+        else {
+            return false;
+        };
+        if self.components.unbreakable {
+            return false;
+        }
+        let unbreaking = self.enchantment_level("unbreaking");
+        let amount = if unbreaking > 0 {
+            let mut rng = rand::thread_rng();
+            (0..amount)
+                .filter(|_| rng.gen_range(0..=unbreaking) == 0)
+                .count() as i32
+        } else {
+            amount
+        };
+        let damage = self.components.damage.unwrap_or(0) + amount;
+        self.components.damage = Some(damage);
+        damage >= i32::from(max_damage)
+    }
+
+    /// Repairs the item with Mending from experience points, each point repairs two durability
+    /// like vanilla's `ExperienceOrb.repairPlayerItems`. Returns the points left over
+    pub fn repair_with_experience(&mut self, points: i32) -> i32 {
+        let damage = self.components.damage.unwrap_or(0);
+        if points <= 0 || damage <= 0 || self.enchantment_level("mending") == 0 {
+            return points;
         }
+        let repairable = points * 2;
+        let repaired = repairable.min(damage);
+        self.components.damage = Some(damage - repaired).filter(|damage| *damage > 0);
+        points - repaired * points / repairable
+    }
+
+    /// Writes the stack using the vanilla item NBT layout (`id`, `count` and `components`)
+    pub fn write_item_nbt(&self, compound: &mut NbtCompound) {
+        if let Some(name) = get_item_name_by_id(self.item_id) {
+            compound.put(
+                "id".to_string(),
+                NbtTag::String(format!("minecraft:{name}")),
+            );
+        }
+        compound.put("count".to_string(), NbtTag::Int(i32::from(self.item_count)));
+        if !self.components.is_empty() {
+            let mut components = NbtCompound::new();
+            self.components.write_nbt(&mut components);
+            compound.put("components".to_string(), NbtTag::Compound(components));
+        }
+    }
+
+    /// Reads a stack from the vanilla item NBT layout, Returns None if the item is unknown
+    pub fn read_item_nbt(compound: &NbtCompound) -> Option<Self> {
+        let item = get_item(compound.get_string("id")?)?;
+        let count = compound.get_int("count").unwrap_or(1);
+        let mut stack = Self::new(count.try_into().ok()?, item.id);
+        if let Some(components) = compound.get_compound("components") {
+            stack.components = ItemComponents::read_nbt(components);
+        }
+        Some(stack)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::compound::NbtCompound;
+
+    use super::{item_registry::get_item, ItemStack};
+
+    #[test]
+    fn items_break_once_their_durability_is_used_up() {
+        let bow = get_item("bow").unwrap();
+        let max_damage = i32::from(bow.components.max_damage.unwrap());
+        let mut stack = ItemStack::new(1, bow.id);
+        assert!(!stack.damage_item(1));
+        assert_eq!(stack.components.damage, Some(1));
+        assert!(stack.damage_item(max_damage - 1));
+
+        let mut unbreakable = ItemStack::new(1, bow.id);
+        unbreakable.components.unbreakable = true;
+        assert!(!unbreakable.damage_item(max_damage));
+        // Items without durability never break
+        let mut stone = ItemStack::new(1, get_item("stone").unwrap().id);
+        assert!(!stone.damage_item(1));
+    }
+
+    #[test]
+    fn mending_repairs_with_experience() {
+        let bow = get_item("bow").unwrap();
+        let mut stack = ItemStack::new(1, bow.id);
+        stack.components.damage = Some(5);
+        // Without Mending the points are kept
+        assert_eq!(stack.repair_with_experience(10), 10);
+        stack.components.enchantments = vec![("mending".to_string(), 1)];
+        assert_eq!(stack.repair_with_experience(10), 8);
+        assert_eq!(stack.components.damage, None);
+        stack.components.damage = Some(30);
+        assert_eq!(stack.repair_with_experience(10), 0);
+        assert_eq!(stack.components.damage, Some(10));
+    }
+
+    #[test]
+    fn shulker_boxes_keep_their_items_when_saved() {
+        let mut shulker_box = ItemStack::of("shulker_box", 1);
+        shulker_box.components.container = vec![None, Some(ItemStack::of("stone", 3))];
+        let mut nbt = NbtCompound::new();
+        shulker_box.write_item_nbt(&mut nbt);
+        assert_eq!(ItemStack::read_item_nbt(&nbt), Some(shulker_box));
     }
 }
@@ -1,21 +1,79 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use dashmap::{DashMap, Entry};
 use num_traits::Zero;
-use pumpkin_core::math::vector2::Vector2;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    get_section_cord,
+    position::WorldPosition,
+    vector2::Vector2,
+    vector3::Vector3,
+};
+use pumpkin_nbt::{compound::NbtCompound, Nbt};
+use rand::Rng;
 use tokio::{
     runtime::Handle,
     sync::{mpsc, RwLock},
 };
 
 use crate::{
+    advancement_data::AdvancementDataStorage,
+    biome::Biome,
+    block::{
+        block_entity::{
+            campfire::CampfireBlockEntity,
+            spawner::{MobSpawnerBlockEntity, SpawnAttempt},
+            BlockEntity, BrewingRecipes, CookingRecipes, NearbyBlockEntities, TickContext,
+            TickResult,
+        },
+        explosion::{exploded_blocks, explosion_hit, ExplosionHit},
+        falling::{harden, landing_state, lands_in, start_falling},
+        fluid::{fluid_state, fluid_tick, Fluid, FluidUpdate},
+        lightning::{lightning_fire, lightning_target},
+        piston::{self, PistonMove},
+        portal::{
+            completed_end_portal, find_nether_portal_shape, find_portal_site, is_nether_portal,
+            nether_portal_at, nether_portal_survives, portal_in, NetherPortalShape, PortalAxis,
+            PortalKind, PORTAL_SITE_RADIUS,
+        },
+        random_tick::{offset, pick_random_ticks, random_tick, BlockAccess, NEIGHBOURS},
+        redstone::{has_neighbor_signal, has_signal},
+        BlockFace,
+    },
     chunk::{
-        anvil::AnvilChunkReader, ChunkData, ChunkParsingError, ChunkReader, ChunkReadingError,
+        get_chunk_storage,
+        light::LightType,
+        ticks::{ScheduledTick, TickPriority},
+        upgrade::upgrade_chunk,
+        ChunkData, ChunkStorage,
     },
-    generation::{get_world_gen, Seed, WorldGenerator},
+    chunk_worker_pool::ChunkWorkerPool,
+    coordinates::{ChunkRelativeBlockCoordinates, XZBlockCoordinates},
+    datapack::DatapackStorage,
+    dimension::Dimension,
+    generation::{get_world_gen, structure::Structure, GeneratorOptions, Seed, WorldGenerator},
+    item::ItemStack,
     lock::{anvil::AnvilLevelLocker, LevelLocker},
-    world_info::{anvil::AnvilLevelInfo, LevelData, WorldInfoReader, WorldInfoWriter},
+    pathfinding::{find_path, NodeEvaluator, Path},
+    physics::{
+        blocks_inside, can_stand_at, clip_blocks, fluid_contact, is_in_fluid, stand_up_position,
+        tick_movement, BlockHit, FluidContact, Movement, MovementProperties,
+    },
+    player_data::PlayerDataStorage,
+    pregen::PregenTask,
+    saved_data::SavedDataStorage,
+    spawning::{spawn_category_at, spawn_origin, MobCategory, NaturalSpawn, SpawnContext},
+    stats::StatsDataStorage,
+    world_info::{
+        anvil::AnvilLevelInfo, LevelData, WorldGenSettings, WorldInfoReader, WorldInfoWriter,
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
 };
 
 /// The `Level` module provides functionality for working with chunks within or outside a Minecraft world.
@@ -24,18 +82,27 @@ use crate::{
 ///
 /// - **Chunk Loading:** Efficiently loads chunks from disk.
 /// - **Chunk Caching:** Stores accessed chunks in memory for faster access.
-/// - **Chunk Generation:** Generates new chunks on-demand using a specified `WorldGenerator`.
+/// - **Chunk Generation:** Generates new chunks on-demand using a specified `WorldGenerator`, on dedicated worker threads.
+/// - **Chunk Saving:** Writes the chunks which changed back to disk, see `flush_dirty_chunks`.
 ///
 /// For more details on world generation, refer to the `WorldGenerator` module.
 pub struct Level {
     pub seed: Seed,
     pub level_info: LevelData,
+    pub player_data: PlayerDataStorage,
+    pub player_advancements: AdvancementDataStorage,
+    pub player_stats: StatsDataStorage,
+    pub datapacks: DatapackStorage,
+    /// The data of the world which isn't in chunks, like the scoreboard
+    pub saved_data: SavedDataStorage,
     world_info_writer: Arc<dyn WorldInfoWriter>,
     level_folder: LevelFolder,
     loaded_chunks: Arc<DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>>,
     chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
-    chunk_reader: Arc<dyn ChunkReader>,
+    chunk_pool: ChunkWorkerPool,
     world_gen: Arc<dyn WorldGenerator>,
+    /// Shared with the worker pool, which reads the chunks
+    chunk_storage: Arc<dyn ChunkStorage>,
     // Gets unlocked when dropped
     // TODO: Make this a trait
     _locker: Arc<AnvilLevelLocker>,
@@ -48,7 +115,16 @@ pub struct LevelFolder {
 }
 
 impl Level {
-    pub fn from_root_folder(root_folder: PathBuf) -> Self {
+    pub fn from_root_folder(root_folder: PathBuf, dimension: Dimension) -> Self {
+        Self::with_generator(root_folder, dimension, &GeneratorOptions::default())
+    }
+
+    /// A level which generates new chunks with its own options instead of the basic config
+    pub fn with_generator(
+        root_folder: PathBuf,
+        dimension: Dimension,
+        generator: &GeneratorOptions,
+    ) -> Self {
         // If we are using an already existing world we want to read the seed from the level.dat, If not we want to check if there is a seed in the config, if not lets create a random one
         let region_folder = root_folder.join("region");
         if !region_folder.exists() {
@@ -66,33 +142,63 @@ impl Level {
         // TODO: Load info correctly based on world format type
         let level_info = AnvilLevelInfo
             .read_world_info(&level_folder)
-            .unwrap_or_default(); // TODO: Improve error handling
+            .unwrap_or_else(|_| LevelData {
+                world_gen_settings: WorldGenSettings {
+                    seed: Seed::from(generator.seed.as_str()).0 as i64,
+                },
+                ..Default::default()
+            }); // TODO: Improve error handling
         let seed = Seed(level_info.world_gen_settings.seed as u64);
-        let world_gen = get_world_gen(seed).into();
+        let player_data = PlayerDataStorage::new(&level_folder.root_folder);
+        let player_advancements = AdvancementDataStorage::new(&level_folder.root_folder);
+        let player_stats = StatsDataStorage::new(&level_folder.root_folder);
+        let datapacks = DatapackStorage::new(&level_folder.root_folder);
+        let saved_data = SavedDataStorage::new(&level_folder.root_folder);
+        let loaded_chunks = Arc::new(DashMap::new());
+        let chunk_watchers = Arc::new(DashMap::new());
+        let chunk_storage: Arc<dyn ChunkStorage> =
+            get_chunk_storage(ADVANCED_CONFIG.chunk.format).into();
+        let world_gen: Arc<dyn WorldGenerator> = get_world_gen(seed, dimension, generator).into();
+        let chunk_pool = ChunkWorkerPool::new(
+            loaded_chunks.clone(),
+            chunk_watchers.clone(),
+            chunk_storage.clone(),
+            level_folder.clone(),
+            world_gen.clone(),
+        );
 
         Self {
             seed,
-            world_gen,
+            player_data,
+            player_advancements,
+            player_stats,
+            datapacks,
+            saved_data,
             world_info_writer: Arc::new(AnvilLevelInfo),
             level_folder,
-            chunk_reader: Arc::new(AnvilChunkReader::new()),
-            loaded_chunks: Arc::new(DashMap::new()),
-            chunk_watchers: Arc::new(DashMap::new()),
+            loaded_chunks,
+            chunk_watchers,
+            chunk_pool,
+            world_gen,
+            chunk_storage,
             level_info,
             _locker: Arc::new(locker),
         }
     }
 
-    pub async fn save(&self) {
+    /// Saves the chunks and the level info, the info changes while the world runs.
+    /// `live_entities` are the entities in each chunk which are in the world right now
+    pub async fn save(
+        &self,
+        level_info: LevelData,
+        live_entities: HashMap<Vector2<i32>, Vec<NbtCompound>>,
+    ) {
         log::info!("Saving level...");
         // lets first save all chunks
-        for chunk in self.loaded_chunks.iter() {
-            let chunk = chunk.read().await;
-            self.clean_chunk(&chunk.position);
-        }
+        self.flush_dirty_chunks(live_entities).await;
         // then lets save the world info
         self.world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder)
+            .write_world_info(level_info, &self.level_folder)
             .expect("Failed to save world info");
     }
 
@@ -102,6 +208,70 @@ impl Level {
         self.loaded_chunks.len()
     }
 
+    /// The chunk if it is in memory, it is never loaded or generated
+    pub fn get_loaded_chunk(&self, chunk: &Vector2<i32>) -> Option<Arc<RwLock<ChunkData>>> {
+        self.loaded_chunks
+            .get(chunk)
+            .map(|chunk| chunk.value().clone())
+    }
+
+    /// The start chunk of the nearest `structure` to the chunk `from`
+    pub fn locate_structure(
+        &self,
+        structure: Structure,
+        from: &Vector2<i32>,
+    ) -> Option<Vector2<i32>> {
+        self.world_gen.locate_structure(structure, from)
+    }
+
+    /// The column with a biome matching `predicate` nearest to `from`, see
+    /// [`WorldGenerator::locate_biome`]
+    pub fn locate_biome(
+        &self,
+        from: XZBlockCoordinates,
+        predicate: &dyn Fn(Biome) -> bool,
+    ) -> Option<XZBlockCoordinates> {
+        self.world_gen.locate_biome(from, predicate)
+    }
+
+    /// The nearest point of interest with a type matching `predicate` at most `radius` blocks away
+    /// from `from`, like vanilla's `PoiManager.findClosest`. Only loaded chunks are searched
+    pub async fn locate_poi(
+        &self,
+        from: WorldPosition,
+        radius: i32,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Option<WorldPosition> {
+        let WorldPosition(center) = from;
+        let distance = |WorldPosition(position): &WorldPosition| {
+            let x = i64::from(position.x - center.x);
+            let y = i64::from(position.y - center.y);
+            let z = i64::from(position.z - center.z);
+            x * x + y * y + z * z
+        };
+        let max_distance = i64::from(radius) * i64::from(radius);
+
+        let mut nearest: Option<(i64, WorldPosition)> = None;
+        for chunk_x in (center.x - radius) >> 4..=(center.x + radius) >> 4 {
+            for chunk_z in (center.z - radius) >> 4..=(center.z + radius) >> 4 {
+                let Some(chunk) = self.get_loaded_chunk(&Vector2::new(chunk_x, chunk_z)) else {
+                    continue;
+                };
+                let chunk = chunk.read().await;
+                for point in &chunk.points_of_interest {
+                    let distance = distance(&point.position);
+                    if distance <= max_distance
+                        && predicate(&point.kind)
+                        && nearest.is_none_or(|(nearest, _)| distance < nearest)
+                    {
+                        nearest = Some((distance, point.position));
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, position)| position)
+    }
+
     pub fn list_cached(&self) {
         for entry in self.loaded_chunks.iter() {
             log::debug!("In map: {:?}", entry.key());
@@ -175,10 +345,17 @@ impl Level {
         });
     }
 
+    /// Unloads the chunk, chunks with unsaved changes stay in memory until they were saved
     pub fn clean_chunk(&self, chunk: &Vector2<i32>) {
-        if let Some(data) = self.loaded_chunks.remove(chunk) {
-            self.write_chunk(data);
-        }
+        self.loaded_chunks
+            .remove_if(chunk, |_, data| !Self::is_dirty(data));
+    }
+
+    fn is_dirty(chunk: &RwLock<ChunkData>) -> bool {
+        // Chunks which are in use right now may be changed
+        chunk
+            .try_read()
+            .map_or(true, |chunk| chunk.dirty || chunk.entities_dirty)
     }
 
     pub fn is_chunk_watched(&self, chunk: &Vector2<i32>) -> bool {
@@ -194,36 +371,155 @@ impl Level {
             }
 
             if self.chunk_watchers.get(chunk).is_none() {
-                self.loaded_chunks.remove(chunk);
+                self.clean_chunk(chunk);
             }
         });
         self.loaded_chunks.shrink_to_fit();
         self.chunk_watchers.shrink_to_fit();
     }
 
-    pub fn write_chunk(&self, _chunk_to_write: (Vector2<i32>, Arc<RwLock<ChunkData>>)) {
-        //TODO
+    /// Saves the chunks which changed since they were last saved, saved chunks nobody watches are unloaded.
+    /// The entities of chunks with `live_entities` are saved together with these
+    pub async fn flush_dirty_chunks(&self, live_entities: HashMap<Vector2<i32>, Vec<NbtCompound>>) {
+        let chunks: Vec<_> = self
+            .loaded_chunks
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+        let chunk_storage = self.chunk_storage.clone();
+        let level_folder = self.level_folder.clone();
+        let saved = tokio::task::spawn_blocking(move || {
+            let saved = chunks
+                .iter()
+                .filter_map(|chunk| {
+                    Self::save_chunk(chunk_storage.as_ref(), &level_folder, chunk, &live_entities)
+                })
+                .collect::<Vec<_>>();
+            // Storages which buffer chunks keep them until they could be written
+            if let Err(err) = chunk_storage.flush(&level_folder) {
+                log::error!("Failed to flush chunk storage: {}", err);
+            }
+            saved
+        })
+        .await;
+        let saved = match saved {
+            Ok(saved) => saved,
+            Err(err) => {
+                log::error!("Failed to save chunks: {}", err);
+                return;
+            }
+        };
+        if !saved.is_empty() {
+            log::debug!("Saved {} chunks", saved.len());
+        }
+
+        for position in saved {
+            if !self.is_chunk_watched(&position) {
+                self.clean_chunk(&position);
+            }
+        }
+    }
+
+    /// Upgrades every stored chunk which was saved by an older version, like vanilla's `--forceUpgrade`.
+    /// Blocks until all chunks were upgraded, chunks which can't be upgraded are left as they are
+    pub fn force_upgrade(&self) {
+        let chunks = self.chunk_storage.stored_chunks(&self.level_folder);
+        log::info!("Checking {} chunks for upgrades...", chunks.len());
+        let mut upgraded = 0;
+        for (index, at) in chunks.iter().enumerate() {
+            if self.upgrade_stored_chunk(at) {
+                upgraded += 1;
+            }
+            if (index + 1) % 1024 == 0 {
+                log::info!("Checked {}/{} chunks", index + 1, chunks.len());
+            }
+        }
+        if let Err(err) = self.chunk_storage.flush(&self.level_folder) {
+            log::error!("Failed to flush upgraded chunks: {}", err);
+        }
+        log::info!("Upgraded {} chunks", upgraded);
+    }
+
+    /// Returns whether the chunk was upgraded and written back
+    fn upgrade_stored_chunk(&self, at: &Vector2<i32>) -> bool {
+        let bytes = match self.chunk_storage.read_chunk_nbt(&self.level_folder, at) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                log::warn!("Failed to read chunk {:?} for upgrading: {}", at, err);
+                return false;
+            }
+        };
+        let mut nbt = match Nbt::read(&mut &bytes[..]) {
+            Ok(nbt) => nbt,
+            Err(err) => {
+                log::warn!("Failed to parse chunk {:?} for upgrading: {}", at, err);
+                return false;
+            }
+        };
+        match upgrade_chunk(&mut nbt.root_tag) {
+            Ok(true) => {}
+            Ok(false) => return false,
+            Err(err) => {
+                log::warn!("Failed to upgrade chunk {:?}: {}", at, err);
+                return false;
+            }
+        }
+        if let Err(err) = self
+            .chunk_storage
+            .write_chunk(&self.level_folder, at, &nbt.write())
+        {
+            log::error!("Failed to save upgraded chunk {:?}: {}", at, err);
+            return false;
+        }
+        true
     }
 
-    fn load_chunk_from_save(
-        chunk_reader: Arc<dyn ChunkReader>,
-        save_file: &LevelFolder,
-        chunk_pos: Vector2<i32>,
-    ) -> Result<Option<Arc<RwLock<ChunkData>>>, ChunkReadingError> {
-        match chunk_reader.read_chunk(save_file, &chunk_pos) {
-            Ok(data) => Ok(Some(Arc::new(RwLock::new(data)))),
-            Err(
-                ChunkReadingError::ChunkNotExist
-                | ChunkReadingError::ParsingError(ChunkParsingError::ChunkNotGenerated),
-            ) => {
-                // This chunk was not generated yet.
-                Ok(None)
+    /// Writes the chunk if it is dirty, returns its position if it was written
+    fn save_chunk(
+        chunk_storage: &dyn ChunkStorage,
+        level_folder: &LevelFolder,
+        chunk: &RwLock<ChunkData>,
+        live_entities: &HashMap<Vector2<i32>, Vec<NbtCompound>>,
+    ) -> Option<Vector2<i32>> {
+        let (position, bytes, entities) = {
+            let mut data = chunk.blocking_write();
+            let live = live_entities.get(&data.position);
+            if !data.dirty && !data.entities_dirty && live.is_none() {
+                return None;
+            }
+            let bytes = data.dirty.then(|| data.to_bytes());
+            let entities = (data.entities_dirty || live.is_some()).then(|| {
+                data.entities
+                    .iter()
+                    .chain(live.into_iter().flatten())
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+            data.dirty = false;
+            // Live entities may be gone by the next save, then the saved ones are outdated
+            data.entities_dirty = live.is_some_and(|live| !live.is_empty());
+            (data.position, bytes, entities)
+        };
+        let mut saved = true;
+        if let Some(bytes) = bytes {
+            if let Err(err) = chunk_storage.write_chunk(level_folder, &position, &bytes) {
+                log::error!("Failed to save chunk {:?}: {}", position, err);
+                // Try again next time
+                chunk.blocking_write().dirty = true;
+                saved = false;
+            }
+        }
+        if let Some(entities) = entities {
+            if let Err(err) = chunk_storage.write_entities(level_folder, &position, &entities) {
+                log::error!("Failed to save entities of chunk {:?}: {}", position, err);
+                chunk.blocking_write().entities_dirty = true;
+                saved = false;
             }
-            Err(err) => Err(err),
         }
+        saved.then_some(position)
     }
 
-    /// Reads/Generates many chunks in a world
+    /// Reads/Generates many chunks in a world, they are handled before the chunks players are waiting for
     /// Note: The order of the output chunks will almost never be in the same order as the order of input chunks
     pub fn fetch_chunks(
         &self,
@@ -231,51 +527,849 @@ impl Level {
         channel: mpsc::Sender<Arc<RwLock<ChunkData>>>,
         rt: &Handle,
     ) {
-        chunks.par_iter().for_each(|at| {
-            let channel = channel.clone();
-            let loaded_chunks = self.loaded_chunks.clone();
-            let chunk_reader = self.chunk_reader.clone();
-            let level_info = self.level_folder.clone();
-            let world_gen = self.world_gen.clone();
-            let chunk_pos = *at;
-
-            let chunk = loaded_chunks
-                .get(&chunk_pos)
-                .map(|entry| entry.value().clone())
-                .unwrap_or_else(|| {
-                    let loaded_chunk =
-                        match Self::load_chunk_from_save(chunk_reader, &level_info, chunk_pos) {
-                            Ok(chunk) => chunk,
-                            Err(err) => {
-                                log::error!(
-                                    "Failed to read chunk (regenerating) {:?}: {:?}",
-                                    chunk_pos,
-                                    err
-                                );
-                                None
-                            }
-                        }
-                        .unwrap_or_else(|| {
-                            Arc::new(RwLock::new(world_gen.generate_chunk(chunk_pos)))
-                        });
-
-                    if let Some(data) = loaded_chunks.get(&chunk_pos) {
-                        // Another thread populated in between the previous check and now
-                        // We did work, but this is basically like a cache miss, not much we
-                        // can do about it
-                        data.value().clone()
-                    } else {
-                        loaded_chunks.insert(chunk_pos, loaded_chunk.clone());
-                        loaded_chunk
-                    }
+        for chunk in chunks {
+            self.chunk_pool
+                .request(*chunk, 0, false, channel.clone(), rt);
+        }
+    }
+
+    /// Reads/Generates the chunks a player is going to watch, the ones closest to the player come first.
+    /// Chunks which nobody watches anymore by the time they would be generated are skipped
+    pub fn fetch_watched_chunks(
+        &self,
+        chunks: &[Vector2<i32>],
+        center: Vector2<i32>,
+        channel: mpsc::Sender<Arc<RwLock<ChunkData>>>,
+        rt: &Handle,
+    ) {
+        for chunk in chunks {
+            let x = i64::from(chunk.x - center.x);
+            let z = i64::from(chunk.z - center.z);
+            let distance = (x * x + z * z).unsigned_abs();
+            // Priority 0 is taken by chunks the server itself needs
+            self.chunk_pool
+                .request(*chunk, distance + 1, true, channel.clone(), rt);
+        }
+    }
+
+    /// How many chunks wait to be loaded or generated
+    pub fn queued_chunk_count(&self) -> usize {
+        self.chunk_pool.queued_chunks()
+    }
+
+    /// Randomly ticks `speed` blocks in every section of the watched chunks, like vanilla does
+    /// for the chunks near players. Returns the blocks which change, chunks which are being
+    /// changed right now are skipped
+    pub fn random_tick(&self, speed: u32, ultra_warm: bool) -> Vec<(WorldPosition, u16)> {
+        let watched: Vec<_> = self
+            .chunk_watchers
+            .iter()
+            .map(|entry| *entry.key())
+            .collect();
+        let blocks = LoadedBlocks(&self.loaded_chunks);
+        let mut rng = rand::thread_rng();
+        let mut changes = Vec::new();
+        for position in watched {
+            let Some(chunk) = self.get_loaded_chunk(&position) else {
+                continue;
+            };
+            let picked = match chunk.try_read() {
+                Ok(chunk) => pick_random_ticks(&chunk, speed, &mut rng),
+                Err(_) => continue,
+            };
+            for (position, state_id) in picked {
+                changes.extend(random_tick(
+                    &blocks, position, state_id, ultra_warm, &mut rng,
+                ));
+            }
+        }
+        changes
+    }
+
+    /// Counts how long players stay near the watched chunks, like vanilla's
+    /// `LevelChunk.incrementInhabitedTime`. Chunks which are being changed right now are skipped
+    pub fn tick_inhabited_time(&self) {
+        for entry in self.chunk_watchers.iter() {
+            let Some(chunk) = self.get_loaded_chunk(entry.key()) else {
+                continue;
+            };
+            let Ok(mut chunk) = chunk.try_write() else {
+                continue;
+            };
+            chunk.inhabited_time += 1;
+        }
+    }
+
+    /// How many ticks players spent near the chunk, 0 if it is not loaded
+    pub async fn inhabited_time(&self, chunk: &Vector2<i32>) -> i64 {
+        match self.get_loaded_chunk(chunk) {
+            Some(chunk) => chunk.read().await.inhabited_time,
+            None => 0,
+        }
+    }
+
+    /// Picks where lightning strikes during a thunderstorm, every watched chunk is struck with a
+    /// chance of one in `chance` each tick like vanilla's `ServerLevel.tickChunk`
+    pub fn lightning_strikes(&self, chance: u32) -> Vec<WorldPosition> {
+        let watched: Vec<_> = self
+            .chunk_watchers
+            .iter()
+            .map(|entry| *entry.key())
+            .collect();
+        let blocks = LoadedBlocks(&self.loaded_chunks);
+        let mut rng = rand::thread_rng();
+        let mut strikes = Vec::new();
+        for chunk in watched {
+            if rng.gen_range(0..chance) != 0 {
+                continue;
+            }
+            let x = chunk.x * 16 + rng.gen_range(0..16);
+            let z = chunk.z * 16 + rng.gen_range(0..16);
+            strikes.extend(lightning_target(&blocks, x, z));
+        }
+        strikes
+    }
+
+    /// The blocks lightning striking at the position sets alight
+    pub fn lightning_fire(&self, strike: WorldPosition) -> Vec<WorldPosition> {
+        lightning_fire(
+            &LoadedBlocks(&self.loaded_chunks),
+            strike,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// Counts down the scheduled block and fluid ticks of the watched chunks. Returns the block
+    /// ticks and the fluid ticks which are due, each ordered by their priority. Chunks which are
+    /// being read or changed right now catch up on their next tick
+    pub fn tick_scheduled(&self) -> (Vec<ScheduledTick>, Vec<ScheduledTick>) {
+        let watched: Vec<_> = self
+            .chunk_watchers
+            .iter()
+            .map(|entry| *entry.key())
+            .collect();
+        let mut block_ticks = Vec::new();
+        let mut fluid_ticks = Vec::new();
+        for position in watched {
+            let Some(chunk) = self.get_loaded_chunk(&position) else {
+                continue;
+            };
+            let Ok(mut chunk) = chunk.try_write() else {
+                continue;
+            };
+            if chunk.block_ticks.is_empty() && chunk.fluid_ticks.is_empty() {
+                continue;
+            }
+            // The remaining delays are saved with the chunk
+            chunk.dirty = true;
+            block_ticks.extend(chunk.block_ticks.advance());
+            fluid_ticks.extend(chunk.fluid_ticks.advance());
+        }
+        block_ticks.sort_by_key(|tick| tick.priority);
+        fluid_ticks.sort_by_key(|tick| tick.priority);
+        (block_ticks, fluid_ticks)
+    }
+
+    /// Ticks the ticking block entities of the watched chunks, like furnaces burning their fuel.
+    /// Returns the blocks the block entities change and the mobs spawners try to spawn, chunks
+    /// which are being read or changed right now catch up on their next tick. Hoppers leave the
+    /// containers which players have open alone, those keep their items until they close
+    pub fn tick_block_entities(
+        &self,
+        players: &[Vector3<f64>],
+        recipes: &dyn CookingRecipes,
+        brewing: &dyn BrewingRecipes,
+        open_containers: &HashSet<WorldPosition>,
+    ) -> Vec<(WorldPosition, TickResult)> {
+        let watched: Vec<_> = self
+            .chunk_watchers
+            .iter()
+            .map(|entry| *entry.key())
+            .collect();
+        let mut results = Vec::new();
+        for position in watched {
+            let Some(chunk) = self.get_loaded_chunk(&position) else {
+                continue;
+            };
+            let Ok(mut chunk) = chunk.try_write() else {
+                continue;
+            };
+            let ticking: Vec<_> = chunk
+                .block_entities
+                .iter()
+                .filter(|(_, block_entity)| block_entity.is_ticking())
+                .map(|(position, _)| *position)
+                .collect();
+            if ticking.is_empty() {
+                continue;
+            }
+            let block_entities = NearbyLoadedBlockEntities {
+                chunk: RefCell::new(&mut *chunk),
+                loaded: LoadedBlocks(&self.loaded_chunks),
+                open_containers,
+            };
+            for position in ticking {
+                // Taken out while it ticks, so it can reach the other block entities of its chunk
+                let mut chunk = block_entities.chunk.borrow_mut();
+                let Some(mut block_entity) = chunk.block_entities.remove(&position) else {
+                    continue;
+                };
+                let (_, relative) = position.chunk_and_chunk_relative_position();
+                let state_id = chunk
+                    .blocks
+                    .get_block(ChunkRelativeBlockCoordinates::from(relative));
+                drop(chunk);
+                let result = state_id.map(|state_id| {
+                    block_entity.tick(&TickContext {
+                        position,
+                        state_id,
+                        players,
+                        recipes,
+                        brewing,
+                        block_entities: &block_entities,
+                    })
                 });
+                block_entities
+                    .chunk
+                    .borrow_mut()
+                    .block_entities
+                    .insert(position, block_entity);
+                let Some(result) = result else {
+                    continue;
+                };
+                if result.state_id.is_some()
+                    || result.spawn.is_some()
+                    || !result.cooked.is_empty()
+                    || !result.dropped.is_empty()
+                    || result.block_entity_update.is_some()
+                    || result.collect_items
+                {
+                    results.push((position, result));
+                }
+            }
+            // The block entities save their progress
+            block_entities.chunk.borrow_mut().dirty = true;
+        }
+        results
+    }
+
+    /// Waits for the next spawn of the spawner at the position after it spawned mobs, or when
+    /// too many mobs are around it. Returns the data players need to show the next mob
+    pub async fn reset_spawner(&self, position: WorldPosition) -> Option<NbtCompound> {
+        let (chunk, _) = LoadedBlocks(&self.loaded_chunks).locate(&position)?;
+        let mut chunk = chunk.write().await;
+        let spawner = chunk
+            .block_entities
+            .get_mut(&position)?
+            .as_any_mut()
+            .downcast_mut::<MobSpawnerBlockEntity>()?;
+        spawner.reset_delay(&mut rand::thread_rng());
+        let data = spawner.update_nbt();
+        chunk.dirty = true;
+        data
+    }
+
+    /// Puts a single item of the stack on the campfire at the position to cook for the ticks.
+    /// Returns the data players need to show the food, `None` if there is no campfire or it is
+    /// full
+    pub async fn place_campfire_food(
+        &self,
+        position: WorldPosition,
+        stack: &ItemStack,
+        cooking_time: i32,
+    ) -> Option<NbtCompound> {
+        let (chunk, _) = LoadedBlocks(&self.loaded_chunks).locate(&position)?;
+        let mut chunk = chunk.write().await;
+        let campfire = chunk
+            .block_entities
+            .get_mut(&position)?
+            .as_any_mut()
+            .downcast_mut::<CampfireBlockEntity>()?;
+        if !campfire.place_food(stack, cooking_time) {
+            return None;
+        }
+        let data = campfire.update_nbt();
+        chunk.dirty = true;
+        data
+    }
+
+    /// Changes the block entity at the position if its chunk is loaded, returns what the change
+    /// returns or false without a block entity. The chunk is saved if the change returns true
+    pub async fn update_block_entity(
+        &self,
+        position: WorldPosition,
+        update: impl FnOnce(&mut dyn BlockEntity) -> bool,
+    ) -> bool {
+        let Some((chunk, _)) = LoadedBlocks(&self.loaded_chunks).locate(&position) else {
+            return false;
+        };
+        let mut chunk = chunk.write().await;
+        let Some(block_entity) = chunk.block_entities.get_mut(&position) else {
+            return false;
+        };
+        let updated = update(block_entity.as_mut());
+        chunk.dirty |= updated;
+        updated
+    }
+
+    /// Reads the block entity at the position if its chunk is loaded
+    pub async fn read_block_entity<T>(
+        &self,
+        position: WorldPosition,
+        read: impl FnOnce(&dyn BlockEntity) -> T,
+    ) -> Option<T> {
+        let (chunk, _) = LoadedBlocks(&self.loaded_chunks).locate(&position)?;
+        let chunk = chunk.read().await;
+        chunk
+            .block_entities
+            .get(&position)
+            .map(|block_entity| read(block_entity.as_ref()))
+    }
+
+    /// Whether a mob of the size spawned by the spawner fits at the position
+    pub fn spawner_allows(
+        &self,
+        attempt: &SpawnAttempt,
+        position: Vector3<f64>,
+        width: f64,
+        height: f64,
+    ) -> bool {
+        attempt.allows(&LoadedBlocks(&self.loaded_chunks), position, width, height)
+    }
+
+    /// The block at the position if its chunk is loaded
+    pub async fn loaded_block_state(&self, position: &WorldPosition) -> Option<u16> {
+        let (chunk, relative) = LoadedBlocks(&self.loaded_chunks).locate(position)?;
+        let chunk = chunk.read().await;
+        chunk
+            .blocks
+            .get_block(ChunkRelativeBlockCoordinates::from(relative))
+    }
+
+    /// Moves an entity with the bounding box for one tick, `None` if its chunk is not loaded and
+    /// it holds still
+    pub fn tick_entity_movement(
+        &self,
+        properties: &MovementProperties,
+        bounding_box: &BoundingBox,
+        velocity: Vector3<f64>,
+        on_ground: bool,
+    ) -> Option<Movement> {
+        let chunk = Vector2::new(
+            get_section_cord(((bounding_box.min_x + bounding_box.max_x) / 2.0).floor() as i32),
+            get_section_cord(((bounding_box.min_z + bounding_box.max_z) / 2.0).floor() as i32),
+        );
+        if !self.loaded_chunks.contains_key(&chunk) {
+            return None;
+        }
+        Some(tick_movement(
+            &LoadedBlocks(&self.loaded_chunks),
+            properties,
+            bounding_box,
+            velocity,
+            on_ground,
+        ))
+    }
 
-            rt.spawn(async move {
-                let _ = channel
-                    .send(chunk)
-                    .await
-                    .inspect_err(|err| log::error!("unable to send chunk to channel: {}", err));
+    /// The states of the loaded blocks the box is inside of
+    pub fn blocks_inside(&self, bounding_box: &BoundingBox) -> Vec<u16> {
+        blocks_inside(&LoadedBlocks(&self.loaded_chunks), bounding_box)
+    }
+
+    /// How deep the box is in the loaded fluid, see `physics::fluid_contact`
+    pub fn fluid_contact(&self, bounding_box: &BoundingBox, fluid: Fluid) -> Option<FluidContact> {
+        fluid_contact(&LoadedBlocks(&self.loaded_chunks), bounding_box, fluid)
+    }
+
+    /// Whether the point is under the surface of the loaded fluid
+    pub fn is_in_fluid(&self, point: Vector3<f64>, fluid: Fluid) -> bool {
+        is_in_fluid(&LoadedBlocks(&self.loaded_chunks), point, fluid)
+    }
+
+    /// The first loaded block the line from `from` to `to` hits, e.g. what a flying arrow hits
+    pub fn clip_blocks(&self, from: Vector3<f64>, to: Vector3<f64>) -> Option<BlockHit> {
+        clip_blocks(&LoadedBlocks(&self.loaded_chunks), from, to)
+    }
+
+    /// The way a mob takes to the target through the loaded blocks, see `pathfinding::find_path`
+    pub fn find_path(
+        &self,
+        evaluator: &NodeEvaluator,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        max_visited: usize,
+    ) -> Option<Path> {
+        find_path(
+            &LoadedBlocks(&self.loaded_chunks),
+            evaluator,
+            from,
+            to,
+            max_visited,
+        )
+    }
+
+    /// Whether a mob of the size fits at the position with loaded ground below it
+    pub fn can_stand_at(
+        &self,
+        position: Vector3<f64>,
+        size: &BoundingBoxSize,
+        avoid_fluids: bool,
+    ) -> bool {
+        can_stand_at(
+            &LoadedBlocks(&self.loaded_chunks),
+            position,
+            size,
+            avoid_fluids,
+        )
+    }
+
+    /// Where a player stands up at the respawn block, see `physics::stand_up_position`
+    pub fn stand_up_position(
+        &self,
+        respawn_block: &WorldPosition,
+        size: &BoundingBoxSize,
+    ) -> Option<Vector3<f64>> {
+        stand_up_position(&LoadedBlocks(&self.loaded_chunks), respawn_block, size)
+    }
+
+    /// The mobs of the category which spawn naturally in the loaded chunk this tick, see
+    /// `spawning::spawn_category_at`. Chunks which are being changed right now spawn nothing
+    pub fn natural_spawns(
+        &self,
+        chunk: &Vector2<i32>,
+        category: MobCategory,
+        context: &SpawnContext,
+        size: impl Fn(&str) -> Option<BoundingBoxSize>,
+    ) -> Vec<NaturalSpawn> {
+        let Some(chunk) = self.get_loaded_chunk(chunk) else {
+            return Vec::new();
+        };
+        let mut rng = rand::thread_rng();
+        let origin = match chunk.try_read() {
+            Ok(chunk) => spawn_origin(&chunk, &mut rng),
+            Err(_) => return Vec::new(),
+        };
+        let Some(origin) = origin else {
+            return Vec::new();
+        };
+        spawn_category_at(
+            &LoadedBlocks(&self.loaded_chunks),
+            context,
+            category,
+            origin,
+            size,
+            &mut rng,
+        )
+    }
+
+    /// Whether nothing blocks the sky above the position, like vanilla's `canSeeSky`. Positions in
+    /// chunks which are not loaded see no sky
+    pub fn can_see_sky(&self, position: &WorldPosition) -> bool {
+        LoadedBlocks(&self.loaded_chunks).light(LightType::Sky, position) == Some(15)
+    }
+
+    /// Whether the chunk the position is in is loaded, entities in chunks which are not loaded
+    /// hold still
+    pub fn is_loaded(&self, position: Vector3<f64>) -> bool {
+        self.loaded_chunks.contains_key(&Vector2::new(
+            get_section_cord(position.x.floor() as i32),
+            get_section_cord(position.z.floor() as i32),
+        ))
+    }
+
+    /// Takes the saved entities with the id out of the loaded chunk, so they can be added to the
+    /// world. Other entities stay in the chunk
+    pub async fn take_entities(&self, chunk: &Vector2<i32>, id: &str) -> Vec<NbtCompound> {
+        let Some(chunk) = self.get_loaded_chunk(chunk) else {
+            return Vec::new();
+        };
+        let mut chunk = chunk.write().await;
+        let (taken, kept) = std::mem::take(&mut chunk.entities)
+            .into_iter()
+            .partition::<Vec<_>, _>(|entity| {
+                entity.get_string("id").is_some_and(|other| other == id)
             });
+        chunk.entities = kept;
+        if !taken.is_empty() {
+            chunk.entities_dirty = true;
+        }
+        taken
+    }
+
+    /// Puts entities which leave the world back into their chunk, where they are saved. Returns
+    /// false if the chunk is not loaded
+    pub async fn store_entities(&self, chunk: &Vector2<i32>, entities: Vec<NbtCompound>) -> bool {
+        let Some(chunk) = self.get_loaded_chunk(chunk) else {
+            return false;
+        };
+        let mut chunk = chunk.write().await;
+        chunk.entities.extend(entities);
+        chunk.entities_dirty = true;
+        true
+    }
+
+    /// Lets the fluid at the position flow, returns the blocks which change. Fluid can't flow into
+    /// chunks which are not loaded
+    pub fn fluid_tick(
+        &self,
+        position: WorldPosition,
+        fluid: Fluid,
+        ultra_warm: bool,
+    ) -> FluidUpdate {
+        fluid_tick(
+            &LoadedBlocks(&self.loaded_chunks),
+            position,
+            fluid,
+            ultra_warm,
+        )
+    }
+
+    /// The state of the falling block at the position if there is nothing below it and it should
+    /// fall down as an entity
+    pub fn start_falling(&self, position: WorldPosition) -> Option<u16> {
+        start_falling(&LoadedBlocks(&self.loaded_chunks), position)
+    }
+
+    /// The block a falling block entity becomes when it lands at the position, `None` if it drops
+    /// as an item instead
+    pub fn falling_block_landing(&self, position: WorldPosition, state_id: u16) -> Option<u16> {
+        landing_state(&LoadedBlocks(&self.loaded_chunks), position, state_id)
+    }
+
+    /// Whether the falling block stops in the block at the position, like concrete powder in water
+    pub fn falling_block_lands_in(&self, position: WorldPosition, state_id: u16) -> bool {
+        lands_in(&LoadedBlocks(&self.loaded_chunks), position, state_id)
+    }
+
+    /// The concrete the concrete powder at the position hardens to if it touches water
+    pub fn harden_concrete_powder(&self, position: WorldPosition, state_id: u16) -> Option<u16> {
+        harden(&LoadedBlocks(&self.loaded_chunks), position, state_id)
+    }
+
+    /// Whether the piston at the position facing the direction receives a redstone signal
+    pub fn is_piston_powered(&self, position: WorldPosition, facing: BlockFace) -> bool {
+        has_neighbor_signal(&LoadedBlocks(&self.loaded_chunks), &position, facing)
+    }
+
+    /// What changes when the piston at the position extends, `None` if it can't
+    pub fn piston_extend(&self, position: WorldPosition) -> Option<PistonMove> {
+        piston::extend(&LoadedBlocks(&self.loaded_chunks), position)
+    }
+
+    /// What changes when the piston at the position retracts
+    pub fn piston_retract(&self, position: WorldPosition) -> Option<PistonMove> {
+        piston::retract(&LoadedBlocks(&self.loaded_chunks), position)
+    }
+
+    /// Whether the block at the position receives a redstone signal from any side
+    pub fn is_block_powered(&self, position: WorldPosition) -> bool {
+        has_signal(&LoadedBlocks(&self.loaded_chunks), &position)
+    }
+
+    /// The blocks an explosion at the center reaches, including air. Explosions stop at the edge of
+    /// the loaded chunks
+    pub fn exploded_blocks(&self, center: Vector3<f64>, power: f32) -> HashSet<WorldPosition> {
+        exploded_blocks(
+            &LoadedBlocks(&self.loaded_chunks),
+            center,
+            power,
+            &mut rand::thread_rng(),
+        )
+    }
+
+    /// How an explosion at the center hits the entity at the position, `None` if it is out of reach
+    pub fn explosion_hit(
+        &self,
+        center: Vector3<f64>,
+        power: f32,
+        position: Vector3<f64>,
+        target: Vector3<f64>,
+        bounding_box: &BoundingBox,
+    ) -> Option<ExplosionHit> {
+        explosion_hit(
+            &LoadedBlocks(&self.loaded_chunks),
+            center,
+            power,
+            position,
+            target,
+            bounding_box,
+        )
+    }
+
+    /// The portal the bounding box is in, portals in chunks which are not loaded don't count
+    pub fn portal_in(&self, bounding_box: &BoundingBox) -> Option<PortalKind> {
+        portal_in(&LoadedBlocks(&self.loaded_chunks), bounding_box)
+    }
+
+    /// The obsidian frame the position is in, `None` if a portal can't be lit there
+    pub fn nether_portal_shape(&self, position: WorldPosition) -> Option<NetherPortalShape> {
+        find_nether_portal_shape(&LoadedBlocks(&self.loaded_chunks), position)
+    }
+
+    /// Whether the nether portal block is still held by its frame, see `portal::nether_portal_survives`
+    pub fn nether_portal_survives(&self, position: WorldPosition, state_id: u16) -> bool {
+        nether_portal_survives(&LoadedBlocks(&self.loaded_chunks), position, state_id)
+    }
+
+    /// The middle of the end portal the frame completed
+    pub fn completed_end_portal(&self, frame: WorldPosition) -> Option<WorldPosition> {
+        completed_end_portal(&LoadedBlocks(&self.loaded_chunks), frame)
+    }
+
+    /// Loads the chunks within `radius` blocks of the position before the chunks players wait for
+    async fn load_chunks_near(
+        &self,
+        center: &WorldPosition,
+        radius: i32,
+        rt: &Handle,
+    ) -> Vec<Vector2<i32>> {
+        let chunks: Vec<_> = ((center.0.x - radius) >> 4..=(center.0.x + radius) >> 4)
+            .flat_map(|x| {
+                ((center.0.z - radius) >> 4..=(center.0.z + radius) >> 4)
+                    .map(move |z| Vector2::new(x, z))
+            })
+            .collect();
+        let (sender, mut receiver) = mpsc::channel(chunks.len().max(1));
+        for chunk in &chunks {
+            self.chunk_pool
+                .request(*chunk, 0, false, sender.clone(), rt);
+        }
+        drop(sender);
+        while receiver.recv().await.is_some() {}
+        chunks
+    }
+
+    fn clean_unwatched_chunks(&self, chunks: &[Vector2<i32>]) {
+        for chunk in chunks {
+            if !self.is_chunk_watched(chunk) {
+                self.clean_chunk(chunk);
+            }
+        }
+    }
+
+    /// The nether portal closest to the position within `radius` blocks horizontally, like
+    /// vanilla's `PortalForcer.findClosestPortalPosition`. The chunks in range are loaded first
+    pub async fn find_nether_portal(
+        &self,
+        center: WorldPosition,
+        radius: i32,
+        rt: &Handle,
+    ) -> Option<NetherPortalShape> {
+        let chunks = self.load_chunks_near(&center, radius, rt).await;
+        let mut closest: Option<(i64, WorldPosition)> = None;
+        for chunk_pos in &chunks {
+            let Some(chunk) = self.get_loaded_chunk(chunk_pos) else {
+                continue;
+            };
+            let portals = chunk.read().await.blocks.find_blocks(is_nether_portal);
+            for relative in portals {
+                let position = WorldPosition(Vector3::new(
+                    chunk_pos.x * 16 + relative.x,
+                    relative.y,
+                    chunk_pos.z * 16 + relative.z,
+                ));
+                let delta = position.0.sub(&center.0);
+                if delta.x.abs() > radius || delta.z.abs() > radius {
+                    continue;
+                }
+                let distance = i64::from(delta.x).pow(2)
+                    + i64::from(delta.y).pow(2)
+                    + i64::from(delta.z).pow(2);
+                if closest.is_none_or(|(closest, _)| distance < closest) {
+                    closest = Some((distance, position));
+                }
+            }
+        }
+        let portal = closest.and_then(|(_, position)| {
+            nether_portal_at(&LoadedBlocks(&self.loaded_chunks), position)
         });
+        self.clean_unwatched_chunks(&chunks);
+        portal
+    }
+
+    /// Where a new nether portal fits close to the position, see `portal::find_portal_site`.
+    /// The chunks in range are loaded first
+    pub async fn find_portal_site(
+        &self,
+        center: WorldPosition,
+        axis: PortalAxis,
+        min_y: i32,
+        max_y: i32,
+        rt: &Handle,
+    ) -> Option<WorldPosition> {
+        let chunks = self.load_chunks_near(&center, PORTAL_SITE_RADIUS, rt).await;
+        let site = find_portal_site(
+            &LoadedBlocks(&self.loaded_chunks),
+            center,
+            axis,
+            PORTAL_SITE_RADIUS,
+            min_y,
+            max_y,
+        );
+        self.clean_unwatched_chunks(&chunks);
+        site
+    }
+
+    /// Schedules fluid ticks for the fluid at the changed block and next to it, so fluids flow
+    /// into the gaps left by removed blocks and dry up without their source
+    pub async fn schedule_fluid_updates(&self, position: WorldPosition, ultra_warm: bool) {
+        let blocks = LoadedBlocks(&self.loaded_chunks);
+        let neighbours = NEIGHBOURS
+            .iter()
+            .map(|(x, y, z)| offset(&position, *x, *y, *z));
+        for position in std::iter::once(position).chain(neighbours) {
+            let Some((chunk, relative)) = blocks.locate(&position) else {
+                continue;
+            };
+            let mut chunk = chunk.write().await;
+            let Some(state) = chunk
+                .blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                .and_then(fluid_state)
+            else {
+                continue;
+            };
+            let name = if state.source {
+                state.fluid.name()
+            } else {
+                state.fluid.flowing_name()
+            };
+            let delay = state.fluid.tick_delay(ultra_warm);
+            if chunk
+                .fluid_ticks
+                .schedule(position, name, delay, TickPriority::Normal)
+            {
+                chunk.dirty = true;
+            }
+        }
+    }
+
+    /// The pregeneration which was still running when the server stopped
+    pub fn pregen_task(&self) -> Option<PregenTask> {
+        PregenTask::load(&self.level_folder)
+    }
+
+    /// Remembers the progress of a pregeneration, so it can be continued after a restart
+    pub fn save_pregen_task(&self, task: &PregenTask) {
+        if let Err(err) = task.save(&self.level_folder) {
+            log::error!("Failed to save the pregeneration progress: {}", err);
+        }
+    }
+
+    /// Stops continuing the pregeneration after a restart
+    pub fn remove_pregen_task(&self) {
+        if let Err(err) = PregenTask::remove(&self.level_folder) {
+            log::error!("Failed to remove the pregeneration progress: {}", err);
+        }
+    }
+
+    /// Generates and saves the next `batch_size` chunks of the pregeneration and saves the progress.
+    /// The chunks are handled after all chunks players are waiting for, returns how many were done
+    pub async fn pregenerate_batch(
+        &self,
+        task: &mut PregenTask,
+        batch_size: usize,
+        rt: &Handle,
+    ) -> usize {
+        let chunks = task.next_batch(batch_size);
+        let (sender, mut receiver) = mpsc::channel(chunks.len().max(1));
+        for chunk in &chunks {
+            self.chunk_pool
+                .request(*chunk, u64::MAX, false, sender.clone(), rt);
+        }
+        drop(sender);
+        while receiver.recv().await.is_some() {}
+
+        self.flush_dirty_chunks(HashMap::new()).await;
+        // Chunks which were generated before were only read, they are not dirty
+        for chunk in &chunks {
+            if !self.is_chunk_watched(chunk) {
+                self.clean_chunk(chunk);
+            }
+        }
+
+        task.done += chunks.len() as u64;
+        if task.is_finished() {
+            self.remove_pregen_task();
+        } else {
+            self.save_pregen_task(task);
+        }
+        chunks.len()
+    }
+}
+
+/// The block entities around a ticking block entity. Those of its own chunk are borrowed from
+/// the ticking chunk, the others are busy while their chunk is locked
+struct NearbyLoadedBlockEntities<'a, 'b> {
+    chunk: RefCell<&'b mut ChunkData>,
+    loaded: LoadedBlocks<'a>,
+    open_containers: &'a HashSet<WorldPosition>,
+}
+
+impl NearbyBlockEntities for NearbyLoadedBlockEntities<'_, '_> {
+    fn update(
+        &self,
+        position: &WorldPosition,
+        update: &mut dyn FnMut(&mut dyn BlockEntity, u16) -> bool,
+    ) -> Option<bool> {
+        let (chunk, relative) = self.loaded.locate(position)?;
+        let mut update_in = |chunk: &mut ChunkData| {
+            let state_id = chunk
+                .blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))?;
+            let block_entity = chunk.block_entities.get_mut(position)?;
+            // Furnaces copy their block entity into their open containers each tick
+            if !block_entity.is_ticking() && self.open_containers.contains(position) {
+                return None;
+            }
+            let updated = update(block_entity.as_mut(), state_id);
+            chunk.dirty |= updated;
+            Some(updated)
+        };
+        let (chunk_position, _) = position.chunk_and_chunk_relative_position();
+        let mut ticking_chunk = self.chunk.borrow_mut();
+        if ticking_chunk.position == chunk_position {
+            return update_in(&mut ticking_chunk);
+        }
+        drop(ticking_chunk);
+        let mut chunk = chunk.try_write().ok()?;
+        update_in(&mut chunk)
+    }
+}
+
+/// The blocks of the loaded chunks, chunks which are locked for writing count as not loaded
+struct LoadedBlocks<'a>(&'a DashMap<Vector2<i32>, Arc<RwLock<ChunkData>>>);
+
+impl LoadedBlocks<'_> {
+    /// The chunk and the position in it, blocks outside of the world are not in any chunk
+    fn locate(&self, position: &WorldPosition) -> Option<(Arc<RwLock<ChunkData>>, Vector3<i32>)> {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        if !(i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&relative.y) {
+            return None;
+        }
+        let chunk = self.0.get(&chunk)?.value().clone();
+        Some((chunk, relative))
+    }
+}
+
+impl BlockAccess for LoadedBlocks<'_> {
+    fn block_state(&self, position: &WorldPosition) -> Option<u16> {
+        let (chunk, relative) = self.locate(position)?;
+        let chunk = chunk.try_read().ok()?;
+        chunk
+            .blocks
+            .get_block(ChunkRelativeBlockCoordinates::from(relative))
+    }
+
+    fn light(&self, kind: LightType, position: &WorldPosition) -> Option<u8> {
+        let (chunk, relative) = self.locate(position)?;
+        let chunk = chunk.try_read().ok()?;
+        // Light counts up from the bottom of the world
+        let y = relative.y - i32::from(WORLD_LOWEST_Y);
+        Some(
+            chunk
+                .light
+                .get(kind, relative.x as usize, y as usize, relative.z as usize),
+        )
     }
 }
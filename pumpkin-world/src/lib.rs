@@ -1,23 +1,47 @@
 use generation::{
     aquifer_sampler::{FluidLevel, FluidLevelSampler},
+    blender::Blender,
     chunk_noise::{ChunkNoiseGenerator, LAVA_BLOCK, WATER_BLOCK},
     generation_shapes::GenerationShape,
     noise::{config::NoiseConfig, router::OVERWORLD_NOISE_ROUTER},
     proto_chunk::{ProtoChunk, StandardChunkFluidLevelSampler},
 };
 use pumpkin_core::math::vector2::Vector2;
+use std::{fs, io, path::Path};
 
+pub mod advancement_data;
 pub mod biome;
 pub mod block;
 pub mod chunk;
+mod chunk_worker_pool;
 pub mod coordinates;
 pub mod cylindrical_chunk_iterator;
+pub mod damage;
+pub mod datapack;
+pub mod difficulty;
 pub mod dimension;
+pub mod effect;
+#[cfg(any(test, feature = "test-fixtures"))]
+mod fixtures;
+pub mod food;
 mod generation;
 pub mod item;
 pub mod level;
 mod lock;
+pub mod loot;
+pub mod pathfinding;
+pub mod physics;
+pub mod player_data;
+pub mod pregen;
+pub mod saved_data;
+pub mod spawning;
+pub mod stats;
 pub mod world_info;
+
+#[cfg(any(test, feature = "test-fixtures"))]
+pub use fixtures::{generate_fixture_biomes, generate_fixture_chunk, FixtureStage};
+pub use generation::{structure, GeneratorOptions, BIOME_SEARCH_RADIUS};
+
 pub const WORLD_HEIGHT: usize = 384;
 pub const WORLD_LOWEST_Y: i16 = -64;
 pub const WORLD_MAX_Y: i16 = WORLD_HEIGHT as i16 - WORLD_LOWEST_Y.abs();
@@ -42,6 +66,15 @@ macro_rules! read_data_from_file {
     };
 }
 
+/// Replaces the file with the contents, which are written next to it first so a crash can't
+/// leave a half written file behind
+pub fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)
+}
+
 // TODO: is there a way to do in-file benches?
 pub fn bench_create_chunk_noise_overworld() {
     let config = NoiseConfig::new(0, &OVERWORLD_NOISE_ROUTER);
@@ -60,6 +93,7 @@ pub fn bench_create_chunk_noise_overworld() {
         sampler,
         true,
         true,
+        Blender::NO_BLEND,
     );
 }
 
@@ -0,0 +1,1055 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::Deserialize;
+
+use pumpkin_nbt::compound::NbtCompound;
+
+use crate::{
+    block::{block_entity::read_items, explosion::survives_explosion},
+    item::{
+        item_registry::{get_item, get_item_by_id},
+        ItemStack,
+    },
+};
+
+const BLOCK_LOOT_TABLES_JSON: &str = include_str!("../../assets/block_loot_tables.json");
+const ENTITY_LOOT_TABLES_JSON: &str = include_str!("../../assets/entity_loot_tables.json");
+const CHEST_LOOT_TABLES_JSON: &str = include_str!("../../assets/chest_loot_tables.json");
+
+/// The loot tables of the blocks which don't simply drop themselves, in the vanilla format
+pub static BLOCK_LOOT_TABLES: LazyLock<HashMap<String, LootTable>> = LazyLock::new(|| {
+    serde_json::from_str(BLOCK_LOOT_TABLES_JSON)
+        .expect("Could not parse block_loot_tables.json registry.")
+});
+
+/// The loot tables of the mobs which drop something when they die, in the vanilla format
+pub static ENTITY_LOOT_TABLES: LazyLock<HashMap<String, LootTable>> = LazyLock::new(|| {
+    serde_json::from_str(ENTITY_LOOT_TABLES_JSON)
+        .expect("Could not parse entity_loot_tables.json registry.")
+});
+
+/// The loot tables of structure chests like `chests/simple_dungeon`, in the vanilla format
+pub static CHEST_LOOT_TABLES: LazyLock<HashMap<String, LootTable>> = LazyLock::new(|| {
+    serde_json::from_str(CHEST_LOOT_TABLES_JSON)
+        .expect("Could not parse chest_loot_tables.json registry.")
+});
+
+/// Mobs drop the equipment they spawned with this rarely when players kill them
+const EQUIPMENT_DROP_CHANCE: f32 = 0.085;
+/// How much more likely each level of Looting makes mobs drop their equipment
+const EQUIPMENT_DROP_CHANCE_PER_LOOTING: f32 = 0.01;
+
+/// What caused the loot to drop
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LootContext<'a> {
+    /// The item the block was mined with
+    pub tool: Option<&'a ItemStack>,
+    /// The power of the explosion which destroyed the block
+    pub explosion_power: Option<f32>,
+    /// The weapon of whoever killed the mob, its Looting gives more drops
+    pub killer_weapon: Option<&'a ItemStack>,
+    /// Whether a player killed the mob, some drops are only for them
+    pub killed_by_player: bool,
+    /// Whether the mob burned when it died, it drops cooked meat then
+    pub on_fire: bool,
+    /// The data of the broken block's block entity, shulker boxes keep their items in their drop
+    pub block_entity: Option<&'a NbtCompound>,
+}
+
+/// The slots of a shulker box
+const SHULKER_BOX_SLOTS: usize = 27;
+
+impl LootContext<'_> {
+    fn tool_enchantment_level(&self, enchantment: &str) -> i32 {
+        enchantment_level(self.tool, enchantment)
+    }
+
+    fn killer_enchantment_level(&self, enchantment: &str) -> i32 {
+        enchantment_level(self.killer_weapon, enchantment)
+    }
+}
+
+fn enchantment_level(item: Option<&ItemStack>, enchantment: &str) -> i32 {
+    let enchantment = enchantment
+        .strip_prefix("minecraft:")
+        .unwrap_or(enchantment);
+    item.map_or(0, |item| item.enchantment_level(enchantment))
+}
+
+/// The items the block drops when it is broken. Blocks without a loot table drop themselves unless
+/// an explosion destroys them
+pub fn block_drops(
+    name: &str,
+    item_id: u16,
+    context: &LootContext,
+    rng: &mut impl Rng,
+) -> Vec<ItemStack> {
+    if let Some(table) = BLOCK_LOOT_TABLES.get(name) {
+        return table.generate(context, rng);
+    }
+    let survives = context
+        .explosion_power
+        .is_none_or(|power| survives_explosion(power, rng));
+    if item_id == 0 || !survives {
+        return Vec::new();
+    }
+    let mut drop = ItemStack::new(1, item_id);
+    // Like the `copy_components` function of vanilla's shulker box loot tables
+    if let Some(block_entity) = context.block_entity.filter(|_| drop.is_shulker_box()) {
+        let mut slots = vec![None; SHULKER_BOX_SLOTS];
+        read_items(block_entity, &mut slots);
+        while slots.last().is_some_and(Option::is_none) {
+            slots.pop();
+        }
+        drop.components.container = slots;
+    }
+    vec![drop]
+}
+
+/// The items the mob drops when it dies, like vanilla's `dropFromLootTable`
+pub fn entity_drops(name: &str, context: &LootContext, rng: &mut impl Rng) -> Vec<ItemStack> {
+    ENTITY_LOOT_TABLES
+        .get(name)
+        .map(|table| table.generate(context, rng))
+        .unwrap_or_default()
+}
+
+/// How likely mobs drop a piece of the equipment they spawned with, like vanilla's
+/// `dropCustomDeathLoot`. Mobs only drop it when a player killed them
+pub fn equipment_drop_chance(looting: i32) -> f32 {
+    EQUIPMENT_DROP_CHANCE + EQUIPMENT_DROP_CHANCE_PER_LOOTING * looting as f32
+}
+
+/// Dropped equipment is worn down by a random amount, but never broken
+pub fn wear_dropped_equipment(stack: &mut ItemStack, rng: &mut impl Rng) {
+    let Some(max_damage) =
+        get_item_by_id(stack.item_id).and_then(|item| item.components.max_damage)
+    else {
+        return;
+    };
+    let max_damage = i32::from(max_damage);
+    let most_left = rng.gen_range(0..(max_damage - 3).max(1));
+    let left = rng.gen_range(0..=most_left);
+    stack.components.damage = Some(max_damage - 1 - left);
+}
+
+/// Fills the empty slots of a container from the chest loot table, like vanilla's
+/// `LootTable.fill`. A seed of 0 rolls different loot each time. Returns false if the loot table
+/// is unknown
+pub fn fill_container(slots: &mut [Option<ItemStack>], table: &str, seed: i64) -> bool {
+    let name = table.strip_prefix("minecraft:").unwrap_or(table);
+    let Some(table) = CHEST_LOOT_TABLES.get(name) else {
+        return false;
+    };
+    let mut rng = if seed == 0 {
+        StdRng::from_entropy()
+    } else {
+        StdRng::seed_from_u64(seed as u64)
+    };
+    let mut empty_slots: Vec<usize> = slots
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, item)| item.is_none().then_some(slot))
+        .collect();
+    empty_slots.shuffle(&mut rng);
+    let drops = table.generate(&LootContext::default(), &mut rng);
+    let stacks = split_into_slots(drops, empty_slots.len(), &mut rng);
+    for stack in stacks {
+        let Some(slot) = empty_slots.pop() else {
+            log::warn!("Tried to overfill a container with loot table {name}");
+            break;
+        };
+        slots[slot] = Some(stack);
+    }
+    true
+}
+
+/// Splits the drops into full stacks, then keeps halving random ones while there are free slots
+/// left so the loot spreads over the container, like vanilla's `shuffleAndSplitItems`
+fn split_into_slots(drops: Vec<ItemStack>, slots: usize, rng: &mut impl Rng) -> Vec<ItemStack> {
+    let mut stacks = Vec::new();
+    let mut splittable = Vec::new();
+    for mut drop in drops {
+        let max_stack_size = drop.max_stack_size().max(1);
+        while drop.item_count > max_stack_size {
+            stacks.push(drop.with_count(max_stack_size));
+            drop.item_count -= max_stack_size;
+        }
+        stacks.push(drop);
+    }
+    stacks.retain(|stack| {
+        if stack.item_count > 1 {
+            splittable.push(stack.clone());
+            return false;
+        }
+        stack.item_count > 0
+    });
+    while !splittable.is_empty() && stacks.len() + splittable.len() < slots {
+        let mut stack = splittable.swap_remove(rng.gen_range(0..splittable.len()));
+        let half = stack.with_count(rng.gen_range(1..=stack.item_count / 2));
+        stack.item_count -= half.item_count;
+        for part in [stack, half] {
+            if part.item_count > 1 && rng.gen::<bool>() {
+                splittable.push(part);
+            } else {
+                stacks.push(part);
+            }
+        }
+    }
+    stacks.append(&mut splittable);
+    stacks.shuffle(rng);
+    stacks
+}
+
+/// A list of pools whose drops add up, like vanilla's `LootTable`
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct LootTable {
+    #[serde(default)]
+    pub pools: Vec<LootPool>,
+}
+
+impl LootTable {
+    pub fn generate(&self, context: &LootContext, rng: &mut impl Rng) -> Vec<ItemStack> {
+        let mut drops = Vec::new();
+        for pool in &self.pools {
+            pool.generate(context, rng, &mut drops);
+        }
+        drops
+    }
+}
+
+/// Picks one of its entries for each roll
+#[derive(Deserialize, Clone, Debug)]
+pub struct LootPool {
+    pub rolls: NumberProvider,
+    pub entries: Vec<LootEntry>,
+    #[serde(default)]
+    pub conditions: Vec<LootCondition>,
+    #[serde(default)]
+    pub functions: Vec<LootFunction>,
+}
+
+impl LootPool {
+    fn generate(&self, context: &LootContext, rng: &mut impl Rng, drops: &mut Vec<ItemStack>) {
+        if !LootCondition::all(&self.conditions, context, rng) {
+            return;
+        }
+        let rolls = self.rolls.get_int(rng);
+        for _ in 0..rolls {
+            let candidates: Vec<&LootEntry> = self
+                .entries
+                .iter()
+                .filter_map(|entry| entry.expand(context, rng))
+                .collect();
+            let total_weight: u32 = candidates.iter().map(|entry| entry.weight()).sum();
+            if total_weight == 0 {
+                continue;
+            }
+            let mut pick = rng.gen_range(0..total_weight);
+            let Some(entry) = candidates.into_iter().find(|entry| {
+                if pick < entry.weight() {
+                    return true;
+                }
+                pick -= entry.weight();
+                false
+            }) else {
+                continue;
+            };
+            let Some(drop) = entry.roll(context, rng) else {
+                continue;
+            };
+            let (name, count) = LootFunction::apply_all(&self.functions, drop, context, rng);
+            push_drop(drops, name, count);
+        }
+    }
+}
+
+fn push_drop(drops: &mut Vec<ItemStack>, name: &str, count: i32) {
+    let Some(item) = get_item(name) else {
+        log::warn!("Loot table drops unknown item {name}");
+        return;
+    };
+    if count > 0 {
+        drops.push(ItemStack::new(count.min(i32::from(u8::MAX)) as u8, item.id));
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum LootEntry {
+    #[serde(rename = "minecraft:item")]
+    Item {
+        name: String,
+        #[serde(default = "default_weight")]
+        weight: u32,
+        #[serde(default)]
+        conditions: Vec<LootCondition>,
+        #[serde(default)]
+        functions: Vec<LootFunction>,
+    },
+    /// Drops nothing, to make other entries less likely
+    #[serde(rename = "minecraft:empty")]
+    Empty {
+        #[serde(default = "default_weight")]
+        weight: u32,
+        #[serde(default)]
+        conditions: Vec<LootCondition>,
+    },
+    /// Uses the first child whose conditions are met
+    #[serde(rename = "minecraft:alternatives")]
+    Alternatives {
+        children: Vec<LootEntry>,
+        #[serde(default)]
+        conditions: Vec<LootCondition>,
+    },
+}
+
+const fn default_weight() -> u32 {
+    1
+}
+
+impl LootEntry {
+    /// The entry which takes part in the roll, None if its conditions aren't met
+    fn expand(&self, context: &LootContext, rng: &mut impl Rng) -> Option<&Self> {
+        match self {
+            Self::Item { conditions, .. } | Self::Empty { conditions, .. } => {
+                LootCondition::all(conditions, context, rng).then_some(self)
+            }
+            Self::Alternatives {
+                children,
+                conditions,
+            } => {
+                if !LootCondition::all(conditions, context, rng) {
+                    return None;
+                }
+                children.iter().find_map(|child| child.expand(context, rng))
+            }
+        }
+    }
+
+    const fn weight(&self) -> u32 {
+        match self {
+            Self::Item { weight, .. } | Self::Empty { weight, .. } => *weight,
+            Self::Alternatives { .. } => 0,
+        }
+    }
+
+    /// The item and how many of it drop
+    fn roll(&self, context: &LootContext, rng: &mut impl Rng) -> Option<(&str, i32)> {
+        let Self::Item {
+            name, functions, ..
+        } = self
+        else {
+            return None;
+        };
+        Some(LootFunction::apply_all(
+            functions,
+            (name.as_str(), 1),
+            context,
+            rng,
+        ))
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "condition")]
+pub enum LootCondition {
+    /// Stronger explosions destroy more of the drops
+    #[serde(rename = "minecraft:survives_explosion")]
+    SurvivesExplosion,
+    #[serde(rename = "minecraft:match_tool")]
+    MatchTool { predicate: ToolPredicate },
+    #[serde(rename = "minecraft:inverted")]
+    Inverted { term: Box<LootCondition> },
+    #[serde(rename = "minecraft:any_of")]
+    AnyOf { terms: Vec<LootCondition> },
+    #[serde(rename = "minecraft:random_chance")]
+    RandomChance { chance: f32 },
+    /// A chance which grows with the level of the enchantment, like Fortune on gravel
+    #[serde(rename = "minecraft:table_bonus")]
+    TableBonus {
+        enchantment: String,
+        chances: Vec<f32>,
+    },
+    /// Drops only for players, like rare zombie drops
+    #[serde(rename = "minecraft:killed_by_player")]
+    KilledByPlayer,
+    /// A chance which grows with the level of the enchantment on the weapon of the killer
+    #[serde(rename = "minecraft:random_chance_with_enchanted_bonus")]
+    RandomChanceWithEnchantedBonus {
+        enchantment: String,
+        unenchanted_chance: f32,
+        enchanted_chance: LevelBasedValue,
+    },
+    /// Only whether the mob burns is known about it
+    #[serde(rename = "minecraft:entity_properties")]
+    EntityProperties { predicate: EntityPredicate },
+    /// Conditions depending on things we don't know about the drop are never met
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct EntityPredicate {
+    #[serde(default)]
+    pub flags: EntityFlags,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct EntityFlags {
+    pub is_on_fire: Option<bool>,
+}
+
+/// A value depending on an enchantment level
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum LevelBasedValue {
+    Constant(f32),
+    Linear(LinearValue),
+}
+
+/// Grows by the same amount for each level after the first
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct LinearValue {
+    pub base: f32,
+    pub per_level_above_first: f32,
+}
+
+impl LevelBasedValue {
+    fn get(self, level: i32) -> f32 {
+        match self {
+            Self::Constant(value) => value,
+            Self::Linear(linear) => linear.base + linear.per_level_above_first * (level - 1) as f32,
+        }
+    }
+}
+
+impl LootCondition {
+    fn all(conditions: &[Self], context: &LootContext, rng: &mut impl Rng) -> bool {
+        conditions
+            .iter()
+            .all(|condition| condition.test(context, rng))
+    }
+
+    fn test(&self, context: &LootContext, rng: &mut impl Rng) -> bool {
+        match self {
+            Self::SurvivesExplosion => context
+                .explosion_power
+                .is_none_or(|power| survives_explosion(power, rng)),
+            Self::MatchTool { predicate } => predicate.matches(context),
+            Self::Inverted { term } => !term.test(context, rng),
+            Self::AnyOf { terms } => terms.iter().any(|term| term.test(context, rng)),
+            Self::RandomChance { chance } => rng.gen::<f32>() < *chance,
+            Self::TableBonus {
+                enchantment,
+                chances,
+            } => {
+                let level = context.tool_enchantment_level(enchantment) as usize;
+                chances
+                    .get(level.min(chances.len().saturating_sub(1)))
+                    .is_some_and(|chance| rng.gen::<f32>() < *chance)
+            }
+            Self::KilledByPlayer => context.killed_by_player,
+            Self::RandomChanceWithEnchantedBonus {
+                enchantment,
+                unenchanted_chance,
+                enchanted_chance,
+            } => {
+                let level = context.killer_enchantment_level(enchantment);
+                let chance = if level > 0 {
+                    enchanted_chance.get(level)
+                } else {
+                    *unenchanted_chance
+                };
+                rng.gen::<f32>() < chance
+            }
+            Self::EntityProperties { predicate } => predicate
+                .flags
+                .is_on_fire
+                .is_none_or(|on_fire| on_fire == context.on_fire),
+            Self::Other => false,
+        }
+    }
+}
+
+/// What the tool breaking the block has to be
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ToolPredicate {
+    /// An item id, or a list of them
+    pub items: Option<ItemIds>,
+    #[serde(default)]
+    pub predicates: ItemSubPredicates,
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ItemSubPredicates {
+    #[serde(rename = "minecraft:enchantments", default)]
+    pub enchantments: Vec<EnchantmentPredicate>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicate {
+    pub enchantments: String,
+    pub levels: Option<LevelRange>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct LevelRange {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ItemIds {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ItemIds {
+    fn contains(&self, name: &str) -> bool {
+        match self {
+            Self::One(id) => id == name,
+            Self::Many(ids) => ids.iter().any(|id| id == name),
+        }
+    }
+}
+
+impl ToolPredicate {
+    fn matches(&self, context: &LootContext) -> bool {
+        let Some(tool) = context.tool else {
+            return false;
+        };
+        if let Some(items) = &self.items {
+            let name = crate::item::item_registry::get_item_name_by_id(tool.item_id)
+                .map(|name| format!("minecraft:{name}"));
+            if !name.is_some_and(|name| items.contains(&name)) {
+                return false;
+            }
+        }
+        self.predicates.enchantments.iter().all(|predicate| {
+            let level = context.tool_enchantment_level(&predicate.enchantments);
+            let levels = predicate.levels.unwrap_or_default();
+            level >= levels.min.unwrap_or(1) && levels.max.is_none_or(|max| level <= max)
+        })
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "function")]
+pub enum LootFunction {
+    #[serde(rename = "minecraft:set_count")]
+    SetCount {
+        count: NumberProvider,
+        #[serde(default)]
+        add: bool,
+        #[serde(default)]
+        conditions: Vec<LootCondition>,
+    },
+    /// More drops with Fortune
+    #[serde(rename = "minecraft:apply_bonus")]
+    ApplyBonus {
+        enchantment: String,
+        formula: BonusFormula,
+        #[serde(default)]
+        parameters: BonusParameters,
+    },
+    /// Stronger explosions destroy more of the dropped items
+    #[serde(rename = "minecraft:explosion_decay")]
+    ExplosionDecay,
+    #[serde(rename = "minecraft:limit_count")]
+    LimitCount { limit: CountLimit },
+    /// More drops with Looting on the weapon of the killer
+    #[serde(rename = "minecraft:enchanted_count_increase")]
+    EnchantedCountIncrease {
+        enchantment: String,
+        count: NumberProvider,
+        #[serde(default)]
+        limit: i32,
+    },
+    /// Cooks the drop, like meat of mobs which burned to death
+    #[serde(rename = "minecraft:furnace_smelt")]
+    FurnaceSmelt {
+        #[serde(default)]
+        conditions: Vec<LootCondition>,
+    },
+    /// Functions changing other things than the count
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BonusFormula {
+    /// Multiplies the count, like ores with Fortune
+    #[serde(rename = "minecraft:ore_drops")]
+    OreDrops,
+    /// Adds up to the level times the multiplier
+    #[serde(rename = "minecraft:uniform_bonus_count")]
+    UniformBonusCount,
+    /// Adds one with the probability for each level plus the extra rolls
+    #[serde(rename = "minecraft:binomial_with_bonus_count")]
+    BinomialWithBonusCount,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct BonusParameters {
+    #[serde(rename = "bonusMultiplier", default)]
+    pub bonus_multiplier: i32,
+    #[serde(default)]
+    pub extra: i32,
+    #[serde(default)]
+    pub probability: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct CountLimit {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl LootFunction {
+    /// Changes the item and how many of it drop
+    fn apply_all<'a>(
+        functions: &[Self],
+        drop: (&'a str, i32),
+        context: &LootContext,
+        rng: &mut impl Rng,
+    ) -> (&'a str, i32) {
+        functions.iter().fold(drop, |(name, count), function| {
+            if let Self::FurnaceSmelt { conditions } = function {
+                if LootCondition::all(conditions, context, rng) {
+                    return (smelted(name).unwrap_or(name), count);
+                }
+                return (name, count);
+            }
+            (name, function.apply(count, context, rng))
+        })
+    }
+
+    fn apply(&self, count: i32, context: &LootContext, rng: &mut impl Rng) -> i32 {
+        match self {
+            Self::SetCount {
+                count: provider,
+                add,
+                conditions,
+            } => {
+                if !LootCondition::all(conditions, context, rng) {
+                    return count;
+                }
+                let value = provider.get_int(rng);
+                if *add {
+                    count + value
+                } else {
+                    value
+                }
+            }
+            Self::ApplyBonus {
+                enchantment,
+                formula,
+                parameters,
+            } => {
+                let level = context.tool_enchantment_level(enchantment);
+                formula.apply(count, level, parameters, rng)
+            }
+            Self::ExplosionDecay => {
+                let Some(power) = context.explosion_power else {
+                    return count;
+                };
+                (0..count)
+                    .filter(|_| survives_explosion(power, rng))
+                    .count() as i32
+            }
+            Self::LimitCount { limit } => {
+                let count = limit.min.map_or(count, |min| count.max(min));
+                limit.max.map_or(count, |max| count.min(max))
+            }
+            Self::EnchantedCountIncrease {
+                enchantment,
+                count: provider,
+                limit,
+            } => {
+                let level = context.killer_enchantment_level(enchantment);
+                if level <= 0 {
+                    return count;
+                }
+                let count = count + (level as f32 * provider.get_float(rng)).round() as i32;
+                if *limit > 0 {
+                    count.min(*limit)
+                } else {
+                    count
+                }
+            }
+            Self::FurnaceSmelt { .. } | Self::Other => count,
+        }
+    }
+}
+
+/// What mob drops turn into in a furnace
+fn smelted(name: &str) -> Option<&'static str> {
+    Some(match name.strip_prefix("minecraft:").unwrap_or(name) {
+        "beef" => "minecraft:cooked_beef",
+        "porkchop" => "minecraft:cooked_porkchop",
+        "chicken" => "minecraft:cooked_chicken",
+        "mutton" => "minecraft:cooked_mutton",
+        "rabbit" => "minecraft:cooked_rabbit",
+        "cod" => "minecraft:cooked_cod",
+        "salmon" => "minecraft:cooked_salmon",
+        _ => return None,
+    })
+}
+
+impl BonusFormula {
+    /// Like vanilla's `ApplyBonusCount` formulas
+    fn apply(
+        self,
+        count: i32,
+        level: i32,
+        parameters: &BonusParameters,
+        rng: &mut impl Rng,
+    ) -> i32 {
+        match self {
+            Self::OreDrops => {
+                if level <= 0 {
+                    return count;
+                }
+                let bonus = (rng.gen_range(0..level + 2) - 1).max(0);
+                count * (bonus + 1)
+            }
+            Self::UniformBonusCount => {
+                let max = level * parameters.bonus_multiplier;
+                if max <= 0 {
+                    return count;
+                }
+                count + rng.gen_range(0..=max)
+            }
+            Self::BinomialWithBonusCount => {
+                let bonus = (0..level + parameters.extra)
+                    .filter(|_| rng.gen::<f32>() < parameters.probability)
+                    .count() as i32;
+                count + bonus
+            }
+        }
+    }
+}
+
+/// A number which may be random
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum NumberProvider {
+    Constant(f32),
+    Distribution(Distribution),
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(tag = "type")]
+pub enum Distribution {
+    #[serde(rename = "minecraft:constant")]
+    Constant { value: f32 },
+    #[serde(rename = "minecraft:uniform")]
+    Uniform { min: f32, max: f32 },
+    #[serde(rename = "minecraft:binomial")]
+    Binomial { n: i32, p: f32 },
+}
+
+impl NumberProvider {
+    fn get_float(&self, rng: &mut impl Rng) -> f32 {
+        match *self {
+            Self::Constant(value) | Self::Distribution(Distribution::Constant { value }) => value,
+            Self::Distribution(Distribution::Uniform { min, max }) => {
+                if max <= min {
+                    min
+                } else {
+                    rng.gen_range(min..max)
+                }
+            }
+            Self::Distribution(Distribution::Binomial { .. }) => self.get_int(rng) as f32,
+        }
+    }
+
+    /// A whole number, uniform distributions include both bounds
+    fn get_int(&self, rng: &mut impl Rng) -> i32 {
+        match *self {
+            Self::Constant(value) | Self::Distribution(Distribution::Constant { value }) => {
+                value.round() as i32
+            }
+            Self::Distribution(Distribution::Uniform { min, max }) => {
+                let (min, max) = (min.round() as i32, max.round() as i32);
+                if max <= min {
+                    min
+                } else {
+                    rng.gen_range(min..=max)
+                }
+            }
+            Self::Distribution(Distribution::Binomial { n, p }) => {
+                (0..n).filter(|_| rng.gen::<f32>() < p).count() as i32
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_nbt::compound::NbtCompound;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        block::block_entity::write_items,
+        item::{item_registry::get_item, ItemStack},
+    };
+
+    use super::{
+        block_drops, entity_drops, equipment_drop_chance, fill_container, wear_dropped_equipment,
+        LootContext, BLOCK_LOOT_TABLES,
+    };
+
+    fn item(name: &str) -> u16 {
+        get_item(name).unwrap().id
+    }
+
+    fn enchanted(name: &str, enchantment: &str, level: i32) -> ItemStack {
+        let mut stack = ItemStack::new(1, item(name));
+        stack
+            .components
+            .enchantments
+            .push((enchantment.to_string(), level));
+        stack
+    }
+
+    fn drops(block: &str, context: &LootContext, rng: &mut StdRng) -> Vec<(u16, u8)> {
+        block_drops(block, item(block), context, rng)
+            .into_iter()
+            .map(|stack| (stack.item_id, stack.item_count))
+            .collect()
+    }
+
+    #[test]
+    fn loot_tables_parse() {
+        assert!(!BLOCK_LOOT_TABLES["diamond_ore"].pools.is_empty());
+        // Spawners never drop anything
+        assert!(BLOCK_LOOT_TABLES["spawner"].pools.is_empty());
+    }
+
+    #[test]
+    fn blocks_without_a_table_drop_themselves() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let context = LootContext::default();
+        assert_eq!(drops("dirt", &context, &mut rng), [(item("dirt"), 1)]);
+    }
+
+    #[test]
+    fn silk_touch() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let pickaxe = ItemStack::new(1, item("diamond_pickaxe"));
+        let silk_touch = enchanted("diamond_pickaxe", "silk_touch", 1);
+        let hand = LootContext::default();
+        let plain = LootContext {
+            tool: Some(&pickaxe),
+            ..Default::default()
+        };
+        let silky = LootContext {
+            tool: Some(&silk_touch),
+            ..Default::default()
+        };
+        assert_eq!(drops("stone", &plain, &mut rng), [(item("cobblestone"), 1)]);
+        assert_eq!(drops("stone", &silky, &mut rng), [(item("stone"), 1)]);
+        assert_eq!(
+            drops("diamond_ore", &plain, &mut rng),
+            [(item("diamond"), 1)]
+        );
+        assert_eq!(
+            drops("diamond_ore", &silky, &mut rng),
+            [(item("diamond_ore"), 1)]
+        );
+        assert!(drops("glass", &hand, &mut rng).is_empty());
+        assert_eq!(drops("glass", &silky, &mut rng), [(item("glass"), 1)]);
+    }
+
+    #[test]
+    fn fortune() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let fortune = enchanted("diamond_pickaxe", "fortune", 3);
+        let context = LootContext {
+            tool: Some(&fortune),
+            ..Default::default()
+        };
+        let mut total = 0;
+        for _ in 0..1000 {
+            let drops = drops("diamond_ore", &context, &mut rng);
+            let [(id, count)] = drops[..] else {
+                panic!("diamond ore drops a single stack");
+            };
+            assert_eq!(id, item("diamond"));
+            assert!((1..=4).contains(&count));
+            total += u32::from(count);
+        }
+        // Fortune III drops 2.2 diamonds on average
+        assert!((2000..2400).contains(&total));
+
+        let redstone = drops("redstone_ore", &context, &mut rng);
+        assert!(matches!(redstone[..], [(_, 4..=8)]));
+    }
+
+    #[test]
+    fn explosions_destroy_drops() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let context = LootContext {
+            explosion_power: Some(4.0),
+            ..Default::default()
+        };
+        let survived = (0..1000)
+            .filter(|_| !drops("dirt", &context, &mut rng).is_empty())
+            .count();
+        assert!((150..350).contains(&survived));
+    }
+
+    #[test]
+    fn shulker_boxes_keep_their_items() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut slots = vec![None; 3];
+        slots[2] = Some(ItemStack::new(5, item("stone")));
+        let mut block_entity = NbtCompound::new();
+        write_items(&slots, &mut block_entity);
+        let context = LootContext {
+            block_entity: Some(&block_entity),
+            ..Default::default()
+        };
+        let drops = block_drops(
+            "red_shulker_box",
+            item("red_shulker_box"),
+            &context,
+            &mut rng,
+        );
+        assert_eq!(drops.len(), 1);
+        assert_eq!(drops[0].components.container, slots);
+
+        // Empty shulker boxes stack with new ones
+        let context = LootContext {
+            block_entity: Some(&NbtCompound::new()),
+            ..Default::default()
+        };
+        let drops = block_drops(
+            "red_shulker_box",
+            item("red_shulker_box"),
+            &context,
+            &mut rng,
+        );
+        assert!(drops[0].components.is_empty());
+    }
+
+    fn count(drops: &[ItemStack], name: &str) -> u32 {
+        drops
+            .iter()
+            .filter(|stack| stack.item_id == item(name))
+            .map(|stack| u32::from(stack.item_count))
+            .sum()
+    }
+
+    #[test]
+    fn mob_drops() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let context = LootContext::default();
+        let mut flesh = 0;
+        for _ in 0..1000 {
+            let drops = entity_drops("zombie", &context, &mut rng);
+            let dropped = count(&drops, "rotten_flesh");
+            assert!(dropped <= 2);
+            flesh += dropped;
+            // Only players get the rare drops
+            assert_eq!(count(&drops, "iron_ingot"), 0);
+        }
+        assert!((800..1200).contains(&flesh));
+
+        let sword = enchanted("diamond_sword", "looting", 3);
+        let looting = LootContext {
+            killer_weapon: Some(&sword),
+            killed_by_player: true,
+            ..Default::default()
+        };
+        let looted: u32 = (0..1000)
+            .map(|_| count(&entity_drops("zombie", &looting, &mut rng), "rotten_flesh"))
+            .sum();
+        assert!(looted > flesh + 1000);
+
+        let skulls: u32 = (0..10_000)
+            .map(|_| {
+                count(
+                    &entity_drops("wither_skeleton", &looting, &mut rng),
+                    "wither_skeleton_skull",
+                )
+            })
+            .sum();
+        // Looting III raises the chance from 2.5 to 5.5 percent
+        assert!((400..700).contains(&skulls));
+        assert!(entity_drops("wither_skeleton", &context, &mut rng)
+            .iter()
+            .all(|stack| stack.item_id != item("wither_skeleton_skull")));
+
+        assert!(entity_drops("bat", &context, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn burning_mobs_drop_cooked_meat() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let burning = LootContext {
+            on_fire: true,
+            ..Default::default()
+        };
+        let drops = entity_drops("cow", &burning, &mut rng);
+        assert!(count(&drops, "cooked_beef") >= 1);
+        assert_eq!(count(&drops, "beef"), 0);
+        let drops = entity_drops("cow", &LootContext::default(), &mut rng);
+        assert!(count(&drops, "beef") >= 1);
+    }
+
+    #[test]
+    fn dropped_equipment() {
+        assert!((equipment_drop_chance(0) - 0.085).abs() < f32::EPSILON);
+        assert!((equipment_drop_chance(3) - 0.115).abs() < 1e-6);
+        let mut rng = StdRng::seed_from_u64(0);
+        let helmet = get_item("iron_helmet").unwrap();
+        let max_damage = i32::from(helmet.components.max_damage.unwrap());
+        for _ in 0..100 {
+            let mut stack = ItemStack::new(1, helmet.id);
+            wear_dropped_equipment(&mut stack, &mut rng);
+            let damage = stack.components.damage.unwrap();
+            assert!((0..max_damage).contains(&damage));
+        }
+    }
+
+    #[test]
+    fn chest_loot() {
+        let mut slots = vec![None; 27];
+        let stone = ItemStack::new(1, item("stone"));
+        slots[0] = Some(stone.clone());
+        assert!(fill_container(
+            &mut slots,
+            "minecraft:chests/simple_dungeon",
+            42
+        ));
+        // Items already in the chest stay
+        assert_eq!(slots[0], Some(stone));
+        let filled = slots[1..].iter().flatten().count();
+        assert!(filled > 0);
+        assert!(slots
+            .iter()
+            .flatten()
+            .all(|stack| stack.item_count > 0 && stack.item_count <= stack.max_stack_size()));
+
+        // The same seed rolls the same loot
+        let contents = |slots: &[Option<ItemStack>]| -> Vec<Option<(u16, u8)>> {
+            slots
+                .iter()
+                .map(|slot| slot.as_ref().map(|stack| (stack.item_id, stack.item_count)))
+                .collect()
+        };
+        let mut again = vec![None; 27];
+        again[0] = slots[0].clone();
+        fill_container(&mut again, "chests/simple_dungeon", 42);
+        assert_eq!(contents(&slots), contents(&again));
+
+        let mut slots = vec![None; 27];
+        assert!(!fill_container(&mut slots, "chests/unknown", 42));
+        assert!(slots.iter().all(Option::is_none));
+    }
+}
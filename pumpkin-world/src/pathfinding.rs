@@ -0,0 +1,754 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    position::WorldPosition,
+    vector3::Vector3,
+};
+
+use crate::{
+    block::{
+        block_registry::get_block_by_state_id,
+        fluid::{fluid_state, Fluid},
+        random_tick::{property, BlockAccess},
+    },
+    physics::block_collision_boxes,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+/// Boxes touching blocks only at their faces don't collide with them
+const EPSILON: f64 = 1.0E-7;
+/// Blocks at most this high are stood on from inside their block, like slabs and carpets
+const LOW_BLOCK_HEIGHT: f64 = 0.5;
+/// How far apart the points are which are checked when a mob walks straight between waypoints
+const SMOOTHING_STEP: f64 = 0.25;
+const HORIZONTAL_DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+/// What a mob walks through at a position, like vanilla's `PathType`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathType {
+    /// Nothing to stand on, mobs fall through
+    Open,
+    /// Ground to stand on
+    Walkable,
+    /// Blocks in the way
+    Blocked,
+    Water,
+    Lava,
+    /// Fences and walls, too high to jump over
+    Fence,
+    Leaves,
+    DoorWoodClosed,
+    DoorIronClosed,
+    DoorOpen,
+    /// Fire and hot blocks which set mobs on fire
+    DamageFire,
+    /// Blocks which prick mobs, like cacti
+    DamageOther,
+}
+
+impl PathType {
+    /// How much mobs avoid walking through, `None` for what they never walk through, like
+    /// vanilla's default `PathType.getMalus`
+    pub const fn malus(self) -> Option<f64> {
+        match self {
+            Self::Open | Self::Walkable | Self::DoorOpen => Some(0.0),
+            Self::Water => Some(8.0),
+            Self::DamageFire => Some(16.0),
+            Self::Blocked
+            | Self::Lava
+            | Self::Fence
+            | Self::Leaves
+            | Self::DoorWoodClosed
+            | Self::DoorIronClosed
+            | Self::DamageOther => None,
+        }
+    }
+}
+
+/// What the block state is for mobs walking through it. Blocks without anything special about
+/// them are `Open`, their collision shape decides whether mobs fit
+pub(crate) fn block_path_type(state_id: u16) -> PathType {
+    let Some(block) = get_block_by_state_id(state_id) else {
+        return PathType::Open;
+    };
+    let name = block.name.as_str();
+    let open = property(block, state_id, "open") == Some("true");
+    match name {
+        "fire" | "soul_fire" | "magma_block" => PathType::DamageFire,
+        "campfire" | "soul_campfire" if property(block, state_id, "lit") == Some("true") => {
+            PathType::DamageFire
+        }
+        "cactus" | "sweet_berry_bush" => PathType::DamageOther,
+        "iron_door" if !open => PathType::DoorIronClosed,
+        _ if name.ends_with("_door") => {
+            if open {
+                PathType::DoorOpen
+            } else {
+                PathType::DoorWoodClosed
+            }
+        }
+        _ if name.ends_with("_fence_gate") => {
+            if open {
+                PathType::Open
+            } else {
+                PathType::Fence
+            }
+        }
+        _ if name.ends_with("_fence") || name.ends_with("_wall") => PathType::Fence,
+        _ if name.ends_with("_leaves") => PathType::Leaves,
+        _ => match fluid_state(state_id) {
+            Some(fluid) if fluid.fluid == Fluid::Lava => PathType::Lava,
+            Some(_) => PathType::Water,
+            None => PathType::Open,
+        },
+    }
+}
+
+/// The block positions the box is inside of
+fn blocks_in(bounding_box: &BoundingBox) -> impl Iterator<Item = WorldPosition> {
+    let area = bounding_box.inflate(-EPSILON);
+    let (min_y, max_y) = (
+        (area.min_y.floor() as i32).max(i32::from(WORLD_LOWEST_Y)),
+        (area.max_y.floor() as i32).min(i32::from(WORLD_MAX_Y) - 1),
+    );
+    (area.min_x.floor() as i32..=area.max_x.floor() as i32).flat_map(move |x| {
+        (min_y..=max_y).flat_map(move |y| {
+            (area.min_z.floor() as i32..=area.max_z.floor() as i32)
+                .map(move |z| WorldPosition(Vector3::new(x, y, z)))
+        })
+    })
+}
+
+fn block_of(position: Vector3<f64>) -> WorldPosition {
+    WorldPosition(Vector3::new(
+        position.x.floor() as i32,
+        position.y.floor() as i32,
+        position.z.floor() as i32,
+    ))
+}
+
+/// Where a mob stands with its feet in a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Node {
+    position: WorldPosition,
+    /// The height the mob stands at, above the block for low blocks like slabs
+    floor: f64,
+    path_type: PathType,
+}
+
+impl Node {
+    /// Where the mob walks to, in the middle of the block
+    fn waypoint(&self) -> Vector3<f64> {
+        Vector3::new(
+            f64::from(self.position.0.x) + 0.5,
+            self.floor,
+            f64::from(self.position.0.z) + 0.5,
+        )
+    }
+}
+
+/// How a mob finds its way through the blocks around it, like vanilla's `WalkNodeEvaluator`
+/// with the settings of its navigation
+#[derive(Clone, Copy)]
+pub struct NodeEvaluator {
+    pub size: BoundingBoxSize,
+    /// How much higher the ground mobs jump onto is at most
+    pub jump_height: f64,
+    /// How many blocks mobs drop down at most
+    pub max_fall_distance: i32,
+    /// Whether mobs walk through closed wooden doors, opening them
+    pub can_open_doors: bool,
+    /// Whether mobs swim up through deep water instead of walking on the bottom
+    pub can_swim: bool,
+}
+
+impl NodeEvaluator {
+    /// How mobs of the size find their way by default, they jump a block up and drop three
+    /// blocks down
+    pub const fn new(size: BoundingBoxSize) -> Self {
+        Self {
+            size,
+            jump_height: 1.125,
+            max_fall_distance: 3,
+            can_open_doors: false,
+            can_swim: false,
+        }
+    }
+
+    /// How much the mob avoids walking through, `None` for what it never walks through
+    fn malus(&self, path_type: PathType) -> Option<f64> {
+        if path_type == PathType::DoorWoodClosed && self.can_open_doors {
+            return Some(0.0);
+        }
+        path_type.malus()
+    }
+
+    fn can_stand_on(&self, node: &Node) -> bool {
+        node.path_type != PathType::Open && self.malus(node.path_type).is_some()
+    }
+
+    /// The box of the mob standing in the middle of the block column at the height
+    fn bounding_box(&self, x: i32, z: i32, y: f64) -> BoundingBox {
+        BoundingBox::new_from_pos(f64::from(x) + 0.5, y, f64::from(z) + 0.5, &self.size)
+    }
+
+    /// Whether the box hits the collision shape of a block, closed doors don't stop mobs opening
+    /// them. Blocks which are not loaded are solid
+    fn collides(&self, blocks: &impl BlockAccess, bounding_box: &BoundingBox) -> bool {
+        let area = bounding_box.inflate(-EPSILON);
+        blocks_in(bounding_box).any(|position| {
+            let Some(state_id) = blocks.block_state(&position) else {
+                return true;
+            };
+            if self.can_open_doors && block_path_type(state_id) == PathType::DoorWoodClosed {
+                return false;
+            }
+            block_collision_boxes(state_id, &position)
+                .iter()
+                .any(|block_box| block_box.intersects(&area))
+        })
+    }
+
+    /// The path type of the blocks in the box the mob avoids most
+    fn path_type_in(&self, blocks: &impl BlockAccess, bounding_box: &BoundingBox) -> PathType {
+        let mut worst = PathType::Open;
+        for position in blocks_in(bounding_box) {
+            let path_type = blocks
+                .block_state(&position)
+                .map_or(PathType::Blocked, block_path_type);
+            match (self.malus(path_type), self.malus(worst)) {
+                (None, _) => return path_type,
+                (Some(malus), Some(worst_malus)) if malus > worst_malus => worst = path_type,
+                _ => {}
+            }
+        }
+        worst
+    }
+
+    /// The height the mob stands at with its feet in the block, on top of the blocks below or of
+    /// low blocks like slabs. `None` without ground
+    fn floor(&self, blocks: &impl BlockAccess, x: i32, y: i32, z: i32) -> Option<f64> {
+        let mut area = self.bounding_box(x, z, f64::from(y) - 1.0);
+        area.max_y = f64::from(y) + LOW_BLOCK_HEIGHT;
+        let inner = area.inflate(-EPSILON);
+        blocks_in(&area)
+            .filter_map(|position| {
+                let state_id = blocks.block_state(&position)?;
+                // Taller blocks are in the way instead
+                block_collision_boxes(state_id, &position)
+                    .into_iter()
+                    .filter(|block_box| {
+                        block_box.intersects(&inner) && block_box.max_y <= area.max_y + EPSILON
+                    })
+                    .map(|block_box| block_box.max_y)
+                    .max_by(f64::total_cmp)
+            })
+            .max_by(f64::total_cmp)
+    }
+
+    /// What the mob finds with its feet in the block
+    fn evaluate(&self, blocks: &impl BlockAccess, x: i32, y: i32, z: i32) -> Node {
+        let position = WorldPosition(Vector3::new(x, y, z));
+        let node = |floor, path_type| Node {
+            position,
+            floor,
+            path_type,
+        };
+        let floor = self.floor(blocks, x, y, z);
+        let height = floor.unwrap_or(f64::from(y));
+        let bounding_box = self.bounding_box(x, z, height);
+        let inside = self.path_type_in(blocks, &bounding_box);
+        if self.malus(inside).is_none() {
+            return node(height, inside);
+        }
+        if self.collides(blocks, &bounding_box) {
+            return node(height, PathType::Blocked);
+        }
+        let below = blocks
+            .block_state(&WorldPosition(Vector3::new(x, y - 1, z)))
+            .map_or(PathType::Open, block_path_type);
+        let path_type = match floor {
+            // Mobs standing on magma blocks or campfires burn
+            Some(_) if below == PathType::DamageFire => PathType::DamageFire,
+            Some(_) if inside == PathType::Open => PathType::Walkable,
+            Some(_) => inside,
+            None if inside == PathType::Water && self.can_swim => PathType::Water,
+            None => PathType::Open,
+        };
+        node(height, path_type)
+    }
+
+    /// Where the mob ends up walking from the node into the block column next to it, like
+    /// vanilla's `findAcceptedNode`. Mobs jump onto blocks in the way and drop down ledges
+    fn step(&self, blocks: &impl BlockAccess, from: &Node, x: i32, z: i32) -> Option<Node> {
+        let y = from.position.0.y;
+        let node = self.evaluate(blocks, x, y, z);
+        if node.path_type == PathType::Blocked {
+            let above = self.evaluate(blocks, x, y + 1, z);
+            // There has to be room above the mob to jump
+            let room = !self.collides(
+                blocks,
+                &self.bounding_box(from.position.0.x, from.position.0.z, above.floor),
+            );
+            return (room
+                && self.can_stand_on(&above)
+                && above.floor - from.floor <= self.jump_height)
+                .then_some(above);
+        }
+        self.malus(node.path_type)?;
+        if node.path_type != PathType::Open {
+            return (node.floor - from.floor <= self.jump_height).then_some(node);
+        }
+        (1..=self.max_fall_distance)
+            .map(|drop| self.evaluate(blocks, x, y - drop, z))
+            .find(|below| below.path_type != PathType::Open)
+            .filter(|below| self.can_stand_on(below))
+    }
+
+    /// The nodes the mob walks to from the node, diagonal ones only when nothing is in the way
+    fn neighbours(&self, blocks: &impl BlockAccess, from: &Node) -> Vec<Node> {
+        let Vector3 { x, y, z } = from.position.0;
+        let level = |node: &Option<Node>| node.filter(|node| node.position.0.y == y);
+        let mut neighbours = Vec::new();
+        let cardinals =
+            HORIZONTAL_DIRECTIONS.map(|(dx, dz)| self.step(blocks, from, x + dx, z + dz));
+        neighbours.extend(cardinals.iter().flatten());
+        for (dx, dz) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let side_x = cardinals[if dx > 0 { 0 } else { 1 }];
+            let side_z = cardinals[if dz > 0 { 2 } else { 3 }];
+            if level(&side_x).is_none() || level(&side_z).is_none() {
+                continue;
+            }
+            neighbours.extend(level(&self.step(blocks, from, x + dx, z + dz)));
+        }
+        if from.path_type == PathType::Water && self.can_swim {
+            let above = self.evaluate(blocks, x, y + 1, z);
+            if self.can_stand_on(&above) {
+                neighbours.push(above);
+            }
+        }
+        neighbours
+    }
+
+    /// Whether the mob walks in a straight line between the points without jumping, falling or
+    /// passing anything it avoids
+    fn can_walk_directly(
+        &self,
+        blocks: &impl BlockAccess,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+    ) -> bool {
+        if (from.y - to.y).abs() > EPSILON {
+            return false;
+        }
+        let distance = to.sub(&from);
+        let steps = ((distance.x.hypot(distance.z) / SMOOTHING_STEP).ceil() as i32).max(1);
+        (0..=steps).all(|step| {
+            let point = from + distance * (f64::from(step) / f64::from(steps));
+            let bounding_box = BoundingBox::new_from_pos(point.x, point.y, point.z, &self.size);
+            let ground = block_of(point).0;
+            let node = self.evaluate(blocks, ground.x, ground.y, ground.z);
+            !self.collides(blocks, &bounding_box)
+                && self.malus(self.path_type_in(blocks, &bounding_box)) == Some(0.0)
+                && node.path_type == PathType::Walkable
+                && (node.floor - point.y).abs() <= EPSILON
+        })
+    }
+
+    /// Leaves out the waypoints the mob walks past anyway when it walks straight to the ones
+    /// after them
+    fn smooth(
+        &self,
+        blocks: &impl BlockAccess,
+        start: Vector3<f64>,
+        waypoints: &[Vector3<f64>],
+    ) -> Vec<Vector3<f64>> {
+        let mut smoothed = Vec::new();
+        let mut anchor = start;
+        for (index, waypoint) in waypoints.iter().enumerate() {
+            if waypoints
+                .get(index + 1)
+                .is_some_and(|next| self.can_walk_directly(blocks, anchor, *next))
+            {
+                continue;
+            }
+            smoothed.push(*waypoint);
+            anchor = *waypoint;
+        }
+        smoothed
+    }
+}
+
+/// The way a mob walks, like vanilla's `Path`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    /// Where the mob walks to one after another, at the height it stands at
+    pub waypoints: Vec<Vector3<f64>>,
+    /// The block the path leads to
+    pub target: WorldPosition,
+    /// Whether the path ends at the target, otherwise it ends as close to it as the mob gets
+    pub reached: bool,
+}
+
+impl Path {
+    /// Whether the path was found to the block the position is in
+    pub fn leads_to(&self, position: Vector3<f64>) -> bool {
+        self.target == block_of(position)
+    }
+}
+
+/// A node waiting to be visited, the cheapest estimated path is visited first
+struct OpenNode {
+    estimate: f64,
+    position: WorldPosition,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+struct VisitedNode {
+    node: Node,
+    parent: Option<WorldPosition>,
+    cost: f64,
+    closed: bool,
+}
+
+/// Finds the way for a mob from where it stands to the target with A*, like vanilla's
+/// `PathFinder`. Mobs avoid what costs them, like water and fire, and never walk through lava or
+/// over fences. When the target can't be reached the path leads as close to it as possible,
+/// `None` when the mob can't get any closer. At most `max_visited` nodes are looked at
+pub fn find_path(
+    blocks: &impl BlockAccess,
+    evaluator: &NodeEvaluator,
+    from: Vector3<f64>,
+    to: Vector3<f64>,
+    max_visited: usize,
+) -> Option<Path> {
+    let start_position = block_of(from);
+    let start = Some(evaluator.evaluate(
+        blocks,
+        start_position.0.x,
+        start_position.0.y,
+        start_position.0.z,
+    ))
+    .filter(|start| evaluator.can_stand_on(start))
+    // Mobs jumping or falling start where they are
+    .unwrap_or(Node {
+        position: start_position,
+        floor: from.y,
+        path_type: PathType::Walkable,
+    });
+    let target = block_of(to);
+    let heuristic = |node: &Node| node.waypoint().sub(&to).length();
+
+    let mut visited = HashMap::from([(
+        start.position,
+        VisitedNode {
+            node: start,
+            parent: None,
+            cost: 0.0,
+            closed: false,
+        },
+    )]);
+    let mut open = BinaryHeap::from([OpenNode {
+        estimate: heuristic(&start),
+        position: start.position,
+    }]);
+    let mut closest = (start.position, heuristic(&start));
+    let mut reached = false;
+    let mut visited_count = 0;
+    while let Some(OpenNode { position, .. }) = open.pop() {
+        let current = visited.get_mut(&position)?;
+        if current.closed {
+            continue;
+        }
+        current.closed = true;
+        let (node, cost) = (current.node, current.cost);
+        let distance = heuristic(&node);
+        if distance < closest.1 {
+            closest = (position, distance);
+        }
+        if position == target {
+            closest = (position, distance);
+            reached = true;
+            break;
+        }
+        visited_count += 1;
+        if visited_count >= max_visited {
+            break;
+        }
+        for neighbour in evaluator.neighbours(blocks, &node) {
+            let malus = evaluator.malus(neighbour.path_type).unwrap_or(0.0);
+            let neighbour_cost = cost + neighbour.waypoint().sub(&node.waypoint()).length() + malus;
+            if visited
+                .get(&neighbour.position)
+                .is_some_and(|known| known.closed || known.cost <= neighbour_cost)
+            {
+                continue;
+            }
+            visited.insert(
+                neighbour.position,
+                VisitedNode {
+                    node: neighbour,
+                    parent: Some(position),
+                    cost: neighbour_cost,
+                    closed: false,
+                },
+            );
+            open.push(OpenNode {
+                estimate: neighbour_cost + heuristic(&neighbour),
+                position: neighbour.position,
+            });
+        }
+    }
+
+    if closest.0 == start.position {
+        return None;
+    }
+    let mut waypoints = Vec::new();
+    let mut position = Some(closest.0);
+    while let Some(current) = position.filter(|position| *position != start.position) {
+        let visited = &visited[&current];
+        waypoints.push(visited.node.waypoint());
+        position = visited.parent;
+    }
+    waypoints.reverse();
+    Some(Path {
+        waypoints: evaluator.smooth(blocks, from, &waypoints),
+        target,
+        reached,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{boundingbox::BoundingBoxSize, vector3::Vector3};
+
+    use crate::block::random_tick::tests::{pos, TestBlocks};
+
+    use super::{find_path, NodeEvaluator};
+
+    const ZOMBIE: BoundingBoxSize = BoundingBoxSize {
+        width: 0.6,
+        height: 1.95,
+    };
+
+    fn floor(world: &mut TestBlocks, name: &str) {
+        for x in -8..=8 {
+            for z in -8..=8 {
+                world.set(x, -1, z, name);
+            }
+        }
+    }
+
+    fn wall(world: &mut TestBlocks, x: i32, height: i32, name: &str) {
+        for z in -8..=8 {
+            for y in 0..height {
+                world.set(x, y, z, name);
+            }
+        }
+    }
+
+    fn at(x: f64, y: f64, z: f64) -> Vector3<f64> {
+        Vector3::new(x, y, z)
+    }
+
+    #[test]
+    fn mobs_walk_straight_over_open_ground() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        let evaluator = NodeEvaluator::new(ZOMBIE);
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(5.5, 0.0, 3.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        // Nothing is in the way, so the mob walks straight to the target
+        assert_eq!(path.waypoints, vec![at(5.5, 0.0, 3.5)]);
+    }
+
+    #[test]
+    fn mobs_jump_one_block_up() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        wall(&mut world, 2, 1, "stone");
+        let evaluator = NodeEvaluator::new(ZOMBIE);
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(2.5, 1.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        assert_eq!(path.waypoints.last(), Some(&at(2.5, 1.0, 0.5)));
+
+        // Two blocks are too high, the mob gets as close as it can
+        wall(&mut world, 2, 2, "stone");
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(2.5, 2.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(!path.reached);
+        assert_eq!(path.waypoints.last(), Some(&at(1.5, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn mobs_walk_around_walls_through_gaps() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        wall(&mut world, 2, 3, "stone");
+        world.blocks.remove(&pos(2, 0, 4));
+        world.blocks.remove(&pos(2, 1, 4));
+        let evaluator = NodeEvaluator::new(ZOMBIE);
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(4.5, 0.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        assert!(path.waypoints.iter().any(|waypoint| waypoint.z > 4.0));
+
+        // Mobs wider than the gap don't fit through it
+        let wide = NodeEvaluator::new(BoundingBoxSize {
+            width: 1.4,
+            height: 1.4,
+        });
+        let path = find_path(&world, &wide, at(0.5, 0.0, 0.5), at(4.5, 0.0, 0.5), 1000);
+        assert!(path.is_none_or(|path| !path.reached));
+    }
+
+    #[test]
+    fn mobs_avoid_lava_and_water() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        wall(&mut world, 2, 1, "lava");
+        world.set(2, 0, 5, "stone");
+        world.set(2, 1, 5, "air");
+        let evaluator = NodeEvaluator::new(ZOMBIE);
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(4.5, 0.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        assert!(path.waypoints.contains(&at(2.5, 1.0, 5.5)));
+
+        // Walking through water costs less than the long way around
+        wall(&mut world, 2, 1, "water");
+        world.set(2, 0, 5, "stone");
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(4.5, 0.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        assert!(!path.waypoints.contains(&at(2.5, 1.0, 5.5)));
+    }
+
+    #[test]
+    fn only_mobs_opening_doors_walk_through_closed_doors() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        wall(&mut world, 2, 3, "stone");
+        world.set(2, 0, 0, "oak_door");
+        world.set(2, 1, 0, "air");
+        let mut evaluator = NodeEvaluator::new(ZOMBIE);
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(4.5, 0.0, 0.5),
+            1000,
+        );
+        assert!(path.is_none_or(|path| !path.reached));
+
+        evaluator.can_open_doors = true;
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(0.5, 0.0, 0.5),
+            at(4.5, 0.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+    }
+
+    #[test]
+    fn mobs_drop_down_ledges() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, "stone");
+        for x in -8..=8 {
+            for z in -8..=8 {
+                world.set(x, 2, z, "stone");
+            }
+        }
+        world.blocks.remove(&pos(0, 2, 0));
+        let evaluator = NodeEvaluator::new(ZOMBIE);
+        // Standing on the upper floor the mob drops down the hole
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(1.5, 3.0, 0.5),
+            at(0.5, 0.0, 0.5),
+            1000,
+        )
+        .unwrap();
+        assert!(path.reached);
+        assert_eq!(path.waypoints.last(), Some(&at(0.5, 0.0, 0.5)));
+
+        // Mobs don't jump down further than they may fall
+        let evaluator = NodeEvaluator {
+            max_fall_distance: 2,
+            ..evaluator
+        };
+        let path = find_path(
+            &world,
+            &evaluator,
+            at(1.5, 3.0, 0.5),
+            at(0.5, 0.0, 0.5),
+            1000,
+        );
+        assert!(path.is_none_or(|path| !path.reached));
+    }
+}
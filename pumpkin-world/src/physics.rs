@@ -0,0 +1,1052 @@
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    position::WorldPosition,
+    vector3::Vector3,
+};
+
+use crate::{
+    block::{
+        block_registry::{get_block_and_state_by_state_id, get_state_by_state_id, BLOCKS},
+        fluid::{fluid_state, Fluid, FluidState},
+        random_tick::{offset, BlockAccess},
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+/// Movements smaller than this count as no movement, so entities resting on blocks stay put
+const EPSILON: f64 = 1.0E-7;
+/// Entities this far below the bottom of the world are removed
+const BELOW_WORLD_DISTANCE: f64 = 64.0;
+/// Horizontal velocity entities keep on most blocks
+const DEFAULT_FRICTION: f64 = 0.6;
+/// How strongly flowing water pushes entities every tick
+const WATER_PUSH: f64 = 0.014;
+/// How strongly flowing lava pushes entities every tick
+const LAVA_PUSH: f64 = 0.002_333_333;
+/// Entities which barely move are pushed at least this strongly, so they don't get stuck in fluids
+const MIN_FLUID_PUSH: f64 = 0.0045;
+/// Velocity swimming entities keep every tick, like vanilla's `getWaterSlowDown`
+const WATER_DRAG: f64 = 0.8;
+
+/// How an entity type moves when nothing but gravity, drag and the blocks around it act on it,
+/// like the constants in vanilla's entity classes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MovementProperties {
+    /// Blocks per tick squared the entity accelerates downwards
+    pub gravity: f64,
+    /// Vertical velocity kept every tick, the rest is lost to drag
+    pub drag: f64,
+    /// Horizontal velocity kept every tick
+    pub horizontal_drag: f64,
+    /// Horizontal velocity kept on the ground on top of the drag, `None` uses how slippery the
+    /// block below is, so items slide far on ice
+    pub ground_friction: Option<f64>,
+    /// How high the blocks are the entity walks up without jumping, like slabs for mobs
+    pub step_height: f64,
+    /// Whether the entity floats up in water instead of sinking, like items
+    pub floats: bool,
+    /// Whether the entity swims in water, sinking slowly and being slowed down like living
+    /// entities in vanilla's `travelInFluid`
+    pub swims: bool,
+}
+
+impl MovementProperties {
+    pub const ITEM: Self = Self {
+        gravity: 0.04,
+        drag: 0.98,
+        horizontal_drag: 0.98,
+        ground_friction: None,
+        step_height: 0.0,
+        floats: true,
+        swims: false,
+    };
+
+    pub const EXPERIENCE_ORB: Self = Self {
+        gravity: 0.03,
+        ..Self::ITEM
+    };
+
+    pub const PRIMED_TNT: Self = Self {
+        gravity: 0.04,
+        drag: 0.98,
+        horizontal_drag: 0.98,
+        ground_friction: Some(0.7),
+        step_height: 0.0,
+        floats: false,
+        swims: false,
+    };
+
+    pub const FALLING_BLOCK: Self = Self {
+        ground_friction: Some(1.0),
+        ..Self::PRIMED_TNT
+    };
+
+    pub const LIVING: Self = Self {
+        gravity: 0.08,
+        drag: 0.98,
+        horizontal_drag: 0.91,
+        ground_friction: None,
+        step_height: 0.6,
+        floats: false,
+        swims: true,
+    };
+}
+
+/// How deep an entity is in a fluid
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FluidContact {
+    /// How far the fluid reaches above the bottom of the entity
+    pub height: f64,
+    /// The direction the fluid flows in, zero in still fluid
+    pub flow: Vector3<f64>,
+}
+
+/// What happened to an entity during a tick of movement
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Movement {
+    /// How far the entity moved, blocks in the way shorten it
+    pub motion: Vector3<f64>,
+    /// The velocity for the next tick
+    pub velocity: Vector3<f64>,
+    pub on_ground: bool,
+    /// Whether a block stopped the entity sideways
+    pub horizontal_collision: bool,
+    /// Whether a block stopped the entity from rising or falling
+    pub vertical_collision: bool,
+    /// How deep the entity was in water before moving
+    pub water: Option<FluidContact>,
+    /// How deep the entity was in lava before moving
+    pub lava: Option<FluidContact>,
+}
+
+/// Whether the entity fell so far below the world that it should be removed
+pub fn is_below_world(y: f64) -> bool {
+    y < f64::from(WORLD_LOWEST_Y) - BELOW_WORLD_DISTANCE
+}
+
+/// The collision boxes of the block state placed at the position
+pub fn block_collision_boxes(state_id: u16, position: &WorldPosition) -> Vec<BoundingBox> {
+    let Some(state) = get_state_by_state_id(state_id) else {
+        return Vec::new();
+    };
+    let base = position.0;
+    state
+        .collision_shapes
+        .iter()
+        .filter_map(|shape| BLOCKS.shapes.get(usize::from(*shape)))
+        .map(|shape| {
+            BoundingBox::new(
+                Vector3::new(
+                    f64::from(base.x) + f64::from(shape.min[0]),
+                    f64::from(base.y) + f64::from(shape.min[1]),
+                    f64::from(base.z) + f64::from(shape.min[2]),
+                ),
+                Vector3::new(
+                    f64::from(base.x) + f64::from(shape.max[0]),
+                    f64::from(base.y) + f64::from(shape.max[1]),
+                    f64::from(base.z) + f64::from(shape.max[2]),
+                ),
+            )
+        })
+        .collect()
+}
+
+/// The collision boxes of the blocks touching the area. Blocks which are not loaded are solid so
+/// entities don't fall into chunks being generated, outside of the world nothing collides
+pub(crate) fn collision_boxes(blocks: &impl BlockAccess, area: &BoundingBox) -> Vec<BoundingBox> {
+    let mut boxes = Vec::new();
+    // One block further down, for fences and walls which are taller than a block
+    for x in area.min_x.floor() as i32..=area.max_x.floor() as i32 {
+        for y in area.min_y.floor() as i32 - 1..=area.max_y.floor() as i32 {
+            if !(i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y)).contains(&y) {
+                continue;
+            }
+            for z in area.min_z.floor() as i32..=area.max_z.floor() as i32 {
+                let position = WorldPosition(Vector3::new(x, y, z));
+                match blocks.block_state(&position) {
+                    Some(state_id) => boxes.extend(
+                        block_collision_boxes(state_id, &position)
+                            .into_iter()
+                            .filter(|block_box| block_box.intersects(area)),
+                    ),
+                    None => boxes.push(BoundingBox::from_block(&position)),
+                }
+            }
+        }
+    }
+    boxes
+}
+
+/// The states of the loaded blocks the box is inside of, like the blocks vanilla's
+/// `checkInsideBlocks` calls `entityInside` on, e.g. cactus pricking items
+pub fn blocks_inside(blocks: &impl BlockAccess, bounding_box: &BoundingBox) -> Vec<u16> {
+    let area = bounding_box.inflate(-EPSILON);
+    let mut states = Vec::new();
+    for x in area.min_x.floor() as i32..=area.max_x.floor() as i32 {
+        for y in area.min_y.floor() as i32..=area.max_y.floor() as i32 {
+            for z in area.min_z.floor() as i32..=area.max_z.floor() as i32 {
+                let position = WorldPosition(Vector3::new(x, y, z));
+                states.extend(blocks.block_state(&position));
+            }
+        }
+    }
+    states
+}
+
+/// Whether an entity of the size fits at the position and has ground below it to stand on, like
+/// the positions vanilla's `RandomPos` finds for mobs to walk to. Mobs avoiding fluids don't
+/// stand in them
+pub fn can_stand_at(
+    blocks: &impl BlockAccess,
+    position: Vector3<f64>,
+    size: &BoundingBoxSize,
+    avoid_fluids: bool,
+) -> bool {
+    let bounding_box = BoundingBox::new_from_pos(position.x, position.y, position.z, size);
+    if !collision_boxes(blocks, &bounding_box.inflate(-EPSILON)).is_empty() {
+        return false;
+    }
+    if avoid_fluids
+        && blocks_inside(blocks, &bounding_box)
+            .into_iter()
+            .any(|state_id| fluid_state(state_id).is_some())
+    {
+        return false;
+    }
+    let ground = BoundingBox {
+        min_y: position.y - 0.01,
+        max_y: position.y,
+        ..bounding_box
+    };
+    !collision_boxes(blocks, &ground).is_empty()
+}
+
+/// The spots around a respawn block players stand up at, the sides before the corners
+const STAND_UP_OFFSETS: [(i32, i32); 8] = [
+    (0, -1),
+    (-1, 0),
+    (0, 1),
+    (1, 0),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// Where a player of the size stands up at their bed or respawn anchor: the first free spot
+/// around the block on its level, below and above, or else on top of it. Like vanilla's
+/// `RespawnAnchorBlock.findStandUpPosition`, `None` if the block is walled in
+pub fn stand_up_position(
+    blocks: &impl BlockAccess,
+    respawn_block: &WorldPosition,
+    size: &BoundingBoxSize,
+) -> Option<Vector3<f64>> {
+    for y in [0, -1, 1] {
+        for (x, z) in STAND_UP_OFFSETS {
+            let feet = offset(respawn_block, x, y, z).0;
+            let position = Vector3::new(
+                f64::from(feet.x) + 0.5,
+                f64::from(feet.y),
+                f64::from(feet.z) + 0.5,
+            );
+            if can_stand_at(blocks, position, size, true) {
+                return Some(position);
+            }
+        }
+    }
+    let top = blocks
+        .block_state(respawn_block)
+        .map(|state_id| block_collision_boxes(state_id, respawn_block))
+        .unwrap_or_default()
+        .iter()
+        .map(|collision_box| collision_box.max_y)
+        .fold(f64::from(respawn_block.0.y), f64::max);
+    let on_top = Vector3::new(
+        f64::from(respawn_block.0.x) + 0.5,
+        top,
+        f64::from(respawn_block.0.z) + 0.5,
+    );
+    can_stand_at(blocks, on_top, size, true).then_some(on_top)
+}
+
+/// Where a line first hits the collision shape of a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlockHit {
+    /// The block which was hit
+    pub position: WorldPosition,
+    /// The point on the block's shape where the line hit it
+    pub location: Vector3<f64>,
+}
+
+/// The first block collision shape the line from `from` to `to` passes through, like vanilla's
+/// `Level.clip` for projectiles. Blocks are visited in the order the line passes them, blocks
+/// which are not loaded don't stop it
+pub fn clip_blocks(
+    blocks: &impl BlockAccess,
+    from: Vector3<f64>,
+    to: Vector3<f64>,
+) -> Option<BlockHit> {
+    let direction = to.sub(&from);
+    let block_of = |point: Vector3<f64>| {
+        Vector3::new(
+            point.x.floor() as i32,
+            point.y.floor() as i32,
+            point.z.floor() as i32,
+        )
+    };
+    let end = block_of(to);
+    let mut block = block_of(from);
+    // How far along the line the next block boundary on each axis is, and the distance between
+    // boundaries, like a voxel traversal
+    let axis = |start: f64, delta: f64, block: i32| {
+        if delta == 0.0 {
+            return (0, f64::INFINITY, f64::INFINITY);
+        }
+        let step = if delta > 0.0 { 1 } else { -1 };
+        let boundary = f64::from(block) + if delta > 0.0 { 1.0 } else { 0.0 };
+        (step, (boundary - start) / delta, 1.0 / delta.abs())
+    };
+    let (step_x, mut next_x, delta_x) = axis(from.x, direction.x, block.x);
+    let (step_y, mut next_y, delta_y) = axis(from.y, direction.y, block.y);
+    let (step_z, mut next_z, delta_z) = axis(from.z, direction.z, block.z);
+    loop {
+        let position = WorldPosition(block);
+        let hit = blocks.block_state(&position).and_then(|state_id| {
+            block_collision_boxes(state_id, &position)
+                .iter()
+                .filter_map(|shape| shape.clip(from, to))
+                .min_by(f64::total_cmp)
+        });
+        if let Some(fraction) = hit {
+            return Some(BlockHit {
+                position,
+                location: from + direction * fraction,
+            });
+        }
+        if block == end {
+            return None;
+        }
+        if next_x <= next_y && next_x <= next_z {
+            if next_x > 1.0 {
+                return None;
+            }
+            block.x += step_x;
+            next_x += delta_x;
+        } else if next_y <= next_z {
+            if next_y > 1.0 {
+                return None;
+            }
+            block.y += step_y;
+            next_y += delta_y;
+        } else {
+            if next_z > 1.0 {
+                return None;
+            }
+            block.z += step_z;
+            next_z += delta_z;
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    fn min(self, bounding_box: &BoundingBox) -> f64 {
+        match self {
+            Self::X => bounding_box.min_x,
+            Self::Y => bounding_box.min_y,
+            Self::Z => bounding_box.min_z,
+        }
+    }
+
+    fn max(self, bounding_box: &BoundingBox) -> f64 {
+        match self {
+            Self::X => bounding_box.max_x,
+            Self::Y => bounding_box.max_y,
+            Self::Z => bounding_box.max_z,
+        }
+    }
+
+    fn vector(self, amount: f64) -> Vector3<f64> {
+        match self {
+            Self::X => Vector3::new(amount, 0.0, 0.0),
+            Self::Y => Vector3::new(0.0, amount, 0.0),
+            Self::Z => Vector3::new(0.0, 0.0, amount),
+        }
+    }
+
+    fn others(self) -> [Self; 2] {
+        match self {
+            Self::X => [Self::Y, Self::Z],
+            Self::Y => [Self::X, Self::Z],
+            Self::Z => [Self::X, Self::Y],
+        }
+    }
+
+    /// Whether the boxes overlap on both of the other axes
+    fn overlaps_across(self, a: &BoundingBox, b: &BoundingBox) -> bool {
+        self.others()
+            .into_iter()
+            .all(|axis| axis.min(a) < axis.max(b) - EPSILON && axis.max(a) > axis.min(b) + EPSILON)
+    }
+}
+
+/// How far the box can move along the axis before it hits one of the block boxes, like vanilla's
+/// `Shapes.collide`
+fn clip(axis: Axis, boxes: &[BoundingBox], bounding_box: &BoundingBox, amount: f64) -> f64 {
+    let mut amount = amount;
+    for block_box in boxes {
+        if amount.abs() < EPSILON {
+            return 0.0;
+        }
+        if !axis.overlaps_across(bounding_box, block_box) {
+            continue;
+        }
+        if amount > 0.0 && axis.min(block_box) >= axis.max(bounding_box) - EPSILON {
+            amount = amount.min(axis.min(block_box) - axis.max(bounding_box));
+        } else if amount < 0.0 && axis.max(block_box) <= axis.min(bounding_box) + EPSILON {
+            amount = amount.max(axis.max(block_box) - axis.min(bounding_box));
+        }
+    }
+    if amount.abs() < EPSILON {
+        0.0
+    } else {
+        amount
+    }
+}
+
+/// Moves the box through the block boxes, vertically first and then along the longer horizontal
+/// axis first like vanilla's `Entity.collideWithShapes`
+fn collide_with_boxes(
+    boxes: &[BoundingBox],
+    bounding_box: &BoundingBox,
+    motion: Vector3<f64>,
+) -> Vector3<f64> {
+    let order = if motion.x.abs() < motion.z.abs() {
+        [Axis::Y, Axis::Z, Axis::X]
+    } else {
+        [Axis::Y, Axis::X, Axis::Z]
+    };
+    let mut moved = *bounding_box;
+    let mut result = Vector3::new(0.0, 0.0, 0.0);
+    for axis in order {
+        let wanted = match axis {
+            Axis::X => motion.x,
+            Axis::Y => motion.y,
+            Axis::Z => motion.z,
+        };
+        let allowed = clip(axis, boxes, &moved, wanted);
+        moved = moved.offset(axis.vector(allowed));
+        result = result + axis.vector(allowed);
+    }
+    result
+}
+
+fn horizontal_length_squared(vector: Vector3<f64>) -> f64 {
+    vector.x.mul_add(vector.x, vector.z * vector.z)
+}
+
+/// How far the box moves when it tries to move by the motion, like vanilla's `Entity.collide`.
+/// Entities on the ground walk up blocks as high as the step height instead of stopping at them
+pub fn collide(
+    blocks: &impl BlockAccess,
+    bounding_box: &BoundingBox,
+    motion: Vector3<f64>,
+    step_height: f64,
+    on_ground: bool,
+) -> Vector3<f64> {
+    if motion.length_squared() == 0.0 {
+        return motion;
+    }
+    let boxes = collision_boxes(blocks, &bounding_box.expand_towards(motion));
+    let collided = collide_with_boxes(&boxes, bounding_box, motion);
+
+    let landed = collided.y != motion.y && motion.y < 0.0;
+    let blocked = collided.x != motion.x || collided.z != motion.z;
+    if step_height <= 0.0 || !(on_ground || landed) || !blocked {
+        return collided;
+    }
+    // Up as far as the step height allows, then sideways and back down onto the step
+    let start = if on_ground {
+        *bounding_box
+    } else {
+        bounding_box.offset(Vector3::new(0.0, collided.y, 0.0))
+    };
+    let step_motion = Vector3::new(motion.x, step_height, motion.z);
+    let boxes = collision_boxes(blocks, &start.expand_towards(step_motion));
+    let up = clip(Axis::Y, &boxes, &start, step_height);
+    let raised = start.offset(Vector3::new(0.0, up, 0.0));
+    let sideways = collide_with_boxes(&boxes, &raised, Vector3::new(motion.x, 0.0, motion.z));
+    let moved = raised.offset(sideways);
+    let down = clip(Axis::Y, &boxes, &moved, -up);
+    let stepped = Vector3::new(
+        sideways.x,
+        up + down + (start.min_y - bounding_box.min_y),
+        sideways.z,
+    );
+    if horizontal_length_squared(stepped) > horizontal_length_squared(collided) {
+        stepped
+    } else {
+        collided
+    }
+}
+
+fn own_height(fluid: FluidState) -> f64 {
+    f64::from(fluid.amount) / 9.0
+}
+
+fn same_fluid(
+    blocks: &impl BlockAccess,
+    position: &WorldPosition,
+    fluid: Fluid,
+) -> Option<FluidState> {
+    blocks
+        .block_state(position)
+        .and_then(fluid_state)
+        .filter(|state| state.fluid == fluid)
+}
+
+/// Whether the block stops entities and fluid flowing against it
+fn blocks_motion(state_id: u16) -> bool {
+    get_state_by_state_id(state_id).is_some_and(|state| !state.collision_shapes.is_empty())
+}
+
+/// How high the fluid fills its block, fluid below more of it fills the whole block
+fn fluid_height(blocks: &impl BlockAccess, position: &WorldPosition, fluid: FluidState) -> f64 {
+    if same_fluid(blocks, &offset(position, 0, 1, 0), fluid.fluid).is_some() {
+        1.0
+    } else {
+        own_height(fluid)
+    }
+}
+
+/// The direction fluid flows in at the position, from higher to lower fluid like vanilla's
+/// `FlowingFluid.getFlow`. Falling fluid next to walls also pushes down
+fn fluid_flow(
+    blocks: &impl BlockAccess,
+    position: &WorldPosition,
+    fluid: FluidState,
+) -> Vector3<f64> {
+    let own = own_height(fluid);
+    let mut flow = Vector3::new(0.0, 0.0, 0.0);
+    let mut next_to_wall = false;
+    for (x, z) in [(0, -1), (0, 1), (-1, 0), (1, 0)] {
+        let neighbor = offset(position, x, 0, z);
+        let Some(state_id) = blocks.block_state(&neighbor) else {
+            continue;
+        };
+        let difference = match fluid_state(state_id) {
+            Some(other) if other.fluid == fluid.fluid => own - own_height(other),
+            // Other fluids don't affect the flow
+            Some(_) => continue,
+            None if blocks_motion(state_id) => {
+                next_to_wall = true;
+                continue;
+            }
+            // Flows towards fluid below the open side
+            None => match same_fluid(blocks, &offset(&neighbor, 0, -1, 0), fluid.fluid) {
+                Some(below) => own - (own_height(below) - 8.0 / 9.0),
+                None => continue,
+            },
+        };
+        flow = flow + Vector3::new(f64::from(x) * difference, 0.0, f64::from(z) * difference);
+    }
+    if flow.length_squared() > 0.0 {
+        flow = flow.normalize();
+    }
+    if fluid.falling && next_to_wall {
+        flow = flow + Vector3::new(0.0, -6.0, 0.0);
+        flow = flow.normalize();
+    }
+    flow
+}
+
+/// How deep the box is in the fluid and where the fluid pushes it, `None` if it doesn't touch
+/// the fluid. Like vanilla's `Entity.updateFluidHeightAndDoFluidPushing`
+pub fn fluid_contact(
+    blocks: &impl BlockAccess,
+    bounding_box: &BoundingBox,
+    fluid: Fluid,
+) -> Option<FluidContact> {
+    let area = bounding_box.inflate(-0.001);
+    let mut height: f64 = 0.0;
+    let mut flow = Vector3::new(0.0, 0.0, 0.0);
+    let mut touching = 0;
+    for x in area.min_x.floor() as i32..area.max_x.ceil() as i32 {
+        for y in area.min_y.floor() as i32..area.max_y.ceil() as i32 {
+            for z in area.min_z.floor() as i32..area.max_z.ceil() as i32 {
+                let position = WorldPosition(Vector3::new(x, y, z));
+                let Some(state) = same_fluid(blocks, &position, fluid) else {
+                    continue;
+                };
+                let surface = f64::from(y) + fluid_height(blocks, &position, state);
+                if surface < area.min_y {
+                    continue;
+                }
+                height = height.max(surface - area.min_y);
+                let block_flow = fluid_flow(blocks, &position, state);
+                // Shallow fluid pushes less
+                flow = flow
+                    + if height < 0.4 {
+                        block_flow * height
+                    } else {
+                        block_flow
+                    };
+                touching += 1;
+            }
+        }
+    }
+    if touching == 0 {
+        return None;
+    }
+    if flow.length_squared() > 0.0 {
+        flow = flow.normalize();
+    }
+    Some(FluidContact { height, flow })
+}
+
+/// Whether the point is under the surface of the fluid, like the eyes of an entity which drowns
+pub fn is_in_fluid(blocks: &impl BlockAccess, point: Vector3<f64>, fluid: Fluid) -> bool {
+    let position = WorldPosition(Vector3::new(
+        point.x.floor() as i32,
+        point.y.floor() as i32,
+        point.z.floor() as i32,
+    ));
+    same_fluid(blocks, &position, fluid).is_some_and(|state| {
+        point.y < f64::from(position.0.y) + fluid_height(blocks, &position, state)
+    })
+}
+
+/// The velocity after flowing fluid pushed the entity
+fn push_by_fluid(velocity: Vector3<f64>, contact: &FluidContact, strength: f64) -> Vector3<f64> {
+    if contact.flow.length_squared() == 0.0 {
+        return velocity;
+    }
+    let mut push = contact.flow * strength;
+    if velocity.x.abs() < 0.003 && velocity.z.abs() < 0.003 && push.length() < MIN_FLUID_PUSH {
+        push = push.normalize() * MIN_FLUID_PUSH;
+    }
+    velocity + push
+}
+
+/// How much horizontal velocity entities keep on the block, ice is slippery and slime sticky
+fn friction(state_id: u16) -> f64 {
+    match get_block_and_state_by_state_id(state_id).map(|(block, _)| block.name.as_str()) {
+        Some("ice" | "packed_ice" | "frosted_ice") => 0.98,
+        Some("blue_ice") => 0.989,
+        Some("slime_block") => 0.8,
+        _ => DEFAULT_FRICTION,
+    }
+}
+
+/// Moves an entity for one tick: fluids push it, gravity pulls it down or it floats up in water,
+/// blocks stop it and drag and friction slow it down, like vanilla's `ItemEntity.tick`
+pub fn tick_movement(
+    blocks: &impl BlockAccess,
+    properties: &MovementProperties,
+    bounding_box: &BoundingBox,
+    velocity: Vector3<f64>,
+    on_ground: bool,
+) -> Movement {
+    let water = fluid_contact(blocks, bounding_box, Fluid::Water);
+    let lava = fluid_contact(blocks, bounding_box, Fluid::Lava);
+    let swimming = properties.swims && water.is_some();
+    let mut velocity = velocity;
+    if let Some(contact) = &water {
+        velocity = push_by_fluid(velocity, contact, WATER_PUSH);
+    }
+    if let Some(contact) = &lava {
+        velocity = push_by_fluid(velocity, contact, LAVA_PUSH);
+    }
+
+    if properties.floats && water.is_some_and(|contact| contact.height > 0.1) {
+        velocity = Vector3::new(
+            velocity.x * 0.99,
+            velocity.y + if velocity.y < 0.06 { 5.0E-4 } else { 0.0 },
+            velocity.z * 0.99,
+        );
+    } else if swimming {
+        velocity.y -= properties.gravity / 16.0;
+    } else {
+        velocity.y -= properties.gravity;
+    }
+
+    let motion = collide(
+        blocks,
+        bounding_box,
+        velocity,
+        properties.step_height,
+        on_ground,
+    );
+    let horizontal_collision = motion.x != velocity.x || motion.z != velocity.z;
+    let vertical_collision = motion.y != velocity.y;
+    let on_ground = vertical_collision && velocity.y < 0.0;
+    // Blocks in the way stop the entity on that axis
+    if motion.x != velocity.x {
+        velocity.x = 0.0;
+    }
+    if motion.z != velocity.z {
+        velocity.z = 0.0;
+    }
+    if vertical_collision {
+        velocity.y = 0.0;
+    }
+
+    if swimming {
+        return Movement {
+            motion,
+            velocity: velocity * WATER_DRAG,
+            on_ground,
+            horizontal_collision,
+            vertical_collision,
+            water,
+            lava,
+        };
+    }
+    let mut horizontal_drag = properties.horizontal_drag;
+    if on_ground {
+        horizontal_drag *= properties.ground_friction.unwrap_or_else(|| {
+            let moved = bounding_box.offset(motion);
+            let below = WorldPosition(Vector3::new(
+                ((moved.min_x + moved.max_x) / 2.0).floor() as i32,
+                (moved.min_y - 0.500_000_1).floor() as i32,
+                ((moved.min_z + moved.max_z) / 2.0).floor() as i32,
+            ));
+            blocks
+                .block_state(&below)
+                .map_or(DEFAULT_FRICTION, friction)
+        });
+    }
+    let velocity = velocity.multiply(horizontal_drag, properties.drag, horizontal_drag);
+
+    Movement {
+        motion,
+        velocity,
+        on_ground,
+        horizontal_collision,
+        vertical_collision,
+        water,
+        lava,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    };
+
+    use crate::block::{
+        fluid::FluidState,
+        random_tick::tests::{pos, TestBlocks},
+    };
+
+    use super::{
+        blocks_inside, can_stand_at, clip_blocks, collide, fluid_contact, is_in_fluid,
+        stand_up_position, tick_movement, Fluid, MovementProperties,
+    };
+
+    fn entity_box(x: f64, y: f64, z: f64, width: f64, height: f64) -> BoundingBox {
+        BoundingBox::new_from_pos(x, y, z, &BoundingBoxSize { width, height })
+    }
+
+    fn floor(world: &mut TestBlocks, y: i32, name: &str) {
+        for x in -3..=3 {
+            for z in -3..=3 {
+                world.set(x, y, z, name);
+            }
+        }
+    }
+
+    /// Ticks the entity until it comes to rest, returns where its bottom ends up
+    fn settle(world: &TestBlocks, properties: &MovementProperties, start: BoundingBox) -> f64 {
+        let mut bounding_box = start;
+        let mut velocity = Vector3::new(0.0, 0.0, 0.0);
+        let mut on_ground = false;
+        for _ in 0..200 {
+            let movement = tick_movement(world, properties, &bounding_box, velocity, on_ground);
+            bounding_box = bounding_box.offset(movement.motion);
+            velocity = movement.velocity;
+            on_ground = movement.on_ground;
+        }
+        assert!(on_ground);
+        bounding_box.min_y
+    }
+
+    #[test]
+    fn entities_land_on_block_shapes() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, 0, "stone");
+        let start = entity_box(0.5, 5.0, 0.5, 0.98, 0.98);
+        let bottom = settle(&world, &MovementProperties::FALLING_BLOCK, start);
+        assert!((bottom - 1.0).abs() < 1.0E-9);
+
+        // Bottom slabs are half a block high
+        world.set(0, 1, 0, "oak_slab");
+        let start = entity_box(0.5, 5.0, 0.5, 0.25, 0.25);
+        let bottom = settle(&world, &MovementProperties::ITEM, start);
+        assert!((bottom - 1.5).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn blocks_stop_sideways_movement() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(1, 0, 0, "stone");
+        let bounding_box = entity_box(0.5, 0.0, 0.5, 0.6, 1.8);
+        let motion = collide(
+            &world,
+            &bounding_box,
+            Vector3::new(0.5, 0.0, 0.1),
+            0.0,
+            true,
+        );
+        assert!((motion.x - 0.2).abs() < 1.0E-9);
+        assert!((motion.z - 0.1).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn mobs_step_up_slabs() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, -1, "stone");
+        world.set(1, 0, 0, "oak_slab");
+        let bounding_box = entity_box(0.5, 0.0, 0.5, 0.6, 1.8);
+        let motion = Vector3::new(0.3, 0.0, 0.0);
+
+        let stepped = collide(&world, &bounding_box, motion, 0.6, true);
+        assert!((stepped.x - 0.3).abs() < 1.0E-9);
+        assert!((stepped.y - 0.5).abs() < 1.0E-9);
+
+        // Full blocks are too high
+        world.set(1, 0, 0, "stone");
+        let blocked = collide(&world, &bounding_box, motion, 0.6, true);
+        assert!(blocked.x < 0.3);
+        assert_eq!(blocked.y, 0.0);
+    }
+
+    #[test]
+    fn items_float_in_water() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, -1, "stone");
+        for y in 0..3 {
+            floor(&mut world, y, "water");
+        }
+        let bounding_box = entity_box(0.5, 1.0, 0.5, 0.25, 0.25);
+        let still = Vector3::new(0.0, 0.0, 0.0);
+        let item = tick_movement(
+            &world,
+            &MovementProperties::ITEM,
+            &bounding_box,
+            still,
+            false,
+        );
+        assert!(item.velocity.y > 0.0);
+        // The water above makes the water around the item a full block high
+        assert!(item.water.is_some_and(|water| water.height > 0.99));
+        let tnt = tick_movement(
+            &world,
+            &MovementProperties::PRIMED_TNT,
+            &bounding_box,
+            still,
+            false,
+        );
+        assert!(tnt.velocity.y < 0.0);
+        // Mobs sink slowly
+        let mob = tick_movement(
+            &world,
+            &MovementProperties::LIVING,
+            &bounding_box,
+            still,
+            false,
+        );
+        assert!(mob.velocity.y < 0.0 && mob.velocity.y > tnt.velocity.y);
+    }
+
+    #[test]
+    fn flowing_water_pushes_downstream() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, -1, "stone");
+        world.set(0, 0, 0, "water");
+        for (x, amount) in [(1, 7), (2, 6), (3, 5)] {
+            let state = FluidState::flowing(Fluid::Water, amount, false);
+            world.blocks.insert(pos(x, 0, 0), state.state_id());
+        }
+
+        let bounding_box = entity_box(1.5, 0.0, 0.5, 0.25, 0.25);
+        let contact = fluid_contact(&world, &bounding_box, Fluid::Water).unwrap();
+        assert!(contact.flow.x > 0.9);
+        assert!(fluid_contact(&world, &bounding_box, Fluid::Lava).is_none());
+
+        let still = Vector3::new(0.0, 0.0, 0.0);
+        let movement = tick_movement(
+            &world,
+            &MovementProperties::PRIMED_TNT,
+            &bounding_box,
+            still,
+            true,
+        );
+        assert!(movement.motion.x > 0.0);
+    }
+
+    #[test]
+    fn points_below_the_surface_are_in_fluid() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(0, 0, 0, "water");
+        let shallow = FluidState::flowing(Fluid::Water, 4, false);
+        world.blocks.insert(pos(1, 0, 0), shallow.state_id());
+
+        assert!(is_in_fluid(
+            &world,
+            Vector3::new(0.5, 0.8, 0.5),
+            Fluid::Water
+        ));
+        assert!(!is_in_fluid(
+            &world,
+            Vector3::new(0.5, 0.8, 0.5),
+            Fluid::Lava
+        ));
+        // Shallow water only fills the bottom of its block
+        assert!(is_in_fluid(
+            &world,
+            Vector3::new(1.5, 0.3, 0.5),
+            Fluid::Water
+        ));
+        assert!(!is_in_fluid(
+            &world,
+            Vector3::new(1.5, 0.8, 0.5),
+            Fluid::Water
+        ));
+        assert!(!is_in_fluid(
+            &world,
+            Vector3::new(0.5, 1.2, 0.5),
+            Fluid::Water
+        ));
+    }
+
+    #[test]
+    fn items_next_to_blocks_are_inside_them() {
+        let mut world = TestBlocks::new(15, 0);
+        let cactus = world.set(1, 0, 0, "cactus");
+
+        let touching = entity_box(1.0, 0.0, 0.5, 0.25, 0.25);
+        assert!(blocks_inside(&world, &touching).contains(&cactus));
+        // Resting right next to the block is not inside it
+        let apart = entity_box(0.875, 0.0, 0.5, 0.25, 0.25);
+        assert!(!blocks_inside(&world, &apart).contains(&cactus));
+    }
+
+    #[test]
+    fn players_stand_up_next_to_respawn_blocks() {
+        let player = BoundingBoxSize {
+            width: 0.6,
+            height: 1.8,
+        };
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, -1, "stone");
+        world.set(0, 0, 0, "red_bed");
+        let position = stand_up_position(&world, &pos(0, 0, 0), &player).unwrap();
+        assert_eq!(position, Vector3::new(0.5, 0.0, -0.5));
+
+        // Walled in players stand on top of the block
+        for (x, z) in [
+            (0, -1),
+            (-1, 0),
+            (0, 1),
+            (1, 0),
+            (-1, -1),
+            (1, -1),
+            (-1, 1),
+            (1, 1),
+        ] {
+            for y in 0..=2 {
+                world.set(x, y, z, "stone");
+            }
+        }
+        let position = stand_up_position(&world, &pos(0, 0, 0), &player).unwrap();
+        assert_eq!(position, Vector3::new(0.5, 0.5625, 0.5));
+
+        world.set(0, 2, 0, "stone");
+        assert!(stand_up_position(&world, &pos(0, 0, 0), &player).is_none());
+    }
+
+    #[test]
+    fn lines_hit_the_first_block_shape() {
+        let mut world = TestBlocks::new(15, 0);
+        world.set(3, 0, 0, "stone");
+        world.set(5, 0, 0, "stone");
+        let hit = clip_blocks(
+            &world,
+            Vector3::new(0.5, 0.5, 0.5),
+            Vector3::new(7.5, 0.5, 0.5),
+        )
+        .unwrap();
+        assert_eq!(hit.position, pos(3, 0, 0));
+        assert!((hit.location.x - 3.0).abs() < 1.0E-9);
+
+        // Lines pass over the top of bottom slabs
+        world.set(3, 0, 0, "oak_slab");
+        let over = clip_blocks(
+            &world,
+            Vector3::new(0.5, 0.75, 0.5),
+            Vector3::new(4.5, 0.75, 0.5),
+        );
+        assert!(over.is_none());
+        let down = clip_blocks(
+            &world,
+            Vector3::new(3.5, 2.0, 0.5),
+            Vector3::new(3.5, -1.0, 0.5),
+        )
+        .unwrap();
+        assert!((down.location.y - 0.5).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn mobs_stand_on_ground_they_fit_on() {
+        let mut world = TestBlocks::new(15, 0);
+        floor(&mut world, -1, "stone");
+        let size = BoundingBoxSize {
+            width: 0.9,
+            height: 1.4,
+        };
+        assert!(can_stand_at(
+            &world,
+            Vector3::new(0.5, 0.0, 0.5),
+            &size,
+            true
+        ));
+        // In the air or inside of blocks
+        assert!(!can_stand_at(
+            &world,
+            Vector3::new(0.5, 1.0, 0.5),
+            &size,
+            true
+        ));
+        world.set(0, 1, 0, "stone");
+        assert!(!can_stand_at(
+            &world,
+            Vector3::new(0.5, 0.0, 0.5),
+            &size,
+            true
+        ));
+
+        world.set(0, 1, 0, "air");
+        world.set(0, 0, 0, "water");
+        assert!(!can_stand_at(
+            &world,
+            Vector3::new(0.5, 0.0, 0.5),
+            &size,
+            true
+        ));
+        assert!(can_stand_at(
+            &world,
+            Vector3::new(0.5, 0.0, 0.5),
+            &size,
+            false
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use pumpkin_nbt::{compound::NbtCompound, Nbt};
+use thiserror::Error;
+
+use crate::write_atomically;
+
+const PLAYER_DATA_FOLDER: &str = "playerdata";
+
+#[derive(Error, Debug)]
+pub enum PlayerDataError {
+    #[error("Io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("The player data file is empty")]
+    Empty,
+    #[error("Nbt error: {0}")]
+    NbtError(#[from] pumpkin_nbt::Error),
+}
+
+/// Reads and writes the gzipped `playerdata/<uuid>.dat` files of a world
+pub struct PlayerDataStorage {
+    folder: PathBuf,
+}
+
+impl PlayerDataStorage {
+    pub fn new(root_folder: &Path) -> Self {
+        Self {
+            folder: root_folder.join(PLAYER_DATA_FOLDER),
+        }
+    }
+
+    fn path(&self, uuid: &str) -> PathBuf {
+        self.folder.join(format!("{uuid}.dat"))
+    }
+
+    /// Loads the data of the player, returns `None` if the player has never joined the world
+    pub fn load(&self, uuid: &str) -> Result<Option<NbtCompound>, PlayerDataError> {
+        let path = self.path(uuid);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let compressed_data = fs::read(path)?;
+        let mut data = Vec::new();
+        GzDecoder::new(&compressed_data[..]).read_to_end(&mut data)?;
+        if data.is_empty() {
+            return Err(PlayerDataError::Empty);
+        }
+        Ok(Some(Nbt::read(&mut &data[..])?.root_tag))
+    }
+
+    pub fn save(&self, uuid: &str, data: NbtCompound) -> Result<(), PlayerDataError> {
+        fs::create_dir_all(&self.folder)?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&Nbt::new(String::new(), data).write())?;
+        let compressed_data = encoder.finish()?;
+
+        write_atomically(&self.path(uuid), compressed_data)?;
+        Ok(())
+    }
+}
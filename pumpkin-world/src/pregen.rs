@@ -0,0 +1,151 @@
+use std::{fs, io, path::PathBuf};
+
+use pumpkin_core::math::vector2::Vector2;
+use serde::{Deserialize, Serialize};
+
+use crate::level::LevelFolder;
+
+/// Where the progress of a running pregeneration is kept, so it continues after a restart
+const PREGEN_FILE: &str = "pregen.json";
+
+/// Generating every chunk in a square around a center ahead of time. Chunks are generated in a
+/// spiral starting at the center, so the progress is just the number of chunks already saved
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PregenTask {
+    pub center_x: i32,
+    pub center_z: i32,
+    /// In chunks, the center chunk is not counted
+    pub radius: u32,
+    /// How many chunks of the spiral were generated and saved
+    pub done: u64,
+}
+
+impl PregenTask {
+    pub fn new(center: Vector2<i32>, radius: u32) -> Self {
+        Self {
+            center_x: center.x,
+            center_z: center.z,
+            radius,
+            done: 0,
+        }
+    }
+
+    pub fn chunk_count(&self) -> u64 {
+        let side = 2 * u64::from(self.radius) + 1;
+        side * side
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.chunk_count()
+    }
+
+    /// Between 0 and 1
+    pub fn progress(&self) -> f32 {
+        (self.done as f64 / self.chunk_count() as f64).min(1.0) as f32
+    }
+
+    /// The chunk at `index` of the spiral. Every ring around the center starts next to the north
+    /// east corner and goes down the east side, then along the south, west and north side
+    pub fn chunk(&self, index: u64) -> Vector2<i32> {
+        let center = Vector2::new(self.center_x, self.center_z);
+        if index == 0 {
+            return center;
+        }
+        // Ring `ring` starts after the (2 * ring - 1)² chunks of the rings inside it
+        let ring = ((index as f64).sqrt() as i64 + 1) / 2;
+        let ring = if (2 * ring + 1).pow(2) <= index as i64 {
+            ring + 1
+        } else {
+            ring
+        };
+        let position = index as i64 - (2 * ring - 1).pow(2);
+        let side_length = 2 * ring;
+        let offset = position % side_length;
+        let (x, z) = match position / side_length {
+            0 => (ring, -ring + 1 + offset),
+            1 => (ring - 1 - offset, ring),
+            2 => (-ring, ring - 1 - offset),
+            _ => (-ring + 1 + offset, -ring),
+        };
+        Vector2::new(center.x + x as i32, center.z + z as i32)
+    }
+
+    /// The next chunks which still have to be generated, at most `size` of them
+    pub fn next_batch(&self, size: usize) -> Vec<Vector2<i32>> {
+        (self.done..self.chunk_count().min(self.done + size as u64))
+            .map(|index| self.chunk(index))
+            .collect()
+    }
+
+    fn path(folder: &LevelFolder) -> PathBuf {
+        folder.root_folder.join(PREGEN_FILE)
+    }
+
+    /// The pregeneration which was running when the world was last saved
+    pub fn load(folder: &LevelFolder) -> Option<Self> {
+        let data = fs::read_to_string(Self::path(folder)).ok()?;
+        serde_json::from_str(&data)
+            .inspect_err(|err| log::warn!("Failed to read the pregeneration progress: {}", err))
+            .ok()
+    }
+
+    pub fn save(&self, folder: &LevelFolder) -> io::Result<()> {
+        fs::write(Self::path(folder), serde_json::to_string(self)?)
+    }
+
+    /// Forgets the progress, the world has no running pregeneration anymore
+    pub fn remove(folder: &LevelFolder) -> io::Result<()> {
+        match fs::remove_file(Self::path(folder)) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use pumpkin_core::math::vector2::Vector2;
+
+    use super::PregenTask;
+
+    #[test]
+    fn spiral_covers_the_square_once() {
+        let task = PregenTask::new(Vector2::new(-3, 7), 5);
+        let chunks: HashSet<_> = (0..task.chunk_count())
+            .map(|index| task.chunk(index))
+            .collect();
+        assert_eq!(chunks.len() as u64, task.chunk_count());
+        for chunk in chunks {
+            assert!((chunk.x + 3).abs() <= 5 && (chunk.z - 7).abs() <= 5);
+        }
+    }
+
+    #[test]
+    fn spiral_goes_outwards() {
+        let task = PregenTask::new(Vector2::new(0, 0), 4);
+        let ring = |index| {
+            let chunk = task.chunk(index);
+            chunk.x.abs().max(chunk.z.abs())
+        };
+        assert_eq!(task.chunk(0), Vector2::new(0, 0));
+        assert_eq!(task.chunk(1), Vector2::new(1, 0));
+        for index in 1..task.chunk_count() {
+            assert!(ring(index) >= ring(index - 1));
+        }
+    }
+
+    #[test]
+    fn batches_continue_where_the_task_stopped() {
+        let mut task = PregenTask::new(Vector2::new(2, 2), 1);
+        assert_eq!(task.next_batch(4).len(), 4);
+        task.done = 4;
+        assert_eq!(task.next_batch(4)[0], task.chunk(4));
+        task.done = 8;
+        assert_eq!(task.next_batch(4).len(), 1);
+        task.done = 9;
+        assert!(task.is_finished());
+        assert!(task.next_batch(4).is_empty());
+    }
+}
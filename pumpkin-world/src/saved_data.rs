@@ -0,0 +1,83 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag, Nbt};
+use thiserror::Error;
+
+use crate::{chunk::WORLD_DATA_VERSION, write_atomically};
+
+const DATA_FOLDER: &str = "data";
+
+#[derive(Error, Debug)]
+pub enum SavedDataError {
+    #[error("Io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("The saved data file has no data")]
+    NoData,
+    #[error("Nbt error: {0}")]
+    NbtError(#[from] pumpkin_nbt::Error),
+}
+
+/// Reads and writes the gzipped `data/<name>.dat` files of a world, like vanilla's
+/// `DimensionDataStorage`. The data is wrapped in a `data` compound next to the `DataVersion`
+pub struct SavedDataStorage {
+    folder: PathBuf,
+}
+
+impl SavedDataStorage {
+    pub fn new(root_folder: &Path) -> Self {
+        Self {
+            folder: root_folder.join(DATA_FOLDER),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.folder.join(format!("{name}.dat"))
+    }
+
+    /// The names of the saved data files starting with the prefix, without the prefix
+    pub fn names_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.folder) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                Some(name.strip_suffix(".dat")?.strip_prefix(prefix)?.to_string())
+            })
+            .collect()
+    }
+
+    /// Loads the saved data, returns `None` if it was never saved
+    pub fn load(&self, name: &str) -> Result<Option<NbtCompound>, SavedDataError> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let compressed_data = fs::read(path)?;
+        let mut data = Vec::new();
+        GzDecoder::new(&compressed_data[..]).read_to_end(&mut data)?;
+        let mut root = Nbt::read(&mut &data[..])?.root_tag;
+        match root.remove("data") {
+            Some(NbtTag::Compound(data)) => Ok(Some(data)),
+            _ => Err(SavedDataError::NoData),
+        }
+    }
+
+    pub fn save(&self, name: &str, data: NbtCompound) -> Result<(), SavedDataError> {
+        fs::create_dir_all(&self.folder)?;
+        let mut root = NbtCompound::new();
+        root.put("data".to_string(), NbtTag::Compound(data));
+        root.put("DataVersion".to_string(), NbtTag::Int(WORLD_DATA_VERSION));
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&Nbt::new(String::new(), root).write())?;
+        let compressed_data = encoder.finish()?;
+
+        write_atomically(&self.path(name), compressed_data)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,793 @@
+use std::f64::consts::{PI, TAU};
+
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        position::WorldPosition,
+        vector2::Vector2,
+        vector3::Vector3,
+    },
+    random::{legacy_rand::LegacyRand, RandomImpl},
+};
+use rand::Rng;
+
+use crate::{
+    biome::Biome,
+    block::{
+        block_registry::{get_block_and_state_by_state_id, BLOCKS},
+        fluid::{fluid_state, Fluid},
+        random_tick::{offset, BlockAccess},
+    },
+    chunk::{light::LightType, ChunkData},
+    coordinates::ChunkRelativeBlockCoordinates,
+    dimension::Dimension,
+    pathfinding::{block_path_type, PathType},
+    physics::collision_boxes,
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+
+/// Boxes touching blocks only at their faces don't collide with them
+const EPSILON: f64 = 1.0E-7;
+/// The chunks around one player, the mob caps are for this many chunks like vanilla's
+/// `MAGIC_NUMBER`
+const CHUNKS_PER_CAP: usize = 289;
+/// Mobs never spawn this close to a player
+const MIN_PLAYER_DISTANCE: f64 = 24.0;
+/// Packs of mobs tried around each position mobs spawn around
+const PACKS: u32 = 3;
+/// Mobs at most spawning around one position, like vanilla's `getMaxSpawnClusterSize`
+const MAX_CLUSTER_SIZE: usize = 4;
+/// Mobs which did nothing for longer than this may despawn once no player is close
+const IDLE_DESPAWN_TICKS: u32 = 600;
+/// Idle mobs far enough from players despawn with a chance of one in this every tick
+const IDLE_DESPAWN_CHANCE: u32 = 800;
+const SEA_LEVEL: i32 = 63;
+/// Mobs closer than this to a player never despawn and stop idling, like vanilla's
+/// `noDespawnDistance`
+pub const NO_DESPAWN_DISTANCE: f64 = 32.0;
+
+/// The kinds of mobs which spawn naturally, each with its own cap, like vanilla's `MobCategory`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MobCategory {
+    Monster,
+    /// Animals, they only spawn every few seconds and never despawn
+    Creature,
+    /// Bats
+    Ambient,
+    /// Squids and dolphins
+    WaterCreature,
+    /// Fish
+    WaterAmbient,
+}
+
+impl MobCategory {
+    pub const ALL: [Self; 5] = [
+        Self::Monster,
+        Self::Creature,
+        Self::Ambient,
+        Self::WaterCreature,
+        Self::WaterAmbient,
+    ];
+
+    /// How many mobs of the category live around one player at most
+    pub const fn max_per_player(self) -> usize {
+        match self {
+            Self::Monster => 70,
+            Self::Creature => 10,
+            Self::Ambient => 15,
+            Self::WaterCreature => 5,
+            Self::WaterAmbient => 20,
+        }
+    }
+
+    /// How many mobs of the category live in the world at most, scaled by the chunks mobs spawn
+    /// in like vanilla's `LocalMobCapCalculator`
+    pub const fn cap(self, spawnable_chunks: usize) -> usize {
+        self.max_per_player() * spawnable_chunks / CHUNKS_PER_CAP
+    }
+
+    /// Whether mobs of the category stay once they spawned, like vanilla's `isPersistent`
+    pub const fn is_persistent(self) -> bool {
+        matches!(self, Self::Creature)
+    }
+
+    /// Mobs of the category further than this from every player despawn right away
+    pub const fn despawn_distance(self) -> f64 {
+        match self {
+            Self::WaterAmbient => 64.0,
+            _ => 128.0,
+        }
+    }
+}
+
+/// A mob which spawns in a biome, the more weight the more often
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpawnEntry {
+    /// The name of the entity type, without the `minecraft:` namespace
+    pub entity: &'static str,
+    pub weight: u32,
+    /// How many mobs spawn together at least
+    pub min_count: u32,
+    /// How many mobs spawn together at most
+    pub max_count: u32,
+}
+
+const fn entry(entity: &'static str, weight: u32, min_count: u32, max_count: u32) -> SpawnEntry {
+    SpawnEntry {
+        entity,
+        weight,
+        min_count,
+        max_count,
+    }
+}
+
+/// The monsters of most overworld biomes, like vanilla's `BiomeDefaultFeatures.monsters`
+const MONSTERS: &[SpawnEntry] = &[
+    entry("spider", 100, 4, 4),
+    entry("zombie", 95, 4, 4),
+    entry("zombie_villager", 5, 1, 1),
+    entry("skeleton", 100, 4, 4),
+    entry("creeper", 100, 4, 4),
+    entry("slime", 100, 4, 4),
+    entry("enderman", 10, 1, 4),
+    entry("witch", 5, 1, 1),
+];
+/// Husks mostly replace zombies in deserts
+const DESERT_MONSTERS: &[SpawnEntry] = &[
+    entry("spider", 100, 4, 4),
+    entry("zombie", 19, 4, 4),
+    entry("zombie_villager", 1, 1, 1),
+    entry("skeleton", 100, 4, 4),
+    entry("creeper", 100, 4, 4),
+    entry("slime", 100, 4, 4),
+    entry("enderman", 10, 1, 4),
+    entry("witch", 5, 1, 1),
+    entry("husk", 80, 4, 4),
+];
+const OCEAN_MONSTERS: &[SpawnEntry] = &[
+    entry("spider", 100, 4, 4),
+    entry("zombie", 95, 4, 4),
+    entry("zombie_villager", 5, 1, 1),
+    entry("skeleton", 100, 4, 4),
+    entry("creeper", 100, 4, 4),
+    entry("slime", 100, 4, 4),
+    entry("enderman", 10, 1, 4),
+    entry("witch", 5, 1, 1),
+    entry("drowned", 5, 1, 1),
+];
+const BATS: &[SpawnEntry] = &[entry("bat", 10, 8, 8)];
+const PLAINS_CREATURES: &[SpawnEntry] = &[
+    entry("sheep", 12, 4, 4),
+    entry("pig", 10, 4, 4),
+    entry("chicken", 10, 4, 4),
+    entry("cow", 8, 4, 4),
+    entry("horse", 5, 2, 6),
+    entry("donkey", 1, 1, 3),
+];
+const SNOWY_TAIGA_CREATURES: &[SpawnEntry] = &[
+    entry("sheep", 12, 4, 4),
+    entry("pig", 10, 4, 4),
+    entry("chicken", 10, 4, 4),
+    entry("cow", 8, 4, 4),
+    entry("wolf", 8, 4, 4),
+    entry("rabbit", 4, 2, 3),
+    entry("fox", 8, 2, 4),
+];
+const DESERT_CREATURES: &[SpawnEntry] = &[entry("rabbit", 4, 2, 3), entry("camel", 1, 1, 1)];
+const BADLANDS_CREATURES: &[SpawnEntry] = &[entry("armadillo", 6, 1, 2)];
+const BEACH_CREATURES: &[SpawnEntry] = &[entry("turtle", 5, 2, 5)];
+const OCEAN_WATER_CREATURES: &[SpawnEntry] = &[entry("squid", 1, 1, 4), entry("dolphin", 1, 1, 2)];
+const OCEAN_WATER_AMBIENT: &[SpawnEntry] = &[entry("cod", 10, 3, 6)];
+const NETHER_WASTES_MONSTERS: &[SpawnEntry] = &[
+    entry("ghast", 50, 4, 4),
+    entry("zombified_piglin", 100, 4, 4),
+    entry("magma_cube", 2, 4, 4),
+    entry("enderman", 1, 4, 4),
+    entry("piglin", 15, 4, 4),
+];
+const SOUL_SAND_VALLEY_MONSTERS: &[SpawnEntry] = &[
+    entry("skeleton", 20, 5, 5),
+    entry("ghast", 50, 4, 4),
+    entry("enderman", 1, 4, 4),
+];
+const CRIMSON_FOREST_MONSTERS: &[SpawnEntry] = &[
+    entry("zombified_piglin", 1, 2, 4),
+    entry("hoglin", 9, 3, 4),
+    entry("piglin", 5, 3, 4),
+];
+const WARPED_FOREST_MONSTERS: &[SpawnEntry] = &[entry("enderman", 1, 4, 4)];
+const BASALT_DELTAS_MONSTERS: &[SpawnEntry] =
+    &[entry("ghast", 40, 1, 1), entry("magma_cube", 100, 2, 5)];
+const STRIDERS: &[SpawnEntry] = &[entry("strider", 60, 1, 2)];
+const END_MONSTERS: &[SpawnEntry] = &[entry("enderman", 10, 4, 4)];
+
+/// The mobs of the category which spawn in the biome, like the spawn settings of vanilla's
+/// biomes
+pub fn spawn_list(biome: Biome, category: MobCategory) -> &'static [SpawnEntry] {
+    use MobCategory::{Ambient, Creature, Monster, WaterAmbient, WaterCreature};
+    match (biome, category) {
+        (Biome::NetherWastes, Monster) => NETHER_WASTES_MONSTERS,
+        (Biome::SoulSandValley, Monster) => SOUL_SAND_VALLEY_MONSTERS,
+        (Biome::CrimsonForest, Monster) => CRIMSON_FOREST_MONSTERS,
+        (Biome::WarpedForest, Monster) => WARPED_FOREST_MONSTERS,
+        (Biome::BasaltDeltas, Monster) => BASALT_DELTAS_MONSTERS,
+        (
+            Biome::NetherWastes
+            | Biome::SoulSandValley
+            | Biome::CrimsonForest
+            | Biome::WarpedForest
+            | Biome::BasaltDeltas,
+            Creature,
+        ) => STRIDERS,
+        (
+            Biome::TheEnd
+            | Biome::EndHighlands
+            | Biome::EndMidlands
+            | Biome::SmallEndIslands
+            | Biome::EndBarrens,
+            Monster,
+        ) => END_MONSTERS,
+        (
+            Biome::NetherWastes
+            | Biome::SoulSandValley
+            | Biome::CrimsonForest
+            | Biome::WarpedForest
+            | Biome::BasaltDeltas
+            | Biome::TheEnd
+            | Biome::EndHighlands
+            | Biome::EndMidlands
+            | Biome::SmallEndIslands
+            | Biome::EndBarrens,
+            _,
+        ) => &[],
+        (Biome::Desert, Monster) => DESERT_MONSTERS,
+        (Biome::Ocean, Monster) => OCEAN_MONSTERS,
+        (_, Monster) => MONSTERS,
+        (_, Ambient) => BATS,
+        (Biome::Plains, Creature) => PLAINS_CREATURES,
+        (Biome::SnowyTiga, Creature) => SNOWY_TAIGA_CREATURES,
+        (Biome::Desert, Creature) => DESERT_CREATURES,
+        (Biome::Badlands, Creature) => BADLANDS_CREATURES,
+        (Biome::Beach, Creature) => BEACH_CREATURES,
+        (Biome::Ocean, WaterCreature) => OCEAN_WATER_CREATURES,
+        (Biome::Ocean, WaterAmbient) => OCEAN_WATER_AMBIENT,
+        _ => &[],
+    }
+}
+
+/// The biome mobs spawn for in the dimension. Chunks don't remember their biomes yet, so every
+/// dimension spawns the mobs of the biome it generates
+pub const fn dimension_biome(dimension: Dimension) -> Biome {
+    match dimension {
+        Dimension::OverWorld => Biome::Plains,
+        Dimension::Nether => Biome::NetherWastes,
+        Dimension::End => Biome::TheEnd,
+    }
+}
+
+/// Picks one of the entries, the more weight the more likely
+fn pick_entry(entries: &'static [SpawnEntry], rng: &mut impl Rng) -> Option<&'static SpawnEntry> {
+    let total: u32 = entries.iter().map(|entry| entry.weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0..total);
+    entries.iter().find(|entry| {
+        if roll < entry.weight {
+            return true;
+        }
+        roll -= entry.weight;
+        false
+    })
+}
+
+/// How much the sky light is darkened by the time of day and the weather, from 0 at noon to 11
+/// at midnight, like vanilla's `Level.updateSkyBrightness`
+pub fn sky_darken(time_of_day: i64, rain_level: f32, thunder_level: f32) -> u8 {
+    // How far the sun moved, 0 at noon and 0.5 at midnight
+    let fraction = ((time_of_day % 24000) as f64 / 24000.0 - 0.25).rem_euclid(1.0);
+    let angle = (fraction * 2.0 + (0.5 - (fraction * PI).cos() / 2.0)) / 3.0;
+    let sun = 0.5 + 2.0 * (angle * TAU).cos().clamp(-0.25, 0.25);
+    let rain = 1.0 - f64::from(rain_level) * 5.0 / 16.0;
+    let thunder = 1.0 - f64::from(thunder_level) * 5.0 / 16.0;
+    ((1.0 - sun * rain * thunder) * 11.0) as u8
+}
+
+/// Whether slimes spawn deep underground in the chunk, like vanilla's
+/// `WorldgenRandom.seedSlimeChunk`
+pub fn is_slime_chunk(seed: u64, chunk: Vector2<i32>) -> bool {
+    let (x, z) = (chunk.x, chunk.z);
+    let seed = (seed as i64)
+        .wrapping_add(i64::from(x.wrapping_mul(x).wrapping_mul(4_987_142)))
+        .wrapping_add(i64::from(x.wrapping_mul(5_947_611)))
+        .wrapping_add(i64::from(z.wrapping_mul(z)).wrapping_mul(4_392_871))
+        .wrapping_add(i64::from(z.wrapping_mul(389_711)))
+        ^ 987_234_911;
+    LegacyRand::from_seed(seed as u64).next_bounded_i32(10) == 0
+}
+
+/// What mobs spawning depends on besides the blocks
+pub struct SpawnContext<'a> {
+    pub dimension: Dimension,
+    pub seed: u64,
+    /// How much the sky light is darkened, see `sky_darken`
+    pub sky_darken: u8,
+    /// Where the players are who aren't spectating, mobs only spawn around them
+    pub players: &'a [Vector3<f64>],
+}
+
+/// A mob natural spawning puts into the world
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NaturalSpawn {
+    /// The name of the entity type, without the `minecraft:` namespace
+    pub entity: &'static str,
+    pub position: Vector3<f64>,
+}
+
+/// Where mobs spawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SpawnPlacement {
+    /// On top of a solid block, with air to stand in
+    OnGround,
+    InWater,
+    InLava,
+}
+
+fn placement(entity: &str) -> SpawnPlacement {
+    match entity {
+        "squid" | "glow_squid" | "dolphin" | "cod" | "salmon" | "tropical_fish" | "pufferfish"
+        | "drowned" => SpawnPlacement::InWater,
+        "strider" => SpawnPlacement::InLava,
+        _ => SpawnPlacement::OnGround,
+    }
+}
+
+/// The blocks animals spawn on, like vanilla's `*_spawnable_on` block tags
+fn animal_ground(entity: &str) -> &'static [&'static str] {
+    match entity {
+        "rabbit" => &["grass_block", "snow", "snow_block", "sand"],
+        "wolf" => &["grass_block", "snow", "snow_block", "podzol", "coarse_dirt"],
+        "fox" => &["grass_block", "snow", "snow_block", "podzol", "coarse_dirt"],
+        "camel" => &["sand"],
+        "turtle" => &["sand", "suspicious_sand"],
+        "armadillo" => &["red_sand", "coarse_dirt", "terracotta"],
+        _ => &["grass_block"],
+    }
+}
+
+/// Where mobs spawn around in the chunk, a random block in a random column below the top of it,
+/// like vanilla's `NaturalSpawner.getRandomPosWithin`. `None` if the column is empty
+pub fn spawn_origin(chunk: &ChunkData, rng: &mut impl Rng) -> Option<WorldPosition> {
+    let x = rng.gen_range(0..16);
+    let z = rng.gen_range(0..16);
+    let top = (i32::from(WORLD_LOWEST_Y)..i32::from(WORLD_MAX_Y))
+        .rev()
+        .find(|y| {
+            chunk
+                .blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(Vector3::new(x, *y, z)))
+                .and_then(get_block_and_state_by_state_id)
+                .is_some_and(|(_, state)| !state.air)
+        })?;
+    let y = rng.gen_range(i32::from(WORLD_LOWEST_Y)..=top + 1);
+    Some(WorldPosition(Vector3::new(
+        chunk.position.x * 16 + x,
+        y,
+        chunk.position.z * 16 + z,
+    )))
+}
+
+/// Whether the block state fills its whole block, mobs never spawn around positions inside of
+/// such blocks
+fn is_full_block(state_id: u16) -> bool {
+    get_block_and_state_by_state_id(state_id).is_some_and(|(_, state)| {
+        state
+            .collision_shapes
+            .iter()
+            .filter_map(|shape| BLOCKS.shapes.get(usize::from(*shape)))
+            .any(|shape| shape.min == [0.0; 3] && shape.max == [1.0; 3])
+    })
+}
+
+/// Whether mobs spawn on top of the block state, like vanilla's `isValidSpawn` of blocks with a
+/// sturdy top face. Bright blocks, leaves and blocks hurting mobs are no ground to spawn on
+fn is_spawn_ground(state_id: u16) -> bool {
+    let Some((block, state)) = get_block_and_state_by_state_id(state_id) else {
+        return false;
+    };
+    let full_top = state
+        .collision_shapes
+        .iter()
+        .filter_map(|shape| BLOCKS.shapes.get(usize::from(*shape)))
+        .any(|shape| {
+            shape.min[0] <= 0.0
+                && shape.min[2] <= 0.0
+                && shape.max[0] >= 1.0
+                && shape.max[1] >= 1.0
+                && shape.max[2] >= 1.0
+        });
+    full_top
+        && state.luminance < 14
+        && !matches!(block.name.as_str(), "bedrock" | "barrier" | "magma_block")
+        && !block.name.ends_with("_leaves")
+}
+
+/// Whether mobs spawn inside of the block state, like vanilla's
+/// `NaturalSpawner.isValidEmptySpawnBlock`. Fluids, rails and blocks hurting mobs keep them away
+fn is_empty_spawn_block(state_id: u16) -> bool {
+    let Some((block, _)) = get_block_and_state_by_state_id(state_id) else {
+        return false;
+    };
+    !is_full_block(state_id)
+        && fluid_state(state_id).is_none()
+        && !block.name.ends_with("rail")
+        && !matches!(
+            block_path_type(state_id),
+            PathType::DamageFire | PathType::DamageOther | PathType::Lava
+        )
+}
+
+fn is_fluid(blocks: &impl BlockAccess, position: &WorldPosition, fluid: Fluid) -> bool {
+    blocks
+        .block_state(position)
+        .and_then(fluid_state)
+        .is_some_and(|state| state.fluid == fluid)
+}
+
+/// Whether the blocks at the position suit where the mob spawns
+fn placement_allows(
+    blocks: &impl BlockAccess,
+    placement: SpawnPlacement,
+    position: &WorldPosition,
+) -> bool {
+    match placement {
+        SpawnPlacement::OnGround => {
+            blocks
+                .block_state(&offset(position, 0, -1, 0))
+                .is_some_and(is_spawn_ground)
+                && blocks
+                    .block_state(position)
+                    .is_some_and(is_empty_spawn_block)
+                && blocks
+                    .block_state(&offset(position, 0, 1, 0))
+                    .is_some_and(is_empty_spawn_block)
+        }
+        SpawnPlacement::InWater => {
+            is_fluid(blocks, position, Fluid::Water)
+                && blocks
+                    .block_state(&offset(position, 0, 1, 0))
+                    .is_some_and(|state_id| !is_full_block(state_id))
+        }
+        SpawnPlacement::InLava => is_fluid(blocks, position, Fluid::Lava),
+    }
+}
+
+/// Whether the position is dark enough for monsters, like vanilla's
+/// `Monster.isDarkEnoughToSpawn`. Monsters in the nether spawn next to anything but the
+/// brightest lights
+fn is_dark_enough(
+    blocks: &impl BlockAccess,
+    context: &SpawnContext,
+    position: &WorldPosition,
+    rng: &mut impl Rng,
+) -> bool {
+    let sky = blocks.light(LightType::Sky, position).unwrap_or(0);
+    if u32::from(sky) > rng.gen_range(0..32) {
+        return false;
+    }
+    let block = blocks.light(LightType::Block, position).unwrap_or(0);
+    let (block_light_limit, threshold) = match context.dimension {
+        Dimension::Nether => (15, 7),
+        Dimension::OverWorld | Dimension::End => (0, rng.gen_range(0..=7)),
+    };
+    block <= block_light_limit && sky.saturating_sub(context.sky_darken).max(block) <= threshold
+}
+
+/// Whether the rules of the mob allow it to spawn at the position, like the spawn predicates
+/// vanilla registers in `SpawnPlacements`
+fn spawn_rules_allow(
+    blocks: &impl BlockAccess,
+    context: &SpawnContext,
+    category: MobCategory,
+    entity: &str,
+    position: &WorldPosition,
+    rng: &mut impl Rng,
+) -> bool {
+    let y = position.0.y;
+    let below = blocks
+        .block_state(&offset(position, 0, -1, 0))
+        .and_then(get_block_and_state_by_state_id)
+        .map(|(block, _)| block.name.as_str());
+    let sees_sky = blocks.light(LightType::Sky, position) == Some(15);
+    match entity {
+        "ghast" => rng.gen_range(0..20) == 0,
+        "zombified_piglin" | "piglin" | "hoglin" => below != Some("nether_wart_block"),
+        "magma_cube" | "strider" => true,
+        // Slimes outside of swamps only spawn deep underground in slime chunks
+        "slime" => {
+            let (chunk, _) = position.chunk_and_chunk_relative_position();
+            rng.gen_range(0..10) == 0 && is_slime_chunk(context.seed, chunk) && y < 40
+        }
+        "husk" | "stray" => sees_sky && is_dark_enough(blocks, context, position, rng),
+        "drowned" => {
+            rng.gen_range(0..40) == 0
+                && y < SEA_LEVEL - 5
+                && is_dark_enough(blocks, context, position, rng)
+        }
+        _ if category == MobCategory::Monster => is_dark_enough(blocks, context, position, rng),
+        "bat" => y < SEA_LEVEL && u32::from(blocks.raw_brightness(position)) <= rng.gen_range(0..4),
+        "squid" | "glow_squid" | "dolphin" | "cod" | "salmon" | "tropical_fish" | "pufferfish" => {
+            (SEA_LEVEL - 13..=SEA_LEVEL).contains(&y)
+        }
+        "turtle" if y >= SEA_LEVEL + 4 => false,
+        _ => {
+            below.is_some_and(|below| animal_ground(entity).contains(&below))
+                && blocks.raw_brightness(position) > 8
+        }
+    }
+}
+
+/// Whether no block collides with a mob of the size at the position, blocks which are not
+/// loaded are in the way
+fn fits(blocks: &impl BlockAccess, position: Vector3<f64>, size: &BoundingBoxSize) -> bool {
+    let bounding_box = BoundingBox::new_from_pos(position.x, position.y, position.z, size);
+    collision_boxes(blocks, &bounding_box.inflate(-EPSILON)).is_empty()
+}
+
+/// How far the closest player is from the position, `None` without players
+fn nearest_player_distance(players: &[Vector3<f64>], position: Vector3<f64>) -> Option<f64> {
+    players
+        .iter()
+        .map(|player| player.sub(&position).length())
+        .min_by(f64::total_cmp)
+}
+
+/// Spawns packs of mobs of the category around the origin, like vanilla's
+/// `NaturalSpawner.spawnCategoryForPosition`. Each pack is of one kind of mob picked from the
+/// spawn list of the biome, it spreads out randomly from the origin. `size` is how large the mob
+/// with the name is, mobs without a size don't spawn
+pub fn spawn_category_at(
+    blocks: &impl BlockAccess,
+    context: &SpawnContext,
+    category: MobCategory,
+    origin: WorldPosition,
+    size: impl Fn(&str) -> Option<BoundingBoxSize>,
+    rng: &mut impl Rng,
+) -> Vec<NaturalSpawn> {
+    let mut spawns = Vec::new();
+    if blocks.block_state(&origin).is_none_or(is_full_block) {
+        return spawns;
+    }
+    let entries = spawn_list(dimension_biome(context.dimension), category);
+    for _ in 0..PACKS {
+        let (mut x, mut z) = (origin.0.x, origin.0.z);
+        let mut pack_entry = None;
+        // Until a mob is picked, then as many as spawn of it together
+        let mut pack_size = rng.gen_range(1..=MAX_CLUSTER_SIZE as u32);
+        let mut attempt = 0;
+        while attempt < pack_size {
+            attempt += 1;
+            x += rng.gen_range(0..6) - rng.gen_range(0..6);
+            z += rng.gen_range(0..6) - rng.gen_range(0..6);
+            let position = WorldPosition(Vector3::new(x, origin.0.y, z));
+            let spawn_position = Vector3::new(
+                f64::from(x) + 0.5,
+                f64::from(origin.0.y),
+                f64::from(z) + 0.5,
+            );
+            let Some(distance) = nearest_player_distance(context.players, spawn_position) else {
+                continue;
+            };
+            if distance <= MIN_PLAYER_DISTANCE {
+                continue;
+            }
+            let entry = match pack_entry {
+                Some(entry) => entry,
+                None => {
+                    let Some(entry) = pick_entry(entries, rng) else {
+                        return spawns;
+                    };
+                    pack_size = rng.gen_range(entry.min_count..=entry.max_count);
+                    pack_entry = Some(entry);
+                    entry
+                }
+            };
+            // Mobs which would despawn right away don't spawn
+            if !category.is_persistent() && distance > category.despawn_distance() {
+                continue;
+            }
+            let Some(mob_size) = size(entry.entity) else {
+                continue;
+            };
+            if !placement_allows(blocks, placement(entry.entity), &position)
+                || !spawn_rules_allow(blocks, context, category, entry.entity, &position, rng)
+                || !fits(blocks, spawn_position, &mob_size)
+            {
+                continue;
+            }
+            spawns.push(NaturalSpawn {
+                entity: entry.entity,
+                position: spawn_position,
+            });
+            if spawns.len() >= MAX_CLUSTER_SIZE {
+                return spawns;
+            }
+        }
+    }
+    spawns
+}
+
+/// Whether a mob of the category `distance` away from the closest player despawns, like
+/// vanilla's `Mob.checkDespawn`. Mobs far away despawn right away, mobs which did nothing for a
+/// while now and then once no player is close
+pub fn should_despawn(
+    category: MobCategory,
+    distance: f64,
+    idle_ticks: u32,
+    rng: &mut impl Rng,
+) -> bool {
+    if category.is_persistent() {
+        return false;
+    }
+    distance > category.despawn_distance()
+        || (distance > NO_DESPAWN_DISTANCE
+            && idle_ticks > IDLE_DESPAWN_TICKS
+            && rng.gen_range(0..IDLE_DESPAWN_CHANCE) == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use pumpkin_core::math::{
+        boundingbox::BoundingBoxSize, position::WorldPosition, vector2::Vector2, vector3::Vector3,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{
+        biome::Biome,
+        block::random_tick::tests::{pos, TestBlocks},
+        dimension::Dimension,
+    };
+
+    use super::{
+        is_slime_chunk, should_despawn, sky_darken, spawn_category_at, spawn_list, MobCategory,
+        NaturalSpawn, SpawnContext, MONSTERS,
+    };
+
+    /// Above where slimes and bats spawn
+    const FLOOR_Y: i32 = 64;
+
+    /// A flat floor of the block around the origin
+    fn floor(sky_light: u8, name: &str) -> TestBlocks {
+        let mut blocks = TestBlocks::new(sky_light, 0);
+        for x in -25..=25 {
+            for z in -25..=25 {
+                blocks.set(x, FLOOR_Y, z, name);
+            }
+        }
+        blocks
+    }
+
+    fn origin() -> WorldPosition {
+        pos(0, FLOOR_Y + 1, 0)
+    }
+
+    fn player() -> Vector3<f64> {
+        Vector3::new(60.0, f64::from(FLOOR_Y + 1), 0.0)
+    }
+
+    fn spawn(
+        blocks: &TestBlocks,
+        category: MobCategory,
+        sky_darken: u8,
+        players: &[Vector3<f64>],
+    ) -> Vec<NaturalSpawn> {
+        let context = SpawnContext {
+            dimension: Dimension::OverWorld,
+            seed: 0,
+            sky_darken,
+            players,
+        };
+        let size = |_: &str| {
+            Some(BoundingBoxSize {
+                width: 0.6,
+                height: 1.8,
+            })
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..20)
+            .flat_map(|_| spawn_category_at(blocks, &context, category, origin(), size, &mut rng))
+            .collect()
+    }
+
+    #[test]
+    fn caps_scale_with_chunks() {
+        assert_eq!(MobCategory::Monster.cap(289), 70);
+        assert_eq!(MobCategory::Creature.cap(289 * 2), 20);
+        assert_eq!(MobCategory::Monster.cap(0), 0);
+    }
+
+    #[test]
+    fn sky_darkens_at_night_and_in_storms() {
+        assert_eq!(sky_darken(6000, 0.0, 0.0), 0);
+        assert_eq!(sky_darken(18000, 0.0, 0.0), 11);
+        assert_eq!(sky_darken(6000, 1.0, 1.0), 5);
+    }
+
+    #[test]
+    fn biomes_have_their_own_mobs() {
+        assert!(spawn_list(Biome::Plains, MobCategory::Creature)
+            .iter()
+            .any(|entry| entry.entity == "sheep"));
+        assert!(spawn_list(Biome::Desert, MobCategory::Monster)
+            .iter()
+            .any(|entry| entry.entity == "husk"));
+        assert!(spawn_list(Biome::NetherWastes, MobCategory::Ambient).is_empty());
+        assert!(spawn_list(Biome::TheEnd, MobCategory::Monster)
+            .iter()
+            .all(|entry| entry.entity == "enderman"));
+    }
+
+    #[test]
+    fn monsters_spawn_in_the_dark_away_from_players() {
+        let blocks = floor(0, "stone");
+        let player = player();
+        let spawns = spawn(&blocks, MobCategory::Monster, 0, &[player]);
+        assert!(!spawns.is_empty());
+        for spawn in spawns {
+            assert!(MONSTERS.iter().any(|entry| entry.entity == spawn.entity));
+            assert_eq!(spawn.position.y, f64::from(FLOOR_Y + 1));
+            assert!(spawn.position.sub(&player).length() > 24.0);
+        }
+    }
+
+    #[test]
+    fn monsters_dont_spawn_in_daylight() {
+        let blocks = floor(15, "stone");
+        let spawns = spawn(&blocks, MobCategory::Monster, 0, &[player()]);
+        assert!(spawns.is_empty());
+    }
+
+    #[test]
+    fn nothing_spawns_without_players() {
+        let blocks = floor(0, "stone");
+        assert!(spawn(&blocks, MobCategory::Monster, 0, &[]).is_empty());
+    }
+
+    #[test]
+    fn animals_spawn_on_grass_in_light() {
+        let players = [player()];
+        let grass = floor(15, "grass_block");
+        assert!(!spawn(&grass, MobCategory::Creature, 0, &players).is_empty());
+        let stone = floor(15, "stone");
+        assert!(spawn(&stone, MobCategory::Creature, 0, &players).is_empty());
+        let dark = floor(0, "grass_block");
+        assert!(spawn(&dark, MobCategory::Creature, 0, &players).is_empty());
+    }
+
+    #[test]
+    fn nothing_spawns_inside_of_blocks() {
+        let mut blocks = floor(0, "stone");
+        blocks.set(0, FLOOR_Y + 1, 0, "stone");
+        let spawns = spawn(&blocks, MobCategory::Monster, 0, &[player()]);
+        assert!(spawns.is_empty());
+    }
+
+    #[test]
+    fn one_in_ten_chunks_are_slime_chunks() {
+        let slime_chunks = (0..100)
+            .flat_map(|x| (0..100).map(move |z| Vector2::new(x, z)))
+            .filter(|chunk| is_slime_chunk(12345, *chunk))
+            .count();
+        assert!((800..1200).contains(&slime_chunks));
+    }
+
+    #[test]
+    fn far_away_mobs_despawn() {
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(should_despawn(MobCategory::Monster, 200.0, 0, &mut rng));
+        assert!(!should_despawn(MobCategory::Monster, 50.0, 0, &mut rng));
+        assert!(!should_despawn(MobCategory::Creature, 200.0, 0, &mut rng));
+        assert!((0..10_000).any(|_| should_despawn(MobCategory::Monster, 50.0, 1000, &mut rng)));
+        assert!(!(0..10_000).any(|_| should_despawn(MobCategory::Monster, 20.0, 1000, &mut rng)));
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{chunk::WORLD_DATA_VERSION, write_atomically};
+
+const STATS_FOLDER: &str = "stats";
+
+/// What a statistic counts, the discriminant is the id in the `stat_type` registry
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(u8)]
+pub enum StatType {
+    Mined,
+    Crafted,
+    Used,
+    Broken,
+    PickedUp,
+    Dropped,
+    Killed,
+    KilledBy,
+    Custom,
+}
+
+impl StatType {
+    pub const ALL: [Self; 9] = [
+        Self::Mined,
+        Self::Crafted,
+        Self::Used,
+        Self::Broken,
+        Self::PickedUp,
+        Self::Dropped,
+        Self::Killed,
+        Self::KilledBy,
+        Self::Custom,
+    ];
+
+    /// The id without the `minecraft:` namespace
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Mined => "mined",
+            Self::Crafted => "crafted",
+            Self::Used => "used",
+            Self::Broken => "broken",
+            Self::PickedUp => "picked_up",
+            Self::Dropped => "dropped",
+            Self::Killed => "killed",
+            Self::KilledBy => "killed_by",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// The stat type with the id, the `minecraft:` namespace is optional
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|stat_type| stat_type.name() == name)
+    }
+}
+
+/// The custom statistics in the order of the `custom_stat` registry, like vanilla's `Stats`
+pub const CUSTOM_STATS: [&str; 75] = [
+    "leave_game",
+    "play_time",
+    "total_world_time",
+    "time_since_death",
+    "time_since_rest",
+    "sneak_time",
+    "walk_one_cm",
+    "crouch_one_cm",
+    "sprint_one_cm",
+    "walk_on_water_one_cm",
+    "fall_one_cm",
+    "climb_one_cm",
+    "fly_one_cm",
+    "walk_under_water_one_cm",
+    "minecart_one_cm",
+    "boat_one_cm",
+    "pig_one_cm",
+    "horse_one_cm",
+    "aviate_one_cm",
+    "swim_one_cm",
+    "strider_one_cm",
+    "jump",
+    "drop",
+    "damage_dealt",
+    "damage_dealt_absorbed",
+    "damage_dealt_resisted",
+    "damage_taken",
+    "damage_blocked_by_shield",
+    "damage_absorbed",
+    "damage_resisted",
+    "deaths",
+    "mob_kills",
+    "animals_bred",
+    "player_kills",
+    "fish_caught",
+    "talked_to_villager",
+    "traded_with_villager",
+    "eat_cake_slice",
+    "fill_cauldron",
+    "use_cauldron",
+    "clean_armor",
+    "clean_banner",
+    "clean_shulker_box",
+    "interact_with_brewingstand",
+    "interact_with_beacon",
+    "inspect_dropper",
+    "inspect_hopper",
+    "inspect_dispenser",
+    "play_noteblock",
+    "tune_noteblock",
+    "pot_flower",
+    "trigger_trapped_chest",
+    "open_enderchest",
+    "enchant_item",
+    "play_record",
+    "interact_with_furnace",
+    "interact_with_crafting_table",
+    "open_chest",
+    "sleep_in_bed",
+    "open_shulker_box",
+    "open_barrel",
+    "interact_with_blast_furnace",
+    "interact_with_smoker",
+    "interact_with_lectern",
+    "interact_with_campfire",
+    "interact_with_cartography_table",
+    "interact_with_loom",
+    "interact_with_stonecutter",
+    "bell_ring",
+    "raid_trigger",
+    "raid_win",
+    "interact_with_anvil",
+    "interact_with_grindstone",
+    "target_hit",
+    "interact_with_smithing_table",
+];
+
+/// The registry id of the custom statistic, the `minecraft:` namespace is optional
+pub fn custom_stat_id(name: &str) -> Option<usize> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    CUSTOM_STATS.iter().position(|stat| *stat == name)
+}
+
+#[derive(Error, Debug)]
+pub enum StatsDataError {
+    #[error("Io error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("Json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// The values of the statistics of a player by the stat type and then what is counted, both
+/// with their `minecraft:` namespace, e.g. `minecraft:mined` and `minecraft:stone`
+pub type StatsData = BTreeMap<String, BTreeMap<String, i32>>;
+
+#[derive(Serialize, Deserialize)]
+struct StatsFile {
+    #[serde(default)]
+    stats: StatsData,
+    #[serde(rename = "DataVersion")]
+    data_version: i32,
+}
+
+/// Reads and writes the `stats/<uuid>.json` files of a world, like vanilla's `ServerStatsCounter`
+pub struct StatsDataStorage {
+    folder: PathBuf,
+}
+
+impl StatsDataStorage {
+    pub fn new(root_folder: &Path) -> Self {
+        Self {
+            folder: root_folder.join(STATS_FOLDER),
+        }
+    }
+
+    fn path(&self, uuid: &str) -> PathBuf {
+        self.folder.join(format!("{uuid}.json"))
+    }
+
+    /// Loads the statistics of the player, which are empty if the player has never joined the
+    /// world
+    pub fn load(&self, uuid: &str) -> Result<StatsData, StatsDataError> {
+        let path = self.path(uuid);
+        if !path.exists() {
+            return Ok(StatsData::new());
+        }
+        let file: StatsFile = serde_json::from_slice(&fs::read(path)?)?;
+        Ok(file.stats)
+    }
+
+    pub fn save(&self, uuid: &str, stats: StatsData) -> Result<(), StatsDataError> {
+        fs::create_dir_all(&self.folder)?;
+        let file = StatsFile {
+            stats,
+            data_version: WORLD_DATA_VERSION,
+        };
+
+        write_atomically(&self.path(uuid), serde_json::to_vec(&file)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{custom_stat_id, StatType, StatsData, StatsDataStorage, CUSTOM_STATS};
+
+    #[test]
+    fn names() {
+        for (id, stat) in CUSTOM_STATS.iter().enumerate() {
+            assert_eq!(custom_stat_id(stat), Some(id));
+        }
+        assert_eq!(custom_stat_id("minecraft:play_time"), Some(1));
+        assert_eq!(custom_stat_id("walk"), None);
+        for (id, stat_type) in StatType::ALL.into_iter().enumerate() {
+            assert_eq!(stat_type as usize, id);
+            assert_eq!(StatType::from_name(stat_type.name()), Some(stat_type));
+        }
+    }
+    #[test]
+    fn saving_replaces_the_file() {
+        let root_folder =
+            std::env::temp_dir().join(format!("pumpkin-stats-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root_folder);
+        let storage = StatsDataStorage::new(&root_folder);
+        let uuid = "069a79f4-44e9-4726-a5be-fca90e38aaf5";
+
+        let mut stats = StatsData::new();
+        stats
+            .entry("minecraft:custom".to_string())
+            .or_default()
+            .insert("minecraft:jump".to_string(), 1);
+        storage.save(uuid, stats.clone()).unwrap();
+        stats
+            .get_mut("minecraft:custom")
+            .unwrap()
+            .insert("minecraft:jump".to_string(), 2);
+        storage.save(uuid, stats.clone()).unwrap();
+
+        assert_eq!(storage.load(uuid).unwrap(), stats);
+        let files = std::fs::read_dir(&storage.folder).unwrap().count();
+        let _ = std::fs::remove_dir_all(&root_folder);
+        assert_eq!(files, 1);
+    }
+}
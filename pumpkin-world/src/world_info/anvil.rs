@@ -61,18 +61,24 @@ impl WorldInfoWriter for AnvilLevelInfo {
                 spawn_z: info.spawn_z,
                 nbt_version: info.nbt_version,
                 version: info.version,
+                game_rules: info.game_rules,
+                data_packs: info.data_packs,
             },
         };
         // convert it into nbt
         let nbt = pumpkin_nbt::serializer::to_bytes_unnamed(&level).unwrap();
-        // now compress using GZip, TODO: im not sure about the to_vec, but writer is not implemented for BytesMut, see https://github.com/tokio-rs/bytes/pull/478
-        let mut encoder = GzEncoder::new(nbt.to_vec(), Compression::best());
-        let compressed_data = Vec::new();
-        encoder.write_all(&compressed_data)?;
+        // now compress using GZip
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&nbt)?;
+        let compressed_data = encoder.finish()?;
 
         // open file
         let path = level_folder.root_folder.join(LEVEL_DAT_FILE_NAME);
-        let mut world_info_file = OpenOptions::new().write(true).open(path)?;
+        let mut world_info_file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
         // write compressed data into file
         world_info_file.write_all(&compressed_data).unwrap();
 
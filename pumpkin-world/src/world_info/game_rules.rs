@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Declares a kind of game rule, every rule with its name in `level.dat` and its vanilla default
+macro_rules! game_rules {
+    ($rule:ident: $ty:ty { $($variant:ident = $name:literal => $default:expr),* $(,)? }) => {
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        pub enum $rule {
+            $($variant),*
+        }
+
+        impl $rule {
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            /// The name used by `/gamerule` and `level.dat`
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name),*
+                }
+            }
+
+            #[must_use]
+            pub const fn default_value(self) -> $ty {
+                match self {
+                    $(Self::$variant => $default),*
+                }
+            }
+        }
+    };
+}
+
+game_rules!(BoolRule: bool {
+    AnnounceAdvancements = "announceAdvancements" => true,
+    BlockExplosionDropDecay = "blockExplosionDropDecay" => true,
+    CommandBlockOutput = "commandBlockOutput" => true,
+    DisableElytraMovementCheck = "disableElytraMovementCheck" => false,
+    DisableRaids = "disableRaids" => false,
+    DoDaylightCycle = "doDaylightCycle" => true,
+    DoEntityDrops = "doEntityDrops" => true,
+    DoFireTick = "doFireTick" => true,
+    DoImmediateRespawn = "doImmediateRespawn" => false,
+    DoInsomnia = "doInsomnia" => true,
+    DoLimitedCrafting = "doLimitedCrafting" => false,
+    DoMobLoot = "doMobLoot" => true,
+    DoMobSpawning = "doMobSpawning" => true,
+    DoPatrolSpawning = "doPatrolSpawning" => true,
+    DoTileDrops = "doTileDrops" => true,
+    DoTraderSpawning = "doTraderSpawning" => true,
+    DoVinesSpread = "doVinesSpread" => true,
+    DoWardenSpawning = "doWardenSpawning" => true,
+    DoWeatherCycle = "doWeatherCycle" => true,
+    DrowningDamage = "drowningDamage" => true,
+    EnderPearlsVanishOnDeath = "enderPearlsVanishOnDeath" => true,
+    FallDamage = "fallDamage" => true,
+    FireDamage = "fireDamage" => true,
+    ForgiveDeadPlayers = "forgiveDeadPlayers" => true,
+    FreezeDamage = "freezeDamage" => true,
+    GlobalSoundEvents = "globalSoundEvents" => true,
+    KeepInventory = "keepInventory" => false,
+    LavaSourceConversion = "lavaSourceConversion" => false,
+    LogAdminCommands = "logAdminCommands" => true,
+    MobExplosionDropDecay = "mobExplosionDropDecay" => true,
+    MobGriefing = "mobGriefing" => true,
+    NaturalRegeneration = "naturalRegeneration" => true,
+    ProjectilesCanBreakBlocks = "projectilesCanBreakBlocks" => true,
+    ReducedDebugInfo = "reducedDebugInfo" => false,
+    SendCommandFeedback = "sendCommandFeedback" => true,
+    ShowDeathMessages = "showDeathMessages" => true,
+    SpectatorsGenerateChunks = "spectatorsGenerateChunks" => true,
+    TntExplosionDropDecay = "tntExplosionDropDecay" => false,
+    UniversalAnger = "universalAnger" => false,
+    WaterSourceConversion = "waterSourceConversion" => true,
+});
+
+game_rules!(IntRule: i32 {
+    CommandModificationBlockLimit = "commandModificationBlockLimit" => 32768,
+    MaxCommandChainLength = "maxCommandChainLength" => 65536,
+    MaxCommandForkCount = "maxCommandForkCount" => 65536,
+    MaxEntityCramming = "maxEntityCramming" => 24,
+    PlayersNetherPortalCreativeDelay = "playersNetherPortalCreativeDelay" => 1,
+    PlayersNetherPortalDefaultDelay = "playersNetherPortalDefaultDelay" => 80,
+    PlayersSleepingPercentage = "playersSleepingPercentage" => 100,
+    RandomTickSpeed = "randomTickSpeed" => 3,
+    SnowAccumulationHeight = "snowAccumulationHeight" => 1,
+    SpawnChunkRadius = "spawnChunkRadius" => 2,
+    SpawnRadius = "spawnRadius" => 10,
+});
+
+/// Any game rule, looked up by its name
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameRule {
+    Bool(BoolRule),
+    Int(IntRule),
+}
+
+impl GameRule {
+    /// Every game rule, the bool rules first
+    pub fn all() -> impl Iterator<Item = Self> {
+        BoolRule::ALL
+            .iter()
+            .copied()
+            .map(Self::Bool)
+            .chain(IntRule::ALL.iter().copied().map(Self::Int))
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all().find(|rule| rule.name() == name)
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bool(rule) => rule.name(),
+            Self::Int(rule) => rule.name(),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum GameRuleError {
+    #[error("Expected true or false but found {0}")]
+    InvalidBool(String),
+    #[error("Expected an integer but found {0}")]
+    InvalidInt(String),
+}
+
+/// The game rules of a world with their vanilla defaults, see `https://minecraft.wiki/w/Game_rule`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRules {
+    bools: [bool; BoolRule::ALL.len()],
+    ints: [i32; IntRule::ALL.len()],
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            bools: std::array::from_fn(|i| BoolRule::ALL[i].default_value()),
+            ints: std::array::from_fn(|i| IntRule::ALL[i].default_value()),
+        }
+    }
+}
+
+impl GameRules {
+    #[must_use]
+    pub const fn get_bool(&self, rule: BoolRule) -> bool {
+        self.bools[rule as usize]
+    }
+
+    pub fn set_bool(&mut self, rule: BoolRule, value: bool) {
+        self.bools[rule as usize] = value;
+    }
+
+    #[must_use]
+    pub const fn get_int(&self, rule: IntRule) -> i32 {
+        self.ints[rule as usize]
+    }
+
+    pub fn set_int(&mut self, rule: IntRule, value: i32) {
+        self.ints[rule as usize] = value;
+    }
+
+    /// The value as `/gamerule` shows it and `level.dat` stores it
+    #[must_use]
+    pub fn value_string(&self, rule: GameRule) -> String {
+        match rule {
+            GameRule::Bool(rule) => self.get_bool(rule).to_string(),
+            GameRule::Int(rule) => self.get_int(rule).to_string(),
+        }
+    }
+
+    /// Parses the value like vanilla reads it from `level.dat`
+    pub fn set_from_string(&mut self, rule: GameRule, value: &str) -> Result<(), GameRuleError> {
+        match rule {
+            GameRule::Bool(rule) => match value {
+                "true" => self.set_bool(rule, true),
+                "false" => self.set_bool(rule, false),
+                _ => return Err(GameRuleError::InvalidBool(value.to_string())),
+            },
+            GameRule::Int(rule) => {
+                let value = value
+                    .parse()
+                    .map_err(|_| GameRuleError::InvalidInt(value.to_string()))?;
+                self.set_int(rule, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Vanilla stores every rule as a string in the `GameRules` compound
+impl Serialize for GameRules {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        for rule in GameRule::all() {
+            map.serialize_entry(rule.name(), &self.value_string(rule))?;
+        }
+        map.end()
+    }
+}
+
+/// Unknown rules and invalid values are ignored, the rules keep their defaults
+impl<'de> Deserialize<'de> for GameRules {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = HashMap::<String, String>::deserialize(deserializer)?;
+        let mut rules = Self::default();
+        for (name, value) in stored {
+            if let Some(rule) = GameRule::from_name(&name) {
+                if let Err(error) = rules.set_from_string(rule, &value) {
+                    log::warn!("Ignoring game rule {name}: {error}");
+                }
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoolRule, GameRule, GameRuleError, GameRules, IntRule};
+
+    #[test]
+    fn rules_start_with_vanilla_defaults() {
+        let rules = GameRules::default();
+        assert!(rules.get_bool(BoolRule::DoDaylightCycle));
+        assert!(!rules.get_bool(BoolRule::KeepInventory));
+        assert_eq!(rules.get_int(IntRule::RandomTickSpeed), 3);
+        assert_eq!(rules.get_int(IntRule::PlayersSleepingPercentage), 100);
+    }
+
+    #[test]
+    fn rules_are_found_by_name() {
+        assert_eq!(
+            GameRule::from_name("mobGriefing"),
+            Some(GameRule::Bool(BoolRule::MobGriefing))
+        );
+        assert_eq!(
+            GameRule::from_name("randomTickSpeed"),
+            Some(GameRule::Int(IntRule::RandomTickSpeed))
+        );
+        assert_eq!(GameRule::from_name("mob_griefing"), None);
+    }
+
+    #[test]
+    fn values_are_parsed_by_type() {
+        let mut rules = GameRules::default();
+        let keep_inventory = GameRule::Bool(BoolRule::KeepInventory);
+        let tick_speed = GameRule::Int(IntRule::RandomTickSpeed);
+        assert_eq!(rules.set_from_string(keep_inventory, "true"), Ok(()));
+        assert_eq!(rules.set_from_string(tick_speed, "20"), Ok(()));
+        assert_eq!(
+            rules.set_from_string(keep_inventory, "1"),
+            Err(GameRuleError::InvalidBool("1".to_string()))
+        );
+        assert_eq!(
+            rules.set_from_string(tick_speed, "fast"),
+            Err(GameRuleError::InvalidInt("fast".to_string()))
+        );
+        assert_eq!(rules.value_string(keep_inventory), "true");
+        assert_eq!(rules.value_string(tick_speed), "20");
+    }
+}
@@ -3,9 +3,12 @@ use pumpkin_core::Difficulty;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{generation::Seed, level::LevelFolder};
+use crate::{datapack::DataPacks, generation::Seed, level::LevelFolder};
 
 pub mod anvil;
+pub mod game_rules;
+
+use game_rules::GameRules;
 
 pub(crate) trait WorldInfoReader {
     fn read_world_info(&self, level_folder: &LevelFolder) -> Result<LevelData, WorldInfoError>;
@@ -45,6 +48,12 @@ pub struct LevelData {
     pub nbt_version: i32,
     #[serde(rename = "Version")]
     pub version: WorldVersion,
+    // The game rules, stored as strings.
+    #[serde(default)]
+    pub game_rules: GameRules,
+    // The enabled and disabled datapacks.
+    #[serde(default)]
+    pub data_packs: DataPacks,
     // TODO: Implement the rest of the fields
 }
 
@@ -97,7 +106,7 @@ impl Default for LevelData {
             allow_commands: true,
             // TODO
             data_version: -1,
-            difficulty: Difficulty::Normal,
+            difficulty: BASIC_CONFIG.default_difficulty,
             world_gen_settings: Default::default(),
             last_played: -1,
             level_name: "world".to_string(),
@@ -106,6 +115,8 @@ impl Default for LevelData {
             spawn_z: 0,
             nbt_version: -1,
             version: Default::default(),
+            game_rules: GameRules::default(),
+            data_packs: DataPacks::default(),
         }
     }
 }
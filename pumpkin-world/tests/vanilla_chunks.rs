@@ -0,0 +1,151 @@
+//! Compares generated chunks against data extracted from vanilla. The fixtures are listed in
+//! `assets/vanilla_chunks.json` with the seed and the position of their chunk. Block fixtures
+//! are a JSON array of the block state ids of one chunk ordered by x, then y from the bottom,
+//! then z. Biome fixtures are a JSON array of the biome ids of the chunk's columns at sea level,
+//! ordered by x, then z.
+//!
+//! Noise fixtures are extracted without blending and without the beardifier, surface fixtures
+//! additionally need a single biome plains world. Biome fixtures name their dimension, the
+//! overworld is the default.
+
+use std::{fs, path::Path};
+
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_world::{
+    dimension::Dimension, generate_fixture_biomes, generate_fixture_chunk, FixtureStage,
+    WORLD_HEIGHT, WORLD_LOWEST_Y,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Fixture {
+    file: String,
+    seed: u64,
+    x: i32,
+    z: i32,
+    stage: Stage,
+    #[serde(default)]
+    dimension: FixtureDimension,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Stage {
+    Noise,
+    Surface,
+    Biomes,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+enum FixtureDimension {
+    #[default]
+    Overworld,
+    Nether,
+    End,
+}
+
+impl From<FixtureDimension> for Dimension {
+    fn from(dimension: FixtureDimension) -> Self {
+        match dimension {
+            FixtureDimension::Overworld => Self::OverWorld,
+            FixtureDimension::Nether => Self::Nether,
+            FixtureDimension::End => Self::End,
+        }
+    }
+}
+
+fn assets() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/assets"))
+}
+
+/// Describes how the generated blocks differ from the fixture, `None` if they are the same
+fn compare_blocks(expected: &[u16], actual: &[u16]) -> Option<String> {
+    if expected.len() != actual.len() {
+        return Some(format!(
+            "expected {} blocks, generated {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    let mut mismatches = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual);
+    let (index, (first_expected, first_actual)) = mismatches.next()?;
+    let column = 16 * WORLD_HEIGHT;
+    let x = index / column;
+    let y = (index % column) / 16;
+    let z = index % 16;
+    Some(format!(
+        "{} blocks differ, the first at {x} {} {z} is {first_actual} instead of {first_expected}",
+        mismatches.count() + 1,
+        y as i32 + WORLD_LOWEST_Y as i32,
+    ))
+}
+
+/// Describes how the generated biomes differ from the fixture, `None` if they are the same
+fn compare_biomes(expected: &[String], actual: &[String]) -> Option<String> {
+    if expected.len() != actual.len() {
+        return Some(format!(
+            "expected {} columns, generated {}",
+            expected.len(),
+            actual.len()
+        ));
+    }
+
+    let mut mismatches = expected
+        .iter()
+        .zip(actual)
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual);
+    let (index, (first_expected, first_actual)) = mismatches.next()?;
+    Some(format!(
+        "{} columns differ, the first at {} {} is {first_actual} instead of {first_expected}",
+        mismatches.count() + 1,
+        index / 16,
+        index % 16,
+    ))
+}
+
+#[test]
+fn generated_chunks_match_vanilla() {
+    let fixtures: Vec<Fixture> = serde_json::from_str(
+        &fs::read_to_string(assets().join("vanilla_chunks.json")).expect("no fixture list"),
+    )
+    .expect("failed to decode fixture list");
+    assert!(!fixtures.is_empty());
+
+    let mut failures = Vec::new();
+    for fixture in fixtures {
+        let contents = fs::read_to_string(assets().join(&fixture.file)).expect("no fixture file");
+        let at = Vector2::new(fixture.x, fixture.z);
+        let difference = match fixture.stage {
+            Stage::Biomes => {
+                let expected: Vec<String> =
+                    serde_json::from_str(&contents).expect("failed to decode fixture");
+                let actual: Vec<_> =
+                    generate_fixture_biomes(fixture.seed, fixture.dimension.into(), at)
+                        .into_iter()
+                        .map(|biome| biome.id())
+                        .collect();
+                compare_biomes(&expected, &actual)
+            }
+            stage => {
+                let expected: Vec<u16> =
+                    serde_json::from_str(&contents).expect("failed to decode fixture");
+                let stage = match stage {
+                    Stage::Surface => FixtureStage::Surface,
+                    _ => FixtureStage::Noise,
+                };
+                compare_blocks(&expected, &generate_fixture_chunk(fixture.seed, at, stage))
+            }
+        };
+        if let Some(difference) = difference {
+            failures.push(format!("{}: {difference}", fixture.file));
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -1,10 +1,13 @@
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
 use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::World;
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_inventory::OpenContainer;
-use pumpkin_world::block::block_registry::Block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_world::block::block_registry::{get_block, Block};
 use pumpkin_world::item::item_registry::Item;
+use pumpkin_world::item::ItemStack;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -60,12 +63,28 @@ impl BlockManager {
         block: &Block,
         player: &Player,
         location: WorldPosition,
+        item: &ItemStack,
         server: &Server,
     ) {
         let pumpkin_block = self.get_pumpkin_block(block);
         if let Some(pumpkin_block) = pumpkin_block {
             pumpkin_block
-                .on_placed(block, player, location, server)
+                .on_placed(block, player, location, item, server)
+                .await;
+        }
+    }
+
+    pub async fn on_player_will_destroy(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_player_will_destroy(block, player, location, server)
                 .await;
         }
     }
@@ -76,11 +95,12 @@ impl BlockManager {
         player: &Player,
         location: WorldPosition,
         server: &Server,
+        block_entity: Option<&NbtCompound>,
     ) {
         let pumpkin_block = self.get_pumpkin_block(block);
         if let Some(pumpkin_block) = pumpkin_block {
             pumpkin_block
-                .on_broken(block, player, location, server)
+                .on_broken(block, player, location, server, block_entity)
                 .await;
         }
     }
@@ -101,6 +121,53 @@ impl BlockManager {
         }
     }
 
+    pub async fn on_scheduled_tick(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_scheduled_tick(block, world, location, server)
+                .await;
+        }
+    }
+
+    /// Fluid ticks are handled by the block of the fluid, flowing fluids by the block of their
+    /// source, e.g. `minecraft:flowing_water` by `minecraft:water`
+    pub async fn on_fluid_tick(
+        &self,
+        fluid: &str,
+        world: &Arc<World>,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        let name = fluid.replace("flowing_", "");
+        let Some(block) = get_block(&name) else {
+            return;
+        };
+        self.on_scheduled_tick(block, world, location, server).await;
+    }
+
+    pub async fn on_neighbor_update(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        source: WorldPosition,
+        server: &Server,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_neighbor_update(block, world, location, source, server)
+                .await;
+        }
+    }
+
     #[must_use]
     pub fn get_pumpkin_block(&self, block: &Block) -> Option<&Arc<dyn PumpkinBlock>> {
         self.blocks
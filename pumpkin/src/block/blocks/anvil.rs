@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_core::GameMode;
+use pumpkin_inventory::{AnvilContainer, OpenContainer, WindowType};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::client::play::CWorldEvent;
+use pumpkin_world::{
+    block::block_registry::{get_block, Block},
+    item::item_registry::Item,
+};
+
+/// Like vanilla, every use has this chance to damage the anvil
+const DAMAGE_CHANCE: f32 = 0.12;
+/// The world event of an anvil being used
+const ANVIL_USED: i32 = 1030;
+/// The world event of an anvil breaking from use
+const ANVIL_DESTROYED: i32 = 1029;
+
+/// Anvils get chipped and damaged from use, but work the same
+macro_rules! anvil_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_use<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    open_anvil_screen(block, player, location, server).await;
+                }
+
+                async fn on_use_with_item<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &Item,
+                    server: &Server,
+                ) -> BlockActionResult {
+                    open_anvil_screen(block, player, location, server).await;
+                    BlockActionResult::Consume
+                }
+
+                async fn on_broken<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                    block_entity: Option<&NbtCompound>,
+                ) {
+                    super::standard_on_broken_with_container(
+                        block,
+                        player,
+                        location,
+                        server,
+                        block_entity,
+                    )
+                    .await;
+                }
+
+                async fn on_close<'a>(
+                    &self,
+                    _block: &Block,
+                    player: &Player,
+                    _location: WorldPosition,
+                    server: &Server,
+                    container: &mut OpenContainer,
+                ) {
+                    player.return_container_inputs(server, container).await;
+                }
+            }
+        )*
+    };
+}
+
+anvil_block! {
+    AnvilBlock => "minecraft:anvil",
+    ChippedAnvilBlock => "minecraft:chipped_anvil",
+    DamagedAnvilBlock => "minecraft:damaged_anvil",
+}
+
+async fn open_anvil_screen(
+    block: &Block,
+    player: &Player,
+    location: WorldPosition,
+    server: &Server,
+) {
+    super::standard_open_container_unique::<AnvilContainer>(
+        block,
+        player,
+        location,
+        server,
+        WindowType::Anvil,
+    )
+    .await;
+}
+
+/// The anvil an anvil becomes when it gets damaged, `None` if it breaks
+fn damaged_anvil(block: &Block) -> Option<&'static Block> {
+    match block.name.as_str() {
+        "anvil" => get_block("minecraft:chipped_anvil"),
+        "chipped_anvil" => get_block("minecraft:damaged_anvil"),
+        _ => None,
+    }
+}
+
+/// Called after the player took the output of the anvil they have open. Like vanilla's
+/// `AnvilMenu::onTake`, the anvil gets damaged by chance unless the player is in creative mode,
+/// a damaged anvil breaks
+pub async fn use_anvil(player: &Player, server: &Server) {
+    let Some(id) = player.open_container.load() else {
+        return;
+    };
+    let Some((location, block)) = server
+        .open_containers
+        .read()
+        .await
+        .get(&id)
+        .and_then(|container| Some((container.get_location()?, container.get_block()?)))
+    else {
+        return;
+    };
+    let world = player.world();
+    if player.gamemode.load() == GameMode::Creative || rand::random::<f32>() >= DAMAGE_CHANCE {
+        world
+            .broadcast_packet_all(&CWorldEvent::new(ANVIL_USED, &location, 0, false))
+            .await;
+        return;
+    }
+
+    let Ok(state_id) = world.get_block_state_id(location).await else {
+        return;
+    };
+    if let Some(damaged) = damaged_anvil(&block) {
+        // The damaged anvil keeps facing the same way
+        let properties: HashMap<_, _> = block
+            .state_properties(state_id)
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        world
+            .set_block_state(location, damaged.state_with_properties(&properties))
+            .await;
+        // The container stays open for the damaged anvil
+        if let Some(container) = server.open_containers.write().await.get_mut(&id) {
+            container.set_block(damaged.clone());
+        }
+        world
+            .broadcast_packet_all(&CWorldEvent::new(ANVIL_USED, &location, 0, false))
+            .await;
+    } else {
+        world.set_block_state(location, 0).await;
+        world
+            .broadcast_packet_all(&CWorldEvent::new(ANVIL_DESTROYED, &location, 0, false))
+            .await;
+        player.force_close_container(server).await;
+    }
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::{Player, RespawnPoint};
+use crate::server::Server;
+use crate::world::{sleep::bed_halves, World};
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_core::{text::TextComponent, GameMode};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::block::{
+    block_registry::{get_block_by_state_id, Block},
+    explosion::BED_POWER,
+};
+use pumpkin_world::damage::DamageType;
+use pumpkin_world::item::item_registry::Item;
+
+/// How far players can be from a bed to sleep in it, horizontally and vertically
+const BED_REACH: (f64, f64) = (3.0, 2.0);
+
+/// Beds only work in the overworld, anywhere else they explode when used like vanilla's
+/// `BedBlock.useWithoutItem`. Returns whether the bed was used
+async fn use_bed(block: &Block, player: &Player, location: WorldPosition, server: &Server) -> bool {
+    let world = &player.world();
+    let Some(state_id) = world.level.loaded_block_state(&location).await else {
+        return false;
+    };
+    let Some((head, foot)) = bed_halves(block, state_id, location) else {
+        return false;
+    };
+    if matches!(
+        world.dimension_type,
+        DimensionType::Overworld | DimensionType::OverworldCaves
+    ) {
+        sleep_in_bed(block, player, world, head, foot).await;
+        return true;
+    }
+    // Both halves are removed first, so they don't drop when the explosion reaches them
+    for part in [head, foot] {
+        let is_bed = world
+            .level
+            .loaded_block_state(&part)
+            .await
+            .and_then(get_block_by_state_id)
+            .is_some_and(|bed| bed.id == block.id);
+        if is_bed {
+            world.set_block_state(part, 0).await;
+        }
+    }
+    let center = Vector3::new(
+        f64::from(head.0.x) + 0.5,
+        f64::from(head.0.y) + 0.5,
+        f64::from(head.0.z) + 0.5,
+    );
+    world
+        .explode(center, BED_POWER, true, DamageType::BadRespawnPoint, server)
+        .await;
+    true
+}
+
+/// Whether the player is close enough to one of the halves of the bed to sleep in it
+fn can_reach_bed(player: &Player, halves: [WorldPosition; 2]) -> bool {
+    let pos = player.living_entity.entity.pos.load();
+    let (horizontal, vertical) = BED_REACH;
+    halves.iter().any(|half| {
+        (f64::from(half.0.x) + 0.5 - pos.x).abs() <= horizontal
+            && (f64::from(half.0.y) - pos.y).abs() <= vertical
+            && (f64::from(half.0.z) + 0.5 - pos.z).abs() <= horizontal
+    })
+}
+
+/// Sets the player's respawn point to the bed and lays them into it at night, like vanilla's
+/// `ServerPlayer.startSleepInBed`
+async fn sleep_in_bed(
+    block: &Block,
+    player: &Player,
+    world: &Arc<World>,
+    head: WorldPosition,
+    foot: WorldPosition,
+) {
+    if player.is_sleeping() {
+        return;
+    }
+    let occupied = world
+        .level
+        .loaded_block_state(&head)
+        .await
+        .is_some_and(|state_id| {
+            block
+                .state_properties(state_id)
+                .contains(&("occupied", "true"))
+        });
+    let problem = if occupied {
+        Some("This bed is occupied")
+    } else if !can_reach_bed(player, [head, foot]) {
+        Some("You may not rest now; the bed is too far away")
+    } else {
+        player
+            .set_respawn_point(RespawnPoint {
+                dimension: world.key.clone(),
+                position: head,
+            })
+            .await;
+        if !world.can_sleep().await {
+            Some("You can only sleep at night or during thunderstorms")
+        } else if player.gamemode.load() != GameMode::Creative && world.monsters_near(head).await {
+            Some("You may not rest now; there are monsters nearby")
+        } else {
+            None
+        }
+    };
+    if let Some(problem) = problem {
+        player
+            .send_action_bar_message(&TextComponent::text(problem))
+            .await;
+        return;
+    }
+    player.start_sleeping(head).await;
+    world.set_bed_occupied(head, true).await;
+    world.announce_sleeping().await;
+}
+
+macro_rules! bed_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_use<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    use_bed(block, player, location, server).await;
+                }
+
+                async fn on_use_with_item<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &Item,
+                    server: &Server,
+                ) -> BlockActionResult {
+                    if use_bed(block, player, location, server).await {
+                        BlockActionResult::Consume
+                    } else {
+                        BlockActionResult::Continue
+                    }
+                }
+            }
+        )*
+    };
+}
+
+bed_block!(
+    WhiteBedBlock => "minecraft:white_bed",
+    OrangeBedBlock => "minecraft:orange_bed",
+    MagentaBedBlock => "minecraft:magenta_bed",
+    LightBlueBedBlock => "minecraft:light_blue_bed",
+    YellowBedBlock => "minecraft:yellow_bed",
+    LimeBedBlock => "minecraft:lime_bed",
+    PinkBedBlock => "minecraft:pink_bed",
+    GrayBedBlock => "minecraft:gray_bed",
+    LightGrayBedBlock => "minecraft:light_gray_bed",
+    CyanBedBlock => "minecraft:cyan_bed",
+    PurpleBedBlock => "minecraft:purple_bed",
+    BlueBedBlock => "minecraft:blue_bed",
+    BrownBedBlock => "minecraft:brown_bed",
+    GreenBedBlock => "minecraft:green_bed",
+    RedBedBlock => "minecraft:red_bed",
+    BlackBedBlock => "minecraft:black_bed",
+);
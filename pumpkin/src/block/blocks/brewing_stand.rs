@@ -0,0 +1,73 @@
+use crate::block::block_manager::BlockActionResult;
+use crate::entity::player::Player;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_inventory::BrewingStandContainer;
+use pumpkin_inventory::WindowType;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::item::item_registry::Item;
+
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server};
+
+#[pumpkin_block("minecraft:brewing_stand")]
+pub struct BrewingStandBlock;
+
+#[async_trait]
+impl PumpkinBlock for BrewingStandBlock {
+    async fn on_use<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        self.open_brewing_stand_screen(block, player, location, server)
+            .await;
+    }
+
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        self.open_brewing_stand_screen(block, player, location, server)
+            .await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_broken<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+        block_entity: Option<&NbtCompound>,
+    ) {
+        super::standard_on_broken_with_container(block, player, location, server, block_entity)
+            .await;
+    }
+}
+
+impl BrewingStandBlock {
+    pub async fn open_brewing_stand_screen(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        super::standard_open_container::<BrewingStandContainer>(
+            block,
+            player,
+            location,
+            server,
+            WindowType::BrewingStand,
+        )
+        .await;
+    }
+}
@@ -0,0 +1,50 @@
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
+
+/// Puts the held food on the campfire, like vanilla's `CampfireBlock.useItemOn`. Items which
+/// can't be cooked on campfires are used as usual
+async fn place_held_food(player: &Player, location: WorldPosition) -> BlockActionResult {
+    let Some(held) = player.inventory().lock().await.held_item().cloned() else {
+        return BlockActionResult::Continue;
+    };
+    if !player.world().place_campfire_food(location, &held).await {
+        return BlockActionResult::Continue;
+    }
+    player.consume_held_item().await;
+    BlockActionResult::Consume
+}
+
+/// Campfires and soul campfires only differ in their light and damage
+macro_rules! campfire_block {
+    ($($name:ident => $id:literal);* $(;)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_use_with_item<'a>(
+                    &self,
+                    _block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &Item,
+                    _server: &Server,
+                ) -> BlockActionResult {
+                    place_held_food(player, location).await
+                }
+            }
+        )*
+    };
+}
+
+campfire_block! {
+    CampfireBlock => "minecraft:campfire";
+    SoulCampfireBlock => "minecraft:soul_campfire";
+}
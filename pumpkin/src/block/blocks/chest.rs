@@ -1,19 +1,30 @@
+use std::sync::{atomic::Ordering, Arc};
+
 use async_trait::async_trait;
 use pumpkin_core::math::position::WorldPosition;
-use pumpkin_inventory::{Chest, OpenContainer, WindowType};
+use pumpkin_inventory::{
+    read_items_nbt, write_items_nbt, Chest, LargeChest, OpenContainer, WindowType,
+};
 use pumpkin_macros::{pumpkin_block, sound};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
 use pumpkin_world::{
-    block::block_registry::{get_block, Block},
-    item::item_registry::Item,
+    block::{
+        block_registry::{get_block, get_block_by_state_id, Block},
+        BlockFace,
+    },
+    item::{item_registry::Item, ItemStack},
 };
 
 use crate::{
     block::{block_manager::BlockActionResult, pumpkin_block::PumpkinBlock},
     entity::player::Player,
     server::Server,
+    world::World,
 };
 
+const CHEST_SLOTS: usize = 27;
+
 #[derive(PartialEq)]
 pub enum ChestState {
     IsOpened,
@@ -49,14 +60,79 @@ impl PumpkinBlock for ChestBlock {
         BlockActionResult::Consume
     }
 
+    /// Chests face the player and join a chest next to them which faces the same way, like
+    /// vanilla's `ChestBlock.getStateForPlacement`. Sneaking players place single chests
+    async fn on_placed<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        _item: &ItemStack,
+        _server: &Server,
+    ) {
+        let entity = &player.living_entity.entity;
+        let facing = super::piston::placement_facing(entity.yaw.load(), 0.0);
+        let world = player.world();
+        let left = relative(location, facing.clockwise());
+        let right = relative(location, facing.clockwise().opposite());
+        let chest_type = if entity.sneaking.load(Ordering::Relaxed) {
+            SINGLE
+        } else if partner_facing(block, &world, left).await == Some(facing) {
+            LEFT
+        } else if partner_facing(block, &world, right).await == Some(facing) {
+            RIGHT
+        } else {
+            SINGLE
+        };
+        world
+            .set_block_state(location, chest_state(block, facing, chest_type))
+            .await;
+        if let Some(side) = connected_direction(facing, chest_type) {
+            let other_type = if chest_type == LEFT { RIGHT } else { LEFT };
+            world
+                .set_block_state(
+                    relative(location, side),
+                    chest_state(block, facing, other_type),
+                )
+                .await;
+        }
+    }
+
     async fn on_broken<'a>(
         &self,
         block: &Block,
         player: &Player,
         location: WorldPosition,
         server: &Server,
+        block_entity: Option<&NbtCompound>,
+    ) {
+        if !self
+            .split_large_chests(block, player, location, server)
+            .await
+        {
+            super::standard_on_broken_with_container(block, player, location, server, block_entity)
+                .await;
+        }
+    }
+
+    /// Halves turn back into single chests once their other half is gone, like vanilla's
+    /// `ChestBlock.updateShape`
+    async fn on_neighbor_update<'a>(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        _source: WorldPosition,
+        _server: &Server,
     ) {
-        super::standard_on_broken_with_container(block, player, location, server).await;
+        let Some((facing, chest_type)) = chest_properties(block, world, location).await else {
+            return;
+        };
+        if chest_type != SINGLE && find_adjacent_chest(block, world, location).await.is_none() {
+            world
+                .set_block_state(location, chest_state(block, facing, SINGLE))
+                .await;
+        }
     }
 
     async fn on_close<'a>(
@@ -68,6 +144,9 @@ impl PumpkinBlock for ChestBlock {
         container: &mut OpenContainer,
     ) {
         container.remove_player(player.entity_id());
+        if container.get_number_of_players() == 0 {
+            save_chest(&player.world(), container).await;
+        }
 
         self.play_chest_action(container, player, location, server, ChestState::IsClosed)
             .await;
@@ -82,15 +161,21 @@ impl ChestBlock {
         location: WorldPosition,
         server: &Server,
     ) {
-        // TODO: shouldn't Chest and window type be constrained together to avoid errors?
-        super::standard_open_container::<Chest>(
-            block,
-            player,
-            location,
-            server,
-            WindowType::Generic9x3,
-        )
-        .await;
+        let opened_large_chest = match find_adjacent_chest(block, &player.world(), location).await {
+            Some(other) => open_large_chest(block, player, location, other, server).await,
+            None => false,
+        };
+        if !opened_large_chest {
+            // TODO: shouldn't Chest and window type be constrained together to avoid errors?
+            super::standard_open_container::<Chest>(
+                block,
+                player,
+                location,
+                server,
+                WindowType::Generic9x3,
+            )
+            .await;
+        }
 
         if let Some(container_id) = server.get_container_id(location, block.clone()).await {
             let open_containers = server.open_containers.read().await;
@@ -101,6 +186,57 @@ impl ChestBlock {
         }
     }
 
+    /// Large chests are split when one of their halves is broken, the items of the broken half
+    /// drop and the remaining half keeps its items. Returns whether an open large chest was split
+    async fn split_large_chests(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) -> bool {
+        let Some(container_ids) = server.get_all_container_ids(location, block.clone()).await
+        else {
+            return false;
+        };
+        let world = player.world();
+        let mut split = false;
+        let mut dropped = Vec::new();
+        let mut open_containers = server.open_containers.write().await;
+        for id in container_ids {
+            let id = u64::from(id);
+            let Some(container) = open_containers.get(&id) else {
+                continue;
+            };
+            let (Some(first), Some(second)) =
+                (container.get_location(), container.get_second_location())
+            else {
+                continue;
+            };
+
+            let mut slots = chest_slots(container).await;
+            let broken_half = if location == first {
+                slots.drain(..CHEST_SLOTS)
+            } else {
+                slots.drain(CHEST_SLOTS..)
+            };
+            dropped.extend(broken_half.flatten());
+            split = true;
+            let remaining = if location == first { second } else { first };
+            world
+                .set_block_entity_nbt(remaining, chest_nbt(&slots))
+                .await;
+
+            player.open_container.store(None);
+            super::close_all_in_container(player, container).await;
+            open_containers.remove(&id);
+        }
+        drop(open_containers);
+
+        super::drop_items(&world, server, location, dropped).await;
+        split
+    }
+
     pub async fn play_chest_action(
         &self,
         container: &OpenContainer,
@@ -134,3 +270,240 @@ impl ChestBlock {
         }
     }
 }
+
+// Values of the `type` property of chests
+const SINGLE: &str = "single";
+const LEFT: &str = "left";
+const RIGHT: &str = "right";
+
+fn relative(position: WorldPosition, face: BlockFace) -> WorldPosition {
+    WorldPosition(position.0 + face.to_offset())
+}
+
+fn chest_state(block: &Block, facing: BlockFace, chest_type: &str) -> u16 {
+    block.state_with_properties(
+        &[
+            ("facing".to_string(), facing.name().to_string()),
+            ("type".to_string(), chest_type.to_string()),
+        ]
+        .into(),
+    )
+}
+
+/// The `facing` and `type` of the chest at the position, if it is this kind of chest
+async fn chest_properties(
+    block: &Block,
+    world: &World,
+    location: WorldPosition,
+) -> Option<(BlockFace, &'static str)> {
+    let state_id = world.get_block_state_id(location).await.ok()?;
+    let chest = get_block_by_state_id(state_id)?;
+    if chest.id != block.id {
+        return None;
+    }
+    let properties = chest.state_properties(state_id);
+    let property = |name: &str| {
+        properties
+            .iter()
+            .find_map(|(property, value)| (*property == name).then_some(*value))
+    };
+    let facing = property("facing").and_then(BlockFace::from_name)?;
+    let chest_type = [SINGLE, LEFT, RIGHT]
+        .into_iter()
+        .find(|chest_type| property("type") == Some(chest_type))?;
+    Some((facing, chest_type))
+}
+
+/// The side of the chest its other half is on, like vanilla's `ChestBlock.getConnectedDirection`
+fn connected_direction(facing: BlockFace, chest_type: &str) -> Option<BlockFace> {
+    match chest_type {
+        LEFT => Some(facing.clockwise()),
+        RIGHT => Some(facing.clockwise().opposite()),
+        _ => None,
+    }
+}
+
+/// The facing of a single chest at the position, which a placed chest can join, like vanilla's
+/// `ChestBlock.candidatePartnerFacing`
+async fn partner_facing(
+    block: &Block,
+    world: &World,
+    location: WorldPosition,
+) -> Option<BlockFace> {
+    chest_properties(block, world, location)
+        .await
+        .filter(|(_, chest_type)| *chest_type == SINGLE)
+        .map(|(facing, _)| facing)
+}
+
+/// Returns the position of the other half of a large chest, both halves have to face the same
+/// way and point at each other
+async fn find_adjacent_chest(
+    block: &Block,
+    world: &World,
+    location: WorldPosition,
+) -> Option<WorldPosition> {
+    let (facing, chest_type) = chest_properties(block, world, location).await?;
+    let other = relative(location, connected_direction(facing, chest_type)?);
+    let (other_facing, other_type) = chest_properties(block, world, other).await?;
+    let points_back = connected_direction(other_facing, other_type)
+        .is_some_and(|side| relative(other, side) == location);
+    (other_facing == facing && points_back).then_some(other)
+}
+
+/// Opens the large chest made up of both halves, returns false if it could not be opened
+/// because one of the halves is still opened as a normal chest
+async fn open_large_chest(
+    block: &Block,
+    player: &Player,
+    location: WorldPosition,
+    other: WorldPosition,
+    server: &Server,
+) -> bool {
+    // Order the halves by position, so both halves open the same layout
+    let (first, second) = if (location.0.x, location.0.z) < (other.0.x, other.0.z) {
+        (location, other)
+    } else {
+        (other, location)
+    };
+    let world = &player.world();
+    let mut halves_nbt = [
+        world.get_block_entity_nbt(first).await,
+        world.get_block_entity_nbt(second).await,
+    ];
+    for (location, nbt) in [first, second].into_iter().zip(&mut halves_nbt) {
+        if let Some(nbt) = nbt {
+            super::unpack_loot_table(world, location, nbt, CHEST_SLOTS).await;
+        }
+    }
+
+    let entity_id = player.entity_id();
+    let mut open_containers = server.open_containers.write().await;
+    let mut large_chest_id = None;
+    let mut unused_chest_ids = vec![];
+    for (id, container) in open_containers.iter() {
+        if !(container.is_location(first) || container.is_location(second))
+            || container.get_block().is_none_or(|b| b.id != block.id)
+        {
+            continue;
+        }
+        if container.get_second_location().is_some() {
+            large_chest_id = Some(*id);
+        } else if container.get_number_of_players() == 0 {
+            unused_chest_ids.push(*id);
+        } else {
+            return false;
+        }
+    }
+    // The contents of normal chests were already saved when they were closed
+    for id in unused_chest_ids {
+        open_containers.remove(&id);
+    }
+
+    if let Some(id) = large_chest_id {
+        log::debug!("Using previous large chest container ID: {id}");
+        if let Some(container) = open_containers.get_mut(&id) {
+            container.add_player(entity_id);
+            player.open_container.store(Some(id));
+        }
+    } else {
+        let new_id = server.new_container_id();
+        log::debug!("Creating new large chest container ID: {new_id}");
+        let mut open_container = OpenContainer::new_empty_container::<LargeChest>(
+            entity_id,
+            Some(first),
+            Some(block.clone()),
+        );
+        open_container.set_second_location(Some(second));
+        load_large_chest(&open_container, halves_nbt).await;
+        open_containers.insert(new_id.into(), open_container);
+        player.open_container.store(Some(new_id.into()));
+    }
+    drop(open_containers);
+    player.open_container(server, WindowType::Generic9x6).await;
+    true
+}
+
+async fn load_large_chest(container: &OpenContainer, halves_nbt: [Option<NbtCompound>; 2]) {
+    let mut halves = [[const { None }; CHEST_SLOTS], [const { None }; CHEST_SLOTS]];
+    for (half, nbt) in halves.iter_mut().zip(halves_nbt) {
+        if let Some(nbt) = nbt {
+            read_items_nbt(half, &nbt);
+        }
+    }
+    let container = container.get_container();
+    let mut container = container.lock().await;
+    for (slot, item) in container
+        .all_slots()
+        .into_iter()
+        .zip(halves.into_iter().flatten())
+    {
+        *slot = item;
+    }
+}
+
+async fn chest_slots(container: &OpenContainer) -> Vec<Option<ItemStack>> {
+    let container = container.get_container();
+    let container = container.lock().await;
+    container
+        .all_slots_ref()
+        .into_iter()
+        .map(Option::<&ItemStack>::cloned)
+        .collect()
+}
+
+fn chest_nbt(slots: &[Option<ItemStack>]) -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.put(
+        "id".to_string(),
+        NbtTag::String("minecraft:chest".to_string()),
+    );
+    write_items_nbt(slots, &mut nbt);
+    nbt
+}
+
+/// Saves every half of the chest into its own block entity
+async fn save_chest(world: &World, container: &OpenContainer) {
+    let slots = chest_slots(container).await;
+    let locations = [container.get_location(), container.get_second_location()];
+    for (location, half) in locations
+        .into_iter()
+        .flatten()
+        .zip(slots.chunks(CHEST_SLOTS))
+    {
+        world.set_block_entity_nbt(location, chest_nbt(half)).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::block::{block_registry::get_block, BlockFace};
+
+    use super::{chest_state, connected_direction, LEFT, RIGHT, SINGLE};
+
+    #[test]
+    fn chest_halves_point_at_each_other() {
+        assert_eq!(
+            connected_direction(BlockFace::North, LEFT),
+            Some(BlockFace::East)
+        );
+        assert_eq!(
+            connected_direction(BlockFace::North, RIGHT),
+            Some(BlockFace::West)
+        );
+        assert_eq!(
+            connected_direction(BlockFace::East, LEFT),
+            Some(BlockFace::South)
+        );
+        assert_eq!(connected_direction(BlockFace::North, SINGLE), None);
+    }
+
+    #[test]
+    fn chest_states_keep_facing_and_type() {
+        let chest = get_block("minecraft:chest").unwrap();
+        let state = chest_state(chest, BlockFace::West, RIGHT);
+        let properties = chest.state_properties(state);
+        assert!(properties.contains(&("facing", "west")));
+        assert!(properties.contains(&("type", "right")));
+    }
+}
@@ -6,6 +6,7 @@ use async_trait::async_trait;
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_inventory::{CraftingTable, OpenContainer, WindowType};
 use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
 
 #[pumpkin_block("minecraft:crafting_table")]
@@ -43,8 +44,10 @@ impl PumpkinBlock for CraftingTableBlock {
         player: &Player,
         location: WorldPosition,
         server: &Server,
+        block_entity: Option<&NbtCompound>,
     ) {
-        super::standard_on_broken_with_container(block, player, location, server).await;
+        super::standard_on_broken_with_container(block, player, location, server, block_entity)
+            .await;
     }
 
     async fn on_close<'a>(
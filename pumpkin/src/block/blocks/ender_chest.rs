@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_inventory::{OpenContainer, WindowType};
+use pumpkin_macros::{pumpkin_block, sound};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
+use tokio::sync::Mutex;
+
+use crate::{
+    block::{block_manager::BlockActionResult, pumpkin_block::PumpkinBlock},
+    entity::player::Player,
+    server::Server,
+};
+
+/// Players further away than this don't see the lid move
+const LID_VIEW_DISTANCE: f64 = 64.0;
+
+/// Every player looks into their own ender chest, so the block only keeps track of
+/// how many players have it open to animate the lid
+#[pumpkin_block("minecraft:ender_chest")]
+#[derive(Default)]
+pub struct EnderChestBlock {
+    viewers: Mutex<HashMap<WorldPosition, u8>>,
+}
+
+#[async_trait]
+impl PumpkinBlock for EnderChestBlock {
+    async fn on_use<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        self.open_ender_chest(block, player, location, server).await;
+    }
+
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        _item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        self.open_ender_chest(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_broken<'a>(
+        &self,
+        _block: &Block,
+        _player: &Player,
+        location: WorldPosition,
+        _server: &Server,
+        _block_entity: Option<&NbtCompound>,
+    ) {
+        // The items belong to the players, their windows get closed once they notice the block is gone
+        self.viewers.lock().await.remove(&location);
+    }
+
+    async fn on_close<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        _server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        let viewers = {
+            let mut viewers = self.viewers.lock().await;
+            let Some(count) = viewers.get_mut(&location) else {
+                return;
+            };
+            *count = count.saturating_sub(1);
+            let count = *count;
+            if count == 0 {
+                viewers.remove(&location);
+            }
+            count
+        };
+        if viewers == 0 {
+            player
+                .world()
+                .play_block_sound(sound!("block.ender_chest.close"), location)
+                .await;
+        }
+        animate_lid(block, player, location, viewers).await;
+    }
+}
+
+impl EnderChestBlock {
+    async fn open_ender_chest(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        let entity_id = player.entity_id();
+        let id = server.new_container_id();
+        let open_container =
+            OpenContainer::new_for_entity(entity_id, entity_id, player.ender_chest.clone())
+                .with_block(location, block.clone());
+        server
+            .open_containers
+            .write()
+            .await
+            .insert(id.into(), open_container);
+        player.open_container.store(Some(id.into()));
+        if !player.open_container(server, WindowType::Generic9x3).await {
+            return;
+        }
+
+        let viewers = {
+            let mut viewers = self.viewers.lock().await;
+            let count = viewers.entry(location).or_default();
+            *count = count.saturating_add(1);
+            *count
+        };
+        if viewers == 1 {
+            player
+                .world()
+                .play_block_sound(sound!("block.ender_chest.open"), location)
+                .await;
+        }
+        animate_lid(block, player, location, viewers).await;
+    }
+}
+
+/// Opens the lid while anyone looks into the ender chest, like chests and shulker boxes do
+async fn animate_lid(block: &Block, player: &Player, location: WorldPosition, viewers: u8) {
+    let center = Vector3::new(
+        f64::from(location.0.x) + 0.5,
+        f64::from(location.0.y) + 0.5,
+        f64::from(location.0.z) + 0.5,
+    );
+    player
+        .world()
+        .broadcast_packet_near(
+            &center,
+            LID_VIEW_DISTANCE,
+            &CBlockAction::new(&location, 1, viewers, VarInt(block.id.into())),
+        )
+        .await;
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::chunk::ticks::TickPriority;
+use pumpkin_world::item::ItemStack;
+
+/// Ticks between a falling block losing its support and starting to fall
+const FALL_DELAY: u32 = 2;
+
+/// Concrete powder touching water turns into concrete, other blocks are checked for falling in a
+/// moment, like vanilla's `FallingBlock`
+async fn check_falling(block: &Block, world: &Arc<World>, location: WorldPosition) {
+    let Ok(state_id) = world.get_block_state_id(location).await else {
+        return;
+    };
+    if let Some(concrete) = world.level.harden_concrete_powder(location, state_id) {
+        world.set_block_state(location, concrete).await;
+        return;
+    }
+    world
+        .schedule_block_tick(location, block, FALL_DELAY, TickPriority::Normal)
+        .await;
+}
+
+/// Sand, gravel and concrete powder fall down when there is nothing below them
+macro_rules! falling_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_placed<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &ItemStack,
+                    _server: &Server,
+                ) {
+                    check_falling(block, &player.world(), location).await;
+                }
+
+                async fn on_neighbor_update<'a>(
+                    &self,
+                    block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    _source: WorldPosition,
+                    _server: &Server,
+                ) {
+                    check_falling(block, world, location).await;
+                }
+
+                async fn on_scheduled_tick<'a>(
+                    &self,
+                    _block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    if let Some(state_id) = world.level.start_falling(location) {
+                        world.spawn_falling_block(location, state_id, server).await;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+falling_block!(
+    SandBlock => "minecraft:sand",
+    RedSandBlock => "minecraft:red_sand",
+    GravelBlock => "minecraft:gravel",
+    WhiteConcretePowderBlock => "minecraft:white_concrete_powder",
+    OrangeConcretePowderBlock => "minecraft:orange_concrete_powder",
+    MagentaConcretePowderBlock => "minecraft:magenta_concrete_powder",
+    LightBlueConcretePowderBlock => "minecraft:light_blue_concrete_powder",
+    YellowConcretePowderBlock => "minecraft:yellow_concrete_powder",
+    LimeConcretePowderBlock => "minecraft:lime_concrete_powder",
+    PinkConcretePowderBlock => "minecraft:pink_concrete_powder",
+    GrayConcretePowderBlock => "minecraft:gray_concrete_powder",
+    LightGrayConcretePowderBlock => "minecraft:light_gray_concrete_powder",
+    CyanConcretePowderBlock => "minecraft:cyan_concrete_powder",
+    PurpleConcretePowderBlock => "minecraft:purple_concrete_powder",
+    BlueConcretePowderBlock => "minecraft:blue_concrete_powder",
+    BrownConcretePowderBlock => "minecraft:brown_concrete_powder",
+    GreenConcretePowderBlock => "minecraft:green_concrete_powder",
+    RedConcretePowderBlock => "minecraft:red_concrete_powder",
+    BlackConcretePowderBlock => "minecraft:black_concrete_powder",
+);
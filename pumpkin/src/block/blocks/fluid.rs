@@ -0,0 +1,41 @@
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::block::fluid::Fluid;
+use std::sync::Arc;
+
+#[pumpkin_block("minecraft:water")]
+pub struct WaterBlock;
+
+#[async_trait]
+impl PumpkinBlock for WaterBlock {
+    async fn on_scheduled_tick<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        world.tick_fluid(location, Fluid::Water, server).await;
+    }
+}
+
+#[pumpkin_block("minecraft:lava")]
+pub struct LavaBlock;
+
+#[async_trait]
+impl PumpkinBlock for LavaBlock {
+    async fn on_scheduled_tick<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        world.tick_fluid(location, Fluid::Lava, server).await;
+    }
+}
@@ -5,6 +5,7 @@ use crate::server::Server;
 use async_trait::async_trait;
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_registry::SYNCED_REGISTRIES;
 use pumpkin_world::block::block_registry::Block;
 use pumpkin_world::item::item_registry::Item;
@@ -22,7 +23,7 @@ impl PumpkinBlock for JukeboxBlock {
         _server: &Server,
     ) {
         // For now just stop the music at this position
-        let world = &player.living_entity.entity.world;
+        let world = &player.living_entity.entity.world();
 
         world.stop_record(location).await;
     }
@@ -35,7 +36,7 @@ impl PumpkinBlock for JukeboxBlock {
         item: &Item,
         _server: &Server,
     ) -> BlockActionResult {
-        let world = &player.living_entity.entity.world;
+        let world = &player.living_entity.entity.world();
 
         let Some(jukebox_playable) = &item.components.jukebox_playable else {
             return BlockActionResult::Continue;
@@ -63,9 +64,10 @@ impl PumpkinBlock for JukeboxBlock {
         player: &Player,
         location: WorldPosition,
         _server: &Server,
+        _block_entity: Option<&NbtCompound>,
     ) {
         // For now just stop the music at this position
-        let world = &player.living_entity.entity.world;
+        let world = &player.living_entity.entity.world();
 
         world.stop_record(location).await;
     }
@@ -1,34 +1,139 @@
+use std::sync::Arc;
+
 use pumpkin_core::math::position::WorldPosition;
-use pumpkin_inventory::{Container, OpenContainer, WindowType};
-use pumpkin_world::block::block_registry::Block;
+use pumpkin_inventory::{read_items_nbt, write_items_nbt, Container, OpenContainer, WindowType};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::{
+    block::block_registry::Block,
+    item::ItemStack,
+    loot::{block_drops, fill_container, LootContext},
+};
 
-use crate::{entity::player::Player, server::Server};
+use crate::{entity::player::Player, server::Server, world::World};
 
+pub(crate) mod anvil;
+pub(crate) mod bed;
+pub(crate) mod brewing_stand;
+pub(crate) mod campfire;
 pub(crate) mod chest;
 pub(crate) mod crafting_table;
+pub(crate) mod ender_chest;
+pub(crate) mod falling;
+pub(crate) mod fluid;
 pub(crate) mod furnace;
 pub(crate) mod jukebox;
+pub(crate) mod piston;
+pub(crate) mod portal;
+pub(crate) mod respawn_anchor;
+pub(crate) mod shulker_box;
+pub(crate) mod supported;
+pub(crate) mod tnt;
+pub(crate) mod workstation;
+
+/// Breaks a block which can't stay where it is and drops it as an item, like vanilla does when
+/// blocks lose what they are attached to
+pub async fn pop_block(
+    world: &Arc<World>,
+    block: &Block,
+    location: WorldPosition,
+    server: &Server,
+) {
+    world.break_block(location, None).await;
+    let drops = block_drops(
+        &block.name,
+        block.item_id,
+        &LootContext::default(),
+        &mut rand::thread_rng(),
+    );
+    for drop in drops {
+        world.pop_resource(server, location, drop).await;
+    }
+    world.update_neighbors(location, server).await;
+}
+
+/// Rolls the loot of a container which structure generation left a loot table reference in, like
+/// vanilla's `unpackLootTable`. This happens once, when the first player opens it
+pub async fn unpack_loot_table(
+    world: &World,
+    location: WorldPosition,
+    nbt: &mut NbtCompound,
+    size: usize,
+) {
+    let Some(NbtTag::String(table)) = nbt.remove("LootTable") else {
+        return;
+    };
+    let seed = match nbt.remove("LootTableSeed") {
+        Some(NbtTag::Long(seed)) => seed,
+        _ => 0,
+    };
+    let mut slots = vec![None; size];
+    read_items_nbt(&mut slots, nbt);
+    if !fill_container(&mut slots, &table, seed) {
+        log::warn!("Container at {location} uses unknown loot table {table}");
+    }
+    write_items_nbt(&slots, nbt);
+    world.set_block_entity_nbt(location, nbt.clone()).await;
+}
 
-/// The standard destroy with container removes the player forcibly from the container,
-/// drops items to the floor, and back to the player's inventory if the item stack is in movement.
+/// The standard destroy with container removes the players forcibly from the container and drops
+/// its items to the floor, like vanilla's `Containers.dropContents`. If nobody has the container
+/// open, the items saved in the block entity drop instead
 pub async fn standard_on_broken_with_container(
     block: &Block,
     player: &Player,
     location: WorldPosition,
     server: &Server,
+    block_entity: Option<&NbtCompound>,
 ) {
-    // TODO: drop all items and back to players inventory if in motion
+    let mut items = Vec::new();
+    let mut was_open = false;
     if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await {
         let mut open_containers = server.open_containers.write().await;
         for individual_id in all_container_ids {
-            if let Some(container) = open_containers.get_mut(&u64::from(individual_id)) {
-                container.clear_all_slots().await;
+            if let Some(container) = open_containers.remove(&u64::from(individual_id)) {
+                was_open = true;
+                items.extend(take_all_items(&container).await);
                 player.open_container.store(None);
-                close_all_in_container(player, container).await;
-                container.clear_all_players();
+                close_all_in_container(player, &container).await;
             }
         }
     }
+    // An open container was loaded from the block entity, so it already holds these items
+    if !was_open {
+        if let Some(nbt) = block_entity {
+            // Slots are saved as a byte, so this fits the items of every container
+            let mut slots = vec![None; i8::MAX as usize + 1];
+            read_items_nbt(&mut slots, nbt);
+            items.extend(slots.into_iter().flatten());
+        }
+    }
+    drop_items(&player.world(), server, location, items).await;
+}
+
+/// Empties every slot of the container, crafting outputs are only a preview so they are discarded
+pub async fn take_all_items(container: &OpenContainer) -> Vec<ItemStack> {
+    let container = container.get_container();
+    let mut container = container.lock().await;
+    let output = container.crafting_output_slot();
+    container
+        .all_slots()
+        .into_iter()
+        .enumerate()
+        .filter(|(slot, _)| Some(*slot) != output)
+        .filter_map(|(_, item)| item.take())
+        .collect()
+}
+
+/// Pops each of the items out of the block
+pub async fn drop_items(
+    world: &Arc<World>,
+    server: &Server,
+    location: WorldPosition,
+    items: Vec<ItemStack>,
+) {
+    for item in items {
+        world.pop_resource(server, location, item).await;
+    }
 }
 
 /// The standard open container creates a new container if a container of the same block
@@ -51,11 +156,21 @@ pub async fn standard_open_container<C: Container + Default + 'static>(
             player.open_container.store(Some(container_id.into()));
         }
     } else {
+        // Fetch the stored contents before locking, loading the chunk may take a while
+        let world = player.world();
+        let mut nbt = world.get_block_entity_nbt(location).await;
+        if let Some(nbt) = &mut nbt {
+            let size = C::default().all_slots_ref().len();
+            unpack_loot_table(&world, location, nbt, size).await;
+        }
         let mut open_containers = server.open_containers.write().await;
         let new_id = server.new_container_id();
         log::debug!("Creating new standard container ID: {}", new_id);
         let open_container =
             OpenContainer::new_empty_container::<C>(entity_id, Some(location), Some(block.clone()));
+        if let Some(nbt) = nbt {
+            open_container.read_nbt(&nbt).await;
+        }
         open_containers.insert(new_id.into(), open_container);
         player.open_container.store(Some(new_id.into()));
     }
@@ -109,6 +224,7 @@ pub async fn close_all_in_container(player: &Player, container: &OpenContainer)
     for id in container.all_player_ids() {
         if let Some(remote_player) = player.world().get_player_by_entityid(id).await {
             remote_player.close_container().await;
+            remote_player.open_container.store(None);
         }
     }
 }
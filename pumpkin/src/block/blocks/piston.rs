@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::{pumpkin_block, sound};
+use pumpkin_protocol::client::play::CBlockAction;
+use pumpkin_world::block::{
+    block_registry::{get_block, get_block_by_state_id, Block},
+    piston::{self, is_extended, MovingPiston, PistonMove},
+    BlockFace,
+};
+use pumpkin_world::chunk::ticks::TickPriority;
+use pumpkin_world::item::ItemStack;
+
+use super::pop_block;
+
+/// Ticks until moving blocks arrive where the piston moves them
+const MOVE_TICKS: u32 = 2;
+
+/// The block event which starts the piston animation for players
+const EXTEND_EVENT: u8 = 0;
+const RETRACT_EVENT: u8 = 1;
+
+/// Pistons face the player placing them, like vanilla's `nearestLookingDirection().getOpposite()`
+pub(super) fn placement_facing(yaw: f32, pitch: f32) -> BlockFace {
+    if pitch > 45.0 {
+        return BlockFace::Top;
+    }
+    if pitch < -45.0 {
+        return BlockFace::Bottom;
+    }
+    // The player looks south at 0 degrees, west at 90
+    match ((yaw / 90.0 + 0.5).floor() as i32).rem_euclid(4) {
+        0 => BlockFace::North,
+        1 => BlockFace::East,
+        2 => BlockFace::South,
+        _ => BlockFace::West,
+    }
+}
+
+fn relative(position: WorldPosition, face: BlockFace) -> WorldPosition {
+    WorldPosition(position.0 + face.to_offset())
+}
+
+fn is_block(state_id: Option<u16>, name: &str) -> bool {
+    state_id
+        .and_then(get_block_by_state_id)
+        .is_some_and(|block| block.name == name)
+}
+
+/// Extends the piston when it gets powered and retracts it when it loses power. Extended pistons
+/// without their head break
+async fn update_piston(
+    block: &Block,
+    world: &Arc<World>,
+    location: WorldPosition,
+    server: &Server,
+) {
+    let Some(state_id) = world.level.loaded_block_state(&location).await else {
+        return;
+    };
+    let Some(facing) = piston::facing(state_id) else {
+        return;
+    };
+    let extended = is_extended(state_id);
+    let front = relative(location, facing);
+    let front_state = world.level.loaded_block_state(&front).await;
+    if extended && !is_block(front_state, "piston_head") && !is_block(front_state, "moving_piston")
+    {
+        pop_block(world, block, location, server).await;
+        return;
+    }
+
+    let powered = world.level.is_piston_powered(location, facing);
+    if powered && !extended {
+        if let Some(piston_move) = world.level.piston_extend(location) {
+            world
+                .play_block_sound(sound!("block.piston.extend"), location)
+                .await;
+            move_blocks(
+                block,
+                world,
+                location,
+                facing,
+                EXTEND_EVENT,
+                piston_move,
+                server,
+            )
+            .await;
+        }
+    } else if !powered && extended {
+        // A head which is still extending arrives before the piston retracts
+        if is_block(front_state, "moving_piston") {
+            finish_moving(world, front, server).await;
+        }
+        if let Some(piston_move) = world.level.piston_retract(location) {
+            world
+                .play_block_sound(sound!("block.piston.contract"), location)
+                .await;
+            move_blocks(
+                block,
+                world,
+                location,
+                facing,
+                RETRACT_EVENT,
+                piston_move,
+                server,
+            )
+            .await;
+        }
+    }
+}
+
+/// Breaks the blocks in the way and replaces the moving blocks with `moving_piston` blocks until
+/// they arrive. Neighbors are only updated once every block changed
+async fn move_blocks(
+    block: &Block,
+    world: &Arc<World>,
+    location: WorldPosition,
+    facing: BlockFace,
+    event: u8,
+    piston_move: PistonMove,
+    server: &Server,
+) {
+    world
+        .broadcast_packet_all(&CBlockAction::new(
+            &location,
+            event,
+            facing as u8,
+            i32::from(block.id).into(),
+        ))
+        .await;
+
+    let mut changed = Vec::new();
+    for (position, state_id) in piston_move.destroyed {
+        world.break_block(position, None).await;
+        let item_id = get_block_by_state_id(state_id).map_or(0, |block| block.item_id);
+        if item_id != 0 {
+            world
+                .pop_resource(server, position, ItemStack::new(1, item_id))
+                .await;
+        }
+        changed.push(position);
+    }
+    for (position, state_id) in piston_move.changes {
+        world.set_block_state(position, state_id).await;
+        changed.push(position);
+    }
+    if let Some(moving_piston) = get_block("minecraft:moving_piston") {
+        for (position, moving) in piston_move.moving {
+            world.set_block_entity_nbt(position, moving.to_nbt()).await;
+            world
+                .schedule_block_tick(position, moving_piston, MOVE_TICKS, TickPriority::Normal)
+                .await;
+        }
+    }
+    for position in changed {
+        world.update_neighbors(position, server).await;
+    }
+}
+
+/// Places the block a `moving_piston` block holds, once it arrived
+async fn finish_moving(world: &Arc<World>, location: WorldPosition, server: &Server) {
+    let Some(nbt) = world.remove_block_entity_nbt(location).await else {
+        return;
+    };
+    let Some(moving) = MovingPiston::from_nbt(&nbt) else {
+        return;
+    };
+    world.set_block_state(location, moving.state_id).await;
+    world.update_neighbors(location, server).await;
+}
+
+/// Pistons and sticky pistons, sticky pistons pull blocks back when they retract
+macro_rules! piston_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_placed<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &ItemStack,
+                    server: &Server,
+                ) {
+                    let entity = &player.living_entity.entity;
+                    let facing = placement_facing(entity.yaw.load(), entity.pitch.load());
+                    let world = &player.world();
+                    let state_id = block.state_with_properties(
+                        &[("facing".to_string(), facing.name().to_string())].into(),
+                    );
+                    world.set_block_state(location, state_id).await;
+                    update_piston(block, world, location, server).await;
+                }
+
+                async fn on_neighbor_update<'a>(
+                    &self,
+                    block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    _source: WorldPosition,
+                    server: &Server,
+                ) {
+                    update_piston(block, world, location, server).await;
+                }
+            }
+        )*
+    };
+}
+
+piston_block!(
+    PistonBlock => "minecraft:piston",
+    StickyPistonBlock => "minecraft:sticky_piston",
+);
+
+/// The head of an extended piston, it breaks without the piston behind it
+#[pumpkin_block("minecraft:piston_head")]
+pub struct PistonHeadBlock;
+
+#[async_trait]
+impl PumpkinBlock for PistonHeadBlock {
+    async fn on_neighbor_update<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        _source: WorldPosition,
+        server: &Server,
+    ) {
+        let Some(state_id) = world.level.loaded_block_state(&location).await else {
+            return;
+        };
+        let Some(facing) = piston::facing(state_id) else {
+            return;
+        };
+        let behind = world
+            .level
+            .loaded_block_state(&relative(location, facing.opposite()))
+            .await;
+        let attached = is_block(behind, "moving_piston")
+            || behind.is_some_and(|behind| {
+                is_extended(behind) && piston::facing(behind) == Some(facing)
+            });
+        if !attached {
+            world.break_block(location, None).await;
+            world.update_neighbors(location, server).await;
+        }
+    }
+}
+
+/// Blocks being moved by a piston, they become the moved block once they arrive
+#[pumpkin_block("minecraft:moving_piston")]
+pub struct MovingPistonBlock;
+
+#[async_trait]
+impl PumpkinBlock for MovingPistonBlock {
+    async fn on_scheduled_tick<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        finish_moving(world, location, server).await;
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::client::play::CWorldEvent;
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::item::item_registry::{get_item, Item};
+
+/// Nether portals break apart once their frame or a part of them is gone
+#[pumpkin_block("minecraft:nether_portal")]
+pub struct NetherPortalBlock;
+
+#[async_trait]
+impl PumpkinBlock for NetherPortalBlock {
+    async fn on_neighbor_update<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        _source: WorldPosition,
+        server: &Server,
+    ) {
+        let Some(state_id) = world.level.loaded_block_state(&location).await else {
+            return;
+        };
+        if !world.level.nether_portal_survives(location, state_id) {
+            world.set_block_state(location, 0).await;
+            world.update_neighbors(location, server).await;
+        }
+    }
+}
+
+/// Eyes of ender are put into end portal frames, twelve frames with eyes open an end portal
+#[pumpkin_block("minecraft:end_portal_frame")]
+pub struct EndPortalFrameBlock;
+
+#[async_trait]
+impl PumpkinBlock for EndPortalFrameBlock {
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        item: &Item,
+        _server: &Server,
+    ) -> BlockActionResult {
+        if get_item("minecraft:ender_eye").is_none_or(|eye| eye.id != item.id) {
+            return BlockActionResult::Continue;
+        }
+        let world = &player.world();
+        let Ok(state_id) = world.get_block_state_id(location).await else {
+            return BlockActionResult::Continue;
+        };
+        let mut properties: HashMap<String, String> = block
+            .state_properties(state_id)
+            .into_iter()
+            .map(|(property, value)| (property.to_string(), value.to_string()))
+            .collect();
+        if properties.get("eye").is_some_and(|eye| eye == "true") {
+            return BlockActionResult::Continue;
+        }
+        properties.insert("eye".to_string(), "true".to_string());
+        world
+            .set_block_state(location, block.state_with_properties(&properties))
+            .await;
+        player.consume_held_item().await;
+        // The particles and sound of the eye
+        world
+            .broadcast_packet_all(&CWorldEvent::new(1503, &location, 0, false))
+            .await;
+
+        if let Some(center) = world.level.completed_end_portal(location) {
+            world.open_end_portal(center).await;
+        }
+        BlockActionResult::Consume
+    }
+}
@@ -0,0 +1,107 @@
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::{Player, RespawnPoint};
+use crate::server::Server;
+use crate::world::respawn::{anchor_charges, MAX_ANCHOR_CHARGES};
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_macros::{pumpkin_block, sound};
+use pumpkin_registry::DimensionType;
+use pumpkin_world::block::{block_registry::Block, explosion::RESPAWN_ANCHOR_POWER};
+use pumpkin_world::damage::DamageType;
+use pumpkin_world::item::item_registry::{get_item_name_by_id, Item};
+
+/// The charges of the respawn anchor at the position, 0 if it is not one
+async fn charges_at(block: &Block, player: &Player, location: WorldPosition) -> u8 {
+    player
+        .world()
+        .level
+        .loaded_block_state(&location)
+        .await
+        .and_then(|state_id| anchor_charges(block, state_id))
+        .unwrap_or(0)
+}
+
+/// Charged respawn anchors set the player's respawn point in the nether and explode anywhere
+/// else, like vanilla's `RespawnAnchorBlock.useWithoutItem`. Returns whether the anchor was used
+async fn use_anchor(
+    block: &Block,
+    player: &Player,
+    location: WorldPosition,
+    server: &Server,
+) -> bool {
+    if charges_at(block, player, location).await == 0 {
+        return false;
+    }
+    let world = player.world();
+    if matches!(world.dimension_type, DimensionType::TheNether) {
+        player
+            .set_respawn_point(RespawnPoint {
+                dimension: world.key.clone(),
+                position: location,
+            })
+            .await;
+        world
+            .play_block_sound(sound!("block.respawn_anchor.set_spawn"), location)
+            .await;
+        return true;
+    }
+    // The anchor is removed first, so it doesn't drop when the explosion reaches it
+    world.set_block_state(location, 0).await;
+    let center = Vector3::new(
+        f64::from(location.0.x) + 0.5,
+        f64::from(location.0.y) + 0.5,
+        f64::from(location.0.z) + 0.5,
+    );
+    world
+        .explode(
+            center,
+            RESPAWN_ANCHOR_POWER,
+            true,
+            DamageType::BadRespawnPoint,
+            server,
+        )
+        .await;
+    true
+}
+
+#[pumpkin_block("minecraft:respawn_anchor")]
+pub struct RespawnAnchorBlock;
+
+#[async_trait]
+impl PumpkinBlock for RespawnAnchorBlock {
+    async fn on_use<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        server: &Server,
+    ) {
+        use_anchor(block, player, location, server).await;
+    }
+
+    async fn on_use_with_item<'a>(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        let charges = charges_at(block, player, location).await;
+        if get_item_name_by_id(item.id) == Some("glowstone") && charges < MAX_ANCHOR_CHARGES {
+            let world = player.world();
+            world.set_anchor_charges(location, charges + 1).await;
+            world
+                .play_block_sound(sound!("block.respawn_anchor.charge"), location)
+                .await;
+            player.consume_held_item().await;
+            return BlockActionResult::Consume;
+        }
+        if use_anchor(block, player, location, server).await {
+            BlockActionResult::Consume
+        } else {
+            BlockActionResult::Continue
+        }
+    }
+}
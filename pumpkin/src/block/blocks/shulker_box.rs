@@ -0,0 +1,201 @@
+use crate::block::block_manager::BlockActionResult;
+use crate::entity::player::Player;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_core::GameMode;
+use pumpkin_inventory::{write_items_nbt, OpenContainer, ShulkerBoxContainer, WindowType};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::item::item_registry::Item;
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::loot::{block_drops, LootContext};
+
+use super::close_all_in_container;
+use crate::{block::pumpkin_block::PumpkinBlock, server::Server};
+
+/// Every shulker box color is its own block, but they all behave the same
+macro_rules! shulker_box_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_use<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    open_shulker_box_screen(block, player, location, server).await;
+                }
+
+                async fn on_use_with_item<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &Item,
+                    server: &Server,
+                ) -> BlockActionResult {
+                    open_shulker_box_screen(block, player, location, server).await;
+                    BlockActionResult::Consume
+                }
+
+                async fn on_placed<'a>(
+                    &self,
+                    _block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    item: &ItemStack,
+                    _server: &Server,
+                ) {
+                    place_shulker_box(player, location, item).await;
+                }
+
+                async fn on_player_will_destroy<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    destroy_shulker_box(block, player, location, server).await;
+                }
+
+                async fn on_close<'a>(
+                    &self,
+                    _block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _server: &Server,
+                    container: &mut OpenContainer,
+                ) {
+                    close_shulker_box(player, location, container).await;
+                }
+            }
+        )*
+    };
+}
+
+shulker_box_block! {
+    ShulkerBoxBlock => "minecraft:shulker_box",
+    WhiteShulkerBoxBlock => "minecraft:white_shulker_box",
+    OrangeShulkerBoxBlock => "minecraft:orange_shulker_box",
+    MagentaShulkerBoxBlock => "minecraft:magenta_shulker_box",
+    LightBlueShulkerBoxBlock => "minecraft:light_blue_shulker_box",
+    YellowShulkerBoxBlock => "minecraft:yellow_shulker_box",
+    LimeShulkerBoxBlock => "minecraft:lime_shulker_box",
+    PinkShulkerBoxBlock => "minecraft:pink_shulker_box",
+    GrayShulkerBoxBlock => "minecraft:gray_shulker_box",
+    LightGrayShulkerBoxBlock => "minecraft:light_gray_shulker_box",
+    CyanShulkerBoxBlock => "minecraft:cyan_shulker_box",
+    PurpleShulkerBoxBlock => "minecraft:purple_shulker_box",
+    BlueShulkerBoxBlock => "minecraft:blue_shulker_box",
+    BrownShulkerBoxBlock => "minecraft:brown_shulker_box",
+    GreenShulkerBoxBlock => "minecraft:green_shulker_box",
+    RedShulkerBoxBlock => "minecraft:red_shulker_box",
+    BlackShulkerBoxBlock => "minecraft:black_shulker_box",
+}
+
+async fn open_shulker_box_screen(
+    block: &Block,
+    player: &Player,
+    location: WorldPosition,
+    server: &Server,
+) {
+    super::standard_open_container::<ShulkerBoxContainer>(
+        block,
+        player,
+        location,
+        server,
+        WindowType::ShulkerBox,
+    )
+    .await;
+}
+
+/// Moves the items stored in the shulker box item into the placed block
+async fn place_shulker_box(player: &Player, location: WorldPosition, item: &ItemStack) {
+    if item.components.container.is_empty() {
+        return;
+    }
+    let mut nbt = shulker_box_nbt();
+    write_items_nbt(&item.components.container, &mut nbt);
+    player.world().set_block_entity_nbt(location, nbt).await;
+}
+
+/// Shulker boxes drop as one item which keeps their contents, like vanilla's
+/// `ShulkerBoxBlock.playerWillDestroy`. Players looking inside are kicked out and the contents
+/// are saved first, so the drop has them. Mining drops nothing in creative, so the box pops out
+/// here unless it is empty
+async fn destroy_shulker_box(
+    block: &Block,
+    player: &Player,
+    location: WorldPosition,
+    server: &Server,
+) {
+    let world = player.world();
+    if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await {
+        let mut open_containers = server.open_containers.write().await;
+        for individual_id in all_container_ids {
+            if let Some(container) = open_containers.remove(&u64::from(individual_id)) {
+                close_all_in_container(player, &container).await;
+                save_shulker_box(&world, location, &container).await;
+            }
+        }
+    }
+
+    if player.gamemode.load() != GameMode::Creative {
+        return;
+    }
+    let Some(nbt) = world.get_block_entity_nbt(location).await else {
+        return;
+    };
+    let context = LootContext {
+        block_entity: Some(&nbt),
+        ..Default::default()
+    };
+    let drops = block_drops(
+        &block.name,
+        block.item_id,
+        &context,
+        &mut rand::thread_rng(),
+    );
+    for drop in drops {
+        if !drop.components.container.is_empty() {
+            world.pop_resource(server, location, drop).await;
+        }
+    }
+}
+
+/// Saves the contents into the block entity once the last player closed the shulker box
+async fn close_shulker_box(
+    player: &Player,
+    location: WorldPosition,
+    container: &mut OpenContainer,
+) {
+    container.remove_player(player.entity_id());
+    if container.get_number_of_players() != 0 {
+        return;
+    }
+    save_shulker_box(&player.world(), location, container).await;
+}
+
+async fn save_shulker_box(world: &World, location: WorldPosition, container: &OpenContainer) {
+    let mut nbt = shulker_box_nbt();
+    container.write_nbt(&mut nbt).await;
+    world.set_block_entity_nbt(location, nbt).await;
+}
+
+fn shulker_box_nbt() -> NbtCompound {
+    let mut nbt = NbtCompound::new();
+    nbt.put(
+        "id".to_string(),
+        NbtTag::String("minecraft:shulker_box".to_string()),
+    );
+    nbt
+}
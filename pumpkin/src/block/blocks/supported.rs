@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::block::block_registry::{get_block_by_state_id, get_state_by_state_id, Block};
+use pumpkin_world::item::ItemStack;
+
+use super::pop_block;
+
+fn offset(location: WorldPosition, x: i32, y: i32, z: i32) -> WorldPosition {
+    WorldPosition(location.0 + Vector3::new(x, y, z))
+}
+
+fn property<'a>(block: &'a Block, state_id: u16, name: &str) -> Option<&'a str> {
+    block
+        .state_properties(state_id)
+        .into_iter()
+        .find_map(|(property, value)| (property == name).then_some(value))
+}
+
+/// Whether the block at the position can hold blocks attached to it. Blocks in chunks which are
+/// not loaded are assumed to hold them so nothing pops off at chunk borders
+async fn is_support(world: &World, position: WorldPosition) -> bool {
+    world
+        .level
+        .loaded_block_state(&position)
+        .await
+        .and_then(get_state_by_state_id)
+        .is_none_or(|state| !state.collision_shapes.is_empty())
+}
+
+/// Torches standing on a block pop off when it is removed
+macro_rules! standing_torch_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_neighbor_update<'a>(
+                    &self,
+                    block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    _source: WorldPosition,
+                    server: &Server,
+                ) {
+                    if !is_support(world, offset(location, 0, -1, 0)).await {
+                        pop_block(world, block, location, server).await;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+standing_torch_block!(
+    TorchBlock => "minecraft:torch",
+    SoulTorchBlock => "minecraft:soul_torch",
+    RedstoneTorchBlock => "minecraft:redstone_torch",
+);
+
+/// Torches on walls pop off when the block behind them is removed
+macro_rules! wall_torch_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_neighbor_update<'a>(
+                    &self,
+                    block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    _source: WorldPosition,
+                    server: &Server,
+                ) {
+                    let Some(state_id) = world.level.loaded_block_state(&location).await else {
+                        return;
+                    };
+                    // The torch faces away from the wall
+                    let wall = match property(block, state_id, "facing") {
+                        Some("north") => offset(location, 0, 0, 1),
+                        Some("south") => offset(location, 0, 0, -1),
+                        Some("west") => offset(location, 1, 0, 0),
+                        Some("east") => offset(location, -1, 0, 0),
+                        _ => return,
+                    };
+                    if !is_support(world, wall).await {
+                        pop_block(world, block, location, server).await;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+wall_torch_block!(
+    WallTorchBlock => "minecraft:wall_torch",
+    SoulWallTorchBlock => "minecraft:soul_wall_torch",
+    RedstoneWallTorchBlock => "minecraft:redstone_wall_torch",
+);
+
+/// Doors are two blocks high, placing the lower half adds the upper one. When one half is
+/// removed the other one breaks too, and doors pop off when the block below them is removed
+macro_rules! door_block {
+    ($($name:ident => $id:literal),* $(,)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_placed<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &ItemStack,
+                    server: &Server,
+                ) {
+                    place_upper_half(block, &player.world(), location, server).await;
+                }
+
+                async fn on_neighbor_update<'a>(
+                    &self,
+                    block: &Block,
+                    world: &Arc<World>,
+                    location: WorldPosition,
+                    source: WorldPosition,
+                    server: &Server,
+                ) {
+                    door_neighbor_update(block, world, location, source, server).await;
+                }
+            }
+        )*
+    };
+}
+
+door_block!(
+    OakDoorBlock => "minecraft:oak_door",
+    IronDoorBlock => "minecraft:iron_door",
+    SpruceDoorBlock => "minecraft:spruce_door",
+    BirchDoorBlock => "minecraft:birch_door",
+    JungleDoorBlock => "minecraft:jungle_door",
+    AcaciaDoorBlock => "minecraft:acacia_door",
+    CherryDoorBlock => "minecraft:cherry_door",
+    DarkOakDoorBlock => "minecraft:dark_oak_door",
+    PaleOakDoorBlock => "minecraft:pale_oak_door",
+    MangroveDoorBlock => "minecraft:mangrove_door",
+    BambooDoorBlock => "minecraft:bamboo_door",
+    CrimsonDoorBlock => "minecraft:crimson_door",
+    WarpedDoorBlock => "minecraft:warped_door",
+    CopperDoorBlock => "minecraft:copper_door",
+    ExposedCopperDoorBlock => "minecraft:exposed_copper_door",
+    OxidizedCopperDoorBlock => "minecraft:oxidized_copper_door",
+    WeatheredCopperDoorBlock => "minecraft:weathered_copper_door",
+    WaxedCopperDoorBlock => "minecraft:waxed_copper_door",
+    WaxedExposedCopperDoorBlock => "minecraft:waxed_exposed_copper_door",
+    WaxedOxidizedCopperDoorBlock => "minecraft:waxed_oxidized_copper_door",
+    WaxedWeatheredCopperDoorBlock => "minecraft:waxed_weathered_copper_door",
+);
+
+async fn place_upper_half(
+    block: &Block,
+    world: &Arc<World>,
+    location: WorldPosition,
+    server: &Server,
+) {
+    let above = offset(location, 0, 1, 0);
+    let Ok(state_id) = world.get_block_state_id(location).await else {
+        return;
+    };
+    let replaceable = world
+        .get_block_state(above)
+        .await
+        .is_ok_and(|state| state.replaceable);
+    if !replaceable {
+        return;
+    }
+    let mut properties: HashMap<String, String> = block
+        .state_properties(state_id)
+        .into_iter()
+        .map(|(property, value)| (property.to_string(), value.to_string()))
+        .collect();
+    properties.insert("half".to_string(), "upper".to_string());
+    world
+        .set_block_state(above, block.state_with_properties(&properties))
+        .await;
+    world.update_neighbors(above, server).await;
+}
+
+async fn door_neighbor_update(
+    block: &Block,
+    world: &Arc<World>,
+    location: WorldPosition,
+    source: WorldPosition,
+    server: &Server,
+) {
+    let Some(state_id) = world.level.loaded_block_state(&location).await else {
+        return;
+    };
+    let lower = property(block, state_id, "half") == Some("lower");
+    let other_half = offset(location, 0, if lower { 1 } else { -1 }, 0);
+    if source == other_half {
+        let other_block = world
+            .level
+            .loaded_block_state(&other_half)
+            .await
+            .and_then(get_block_by_state_id);
+        if other_block.is_some_and(|other| other.id != block.id) {
+            // Only the half which was broken drops the door
+            world.break_block(location, None).await;
+            world.update_neighbors(location, server).await;
+        }
+    } else if lower && !is_support(world, offset(location, 0, -1, 0)).await {
+        pop_block(world, block, location, server).await;
+    }
+}
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::entity::tnt::DEFAULT_FUSE;
+use crate::server::Server;
+use crate::world::World;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::item::item_registry::{get_item, Item};
+use pumpkin_world::item::ItemStack;
+
+/// Items which light TNT they are used on
+const IGNITERS: [&str; 2] = ["minecraft:flint_and_steel", "minecraft:fire_charge"];
+
+/// Lights the TNT once it receives a redstone signal
+async fn check_powered(world: &Arc<World>, location: WorldPosition, server: &Server) {
+    if world.level.is_block_powered(location) {
+        world.spawn_primed_tnt(location, DEFAULT_FUSE, server).await;
+    }
+}
+
+/// TNT is lit by redstone, fire items and explosions and explodes after its fuse
+#[pumpkin_block("minecraft:tnt")]
+pub struct TntBlock;
+
+#[async_trait]
+impl PumpkinBlock for TntBlock {
+    async fn on_use_with_item<'a>(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        item: &Item,
+        server: &Server,
+    ) -> BlockActionResult {
+        let igniter = IGNITERS
+            .into_iter()
+            .find(|name| get_item(name).is_some_and(|igniter| igniter.id == item.id));
+        let Some(igniter) = igniter else {
+            return BlockActionResult::Continue;
+        };
+        player
+            .world()
+            .spawn_primed_tnt(location, DEFAULT_FUSE, server)
+            .await;
+        // Flint and steel wears out while fire charges are used up
+        if igniter == "minecraft:fire_charge" {
+            player.consume_held_item().await;
+        } else {
+            player.damage_hand_item(false, 1).await;
+        }
+        BlockActionResult::Consume
+    }
+
+    async fn on_placed<'a>(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: WorldPosition,
+        _item: &ItemStack,
+        server: &Server,
+    ) {
+        check_powered(&player.world(), location, server).await;
+    }
+
+    async fn on_neighbor_update<'a>(
+        &self,
+        _block: &Block,
+        world: &Arc<World>,
+        location: WorldPosition,
+        _source: WorldPosition,
+        server: &Server,
+    ) {
+        check_powered(world, location, server).await;
+    }
+}
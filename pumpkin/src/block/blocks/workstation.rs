@@ -0,0 +1,101 @@
+use crate::block::block_manager::BlockActionResult;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::player::Player;
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_inventory::{
+    CartographyTableContainer, GrindstoneContainer, LoomContainer, OpenContainer,
+    SmithingTableContainer, StonecutterContainer, WindowType,
+};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_world::{block::block_registry::Block, item::item_registry::Item};
+
+/// Workstations don't keep their items, like anvils every player gets their own screen
+macro_rules! workstation_block {
+    ($($name:ident => $id:literal, $container:ty, $window_type:expr);* $(;)?) => {
+        $(
+            #[pumpkin_block($id)]
+            pub struct $name;
+
+            #[async_trait]
+            impl PumpkinBlock for $name {
+                async fn on_use<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                ) {
+                    super::standard_open_container_unique::<$container>(
+                        block,
+                        player,
+                        location,
+                        server,
+                        $window_type,
+                    )
+                    .await;
+                }
+
+                async fn on_use_with_item<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    _item: &Item,
+                    server: &Server,
+                ) -> BlockActionResult {
+                    super::standard_open_container_unique::<$container>(
+                        block,
+                        player,
+                        location,
+                        server,
+                        $window_type,
+                    )
+                    .await;
+                    BlockActionResult::Consume
+                }
+
+                async fn on_broken<'a>(
+                    &self,
+                    block: &Block,
+                    player: &Player,
+                    location: WorldPosition,
+                    server: &Server,
+                    block_entity: Option<&NbtCompound>,
+                ) {
+                    super::standard_on_broken_with_container(
+                        block,
+                        player,
+                        location,
+                        server,
+                        block_entity,
+                    )
+                    .await;
+                }
+
+                async fn on_close<'a>(
+                    &self,
+                    _block: &Block,
+                    player: &Player,
+                    _location: WorldPosition,
+                    server: &Server,
+                    container: &mut OpenContainer,
+                ) {
+                    player.return_container_inputs(server, container).await;
+                }
+            }
+        )*
+    };
+}
+
+workstation_block! {
+    StonecutterBlock => "minecraft:stonecutter", StonecutterContainer, WindowType::Stonecutter;
+    LoomBlock => "minecraft:loom", LoomContainer, WindowType::Loom;
+    GrindstoneBlock => "minecraft:grindstone", GrindstoneContainer, WindowType::Grindstone;
+    CartographyTableBlock => "minecraft:cartography_table",
+        CartographyTableContainer, WindowType::CartographyTable;
+    SmithingTableBlock => "minecraft:smithing_table",
+        SmithingTableContainer, WindowType::SmithingTable;
+}
@@ -1,15 +1,58 @@
+use blocks::anvil::{AnvilBlock, ChippedAnvilBlock, DamagedAnvilBlock};
+use blocks::bed::{
+    BlackBedBlock, BlueBedBlock, BrownBedBlock, CyanBedBlock, GrayBedBlock, GreenBedBlock,
+    LightBlueBedBlock, LightGrayBedBlock, LimeBedBlock, MagentaBedBlock, OrangeBedBlock,
+    PinkBedBlock, PurpleBedBlock, RedBedBlock, WhiteBedBlock, YellowBedBlock,
+};
+use blocks::brewing_stand::BrewingStandBlock;
+use blocks::campfire::{CampfireBlock, SoulCampfireBlock};
 use blocks::chest::ChestBlock;
-use blocks::furnace::FurnaceBlock;
+use blocks::ender_chest::EnderChestBlock;
+use blocks::falling::{
+    BlackConcretePowderBlock, BlueConcretePowderBlock, BrownConcretePowderBlock,
+    CyanConcretePowderBlock, GravelBlock, GrayConcretePowderBlock, GreenConcretePowderBlock,
+    LightBlueConcretePowderBlock, LightGrayConcretePowderBlock, LimeConcretePowderBlock,
+    MagentaConcretePowderBlock, OrangeConcretePowderBlock, PinkConcretePowderBlock,
+    PurpleConcretePowderBlock, RedConcretePowderBlock, RedSandBlock, SandBlock,
+    WhiteConcretePowderBlock, YellowConcretePowderBlock,
+};
+use blocks::fluid::{LavaBlock, WaterBlock};
+use blocks::furnace::{BlastFurnaceBlock, FurnaceBlock, SmokerBlock};
+use blocks::piston::{MovingPistonBlock, PistonBlock, PistonHeadBlock, StickyPistonBlock};
+use blocks::portal::{EndPortalFrameBlock, NetherPortalBlock};
+use blocks::respawn_anchor::RespawnAnchorBlock;
+use blocks::tnt::TntBlock;
 
 use crate::block::block_manager::BlockManager;
 use crate::block::blocks::crafting_table::CraftingTableBlock;
 use crate::block::blocks::jukebox::JukeboxBlock;
+use crate::block::blocks::shulker_box::{
+    BlackShulkerBoxBlock, BlueShulkerBoxBlock, BrownShulkerBoxBlock, CyanShulkerBoxBlock,
+    GrayShulkerBoxBlock, GreenShulkerBoxBlock, LightBlueShulkerBoxBlock, LightGrayShulkerBoxBlock,
+    LimeShulkerBoxBlock, MagentaShulkerBoxBlock, OrangeShulkerBoxBlock, PinkShulkerBoxBlock,
+    PurpleShulkerBoxBlock, RedShulkerBoxBlock, ShulkerBoxBlock, WhiteShulkerBoxBlock,
+    YellowShulkerBoxBlock,
+};
+use crate::block::blocks::supported::{
+    AcaciaDoorBlock, BambooDoorBlock, BirchDoorBlock, CherryDoorBlock, CopperDoorBlock,
+    CrimsonDoorBlock, DarkOakDoorBlock, ExposedCopperDoorBlock, IronDoorBlock, JungleDoorBlock,
+    MangroveDoorBlock, OakDoorBlock, OxidizedCopperDoorBlock, PaleOakDoorBlock, RedstoneTorchBlock,
+    RedstoneWallTorchBlock, SoulTorchBlock, SoulWallTorchBlock, SpruceDoorBlock, TorchBlock,
+    WallTorchBlock, WarpedDoorBlock, WaxedCopperDoorBlock, WaxedExposedCopperDoorBlock,
+    WaxedOxidizedCopperDoorBlock, WaxedWeatheredCopperDoorBlock, WeatheredCopperDoorBlock,
+};
+use crate::block::blocks::workstation::{
+    CartographyTableBlock, GrindstoneBlock, LoomBlock, SmithingTableBlock, StonecutterBlock,
+};
 use std::sync::Arc;
 
 pub mod block_manager;
 mod blocks;
 pub mod pumpkin_block;
 
+pub(crate) use blocks::anvil::use_anvil;
+pub(crate) use blocks::pop_block;
+
 #[must_use]
 pub fn default_block_manager() -> Arc<BlockManager> {
     let mut manager = BlockManager::default();
@@ -17,7 +60,110 @@ pub fn default_block_manager() -> Arc<BlockManager> {
     manager.register(JukeboxBlock);
     manager.register(CraftingTableBlock);
     manager.register(FurnaceBlock);
+    manager.register(BlastFurnaceBlock);
+    manager.register(SmokerBlock);
+    manager.register(CampfireBlock);
+    manager.register(SoulCampfireBlock);
+    manager.register(BrewingStandBlock);
+    manager.register(AnvilBlock);
+    manager.register(ChippedAnvilBlock);
+    manager.register(DamagedAnvilBlock);
+    manager.register(StonecutterBlock);
+    manager.register(LoomBlock);
+    manager.register(GrindstoneBlock);
+    manager.register(CartographyTableBlock);
+    manager.register(SmithingTableBlock);
     manager.register(ChestBlock);
+    manager.register(EnderChestBlock::default());
+    manager.register(ShulkerBoxBlock);
+    manager.register(WhiteShulkerBoxBlock);
+    manager.register(OrangeShulkerBoxBlock);
+    manager.register(MagentaShulkerBoxBlock);
+    manager.register(LightBlueShulkerBoxBlock);
+    manager.register(YellowShulkerBoxBlock);
+    manager.register(LimeShulkerBoxBlock);
+    manager.register(PinkShulkerBoxBlock);
+    manager.register(GrayShulkerBoxBlock);
+    manager.register(LightGrayShulkerBoxBlock);
+    manager.register(CyanShulkerBoxBlock);
+    manager.register(PurpleShulkerBoxBlock);
+    manager.register(BlueShulkerBoxBlock);
+    manager.register(BrownShulkerBoxBlock);
+    manager.register(GreenShulkerBoxBlock);
+    manager.register(RedShulkerBoxBlock);
+    manager.register(BlackShulkerBoxBlock);
+    manager.register(WaterBlock);
+    manager.register(LavaBlock);
+    manager.register(SandBlock);
+    manager.register(RedSandBlock);
+    manager.register(GravelBlock);
+    manager.register(WhiteConcretePowderBlock);
+    manager.register(OrangeConcretePowderBlock);
+    manager.register(MagentaConcretePowderBlock);
+    manager.register(LightBlueConcretePowderBlock);
+    manager.register(YellowConcretePowderBlock);
+    manager.register(LimeConcretePowderBlock);
+    manager.register(PinkConcretePowderBlock);
+    manager.register(GrayConcretePowderBlock);
+    manager.register(LightGrayConcretePowderBlock);
+    manager.register(CyanConcretePowderBlock);
+    manager.register(PurpleConcretePowderBlock);
+    manager.register(BlueConcretePowderBlock);
+    manager.register(BrownConcretePowderBlock);
+    manager.register(GreenConcretePowderBlock);
+    manager.register(RedConcretePowderBlock);
+    manager.register(BlackConcretePowderBlock);
+    manager.register(PistonBlock);
+    manager.register(StickyPistonBlock);
+    manager.register(PistonHeadBlock);
+    manager.register(MovingPistonBlock);
+    manager.register(TntBlock);
+    manager.register(NetherPortalBlock);
+    manager.register(EndPortalFrameBlock);
+    manager.register(WhiteBedBlock);
+    manager.register(OrangeBedBlock);
+    manager.register(MagentaBedBlock);
+    manager.register(LightBlueBedBlock);
+    manager.register(YellowBedBlock);
+    manager.register(LimeBedBlock);
+    manager.register(PinkBedBlock);
+    manager.register(GrayBedBlock);
+    manager.register(LightGrayBedBlock);
+    manager.register(CyanBedBlock);
+    manager.register(PurpleBedBlock);
+    manager.register(BlueBedBlock);
+    manager.register(BrownBedBlock);
+    manager.register(GreenBedBlock);
+    manager.register(RedBedBlock);
+    manager.register(BlackBedBlock);
+    manager.register(RespawnAnchorBlock);
+    manager.register(TorchBlock);
+    manager.register(SoulTorchBlock);
+    manager.register(RedstoneTorchBlock);
+    manager.register(WallTorchBlock);
+    manager.register(SoulWallTorchBlock);
+    manager.register(RedstoneWallTorchBlock);
+    manager.register(OakDoorBlock);
+    manager.register(IronDoorBlock);
+    manager.register(SpruceDoorBlock);
+    manager.register(BirchDoorBlock);
+    manager.register(JungleDoorBlock);
+    manager.register(AcaciaDoorBlock);
+    manager.register(CherryDoorBlock);
+    manager.register(DarkOakDoorBlock);
+    manager.register(PaleOakDoorBlock);
+    manager.register(MangroveDoorBlock);
+    manager.register(BambooDoorBlock);
+    manager.register(CrimsonDoorBlock);
+    manager.register(WarpedDoorBlock);
+    manager.register(CopperDoorBlock);
+    manager.register(ExposedCopperDoorBlock);
+    manager.register(OxidizedCopperDoorBlock);
+    manager.register(WeatheredCopperDoorBlock);
+    manager.register(WaxedCopperDoorBlock);
+    manager.register(WaxedExposedCopperDoorBlock);
+    manager.register(WaxedOxidizedCopperDoorBlock);
+    manager.register(WaxedWeatheredCopperDoorBlock);
 
     Arc::new(manager)
 }
@@ -1,11 +1,15 @@
 use crate::block::block_manager::BlockActionResult;
 use crate::entity::player::Player;
 use crate::server::Server;
+use crate::world::World;
 use async_trait::async_trait;
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_inventory::OpenContainer;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_world::block::block_registry::Block;
 use pumpkin_world::item::item_registry::Item;
+use pumpkin_world::item::ItemStack;
+use std::sync::Arc;
 
 pub trait BlockMetadata {
     const NAMESPACE: &'static str;
@@ -36,21 +40,35 @@ pub trait PumpkinBlock: Send + Sync {
         BlockActionResult::Continue
     }
 
+    /// The player placed this block with the item
     async fn on_placed<'a>(
         &self,
         _block: &Block,
         _player: &Player,
         _location: WorldPosition,
+        _item: &ItemStack,
         _server: &Server,
     ) {
     }
 
+    /// The player is about to break this block, its block entity is still in the world
+    async fn on_player_will_destroy<'a>(
+        &self,
+        _block: &Block,
+        _player: &Player,
+        _location: WorldPosition,
+        _server: &Server,
+    ) {
+    }
+
+    /// The player broke this block, the block entity it had is already removed from the world
     async fn on_broken<'a>(
         &self,
         _block: &Block,
         _player: &Player,
         _location: WorldPosition,
         _server: &Server,
+        _block_entity: Option<&NbtCompound>,
     ) {
     }
 
@@ -63,4 +81,25 @@ pub trait PumpkinBlock: Send + Sync {
         _container: &mut OpenContainer,
     ) {
     }
+
+    /// A tick scheduled for this block, or for the fluid if this is a fluid block, is due
+    async fn on_scheduled_tick<'a>(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _location: WorldPosition,
+        _server: &Server,
+    ) {
+    }
+
+    /// The block at `source` next to this block changed
+    async fn on_neighbor_update<'a>(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _location: WorldPosition,
+        _source: WorldPosition,
+        _server: &Server,
+    ) {
+    }
 }
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+use pumpkin_registry::ADVANCEMENTS;
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// The id of an advancement, the server suggests the advancements it knows
+pub(crate) struct AdvancementArgumentConsumer;
+
+impl GetClientSideArgParser for AdvancementArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ResourceLocation
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for AdvancementArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Advancement(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let suggestions = ADVANCEMENTS
+            .keys()
+            .map(|id| CommandSuggestion::new(id.clone(), None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for AdvancementArgumentConsumer {
+    fn default_name(&self) -> String {
+        "advancement".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for AdvancementArgumentConsumer {
+    /// The id the advancement is registered with
+    type Data = &'static str;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Advancement(id)) => {
+                let id = id.strip_prefix("minecraft:").unwrap_or(id);
+                ADVANCEMENTS
+                    .get_key_value(format!("minecraft:{id}").as_str())
+                    .map(|(id, _)| id.as_str())
+                    .ok_or_else(|| {
+                        CommandError::GeneralCommandIssue(format!("Unknown advancement: {id}"))
+                    })
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
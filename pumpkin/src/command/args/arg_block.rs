@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use pumpkin_protocol::client::play::{
     CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
 };
-use pumpkin_world::block::block_registry::{self, Block};
+use pumpkin_world::block::block_state::BlockStateInput;
 
 use crate::{command::dispatcher::CommandError, server::Server};
 
@@ -11,20 +11,20 @@ use super::{
         args::{ArgumentConsumer, RawArgs},
         CommandSender,
     },
+    suggestions::suggest_block_states,
     Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
 };
 
+/// A block state with optional block entity data, like `minecraft:chest[facing=north]{Items:[]}`
 pub(crate) struct BlockArgumentConsumer;
 
 impl GetClientSideArgParser for BlockArgumentConsumer {
     fn get_client_side_parser(&self) -> ProtoCmdArgParser {
-        ProtoCmdArgParser::Resource {
-            identifier: "block",
-        }
+        ProtoCmdArgParser::BlockState
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -44,9 +44,9 @@ impl ArgumentConsumer for BlockArgumentConsumer {
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_block_states(input)))
     }
 }
 
@@ -57,18 +57,12 @@ impl DefaultNameArgConsumer for BlockArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for BlockArgumentConsumer {
-    type Data = &'a Block;
+    type Data = BlockStateInput;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Block(name)) => block_registry::get_block(name).map_or_else(
-                || {
-                    Err(CommandError::GeneralCommandIssue(format!(
-                        "Block {name} does not exist."
-                    )))
-                },
-                Result::Ok,
-            ),
+            Some(Arg::Block(input)) => BlockStateInput::parse(input)
+                .map_err(|error| CommandError::GeneralCommandIssue(error.to_string())),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
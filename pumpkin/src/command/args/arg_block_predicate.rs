@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+use pumpkin_registry::{get_tag_values, is_in_tag, TagCategory};
+use pumpkin_world::block::{
+    block_registry::get_block_by_state_id,
+    block_state::{BlockInputParts, BlockStateInput, BlockStateParseError},
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    suggestions::{suggest_block_states, suggest_tags},
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// A block state or a block tag like `#minecraft:logs[axis=y]`, only the given properties have
+/// to match. Like vanilla's `BlockPredicateArgument`, but block entity data isn't compared
+pub(crate) enum BlockPredicate<'a> {
+    Block(BlockStateInput),
+    Tag {
+        tag: &'a str,
+        properties: Vec<(&'a str, &'a str)>,
+    },
+}
+
+impl BlockPredicate<'_> {
+    pub fn matches(&self, state_id: u16) -> bool {
+        match self {
+            Self::Block(input) => input.matches(state_id),
+            Self::Tag { tag, properties } => {
+                let Some(block) = get_block_by_state_id(state_id) else {
+                    return false;
+                };
+                let state = block.state_properties(state_id);
+                is_in_tag(TagCategory::Block, &block.name, tag)
+                    && properties.iter().all(|property| state.contains(property))
+            }
+        }
+    }
+}
+
+pub(crate) struct BlockPredicateArgumentConsumer;
+
+impl GetClientSideArgParser for BlockPredicateArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::BlockPredicate
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for BlockPredicateArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::BlockPredicate(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let mut suggestions = suggest_block_states(input);
+        suggestions.extend(suggest_tags(input, TagCategory::Block));
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for BlockPredicateArgumentConsumer {
+    fn default_name(&self) -> String {
+        "block".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for BlockPredicateArgumentConsumer {
+    type Data = BlockPredicate<'a>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        let Some(Arg::BlockPredicate(input)) = args.get(name) else {
+            return Err(CommandError::InvalidConsumption(Some(name.to_string())));
+        };
+        let invalid =
+            |error: BlockStateParseError| CommandError::GeneralCommandIssue(error.to_string());
+        let Some(tag) = input.strip_prefix('#') else {
+            return BlockStateInput::parse(input)
+                .map(BlockPredicate::Block)
+                .map_err(invalid);
+        };
+        let parts = BlockInputParts::split(tag).map_err(invalid)?;
+        if get_tag_values(TagCategory::Block, parts.name).is_none() {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown block tag '{}'",
+                parts.name
+            )));
+        }
+        Ok(BlockPredicate::Tag {
+            tag: parts.name,
+            properties: parts.properties,
+        })
+    }
+}
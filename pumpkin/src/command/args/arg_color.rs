@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use pumpkin_core::text::color::NamedColor;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server, world::team::TEAM_COLORS};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// A named chat color like `dark_red`, `reset` stands for no color
+pub(crate) struct ColorArgumentConsumer;
+
+impl GetClientSideArgParser for ColorArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::Color
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ColorArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Color(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for ColorArgumentConsumer {
+    fn default_name(&self) -> String {
+        "value".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ColorArgumentConsumer {
+    type Data = Option<NamedColor>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Color("reset")) => Ok(None),
+            Some(Arg::Color(color)) => TEAM_COLORS
+                .iter()
+                .find(|(_, name)| name == color)
+                .map(|(color, _)| Some(*color))
+                .ok_or_else(|| {
+                    CommandError::GeneralCommandIssue(format!("Unknown color '{color}'"))
+                }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{
+    command::dispatcher::CommandError, server::Server, world::scoreboard::ObjectiveCriterion,
+};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// What a new scoreboard objective counts
+pub(crate) struct CriterionArgumentConsumer;
+
+impl GetClientSideArgParser for CriterionArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ObjectiveCriteria
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for CriterionArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Criterion(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for CriterionArgumentConsumer {
+    fn default_name(&self) -> String {
+        "criteria".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for CriterionArgumentConsumer {
+    type Data = ObjectiveCriterion;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Criterion(name)) => ObjectiveCriterion::from_name(name).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Unknown criterion '{name}'"))
+            }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType, StringProtoArgBehavior,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// The name of a datapack, which may be quoted like vanilla suggests it
+pub(crate) struct DatapackArgumentConsumer {
+    /// Whether the enabled packs are suggested, otherwise the ones which can be enabled are
+    pub enabled: bool,
+}
+
+impl GetClientSideArgParser for DatapackArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::String(StringProtoArgBehavior::QuotablePhrase)
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for DatapackArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let name = args.pop()?;
+        let name = name
+            .strip_prefix('"')
+            .and_then(|name| name.strip_suffix('"'))
+            .unwrap_or(name);
+        Some(Arg::Datapack(name))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let world = &server.worlds[0];
+        let enabled = world.data_packs.lock().await.enabled.clone();
+        let packs = if self.enabled {
+            enabled
+        } else {
+            world
+                .level
+                .datapacks
+                .available()
+                .into_iter()
+                .filter(|pack| !enabled.contains(pack))
+                .collect()
+        };
+        let suggestions = packs
+            .into_iter()
+            .map(|pack| CommandSuggestion::new(format!("\"{pack}\""), None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for DatapackArgumentConsumer {
+    fn default_name(&self) -> String {
+        "name".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for DatapackArgumentConsumer {
+    /// The name without quotes, which may not be a known pack
+    type Data = &'a str;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Datapack(pack)) => Ok(pack),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
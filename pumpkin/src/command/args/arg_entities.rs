@@ -1,5 +1,3 @@
-use std::sync::Arc;
-
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{
     CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
@@ -8,26 +6,24 @@ use pumpkin_protocol::client::play::{
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
 use crate::command::CommandSender;
-use crate::entity::player::Player;
+use crate::entity::any_entity::AnyEntity;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
-use super::arg_players::PlayersArgumentConsumer;
+use super::entity_selector::{consume_target, suggest_targets};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// todo: implement (currently just calls [`super::arg_player::PlayerArgumentConsumer`])
-///
 /// For selecting zero, one or multiple entities, eg. using @s, a player name, @a or @e
 pub(crate) struct EntitiesArgumentConsumer;
 
 impl GetClientSideArgParser for EntitiesArgumentConsumer {
     fn get_client_side_parser(&self) -> ProtoCmdArgParser {
-        // todo: investigate why this does not accept target selectors
+        // Players only get to write selectors with permission level 2, like on vanilla servers
         ProtoCmdArgParser::Entity { flags: 0 }
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -39,20 +35,19 @@ impl ArgumentConsumer for EntitiesArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        // todo
-        match PlayersArgumentConsumer.consume(src, server, args).await {
-            Some(Arg::Players(p)) => Some(Arg::Entities(p)),
-            _ => None,
-        }
+        let entities = consume_target(src, server, args)
+            .await?
+            .map(|(_, entities)| entities);
+        Some(Arg::Entities(entities))
     }
 
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender<'a>,
-        _server: &'a Server,
-        _input: &'a str,
+        server: &'a Server,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_targets(server, input, false).await))
     }
 }
 
@@ -63,11 +58,17 @@ impl DefaultNameArgConsumer for EntitiesArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EntitiesArgumentConsumer {
-    type Data = &'a [Arc<Player>];
+    type Data = &'a [AnyEntity];
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Entities(data)) => Ok(data),
+            Some(Arg::Entities(Ok(data))) if data.is_empty() => Err(
+                CommandError::GeneralCommandIssue("No entity was found".to_string()),
+            ),
+            Some(Arg::Entities(Ok(data))) => Ok(data),
+            Some(Arg::Entities(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.clone()))
+            }
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
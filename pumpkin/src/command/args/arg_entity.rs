@@ -1,5 +1,3 @@
-use std::sync::Arc;
-
 use async_trait::async_trait;
 use pumpkin_protocol::client::play::{
     CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
@@ -8,14 +6,13 @@ use pumpkin_protocol::client::play::{
 use crate::command::dispatcher::CommandError;
 use crate::command::tree::RawArgs;
 use crate::command::CommandSender;
-use crate::entity::player::Player;
+use crate::entity::any_entity::AnyEntity;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
+use super::entity_selector::{consume_target, suggest_targets};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
-/// todo: implement for entities that aren't players
-///
 /// For selecting a single entity, eg. using @s, a player name or entity uuid.
 ///
 /// Use [`super::arg_entities::EntitiesArgumentConsumer`] when there may be multiple targets.
@@ -23,14 +20,13 @@ pub(crate) struct EntityArgumentConsumer;
 
 impl GetClientSideArgParser for EntityArgumentConsumer {
     fn get_client_side_parser(&self) -> ProtoCmdArgParser {
-        // todo: investigate why this does not accept target selectors
         ProtoCmdArgParser::Entity {
             flags: ProtoCmdArgParser::ENTITY_FLAG_ONLY_SINGLE,
         }
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -42,39 +38,30 @@ impl ArgumentConsumer for EntityArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let s = args.pop()?;
-
-        let entity = match s {
-            // @s is always valid when sender is a player
-            "@s" => match src {
-                CommandSender::Player(p) => Some(p.clone()),
-                _ => None,
-            },
-            // @n/@p are always valid when sender is a player
-            #[allow(clippy::match_same_arms)] // todo: implement for non-players
-            "@n" | "@p" => match src {
-                CommandSender::Player(p) => Some(p.clone()),
-                // todo: implement for non-players: how should this behave when sender is console/rcon?
-                _ => None,
-            },
-            // @r is valid when there is at least one player
-            "@r" => server.get_random_player().await,
-            // @a/@e/@r are not valid because we're looking for a single entity
-            "@a" | "@e" => None,
-            // player name is only valid if player is online
-            name => server.get_player_by_name(name).await,
-        };
-
-        entity.map(Arg::Entity)
+        let entity = consume_target(src, server, args)
+            .await?
+            .and_then(|(target, entities)| {
+                if !target.is_single() {
+                    return Err(
+                        "Only one entity is allowed, but the provided selector allows more than one"
+                            .to_string(),
+                    );
+                }
+                entities
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| "No entity was found".to_string())
+            });
+        Some(Arg::Entity(entity))
     }
 
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender<'a>,
-        _server: &'a Server,
-        _input: &'a str,
+        server: &'a Server,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_targets(server, input, false).await))
     }
 }
 
@@ -85,11 +72,12 @@ impl DefaultNameArgConsumer for EntityArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for EntityArgumentConsumer {
-    type Data = Arc<Player>;
+    type Data = AnyEntity;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Entity(data)) => Ok(data.clone()),
+            Some(Arg::Entity(Ok(data))) => Ok(data.clone()),
+            Some(Arg::Entity(Err(error))) => Err(CommandError::GeneralCommandIssue(error.clone())),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, entity::damage::entity_type_name, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    suggestions::suggest_resources,
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// The type of an entity, like `minecraft:zombie`
+pub(crate) struct EntityTypeArgumentConsumer;
+
+impl GetClientSideArgParser for EntityTypeArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::Resource {
+            identifier: "entity_type",
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for EntityTypeArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::EntityType(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(Some(suggest_resources(
+            input,
+            EntityType::ALL.iter().map(EntityType::name),
+            |id| EntityType::from_name(id).map(|entity_type| entity_type_name(&entity_type)),
+        )))
+    }
+}
+
+impl DefaultNameArgConsumer for EntityTypeArgumentConsumer {
+    fn default_name(&self) -> String {
+        "entity".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for EntityTypeArgumentConsumer {
+    type Data = EntityType;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::EntityType(name)) => EntityType::from_name(name).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Unknown entity type {name}"))
+            }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
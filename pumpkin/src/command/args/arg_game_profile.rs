@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::net::{lookup_profile, offline_uuid, GameProfile};
+use crate::server::Server;
+
+use super::super::args::ArgumentConsumer;
+use super::entity_selector::{consume_target, suggest_targets};
+use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Select the profiles of players, who don't have to be online, by selector or name. Like vanilla's
+/// `GameProfileArgument`
+pub(crate) struct GameProfileArgumentConsumer;
+
+impl GetClientSideArgParser for GameProfileArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::GameProfile
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for GameProfileArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        if !args.last()?.starts_with('@') {
+            let name = args.pop()?;
+            return Some(Arg::GameProfiles(
+                resolve_profile(server, name)
+                    .await
+                    .map(|profile| vec![profile]),
+            ));
+        }
+
+        let profiles = consume_target(src, server, args)
+            .await?
+            .and_then(|(target, entities)| {
+                if target.includes_entities() {
+                    return Err("Only players may be affected by this command, but the provided selector includes entities".to_string());
+                }
+                Ok(entities
+                    .iter()
+                    .filter_map(|entity| entity.as_player())
+                    .map(|player| player.gameprofile.clone())
+                    .collect())
+            });
+        Some(Arg::GameProfiles(profiles))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(Some(suggest_targets(server, input, true).await))
+    }
+}
+
+/// The profile of an online player, or of the account with the name. Offline servers use the
+/// same offline UUID the player would get when joining
+async fn resolve_profile(server: &Server, name: &str) -> Result<GameProfile, String> {
+    if let Some(player) = server.get_player_by_name(name).await {
+        return Ok(player.gameprofile.clone());
+    }
+    let unknown = || "That player does not exist".to_string();
+    match &server.auth_client {
+        Some(auth_client) => lookup_profile(name, auth_client)
+            .await
+            .map_err(|_| unknown()),
+        None => Ok(GameProfile {
+            id: offline_uuid(name).map_err(|_| unknown())?,
+            name: name.to_string(),
+            properties: Vec::new(),
+            profile_actions: None,
+        }),
+    }
+}
+
+impl DefaultNameArgConsumer for GameProfileArgumentConsumer {
+    fn default_name(&self) -> String {
+        "targets".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for GameProfileArgumentConsumer {
+    type Data = &'a [GameProfile];
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::GameProfiles(Ok(data))) if data.is_empty() => Err(
+                CommandError::GeneralCommandIssue("No player was found".to_string()),
+            ),
+            Some(Arg::GameProfiles(Ok(data))) => Ok(data),
+            Some(Arg::GameProfiles(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.clone()))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
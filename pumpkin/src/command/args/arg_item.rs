@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use pumpkin_protocol::client::play::{
     CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
 };
-use pumpkin_world::item::item_registry::{self, Item};
+use pumpkin_world::item::{components::parse_components, item_registry, ItemStack};
 
 use crate::{command::dispatcher::CommandError, server::Server};
 
@@ -11,18 +11,34 @@ use super::{
         args::{ArgumentConsumer, RawArgs},
         CommandSender,
     },
+    suggestions::suggest_items,
     Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
 };
 
+/// An item with optional components, like `diamond_sword[damage=5]`. Components can't contain
+/// spaces, arguments are split at them
 pub(crate) struct ItemArgumentConsumer;
 
+/// The item id and the components in brackets after it, if there are any
+pub(crate) fn split_components(input: &str) -> Result<(&str, Option<&str>), CommandError> {
+    let Some((id, components)) = input.split_once('[') else {
+        return Ok((input, None));
+    };
+    let components = components.strip_suffix(']').ok_or_else(|| {
+        CommandError::GeneralCommandIssue(format!(
+            "Expected closing ] for item components in {input}"
+        ))
+    })?;
+    Ok((id, Some(components)))
+}
+
 impl GetClientSideArgParser for ItemArgumentConsumer {
     fn get_client_side_parser(&self) -> ProtoCmdArgParser {
         ProtoCmdArgParser::Resource { identifier: "item" }
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -42,9 +58,9 @@ impl ArgumentConsumer for ItemArgumentConsumer {
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_items(input)))
     }
 }
 
@@ -55,18 +71,23 @@ impl DefaultNameArgConsumer for ItemArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for ItemArgumentConsumer {
-    type Data = (&'a str, &'a Item);
+    /// The item id without the components and a single item with the components
+    type Data = (&'a str, ItemStack);
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Item(name)) => item_registry::get_item(name).map_or_else(
-                || {
-                    Err(CommandError::GeneralCommandIssue(format!(
-                        "Item {name} does not exist."
-                    )))
-                },
-                |item| Ok((*name, item)),
-            ),
+            Some(Arg::Item(input)) => {
+                let (id, components) = split_components(input)?;
+                let item = item_registry::get_item(id).ok_or_else(|| {
+                    CommandError::GeneralCommandIssue(format!("Item {id} does not exist."))
+                })?;
+                let mut stack = ItemStack::new(1, item.id);
+                if let Some(components) = components {
+                    stack.components = parse_components(components)
+                        .map_err(|error| CommandError::GeneralCommandIssue(error.to_string()))?;
+                }
+                Ok((id, stack))
+            }
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
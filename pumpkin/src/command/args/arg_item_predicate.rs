@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+use pumpkin_registry::{get_tag_values, is_in_tag, TagCategory};
+use pumpkin_world::item::{
+    components::ComponentPredicate,
+    item_registry::{self, get_item_name_by_id, Item},
+    ItemStack,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    arg_item::split_components,
+    suggestions::{suggest_items, suggest_tags},
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Which items an item predicate accepts
+enum Items<'a> {
+    Any,
+    Item(&'a Item),
+    Tag(&'a str),
+}
+
+/// An item, an item tag like `#minecraft:logs` or any item with `*`, optionally followed by
+/// tests on the components like `*[damage=0]`, like vanilla's `ItemPredicateArgument`
+pub(crate) struct ItemPredicate<'a> {
+    items: Items<'a>,
+    components: ComponentPredicate,
+}
+
+impl ItemPredicate<'_> {
+    pub fn matches(&self, stack: &ItemStack) -> bool {
+        let item_matches = match self.items {
+            Items::Any => true,
+            Items::Item(item) => item.id == stack.item_id,
+            Items::Tag(tag) => get_item_name_by_id(stack.item_id)
+                .is_some_and(|name| is_in_tag(TagCategory::Item, name, tag)),
+        };
+        item_matches && self.components.matches(&stack.components)
+    }
+}
+
+pub(crate) struct ItemPredicateArgumentConsumer;
+
+impl GetClientSideArgParser for ItemPredicateArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ItemPredicate
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ItemPredicateArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ItemPredicate(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let mut suggestions = suggest_items(input);
+        suggestions.extend(suggest_tags(input, TagCategory::Item));
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for ItemPredicateArgumentConsumer {
+    fn default_name(&self) -> String {
+        "item".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ItemPredicateArgumentConsumer {
+    type Data = ItemPredicate<'a>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ItemPredicate(input)) => {
+                let (predicate, components) = split_components(input)?;
+                let items = match predicate.strip_prefix('#') {
+                    Some(tag) if get_tag_values(TagCategory::Item, tag).is_some() => {
+                        Items::Tag(tag)
+                    }
+                    Some(tag) => {
+                        return Err(CommandError::GeneralCommandIssue(format!(
+                            "Unknown item tag '{tag}'"
+                        )))
+                    }
+                    None if predicate == "*" => Items::Any,
+                    None => Items::Item(item_registry::get_item(predicate).ok_or_else(|| {
+                        CommandError::GeneralCommandIssue(format!(
+                            "Item {predicate} does not exist."
+                        ))
+                    })?),
+                };
+                let components = components
+                    .map(ComponentPredicate::parse)
+                    .transpose()
+                    .map_err(|error| CommandError::GeneralCommandIssue(error.to_string()))?
+                    .unwrap_or_default();
+                Ok(ItemPredicate { items, components })
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Numbered slots by their prefix, the number of the first one and how many there are
+const SLOT_RANGES: [(&str, i32, i32); 4] = [
+    ("container", 0, 54),
+    ("hotbar", 0, 9),
+    ("inventory", 9, 27),
+    ("enderchest", 200, 27),
+];
+
+/// The slots with a single name
+const NAMED_SLOTS: [(&str, i32); 9] = [
+    ("contents", 0),
+    ("weapon", 98),
+    ("weapon.mainhand", 98),
+    ("weapon.offhand", 99),
+    ("armor.feet", 100),
+    ("armor.legs", 101),
+    ("armor.chest", 102),
+    ("armor.head", 103),
+    ("armor.body", 105),
+];
+
+/// The number of the slot, like vanilla's `SlotRanges`. What the number means depends on what
+/// holds the slot
+fn parse_slot(name: &str) -> Option<i32> {
+    if let Some((_, slot)) = NAMED_SLOTS.iter().find(|(named, _)| *named == name) {
+        return Some(*slot);
+    }
+    let (prefix, index) = name.split_once('.')?;
+    let (_, first, count) = SLOT_RANGES.iter().find(|(range, ..)| *range == prefix)?;
+    let index: i32 = index.parse().ok()?;
+    (0..*count).contains(&index).then_some(first + index)
+}
+
+/// A slot of a block or an entity, like `container.5` or `armor.head`
+pub(crate) struct ItemSlotArgumentConsumer;
+
+impl GetClientSideArgParser for ItemSlotArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ItemSlot
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ItemSlotArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ItemSlot(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let ranges = SLOT_RANGES.iter().flat_map(|(prefix, _, count)| {
+            (0..*count).map(move |index| format!("{prefix}.{index}"))
+        });
+        let suggestions = NAMED_SLOTS
+            .iter()
+            .map(|(name, _)| (*name).to_string())
+            .chain(ranges)
+            .map(|name| CommandSuggestion::new(name, None))
+            .collect();
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for ItemSlotArgumentConsumer {
+    fn default_name(&self) -> String {
+        "slot".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ItemSlotArgumentConsumer {
+    /// The number of the slot
+    type Data = i32;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ItemSlot(slot)) => parse_slot(slot)
+                .ok_or_else(|| CommandError::GeneralCommandIssue(format!("Unknown slot '{slot}'"))),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use pumpkin_nbt::{compound::NbtCompound, snbt::compound_from_snbt};
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// A compound in SNBT, like `{CustomName:'"Bob"'}`. Arguments are split at spaces, so the
+/// compound consumes all remaining words like [`super::arg_message::MsgArgConsumer`]
+pub(crate) struct NbtCompoundArgumentConsumer;
+
+impl GetClientSideArgParser for NbtCompoundArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::Nbt
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for NbtCompoundArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let mut snbt = args.pop()?.to_string();
+        while let Some(word) = args.pop() {
+            snbt.push(' ');
+            snbt.push_str(word);
+        }
+        Some(Arg::NbtCompound(snbt))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for NbtCompoundArgumentConsumer {
+    fn default_name(&self) -> String {
+        "nbt".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for NbtCompoundArgumentConsumer {
+    type Data = NbtCompound;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::NbtCompound(snbt)) => compound_from_snbt(snbt)
+                .map_err(|err| CommandError::GeneralCommandIssue(format!("Invalid NBT: {err}"))),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use pumpkin_nbt::{snbt::from_snbt, tag::NbtTag};
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Any tag in SNBT, like `5b` or `[1, 2]`. Consumes all remaining words like
+/// [`super::arg_nbt_compound::NbtCompoundArgumentConsumer`]
+pub(crate) struct NbtTagArgumentConsumer;
+
+impl GetClientSideArgParser for NbtTagArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::NbtTag
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for NbtTagArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let mut snbt = args.pop()?.to_string();
+        while let Some(word) = args.pop() {
+            snbt.push(' ');
+            snbt.push_str(word);
+        }
+        Some(Arg::NbtTag(snbt))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for NbtTagArgumentConsumer {
+    fn default_name(&self) -> String {
+        "value".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for NbtTagArgumentConsumer {
+    type Data = NbtTag;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::NbtTag(snbt)) => from_snbt(snbt)
+                .map_err(|err| CommandError::GeneralCommandIssue(format!("Invalid NBT: {err}"))),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{command::dispatcher::CommandError, server::Server};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// How `/scoreboard players operation` combines two scores
+#[derive(Clone, Copy)]
+pub(crate) enum Operation {
+    Assign,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Min,
+    Max,
+    Swap,
+}
+
+impl Operation {
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        Some(match symbol {
+            "=" => Self::Assign,
+            "+=" => Self::Add,
+            "-=" => Self::Subtract,
+            "*=" => Self::Multiply,
+            "/=" => Self::Divide,
+            "%=" => Self::Modulo,
+            "<" => Self::Min,
+            ">" => Self::Max,
+            "><" => Self::Swap,
+            _ => return None,
+        })
+    }
+
+    /// The new target and source scores, like vanilla's `OperationArgument`. Division rounds
+    /// down like `Mth.floorDiv`
+    pub(crate) fn apply(self, target: i32, source: i32) -> Result<(i32, i32), CommandError> {
+        let target = match self {
+            Self::Assign => source,
+            Self::Add => target.wrapping_add(source),
+            Self::Subtract => target.wrapping_sub(source),
+            Self::Multiply => target.wrapping_mul(source),
+            Self::Divide | Self::Modulo if source == 0 => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "Can't divide by zero".to_string(),
+                ))
+            }
+            Self::Divide => {
+                let quotient = target.wrapping_div(source);
+                if target.wrapping_rem(source) != 0 && (target < 0) != (source < 0) {
+                    quotient - 1
+                } else {
+                    quotient
+                }
+            }
+            Self::Modulo => {
+                let remainder = target.wrapping_rem(source);
+                if remainder != 0 && (remainder < 0) != (source < 0) {
+                    remainder + source
+                } else {
+                    remainder
+                }
+            }
+            Self::Min => target.min(source),
+            Self::Max => target.max(source),
+            Self::Swap => return Ok((source, target)),
+        };
+        Ok((target, source))
+    }
+}
+
+pub(crate) struct OperationArgumentConsumer;
+
+impl GetClientSideArgParser for OperationArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::Operation
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for OperationArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::Operation(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for OperationArgumentConsumer {
+    fn default_name(&self) -> String {
+        "operation".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for OperationArgumentConsumer {
+    type Data = Operation;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Operation(symbol)) => Operation::from_symbol(symbol)
+                .ok_or_else(|| CommandError::GeneralCommandIssue("Invalid operation".to_string())),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -12,6 +12,7 @@ use crate::entity::player::Player;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
+use super::entity_selector::{consume_target, suggest_targets};
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// Select zero, one or multiple players
@@ -19,14 +20,13 @@ pub(crate) struct PlayersArgumentConsumer;
 
 impl GetClientSideArgParser for PlayersArgumentConsumer {
     fn get_client_side_parser(&self) -> ProtoCmdArgParser {
-        // todo: investigate why this does not accept target selectors
         ProtoCmdArgParser::Entity {
             flags: ProtoCmdArgParser::ENTITY_FLAG_PLAYERS_ONLY,
         }
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -38,37 +38,28 @@ impl ArgumentConsumer for PlayersArgumentConsumer {
         server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let s = args.pop()?;
-
-        let players = match s {
-            "@s" => match src {
-                CommandSender::Player(p) => Some(vec![p.clone()]),
-                _ => None,
-            },
-            #[allow(clippy::match_same_arms)]
-            // todo: implement for non-players and remove this line
-            "@n" | "@p" => match src {
-                CommandSender::Player(p) => Some(vec![p.clone()]),
-                // todo: implement for non-players: how should this behave when sender is console/rcon?
-                _ => None,
-            },
-            "@r" => {
-                (server.get_random_player().await).map_or_else(|| Some(vec![]), |p| Some(vec![p]))
-            }
-            "@a" | "@e" => Some(server.get_all_players().await),
-            name => server.get_player_by_name(name).await.map(|p| vec![p]),
-        };
-
-        players.map(Arg::Players)
+        let players = consume_target(src, server, args)
+            .await?
+            .and_then(|(target, entities)| {
+                if target.includes_entities() {
+                    return Err("Only players may be affected by this command, but the provided selector includes entities".to_string());
+                }
+                // @s can still be an entity other than a player
+                Ok(entities
+                    .iter()
+                    .filter_map(|entity| entity.as_player().cloned())
+                    .collect())
+            });
+        Some(Arg::Players(players))
     }
 
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender<'a>,
-        _server: &'a Server,
-        _input: &'a str,
+        server: &'a Server,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_targets(server, input, true).await))
     }
 }
 
@@ -83,7 +74,11 @@ impl<'a> FindArg<'a> for PlayersArgumentConsumer {
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Players(data)) => Ok(data),
+            Some(Arg::Players(Ok(data))) if data.is_empty() => Err(
+                CommandError::GeneralCommandIssue("No player was found".to_string()),
+            ),
+            Some(Arg::Players(Ok(data))) => Ok(data),
+            Some(Arg::Players(Err(error))) => Err(CommandError::GeneralCommandIssue(error.clone())),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
@@ -12,6 +12,7 @@ use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
 use super::coordinate::MaybeRelativeCoordinate;
+use super::suggestions::suggest_coordinates;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x and z coordinates only
@@ -25,7 +26,7 @@ impl GetClientSideArgParser for Position2DArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -48,9 +49,9 @@ impl ArgumentConsumer for Position2DArgumentConsumer {
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(input, 2)))
     }
 }
 
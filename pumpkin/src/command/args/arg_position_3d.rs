@@ -11,6 +11,7 @@ use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
 use super::coordinate::MaybeRelativeCoordinate;
+use super::suggestions::suggest_coordinates;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x, y and z coordinates
@@ -22,7 +23,7 @@ impl GetClientSideArgParser for Position3DArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -36,7 +37,7 @@ impl ArgumentConsumer for Position3DArgumentConsumer {
     ) -> Option<Arg<'a>> {
         let pos = MaybeRelativePosition3D::try_new(args.pop()?, args.pop()?, args.pop()?)?;
 
-        let vec3 = pos.try_to_absolute(src.position())?;
+        let vec3 = pos.try_to_absolute(src.position(), src.rotation())?;
 
         Some(Arg::Pos3D(vec3))
     }
@@ -45,36 +46,94 @@ impl ArgumentConsumer for Position3DArgumentConsumer {
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(input, 3)))
     }
 }
 
-struct MaybeRelativePosition3D(
-    MaybeRelativeCoordinate<false>,
-    MaybeRelativeCoordinate<true>,
-    MaybeRelativeCoordinate<false>,
-);
+enum MaybeRelativePosition3D {
+    /// Absolute coordinates or ones relative to the position of the sender, like `~ ~1 5`
+    World(
+        MaybeRelativeCoordinate<false>,
+        MaybeRelativeCoordinate<true>,
+        MaybeRelativeCoordinate<false>,
+    ),
+    /// Blocks to the left, up and forwards from where the sender looks, like `^ ^ ^2`
+    Local(f64, f64, f64),
+}
+
+/// The offset of a local coordinate
+fn local_coordinate(s: &str) -> Option<f64> {
+    let s = s.strip_prefix('^')?;
+    if s.is_empty() {
+        Some(0.0)
+    } else {
+        s.parse().ok()
+    }
+}
 
 impl MaybeRelativePosition3D {
     fn try_new(x: &str, y: &str, z: &str) -> Option<Self> {
-        Some(Self(
+        // Local coordinates can't be mixed with the others
+        if [x, y, z].iter().any(|s| s.starts_with('^')) {
+            return Some(Self::Local(
+                local_coordinate(x)?,
+                local_coordinate(y)?,
+                local_coordinate(z)?,
+            ));
+        }
+        Some(Self::World(
             x.try_into().ok()?,
             y.try_into().ok()?,
             z.try_into().ok()?,
         ))
     }
 
-    fn try_to_absolute(self, origin: Option<Vector3<f64>>) -> Option<Vector3<f64>> {
-        Some(Vector3::new(
-            self.0.into_absolute(origin.map(|o| o.x))?,
-            self.1.into_absolute(origin.map(|o| o.y))?,
-            self.2.into_absolute(origin.map(|o| o.z))?,
-        ))
+    fn try_to_absolute(
+        self,
+        origin: Option<Vector3<f64>>,
+        rotation: Option<(f32, f32)>,
+    ) -> Option<Vector3<f64>> {
+        match self {
+            Self::World(x, y, z) => Some(Vector3::new(
+                x.into_absolute(origin.map(|o| o.x))?,
+                y.into_absolute(origin.map(|o| o.y))?,
+                z.into_absolute(origin.map(|o| o.z))?,
+            )),
+            Self::Local(left, up, forwards) => {
+                let (yaw, pitch) = rotation?;
+                Some(origin? + local_offset(yaw, pitch, left, up, forwards))
+            }
+        }
     }
 }
 
+/// The offset of local coordinates when looking in the direction, like vanilla's
+/// `LocalCoordinates.getPosition`
+fn local_offset(yaw: f32, pitch: f32, left: f64, up: f64, forwards: f64) -> Vector3<f64> {
+    let yaw = f64::from(yaw + 90.0).to_radians();
+    let pitch = f64::from(-pitch).to_radians();
+    let forwards_direction = Vector3::new(
+        yaw.cos() * pitch.cos(),
+        pitch.sin(),
+        yaw.sin() * pitch.cos(),
+    );
+    let up_pitch = pitch + std::f64::consts::FRAC_PI_2;
+    let up_direction = Vector3::new(
+        yaw.cos() * up_pitch.cos(),
+        up_pitch.sin(),
+        yaw.sin() * up_pitch.cos(),
+    );
+    // The cross product of the directions, turned around
+    let left_direction = Vector3::new(
+        up_direction.y * forwards_direction.z - up_direction.z * forwards_direction.y,
+        up_direction.z * forwards_direction.x - up_direction.x * forwards_direction.z,
+        up_direction.x * forwards_direction.y - up_direction.y * forwards_direction.x,
+    );
+    forwards_direction * forwards + up_direction * up + left_direction * left
+}
+
 impl DefaultNameArgConsumer for Position3DArgumentConsumer {
     fn default_name(&self) -> String {
         "pos".to_string()
@@ -91,3 +150,63 @@ impl<'a> FindArg<'a> for Position3DArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::vector3::Vector3;
+
+    use super::MaybeRelativePosition3D;
+
+    fn absolute(
+        input: [&str; 3],
+        origin: Vector3<f64>,
+        rotation: (f32, f32),
+    ) -> Option<Vector3<f64>> {
+        MaybeRelativePosition3D::try_new(input[0], input[1], input[2])?
+            .try_to_absolute(Some(origin), Some(rotation))
+    }
+
+    fn assert_close(position: Option<Vector3<f64>>, expected: (f64, f64, f64)) {
+        let position = position.unwrap();
+        assert!(
+            (position.x - expected.0).abs() < 1.0E-9
+                && (position.y - expected.1).abs() < 1.0E-9
+                && (position.z - expected.2).abs() < 1.0E-9,
+            "{position:?}"
+        );
+    }
+
+    #[test]
+    fn local_coordinates_follow_the_rotation() {
+        let origin = Vector3::new(0.0, 64.0, 0.0);
+        // Yaw 0 looks south, the left of it is east
+        assert_close(
+            absolute(["^", "^", "^2"], origin, (0.0, 0.0)),
+            (0.0, 64.0, 2.0),
+        );
+        assert_close(
+            absolute(["^1", "^", "^"], origin, (0.0, 0.0)),
+            (1.0, 64.0, 0.0),
+        );
+        assert_close(
+            absolute(["^", "^", "^3"], origin, (90.0, 0.0)),
+            (-3.0, 64.0, 0.0),
+        );
+        // Looking straight down, forwards goes down and up goes south
+        assert_close(
+            absolute(["^", "^1", "^2"], origin, (0.0, 90.0)),
+            (0.0, 62.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn local_coordinates_cannot_be_mixed() {
+        let origin = Vector3::new(1.0, 2.0, 3.0);
+        assert!(absolute(["^", "~", "^"], origin, (0.0, 0.0)).is_none());
+        assert!(absolute(["^", "^", "5"], origin, (0.0, 0.0)).is_none());
+        assert_close(
+            absolute(["~1", "~", "5"], origin, (0.0, 0.0)),
+            (2.0, 2.0, 5.5),
+        );
+    }
+}
@@ -12,6 +12,7 @@ use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
 use super::coordinate::MaybeRelativeBlockCoordinate;
+use super::suggestions::suggest_coordinates;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
 /// x, y and z coordinates
@@ -23,7 +24,7 @@ impl GetClientSideArgParser for BlockPosArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -46,9 +47,9 @@ impl ArgumentConsumer for BlockPosArgumentConsumer {
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(input, 3)))
     }
 }
 
@@ -38,24 +38,22 @@ impl ArgumentConsumer for ResourceLocationArgumentConsumer {
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender<'a>,
-        _server: &'a Server,
+        server: &'a Server,
         _input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
         if !self.autocomplete {
             return Ok(None);
         }
-        // TODO
-
-        // let suggestions = server
-        //     .bossbars
-        //     .lock()
-        //     .await
-        //     .custom_bossbars
-        //     .keys()
-        //     .map(|suggestion| CommandSuggestion::new(suggestion, None))
-        //     .collect();
+        let suggestions = server
+            .bossbars
+            .lock()
+            .await
+            .custom_bossbars
+            .keys()
+            .map(|suggestion| CommandSuggestion::new(suggestion.clone(), None))
+            .collect();
 
-        Ok(None)
+        Ok(Some(suggestions))
     }
 }
 
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+use pumpkin_registry::{is_in_tag, TagCategory};
+use pumpkin_world::biome::Biome;
+
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::server::Server;
+
+use super::suggestions::{suggest_resources, suggest_tags};
+use super::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// An entry of a registry or a `#tag` of its entries
+pub(crate) struct ResourceOrTagArgumentConsumer {
+    category: TagCategory,
+}
+
+impl ResourceOrTagArgumentConsumer {
+    pub(crate) const fn new(category: TagCategory) -> Self {
+        Self { category }
+    }
+}
+
+/// A resource or tag a [`ResourceOrTagArgumentConsumer`] consumed
+#[derive(Clone, Copy)]
+pub(crate) enum ResourceOrTag<'a> {
+    Resource(&'a str),
+    Tag(&'a str),
+}
+
+impl ResourceOrTag<'_> {
+    /// Whether the entry with the id is the resource or in the tag
+    pub(crate) fn matches(self, category: TagCategory, id: &str) -> bool {
+        match self {
+            Self::Resource(resource) => {
+                resource.strip_prefix("minecraft:").unwrap_or(resource)
+                    == id.strip_prefix("minecraft:").unwrap_or(id)
+            }
+            Self::Tag(tag) => is_in_tag(category, id, tag),
+        }
+    }
+}
+
+impl std::fmt::Display for ResourceOrTag<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Resource(resource) => write!(f, "{resource}"),
+            Self::Tag(tag) => write!(f, "#{tag}"),
+        }
+    }
+}
+
+impl GetClientSideArgParser for ResourceOrTagArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ResourceOrTagKey {
+            identifier: self.category.registry(),
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        // The client knows the entries of the other registries better
+        (self.category == TagCategory::WorldGenBiome)
+            .then_some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ResourceOrTagArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ResourceOrTag(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        let mut suggestions = if self.category == TagCategory::WorldGenBiome {
+            let biomes: Vec<_> = Biome::ALL.into_iter().map(Biome::id).collect();
+            suggest_resources(
+                input,
+                biomes.iter().map(|id| &id["minecraft:".len()..]),
+                |id| {
+                    Some(TextComponent::translate(
+                        format!("biome.minecraft.{id}"),
+                        vec![],
+                    ))
+                },
+            )
+        } else {
+            Vec::new()
+        };
+        suggestions.extend(suggest_tags(input, self.category));
+        Ok(Some(suggestions))
+    }
+}
+
+impl DefaultNameArgConsumer for ResourceOrTagArgumentConsumer {
+    fn default_name(&self) -> String {
+        "id".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ResourceOrTagArgumentConsumer {
+    type Data = ResourceOrTag<'a>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ResourceOrTag(input)) => Ok(input
+                .strip_prefix('#')
+                .map_or(ResourceOrTag::Resource(input), ResourceOrTag::Tag)),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
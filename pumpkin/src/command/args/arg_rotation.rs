@@ -9,8 +9,30 @@ use crate::command::CommandSender;
 use crate::server::Server;
 
 use super::super::args::ArgumentConsumer;
+use super::suggestions::suggest_coordinates;
 use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
 
+/// An angle in degrees, `~` makes it relative to the one of the sender
+fn angle(s: &str, origin: Option<f32>) -> Option<f32> {
+    match s.strip_prefix('~') {
+        Some("") => origin,
+        Some(offset) => Some(origin? + offset.parse::<f32>().ok()?),
+        None => s.parse().ok(),
+    }
+}
+
+/// The angle between -180 and 180 degrees
+fn wrap_degrees(angle: f32) -> f32 {
+    let angle = angle % 360.0;
+    if angle >= 180.0 {
+        angle - 360.0
+    } else if angle < -180.0 {
+        angle + 360.0
+    } else {
+        angle
+    }
+}
+
 /// yaw and pitch
 pub(crate) struct RotationArgumentConsumer;
 
@@ -20,7 +42,7 @@ impl GetClientSideArgParser for RotationArgumentConsumer {
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
-        None
+        Some(ProtoCmdArgSuggestionType::AskServer)
     }
 }
 
@@ -28,35 +50,28 @@ impl GetClientSideArgParser for RotationArgumentConsumer {
 impl ArgumentConsumer for RotationArgumentConsumer {
     async fn consume<'a>(
         &'a self,
-        _src: &CommandSender<'a>,
+        src: &CommandSender<'a>,
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
-        let yaw = args.pop()?;
-        let pitch = args.pop()?;
-
-        let mut yaw = yaw.parse::<f32>().ok()?;
-        let mut pitch = pitch.parse::<f32>().ok()?;
-
-        yaw %= 360.0;
-        if yaw >= 180.0 {
-            yaw -= 360.0;
-        };
-        pitch %= 360.0;
-        if pitch >= 180.0 {
-            pitch -= 360.0;
-        };
-
-        Some(Arg::Rotation(yaw, pitch))
+        let rotation = src.rotation();
+        let yaw = angle(args.pop()?, rotation.map(|(yaw, _)| yaw))?;
+        let pitch = angle(args.pop()?, rotation.map(|(_, pitch)| pitch))?;
+
+        // Like vanilla, players can't look further up or down
+        Some(Arg::Rotation(
+            wrap_degrees(yaw),
+            wrap_degrees(pitch).clamp(-90.0, 90.0),
+        ))
     }
 
     async fn suggest<'a>(
         &'a self,
         _sender: &CommandSender<'a>,
         _server: &'a Server,
-        _input: &'a str,
+        input: &'a str,
     ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
-        Ok(None)
+        Ok(Some(suggest_coordinates(input, 2)))
     }
 }
 
@@ -76,3 +91,26 @@ impl<'a> FindArg<'a> for RotationArgumentConsumer {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{angle, wrap_degrees};
+
+    #[test]
+    fn angles_can_be_relative() {
+        assert_eq!(angle("45", Some(10.0)), Some(45.0));
+        assert_eq!(angle("~", Some(10.0)), Some(10.0));
+        assert_eq!(angle("~-20", Some(10.0)), Some(-10.0));
+        // The console has no rotation to start from
+        assert_eq!(angle("~", None), None);
+        assert_eq!(angle("north", Some(0.0)), None);
+    }
+
+    #[test]
+    fn angles_wrap_around() {
+        assert!((wrap_degrees(270.0) + 90.0).abs() < f32::EPSILON);
+        assert!((wrap_degrees(-190.0) - 170.0).abs() < f32::EPSILON);
+        assert!((wrap_degrees(180.0) + 180.0).abs() < f32::EPSILON);
+        assert!((wrap_degrees(45.0) - 45.0).abs() < f32::EPSILON);
+    }
+}
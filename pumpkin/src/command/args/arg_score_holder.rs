@@ -0,0 +1,103 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::command::CommandSender;
+use crate::entity::any_entity::AnyEntity;
+use crate::server::Server;
+
+use super::super::args::ArgumentConsumer;
+use super::entity_selector::{consume_target, suggest_targets};
+use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Who holds scoreboard scores: a selector, any name, even of players who aren't online, or `*`
+/// for everyone who has a score
+pub(crate) struct ScoreHolderArgumentConsumer {
+    pub multiple: bool,
+}
+
+impl GetClientSideArgParser for ScoreHolderArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ScoreHolder {
+            flags: if self.multiple {
+                ProtoCmdArgParser::SCORE_HOLDER_FLAG_ALLOW_MULTIPLE
+            } else {
+                0
+            },
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        Some(ProtoCmdArgSuggestionType::AskServer)
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ScoreHolderArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender<'a>,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let holders = match *args.last()? {
+            "*" => {
+                args.pop();
+                let scoreboard = server.scoreboard.lock().await;
+                Ok(scoreboard
+                    .holders()
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect())
+            }
+            selector if selector.starts_with('@') => consume_target(src, server, args)
+                .await?
+                .and_then(|(target, entities)| {
+                    if !self.multiple && !target.is_single() {
+                        return Err("Only one entity is allowed, but the provided selector allows more than one".to_string());
+                    }
+                    Ok(entities.iter().filter_map(AnyEntity::score_holder).collect())
+                }),
+            name => {
+                args.pop();
+                Ok(vec![name.to_string()])
+            }
+        };
+        Some(Arg::ScoreHolders(holders))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        server: &'a Server,
+        input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(Some(suggest_targets(server, input, false).await))
+    }
+}
+
+impl DefaultNameArgConsumer for ScoreHolderArgumentConsumer {
+    fn default_name(&self) -> String {
+        if self.multiple { "targets" } else { "target" }.to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ScoreHolderArgumentConsumer {
+    type Data = &'a [String];
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ScoreHolders(Ok(data))) if data.is_empty() => Err(
+                CommandError::GeneralCommandIssue("No entity was found".to_string()),
+            ),
+            Some(Arg::ScoreHolders(Ok(data))) => Ok(data),
+            Some(Arg::ScoreHolders(Err(error))) => {
+                Err(CommandError::GeneralCommandIssue(error.clone()))
+            }
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
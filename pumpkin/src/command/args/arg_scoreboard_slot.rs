@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{
+    CommandSuggestion, DisplaySlot, ProtoCmdArgParser, ProtoCmdArgSuggestionType,
+};
+
+use crate::{
+    command::dispatcher::CommandError, server::Server, world::scoreboard::display_slot_by_name,
+};
+
+use super::{
+    super::{
+        args::{ArgumentConsumer, RawArgs},
+        CommandSender,
+    },
+    Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser,
+};
+
+/// Where a scoreboard objective is shown, like `sidebar`
+pub(crate) struct ScoreboardSlotArgumentConsumer;
+
+impl GetClientSideArgParser for ScoreboardSlotArgumentConsumer {
+    fn get_client_side_parser(&self) -> ProtoCmdArgParser {
+        ProtoCmdArgParser::ScoreboardSlot
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<ProtoCmdArgSuggestionType> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ScoreboardSlotArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        Some(Arg::ScoreboardSlot(args.pop()?))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender<'a>,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion<'a>>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for ScoreboardSlotArgumentConsumer {
+    fn default_name(&self) -> String {
+        "slot".to_string()
+    }
+}
+
+impl<'a> FindArg<'a> for ScoreboardSlotArgumentConsumer {
+    type Data = DisplaySlot;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ScoreboardSlot(name)) => display_slot_by_name(name).ok_or_else(|| {
+                CommandError::GeneralCommandIssue(format!("Unknown display slot '{name}'"))
+            }),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
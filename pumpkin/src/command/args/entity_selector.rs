@@ -0,0 +1,698 @@
+use std::{str::FromStr, sync::Arc};
+
+use pumpkin_core::{math::vector3::Vector3, permission::PermissionLvl, GameMode};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_nbt::{compound::NbtCompound, snbt::compound_from_snbt};
+use pumpkin_protocol::client::play::CommandSuggestion;
+use pumpkin_registry::{get_tag_values, is_in_tag, TagCategory};
+use rand::{seq::SliceRandom, thread_rng};
+
+use crate::{
+    command::{tree::RawArgs, CommandSender},
+    entity::any_entity::AnyEntity,
+    server::Server,
+    world::World,
+};
+
+use super::suggestions::last_word;
+
+/// The selector variables, `@n` is the nearest entity
+const VARIABLES: [&str; 6] = ["@a", "@e", "@n", "@p", "@r", "@s"];
+
+const OPTIONS: [&str; 13] = [
+    "x", "y", "z", "distance", "type", "name", "tag", "team", "gamemode", "limit", "sort",
+    "scores", "nbt",
+];
+
+const SORTS: [&str; 4] = ["nearest", "furthest", "random", "arbitrary"];
+
+const GAME_MODES: [&str; 4] = ["survival", "creative", "adventure", "spectator"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Variable {
+    AllPlayers,
+    AllEntities,
+    NearestPlayer,
+    NearestEntity,
+    RandomPlayer,
+    Executor,
+}
+
+#[derive(Clone, Copy)]
+enum Sort {
+    Nearest,
+    Furthest,
+    Random,
+    Arbitrary,
+}
+
+/// Ranges like `..5`, `1..` or `3`, like vanilla's `MinMaxBounds`
+#[derive(Clone, Copy)]
+pub(crate) struct Bounds<T> {
+    min: Option<T>,
+    max: Option<T>,
+}
+
+impl<T: FromStr + PartialOrd + Copy> Bounds<T> {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let (min, max) = input.split_once("..").unwrap_or((input, input));
+        let bound = |bound: &str| {
+            if bound.is_empty() {
+                Ok(None)
+            } else {
+                bound
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| format!("Invalid range '{input}'"))
+            }
+        };
+        let bounds = Self {
+            min: bound(min)?,
+            max: bound(max)?,
+        };
+        match (bounds.min, bounds.max) {
+            (None, None) => Err("Expected value or range of values".to_string()),
+            (Some(min), Some(max)) if min > max => Err("Min cannot be bigger than max".to_string()),
+            _ => Ok(bounds),
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+/// An option `!` negates, like `tag=!boss`
+struct Filter<T> {
+    value: T,
+    negated: bool,
+}
+
+impl<T> Filter<T> {
+    fn parse(input: &str, parse: impl FnOnce(&str) -> Result<T, String>) -> Result<Self, String> {
+        let (input, negated) = input
+            .strip_prefix('!')
+            .map_or((input, false), |input| (input, true));
+        Ok(Self {
+            value: parse(input)?,
+            negated,
+        })
+    }
+
+    const fn matches(&self, matches: bool) -> bool {
+        matches != self.negated
+    }
+}
+
+enum TypeFilter {
+    Type(EntityType),
+    Tag(String),
+}
+
+impl TypeFilter {
+    fn matches(&self, entity_type: &EntityType) -> bool {
+        match self {
+            Self::Type(expected) => expected.name() == entity_type.name(),
+            Self::Tag(tag) => is_in_tag(TagCategory::Entity, entity_type.name(), tag),
+        }
+    }
+}
+
+/// A target selector like `@e[type=zombie,distance=..10]`, like vanilla's `EntitySelector`
+pub(crate) struct EntitySelector {
+    variable: Variable,
+    x: Option<f64>,
+    y: Option<f64>,
+    z: Option<f64>,
+    distance: Option<Bounds<f64>>,
+    types: Vec<Filter<TypeFilter>>,
+    names: Vec<Filter<String>>,
+    tags: Vec<Filter<String>>,
+    /// An empty team matches entities on no team
+    teams: Vec<Filter<String>>,
+    game_modes: Vec<Filter<GameMode>>,
+    scores: Vec<(String, Bounds<i32>)>,
+    nbt: Vec<Filter<NbtCompound>>,
+    limit: Option<usize>,
+    sort: Option<Sort>,
+}
+
+/// What commands target, a player by name, an entity by UUID or a selector
+pub(crate) enum EntityTarget {
+    Player(String),
+    Uuid(uuid::Uuid),
+    Selector(Box<EntitySelector>),
+}
+
+/// Strips the quotes of option values like `name="Some Name"`
+fn unquote(input: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| input.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(input)
+}
+
+/// Splits the options at commas which aren't quoted or within braces
+fn split_options(options: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, char) in options.char_indices() {
+        if let Some(open) = quote {
+            if char == open {
+                quote = None;
+            }
+            continue;
+        }
+        match char {
+            '"' | '\'' => quote = Some(char),
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&options[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&options[start..]);
+    parts
+}
+
+/// Whether all brackets and quotes of the selector are closed
+fn is_complete(input: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+    for char in input.chars() {
+        if let Some(open) = quote {
+            if char == open {
+                quote = None;
+            }
+            continue;
+        }
+        match char {
+            '"' | '\'' => quote = Some(char),
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0 && quote.is_none()
+}
+
+/// Parses scores like `{kills=1..,deaths=0}`
+fn parse_scores(input: &str) -> Result<Vec<(String, Bounds<i32>)>, String> {
+    let scores = input
+        .strip_prefix('{')
+        .and_then(|scores| scores.strip_suffix('}'))
+        .ok_or("Expected '{' and '}' around the scores")?;
+    let mut parsed = Vec::new();
+    for score in split_options(scores) {
+        let score = score.trim();
+        if score.is_empty() {
+            continue;
+        }
+        let (objective, range) = score
+            .split_once('=')
+            .ok_or_else(|| format!("Expected value for score '{score}'"))?;
+        parsed.push((objective.trim().to_string(), Bounds::parse(range.trim())?));
+    }
+    Ok(parsed)
+}
+
+impl EntitySelector {
+    fn parse(input: &str) -> Result<Self, String> {
+        let (variable, options) = match input.split_once('[') {
+            Some((variable, options)) => (variable, Some(options)),
+            None => (input, None),
+        };
+        let variable = match variable {
+            "@a" => Variable::AllPlayers,
+            "@e" => Variable::AllEntities,
+            "@p" => Variable::NearestPlayer,
+            "@n" => Variable::NearestEntity,
+            "@r" => Variable::RandomPlayer,
+            "@s" => Variable::Executor,
+            _ => return Err(format!("Unknown selector type '{variable}'")),
+        };
+        let mut selector = Self {
+            variable,
+            x: None,
+            y: None,
+            z: None,
+            distance: None,
+            types: Vec::new(),
+            names: Vec::new(),
+            tags: Vec::new(),
+            teams: Vec::new(),
+            game_modes: Vec::new(),
+            scores: Vec::new(),
+            nbt: Vec::new(),
+            limit: None,
+            sort: None,
+        };
+        if let Some(options) = options {
+            let options = options.strip_suffix(']').ok_or("Expected end of options")?;
+            for option in split_options(options) {
+                let option = option.trim();
+                if option.is_empty() {
+                    continue;
+                }
+                let (key, value) = option
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected value for option '{option}'"))?;
+                selector.parse_option(key.trim(), value.trim())?;
+            }
+        }
+        Ok(selector)
+    }
+
+    /// Applies an option, like vanilla's `EntitySelectorOptions`
+    fn parse_option(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let not_applicable = || format!("Option '{key}' isn't applicable here");
+        let coordinate = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid double '{value}'"))
+        };
+        match key {
+            "x" => self.x = Some(coordinate(value)?),
+            "y" => self.y = Some(coordinate(value)?),
+            "z" => self.z = Some(coordinate(value)?),
+            "distance" => {
+                let distance = Bounds::<f64>::parse(value)?;
+                if distance.min.is_some_and(|min| min < 0.0)
+                    || distance.max.is_some_and(|max| max < 0.0)
+                {
+                    return Err("Distance cannot be negative".to_string());
+                }
+                self.distance = Some(distance);
+            }
+            "type" => {
+                let filter = Filter::parse(value, |value| match value.strip_prefix('#') {
+                    Some(tag) if get_tag_values(TagCategory::Entity, tag).is_some() => {
+                        Ok(TypeFilter::Tag(tag.to_string()))
+                    }
+                    Some(tag) => Err(format!("Unknown entity tag '{tag}'")),
+                    None => EntityType::from_name(value)
+                        .map(TypeFilter::Type)
+                        .ok_or_else(|| format!("Invalid or unknown entity type '{value}'")),
+                })?;
+                // Only one type can be required, and players are the only type of player selectors
+                if self.players_only() || (!filter.negated && self.types.iter().any(|t| !t.negated))
+                {
+                    return Err(not_applicable());
+                }
+                self.types.push(filter);
+            }
+            "name" => self.names.push(Filter::parse(
+                value,
+                |value| Ok(unquote(value).to_string()),
+            )?),
+            "tag" => self.tags.push(Filter::parse(
+                value,
+                |value| Ok(unquote(value).to_string()),
+            )?),
+            "team" => self.teams.push(Filter::parse(
+                value,
+                |value| Ok(unquote(value).to_string()),
+            )?),
+            "gamemode" => self.game_modes.push(Filter::parse(value, |value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("Invalid or unknown game mode '{value}'"))
+            })?),
+            "limit" => {
+                if self.variable == Variable::Executor || self.limit.is_some() {
+                    return Err(not_applicable());
+                }
+                let limit: usize = value
+                    .parse()
+                    .map_err(|_| format!("Invalid integer '{value}'"))?;
+                if limit < 1 {
+                    return Err("Limit must be at least 1".to_string());
+                }
+                self.limit = Some(limit);
+            }
+            "sort" => {
+                if self.variable == Variable::Executor || self.sort.is_some() {
+                    return Err(not_applicable());
+                }
+                self.sort = Some(match value {
+                    "nearest" => Sort::Nearest,
+                    "furthest" => Sort::Furthest,
+                    "random" => Sort::Random,
+                    "arbitrary" => Sort::Arbitrary,
+                    _ => return Err(format!("Invalid or unknown sort type '{value}'")),
+                });
+            }
+            "scores" => self.scores.extend(parse_scores(value)?),
+            "nbt" => self.nbt.push(Filter::parse(value, |value| {
+                compound_from_snbt(value).map_err(|error| format!("Invalid NBT: {error}"))
+            })?),
+            _ => return Err(format!("Unknown option '{key}'")),
+        }
+        Ok(())
+    }
+
+    /// Whether only players can be selected
+    fn players_only(&self) -> bool {
+        matches!(
+            self.variable,
+            Variable::AllPlayers | Variable::NearestPlayer | Variable::RandomPlayer
+        ) || self.types.iter().any(|filter| {
+            !filter.negated && matches!(filter.value, TypeFilter::Type(EntityType::Player))
+        })
+    }
+
+    /// Whether the selector only looks at the world it's used in
+    const fn world_limited(&self) -> bool {
+        self.x.is_some() || self.y.is_some() || self.z.is_some() || self.distance.is_some()
+    }
+
+    fn max_results(&self) -> usize {
+        self.limit.unwrap_or(match self.variable {
+            Variable::AllPlayers | Variable::AllEntities => usize::MAX,
+            _ => 1,
+        })
+    }
+
+    /// Whether the entity passes all options, `world` is the one the selector is used in
+    async fn matches(
+        &self,
+        server: &Server,
+        entity: &AnyEntity,
+        world: &Arc<World>,
+        origin: Vector3<f64>,
+    ) -> bool {
+        let base = entity.entity();
+        if let Some(distance) = self.distance {
+            if !Arc::ptr_eq(&base.world(), world)
+                || !distance.contains(base.pos.load().sub(&origin).length())
+            {
+                return false;
+            }
+        }
+        if !self
+            .types
+            .iter()
+            .all(|filter| filter.matches(filter.value.matches(&base.entity_type)))
+        {
+            return false;
+        }
+        let player = entity.as_player();
+        let name = player.map(|player| player.gameprofile.name.as_str());
+        if !self
+            .names
+            .iter()
+            .all(|filter| filter.matches(name == Some(filter.value.as_str())))
+        {
+            return false;
+        }
+        let tags = base.tags();
+        if !self.tags.iter().all(|filter| {
+            filter.matches(if filter.value.is_empty() {
+                tags.is_empty()
+            } else {
+                tags.contains(&filter.value)
+            })
+        }) {
+            return false;
+        }
+        // Only players have a game mode
+        let game_mode = player.map(|player| player.gamemode.load());
+        if !self
+            .game_modes
+            .iter()
+            .all(|filter| filter.matches(game_mode == Some(filter.value)))
+        {
+            return false;
+        }
+        if !self.teams.is_empty() {
+            let scoreboard = server.scoreboard.lock().await;
+            let team = entity
+                .score_holder()
+                .and_then(|holder| scoreboard.team_of(&holder).map(|team| team.name.clone()));
+            if !self.teams.iter().all(|filter| {
+                filter.matches(if filter.value.is_empty() {
+                    team.is_none()
+                } else {
+                    team.as_ref() == Some(&filter.value)
+                })
+            }) {
+                return false;
+            }
+        }
+        if !self.scores.is_empty() {
+            let Some(holder) = entity.score_holder() else {
+                return false;
+            };
+            let scoreboard = server.scoreboard.lock().await;
+            if !self.scores.iter().all(|(objective, range)| {
+                scoreboard
+                    .score(&holder, objective)
+                    .is_some_and(|score| range.contains(score))
+            }) {
+                return false;
+            }
+        }
+        if !self.nbt.is_empty() {
+            let mut nbt = NbtCompound::new();
+            entity.write_nbt(&mut nbt).await;
+            if !self
+                .nbt
+                .iter()
+                .all(|filter| filter.matches(nbt.contains(&filter.value)))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    async fn select(&self, sender: &CommandSender<'_>, server: &Server) -> Vec<AnyEntity> {
+        let (world, position) =
+            if let (Some(world), Some(position)) = (sender.world(), sender.position()) {
+                (world, position)
+            } else {
+                let world = server.worlds[0].clone();
+                let position = world.spawn_position().await;
+                (world, position)
+            };
+        let origin = Vector3::new(
+            self.x.unwrap_or(position.x),
+            self.y.unwrap_or(position.y),
+            self.z.unwrap_or(position.z),
+        );
+
+        let candidates = if self.variable == Variable::Executor {
+            sender.executor().into_iter().collect()
+        } else {
+            let worlds = if self.world_limited() {
+                vec![world.clone()]
+            } else {
+                server.worlds.clone()
+            };
+            let mut candidates = Vec::new();
+            for world in worlds {
+                if self.players_only() {
+                    let players = world.current_players.lock().await;
+                    candidates.extend(players.values().cloned().map(AnyEntity::Player));
+                } else {
+                    candidates.extend(world.all_entities().await);
+                }
+            }
+            candidates
+        };
+
+        let mut selected = Vec::new();
+        for entity in candidates {
+            if self.matches(server, &entity, &world, origin).await {
+                selected.push(entity);
+            }
+        }
+
+        let distance =
+            |entity: &AnyEntity| entity.entity().pos.load().sub(&origin).length_squared();
+        let sort = self.sort.unwrap_or(match self.variable {
+            Variable::NearestPlayer | Variable::NearestEntity => Sort::Nearest,
+            Variable::RandomPlayer => Sort::Random,
+            _ => Sort::Arbitrary,
+        });
+        match sort {
+            Sort::Nearest => selected.sort_by(|a, b| distance(a).total_cmp(&distance(b))),
+            Sort::Furthest => selected.sort_by(|a, b| distance(b).total_cmp(&distance(a))),
+            Sort::Random => selected.shuffle(&mut thread_rng()),
+            Sort::Arbitrary => {}
+        }
+        selected.truncate(self.max_results());
+        selected
+    }
+}
+
+impl EntityTarget {
+    fn parse(input: &str) -> Result<Self, String> {
+        if input.starts_with('@') {
+            return EntitySelector::parse(input).map(|selector| Self::Selector(Box::new(selector)));
+        }
+        // Only hyphenated UUIDs, so names are never mistaken for one
+        if input.contains('-') {
+            if let Ok(uuid) = uuid::Uuid::parse_str(input) {
+                return Ok(Self::Uuid(uuid));
+            }
+        }
+        Ok(Self::Player(input.to_string()))
+    }
+
+    /// Whether it can't select more than one entity
+    pub fn is_single(&self) -> bool {
+        match self {
+            Self::Player(_) | Self::Uuid(_) => true,
+            Self::Selector(selector) => selector.max_results() <= 1,
+        }
+    }
+
+    /// Whether entities other than players can be selected. `@s` always counts as a player
+    pub fn includes_entities(&self) -> bool {
+        match self {
+            Self::Player(_) => false,
+            Self::Uuid(_) => true,
+            Self::Selector(selector) => {
+                !selector.players_only() && selector.variable != Variable::Executor
+            }
+        }
+    }
+
+    pub async fn select(
+        &self,
+        sender: &CommandSender<'_>,
+        server: &Server,
+    ) -> Result<Vec<AnyEntity>, String> {
+        match self {
+            Self::Player(name) => Ok(server
+                .get_player_by_name(name)
+                .await
+                .map(AnyEntity::Player)
+                .into_iter()
+                .collect()),
+            Self::Uuid(uuid) => {
+                for world in &server.worlds {
+                    if let Some(entity) = world
+                        .all_entities()
+                        .await
+                        .into_iter()
+                        .find(|entity| entity.uuid() == Some(*uuid))
+                    {
+                        return Ok(vec![entity]);
+                    }
+                }
+                Ok(Vec::new())
+            }
+            Self::Selector(selector) => {
+                if !sender.has_permission_lvl(PermissionLvl::Two) {
+                    return Err("Selector not allowed".to_string());
+                }
+                Ok(selector.select(sender, server).await)
+            }
+        }
+    }
+}
+
+/// Takes the next target out of the args and selects its entities. Selectors whose options
+/// contain spaces are joined back together. `None` if the selector isn't closed or no player has
+/// the name, so that other arguments can be tried
+pub(crate) async fn consume_target(
+    sender: &CommandSender<'_>,
+    server: &Server,
+    args: &mut RawArgs<'_>,
+) -> Option<Result<(EntityTarget, Vec<AnyEntity>), String>> {
+    let mut input = args.pop()?.to_string();
+    while !is_complete(&input) {
+        input.push(' ');
+        input.push_str(args.pop()?);
+    }
+    if input == "@" {
+        return None;
+    }
+    let target = match EntityTarget::parse(&input) {
+        Ok(target) => target,
+        Err(error) => return Some(Err(error)),
+    };
+    match target.select(sender, server).await {
+        Ok(entities) if entities.is_empty() && matches!(target, EntityTarget::Player(_)) => None,
+        Ok(entities) => Some(Ok((target, entities))),
+        Err(error) => Some(Err(error)),
+    }
+}
+
+/// Suggests selector variables, options and online players for the last word of the input
+pub(crate) async fn suggest_targets<'a>(
+    server: &Server,
+    input: &str,
+    players_only: bool,
+) -> Vec<CommandSuggestion<'a>> {
+    let word = last_word(input);
+    let suggest = |prefix: &str, values: &[&str]| -> Vec<CommandSuggestion<'a>> {
+        values
+            .iter()
+            .map(|value| format!("{prefix}{value}"))
+            .filter(|suggestion| suggestion.starts_with(word))
+            .map(|suggestion| CommandSuggestion::new(suggestion, None))
+            .collect()
+    };
+
+    let Some(open) = word.find('[') else {
+        let mut suggestions = suggest("", &VARIABLES);
+        if word.starts_with('@') {
+            let variables: Vec<_> = VARIABLES
+                .iter()
+                .map(|variable| format!("{variable}["))
+                .collect();
+            let variables: Vec<_> = variables.iter().map(String::as_str).collect();
+            suggestions.extend(suggest("", &variables));
+        } else {
+            let names: Vec<_> = server
+                .get_all_players()
+                .await
+                .iter()
+                .map(|player| player.gameprofile.name.clone())
+                .collect();
+            let names: Vec<_> = names.iter().map(String::as_str).collect();
+            suggestions.extend(suggest("", &names));
+        }
+        return suggestions;
+    };
+    if word.ends_with(']') {
+        return Vec::new();
+    }
+
+    // The option which is being written
+    let option_start = word[open..]
+        .rfind(',')
+        .map_or(open + 1, |comma| open + comma + 1);
+    let (prefix, option) = word.split_at(option_start);
+    let Some((key, _)) = option.split_once('=') else {
+        let options: Vec<_> = OPTIONS
+            .iter()
+            .filter(|key| !(players_only && **key == "type"))
+            .map(|key| format!("{key}="))
+            .collect();
+        let options: Vec<_> = options.iter().map(String::as_str).collect();
+        return suggest(prefix, &options);
+    };
+    let prefix = format!("{prefix}{key}=");
+    let entity_types: Vec<_>;
+    let values: &[&str] = match key {
+        "sort" => &SORTS,
+        "gamemode" => &GAME_MODES,
+        "type" if !players_only => {
+            entity_types = EntityType::ALL.iter().map(EntityType::name).collect();
+            &entity_types
+        }
+        _ => return Vec::new(),
+    };
+    let mut suggestions = suggest(&prefix, values);
+    suggestions.extend(suggest(&format!("{prefix}!"), values));
+    suggestions
+}
@@ -16,27 +16,54 @@ use super::{
     CommandSender,
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
-use crate::{entity::player::Player, server::Server};
+use crate::{
+    entity::{any_entity::AnyEntity, player::Player},
+    net::GameProfile,
+    server::Server,
+};
 
+pub(crate) mod arg_advancement;
 pub(crate) mod arg_block;
+pub(crate) mod arg_block_predicate;
 pub(crate) mod arg_bool;
 pub(crate) mod arg_bossbar_color;
 pub(crate) mod arg_bossbar_style;
 pub(crate) mod arg_bounded_num;
+pub(crate) mod arg_color;
 pub(crate) mod arg_command;
+pub(crate) mod arg_criterion;
+pub(crate) mod arg_damage_type;
+pub(crate) mod arg_datapack;
+pub(crate) mod arg_effect;
 pub(crate) mod arg_entities;
 pub(crate) mod arg_entity;
+pub(crate) mod arg_entity_type;
+pub(crate) mod arg_function;
+pub(crate) mod arg_game_profile;
 pub(crate) mod arg_gamemode;
 pub(crate) mod arg_item;
+pub(crate) mod arg_item_predicate;
+pub(crate) mod arg_item_slot;
 pub(crate) mod arg_message;
+pub(crate) mod arg_nbt_compound;
+pub(crate) mod arg_nbt_path;
+pub(crate) mod arg_nbt_tag;
+pub(crate) mod arg_objective;
+pub(crate) mod arg_operation;
 pub(crate) mod arg_players;
 pub(crate) mod arg_position_2d;
 pub(crate) mod arg_position_3d;
 pub(crate) mod arg_position_block;
 pub(crate) mod arg_resource_location;
+pub(crate) mod arg_resource_or_tag;
 pub(crate) mod arg_rotation;
+pub(crate) mod arg_score_holder;
+pub(crate) mod arg_scoreboard_slot;
 pub(crate) mod arg_simple;
+pub(crate) mod arg_team;
 mod coordinate;
+pub(crate) mod entity_selector;
+pub(crate) mod suggestions;
 
 /// see [`crate::commands::tree_builder::argument`]
 #[async_trait]
@@ -49,6 +76,8 @@ pub(crate) trait ArgumentConsumer: Sync + GetClientSideArgParser {
     ) -> Option<Arg>;
 
     /// Used for tab completion (but only if argument suggestion type is "minecraft:ask_server"!).
+    /// The input is what was written for this argument so far, several words for arguments like
+    /// positions. Suggestions replace its last word.
     ///
     /// NOTE: This is called after this consumer's [`ArgumentConsumer::consume`] method returned None, so if args is used here, make sure [`ArgumentConsumer::consume`] never returns None after mutating args.
     async fn suggest<'a>(
@@ -72,9 +101,11 @@ pub(crate) trait DefaultNameArgConsumer: ArgumentConsumer {
 
 #[derive(Clone)]
 pub(crate) enum Arg<'a> {
-    Entities(Vec<Arc<Player>>),
-    Entity(Arc<Player>),
-    Players(Vec<Arc<Player>>),
+    Entities(Result<Vec<AnyEntity>, String>),
+    Entity(Result<AnyEntity, String>),
+    Players(Result<Vec<Arc<Player>>, String>),
+    GameProfiles(Result<Vec<GameProfile>, String>),
+    ScoreHolders(Result<Vec<String>, String>),
     BlockPos(WorldPosition),
     Pos3D(Vector3<f64>),
     Pos2D(Vector2<f64>),
@@ -82,11 +113,30 @@ pub(crate) enum Arg<'a> {
     GameMode(GameMode),
     CommandTree(CommandTree),
     Item(&'a str),
+    ItemPredicate(&'a str),
+    ItemSlot(&'a str),
+    Effect(&'a str),
+    DamageType(&'a str),
+    EntityType(&'a str),
     ResourceLocation(&'a str),
+    ResourceOrTag(&'a str),
     Block(&'a str),
+    BlockPredicate(&'a str),
+    Objective(&'a str),
+    Criterion(&'a str),
+    ScoreboardSlot(&'a str),
+    Operation(&'a str),
+    Team(&'a str),
+    Advancement(&'a str),
+    Function(&'a str),
+    Datapack(&'a str),
+    Color(&'a str),
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
     Msg(String),
+    NbtCompound(String),
+    NbtTag(String),
+    NbtPath(&'a str),
     Num(Result<Number, NotInBounds>),
     Bool(bool),
     #[allow(unused)]
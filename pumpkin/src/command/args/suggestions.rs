@@ -0,0 +1,220 @@
+use pumpkin_core::text::TextComponent;
+use pumpkin_protocol::client::play::CommandSuggestion;
+use pumpkin_registry::{get_tags, TagCategory};
+use pumpkin_world::{
+    block::block_registry::{get_block, Block, BLOCKS},
+    item::item_registry::ITEMS,
+};
+
+/// The word suggestions replace, the last one of the input
+pub(crate) fn last_word(input: &str) -> &str {
+    input.rsplit(char::is_whitespace).next().unwrap_or_default()
+}
+
+/// The resources whose id starts like the last word, with or without the `minecraft:`
+/// namespace, like vanilla's `SharedSuggestionProvider.suggestResource`. Ids are given without
+/// namespace, the suggestions have it
+pub(crate) fn suggest_resources<'a, 'b>(
+    input: &str,
+    ids: impl IntoIterator<Item = &'b str>,
+    tooltip: impl Fn(&str) -> Option<TextComponent<'a>>,
+) -> Vec<CommandSuggestion<'a>> {
+    let word = last_word(input);
+    ids.into_iter()
+        .filter(|id| id.starts_with(word) || format!("minecraft:{id}").starts_with(word))
+        .map(|id| CommandSuggestion::new(format!("minecraft:{id}"), tooltip(id)))
+        .collect()
+}
+
+/// The `#tags` of the category whose id starts like the last word
+pub(crate) fn suggest_tags<'a>(input: &str, category: TagCategory) -> Vec<CommandSuggestion<'a>> {
+    let Some(word) = last_word(input).strip_prefix('#') else {
+        return Vec::new();
+    };
+    suggest_resources(word, get_tags(category).map(|(tag, _)| tag), |_| None)
+        .into_iter()
+        .map(|suggestion| CommandSuggestion::new(format!("#{}", suggestion.suggestion), None))
+        .collect()
+}
+
+/// Completes the coordinates being written with `~`, or `^` if they are local, like vanilla's
+/// `SharedSuggestionProvider.suggestCoordinates`
+pub(crate) fn suggest_coordinates<'a>(
+    input: &str,
+    dimensions: usize,
+) -> Vec<CommandSuggestion<'a>> {
+    let word = last_word(input);
+    // The coordinates written so far, including the one being written
+    let written = input.split_whitespace().count();
+    let filler = if input.trim_start().starts_with('^') {
+        "^"
+    } else {
+        "~"
+    };
+
+    let mut suggestions = Vec::new();
+    let mut suggestion = word.to_string();
+    for _ in written..dimensions {
+        if !suggestion.is_empty() {
+            suggestion.push(' ');
+        }
+        suggestion.push_str(filler);
+        suggestions.push(CommandSuggestion::new(suggestion.clone(), None));
+    }
+    suggestions
+}
+
+/// The translated name of the item, block items are named after their block
+fn item_tooltip<'a>(id: &str) -> Option<TextComponent<'a>> {
+    let item = ITEMS.get(id)?;
+    let key = get_block(id)
+        .filter(|block| block.item_id == item.id)
+        .map_or_else(
+            || format!("item.minecraft.{id}"),
+            |block| block.translation_key.clone(),
+        );
+    Some(TextComponent::translate(key, vec![]))
+}
+
+/// The items whose id starts like the last word
+pub(crate) fn suggest_items<'a>(input: &str) -> Vec<CommandSuggestion<'a>> {
+    // Components can't be suggested
+    if last_word(input).contains('[') {
+        return Vec::new();
+    }
+    suggest_resources(input, ITEMS.keys().map(String::as_str), item_tooltip)
+}
+
+fn block_tooltip<'a>(id: &str) -> Option<TextComponent<'a>> {
+    get_block(id).map(|block| TextComponent::translate(block.translation_key.clone(), vec![]))
+}
+
+/// Block ids, then the properties of the block in brackets and their values, like vanilla's
+/// `BlockStateParser`. Block entity data isn't suggested
+pub(crate) fn suggest_block_states<'a>(input: &str) -> Vec<CommandSuggestion<'a>> {
+    let word = last_word(input);
+    let Some((id, properties)) = word.split_once('[') else {
+        let mut suggestions = suggest_resources(
+            input,
+            BLOCKS.blocks.iter().map(|block| block.name.as_str()),
+            block_tooltip,
+        );
+        // A complete id can go on with the properties of the block
+        if get_block(word).is_some_and(|block| !block.properties.is_empty()) {
+            suggestions.push(CommandSuggestion::new(format!("{word}["), None));
+        }
+        return suggestions;
+    };
+    if properties.contains(']') {
+        return Vec::new();
+    }
+    let Some(block) = get_block(id) else {
+        return Vec::new();
+    };
+
+    // The property which is being written
+    let property_start =
+        word.len() - properties.len() + properties.rfind(',').map_or(0, |comma| comma + 1);
+    let (prefix, property) = word.split_at(property_start);
+    let Some((key, value)) = property.split_once('=') else {
+        let written: Vec<_> = properties
+            .split(',')
+            .filter_map(|property| property.split_once('='))
+            .map(|(key, _)| key)
+            .collect();
+        return block
+            .properties
+            .iter()
+            .filter(|property| !written.contains(&property.name.as_str()))
+            .map(|property| format!("{prefix}{}=", property.name))
+            .filter(|suggestion| suggestion.starts_with(word))
+            .map(|suggestion| CommandSuggestion::new(suggestion, None))
+            .collect();
+    };
+    suggest_property_values(block, prefix, key, value)
+}
+
+/// The values of the property, a complete value is followed by the next property or the end
+fn suggest_property_values<'a>(
+    block: &Block,
+    prefix: &str,
+    key: &str,
+    value: &str,
+) -> Vec<CommandSuggestion<'a>> {
+    let Some(property) = block
+        .properties
+        .iter()
+        .find(|property| property.name == key)
+    else {
+        return Vec::new();
+    };
+    if property.values.iter().any(|known| known == value) {
+        let written = format!("{prefix}{key}={value}");
+        return vec![
+            CommandSuggestion::new(format!("{written},"), None),
+            CommandSuggestion::new(format!("{written}]"), None),
+        ];
+    }
+    property
+        .values
+        .iter()
+        .filter(|known| known.starts_with(value))
+        .map(|known| CommandSuggestion::new(format!("{prefix}{key}={known}"), None))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::client::play::CommandSuggestion;
+
+    use super::{suggest_block_states, suggest_coordinates, suggest_resources};
+
+    fn suggestions(suggestions: Vec<CommandSuggestion<'_>>) -> Vec<String> {
+        suggestions
+            .into_iter()
+            .map(|suggestion| suggestion.suggestion)
+            .collect()
+    }
+
+    #[test]
+    fn resources_are_suggested_with_their_namespace() {
+        let ids = ["diamond", "diamond_block", "dirt"];
+        let expected = ["minecraft:diamond", "minecraft:diamond_block"];
+        assert_eq!(
+            suggestions(suggest_resources("give @s dia", ids, |_| None)),
+            expected
+        );
+        assert_eq!(
+            suggestions(suggest_resources("give @s minecraft:dia", ids, |_| None)),
+            expected
+        );
+        assert!(suggest_resources("give @s stone", ids, |_| None).is_empty());
+    }
+
+    #[test]
+    fn missing_coordinates_are_filled_in() {
+        assert_eq!(
+            suggestions(suggest_coordinates("", 3)),
+            ["~", "~ ~", "~ ~ ~"]
+        );
+        assert_eq!(suggestions(suggest_coordinates("1 ", 3)), ["~", "~ ~"]);
+        assert_eq!(suggestions(suggest_coordinates("^ ^", 3)), ["^ ^"]);
+    }
+
+    #[test]
+    fn block_states_are_suggested_property_by_property() {
+        assert_eq!(
+            suggestions(suggest_block_states("minecraft:oak_stairs[facing=e")),
+            ["minecraft:oak_stairs[facing=east"]
+        );
+        assert_eq!(
+            suggestions(suggest_block_states("oak_stairs[facing=east")),
+            ["oak_stairs[facing=east,", "oak_stairs[facing=east]"]
+        );
+        assert_eq!(
+            suggestions(suggest_block_states("oak_stairs[facing=east,ha")),
+            ["oak_stairs[facing=east,half="]
+        );
+        assert!(suggest_block_states("oak_stairs[facing=east]").is_empty());
+    }
+}
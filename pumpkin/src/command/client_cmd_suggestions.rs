@@ -7,7 +7,7 @@ use crate::entity::player::Player;
 
 use super::{
     dispatcher::CommandDispatcher,
-    tree::{Node, NodeType},
+    tree::{Node, NodeType, RedirectTarget},
 };
 
 pub async fn send_c_commands_packet(player: &Arc<Player>, dispatcher: &RwLock<CommandDispatcher>) {
@@ -20,19 +20,25 @@ pub async fn send_c_commands_packet(player: &Arc<Player>, dispatcher: &RwLock<Co
             continue;
         };
 
-        let Some(permission) = dispatcher.get_permission_lvl(key) else {
+        // Commands the player may not run aren't sent, so the client doesn't suggest them
+        let Some(node) = dispatcher.get_permission_node(key) else {
             continue;
         };
 
-        if !cmd_src.has_permission_lvl(permission) {
+        if !cmd_src
+            .has_permission(&dispatcher.permission_registry, node)
+            .await
+        {
             continue;
         }
 
-        let (is_executable, child_nodes) =
+        let (is_executable, redirect, child_nodes) =
             nodes_to_proto_node_builders(&cmd_src, &tree.nodes, &tree.children);
 
         let proto_node = ProtoNodeBuilder {
             child_nodes,
+            redirect,
+            is_command: true,
             node_type: ProtoNodeType::Literal {
                 name: key,
                 is_executable,
@@ -44,11 +50,13 @@ pub async fn send_c_commands_packet(player: &Arc<Player>, dispatcher: &RwLock<Co
 
     let root = ProtoNodeBuilder {
         child_nodes: first_level,
+        redirect: None,
+        is_command: false,
         node_type: ProtoNodeType::Root,
     };
 
     let mut proto_nodes = Vec::new();
-    let root_node_index = root.build(&mut proto_nodes);
+    let root_node_index = root.build(&mut proto_nodes, &mut Vec::new());
 
     let packet = CCommands::new(proto_nodes, root_node_index.into());
     player.client.send_packet(&packet).await;
@@ -57,42 +65,72 @@ pub async fn send_c_commands_packet(player: &Arc<Player>, dispatcher: &RwLock<Co
 #[derive(Debug)]
 struct ProtoNodeBuilder<'a> {
     child_nodes: Vec<ProtoNodeBuilder<'a>>,
+    redirect: Option<RedirectTarget>,
+    /// Whether this is the literal of a command, the target of [`RedirectTarget::Command`]
+    is_command: bool,
     node_type: ProtoNodeType<'a>,
 }
 
 impl<'a> ProtoNodeBuilder<'a> {
-    fn build(self, buffer: &mut Vec<ProtoNode<'a>>) -> usize {
+    /// Children are built before their parents, so redirects wait in `redirects` until the node
+    /// they point to is built
+    fn build(
+        self,
+        buffer: &mut Vec<ProtoNode<'a>>,
+        redirects: &mut Vec<(usize, RedirectTarget)>,
+    ) -> usize {
         let mut children = Vec::new();
         for node in self.child_nodes {
-            let i = node.build(buffer);
+            let i = node.build(buffer, redirects);
             children.push(i.into());
         }
 
         let i = buffer.len();
         buffer.push(ProtoNode {
             children,
+            redirect: None,
             node_type: self.node_type,
         });
+        if let Some(target) = self.redirect {
+            redirects.push((i, target));
+        }
+
+        let is_root = matches!(buffer[i].node_type, ProtoNodeType::Root);
+        redirects.retain(|&(node, target)| {
+            let resolved = match target {
+                RedirectTarget::Command => self.is_command,
+                RedirectTarget::Commands => is_root,
+            };
+            if resolved {
+                buffer[node].redirect = Some(i.into());
+            }
+            !resolved
+        });
         i
     }
 }
 
+/// Whether the children make their parent executable, where they redirect it and the nodes
+/// the client gets for them
 fn nodes_to_proto_node_builders<'a>(
     cmd_src: &super::CommandSender,
     nodes: &'a [Node],
     children: &[usize],
-) -> (bool, Vec<ProtoNodeBuilder<'a>>) {
+) -> (bool, Option<RedirectTarget>, Vec<ProtoNodeBuilder<'a>>) {
     let mut child_nodes = Vec::new();
     let mut is_executable = false;
+    let mut redirect = None;
 
     for i in children {
         let node = &nodes[*i];
         match &node.node_type {
             NodeType::Argument { name, consumer } => {
-                let (node_is_executable, node_children) =
+                let (node_is_executable, node_redirect, node_children) =
                     nodes_to_proto_node_builders(cmd_src, nodes, &node.children);
                 child_nodes.push(ProtoNodeBuilder {
                     child_nodes: node_children,
+                    redirect: node_redirect,
+                    is_command: false,
                     node_type: ProtoNodeType::Argument {
                         name,
                         is_executable: node_is_executable,
@@ -104,10 +142,12 @@ fn nodes_to_proto_node_builders<'a>(
             }
 
             NodeType::Literal { string, .. } => {
-                let (node_is_executable, node_children) =
+                let (node_is_executable, node_redirect, node_children) =
                     nodes_to_proto_node_builders(cmd_src, nodes, &node.children);
                 child_nodes.push(ProtoNodeBuilder {
                     child_nodes: node_children,
+                    redirect: node_redirect,
+                    is_command: false,
                     node_type: ProtoNodeType::Literal {
                         name: string,
                         is_executable: node_is_executable,
@@ -117,18 +157,83 @@ fn nodes_to_proto_node_builders<'a>(
 
             NodeType::ExecuteLeaf { .. } => is_executable = true,
 
+            NodeType::Redirect { target, .. } => redirect = Some(*target),
+
             NodeType::Require { predicate } => {
                 if predicate(cmd_src) {
-                    let (node_is_executable, node_children) =
+                    let (node_is_executable, node_redirect, node_children) =
                         nodes_to_proto_node_builders(cmd_src, nodes, &node.children);
                     if node_is_executable {
                         is_executable = true;
                     }
+                    redirect = redirect.or(node_redirect);
                     child_nodes.extend(node_children);
                 }
             }
         }
     }
 
-    (is_executable, child_nodes)
+    (is_executable, redirect, child_nodes)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::client::play::{ProtoNode, ProtoNodeType};
+
+    use super::{nodes_to_proto_node_builders, ProtoNodeBuilder};
+    use crate::command::{commands::cmd_execute, CommandSender};
+
+    fn child(nodes: &[ProtoNode], parent: usize, name: &str) -> usize {
+        nodes[parent]
+            .children
+            .iter()
+            .map(|child| child.0 as usize)
+            .find(|&child| match nodes[child].node_type {
+                ProtoNodeType::Literal { name: child, .. }
+                | ProtoNodeType::Argument { name: child, .. } => child == name,
+                ProtoNodeType::Root => false,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn redirects_point_at_their_targets() {
+        let tree = cmd_execute::init_command_tree();
+        let (is_executable, redirect, child_nodes) =
+            nodes_to_proto_node_builders(&CommandSender::Console, &tree.nodes, &tree.children);
+        let root = ProtoNodeBuilder {
+            child_nodes: vec![ProtoNodeBuilder {
+                child_nodes,
+                redirect,
+                is_command: true,
+                node_type: ProtoNodeType::Literal {
+                    name: "execute",
+                    is_executable,
+                },
+            }],
+            redirect: None,
+            is_command: false,
+            node_type: ProtoNodeType::Root,
+        };
+        let mut nodes = Vec::new();
+        let root = root.build(&mut nodes, &mut Vec::new());
+        let redirect = |node: usize| nodes[node].redirect.clone().map(|i| i.0 as usize);
+
+        let execute = child(&nodes, root, "execute");
+        let run = child(&nodes, execute, "run");
+        assert_eq!(redirect(run), Some(root));
+        let targets = child(&nodes, child(&nodes, execute, "as"), "targets");
+        assert_eq!(redirect(targets), Some(execute));
+        let block = child(&nodes, child(&nodes, execute, "if"), "block");
+        let predicate = child(&nodes, child(&nodes, block, "pos"), "block");
+        assert_eq!(redirect(predicate), Some(execute));
+        assert!(matches!(
+            nodes[predicate].node_type,
+            ProtoNodeType::Argument {
+                is_executable: true,
+                ..
+            }
+        ));
+        assert_eq!(redirect(execute), None);
+    }
 }
@@ -0,0 +1,261 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+use pumpkin_registry::{get_advancement, ADVANCEMENTS};
+
+use crate::command::args::arg_advancement::AdvancementArgumentConsumer;
+use crate::command::args::arg_message::MsgArgConsumer;
+use crate::command::args::arg_players::PlayersArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::net::advancements::advancement_name;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["advancement"];
+
+const DESCRIPTION: &str = "Grants or revokes advancements of players.";
+
+const ARG_CRITERION: &str = "criterion";
+
+/// Which advancements are changed, like vanilla's `AdvancementCommands.Mode`
+#[derive(Clone, Copy)]
+enum Mode {
+    Only,
+    From,
+    Through,
+    Until,
+    Everything,
+}
+
+/// The advancement, its ancestors and its descendants as the mode wants them, parents first
+fn select(advancement: &'static str, mode: Mode) -> Vec<&'static str> {
+    let mut selected = Vec::new();
+    if matches!(mode, Mode::Through | Mode::Until) {
+        let mut parent = get_advancement(advancement).and_then(|a| a.parent.as_deref());
+        while let Some((id, ancestor)) = parent.and_then(|id| ADVANCEMENTS.get_key_value(id)) {
+            selected.insert(0, id.as_str());
+            parent = ancestor.parent.as_deref();
+        }
+    }
+    selected.push(advancement);
+    if matches!(mode, Mode::From | Mode::Through) {
+        // Parents come before their children, so one pass finds all descendants
+        let start = selected.len() - 1;
+        for (id, descendant) in ADVANCEMENTS.iter() {
+            let parent = descendant.parent.as_deref();
+            if parent.is_some_and(|parent| selected[start..].contains(&parent)) {
+                selected.push(id);
+            }
+        }
+    }
+    selected
+}
+
+struct AdvancementExecutor {
+    grant: bool,
+    mode: Mode,
+}
+
+impl AdvancementExecutor {
+    const fn verb(&self) -> &'static str {
+        if self.grant {
+            "grant"
+        } else {
+            "revoke"
+        }
+    }
+
+    /// How the failure messages end, players already have what they are granted
+    const fn failure(&self) -> (&'static str, &'static str) {
+        if self.grant {
+            ("to", "already have")
+        } else {
+            ("from", "don't have")
+        }
+    }
+
+    /// Changes a single criterion of the advancement
+    async fn execute_criterion(
+        &self,
+        sender: &mut CommandSender<'_>,
+        args: &ConsumedArgs<'_>,
+        id: &'static str,
+        criterion: &str,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let Some(advancement) = get_advancement(id) else {
+            return Ok(());
+        };
+        if !advancement.criteria.contains_key(criterion) {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "The advancement {id} does not contain the criterion '{criterion}'"
+            )));
+        }
+        let mut changed = 0;
+        for target in targets {
+            let done = if self.grant {
+                target.award_criterion(id, criterion).await
+            } else {
+                target.revoke_criterion(id, criterion).await
+            };
+            if done {
+                changed += 1;
+            }
+        }
+
+        let verb = self.verb();
+        if changed == 0 {
+            let (to, action) = self.failure();
+            return Err(CommandError::GeneralCommandIssue(match targets {
+                [target] => format!(
+                    "Couldn't {verb} criterion '{criterion}' of advancement {id} {to} {} as they {action} it",
+                    target.gameprofile.name
+                ),
+                _ => format!(
+                    "Couldn't {verb} criterion '{criterion}' of advancement {id} {to} {} players as they {action} it",
+                    targets.len()
+                ),
+            }));
+        }
+        let (key, target) = match targets {
+            [target] => (
+                "one",
+                TextComponent::text_string(target.gameprofile.name.clone()),
+            ),
+            _ => (
+                "many",
+                TextComponent::text_string(targets.len().to_string()),
+            ),
+        };
+        sender
+            .send_message(TextComponent::translate(
+                format!("commands.advancement.{verb}.criterion.to.{key}.success"),
+                vec![
+                    TextComponent::text_string(criterion.to_string()),
+                    advancement_name(id, advancement),
+                    target,
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for AdvancementExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let advancements = match self.mode {
+            Mode::Everything => ADVANCEMENTS.keys().map(String::as_str).collect(),
+            mode => {
+                let advancement = AdvancementArgumentConsumer.find_arg_default_name(args)?;
+                if args.contains_key(ARG_CRITERION) {
+                    let criterion = MsgArgConsumer::find_arg(args, ARG_CRITERION)?;
+                    return self
+                        .execute_criterion(sender, args, advancement, criterion)
+                        .await;
+                }
+                select(advancement, mode)
+            }
+        };
+
+        let mut changed = 0;
+        for target in targets {
+            for id in &advancements {
+                let done = if self.grant {
+                    target.grant_advancement(id).await
+                } else {
+                    target.revoke_advancement(id).await
+                };
+                if done {
+                    changed += 1;
+                }
+            }
+        }
+
+        let verb = self.verb();
+        let single = match advancements.as_slice() {
+            [id] => get_advancement(id).map(|advancement| (*id, advancement)),
+            _ => None,
+        };
+        if changed == 0 {
+            let (to, action) = self.failure();
+            let (what, them) = match single {
+                Some((id, _)) => (format!("advancement {id}"), "it"),
+                None => (format!("{} advancements", advancements.len()), "them"),
+            };
+            return Err(CommandError::GeneralCommandIssue(match targets {
+                [target] => format!(
+                    "Couldn't {verb} {what} {to} {} as they {action} {them}",
+                    target.gameprofile.name
+                ),
+                _ => format!(
+                    "Couldn't {verb} {what} {to} {} players as they {action} {them}",
+                    targets.len()
+                ),
+            }));
+        }
+        let (amount, what) = match single {
+            Some((id, advancement)) => ("one", advancement_name(id, advancement)),
+            None => (
+                "many",
+                TextComponent::text_string(advancements.len().to_string()),
+            ),
+        };
+        let (key, target) = match targets {
+            [target] => (
+                "one",
+                TextComponent::text_string(target.gameprofile.name.clone()),
+            ),
+            _ => (
+                "many",
+                TextComponent::text_string(targets.len().to_string()),
+            ),
+        };
+        sender
+            .send_message(TextComponent::translate(
+                format!("commands.advancement.{verb}.{amount}.to.{key}.success"),
+                vec![what, target],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+fn action_tree(grant: bool) -> NonLeafNodeBuilder {
+    let executor = |mode| AdvancementExecutor { grant, mode };
+    let modes = [
+        ("from", Mode::From),
+        ("through", Mode::Through),
+        ("until", Mode::Until),
+    ];
+    let mut targets = argument_default_name(PlayersArgumentConsumer)
+        .with_child(literal("everything").execute(executor(Mode::Everything)))
+        .with_child(
+            literal("only").with_child(
+                argument_default_name(AdvancementArgumentConsumer)
+                    .execute(executor(Mode::Only))
+                    .with_child(
+                        argument(ARG_CRITERION, MsgArgConsumer).execute(executor(Mode::Only)),
+                    ),
+            ),
+        );
+    for (name, mode) in modes {
+        targets = targets.with_child(literal(name).with_child(
+            argument_default_name(AdvancementArgumentConsumer).execute(executor(mode)),
+        ));
+    }
+    literal(if grant { "grant" } else { "revoke" }).with_child(targets)
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(action_tree(true))
+        .with_child(action_tree(false))
+}
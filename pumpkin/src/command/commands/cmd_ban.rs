@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            arg_game_profile::GameProfileArgumentConsumer, arg_message::MsgArgConsumer,
+            ConsumedArgs, FindArg,
+        },
+        tree::CommandTree,
+        tree_builder::argument,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        banlist_serializer::ban_message,
+        banned_player_data::{BannedPlayerEntry, BANNED_PLAYER_LIST},
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["ban"];
+const DESCRIPTION: &str = "Bans a player from the server.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_REASON: &str = "reason";
+
+/// The reason of bans which don't give one, like vanilla
+pub(super) const DEFAULT_REASON: &str = "Banned by an operator.";
+
+struct BanExecutor;
+
+#[async_trait]
+impl CommandExecutor for BanExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = GameProfileArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let reason = if args.contains_key(ARG_REASON) {
+            MsgArgConsumer::find_arg(args, ARG_REASON)?
+        } else {
+            DEFAULT_REASON
+        };
+
+        let mut banned = 0;
+        for target in targets {
+            let entry =
+                BannedPlayerEntry::new(target, sender.to_string(), None, reason.to_string());
+            if !BANNED_PLAYER_LIST.write().await.add(entry) {
+                continue;
+            }
+            banned += 1;
+
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.ban.success",
+                    vec![
+                        TextComponent::text_string(target.name.clone()),
+                        TextComponent::text_string(reason.to_string()),
+                    ],
+                ))
+                .await;
+            if let Some(player) = server.get_player_by_uuid(target.id).await {
+                player
+                    .kick(TextComponent::text_string(ban_message(
+                        &ADVANCED_CONFIG.moderation.banned_message,
+                        reason,
+                        None,
+                    )))
+                    .await;
+            }
+        }
+
+        if banned == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. The player is already banned".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        argument(ARG_TARGETS, GameProfileArgumentConsumer)
+            .execute(BanExecutor)
+            .with_child(argument(ARG_REASON, MsgArgConsumer).execute(BanExecutor)),
+    )
+}
@@ -0,0 +1,109 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{arg_message::MsgArgConsumer, arg_simple::SimpleArgConsumer, ConsumedArgs, FindArg},
+        tree::CommandTree,
+        tree_builder::argument,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        banlist_serializer::ban_message,
+        banned_ip_data::{BannedIpEntry, BANNED_IP_LIST},
+    },
+    server::Server,
+};
+
+use super::cmd_ban::DEFAULT_REASON;
+
+const NAMES: [&str; 1] = ["ban-ip"];
+const DESCRIPTION: &str = "Bans an IP address from the server.";
+
+const ARG_TARGET: &str = "target";
+const ARG_REASON: &str = "reason";
+
+struct BanIpExecutor;
+
+#[async_trait]
+impl CommandExecutor for BanIpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = SimpleArgConsumer::find_arg(args, ARG_TARGET)?;
+        let reason = if args.contains_key(ARG_REASON) {
+            MsgArgConsumer::find_arg(args, ARG_REASON)?
+        } else {
+            DEFAULT_REASON
+        };
+
+        // Like vanilla, the target is an address or the name of an online player
+        let ip = if let Ok(ip) = target.parse::<IpAddr>() {
+            ip
+        } else if let Some(player) = server.get_player_by_name(target).await {
+            player.client.address.lock().await.ip()
+        } else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Invalid IP address or unknown player".to_string(),
+            ));
+        };
+
+        let entry = BannedIpEntry::new(ip, sender.to_string(), None, reason.to_string());
+        if !BANNED_IP_LIST.write().await.add(entry) {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. That IP is already banned".to_string(),
+            ));
+        }
+
+        let mut names = Vec::new();
+        for player in server.get_all_players().await {
+            if player.client.address.lock().await.ip() != ip {
+                continue;
+            }
+            names.push(player.gameprofile.name.clone());
+            player
+                .kick(TextComponent::text_string(ban_message(
+                    &ADVANCED_CONFIG.moderation.ip_banned_message,
+                    reason,
+                    None,
+                )))
+                .await;
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.banip.success",
+                vec![
+                    TextComponent::text_string(ip.to_string()),
+                    TextComponent::text_string(reason.to_string()),
+                ],
+            ))
+            .await;
+        if !names.is_empty() {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.banip.info",
+                    vec![
+                        TextComponent::text_string(names.len().to_string()),
+                        TextComponent::text_string(names.join(", ")),
+                    ],
+                ))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        argument(ARG_TARGET, SimpleArgConsumer)
+            .execute(BanIpExecutor)
+            .with_child(argument(ARG_REASON, MsgArgConsumer).execute(BanIpExecutor)),
+    )
+}
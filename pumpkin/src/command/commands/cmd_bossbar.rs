@@ -84,6 +84,7 @@ impl CommandExecutor for BossbarAddExecuter {
             .await
             .create_bossbar(namespace.to_string(), bossbar.clone());
 
+        send_success_message(sender, format!("Created custom bossbar [{name}]")).await;
         Ok(())
     }
 }
@@ -201,14 +202,14 @@ impl CommandExecutor for BossbarRemoveExecuter {
     ) -> Result<(), CommandError> {
         let namespace = autocomplete_consumer().find_arg_default_name(args)?;
 
-        if !server.bossbars.lock().await.has_bossbar(namespace) {
+        let Some(bossbar) = server.bossbars.lock().await.get_bossbar(namespace) else {
             send_error_message(
                 sender,
-                format!("A boss bar already exists with the ID '{namespace}'"),
+                format!("No bossbar exists with the ID '{namespace}'"),
             )
             .await;
             return Ok(());
-        }
+        };
 
         match server
             .bossbars
@@ -224,6 +225,11 @@ impl CommandExecutor for BossbarRemoveExecuter {
             }
         };
 
+        send_success_message(
+            sender,
+            format!("Removed custom bossbar [{}]", bossbar.bossbar_data.title),
+        )
+        .await;
         Ok(())
     }
 }
@@ -5,37 +5,83 @@ use pumpkin_core::text::color::NamedColor;
 use pumpkin_core::text::TextComponent;
 use pumpkin_inventory::Container;
 
-use crate::command::args::arg_entities::EntitiesArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_item_predicate::{ItemPredicate, ItemPredicateArgumentConsumer};
+use crate::command::args::arg_players::PlayersArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
 use crate::command::tree::CommandTree;
-use crate::command::tree_builder::{argument, require};
+use crate::command::tree_builder::{argument, argument_default_name, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
 use crate::entity::player::Player;
-use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["clear"];
 const DESCRIPTION: &str = "Clear yours or targets inventory.";
 
 const ARG_TARGET: &str = "target";
+const ARG_ITEM: &str = "item";
+const ARG_MAX_COUNT: &str = "maxCount";
 
-async fn clear_player(target: &Player) -> usize {
+fn max_count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_MAX_COUNT).min(0)
+}
+
+/// Removes the items from the inventory of the player, only the ones matching the predicate if
+/// there is one and at most the max count. A max count of zero only counts the matching items
+async fn clear_player(
+    target: &Player,
+    predicate: Option<&ItemPredicate<'_>>,
+    max_count: Option<usize>,
+) -> usize {
     let mut inventory = target.inventory().lock().await;
 
-    let slots = inventory.all_slots();
-    let items_count = slots
-        .iter()
-        .filter_map(|slot| slot.as_ref().map(|slot| slot.item_count as usize))
-        .sum();
-    for slot in slots {
-        *slot = None;
+    let mut items_count = 0;
+    for slot in inventory.all_slots() {
+        let Some(stack_count) = slot
+            .as_ref()
+            .filter(|stack| predicate.is_none_or(|predicate| predicate.matches(stack)))
+            .map(|stack| usize::from(stack.item_count))
+        else {
+            continue;
+        };
+        let count = match max_count {
+            Some(0) => {
+                items_count += stack_count;
+                continue;
+            }
+            Some(max_count) => stack_count.min(max_count - items_count),
+            None => stack_count,
+        };
+        items_count += count;
+        if count == stack_count {
+            *slot = None;
+        } else if let Some(stack) = slot.as_mut() {
+            stack.item_count -= count as u8;
+        }
+        if max_count == Some(items_count) {
+            break;
+        }
     }
     drop(inventory);
-    target.set_container_content(None).await;
+    if max_count != Some(0) {
+        target.set_container_content(None).await;
+    }
     items_count
 }
 
-fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> TextComponent {
+fn clear_command_text_output(
+    item_count: usize,
+    targets: &[Arc<Player>],
+    test: bool,
+) -> TextComponent {
     match targets {
+        [target] if test && item_count > 0 => TextComponent::text_string(format!(
+            "Found {} matching item(s) on player {}",
+            item_count, target.gameprofile.name
+        )),
+        targets if test && item_count > 0 => TextComponent::text_string(format!(
+            "Found {item_count} matching item(s) on {} players",
+            targets.len()
+        )),
         [target] if item_count == 0 => TextComponent::text_string(format!(
             "No items were found on player {}",
             target.gameprofile.name
@@ -66,16 +112,32 @@ impl CommandExecutor for ClearExecutor {
         _server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let predicate = if args.contains_key(ARG_ITEM) {
+            Some(ItemPredicateArgumentConsumer::find_arg(args, ARG_ITEM)?)
+        } else {
+            None
+        };
+
+        let max_count = if args.contains_key(ARG_MAX_COUNT) {
+            match max_count_consumer().find_arg_default_name(args)? {
+                Ok(max_count) => Some(max_count as usize),
+                Err(()) => {
+                    return Err(CommandError::GeneralCommandIssue(
+                        "Max count is too small.".to_string(),
+                    ))
+                }
+            }
+        } else {
+            None
         };
 
         let mut item_count = 0;
         for target in targets {
-            item_count += clear_player(target).await;
+            item_count += clear_player(target, predicate.as_ref(), max_count).await;
         }
 
-        let msg = clear_command_text_output(item_count, targets);
+        let msg = clear_command_text_output(item_count, targets, max_count == Some(0));
 
         sender.send_message(msg).await;
 
@@ -95,10 +157,10 @@ impl CommandExecutor for ClearSelfExecutor {
     ) -> Result<(), CommandError> {
         let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-        let item_count = clear_player(&target).await;
+        let item_count = clear_player(&target, None, None).await;
 
         let hold_target = [target];
-        let msg = clear_command_text_output(item_count, &hold_target);
+        let msg = clear_command_text_output(item_count, &hold_target, false);
 
         sender.send_message(msg).await;
 
@@ -109,6 +171,16 @@ impl CommandExecutor for ClearSelfExecutor {
 #[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .with_child(argument(ARG_TARGET, EntitiesArgumentConsumer).execute(ClearExecutor))
+        .with_child(
+            argument(ARG_TARGET, PlayersArgumentConsumer)
+                .execute(ClearExecutor)
+                .with_child(
+                    argument(ARG_ITEM, ItemPredicateArgumentConsumer)
+                        .execute(ClearExecutor)
+                        .with_child(
+                            argument_default_name(max_count_consumer()).execute(ClearExecutor),
+                        ),
+                ),
+        )
         .with_child(require(|sender| sender.is_player()).execute(ClearSelfExecutor))
 }
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_core::text::TextComponent;
+use pumpkin_world::block::block_registry::get_block_entity_type;
+
+use crate::command::args::arg_block_predicate::BlockPredicateArgumentConsumer;
+use crate::command::args::arg_position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+use super::cmd_fill::{bounds, check_volume, is_air, place_blocks};
+
+const NAMES: [&str; 1] = ["clone"];
+
+const DESCRIPTION: &str = "Copies blocks from one place to another.";
+
+const ARG_BEGIN: &str = "begin";
+const ARG_END: &str = "end";
+const ARG_DESTINATION: &str = "destination";
+const ARG_FILTER: &str = "filter";
+
+/// Which blocks of the source are copied
+#[derive(Clone, Copy)]
+enum Mask {
+    /// Every block
+    Replace,
+    /// Every block except air
+    Masked,
+    /// The blocks matching the filter
+    Filtered,
+}
+
+/// How the blocks are copied
+#[derive(Clone, Copy)]
+enum CloneMode {
+    /// Copies even when the source and destination overlap
+    Force,
+    /// Replaces the copied blocks of the source with air
+    Move,
+    Normal,
+}
+
+#[derive(Clone, Copy)]
+struct CloneExecutor(Mask, CloneMode);
+
+/// Whether the areas between the corners share a block
+fn overlap(
+    (min, max): (Vector3<i32>, Vector3<i32>),
+    (other_min, other_max): (Vector3<i32>, Vector3<i32>),
+) -> bool {
+    min.x <= other_max.x
+        && other_min.x <= max.x
+        && min.y <= other_max.y
+        && other_min.y <= max.y
+        && min.z <= other_max.z
+        && other_min.z <= max.z
+}
+
+#[async_trait]
+impl CommandExecutor for CloneExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Self(mask, mode) = *self;
+        let begin = BlockPosArgumentConsumer::find_arg(args, ARG_BEGIN)?;
+        let end = BlockPosArgumentConsumer::find_arg(args, ARG_END)?;
+        let destination = BlockPosArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
+        let filter = if matches!(mask, Mask::Filtered) {
+            Some(BlockPredicateArgumentConsumer::find_arg(args, ARG_FILTER)?)
+        } else {
+            None
+        };
+
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let (min, max) = bounds(begin.0, end.0);
+        check_volume(&world, min, max).await?;
+        let offset = destination.0.sub(&min);
+        if !matches!(mode, CloneMode::Force)
+            && overlap((min, max), (destination.0, max.add(&offset)))
+        {
+            return Err(CommandError::GeneralCommandIssue(
+                "The source and destination areas cannot overlap".to_string(),
+            ));
+        }
+
+        let mut sources = Vec::new();
+        let mut blocks = Vec::new();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let position = WorldPosition(Vector3::new(x, y, z));
+                    let state_id = world.get_block_state_id(position).await.unwrap_or_default();
+                    let copy = match mask {
+                        Mask::Replace => true,
+                        Mask::Masked => !is_air(state_id),
+                        Mask::Filtered => filter
+                            .as_ref()
+                            .is_some_and(|filter| filter.matches(state_id)),
+                    };
+                    if !copy {
+                        continue;
+                    }
+                    let nbt = if get_block_entity_type(state_id).is_some() {
+                        world.get_block_entity_nbt(position).await
+                    } else {
+                        None
+                    };
+                    sources.push((position, 0, None));
+                    blocks.push((WorldPosition(position.0.add(&offset)), state_id, nbt));
+                }
+            }
+        }
+        if blocks.is_empty() {
+            return Err(CommandError::GeneralCommandIssue(
+                "No blocks were cloned".to_string(),
+            ));
+        }
+
+        let cloned = blocks.len();
+        if matches!(mode, CloneMode::Move) {
+            place_blocks(&world, sources).await;
+        }
+        place_blocks(&world, blocks).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.clone.success",
+                vec![TextComponent::text_string(cloned.to_string())],
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+/// The optional `force`, `move` or `normal` after the mask
+fn with_modes(node: NonLeafNodeBuilder, mask: Mask) -> NonLeafNodeBuilder {
+    node.execute(CloneExecutor(mask, CloneMode::Normal))
+        .with_child(literal("force").execute(CloneExecutor(mask, CloneMode::Force)))
+        .with_child(literal("move").execute(CloneExecutor(mask, CloneMode::Move)))
+        .with_child(literal("normal").execute(CloneExecutor(mask, CloneMode::Normal)))
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        argument(ARG_BEGIN, BlockPosArgumentConsumer).with_child(
+            argument(ARG_END, BlockPosArgumentConsumer).with_child(
+                argument(ARG_DESTINATION, BlockPosArgumentConsumer)
+                    .execute(CloneExecutor(Mask::Replace, CloneMode::Normal))
+                    .with_child(with_modes(literal("replace"), Mask::Replace))
+                    .with_child(with_modes(literal("masked"), Mask::Masked))
+                    .with_child(literal("filtered").with_child(with_modes(
+                        argument(ARG_FILTER, BlockPredicateArgumentConsumer),
+                        Mask::Filtered,
+                    ))),
+            ),
+        ),
+    )
+}
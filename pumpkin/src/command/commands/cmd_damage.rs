@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+use pumpkin_world::damage::DamageType;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_damage_type::DamageTypeArgumentConsumer;
+use crate::command::args::arg_entity::EntityArgumentConsumer;
+use crate::command::args::arg_position_3d::Position3DArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::damage::DamageSource;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["damage"];
+
+const DESCRIPTION: &str = "Damages entities.";
+
+const ARG_TARGET: &str = "target";
+const ARG_DAMAGE_TYPE: &str = "damageType";
+const ARG_LOCATION: &str = "location";
+const ARG_ENTITY: &str = "entity";
+const ARG_CAUSE: &str = "cause";
+
+fn amount_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new().name("amount").min(0.0)
+}
+
+/// Where the damage comes from
+#[derive(Clone, Copy)]
+enum Origin {
+    /// Nowhere in particular
+    None,
+    /// `at <location>`
+    Location,
+    /// `by <entity>`
+    Entity,
+    /// `by <entity> from <cause>`, the entity hit on behalf of the cause like a projectile
+    EntityFromCause,
+}
+
+struct DamageExecutor(Origin);
+
+impl DamageExecutor {
+    /// The damage source the arguments describe, like vanilla's `DamageCommand.damage`
+    fn source(&self, args: &ConsumedArgs) -> Result<DamageSource, CommandError> {
+        let damage_type = if args.contains_key(ARG_DAMAGE_TYPE) {
+            DamageTypeArgumentConsumer::find_arg(args, ARG_DAMAGE_TYPE)?
+        } else {
+            DamageType::Generic
+        };
+        Ok(match self.0 {
+            Origin::None => DamageSource::new(damage_type),
+            Origin::Location => DamageSource::at(
+                damage_type,
+                Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?,
+            ),
+            Origin::Entity => DamageSource::by(
+                damage_type,
+                EntityArgumentConsumer::find_arg(args, ARG_ENTITY)?.damage_entity(),
+            ),
+            Origin::EntityFromCause => DamageSource::projectile(
+                damage_type,
+                EntityArgumentConsumer::find_arg(args, ARG_ENTITY)?.damage_entity(),
+                Some(EntityArgumentConsumer::find_arg(args, ARG_CAUSE)?.damage_entity()),
+            ),
+        })
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for DamageExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let Ok(amount) = amount_consumer().find_arg_default_name(args)? else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Damage amount is too small".to_string(),
+            ));
+        };
+        let source = self.source(args)?;
+
+        if !target.hurt(&source, amount).await {
+            return Err(CommandError::GeneralCommandIssue(
+                "Target is either invulnerable to the given damage type".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.damage.success",
+                vec![
+                    TextComponent::text_string(amount.to_string()),
+                    target.display_name(),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        argument(ARG_TARGET, EntityArgumentConsumer).with_child(
+            argument_default_name(amount_consumer())
+                .execute(DamageExecutor(Origin::None))
+                .with_child(
+                    argument(ARG_DAMAGE_TYPE, DamageTypeArgumentConsumer)
+                        .execute(DamageExecutor(Origin::None))
+                        .with_child(
+                            literal("at").with_child(
+                                argument(ARG_LOCATION, Position3DArgumentConsumer)
+                                    .execute(DamageExecutor(Origin::Location)),
+                            ),
+                        )
+                        .with_child(
+                            literal("by").with_child(
+                                argument(ARG_ENTITY, EntityArgumentConsumer)
+                                    .execute(DamageExecutor(Origin::Entity))
+                                    .with_child(
+                                        literal("from").with_child(
+                                            argument(ARG_CAUSE, EntityArgumentConsumer)
+                                                .execute(DamageExecutor(Origin::EntityFromCause)),
+                                        ),
+                                    ),
+                            ),
+                        ),
+                ),
+        ),
+    )
+}
@@ -0,0 +1,592 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::{math::position::WorldPosition, text::TextComponent};
+use pumpkin_nbt::{compound::NbtCompound, path::NbtPath, snbt::to_snbt, tag::NbtTag};
+
+use crate::command::{
+    args::{
+        arg_bounded_num::BoundedNumArgumentConsumer, arg_entity::EntityArgumentConsumer,
+        arg_nbt_compound::NbtCompoundArgumentConsumer, arg_nbt_path::NbtPathArgumentConsumer,
+        arg_nbt_tag::NbtTagArgumentConsumer, arg_position_block::BlockPosArgumentConsumer,
+        arg_resource_location::ResourceLocationArgumentConsumer, ConsumedArgs, FindArg,
+        FindArgDefaultName,
+    },
+    tree::CommandTree,
+    tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder},
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::entity::any_entity::AnyEntity;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["data"];
+
+const DESCRIPTION: &str =
+    "Gets, merges, modifies and removes block entity, entity and storage NBT.";
+
+const ARG_TARGET: &str = "target";
+const ARG_TARGET_POS: &str = "targetPos";
+const ARG_SOURCE: &str = "source";
+const ARG_SOURCE_POS: &str = "sourcePos";
+const ARG_PATH: &str = "path";
+const ARG_TARGET_PATH: &str = "targetPath";
+const ARG_SOURCE_PATH: &str = "sourcePath";
+const ARG_NBT: &str = "nbt";
+const ARG_VALUE: &str = "value";
+
+fn scale_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new().name("scale")
+}
+
+fn index_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("index")
+}
+
+fn unchanged() -> CommandError {
+    CommandError::GeneralCommandIssue(
+        "Nothing changed. The specified properties already have these values".to_string(),
+    )
+}
+
+fn nothing_found(path: &NbtPath) -> CommandError {
+    CommandError::GeneralCommandIssue(format!("Found no elements matching {path}"))
+}
+
+/// What `/data` works on
+#[derive(Clone, Copy)]
+enum DataKind {
+    Block,
+    Entity,
+    Storage,
+}
+
+/// Whether the accessor is the one changed or the one `/data modify ... from` reads
+#[derive(Clone, Copy)]
+enum Role {
+    Target,
+    Source,
+}
+
+impl DataKind {
+    const ALL: [Self; 3] = [Self::Block, Self::Entity, Self::Storage];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::Entity => "entity",
+            Self::Storage => "storage",
+        }
+    }
+
+    const fn arg_name(self, role: Role) -> &'static str {
+        match (self, role) {
+            (Self::Block, Role::Target) => ARG_TARGET_POS,
+            (Self::Block, Role::Source) => ARG_SOURCE_POS,
+            (_, Role::Target) => ARG_TARGET,
+            (_, Role::Source) => ARG_SOURCE,
+        }
+    }
+
+    /// `block <pos>`, `entity <target>` or `storage <id>`, `build` adds what comes after it
+    fn node(
+        self,
+        role: Role,
+        build: impl FnOnce(NonLeafNodeBuilder) -> NonLeafNodeBuilder,
+    ) -> NonLeafNodeBuilder {
+        let name = self.arg_name(role);
+        let accessor = match self {
+            Self::Block => argument(name, BlockPosArgumentConsumer),
+            Self::Entity => argument(name, EntityArgumentConsumer),
+            Self::Storage => argument(name, ResourceLocationArgumentConsumer::new(false)),
+        };
+        literal(self.name()).with_child(build(accessor))
+    }
+
+    fn accessor<'a>(
+        self,
+        role: Role,
+        sender: &CommandSender,
+        server: &Server,
+        args: &'a ConsumedArgs,
+    ) -> Result<DataAccessor<'a>, CommandError> {
+        let name = self.arg_name(role);
+        Ok(match self {
+            Self::Block => DataAccessor::Block(
+                sender.world().unwrap_or_else(|| server.worlds[0].clone()),
+                BlockPosArgumentConsumer::find_arg(args, name)?,
+            ),
+            Self::Entity => DataAccessor::Entity(EntityArgumentConsumer::find_arg(args, name)?),
+            Self::Storage => {
+                DataAccessor::Storage(ResourceLocationArgumentConsumer::find_arg(args, name)?)
+            }
+        })
+    }
+}
+
+/// The NBT `/data` reads and writes, like vanilla's `DataAccessor`
+enum DataAccessor<'a> {
+    Block(Arc<World>, WorldPosition),
+    Entity(AnyEntity),
+    Storage(&'a str),
+}
+
+impl DataAccessor<'_> {
+    async fn data(&self, server: &Server) -> Result<NbtCompound, CommandError> {
+        match self {
+            Self::Block(world, pos) => world.get_block_entity_nbt(*pos).await.ok_or_else(|| {
+                CommandError::GeneralCommandIssue(
+                    "The target block is not a block entity".to_string(),
+                )
+            }),
+            Self::Entity(entity) => {
+                let mut nbt = NbtCompound::new();
+                entity.write_nbt(&mut nbt).await;
+                Ok(nbt)
+            }
+            Self::Storage(id) => Ok(server.command_storage.lock().await.get(id)),
+        }
+    }
+
+    async fn set_data(&self, server: &Server, nbt: NbtCompound) -> Result<(), CommandError> {
+        match self {
+            Self::Block(world, pos) => world.set_block_entity_nbt(*pos, nbt).await,
+            Self::Entity(AnyEntity::Player(_)) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "Unable to modify player data".to_string(),
+                ))
+            }
+            Self::Entity(entity) => entity.read_nbt(&nbt).await,
+            Self::Storage(id) => server.command_storage.lock().await.set(id, nbt),
+        }
+        Ok(())
+    }
+
+    const fn kind(&self) -> &'static str {
+        match self {
+            Self::Block(..) => "block",
+            Self::Entity(_) => "entity",
+            Self::Storage(_) => "storage",
+        }
+    }
+
+    /// The arguments naming the accessor in feedback
+    fn description(&self) -> Vec<TextComponent<'static>> {
+        match self {
+            Self::Block(_, WorldPosition(pos)) => [pos.x, pos.y, pos.z]
+                .map(|coordinate| TextComponent::text_string(coordinate.to_string()))
+                .to_vec(),
+            Self::Entity(entity) => vec![entity.display_name()],
+            Self::Storage(id) => vec![TextComponent::text_string((*id).to_string())],
+        }
+    }
+
+    fn query_message(&self, tag: &NbtTag) -> TextComponent<'static> {
+        let mut args = self.description();
+        args.push(TextComponent::text_string(to_snbt(tag)));
+        TextComponent::translate(format!("commands.data.{}.query", self.kind()), args)
+    }
+
+    fn modified_message(&self) -> TextComponent<'static> {
+        TextComponent::translate(
+            format!("commands.data.{}.modified", self.kind()),
+            self.description(),
+        )
+    }
+}
+
+/// The value of a number tag, `None` for other tags
+fn numeric(tag: &NbtTag) -> Option<f64> {
+    Some(match tag {
+        NbtTag::Byte(value) => f64::from(*value),
+        NbtTag::Short(value) => f64::from(*value),
+        NbtTag::Int(value) => f64::from(*value),
+        #[allow(clippy::cast_precision_loss)]
+        NbtTag::Long(value) => *value as f64,
+        NbtTag::Float(value) => f64::from(*value),
+        NbtTag::Double(value) => *value,
+        _ => return None,
+    })
+}
+
+struct GetExecutor(DataKind);
+
+#[async_trait]
+impl CommandExecutor for GetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let accessor = self.0.accessor(Role::Target, sender, server, args)?;
+        let nbt = accessor.data(server).await?;
+        if !args.contains_key(ARG_PATH) {
+            let message = accessor.query_message(&NbtTag::Compound(nbt));
+            sender.send_message(message).await;
+            return Ok(());
+        }
+
+        let path = NbtPathArgumentConsumer::find_arg(args, ARG_PATH)?;
+        let tag = match path.get(&nbt).as_slice() {
+            [] => return Err(nothing_found(&path)),
+            [tag] => tag.clone(),
+            _ => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "This argument accepts a single NBT value".to_string(),
+                ))
+            }
+        };
+        let Ok(scale) = scale_consumer().find_arg_default_name(args) else {
+            sender.send_message(accessor.query_message(&tag)).await;
+            return Ok(());
+        };
+        let scale = scale.map_err(|()| {
+            CommandError::GeneralCommandIssue("The scale is out of bounds".to_string())
+        })?;
+        let value = numeric(&tag).ok_or_else(|| {
+            CommandError::GeneralCommandIssue(format!(
+                "Can't get {path}; only numeric tags are allowed"
+            ))
+        })?;
+
+        let mut message_args = vec![TextComponent::text_string(path.to_string())];
+        message_args.extend(accessor.description());
+        message_args.push(TextComponent::text_string(scale.to_string()));
+        message_args.push(TextComponent::text_string(
+            ((value * scale).floor() as i32).to_string(),
+        ));
+        sender
+            .send_message(TextComponent::translate(
+                format!("commands.data.{}.get", accessor.kind()),
+                message_args,
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct MergeExecutor(DataKind);
+
+#[async_trait]
+impl CommandExecutor for MergeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let accessor = self.0.accessor(Role::Target, sender, server, args)?;
+        let nbt = NbtCompoundArgumentConsumer::find_arg(args, ARG_NBT)?;
+
+        let old = accessor.data(server).await?;
+        let mut merged = old.clone();
+        merged.merge(&nbt);
+        if merged == old {
+            return Err(unchanged());
+        }
+        accessor.set_data(server, merged).await?;
+
+        sender.send_message(accessor.modified_message()).await;
+        Ok(())
+    }
+}
+
+struct RemoveExecutor(DataKind);
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let accessor = self.0.accessor(Role::Target, sender, server, args)?;
+        let path = NbtPathArgumentConsumer::find_arg(args, ARG_PATH)?;
+
+        let mut nbt = accessor.data(server).await?;
+        if path.remove(&mut nbt) == 0 {
+            return Err(unchanged());
+        }
+        accessor.set_data(server, nbt).await?;
+
+        sender.send_message(accessor.modified_message()).await;
+        Ok(())
+    }
+}
+
+/// How `/data modify` changes the tags at the path
+#[derive(Clone, Copy)]
+enum ModifyOperation {
+    Set,
+    Merge,
+    Append,
+    Prepend,
+    Insert,
+}
+
+/// Inserts the values into the list or array at the index, like vanilla's
+/// `CollectionTag.addTag`. Negative indices count from the end, values of another type than
+/// the elements are skipped. Returns how many values were inserted
+fn insert_values(tag: &mut NbtTag, index: i32, values: &[NbtTag]) -> Result<usize, CommandError> {
+    let len = match tag {
+        NbtTag::List(list) => list.len(),
+        NbtTag::IntArray(array) => array.len(),
+        NbtTag::LongArray(array) => array.len(),
+        _ => {
+            return Err(CommandError::GeneralCommandIssue(
+                "Expected list, got a different tag".to_string(),
+            ))
+        }
+    };
+    let position = if index < 0 {
+        len as i64 + i64::from(index) + 1
+    } else {
+        i64::from(index)
+    };
+    if !(0..=len as i64).contains(&position) {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "Invalid list index: {index}"
+        )));
+    }
+
+    let mut position = position as usize;
+    let mut inserted = 0;
+    for value in values {
+        match (&mut *tag, value) {
+            (NbtTag::List(list), value)
+                if list.first().is_none_or(|first| {
+                    std::mem::discriminant(first) == std::mem::discriminant(value)
+                }) =>
+            {
+                list.insert(position, value.clone());
+            }
+            (NbtTag::IntArray(array), NbtTag::Int(value)) => array.insert(position, *value),
+            (NbtTag::LongArray(array), NbtTag::Long(value)) => array.insert(position, *value),
+            _ => continue,
+        }
+        position += 1;
+        inserted += 1;
+    }
+    Ok(inserted)
+}
+
+impl ModifyOperation {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Set => "set",
+            Self::Merge => "merge",
+            Self::Append => "append",
+            Self::Prepend => "prepend",
+            Self::Insert => "insert",
+        }
+    }
+
+    /// Changes the tags at the path with the values, like vanilla's `DataCommands.ModifyOperation`.
+    /// Returns how many tags changed
+    fn apply(
+        self,
+        args: &ConsumedArgs,
+        path: &NbtPath,
+        nbt: &mut NbtCompound,
+        values: &[NbtTag],
+    ) -> Result<usize, CommandError> {
+        let mut error = None;
+        let changed = match self {
+            Self::Set => path.set(nbt, values.last().expect("There is always a value")),
+            Self::Merge => {
+                let expected_object = || {
+                    CommandError::GeneralCommandIssue(
+                        "Expected object, got a different tag".to_string(),
+                    )
+                };
+                let sources = values
+                    .iter()
+                    .map(|value| value.extract_compound().ok_or_else(expected_object))
+                    .collect::<Result<Vec<_>, _>>()?;
+                path.modify(
+                    nbt,
+                    || NbtTag::Compound(NbtCompound::new()),
+                    |tag| {
+                        let NbtTag::Compound(compound) = tag else {
+                            error = Some(expected_object());
+                            return 0;
+                        };
+                        let old = compound.clone();
+                        for source in &sources {
+                            compound.merge(source);
+                        }
+                        usize::from(*compound != old)
+                    },
+                )
+            }
+            Self::Append | Self::Prepend | Self::Insert => {
+                let index = match self {
+                    Self::Append => -1,
+                    Self::Prepend => 0,
+                    _ => index_consumer()
+                        .find_arg_default_name(args)?
+                        .map_err(|()| {
+                            CommandError::GeneralCommandIssue(
+                                "The index is out of bounds".to_string(),
+                            )
+                        })?,
+                };
+                path.modify(
+                    nbt,
+                    || NbtTag::List(Vec::new()),
+                    |tag| match insert_values(tag, index, values) {
+                        Ok(inserted) => inserted,
+                        Err(err) => {
+                            error = Some(err);
+                            0
+                        }
+                    },
+                )
+            }
+        };
+        error.map_or(Ok(changed), Err)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ModifyExecutor {
+    target: DataKind,
+    operation: ModifyOperation,
+    /// Where `from` reads the values, `None` for `value`
+    source: Option<DataKind>,
+}
+
+impl ModifyExecutor {
+    /// The values the target is modified with
+    async fn values(
+        self,
+        sender: &CommandSender<'_>,
+        server: &Server,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<Vec<NbtTag>, CommandError> {
+        let Some(source) = self.source else {
+            return Ok(vec![NbtTagArgumentConsumer::find_arg(args, ARG_VALUE)?]);
+        };
+        let nbt = source
+            .accessor(Role::Source, sender, server, args)?
+            .data(server)
+            .await?;
+        if !args.contains_key(ARG_SOURCE_PATH) {
+            return Ok(vec![NbtTag::Compound(nbt)]);
+        }
+        let path = NbtPathArgumentConsumer::find_arg(args, ARG_SOURCE_PATH)?;
+        let values = path.get(&nbt);
+        if values.is_empty() {
+            return Err(nothing_found(&path));
+        }
+        Ok(values)
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ModifyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let accessor = self.target.accessor(Role::Target, sender, server, args)?;
+        let path = NbtPathArgumentConsumer::find_arg(args, ARG_TARGET_PATH)?;
+        let values = self.values(sender, server, args).await?;
+
+        let mut nbt = accessor.data(server).await?;
+        if self.operation.apply(args, &path, &mut nbt, &values)? == 0 {
+            return Err(unchanged());
+        }
+        accessor.set_data(server, nbt).await?;
+
+        sender.send_message(accessor.modified_message()).await;
+        Ok(())
+    }
+}
+
+/// `from <source> [sourcePath]` and `value <value>` after the operation
+fn with_sources(node: NonLeafNodeBuilder, executor: ModifyExecutor) -> NonLeafNodeBuilder {
+    let mut from = literal("from");
+    for source in DataKind::ALL {
+        let executor = ModifyExecutor {
+            source: Some(source),
+            ..executor
+        };
+        from = from.with_child(source.node(Role::Source, |accessor| {
+            accessor
+                .execute(executor)
+                .with_child(argument(ARG_SOURCE_PATH, NbtPathArgumentConsumer).execute(executor))
+        }));
+    }
+    node.with_child(from)
+        .with_child(literal("value").with_child(
+            argument(ARG_VALUE, NbtTagArgumentConsumer).execute(ModifyExecutor {
+                source: None,
+                ..executor
+            }),
+        ))
+}
+
+fn modify_node(target: DataKind) -> NonLeafNodeBuilder {
+    target.node(Role::Target, |accessor| {
+        let mut path = argument(ARG_TARGET_PATH, NbtPathArgumentConsumer);
+        for operation in [
+            ModifyOperation::Set,
+            ModifyOperation::Merge,
+            ModifyOperation::Append,
+            ModifyOperation::Prepend,
+            ModifyOperation::Insert,
+        ] {
+            let executor = ModifyExecutor {
+                target,
+                operation,
+                source: None,
+            };
+            let node = match operation {
+                ModifyOperation::Insert => literal(operation.name()).with_child(with_sources(
+                    argument_default_name(index_consumer()),
+                    executor,
+                )),
+                _ => with_sources(literal(operation.name()), executor),
+            };
+            path = path.with_child(node);
+        }
+        accessor.with_child(path)
+    })
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut get = literal("get");
+    let mut merge = literal("merge");
+    let mut modify = literal("modify");
+    let mut remove = literal("remove");
+    for kind in DataKind::ALL {
+        get = get.with_child(kind.node(Role::Target, |accessor| {
+            accessor.execute(GetExecutor(kind)).with_child(
+                argument(ARG_PATH, NbtPathArgumentConsumer)
+                    .execute(GetExecutor(kind))
+                    .with_child(argument_default_name(scale_consumer()).execute(GetExecutor(kind))),
+            )
+        }));
+        merge = merge.with_child(kind.node(Role::Target, |accessor| {
+            accessor.with_child(
+                argument(ARG_NBT, NbtCompoundArgumentConsumer).execute(MergeExecutor(kind)),
+            )
+        }));
+        modify = modify.with_child(modify_node(kind));
+        remove = remove.with_child(kind.node(Role::Target, |accessor| {
+            accessor.with_child(
+                argument(ARG_PATH, NbtPathArgumentConsumer).execute(RemoveExecutor(kind)),
+            )
+        }));
+    }
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(get)
+        .with_child(merge)
+        .with_child(modify)
+        .with_child(remove)
+}
@@ -0,0 +1,208 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::arg_datapack::DatapackArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["datapack"];
+
+const DESCRIPTION: &str = "Lists, enables or disables the datapacks of the world.";
+
+const ARG_NAME: &str = "name";
+const ARG_EXISTING: &str = "existing";
+
+/// The packs as vanilla lists them, like `[vanilla], [file/pack]`
+fn pack_list(packs: &[String]) -> TextComponent<'static> {
+    TextComponent::text_string(
+        packs
+            .iter()
+            .map(|pack| format!("[{pack}]"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+struct ListExecutor {
+    available: bool,
+    enabled: bool,
+}
+
+impl ListExecutor {
+    async fn list(sender: &CommandSender<'_>, kind: &str, packs: &[String]) {
+        let message = if packs.is_empty() {
+            TextComponent::translate(format!("commands.datapack.list.{kind}.none"), vec![])
+        } else {
+            TextComponent::translate(
+                format!("commands.datapack.list.{kind}.success"),
+                vec![
+                    TextComponent::text_string(packs.len().to_string()),
+                    pack_list(packs),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = &server.worlds[0];
+        let enabled = world.data_packs.lock().await.enabled.clone();
+        if self.available {
+            let available: Vec<_> = world
+                .level
+                .datapacks
+                .available()
+                .into_iter()
+                .filter(|pack| !enabled.contains(pack))
+                .collect();
+            Self::list(sender, "available", &available).await;
+        }
+        if self.enabled {
+            Self::list(sender, "enabled", &enabled).await;
+        }
+        Ok(())
+    }
+}
+
+/// Where an enabled pack goes in the load order, later packs override earlier ones
+#[derive(Clone, Copy)]
+enum Order {
+    First,
+    Last,
+    Before,
+    After,
+}
+
+struct EnableExecutor(Order);
+
+#[async_trait]
+impl CommandExecutor for EnableExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let pack = DatapackArgumentConsumer::find_arg(args, ARG_NAME)?;
+        let world = &server.worlds[0];
+        if !world.level.datapacks.available().iter().any(|p| p == pack) {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown data pack '{pack}'"
+            )));
+        }
+        let mut data_packs = world.data_packs.lock().await;
+        if data_packs.enabled.iter().any(|p| p == pack) {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Pack '{pack}' is already enabled!"
+            )));
+        }
+        let index = match self.0 {
+            Order::First => 0,
+            Order::Last => data_packs.enabled.len(),
+            Order::Before | Order::After => {
+                let existing = DatapackArgumentConsumer::find_arg(args, ARG_EXISTING)?;
+                let Some(index) = data_packs.enabled.iter().position(|p| p == existing) else {
+                    return Err(CommandError::GeneralCommandIssue(format!(
+                        "Pack '{existing}' is not enabled!"
+                    )));
+                };
+                index + usize::from(matches!(self.0, Order::After))
+            }
+        };
+        data_packs.enabled.insert(index, pack.to_string());
+        data_packs.disabled.retain(|p| p != pack);
+        drop(data_packs);
+        sender
+            .send_message(TextComponent::translate(
+                "commands.datapack.modify.enable",
+                vec![pack_list(&[pack.to_string()])],
+            ))
+            .await;
+        server.reload_datapacks().await;
+        Ok(())
+    }
+}
+
+struct DisableExecutor;
+
+#[async_trait]
+impl CommandExecutor for DisableExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let pack = DatapackArgumentConsumer::find_arg(args, ARG_NAME)?;
+        let mut data_packs = server.worlds[0].data_packs.lock().await;
+        // The built in pack is always needed
+        let Some(index) = data_packs
+            .enabled
+            .iter()
+            .position(|p| p == pack && p != pumpkin_world::datapack::VANILLA_PACK)
+        else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Pack '{pack}' is not enabled!"
+            )));
+        };
+        data_packs.enabled.remove(index);
+        data_packs.disabled.push(pack.to_string());
+        drop(data_packs);
+        sender
+            .send_message(TextComponent::translate(
+                "commands.datapack.modify.disable",
+                vec![pack_list(&[pack.to_string()])],
+            ))
+            .await;
+        server.reload_datapacks().await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let enabled = || DatapackArgumentConsumer { enabled: true };
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(
+            literal("list")
+                .execute(ListExecutor {
+                    available: true,
+                    enabled: true,
+                })
+                .with_child(literal("available").execute(ListExecutor {
+                    available: true,
+                    enabled: false,
+                }))
+                .with_child(literal("enabled").execute(ListExecutor {
+                    available: false,
+                    enabled: true,
+                })),
+        )
+        .with_child(
+            literal("enable").with_child(
+                argument(ARG_NAME, DatapackArgumentConsumer { enabled: false })
+                    .execute(EnableExecutor(Order::Last))
+                    .with_child(literal("first").execute(EnableExecutor(Order::First)))
+                    .with_child(literal("last").execute(EnableExecutor(Order::Last)))
+                    .with_child(literal("before").with_child(
+                        argument(ARG_EXISTING, enabled()).execute(EnableExecutor(Order::Before)),
+                    ))
+                    .with_child(literal("after").with_child(
+                        argument(ARG_EXISTING, enabled()).execute(EnableExecutor(Order::After)),
+                    )),
+            ),
+        )
+        .with_child(
+            literal("disable").with_child(argument(ARG_NAME, enabled()).execute(DisableExecutor)),
+        )
+}
@@ -0,0 +1,73 @@
+use crate::{
+    command::{
+        args::{arg_players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
+        tree::CommandTree,
+        tree_builder::argument,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{op_data::OPERATOR_CONFIG, SaveJSONConfiguration},
+};
+use async_trait::async_trait;
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::TextComponent;
+
+const NAMES: [&str; 1] = ["deop"];
+const DESCRIPTION: &str = "Revokes operator status from a player.";
+const ARG_TARGET: &str = "player";
+
+struct DeopExecutor;
+
+#[async_trait]
+impl CommandExecutor for DeopExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let mut config = OPERATOR_CONFIG.write().await;
+
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
+
+        let mut changed = 0;
+        for player in targets {
+            let Some(index) = config
+                .ops
+                .iter()
+                .position(|o| o.uuid == player.gameprofile.id)
+            else {
+                continue;
+            };
+            config.ops.remove(index);
+            config.save();
+            changed += 1;
+
+            player
+                .set_permission_lvl(
+                    ADVANCED_CONFIG.commands.default_op_level,
+                    &server.command_dispatcher,
+                )
+                .await;
+
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.deop.success",
+                    vec![TextComponent::text_string(player.gameprofile.name.clone())],
+                ))
+                .await;
+        }
+
+        if changed == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. The player is not an operator".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(argument(ARG_TARGET, PlayersArgumentConsumer).execute(DeopExecutor))
+}
@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::{
+    text::{
+        color::{Color, NamedColor},
+        TextComponent,
+    },
+    Difficulty,
+};
+
+use crate::{
+    command::{
+        tree::CommandTree, tree_builder::literal, CommandError, CommandExecutor, CommandSender,
+        ConsumedArgs,
+    },
+    server::Server,
+    world::World,
+};
+
+const NAMES: [&str; 1] = ["difficulty"];
+
+const DESCRIPTION: &str = "Sets the difficulty level.";
+
+/// The difficulty changes on the sender's world, the console changes the first world
+fn difficulty_world(sender: &CommandSender<'_>, server: &Server) -> Arc<World> {
+    sender.world().unwrap_or_else(|| {
+        server
+            .worlds
+            .first()
+            .cloned()
+            .expect("There should always be at least one world")
+    })
+}
+
+struct QueryExecutor;
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let difficulty = difficulty_world(sender, server).difficulty.load();
+        sender
+            .send_message(TextComponent::text(&format!(
+                "The difficulty is {difficulty:?}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetExecutor(Difficulty);
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let difficulty = self.0;
+        let world = difficulty_world(sender, server);
+        if world.difficulty.load() == difficulty {
+            sender
+                .send_message(
+                    TextComponent::text(&format!(
+                        "The difficulty did not change; it is already set to {difficulty:?}"
+                    ))
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
+        world.set_difficulty(difficulty).await;
+        sender
+            .send_message(TextComponent::text(&format!(
+                "The difficulty has been set to {difficulty:?}"
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    [
+        ("peaceful", Difficulty::Peaceful),
+        ("easy", Difficulty::Easy),
+        ("normal", Difficulty::Normal),
+        ("hard", Difficulty::Hard),
+    ]
+    .into_iter()
+    .fold(
+        CommandTree::new(NAMES, DESCRIPTION).execute(QueryExecutor),
+        |tree, (name, difficulty)| tree.with_child(literal(name).execute(SetExecutor(difficulty))),
+    )
+}
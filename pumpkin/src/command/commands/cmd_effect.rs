@@ -0,0 +1,266 @@
+use async_trait::async_trait;
+use pumpkin_core::text::color::{Color, NamedColor};
+use pumpkin_core::text::TextComponent;
+use pumpkin_world::effect::{EffectInstance, StatusEffect, INFINITE_DURATION};
+
+use crate::command::args::arg_bool::BoolArgConsumer;
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_effect::EffectArgumentConsumer;
+use crate::command::args::arg_entities::EntitiesArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{
+    argument, argument_default_name, literal, require, NonLeafNodeBuilder,
+};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::any_entity::AnyEntity;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["effect"];
+
+const DESCRIPTION: &str = "Gives or takes status effects.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_EFFECT: &str = "effect";
+const ARG_HIDE_PARTICLES: &str = "hideParticles";
+
+/// Effects last this long unless the command says otherwise
+const DEFAULT_SECONDS: i32 = 30;
+
+fn seconds_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("seconds")
+        .min(1)
+        .max(1_000_000)
+}
+
+fn amplifier_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("amplifier")
+        .min(0)
+        .max(255)
+}
+
+/// The name of the effect in messages, like "Night Vision"
+fn effect_name(effect: StatusEffect) -> TextComponent<'static> {
+    TextComponent::translate(format!("effect.minecraft.{}", effect.name()), vec![])
+}
+
+/// The message about the targets, `single` or `multiple` is added to the key. `args` come first,
+/// the name of the only target or how many there were after them
+fn targets_message(
+    key: &str,
+    mut args: Vec<TextComponent<'static>>,
+    targets: &[&AnyEntity],
+) -> TextComponent<'static> {
+    let (kind, target) = match targets {
+        [target] => ("single", target.display_name()),
+        _ => (
+            "multiple",
+            TextComponent::text_string(targets.len().to_string()),
+        ),
+    };
+    args.push(target);
+    TextComponent::translate(format!("{key}.success.{kind}"), args)
+}
+
+async fn send_error(sender: &mut CommandSender<'_>, message: &str) {
+    sender
+        .send_message(TextComponent::text(message).color(Color::Named(NamedColor::Red)))
+        .await;
+}
+
+struct GiveExecutor {
+    infinite: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for GiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let effect = EffectArgumentConsumer::find_arg(args, ARG_EFFECT)?;
+        let duration = if self.infinite {
+            INFINITE_DURATION
+        } else {
+            match seconds_consumer().find_arg_default_name(args) {
+                // Instant effects apply once unless a duration is given
+                Err(_) if effect.is_instant() => 1,
+                Err(_) => DEFAULT_SECONDS * 20,
+                Ok(Ok(seconds)) => seconds * 20,
+                Ok(Err(())) => {
+                    send_error(sender, "Effect duration is too large or too small.").await;
+                    return Ok(());
+                }
+            }
+        };
+        let amplifier = match amplifier_consumer().find_arg_default_name(args) {
+            Err(_) => 0,
+            Ok(Ok(amplifier)) => amplifier as u8,
+            Ok(Err(())) => {
+                send_error(sender, "Effect amplifier is too large or too small.").await;
+                return Ok(());
+            }
+        };
+        let hide_particles = BoolArgConsumer::find_arg(args, ARG_HIDE_PARTICLES).unwrap_or(false);
+
+        let instance = EffectInstance {
+            show_particles: !hide_particles,
+            ..EffectInstance::new(effect, duration, amplifier)
+        };
+        let mut affected = Vec::new();
+        for target in targets {
+            if target.add_effect(instance).await {
+                affected.push(target);
+            }
+        }
+
+        if affected.is_empty() {
+            send_error(
+                sender,
+                "Unable to apply this effect (target is either immune to effects, or has something stronger)",
+            )
+            .await;
+            return Ok(());
+        }
+        sender
+            .send_message(targets_message(
+                "commands.effect.give",
+                vec![effect_name(effect)],
+                &affected,
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+/// Takes every effect or just the one named from the targets
+async fn clear(
+    sender: &mut CommandSender<'_>,
+    targets: &[AnyEntity],
+    effect: Option<StatusEffect>,
+) {
+    let mut affected = Vec::new();
+    for target in targets {
+        let removed = match effect {
+            Some(effect) => target.remove_effect(effect).await,
+            None => !target.clear_effects().await.is_empty(),
+        };
+        if removed {
+            affected.push(target);
+        }
+    }
+
+    if affected.is_empty() {
+        let message = if effect.is_some() {
+            "Target doesn't have the requested effect"
+        } else {
+            "Target has no effects to remove"
+        };
+        send_error(sender, message).await;
+        return;
+    }
+    let message = effect.map_or_else(
+        || targets_message("commands.effect.clear.everything", Vec::new(), &affected),
+        |effect| {
+            targets_message(
+                "commands.effect.clear.specific",
+                vec![effect_name(effect)],
+                &affected,
+            )
+        },
+    );
+    sender.send_message(message).await;
+}
+
+struct ClearExecutor {
+    single_effect: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for ClearExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let effect = if self.single_effect {
+            Some(EffectArgumentConsumer::find_arg(args, ARG_EFFECT)?)
+        } else {
+            None
+        };
+        clear(sender, targets, effect).await;
+        Ok(())
+    }
+}
+
+struct ClearSelfExecutor;
+
+#[async_trait]
+impl CommandExecutor for ClearSelfExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = sender.executor().ok_or(CommandError::InvalidRequirement)?;
+        clear(sender, &[target], None).await;
+        Ok(())
+    }
+}
+
+/// The optional `[amplifier] [hideParticles]` after the duration
+fn give_options(infinite: bool) -> NonLeafNodeBuilder {
+    argument_default_name(amplifier_consumer())
+        .execute(GiveExecutor { infinite })
+        .with_child(
+            argument(ARG_HIDE_PARTICLES, BoolArgConsumer).execute(GiveExecutor { infinite }),
+        )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(
+            literal("give").with_child(
+                argument(ARG_TARGETS, EntitiesArgumentConsumer).with_child(
+                    argument(ARG_EFFECT, EffectArgumentConsumer)
+                        .execute(GiveExecutor { infinite: false })
+                        .with_child(
+                            argument_default_name(seconds_consumer())
+                                .execute(GiveExecutor { infinite: false })
+                                .with_child(give_options(false)),
+                        )
+                        .with_child(
+                            literal("infinite")
+                                .execute(GiveExecutor { infinite: true })
+                                .with_child(give_options(true)),
+                        ),
+                ),
+            ),
+        )
+        .with_child(
+            literal("clear")
+                .with_child(
+                    require(|sender| sender.executor().is_some()).execute(ClearSelfExecutor),
+                )
+                .with_child(
+                    argument(ARG_TARGETS, EntitiesArgumentConsumer)
+                        .execute(ClearExecutor {
+                            single_effect: false,
+                        })
+                        .with_child(argument(ARG_EFFECT, EffectArgumentConsumer).execute(
+                            ClearExecutor {
+                                single_effect: true,
+                            },
+                        )),
+                ),
+        )
+}
@@ -0,0 +1,386 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            arg_block_predicate::BlockPredicateArgumentConsumer,
+            arg_entities::EntitiesArgumentConsumer, arg_entity::EntityArgumentConsumer,
+            arg_objective::ObjectiveArgumentConsumer, arg_position_3d::Position3DArgumentConsumer,
+            arg_position_block::BlockPosArgumentConsumer, arg_rotation::RotationArgumentConsumer,
+            arg_simple::SimpleArgConsumer, entity_selector::Bounds, Arg, ConsumedArgs, FindArg,
+        },
+        dispatcher::CommandError,
+        tree::{CommandTree, RedirectTarget},
+        tree_builder::{argument, literal, NonLeafNodeBuilder},
+        CommandExecutor, CommandModifier, CommandSender, ExecutionContext,
+    },
+    entity::any_entity::AnyEntity,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["execute"];
+
+const DESCRIPTION: &str =
+    "Runs a command as other entities, at other places or only if conditions are met.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_POS: &str = "pos";
+const ARG_ROTATION: &str = "rot";
+const ARG_DIMENSION: &str = "dimension";
+const ARG_BLOCK: &str = "block";
+const ARG_TARGET: &str = "target";
+const ARG_TARGET_OBJECTIVE: &str = "targetObjective";
+const ARG_SOURCE: &str = "source";
+const ARG_SOURCE_OBJECTIVE: &str = "sourceObjective";
+const ARG_RANGE: &str = "range";
+
+/// The entities of a selector, no entities aren't an error unlike for commands
+fn entities(args: &ConsumedArgs, name: &str) -> Result<Vec<AnyEntity>, CommandError> {
+    match args.get(name) {
+        Some(Arg::Entities(Ok(entities))) => Ok(entities.clone()),
+        Some(Arg::Entities(Err(error))) => Err(CommandError::GeneralCommandIssue(error.clone())),
+        _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+    }
+}
+
+/// Which part of the context a subcommand changes
+#[derive(Clone, Copy)]
+enum Modifier {
+    /// `as`, runs as each of the entities
+    As,
+    /// `at`, runs at the position, rotation and world of each of the entities
+    At,
+    /// `positioned <pos>`
+    Positioned,
+    /// `positioned as`, runs at the position of each of the entities
+    PositionedAs,
+    /// `rotated <rot>`
+    Rotated,
+    /// `rotated as`, runs with the rotation of each of the entities
+    RotatedAs,
+    /// `in <dimension>`
+    In,
+    /// `run`, runs the command as it is
+    Run,
+}
+
+#[async_trait]
+impl CommandModifier for Modifier {
+    async fn modify<'a>(
+        &self,
+        sender: &CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<Vec<CommandSender<'a>>, CommandError> {
+        let context = ExecutionContext::of(sender, server).await;
+        let contexts = match self {
+            Self::As => entities(args, ARG_TARGETS)?
+                .into_iter()
+                .map(|entity| ExecutionContext {
+                    executor: Some(entity),
+                    ..context.clone()
+                })
+                .collect(),
+            Self::At => entities(args, ARG_TARGETS)?
+                .iter()
+                .map(|entity| {
+                    let base = entity.entity();
+                    ExecutionContext {
+                        position: base.pos.load(),
+                        rotation: (base.yaw.load(), base.pitch.load()),
+                        world: base.world(),
+                        ..context.clone()
+                    }
+                })
+                .collect(),
+            Self::Positioned => vec![ExecutionContext {
+                position: Position3DArgumentConsumer::find_arg(args, ARG_POS)?,
+                ..context
+            }],
+            Self::PositionedAs => entities(args, ARG_TARGETS)?
+                .iter()
+                .map(|entity| ExecutionContext {
+                    position: entity.entity().pos.load(),
+                    ..context.clone()
+                })
+                .collect(),
+            Self::Rotated => vec![ExecutionContext {
+                rotation: RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?,
+                ..context
+            }],
+            Self::RotatedAs => entities(args, ARG_TARGETS)?
+                .iter()
+                .map(|entity| {
+                    let base = entity.entity();
+                    ExecutionContext {
+                        rotation: (base.yaw.load(), base.pitch.load()),
+                        ..context.clone()
+                    }
+                })
+                .collect(),
+            Self::In => {
+                let dimension = SimpleArgConsumer::find_arg(args, ARG_DIMENSION)?;
+                let Some(world) = server.get_world(dimension) else {
+                    return Err(CommandError::GeneralCommandIssue(format!(
+                        "Unknown dimension {dimension}"
+                    )));
+                };
+                // Like vanilla, positions keep their place relative to the scale of the dimensions
+                let scale = context.world.coordinate_scale() / world.coordinate_scale();
+                vec![ExecutionContext {
+                    position: context.position.multiply(scale, 1.0, scale),
+                    world,
+                    ..context
+                }]
+            }
+            Self::Run => return Ok(vec![sender.clone()]),
+        };
+        Ok(contexts
+            .into_iter()
+            .map(|context| CommandSender::Execute(Box::new(context)))
+            .collect())
+    }
+}
+
+/// Compares the score of the target with the score of the source
+type ScoreComparison = fn(&i32, &i32) -> bool;
+
+/// What `if` and `unless` test
+#[derive(Clone, Copy)]
+enum Condition {
+    Block,
+    Entity,
+    Score(ScoreComparison),
+    /// Whether the score of the target is in the range
+    ScoreMatches,
+}
+
+async fn score(server: &Server, entity: &AnyEntity, objective: &str) -> Option<i32> {
+    let holder = entity.score_holder()?;
+    server.scoreboard.lock().await.score(&holder, objective)
+}
+
+impl Condition {
+    /// How many times the condition matches, only entity conditions can match more than once
+    async fn test(
+        self,
+        sender: &CommandSender<'_>,
+        server: &Server,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<usize, CommandError> {
+        match self {
+            Self::Block => {
+                let position = BlockPosArgumentConsumer::find_arg(args, ARG_POS)?;
+                let predicate = BlockPredicateArgumentConsumer::find_arg(args, ARG_BLOCK)?;
+                let context = ExecutionContext::of(sender, server).await;
+                let actual = context.world.get_block_state_id(position).await;
+                Ok(usize::from(
+                    actual.is_ok_and(|actual| predicate.matches(actual)),
+                ))
+            }
+            Self::Entity => Ok(entities(args, ARG_TARGETS)?.len()),
+            Self::Score(compare) => {
+                let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+                let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_TARGET_OBJECTIVE)?;
+                let source = EntityArgumentConsumer::find_arg(args, ARG_SOURCE)?;
+                let source_objective =
+                    ObjectiveArgumentConsumer::find_arg(args, ARG_SOURCE_OBJECTIVE)?;
+                let target_score = score(server, &target, objective).await;
+                let source_score = score(server, &source, source_objective).await;
+                Ok(usize::from(
+                    target_score
+                        .zip(source_score)
+                        .is_some_and(|(target, source)| compare(&target, &source)),
+                ))
+            }
+            Self::ScoreMatches => {
+                let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+                let objective = ObjectiveArgumentConsumer::find_arg(args, ARG_TARGET_OBJECTIVE)?;
+                let range = Bounds::<i32>::parse(SimpleArgConsumer::find_arg(args, ARG_RANGE)?)
+                    .map_err(CommandError::GeneralCommandIssue)?;
+                Ok(usize::from(
+                    score(server, &target, objective)
+                        .await
+                        .is_some_and(|score| range.contains(score)),
+                ))
+            }
+        }
+    }
+}
+
+/// `if` and `unless` in the middle of the chain, drops the sender if the test fails
+struct ConditionModifier {
+    is_if: bool,
+    condition: Condition,
+}
+
+#[async_trait]
+impl CommandModifier for ConditionModifier {
+    async fn modify<'a>(
+        &self,
+        sender: &CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<Vec<CommandSender<'a>>, CommandError> {
+        let matched = self.condition.test(sender, server, args).await? > 0;
+        Ok(if matched == self.is_if {
+            vec![sender.clone()]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+/// `if` and `unless` at the end of the chain, tells the sender whether the test passed. Entity
+/// conditions report how many entities matched
+struct ConditionExecutor {
+    is_if: bool,
+    condition: Condition,
+}
+
+#[async_trait]
+impl CommandExecutor for ConditionExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        self.execute_counted(sender, server, args).await.map(|_| ())
+    }
+
+    async fn execute_counted<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<i32, CommandError> {
+        let count = self.condition.test(sender, server, args).await?;
+        let counted = matches!(self.condition, Condition::Entity);
+        if (count > 0) != self.is_if {
+            return Err(CommandError::GeneralCommandIssue(
+                if counted && !self.is_if {
+                    format!("Test failed, count: {count}")
+                } else {
+                    "Test failed".to_string()
+                },
+            ));
+        }
+        if counted && self.is_if {
+            sender
+                .send_message(TextComponent::text_string(format!(
+                    "Test passed, count: {count}"
+                )))
+                .await;
+            Ok(i32::try_from(count).unwrap_or(i32::MAX))
+        } else {
+            sender
+                .send_message(TextComponent::text("Test passed"))
+                .await;
+            Ok(1)
+        }
+    }
+}
+
+/// Ends a condition, either reporting the result or continuing the chain
+fn condition_end(
+    node: NonLeafNodeBuilder,
+    is_if: bool,
+    condition: Condition,
+) -> NonLeafNodeBuilder {
+    node.execute(ConditionExecutor { is_if, condition })
+        .redirect(
+            RedirectTarget::Command,
+            ConditionModifier { is_if, condition },
+        )
+}
+
+/// The conditions of `if` and `unless`
+fn conditions(node: NonLeafNodeBuilder, is_if: bool) -> NonLeafNodeBuilder {
+    let comparisons: [(&str, ScoreComparison); 5] = [
+        ("<", i32::lt),
+        ("<=", i32::le),
+        ("=", i32::eq),
+        (">", i32::gt),
+        (">=", i32::ge),
+    ];
+    let mut objective = argument(ARG_TARGET_OBJECTIVE, ObjectiveArgumentConsumer).with_child(
+        literal("matches").with_child(condition_end(
+            argument(ARG_RANGE, SimpleArgConsumer),
+            is_if,
+            Condition::ScoreMatches,
+        )),
+    );
+    for (operation, compare) in comparisons {
+        objective = objective.with_child(literal(operation).with_child(
+            argument(ARG_SOURCE, EntityArgumentConsumer).with_child(condition_end(
+                argument(ARG_SOURCE_OBJECTIVE, ObjectiveArgumentConsumer),
+                is_if,
+                Condition::Score(compare),
+            )),
+        ));
+    }
+
+    node.with_child(literal("block").with_child(
+        argument(ARG_POS, BlockPosArgumentConsumer).with_child(condition_end(
+            argument(ARG_BLOCK, BlockPredicateArgumentConsumer),
+            is_if,
+            Condition::Block,
+        )),
+    ))
+    .with_child(literal("entity").with_child(condition_end(
+        argument(ARG_TARGETS, EntitiesArgumentConsumer),
+        is_if,
+        Condition::Entity,
+    )))
+    .with_child(
+        literal("score")
+            .with_child(argument(ARG_TARGET, EntityArgumentConsumer).with_child(objective)),
+    )
+}
+
+/// Like vanilla, every subcommand parses the rest of the command again from the start, as and
+/// relative to each of the senders it turns the sender into
+pub fn init_command_tree() -> CommandTree {
+    let redirect =
+        |node: NonLeafNodeBuilder, modifier| node.redirect(RedirectTarget::Command, modifier);
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(literal("as").with_child(redirect(
+            argument(ARG_TARGETS, EntitiesArgumentConsumer),
+            Modifier::As,
+        )))
+        .with_child(literal("at").with_child(redirect(
+            argument(ARG_TARGETS, EntitiesArgumentConsumer),
+            Modifier::At,
+        )))
+        .with_child(
+            literal("positioned")
+                .with_child(literal("as").with_child(redirect(
+                    argument(ARG_TARGETS, EntitiesArgumentConsumer),
+                    Modifier::PositionedAs,
+                )))
+                .with_child(redirect(
+                    argument(ARG_POS, Position3DArgumentConsumer),
+                    Modifier::Positioned,
+                )),
+        )
+        .with_child(
+            literal("rotated")
+                .with_child(literal("as").with_child(redirect(
+                    argument(ARG_TARGETS, EntitiesArgumentConsumer),
+                    Modifier::RotatedAs,
+                )))
+                .with_child(redirect(
+                    argument(ARG_ROTATION, RotationArgumentConsumer),
+                    Modifier::Rotated,
+                )),
+        )
+        .with_child(literal("in").with_child(redirect(
+            argument(ARG_DIMENSION, SimpleArgConsumer),
+            Modifier::In,
+        )))
+        .with_child(conditions(literal("if"), true))
+        .with_child(conditions(literal("unless"), false))
+        .with_child(literal("run").redirect(RedirectTarget::Commands, Modifier::Run))
+}
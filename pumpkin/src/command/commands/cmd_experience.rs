@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use pumpkin_core::text::color::{Color, NamedColor};
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_entity::EntityArgumentConsumer;
+use crate::command::args::arg_players::PlayersArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 2] = ["experience", "xp"];
+
+const DESCRIPTION: &str = "Adds, sets or queries the experience of players.";
+
+const ARG_TARGET: &str = "target";
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Points,
+    Levels,
+}
+
+impl Unit {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Points => "points",
+            Self::Levels => "levels",
+        }
+    }
+}
+
+fn amount_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("amount")
+}
+
+fn set_amount_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("amount").min(0)
+}
+
+/// The amount, `None` after telling the sender it is out of bounds
+async fn find_amount(
+    consumer: &BoundedNumArgumentConsumer<i32>,
+    sender: &mut CommandSender<'_>,
+    args: &ConsumedArgs<'_>,
+) -> Result<Option<i32>, CommandError> {
+    if let Ok(amount) = consumer.find_arg_default_name(args)? {
+        return Ok(Some(amount));
+    }
+    sender
+        .send_message(
+            TextComponent::text("Experience amount is too large or too small.")
+                .color(Color::Named(NamedColor::Red)),
+        )
+        .await;
+    Ok(None)
+}
+
+struct AddExecutor(Unit);
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let Some(amount) = find_amount(&amount_consumer(), sender, args).await? else {
+            return Ok(());
+        };
+        for target in targets {
+            match self.0 {
+                Unit::Points => target.add_experience_points(amount).await,
+                Unit::Levels => target.add_experience_levels(amount).await,
+            }
+        }
+
+        let unit = self.0.name();
+        sender
+            .send_message(TextComponent::text_string(match targets {
+                [target] => format!(
+                    "Gave {amount} experience {unit} to {}",
+                    target.gameprofile.name
+                ),
+                _ => format!(
+                    "Gave {amount} experience {unit} to {} players",
+                    targets.len()
+                ),
+            }))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetExecutor(Unit);
+
+#[async_trait]
+impl CommandExecutor for SetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
+        let Some(amount) = find_amount(&set_amount_consumer(), sender, args).await? else {
+            return Ok(());
+        };
+        let mut changed = 0;
+        for target in targets {
+            match self.0 {
+                Unit::Points => {
+                    if target.set_experience_points(amount).await {
+                        changed += 1;
+                    }
+                }
+                Unit::Levels => {
+                    target.set_experience_levels(amount).await;
+                    changed += 1;
+                }
+            }
+        }
+
+        if changed == 0 {
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "Cannot set experience points above the maximum points for the player's current level",
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+        let unit = self.0.name();
+        sender
+            .send_message(TextComponent::text_string(match targets {
+                [target] => format!(
+                    "Set {amount} experience {unit} on {}",
+                    target.gameprofile.name
+                ),
+                _ => format!("Set {amount} experience {unit} on {changed} players"),
+            }))
+            .await;
+        Ok(())
+    }
+}
+
+struct QueryExecutor(Unit);
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let Some(target) = target.as_player() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "No player was found".to_string(),
+            ));
+        };
+        let amount = match self.0 {
+            Unit::Points => target.experience_points(),
+            Unit::Levels => target
+                .experience_level
+                .load(std::sync::atomic::Ordering::Relaxed),
+        };
+        sender
+            .send_message(TextComponent::text_string(format!(
+                "{} has {amount} experience {}",
+                target.gameprofile.name,
+                self.0.name()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(
+            literal("add").with_child(
+                argument_default_name(PlayersArgumentConsumer).with_child(
+                    argument_default_name(amount_consumer())
+                        .execute(AddExecutor(Unit::Points))
+                        .with_child(literal("points").execute(AddExecutor(Unit::Points)))
+                        .with_child(literal("levels").execute(AddExecutor(Unit::Levels))),
+                ),
+            ),
+        )
+        .with_child(
+            literal("set").with_child(
+                argument_default_name(PlayersArgumentConsumer).with_child(
+                    argument_default_name(set_amount_consumer())
+                        .execute(SetExecutor(Unit::Points))
+                        .with_child(literal("points").execute(SetExecutor(Unit::Points)))
+                        .with_child(literal("levels").execute(SetExecutor(Unit::Levels))),
+                ),
+            ),
+        )
+        .with_child(
+            literal("query").with_child(
+                argument(ARG_TARGET, EntityArgumentConsumer)
+                    .with_child(literal("points").execute(QueryExecutor(Unit::Points)))
+                    .with_child(literal("levels").execute(QueryExecutor(Unit::Levels))),
+            ),
+        )
+}
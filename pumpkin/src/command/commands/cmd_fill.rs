@@ -1,14 +1,26 @@
+use std::sync::Arc;
+
+use crate::block::pop_block;
 use crate::command::args::arg_block::BlockArgumentConsumer;
+use crate::command::args::arg_block_predicate::{BlockPredicate, BlockPredicateArgumentConsumer};
 use crate::command::args::arg_position_block::BlockPosArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, literal};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::World;
 
 use async_trait::async_trait;
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_core::math::vector3::Vector3;
 use pumpkin_core::text::TextComponent;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_world::block::block_registry::{
+    get_block_by_state_id, get_block_entity_type, get_state_by_state_id,
+};
+use pumpkin_world::block::block_state::BlockStateInput;
+use pumpkin_world::world_info::game_rules::IntRule;
 
 const NAMES: [&str; 1] = ["fill"];
 
@@ -17,6 +29,7 @@ const DESCRIPTION: &str = "Fills all or parts of a region with a specific block.
 const ARG_BLOCK: &str = "block";
 const ARG_FROM: &str = "from";
 const ARG_TO: &str = "to";
+const ARG_FILTER: &str = "filter";
 
 #[derive(Clone, Copy, Default)]
 enum Mode {
@@ -28,126 +41,159 @@ enum Mode {
     Keep,
     /// Like Hollow but doesn't replace inner blocks with air, just the outline
     Outline,
-    /// Replaces all blocks with the new block state, without particles
+    /// Replaces all blocks with the new block state, without particles. Only the blocks matching
+    /// the filter if there is one
     #[default]
     Replace,
 }
 
-struct SetblockExecutor(Mode);
+/// The lowest and the highest corner of the area between the positions
+pub(super) fn bounds(from: Vector3<i32>, to: Vector3<i32>) -> (Vector3<i32>, Vector3<i32>) {
+    (
+        Vector3::new(from.x.min(to.x), from.y.min(to.y), from.z.min(to.z)),
+        Vector3::new(from.x.max(to.x), from.y.max(to.y), from.z.max(to.z)),
+    )
+}
+
+/// Fails when the area has more blocks than the `commandModificationBlockLimit` game rule allows,
+/// like vanilla
+pub(super) async fn check_volume(
+    world: &World,
+    min: Vector3<i32>,
+    max: Vector3<i32>,
+) -> Result<(), CommandError> {
+    let volume = [max.x - min.x, max.y - min.y, max.z - min.z]
+        .into_iter()
+        .map(|length| i64::from(length) + 1)
+        .product::<i64>();
+    let limit = world
+        .game_rules
+        .lock()
+        .await
+        .get_int(IntRule::CommandModificationBlockLimit);
+    if volume > i64::from(limit) {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "Too many blocks in the specified area (maximum {limit}, specified {volume})"
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn is_air(state_id: u16) -> bool {
+    get_state_by_state_id(state_id).is_some_and(|state| state.air)
+}
+
+/// Places the blocks at once. Block entities of the placed blocks get the given data or start out
+/// empty, vanilla clears them as well
+pub(super) async fn place_blocks(
+    world: &World,
+    blocks: Vec<(WorldPosition, u16, Option<NbtCompound>)>,
+) {
+    let states: Vec<_> = blocks
+        .iter()
+        .map(|(position, state_id, _)| (*position, *state_id))
+        .collect();
+    world.set_block_states(&states).await;
+    for (position, state_id, nbt) in blocks {
+        if get_block_entity_type(state_id).is_none() {
+            continue;
+        }
+        world
+            .set_block_entity_nbt(position, nbt.clone().unwrap_or_default())
+            .await;
+        if let Some(nbt) = nbt {
+            world.update_block_entity(position, nbt).await;
+        }
+    }
+}
+
+/// The blocks to place in the area, blocks which would stay the same are left out
+async fn fill_blocks(
+    world: &Arc<World>,
+    server: &Server,
+    block: &BlockStateInput,
+    (min, max): (Vector3<i32>, Vector3<i32>),
+    mode: Mode,
+    filter: Option<&BlockPredicate<'_>>,
+) -> Vec<(WorldPosition, u16, Option<NbtCompound>)> {
+    let mut blocks = Vec::new();
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                let position = WorldPosition(Vector3::new(x, y, z));
+                let is_edge = x == min.x
+                    || x == max.x
+                    || y == min.y
+                    || y == max.y
+                    || z == min.z
+                    || z == max.z;
+                let state_id = match mode {
+                    Mode::Hollow if !is_edge => 0,
+                    Mode::Outline if !is_edge => continue,
+                    _ => block.state_id,
+                };
+                let mut current = world.get_block_state_id(position).await.unwrap_or_default();
+                let replace = match mode {
+                    Mode::Keep => is_air(current),
+                    Mode::Replace => filter.is_none_or(|filter| filter.matches(current)),
+                    Mode::Destroy | Mode::Hollow | Mode::Outline => true,
+                };
+                if !replace {
+                    continue;
+                }
+                if matches!(mode, Mode::Destroy) && !is_air(current) {
+                    if let Some(broken) = get_block_by_state_id(current) {
+                        pop_block(world, broken, position, server).await;
+                        current = 0;
+                    }
+                }
+                // Block entity data is placed again even on the same block
+                if current != state_id || (state_id == block.state_id && block.nbt.is_some()) {
+                    blocks.push((position, state_id, block.nbt.clone()));
+                }
+            }
+        }
+    }
+    blocks
+}
+
+struct FillExecutor(Mode);
 
-#[expect(clippy::too_many_lines)]
 #[async_trait]
-impl CommandExecutor for SetblockExecutor {
+impl CommandExecutor for FillExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
-        let block_state_id = block.default_state_id;
         let from = BlockPosArgumentConsumer::find_arg(args, ARG_FROM)?;
         let to = BlockPosArgumentConsumer::find_arg(args, ARG_TO)?;
-        let mode = self.0;
-
-        let start_x = from.0.x.min(to.0.x);
-        let start_y = from.0.y.min(to.0.y);
-        let start_z = from.0.z.min(to.0.z);
-
-        let end_x = from.0.x.max(to.0.x);
-        let end_y = from.0.y.max(to.0.y);
-        let end_z = from.0.z.max(to.0.z);
-
-        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
-        let mut placed_blocks = 0;
-
-        match mode {
-            Mode::Destroy => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = WorldPosition(Vector3 { x, y, z });
-                            world.break_block(block_position, None).await;
-                            world.set_block_state(block_position, block_state_id).await;
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Replace => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = WorldPosition(Vector3 { x, y, z });
-                            world.set_block_state(block_position, block_state_id).await;
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Keep => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = WorldPosition(Vector3 { x, y, z });
-                            match world.get_block_state(block_position).await {
-                                Ok(old_state) if old_state.air => {
-                                    world.set_block_state(block_position, block_state_id).await;
-                                    placed_blocks += 1;
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-            }
-            Mode::Hollow => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = WorldPosition(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
-                            if is_edge {
-                                world.set_block_state(block_position, block_state_id).await;
-                            } else {
-                                world.set_block_state(block_position, 0).await;
-                            }
-                            placed_blocks += 1;
-                        }
-                    }
-                }
-            }
-            Mode::Outline => {
-                for x in start_x..=end_x {
-                    for y in start_y..=end_y {
-                        for z in start_z..=end_z {
-                            let block_position = WorldPosition(Vector3::new(x, y, z));
-                            let is_edge = x == start_x
-                                || x == end_x
-                                || y == start_y
-                                || y == end_y
-                                || z == start_z
-                                || z == end_z;
-                            if is_edge {
-                                world.set_block_state(block_position, block_state_id).await;
-                                placed_blocks += 1;
-                            }
-                        }
-                    }
-                }
-            }
+        let filter = if args.contains_key(ARG_FILTER) {
+            Some(BlockPredicateArgumentConsumer::find_arg(args, ARG_FILTER)?)
+        } else {
+            None
         };
 
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let area = bounds(from.0, to.0);
+        check_volume(&world, area.0, area.1).await?;
+
+        let blocks = fill_blocks(&world, server, &block, area, self.0, filter.as_ref()).await;
+        if blocks.is_empty() {
+            return Err(CommandError::GeneralCommandIssue(
+                "No blocks were filled".to_string(),
+            ));
+        }
+        let placed_blocks = blocks.len();
+        place_blocks(&world, blocks).await;
+
         sender
-            .send_message(TextComponent::text_string(format!(
-                "Placed {} blocks of {} from {from} to {to}",
-                placed_blocks, block.name
-            )))
+            .send_message(TextComponent::translate(
+                "commands.fill.success",
+                vec![TextComponent::text_string(placed_blocks.to_string())],
+            ))
             .await;
 
         Ok(())
@@ -159,12 +205,19 @@ pub fn init_command_tree() -> CommandTree {
         argument(ARG_FROM, BlockPosArgumentConsumer).with_child(
             argument(ARG_TO, BlockPosArgumentConsumer).with_child(
                 argument(ARG_BLOCK, BlockArgumentConsumer)
-                    .with_child(literal("destroy").execute(SetblockExecutor(Mode::Destroy)))
-                    .with_child(literal("hollow").execute(SetblockExecutor(Mode::Hollow)))
-                    .with_child(literal("keep").execute(SetblockExecutor(Mode::Keep)))
-                    .with_child(literal("outline").execute(SetblockExecutor(Mode::Outline)))
-                    .with_child(literal("replace").execute(SetblockExecutor(Mode::Replace)))
-                    .execute(SetblockExecutor(Mode::Replace)),
+                    .with_child(literal("destroy").execute(FillExecutor(Mode::Destroy)))
+                    .with_child(literal("hollow").execute(FillExecutor(Mode::Hollow)))
+                    .with_child(literal("keep").execute(FillExecutor(Mode::Keep)))
+                    .with_child(literal("outline").execute(FillExecutor(Mode::Outline)))
+                    .with_child(
+                        literal("replace")
+                            .execute(FillExecutor(Mode::Replace))
+                            .with_child(
+                                argument(ARG_FILTER, BlockPredicateArgumentConsumer)
+                                    .execute(FillExecutor(Mode::Replace)),
+                            ),
+                    )
+                    .execute(FillExecutor(Mode::Replace)),
             ),
         ),
     )
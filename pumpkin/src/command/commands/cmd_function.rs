@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::arg_function::FunctionArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::argument_default_name;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["function"];
+
+const DESCRIPTION: &str = "Runs a function or the functions of a tag from the datapacks.";
+
+struct FunctionExecutor;
+
+#[async_trait]
+impl CommandExecutor for FunctionExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let id = FunctionArgumentConsumer.find_arg_default_name(args)?;
+        let (functions, commands) = match id.strip_prefix('#') {
+            Some(tag) => server.run_function_tag(sender, tag).await?,
+            None => (1, server.run_function(sender, id).await?),
+        };
+        let message = if functions == 1 {
+            TextComponent::translate(
+                "commands.function.success.single",
+                vec![
+                    TextComponent::text_string(commands.to_string()),
+                    TextComponent::text_string(id.to_string()),
+                ],
+            )
+        } else {
+            TextComponent::translate(
+                "commands.function.success.multiple",
+                vec![
+                    TextComponent::text_string(commands.to_string()),
+                    TextComponent::text_string(functions.to_string()),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(argument_default_name(FunctionArgumentConsumer).execute(FunctionExecutor))
+}
@@ -7,12 +7,11 @@ use crate::TextComponent;
 
 use crate::command::args::arg_players::PlayersArgumentConsumer;
 
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::{Arg, ConsumedArgs, FindArg};
 use crate::command::dispatcher::CommandError;
 use crate::command::dispatcher::CommandError::{InvalidConsumption, InvalidRequirement};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, require};
-use crate::command::CommandSender::Player;
 use crate::command::{CommandExecutor, CommandSender};
 use crate::server::Server;
 
@@ -37,7 +36,7 @@ impl CommandExecutor for GamemodeTargetSelf {
             return Err(InvalidConsumption(Some(ARG_GAMEMODE.into())));
         };
 
-        if let Player(target) = sender {
+        if let Some(target) = sender.as_player() {
             if target.gamemode.load() == gamemode {
                 target
                     .send_system_message(&TextComponent::text(&format!(
@@ -72,9 +71,7 @@ impl CommandExecutor for GamemodeTargetPlayer {
         let Some(Arg::GameMode(gamemode)) = args.get_cloned(&ARG_GAMEMODE) else {
             return Err(InvalidConsumption(Some(ARG_GAMEMODE.into())));
         };
-        let Some(Arg::Players(targets)) = args.get(ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
         let target_count = targets.len();
 
@@ -0,0 +1,192 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::text::{
+    color::{Color, NamedColor},
+    TextComponent,
+};
+use pumpkin_protocol::client::play::{CEntityStatus, CGameEvent, GameEvent};
+use pumpkin_world::world_info::game_rules::{BoolRule, GameRule, IntRule};
+
+use crate::{
+    command::{
+        args::{
+            arg_bool::BoolArgConsumer, arg_bounded_num::BoundedNumArgumentConsumer, ConsumedArgs,
+            FindArg, FindArgDefaultName,
+        },
+        tree::CommandTree,
+        tree_builder::{argument, argument_default_name, literal},
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+    world::World,
+};
+
+const NAMES: [&str; 1] = ["gamerule"];
+
+const DESCRIPTION: &str = "Sets or queries a game rule value.";
+
+const ARG_VALUE: &str = "value";
+
+fn int_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_VALUE)
+}
+
+/// Queries run on the sender's world, the console queries the first world
+fn rule_world(sender: &CommandSender<'_>, server: &Server) -> Arc<World> {
+    sender.world().unwrap_or_else(|| {
+        server
+            .worlds
+            .first()
+            .cloned()
+            .expect("There should always be at least one world")
+    })
+}
+
+/// Tells the players about the rules their clients need to know, like vanilla's callbacks in
+/// `GameRules`
+async fn sync_rule(world: &World, rule: BoolRule, value: bool) {
+    match rule {
+        BoolRule::DoDaylightCycle => world.level_time.lock().await.send_time(world).await,
+        BoolRule::DoImmediateRespawn => {
+            world
+                .broadcast_packet_all(&CGameEvent::new(
+                    GameEvent::EnabledRespawnScreen,
+                    if value { 1.0 } else { 0.0 },
+                ))
+                .await;
+        }
+        BoolRule::DoLimitedCrafting => {
+            world
+                .broadcast_packet_all(&CGameEvent::new(
+                    GameEvent::LimitedCrafting,
+                    if value { 1.0 } else { 0.0 },
+                ))
+                .await;
+        }
+        BoolRule::ReducedDebugInfo => {
+            let players: Vec<_> = world
+                .current_players
+                .lock()
+                .await
+                .values()
+                .cloned()
+                .collect();
+            for player in players {
+                player
+                    .client
+                    .send_packet(&CEntityStatus::new(
+                        player.entity_id(),
+                        if value { 22 } else { 23 },
+                    ))
+                    .await;
+            }
+        }
+        _ => {}
+    }
+}
+
+struct QueryExecutor(GameRule);
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let value = rule_world(sender, server)
+            .game_rules
+            .lock()
+            .await
+            .value_string(self.0);
+        sender
+            .send_message(TextComponent::text(&format!(
+                "Gamerule {} is currently set to: {value}",
+                self.0.name()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetBoolExecutor(BoolRule);
+
+#[async_trait]
+impl CommandExecutor for SetBoolExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+
+        // Game rules apply to every world like in vanilla
+        for world in &server.worlds {
+            world.game_rules.lock().await.set_bool(self.0, value);
+            sync_rule(world, self.0, value).await;
+        }
+
+        sender
+            .send_message(TextComponent::text(&format!(
+                "Gamerule {} is now set to: {value}",
+                self.0.name()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetIntExecutor(IntRule);
+
+#[async_trait]
+impl CommandExecutor for SetIntExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(value) = int_consumer().find_arg_default_name(args)? else {
+            sender
+                .send_message(
+                    TextComponent::text("Value is too large or too small.")
+                        .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        };
+
+        for world in &server.worlds {
+            world.game_rules.lock().await.set_int(self.0, value);
+        }
+
+        sender
+            .send_message(TextComponent::text(&format!(
+                "Gamerule {} is now set to: {value}",
+                self.0.name()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    GameRule::all().fold(CommandTree::new(NAMES, DESCRIPTION), |tree, rule| {
+        let value = match rule {
+            GameRule::Bool(rule) => {
+                argument(ARG_VALUE, BoolArgConsumer).execute(SetBoolExecutor(rule))
+            }
+            GameRule::Int(rule) => {
+                argument_default_name(int_consumer()).execute(SetIntExecutor(rule))
+            }
+        };
+        tree.with_child(
+            literal(rule.name())
+                .execute(QueryExecutor(rule))
+                .with_child(value),
+        )
+    })
+}
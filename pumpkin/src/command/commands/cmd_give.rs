@@ -30,12 +30,12 @@ impl CommandExecutor for GiveExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
 
-        let (item_name, item) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+        let (item_name, stack) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
 
         let item_count = match item_count_consumer().find_arg_default_name(args) {
             Err(_) => 1,
@@ -51,8 +51,16 @@ impl CommandExecutor for GiveExecutor {
             }
         };
 
+        // Like vanilla, at most a hundred stacks
+        let max_count = i32::from(stack.max_stack_size()) * 100;
+        if item_count > max_count {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Can't give more than {max_count} of {item_name}"
+            )));
+        }
+
         for target in targets {
-            target.give_items(item, item_count as u32).await;
+            target.give_stack(server, &stack, item_count as u32).await;
         }
 
         sender
@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use pumpkin_core::math::position::WorldPosition;
+use pumpkin_core::text::TextComponent;
+use pumpkin_inventory::{read_items_nbt, write_items_nbt};
+use pumpkin_world::block::block_registry::get_block_entity_type;
+use pumpkin_world::item::ItemStack;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_entities::EntitiesArgumentConsumer;
+use crate::command::args::arg_item::ItemArgumentConsumer;
+use crate::command::args::arg_item_slot::ItemSlotArgumentConsumer;
+use crate::command::args::arg_position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::any_entity::AnyEntity;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["item"];
+
+const DESCRIPTION: &str = "Replaces items in the inventories of blocks and entities.";
+
+const ARG_POS: &str = "pos";
+const ARG_TARGETS: &str = "targets";
+const ARG_SLOT: &str = "slot";
+const ARG_ITEM: &str = "item";
+
+/// The slots of the large chest half at the second location come after the ones of the first
+const LARGE_CHEST_HALF: usize = 27;
+
+fn count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("count")
+        .min(1)
+        .max(99)
+}
+
+/// The item and the stack to put into the slot, with the count if one is given
+fn find_stack<'a>(args: &'a ConsumedArgs<'a>) -> Result<(&'a str, ItemStack), CommandError> {
+    let (item_name, stack) = ItemArgumentConsumer::find_arg(args, ARG_ITEM)?;
+    match count_consumer().find_arg_default_name(args) {
+        Err(_) => Ok((item_name, stack)),
+        Ok(Ok(count)) => Ok((item_name, stack.with_count(count as u8))),
+        Ok(Err(())) => Err(CommandError::GeneralCommandIssue(
+            "Item count is too large or too small.".to_string(),
+        )),
+    }
+}
+
+/// How many slots the containers with the block entity have
+fn container_size(block_entity_type: &str) -> Option<usize> {
+    match block_entity_type.strip_prefix("minecraft:")? {
+        "chest" | "trapped_chest" | "barrel" | "shulker_box" => Some(27),
+        "dispenser" | "dropper" | "crafter" => Some(9),
+        "hopper" | "brewing_stand" => Some(5),
+        "furnace" | "blast_furnace" | "smoker" => Some(3),
+        _ => None,
+    }
+}
+
+/// Puts the stack into the container at the position. Containers players look into are changed
+/// directly, the others in their block entity
+async fn replace_block_slot(
+    server: &Server,
+    world: &World,
+    pos: WorldPosition,
+    size: usize,
+    slot: usize,
+    stack: ItemStack,
+) {
+    let open_container = server
+        .open_containers
+        .read()
+        .await
+        .values()
+        .find_map(|container| {
+            if container.get_location() == Some(pos) {
+                Some((container.get_container(), container.all_player_ids(), 0))
+            } else if container.get_second_location() == Some(pos) {
+                let players = container.all_player_ids();
+                Some((container.get_container(), players, LARGE_CHEST_HALF))
+            } else {
+                None
+            }
+        });
+    if let Some((container, players, offset)) = open_container {
+        let mut container = container.lock().await;
+        if let Some(target) = container.all_slots().into_iter().nth(offset + slot) {
+            *target = Some(stack);
+        }
+        for id in players {
+            if let Some(player) = world.get_player_by_entityid(id).await {
+                player.set_container_content(Some(&mut *container)).await;
+            }
+        }
+        return;
+    }
+
+    let mut nbt = world.get_block_entity_nbt(pos).await.unwrap_or_default();
+    let mut slots = vec![None; size];
+    read_items_nbt(&mut slots, &nbt);
+    slots[slot] = Some(stack);
+    write_items_nbt(&slots, &mut nbt);
+    world.set_block_entity_nbt(pos, nbt).await;
+}
+
+/// The window slot of the player inventory with the number, see `PlayerInventory`
+fn player_window_slot(slot: i32) -> Option<usize> {
+    match slot {
+        0..=8 => Some(36 + slot as usize),
+        9..=35 => Some(slot as usize),
+        99 => Some(45),
+        100..=103 => Some(108 - slot as usize),
+        _ => None,
+    }
+}
+
+/// Puts the stack into the slot of the player, returns whether the player has the slot
+async fn replace_player_slot(player: &Player, slot: i32, stack: ItemStack) -> bool {
+    if (200..227).contains(&slot) {
+        let mut ender_chest = player.ender_chest.lock().await;
+        let Some(target) = ender_chest
+            .all_slots()
+            .into_iter()
+            .nth((slot - 200) as usize)
+        else {
+            return false;
+        };
+        *target = Some(stack);
+        return true;
+    }
+    let mut inventory = player.inventory().lock().await;
+    let target = if slot == 98 {
+        inventory.held_item_mut()
+    } else {
+        let Some(target) = player_window_slot(slot).and_then(|i| inventory.get_slot(i).ok()) else {
+            return false;
+        };
+        target
+    };
+    *target = Some(stack);
+    drop(inventory);
+    player.set_container_content(None).await;
+    true
+}
+
+/// Puts the stack into the slot of the entity, returns whether the entity has the slot
+async fn replace_entity_slot(entity: &AnyEntity, slot: i32, stack: ItemStack) -> bool {
+    match entity {
+        AnyEntity::Player(player) => replace_player_slot(player, slot, stack).await,
+        AnyEntity::Mob(mob) => {
+            let index = match slot {
+                98 => 0,
+                99 => 1,
+                100..=103 => slot as usize - 98,
+                _ => return false,
+            };
+            mob.set_equipment(index, Some(stack)).await;
+            true
+        }
+        AnyEntity::Item(item) if slot == 0 => {
+            *item.item_stack.lock().await = stack;
+            item.update_item_stack().await;
+            true
+        }
+        AnyEntity::Item(_) | AnyEntity::Other(_) => false,
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ReplaceBlockExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReplaceBlockExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let pos = BlockPosArgumentConsumer::find_arg(args, ARG_POS)?;
+        let slot = ItemSlotArgumentConsumer::find_arg(args, ARG_SLOT)?;
+        let (item_name, stack) = find_stack(args)?;
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+
+        let state_id = world
+            .get_block_state_id(pos)
+            .await
+            .map_err(|e| CommandError::OtherPumpkin(e.into()))?;
+        let Some(size) = get_block_entity_type(state_id)
+            .as_deref()
+            .and_then(container_size)
+        else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Target position {pos} is not a container"
+            )));
+        };
+        let Some(slot) = usize::try_from(slot).ok().filter(|slot| *slot < size) else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "The target does not have slot {slot}"
+            )));
+        };
+        replace_block_slot(server, &world, pos, size, slot, stack).await;
+
+        let WorldPosition(position) = pos;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.item.block.set.success",
+                vec![
+                    TextComponent::text_string(position.x.to_string()),
+                    TextComponent::text_string(position.y.to_string()),
+                    TextComponent::text_string(position.z.to_string()),
+                    TextComponent::text_string(item_name.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+struct ReplaceEntityExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReplaceEntityExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let slot = ItemSlotArgumentConsumer::find_arg(args, ARG_SLOT)?;
+        let (item_name, stack) = find_stack(args)?;
+
+        let mut changed = Vec::new();
+        for target in targets {
+            if replace_entity_slot(target, slot, stack.clone()).await {
+                changed.push(target);
+            }
+        }
+
+        let item = TextComponent::text_string(item_name.to_string());
+        let message = match changed.as_slice() {
+            [] => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "No targets accepted item into slot {slot}"
+                )))
+            }
+            [target] => TextComponent::translate(
+                "commands.item.entity.set.success.single",
+                vec![target.display_name(), item],
+            ),
+            changed => TextComponent::translate(
+                "commands.item.entity.set.success.multiple",
+                vec![TextComponent::text_string(changed.len().to_string()), item],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+/// The `with <item> [<count>]` part after the slot
+fn with_item<E: CommandExecutor + Copy + Send + 'static>(executor: E) -> NonLeafNodeBuilder {
+    literal("with").with_child(
+        argument(ARG_ITEM, ItemArgumentConsumer)
+            .execute(executor)
+            .with_child(argument_default_name(count_consumer()).execute(executor)),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        literal("replace")
+            .with_child(
+                literal("block").with_child(
+                    argument(ARG_POS, BlockPosArgumentConsumer).with_child(
+                        argument(ARG_SLOT, ItemSlotArgumentConsumer)
+                            .with_child(with_item(ReplaceBlockExecutor)),
+                    ),
+                ),
+            )
+            .with_child(
+                literal("entity").with_child(
+                    argument(ARG_TARGETS, EntitiesArgumentConsumer).with_child(
+                        argument(ARG_SLOT, ItemSlotArgumentConsumer)
+                            .with_child(with_item(ReplaceEntityExecutor)),
+                    ),
+                ),
+            ),
+    )
+}
@@ -3,12 +3,11 @@ use pumpkin_core::text::color::NamedColor;
 use pumpkin_core::text::TextComponent;
 
 use crate::command::args::arg_players::PlayersArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::argument;
 use crate::command::CommandError;
 use crate::command::{CommandExecutor, CommandSender};
-use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["kick"];
 const DESCRIPTION: &str = "Kicks the target player from the server.";
@@ -25,9 +24,7 @@ impl CommandExecutor for KickExecutor {
         _server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
         let target_count = targets.len();
 
@@ -3,17 +3,36 @@ use pumpkin_core::text::color::NamedColor;
 use pumpkin_core::text::TextComponent;
 
 use crate::command::args::arg_entities::EntitiesArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
-use CommandError::InvalidConsumption;
+use crate::entity::any_entity::AnyEntity;
 
 const NAMES: [&str; 1] = ["kill"];
 const DESCRIPTION: &str = "Kills all target entities.";
 
 const ARG_TARGET: &str = "target";
 
+/// Kills the targets and tells how many died
+async fn kill(sender: &CommandSender<'_>, targets: &[AnyEntity]) {
+    for target in targets {
+        target.kill().await;
+    }
+
+    let msg = match targets {
+        [target] => {
+            TextComponent::translate("commands.kill.success.single", vec![target.display_name()])
+        }
+        _ => TextComponent::translate(
+            "commands.kill.success.multiple",
+            vec![TextComponent::text_string(targets.len().to_string())],
+        ),
+    };
+
+    sender.send_message(msg.color_named(NamedColor::Blue)).await;
+}
+
 struct KillExecutor;
 
 #[async_trait]
@@ -24,24 +43,8 @@ impl CommandExecutor for KillExecutor {
         _server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
-
-        let target_count = targets.len();
-
-        for target in targets {
-            target.living_entity.kill().await;
-        }
-
-        let msg = if target_count == 1 {
-            TextComponent::text("Entity has been killed.")
-        } else {
-            TextComponent::text_string(format!("{target_count} entities have been killed."))
-        };
-
-        sender.send_message(msg.color_named(NamedColor::Blue)).await;
-
+        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        kill(sender, targets).await;
         Ok(())
     }
 }
@@ -56,10 +59,8 @@ impl CommandExecutor for KillSelfExecutor {
         _server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
-
-        target.living_entity.kill().await;
-
+        let target = sender.executor().ok_or(CommandError::InvalidRequirement)?;
+        kill(sender, &[target]).await;
         Ok(())
     }
 }
@@ -68,5 +69,5 @@ impl CommandExecutor for KillSelfExecutor {
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
         .with_child(argument(ARG_TARGET, EntitiesArgumentConsumer).execute(KillExecutor))
-        .with_child(require(|sender| sender.is_player()).execute(KillSelfExecutor))
+        .with_child(require(|sender| sender.executor().is_some()).execute(KillSelfExecutor))
 }
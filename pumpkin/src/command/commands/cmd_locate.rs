@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+
+use async_trait::async_trait;
+use pumpkin_core::{
+    math::{position::WorldPosition, vector2::Vector2, vector3::Vector3},
+    text::{click::ClickEvent, color::NamedColor, hover::HoverEvent, TextComponent},
+};
+use pumpkin_registry::{get_tag_values, TagCategory};
+use pumpkin_world::{
+    biome::Biome, coordinates::XZBlockCoordinates, structure::Structure, BIOME_SEARCH_RADIUS,
+};
+
+use crate::command::{
+    args::{
+        arg_resource_or_tag::{ResourceOrTag, ResourceOrTagArgumentConsumer},
+        ConsumedArgs, FindArg,
+    },
+    tree::CommandTree,
+    tree_builder::{argument, literal},
+    CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["locate"];
+
+const DESCRIPTION: &str =
+    "Finds the nearest structure, biome or point of interest of the given type.";
+
+const ARG_BIOME: &str = "biome";
+const ARG_POI: &str = "poi";
+
+/// How far points of interest are searched for, in blocks, like vanilla
+const POI_SEARCH_RADIUS: i32 = 256;
+
+/// The block the sender searches from, the console searches from the world origin
+fn search_origin(sender: &CommandSender) -> Vector3<i32> {
+    sender.position().map_or(Vector3::new(0, 0, 0), |pos| {
+        Vector3::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        )
+    })
+}
+
+/// Tells where the nearest match is, with coordinates to click on to teleport there. Without a
+/// height the distance is only measured horizontally, like vanilla's `LocateCommand.showLocateResult`
+async fn send_result(
+    sender: &CommandSender<'_>,
+    kind: &str,
+    name: String,
+    from: Vector3<i32>,
+    found: Vector3<i32>,
+    exact_y: bool,
+) {
+    let y = if exact_y {
+        found.y.to_string()
+    } else {
+        "~".to_string()
+    };
+    let dx = f64::from(found.x - from.x);
+    let dz = f64::from(found.z - from.z);
+    let distance = if exact_y {
+        dx.hypot(dz).hypot(f64::from(found.y - from.y))
+    } else {
+        dx.hypot(dz)
+    };
+
+    let coordinates = TextComponent::text_string(format!("[{}, {y}, {}]", found.x, found.z))
+        .hover_event(HoverEvent::ShowText(Cow::from("Click to teleport")))
+        .click_event(ClickEvent::SuggestCommand(Cow::from(format!(
+            "/tp @s {} {y} {}",
+            found.x, found.z
+        ))))
+        .color_named(NamedColor::Green);
+    sender
+        .send_message(TextComponent::translate(
+            format!("commands.locate.{kind}.success"),
+            vec![
+                TextComponent::text_string(name),
+                coordinates,
+                TextComponent::text_string((distance.floor() as i64).to_string()),
+            ],
+        ))
+        .await;
+}
+
+struct LocateStructureExecutor(Structure);
+
+#[async_trait]
+impl CommandExecutor for LocateStructureExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let from = search_origin(sender);
+        let id = self.0.id();
+
+        let Some(chunk) = world
+            .level
+            .locate_structure(self.0, &Vector2::new(from.x >> 4, from.z >> 4))
+        else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Could not find a structure of type \"{id}\" nearby"
+            )));
+        };
+
+        let found = Vector3::new(chunk.x << 4, from.y, chunk.z << 4);
+        send_result(sender, "structure", id.to_string(), from, found, false).await;
+        Ok(())
+    }
+}
+
+/// The biomes the resource or tag stands for
+fn find_biomes(target: ResourceOrTag) -> Result<Vec<Biome>, CommandError> {
+    let biomes: Vec<Biome> = Biome::ALL
+        .into_iter()
+        .filter(|biome| target.matches(TagCategory::WorldGenBiome, &biome.id()))
+        .collect();
+    match target {
+        ResourceOrTag::Resource(biome) if biomes.is_empty() => Err(
+            CommandError::GeneralCommandIssue(format!("There is no biome with type \"{biome}\"")),
+        ),
+        ResourceOrTag::Tag(tag) if get_tag_values(TagCategory::WorldGenBiome, tag).is_none() => {
+            Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown biome tag \"{tag}\""
+            )))
+        }
+        _ => Ok(biomes),
+    }
+}
+
+struct LocateBiomeExecutor;
+
+#[async_trait]
+impl CommandExecutor for LocateBiomeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = ResourceOrTagArgumentConsumer::find_arg(args, ARG_BIOME)?;
+        let biomes = find_biomes(target)?;
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let from = search_origin(sender);
+
+        // Sampling thousands of columns takes a while, so the search doesn't block the tick
+        let level = world.level.clone();
+        let column = XZBlockCoordinates {
+            x: from.x,
+            z: from.z,
+        };
+        let found = tokio::task::spawn_blocking(move || {
+            level.locate_biome(column, &|biome: Biome| biomes.contains(&biome))
+        })
+        .await
+        .map_err(|_| CommandError::GeneralCommandIssue("The biome search failed".to_string()))?;
+        let Some(found) = found else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Could not find a biome of type \"{target}\" within {BIOME_SEARCH_RADIUS} blocks"
+            )));
+        };
+
+        let found = Vector3::new(found.x, from.y, found.z);
+        send_result(sender, "biome", target.to_string(), from, found, false).await;
+        Ok(())
+    }
+}
+
+struct LocatePoiExecutor;
+
+#[async_trait]
+impl CommandExecutor for LocatePoiExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = ResourceOrTagArgumentConsumer::find_arg(args, ARG_POI)?;
+        if let ResourceOrTag::Tag(tag) = target {
+            if get_tag_values(TagCategory::PointOfInterest, tag).is_none() {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "Unknown point of interest tag \"{tag}\""
+                )));
+            }
+        }
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let from = search_origin(sender);
+
+        let Some(WorldPosition(found)) = world
+            .level
+            .locate_poi(WorldPosition(from), POI_SEARCH_RADIUS, |kind| {
+                target.matches(TagCategory::PointOfInterest, kind)
+            })
+            .await
+        else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Could not find a point of interest of type \"{target}\" within {POI_SEARCH_RADIUS} blocks"
+            )));
+        };
+
+        send_result(sender, "poi", target.to_string(), from, found, true).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut structure = literal("structure");
+    for locatable in Structure::ALL {
+        structure = structure.with_child(
+            literal(&locatable.id()["minecraft:".len()..])
+                .execute(LocateStructureExecutor(locatable)),
+        );
+    }
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(structure)
+        .with_child(
+            literal("biome").with_child(
+                argument(
+                    ARG_BIOME,
+                    ResourceOrTagArgumentConsumer::new(TagCategory::WorldGenBiome),
+                )
+                .execute(LocateBiomeExecutor),
+            ),
+        )
+        .with_child(
+            literal("poi").with_child(
+                argument(
+                    ARG_POI,
+                    ResourceOrTagArgumentConsumer::new(TagCategory::PointOfInterest),
+                )
+                .execute(LocatePoiExecutor),
+            ),
+        )
+}
@@ -1,6 +1,6 @@
 use crate::{
     command::{
-        args::{arg_players::PlayersArgumentConsumer, Arg, ConsumedArgs},
+        args::{arg_players::PlayersArgumentConsumer, ConsumedArgs, FindArg},
         tree::CommandTree,
         tree_builder::argument,
         CommandError, CommandExecutor, CommandSender,
@@ -10,7 +10,6 @@ use crate::{
 use async_trait::async_trait;
 use pumpkin_config::{op::Op, BASIC_CONFIG};
 use pumpkin_core::text::TextComponent;
-use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 1] = ["op"];
 const DESCRIPTION: &str = "Grants operator status to a player.";
@@ -28,11 +27,9 @@ impl CommandExecutor for OpExecutor {
     ) -> Result<(), CommandError> {
         let mut config = OPERATOR_CONFIG.write().await;
 
-        let Some(Arg::Players(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
-        };
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGET)?;
 
-        // log each player to the console.
+        let mut changed = 0;
         for player in targets {
             let new_level = if BASIC_CONFIG.op_permission_level > sender.permission_lvl() {
                 sender.permission_lvl()
@@ -40,33 +37,43 @@ impl CommandExecutor for OpExecutor {
                 BASIC_CONFIG.op_permission_level
             };
 
-            let op_entry = Op::new(
-                player.gameprofile.id,
-                player.gameprofile.name.clone(),
-                new_level,
-                false,
-            );
             if let Some(op) = config
                 .ops
                 .iter_mut()
                 .find(|o| o.uuid == player.gameprofile.id)
             {
+                if op.level == new_level {
+                    continue;
+                }
                 op.level = new_level;
             } else {
-                config.ops.push(op_entry);
+                config.ops.push(Op::new(
+                    player.gameprofile.id,
+                    player.gameprofile.name.clone(),
+                    new_level,
+                    false,
+                ));
             }
             config.save();
+            changed += 1;
 
             player
                 .set_permission_lvl(new_level, &server.command_dispatcher)
                 .await;
 
-            let player_name = player.gameprofile.name.clone();
-            let message = format!("Made {player_name} a server operator.");
-            let msg = TextComponent::text(&message);
-            sender.send_message(msg).await;
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.op.success",
+                    vec![TextComponent::text_string(player.gameprofile.name.clone())],
+                ))
+                .await;
         }
 
+        if changed == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. The player already is an operator".to_string(),
+            ));
+        }
         Ok(())
     }
 }
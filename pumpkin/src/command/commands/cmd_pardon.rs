@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{arg_simple::SimpleArgConsumer, ConsumedArgs, FindArg},
+        tree::CommandTree,
+        tree_builder::argument,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::banned_player_data::BANNED_PLAYER_LIST,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["pardon"];
+const DESCRIPTION: &str = "Removes a player from the ban list.";
+
+const ARG_TARGET: &str = "target";
+
+struct PardonExecutor;
+
+#[async_trait]
+impl CommandExecutor for PardonExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = SimpleArgConsumer::find_arg(args, ARG_TARGET)?;
+
+        let Some(entry) = BANNED_PLAYER_LIST.write().await.remove(target) else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. The player isn't banned".to_string(),
+            ));
+        };
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.pardon.success",
+                vec![TextComponent::text_string(entry.name)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(argument(ARG_TARGET, SimpleArgConsumer).execute(PardonExecutor))
+}
@@ -0,0 +1,58 @@
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{arg_simple::SimpleArgConsumer, ConsumedArgs, FindArg},
+        tree::CommandTree,
+        tree_builder::argument,
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::banned_ip_data::BANNED_IP_LIST,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["pardon-ip"];
+const DESCRIPTION: &str = "Removes an IP address from the ban list.";
+
+const ARG_TARGET: &str = "target";
+
+struct PardonIpExecutor;
+
+#[async_trait]
+impl CommandExecutor for PardonIpExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = SimpleArgConsumer::find_arg(args, ARG_TARGET)?;
+        let Ok(ip) = target.parse::<IpAddr>() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Invalid IP address".to_string(),
+            ));
+        };
+
+        if BANNED_IP_LIST.write().await.remove(ip).is_none() {
+            return Err(CommandError::GeneralCommandIssue(
+                "Nothing changed. That IP isn't banned".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.pardonip.success",
+                vec![TextComponent::text_string(ip.to_string())],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(argument(ARG_TARGET, SimpleArgConsumer).execute(PardonIpExecutor))
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::math::vector2::Vector2;
+use pumpkin_core::text::TextComponent;
+use pumpkin_world::pregen::PregenTask;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::FindArgDefaultName;
+use crate::command::dispatcher::CommandError::InvalidConsumption;
+use crate::command::tree_builder::{argument_default_name, literal};
+use crate::command::{
+    args::ConsumedArgs, tree::CommandTree, CommandError, CommandExecutor, CommandSender,
+};
+use crate::server::Server;
+use crate::world::pregen::Pregeneration;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["pregen"];
+
+const DESCRIPTION: &str = "Generates and saves all chunks in a radius around the world spawn.";
+
+fn radius_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("radius")
+        .min(0)
+        .max(1000)
+}
+
+/// The world of the player, the console uses the overworld
+fn target_world(sender: &CommandSender<'_>, server: &Server) -> Result<Arc<World>, CommandError> {
+    sender.world().map_or_else(
+        || {
+            server.worlds.first().cloned().ok_or_else(|| {
+                CommandError::GeneralCommandIssue("There is no world to pregenerate".to_string())
+            })
+        },
+        Ok,
+    )
+}
+
+struct StartExecutor;
+
+#[async_trait]
+impl CommandExecutor for StartExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let radius = match radius_consumer().find_arg_default_name(args) {
+            Err(_) => return Err(InvalidConsumption(Some("radius".into()))),
+            Ok(Ok(radius)) => radius as u32,
+            Ok(Err(())) => {
+                return Err(CommandError::GeneralCommandIssue(
+                    "The radius must be between 0 and 1000 chunks".to_string(),
+                ))
+            }
+        };
+        let world = target_world(sender, server)?;
+        let spawn = Vector2::new(
+            world.level.level_info.spawn_x >> 4,
+            world.level.level_info.spawn_z >> 4,
+        );
+        let task = PregenTask::new(spawn, radius);
+        let requester = sender.as_player();
+        if Pregeneration::start(world, task, requester).await.is_none() {
+            return Err(CommandError::GeneralCommandIssue(
+                "A pregeneration is already running, cancel it with /pregen cancel".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::text_string(format!(
+                "Pregenerating {} chunks around the spawn",
+                task.chunk_count()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+struct CancelExecutor;
+
+#[async_trait]
+impl CommandExecutor for CancelExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = target_world(sender, server)?;
+        let Some(pregeneration) = world.pregeneration.lock().await.clone() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "No pregeneration is running".to_string(),
+            ));
+        };
+        pregeneration.cancel();
+
+        sender
+            .send_message(TextComponent::text(
+                "Cancelling the pregeneration after the current batch",
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct StatusExecutor;
+
+#[async_trait]
+impl CommandExecutor for StatusExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &crate::server::Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = target_world(sender, server)?;
+        let Some(pregeneration) = world.pregeneration.lock().await.clone() else {
+            return Err(CommandError::GeneralCommandIssue(
+                "No pregeneration is running".to_string(),
+            ));
+        };
+        let task = pregeneration.task();
+
+        sender
+            .send_message(TextComponent::text_string(format!(
+                "Pregenerated {}/{} chunks ({:.1}%)",
+                task.done,
+                task.chunk_count(),
+                task.progress() * 100.0
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(argument_default_name(radius_consumer()).execute(StartExecutor))
+        .with_child(literal("cancel").execute(CancelExecutor))
+        .with_child(literal("status").execute(StatusExecutor))
+}
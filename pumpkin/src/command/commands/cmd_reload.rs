@@ -0,0 +1,33 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["reload"];
+
+const DESCRIPTION: &str = "Reloads the functions of the datapacks.";
+
+struct ReloadExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReloadExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(TextComponent::translate("commands.reload.success", vec![]))
+            .await;
+        server.reload_datapacks().await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(ReloadExecutor)
+}
@@ -0,0 +1,621 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+use pumpkin_protocol::client::play::RenderType;
+
+use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::arg_criterion::CriterionArgumentConsumer;
+use crate::command::args::arg_message::MsgArgConsumer;
+use crate::command::args::arg_objective::ObjectiveArgumentConsumer;
+use crate::command::args::arg_operation::OperationArgumentConsumer;
+use crate::command::args::arg_score_holder::ScoreHolderArgumentConsumer;
+use crate::command::args::arg_scoreboard_slot::ScoreboardSlotArgumentConsumer;
+use crate::command::args::{ConsumedArgs, DefaultNameArgConsumer, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::scoreboard::{
+    display_slot_name, Scoreboard, ScoreboardError, ScoreboardObjective,
+};
+
+const NAMES: [&str; 1] = ["scoreboard"];
+
+const DESCRIPTION: &str = "Manages scoreboard objectives and the scores of players and entities.";
+
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_SCORE: &str = "score";
+const ARG_TARGET: &str = "target";
+const ARG_TARGETS: &str = "targets";
+const ARG_TARGET_OBJECTIVE: &str = "targetObjective";
+const ARG_SOURCE: &str = "source";
+const ARG_SOURCE_OBJECTIVE: &str = "sourceObjective";
+
+const fn single_holder() -> ScoreHolderArgumentConsumer {
+    ScoreHolderArgumentConsumer { multiple: false }
+}
+
+const fn holders() -> ScoreHolderArgumentConsumer {
+    ScoreHolderArgumentConsumer { multiple: true }
+}
+
+fn text(value: &impl ToString) -> TextComponent<'static> {
+    TextComponent::text_string(value.to_string())
+}
+
+/// Objectives are shown by their display name in brackets, like vanilla's
+/// `Objective.getFormattedDisplayName`
+fn objective_text(objective: &ScoreboardObjective) -> TextComponent<'static> {
+    text(&format!("[{}]", objective.display_name))
+}
+
+impl From<ScoreboardError> for CommandError {
+    fn from(error: ScoreboardError) -> Self {
+        Self::GeneralCommandIssue(error.to_string())
+    }
+}
+
+fn find_objective<'a>(
+    scoreboard: &'a Scoreboard,
+    name: &str,
+) -> Result<&'a ScoreboardObjective, CommandError> {
+    scoreboard
+        .objective(name)
+        .ok_or_else(|| ScoreboardError::UnknownObjective(name.to_string()).into())
+}
+
+/// Only objectives which don't follow the game can be changed by commands
+fn find_writable_objective<'a>(
+    scoreboard: &'a Scoreboard,
+    name: &str,
+) -> Result<&'a ScoreboardObjective, CommandError> {
+    let objective = find_objective(scoreboard, name)?;
+    if objective.criterion.is_read_only() {
+        return Err(CommandError::GeneralCommandIssue(format!(
+            "Objective {name} is read-only"
+        )));
+    }
+    Ok(objective)
+}
+
+fn has_objective_arg(args: &ConsumedArgs) -> bool {
+    args.contains_key(ObjectiveArgumentConsumer.default_name().as_str())
+}
+
+struct ListObjectivesExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListObjectivesExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        let objectives = scoreboard.objectives();
+        let msg = if objectives.is_empty() {
+            TextComponent::translate("commands.scoreboard.objectives.list.empty", vec![])
+        } else {
+            let names: Vec<_> = objectives
+                .iter()
+                .map(|objective| format!("[{}]", objective.display_name))
+                .collect();
+            TextComponent::translate(
+                "commands.scoreboard.objectives.list.success",
+                vec![text(&objectives.len()), text(&names.join(", "))],
+            )
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct AddObjectiveExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddObjectiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+        let criterion = CriterionArgumentConsumer.find_arg_default_name(args)?;
+        let display_name = if args.contains_key(ARG_DISPLAY_NAME) {
+            MsgArgConsumer::find_arg(args, ARG_DISPLAY_NAME)?
+        } else {
+            name
+        };
+        let objective =
+            ScoreboardObjective::new(name.to_string(), criterion, display_name.to_string());
+        let msg = TextComponent::translate(
+            "commands.scoreboard.objectives.add.success",
+            vec![objective_text(&objective)],
+        );
+        server
+            .scoreboard
+            .lock()
+            .await
+            .add_objective(server, objective)
+            .await?;
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct RemoveObjectiveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveObjectiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let objective = objective_text(find_objective(&scoreboard, name)?);
+        scoreboard.remove_objective(server, name).await?;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.remove.success",
+                vec![objective],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct SetDisplayExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetDisplayExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let slot = ScoreboardSlotArgumentConsumer.find_arg_default_name(args)?;
+        let name = if has_objective_arg(args) {
+            Some(ObjectiveArgumentConsumer.find_arg_default_name(args)?)
+        } else {
+            None
+        };
+        let mut scoreboard = server.scoreboard.lock().await;
+        if scoreboard.displayed_objective(slot) == name {
+            return Err(CommandError::GeneralCommandIssue(
+                if name.is_some() {
+                    "Nothing changed. That display slot is already showing that objective"
+                } else {
+                    "Nothing changed. That display slot is already empty"
+                }
+                .to_string(),
+            ));
+        }
+        let msg = match name {
+            Some(name) => TextComponent::translate(
+                "commands.scoreboard.objectives.display.set",
+                vec![
+                    text(&display_slot_name(slot)),
+                    objective_text(find_objective(&scoreboard, name)?),
+                ],
+            ),
+            None => TextComponent::translate(
+                "commands.scoreboard.objectives.display.cleared",
+                vec![text(&display_slot_name(slot))],
+            ),
+        };
+        scoreboard.set_display_slot(server, slot, name).await?;
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+enum ModifyObjectiveExecutor {
+    DisplayName,
+    RenderType(RenderType),
+}
+
+#[async_trait]
+impl CommandExecutor for ModifyObjectiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let msg = match self {
+            Self::DisplayName => {
+                let display_name = MsgArgConsumer::find_arg(args, ARG_DISPLAY_NAME)?;
+                scoreboard
+                    .modify_objective(server, name, Some(display_name.to_string()), None)
+                    .await?;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.displayname",
+                    vec![text(&name), text(&format!("[{display_name}]"))],
+                )
+            }
+            Self::RenderType(render_type) => {
+                scoreboard
+                    .modify_objective(server, name, None, Some(*render_type))
+                    .await?;
+                TextComponent::translate(
+                    "commands.scoreboard.objectives.modify.rendertype",
+                    vec![objective_text(find_objective(&scoreboard, name)?)],
+                )
+            }
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct ListScoresExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListScoresExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        if !args.contains_key(ARG_TARGET) {
+            let holders = scoreboard.holders();
+            let msg = if holders.is_empty() {
+                TextComponent::translate("commands.scoreboard.players.list.empty", vec![])
+            } else {
+                TextComponent::translate(
+                    "commands.scoreboard.players.list.success",
+                    vec![text(&holders.len()), text(&holders.join(", "))],
+                )
+            };
+            sender.send_message(msg).await;
+            return Ok(());
+        }
+
+        let holder = &ScoreHolderArgumentConsumer::find_arg(args, ARG_TARGET)?[0];
+        let scores = scoreboard.scores_of(holder);
+        if scores.is_empty() {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.entity.empty",
+                    vec![text(&holder)],
+                ))
+                .await;
+            return Ok(());
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.players.list.entity.success",
+                vec![text(&holder), text(&scores.len())],
+            ))
+            .await;
+        for (objective, score) in scores {
+            let objective = find_objective(&scoreboard, objective)?;
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.scoreboard.players.list.entity.entry",
+                    vec![objective_text(objective), text(&score)],
+                ))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct GetScoreExecutor;
+
+#[async_trait]
+impl CommandExecutor for GetScoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let holder = &ScoreHolderArgumentConsumer::find_arg(args, ARG_TARGET)?[0];
+        let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+        let scoreboard = server.scoreboard.lock().await;
+        let objective = find_objective(&scoreboard, name)?;
+        let Some(score) = scoreboard.score(holder, name) else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Can't get value of {name} for {holder}; none is set"
+            )));
+        };
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.players.get.success",
+                vec![text(&holder), text(&score), objective_text(objective)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ChangeScoreExecutor {
+    Set,
+    Add,
+    Remove,
+}
+
+impl ChangeScoreExecutor {
+    const fn key(self) -> &'static str {
+        match self {
+            Self::Set => "set",
+            Self::Add => "add",
+            Self::Remove => "remove",
+        }
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ChangeScoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHolderArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+        let Ok(amount) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SCORE)? else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Integer must not be less than 0".to_string(),
+            ));
+        };
+        let mut scoreboard = server.scoreboard.lock().await;
+        let objective = objective_text(find_writable_objective(&scoreboard, name)?);
+        let mut score = amount;
+        for target in targets {
+            let current = scoreboard.score(target, name).unwrap_or(0);
+            score = match self {
+                Self::Set => amount,
+                Self::Add => current.wrapping_add(amount),
+                Self::Remove => current.wrapping_sub(amount),
+            };
+            scoreboard.set_score(server, target, name, score).await?;
+        }
+        let key = self.key();
+        let msg = match (self, targets) {
+            (Self::Set, [target]) => TextComponent::translate(
+                "commands.scoreboard.players.set.success.single",
+                vec![objective, text(&target), text(&score)],
+            ),
+            (Self::Set, _) => TextComponent::translate(
+                "commands.scoreboard.players.set.success.multiple",
+                vec![objective, text(&targets.len()), text(&score)],
+            ),
+            (_, [target]) => TextComponent::translate(
+                format!("commands.scoreboard.players.{key}.success.single"),
+                vec![text(&amount), objective, text(&target), text(&score)],
+            ),
+            _ => TextComponent::translate(
+                format!("commands.scoreboard.players.{key}.success.multiple"),
+                vec![text(&amount), objective, text(&targets.len())],
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct ResetScoresExecutor;
+
+#[async_trait]
+impl CommandExecutor for ResetScoresExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHolderArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let (name, objective) = if has_objective_arg(args) {
+            let name = ObjectiveArgumentConsumer.find_arg_default_name(args)?;
+            (
+                Some(name),
+                Some(objective_text(find_objective(&scoreboard, name)?)),
+            )
+        } else {
+            (None, None)
+        };
+        for target in targets {
+            scoreboard.reset_score(server, target, name).await;
+        }
+        let msg = match (objective, targets) {
+            (Some(objective), [target]) => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.single",
+                vec![objective, text(&target)],
+            ),
+            (Some(objective), _) => TextComponent::translate(
+                "commands.scoreboard.players.reset.specific.multiple",
+                vec![objective, text(&targets.len())],
+            ),
+            (None, [target]) => TextComponent::translate(
+                "commands.scoreboard.players.reset.all.single",
+                vec![text(&target)],
+            ),
+            (None, _) => TextComponent::translate(
+                "commands.scoreboard.players.reset.all.multiple",
+                vec![text(&targets.len())],
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct OperationExecutor;
+
+#[async_trait]
+impl CommandExecutor for OperationExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = ScoreHolderArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let target_name = ObjectiveArgumentConsumer::find_arg(args, ARG_TARGET_OBJECTIVE)?;
+        let operation = OperationArgumentConsumer.find_arg_default_name(args)?;
+        let sources = ScoreHolderArgumentConsumer::find_arg(args, ARG_SOURCE)?;
+        let source_name = ObjectiveArgumentConsumer::find_arg(args, ARG_SOURCE_OBJECTIVE)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let objective = objective_text(find_writable_objective(&scoreboard, target_name)?);
+        find_objective(&scoreboard, source_name)?;
+
+        let mut score = 0;
+        for target in targets {
+            for source in sources {
+                let target_score = scoreboard.score(target, target_name).unwrap_or(0);
+                let source_score = scoreboard.score(source, source_name).unwrap_or(0);
+                let (new_target, new_source) = operation.apply(target_score, source_score)?;
+                score = new_target;
+                scoreboard
+                    .set_score(server, target, target_name, new_target)
+                    .await?;
+                // Only swapping changes the source
+                if new_source != source_score {
+                    scoreboard
+                        .set_score(server, source, source_name, new_source)
+                        .await?;
+                }
+            }
+        }
+        let msg = match targets {
+            [target] => TextComponent::translate(
+                "commands.scoreboard.players.operation.success.single",
+                vec![objective, text(&target), text(&score)],
+            ),
+            _ => TextComponent::translate(
+                "commands.scoreboard.players.operation.success.multiple",
+                vec![objective, text(&targets.len())],
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+fn objectives_tree() -> NonLeafNodeBuilder {
+    literal("objectives")
+        .with_child(literal("list").execute(ListObjectivesExecutor))
+        .with_child(
+            literal("add").with_child(
+                argument_default_name(ObjectiveArgumentConsumer).with_child(
+                    argument_default_name(CriterionArgumentConsumer)
+                        .execute(AddObjectiveExecutor)
+                        .with_child(
+                            argument(ARG_DISPLAY_NAME, MsgArgConsumer)
+                                .execute(AddObjectiveExecutor),
+                        ),
+                ),
+            ),
+        )
+        .with_child(literal("remove").with_child(
+            argument_default_name(ObjectiveArgumentConsumer).execute(RemoveObjectiveExecutor),
+        ))
+        .with_child(
+            literal("setdisplay").with_child(
+                argument_default_name(ScoreboardSlotArgumentConsumer)
+                    .execute(SetDisplayExecutor)
+                    .with_child(
+                        argument_default_name(ObjectiveArgumentConsumer)
+                            .execute(SetDisplayExecutor),
+                    ),
+            ),
+        )
+        .with_child(
+            literal("modify").with_child(
+                argument_default_name(ObjectiveArgumentConsumer)
+                    .with_child(
+                        literal("displayname").with_child(
+                            argument(ARG_DISPLAY_NAME, MsgArgConsumer)
+                                .execute(ModifyObjectiveExecutor::DisplayName),
+                        ),
+                    )
+                    .with_child(
+                        literal("rendertype")
+                            .with_child(
+                                literal("hearts").execute(ModifyObjectiveExecutor::RenderType(
+                                    RenderType::Hearts,
+                                )),
+                            )
+                            .with_child(
+                                literal("integer").execute(ModifyObjectiveExecutor::RenderType(
+                                    RenderType::Integer,
+                                )),
+                            ),
+                    ),
+            ),
+        )
+}
+
+fn change_score(name: &str, executor: ChangeScoreExecutor, min: Option<i32>) -> NonLeafNodeBuilder {
+    let mut score = BoundedNumArgumentConsumer::new().name(ARG_SCORE);
+    if let Some(min) = min {
+        score = score.min(min);
+    }
+    literal(name).with_child(
+        argument(ARG_TARGETS, holders()).with_child(
+            argument_default_name(ObjectiveArgumentConsumer)
+                .with_child(argument(ARG_SCORE, score).execute(executor)),
+        ),
+    )
+}
+
+fn players_tree() -> NonLeafNodeBuilder {
+    literal("players")
+        .with_child(
+            literal("list")
+                .execute(ListScoresExecutor)
+                .with_child(argument(ARG_TARGET, single_holder()).execute(ListScoresExecutor)),
+        )
+        .with_child(
+            literal("get").with_child(argument(ARG_TARGET, single_holder()).with_child(
+                argument_default_name(ObjectiveArgumentConsumer).execute(GetScoreExecutor),
+            )),
+        )
+        .with_child(change_score("set", ChangeScoreExecutor::Set, None))
+        .with_child(change_score("add", ChangeScoreExecutor::Add, Some(0)))
+        .with_child(change_score("remove", ChangeScoreExecutor::Remove, Some(0)))
+        .with_child(
+            literal("reset").with_child(
+                argument(ARG_TARGETS, holders())
+                    .execute(ResetScoresExecutor)
+                    .with_child(
+                        argument_default_name(ObjectiveArgumentConsumer)
+                            .execute(ResetScoresExecutor),
+                    ),
+            ),
+        )
+        .with_child(
+            literal("operation").with_child(
+                argument(ARG_TARGETS, holders()).with_child(
+                    argument(ARG_TARGET_OBJECTIVE, ObjectiveArgumentConsumer).with_child(
+                        argument_default_name(OperationArgumentConsumer).with_child(
+                            argument(ARG_SOURCE, holders()).with_child(
+                                argument(ARG_SOURCE_OBJECTIVE, ObjectiveArgumentConsumer)
+                                    .execute(OperationExecutor),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+        )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(objectives_tree())
+        .with_child(players_tree())
+}
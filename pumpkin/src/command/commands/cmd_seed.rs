@@ -21,9 +21,9 @@ impl CommandExecutor for PumpkinExecutor {
         server: &crate::server::Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let seed = match sender {
-            CommandSender::Player(player) => player.living_entity.entity.world.level.seed.0,
-            _ => match server.worlds.first() {
+        let seed = match sender.world() {
+            Some(world) => world.level.seed.0,
+            None => match server.worlds.first() {
                 Some(world) => world.level.seed.0,
                 None => {
                     return Err(CommandError::GeneralCommandIssue(
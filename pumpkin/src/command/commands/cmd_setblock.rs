@@ -1,13 +1,18 @@
 use async_trait::async_trait;
-use pumpkin_core::text::color::NamedColor;
+use pumpkin_core::math::position::WorldPosition;
 use pumpkin_core::text::TextComponent;
+use pumpkin_world::block::block_registry::get_block_by_state_id;
 
+use crate::block::pop_block;
 use crate::command::args::arg_block::BlockArgumentConsumer;
 use crate::command::args::arg_position_block::BlockPosArgumentConsumer;
 use crate::command::args::{ConsumedArgs, FindArg};
 use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, literal};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+use super::cmd_fill::{is_air, place_blocks};
 
 const NAMES: [&str; 1] = ["setblock"];
 
@@ -35,43 +40,40 @@ impl CommandExecutor for SetblockExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let block = BlockArgumentConsumer::find_arg(args, ARG_BLOCK)?;
-        let block_state_id = block.default_state_id;
         let pos = BlockPosArgumentConsumer::find_arg(args, ARG_BLOCK_POS)?;
-        let mode = self.0;
-        // TODO: allow console to use the command (seed sender.world)
-        let world = sender.world().ok_or(CommandError::InvalidRequirement)?;
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
 
-        let success = match mode {
-            Mode::Destroy => {
-                world.break_block(pos, None).await;
-                world.set_block_state(pos, block_state_id).await;
-                true
-            }
-            Mode::Replace => {
-                world.set_block_state(pos, block_state_id).await;
-                true
+        let current = world
+            .get_block_state_id(pos)
+            .await
+            .map_err(|e| CommandError::OtherPumpkin(e.into()))?;
+        let unchanged = current == block.state_id && block.nbt.is_none();
+        if unchanged || (matches!(self.0, Mode::Keep) && !is_air(current)) {
+            return Err(CommandError::GeneralCommandIssue(
+                "Could not set the block".to_string(),
+            ));
+        }
+        if matches!(self.0, Mode::Destroy) && !is_air(current) {
+            if let Some(broken) = get_block_by_state_id(current) {
+                pop_block(&world, broken, pos, server).await;
             }
-            Mode::Keep => match world.get_block_state(pos).await {
-                Ok(old_state) if old_state.air => {
-                    world.set_block_state(pos, block_state_id).await;
-                    true
-                }
-                Ok(_) => false,
-                Err(e) => return Err(CommandError::OtherPumpkin(e.into())),
-            },
-        };
+        }
+        place_blocks(&world, vec![(pos, block.state_id, block.nbt)]).await;
 
+        let WorldPosition(position) = pos;
         sender
-            .send_message(if success {
-                TextComponent::text_string(format!("Placed block {} at {pos}", block.name,))
-            } else {
-                TextComponent::text_string(format!("Kept block at {pos}"))
-                    .color_named(NamedColor::Red)
-            })
+            .send_message(TextComponent::translate(
+                "commands.setblock.success",
+                vec![
+                    TextComponent::text_string(position.x.to_string()),
+                    TextComponent::text_string(position.y.to_string()),
+                    TextComponent::text_string(position.z.to_string()),
+                ],
+            ))
             .await;
 
         Ok(())
@@ -0,0 +1,222 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    text::TextComponent,
+};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+
+use crate::command::{
+    args::{
+        arg_entity_type::EntityTypeArgumentConsumer, arg_nbt_compound::NbtCompoundArgumentConsumer,
+        arg_position_3d::Position3DArgumentConsumer, ConsumedArgs, FindArg,
+    },
+    tree::CommandTree,
+    tree_builder::argument,
+    CommandError, CommandExecutor, CommandSender, ExecutionContext,
+};
+use crate::entity::{
+    any_entity::AnyEntity, armor_stand::ArmorStandEntity, damage::entity_type_name,
+    horse::HorseEntity, item::ItemEntity, mob::MobEntity,
+};
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["summon"];
+
+const DESCRIPTION: &str = "Summons an entity.";
+
+const ARG_ENTITY: &str = "entity";
+const ARG_POS: &str = "pos";
+const ARG_NBT: &str = "nbt";
+
+/// Entities which aren't mobs and can't be summoned yet
+const NOT_MOBS: [&str; 36] = [
+    "area_effect_cloud",
+    "arrow",
+    "block_display",
+    "breeze_wind_charge",
+    "dragon_fireball",
+    "egg",
+    "end_crystal",
+    "ender_pearl",
+    "evoker_fangs",
+    "experience_bottle",
+    "experience_orb",
+    "eye_of_ender",
+    "falling_block",
+    "fireball",
+    "firework_rocket",
+    "fishing_bobber",
+    "glow_item_frame",
+    "interaction",
+    "item_display",
+    "item_frame",
+    "leash_knot",
+    "llama_spit",
+    "marker",
+    "ominous_item_spawner",
+    "painting",
+    "player",
+    "potion",
+    "shulker_bullet",
+    "small_fireball",
+    "snowball",
+    "spectral_arrow",
+    "text_display",
+    "tnt",
+    "trident",
+    "wind_charge",
+    "wither_skull",
+];
+
+fn is_mob(entity_type: &EntityType) -> bool {
+    let name = entity_type.name();
+    !(name.ends_with("boat")
+        || name.ends_with("raft")
+        || name.ends_with("minecart")
+        || NOT_MOBS.contains(&name))
+}
+
+/// Spawns a mob, horses get their own entity. Mobs summoned without data are equipped like
+/// naturally spawned ones, like vanilla's `Mob.finalizeSpawn`
+async fn summon_mob(
+    world: &Arc<World>,
+    server: &Server,
+    entity_type: EntityType,
+    pos: Vector3<f64>,
+    nbt: &NbtCompound,
+) {
+    let entity_id = server.new_entity_id();
+    if let Some(horse) = HorseEntity::new(entity_id, world.clone(), entity_type.clone()) {
+        horse.entity().set_pos(pos);
+        let horse = Arc::new(horse);
+        AnyEntity::Other(horse.clone()).read_nbt(nbt).await;
+        world.add_horse_entity(horse).await;
+        return;
+    }
+    let mob = Arc::new(MobEntity::new(
+        entity_id,
+        world.clone(),
+        entity_type,
+        pos,
+        0.0,
+    ));
+    if nbt.child_tags.is_empty() {
+        world.equip_spawned_mob(&mob).await;
+    }
+    AnyEntity::Mob(mob.clone()).read_nbt(nbt).await;
+    world.add_mob(mob).await;
+}
+
+/// Puts a new entity of the type into the world with the data applied, like vanilla's
+/// `SummonCommand.createEntity`. Returns whether the type can be summoned
+async fn summon(
+    world: &Arc<World>,
+    server: &Server,
+    entity_type: EntityType,
+    pos: Vector3<f64>,
+    nbt: &NbtCompound,
+) -> bool {
+    match entity_type {
+        EntityType::LightningBolt => {
+            let block = Vector3::new(
+                pos.x.floor() as i32,
+                pos.y.floor() as i32,
+                pos.z.floor() as i32,
+            );
+            world.strike_lightning(WorldPosition(block), server).await;
+        }
+        EntityType::Item => {
+            // Items are read like the ones saved in chunks, which need a position
+            let mut item_nbt = nbt.clone();
+            item_nbt.insert(
+                "Pos".to_string(),
+                NbtTag::List(vec![
+                    NbtTag::Double(pos.x),
+                    NbtTag::Double(pos.y),
+                    NbtTag::Double(pos.z),
+                ]),
+            );
+            let Some(item) = ItemEntity::from_nbt(server.new_entity_id(), world.clone(), &item_nbt)
+            else {
+                return false;
+            };
+            let item = Arc::new(item);
+            AnyEntity::Item(item.clone()).read_nbt(nbt).await;
+            world.add_item_entity(item).await;
+        }
+        EntityType::ArmorStand => {
+            let armor_stand = Arc::new(ArmorStandEntity::new(
+                server.new_entity_id(),
+                world.clone(),
+                pos,
+                0.0,
+            ));
+            AnyEntity::Other(armor_stand.clone()).read_nbt(nbt).await;
+            world.add_armor_stand(armor_stand).await;
+        }
+        entity_type if is_mob(&entity_type) => {
+            summon_mob(world, server, entity_type, pos, nbt).await;
+        }
+        _ => return false,
+    }
+    true
+}
+
+struct SummonExecutor;
+
+#[async_trait]
+impl CommandExecutor for SummonExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let entity_type = EntityTypeArgumentConsumer::find_arg(args, ARG_ENTITY)?;
+        let context = ExecutionContext::of(sender, server).await;
+        let pos = if args.contains_key(ARG_POS) {
+            Position3DArgumentConsumer::find_arg(args, ARG_POS)?
+        } else {
+            context.position
+        };
+        let mut nbt = if args.contains_key(ARG_NBT) {
+            NbtCompoundArgumentConsumer::find_arg(args, ARG_NBT)?
+        } else {
+            NbtCompound::new()
+        };
+        // The entity is summoned where the command says, wherever the data puts it
+        nbt.remove("Pos");
+
+        if !summon(&context.world, server, entity_type.clone(), pos, &nbt).await {
+            return Err(CommandError::GeneralCommandIssue(
+                "Unable to summon entity".to_string(),
+            ));
+        }
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.summon.success",
+                vec![entity_type_name(&entity_type)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).with_child(
+        argument(ARG_ENTITY, EntityTypeArgumentConsumer)
+            .execute(SummonExecutor)
+            .with_child(
+                argument(ARG_POS, Position3DArgumentConsumer)
+                    .execute(SummonExecutor)
+                    .with_child(
+                        argument(ARG_NBT, NbtCompoundArgumentConsumer).execute(SummonExecutor),
+                    ),
+            ),
+    )
+}
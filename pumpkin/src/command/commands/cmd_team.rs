@@ -0,0 +1,477 @@
+use async_trait::async_trait;
+use pumpkin_core::text::TextComponent;
+
+use crate::command::args::arg_bool::BoolArgConsumer;
+use crate::command::args::arg_color::ColorArgumentConsumer;
+use crate::command::args::arg_message::MsgArgConsumer;
+use crate::command::args::arg_score_holder::ScoreHolderArgumentConsumer;
+use crate::command::args::arg_team::TeamArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree_builder::{argument, argument_default_name, literal, NonLeafNodeBuilder};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::world::scoreboard::{Scoreboard, ScoreboardError};
+use crate::world::team::{team_color_name, CollisionRule, NameTagVisibility, Team};
+
+const NAMES: [&str; 1] = ["team"];
+
+const DESCRIPTION: &str = "Manages teams and their members.";
+
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_MEMBERS: &str = "members";
+const ARG_VALUE: &str = "value";
+
+const fn members() -> ScoreHolderArgumentConsumer {
+    ScoreHolderArgumentConsumer { multiple: true }
+}
+
+fn text(value: &impl ToString) -> TextComponent<'static> {
+    TextComponent::text_string(value.to_string())
+}
+
+fn unchanged(reason: &str) -> CommandError {
+    CommandError::GeneralCommandIssue(format!("Nothing changed. {reason}"))
+}
+
+fn find_team<'a>(scoreboard: &'a Scoreboard, name: &str) -> Result<&'a Team, CommandError> {
+    scoreboard
+        .team(name)
+        .ok_or_else(|| ScoreboardError::UnknownTeam(name.to_string()).into())
+}
+
+/// Teams are shown by their colored display name in brackets, like vanilla's
+/// `PlayerTeam.getFormattedDisplayName`
+fn team_text(team: &Team) -> TextComponent<'static> {
+    TextComponent::text("[")
+        .add_child(team.formatted_name())
+        .add_child(TextComponent::text("]"))
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let scoreboard = server.scoreboard.lock().await;
+        let msg = if args.is_empty() {
+            let teams = scoreboard.teams();
+            if teams.is_empty() {
+                TextComponent::translate("commands.team.list.teams.empty", vec![])
+            } else {
+                let names: Vec<_> = teams
+                    .iter()
+                    .map(|team| format!("[{}]", team.display_name))
+                    .collect();
+                TextComponent::translate(
+                    "commands.team.list.teams.success",
+                    vec![text(&teams.len()), text(&names.join(", "))],
+                )
+            }
+        } else {
+            let team = find_team(
+                &scoreboard,
+                TeamArgumentConsumer.find_arg_default_name(args)?,
+            )?;
+            if team.members().is_empty() {
+                TextComponent::translate("commands.team.list.members.empty", vec![team_text(team)])
+            } else {
+                TextComponent::translate(
+                    "commands.team.list.members.success",
+                    vec![
+                        team_text(team),
+                        text(&team.members().len()),
+                        text(&team.members().join(", ")),
+                    ],
+                )
+            }
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct AddExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer.find_arg_default_name(args)?;
+        let display_name = if args.contains_key(ARG_DISPLAY_NAME) {
+            MsgArgConsumer::find_arg(args, ARG_DISPLAY_NAME)?.to_string()
+        } else {
+            name.to_string()
+        };
+        let team = Team::new(name.to_string(), display_name);
+        let msg = TextComponent::translate("commands.team.add.success", vec![team_text(&team)]);
+        server
+            .scoreboard
+            .lock()
+            .await
+            .add_team(server, team)
+            .await?;
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let team = team_text(find_team(&scoreboard, name)?);
+        scoreboard.remove_team(server, name).await?;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.remove.success",
+                vec![team],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct EmptyExecutor;
+
+#[async_trait]
+impl CommandExecutor for EmptyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let team = find_team(&scoreboard, name)?;
+        if team.members().is_empty() {
+            return Err(unchanged("That team is already empty"));
+        }
+        let team_name = team_text(team);
+        let members = team.members().to_vec();
+        let count = scoreboard.leave_team(server, &members).await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.empty.success",
+                vec![text(&count), team_name],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+/// The members given or the sender itself
+fn members_or_sender(
+    sender: &CommandSender<'_>,
+    args: &ConsumedArgs<'_>,
+) -> Result<Vec<String>, CommandError> {
+    if args.contains_key(ARG_MEMBERS) {
+        return Ok(ScoreHolderArgumentConsumer::find_arg(args, ARG_MEMBERS)?.to_vec());
+    }
+    sender
+        .executor()
+        .and_then(|entity| entity.score_holder())
+        .map(|holder| vec![holder])
+        .ok_or_else(|| {
+            CommandError::GeneralCommandIssue(
+                "An entity is required to run this command here".to_string(),
+            )
+        })
+}
+
+struct JoinExecutor;
+
+#[async_trait]
+impl CommandExecutor for JoinExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer.find_arg_default_name(args)?;
+        let members = members_or_sender(sender, args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let team = team_text(find_team(&scoreboard, name)?);
+        scoreboard.join_team(server, name, &members).await?;
+        let msg = match members.as_slice() {
+            [member] => TextComponent::translate(
+                "commands.team.join.success.single",
+                vec![text(&member), team],
+            ),
+            _ => TextComponent::translate(
+                "commands.team.join.success.multiple",
+                vec![text(&members.len()), team],
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+struct LeaveExecutor;
+
+#[async_trait]
+impl CommandExecutor for LeaveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let members = ScoreHolderArgumentConsumer::find_arg(args, ARG_MEMBERS)?;
+        server
+            .scoreboard
+            .lock()
+            .await
+            .leave_team(server, members)
+            .await;
+        let msg = match members {
+            [member] => {
+                TextComponent::translate("commands.team.leave.success.single", vec![text(&member)])
+            }
+            _ => TextComponent::translate(
+                "commands.team.leave.success.multiple",
+                vec![text(&members.len())],
+            ),
+        };
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+/// The options of `/team modify`, the rules are chosen by literals
+enum ModifyExecutor {
+    DisplayName,
+    Color,
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+    NameTagVisibility(NameTagVisibility),
+    CollisionRule(CollisionRule),
+    Prefix,
+    Suffix,
+}
+
+impl ModifyExecutor {
+    /// Checks that the option changes and returns the feedback for it
+    fn feedback(
+        &self,
+        team: &Team,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<TextComponent<'static>, CommandError> {
+        let team_name = team_text(team);
+        let msg = match self {
+            Self::DisplayName => {
+                if team.display_name == MsgArgConsumer::find_arg(args, ARG_VALUE)? {
+                    return Err(unchanged("That team already has that name"));
+                }
+                TextComponent::translate("commands.team.option.name.success", vec![team_name])
+            }
+            Self::Color => {
+                let color = ColorArgumentConsumer::find_arg(args, ARG_VALUE)?;
+                if team.color == color {
+                    return Err(unchanged("That team already has that color"));
+                }
+                TextComponent::translate(
+                    "commands.team.option.color.success",
+                    vec![team_name, text(&color.map_or("reset", team_color_name))],
+                )
+            }
+            Self::FriendlyFire | Self::SeeFriendlyInvisibles => {
+                let value = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                let (current, key, reason) = if matches!(self, Self::FriendlyFire) {
+                    (
+                        team.allow_friendly_fire,
+                        "friendlyfire",
+                        if value {
+                            "Friendly fire is already enabled for that team"
+                        } else {
+                            "Friendly fire is already disabled for that team"
+                        },
+                    )
+                } else {
+                    (
+                        team.see_friendly_invisibles,
+                        "seeFriendlyInvisibles",
+                        if value {
+                            "That team can already see invisible teammates"
+                        } else {
+                            "That team already can't see invisible teammates"
+                        },
+                    )
+                };
+                if current == value {
+                    return Err(unchanged(reason));
+                }
+                let state = if value { "enabled" } else { "disabled" };
+                TextComponent::translate(
+                    format!("commands.team.option.{key}.{state}"),
+                    vec![team_name],
+                )
+            }
+            Self::NameTagVisibility(rule) => {
+                if team.name_tag_visibility == *rule {
+                    return Err(unchanged("Nametag visibility is already that value"));
+                }
+                TextComponent::translate(
+                    "commands.team.option.nametagVisibility.success",
+                    vec![team_name, text(&rule.name())],
+                )
+            }
+            Self::CollisionRule(rule) => {
+                if team.collision_rule == *rule {
+                    return Err(unchanged("Collision rule is already that value"));
+                }
+                TextComponent::translate(
+                    "commands.team.option.collisionRule.success",
+                    vec![team_name, text(&rule.name())],
+                )
+            }
+            Self::Prefix | Self::Suffix => {
+                let value = MsgArgConsumer::find_arg(args, ARG_VALUE)?;
+                let key = if matches!(self, Self::Prefix) {
+                    "commands.team.option.prefix.success"
+                } else {
+                    "commands.team.option.suffix.success"
+                };
+                TextComponent::translate(key, vec![text(&value)])
+            }
+        };
+        Ok(msg)
+    }
+
+    fn apply(&self, team: &mut Team, args: &ConsumedArgs<'_>) -> Result<(), CommandError> {
+        match self {
+            Self::DisplayName => {
+                team.display_name = MsgArgConsumer::find_arg(args, ARG_VALUE)?.to_string();
+            }
+            Self::Color => team.color = ColorArgumentConsumer::find_arg(args, ARG_VALUE)?,
+            Self::FriendlyFire => {
+                team.allow_friendly_fire = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+            }
+            Self::SeeFriendlyInvisibles => {
+                team.see_friendly_invisibles = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+            }
+            Self::NameTagVisibility(rule) => team.name_tag_visibility = *rule,
+            Self::CollisionRule(rule) => team.collision_rule = *rule,
+            Self::Prefix => team.prefix = MsgArgConsumer::find_arg(args, ARG_VALUE)?.to_string(),
+            Self::Suffix => team.suffix = MsgArgConsumer::find_arg(args, ARG_VALUE)?.to_string(),
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ModifyExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = TeamArgumentConsumer.find_arg_default_name(args)?;
+        let mut scoreboard = server.scoreboard.lock().await;
+        let msg = self.feedback(find_team(&scoreboard, name)?, args)?;
+        let mut result = Ok(());
+        scoreboard
+            .modify_team(server, name, |team| result = self.apply(team, args))
+            .await?;
+        result?;
+        sender.send_message(msg).await;
+        Ok(())
+    }
+}
+
+fn modify_tree() -> NonLeafNodeBuilder {
+    let mut team =
+        argument_default_name(TeamArgumentConsumer)
+            .with_child(literal("displayName").with_child(
+                argument(ARG_VALUE, MsgArgConsumer).execute(ModifyExecutor::DisplayName),
+            ))
+            .with_child(literal("color").with_child(
+                argument(ARG_VALUE, ColorArgumentConsumer).execute(ModifyExecutor::Color),
+            ))
+            .with_child(literal("friendlyFire").with_child(
+                argument(ARG_VALUE, BoolArgConsumer).execute(ModifyExecutor::FriendlyFire),
+            ))
+            .with_child(literal("seeFriendlyInvisibles").with_child(
+                argument(ARG_VALUE, BoolArgConsumer).execute(ModifyExecutor::SeeFriendlyInvisibles),
+            ))
+            .with_child(
+                literal("prefix").with_child(
+                    argument(ARG_VALUE, MsgArgConsumer).execute(ModifyExecutor::Prefix),
+                ),
+            )
+            .with_child(
+                literal("suffix").with_child(
+                    argument(ARG_VALUE, MsgArgConsumer).execute(ModifyExecutor::Suffix),
+                ),
+            );
+    let mut name_tag_visibility = literal("nametagVisibility");
+    for rule in NameTagVisibility::ALL {
+        name_tag_visibility = name_tag_visibility
+            .with_child(literal(rule.name()).execute(ModifyExecutor::NameTagVisibility(rule)));
+    }
+    let mut collision_rule = literal("collisionRule");
+    for rule in CollisionRule::ALL {
+        collision_rule = collision_rule
+            .with_child(literal(rule.name()).execute(ModifyExecutor::CollisionRule(rule)));
+    }
+    team = team
+        .with_child(name_tag_visibility)
+        .with_child(collision_rule);
+    literal("modify").with_child(team)
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(
+            literal("list")
+                .execute(ListExecutor)
+                .with_child(argument_default_name(TeamArgumentConsumer).execute(ListExecutor)),
+        )
+        .with_child(
+            literal("add").with_child(
+                argument_default_name(TeamArgumentConsumer)
+                    .execute(AddExecutor)
+                    .with_child(argument(ARG_DISPLAY_NAME, MsgArgConsumer).execute(AddExecutor)),
+            ),
+        )
+        .with_child(
+            literal("remove")
+                .with_child(argument_default_name(TeamArgumentConsumer).execute(RemoveExecutor)),
+        )
+        .with_child(
+            literal("empty")
+                .with_child(argument_default_name(TeamArgumentConsumer).execute(EmptyExecutor)),
+        )
+        .with_child(
+            literal("join").with_child(
+                argument_default_name(TeamArgumentConsumer)
+                    .execute(JoinExecutor)
+                    .with_child(argument(ARG_MEMBERS, members()).execute(JoinExecutor)),
+            ),
+        )
+        .with_child(
+            literal("leave").with_child(argument(ARG_MEMBERS, members()).execute(LeaveExecutor)),
+        )
+        .with_child(modify_tree())
+}
@@ -12,6 +12,8 @@ use crate::command::tree::CommandTree;
 use crate::command::tree_builder::{argument, literal};
 use crate::command::CommandError;
 use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::any_entity::AnyEntity;
+use crate::server::Server;
 
 const NAMES: [&str; 2] = ["teleport", "tp"];
 const DESCRIPTION: &str = "Teleports entities, including players."; // todo
@@ -49,225 +51,198 @@ fn yaw_pitch_facing_position(
     (yaw_degrees as f32, pitch_degrees as f32)
 }
 
-struct TpEntitiesToEntityExecutor;
-
-#[async_trait]
-impl CommandExecutor for TpEntitiesToEntityExecutor {
-    async fn execute<'a>(
-        &self,
-        _sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
-
-        let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
-        let pos = destination.living_entity.entity.pos.load();
-
-        for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
-        }
-
-        Ok(())
-    }
+/// Where the targets look after teleporting to a location
+#[derive(Clone, Copy)]
+enum Look {
+    /// Where they looked before
+    Unchanged,
+    /// The given rotation
+    Rotation,
+    /// Towards the given location
+    FacingLocation,
+    /// Towards the feet or the eyes of the given entity
+    FacingEntity { eyes: bool },
 }
 
-struct TpEntitiesToPosFacingPosExecutor;
-
-#[async_trait]
-impl CommandExecutor for TpEntitiesToPosFacingPosExecutor {
-    async fn execute<'a>(
-        &self,
-        _sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
-
-        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
-
-        let facing_pos = Position3DArgumentConsumer::find_arg(args, ARG_FACING_LOCATION)?;
-        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_pos);
-
-        for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
-        }
-
-        Ok(())
-    }
-}
-
-struct TpEntitiesToPosFacingEntityExecutor;
-
-#[async_trait]
-impl CommandExecutor for TpEntitiesToPosFacingEntityExecutor {
-    async fn execute<'a>(
-        &self,
-        _sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
-
-        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
-
-        let facing_entity = &EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?
-            .living_entity
-            .entity;
-        let (yaw, pitch) = yaw_pitch_facing_position(&pos, &facing_entity.pos.load());
-
-        for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
-        }
-
-        Ok(())
+/// The targets of the command, the executor itself when no targets are given
+fn find_targets(
+    sender: &CommandSender,
+    args: &ConsumedArgs,
+) -> Result<Vec<AnyEntity>, CommandError> {
+    if args.contains_key(ARG_TARGETS) {
+        return Ok(EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?.to_vec());
     }
+    let executor = sender.executor().ok_or_else(|| {
+        CommandError::GeneralCommandIssue(
+            "An entity is required to run this command here".to_string(),
+        )
+    })?;
+    Ok(vec![executor])
 }
 
-struct TpEntitiesToPosWithRotationExecutor;
-
-#[async_trait]
-impl CommandExecutor for TpEntitiesToPosWithRotationExecutor {
-    async fn execute<'a>(
-        &self,
-        _sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
-
-        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
-
-        let (yaw, pitch) = RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?;
-
-        for target in targets {
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+/// The message telling how many of the targets were teleported to the destination
+fn success_message(
+    kind: &str,
+    teleported: &[&AnyEntity],
+    destination: Vec<TextComponent<'static>>,
+) -> Result<TextComponent<'static>, CommandError> {
+    let (key, target) = match teleported {
+        [] => {
+            return Err(CommandError::GeneralCommandIssue(
+                "Entities can't be teleported into another dimension".to_string(),
+            ))
         }
-
-        Ok(())
-    }
+        [target] => ("single", target.display_name()),
+        teleported => (
+            "multiple",
+            TextComponent::text_string(teleported.len().to_string()),
+        ),
+    };
+    let mut args = vec![target];
+    args.extend(destination);
+    Ok(TextComponent::translate(
+        format!("commands.teleport.success.{kind}.{key}"),
+        args,
+    ))
 }
 
-struct TpEntitiesToPosExecutor;
+struct TpToEntityExecutor;
 
 #[async_trait]
-impl CommandExecutor for TpEntitiesToPosExecutor {
+impl CommandExecutor for TpToEntityExecutor {
     async fn execute<'a>(
         &self,
-        _sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let targets = EntitiesArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let targets = find_targets(sender, args)?;
 
-        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
-
-        for target in targets {
-            let yaw = target.living_entity.entity.yaw.load();
-            let pitch = target.living_entity.entity.pitch.load();
-            target.living_entity.entity.teleport(pos, yaw, pitch).await;
+        let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
+        let entity = destination.entity();
+        let world = entity.world();
+        let pos = entity.pos.load();
+        let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+
+        let mut teleported = Vec::new();
+        for target in &targets {
+            if world.teleport_entity(target, pos, yaw, pitch).await {
+                teleported.push(target);
+            }
         }
 
+        let message = success_message("entity", &teleported, vec![destination.display_name()])?;
+        sender.send_message(message).await;
         Ok(())
     }
 }
 
-struct TpSelfToEntityExecutor;
+struct TpToPosExecutor(Look);
 
 #[async_trait]
-impl CommandExecutor for TpSelfToEntityExecutor {
+impl CommandExecutor for TpToPosExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let destination = EntityArgumentConsumer::find_arg(args, ARG_DESTINATION)?;
-        let pos = destination.living_entity.entity.pos.load();
+        let targets = find_targets(sender, args)?;
 
-        match sender {
-            CommandSender::Player(player) => {
-                let yaw = player.living_entity.entity.yaw.load();
-                let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
-            }
-            _ => {
-                sender
-                    .send_message(TextComponent::text(
-                        "Only players may execute this command.",
-                    ))
-                    .await;
+        let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+
+        let facing = match self.0 {
+            Look::Unchanged | Look::Rotation => None,
+            Look::FacingLocation => Some(Position3DArgumentConsumer::find_arg(
+                args,
+                ARG_FACING_LOCATION,
+            )?),
+            Look::FacingEntity { eyes } => {
+                let facing_entity = EntityArgumentConsumer::find_arg(args, ARG_FACING_ENTITY)?;
+                let entity = facing_entity.entity();
+                Some(if eyes {
+                    entity.eye_position()
+                } else {
+                    entity.pos.load()
+                })
             }
         };
+        let rotation = if matches!(self.0, Look::Rotation) {
+            Some(RotationArgumentConsumer::find_arg(args, ARG_ROTATION)?)
+        } else {
+            None
+        };
 
-        Ok(())
-    }
-}
-
-struct TpSelfToPosExecutor;
-
-#[async_trait]
-impl CommandExecutor for TpSelfToPosExecutor {
-    async fn execute<'a>(
-        &self,
-        sender: &mut CommandSender<'a>,
-        _server: &crate::server::Server,
-        args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        match sender {
-            CommandSender::Player(player) => {
-                let pos = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
-                let yaw = player.living_entity.entity.yaw.load();
-                let pitch = player.living_entity.entity.pitch.load();
-                player.living_entity.entity.teleport(pos, yaw, pitch).await;
-            }
-            _ => {
-                sender
-                    .send_message(TextComponent::text(
-                        "Only players may execute this command.",
-                    ))
-                    .await;
+        let mut teleported = Vec::new();
+        for target in &targets {
+            let entity = target.entity();
+            let (yaw, pitch) = match (rotation, facing) {
+                (Some(rotation), _) => rotation,
+                // The targets look from their eyes at the new position
+                (None, Some(facing)) => {
+                    let eyes =
+                        Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z);
+                    yaw_pitch_facing_position(&eyes, &facing)
+                }
+                (None, None) => (entity.yaw.load(), entity.pitch.load()),
+            };
+            if world.teleport_entity(target, pos, yaw, pitch).await {
+                teleported.push(target);
             }
-        };
+        }
 
+        let coordinate = |value: f64| TextComponent::text_string(format!("{value:.6}"));
+        let message = success_message(
+            "location",
+            &teleported,
+            vec![coordinate(pos.x), coordinate(pos.y), coordinate(pos.z)],
+        )?;
+        sender.send_message(message).await;
         Ok(())
     }
 }
 
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
-        .with_child(argument(ARG_LOCATION, Position3DArgumentConsumer).execute(TpSelfToPosExecutor))
         .with_child(
-            argument(ARG_DESTINATION, EntityArgumentConsumer).execute(TpSelfToEntityExecutor),
+            argument(ARG_LOCATION, Position3DArgumentConsumer)
+                .execute(TpToPosExecutor(Look::Unchanged)),
         )
+        .with_child(argument(ARG_DESTINATION, EntityArgumentConsumer).execute(TpToEntityExecutor))
         .with_child(
             argument(ARG_TARGETS, EntitiesArgumentConsumer)
                 .with_child(
                     argument(ARG_LOCATION, Position3DArgumentConsumer)
-                        .execute(TpEntitiesToPosExecutor)
+                        .execute(TpToPosExecutor(Look::Unchanged))
                         .with_child(
                             argument(ARG_ROTATION, RotationArgumentConsumer)
-                                .execute(TpEntitiesToPosWithRotationExecutor),
+                                .execute(TpToPosExecutor(Look::Rotation)),
                         )
                         .with_child(
                             literal("facing")
                                 .with_child(
                                     literal("entity").with_child(
                                         argument(ARG_FACING_ENTITY, EntityArgumentConsumer)
-                                            .execute(TpEntitiesToPosFacingEntityExecutor),
+                                            .execute(TpToPosExecutor(Look::FacingEntity {
+                                                eyes: false,
+                                            }))
+                                            .with_child(literal("feet").execute(TpToPosExecutor(
+                                                Look::FacingEntity { eyes: false },
+                                            )))
+                                            .with_child(literal("eyes").execute(TpToPosExecutor(
+                                                Look::FacingEntity { eyes: true },
+                                            ))),
                                     ),
                                 )
                                 .with_child(
                                     argument(ARG_FACING_LOCATION, Position3DArgumentConsumer)
-                                        .execute(TpEntitiesToPosFacingPosExecutor),
+                                        .execute(TpToPosExecutor(Look::FacingLocation)),
                                 ),
                         ),
                 )
                 .with_child(
-                    argument(ARG_DESTINATION, EntityArgumentConsumer)
-                        .execute(TpEntitiesToEntityExecutor),
+                    argument(ARG_DESTINATION, EntityArgumentConsumer).execute(TpToEntityExecutor),
                 ),
         )
 }
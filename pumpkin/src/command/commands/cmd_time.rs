@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_core::text::color::{Color, NamedColor};
 use pumpkin_core::text::TextComponent;
@@ -8,17 +10,44 @@ use crate::command::tree_builder::{argument_default_name, literal};
 use crate::command::{
     tree::CommandTree, CommandError, CommandExecutor, CommandSender, ConsumedArgs,
 };
+use crate::server::Server;
+use crate::world::{level_time::DayTime, World};
 
 const NAMES: [&str; 1] = ["time"];
 
 const DESCRIPTION: &str = "Query the world time.";
 
-// TODO: This should be either higher or not bounded
 fn arg_number() -> BoundedNumArgumentConsumer<i32> {
-    BoundedNumArgumentConsumer::new()
-        .name("time")
-        .min(0)
-        .max(24000)
+    BoundedNumArgumentConsumer::new().name("time").min(0)
+}
+
+/// Queries run on the sender's world, the console queries the first world
+fn time_world(sender: &CommandSender<'_>, server: &Server) -> Arc<World> {
+    sender.world().unwrap_or_else(|| {
+        server
+            .worlds
+            .first()
+            .cloned()
+            .expect("There should always be at least one world")
+    })
+}
+
+/// Changes the time of day in every world like vanilla does, returns the new time of day in the
+/// sender's world
+async fn change_time(sender: &CommandSender<'_>, server: &Server, mode: Mode, time: i64) -> i64 {
+    for world in &server.worlds {
+        let mut level_time = world.level_time.lock().await;
+        match mode {
+            Mode::Add => level_time.add_time(time),
+            Mode::Set => level_time.set_time(time),
+        }
+        level_time.send_time(world).await;
+    }
+    time_world(sender, server)
+        .level_time
+        .lock()
+        .await
+        .query_daytime()
 }
 
 #[derive(Clone, Copy)]
@@ -41,32 +70,23 @@ impl CommandExecutor for TimeQueryExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        server: &crate::server::Server,
+        server: &Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let mode = self.0;
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = time_world(sender, server);
         let level_time = world.level_time.lock().await;
 
-        let msg = match mode {
-            QueryMode::DayTime => {
-                let curr_time = level_time.query_daytime();
-                format!("Daytime is: {curr_time}")
-            }
-            QueryMode::GameTime => {
-                let curr_time = level_time.query_gametime();
-                format!("Gametime is: {curr_time}")
-            }
-            QueryMode::Day => {
-                let curr_time = level_time.query_day();
-                format!("Day is: {curr_time}")
-            }
+        let curr_time = match mode {
+            QueryMode::DayTime => level_time.query_daytime(),
+            QueryMode::GameTime => level_time.query_gametime(),
+            QueryMode::Day => level_time.query_day(),
         };
+        drop(level_time);
 
-        sender.send_message(TextComponent::text(&msg)).await;
+        sender
+            .send_message(TextComponent::text(&format!("The time is {curr_time}")))
+            .await;
         Ok(())
     }
 }
@@ -78,7 +98,7 @@ impl CommandExecutor for TimeChangeExecutor {
     async fn execute<'a>(
         &self,
         sender: &mut CommandSender<'a>,
-        server: &crate::server::Server,
+        server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
         let time_count = match arg_number().find_arg_default_name(args) {
@@ -94,30 +114,37 @@ impl CommandExecutor for TimeChangeExecutor {
                 return Ok(());
             }
         };
-        let mode = self.0;
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be at least one world");
-        let mut level_time = world.level_time.lock().await;
-
-        let msg = match mode {
-            Mode::Add => {
-                // add
-                level_time.add_time(time_count.into());
-                level_time.send_time(world).await;
-                let curr_time = level_time.query_daytime();
-                format!("Added {time_count} time for result: {curr_time}")
-            }
-            Mode::Set => {
-                // set
-                level_time.set_time(time_count.into());
-                level_time.send_time(world).await;
-                format!("Changed time to: {time_count}")
-            }
+        let curr_time = change_time(sender, server, self.0, time_count.into()).await;
+        // Setting reports the time set, adding the time of day it results in
+        let result = match self.0 {
+            Mode::Add => curr_time,
+            Mode::Set => time_count.into(),
         };
 
-        sender.send_message(TextComponent::text(&msg)).await;
+        sender
+            .send_message(TextComponent::text(&format!("Set the time to {result}")))
+            .await;
+        Ok(())
+    }
+}
+
+/// Sets the time to one of the times of day known by name, like `/time set night`
+struct TimeSetNamedExecutor(DayTime);
+
+#[async_trait]
+impl CommandExecutor for TimeSetNamedExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let time = self.0 as i64;
+        change_time(sender, server, Mode::Set, time).await;
+
+        sender
+            .send_message(TextComponent::text(&format!("Set the time to {time}")))
+            .await;
         Ok(())
     }
 }
@@ -136,8 +163,13 @@ pub fn init_command_tree() -> CommandTree {
                 .with_child(literal("day").execute(TimeQueryExecutor(QueryMode::Day))),
         )
         .with_child(
-            literal("set").with_child(
-                argument_default_name(arg_number()).execute(TimeChangeExecutor(Mode::Set)),
-            ),
+            literal("set")
+                .with_child(literal("day").execute(TimeSetNamedExecutor(DayTime::Day)))
+                .with_child(literal("noon").execute(TimeSetNamedExecutor(DayTime::Noon)))
+                .with_child(literal("night").execute(TimeSetNamedExecutor(DayTime::Night)))
+                .with_child(literal("midnight").execute(TimeSetNamedExecutor(DayTime::Midnight)))
+                .with_child(
+                    argument_default_name(arg_number()).execute(TimeChangeExecutor(Mode::Set)),
+                ),
         )
 }
@@ -7,7 +7,7 @@ use pumpkin_protocol::codec::var_int::VarInt;
 use crate::command::args::arg_bounded_num::BoundedNumArgumentConsumer;
 use crate::command::args::arg_players::PlayersArgumentConsumer;
 use crate::command::args::arg_simple::SimpleArgConsumer;
-use crate::command::args::{Arg, FindArgDefaultName};
+use crate::command::args::{Arg, FindArg, FindArgDefaultName};
 use crate::command::dispatcher::CommandError::{InvalidConsumption, InvalidRequirement};
 use crate::command::tree_builder::{argument, argument_default_name, require};
 use crate::command::{
@@ -57,7 +57,7 @@ impl CommandExecutor for TransferTargetSelf {
             }
         };
 
-        if let CommandSender::Player(player) = sender {
+        if let Some(player) = sender.as_player() {
             let name = &player.gameprofile.name;
             log::info!("[{name}: Transferring {name} to {hostname}:{port}]");
             player
@@ -99,9 +99,7 @@ impl CommandExecutor for TransferTargetPlayer {
             }
         };
 
-        let Some(Arg::Players(players)) = args.get(ARG_PLAYERS) else {
-            return Err(InvalidConsumption(Some(ARG_PLAYERS.into())));
-        };
+        let players = PlayersArgumentConsumer::find_arg(args, ARG_PLAYERS)?;
 
         for p in players {
             p.client
@@ -0,0 +1,122 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use async_trait::async_trait;
+use pumpkin_core::text::{
+    color::{Color, NamedColor},
+    TextComponent,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    command::{
+        args::{arg_bounded_num::BoundedNumArgumentConsumer, ConsumedArgs, FindArgDefaultName},
+        tree::CommandTree,
+        tree_builder::{argument_default_name, literal},
+        CommandError, CommandExecutor, CommandSender,
+    },
+    server::Server,
+    world::{
+        weather::{CLEAR_DELAY, RAIN_DURATION, THUNDER_DURATION},
+        World,
+    },
+};
+
+const NAMES: [&str; 1] = ["weather"];
+
+const DESCRIPTION: &str = "Sets the weather.";
+
+/// The duration in ticks, vanilla doesn't allow more than a million
+fn duration_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("duration")
+        .min(1)
+        .max(1_000_000)
+}
+
+#[derive(Clone, Copy)]
+enum WeatherKind {
+    Clear,
+    Rain,
+    Thunder,
+}
+
+impl WeatherKind {
+    /// How long the weather lasts when no duration is given
+    const fn default_duration(self) -> RangeInclusive<i32> {
+        match self {
+            Self::Clear => CLEAR_DELAY,
+            Self::Rain => RAIN_DURATION,
+            Self::Thunder => THUNDER_DURATION,
+        }
+    }
+}
+
+/// The weather changes on the sender's world, the console changes the first world
+fn weather_world(sender: &CommandSender<'_>, server: &Server) -> Arc<World> {
+    sender.world().unwrap_or_else(|| {
+        server
+            .worlds
+            .first()
+            .cloned()
+            .expect("There should always be at least one world")
+    })
+}
+
+struct WeatherExecutor(WeatherKind);
+
+#[async_trait]
+impl CommandExecutor for WeatherExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let duration = match duration_consumer().find_arg_default_name(args) {
+            Err(_) => thread_rng().gen_range(self.0.default_duration()),
+            Ok(Ok(duration)) => duration,
+            Ok(Err(())) => {
+                sender
+                    .send_message(
+                        TextComponent::text("Duration is too large or too small.")
+                            .color(Color::Named(NamedColor::Red)),
+                    )
+                    .await;
+                return Ok(());
+            }
+        };
+
+        let world = weather_world(sender, server);
+        let mut weather = world.weather.lock().await;
+        let message = match self.0 {
+            WeatherKind::Clear => {
+                weather.set(duration, 0, false, false);
+                "Set the weather to clear"
+            }
+            WeatherKind::Rain => {
+                weather.set(0, duration, true, false);
+                "Set the weather to rain"
+            }
+            WeatherKind::Thunder => {
+                weather.set(0, duration, true, true);
+                "Set the weather to rain & thunder"
+            }
+        };
+        drop(weather);
+
+        sender.send_message(TextComponent::text(message)).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let weather = |name: &str, kind: WeatherKind| {
+        literal(name)
+            .execute(WeatherExecutor(kind))
+            .with_child(argument_default_name(duration_consumer()).execute(WeatherExecutor(kind)))
+    };
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(weather("clear", WeatherKind::Clear))
+        .with_child(weather("rain", WeatherKind::Rain))
+        .with_child(weather("thunder", WeatherKind::Thunder))
+}
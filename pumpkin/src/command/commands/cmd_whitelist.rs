@@ -0,0 +1,202 @@
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use pumpkin_config::BasicConfiguration;
+use pumpkin_core::text::TextComponent;
+
+use crate::{
+    command::{
+        args::{
+            arg_game_profile::GameProfileArgumentConsumer, arg_simple::SimpleArgConsumer,
+            ConsumedArgs, FindArg,
+        },
+        tree::CommandTree,
+        tree_builder::{argument, literal},
+        CommandError, CommandExecutor, CommandSender,
+    },
+    data::{
+        whitelist_data::{WhitelistConfig, WHITELIST_CONFIG},
+        LoadJSONConfiguration,
+    },
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["whitelist"];
+const DESCRIPTION: &str = "Manages the server whitelist.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_NAME: &str = "name";
+
+struct ToggleExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for ToggleExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let enabled = self.0;
+        if server.white_list.swap(enabled, Ordering::Relaxed) == enabled {
+            let message = if enabled {
+                "Whitelist is already turned on"
+            } else {
+                "Whitelist is already turned off"
+            };
+            return Err(CommandError::GeneralCommandIssue(message.to_string()));
+        }
+        BasicConfiguration::save_white_list(enabled);
+
+        if enabled {
+            server.kick_unlisted_players().await;
+        }
+        let key = if enabled {
+            "commands.whitelist.enabled"
+        } else {
+            "commands.whitelist.disabled"
+        };
+        sender
+            .send_message(TextComponent::translate(key, vec![]))
+            .await;
+        Ok(())
+    }
+}
+
+struct ListExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let names: Vec<_> = WHITELIST_CONFIG
+            .read()
+            .await
+            .whitelist
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        let message = if names.is_empty() {
+            TextComponent::translate("commands.whitelist.none", vec![])
+        } else {
+            TextComponent::translate(
+                "commands.whitelist.list",
+                vec![
+                    TextComponent::text_string(names.len().to_string()),
+                    TextComponent::text_string(names.join(", ")),
+                ],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct AddExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = GameProfileArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+
+        let mut whitelist = WHITELIST_CONFIG.write().await;
+        let mut added = 0;
+        for target in targets {
+            if whitelist.add(target) {
+                added += 1;
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.whitelist.add.success",
+                        vec![TextComponent::text_string(target.name.clone())],
+                    ))
+                    .await;
+            }
+        }
+
+        if added == 0 {
+            return Err(CommandError::GeneralCommandIssue(
+                "Player is already whitelisted".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct RemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_NAME)?;
+
+        let Some(entry) = WHITELIST_CONFIG.write().await.remove(name) else {
+            return Err(CommandError::GeneralCommandIssue(
+                "Player is not whitelisted".to_string(),
+            ));
+        };
+
+        server.kick_unlisted_players().await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.whitelist.remove.success",
+                vec![TextComponent::text_string(entry.name)],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct ReloadExecutor;
+
+#[async_trait]
+impl CommandExecutor for ReloadExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        *WHITELIST_CONFIG.write().await = WhitelistConfig::load();
+
+        server.kick_unlisted_players().await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.whitelist.reloaded",
+                vec![],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .with_child(literal("on").execute(ToggleExecutor(true)))
+        .with_child(literal("off").execute(ToggleExecutor(false)))
+        .with_child(literal("list").execute(ListExecutor))
+        .with_child(
+            literal("add").with_child(
+                argument(ARG_TARGETS, GameProfileArgumentConsumer).execute(AddExecutor),
+            ),
+        )
+        .with_child(
+            literal("remove")
+                .with_child(argument(ARG_NAME, SimpleArgConsumer).execute(RemoveExecutor)),
+        )
+        .with_child(literal("reload").execute(ReloadExecutor))
+}
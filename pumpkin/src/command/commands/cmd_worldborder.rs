@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_core::{
     math::vector2::Vector2,
@@ -19,11 +21,18 @@ use crate::{
         CommandError, CommandExecutor, CommandSender,
     },
     server::Server,
+    world::{worldborder::Worldborder, World},
 };
 
 const NAMES: [&str; 1] = ["worldborder"];
 
-const DESCRIPTION: &str = "Worldborder command.";
+const DESCRIPTION: &str = "Manages the world border.";
+
+/// Vanilla's largest world border
+const MAX_DIAMETER: f64 = 59_999_968.0;
+
+/// The border center can't be further out than this
+const MAX_CENTER: f64 = 29_999_984.0;
 
 fn distance_consumer() -> BoundedNumArgumentConsumer<f64> {
     BoundedNumArgumentConsumer::new().min(0.0).name("distance")
@@ -47,6 +56,76 @@ fn warning_distance_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new().min(0).name("distance")
 }
 
+/// Borders can shrink by adding negative distances
+fn add_distance_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new()
+        .min(-MAX_DIAMETER)
+        .max(MAX_DIAMETER)
+        .name("distance")
+}
+
+/// The border of the sender's world, the console changes the border of the first world
+fn border_world(sender: &CommandSender<'_>, server: &Server) -> Arc<World> {
+    sender.world().unwrap_or_else(|| {
+        server
+            .worlds
+            .first()
+            .cloned()
+            .expect("There should always be atleast one world")
+    })
+}
+
+/// The feedback for resizing the border to the diameter, errors for sizes which are not allowed
+fn resize_message(
+    border: &Worldborder,
+    diameter: f64,
+    time: Option<i32>,
+) -> Result<String, &'static str> {
+    if diameter < 1.0 {
+        Err("The world border cannot be smaller than 1 block wide")
+    } else if diameter > MAX_DIAMETER {
+        Err("The world border cannot be bigger than 59,999,968 blocks wide")
+    } else if (diameter - border.new_diameter).abs() < f64::EPSILON {
+        Err("Nothing changed. The world border is already that size")
+    } else {
+        Ok(match time.filter(|time| *time > 0) {
+            Some(time) if diameter < border.diameter() => format!(
+                "Shrinking the world border to {diameter:.2} blocks wide over {time} second(s)"
+            ),
+            Some(time) => format!(
+                "Growing the world border to {diameter:.2} blocks wide over {time} second(s)"
+            ),
+            None => format!("Set the world border to {diameter:.1} block(s) wide"),
+        })
+    }
+}
+
+/// Moves the border to the diameter, over `time` seconds if there is a time
+async fn resize_border(
+    sender: &CommandSender<'_>,
+    world: &World,
+    diameter: f64,
+    time: Option<i32>,
+) {
+    let mut border = world.worldborder.lock().await;
+    match resize_message(&border, diameter, time) {
+        Ok(message) => {
+            border
+                .set_diameter(world, diameter, time.map(|time| i64::from(time) * 1000))
+                .await;
+            drop(border);
+            sender
+                .send_message(TextComponent::text_string(message))
+                .await;
+        }
+        Err(message) => {
+            sender
+                .send_message(TextComponent::text(message).color(Color::Named(NamedColor::Red)))
+                .await;
+        }
+    }
+}
+
 struct WorldborderGetExecutor;
 
 #[async_trait]
@@ -57,13 +136,11 @@ impl CommandExecutor for WorldborderGetExecutor {
         server: &Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
+        let world = &border_world(sender, server);
         let border = world.worldborder.lock().await;
 
-        let diameter = border.new_diameter.round() as i32;
+        // Moving borders report the diameter they have right now
+        let diameter = border.diameter().round() as i64;
         sender
             .send_message(TextComponent::text(&format!(
                 "The world border is currently {diameter} block(s) wide"
@@ -83,11 +160,7 @@ impl CommandExecutor for WorldborderSetExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
-        let mut border = world.worldborder.lock().await;
+        let world = &border_world(sender, server);
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
             sender
@@ -102,22 +175,7 @@ impl CommandExecutor for WorldborderSetExecutor {
             return Ok(());
         };
 
-        if (distance - border.new_diameter).abs() < f64::EPSILON {
-            sender
-                .send_message(
-                    TextComponent::text("Nothing changed. The world border is already that size")
-                        .color(Color::Named(NamedColor::Red)),
-                )
-                .await;
-            return Ok(());
-        }
-
-        sender
-            .send_message(TextComponent::text(&format!(
-                "Set the world border to {distance:.1} block(s) wide"
-            )))
-            .await;
-        border.set_diameter(world, distance, None).await;
+        resize_border(sender, world, distance, None).await;
         Ok(())
     }
 }
@@ -132,11 +190,7 @@ impl CommandExecutor for WorldborderSetTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
-        let mut border = world.worldborder.lock().await;
+        let world = &border_world(sender, server);
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
             sender
@@ -163,33 +217,7 @@ impl CommandExecutor for WorldborderSetTimeExecutor {
             return Ok(());
         };
 
-        match distance.total_cmp(&border.new_diameter) {
-            std::cmp::Ordering::Equal => {
-                sender
-                    .send_message(
-                        TextComponent::text(
-                            "Nothing changed. The world border is already that size",
-                        )
-                        .color(Color::Named(NamedColor::Red)),
-                    )
-                    .await;
-                return Ok(());
-            }
-            std::cmp::Ordering::Less => {
-                sender.send_message(TextComponent::text(&format!("Shrinking the world border to {distance:.2} blocks wide over {time} second(s)"))).await;
-            }
-            std::cmp::Ordering::Greater => {
-                sender
-                    .send_message(TextComponent::text(&format!(
-                        "Growing the world border to {distance:.2} blocks wide over {time} seconds"
-                    )))
-                    .await;
-            }
-        }
-
-        border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
-            .await;
+        resize_border(sender, world, distance, Some(time)).await;
         Ok(())
     }
 }
@@ -204,18 +232,14 @@ impl CommandExecutor for WorldborderAddExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
-        let mut border = world.worldborder.lock().await;
+        let world = &border_world(sender, server);
 
-        let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
+        let Ok(distance) = add_distance_consumer().find_arg_default_name(args)? else {
             sender
                 .send_message(
                     TextComponent::text_string(format!(
                         "{} is out of bounds.",
-                        distance_consumer().default_name()
+                        add_distance_consumer().default_name()
                     ))
                     .color(Color::Named(NamedColor::Red)),
                 )
@@ -223,24 +247,8 @@ impl CommandExecutor for WorldborderAddExecutor {
             return Ok(());
         };
 
-        if distance == 0.0 {
-            sender
-                .send_message(
-                    TextComponent::text("Nothing changed. The world border is already that size")
-                        .color(Color::Named(NamedColor::Red)),
-                )
-                .await;
-            return Ok(());
-        }
-
-        let distance = border.new_diameter + distance;
-
-        sender
-            .send_message(TextComponent::text(&format!(
-                "Set the world border to {distance:.1} block(s) wide"
-            )))
-            .await;
-        border.set_diameter(world, distance, None).await;
+        let diameter = world.worldborder.lock().await.new_diameter + distance;
+        resize_border(sender, world, diameter, None).await;
         Ok(())
     }
 }
@@ -255,18 +263,14 @@ impl CommandExecutor for WorldborderAddTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
-        let mut border = world.worldborder.lock().await;
+        let world = &border_world(sender, server);
 
-        let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
+        let Ok(distance) = add_distance_consumer().find_arg_default_name(args)? else {
             sender
                 .send_message(
                     TextComponent::text_string(format!(
                         "{} is out of bounds.",
-                        distance_consumer().default_name()
+                        add_distance_consumer().default_name()
                     ))
                     .color(Color::Named(NamedColor::Red)),
                 )
@@ -286,35 +290,8 @@ impl CommandExecutor for WorldborderAddTimeExecutor {
             return Ok(());
         };
 
-        let distance = distance + border.new_diameter;
-
-        match distance.total_cmp(&border.new_diameter) {
-            std::cmp::Ordering::Equal => {
-                sender
-                    .send_message(
-                        TextComponent::text(
-                            "Nothing changed. The world border is already that size",
-                        )
-                        .color(Color::Named(NamedColor::Red)),
-                    )
-                    .await;
-                return Ok(());
-            }
-            std::cmp::Ordering::Less => {
-                sender.send_message(TextComponent::text(&format!("Shrinking the world border to {distance:.2} blocks wide over {time} second(s)"))).await;
-            }
-            std::cmp::Ordering::Greater => {
-                sender
-                    .send_message(TextComponent::text(&format!(
-                        "Growing the world border to {distance:.2} blocks wide over {time} seconds"
-                    )))
-                    .await;
-            }
-        }
-
-        border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
-            .await;
+        let diameter = world.worldborder.lock().await.new_diameter + distance;
+        resize_border(sender, world, diameter, Some(time)).await;
         Ok(())
     }
 }
@@ -329,20 +306,42 @@ impl CommandExecutor for WorldborderCenterExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
-        let mut border = world.worldborder.lock().await;
+        let world = &border_world(sender, server);
 
         let Vector2 { x, z } = Position2DArgumentConsumer.find_arg_default_name(args)?;
 
+        let mut border = world.worldborder.lock().await;
+        if x.abs() > MAX_CENTER || z.abs() > MAX_CENTER {
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "The world border cannot be further out than the edge of the world",
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+        if (border.center_x - x).abs() < f64::EPSILON && (border.center_z - z).abs() < f64::EPSILON
+        {
+            sender
+                .send_message(
+                    TextComponent::text(
+                        "Nothing changed. The world border is already centered there",
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
+        border.set_center(world, x, z).await;
+        drop(border);
         sender
             .send_message(TextComponent::text(&format!(
-                "Set the center of world border to {x:.2}, {z:.2}"
+                "Set the center of the world border to {x:.2}, {z:.2}"
             )))
             .await;
-        border.set_center(world, x, z).await;
         Ok(())
     }
 }
@@ -357,10 +356,7 @@ impl CommandExecutor for WorldborderDamageAmountExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
+        let world = &border_world(sender, server);
         let mut border = world.worldborder.lock().await;
 
         let Ok(damage_per_block) = damage_per_block_consumer().find_arg_default_name(args)? else {
@@ -408,10 +404,7 @@ impl CommandExecutor for WorldborderDamageBufferExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
+        let world = &border_world(sender, server);
         let mut border = world.worldborder.lock().await;
 
         let Ok(buffer) = damage_buffer_consumer().find_arg_default_name(args)? else {
@@ -459,10 +452,7 @@ impl CommandExecutor for WorldborderWarningDistanceExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
+        let world = &border_world(sender, server);
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = warning_distance_consumer().find_arg_default_name(args)? else {
@@ -510,10 +500,7 @@ impl CommandExecutor for WorldborderWarningTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let world = server
-            .worlds
-            .first()
-            .expect("There should always be atleast one world");
+        let world = &border_world(sender, server);
         let mut border = world.worldborder.lock().await;
 
         let Ok(time) = time_consumer().find_arg_default_name(args)? else {
@@ -555,7 +542,7 @@ pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION)
         .with_child(
             literal("add").with_child(
-                argument_default_name(distance_consumer())
+                argument_default_name(add_distance_consumer())
                     .execute(WorldborderAddExecutor)
                     .with_child(
                         argument_default_name(time_consumer()).execute(WorldborderAddTimeExecutor),
@@ -603,3 +590,26 @@ pub fn init_command_tree() -> CommandTree {
                 )),
         )
 }
+
+#[cfg(test)]
+mod test {
+    use crate::world::worldborder::Worldborder;
+
+    use super::resize_message;
+
+    #[test]
+    fn borders_stay_within_vanilla_sizes() {
+        let border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        assert!(resize_message(&border, 0.5, None).is_err());
+        assert!(resize_message(&border, 60_000_000.0, None).is_err());
+        assert!(resize_message(&border, 100.0, Some(10)).is_err());
+        assert_eq!(
+            resize_message(&border, 50.0, Some(10)).unwrap(),
+            "Shrinking the world border to 50.00 blocks wide over 10 second(s)"
+        );
+        assert_eq!(
+            resize_message(&border, 200.0, Some(0)).unwrap(),
+            "Set the world border to 200.0 block(s) wide"
+        );
+    }
+}
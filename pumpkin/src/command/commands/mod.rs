@@ -1,19 +1,44 @@
+pub mod cmd_advancement;
+pub mod cmd_ban;
+pub mod cmd_banip;
 pub mod cmd_bossbar;
 pub mod cmd_clear;
+pub mod cmd_clone;
+pub mod cmd_damage;
+pub mod cmd_data;
+pub mod cmd_datapack;
+pub mod cmd_deop;
+pub mod cmd_difficulty;
+pub mod cmd_effect;
+pub mod cmd_execute;
+pub mod cmd_experience;
 pub mod cmd_fill;
+pub mod cmd_function;
 pub mod cmd_gamemode;
+pub mod cmd_gamerule;
 pub mod cmd_give;
 pub mod cmd_help;
+pub mod cmd_item;
 pub mod cmd_kick;
 pub mod cmd_kill;
 pub mod cmd_list;
+pub mod cmd_locate;
 pub mod cmd_op;
+pub mod cmd_pardon;
+pub mod cmd_pardonip;
+pub mod cmd_pregen;
 pub mod cmd_pumpkin;
+pub mod cmd_reload;
 pub mod cmd_say;
+pub mod cmd_scoreboard;
 pub mod cmd_seed;
 pub mod cmd_setblock;
 pub mod cmd_stop;
+pub mod cmd_summon;
+pub mod cmd_team;
 pub mod cmd_teleport;
 pub mod cmd_time;
 pub mod cmd_transfer;
+pub mod cmd_weather;
+pub mod cmd_whitelist;
 pub mod cmd_worldborder;
@@ -1,4 +1,4 @@
-use pumpkin_core::permission::PermissionLvl;
+use pumpkin_core::permission::{PermissionLvl, PermissionRegistry};
 use pumpkin_core::text::TextComponent;
 use pumpkin_protocol::client::play::CommandSuggestion;
 
@@ -7,12 +7,14 @@ use super::args::ConsumedArgs;
 use crate::command::dispatcher::CommandError::{
     GeneralCommandIssue, InvalidConsumption, InvalidRequirement, OtherPumpkin, PermissionDenied,
 };
-use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
+use crate::command::tree::{Command, CommandTree, NodeType, RawArgs, RedirectTarget};
 use crate::command::CommandSender;
 use crate::error::PumpkinError;
 use crate::server::Server;
 use pumpkin_core::text::color::{Color, NamedColor};
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
 
 #[derive(Debug)]
 pub(crate) enum CommandError {
@@ -55,17 +57,21 @@ impl CommandError {
 #[derive(Default)]
 pub struct CommandDispatcher {
     pub(crate) commands: HashMap<String, Command>,
-    pub(crate) permissions: HashMap<String, PermissionLvl>,
+    /// The permission node of each command, keyed by its primary name
+    pub(crate) permissions: HashMap<String, String>,
+    /// The nodes commands and plugins check, with the levels granting them by default
+    pub permission_registry: PermissionRegistry,
 }
 
 /// Stores registered [`CommandTree`]s and dispatches commands to them.
 impl CommandDispatcher {
+    /// Runs the command and tells the sender what went wrong, returns whether it succeeded
     pub async fn handle_command<'a>(
         &'a self,
         sender: &mut CommandSender<'a>,
         server: &'a Server,
         cmd: &'a str,
-    ) {
+    ) -> bool {
         if let Err(e) = self.dispatch(sender, server, cmd).await {
             match e.into_string_or_pumpkin_error(cmd) {
                 Ok(err) => {
@@ -81,7 +87,9 @@ impl CommandDispatcher {
                     sender.send_message(TextComponent::text("Unknown internal error occurred while running command. Please see server log").color(Color::Named(NamedColor::Red))).await;
                 }
             }
+            return false;
         }
+        true
     }
 
     /// server side suggestions (client side suggestions work independently)
@@ -99,59 +107,94 @@ impl CommandDispatcher {
         let Some(key) = parts.next() else {
             return Vec::new();
         };
-        let mut raw_args: Vec<&str> = parts.rev().collect();
+        let raw_args: Vec<&str> = parts.rev().collect();
+
+        let Some(node) = self.get_permission_node(key) else {
+            return Vec::new();
+        };
+        if !src.has_permission(&self.permission_registry, node).await {
+            return Vec::new();
+        }
 
         let Ok(tree) = self.get_tree(key) else {
             return Vec::new();
         };
 
-        let mut suggestions = HashSet::new();
-
-        // try paths and collect the nodes that fail
-        // todo: make this more fine-grained
-        for path in tree.iter_paths() {
-            match Self::try_find_suggestions_on_path(src, server, &path, tree, &mut raw_args, cmd)
-                .await
-            {
-                Err(InvalidConsumption(s)) => {
-                    log::error!("Error while parsing command \"{cmd}\": {s:?} was consumed, but couldn't be parsed");
-                    return Vec::new();
-                }
-                Err(InvalidRequirement) => {
-                    log::error!("Error while parsing command \"{cmd}\": a requirement that was expected was not met.");
-                    return Vec::new();
-                }
-                Err(PermissionDenied) => {
-                    log::warn!("Permission denied for command \"{cmd}\"");
-                    return Vec::new();
-                }
-                Err(GeneralCommandIssue(issue)) => {
-                    log::error!("Error while parsing command \"{cmd}\": {issue}");
-                    return Vec::new();
-                }
-                Err(OtherPumpkin(e)) => {
-                    log::error!("Error while parsing command \"{cmd}\": {e}");
-                    return Vec::new();
-                }
-                Ok(Some(new_suggestions)) => {
-                    suggestions.extend(new_suggestions);
+        let mut suggestions = self
+            .find_tree_suggestions(src, server, tree, raw_args, cmd)
+            .await;
+        suggestions.sort_by(|a, b| a.suggestion.cmp(&b.suggestion));
+        suggestions
+    }
+
+    /// The suggestions of the paths through the tree the arguments fit
+    fn find_tree_suggestions<'a, 'b>(
+        &'a self,
+        src: &'b mut CommandSender<'a>,
+        server: &'a Server,
+        tree: &'a CommandTree,
+        raw_args: RawArgs<'a>,
+        cmd: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Vec<CommandSuggestion<'a>>> + Send + 'b>>
+    where
+        'a: 'b,
+    {
+        Box::pin(async move {
+            let mut suggestions = HashSet::new();
+
+            // try paths and collect the nodes that fail
+            // todo: make this more fine-grained
+            for path in tree.iter_paths() {
+                match self
+                    .try_find_suggestions_on_path(
+                        src,
+                        server,
+                        &path,
+                        tree,
+                        &mut raw_args.clone(),
+                        cmd,
+                    )
+                    .await
+                {
+                    Err(InvalidConsumption(s)) => {
+                        log::error!("Error while parsing command \"{cmd}\": {s:?} was consumed, but couldn't be parsed");
+                        return Vec::new();
+                    }
+                    Err(InvalidRequirement) => {
+                        log::error!("Error while parsing command \"{cmd}\": a requirement that was expected was not met.");
+                        return Vec::new();
+                    }
+                    Err(PermissionDenied) => {
+                        log::warn!("Permission denied for command \"{cmd}\"");
+                        return Vec::new();
+                    }
+                    Err(GeneralCommandIssue(issue)) => {
+                        log::error!("Error while parsing command \"{cmd}\": {issue}");
+                        return Vec::new();
+                    }
+                    Err(OtherPumpkin(e)) => {
+                        log::error!("Error while parsing command \"{cmd}\": {e}");
+                        return Vec::new();
+                    }
+                    Ok(Some(new_suggestions)) => {
+                        suggestions.extend(new_suggestions);
+                    }
+                    Ok(None) => {}
                 }
-                Ok(None) => {}
             }
-        }
 
-        let mut suggestions = Vec::from_iter(suggestions);
-        suggestions.sort_by(|a, b| a.suggestion.cmp(&b.suggestion));
-        suggestions
+            Vec::from_iter(suggestions)
+        })
     }
 
-    /// Execute a command using its corresponding [`CommandTree`].
+    /// Execute a command using its corresponding [`CommandTree`]. Returns how often it
+    /// succeeded, commands which fork like `/execute as @a` add up the successes of every sender
     pub(crate) async fn dispatch<'a>(
         &'a self,
         src: &mut CommandSender<'a>,
         server: &'a Server,
         cmd: &'a str,
-    ) -> Result<(), CommandError> {
+    ) -> Result<i32, CommandError> {
         // Other languages dont use the ascii whitespace
         let mut parts = cmd.split_whitespace();
         let key = parts
@@ -159,25 +202,125 @@ impl CommandDispatcher {
             .ok_or(GeneralCommandIssue("Empty Command".to_string()))?;
         let raw_args: Vec<&str> = parts.rev().collect();
 
-        let Some(permission) = self.permissions.get(key) else {
+        let Some(node) = self.get_permission_node(key) else {
             return Err(GeneralCommandIssue("Command not found".to_string()));
         };
 
-        if !src.has_permission_lvl(*permission) {
+        if !src.has_permission(&self.permission_registry, node).await {
             return Err(PermissionDenied);
         }
 
         let tree = self.get_tree(key)?;
+        self.dispatch_tree(src, server, tree, raw_args, cmd).await
+    }
+
+    /// Runs the first path through the tree the arguments fit, returns how often it succeeded
+    fn dispatch_tree<'a, 'b>(
+        &'a self,
+        src: &'b mut CommandSender<'a>,
+        server: &'a Server,
+        tree: &'a CommandTree,
+        raw_args: RawArgs<'a>,
+        cmd: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<i32, CommandError>> + Send + 'b>>
+    where
+        'a: 'b,
+    {
+        Box::pin(async move {
+            // try paths until fitting path is found
+            for path in tree.iter_paths() {
+                if let Some(successes) = self
+                    .try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone(), cmd)
+                    .await?
+                {
+                    return Ok(successes);
+                }
+            }
+            Err(GeneralCommandIssue(format!(
+                "Invalid Syntax. Usage: {tree}"
+            )))
+        })
+    }
 
-        // try paths until fitting path is found
-        for path in tree.iter_paths() {
-            if Self::try_is_fitting_path(src, server, &path, tree, &mut raw_args.clone()).await? {
-                return Ok(());
+    /// Checks that the rest of a redirected command parses, without running anything. Forks
+    /// which leave no sender would not parse it otherwise
+    fn check_syntax<'a, 'b>(
+        &'a self,
+        src: &'b mut CommandSender<'a>,
+        server: &'a Server,
+        tree: &'a CommandTree,
+        target: RedirectTarget,
+        raw_args: RawArgs<'a>,
+        cmd: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommandError>> + Send + 'b>>
+    where
+        'a: 'b,
+    {
+        Box::pin(async move {
+            let (tree, raw_args) = match target {
+                RedirectTarget::Command => (tree, raw_args),
+                RedirectTarget::Commands => {
+                    let mut parts = argument_input(cmd, raw_args.len()).split_whitespace();
+                    let key = parts
+                        .next()
+                        .ok_or(GeneralCommandIssue("Empty Command".to_string()))?;
+                    (self.get_tree(key)?, parts.rev().collect())
+                }
+            };
+            for path in tree.iter_paths() {
+                if self
+                    .fits_path(src, server, &path, tree, &mut raw_args.clone(), cmd)
+                    .await?
+                {
+                    return Ok(());
+                }
+            }
+            Err(GeneralCommandIssue(format!(
+                "Invalid Syntax. Usage: {tree}"
+            )))
+        })
+    }
+
+    /// Whether the arguments parse along the path, like [`Self::try_is_fitting_path`] without
+    /// running the command
+    async fn fits_path<'a>(
+        &'a self,
+        src: &mut CommandSender<'a>,
+        server: &'a Server,
+        path: &[usize],
+        tree: &'a CommandTree,
+        raw_args: &mut RawArgs<'a>,
+        cmd: &'a str,
+    ) -> Result<bool, CommandError> {
+        for node in path.iter().map(|&i| &tree.nodes[i]) {
+            match &node.node_type {
+                NodeType::ExecuteLeaf { .. } => return Ok(raw_args.is_empty()),
+                NodeType::Literal { string, .. } => {
+                    if raw_args.pop() != Some(string) {
+                        return Ok(false);
+                    }
+                }
+                NodeType::Argument { consumer, .. } => {
+                    if consumer.consume(src, server, raw_args).await.is_none() {
+                        return Ok(false);
+                    }
+                }
+                NodeType::Require { predicate, .. } => {
+                    if !predicate(src) {
+                        return Ok(false);
+                    }
+                }
+                NodeType::Redirect { target, .. } => {
+                    if raw_args.is_empty() {
+                        return Ok(false);
+                    }
+                    self.check_syntax(src, server, tree, *target, raw_args.clone(), cmd)
+                        .await?;
+                    return Ok(true);
+                }
             }
         }
-        Err(GeneralCommandIssue(format!(
-            "Invalid Syntax. Usage: {tree}"
-        )))
+        Ok(false)
     }
 
     pub(crate) fn get_tree(&self, key: &str) -> Result<&CommandTree, CommandError> {
@@ -200,32 +343,41 @@ impl CommandDispatcher {
         }
     }
 
-    pub(crate) fn get_permission_lvl(&self, key: &str) -> Option<PermissionLvl> {
-        self.permissions.get(key).copied()
+    /// The node needed to run the command, aliases need the one of the command they point to
+    pub(crate) fn get_permission_node(&self, key: &str) -> Option<&str> {
+        let primary_name = match self.commands.get(key)? {
+            Command::Tree(_) => key,
+            Command::Alias(target) => target,
+        };
+        self.permissions.get(primary_name).map(String::as_str)
     }
 
+    /// Runs the command along the path if the arguments fit it, returns how often it succeeded
     async fn try_is_fitting_path<'a>(
+        &'a self,
         src: &mut CommandSender<'a>,
         server: &'a Server,
         path: &[usize],
         tree: &'a CommandTree,
         raw_args: &mut RawArgs<'a>,
-    ) -> Result<bool, CommandError> {
+        cmd: &'a str,
+    ) -> Result<Option<i32>, CommandError> {
         let mut parsed_args: ConsumedArgs = HashMap::new();
 
         for node in path.iter().map(|&i| &tree.nodes[i]) {
             match &node.node_type {
                 NodeType::ExecuteLeaf { executor } => {
                     return if raw_args.is_empty() {
-                        executor.execute(src, server, &parsed_args).await?;
-                        Ok(true)
+                        Ok(Some(
+                            executor.execute_counted(src, server, &parsed_args).await?,
+                        ))
                     } else {
-                        Ok(false)
+                        Ok(None)
                     };
                 }
                 NodeType::Literal { string, .. } => {
                     if raw_args.pop() != Some(string) {
-                        return Ok(false);
+                        return Ok(None);
                     }
                 }
                 NodeType::Argument { consumer, name, .. } => {
@@ -233,21 +385,55 @@ impl CommandDispatcher {
                         Some(consumed) => {
                             parsed_args.insert(name, consumed);
                         }
-                        None => return Ok(false),
+                        None => return Ok(None),
                     }
                 }
                 NodeType::Require { predicate, .. } => {
                     if !predicate(src) {
-                        return Ok(false);
+                        return Ok(None);
+                    }
+                }
+                NodeType::Redirect { target, modifier } => {
+                    if raw_args.is_empty() {
+                        return Ok(None);
+                    }
+                    self.check_syntax(src, server, tree, *target, raw_args.clone(), cmd)
+                        .await?;
+                    // Every sender parses the rest again, relative to itself. A sender failing
+                    // doesn't stop the others, only their successes count
+                    let mut successes = 0;
+                    let mut first_error = None;
+                    for mut sender in modifier.modify(src, server, &parsed_args).await? {
+                        let result = match target {
+                            RedirectTarget::Command => {
+                                self.dispatch_tree(&mut sender, server, tree, raw_args.clone(), cmd)
+                                    .await
+                            }
+                            RedirectTarget::Commands => {
+                                let command = argument_input(cmd, raw_args.len());
+                                Box::pin(self.dispatch(&mut sender, server, command)).await
+                            }
+                        };
+                        match result {
+                            Ok(count) => successes += count,
+                            Err(error) => {
+                                first_error.get_or_insert(error);
+                            }
+                        }
                     }
+                    return match first_error {
+                        Some(error) if successes == 0 => Err(error),
+                        _ => Ok(Some(successes)),
+                    };
                 }
             }
         }
 
-        Ok(false)
+        Ok(None)
     }
 
     async fn try_find_suggestions_on_path<'a>(
+        &'a self,
         src: &mut CommandSender<'a>,
         server: &'a Server,
         path: &[usize],
@@ -268,18 +454,18 @@ impl CommandDispatcher {
                     }
                 }
                 NodeType::Argument { consumer, name } => {
+                    let argument_input = argument_input(input, raw_args.len());
                     match consumer.consume(src, server, raw_args).await {
-                        Some(consumed) => {
+                        // The argument is complete unless its last word is still being written
+                        Some(consumed)
+                            if !raw_args.is_empty() || input.ends_with(char::is_whitespace) =>
+                        {
                             parsed_args.insert(name, consumed);
                         }
-                        None => {
-                            return if raw_args.is_empty() {
-                                let suggestions = consumer.suggest(src, server, input).await?;
-                                Ok(suggestions)
-                            } else {
-                                Ok(None)
-                            };
+                        _ if raw_args.is_empty() => {
+                            return consumer.suggest(src, server, argument_input).await;
                         }
+                        _ => return Ok(None),
                     }
                 }
                 NodeType::Require { predicate, .. } => {
@@ -287,14 +473,48 @@ impl CommandDispatcher {
                         return Ok(None);
                     }
                 }
+                NodeType::Redirect { target, .. } => {
+                    // The first word after the redirect may not have been started yet
+                    if raw_args.is_empty() && !input.ends_with(char::is_whitespace) {
+                        return Ok(None);
+                    }
+                    let suggestions = match target {
+                        RedirectTarget::Command => {
+                            self.find_tree_suggestions(src, server, tree, raw_args.clone(), input)
+                                .await
+                        }
+                        RedirectTarget::Commands => {
+                            let command = argument_input(input, raw_args.len());
+                            Box::pin(self.find_suggestions(src, server, command)).await
+                        }
+                    };
+                    return Ok(Some(suggestions));
+                }
             }
         }
 
         Ok(None)
     }
 
-    /// Register a command with the dispatcher.
+    /// Register a command with the dispatcher. Running it needs the node
+    /// `minecraft.command.<name>`, granted from the permission level on.
     pub(crate) fn register(&mut self, tree: CommandTree, permission: PermissionLvl) {
+        let primary_name = tree
+            .names
+            .first()
+            .expect("at least one name must be provided");
+        let node = format!("minecraft.command.{primary_name}");
+        self.register_with_node(tree, &node, permission);
+    }
+
+    /// Register a command with the dispatcher which needs its own permission node, granted from
+    /// the permission level on.
+    pub(crate) fn register_with_node(
+        &mut self,
+        tree: CommandTree,
+        node: &str,
+        permission: PermissionLvl,
+    ) {
         let mut names = tree.names.iter();
 
         let primary_name = names.next().expect("at least one name must be provided");
@@ -304,21 +524,141 @@ impl CommandDispatcher {
                 .insert(name.to_string(), Command::Alias(primary_name.to_string()));
         }
 
+        self.permission_registry.register(node, permission);
         self.permissions
-            .insert(primary_name.to_string(), permission);
+            .insert(primary_name.clone(), node.to_string());
         self.commands
             .insert(primary_name.to_string(), Command::Tree(tree));
     }
 }
 
+/// What was written from the first of the remaining words on, the input of the argument which
+/// consumes them
+fn argument_input(input: &str, remaining_words: usize) -> &str {
+    let written_words = input.split_whitespace().count();
+    let mut rest = input;
+    for _ in remaining_words..written_words {
+        rest = rest.trim_start();
+        rest = &rest[rest.find(char::is_whitespace).unwrap_or(rest.len())..];
+    }
+    rest.trim_start()
+}
+
 #[cfg(test)]
 mod test {
-    use crate::command::{default_dispatcher, tree::CommandTree};
+    use std::collections::HashMap;
+
+    use crate::command::{
+        args::{arg_block_predicate::BlockPredicateArgumentConsumer, Arg, FindArg},
+        commands::cmd_execute,
+        default_dispatcher,
+        tree::{CommandTree, NodeType, RedirectTarget},
+    };
     use pumpkin_core::permission::PermissionLvl;
+    use pumpkin_world::block::block_state::BlockStateInput;
+
+    use super::argument_input;
+
     #[test]
     fn test_dynamic_command() {
         let mut dispatcher = default_dispatcher();
         let tree = CommandTree::new(["test"], "test_desc");
         dispatcher.register(tree, PermissionLvl::Zero);
     }
+
+    /// The usage of each path through the tree with the node it ends in
+    fn paths(tree: &CommandTree) -> Vec<(String, &NodeType)> {
+        tree.iter_paths()
+            .map(|path| {
+                let usage: Vec<_> = path
+                    .iter()
+                    .map(|&i| tree.nodes[i].to_string())
+                    .filter(|word| !word.is_empty())
+                    .collect();
+                let end = &tree.nodes[*path.last().unwrap()].node_type;
+                (usage.join(" "), end)
+            })
+            .collect()
+    }
+
+    fn redirect_of(tree: &CommandTree, usage: &str) -> Option<RedirectTarget> {
+        paths(tree).into_iter().find_map(|(path, end)| match end {
+            NodeType::Redirect { target, .. } if path == usage => Some(*target),
+            _ => None,
+        })
+    }
+
+    fn executes(tree: &CommandTree, usage: &str) -> bool {
+        paths(tree)
+            .into_iter()
+            .any(|(path, end)| path == usage && matches!(end, NodeType::ExecuteLeaf { .. }))
+    }
+
+    #[test]
+    fn execute_subcommands_continue_at_execute() {
+        let tree = cmd_execute::init_command_tree();
+        for usage in [
+            "as <targets>",
+            "at <targets>",
+            "positioned <pos>",
+            "positioned as <targets>",
+            "rotated <rot>",
+            "rotated as <targets>",
+            "in <dimension>",
+            "if block <pos> <block>",
+            "unless entity <targets>",
+            "if score <target> <targetObjective> matches <range>",
+            "unless score <target> <targetObjective> >= <source> <sourceObjective>",
+        ] {
+            assert_eq!(
+                redirect_of(&tree, usage),
+                Some(RedirectTarget::Command),
+                "{usage}"
+            );
+        }
+        assert_eq!(redirect_of(&tree, "run"), Some(RedirectTarget::Commands));
+    }
+
+    #[test]
+    fn only_conditions_end_execute() {
+        let tree = cmd_execute::init_command_tree();
+        assert!(executes(&tree, "if block <pos> <block>"));
+        assert!(executes(&tree, "unless entity <targets>"));
+        assert!(executes(
+            &tree,
+            "if score <target> <targetObjective> = <source> <sourceObjective>"
+        ));
+        assert!(!executes(&tree, "as <targets>"));
+        assert!(!executes(&tree, "run"));
+    }
+
+    #[test]
+    fn redirects_continue_with_the_rest_of_the_input() {
+        let input = "execute as @a  run say hello  there";
+        // `run` leaves the command with its own spacing
+        assert_eq!(argument_input(input, 3), "say hello  there");
+        assert_eq!(argument_input(input, 1), "there");
+        assert_eq!(argument_input(input, 0), "");
+    }
+
+    #[test]
+    fn if_block_compares_state_properties() {
+        let state = |input| BlockStateInput::parse(input).unwrap().state_id;
+        let east = state("minecraft:oak_stairs[facing=east]");
+        let west = state("minecraft:oak_stairs[facing=west]");
+        let predicate = |input| {
+            let args = HashMap::from([("block", Arg::BlockPredicate(input))]);
+            let predicate = BlockPredicateArgumentConsumer::find_arg(&args, "block").unwrap();
+            (predicate.matches(east), predicate.matches(west))
+        };
+
+        assert_eq!(
+            predicate("minecraft:oak_stairs[facing=east]"),
+            (true, false)
+        );
+        assert_eq!(predicate("oak_stairs[facing=west]"), (false, true));
+        // Properties which aren't given match any value
+        assert_eq!(predicate("minecraft:oak_stairs"), (true, true));
+        assert_eq!(predicate("minecraft:stone"), (false, false));
+    }
 }
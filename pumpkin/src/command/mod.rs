@@ -4,19 +4,23 @@ use std::sync::Arc;
 use crate::command::commands::cmd_seed;
 use crate::command::commands::{cmd_bossbar, cmd_transfer};
 use crate::command::dispatcher::CommandDispatcher;
-use crate::entity::player::Player;
+use crate::entity::{any_entity::AnyEntity, player::Player};
 use crate::server::Server;
 use crate::world::World;
 use args::ConsumedArgs;
 use async_trait::async_trait;
 use commands::cmd_op;
 use commands::{
-    cmd_clear, cmd_fill, cmd_gamemode, cmd_give, cmd_help, cmd_kick, cmd_kill, cmd_list,
-    cmd_pumpkin, cmd_say, cmd_setblock, cmd_stop, cmd_teleport, cmd_time, cmd_worldborder,
+    cmd_advancement, cmd_ban, cmd_banip, cmd_clear, cmd_clone, cmd_damage, cmd_data, cmd_datapack,
+    cmd_deop, cmd_difficulty, cmd_effect, cmd_execute, cmd_experience, cmd_fill, cmd_function,
+    cmd_gamemode, cmd_gamerule, cmd_give, cmd_help, cmd_item, cmd_kick, cmd_kill, cmd_list,
+    cmd_locate, cmd_pardon, cmd_pardonip, cmd_pregen, cmd_pumpkin, cmd_reload, cmd_say,
+    cmd_scoreboard, cmd_setblock, cmd_stop, cmd_summon, cmd_team, cmd_teleport, cmd_time,
+    cmd_weather, cmd_whitelist, cmd_worldborder,
 };
 use dispatcher::CommandError;
 use pumpkin_core::math::vector3::Vector3;
-use pumpkin_core::permission::PermissionLvl;
+use pumpkin_core::permission::{PermissionGrants, PermissionLvl, PermissionRegistry};
 use pumpkin_core::text::TextComponent;
 
 pub mod args;
@@ -27,10 +31,50 @@ mod tree;
 mod tree_builder;
 mod tree_format;
 
+#[derive(Clone)]
 pub enum CommandSender<'a> {
     Rcon(&'a tokio::sync::Mutex<Vec<String>>),
     Console,
     Player(Arc<Player>),
+    /// A command `/execute` runs
+    Execute(Box<ExecutionContext<'a>>),
+}
+
+/// Where and as whom `/execute` runs a command, like vanilla's `CommandSourceStack`
+#[derive(Clone)]
+pub struct ExecutionContext<'a> {
+    /// Who gets the feedback and whose permissions apply, never an execution context itself
+    pub source: CommandSender<'a>,
+    /// The entity running the command, none when the console runs it
+    pub executor: Option<AnyEntity>,
+    pub position: Vector3<f64>,
+    /// The yaw and pitch
+    pub rotation: (f32, f32),
+    pub world: Arc<World>,
+    /// How many functions deep the command runs, commands in functions give no feedback
+    pub function_depth: usize,
+}
+
+impl<'a> ExecutionContext<'a> {
+    /// The context of the sender itself, at its position or the world spawn
+    pub async fn of(sender: &CommandSender<'a>, server: &Server) -> Self {
+        if let CommandSender::Execute(context) = sender {
+            return (**context).clone();
+        }
+        let world = sender.world().unwrap_or_else(|| server.worlds[0].clone());
+        let position = match sender.position() {
+            Some(position) => position,
+            None => world.spawn_position().await,
+        };
+        Self {
+            source: sender.clone(),
+            executor: sender.executor(),
+            position,
+            rotation: sender.rotation().unwrap_or_default(),
+            world,
+            function_depth: 0,
+        }
+    }
 }
 
 impl fmt::Display for CommandSender<'_> {
@@ -42,23 +86,45 @@ impl fmt::Display for CommandSender<'_> {
                 CommandSender::Console => "Server",
                 CommandSender::Rcon(_) => "Rcon",
                 CommandSender::Player(p) => &p.gameprofile.name,
+                CommandSender::Execute(context) => match &context.executor {
+                    Some(AnyEntity::Player(p)) => &p.gameprofile.name,
+                    _ => return write!(f, "{}", context.source),
+                },
             }
         )
     }
 }
 
 impl<'a> CommandSender<'a> {
-    pub async fn send_message(&self, text: TextComponent<'a>) {
+    /// Who gets the feedback of the command, the one who used `/execute` for executed commands
+    #[must_use]
+    pub fn source(&self) -> &Self {
         match self {
+            CommandSender::Execute(context) => &context.source,
+            sender => sender,
+        }
+    }
+
+    pub async fn send_message(&self, text: TextComponent<'a>) {
+        if matches!(self, CommandSender::Execute(context) if context.function_depth > 0) {
+            return;
+        }
+        match self.source() {
             CommandSender::Console => log::info!("{}", text.to_pretty_console()),
             CommandSender::Player(c) => c.send_system_message(&text).await,
             CommandSender::Rcon(s) => s.lock().await.push(text.to_pretty_console()),
+            CommandSender::Execute(_) => {}
         }
     }
 
     #[must_use]
-    pub const fn is_player(&self) -> bool {
-        matches!(self, CommandSender::Player(_))
+    pub fn is_player(&self) -> bool {
+        match self {
+            CommandSender::Execute(context) => {
+                matches!(context.executor, Some(AnyEntity::Player(_)))
+            }
+            _ => matches!(self, CommandSender::Player(_)),
+        }
     }
 
     #[must_use]
@@ -69,6 +135,17 @@ impl<'a> CommandSender<'a> {
     pub fn as_player(&self) -> Option<Arc<Player>> {
         match self {
             CommandSender::Player(player) => Some(player.clone()),
+            CommandSender::Execute(context) => context.executor.as_ref()?.as_player().cloned(),
+            _ => None,
+        }
+    }
+
+    /// The entity running the command, like `@s` selects it
+    #[must_use]
+    pub fn executor(&self) -> Option<AnyEntity> {
+        match self {
+            CommandSender::Player(player) => Some(AnyEntity::Player(player.clone())),
+            CommandSender::Execute(context) => context.executor.clone(),
             _ => None,
         }
     }
@@ -76,17 +153,29 @@ impl<'a> CommandSender<'a> {
     /// prefer using `has_permission_lvl(lvl)`
     #[must_use]
     pub fn permission_lvl(&self) -> PermissionLvl {
-        match self {
+        match self.source() {
             CommandSender::Console | CommandSender::Rcon(_) => PermissionLvl::Four,
             CommandSender::Player(p) => p.permission_lvl.load(),
+            CommandSender::Execute(_) => PermissionLvl::Zero,
         }
     }
 
     #[must_use]
     pub fn has_permission_lvl(&self, lvl: PermissionLvl) -> bool {
-        match self {
-            CommandSender::Console | CommandSender::Rcon(_) => true,
-            CommandSender::Player(p) => p.permission_lvl.load().ge(&lvl),
+        self.permission_lvl().ge(&lvl)
+    }
+
+    /// Whether the sender has the permission node, from the nodes granted to them or their level
+    pub async fn has_permission(&self, registry: &PermissionRegistry, node: &str) -> bool {
+        match self.source() {
+            CommandSender::Player(player) => registry.has_permission(
+                player.permission_lvl.load(),
+                &*player.permission_grants.lock().await,
+                node,
+            ),
+            source => {
+                registry.has_permission(source.permission_lvl(), &PermissionGrants::default(), node)
+            }
         }
     }
 
@@ -95,15 +184,30 @@ impl<'a> CommandSender<'a> {
         match self {
             CommandSender::Console | CommandSender::Rcon(..) => None,
             CommandSender::Player(p) => Some(p.living_entity.entity.pos.load()),
+            CommandSender::Execute(context) => Some(context.position),
+        }
+    }
+
+    /// The yaw and pitch the command runs with
+    #[must_use]
+    pub fn rotation(&self) -> Option<(f32, f32)> {
+        match self {
+            CommandSender::Console | CommandSender::Rcon(..) => None,
+            CommandSender::Player(p) => {
+                let entity = &p.living_entity.entity;
+                Some((entity.yaw.load(), entity.pitch.load()))
+            }
+            CommandSender::Execute(context) => Some(context.rotation),
         }
     }
 
     #[must_use]
-    pub fn world(&self) -> Option<&World> {
+    pub fn world(&self) -> Option<Arc<World>> {
         match self {
             // TODO: maybe return first world when console
             CommandSender::Console | CommandSender::Rcon(..) => None,
-            CommandSender::Player(p) => Some(&p.living_entity.entity.world),
+            CommandSender::Player(p) => Some(p.world()),
+            CommandSender::Execute(context) => Some(context.world.clone()),
         }
     }
 }
@@ -114,6 +218,7 @@ pub fn default_dispatcher() -> CommandDispatcher {
 
     dispatcher.register(cmd_pumpkin::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(cmd_bossbar::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_advancement::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_say::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_gamemode::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_stop::init_command_tree(), PermissionLvl::Four);
@@ -130,7 +235,31 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(cmd_seed::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_transfer::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(cmd_fill::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_clone::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(cmd_op::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_deop::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_ban::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_banip::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_pardon::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_pardonip::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_whitelist::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(cmd_locate::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_pregen::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(cmd_execute::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_weather::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_gamerule::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_difficulty::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_experience::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_effect::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_damage::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_scoreboard::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_team::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_function::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_reload::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_datapack::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_item::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_summon::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(cmd_data::init_command_tree(), PermissionLvl::Two);
 
     dispatcher
 }
@@ -143,4 +272,26 @@ pub(crate) trait CommandExecutor: Sync {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError>;
+
+    /// Runs the command, returns how often it succeeded. Most commands succeed once
+    async fn execute_counted<'a>(
+        &self,
+        sender: &mut CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<i32, CommandError> {
+        self.execute(sender, server, args).await.map(|()| 1)
+    }
+}
+
+/// Turns the sender into the senders the rest of a redirected command runs as, none if it
+/// shouldn't run at all. Like brigadier's `RedirectModifier`
+#[async_trait]
+pub(crate) trait CommandModifier: Sync {
+    async fn modify<'a>(
+        &self,
+        sender: &CommandSender<'a>,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<Vec<CommandSender<'a>>, CommandError>;
 }
@@ -1,4 +1,4 @@
-use super::{args::ArgumentConsumer, CommandExecutor};
+use super::{args::ArgumentConsumer, CommandExecutor, CommandModifier};
 use crate::command::CommandSender;
 use std::{collections::VecDeque, fmt::Debug, sync::Arc};
 
@@ -26,6 +26,21 @@ pub enum NodeType {
     Require {
         predicate: Arc<dyn Fn(&CommandSender) -> bool + Send + Sync>,
     },
+    /// Parses the rest of the command again from the target, as each of the senders the
+    /// modifier turns the sender into. Like brigadier's redirects and forks
+    Redirect {
+        target: RedirectTarget,
+        modifier: Arc<dyn CommandModifier + Send>,
+    },
+}
+
+/// Where parsing continues after a [`NodeType::Redirect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedirectTarget {
+    /// The root of the same command, like the subcommands of `/execute`
+    Command,
+    /// The root of all commands, like `/execute run`
+    Commands,
 }
 
 impl Debug for NodeType {
@@ -42,6 +57,11 @@ impl Debug for NodeType {
                 .field("consumer", &"..")
                 .finish(),
             Self::Require { .. } => f.debug_struct("Require").field("predicate", &"..").finish(),
+            Self::Redirect { target, .. } => f
+                .debug_struct("Redirect")
+                .field("target", target)
+                .field("modifier", &"..")
+                .finish(),
         }
     }
 }
@@ -60,7 +80,8 @@ pub struct CommandTree {
 }
 
 impl CommandTree {
-    /// iterate over all possible paths that end in a [`NodeType::ExecuteLeaf`]
+    /// iterate over all possible paths that end in a [`NodeType::ExecuteLeaf`] or a
+    /// [`NodeType::Redirect`]
     pub(crate) fn iter_paths(&self) -> impl Iterator<Item = Vec<usize>> + use<'_> {
         let mut todo = VecDeque::<(usize, usize)>::new();
 
@@ -103,7 +124,7 @@ impl Iterator for TraverseAllPathsIter<'_> {
             }
             self.path.push(i);
 
-            if let NodeType::ExecuteLeaf { .. } = node.node_type {
+            if let NodeType::ExecuteLeaf { .. } | NodeType::Redirect { .. } = node.node_type {
                 return Some(self.path.clone());
             }
         }
@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
 use super::args::DefaultNameArgConsumer;
-use super::CommandExecutor;
+use super::{CommandExecutor, CommandModifier};
 use crate::command::args::ArgumentConsumer;
-use crate::command::tree::{CommandTree, Node, NodeType};
+use crate::command::tree::{CommandTree, Node, NodeType, RedirectTarget};
 use crate::command::CommandSender;
 
 impl CommandTree {
@@ -121,6 +121,23 @@ impl NonLeafNodeBuilder {
 
         self
     }
+
+    /// Parses the rest of the command again from the target, once for each sender the modifier
+    /// turns the sender into. Unlike [`NonLeafNodeBuilder::execute`] this needs more arguments.
+    pub fn redirect(
+        mut self,
+        target: RedirectTarget,
+        modifier: impl CommandModifier + 'static + Send,
+    ) -> Self {
+        self.leaf_nodes.push(LeafNodeBuilder {
+            node_type: NodeType::Redirect {
+                target,
+                modifier: Arc::new(modifier),
+            },
+        });
+
+        self
+    }
 }
 
 /// Matches a sting literal.
@@ -0,0 +1,150 @@
+use pumpkin_config::ADVANCED_CONFIG;
+use serde::{Deserialize, Deserializer, Serializer};
+use time::{format_description::BorrowedFormatItem, macros::format_description, OffsetDateTime};
+
+/// How ban lists save dates, like `2024-12-24 18:00:00 +0000`
+const DATE_FORMAT: &[BorrowedFormatItem<'static>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]"
+);
+
+/// What ban lists save instead of a date for bans which don't expire
+const FOREVER: &str = "forever";
+
+#[must_use]
+pub fn format_date(date: OffsetDateTime) -> String {
+    date.format(DATE_FORMAT).unwrap_or_default()
+}
+
+fn parse_date(date: &str) -> Result<OffsetDateTime, time::error::Parse> {
+    OffsetDateTime::parse(date, DATE_FORMAT)
+}
+
+/// Whether a ban which expires then is over
+#[must_use]
+pub fn is_expired(expires: Option<OffsetDateTime>) -> bool {
+    expires.is_some_and(|expires| expires <= OffsetDateTime::now_utc())
+}
+
+/// The kick message of a ban, from the configured template with the reason and expiration
+#[must_use]
+pub fn ban_message(template: &str, reason: &str, expires: Option<OffsetDateTime>) -> String {
+    let mut message = template.replace("{reason}", reason);
+    if let Some(expires) = expires {
+        message.push('\n');
+        message.push_str(
+            &ADVANCED_CONFIG
+                .moderation
+                .ban_expiration_message
+                .replace("{expiration}", &format_date(expires)),
+        );
+    }
+    message
+}
+
+/// The date a ban was made
+pub mod created {
+    use super::{format_date, parse_date, Deserialize, Deserializer, OffsetDateTime, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        date: &OffsetDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_date(*date))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OffsetDateTime, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        parse_date(&date).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The date a ban expires, `forever` if it doesn't
+pub mod expires {
+    use super::{
+        format_date, parse_date, Deserialize, Deserializer, OffsetDateTime, Serializer, FOREVER,
+    };
+
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer>(
+        date: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match date {
+            Some(date) => serializer.serialize_str(&format_date(*date)),
+            None => serializer.serialize_str(FOREVER),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<OffsetDateTime>, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        if date == FOREVER {
+            return Ok(None);
+        }
+        parse_date(&date)
+            .map(Some)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use time::{macros::datetime, Duration, OffsetDateTime};
+
+    use super::{format_date, is_expired, parse_date};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Ban {
+        #[serde(with = "super::created")]
+        created: OffsetDateTime,
+        #[serde(with = "super::expires")]
+        expires: Option<OffsetDateTime>,
+    }
+
+    #[test]
+    fn dates_use_the_vanilla_format() {
+        let date = datetime!(2024-12-24 18:00:00 +01:00);
+        assert_eq!(format_date(date), "2024-12-24 18:00:00 +0100");
+        assert_eq!(parse_date("2024-12-24 18:00:00 +0100").unwrap(), date);
+    }
+
+    #[test]
+    fn ban_dates_reserialize() {
+        let ban = Ban {
+            created: datetime!(2024-12-24 18:00:00 +00:00),
+            expires: Some(datetime!(2025-01-01 00:00:00 -05:00)),
+        };
+        let json = serde_json::to_string(&ban).unwrap();
+        assert_eq!(
+            json,
+            r#"{"created":"2024-12-24 18:00:00 +0000","expires":"2025-01-01 00:00:00 -0500"}"#
+        );
+        assert_eq!(serde_json::from_str::<Ban>(&json).unwrap(), ban);
+    }
+
+    #[test]
+    fn forever_bans_have_no_expiration() {
+        let json = r#"{"created":"2024-12-24 18:00:00 +0000","expires":"forever"}"#;
+        let ban: Ban = serde_json::from_str(json).unwrap();
+        assert_eq!(ban.expires, None);
+        assert_eq!(serde_json::to_string(&ban).unwrap(), json);
+    }
+
+    #[test]
+    fn invalid_dates_are_rejected() {
+        let json = r#"{"created":"yesterday","expires":"forever"}"#;
+        assert!(serde_json::from_str::<Ban>(json).is_err());
+    }
+
+    #[test]
+    fn bans_expire_once_the_date_passed() {
+        let now = OffsetDateTime::now_utc();
+        assert!(!is_expired(None));
+        assert!(is_expired(Some(now - Duration::minutes(1))));
+        assert!(!is_expired(Some(now + Duration::minutes(1))));
+    }
+}
@@ -0,0 +1,94 @@
+use std::{net::IpAddr, path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use super::{banlist_serializer, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static BANNED_IP_LIST: LazyLock<tokio::sync::RwLock<BannedIpList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(BannedIpList::load()));
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannedIpEntry {
+    pub ip: IpAddr,
+    #[serde(with = "banlist_serializer::created")]
+    pub created: OffsetDateTime,
+    /// Who made the ban
+    pub source: String,
+    /// When the ban is over, `None` if it doesn't expire
+    #[serde(with = "banlist_serializer::expires")]
+    pub expires: Option<OffsetDateTime>,
+    pub reason: String,
+}
+
+impl BannedIpEntry {
+    #[must_use]
+    pub fn new(
+        ip: IpAddr,
+        source: String,
+        expires: Option<OffsetDateTime>,
+        reason: String,
+    ) -> Self {
+        Self {
+            ip,
+            created: OffsetDateTime::now_utc(),
+            source,
+            expires,
+            reason,
+        }
+    }
+}
+
+/// The IP addresses nobody may join from, like vanilla's `IpBanList`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct BannedIpList {
+    pub banned_ips: Vec<BannedIpEntry>,
+}
+
+impl BannedIpList {
+    /// The ban of the address if it is banned. Bans which expired are removed
+    pub fn get_entry(&mut self, ip: IpAddr) -> Option<&BannedIpEntry> {
+        self.remove_invalid_entries();
+        self.banned_ips.iter().find(|entry| entry.ip == ip)
+    }
+
+    /// Bans the address, returns whether it wasn't banned yet
+    pub fn add(&mut self, entry: BannedIpEntry) -> bool {
+        if self.get_entry(entry.ip).is_some() {
+            return false;
+        }
+        self.banned_ips.push(entry);
+        self.save();
+        true
+    }
+
+    /// Lifts the ban of the address, returns it if it was banned
+    pub fn remove(&mut self, ip: IpAddr) -> Option<BannedIpEntry> {
+        self.remove_invalid_entries();
+        let index = self.banned_ips.iter().position(|entry| entry.ip == ip)?;
+        let entry = self.banned_ips.remove(index);
+        self.save();
+        Some(entry)
+    }
+
+    fn remove_invalid_entries(&mut self) {
+        let count = self.banned_ips.len();
+        self.banned_ips
+            .retain(|entry| !banlist_serializer::is_expired(entry.expires));
+        if self.banned_ips.len() != count {
+            self.save();
+        }
+    }
+}
+
+impl LoadJSONConfiguration for BannedIpList {
+    fn get_path() -> &'static Path {
+        Path::new("banned-ips.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the banned IPs configuration
+    }
+}
+
+impl SaveJSONConfiguration for BannedIpList {}
@@ -0,0 +1,109 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{banlist_serializer, LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static BANNED_PLAYER_LIST: LazyLock<tokio::sync::RwLock<BannedPlayerList>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(BannedPlayerList::load()));
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BannedPlayerEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(with = "banlist_serializer::created")]
+    pub created: OffsetDateTime,
+    /// Who made the ban
+    pub source: String,
+    /// When the ban is over, `None` if it doesn't expire
+    #[serde(with = "banlist_serializer::expires")]
+    pub expires: Option<OffsetDateTime>,
+    pub reason: String,
+}
+
+impl BannedPlayerEntry {
+    #[must_use]
+    pub fn new(
+        profile: &GameProfile,
+        source: String,
+        expires: Option<OffsetDateTime>,
+        reason: String,
+    ) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+            created: OffsetDateTime::now_utc(),
+            source,
+            expires,
+            reason,
+        }
+    }
+}
+
+/// The players who may not join, like vanilla's `UserBanList`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct BannedPlayerList {
+    pub banned_players: Vec<BannedPlayerEntry>,
+}
+
+impl BannedPlayerList {
+    /// The ban of the player if they are banned. Bans which expired are removed
+    pub fn get_entry(&mut self, profile: &GameProfile) -> Option<&BannedPlayerEntry> {
+        self.remove_invalid_entries();
+        self.banned_players
+            .iter()
+            .find(|entry| entry.uuid == profile.id)
+    }
+
+    /// Bans the player, returns whether they weren't banned yet
+    pub fn add(&mut self, entry: BannedPlayerEntry) -> bool {
+        self.remove_invalid_entries();
+        if self
+            .banned_players
+            .iter()
+            .any(|banned| banned.uuid == entry.uuid)
+        {
+            return false;
+        }
+        self.banned_players.push(entry);
+        self.save();
+        true
+    }
+
+    /// Lifts the ban of the player with the name, returns it if they were banned
+    pub fn remove(&mut self, name: &str) -> Option<BannedPlayerEntry> {
+        self.remove_invalid_entries();
+        let index = self
+            .banned_players
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name))?;
+        let entry = self.banned_players.remove(index);
+        self.save();
+        Some(entry)
+    }
+
+    fn remove_invalid_entries(&mut self) {
+        let count = self.banned_players.len();
+        self.banned_players
+            .retain(|entry| !banlist_serializer::is_expired(entry.expires));
+        if self.banned_players.len() != count {
+            self.save();
+        }
+    }
+}
+
+impl LoadJSONConfiguration for BannedPlayerList {
+    fn get_path() -> &'static Path {
+        Path::new("banned-players.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the banned players configuration
+    }
+}
+
+impl SaveJSONConfiguration for BannedPlayerList {}
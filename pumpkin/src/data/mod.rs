@@ -1,10 +1,15 @@
 use std::{env, fs, path::Path};
 
+use pumpkin_world::write_atomically;
 use serde::{Deserialize, Serialize};
 
 const DATA_FOLDER: &str = "data/";
 
+pub mod banlist_serializer;
+pub mod banned_ip_data;
+pub mod banned_player_data;
 pub mod op_data;
+pub mod whitelist_data;
 
 pub trait LoadJSONConfiguration {
     #[must_use]
@@ -51,8 +56,8 @@ pub trait LoadJSONConfiguration {
 }
 
 pub trait SaveJSONConfiguration: LoadJSONConfiguration {
-    // suppress clippy warning
-
+    /// Writes the data next to the old file first and then replaces it, so the file is never
+    /// left half written
     fn save(&self)
     where
         Self: Sized + Default + Serialize + for<'de> Deserialize<'de>,
@@ -69,19 +74,17 @@ pub trait SaveJSONConfiguration: LoadJSONConfiguration {
             Ok(content) => content,
             Err(err) => {
                 log::warn!(
-                    "Couldn't serialize operator data config to {:?}. Reason: {}",
-                    path,
-                    err
+                    "Couldn't serialize data config to {}. Reason: {err}",
+                    path.display()
                 );
                 return;
             }
         };
 
-        if let Err(err) = std::fs::write(&path, content) {
+        if let Err(err) = write_atomically(&path, content) {
             log::warn!(
-                "Couldn't write operator config to {:?}. Reason: {}",
-                path,
-                err
+                "Couldn't write data config to {}. Reason: {err}",
+                path.display()
             );
         }
     }
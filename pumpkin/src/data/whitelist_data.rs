@@ -0,0 +1,73 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::net::GameProfile;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static WHITELIST_CONFIG: LazyLock<tokio::sync::RwLock<WhitelistConfig>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(WhitelistConfig::load()));
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WhitelistEntry {
+    pub uuid: Uuid,
+    pub name: String,
+}
+
+impl WhitelistEntry {
+    #[must_use]
+    pub fn new(profile: &GameProfile) -> Self {
+        Self {
+            uuid: profile.id,
+            name: profile.name.clone(),
+        }
+    }
+}
+
+/// The players who may join while the whitelist is on, like vanilla's `UserWhiteList`
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct WhitelistConfig {
+    pub whitelist: Vec<WhitelistEntry>,
+}
+
+impl WhitelistConfig {
+    #[must_use]
+    pub fn is_whitelisted(&self, profile: &GameProfile) -> bool {
+        self.whitelist.iter().any(|entry| entry.uuid == profile.id)
+    }
+
+    /// Adds the player, returns whether they weren't on the whitelist yet
+    pub fn add(&mut self, profile: &GameProfile) -> bool {
+        if self.is_whitelisted(profile) {
+            return false;
+        }
+        self.whitelist.push(WhitelistEntry::new(profile));
+        self.save();
+        true
+    }
+
+    /// Removes the player with the name, returns the entry if they were on the whitelist
+    pub fn remove(&mut self, name: &str) -> Option<WhitelistEntry> {
+        let index = self
+            .whitelist
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name))?;
+        let entry = self.whitelist.remove(index);
+        self.save();
+        Some(entry)
+    }
+}
+
+impl LoadJSONConfiguration for WhitelistConfig {
+    fn get_path() -> &'static Path {
+        Path::new("whitelist.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the whitelist configuration
+    }
+}
+
+impl SaveJSONConfiguration for WhitelistConfig {}
@@ -0,0 +1,214 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::{vector3::Vector3, wrap_degrees};
+use pumpkin_world::physics::Movement;
+
+use crate::entity::Entity;
+
+/// How much faster mobs accelerate on the ground than in the air, like vanilla's
+/// `getFrictionInfluencedSpeed` on blocks with the default friction
+const GROUND_ACCELERATION: f64 = 0.216_000_02 / (0.546 * 0.546 * 0.546);
+/// How fast mobs accelerate in the air and in water
+const AIR_ACCELERATION: f64 = 0.02;
+/// Mobs strafe with a quarter of their walking speed
+const STRAFE_SPEED: f64 = 0.25;
+/// Velocity mobs jump off the ground with
+const JUMP_VELOCITY: f64 = 0.42;
+/// Velocity mobs swimming up gain every tick
+const SWIM_UP_VELOCITY: f64 = 0.04;
+/// Mobs count as arrived once they are this close to where they walk to horizontally
+const ARRIVAL_DISTANCE: f64 = 0.5;
+/// Mobs which can't reach where they walk to give up after this many ticks
+const MAX_MOVE_TICKS: u32 = 200;
+/// Degrees the body of a mob turns towards where it walks every tick
+const MAX_BODY_TURN: f32 = 90.0;
+/// Degrees the head of a mob turns every tick
+const MAX_HEAD_TURN: f32 = 10.0;
+const MAX_HEAD_PITCH: f32 = 40.0;
+/// Degrees the head of a mob turns away from its body at most
+const MAX_HEAD_OFFSET: f32 = 75.0;
+
+/// Where a mob walks to
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct MoveTarget {
+    position: Vector3<f64>,
+    /// Multiplies the walking speed of the mob
+    speed: f64,
+    ticks: u32,
+}
+
+/// Turns the angle towards the target angle by at most the maximum, like vanilla's `rotlerp`
+fn rotate_towards(from: f32, to: f32, max: f32) -> f32 {
+    from + wrap_degrees(to - from).clamp(-max, max)
+}
+
+/// Steers a mob, like vanilla's `MoveControl`, `LookControl` and `JumpControl`. The navigation
+/// and goals set where the mob walks and looks, the mob turns and accelerates towards it every
+/// tick
+#[derive(Default)]
+pub struct MobControls {
+    move_target: AtomicCell<Option<MoveTarget>>,
+    /// How far forwards and sideways the mob strafes this tick
+    strafe: AtomicCell<Option<(f64, f64)>>,
+    /// Where the mob looks this tick, goals have to keep looking
+    look_target: AtomicCell<Option<Vector3<f64>>>,
+    jumping: AtomicBool,
+    /// Whether a block stopped the mob sideways during its last movement
+    blocked: AtomicBool,
+    /// How deep the mob was in water during its last movement
+    water_height: AtomicCell<f64>,
+}
+
+impl MobControls {
+    /// Walks the mob in a straight line to the position, the speed multiplies its walking speed
+    pub fn move_to(&self, position: Vector3<f64>, speed: f64) {
+        self.move_target.store(Some(MoveTarget {
+            position,
+            speed,
+            ticks: 0,
+        }));
+    }
+
+    pub fn stop(&self) {
+        self.move_target.store(None);
+    }
+
+    /// Walks forwards or backwards and sideways this tick instead of walking somewhere, like
+    /// skeletons circling what they shoot at. The inputs go from -1 to 1
+    pub fn strafe(&self, forward: f64, sideways: f64) {
+        self.move_target.store(None);
+        self.strafe.store(Some((forward, sideways)));
+    }
+
+    /// Turns the head of the mob towards the position this tick
+    pub fn look_at(&self, position: Vector3<f64>) {
+        self.look_target.store(Some(position));
+    }
+
+    /// Jumps or swims up this tick
+    pub fn jump(&self) {
+        self.jumping.store(true, Ordering::Relaxed);
+    }
+
+    /// How deep the mob is in water
+    pub fn water_height(&self) -> f64 {
+        self.water_height.load()
+    }
+
+    /// Remembers what happened during the movement of the mob, blocks in the way are jumped onto
+    pub fn moved(&self, movement: &Movement) {
+        self.blocked
+            .store(movement.horizontal_collision, Ordering::Relaxed);
+        self.water_height
+            .store(movement.water.map_or(0.0, |water| water.height));
+    }
+
+    /// Turns and accelerates the mob for this tick, its walking speed is in blocks per tick
+    pub fn tick(&self, entity: &Entity, walking_speed: f64) {
+        let moving = self.tick_move(entity, walking_speed);
+        self.tick_look(entity, moving);
+        self.tick_jump(entity);
+    }
+
+    fn tick_move(&self, entity: &Entity, walking_speed: f64) -> bool {
+        if let Some((forward, sideways)) = self.strafe.take() {
+            self.accelerate(entity, forward, sideways, STRAFE_SPEED * walking_speed);
+            return false;
+        }
+        let Some(mut target) = self.move_target.load() else {
+            return false;
+        };
+        let pos = entity.pos.load();
+        let distance = target.position.sub(&pos);
+        let horizontal = distance.x.hypot(distance.z);
+        target.ticks += 1;
+        if horizontal < ARRIVAL_DISTANCE || target.ticks > MAX_MOVE_TICKS {
+            self.move_target.store(None);
+            return false;
+        }
+        self.move_target.store(Some(target));
+
+        let yaw = rotate_towards(
+            entity.yaw.load(),
+            distance.z.atan2(distance.x).to_degrees() as f32 - 90.0,
+            MAX_BODY_TURN,
+        );
+        entity.set_rotation(yaw, entity.pitch.load());
+        let speed = target.speed * walking_speed;
+        self.accelerate(entity, speed, 0.0, speed);
+
+        // Blocks in the way and higher ground are jumped onto
+        let width = entity.bounding_box_size.load().width;
+        let step_up = distance.y > 0.6 && horizontal < width.max(1.0);
+        if entity.on_ground.load(Ordering::Relaxed)
+            && (self.blocked.load(Ordering::Relaxed) || step_up)
+        {
+            self.jump();
+        }
+        true
+    }
+
+    /// Accelerates the mob relative to where its body faces, like vanilla's `moveRelative`.
+    /// Mobs accelerate faster on the ground
+    fn accelerate(&self, entity: &Entity, forward: f64, sideways: f64, speed: f64) {
+        let length = forward.hypot(sideways).max(1.0);
+        let (forward, sideways) = (forward / length, sideways / length);
+        // Mobs only walk with 98% of their input, like vanilla's `zza`
+        let acceleration = 0.98
+            * if entity.on_ground.load(Ordering::Relaxed) && self.water_height() == 0.0 {
+                speed * GROUND_ACCELERATION
+            } else {
+                AIR_ACCELERATION
+            };
+        let (yaw_sin, yaw_cos) = f64::from(entity.yaw.load()).to_radians().sin_cos();
+        let direction = Vector3::new(
+            sideways.mul_add(yaw_cos, -forward * yaw_sin),
+            0.0,
+            forward.mul_add(yaw_cos, sideways * yaw_sin),
+        );
+        entity
+            .velocity
+            .store(entity.velocity.load() + direction * acceleration);
+    }
+
+    fn tick_look(&self, entity: &Entity, moving: bool) {
+        let mut body_yaw = entity.yaw.load();
+        let mut head_yaw = entity.head_yaw.load();
+        let pitch = if let Some(target) = self.look_target.take() {
+            let distance = target.sub(&entity.eye_position());
+            let target_yaw = distance.z.atan2(distance.x).to_degrees() as f32 - 90.0;
+            let target_pitch = -distance.y.atan2(distance.x.hypot(distance.z)).to_degrees() as f32;
+            head_yaw = rotate_towards(head_yaw, target_yaw, MAX_HEAD_TURN);
+            rotate_towards(0.0, target_pitch, MAX_HEAD_PITCH)
+        } else {
+            head_yaw = rotate_towards(head_yaw, body_yaw, MAX_HEAD_TURN);
+            0.0
+        };
+        // Walking mobs turn their head with their body, standing ones turn their body with their head
+        if moving {
+            head_yaw = body_yaw
+                + wrap_degrees(head_yaw - body_yaw).clamp(-MAX_HEAD_OFFSET, MAX_HEAD_OFFSET);
+        } else {
+            body_yaw = head_yaw
+                + wrap_degrees(body_yaw - head_yaw).clamp(-MAX_HEAD_OFFSET, MAX_HEAD_OFFSET);
+        }
+        entity.head_yaw.store(head_yaw);
+        entity.set_rotation(body_yaw, pitch);
+    }
+
+    fn tick_jump(&self, entity: &Entity) {
+        if !self.jumping.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        let mut velocity = entity.velocity.load();
+        if self.water_height() > 0.0 {
+            velocity.y += SWIM_UP_VELOCITY;
+        } else if entity.on_ground.load(Ordering::Relaxed) {
+            velocity.y = JUMP_VELOCITY;
+        } else {
+            return;
+        }
+        entity.velocity.store(velocity);
+    }
+}
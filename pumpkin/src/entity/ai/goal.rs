@@ -0,0 +1,123 @@
+use async_trait::async_trait;
+
+use crate::entity::mob::MobEntity;
+
+use super::Surroundings;
+
+/// What a goal controls while it runs, goals controlling the same thing can't run together,
+/// like vanilla's `Goal.Flag`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Move,
+    Look,
+    Jump,
+    /// Picking what the mob attacks
+    Target,
+}
+
+/// Something a mob wants to do, like wandering around or following a player holding food
+#[async_trait]
+pub trait Goal: Send + Sync {
+    /// What the goal takes over while it runs
+    fn controls(&self) -> &'static [Control];
+
+    /// Whether the goal should start running
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool;
+
+    /// Whether the running goal should keep running, goals run as long as they could start by
+    /// default
+    async fn should_continue(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        self.can_start(mob, surroundings).await
+    }
+
+    /// Whether more important goals may stop the goal to take over its controls
+    fn can_be_interrupted(&self) -> bool {
+        true
+    }
+
+    async fn start(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) {}
+
+    async fn stop(&mut self, _mob: &MobEntity) {}
+
+    /// Called every tick while the goal runs
+    async fn tick(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) {}
+}
+
+struct PrioritizedGoal {
+    /// Goals with lower priorities are more important
+    priority: u8,
+    running: bool,
+    goal: Box<dyn Goal>,
+}
+
+impl PrioritizedGoal {
+    fn shares_controls(&self, controls: &[Control]) -> bool {
+        self.goal
+            .controls()
+            .iter()
+            .any(|control| controls.contains(control))
+    }
+}
+
+/// The goals of a mob, like vanilla's `GoalSelector`. More important goals start first and take
+/// over the controls of less important goals
+#[derive(Default)]
+pub struct GoalSelector {
+    goals: Vec<PrioritizedGoal>,
+}
+
+impl GoalSelector {
+    pub fn add_goal(&mut self, priority: u8, goal: impl Goal + 'static) {
+        self.goals.push(PrioritizedGoal {
+            priority,
+            running: false,
+            goal: Box::new(goal),
+        });
+        // Goals with the same priority keep the order they were added in
+        self.goals.sort_by_key(|goal| goal.priority);
+    }
+
+    /// Stops the goals which are done, starts the goals which can run and ticks the running ones
+    pub async fn tick(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) {
+        for goal in &mut self.goals {
+            if goal.running && !goal.goal.should_continue(mob, surroundings).await {
+                goal.running = false;
+                goal.goal.stop(mob).await;
+            }
+        }
+
+        for index in 0..self.goals.len() {
+            let (priority, controls) = {
+                let goal = &self.goals[index];
+                if goal.running {
+                    continue;
+                }
+                (goal.priority, goal.goal.controls())
+            };
+            // The controls have to be free or held by less important goals
+            let available = self
+                .goals
+                .iter()
+                .filter(|other| other.running && other.shares_controls(controls))
+                .all(|other| other.priority > priority && other.goal.can_be_interrupted());
+            if !available || !self.goals[index].goal.can_start(mob, surroundings).await {
+                continue;
+            }
+            for other in &mut self.goals {
+                if other.running && other.shares_controls(controls) {
+                    other.running = false;
+                    other.goal.stop(mob).await;
+                }
+            }
+            let goal = &mut self.goals[index];
+            goal.running = true;
+            goal.goal.start(mob, surroundings).await;
+        }
+
+        for goal in &mut self.goals {
+            if goal.running {
+                goal.goal.tick(mob, surroundings).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_core::{math::vector3::Vector3, Difficulty};
+use rand::{random, thread_rng, Rng};
+
+use crate::entity::{mob::MobEntity, player::Player, projectile::bow_power};
+
+use super::{
+    can_attack, can_see,
+    goal::{Control, Goal, GoalSelector},
+    passive::{LookAtPlayerGoal, RandomLookAroundGoal, RandomStrollGoal},
+    sees_sky, sees_sky_at, Surroundings,
+};
+
+/// How far mobs reach past their bounding box when they hit something, like vanilla's
+/// `DEFAULT_ATTACK_REACH`
+const ATTACK_REACH: f64 = 0.828_427_124_746_19;
+/// Ticks between the melee attacks of mobs
+const ATTACK_COOLDOWN: u32 = 20;
+/// Ticks skeletons draw their bow before they shoot
+const BOW_DRAW_TICKS: u32 = 20;
+/// How far away skeletons shoot from
+const BOW_RANGE: f64 = 15.0;
+
+/// The goals of zombies, like their vanilla `registerGoals`
+#[must_use]
+pub fn zombie_goals() -> GoalSelector {
+    let mut goals = GoalSelector::default();
+    goals.add_goal(2, MeleeAttackGoal::new(1.0));
+    goals.add_goal(7, RandomStrollGoal::new(1.0));
+    goals.add_goal(8, LookAtPlayerGoal::new(8.0));
+    goals.add_goal(8, RandomLookAroundGoal::default());
+    goals
+}
+
+/// The goals of skeletons, like their vanilla `registerGoals`. Skeletons without a bow fight in
+/// melee instead
+#[must_use]
+pub fn skeleton_goals() -> GoalSelector {
+    let mut goals = GoalSelector::default();
+    goals.add_goal(3, FleeSunGoal::new(1.0));
+    goals.add_goal(4, RangedBowAttackGoal::new(1.0));
+    goals.add_goal(4, MeleeAttackGoal::new(1.2));
+    goals.add_goal(5, RandomStrollGoal::new(1.0));
+    goals.add_goal(6, LookAtPlayerGoal::new(8.0));
+    goals.add_goal(6, RandomLookAroundGoal::default());
+    goals
+}
+
+/// The player the mob attacks if it can still attack them
+fn attackable_target<'a>(
+    mob: &MobEntity,
+    surroundings: &Surroundings<'a>,
+) -> Option<&'a Arc<Player>> {
+    mob.target(surroundings).filter(|target| can_attack(target))
+}
+
+/// Runs at the target and hits it, like vanilla's `MeleeAttackGoal`. Zombies raise their arms
+/// meanwhile
+pub struct MeleeAttackGoal {
+    speed: f64,
+    /// Ticks until the mob walks to where the target is now
+    repath: u32,
+    /// Ticks until the mob can hit again
+    cooldown: u32,
+}
+
+impl MeleeAttackGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            repath: 0,
+            cooldown: 0,
+        }
+    }
+
+    /// Whether the target is within reach of the mob
+    fn can_reach(mob: &MobEntity, target: &Player) -> bool {
+        let bounding_box = mob.entity().bounding_box.load();
+        let mut reach = bounding_box;
+        reach.min_x -= ATTACK_REACH;
+        reach.min_z -= ATTACK_REACH;
+        reach.max_x += ATTACK_REACH;
+        reach.max_z += ATTACK_REACH;
+        reach.intersects(&target.living_entity.entity.bounding_box.load())
+    }
+}
+
+#[async_trait]
+impl Goal for MeleeAttackGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        // Mobs holding a bow shoot instead
+        attackable_target(mob, surroundings).is_some() && !mob.holds_bow().await
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        self.repath = 0;
+        self.cooldown = 0;
+        mob.set_aggressive(true);
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.set_aggressive(false);
+        mob.navigation.stop();
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) {
+        let Some(target) = attackable_target(mob, surroundings) else {
+            return;
+        };
+        let target_entity = &target.living_entity.entity;
+        mob.controls.look_at(target_entity.eye_position());
+        let sees_target = can_see(mob, target_entity);
+        self.repath = self.repath.saturating_sub(1);
+        if sees_target && self.repath == 0 {
+            let position = target_entity.pos.load();
+            let distance = position.sub(&mob.entity().pos.load()).length_squared();
+            // Mobs far away from their target walk longer before they look where it went
+            let delay = if distance > 1024.0 {
+                10
+            } else if distance > 256.0 {
+                5
+            } else {
+                0
+            };
+            self.repath = thread_rng().gen_range(4..=10) + delay;
+            mob.navigation.move_to(position, self.speed);
+        }
+        self.cooldown = self.cooldown.saturating_sub(1);
+        if self.cooldown == 0 && sees_target && Self::can_reach(mob, target) {
+            self.cooldown = ATTACK_COOLDOWN;
+            mob.attack(target).await;
+        }
+    }
+}
+
+/// Shoots at the target with a bow, strafing around it once it is close, like vanilla's
+/// `RangedBowAttackGoal`
+pub struct RangedBowAttackGoal {
+    speed: f64,
+    /// Ticks until the mob draws its bow again
+    attack_time: i32,
+    /// Ticks the mob saw its target for when positive, didn't see it for when negative
+    see_time: i32,
+    /// Ticks the mob strafed in one direction, `None` while it walks to its target
+    strafing_time: Option<u32>,
+    clockwise: bool,
+    backwards: bool,
+    /// Ticks the mob draws its bow for
+    draw_ticks: u32,
+}
+
+impl RangedBowAttackGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            attack_time: -1,
+            see_time: 0,
+            strafing_time: None,
+            clockwise: false,
+            backwards: false,
+            draw_ticks: 0,
+        }
+    }
+
+    /// Walks to the target until it is close and seen, then strafes around it
+    fn tick_movement(&mut self, mob: &MobEntity, target: &Player, distance: f64) {
+        let target_entity = &target.living_entity.entity;
+        if distance <= BOW_RANGE * BOW_RANGE && self.see_time >= 20 {
+            mob.navigation.stop();
+            self.strafing_time = Some(self.strafing_time.map_or(0, |time| time + 1));
+        } else {
+            mob.navigation.move_to(target_entity.pos.load(), self.speed);
+            self.strafing_time = None;
+        }
+        let Some(strafing_time) = self.strafing_time else {
+            mob.controls.look_at(target_entity.eye_position());
+            return;
+        };
+        if strafing_time >= 20 {
+            if random::<f32>() < 0.3 {
+                self.clockwise = !self.clockwise;
+            }
+            if random::<f32>() < 0.3 {
+                self.backwards = !self.backwards;
+            }
+            self.strafing_time = Some(0);
+        }
+        // Skeletons keep their distance
+        if distance > BOW_RANGE * BOW_RANGE * 0.75 {
+            self.backwards = false;
+        } else if distance < BOW_RANGE * BOW_RANGE * 0.25 {
+            self.backwards = true;
+        }
+        mob.controls.strafe(
+            if self.backwards { -0.5 } else { 0.5 },
+            if self.clockwise { 0.5 } else { -0.5 },
+        );
+        mob.controls.look_at(target_entity.eye_position());
+    }
+}
+
+#[async_trait]
+impl Goal for RangedBowAttackGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        attackable_target(mob, surroundings).is_some() && mob.holds_bow().await
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        mob.set_aggressive(true);
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.set_aggressive(false);
+        mob.set_using_item(false);
+        mob.navigation.stop();
+        self.see_time = 0;
+        self.attack_time = -1;
+        self.draw_ticks = 0;
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) {
+        let Some(target) = attackable_target(mob, surroundings) else {
+            return;
+        };
+        let target_entity = &target.living_entity.entity;
+        let distance = target_entity
+            .pos
+            .load()
+            .sub(&mob.entity().pos.load())
+            .length_squared();
+        let sees_target = can_see(mob, target_entity);
+        if sees_target != (self.see_time > 0) {
+            self.see_time = 0;
+        }
+        self.see_time += if sees_target { 1 } else { -1 };
+        self.tick_movement(mob, target, distance);
+
+        if mob.is_using_item() {
+            if !sees_target && self.see_time < -60 {
+                mob.set_using_item(false);
+                self.draw_ticks = 0;
+            } else if sees_target {
+                self.draw_ticks += 1;
+                if self.draw_ticks >= BOW_DRAW_TICKS {
+                    mob.set_using_item(false);
+                    mob.shoot_arrow(
+                        surroundings.server,
+                        target_entity,
+                        bow_power(self.draw_ticks),
+                    )
+                    .await;
+                    self.draw_ticks = 0;
+                    // Skeletons shoot faster on hard difficulty
+                    self.attack_time = if mob.entity().world().difficulty.load() == Difficulty::Hard
+                    {
+                        20
+                    } else {
+                        40
+                    };
+                }
+            }
+        } else {
+            self.attack_time -= 1;
+            if self.attack_time <= 0 && self.see_time >= -60 {
+                mob.set_using_item(true);
+            }
+        }
+    }
+}
+
+/// Burning mobs look for shade while the sun shines, like vanilla's `FleeSunGoal`
+pub struct FleeSunGoal {
+    speed: f64,
+    shade: Option<Vector3<f64>>,
+}
+
+impl FleeSunGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self { speed, shade: None }
+    }
+
+    /// A random place near the mob it can stand at out of the sun
+    fn find_shade(mob: &MobEntity) -> Option<Vector3<f64>> {
+        let entity = mob.entity();
+        let world = entity.world();
+        let origin = entity.pos.load();
+        let size = entity.bounding_box_size.load();
+        let mut rng = thread_rng();
+        (0..10).find_map(|_| {
+            let position = Vector3::new(
+                origin.x.floor() + f64::from(rng.gen_range(-10..=10)) + 0.5,
+                origin.y.floor() + f64::from(rng.gen_range(-3..=3)),
+                origin.z.floor() + f64::from(rng.gen_range(-10..=10)) + 0.5,
+            );
+            (!sees_sky_at(entity, position) && world.level.can_stand_at(position, &size, false))
+                .then_some(position)
+        })
+    }
+}
+
+#[async_trait]
+impl Goal for FleeSunGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        // Mobs only flee when they have no target
+        if mob.target(surroundings).is_some()
+            || !surroundings.daylight
+            || !mob.living_entity.is_on_fire()
+            || !sees_sky(mob.entity())
+            || mob.equipment.lock().await[5].is_some()
+        {
+            return false;
+        }
+        self.shade = Self::find_shade(mob);
+        self.shade.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        mob.navigation.is_moving()
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if let Some(shade) = self.shade {
+            mob.navigation.move_to(shade, self.speed);
+        }
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.navigation.stop();
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use goal::GoalSelector;
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_entity::entity_type::EntityType;
+
+use crate::server::Server;
+
+use super::{
+    mob::{follow_range, MobEntity},
+    player::Player,
+    Entity,
+};
+
+pub mod control;
+pub mod goal;
+pub mod hostile;
+pub mod navigation;
+pub mod passive;
+pub mod target;
+
+/// The goals mobs of the type think with, mobs without AI have none
+#[must_use]
+pub fn goals(entity_type: &EntityType) -> GoalSelector {
+    match entity_type {
+        EntityType::Cow | EntityType::Pig | EntityType::Sheep | EntityType::Chicken => {
+            passive::animal_goals(entity_type)
+        }
+        EntityType::Zombie | EntityType::Husk | EntityType::ZombieVillager => {
+            hostile::zombie_goals()
+        }
+        EntityType::Skeleton | EntityType::Stray | EntityType::Bogged => hostile::skeleton_goals(),
+        _ => GoalSelector::default(),
+    }
+}
+
+/// The goals picking what mobs of the type attack, peaceful mobs have none
+#[must_use]
+pub fn target_goals(entity_type: &EntityType) -> GoalSelector {
+    match entity_type {
+        EntityType::Zombie
+        | EntityType::Husk
+        | EntityType::ZombieVillager
+        | EntityType::Skeleton
+        | EntityType::Stray
+        | EntityType::Bogged => target::hostile_target_goals(follow_range(entity_type)),
+        _ => GoalSelector::default(),
+    }
+}
+
+/// Whether mobs attack the player, players in creative or spectator mode and dead players are
+/// left alone
+pub fn can_attack(player: &Player) -> bool {
+    matches!(
+        player.gamemode.load(),
+        GameMode::Survival | GameMode::Adventure
+    ) && player.living_entity.health.load() > 0.0
+}
+
+/// Whether no block is in the way between the eyes of the mob and the entity, like vanilla's
+/// `hasLineOfSight`
+pub fn can_see(mob: &MobEntity, target: &Entity) -> bool {
+    let entity = mob.entity();
+    entity
+        .world()
+        .level
+        .clip_blocks(entity.eye_position(), target.eye_position())
+        .is_none()
+}
+
+/// Whether nothing blocks the sky above the position
+fn sees_sky_at(entity: &Entity, position: Vector3<f64>) -> bool {
+    entity
+        .world()
+        .level
+        .can_see_sky(&WorldPosition(Vector3::new(
+            position.x.floor() as i32,
+            position.y.floor() as i32,
+            position.z.floor() as i32,
+        )))
+}
+
+/// Whether nothing blocks the sky above the eyes of the entity, undead mobs burn then
+pub fn sees_sky(entity: &Entity) -> bool {
+    sees_sky_at(entity, entity.eye_position())
+}
+
+/// What mobs know of the world around them while they think, gathered once every tick
+pub struct Surroundings<'a> {
+    pub server: &'a Server,
+    pub players: &'a [Arc<Player>],
+    pub mobs: &'a [Arc<MobEntity>],
+    /// Whether the sun shines, undead mobs burn in it
+    pub daylight: bool,
+}
+
+impl Surroundings<'_> {
+    /// The players mobs notice, spectators and dead players are ignored
+    pub fn visible_players(&self) -> impl Iterator<Item = &Arc<Player>> {
+        self.players.iter().filter(|player| {
+            player.gamemode.load() != GameMode::Spectator
+                && player.living_entity.health.load() > 0.0
+        })
+    }
+
+    /// The closest player mobs notice within the range of the position
+    #[must_use]
+    pub fn nearest_player(&self, position: Vector3<f64>, range: f64) -> Option<&Arc<Player>> {
+        self.visible_players()
+            .map(|player| {
+                let distance = player.living_entity.entity.pos.load().sub(&position);
+                (distance.length_squared(), player)
+            })
+            .filter(|(distance, _)| *distance <= range * range)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, player)| player)
+    }
+}
@@ -0,0 +1,136 @@
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_world::pathfinding::{NodeEvaluator, Path};
+use tokio::sync::Mutex;
+
+use crate::entity::{mob::follow_range, Entity};
+
+use super::control::MobControls;
+
+/// Ticks between the checks whether a mob got stuck on its path
+const STUCK_CHECK_INTERVAL: u32 = 100;
+/// Mobs which moved less than this since the last check are stuck, like in vanilla's
+/// `doStuckDetection`
+const STUCK_DISTANCE: f64 = 1.5;
+/// Mobs count as at a waypoint once they are this close to it horizontally
+const WAYPOINT_DISTANCE: f64 = 0.5;
+
+/// Where a mob walks to
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Destination {
+    position: Vector3<f64>,
+    /// Multiplies the walking speed of the mob
+    speed: f64,
+}
+
+#[derive(Default)]
+struct PathState {
+    path: Option<Path>,
+    /// The waypoint the mob walks to next
+    index: usize,
+    ticks_since_stuck_check: u32,
+    /// Where the mob was at the last check whether it got stuck
+    stuck_check_position: Vector3<f64>,
+}
+
+/// Whether mobs of the type swim up in deep water, like the mobs with a vanilla `FloatGoal`
+const fn can_swim(entity_type: &EntityType) -> bool {
+    matches!(
+        entity_type,
+        EntityType::Cow | EntityType::Pig | EntityType::Sheep | EntityType::Chicken
+    )
+}
+
+/// Finds the way for a mob and walks it along, like vanilla's `GroundPathNavigation`. Goals set
+/// where the mob walks to, the mob follows the path there through the controls every tick
+#[derive(Default)]
+pub struct MobNavigation {
+    destination: AtomicCell<Option<Destination>>,
+    state: Mutex<PathState>,
+}
+
+impl MobNavigation {
+    /// Walks the mob along a path to the position, the speed multiplies its walking speed. The
+    /// path is found again once the position is in another block
+    pub fn move_to(&self, position: Vector3<f64>, speed: f64) {
+        self.destination
+            .store(Some(Destination { position, speed }));
+    }
+
+    pub fn stop(&self) {
+        self.destination.store(None);
+    }
+
+    /// Whether the mob still walks somewhere, mobs stop at the end of their path even when it
+    /// didn't lead all the way
+    pub fn is_moving(&self) -> bool {
+        self.destination.load().is_some()
+    }
+
+    /// Finds the way from where the mob stands, mobs look further for a way the further they
+    /// follow their targets
+    fn find_path(entity: &Entity, to: Vector3<f64>) -> Option<Path> {
+        let evaluator = NodeEvaluator {
+            can_swim: can_swim(&entity.entity_type),
+            ..NodeEvaluator::new(entity.bounding_box_size.load())
+        };
+        let max_visited = (follow_range(&entity.entity_type) * 16.0) as usize;
+        entity
+            .world()
+            .level
+            .find_path(&evaluator, entity.pos.load(), to, max_visited)
+    }
+
+    /// Finds a new path when the destination moved or the mob got stuck, and steers the mob
+    /// towards the next waypoint
+    pub async fn tick(&self, entity: &Entity, controls: &MobControls) {
+        let mut state = self.state.lock().await;
+        let state = &mut *state;
+        let Some(destination) = self.destination.load() else {
+            if state.path.take().is_some() {
+                controls.stop();
+            }
+            return;
+        };
+        let position = entity.pos.load();
+
+        state.ticks_since_stuck_check += 1;
+        let mut stuck = false;
+        if state.ticks_since_stuck_check >= STUCK_CHECK_INTERVAL {
+            stuck = position.sub(&state.stuck_check_position).length() < STUCK_DISTANCE;
+            state.ticks_since_stuck_check = 0;
+            state.stuck_check_position = position;
+        }
+        if stuck
+            || state
+                .path
+                .as_ref()
+                .is_none_or(|path| !path.leads_to(destination.position))
+        {
+            state.path = Self::find_path(entity, destination.position);
+            state.index = 0;
+            state.ticks_since_stuck_check = 0;
+            state.stuck_check_position = position;
+        }
+
+        let waypoint = state.path.as_ref().and_then(|path| {
+            // Waypoints the mob already reached are skipped
+            while let Some(waypoint) = path.waypoints.get(state.index) {
+                let offset = waypoint.sub(&position);
+                if offset.x.hypot(offset.z) >= WAYPOINT_DISTANCE || offset.y.abs() >= 1.0 {
+                    return Some(*waypoint);
+                }
+                state.index += 1;
+            }
+            None
+        });
+        if let Some(waypoint) = waypoint {
+            controls.move_to(waypoint, destination.speed);
+        } else {
+            self.destination.store(None);
+            state.path = None;
+            controls.stop();
+        }
+    }
+}
@@ -0,0 +1,630 @@
+use std::{f64::consts::TAU, mem, sync::Arc};
+
+use async_trait::async_trait;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_world::{item::item_registry::get_item_name_by_id, world_info::game_rules::BoolRule};
+use rand::{random, thread_rng, Rng};
+
+use crate::{
+    entity::{mob::MobEntity, player::Player},
+    server::Server,
+};
+
+use super::{
+    goal::{Control, Goal, GoalSelector},
+    Surroundings,
+};
+
+/// Ticks animals wait after breeding before they can breed again
+const BREEDING_COOLDOWN: i32 = 6000;
+/// Ticks babies take to grow up
+const BABY_AGE: i32 = -24000;
+/// Ticks animals in love look for a partner before they breed
+const BREEDING_TIME: u32 = 60;
+
+/// Whether animals of the type eat the item, they breed with it and grow up faster from it
+#[must_use]
+pub fn is_food(entity_type: &EntityType, item: &str) -> bool {
+    match entity_type {
+        EntityType::Cow | EntityType::Sheep => item == "wheat",
+        EntityType::Pig => matches!(item, "carrot" | "potato" | "beetroot"),
+        EntityType::Chicken => matches!(
+            item,
+            "wheat_seeds"
+                | "melon_seeds"
+                | "pumpkin_seeds"
+                | "beetroot_seeds"
+                | "torchflower_seeds"
+                | "pitcher_pod"
+        ),
+        _ => false,
+    }
+}
+
+/// Whether animals of the type follow players holding the item
+fn is_tempted_by(entity_type: &EntityType, item: &str) -> bool {
+    is_food(entity_type, item)
+        || (matches!(entity_type, EntityType::Pig) && item == "carrot_on_a_stick")
+}
+
+/// The goals of cows, pigs, sheep and chickens, like their vanilla `registerGoals`
+#[must_use]
+pub fn animal_goals(entity_type: &EntityType) -> GoalSelector {
+    let (panic, tempt, follow_parent) = match entity_type {
+        EntityType::Cow => (2.0, 1.25, 1.25),
+        EntityType::Pig => (1.25, 1.2, 1.1),
+        EntityType::Sheep => (1.25, 1.1, 1.1),
+        _ => (1.4, 1.0, 1.1),
+    };
+    let mut goals = GoalSelector::default();
+    goals.add_goal(0, FloatGoal);
+    goals.add_goal(1, PanicGoal::new(panic));
+    goals.add_goal(2, BreedGoal::new(1.0));
+    goals.add_goal(3, TemptGoal::new(tempt));
+    goals.add_goal(4, FollowParentGoal::new(follow_parent));
+    goals.add_goal(5, RandomStrollGoal::new(1.0));
+    goals.add_goal(6, LookAtPlayerGoal::new(6.0));
+    goals.add_goal(7, RandomLookAroundGoal::default());
+    goals
+}
+
+/// A random position near the mob it can stand at, like vanilla's `LandRandomPos`. Positions in
+/// the air are moved down onto the ground
+fn random_position(
+    mob: &MobEntity,
+    horizontal: i32,
+    vertical: i32,
+    avoid_water: bool,
+) -> Option<Vector3<f64>> {
+    let entity = mob.entity();
+    let world = entity.world();
+    let origin = entity.pos.load();
+    let size = entity.bounding_box_size.load();
+    let mut rng = thread_rng();
+    (0..10).find_map(|_| {
+        let x = origin.x.floor() + f64::from(rng.gen_range(-horizontal..=horizontal)) + 0.5;
+        let z = origin.z.floor() + f64::from(rng.gen_range(-horizontal..=horizontal)) + 0.5;
+        let top = origin.y.floor() + f64::from(rng.gen_range(-vertical..=vertical));
+        (0..=vertical * 2)
+            .map(|depth| Vector3::new(x, top - f64::from(depth), z))
+            .find(|position| world.level.can_stand_at(*position, &size, avoid_water))
+    })
+}
+
+/// Whether both mobs are of the same kind, like two cows
+fn same_type(a: &EntityType, b: &EntityType) -> bool {
+    mem::discriminant(a) == mem::discriminant(b)
+}
+
+/// Whether the player holds an item the mob follows in either hand
+async fn holds_temptation(player: &Player, entity_type: &EntityType) -> bool {
+    let mut inventory = player.inventory().lock().await;
+    [false, true].into_iter().any(|offhand| {
+        inventory
+            .hand_item_mut(offhand)
+            .as_ref()
+            .and_then(|item| get_item_name_by_id(item.item_id))
+            .is_some_and(|item| is_tempted_by(entity_type, item))
+    })
+}
+
+/// Breeds the animals, the baby spawns at the first one
+async fn breed(mob: &MobEntity, partner: &MobEntity, server: &Server) {
+    for parent in [mob, partner] {
+        parent.set_age(BREEDING_COOLDOWN);
+        parent.reset_love();
+    }
+    let entity = mob.entity();
+    let world = entity.world();
+    let baby = MobEntity::new_baby(
+        server.new_entity_id(),
+        world.clone(),
+        entity.entity_type.clone(),
+        entity.pos.load(),
+        entity.yaw.load(),
+        BABY_AGE,
+    );
+    world.add_mob(Arc::new(baby)).await;
+    mob.show_hearts().await;
+    if world.game_rules.lock().await.get_bool(BoolRule::DoMobLoot) {
+        let points = thread_rng().gen_range(1..=7);
+        world
+            .award_experience(server, entity.pos.load(), points)
+            .await;
+    }
+}
+
+/// Swims up in water, like vanilla's `FloatGoal`
+pub struct FloatGoal;
+
+#[async_trait]
+impl Goal for FloatGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Jump]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        // Small mobs swim up before the water reaches their eyes
+        let threshold = if mob.entity().standing_eye_height < 0.4 {
+            0.2
+        } else {
+            0.4
+        };
+        mob.controls.water_height() > threshold
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if random::<f32>() < 0.8 {
+            mob.controls.jump();
+        }
+    }
+}
+
+/// Runs around after being hurt, like vanilla's `PanicGoal`
+pub struct PanicGoal {
+    speed: f64,
+    target: Option<Vector3<f64>>,
+}
+
+impl PanicGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            target: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for PanicGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        if !mob.was_hurt_recently() {
+            return false;
+        }
+        self.target = random_position(mob, 5, 4, false);
+        self.target.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        mob.navigation.is_moving()
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if let Some(target) = self.target {
+            mob.navigation.move_to(target, self.speed);
+        }
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.navigation.stop();
+    }
+}
+
+/// Walks to another animal in love and breeds with it, like vanilla's `BreedGoal`
+pub struct BreedGoal {
+    speed: f64,
+    partner: Option<Arc<MobEntity>>,
+    love_time: u32,
+}
+
+impl BreedGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            partner: None,
+            love_time: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for BreedGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        if !mob.is_in_love() {
+            return false;
+        }
+        let entity = mob.entity();
+        let position = entity.pos.load();
+        self.partner = surroundings
+            .mobs
+            .iter()
+            .filter(|other| {
+                let other_entity = other.entity();
+                other_entity.entity_id != entity.entity_id
+                    && same_type(&other_entity.entity_type, &entity.entity_type)
+                    && other.is_in_love()
+                    && other.living_entity.health.load() > 0.0
+            })
+            .map(|other| {
+                (
+                    other.entity().pos.load().sub(&position).length_squared(),
+                    other,
+                )
+            })
+            .filter(|(distance, _)| *distance <= 64.0)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, other)| other.clone());
+        self.partner.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        self.partner.as_ref().is_some_and(|partner| {
+            mob.is_in_love()
+                && partner.is_in_love()
+                && partner.living_entity.health.load() > 0.0
+                && self.love_time < BREEDING_TIME
+        })
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        self.partner = None;
+        self.love_time = 0;
+        mob.navigation.stop();
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) {
+        let Some(partner) = self.partner.clone() else {
+            return;
+        };
+        let partner_entity = partner.entity();
+        mob.controls.look_at(partner_entity.eye_position());
+        mob.navigation
+            .move_to(partner_entity.pos.load(), self.speed);
+        self.love_time += 1;
+        let distance = partner_entity
+            .pos
+            .load()
+            .sub(&mob.entity().pos.load())
+            .length_squared();
+        if self.love_time >= BREEDING_TIME && distance < 9.0 {
+            breed(mob, &partner, surroundings.server).await;
+        }
+    }
+}
+
+/// Follows players holding food, like vanilla's `TemptGoal`
+pub struct TemptGoal {
+    speed: f64,
+    player: Option<Arc<Player>>,
+    /// Ticks until the mob follows players again after it stopped following one
+    calm_down: u32,
+}
+
+impl TemptGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            player: None,
+            calm_down: 0,
+        }
+    }
+
+    /// The closest player within 10 blocks holding an item the mob follows
+    async fn find_player(mob: &MobEntity, surroundings: &Surroundings<'_>) -> Option<Arc<Player>> {
+        let entity = mob.entity();
+        let position = entity.pos.load();
+        let mut players: Vec<_> = surroundings
+            .visible_players()
+            .map(|player| {
+                let distance = player.living_entity.entity.pos.load().sub(&position);
+                (distance.length_squared(), player)
+            })
+            .filter(|(distance, _)| *distance <= 100.0)
+            .collect();
+        players.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        for (_, player) in players {
+            if holds_temptation(player, &entity.entity_type).await {
+                return Some(player.clone());
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl Goal for TemptGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        if self.calm_down > 0 {
+            self.calm_down -= 1;
+            return false;
+        }
+        self.player = Self::find_player(mob, surroundings).await;
+        self.player.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        self.player = Self::find_player(mob, surroundings).await;
+        self.player.is_some()
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        self.player = None;
+        self.calm_down = 100;
+        mob.navigation.stop();
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        let Some(player) = &self.player else {
+            return;
+        };
+        let player_entity = &player.living_entity.entity;
+        mob.controls.look_at(player_entity.eye_position());
+        let position = player_entity.pos.load();
+        if position.sub(&mob.entity().pos.load()).length_squared() < 6.25 {
+            mob.navigation.stop();
+        } else {
+            mob.navigation.move_to(position, self.speed);
+        }
+    }
+}
+
+/// Babies follow adults of their kind, like vanilla's `FollowParentGoal`
+pub struct FollowParentGoal {
+    speed: f64,
+    parent: Option<Arc<MobEntity>>,
+    /// Ticks until the baby walks to where the parent is now
+    repath: u32,
+}
+
+impl FollowParentGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            parent: None,
+            repath: 0,
+        }
+    }
+
+    fn distance(&self, mob: &MobEntity) -> Option<f64> {
+        let parent = self.parent.as_ref()?;
+        Some(
+            parent
+                .entity()
+                .pos
+                .load()
+                .sub(&mob.entity().pos.load())
+                .length_squared(),
+        )
+    }
+}
+
+#[async_trait]
+impl Goal for FollowParentGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        if mob.age() >= 0 {
+            return false;
+        }
+        let entity = mob.entity();
+        let position = entity.pos.load();
+        self.parent = surroundings
+            .mobs
+            .iter()
+            .filter(|other| {
+                let other_entity = other.entity();
+                same_type(&other_entity.entity_type, &entity.entity_type)
+                    && other.age() >= 0
+                    && other.living_entity.health.load() > 0.0
+            })
+            .map(|other| {
+                (
+                    other.entity().pos.load().sub(&position).length_squared(),
+                    other,
+                )
+            })
+            .filter(|(distance, _)| *distance <= 64.0)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, other)| other.clone());
+        // Babies close to their parent stay where they are
+        self.distance(mob).is_some_and(|distance| distance >= 9.0)
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        mob.age() < 0
+            && self
+                .parent
+                .as_ref()
+                .is_some_and(|parent| parent.living_entity.health.load() > 0.0)
+            && self
+                .distance(mob)
+                .is_some_and(|distance| (9.0..=256.0).contains(&distance))
+    }
+
+    async fn start(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        self.repath = 0;
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        self.parent = None;
+        mob.navigation.stop();
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if self.repath > 0 {
+            self.repath -= 1;
+            return;
+        }
+        self.repath = 10;
+        if let Some(parent) = &self.parent {
+            mob.navigation
+                .move_to(parent.entity().pos.load(), self.speed);
+        }
+    }
+}
+
+/// Wanders around on land now and then, like vanilla's `WaterAvoidingRandomStrollGoal`
+pub struct RandomStrollGoal {
+    speed: f64,
+    target: Option<Vector3<f64>>,
+}
+
+impl RandomStrollGoal {
+    #[must_use]
+    pub const fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            target: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for RandomStrollGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        // Mobs stroll every 120 ticks on average
+        if thread_rng().gen_range(0..120) != 0 {
+            return false;
+        }
+        self.target = random_position(mob, 10, 7, true);
+        self.target.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        mob.navigation.is_moving()
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if let Some(target) = self.target {
+            mob.navigation.move_to(target, self.speed);
+        }
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.navigation.stop();
+    }
+}
+
+/// Looks at a nearby player for a while, like vanilla's `LookAtPlayerGoal`
+pub struct LookAtPlayerGoal {
+    range: f64,
+    player: Option<Arc<Player>>,
+    look_time: u32,
+}
+
+impl LookAtPlayerGoal {
+    #[must_use]
+    pub const fn new(range: f64) -> Self {
+        Self {
+            range,
+            player: None,
+            look_time: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for LookAtPlayerGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Look]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        if random::<f32>() >= 0.02 {
+            return false;
+        }
+        self.player = surroundings
+            .nearest_player(mob.entity().pos.load(), self.range)
+            .cloned();
+        self.player.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        let position = mob.entity().pos.load();
+        self.look_time > 0
+            && self.player.as_ref().is_some_and(|player| {
+                player.living_entity.health.load() > 0.0
+                    && player
+                        .living_entity
+                        .entity
+                        .pos
+                        .load()
+                        .sub(&position)
+                        .length_squared()
+                        <= self.range * self.range
+            })
+    }
+
+    async fn start(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        self.look_time = thread_rng().gen_range(40..80);
+    }
+
+    async fn stop(&mut self, _mob: &MobEntity) {
+        self.player = None;
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        if let Some(player) = &self.player {
+            mob.controls
+                .look_at(player.living_entity.entity.eye_position());
+        }
+        self.look_time = self.look_time.saturating_sub(1);
+    }
+}
+
+/// Looks around in random directions, like vanilla's `RandomLookAroundGoal`
+#[derive(Default)]
+pub struct RandomLookAroundGoal {
+    /// The horizontal direction the mob looks in
+    direction: (f64, f64),
+    look_time: u32,
+}
+
+#[async_trait]
+impl Goal for RandomLookAroundGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Move, Control::Look]
+    }
+
+    async fn can_start(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) -> bool {
+        random::<f32>() < 0.02
+    }
+
+    async fn should_continue(
+        &mut self,
+        _mob: &MobEntity,
+        _surroundings: &Surroundings<'_>,
+    ) -> bool {
+        self.look_time > 0
+    }
+
+    async fn start(&mut self, _mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        let mut rng = thread_rng();
+        let (sin, cos) = (TAU * rng.gen::<f64>()).sin_cos();
+        self.direction = (cos, sin);
+        self.look_time = rng.gen_range(20..40);
+    }
+
+    async fn tick(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        self.look_time -= 1;
+        let eyes = mob.entity().eye_position();
+        mob.controls.look_at(Vector3::new(
+            eyes.x + self.direction.0,
+            eyes.y,
+            eyes.z + self.direction.1,
+        ));
+    }
+}
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use pumpkin_entity::EntityId;
+use rand::random;
+
+use crate::entity::mob::MobEntity;
+
+use super::{
+    can_attack, can_see,
+    goal::{Control, Goal, GoalSelector},
+    Surroundings,
+};
+
+/// Ticks mobs keep attacking a player they can't see
+const UNSEEN_MEMORY_TICKS: u32 = 60;
+/// How far above and below mobs look for players to attack
+const VERTICAL_SEARCH_RANGE: f64 = 4.0;
+
+/// The target goals of zombies and skeletons, like their vanilla `registerGoals`. Mobs fight back
+/// and attack the closest player they see
+#[must_use]
+pub fn hostile_target_goals(follow_range: f64) -> GoalSelector {
+    let mut goals = GoalSelector::default();
+    goals.add_goal(1, HurtByTargetGoal::new(follow_range));
+    goals.add_goal(2, NearestAttackableTargetGoal::new(follow_range));
+    goals
+}
+
+/// Whether the mob keeps attacking its target, like vanilla's `TargetGoal.canContinueToUse`.
+/// Mobs forget players which went too far away or which they didn't see for a while
+fn keeps_target(
+    mob: &MobEntity,
+    surroundings: &Surroundings<'_>,
+    follow_range: f64,
+    unseen_ticks: &mut u32,
+) -> bool {
+    let Some(target) = mob.target(surroundings) else {
+        return false;
+    };
+    let target_entity = &target.living_entity.entity;
+    let distance = target_entity
+        .pos
+        .load()
+        .sub(&mob.entity().pos.load())
+        .length_squared();
+    if !can_attack(target) || distance > follow_range * follow_range {
+        return false;
+    }
+    if can_see(mob, target_entity) {
+        *unseen_ticks = 0;
+    } else {
+        *unseen_ticks += 1;
+    }
+    *unseen_ticks <= UNSEEN_MEMORY_TICKS
+}
+
+/// Attacks the player which hurt the mob, like vanilla's `HurtByTargetGoal`
+pub struct HurtByTargetGoal {
+    follow_range: f64,
+    unseen_ticks: u32,
+}
+
+impl HurtByTargetGoal {
+    #[must_use]
+    pub const fn new(follow_range: f64) -> Self {
+        Self {
+            follow_range,
+            unseen_ticks: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for HurtByTargetGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Target]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        let Some(attacker) = mob.last_attacker() else {
+            return false;
+        };
+        // Mobs turn on whoever hurt them, even while they attack someone else
+        mob.target(surroundings)
+            .is_none_or(|target| target.entity_id() != attacker)
+            && surroundings
+                .players
+                .iter()
+                .any(|player| player.entity_id() == attacker && can_attack(player))
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        keeps_target(mob, surroundings, self.follow_range, &mut self.unseen_ticks)
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        mob.set_target(mob.last_attacker());
+        self.unseen_ticks = 0;
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.set_target(None);
+    }
+}
+
+/// Attacks the closest player the mob sees, like vanilla's `NearestAttackableTargetGoal`
+pub struct NearestAttackableTargetGoal {
+    follow_range: f64,
+    unseen_ticks: u32,
+    /// The player the mob found to attack
+    candidate: Option<EntityId>,
+}
+
+impl NearestAttackableTargetGoal {
+    #[must_use]
+    pub const fn new(follow_range: f64) -> Self {
+        Self {
+            follow_range,
+            unseen_ticks: 0,
+            candidate: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for NearestAttackableTargetGoal {
+    fn controls(&self) -> &'static [Control] {
+        &[Control::Target]
+    }
+
+    async fn can_start(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        // Mobs only look around every few ticks
+        if random::<f32>() >= 0.2 {
+            return false;
+        }
+        let position = mob.entity().pos.load();
+        self.candidate = surroundings
+            .players
+            .iter()
+            .filter(|player| can_attack(player))
+            .map(|player| {
+                let distance = player.living_entity.entity.pos.load().sub(&position);
+                (distance, player)
+            })
+            .filter(|(distance, _)| {
+                distance.y.abs() <= VERTICAL_SEARCH_RANGE
+                    && distance.x.hypot(distance.z) <= self.follow_range
+            })
+            .filter(|(_, player)| can_see(mob, &player.living_entity.entity))
+            .min_by(|(a, _), (b, _)| a.length_squared().total_cmp(&b.length_squared()))
+            .map(|(_, player)| player.entity_id());
+        self.candidate.is_some()
+    }
+
+    async fn should_continue(&mut self, mob: &MobEntity, surroundings: &Surroundings<'_>) -> bool {
+        keeps_target(mob, surroundings, self.follow_range, &mut self.unseen_ticks)
+    }
+
+    async fn start(&mut self, mob: &MobEntity, _surroundings: &Surroundings<'_>) {
+        mob.set_target(self.candidate.take());
+        self.unseen_ticks = 0;
+    }
+
+    async fn stop(&mut self, mob: &MobEntity) {
+        mob.set_target(None);
+    }
+}
@@ -0,0 +1,259 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use pumpkin_core::{math::vector3::Vector3, text::TextComponent};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::{
+    damage::DamageType,
+    effect::{EffectInstance, StatusEffect},
+};
+
+use crate::world::scoreboard::text_from_json;
+
+use super::{
+    damage::{entity_type_name, DamageEntity, DamageSource},
+    item::ItemEntity,
+    mob::MobEntity,
+    player::Player,
+    tracker::TrackedEntity,
+    Entity,
+};
+
+/// Any entity of a world, for commands which work on every kind of entity
+#[derive(Clone)]
+pub enum AnyEntity {
+    Player(Arc<Player>),
+    Mob(Arc<MobEntity>),
+    Item(Arc<ItemEntity>),
+    Other(Arc<dyn TrackedEntity>),
+}
+
+impl AnyEntity {
+    #[must_use]
+    pub fn entity(&self) -> &Entity {
+        match self {
+            Self::Player(player) => &player.living_entity.entity,
+            Self::Mob(mob) => mob.entity(),
+            Self::Item(item) => &item.entity,
+            Self::Other(other) => other.tracked_entity(),
+        }
+    }
+
+    #[must_use]
+    pub const fn as_player(&self) -> Option<&Arc<Player>> {
+        match self {
+            Self::Player(player) => Some(player),
+            _ => None,
+        }
+    }
+
+    /// The UUID of the entity, entities which aren't saved don't have one
+    #[must_use]
+    pub fn uuid(&self) -> Option<uuid::Uuid> {
+        match self {
+            Self::Player(player) => Some(player.gameprofile.id),
+            Self::Mob(mob) => Some(mob.uuid),
+            Self::Item(item) => Some(item.uuid),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// The name the entity holds scoreboard scores by, other entities than players go by their
+    /// UUID
+    #[must_use]
+    pub fn score_holder(&self) -> Option<String> {
+        match self {
+            Self::Player(player) => Some(player.gameprofile.name.clone()),
+            _ => self.uuid().map(|uuid| uuid.to_string()),
+        }
+    }
+
+    /// The name of the entity in command feedback, players go by their name
+    #[must_use]
+    pub fn display_name(&self) -> TextComponent<'static> {
+        match self {
+            Self::Player(player) => TextComponent::text_string(player.gameprofile.name.clone()),
+            _ => entity_type_name(&self.entity().entity_type),
+        }
+    }
+
+    /// The entity as the attacker or the direct cause of damage
+    #[must_use]
+    pub fn damage_entity(&self) -> DamageEntity {
+        match self {
+            Self::Player(player) => DamageEntity::player(player),
+            _ => DamageEntity::entity(self.entity()),
+        }
+    }
+
+    /// Hurts the entity like `/damage`, returns whether it took the damage. Only players and
+    /// mobs can be hurt
+    pub async fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        match self {
+            Self::Player(player) => player.hurt(source, amount).await,
+            Self::Mob(mob) => mob.hurt(source, amount).await,
+            Self::Item(_) | Self::Other(_) => false,
+        }
+    }
+
+    /// Gives the entity the effect unless it has a stronger one, only players and mobs have
+    /// effects. Returns whether the entity got the effect
+    pub async fn add_effect(&self, instance: EffectInstance) -> bool {
+        match self {
+            Self::Player(player) => player.add_effect(instance).await,
+            Self::Mob(mob) => mob.living_entity.add_effect(instance).await,
+            Self::Item(_) | Self::Other(_) => false,
+        }
+    }
+
+    /// Takes the effect from the entity, returns whether it had it
+    pub async fn remove_effect(&self, effect: StatusEffect) -> bool {
+        match self {
+            Self::Player(player) => player.remove_effect(effect).await,
+            Self::Mob(mob) => mob.living_entity.remove_effect(effect).await,
+            Self::Item(_) | Self::Other(_) => false,
+        }
+    }
+
+    /// Takes every effect from the entity, returns the effects it had
+    pub async fn clear_effects(&self) -> Vec<EffectInstance> {
+        match self {
+            Self::Player(player) => player.clear_effects().await,
+            Self::Mob(mob) => mob.living_entity.clear_effects().await,
+            Self::Item(_) | Self::Other(_) => Vec::new(),
+        }
+    }
+
+    /// Kills the entity like `/kill`, entities which can't die are removed
+    pub async fn kill(&self) {
+        match self {
+            Self::Player(player) => player.kill().await,
+            Self::Mob(mob) => {
+                mob.hurt(&DamageSource::new(DamageType::GenericKill), f32::MAX)
+                    .await;
+            }
+            Self::Item(_) | Self::Other(_) => {
+                let entity = self.entity();
+                entity.world().discard_entity(entity).await;
+            }
+        }
+    }
+
+    /// Writes the data of the entity which commands compare against, like vanilla's
+    /// `Entity.saveWithoutId`
+    pub async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        match self {
+            Self::Player(player) => player.write_nbt(nbt).await,
+            Self::Item(item) => item.write_nbt(nbt).await,
+            Self::Mob(_) | Self::Other(_) => {}
+        }
+        let entity = self.entity();
+        let vector = |vector: Vector3<f64>| {
+            NbtTag::List(vec![
+                NbtTag::Double(vector.x),
+                NbtTag::Double(vector.y),
+                NbtTag::Double(vector.z),
+            ])
+        };
+        nbt.put("Pos".to_string(), vector(entity.pos.load()));
+        nbt.put("Motion".to_string(), vector(entity.velocity.load()));
+        nbt.put(
+            "Rotation".to_string(),
+            NbtTag::List(vec![
+                NbtTag::Float(entity.yaw.load()),
+                NbtTag::Float(entity.pitch.load()),
+            ]),
+        );
+        nbt.put(
+            "OnGround".to_string(),
+            NbtTag::Byte(i8::from(entity.on_ground.load(Ordering::Relaxed))),
+        );
+        let tags = entity.tags();
+        if !tags.is_empty() {
+            nbt.put(
+                "Tags".to_string(),
+                NbtTag::List(tags.into_iter().map(NbtTag::String).collect()),
+            );
+        }
+        let health = match self {
+            Self::Player(player) => Some(player.living_entity.health.load()),
+            Self::Mob(mob) => Some(mob.living_entity.health.load()),
+            Self::Item(_) | Self::Other(_) => None,
+        };
+        if let Some(health) = health {
+            nbt.put("Health".to_string(), NbtTag::Float(health));
+        }
+    }
+
+    /// Applies the data to the entity, like vanilla's `Entity.load` when `/data` or `/summon`
+    /// change it. Data which isn't known is ignored, players can't be changed this way
+    pub async fn read_nbt(&self, nbt: &NbtCompound) {
+        if let Self::Player(_) = self {
+            return;
+        }
+        let entity = self.entity();
+        let vector = |name: &str| {
+            let list = nbt.get_list(name)?;
+            let mut values = list.iter().map(NbtTag::extract_double);
+            Some(Vector3::new(
+                values.next()??,
+                values.next()??,
+                values.next()??,
+            ))
+        };
+        let rotation = nbt.get_list("Rotation").and_then(|list| {
+            Some((
+                list.first()?.extract_float()?,
+                list.get(1)?.extract_float()?,
+            ))
+        });
+        let pos = vector("Pos");
+        if pos.is_some() || rotation.is_some() {
+            let (yaw, pitch) = rotation.unwrap_or((entity.yaw.load(), entity.pitch.load()));
+            entity
+                .teleport(pos.unwrap_or_else(|| entity.pos.load()), yaw, pitch)
+                .await;
+        }
+        if let Some(motion) = vector("Motion") {
+            entity.set_velocity(motion);
+        }
+        if let Some(on_ground) = nbt.get_bool("OnGround") {
+            entity.on_ground.store(on_ground, Ordering::Relaxed);
+        }
+        if let Some(tags) = nbt.get_list("Tags") {
+            entity.set_tags(
+                tags.iter()
+                    .filter_map(NbtTag::extract_string)
+                    .cloned()
+                    .collect(),
+            );
+        }
+        if let Some(name) = nbt.get_string("CustomName") {
+            let name = TextComponent::text_string(text_from_json(name));
+            let always_visible = nbt.get_bool("CustomNameVisible").unwrap_or(false);
+            match self {
+                Self::Mob(mob) => mob.set_name(name, always_visible),
+                _ => entity.set_custom_name(Some(name), always_visible),
+            }
+        }
+        match self {
+            Self::Mob(mob) => {
+                if let Some(health) = nbt.get_float("Health") {
+                    mob.living_entity.set_health(health);
+                }
+            }
+            Self::Item(item) => item.read_nbt(nbt).await,
+            Self::Player(_) | Self::Other(_) => {}
+        }
+        entity.send_data_changes().await;
+    }
+
+    #[must_use]
+    pub fn into_tracked(self) -> Arc<dyn TrackedEntity> {
+        match self {
+            Self::Player(player) => player,
+            Self::Mob(mob) => mob,
+            Self::Item(item) => item,
+            Self::Other(other) => other,
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    },
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_macros::sound;
+use pumpkin_protocol::{
+    client::play::{CSetEquipment, CSpawnEntity},
+    codec::slot::Slot,
+    SoundCategory,
+};
+use pumpkin_world::item::{
+    item_registry::{get_item, EquipmentSlot},
+    ItemStack,
+};
+use tokio::sync::Mutex;
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{player::Player, tracker::TrackedEntity, Entity};
+
+/// Equipment slots in the order the equipment packet numbers them
+const MAIN_HAND: usize = 0;
+const OFF_HAND: usize = 1;
+const FEET: usize = 2;
+const LEGS: usize = 3;
+const CHEST: usize = 4;
+const HEAD: usize = 5;
+
+/// An armor stand players can put armor on, by default it has no arms to hold items
+pub struct ArmorStandEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    /// Indexed like the slots of the equipment packet
+    pub equipment: Mutex<[Option<ItemStack>; 6]>,
+    /// Armor stands with arms can also hold items in their hands
+    pub show_arms: bool,
+}
+
+impl ArmorStandEntity {
+    pub fn new(entity_id: EntityId, world: Arc<World>, position: Vector3<f64>, yaw: f32) -> Self {
+        let bounding_box_size = BoundingBoxSize {
+            width: 0.5,
+            height: 1.975,
+        };
+        let entity = Entity::new(
+            entity_id,
+            world,
+            EntityType::ArmorStand,
+            1.7775,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        entity.set_pos(position);
+        entity.set_rotation(yaw, 0.0);
+        entity.head_yaw.store(yaw);
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            equipment: Mutex::new([const { None }; 6]),
+            show_arms: false,
+        }
+    }
+
+    /// The equipment slot players aim at when using the stand with an empty hand,
+    /// `target_y` is the height above the stand's feet
+    fn clicked_slot(equipment: &[Option<ItemStack>; 6], target_y: f64) -> usize {
+        if (0.1..0.55).contains(&target_y) && equipment[FEET].is_some() {
+            FEET
+        } else if (0.9..1.6).contains(&target_y) && equipment[CHEST].is_some() {
+            CHEST
+        } else if (0.4..1.2).contains(&target_y) && equipment[LEGS].is_some() {
+            LEGS
+        } else if target_y >= 1.6 && equipment[HEAD].is_some() {
+            HEAD
+        } else if equipment[MAIN_HAND].is_none() && equipment[OFF_HAND].is_some() {
+            OFF_HAND
+        } else {
+            MAIN_HAND
+        }
+    }
+
+    /// The slot the held item goes into, armor is worn and the rest is held in the main hand
+    fn slot_for(item: &ItemStack) -> usize {
+        match item.equipment_slot() {
+            Some(EquipmentSlot::Head) => HEAD,
+            Some(EquipmentSlot::Chest) => CHEST,
+            Some(EquipmentSlot::Legs) => LEGS,
+            Some(EquipmentSlot::Feet) => FEET,
+            Some(EquipmentSlot::OffHand) => OFF_HAND,
+            Some(EquipmentSlot::MainHand | EquipmentSlot::Body) | None => MAIN_HAND,
+        }
+    }
+
+    /// Swaps the held item with the stand's equipment, `target_y` is where the player aimed at.
+    /// Players in creative keep their items when putting them on the stand
+    pub async fn interact_at(&self, player: &Player, offhand: bool, target_y: f32) {
+        let creative = player.gamemode.load() == GameMode::Creative;
+        let mut equipment = self.equipment.lock().await;
+        {
+            let mut inventory = player.inventory().lock().await;
+            let hand = inventory.hand_item_mut(offhand);
+            let slot = hand.as_ref().map_or_else(
+                || Self::clicked_slot(&equipment, f64::from(target_y)),
+                Self::slot_for,
+            );
+            if !self.show_arms && matches!(slot, MAIN_HAND | OFF_HAND) {
+                return;
+            }
+            let worn = &mut equipment[slot];
+            match hand.as_mut() {
+                Some(held) if creative && worn.is_none() => *worn = Some(held.with_count(1)),
+                // Only a single item goes onto the stand, the rest stays in the hand
+                Some(held) if held.item_count > 1 => {
+                    if worn.is_some() {
+                        return;
+                    }
+                    *worn = Some(held.with_count(1));
+                    held.item_count -= 1;
+                }
+                _ => {
+                    if hand.is_none() && worn.is_none() {
+                        return;
+                    }
+                    std::mem::swap(hand, worn);
+                }
+            }
+        }
+        let packet = Self::equipment_packet(self.entity.entity_id, &equipment);
+        drop(equipment);
+        player.set_container_content(None).await;
+        self.entity.broadcast_to_trackers(&packet).await;
+        self.play_sound(sound!("item.armor.equip_generic")).await;
+    }
+
+    /// Attacking breaks the stand, the equipment pops out. Nothing drops for players in creative
+    pub async fn attack(&self, player: &Player, server: &Server) {
+        let world = &self.entity.world();
+        world.remove_armor_stand(self).await;
+        self.play_sound(sound!("entity.armor_stand.break")).await;
+        if player.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let pos = self.entity.pos.load();
+        let stand = get_item("armor_stand").map(|item| ItemStack::new(1, item.id));
+        let equipment = std::mem::replace(&mut *self.equipment.lock().await, [const { None }; 6]);
+        for item in stand.into_iter().chain(equipment.into_iter().flatten()) {
+            world
+                .drop_item_stack(server.new_entity_id(), pos, item)
+                .await;
+        }
+    }
+
+    async fn play_sound(&self, sound_id: u16) {
+        let pos = self.entity.pos.load();
+        self.entity
+            .world()
+            .play_sound(sound_id, SoundCategory::Neutral, &pos)
+            .await;
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        let yaw = self.entity.yaw.load();
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (EntityType::ArmorStand as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            yaw,
+            yaw,
+            0.into(),
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    fn equipment_packet(entity_id: EntityId, equipment: &[Option<ItemStack>; 6]) -> CSetEquipment {
+        CSetEquipment::new(
+            entity_id.into(),
+            equipment
+                .iter()
+                .enumerate()
+                .map(|(slot, item)| (slot as u8, Slot::from(item.as_ref())))
+                .collect(),
+        )
+    }
+
+    /// Makes the stand visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+        self.play_sound(sound!("entity.armor_stand.place")).await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for ArmorStandEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        let equipment = self.equipment.lock().await;
+        if equipment.iter().any(Option::is_some) {
+            client
+                .send_packet(&Self::equipment_packet(self.entity.entity_id, &equipment))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::{ArmorStandEntity, CHEST, FEET, HEAD, MAIN_HAND, OFF_HAND};
+
+    #[test]
+    fn items_go_into_the_matching_slot() {
+        assert_eq!(
+            ArmorStandEntity::slot_for(&ItemStack::of("minecraft:iron_helmet", 1)),
+            HEAD
+        );
+        assert_eq!(
+            ArmorStandEntity::slot_for(&ItemStack::of("minecraft:iron_boots", 1)),
+            FEET
+        );
+        assert_eq!(
+            ArmorStandEntity::slot_for(&ItemStack::of("minecraft:shield", 1)),
+            OFF_HAND
+        );
+        assert_eq!(
+            ArmorStandEntity::slot_for(&ItemStack::of("minecraft:stone", 1)),
+            MAIN_HAND
+        );
+    }
+
+    #[test]
+    fn players_take_the_armor_they_aim_at() {
+        let mut equipment = [const { None }; 6];
+        equipment[CHEST] = Some(ItemStack::of("minecraft:iron_chestplate", 1));
+        assert_eq!(ArmorStandEntity::clicked_slot(&equipment, 1.2), CHEST);
+        // Aiming at the feet without boots falls back to the hands
+        assert_eq!(ArmorStandEntity::clicked_slot(&equipment, 0.2), MAIN_HAND);
+        equipment[OFF_HAND] = Some(ItemStack::of("minecraft:shield", 1));
+        assert_eq!(ArmorStandEntity::clicked_slot(&equipment, 0.2), OFF_HAND);
+    }
+}
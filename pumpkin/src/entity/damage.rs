@@ -0,0 +1,119 @@
+use pumpkin_core::{math::vector3::Vector3, text::TextComponent};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_world::damage::DamageType;
+
+use super::{player::Player, Entity};
+
+/// The name of the entity type the client shows, like "Zombie"
+#[must_use]
+pub fn entity_type_name(entity_type: &EntityType) -> TextComponent<'static> {
+    TextComponent::translate(format!("entity.minecraft.{}", entity_type.name()), vec![])
+}
+
+/// An entity taking part in damage, the one responsible for it or the one which hit
+#[derive(Clone)]
+pub struct DamageEntity {
+    pub entity_id: EntityId,
+    /// The name of the entity in death messages
+    pub name: TextComponent<'static>,
+    pub position: Vector3<f64>,
+}
+
+impl DamageEntity {
+    pub fn player(player: &Player) -> Self {
+        Self {
+            entity_id: player.entity_id(),
+            name: TextComponent::text_string(player.gameprofile.name.clone()),
+            position: player.living_entity.entity.pos.load(),
+        }
+    }
+
+    pub fn entity(entity: &Entity) -> Self {
+        Self {
+            entity_id: entity.entity_id,
+            name: entity_type_name(&entity.entity_type),
+            position: entity.pos.load(),
+        }
+    }
+}
+
+/// Where damage comes from, like vanilla's `DamageSource`
+#[derive(Clone)]
+pub struct DamageSource {
+    pub damage_type: DamageType,
+    /// The entity responsible for the damage, the shooter of a projectile
+    pub attacker: Option<DamageEntity>,
+    /// The entity which hit, the projectile or the attacker itself
+    pub direct: Option<DamageEntity>,
+    /// Where the damage came from without an entity, like the center of an explosion
+    pub position: Option<Vector3<f64>>,
+}
+
+impl DamageSource {
+    #[must_use]
+    pub const fn new(damage_type: DamageType) -> Self {
+        Self {
+            damage_type,
+            attacker: None,
+            direct: None,
+            position: None,
+        }
+    }
+
+    /// Damage from a place rather than an entity, like an explosion
+    #[must_use]
+    pub const fn at(damage_type: DamageType, position: Vector3<f64>) -> Self {
+        Self {
+            damage_type,
+            attacker: None,
+            direct: None,
+            position: Some(position),
+        }
+    }
+
+    /// Damage an entity did itself, like a melee attack
+    #[must_use]
+    pub fn by(damage_type: DamageType, attacker: DamageEntity) -> Self {
+        Self {
+            damage_type,
+            attacker: Some(attacker.clone()),
+            direct: Some(attacker),
+            position: None,
+        }
+    }
+
+    /// Damage from a projectile, the owner is the one who shot or threw it
+    #[must_use]
+    pub const fn projectile(
+        damage_type: DamageType,
+        projectile: DamageEntity,
+        owner: Option<DamageEntity>,
+    ) -> Self {
+        Self {
+            damage_type,
+            attacker: owner,
+            direct: Some(projectile),
+            position: None,
+        }
+    }
+
+    /// Where the damage came from, shields only block damage coming from the front
+    #[must_use]
+    pub fn source_position(&self) -> Option<Vector3<f64>> {
+        self.direct
+            .as_ref()
+            .map(|direct| direct.position)
+            .or(self.position)
+    }
+
+    /// The death message of the victim, see `DamageType::death_message`
+    #[must_use]
+    pub fn death_message(&self, victim: TextComponent<'static>) -> TextComponent<'static> {
+        let attacker = self
+            .attacker
+            .as_ref()
+            .or(self.direct.as_ref())
+            .map(|attacker| attacker.name.clone());
+        self.damage_type.death_message(victim, attacker)
+    }
+}
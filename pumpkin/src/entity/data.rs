@@ -0,0 +1,220 @@
+use std::{
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+};
+
+use pumpkin_core::{math::position::WorldPosition, text::TextComponent};
+use pumpkin_entity::pose::EntityPose;
+use pumpkin_protocol::{
+    client::play::{Metadata, MetadataValue},
+    codec::slot::Slot,
+};
+
+/// A value entities can hold in their metadata
+pub trait DataValue {
+    fn into_metadata(self) -> MetadataValue;
+}
+
+impl DataValue for i8 {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::Byte(self)
+    }
+}
+
+impl DataValue for i32 {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::VarInt(self)
+    }
+}
+
+impl DataValue for f32 {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::Float(self)
+    }
+}
+
+impl DataValue for bool {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::Boolean(self)
+    }
+}
+
+impl DataValue for Slot {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::Slot(self)
+    }
+}
+
+impl DataValue for Option<TextComponent<'static>> {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::OptionalTextComponent(self)
+    }
+}
+
+impl DataValue for Option<WorldPosition> {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::OptionalBlockPosition(self)
+    }
+}
+
+impl DataValue for EntityPose {
+    fn into_metadata(self) -> MetadataValue {
+        MetadataValue::Pose(self as i32)
+    }
+}
+
+/// A metadata field of an entity type holding values of `T`, like vanilla's
+/// `EntityDataAccessor`. The index is the place of the field in the metadata of the entity type
+pub struct DataField<T> {
+    index: u8,
+    value: PhantomData<T>,
+}
+
+impl<T> DataField<T> {
+    #[must_use]
+    pub const fn new(index: u8) -> Self {
+        Self {
+            index,
+            value: PhantomData,
+        }
+    }
+}
+
+// The fields every entity has
+/// Whether the entity is on fire, sneaking, sprinting, swimming, invisible, glowing or gliding
+pub const SHARED_FLAGS: DataField<i8> = DataField::new(0);
+pub const AIR_SUPPLY: DataField<i32> = DataField::new(1);
+pub const CUSTOM_NAME: DataField<Option<TextComponent<'static>>> = DataField::new(2);
+pub const CUSTOM_NAME_VISIBLE: DataField<bool> = DataField::new(3);
+pub const POSE: DataField<EntityPose> = DataField::new(6);
+
+// The fields of living entities
+/// Whether the entity uses an item, with which hand and whether it spins with a trident
+pub const LIVING_FLAGS: DataField<i8> = DataField::new(8);
+pub const HEALTH: DataField<f32> = DataField::new(9);
+/// The bed the entity sleeps in
+pub const SLEEPING_POSITION: DataField<Option<WorldPosition>> = DataField::new(14);
+
+struct DataItem {
+    index: u8,
+    value: MetadataValue,
+    /// Changed since players were last told about the entity's metadata
+    dirty: bool,
+}
+
+/// The metadata of an entity, like vanilla's `SynchedEntityData`. Fields are only tracked once
+/// they were set, and only the ones which changed are sent to players every tick
+#[derive(Default)]
+pub struct EntityData {
+    items: Mutex<Vec<DataItem>>,
+}
+
+impl EntityData {
+    /// Changes the value of the field, the field only becomes dirty if the value differs
+    pub fn set<T: DataValue>(&self, field: &DataField<T>, value: T) {
+        self.update(field.index, |_| value.into_metadata());
+    }
+
+    /// Turns a single bit of a byte field on or off, like vanilla's `setSharedFlag`
+    pub fn set_bit(&self, field: &DataField<i8>, bit: u8, on: bool) {
+        self.update(field.index, |value| {
+            let flags = match value {
+                Some(MetadataValue::Byte(flags)) => *flags,
+                _ => 0,
+            };
+            MetadataValue::Byte(if on {
+                flags | (1 << bit)
+            } else {
+                flags & !(1 << bit)
+            })
+        });
+    }
+
+    fn update(&self, index: u8, value: impl FnOnce(Option<&MetadataValue>) -> MetadataValue) {
+        let mut items = self.items.lock().unwrap_or_else(PoisonError::into_inner);
+        match items.binary_search_by_key(&index, |item| item.index) {
+            Ok(position) => {
+                let item = &mut items[position];
+                let value = value(Some(&item.value));
+                if item.value != value {
+                    item.value = value;
+                    item.dirty = true;
+                }
+            }
+            Err(position) => items.insert(
+                position,
+                DataItem {
+                    index,
+                    value: value(None),
+                    dirty: true,
+                },
+            ),
+        }
+    }
+
+    /// The fields which changed since the last call, like vanilla's `packDirty`
+    pub fn take_dirty(&self) -> Vec<Metadata> {
+        self.items
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter_mut()
+            .filter(|item| item.dirty)
+            .map(|item| {
+                item.dirty = false;
+                Metadata::new(item.index, item.value.clone())
+            })
+            .collect()
+    }
+
+    /// Every field which was set, sent to players who start seeing the entity
+    pub fn all(&self) -> Vec<Metadata> {
+        self.items
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|item| Metadata::new(item.index, item.value.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::client::play::MetadataValue;
+
+    use super::{EntityData, AIR_SUPPLY, HEALTH, SHARED_FLAGS};
+
+    fn value(data: &EntityData, index: u8) -> Option<MetadataValue> {
+        data.items
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|item| item.index == index)
+            .map(|item| item.value.clone())
+    }
+
+    #[test]
+    fn only_changed_values_are_sent() {
+        let data = EntityData::default();
+        data.set(&HEALTH, 20.0);
+        data.set(&AIR_SUPPLY, 300);
+        assert_eq!(data.take_dirty().len(), 2);
+        assert!(data.take_dirty().is_empty());
+
+        data.set(&HEALTH, 20.0);
+        assert!(data.take_dirty().is_empty());
+        data.set(&HEALTH, 19.0);
+        assert_eq!(data.take_dirty().len(), 1);
+        // Players starting to see the entity get every value
+        assert_eq!(data.all().len(), 2);
+    }
+
+    #[test]
+    fn flags_are_set_bit_by_bit() {
+        let data = EntityData::default();
+        data.set_bit(&SHARED_FLAGS, 1, true);
+        data.set_bit(&SHARED_FLAGS, 3, true);
+        assert_eq!(value(&data, 0), Some(MetadataValue::Byte(0b1010)));
+        data.set_bit(&SHARED_FLAGS, 1, false);
+        assert_eq!(value(&data, 0), Some(MetadataValue::Byte(0b1000)));
+    }
+}
@@ -0,0 +1,164 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    },
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::client::play::CAddExperienceOrb;
+use pumpkin_world::physics::is_below_world;
+
+use crate::{net::Client, world::World};
+
+use super::{player::Player, tracker::TrackedEntity, Entity};
+
+/// Ticks until orbs despawn, five minutes like items
+const DESPAWN_AGE: u32 = 6000;
+/// Orbs fly towards players closer than this many blocks
+const FOLLOW_DISTANCE: f64 = 8.0;
+/// The values orbs are split into, the client picks the size of an orb from its value
+const ORB_VALUES: [i32; 11] = [2477, 1237, 617, 307, 149, 73, 37, 17, 7, 3, 1];
+
+/// The value of the largest orb the experience points can be split into, like vanilla's
+/// `ExperienceOrb.getExperienceValue`
+#[must_use]
+pub fn orb_value(points: i32) -> i32 {
+    ORB_VALUES
+        .into_iter()
+        .find(|value| points >= *value)
+        .unwrap_or(1)
+}
+
+/// An orb of experience points, e.g. dropped by mined ores. It flies towards players nearby and
+/// gives its points to the first player touching it
+pub struct ExperienceOrbEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    /// The experience points the orb gives
+    pub value: i32,
+    /// Ticks the orb exists, it despawns once it is old enough
+    age: AtomicU32,
+    /// Whether the orb was picked up or despawned
+    removed: AtomicBool,
+}
+
+impl ExperienceOrbEntity {
+    pub fn new(entity_id: EntityId, world: Arc<World>, value: i32) -> Self {
+        let bounding_box_size = BoundingBoxSize {
+            width: 0.5,
+            height: 0.5,
+        };
+        Self {
+            entity: Entity::new(
+                entity_id,
+                world,
+                EntityType::ExperienceOrb,
+                0.0,
+                AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+                AtomicCell::new(bounding_box_size),
+            ),
+            value,
+            age: AtomicU32::new(0),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Relaxed)
+    }
+
+    /// Removes the orb from the world for good, e.g. once it was picked up
+    pub async fn discard(&self) {
+        if !self.removed.swap(true, Ordering::Relaxed) {
+            self.entity.world().remove_experience_orb(self).await;
+        }
+    }
+
+    /// Ages the orb, lets it fly towards the nearest player and moves it like vanilla's
+    /// `ExperienceOrb.tick`. Returns false once the orb was removed
+    pub async fn tick(&self, players: &[Arc<Player>]) -> bool {
+        if self.is_removed() {
+            return false;
+        }
+        if self.age.fetch_add(1, Ordering::Relaxed) + 1 >= DESPAWN_AGE {
+            self.discard().await;
+            return false;
+        }
+
+        if let Some(target) = self.follow_target(players) {
+            let distance = target.sub(&self.entity.pos.load());
+            let length = distance.length();
+            if length > 0.0 && length < FOLLOW_DISTANCE {
+                // Closer orbs are pulled harder
+                let pull = 1.0 - length / FOLLOW_DISTANCE;
+                let velocity =
+                    self.entity.velocity.load() + distance.normalize() * (pull * pull * 0.1);
+                self.entity.velocity.store(velocity);
+            }
+        }
+
+        self.entity.tick_movement().await;
+        if is_below_world(self.entity.pos.load().y) {
+            self.discard().await;
+            return false;
+        }
+        true
+    }
+
+    /// Half way up the nearest player which can pick the orb up, spectators don't attract orbs
+    fn follow_target(&self, players: &[Arc<Player>]) -> Option<Vector3<f64>> {
+        let pos = self.entity.pos.load();
+        players
+            .iter()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+            })
+            .map(|player| {
+                let entity = &player.living_entity.entity;
+                entity.pos.load()
+                    + Vector3::new(0.0, f64::from(entity.standing_eye_height) / 2.0, 0.0)
+            })
+            .filter(|target| target.sub(&pos).length_squared() < FOLLOW_DISTANCE * FOLLOW_DISTANCE)
+            .min_by(|a, b| {
+                a.sub(&pos)
+                    .length_squared()
+                    .total_cmp(&b.sub(&pos).length_squared())
+            })
+    }
+
+    fn spawn_packet(&self) -> CAddExperienceOrb {
+        let pos = self.entity.pos.load();
+        CAddExperienceOrb::new(
+            self.entity.entity_id.into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            self.value.clamp(0, i32::from(i16::MAX)) as i16,
+        )
+    }
+
+    /// Makes the orb visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for ExperienceOrbEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+    }
+}
@@ -0,0 +1,186 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    position::WorldPosition,
+    vector3::Vector3,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::client::play::CSpawnEntity;
+use pumpkin_world::{
+    block::block_registry::get_block_by_state_id, item::ItemStack, physics::is_below_world,
+};
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{tracker::TrackedEntity, Entity};
+
+/// Ticks after which a falling block which never landed drops as an item
+const MAX_FALL_TIME: u32 = 600;
+
+/// A block falling down, like sand without a block below it. It becomes a block again when it
+/// lands, or an item if it can't be placed where it lands
+pub struct FallingBlockEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    /// The state of the falling block
+    pub block_state_id: u16,
+    /// Ticks since the block started falling
+    time: AtomicU32,
+    /// Set once the block landed or dropped, the entity is removed afterwards
+    removed: AtomicBool,
+}
+
+impl FallingBlockEntity {
+    /// A falling block starting at the center of the block it was
+    pub fn new(
+        entity_id: EntityId,
+        world: Arc<World>,
+        location: WorldPosition,
+        block_state_id: u16,
+    ) -> Self {
+        let bounding_box_size = BoundingBoxSize {
+            width: 0.98,
+            height: 0.98,
+        };
+        let entity = Entity::new(
+            entity_id,
+            world,
+            EntityType::FallingBlock,
+            0.0,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        entity.set_pos(Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y),
+            f64::from(location.0.z) + 0.5,
+        ));
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            block_state_id,
+            time: AtomicU32::new(0),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Relaxed)
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        let velocity = self.entity.velocity.load();
+        // The data of falling blocks is their block state
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (EntityType::FallingBlock as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            i32::from(self.block_state_id).into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// Makes the falling block visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+    }
+
+    /// Moves the block down like vanilla's `FallingBlockEntity.tick`. Lands once it stands on a
+    /// block, concrete powder lands in water. Blocks in chunks which are not loaded hold still
+    pub async fn tick(&self, server: &Server) {
+        if self.is_removed() {
+            return;
+        }
+        let time = self.time.fetch_add(1, Ordering::Relaxed) + 1;
+        let Some(movement) = self.entity.tick_movement().await else {
+            return;
+        };
+        let pos = self.entity.pos.load();
+        if is_below_world(pos.y) {
+            self.discard().await;
+            return;
+        }
+
+        // Landing on a block can leave the bottom a rounding error below its top
+        let block_pos = WorldPosition(Vector3::new(
+            pos.x.floor() as i32,
+            (pos.y + 1.0E-7).floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        if self
+            .entity
+            .world()
+            .level
+            .falling_block_lands_in(block_pos, self.block_state_id)
+            || movement.on_ground
+        {
+            self.land(block_pos, server).await;
+        } else if time > MAX_FALL_TIME {
+            self.drop_item(server).await;
+        }
+    }
+
+    /// Places the block where it landed, or drops it if it can't be placed there
+    async fn land(&self, location: WorldPosition, server: &Server) {
+        let world = &self.entity.world();
+        let Some(state_id) = world
+            .level
+            .falling_block_landing(location, self.block_state_id)
+        else {
+            self.drop_item(server).await;
+            return;
+        };
+        self.discard().await;
+        world.set_block_state(location, state_id).await;
+        world.update_neighbors(location, server).await;
+    }
+
+    async fn drop_item(&self, server: &Server) {
+        self.discard().await;
+        let Some(block) = get_block_by_state_id(self.block_state_id) else {
+            return;
+        };
+        if block.item_id != 0 {
+            self.entity
+                .world()
+                .drop_item_stack(
+                    server.new_entity_id(),
+                    self.entity.pos.load(),
+                    ItemStack::new(1, block.item_id),
+                )
+                .await;
+        }
+    }
+
+    async fn discard(&self) {
+        self.removed.store(true, Ordering::Relaxed);
+        self.entity.world().remove_falling_block(self).await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for FallingBlockEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+    }
+}
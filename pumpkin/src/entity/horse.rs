@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::boundingbox::{BoundingBox, BoundingBoxSize};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_inventory::{Container, HorseContainer, HorseType};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::client::play::CSpawnEntity;
+use tokio::sync::Mutex;
+
+use crate::{net::Client, world::World};
+
+use super::{living::LivingEntity, tracker::TrackedEntity, Entity};
+
+/// A horse, donkey, mule or llama, the animals with an inventory players can open
+pub struct HorseEntity {
+    /// The underlying living entity
+    pub living_entity: LivingEntity,
+    pub uuid: uuid::Uuid,
+    /// Saddle, armor and chest of the animal, shared with the open container while players look into it
+    pub inventory: Arc<Mutex<Box<dyn Container>>>,
+}
+
+impl HorseEntity {
+    /// Returns `None` if entities of this type have no horse inventory
+    pub fn new(entity_id: EntityId, world: Arc<World>, entity_type: EntityType) -> Option<Self> {
+        let (horse_type, width, height, eye_height) = match entity_type {
+            EntityType::Horse => (HorseType::Horse, 1.396_484_4, 1.6, 1.52),
+            EntityType::Donkey => (HorseType::Chested, 1.396_484_4, 1.5, 1.425),
+            EntityType::Mule => (HorseType::Chested, 1.396_484_4, 1.6, 1.52),
+            EntityType::Llama | EntityType::TraderLlama => {
+                (HorseType::Llama { strength: 1 }, 0.9, 1.87, 1.7765)
+            }
+            EntityType::SkeletonHorse | EntityType::ZombieHorse => {
+                (HorseType::Undead, 1.396_484_4, 1.6, 1.52)
+            }
+            _ => return None,
+        };
+        let bounding_box_size = BoundingBoxSize { width, height };
+        Some(Self {
+            living_entity: LivingEntity::new(Entity::new(
+                entity_id,
+                world,
+                entity_type,
+                eye_height,
+                AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+                AtomicCell::new(bounding_box_size),
+            )),
+            uuid: uuid::Uuid::new_v4(),
+            inventory: Arc::new(Mutex::new(Box::new(HorseContainer::new(horse_type)))),
+        })
+    }
+
+    pub const fn entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    /// Columns of the chest the inventory window shows, zero if the animal carries no chest
+    pub async fn inventory_columns(&self) -> u8 {
+        // The saddle and armor slot come before the chest, which has three rows
+        let chest_slots = self.inventory.lock().await.all_slots_ref().len() - 2;
+        (chest_slots / 3) as u8
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let entity = self.entity();
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        CSpawnEntity::new(
+            entity.entity_id.into(),
+            self.uuid,
+            (entity.entity_type.clone() as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            entity.pitch.load(),
+            entity.yaw.load(),
+            entity.head_yaw.load(),
+            0.into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// Makes the animal visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity().world().start_tracking(self).await;
+    }
+
+    /// Writes the inventory into the entity NBT
+    pub async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.inventory.lock().await.write_nbt(nbt);
+    }
+
+    /// Restores the inventory from the entity NBT, llamas also get their strength back
+    pub async fn read_nbt(&self, nbt: &NbtCompound) {
+        self.inventory.lock().await.read_nbt(nbt);
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for HorseEntity {
+    fn tracked_entity(&self) -> &Entity {
+        self.entity()
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity().data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
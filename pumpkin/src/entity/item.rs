@@ -0,0 +1,351 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    vector3::Vector3,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{client::play::CSpawnEntity, codec::slot::Slot};
+use pumpkin_world::{
+    block::block_registry::get_block_by_state_id,
+    item::ItemStack,
+    physics::{is_below_world, Movement},
+};
+use tokio::sync::Mutex;
+
+use crate::{net::Client, world::World};
+
+use super::{data::DataField, tracker::TrackedEntity, Entity};
+
+/// Ticks until items can be picked up, e.g. after a block dropped them
+const DEFAULT_PICKUP_DELAY: u32 = 10;
+/// Ticks until items a player threw can be picked up, so they don't land right back in the inventory
+pub const THROWN_ITEM_PICKUP_DELAY: u32 = 40;
+/// Ticks until items despawn, five minutes
+const DESPAWN_AGE: u32 = 6000;
+/// Health of new items, lava and cactus destroy them quickly
+const MAX_HEALTH: u32 = 5;
+/// Damage lava does every tick
+const LAVA_DAMAGE: u32 = 4;
+/// Ticks between looking for items to merge with, items which moved to another block look every
+/// other tick
+const MERGE_INTERVAL: u32 = 40;
+/// The entity id vanilla saves items with
+pub const ITEM_ENTITY_ID: &str = "minecraft:item";
+/// The items players see
+const ITEM: DataField<Slot> = DataField::new(8);
+
+/// A stack of items lying in the world, e.g. after a player dropped it
+pub struct ItemEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    /// The items this entity represents
+    pub item_stack: Mutex<ItemStack>,
+    /// Used for the spawn packet, item entities have no game profile
+    pub uuid: uuid::Uuid,
+    /// Ticks until players can pick the items up
+    pickup_delay: AtomicU32,
+    /// Ticks the items are lying in the world, they despawn once they are old enough
+    age: AtomicU32,
+    /// Lava, fire and cactus destroy the items once they did this much damage
+    health: AtomicU32,
+    /// Whether the items moved to another block during the last tick
+    moved_block: AtomicBool,
+    /// Whether the items were picked up, merged or destroyed, removed items are not ticked anymore
+    removed: AtomicBool,
+}
+
+impl ItemEntity {
+    pub fn new(entity_id: EntityId, world: Arc<World>, item_stack: ItemStack) -> Self {
+        let bounding_box_size = BoundingBoxSize {
+            width: 0.25,
+            height: 0.25,
+        };
+        let entity = Entity::new(
+            entity_id,
+            world,
+            EntityType::Item,
+            0.2125,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        entity.data.set(&ITEM, Slot::from(&item_stack));
+        Self {
+            entity,
+            item_stack: Mutex::new(item_stack),
+            uuid: uuid::Uuid::new_v4(),
+            pickup_delay: AtomicU32::new(DEFAULT_PICKUP_DELAY),
+            age: AtomicU32::new(0),
+            health: AtomicU32::new(MAX_HEALTH),
+            moved_block: AtomicBool::new(false),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    /// Reads items saved in a chunk's entities, `None` if the item is unknown
+    pub fn from_nbt(entity_id: EntityId, world: Arc<World>, nbt: &NbtCompound) -> Option<Self> {
+        let item_stack = ItemStack::read_item_nbt(nbt.get_compound("Item")?)?;
+        let mut item_entity = Self::new(entity_id, world, item_stack);
+        // Vanilla saves UUIDs as four ints, the most significant first
+        if let Some(ints) = nbt.get_int_array("UUID").filter(|ints| ints.len() == 4) {
+            let value = ints
+                .iter()
+                .fold(0u128, |value, int| (value << 32) | u128::from(*int as u32));
+            item_entity.uuid = uuid::Uuid::from_u128(value);
+        }
+        let vector = |name: &str| {
+            let list = nbt.get_list(name)?;
+            let mut values = list.iter().map(NbtTag::extract_double);
+            Some(Vector3::new(
+                values.next()??,
+                values.next()??,
+                values.next()??,
+            ))
+        };
+        item_entity.set_motion(
+            vector("Pos")?,
+            vector("Motion").unwrap_or(Vector3::new(0.0, 0.0, 0.0)),
+        );
+        item_entity
+            .entity
+            .on_ground
+            .store(nbt.get_bool("OnGround").unwrap_or(false), Ordering::Relaxed);
+        let short = |name: &str, default: u32| {
+            nbt.get_short(name)
+                .map_or(default, |value| value.max(0) as u32)
+        };
+        item_entity.set_pickup_delay(short("PickupDelay", 0));
+        item_entity.age.store(short("Age", 0), Ordering::Relaxed);
+        item_entity
+            .health
+            .store(short("Health", MAX_HEALTH), Ordering::Relaxed);
+        Some(item_entity)
+    }
+
+    /// Writes the items like vanilla saves them in a chunk's entities
+    pub async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put("id".to_string(), NbtTag::String(ITEM_ENTITY_ID.to_string()));
+        let vector = |vector: Vector3<f64>| {
+            NbtTag::List(vec![
+                NbtTag::Double(vector.x),
+                NbtTag::Double(vector.y),
+                NbtTag::Double(vector.z),
+            ])
+        };
+        nbt.put("Pos".to_string(), vector(self.entity.pos.load()));
+        nbt.put("Motion".to_string(), vector(self.entity.velocity.load()));
+        nbt.put(
+            "OnGround".to_string(),
+            NbtTag::Byte(i8::from(self.entity.on_ground.load(Ordering::Relaxed))),
+        );
+        let short = |value: &AtomicU32| NbtTag::Short(value.load(Ordering::Relaxed) as i16);
+        nbt.put("PickupDelay".to_string(), short(&self.pickup_delay));
+        nbt.put("Age".to_string(), short(&self.age));
+        nbt.put("Health".to_string(), short(&self.health));
+        let uuid = self.uuid.as_u128();
+        nbt.put(
+            "UUID".to_string(),
+            NbtTag::IntArray([96, 64, 32, 0].map(|shift| (uuid >> shift) as i32).to_vec()),
+        );
+        let mut item = NbtCompound::new();
+        self.item_stack.lock().await.write_item_nbt(&mut item);
+        nbt.put("Item".to_string(), NbtTag::Compound(item));
+    }
+
+    /// Applies the item, pickup delay and age of the data, like `/data` changes them
+    pub async fn read_nbt(&self, nbt: &NbtCompound) {
+        if let Some(item_stack) = nbt.get_compound("Item").and_then(ItemStack::read_item_nbt) {
+            *self.item_stack.lock().await = item_stack;
+            self.update_item_stack().await;
+        }
+        if let Some(ticks) = nbt.get_short("PickupDelay") {
+            self.set_pickup_delay(ticks.max(0) as u32);
+        }
+        if let Some(age) = nbt.get_short("Age") {
+            self.age.store(age.max(0) as u32, Ordering::Relaxed);
+        }
+    }
+
+    pub fn set_pickup_delay(&self, ticks: u32) {
+        self.pickup_delay.store(ticks, Ordering::Relaxed);
+    }
+
+    pub fn can_be_picked_up(&self) -> bool {
+        self.pickup_delay.load(Ordering::Relaxed) == 0
+    }
+
+    /// Whether the items were picked up, merged or destroyed
+    pub fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Relaxed)
+    }
+
+    /// Removes the items from the world for good, e.g. once they were picked up
+    pub async fn discard(&self) {
+        if !self.removed.swap(true, Ordering::Relaxed) {
+            self.entity.world().remove_item_entity(self).await;
+        }
+    }
+
+    /// Counts down the pickup delay, ages and moves the items, they float up in water. Lava, fire
+    /// and cactus destroy them. Returns false once the items were removed
+    pub async fn tick(&self) -> bool {
+        if self.is_removed() {
+            return false;
+        }
+        // Fails once the delay is over, which leaves it at zero
+        let _ = self
+            .pickup_delay
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |delay| {
+                delay.checked_sub(1)
+            });
+        if self.age.fetch_add(1, Ordering::Relaxed) + 1 >= DESPAWN_AGE {
+            self.discard().await;
+            return false;
+        }
+
+        let block_pos = self.entity.block_pos.load();
+        let movement = self.entity.tick_movement().await;
+        self.moved_block
+            .store(self.entity.block_pos.load() != block_pos, Ordering::Relaxed);
+        if is_below_world(self.entity.pos.load().y) {
+            self.discard().await;
+            return false;
+        }
+
+        let damage = self.environment_damage(movement.as_ref()).await;
+        if damage > 0 {
+            let health = self.health.load(Ordering::Relaxed).saturating_sub(damage);
+            self.health.store(health, Ordering::Relaxed);
+            if health == 0 {
+                self.discard().await;
+                return false;
+            }
+        }
+        true
+    }
+
+    /// The damage lava and the fire and cactus blocks the items are in do this tick, items like
+    /// netherite only burn up in nothing
+    async fn environment_damage(&self, movement: Option<&Movement>) -> u32 {
+        let fire_resistant = self.item_stack.lock().await.is_fire_resistant();
+        let mut damage = 0;
+        if !fire_resistant && movement.is_some_and(|movement| movement.lava.is_some()) {
+            damage += LAVA_DAMAGE;
+        }
+        let blocks = self
+            .entity
+            .world()
+            .level
+            .blocks_inside(&self.entity.bounding_box.load());
+        for state_id in blocks {
+            match get_block_by_state_id(state_id).map(|block| block.name.as_str()) {
+                Some("cactus") => damage += 1,
+                Some("fire" | "soul_fire") if !fire_resistant => damage += 1,
+                _ => {}
+            }
+        }
+        damage
+    }
+
+    /// Whether the items look for items to merge with this tick, like vanilla they look more
+    /// often while they move
+    pub fn should_merge(&self) -> bool {
+        let interval = if self.moved_block.load(Ordering::Relaxed) {
+            2
+        } else {
+            MERGE_INTERVAL
+        };
+        !self.is_removed() && self.age.load(Ordering::Relaxed).is_multiple_of(interval)
+    }
+
+    /// Merges the items with the other items if all of them fit into a single stack, the smaller
+    /// stack joins the bigger one like in vanilla's `ItemEntity.tryToMerge`
+    pub async fn try_merge(&self, other: &Self) {
+        if self.is_removed() || other.is_removed() {
+            return;
+        }
+        let (target, source) = {
+            let mut stack = self.item_stack.lock().await;
+            let mut other_stack = other.item_stack.lock().await;
+            let total = u16::from(stack.item_count) + u16::from(other_stack.item_count);
+            if !stack.is_stackable_with(&other_stack) || total > u16::from(stack.max_stack_size()) {
+                return;
+            }
+            if other_stack.item_count < stack.item_count {
+                stack.item_count += other_stack.item_count;
+                other_stack.item_count = 0;
+                (self, other)
+            } else {
+                other_stack.item_count += stack.item_count;
+                stack.item_count = 0;
+                (other, self)
+            }
+        };
+        target.pickup_delay.fetch_max(
+            source.pickup_delay.load(Ordering::Relaxed),
+            Ordering::Relaxed,
+        );
+        target
+            .age
+            .fetch_min(source.age.load(Ordering::Relaxed), Ordering::Relaxed);
+        target.update_item_stack().await;
+        source.discard().await;
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        let velocity = self.entity.velocity.load();
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (EntityType::Item as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            0.into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// Makes the item entity visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+    }
+
+    /// Shows the changed item stack to every player, e.g. after a player picked up part of it
+    pub async fn update_item_stack(&self) {
+        let item_stack = Slot::from(&*self.item_stack.lock().await);
+        self.entity.data.set(&ITEM, item_stack);
+    }
+
+    /// Sets position and motion, should be called before spawning
+    pub fn set_motion(&self, position: Vector3<f64>, velocity: Vector3<f64>) {
+        self.entity.set_pos(position);
+        self.entity.velocity.store(velocity);
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for ItemEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity.data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::{
+    atomic::{AtomicU8, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        position::WorldPosition,
+        vector3::Vector3,
+    },
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_macros::sound;
+use pumpkin_protocol::{client::play::CSpawnEntity, codec::slot::Slot, SoundCategory};
+use pumpkin_world::{
+    block::BlockFace,
+    item::{item_registry::get_item, ItemStack},
+};
+use tokio::sync::Mutex;
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{data::DataField, player::Player, tracker::TrackedEntity, Entity};
+
+/// Item frames show the item in eight rotations, using it again turns it further
+const ROTATIONS: u8 = 8;
+const ITEM: DataField<Slot> = DataField::new(8);
+const ROTATION: DataField<i32> = DataField::new(9);
+
+/// An item frame hanging on a block, players can put an item into it and rotate it
+pub struct ItemFrameEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    /// The side of the block the frame hangs on, it faces away from the block
+    pub facing: BlockFace,
+    /// The item shown in the frame
+    pub item: Mutex<Option<ItemStack>>,
+    rotation: AtomicU8,
+}
+
+impl ItemFrameEntity {
+    /// Hangs a frame on the face of the block, glowing frames are a different entity type
+    pub fn new(
+        entity_id: EntityId,
+        world: Arc<World>,
+        glowing: bool,
+        location: WorldPosition,
+        facing: BlockFace,
+    ) -> Self {
+        let entity_type = if glowing {
+            EntityType::GlowItemFrame
+        } else {
+            EntityType::ItemFrame
+        };
+        let bounding_box_size = BoundingBoxSize {
+            width: 0.75,
+            height: 0.75,
+        };
+        let entity = Entity::new(
+            entity_id,
+            world,
+            entity_type,
+            0.0,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        // The frame sits in the block in front of the face, flat against the block it hangs on
+        let offset = facing.to_offset();
+        let in_front = location.0.add(&offset);
+        entity.set_pos(Vector3::new(
+            f64::from(in_front.x) + 0.5 - f64::from(offset.x) * 0.468_75,
+            f64::from(in_front.y) + 0.5 - f64::from(offset.y) * 0.468_75,
+            f64::from(in_front.z) + 0.5 - f64::from(offset.z) * 0.468_75,
+        ));
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            facing,
+            item: Mutex::new(None),
+            rotation: AtomicU8::new(0),
+        }
+    }
+
+    /// The item players get back when breaking the frame
+    fn frame_item(&self) -> Option<ItemStack> {
+        let name = if matches!(self.entity.entity_type, EntityType::GlowItemFrame) {
+            "glow_item_frame"
+        } else {
+            "item_frame"
+        };
+        get_item(name).map(|item| ItemStack::new(1, item.id))
+    }
+
+    /// Puts the held item into the empty frame, otherwise the shown item is rotated
+    pub async fn interact(&self, player: &Player, offhand: bool) {
+        let mut item = self.item.lock().await;
+        if item.is_some() {
+            drop(item);
+            self.set_rotation((self.rotation.load(Ordering::Relaxed) + 1) % ROTATIONS);
+            self.play_sound(sound!("entity.item_frame.rotate_item"))
+                .await;
+            return;
+        }
+
+        {
+            let mut inventory = player.inventory().lock().await;
+            let hand = inventory.hand_item_mut(offhand);
+            let Some(held) = hand.as_mut() else {
+                return;
+            };
+            *item = Some(held.with_count(1));
+            if player.gamemode.load() != GameMode::Creative {
+                held.item_count -= 1;
+                if held.item_count == 0 {
+                    *hand = None;
+                }
+            }
+        }
+        player.set_container_content(None).await;
+        self.entity.data.set(&ITEM, Slot::from(item.as_ref()));
+        drop(item);
+        self.set_rotation(0);
+        self.play_sound(sound!("entity.item_frame.add_item")).await;
+    }
+
+    /// Attacking pops the item out of the frame, attacking an empty frame breaks it.
+    /// Nothing drops for players in creative
+    pub async fn attack(&self, player: &Player, server: &Server) {
+        let world = &self.entity.world();
+        let drops = player.gamemode.load() != GameMode::Creative;
+        let pos = self.entity.pos.load();
+        let removed = self.item.lock().await.take();
+        if let Some(removed) = removed {
+            self.entity.data.set(&ITEM, Slot::empty());
+            self.play_sound(sound!("entity.item_frame.remove_item"))
+                .await;
+            if drops {
+                world
+                    .drop_item_stack(server.new_entity_id(), pos, removed)
+                    .await;
+            }
+            return;
+        }
+
+        world.remove_item_frame(self).await;
+        self.play_sound(sound!("entity.item_frame.break")).await;
+        if let Some(frame_item) = self.frame_item().filter(|_| drops) {
+            world
+                .drop_item_stack(server.new_entity_id(), pos, frame_item)
+                .await;
+        }
+    }
+
+    async fn play_sound(&self, sound_id: u16) {
+        let pos = self.entity.pos.load();
+        self.entity
+            .world()
+            .play_sound(sound_id, SoundCategory::Blocks, &pos)
+            .await;
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (self.entity.entity_type.clone() as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            // The direction the frame faces, in the same order as block faces
+            (self.facing as i32).into(),
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    fn set_rotation(&self, rotation: u8) {
+        self.rotation.store(rotation, Ordering::Relaxed);
+        self.entity.data.set(&ROTATION, i32::from(rotation));
+    }
+
+    /// Makes the frame visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+        self.play_sound(sound!("entity.item_frame.place")).await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for ItemFrameEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity.data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    position::WorldPosition,
+    vector3::Vector3,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::client::play::CSpawnEntity;
+
+use crate::{net::Client, world::World};
+
+use super::{tracker::TrackedEntity, Entity};
+
+/// Ticks lightning stays visible, about as long as vanilla's bolts flash
+const LIFE: u32 = 10;
+const SIZE: BoundingBoxSize = BoundingBoxSize {
+    width: 0.0,
+    height: 0.0,
+};
+
+/// A lightning bolt, players see the flash and hear the thunder while it exists
+pub struct LightningBoltEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    /// Ticks until the bolt disappears
+    life: AtomicU32,
+}
+
+impl LightningBoltEntity {
+    /// A bolt striking the bottom of the block
+    pub fn new(entity_id: EntityId, world: Arc<World>, location: WorldPosition) -> Self {
+        let entity = Entity::new(
+            entity_id,
+            world,
+            EntityType::LightningBolt,
+            0.0,
+            AtomicCell::new(BoundingBox::new_default(&SIZE)),
+            AtomicCell::new(SIZE),
+        );
+        entity.set_pos(Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y),
+            f64::from(location.0.z) + 0.5,
+        ));
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            life: AtomicU32::new(LIFE),
+        }
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (EntityType::LightningBolt as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            0.into(),
+            0.0,
+            0.0,
+            0.0,
+        )
+    }
+
+    /// Makes the bolt visible to the players within its tracking range, their clients play the
+    /// thunder
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+    }
+
+    /// Removes the bolt once it flashed long enough
+    pub async fn tick(&self) {
+        let life = self.life.load(Ordering::Relaxed).saturating_sub(1);
+        self.life.store(life, Ordering::Relaxed);
+        if life == 0 {
+            self.entity.world().remove_lightning_bolt(self).await;
+        }
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for LightningBoltEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+    }
+}
@@ -1,12 +1,52 @@
 use std::sync::atomic::AtomicI32;
 
 use crossbeam::atomic::AtomicCell;
-use pumpkin_core::math::vector3::Vector3;
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_entity::entity_type::EntityType;
 use pumpkin_inventory::{Container, EmptyContainer};
-use pumpkin_protocol::client::play::{CDamageEvent, CEntityStatus, CSetEntityMetadata, Metadata};
+use pumpkin_protocol::client::play::{
+    CDamageEvent, CEntityStatus, CRemoveMobEffect, CUpdateMobEffect,
+};
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_world::{
+    block::{
+        block_registry::{get_block, get_block_by_state_id},
+        fluid::Fluid,
+        random_tick::{random_tick_behavior, RandomTickBehavior},
+    },
+    damage::{
+        breaks_fall, damage_after_protection, fall_damage, landing_damage_multiplier,
+        tick_air_supply, DamageType, Protection, MAX_AIR_SUPPLY,
+    },
+    effect::{ActiveEffects, EffectInstance, StatusEffect},
+    world_info::game_rules::BoolRule,
+};
 use tokio::sync::Mutex;
 
-use super::Entity;
+use super::{
+    damage::{DamageEntity, DamageSource},
+    data::{DataField, AIR_SUPPLY, HEALTH},
+    Entity, Flag,
+};
+
+pub const MAX_HEALTH: f32 = 20.0;
+/// The absorption hearts of players
+const ABSORPTION: DataField<f32> = DataField::new(15);
+
+/// Whether healing hurts mobs of the type and harming heals them, like vanilla's
+/// `isInvertedHealAndHarm`
+const fn is_undead(entity_type: &EntityType) -> bool {
+    matches!(
+        entity_type,
+        EntityType::Zombie
+            | EntityType::Husk
+            | EntityType::Drowned
+            | EntityType::ZombieVillager
+            | EntityType::Skeleton
+            | EntityType::Stray
+            | EntityType::WitherSkeleton
+    )
+}
 
 /// Represents a living entity within the game world.
 ///
@@ -27,11 +67,21 @@ where
     pub health: AtomicCell<f32>,
     /// The distance the entity has been falling
     pub fall_distance: AtomicCell<f64>,
+    /// Ticks the entity keeps burning
+    pub fire_ticks: AtomicI32,
+    /// The air left while holding its breath under water
+    pub air_supply: AtomicI32,
+    /// The status effects the entity has
+    pub effects: Mutex<ActiveEffects>,
+    /// Extra health which is lost before the real one, like from the Absorption effect
+    pub absorption: AtomicCell<f32>,
+    /// What hurt the entity last, tells how it died
+    pub last_damage_source: Mutex<Option<DamageSource>>,
     /// Inventory if it exists on the entity
     pub inventory: Option<Mutex<C>>,
 }
 impl LivingEntity {
-    pub const fn new(entity: Entity) -> Self {
+    pub fn new(entity: Entity) -> Self {
         Self {
             entity,
             last_pos: AtomicCell::new(Vector3::new(0.0, 0.0, 0.0)),
@@ -39,6 +89,11 @@ impl LivingEntity {
             last_damage_taken: AtomicCell::new(0.0),
             health: AtomicCell::new(20.0),
             fall_distance: AtomicCell::new(0.0),
+            fire_ticks: AtomicI32::new(0),
+            air_supply: AtomicI32::new(MAX_AIR_SUPPLY),
+            effects: Mutex::new(ActiveEffects::default()),
+            absorption: AtomicCell::new(0.0),
+            last_damage_source: Mutex::new(None),
             // This automatically gets inferred as Option::<EmptyContainer>::None
             inventory: None,
         }
@@ -53,6 +108,11 @@ impl<C: Container> LivingEntity<C> {
             last_damage_taken: AtomicCell::new(0.0),
             health: AtomicCell::new(20.0),
             fall_distance: AtomicCell::new(0.0),
+            fire_ticks: AtomicI32::new(0),
+            air_supply: AtomicI32::new(MAX_AIR_SUPPLY),
+            effects: Mutex::new(ActiveEffects::default()),
+            absorption: AtomicCell::new(0.0),
+            last_damage_source: Mutex::new(None),
             inventory: Some(Mutex::new(inventory)),
         }
     }
@@ -73,62 +133,123 @@ impl<C: Container> LivingEntity<C> {
         self.entity.set_pos(position);
     }
 
-    pub async fn set_health(&self, health: f32) {
+    pub fn set_health(&self, health: f32) {
         self.health.store(health);
-        // tell everyone entities health changed
-        self.entity
-            .world
-            .broadcast_packet_all(&CSetEntityMetadata::new(
-                self.entity.entity_id.into(),
-                Metadata::new(9, 3.into(), health),
-            ))
-            .await;
+        self.entity.data.set(&HEALTH, health);
     }
 
-    // TODO add damage_type enum
-    pub async fn damage(&self, amount: f32, damage_type: u8) {
-        self.entity
-            .world
-            .broadcast_packet_all(&CDamageEvent::new(
-                self.entity.entity_id.into(),
-                damage_type.into(),
-                None,
-                None,
-                None,
-            ))
-            .await;
+    /// Heals the entity up to its maximum health
+    pub fn heal(&self, amount: f32) {
+        let health = self.health.load();
+        if health <= 0.0 || health >= MAX_HEALTH {
+            return;
+        }
+        self.set_health((health + amount).min(MAX_HEALTH));
+    }
 
-        let new_health = (self.health.load() - amount).max(0.0);
+    /// Hurts the entity unless it can't be hurt right now, like vanilla's `hurt` for entities
+    /// without armor. Returns whether the entity was hurt
+    pub async fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        let Some(amount) = self.check_hurt(source, amount).await else {
+            return false;
+        };
+        self.actually_hurt(source, amount, Protection::default())
+            .await;
+        true
+    }
 
-        if new_health == 0.0 {
-            self.kill().await;
-        } else {
-            self.set_health(new_health).await;
+    /// Checks whether the entity can be hurt by the damage right now and tells everyone when it
+    /// is. Shortly after being hurt the entity only takes the damage above the last one. Returns
+    /// the damage left to deal
+    pub async fn check_hurt(&self, source: &DamageSource, amount: f32) -> Option<f32> {
+        if self.health.load() <= 0.0 {
+            return None;
+        }
+        if source.damage_type.is_fire()
+            && !source.damage_type.bypasses_effects()
+            && self.effects.lock().await.has(StatusEffect::FireResistance)
+        {
+            return None;
         }
-    }
 
-    /// Returns if the entity was damaged or not
-    pub fn check_damage(&self, amount: f32) -> bool {
         let regen = self
             .time_until_regen
             .load(std::sync::atomic::Ordering::Relaxed);
-
         let last_damage = self.last_damage_taken.load();
-        // TODO: check if bypasses iframe
         if regen > 10 {
             if amount <= last_damage {
-                return false;
+                return None;
             }
-        } else {
-            self.time_until_regen
-                .store(20, std::sync::atomic::Ordering::Relaxed);
+            self.last_damage_taken.store(amount);
+            return Some(amount - last_damage);
         }
 
         self.last_damage_taken.store(amount);
-        amount > 0.0
+        self.time_until_regen
+            .store(20, std::sync::atomic::Ordering::Relaxed);
+        self.broadcast_damage_event(source).await;
+        Some(amount)
+    }
+
+    async fn broadcast_damage_event(&self, source: &DamageSource) {
+        let entity_id =
+            |entity: &Option<DamageEntity>| entity.as_ref().map(|entity| VarInt(entity.entity_id));
+        self.entity
+            .broadcast_to_trackers_and_self(&CDamageEvent::new(
+                self.entity.entity_id.into(),
+                source.damage_type.id().into(),
+                entity_id(&source.attacker),
+                entity_id(&source.direct),
+                source.position,
+            ))
+            .await;
+    }
+
+    /// Deals the damage after what protects the entity and its absorption hearts, like vanilla's
+    /// `actuallyHurt`. The Resistance effect of the entity is added to the protection
+    pub async fn actually_hurt(&self, source: &DamageSource, amount: f32, protection: Protection) {
+        let protection = Protection {
+            resistance: self
+                .effects
+                .lock()
+                .await
+                .get(StatusEffect::Resistance)
+                .map(|instance| instance.amplifier),
+            ..protection
+        };
+        let amount = damage_after_protection(source.damage_type, amount, &protection);
+        *self.last_damage_source.lock().await = Some(source.clone());
+
+        let absorption = self.absorption.load();
+        let absorbed = amount.min(absorption);
+        if absorbed > 0.0 {
+            self.set_absorption(absorption - absorbed);
+        }
+        let amount = amount - absorbed;
+        if amount <= 0.0 {
+            return;
+        }
+
+        let new_health = (self.health.load() - amount).max(0.0);
+        if new_health == 0.0 {
+            self.kill().await;
+        } else {
+            self.set_health(new_health);
+        }
+    }
+
+    /// Sets the extra hearts which are lost before health
+    pub fn set_absorption(&self, absorption: f32) {
+        self.absorption.store(absorption.max(0.0));
+        // Only players show their absorption hearts
+        if matches!(self.entity.entity_type, EntityType::Player) {
+            self.entity.data.set(&ABSORPTION, absorption.max(0.0));
+        }
     }
 
-    pub async fn update_fall_distance(&self, dont_damage: bool) {
+    /// Tracks how far the entity fell, returns the fall damage it takes when it lands. Water,
+    /// climbing and Slow Falling stop the fall, the block it lands on and Jump Boost soften it
+    pub async fn update_fall_distance(&self, dont_damage: bool) -> Option<f32> {
         let y = self.entity.pos.load().y;
         let last_y = self.last_pos.load().y;
         let grounded = self
@@ -139,42 +260,327 @@ impl<C: Container> LivingEntity<C> {
         // + => falling, - => up
         let y_diff = last_y - y;
 
+        let (slow_falling, jump_boost) = {
+            let effects = self.effects.lock().await;
+            (
+                effects.has(StatusEffect::SlowFalling),
+                effects
+                    .get(StatusEffect::JumpBoost)
+                    .map(|instance| instance.amplifier),
+            )
+        };
+        let world = self.entity.world();
+        let bounding_box = self.entity.bounding_box.load();
+        let fall_broken = slow_falling
+            || world
+                .level
+                .fluid_contact(&bounding_box, Fluid::Water)
+                .is_some()
+            || world
+                .level
+                .blocks_inside(&bounding_box)
+                .into_iter()
+                .any(|state_id| {
+                    get_block_by_state_id(state_id).is_some_and(|block| breaks_fall(&block.name))
+                });
+        if fall_broken {
+            self.fall_distance.store(0.0);
+        }
+
         if grounded {
             let fall_distance = self.fall_distance.swap(0.0);
-            if dont_damage {
-                return;
-            }
-
-            let mut damage = (fall_distance - 3.0).max(0.0) as f32;
-            damage = (damage * 2.0).round() / 2.0;
-            if !self.check_damage(damage) {
-                return;
+            self.trample_farmland(fall_distance).await;
+            if dont_damage
+                || fall_distance <= 0.0
+                || !world.game_rules.lock().await.get_bool(BoolRule::FallDamage)
+            {
+                return None;
             }
 
-            self.damage(damage, 10).await; // Fall
-        } else if y_diff < 0.0 {
+            let pos = self.entity.pos.load();
+            let below = WorldPosition(Vector3::new(
+                pos.x.floor() as i32,
+                (pos.y - 0.2).floor() as i32,
+                pos.z.floor() as i32,
+            ));
+            let sneaking = self
+                .entity
+                .sneaking
+                .load(std::sync::atomic::Ordering::Relaxed);
+            let multiplier = world.get_block(below).await.map_or(1.0, |block| {
+                landing_damage_multiplier(&block.name, sneaking)
+            });
+            let damage = fall_damage(fall_distance, jump_boost, multiplier);
+            return (damage > 0.0).then_some(damage);
+        }
+        if y_diff < 0.0 {
             self.fall_distance.store(0.0);
-        } else {
+        } else if !fall_broken {
             let fall_distance = self.fall_distance.load();
             self.fall_distance.store(fall_distance + y_diff);
         }
+        None
+    }
+
+    /// Landing on farmland turns it back into dirt and breaks the crops on top, like vanilla's
+    /// `FarmBlock.fallOn`. Only players trample farmland when mobs are not allowed to grief
+    async fn trample_farmland(&self, fall_distance: f64) {
+        let world = self.entity.world();
+        let pos = self.entity.pos.load();
+        let below = WorldPosition(Vector3::new(
+            pos.x.floor() as i32,
+            (pos.y - 0.2).floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        if !world
+            .get_block(below)
+            .await
+            .is_ok_and(|block| block.name == "farmland")
+        {
+            return;
+        }
+        let size = self.entity.bounding_box_size.load();
+        if rand::random::<f64>() >= fall_distance - 0.5
+            || size.width * size.width * size.height <= 0.512
+        {
+            return;
+        }
+        if !matches!(self.entity.entity_type, EntityType::Player)
+            && !world
+                .game_rules
+                .lock()
+                .await
+                .get_bool(BoolRule::MobGriefing)
+        {
+            return;
+        }
+        let Some(dirt) = get_block("minecraft:dirt") else {
+            return;
+        };
+        let above = WorldPosition(below.0 + Vector3::new(0, 1, 0));
+        let crop = world.get_block_state_id(above).await.is_ok_and(|state_id| {
+            matches!(
+                random_tick_behavior(state_id),
+                Some(RandomTickBehavior::Crop { .. })
+            )
+        });
+        if crop {
+            world.break_block(above, None).await;
+        }
+        world.set_block_state(below, dirt.default_state_id).await;
+    }
+
+    pub fn is_on_fire(&self) -> bool {
+        self.fire_ticks.load(std::sync::atomic::Ordering::Relaxed) > 0
+    }
+
+    /// Sets the entity on fire for at least the ticks, like vanilla's `igniteForTicks`
+    pub fn set_on_fire_for(&self, ticks: i32) {
+        let burning = self
+            .fire_ticks
+            .fetch_max(ticks, std::sync::atomic::Ordering::Relaxed)
+            > 0;
+        if !burning && ticks > 0 {
+            self.entity.set_flag(Flag::OnFire, true);
+        }
+    }
+
+    /// Puts out the fire the entity burns in
+    pub fn extinguish(&self) {
+        if self
+            .fire_ticks
+            .swap(0, std::sync::atomic::Ordering::Relaxed)
+            > 0
+        {
+            self.entity.set_flag(Flag::OnFire, false);
+        }
+    }
+
+    /// Counts down the fire of the entity, water puts it out. Returns whether the fire burns the
+    /// entity this tick, it does so once a second
+    pub fn tick_fire(&self, in_water: bool) -> bool {
+        let ticks = self.fire_ticks.load(std::sync::atomic::Ordering::Relaxed);
+        if ticks <= 0 {
+            return false;
+        }
+        let burns = !in_water && ticks % 20 == 0;
+        let remaining = if in_water { 0 } else { ticks - 1 };
+        self.fire_ticks
+            .store(remaining, std::sync::atomic::Ordering::Relaxed);
+        if remaining == 0 {
+            self.entity.set_flag(Flag::OnFire, false);
+        }
+        burns
+    }
+
+    /// Counts down the air of the entity while its eyes are under water and brings it back when
+    /// it breathes, Water Breathing lets it breathe under water. Returns whether it drowns this
+    /// tick
+    pub async fn tick_air(&self, eyes_in_water: bool, respiration: i32) -> bool {
+        let holding_breath =
+            eyes_in_water && !self.effects.lock().await.has(StatusEffect::WaterBreathing);
+        let air_supply = self.air_supply.load(std::sync::atomic::Ordering::Relaxed);
+        let (new_air_supply, drowns) = tick_air_supply(
+            air_supply,
+            holding_breath,
+            respiration,
+            &mut rand::thread_rng(),
+        );
+        if new_air_supply != air_supply {
+            self.set_air_supply(new_air_supply);
+        }
+        drowns
+    }
+
+    pub fn set_air_supply(&self, air_supply: i32) {
+        self.air_supply
+            .store(air_supply, std::sync::atomic::Ordering::Relaxed);
+        self.entity.data.set(&AIR_SUPPLY, air_supply);
+    }
+
+    /// Gives the entity the effect unless it already has a stronger or longer one of it, like
+    /// vanilla's `addEffect`. Returns whether the entity got the effect
+    pub async fn add_effect(&self, instance: EffectInstance) -> bool {
+        // Undead mobs can't be poisoned or regenerate
+        if is_undead(&self.entity.entity_type)
+            && matches!(
+                instance.effect,
+                StatusEffect::Poison | StatusEffect::Regeneration
+            )
+        {
+            return false;
+        }
+        if !self.effects.lock().await.add(instance) {
+            return false;
+        }
+        let mut flags = 0;
+        if instance.ambient {
+            flags |= 0x01;
+        }
+        if instance.show_particles {
+            flags |= 0x02;
+        }
+        if instance.show_icon {
+            flags |= 0x04;
+        }
+        self.entity
+            .broadcast_to_trackers_and_self(&CUpdateMobEffect::new(
+                self.entity.entity_id.into(),
+                instance.effect.id().into(),
+                i32::from(instance.amplifier).into(),
+                instance.duration.into(),
+                flags,
+            ))
+            .await;
+        self.set_effect_flag(instance.effect, true);
+        if matches!(instance.effect, StatusEffect::Absorption) {
+            // Absorption hearts are given once, they don't come back after being lost
+            let absorption = 4.0 * (f32::from(instance.amplifier) + 1.0);
+            if self.absorption.load() < absorption {
+                self.set_absorption(absorption);
+            }
+        }
+        true
+    }
+
+    /// Takes the effect from the entity, returns whether it had it
+    pub async fn remove_effect(&self, effect: StatusEffect) -> bool {
+        if self.effects.lock().await.remove(effect).is_none() {
+            return false;
+        }
+        self.on_effect_removed(effect).await;
+        true
+    }
+
+    /// Takes every effect from the entity, like drinking milk. Returns the effects it had
+    pub async fn clear_effects(&self) -> Vec<EffectInstance> {
+        let removed = self.effects.lock().await.clear();
+        for instance in &removed {
+            self.on_effect_removed(instance.effect).await;
+        }
+        removed
+    }
+
+    async fn on_effect_removed(&self, effect: StatusEffect) {
+        self.entity
+            .broadcast_to_trackers_and_self(&CRemoveMobEffect::new(
+                self.entity.entity_id.into(),
+                effect.id().into(),
+            ))
+            .await;
+        self.set_effect_flag(effect, false);
+        if matches!(effect, StatusEffect::Absorption) {
+            self.set_absorption(0.0);
+        }
+    }
+
+    /// Glowing and invisible entities are shown as such through their entity flags
+    fn set_effect_flag(&self, effect: StatusEffect, value: bool) {
+        let flag = match effect {
+            StatusEffect::Glowing => Flag::Glowing,
+            StatusEffect::Invisibility => Flag::Invisible,
+            _ => return,
+        };
+        self.entity.set_flag(flag, value);
+    }
+
+    /// Applies the effects of the entity which do something this tick and removes the ones which
+    /// ran out, like vanilla's `tickEffects`. Returns the effects which ran out
+    pub async fn tick_effects(&self) -> Vec<StatusEffect> {
+        let tick = self.effects.lock().await.tick();
+        for instance in &tick.applied {
+            self.apply_effect_tick(instance).await;
+        }
+        for &effect in &tick.expired {
+            self.on_effect_removed(effect).await;
+        }
+        tick.expired
+    }
+
+    /// What effects do to the entity when they apply, like vanilla's `applyEffectTick`
+    async fn apply_effect_tick(&self, instance: &EffectInstance) {
+        let undead = is_undead(&self.entity.entity_type);
+        match instance.effect {
+            StatusEffect::Regeneration => self.heal(1.0),
+            // Poison never kills
+            StatusEffect::Poison if self.health.load() > 1.0 => {
+                self.hurt(&DamageSource::new(DamageType::Magic), 1.0).await;
+            }
+            StatusEffect::Wither => {
+                self.hurt(&DamageSource::new(DamageType::Wither), 1.0).await;
+            }
+            effect @ (StatusEffect::InstantHealth | StatusEffect::InstantDamage) => {
+                let heals = matches!(effect, StatusEffect::InstantHealth) != undead;
+                if heals {
+                    #[allow(clippy::cast_precision_loss)]
+                    self.heal(4i32.wrapping_shl(u32::from(instance.amplifier)).max(0) as f32);
+                } else {
+                    #[allow(clippy::cast_precision_loss)]
+                    let amount = 6i32.wrapping_shl(u32::from(instance.amplifier)).max(0) as f32;
+                    // Instant damage ignores the time the entity can't be hurt after being hurt
+                    let source = DamageSource::new(DamageType::Magic);
+                    self.broadcast_damage_event(&source).await;
+                    self.actually_hurt(&source, amount, Protection::default())
+                        .await;
+                }
+            }
+            _ => {}
+        }
     }
 
     /// Kills the Entity
     ///
     /// This is similar to `kill` but Spawn Particles, Animation and plays death sound
     pub async fn kill(&self) {
-        self.set_health(0.0).await;
+        self.set_health(0.0);
 
         // Spawns death smoke particles
         self.entity
-            .world
-            .broadcast_packet_all(&CEntityStatus::new(self.entity.entity_id, 60))
+            .broadcast_to_trackers_and_self(&CEntityStatus::new(self.entity.entity_id, 60))
             .await;
         // Plays the death sound and death animation
         self.entity
-            .world
-            .broadcast_packet_all(&CEntityStatus::new(self.entity.entity_id, 3))
+            .broadcast_to_trackers_and_self(&CEntityStatus::new(self.entity.entity_id, 3))
             .await;
     }
 }
@@ -0,0 +1,331 @@
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_protocol::client::play::{CBlockUpdate, CSetBlockDestroyStage};
+use pumpkin_registry::{is_in_tag, TagCategory};
+use pumpkin_world::{
+    block::{
+        block_registry::Block,
+        experience::block_experience,
+        fluid::Fluid,
+        mining::{
+            destroy_progress, destroy_stage, efficiency_bonus, haste_multiplier,
+            mining_fatigue_multiplier, MIN_FINISHED_PROGRESS,
+        },
+    },
+    effect::StatusEffect,
+    food::EXHAUSTION_MINE,
+    item::item_registry::get_item_by_id,
+    loot::{block_drops, LootContext},
+    stats::StatType,
+};
+use rand::Rng;
+
+use crate::{net::stats::Stat, server::Server};
+
+use super::player::Player;
+
+/// Players see others breaking blocks within this distance, like vanilla's `destroyBlockProgress`
+const DESTROY_PROGRESS_RANGE: f64 = 32.0;
+/// The destroy stage which removes the cracks from a block
+const NO_DESTROY_STAGE: u8 = 255;
+/// Mining underwater without Aqua Affinity or while not standing on something is this much slower
+const HINDERED_MINING_DIVISOR: f32 = 5.0;
+
+/// A block a player in survival is breaking
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Digging {
+    pub position: WorldPosition,
+    /// Ticks the player is breaking the block
+    pub ticks: u32,
+    /// The crack other players see on the block
+    pub stage: u8,
+}
+
+/// Whether the block is in the block tag
+#[must_use]
+pub fn is_in_block_tag(tag: &str, block: &str) -> bool {
+    is_in_tag(TagCategory::Block, block, tag)
+}
+
+/// Blocks which drop nothing unless mined with the right tool, like vanilla's
+/// `requiresCorrectToolForDrops`. Nearly every block mined with a pickaxe needs one
+fn requires_correct_tool(block: &str) -> bool {
+    match block {
+        "cobweb" | "snow" | "snow_block" => true,
+        "ice" | "packed_ice" | "blue_ice" | "piston" | "sticky_piston" | "piston_head" | "rail"
+        | "powered_rail" | "detector_rail" | "activator_rail" | "conduit" | "packed_mud" => false,
+        _ => !block.ends_with("_button") && is_in_block_tag("mineable/pickaxe", block),
+    }
+}
+
+impl Player {
+    /// How fast the player mines the block, like vanilla's `Player.getDestroySpeed`
+    async fn destroy_speed(&self, block: &str) -> f32 {
+        let (mut speed, efficiency, aqua_affinity) = {
+            let mut inventory = self.inventory().lock().await;
+            let (speed, efficiency) = inventory.held_item().map_or((1.0, 0), |held| {
+                let speed = get_item_by_id(held.item_id)
+                    .and_then(|item| item.components.tool.as_ref())
+                    .map_or(1.0, |tool| {
+                        tool.mining_speed(block, |tag| is_in_block_tag(tag, block))
+                    });
+                (speed, held.enchantment_level("efficiency"))
+            });
+            let aqua_affinity = inventory.armor_mut()[0]
+                .as_ref()
+                .is_some_and(|helmet| helmet.enchantment_level("aqua_affinity") > 0);
+            (speed, efficiency, aqua_affinity)
+        };
+        speed += efficiency_bonus(speed, efficiency);
+        {
+            let effects = self.living_entity.effects.lock().await;
+            if let Some(haste) = effects.get(StatusEffect::Haste) {
+                speed *= haste_multiplier(haste.amplifier);
+            }
+            if let Some(fatigue) = effects.get(StatusEffect::MiningFatigue) {
+                speed *= mining_fatigue_multiplier(fatigue.amplifier);
+            }
+        }
+        let entity = &self.living_entity.entity;
+        if !aqua_affinity
+            && self
+                .world()
+                .level
+                .is_in_fluid(entity.eye_position(), Fluid::Water)
+        {
+            speed /= HINDERED_MINING_DIVISOR;
+        }
+        if !entity.on_ground.load(std::sync::atomic::Ordering::Relaxed) {
+            speed /= HINDERED_MINING_DIVISOR;
+        }
+        speed
+    }
+
+    /// Whether the block drops anything mined with the held item, like vanilla's
+    /// `hasCorrectToolForDrops`
+    async fn has_correct_tool_for_drops(&self, block: &str) -> bool {
+        if !requires_correct_tool(block) {
+            return true;
+        }
+        self.inventory()
+            .lock()
+            .await
+            .held_item()
+            .and_then(|held| get_item_by_id(held.item_id))
+            .and_then(|item| item.components.tool.as_ref())
+            .is_some_and(|tool| tool.is_correct_for_drops(block, |tag| is_in_block_tag(tag, block)))
+    }
+
+    /// How much of the block the player mines each tick
+    async fn block_destroy_progress(&self, block: &Block) -> f32 {
+        let speed = self.destroy_speed(&block.name).await;
+        let correct_tool = self.has_correct_tool_for_drops(&block.name).await;
+        destroy_progress(block.hardness, speed, correct_tool)
+    }
+
+    /// Starts breaking a block, like vanilla's `ServerPlayerGameMode.handleBlockBreakAction`.
+    /// Players in creative and blocks the player mines within a tick break right away
+    pub async fn start_digging(&self, server: &Server, position: WorldPosition) {
+        self.cancel_digging().await;
+        if self.gamemode.load() == GameMode::Creative {
+            self.destroy_block(server, position).await;
+            return;
+        }
+        let world = self.world();
+        let Ok((block, block_state)) = world.get_block_and_block_state(position).await else {
+            return;
+        };
+        if block_state.air {
+            return;
+        }
+        let progress = self.block_destroy_progress(block).await;
+        if progress >= 1.0 {
+            self.destroy_block(server, position).await;
+            return;
+        }
+        let stage = destroy_stage(progress);
+        self.digging.store(Some(Digging {
+            position,
+            ticks: 0,
+            stage,
+        }));
+        self.send_destroy_stage(position, stage).await;
+    }
+
+    /// Stops breaking the block, the cracks disappear
+    pub async fn cancel_digging(&self) {
+        if let Some(digging) = self.digging.take() {
+            self.send_destroy_stage(digging.position, NO_DESTROY_STAGE)
+                .await;
+        }
+    }
+
+    /// Breaks the block the player finished mining. Blocks the player couldn't have mined yet are
+    /// put back for them
+    pub async fn finish_digging(&self, server: &Server, position: WorldPosition) {
+        let Some(digging) = self.digging.take() else {
+            self.reject_block_break(position).await;
+            return;
+        };
+        self.send_destroy_stage(digging.position, NO_DESTROY_STAGE)
+            .await;
+        if digging.position != position {
+            self.reject_block_break(position).await;
+            return;
+        }
+        let world = self.world();
+        let Ok(block) = world.get_block(position).await else {
+            return;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let progress = self.block_destroy_progress(block).await * (digging.ticks + 1) as f32;
+        if progress < MIN_FINISHED_PROGRESS {
+            log::debug!(
+                "Player {} broke a block at {} too fast",
+                self.gameprofile.name,
+                position
+            );
+            self.reject_block_break(position).await;
+            return;
+        }
+        self.destroy_block(server, position).await;
+    }
+
+    /// Tells the player the block they claim to have broken is still there
+    async fn reject_block_break(&self, position: WorldPosition) {
+        if let Ok(state_id) = self.world().get_block_state_id(position).await {
+            self.client
+                .send_packet(&CBlockUpdate::new(&position, i32::from(state_id).into()))
+                .await;
+        }
+    }
+
+    /// Advances the cracks of the block the player is breaking, like vanilla's
+    /// `ServerPlayerGameMode.incrementDestroyProgress`
+    pub async fn tick_digging(&self) {
+        let Some(started) = self.digging.load() else {
+            return;
+        };
+        let mut digging = started;
+        digging.ticks += 1;
+        let world = self.world();
+        let block = match world.get_block_and_block_state(digging.position).await {
+            Ok((block, state)) if !state.air => block,
+            _ => {
+                self.cancel_digging().await;
+                return;
+            }
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let progress = self.block_destroy_progress(block).await * (digging.ticks + 1) as f32;
+        let stage = destroy_stage(progress);
+        if stage != digging.stage {
+            digging.stage = stage;
+            self.send_destroy_stage(digging.position, stage).await;
+        }
+        // The player may have stopped digging meanwhile
+        let _ = self.digging.compare_exchange(Some(started), Some(digging));
+    }
+
+    /// Shows the cracks to the other players nearby
+    async fn send_destroy_stage(&self, position: WorldPosition, stage: u8) {
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        let packet = CSetBlockDestroyStage::new(self.entity_id().into(), position, stage);
+        for player in self.world().current_players.lock().await.values() {
+            if player.entity_id() == self.entity_id() {
+                continue;
+            }
+            let offset = player.living_entity.entity.pos.load().sub(&center);
+            if offset.length_squared() < DESTROY_PROGRESS_RANGE * DESTROY_PROGRESS_RANGE {
+                player.client.send_packet(&packet).await;
+            }
+        }
+    }
+
+    /// Breaks the block like the player mined it. Outside of creative it drops if the held tool
+    /// harvests it and the tool loses durability
+    pub async fn destroy_block(&self, server: &Server, position: WorldPosition) {
+        let world = self.world();
+        let block = world.get_block(position).await;
+        if let Ok(block) = block {
+            server
+                .block_manager
+                .on_player_will_destroy(block, self, position, server)
+                .await;
+        }
+
+        let block_entity = world.break_block(position, Some(self)).await;
+
+        if let Ok(block) = block {
+            if self.gamemode.load() != GameMode::Creative {
+                if self.has_correct_tool_for_drops(&block.name).await {
+                    let tool = self.inventory().lock().await.held_item().cloned();
+                    let context = LootContext {
+                        tool: tool.as_ref(),
+                        block_entity: block_entity.as_ref(),
+                        ..Default::default()
+                    };
+                    let drops = block_drops(
+                        &block.name,
+                        block.item_id,
+                        &context,
+                        &mut rand::thread_rng(),
+                    );
+                    for drop in drops {
+                        world.pop_resource(server, position, drop).await;
+                    }
+                    self.drop_block_experience(server, &block.name, position)
+                        .await;
+                }
+                if block.hardness > 0.0 {
+                    self.damage_mining_tool().await;
+                }
+                if let Some(stat) = Stat::new(StatType::Mined, &block.name) {
+                    self.award_stat(stat, 1).await;
+                }
+            }
+            self.add_exhaustion(EXHAUSTION_MINE).await;
+            server
+                .block_manager
+                .on_broken(block, self, position, server, block_entity.as_ref())
+                .await;
+        }
+        world.update_neighbors(position, server).await;
+    }
+
+    /// Ores and sculk mined without Silk Touch drop experience
+    async fn drop_block_experience(&self, server: &Server, name: &str, position: WorldPosition) {
+        let Some(points) = block_experience(name) else {
+            return;
+        };
+        let silk_touch = self
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .is_some_and(|held| held.enchantment_level("silk_touch") > 0);
+        if !silk_touch {
+            let points = rand::thread_rng().gen_range(points);
+            self.world().pop_experience(server, position, points).await;
+        }
+    }
+
+    /// Tools lose durability for each block they mine, like vanilla's `Item.mineBlock`
+    async fn damage_mining_tool(&self) {
+        let damage_per_block = self
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .and_then(|held| get_item_by_id(held.item_id))
+            .and_then(|item| item.components.tool.as_ref())
+            .map_or(0, |tool| tool.damage_per_block);
+        self.damage_hand_item(false, damage_per_block).await;
+    }
+}
@@ -0,0 +1,970 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        vector3::Vector3,
+    },
+    text::TextComponent,
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_macros::sound;
+use pumpkin_protocol::{
+    client::play::{
+        Animation, CEntityAnimation, CEntityStatus, CEntityVelocity, CHurtAnimation, CSetEquipment,
+        CSpawnEntity,
+    },
+    codec::{slot::Slot, var_int::VarInt},
+    SoundCategory,
+};
+use pumpkin_world::{
+    damage::{armor_durability_damage, DamageType, Protection},
+    difficulty::{mob_armor, scale_damage, zombie_weapon, LocalDifficulty},
+    item::{
+        item_registry::{get_item, get_item_name_by_id, EquipmentSlot},
+        ItemStack,
+    },
+    loot::{equipment_drop_chance, wear_dropped_equipment},
+    spawning::{should_despawn, MobCategory, NO_DESPAWN_DISTANCE},
+};
+use rand::{thread_rng, Rng};
+use tokio::sync::Mutex;
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{
+    ai::{
+        self, control::MobControls, goal::GoalSelector, navigation::MobNavigation,
+        passive::is_food, Surroundings,
+    },
+    damage::{DamageEntity, DamageSource},
+    data::{DataField, LIVING_FLAGS},
+    living::LivingEntity,
+    player::Player,
+    projectile::{Pickup, ProjectileEntity, ProjectileKind},
+    tracker::TrackedEntity,
+    Entity,
+};
+
+/// A mob, mobs which have no goals just stand around
+pub struct MobEntity {
+    /// The underlying living entity
+    pub living_entity: LivingEntity,
+    pub uuid: uuid::Uuid,
+    /// Whether lightning charged the mob, only creepers can be charged
+    powered: AtomicBool,
+    /// Indexed like the slots of the equipment packet
+    pub equipment: Mutex<[Option<ItemStack>; 6]>,
+    /// Ticks the mob still drops experience when it dies, set when a player hits it
+    player_hurt_time: AtomicU32,
+    /// Ticks since the mob died, it is removed once the death animation is over
+    death_time: AtomicU32,
+    goal_selector: Mutex<GoalSelector>,
+    /// The goals picking what the mob attacks, they run before the other goals
+    target_selector: Mutex<GoalSelector>,
+    pub controls: MobControls,
+    pub navigation: MobNavigation,
+    /// The player the mob attacks
+    target: AtomicCell<Option<EntityId>>,
+    /// Whether the mob attacks, zombies raise their arms then
+    aggressive: AtomicBool,
+    /// Whether the mob draws its bow
+    using_item: AtomicBool,
+    /// Ticks until babies grow up when negative, ticks until adults can breed again when positive
+    age: AtomicI32,
+    /// Ticks the animal still looks for a partner to breed with
+    in_love: AtomicU32,
+    /// The entity which hurt the mob last
+    last_attacker: AtomicCell<Option<EntityId>>,
+    /// Ticks the mob still remembers being hurt
+    last_hurt_by_time: AtomicU32,
+    /// Ticks since the mob was last hurt or near a player, idle mobs may despawn
+    no_action_time: AtomicU32,
+    /// Mobs named with a name tag never despawn
+    named: AtomicBool,
+}
+
+/// Whether a creeper is charged
+const POWERED: DataField<bool> = DataField::new(17);
+/// Ticks mobs drop experience after a player hit them, like vanilla's `lastHurtByPlayerTime`
+const PLAYER_HURT_TIME: u32 = 100;
+/// Ticks the death animation of mobs lasts
+const DEATH_TIME: u32 = 20;
+/// Whether an animal is a baby
+const BABY: DataField<bool> = DataField::new(16);
+/// Ticks mobs remember being hurt, like vanilla's `lastHurtByMobTimestamp`
+const LAST_HURT_BY_TIME: u32 = 100;
+/// Ticks animals fed by players look for a partner
+const LOVE_TIME: u32 = 600;
+/// The mob flags, whether the mob is aggressive
+const MOB_FLAGS: DataField<i8> = DataField::new(15);
+const AGGRESSIVE_FLAG: u8 = 2;
+/// Ticks undead mobs burn for after the sun set them on fire
+const SUN_FIRE_TICKS: i32 = 160;
+/// The slots of the worn armor in the equipment packet, from the boots to the helmet
+const ARMOR_SLOTS: [EquipmentSlot; 4] = [
+    EquipmentSlot::Feet,
+    EquipmentSlot::Legs,
+    EquipmentSlot::Chest,
+    EquipmentSlot::Head,
+];
+
+impl MobEntity {
+    pub fn new(
+        entity_id: EntityId,
+        world: Arc<World>,
+        entity_type: EntityType,
+        position: Vector3<f64>,
+        yaw: f32,
+    ) -> Self {
+        let (width, height) = mob_size(&entity_type);
+        let bounding_box_size = BoundingBoxSize { width, height };
+        let goals = ai::goals(&entity_type);
+        let target_goals = ai::target_goals(&entity_type);
+        let entity = Entity::new(
+            entity_id,
+            world,
+            entity_type,
+            (height * 0.85) as f32,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        entity.set_pos(position);
+        entity.set_rotation(yaw, 0.0);
+        entity.head_yaw.store(yaw);
+        Self {
+            living_entity: LivingEntity::new(entity),
+            uuid: uuid::Uuid::new_v4(),
+            powered: AtomicBool::new(false),
+            equipment: Mutex::new([const { None }; 6]),
+            player_hurt_time: AtomicU32::new(0),
+            death_time: AtomicU32::new(0),
+            goal_selector: Mutex::new(goals),
+            target_selector: Mutex::new(target_goals),
+            controls: MobControls::default(),
+            navigation: MobNavigation::default(),
+            target: AtomicCell::new(None),
+            aggressive: AtomicBool::new(false),
+            using_item: AtomicBool::new(false),
+            age: AtomicI32::new(0),
+            in_love: AtomicU32::new(0),
+            last_attacker: AtomicCell::new(None),
+            last_hurt_by_time: AtomicU32::new(0),
+            no_action_time: AtomicU32::new(0),
+            named: AtomicBool::new(false),
+        }
+    }
+
+    /// A baby which grows up once its age counts up to zero
+    pub fn new_baby(
+        entity_id: EntityId,
+        world: Arc<World>,
+        entity_type: EntityType,
+        position: Vector3<f64>,
+        yaw: f32,
+        age: i32,
+    ) -> Self {
+        let mob = Self::new(entity_id, world, entity_type, position, yaw);
+        mob.set_age(age);
+        mob
+    }
+
+    pub const fn entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let entity = self.entity();
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        CSpawnEntity::new(
+            entity.entity_id.into(),
+            self.uuid,
+            (entity.entity_type.clone() as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            entity.pitch.load(),
+            entity.yaw.load(),
+            entity.head_yaw.load(),
+            0.into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// `None` if the mob has no equipment
+    async fn equipment_packet(&self) -> Option<CSetEquipment> {
+        let equipment = self.equipment.lock().await;
+        if equipment.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(CSetEquipment::new(
+            self.entity().entity_id.into(),
+            equipment
+                .iter()
+                .enumerate()
+                .map(|(slot, item)| (slot as u8, Slot::from(item.as_ref())))
+                .collect(),
+        ))
+    }
+
+    /// Makes the mob visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity().world().start_tracking(self).await;
+    }
+
+    /// Gives zombies and skeletons the armor and weapons they spawn with, like vanilla's
+    /// `Mob.populateDefaultEquipmentSlots`. Skeletons always carry a bow
+    pub async fn populate_equipment(&self, local: &LocalDifficulty) {
+        let entity_type = &self.entity().entity_type;
+        let skeleton = matches!(
+            entity_type,
+            EntityType::Skeleton | EntityType::Stray | EntityType::Bogged
+        );
+        let zombie = matches!(
+            entity_type,
+            EntityType::Zombie | EntityType::Husk | EntityType::ZombieVillager
+        );
+        if !skeleton && !zombie {
+            return;
+        }
+        let (weapon, armor) = {
+            let mut rng = thread_rng();
+            let weapon = if skeleton {
+                Some("bow")
+            } else {
+                zombie_weapon(local.base, &mut rng)
+            };
+            (weapon, mob_armor(local, &mut rng))
+        };
+        let item = |name: &str| get_item(name).map(|item| ItemStack::new(1, item.id));
+        let mut equipment = self.equipment.lock().await;
+        equipment[0] = weapon.and_then(item);
+        for (slot, piece) in armor.into_iter().enumerate() {
+            equipment[slot + 2] = piece.and_then(item);
+        }
+    }
+
+    /// Puts the item into the equipment slot and shows it to the players tracking the mob
+    pub async fn set_equipment(&self, slot: usize, item: Option<ItemStack>) {
+        let packet = CSetEquipment::new(
+            self.entity().entity_id.into(),
+            vec![(slot as u8, Slot::from(item.as_ref()))],
+        );
+        self.equipment.lock().await[slot] = item;
+        self.entity().broadcast_to_trackers(&packet).await;
+    }
+
+    /// Lets the mob drop experience if it dies during the next few ticks
+    pub fn hurt_by_player(&self) {
+        self.player_hurt_time
+            .store(PLAYER_HURT_TIME, Ordering::Relaxed);
+    }
+
+    /// Remembers being hurt for a few ticks, animals panic meanwhile
+    pub fn hurt_by(&self, attacker: Option<EntityId>) {
+        self.last_attacker.store(attacker);
+        self.last_hurt_by_time
+            .store(LAST_HURT_BY_TIME, Ordering::Relaxed);
+        self.no_action_time.store(0, Ordering::Relaxed);
+    }
+
+    /// Hurts the mob through the armor it wears, which loses durability unless the damage goes
+    /// through it. Returns whether the mob was hurt
+    pub async fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        let Some(amount) = self.living_entity.check_hurt(source, amount).await else {
+            return false;
+        };
+        let damage_type = source.damage_type;
+        let wears_down = !damage_type.bypasses_armor() && amount > 0.0;
+        let (protection, worn, broken) = {
+            let mut equipment = self.equipment.lock().await;
+            let armor = &mut equipment[2..];
+            let protection = Protection::of_armor(damage_type, armor.iter().flatten());
+            let worn = armor.iter().any(Option::is_some);
+            let mut broken = Vec::new();
+            if wears_down {
+                let durability_damage = armor_durability_damage(amount);
+                for (piece, slot) in armor.iter_mut().zip(&ARMOR_SLOTS) {
+                    if piece
+                        .as_mut()
+                        .is_some_and(|piece| piece.damage_item(durability_damage))
+                    {
+                        *piece = None;
+                        broken.push(*slot);
+                    }
+                }
+            }
+            (protection, worn, broken)
+        };
+        if wears_down && worn {
+            if let Some(equipment) = self.equipment_packet().await {
+                self.entity().broadcast_to_trackers(&equipment).await;
+            }
+        }
+        for slot in broken {
+            self.entity()
+                .broadcast_to_trackers(&CEntityStatus::new(
+                    self.entity().entity_id,
+                    slot.break_status(),
+                ))
+                .await;
+        }
+        self.living_entity
+            .actually_hurt(source, amount, protection)
+            .await;
+        true
+    }
+
+    /// Whether the mob despawns because no player is close, see `spawning::should_despawn`.
+    /// Mobs close to a player stop idling, named mobs and mobs which don't spawn naturally never
+    /// despawn
+    pub fn check_despawn(&self, players: &[Vector3<f64>]) -> bool {
+        if self.named.load(Ordering::Relaxed) {
+            return false;
+        }
+        let entity = self.entity();
+        let Some(category) = mob_category(&entity.entity_type) else {
+            return false;
+        };
+        let position = entity.pos.load();
+        let Some(distance) = players
+            .iter()
+            .map(|player| player.sub(&position).length())
+            .min_by(f64::total_cmp)
+        else {
+            return false;
+        };
+        if distance < NO_DESPAWN_DISTANCE {
+            self.no_action_time.store(0, Ordering::Relaxed);
+            return false;
+        }
+        should_despawn(
+            category,
+            distance,
+            self.no_action_time.load(Ordering::Relaxed),
+            &mut thread_rng(),
+        )
+    }
+
+    pub fn was_hurt_recently(&self) -> bool {
+        self.last_hurt_by_time.load(Ordering::Relaxed) > 0
+    }
+
+    pub fn age(&self) -> i32 {
+        self.age.load(Ordering::Relaxed)
+    }
+
+    /// Babies are half as large as adults
+    pub fn set_age(&self, age: i32) {
+        let was_baby = self.age.swap(age, Ordering::Relaxed) < 0;
+        if was_baby == (age < 0) {
+            return;
+        }
+        let entity = self.entity();
+        entity.data.set(&BABY, age < 0);
+        let (width, height) = mob_size(&entity.entity_type);
+        let scale = if age < 0 { 0.5 } else { 1.0 };
+        let size = BoundingBoxSize {
+            width: width * scale,
+            height: height * scale,
+        };
+        let pos = entity.pos.load();
+        entity.bounding_box_size.store(size);
+        entity
+            .bounding_box
+            .store(BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &size));
+    }
+
+    pub fn is_in_love(&self) -> bool {
+        self.in_love.load(Ordering::Relaxed) > 0
+    }
+
+    /// Lets the animal look for a partner to breed with
+    pub async fn set_in_love(&self) {
+        self.in_love.store(LOVE_TIME, Ordering::Relaxed);
+        self.show_hearts().await;
+    }
+
+    pub fn reset_love(&self) {
+        self.in_love.store(0, Ordering::Relaxed);
+    }
+
+    /// Shows hearts around the mob
+    pub async fn show_hearts(&self) {
+        self.entity()
+            .broadcast_to_trackers(&CEntityStatus::new(self.entity().entity_id, 18))
+            .await;
+    }
+
+    /// Babies grow up and adults can breed again over time, like vanilla's `AgeableMob.aiStep`.
+    /// Animals only stay in love while they can breed
+    fn tick_age(&self) {
+        let age = self.age();
+        match age.cmp(&0) {
+            std::cmp::Ordering::Less => self.set_age(age + 1),
+            std::cmp::Ordering::Greater => self.set_age(age - 1),
+            std::cmp::Ordering::Equal => {}
+        }
+        if age != 0 {
+            self.reset_love();
+        }
+        // Fails once the animal is not in love anymore, which leaves it at zero
+        let _ = self
+            .in_love
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |time| {
+                time.checked_sub(1)
+            });
+    }
+
+    /// Lets the mob think and steer itself, mobs in chunks which are not loaded wait
+    pub async fn tick_ai(&self, surroundings: &Surroundings<'_>) {
+        let entity = self.entity();
+        if !entity.world().level.is_loaded(entity.pos.load()) {
+            return;
+        }
+        self.no_action_time.fetch_add(1, Ordering::Relaxed);
+        self.tick_age();
+        self.tick_sun_burn(surroundings).await;
+        if self
+            .living_entity
+            .tick_fire(self.controls.water_height() > 0.0)
+        {
+            self.hurt(&DamageSource::new(DamageType::OnFire), 1.0).await;
+        }
+        self.living_entity.tick_effects().await;
+        self.target_selector
+            .lock()
+            .await
+            .tick(self, surroundings)
+            .await;
+        self.goal_selector
+            .lock()
+            .await
+            .tick(self, surroundings)
+            .await;
+        self.navigation.tick(entity, &self.controls).await;
+        // Speed and slowness change how fast the mob walks
+        let speed_multiplier = self
+            .living_entity
+            .effects
+            .lock()
+            .await
+            .movement_speed_multiplier();
+        self.controls.tick(
+            entity,
+            movement_speed(&entity.entity_type) * speed_multiplier,
+        );
+    }
+
+    /// The player the mob attacks, `None` once the player left or died
+    pub fn target<'a>(&self, surroundings: &Surroundings<'a>) -> Option<&'a Arc<Player>> {
+        let target = self.target.load()?;
+        surroundings
+            .players
+            .iter()
+            .find(|player| player.entity_id() == target && player.living_entity.health.load() > 0.0)
+    }
+
+    pub fn set_target(&self, target: Option<EntityId>) {
+        self.target.store(target);
+    }
+
+    /// The entity which hurt the mob last, if it was hurt recently
+    pub fn last_attacker(&self) -> Option<EntityId> {
+        self.last_attacker
+            .load()
+            .filter(|_| self.was_hurt_recently())
+    }
+
+    /// Lets zombies raise their arms while they attack
+    pub fn set_aggressive(&self, aggressive: bool) {
+        self.aggressive.store(aggressive, Ordering::Relaxed);
+        self.entity()
+            .data
+            .set_bit(&MOB_FLAGS, AGGRESSIVE_FLAG, aggressive);
+    }
+
+    /// Draws or lowers the bow of the mob
+    pub fn set_using_item(&self, using_item: bool) {
+        self.using_item.store(using_item, Ordering::Relaxed);
+        self.entity().data.set(&LIVING_FLAGS, i8::from(using_item));
+    }
+
+    pub fn is_using_item(&self) -> bool {
+        self.using_item.load(Ordering::Relaxed)
+    }
+
+    pub async fn holds_bow(&self) -> bool {
+        self.equipment.lock().await[0]
+            .as_ref()
+            .and_then(|item| get_item_name_by_id(item.item_id))
+            == Some("bow")
+    }
+
+    /// Undead mobs catch fire in the sun, like vanilla's `Mob.isSunBurnTick`. Helmets and water
+    /// protect them
+    async fn tick_sun_burn(&self, surroundings: &Surroundings<'_>) {
+        let entity = self.entity();
+        if !surroundings.daylight
+            || !burns_in_daylight(&entity.entity_type)
+            || self.controls.water_height() > 0.0
+            || rand::random::<f32>() * 30.0 >= 1.2
+        {
+            return;
+        }
+        if !ai::sees_sky(entity) || self.equipment.lock().await[5].is_some() {
+            return;
+        }
+        self.living_entity.set_on_fire_for(SUN_FIRE_TICKS);
+    }
+
+    /// Swings at the player and hurts them, like vanilla's `Mob.doHurtTarget`. Returns whether
+    /// the player was hurt
+    pub async fn attack(&self, player: &Player) -> bool {
+        let entity = self.entity();
+        let world = entity.world();
+        entity
+            .broadcast_to_trackers(&CEntityAnimation::new(
+                entity.entity_id.into(),
+                Animation::SwingMainArm as u8,
+            ))
+            .await;
+        let damage = scale_damage(world.difficulty.load(), attack_damage(&entity.entity_type));
+        let source = DamageSource::by(DamageType::MobAttack, DamageEntity::entity(entity));
+        if !player.hurt(&source, damage).await {
+            return false;
+        }
+        let victim = &player.living_entity.entity;
+        let entity_id = VarInt(victim.entity_id);
+        victim
+            .broadcast_to_trackers_and_self(&CHurtAnimation::new(&entity_id, entity.yaw.load()))
+            .await;
+        // Players move themselves, they only get their new velocity
+        let saved_velocity = victim.velocity.load();
+        let pos = entity.pos.load();
+        let victim_pos = victim.pos.load();
+        victim.knockback(0.4, pos.x - victim_pos.x, pos.z - victim_pos.z);
+        let knocked = victim.velocity.load();
+        victim.velocity.store(saved_velocity);
+        player
+            .client
+            .send_packet(&CEntityVelocity::new(
+                &entity_id, knocked.x, knocked.y, knocked.z,
+            ))
+            .await;
+        true
+    }
+
+    /// Shoots an arrow at the target, like vanilla's `AbstractSkeleton.performRangedAttack`. More
+    /// drawn bows shoot arrows which hurt more, mobs aim better on harder difficulties
+    pub async fn shoot_arrow(&self, server: &Server, target: &Entity, power: f64) {
+        let entity = self.entity();
+        let world = entity.world();
+        let difficulty = f64::from(world.difficulty.load() as u8);
+        let arrow = ProjectileEntity::new(
+            server.new_entity_id(),
+            world.clone(),
+            ProjectileKind::Arrow,
+            Some(entity),
+            None,
+            Pickup::Disallowed,
+        );
+        let spread = {
+            let mut rng = thread_rng();
+            rng.gen::<f64>() - rng.gen::<f64>()
+        };
+        arrow.set_base_damage(power.mul_add(2.0, difficulty.mul_add(0.11, 0.574_25 * spread)));
+        let from = arrow.entity.pos.load();
+        let to = target.pos.load();
+        let (dx, dz) = (to.x - from.x, to.z - from.z);
+        // Mobs aim at the lower third of the target and a little higher the further it is away
+        let dy = to.y + target.bounding_box_size.load().height / 3.0 - from.y;
+        arrow.shoot(
+            Vector3::new(dx, dx.hypot(dz).mul_add(0.2, dy), dz),
+            1.6,
+            difficulty.mul_add(-4.0, 14.0),
+        );
+        world.add_projectile(Arc::new(arrow)).await;
+        world
+            .play_sound(
+                sound!("entity.skeleton.shoot"),
+                SoundCategory::Hostile,
+                &entity.pos.load(),
+            )
+            .await;
+    }
+
+    /// Names the mob after the renamed name tag in the hand of the player, like vanilla's
+    /// `NameTagItem.interactLivingEntity`. Returns whether the name tag was used
+    pub async fn use_name_tag(&self, player: &Player, offhand: bool) -> bool {
+        let name = {
+            let mut inventory = player.inventory().lock().await;
+            let held = inventory.hand_item_mut(offhand);
+            let Some(name) = held
+                .as_ref()
+                .filter(|item| get_item_name_by_id(item.item_id) == Some("name_tag"))
+                .and_then(|item| item.components.custom_name.clone())
+            else {
+                return false;
+            };
+            if player.gamemode.load() != GameMode::Creative {
+                if let Some(item) = held.as_mut() {
+                    item.item_count -= 1;
+                }
+                held.take_if(|item| item.item_count == 0);
+            }
+            name
+        };
+        player.set_container_content(None).await;
+        self.set_name(TextComponent::text_string(name), false);
+        true
+    }
+
+    /// Names the mob, named mobs never despawn
+    pub fn set_name(&self, name: TextComponent<'static>, always_visible: bool) {
+        self.entity().set_custom_name(Some(name), always_visible);
+        self.named.store(true, Ordering::Relaxed);
+    }
+
+    /// Feeds the item in the hand of the player to the animal, like vanilla's `Animal.mobInteract`.
+    /// Babies grow up faster and adults which can breed fall in love. Returns whether the animal
+    /// ate the item
+    pub async fn feed(&self, player: &Player, offhand: bool) -> bool {
+        let entity_type = &self.entity().entity_type;
+        let age = self.age();
+        if age > 0 || (age == 0 && self.is_in_love()) {
+            return false;
+        }
+        {
+            let mut inventory = player.inventory().lock().await;
+            let held = inventory.hand_item_mut(offhand);
+            if !held
+                .as_ref()
+                .and_then(|item| get_item_name_by_id(item.item_id))
+                .is_some_and(|item| is_food(entity_type, item))
+            {
+                return false;
+            }
+            if player.gamemode.load() != GameMode::Creative {
+                if let Some(item) = held.as_mut() {
+                    item.item_count -= 1;
+                }
+                held.take_if(|item| item.item_count == 0);
+            }
+        }
+        player.set_container_content(None).await;
+        if age < 0 {
+            // Babies grow up a tenth of the time they have left faster
+            self.set_age(age + (-age / 20 / 10) * 20);
+        } else {
+            self.set_in_love().await;
+        }
+        true
+    }
+
+    /// Counts down the time the mob is invulnerable after being hurt and drops experience after
+    /// a player hit it, and up the death animation of dead mobs. Returns true once the death
+    /// animation is over
+    pub fn tick_timers(&self) -> bool {
+        self.living_entity.tick();
+        // Fails once the times are over, which leaves them at zero
+        for timer in [&self.player_hurt_time, &self.last_hurt_by_time] {
+            let _ = timer.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |time| {
+                time.checked_sub(1)
+            });
+        }
+        if self.living_entity.health.load() > 0.0 {
+            return false;
+        }
+        self.death_time.fetch_add(1, Ordering::Relaxed) + 1 >= DEATH_TIME
+    }
+
+    /// Whether the mob died during the last tick, it drops its loot then
+    pub fn just_died(&self) -> bool {
+        self.death_time.load(Ordering::Relaxed) == 1
+    }
+
+    /// Whether a player hit the mob shortly before it died, only they get some of the drops
+    pub fn killed_by_player(&self) -> bool {
+        self.player_hurt_time.load(Ordering::Relaxed) > 0
+    }
+
+    /// Takes the pieces of equipment the dead mob drops, like vanilla's `dropCustomDeathLoot`.
+    /// They are worn down and Curse of Vanishing makes them disappear
+    pub async fn take_dropped_equipment(&self, looting: i32) -> Vec<ItemStack> {
+        let mut equipment = self.equipment.lock().await;
+        let mut rng = thread_rng();
+        let mut dropped = Vec::new();
+        for slot in equipment.iter_mut() {
+            let drops = slot.as_ref().is_some_and(|piece| {
+                piece.enchantment_level("vanishing_curse") == 0
+                    && rng.gen::<f32>() < equipment_drop_chance(looting)
+            });
+            if let Some(mut piece) = slot.take_if(|_| drops) {
+                wear_dropped_equipment(&mut piece, &mut rng);
+                dropped.push(piece);
+            }
+        }
+        dropped
+    }
+
+    /// The experience points the dead mob drops, like vanilla's `Mob.getBaseExperienceReward`.
+    /// Only mobs killed by players drop experience, a few more points for each piece of equipment
+    pub async fn experience_reward(&self) -> i32 {
+        if !self.killed_by_player() {
+            return 0;
+        }
+        let equipment = self.equipment.lock().await.iter().flatten().count();
+        let mut rng = thread_rng();
+        let base = base_experience_reward(&self.entity().entity_type, &mut rng);
+        if base == 0 {
+            return 0;
+        }
+        base + (0..equipment).map(|_| rng.gen_range(1..=3)).sum::<i32>()
+    }
+
+    pub fn is_powered(&self) -> bool {
+        self.powered.load(Ordering::Relaxed)
+    }
+
+    /// Charges creepers struck by lightning, like vanilla's `Creeper.thunderHit`
+    pub fn thunder_hit(&self) {
+        if !matches!(self.entity().entity_type, EntityType::Creeper) || self.is_powered() {
+            return;
+        }
+        self.powered.store(true, Ordering::Relaxed);
+        self.entity().data.set(&POWERED, true);
+    }
+}
+
+/// The width and height of mobs, most mobs are as large as players
+#[must_use]
+pub const fn mob_size(entity_type: &EntityType) -> (f64, f64) {
+    match entity_type {
+        EntityType::Spider => (1.4, 0.9),
+        EntityType::CaveSpider => (0.7, 0.5),
+        EntityType::Silverfish | EntityType::Endermite => (0.4, 0.3),
+        EntityType::Skeleton | EntityType::Stray => (0.6, 1.99),
+        EntityType::Zombie
+        | EntityType::Husk
+        | EntityType::Drowned
+        | EntityType::ZombieVillager
+        | EntityType::Witch
+        | EntityType::Piglin
+        | EntityType::ZombifiedPiglin => (0.6, 1.95),
+        EntityType::Creeper => (0.6, 1.7),
+        EntityType::Enderman => (0.6, 2.9),
+        EntityType::MagmaCube | EntityType::Slime => (0.52, 0.52),
+        EntityType::Pig => (0.9, 0.9),
+        EntityType::Chicken => (0.4, 0.7),
+        EntityType::Cow | EntityType::Mooshroom => (0.9, 1.4),
+        EntityType::Sheep => (0.9, 1.3),
+        EntityType::Ghast => (4.0, 4.0),
+        EntityType::Hoglin => (1.396_484_4, 1.4),
+        EntityType::Strider => (0.9, 1.7),
+        EntityType::Horse => (1.396_484_4, 1.6),
+        EntityType::Donkey => (1.396_484_4, 1.5),
+        EntityType::Wolf => (0.6, 0.85),
+        EntityType::Fox => (0.6, 0.7),
+        EntityType::Rabbit => (0.4, 0.5),
+        EntityType::Camel => (1.7, 2.375),
+        EntityType::Armadillo => (0.7, 0.65),
+        EntityType::Turtle => (1.2, 0.4),
+        EntityType::Bat => (0.5, 0.9),
+        EntityType::Squid => (0.8, 0.8),
+        EntityType::Dolphin => (0.9, 0.6),
+        EntityType::Cod => (0.5, 0.3),
+        _ => (0.6, 1.8),
+    }
+}
+
+/// How far away mobs notice and follow players they attack, like the vanilla `follow_range`
+/// attribute
+#[must_use]
+pub const fn follow_range(entity_type: &EntityType) -> f64 {
+    match entity_type {
+        EntityType::Zombie | EntityType::Husk | EntityType::ZombieVillager => 35.0,
+        _ => 16.0,
+    }
+}
+
+/// The damage the melee attacks of the mob do before the difficulty is applied, like the
+/// vanilla `attack_damage` attribute
+const fn attack_damage(entity_type: &EntityType) -> f32 {
+    match entity_type {
+        EntityType::Zombie | EntityType::Husk | EntityType::ZombieVillager => 3.0,
+        _ => 2.0,
+    }
+}
+
+/// Whether the mob is undead and burns in daylight
+const fn burns_in_daylight(entity_type: &EntityType) -> bool {
+    matches!(
+        entity_type,
+        EntityType::Zombie
+            | EntityType::ZombieVillager
+            | EntityType::Drowned
+            | EntityType::Skeleton
+            | EntityType::Stray
+            | EntityType::Bogged
+    )
+}
+
+/// How many blocks per tick the mob walks, like the vanilla `movement_speed` attribute
+#[must_use]
+pub const fn movement_speed(entity_type: &EntityType) -> f64 {
+    match entity_type {
+        EntityType::Cow => 0.2,
+        EntityType::Sheep | EntityType::Zombie | EntityType::Husk | EntityType::ZombieVillager => {
+            0.23
+        }
+        _ => 0.25,
+    }
+}
+
+/// The experience points killing the mob drops before its equipment is counted, like the
+/// `xpReward` of vanilla's mobs. Animals drop one to three points
+fn base_experience_reward(entity_type: &EntityType, rng: &mut impl Rng) -> i32 {
+    match entity_type {
+        EntityType::Wither => 50,
+        EntityType::Ravager => 20,
+        EntityType::Blaze
+        | EntityType::Breeze
+        | EntityType::ElderGuardian
+        | EntityType::Evoker
+        | EntityType::Guardian => 10,
+        EntityType::Endermite | EntityType::Vex => 3,
+        // Vanilla drops as many points as the slime is large
+        EntityType::Slime | EntityType::MagmaCube => 1,
+        EntityType::Allay
+        | EntityType::Bat
+        | EntityType::Creaking
+        | EntityType::IronGolem
+        | EntityType::SnowGolem
+        | EntityType::Villager
+        | EntityType::WanderingTrader => 0,
+        EntityType::ZombifiedPiglin => 5,
+        entity_type if is_monster(entity_type) => 5,
+        _ => rng.gen_range(1..=3),
+    }
+}
+
+/// Which natural spawn cap the mob counts towards, like vanilla's `MobCategory` of entity types.
+/// Villagers, golems and other mobs which never spawn naturally have none
+#[must_use]
+pub const fn mob_category(entity_type: &EntityType) -> Option<MobCategory> {
+    match entity_type {
+        EntityType::Ghast
+        | EntityType::Hoglin
+        | EntityType::MagmaCube
+        | EntityType::Phantom
+        | EntityType::Slime
+        | EntityType::ZombifiedPiglin => Some(MobCategory::Monster),
+        entity_type if is_monster(entity_type) => Some(MobCategory::Monster),
+        EntityType::Bat => Some(MobCategory::Ambient),
+        EntityType::Squid | EntityType::GlowSquid | EntityType::Dolphin => {
+            Some(MobCategory::WaterCreature)
+        }
+        EntityType::Cod
+        | EntityType::Salmon
+        | EntityType::TropicalFish
+        | EntityType::Pufferfish
+        | EntityType::Tadpole => Some(MobCategory::WaterAmbient),
+        EntityType::Allay
+        | EntityType::IronGolem
+        | EntityType::SnowGolem
+        | EntityType::Villager
+        | EntityType::WanderingTrader => None,
+        _ => Some(MobCategory::Creature),
+    }
+}
+
+/// Whether the mob is a monster, players can't sleep while monsters are near their bed.
+/// Zombified piglins only keep players awake when they are angry, which mobs without AI never are
+#[must_use]
+pub const fn is_monster(entity_type: &EntityType) -> bool {
+    matches!(
+        entity_type,
+        EntityType::Blaze
+            | EntityType::Bogged
+            | EntityType::Breeze
+            | EntityType::CaveSpider
+            | EntityType::Creaking
+            | EntityType::Creeper
+            | EntityType::Drowned
+            | EntityType::ElderGuardian
+            | EntityType::Enderman
+            | EntityType::Endermite
+            | EntityType::Evoker
+            | EntityType::Giant
+            | EntityType::Guardian
+            | EntityType::Husk
+            | EntityType::Illusioner
+            | EntityType::Piglin
+            | EntityType::PiglinBrute
+            | EntityType::Pillager
+            | EntityType::Ravager
+            | EntityType::Silverfish
+            | EntityType::Skeleton
+            | EntityType::Spider
+            | EntityType::Stray
+            | EntityType::Vex
+            | EntityType::Vindicator
+            | EntityType::Warden
+            | EntityType::Witch
+            | EntityType::Wither
+            | EntityType::WitherSkeleton
+            | EntityType::Zoglin
+            | EntityType::Zombie
+            | EntityType::ZombieVillager
+    )
+}
+
+#[async_trait]
+impl TrackedEntity for MobEntity {
+    fn tracked_entity(&self) -> &Entity {
+        self.entity()
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity().data_packet() {
+            client.send_packet(&packet).await;
+        }
+        if let Some(equipment) = self.equipment_packet().await {
+            client.send_packet(&equipment).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_entity::entity_type::EntityType;
+
+    use super::{attack_damage, burns_in_daylight, follow_range};
+
+    #[test]
+    fn zombies_notice_players_from_further_away() {
+        assert!((follow_range(&EntityType::Zombie) - 35.0).abs() < f64::EPSILON);
+        assert!((follow_range(&EntityType::Skeleton) - 16.0).abs() < f64::EPSILON);
+        assert!(attack_damage(&EntityType::Husk) > attack_damage(&EntityType::Skeleton));
+    }
+
+    #[test]
+    fn husks_and_strays_handle_sunlight_differently() {
+        assert!(burns_in_daylight(&EntityType::Zombie));
+        assert!(burns_in_daylight(&EntityType::Stray));
+        // Husks are made for the desert
+        assert!(!burns_in_daylight(&EntityType::Husk));
+        assert!(!burns_in_daylight(&EntityType::Cow));
+    }
+}
@@ -1,24 +1,51 @@
-use std::sync::{atomic::AtomicBool, Arc};
+use std::sync::{atomic::AtomicBool, Arc, Mutex, PoisonError, RwLock};
 
 use crossbeam::atomic::AtomicCell;
 use num_derive::FromPrimitive;
-use pumpkin_core::math::{
-    boundingbox::{BoundingBox, BoundingBoxSize},
-    get_section_cord,
-    position::WorldPosition,
-    vector2::Vector2,
-    vector3::Vector3,
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        get_section_cord,
+        position::WorldPosition,
+        vector2::Vector2,
+        vector3::Vector3,
+    },
+    text::TextComponent,
 };
 use pumpkin_entity::{entity_type::EntityType, pose::EntityPose, EntityId};
 use pumpkin_protocol::{
-    client::play::{CSetEntityMetadata, CTeleportEntity, Metadata},
-    codec::var_int::VarInt,
+    client::play::{CSetEntityMetadata, CTeleportEntity},
+    ClientPacket,
 };
 
-use crate::world::World;
+use crate::{
+    entity::{
+        data::{EntityData, CUSTOM_NAME, CUSTOM_NAME_VISIBLE, POSE, SHARED_FLAGS},
+        physics::MovementSync,
+        tracker::EntityTracker,
+    },
+    world::World,
+};
 
+pub mod ai;
+pub mod any_entity;
+pub mod armor_stand;
+pub mod damage;
+pub mod data;
+pub mod experience_orb;
+pub mod falling_block;
+pub mod horse;
+pub mod item;
+pub mod item_frame;
+pub mod lightning;
 pub mod living;
+pub mod mining;
+pub mod mob;
+pub mod physics;
 pub mod player;
+pub mod projectile;
+pub mod tnt;
+pub mod tracker;
 
 /// Represents a not living Entity (e.g. Item, Egg, Snowball...)
 pub struct Entity {
@@ -26,8 +53,8 @@ pub struct Entity {
     pub entity_id: EntityId,
     /// The type of entity (e.g., player, zombie, item)
     pub entity_type: EntityType,
-    /// The world in which the entity exists.
-    pub world: Arc<World>,
+    /// The world in which the entity exists, players change it when they travel between worlds
+    world: RwLock<Arc<World>>,
     /// The entity's current position in the world
     pub pos: AtomicCell<Vector3<f64>>,
     /// The entity's position rounded to the nearest block coordinates
@@ -42,6 +69,15 @@ pub struct Entity {
     pub fall_flying: AtomicBool,
     /// The entity's current velocity vector, aka Knockback
     pub velocity: AtomicCell<Vector3<f64>>,
+    /// Set when something else than the entity's own movement changed its velocity, players are
+    /// told about it with the next movement update
+    pub velocity_dirty: AtomicBool,
+    /// What players last got of the entity's movement
+    pub movement_sync: MovementSync,
+    /// The metadata players see, changes are sent at the end of every tick
+    pub data: EntityData,
+    /// The players who see the entity
+    pub tracker: EntityTracker,
     /// Indicates whether the entity is on the ground (may not always be accurate).
     pub on_ground: AtomicBool,
     /// The entity's yaw rotation (horizontal rotation) ← →
@@ -58,6 +94,12 @@ pub struct Entity {
     pub bounding_box: AtomicCell<BoundingBox>,
     ///The size (width and height) of the bounding box
     pub bounding_box_size: AtomicCell<BoundingBoxSize>,
+    /// Ticks until portals take the entity again, they only run out outside of portals
+    pub portal_cooldown: AtomicCell<i32>,
+    /// Ticks the entity has been standing in a nether portal
+    pub portal_time: AtomicCell<i32>,
+    /// Scoreboard tags, which commands select entities by, like vanilla's `Entity.tags`
+    pub tags: Mutex<Vec<String>>,
 }
 
 impl Entity {
@@ -77,7 +119,7 @@ impl Entity {
             block_pos: AtomicCell::new(WorldPosition(Vector3::new(0, 0, 0))),
             chunk_pos: AtomicCell::new(Vector2::new(0, 0)),
             sneaking: AtomicBool::new(false),
-            world,
+            world: RwLock::new(world),
             // TODO: Load this from previous instance
             sprinting: AtomicBool::new(false),
             fall_flying: AtomicBool::new(false),
@@ -85,10 +127,17 @@ impl Entity {
             head_yaw: AtomicCell::new(0.0),
             pitch: AtomicCell::new(0.0),
             velocity: AtomicCell::new(Vector3::new(0.0, 0.0, 0.0)),
+            velocity_dirty: AtomicBool::new(false),
+            movement_sync: MovementSync::default(),
+            data: EntityData::default(),
+            tracker: EntityTracker::default(),
             standing_eye_height,
             pose: AtomicCell::new(EntityPose::Standing),
             bounding_box,
             bounding_box_size,
+            portal_cooldown: AtomicCell::new(0),
+            portal_time: AtomicCell::new(0),
+            tags: Mutex::new(Vec::new()),
         }
     }
 
@@ -100,9 +149,9 @@ impl Entity {
         if pos != new_position {
             self.pos.store(new_position);
             self.bounding_box.store(BoundingBox::new_from_pos(
-                pos.x,
-                pos.y,
-                pos.z,
+                new_position.x,
+                new_position.y,
+                new_position.z,
                 &self.bounding_box_size.load(),
             ));
 
@@ -132,23 +181,56 @@ impl Entity {
         }
     }
 
-    pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
+    /// The world in which the entity exists
+    pub fn world(&self) -> Arc<World> {
         self.world
-            .broadcast_packet_all(&CTeleportEntity::new(
-                self.entity_id.into(),
-                position,
-                Vector3::new(0.0, 0.0, 0.0),
-                yaw,
-                pitch,
-                // TODO
-                &[],
-                self.on_ground.load(std::sync::atomic::Ordering::SeqCst),
-            ))
-            .await;
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Moves the entity into another world, the worlds have to add and remove the entity themselves
+    pub fn set_world(&self, world: Arc<World>) {
+        *self.world.write().unwrap_or_else(PoisonError::into_inner) = world;
+    }
+
+    /// Teleports the entity, positions beyond the world border are moved inside of it
+    pub async fn teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
+        let position = self.world().worldborder.lock().await.clamp(position);
+        self.broadcast_to_trackers(&CTeleportEntity::new(
+            self.entity_id.into(),
+            position,
+            Vector3::new(0.0, 0.0, 0.0),
+            yaw,
+            pitch,
+            // TODO
+            &[],
+            self.on_ground.load(std::sync::atomic::Ordering::SeqCst),
+        ))
+        .await;
         self.set_pos(position);
+        self.movement_sync.set_position(position);
         self.set_rotation(yaw, pitch);
     }
 
+    /// The scoreboard tags of the entity
+    pub fn tags(&self) -> Vec<String> {
+        self.tags
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub fn set_tags(&self, tags: Vec<String>) {
+        *self.tags.lock().unwrap_or_else(PoisonError::into_inner) = tags;
+    }
+
+    /// Where the entity looks from
+    pub fn eye_position(&self) -> Vector3<f64> {
+        let pos = self.pos.load();
+        Vector3::new(pos.x, pos.y + f64::from(self.standing_eye_height), pos.z)
+    }
+
     /// Sets the Entity yaw & pitch Rotation
     pub fn set_rotation(&self, yaw: f32, pitch: f32) {
         // TODO
@@ -158,7 +240,7 @@ impl Entity {
 
     /// Removes the Entity from their current World
     pub async fn remove(&self) {
-        self.world.remove_entity(self).await;
+        self.world().remove_entity(self).await;
     }
 
     /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
@@ -186,56 +268,90 @@ impl Entity {
         ));
     }
 
-    pub async fn set_sneaking(&self, sneaking: bool) {
+    pub fn set_sneaking(&self, sneaking: bool) {
         assert!(self.sneaking.load(std::sync::atomic::Ordering::Relaxed) != sneaking);
         self.sneaking
             .store(sneaking, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sneaking, sneaking).await;
+        self.set_flag(Flag::Sneaking, sneaking);
         if sneaking {
-            self.set_pose(EntityPose::Crouching).await;
+            self.set_pose(EntityPose::Crouching);
         } else {
-            self.set_pose(EntityPose::Standing).await;
+            self.set_pose(EntityPose::Standing);
         }
     }
 
-    pub async fn set_sprinting(&self, sprinting: bool) {
+    pub fn set_sprinting(&self, sprinting: bool) {
         assert!(self.sprinting.load(std::sync::atomic::Ordering::Relaxed) != sprinting);
         self.sprinting
             .store(sprinting, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::Sprinting, sprinting).await;
+        self.set_flag(Flag::Sprinting, sprinting);
     }
 
     pub fn check_fall_flying(&self) -> bool {
         !self.on_ground.load(std::sync::atomic::Ordering::Relaxed)
     }
 
-    pub async fn set_fall_flying(&self, fall_flying: bool) {
+    pub fn set_fall_flying(&self, fall_flying: bool) {
         assert!(self.fall_flying.load(std::sync::atomic::Ordering::Relaxed) != fall_flying);
         self.fall_flying
             .store(fall_flying, std::sync::atomic::Ordering::Relaxed);
-        self.set_flag(Flag::FallFlying, fall_flying).await;
+        self.set_flag(Flag::FallFlying, fall_flying);
     }
 
-    async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
-        let mut b = 0i8;
-        if value {
-            b |= 1 << index;
-        } else {
-            b &= !(1 << index);
-        }
-        let packet = CSetEntityMetadata::new(self.entity_id.into(), Metadata::new(0, 0.into(), b));
-        self.world.broadcast_packet_all(&packet).await;
+    fn set_flag(&self, flag: Flag, value: bool) {
+        self.data.set_bit(&SHARED_FLAGS, flag as u8, value);
     }
 
-    pub async fn set_pose(&self, pose: EntityPose) {
+    pub fn set_pose(&self, pose: EntityPose) {
         self.pose.store(pose);
-        let pose = pose as i32;
-        let packet = CSetEntityMetadata::<VarInt>::new(
-            self.entity_id.into(),
-            Metadata::new(6, 21.into(), pose.into()),
-        );
-        self.world.broadcast_packet_all(&packet).await;
+        self.data.set(&POSE, pose);
+    }
+
+    /// Names the entity, the name is shown above it if it is always visible or players look at it
+    pub fn set_custom_name(&self, name: Option<TextComponent<'static>>, always_visible: bool) {
+        self.data.set(&CUSTOM_NAME, name);
+        self.data.set(&CUSTOM_NAME_VISIBLE, always_visible);
+    }
+
+    /// All metadata which was set, `None` if the entity still looks like any other of its type
+    pub fn data_packet(&self) -> Option<CSetEntityMetadata> {
+        let metadata = self.data.all();
+        (!metadata.is_empty()).then(|| CSetEntityMetadata::new(self.entity_id.into(), metadata))
+    }
+
+    /// Tells players about the metadata which changed since the last time, like vanilla's
+    /// `ServerEntity.sendDirtyEntityData`
+    pub async fn send_data_changes(&self) {
+        let metadata = self.data.take_dirty();
+        if !metadata.is_empty() {
+            self.broadcast_to_trackers_and_self(&CSetEntityMetadata::new(
+                self.entity_id.into(),
+                metadata,
+            ))
+            .await;
+        }
+    }
+
+    /// Sends the packet to the players who see the entity, like vanilla's `ServerEntity.broadcast`
+    pub async fn broadcast_to_trackers<P: ClientPacket>(&self, packet: &P) {
+        self.send_to_trackers(packet, false).await;
+    }
+
+    /// Sends the packet to the players who see the entity and to the entity itself if it is a
+    /// player, like vanilla's `ServerEntity.broadcastAndSend`
+    pub async fn broadcast_to_trackers_and_self<P: ClientPacket>(&self, packet: &P) {
+        self.send_to_trackers(packet, true).await;
+    }
+
+    async fn send_to_trackers<P: ClientPacket>(&self, packet: &P, include_self: bool) {
+        let world = self.world();
+        let players = world.current_players.lock().await;
+        for player in players.values() {
+            let player_id = player.entity_id();
+            if (include_self && player_id == self.entity_id) || self.tracker.is_seen_by(player_id) {
+                player.client.send_packet(packet).await;
+            }
+        }
     }
 }
 
@@ -0,0 +1,271 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_protocol::client::play::{
+    CEntityVelocity, CHeadRot, CTeleportEntity, CUpdateEntityPos, CUpdateEntityPosRot,
+    CUpdateEntityRot,
+};
+use pumpkin_world::physics::{Movement, MovementProperties};
+
+use crate::net::Client;
+
+use super::{tracker::tracking_range, Entity};
+
+/// Ticks between full position updates, relative updates drift a little in between
+const TELEPORT_INTERVAL: u32 = 60;
+/// Players further away than this part of the tracking range only get every few position updates
+const CLOSE_RANGE_FRACTION: f64 = 0.5;
+/// Position updates between the ones players far away from the entity get
+const FAR_UPDATE_INTERVAL: u32 = 4;
+/// Velocity changes smaller than this are not sent, players keep moving entities themselves
+const VELOCITY_THRESHOLD: f64 = 1.0E-7;
+
+/// How the entity type moves on its own
+#[must_use]
+pub const fn movement_properties(entity_type: &EntityType) -> MovementProperties {
+    match entity_type {
+        EntityType::Item => MovementProperties::ITEM,
+        EntityType::ExperienceOrb => MovementProperties::EXPERIENCE_ORB,
+        EntityType::Tnt => MovementProperties::PRIMED_TNT,
+        EntityType::FallingBlock => MovementProperties::FALLING_BLOCK,
+        _ => MovementProperties::LIVING,
+    }
+}
+
+/// Ticks between position updates sent to players, like the update intervals of vanilla's
+/// `EntityType`s. Players move entities themselves in between
+const fn update_interval(entity_type: &EntityType) -> u32 {
+    match entity_type {
+        EntityType::Item
+        | EntityType::ExperienceOrb
+        | EntityType::FallingBlock
+        | EntityType::Arrow
+        | EntityType::Trident => 20,
+        EntityType::Tnt
+        | EntityType::Snowball
+        | EntityType::Egg
+        | EntityType::EnderPearl
+        | EntityType::ExperienceBottle => 10,
+        _ => 3,
+    }
+}
+
+/// Whether players are told about every change of the entity's velocity, not just the ones
+/// something else caused. Mobs only send their position
+const fn tracks_velocity(entity_type: &EntityType) -> bool {
+    matches!(
+        entity_type,
+        EntityType::Item
+            | EntityType::ExperienceOrb
+            | EntityType::FallingBlock
+            | EntityType::Tnt
+            | EntityType::Arrow
+            | EntityType::Trident
+            | EntityType::Snowball
+            | EntityType::Egg
+            | EntityType::EnderPearl
+            | EntityType::ExperienceBottle
+    )
+}
+
+/// Rotations are sent in 1/256 of a turn
+fn angle(degrees: f32) -> u8 {
+    (degrees * 256.0 / 360.0).floor() as i32 as u8
+}
+
+/// The movement players close to the entity get instead of the full position
+enum RelativeMove {
+    Position(CUpdateEntityPos),
+    PositionRotation(CUpdateEntityPosRot),
+    Rotation(CUpdateEntityRot),
+}
+
+impl RelativeMove {
+    async fn send_to(&self, client: &Client) {
+        match self {
+            Self::Position(packet) => client.send_packet(packet).await,
+            Self::PositionRotation(packet) => client.send_packet(packet).await,
+            Self::Rotation(packet) => client.send_packet(packet).await,
+        }
+    }
+}
+
+/// The position, rotation and velocity players last got of an entity, like vanilla's
+/// `ServerEntity`
+#[derive(Default)]
+pub struct MovementSync {
+    position: AtomicCell<Vector3<f64>>,
+    /// Yaw and pitch as sent
+    rotation: AtomicCell<(u8, u8)>,
+    head_yaw: AtomicCell<u8>,
+    velocity: AtomicCell<Vector3<f64>>,
+    ticks: AtomicU32,
+}
+
+impl MovementSync {
+    /// Players got the position some other way, e.g. with a teleport
+    pub fn set_position(&self, position: Vector3<f64>) {
+        self.position.store(position);
+    }
+}
+
+impl Entity {
+    /// Sets the velocity of the entity, players are told about it with the next movement update
+    pub fn set_velocity(&self, velocity: Vector3<f64>) {
+        self.velocity.store(velocity);
+        self.velocity_dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Adds to the velocity of the entity, e.g. when an explosion pushes it
+    pub fn add_velocity(&self, velocity: Vector3<f64>) {
+        self.set_velocity(self.velocity.load() + velocity);
+    }
+
+    /// Moves the entity for one tick with the movement of its type and tells players about it.
+    /// `None` if the entity is in a chunk which is not loaded and holds still
+    pub async fn tick_movement(&self) -> Option<Movement> {
+        let properties = movement_properties(&self.entity_type);
+        let movement = self.world().level.tick_entity_movement(
+            &properties,
+            &self.bounding_box.load(),
+            self.velocity.load(),
+            self.on_ground.load(Ordering::Relaxed),
+        )?;
+        self.set_pos(self.pos.load() + movement.motion);
+        self.velocity.store(movement.velocity);
+        self.on_ground.store(movement.on_ground, Ordering::Relaxed);
+        self.sync_movement().await;
+        Some(movement)
+    }
+
+    /// Sends players the position and rotation every few ticks and velocity changes, velocity set
+    /// by something else than the movement itself is sent right away. Head turns are sent every
+    /// tick. Players far away from the entity only get every few position updates
+    pub async fn sync_movement(&self) {
+        let sync = &self.movement_sync;
+        let ticks = sync.ticks.fetch_add(1, Ordering::Relaxed) + 1;
+        let head_yaw = angle(self.head_yaw.load());
+        if sync.head_yaw.swap(head_yaw) != head_yaw {
+            self.broadcast_to_trackers(&CHeadRot::new(self.entity_id.into(), head_yaw))
+                .await;
+        }
+        let velocity = self.velocity.load();
+        let on_ground = self.on_ground.load(Ordering::Relaxed);
+
+        let interval = update_interval(&self.entity_type);
+        let update = ticks.is_multiple_of(interval);
+        let last_velocity = sync.velocity.load();
+        let difference = velocity.sub(&last_velocity).length_squared();
+        let velocity_changed = difference > VELOCITY_THRESHOLD
+            || (difference > 0.0 && velocity.length_squared() == 0.0);
+        if self.velocity_dirty.swap(false, Ordering::Relaxed)
+            || (update && tracks_velocity(&self.entity_type) && velocity_changed)
+        {
+            sync.velocity.store(velocity);
+            self.broadcast_to_trackers(&CEntityVelocity::new(
+                &self.entity_id.into(),
+                velocity.x,
+                velocity.y,
+                velocity.z,
+            ))
+            .await;
+        }
+        if !update {
+            return;
+        }
+
+        let position = self.pos.load();
+        let last_position = sync.position.load();
+        let rotation = (angle(self.yaw.load()), angle(self.pitch.load()));
+        let rotated = sync.rotation.swap(rotation) != rotation;
+        // Relative moves are sent in 1/4096 blocks
+        let delta = position.sub(&last_position) * 4096.0;
+        let fits = [delta.x, delta.y, delta.z]
+            .iter()
+            .all(|delta| (f64::from(i16::MIN)..=f64::from(i16::MAX)).contains(&delta.round()));
+        if !fits || ticks.is_multiple_of(TELEPORT_INTERVAL) {
+            sync.position.store(position);
+            self.broadcast_to_trackers(&self.teleport_packet(position))
+                .await;
+            return;
+        }
+        let delta = Vector3::new(
+            delta.x.round() as i16,
+            delta.y.round() as i16,
+            delta.z.round() as i16,
+        );
+        let relative = if delta == Vector3::new(0, 0, 0) {
+            rotated.then(|| {
+                RelativeMove::Rotation(CUpdateEntityRot::new(
+                    self.entity_id.into(),
+                    rotation.0,
+                    rotation.1,
+                    on_ground,
+                ))
+            })
+        } else {
+            // Keeps what the players saw, so rounding errors don't add up
+            sync.position.store(
+                last_position
+                    + Vector3::new(f64::from(delta.x), f64::from(delta.y), f64::from(delta.z))
+                        * (1.0 / 4096.0),
+            );
+            Some(if rotated {
+                RelativeMove::PositionRotation(CUpdateEntityPosRot::new(
+                    self.entity_id.into(),
+                    delta,
+                    rotation.0,
+                    rotation.1,
+                    on_ground,
+                ))
+            } else {
+                RelativeMove::Position(CUpdateEntityPos::new(
+                    self.entity_id.into(),
+                    delta,
+                    on_ground,
+                ))
+            })
+        };
+        let far_update = ticks.is_multiple_of(interval * FAR_UPDATE_INTERVAL);
+        self.send_movement(relative.as_ref(), far_update).await;
+    }
+
+    /// Sends close players the relative movement. Far players only get the full position every
+    /// few updates, players who came close get it right away so later relative moves start from
+    /// the position the other players see
+    async fn send_movement(&self, relative: Option<&RelativeMove>, far_update: bool) {
+        let position = self.pos.load();
+        let close_range = f64::from(tracking_range(&self.entity_type) * 16) * CLOSE_RANGE_FRACTION;
+        let teleport = self.teleport_packet(self.movement_sync.position.load());
+        let world = self.world();
+        let players = world.current_players.lock().await;
+        for player in players.values() {
+            let offset = player.living_entity.entity.pos.load().sub(&position);
+            let close = offset.x * offset.x + offset.z * offset.z <= close_range * close_range;
+            match self.tracker.set_close(player.entity_id(), close) {
+                Some(true) if close => {
+                    if let Some(relative) = relative {
+                        relative.send_to(&player.client).await;
+                    }
+                }
+                Some(false) if close => player.client.send_packet(&teleport).await,
+                Some(_) if far_update => player.client.send_packet(&teleport).await,
+                _ => {}
+            }
+        }
+    }
+
+    fn teleport_packet(&self, position: Vector3<f64>) -> CTeleportEntity<'static> {
+        CTeleportEntity::new(
+            self.entity_id.into(),
+            position,
+            self.velocity.load(),
+            self.yaw.load(),
+            self.pitch.load(),
+            &[],
+            self.on_ground.load(Ordering::Relaxed),
+        )
+    }
+}
@@ -1,12 +1,13 @@
 use std::{
     num::NonZeroU8,
     sync::{
-        atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32, AtomicU8},
+        atomic::{AtomicBool, AtomicI32, AtomicI64, AtomicU32},
         Arc,
     },
     time::{Duration, Instant},
 };
 
+use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use num_derive::FromPrimitive;
 use num_traits::Pow;
@@ -18,22 +19,29 @@ use pumpkin_core::{
         vector2::Vector2,
         vector3::Vector3,
     },
-    permission::PermissionLvl,
+    permission::{PermissionGrants, PermissionLvl},
     text::TextComponent,
     GameMode,
 };
-use pumpkin_entity::{entity_type::EntityType, EntityId};
-use pumpkin_inventory::player::PlayerInventory;
+use pumpkin_entity::{entity_type::EntityType, pose::EntityPose, EntityId};
+use pumpkin_inventory::{
+    player::PlayerInventory, recipe_book::RecipeBook, Container, EnderChestContainer,
+};
 use pumpkin_macros::sound;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::server::play::{
-    SCloseContainer, SCookieResponse as SPCookieResponse, SPlayPingRequest,
+    SBundleItemSelected, SClickContainer, SCloseContainer, SContainerButtonClick,
+    SCookieResponse as SPCookieResponse, SKeepAlive, SPlaceRecipe, SPlayPingRequest,
+    SRecipeBookChangeSettings, SRecipeBookSeenRecipe, SRenameItem, SSelectTrade,
 };
 use pumpkin_protocol::{
     bytebuf::packet_id::Packet,
     client::play::{
-        CCombatDeath, CEntityStatus, CGameEvent, CHurtAnimation, CKeepAlive, CPlayDisconnect,
-        CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CSetHealth, CSystemChatMessage,
-        GameEvent, PlayerAction,
+        Animation, AttributeModifier, AttributeProperty, CCombatDeath, CEntityAnimation,
+        CEntityStatus, CGameEvent, CHurtAnimation, CKeepAlive, CPlayDisconnect, CPlayerAbilities,
+        CPlayerInfoUpdate, CPlayerPosition, CSetExperience, CSetHealth, CSpawnEntity,
+        CSystemChatMessage, CTakeItemEntity, CTeleportEntity, CUpdateAttributes, GameEvent,
+        PlayerAction,
     },
     server::play::{
         SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay, SClientTickEnd,
@@ -44,34 +52,125 @@ use pumpkin_protocol::{
     },
     RawPacket, ServerPacket, SoundCategory,
 };
-use pumpkin_protocol::{client::play::CUpdateTime, codec::var_int::VarInt};
 use pumpkin_protocol::{
-    client::play::{CSetEntityMetadata, Metadata},
-    server::play::{SClickContainer, SKeepAlive},
+    client::play::CUpdateTime,
+    codec::{identifier::Identifier, var_int::VarInt},
 };
+use pumpkin_registry::{is_in_tag, TagCategory};
 use pumpkin_world::{
+    block::fluid::Fluid,
     cylindrical_chunk_iterator::Cylindrical,
+    damage::{
+        armor_durability_damage, block_contact_damage, enchantment_damage_bonus, fire_aspect_ticks,
+        fly_into_wall_damage, shield_blocks, DamageType, Protection, DROWNING_DAMAGE,
+        MAX_AIR_SUPPLY,
+    },
+    effect::{EffectInstance, StatusEffect},
+    food::{
+        FoodData, FoodTick, EXHAUSTION_ATTACK, EXHAUSTION_HUNGER_EFFECT, EXHAUSTION_JUMP,
+        EXHAUSTION_SPRINT_JUMP, EXHAUSTION_SPRINT_PER_METER, MAX_FOOD_LEVEL,
+    },
     item::{
-        item_registry::{get_item_by_id, Operation},
+        item_registry::{
+            get_item, get_item_by_id, get_item_name_by_id, Consumable, ConsumeEffect,
+            EquipmentSlot, Operation, UseAnimation,
+        },
         ItemStack,
     },
+    physics::is_below_world,
+    stats::StatType,
+    world_info::game_rules::BoolRule,
 };
+use rand::Rng;
 use tokio::sync::{Mutex, Notify, RwLock};
 
-use super::Entity;
+use super::{
+    damage::{DamageEntity, DamageSource},
+    data::{DataField, LIVING_FLAGS, SLEEPING_POSITION},
+    experience_orb::ExperienceOrbEntity,
+    item::{ItemEntity, THROWN_ITEM_PICKUP_DELAY},
+    mining::Digging,
+    mob::MobEntity,
+    tracker::TrackedEntity,
+    Entity,
+};
 use crate::{
     command::{client_cmd_suggestions, dispatcher::CommandDispatcher},
     data::op_data::OPERATOR_CONFIG,
     net::{
-        combat::{self, player_attack_sound, AttackType},
+        advancements::PlayerAdvancements,
+        combat::{self, attack_durability_damage, player_attack_sound, AttackType},
+        stats::{PlayerStats, Stat},
         Client, PlayerConfig,
     },
     server::Server,
-    world::World,
+    world::{scoreboard::ObjectiveCriterion, World},
 };
 use crate::{error::PumpkinError, net::GameProfile};
 
-use super::living::LivingEntity;
+use super::living::{LivingEntity, MAX_HEALTH};
+
+/// Ticks players have to sleep before the night can be skipped
+pub const DEEP_SLEEP_TICKS: i32 = 100;
+/// Ticks between players picking up experience orbs
+const EXPERIENCE_PICKUP_DELAY: u32 = 2;
+
+/// The id of the `movement_speed` attribute in the `attribute` registry
+const MOVEMENT_SPEED_ATTRIBUTE: i32 = 21;
+/// How fast players walk without any effects
+const BASE_MOVEMENT_SPEED: f64 = 0.1;
+/// Ticks a shield has to be raised before it blocks damage
+const SHIELD_BLOCK_DELAY: u32 = 5;
+/// Shields only lose durability from hits doing at least this much damage
+const SHIELD_DAMAGE_THRESHOLD: f32 = 3.0;
+/// The entity statuses playing the sounds of a shield blocking and breaking
+const SHIELD_BLOCK_STATUS: i8 = 29;
+const SHIELD_BREAK_STATUS: i8 = 30;
+/// The slots of the worn armor, from the helmet to the boots
+const ARMOR_SLOTS: [EquipmentSlot; 4] = [
+    EquipmentSlot::Head,
+    EquipmentSlot::Chest,
+    EquipmentSlot::Legs,
+    EquipmentSlot::Feet,
+];
+/// The entity status telling the player they finished eating or drinking
+const USE_ITEM_COMPLETE_STATUS: i8 = 9;
+/// Ticks touching fire or lava sets players on fire for
+const FIRE_IGNITE_TICKS: i32 = 8 * 20;
+const LAVA_IGNITE_TICKS: i32 = 15 * 20;
+/// The damage lava does every tick players touch it
+const LAVA_DAMAGE: f32 = 4.0;
+/// The damage the void does every tick players are below the world
+const VOID_DAMAGE: f32 = 4.0;
+
+/// The living entity flags telling players an item is being used, like a bow being drawn
+const USING_ITEM_FLAG: i8 = 0x01;
+const OFFHAND_FLAG: i8 = 0x02;
+/// Which parts of their skin players show, like the cape or jacket
+const SKIN_PARTS: DataField<i8> = DataField::new(17);
+const MAIN_HAND: DataField<i8> = DataField::new(18);
+
+/// An item a player holds down right click with, like a bow they draw
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemInUse {
+    pub offhand: bool,
+    pub item_id: u16,
+    /// Ticks the item is being used
+    pub ticks: u32,
+}
+
+/// Whether the player walks at another speed with or without the effect
+const fn changes_movement_speed(effect: StatusEffect) -> bool {
+    matches!(effect, StatusEffect::Speed | StatusEffect::Slowness)
+}
+
+/// Where a player respawns after dying
+#[derive(Clone, PartialEq, Eq)]
+pub struct RespawnPoint {
+    /// The world the respawn point is in
+    pub dimension: Identifier,
+    pub position: WorldPosition,
+}
 
 /// Represents a Minecraft player entity.
 ///
@@ -87,14 +186,28 @@ pub struct Player {
     pub config: Mutex<PlayerConfig>,
     /// The player's current gamemode (e.g., Survival, Creative, Adventure).
     pub gamemode: AtomicCell<GameMode>,
-    /// The player's hunger level.
-    pub food: AtomicI32,
-    /// The player's food saturation level.
-    pub food_saturation: AtomicCell<f32>,
+    /// The player's hunger, food level and saturation.
+    pub food_data: Mutex<FoodData>,
+    /// The player's experience level.
+    pub experience_level: AtomicI32,
+    /// The player's progress towards the next experience level, between 0 and 1.
+    pub experience_progress: AtomicCell<f32>,
+    /// All experience points the player has collected.
+    pub total_experience: AtomicI32,
+    /// Ticks until the player can pick up the next experience orb.
+    pub experience_pickup_delay: AtomicU32,
     /// The ID of the currently open container (if any).
     pub open_container: AtomicCell<Option<u64>>,
     /// The item currently being held by the player.
-    pub carried_item: AtomicCell<Option<ItemStack>>,
+    pub carried_item: Mutex<Option<ItemStack>>,
+    /// The recipes the player has unlocked and the state of their recipe books.
+    pub recipe_book: Mutex<RecipeBook>,
+    /// The advancement criteria the player has done
+    pub advancements: Mutex<PlayerAdvancements>,
+    /// The statistics of the player, like the blocks they mined and how far they walked
+    pub stats: Mutex<PlayerStats>,
+    /// The player's ender chest, shared with the open container while they look into it
+    pub ender_chest: Arc<Mutex<Box<dyn Container>>>,
 
     /// send `send_abilities_update` when changed
     /// The player's abilities and special powers.
@@ -104,8 +217,8 @@ pub struct Player {
     /// **Note:** When the `abilities` field is updated, the server should send a `send_abilities_update` packet to the client to notify them of the changes.
     pub abilities: Mutex<Abilities>,
 
-    /// The current stage of the block the player is breaking.
-    pub current_block_destroy_stage: AtomicU8,
+    /// The block the player is breaking in survival
+    pub digging: AtomicCell<Option<Digging>>,
     /// A counter for teleport IDs used to track pending teleports.
     pub teleport_id_count: AtomicI32,
     /// The pending teleport information, including the teleport ID and target location.
@@ -122,6 +235,22 @@ pub struct Player {
     pub last_attacked_ticks: AtomicU32,
     /// The players op permission level
     pub permission_lvl: AtomicCell<PermissionLvl>,
+    /// Permission nodes granted or revoked for the player regardless of their level
+    pub permission_grants: Mutex<PermissionGrants>,
+    /// Where the player respawns after dying, set by sleeping in beds
+    pub respawn_point: Mutex<Option<RespawnPoint>>,
+    /// The head of the bed the player sleeps in, if they are sleeping
+    pub sleeping_position: AtomicCell<Option<WorldPosition>>,
+    /// Ticks the player is sleeping, the night is skipped once players slept long enough
+    pub sleep_ticks: AtomicI32,
+    /// The item the player is using, like a bow being drawn
+    pub item_in_use: AtomicCell<Option<ItemInUse>>,
+    /// Whether the player died and was told so, until they respawn
+    pub dead: AtomicBool,
+    /// The horizontal speed of the last move, gliding players get hurt losing much of it at once
+    pub glide_speed: AtomicCell<f64>,
+    /// The health last put into `health` objectives, they are updated when it changes
+    last_health_score: AtomicCell<Option<i32>>,
     /// Tell tasks to stop if we are closing
     cancel_tasks: Notify,
 }
@@ -170,11 +299,18 @@ impl Player {
             client,
             awaiting_teleport: Mutex::new(None),
             // TODO: Load this from previous instance
-            food: AtomicI32::new(20),
-            food_saturation: AtomicCell::new(20.0),
-            current_block_destroy_stage: AtomicU8::new(0),
+            food_data: Mutex::new(FoodData::default()),
+            experience_level: AtomicI32::new(0),
+            experience_progress: AtomicCell::new(0.0),
+            total_experience: AtomicI32::new(0),
+            experience_pickup_delay: AtomicU32::new(0),
+            digging: AtomicCell::new(None),
             open_container: AtomicCell::new(None),
-            carried_item: AtomicCell::new(None),
+            carried_item: Mutex::new(None),
+            recipe_book: Mutex::new(RecipeBook::default()),
+            advancements: Mutex::new(PlayerAdvancements::default()),
+            stats: Mutex::new(PlayerStats::default()),
+            ender_chest: Arc::new(Mutex::new(Box::new(EnderChestContainer::new()))),
             teleport_id_count: AtomicI32::new(0),
             abilities: Mutex::new(Abilities::default()),
             gamemode: AtomicCell::new(gamemode),
@@ -189,6 +325,13 @@ impl Player {
             keep_alive_id: AtomicI64::new(0),
             last_keep_alive_time: AtomicCell::new(std::time::Instant::now()),
             last_attacked_ticks: AtomicU32::new(0),
+            respawn_point: Mutex::new(None),
+            sleeping_position: AtomicCell::new(None),
+            sleep_ticks: AtomicI32::new(0),
+            item_in_use: AtomicCell::new(None),
+            dead: AtomicBool::new(false),
+            glide_speed: AtomicCell::new(0.0),
+            last_health_score: AtomicCell::new(None),
             cancel_tasks: Notify::new(),
             // Minecraft has no why to change the default permission level of new players.
             // Minecrafts default permission level is 0
@@ -202,6 +345,7 @@ impl Player {
                     AtomicCell::new(ADVANCED_CONFIG.commands.default_op_level),
                     |op| AtomicCell::new(op.level),
                 ),
+            permission_grants: Mutex::new(PermissionGrants::default()),
         }
     }
 
@@ -215,31 +359,20 @@ impl Player {
     /// Removes the Player out of the current World
     #[allow(unused_variables)]
     pub async fn remove(&self) {
-        let world = self.world();
+        let world = &self.world();
         self.cancel_tasks.notify_waiters();
+        self.award_stat(Stat::custom("leave_game"), 1).await;
+        self.save_data().await;
+        self.wake_up().await;
 
         world.remove_player(self).await;
 
-        let cylindrical = self.watched_section.load();
-
-        // Radial chunks are all of the chunks the player is theoretically viewing
-        // Giving enough time, all of these chunks will be in memory
-        let radial_chunks = cylindrical.all_chunks_within();
-
         log::debug!(
-            "Removing player {} ({}), unwatching {} chunks",
+            "Removing player {} ({})",
             self.gameprofile.name,
             self.client.id,
-            radial_chunks.len()
         );
-
-        // Decrement value of watched chunks
-        let chunks_to_clean = world.level.mark_chunks_as_not_watched(&radial_chunks);
-
-        // Remove chunks with no watchers from the cache
-        world.level.clean_chunks(&chunks_to_clean);
-        // Remove left over entries from all possiblily loaded chunks
-        world.level.clean_memory(&radial_chunks);
+        self.unwatch_chunks(world).await;
 
         log::debug!(
             "Removed player id {} ({}) ({} chunks remain cached)",
@@ -251,14 +384,48 @@ impl Player {
         //self.world().level.list_cached();
     }
 
-    pub async fn attack(&self, victim: &Arc<Self>) {
-        let world = self.world();
-        let victim_entity = &victim.living_entity.entity;
-        let attacker_entity = &self.living_entity.entity;
-        let config = &ADVANCED_CONFIG.pvp;
+    /// Stops watching the chunks of the world, when the player leaves it. The player watches the
+    /// chunks around it from scratch when it joins a world again
+    pub async fn unwatch_chunks(&self, world: &World) {
+        let cylindrical = self.watched_section.load();
+
+        // Radial chunks are all of the chunks the player is theoretically viewing
+        // Giving enough time, all of these chunks will be in memory
+        let radial_chunks = cylindrical.all_chunks_within();
+
+        // Decrement value of watched chunks
+        let chunks_to_clean = world.level.mark_chunks_as_not_watched(&radial_chunks);
+        // Items in the chunks are saved with them
+        world.unload_chunk_entities(&chunks_to_clean).await;
+
+        // Remove chunks with no watchers from the cache
+        world.level.clean_chunks(&chunks_to_clean);
+        // Remove left over entries from all possiblily loaded chunks
+        world.level.clean_memory(&radial_chunks);
+
+        self.watched_section.store(Cylindrical::new(
+            Vector2::new(i32::MAX >> 1, i32::MAX >> 1),
+            unsafe { NonZeroU8::new_unchecked(1) },
+        ));
+    }
 
+    /// The damage of an attack with the held item on an entity of the type, the damage its
+    /// enchantments add, which critical hits don't raise, and how far the attack cooled down.
+    /// Resets the cooldown
+    async fn attack_damage(&self, target: &EntityType) -> (f64, f64, f64) {
         let inventory = self.inventory().lock().await;
         let item_slot = inventory.held_item();
+        let enchantment_bonus = item_slot.map_or(0.0, |weapon| {
+            enchantment_damage_bonus(
+                weapon,
+                is_in_tag(TagCategory::Entity, target.name(), "sensitive_to_smite"),
+                is_in_tag(
+                    TagCategory::Entity,
+                    target.name(),
+                    "sensitive_to_bane_of_arthropods",
+                ),
+            )
+        });
 
         let base_damage = 1.0;
         let base_attack_speed = 4.0;
@@ -293,18 +460,116 @@ impl Player {
         self.last_attacked_ticks
             .store(0, std::sync::atomic::Ordering::Relaxed);
 
-        // only reduce attack damage if in cooldown
-        // TODO: Enchantments are reduced same way just without the square
+        // only reduce attack damage if in cooldown, enchantments are reduced without the square
         if attack_cooldown_progress < 1.0 {
             damage_multiplier = 0.2 + attack_cooldown_progress.pow(2) * 0.8;
         }
+        // Strength and weakness change the damage before the cooldown reduces it
+        let effect_bonus = f64::from(
+            self.living_entity
+                .effects
+                .lock()
+                .await
+                .attack_damage_bonus(),
+        );
         // modify added damage based on multiplier
-        let mut damage = base_damage + add_damage * damage_multiplier;
+        (
+            (base_damage + (add_damage + effect_bonus) * damage_multiplier).max(0.0),
+            f64::from(enchantment_bonus) * attack_cooldown_progress,
+            attack_cooldown_progress,
+        )
+    }
+
+    /// Uses up durability of the item in the hand unless the player is in creative, a broken
+    /// item is removed like vanilla's `ItemStack.hurtAndBreak`
+    pub async fn damage_hand_item(&self, offhand: bool, amount: i32) {
+        if amount <= 0 || self.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let broke = {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.hand_item_mut(offhand);
+            let broke = held.as_mut().is_some_and(|item| item.damage_item(amount));
+            if broke {
+                *held = None;
+            }
+            broke
+        };
+        self.set_container_content(None).await;
+        if broke {
+            let slot = if offhand {
+                EquipmentSlot::OffHand
+            } else {
+                EquipmentSlot::MainHand
+            };
+            self.broadcast_item_break(slot).await;
+        }
+    }
+
+    /// Shows everyone that the item in the slot broke, players see its pieces and hear it break
+    pub async fn broadcast_item_break(&self, slot: EquipmentSlot) {
+        self.living_entity
+            .entity
+            .broadcast_to_trackers_and_self(&CEntityStatus::new(
+                self.entity_id(),
+                slot.break_status(),
+            ))
+            .await;
+    }
+
+    /// Weapons lose durability for each hit, like vanilla's `Item.postHurtEnemy`
+    async fn damage_weapon(&self) {
+        let amount = self
+            .inventory()
+            .lock()
+            .await
+            .held_item()
+            .map_or(0, attack_durability_damage);
+        self.damage_hand_item(false, amount).await;
+    }
+
+    /// The Knockback level and the Fire Aspect burning ticks of the held item
+    async fn weapon_enchantments(&self) -> (i32, i32) {
+        self.inventory()
+            .lock()
+            .await
+            .held_item()
+            .map_or((0, 0), |weapon| {
+                (
+                    weapon.enchantment_level("knockback"),
+                    fire_aspect_ticks(weapon),
+                )
+            })
+    }
+
+    pub async fn attack(&self, server: &Server, victim: &Arc<Self>) {
+        let world = &self.world();
+        let victim_entity = &victim.living_entity.entity;
+        let attacker_entity = &self.living_entity.entity;
+        let config = &ADVANCED_CONFIG.pvp;
+
+        let (mut damage, enchantment_damage, attack_cooldown_progress) =
+            self.attack_damage(&EntityType::Player).await;
 
         let pos = victim_entity.pos.load();
 
+        let attack_type = AttackType::new(self, attack_cooldown_progress as f32).await;
+        if matches!(attack_type, AttackType::Critical) {
+            damage *= 1.5;
+        }
+        damage += enchantment_damage;
+
+        let source = DamageSource::by(DamageType::PlayerAttack, DamageEntity::player(self));
+        // Members of a team without friendly fire don't hurt each other, like vanilla's
+        // `Player.canHarmPlayer`
+        let friendly = !server
+            .scoreboard
+            .lock()
+            .await
+            .can_harm(&self.gameprofile.name, &victim.gameprofile.name);
         if (config.protect_creative && victim.gamemode.load() == GameMode::Creative)
-            || !victim.living_entity.check_damage(damage as f32)
+            || friendly
+            || !victim.hurt(&source, damage as f32).await
         {
             world
                 .play_sound(
@@ -319,21 +584,17 @@ impl Player {
         world
             .play_sound(sound!("entity.player.hurt"), SoundCategory::Players, &pos)
             .await;
-
-        let attack_type = AttackType::new(self, attack_cooldown_progress as f32).await;
+        self.add_exhaustion(EXHAUSTION_ATTACK).await;
 
         player_attack_sound(&pos, world, attack_type).await;
 
-        if matches!(attack_type, AttackType::Critical) {
-            damage *= 1.5;
+        let (knockback, fire_aspect) = self.weapon_enchantments().await;
+        self.damage_weapon().await;
+        if fire_aspect > 0 {
+            victim.living_entity.set_on_fire_for(fire_aspect);
         }
 
-        victim
-            .living_entity
-            .damage(damage as f32, 34) // PlayerAttack
-            .await;
-
-        let mut knockback_strength = 1.0;
+        let mut knockback_strength = 1.0 + f64::from(knockback);
         match attack_type {
             AttackType::Knockback => knockback_strength += 1.0,
             AttackType::Sweeping => {
@@ -349,19 +610,74 @@ impl Player {
 
         if config.hurt_animation {
             let entity_id = VarInt(victim_entity.entity_id);
-            world
-                .broadcast_packet_all(&CHurtAnimation::new(&entity_id, attacker_entity.yaw.load()))
+            victim_entity
+                .broadcast_to_trackers_and_self(&CHurtAnimation::new(
+                    &entity_id,
+                    attacker_entity.yaw.load(),
+                ))
                 .await;
         }
 
         if config.swing {}
     }
 
+    /// Hits the mob with the held item, mobs players killed drop experience
+    pub async fn attack_mob(&self, mob: &MobEntity) {
+        let world = &self.world();
+        let living_entity = &mob.living_entity;
+        if living_entity.health.load() <= 0.0 {
+            return;
+        }
+        let (mut damage, enchantment_damage, attack_cooldown_progress) =
+            self.attack_damage(&mob.entity().entity_type).await;
+        let pos = mob.entity().pos.load();
+        let attack_type = AttackType::new(self, attack_cooldown_progress as f32).await;
+        if matches!(attack_type, AttackType::Critical) {
+            damage *= 1.5;
+        }
+        damage += enchantment_damage;
+        let source = DamageSource::by(DamageType::PlayerAttack, DamageEntity::player(self));
+        if !mob.hurt(&source, damage as f32).await {
+            world
+                .play_sound(
+                    sound!("entity.player.attack.nodamage"),
+                    SoundCategory::Players,
+                    &pos,
+                )
+                .await;
+            return;
+        }
+        player_attack_sound(&pos, world, attack_type).await;
+        self.add_exhaustion(EXHAUSTION_ATTACK).await;
+        let (knockback, fire_aspect) = self.weapon_enchantments().await;
+        self.damage_weapon().await;
+        if fire_aspect > 0 {
+            living_entity.set_on_fire_for(fire_aspect);
+        }
+        if knockback > 0 {
+            let yaw = self.living_entity.entity.yaw.load().to_radians();
+            mob.entity().knockback(
+                f64::from(knockback) * 0.5,
+                f64::from(yaw.sin()),
+                f64::from(-yaw.cos()),
+            );
+        }
+        mob.hurt_by_player();
+        mob.hurt_by(Some(self.entity_id()));
+        let entity_id = VarInt(mob.entity().entity_id);
+        mob.entity()
+            .broadcast_to_trackers(&CHurtAnimation::new(
+                &entity_id,
+                self.living_entity.entity.yaw.load(),
+            ))
+            .await;
+    }
+
     pub async fn await_cancel(&self) {
         self.cancel_tasks.notified().await;
     }
 
-    pub async fn tick(&self) {
+    pub async fn tick(&self, server: &Server) {
         if self
             .client
             .closed
@@ -374,6 +690,45 @@ impl Player {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
         self.living_entity.tick();
+        // Fails once the delay is over, which leaves it at zero
+        let _ = self.experience_pickup_delay.fetch_update(
+            std::sync::atomic::Ordering::Relaxed,
+            std::sync::atomic::Ordering::Relaxed,
+            |delay| delay.checked_sub(1),
+        );
+        if let Some(mut item_in_use) = self.item_in_use.load() {
+            item_in_use.ticks += 1;
+            self.item_in_use.store(Some(item_in_use));
+            let consume_ticks = get_item_by_id(item_in_use.item_id)
+                .and_then(|item| item.components.consumable.as_ref())
+                .map(Consumable::consume_ticks);
+            if consume_ticks.is_some_and(|ticks| item_in_use.ticks >= ticks) {
+                self.finish_consuming(server, item_in_use).await;
+            }
+        }
+        let expired = self.living_entity.tick_effects().await;
+        if expired.iter().copied().any(changes_movement_speed) {
+            self.send_movement_speed().await;
+        }
+        self.tick_digging().await;
+        self.tick_food().await;
+        self.tick_environment().await;
+        self.tick_stats().await;
+        self.update_health_score(server).await;
+        self.update_stat_scores(server).await;
+        self.trigger_inventory_changed().await;
+        if self.living_entity.health.load() <= 0.0
+            && !self.dead.swap(true, std::sync::atomic::Ordering::Relaxed)
+        {
+            self.die(server).await;
+        }
+        if self.is_sleeping() {
+            let ticks = self.sleep_ticks.load(std::sync::atomic::Ordering::Relaxed);
+            self.sleep_ticks.store(
+                (ticks + 1).min(DEEP_SLEEP_TICKS),
+                std::sync::atomic::Ordering::Relaxed,
+            );
+        }
 
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
             // We never got a response from our last keep alive we send
@@ -410,8 +765,189 @@ impl Player {
         self.living_entity.entity.entity_id
     }
 
-    pub const fn world(&self) -> &Arc<World> {
-        &self.living_entity.entity.world
+    pub fn world(&self) -> Arc<World> {
+        self.living_entity.entity.world()
+    }
+
+    /// Loads the player's data from the world, if they have joined it before
+    pub async fn load_data(&self) {
+        let uuid = self.gameprofile.id.to_string();
+        match self.world().level.player_data.load(&uuid) {
+            Ok(Some(nbt)) => self.read_nbt(&nbt).await,
+            Ok(None) => {}
+            Err(error) => log::error!(
+                "Failed to load player data of {}: {error}",
+                self.gameprofile.name
+            ),
+        }
+        self.load_advancements().await;
+        self.load_stats().await;
+    }
+
+    pub async fn save_data(&self) {
+        let mut nbt = NbtCompound::new();
+        self.write_nbt(&mut nbt).await;
+        let uuid = self.gameprofile.id.to_string();
+        if let Err(error) = self.world().level.player_data.save(&uuid, nbt) {
+            log::error!(
+                "Failed to save player data of {}: {error}",
+                self.gameprofile.name
+            );
+        }
+        self.save_advancements().await;
+        self.save_stats().await;
+    }
+
+    pub async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut recipe_book = NbtCompound::new();
+        self.recipe_book.lock().await.write_nbt(&mut recipe_book);
+        nbt.put("recipeBook".to_string(), NbtTag::Compound(recipe_book));
+        self.ender_chest.lock().await.write_nbt(nbt);
+        nbt.put(
+            "XpLevel".to_string(),
+            NbtTag::Int(
+                self.experience_level
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
+        );
+        nbt.put(
+            "XpP".to_string(),
+            NbtTag::Float(self.experience_progress.load()),
+        );
+        nbt.put(
+            "XpTotal".to_string(),
+            NbtTag::Int(
+                self.total_experience
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ),
+        );
+        let food_data = *self.food_data.lock().await;
+        nbt.put("foodLevel".to_string(), NbtTag::Int(food_data.food_level));
+        nbt.put(
+            "foodSaturationLevel".to_string(),
+            NbtTag::Float(food_data.saturation),
+        );
+        nbt.put(
+            "foodExhaustionLevel".to_string(),
+            NbtTag::Float(food_data.exhaustion),
+        );
+        nbt.put(
+            "foodTickTimer".to_string(),
+            NbtTag::Int(food_data.tick_timer as i32),
+        );
+        nbt.put(
+            "Air".to_string(),
+            NbtTag::Short(
+                self.living_entity
+                    .air_supply
+                    .load(std::sync::atomic::Ordering::Relaxed) as i16,
+            ),
+        );
+        if let Some(respawn_point) = self.respawn_point.lock().await.as_ref() {
+            let position = respawn_point.position.0;
+            nbt.put("SpawnX".to_string(), NbtTag::Int(position.x));
+            nbt.put("SpawnY".to_string(), NbtTag::Int(position.y));
+            nbt.put("SpawnZ".to_string(), NbtTag::Int(position.z));
+            nbt.put(
+                "SpawnDimension".to_string(),
+                NbtTag::String(respawn_point.dimension.to_string()),
+            );
+        }
+        let tags = self.living_entity.entity.tags();
+        if !tags.is_empty() {
+            nbt.put(
+                "Tags".to_string(),
+                NbtTag::List(tags.into_iter().map(NbtTag::String).collect()),
+            );
+        }
+    }
+
+    pub async fn read_nbt(&self, nbt: &NbtCompound) {
+        if let Some(recipe_book) = nbt.get_compound("recipeBook") {
+            self.recipe_book.lock().await.read_nbt(recipe_book);
+        }
+        self.ender_chest.lock().await.read_nbt(nbt);
+        self.experience_level.store(
+            nbt.get_int("XpLevel").unwrap_or(0).max(0),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        self.experience_progress
+            .store(nbt.get_float("XpP").unwrap_or(0.0).clamp(0.0, 1.0));
+        self.total_experience.store(
+            nbt.get_int("XpTotal").unwrap_or(0).max(0),
+            std::sync::atomic::Ordering::Relaxed,
+        );
+        if let Some(food_level) = nbt.get_int("foodLevel") {
+            *self.food_data.lock().await = FoodData {
+                food_level,
+                saturation: nbt.get_float("foodSaturationLevel").unwrap_or(5.0),
+                exhaustion: nbt.get_float("foodExhaustionLevel").unwrap_or(0.0),
+                tick_timer: nbt.get_int("foodTickTimer").unwrap_or(0).max(0) as u32,
+            };
+        }
+        if let Some(air_supply) = nbt.get_short("Air") {
+            self.living_entity
+                .air_supply
+                .store(i32::from(air_supply), std::sync::atomic::Ordering::Relaxed);
+        }
+        let position = nbt
+            .get_int("SpawnX")
+            .zip(nbt.get_int("SpawnY"))
+            .zip(nbt.get_int("SpawnZ"));
+        let dimension = nbt
+            .get_string("SpawnDimension")
+            .and_then(|dimension| dimension.split_once(':'));
+        if let (Some(((x, y), z)), Some((namespace, path))) = (position, dimension) {
+            *self.respawn_point.lock().await = Some(RespawnPoint {
+                dimension: Identifier {
+                    namespace: namespace.to_string(),
+                    path: path.to_string(),
+                },
+                position: WorldPosition(Vector3::new(x, y, z)),
+            });
+        }
+        if let Some(tags) = nbt.get_list("Tags") {
+            let tags = tags.iter().filter_map(|tag| match tag {
+                NbtTag::String(tag) => Some(tag.clone()),
+                _ => None,
+            });
+            self.living_entity.entity.set_tags(tags.collect());
+        }
+    }
+
+    /// Throws the item stack in front of the player, like vanilla does when pressing the drop key
+    pub async fn drop_item(&self, server: &Server, item_stack: ItemStack) {
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        let position = Vector3::new(
+            pos.x,
+            pos.y + f64::from(entity.standing_eye_height) - 0.3,
+            pos.z,
+        );
+
+        let (pitch_sin, pitch_cos) = f64::from(entity.pitch.load()).to_radians().sin_cos();
+        let (yaw_sin, yaw_cos) = f64::from(entity.yaw.load()).to_radians().sin_cos();
+        // Small random spread so stacks dropped at once don't overlap
+        let angle = rand::random::<f64>() * std::f64::consts::TAU;
+        let spread = 0.02 * rand::random::<f64>();
+        let velocity = Vector3::new(
+            (-yaw_sin * pitch_cos).mul_add(0.3, angle.cos() * spread),
+            (-pitch_sin).mul_add(0.3, 0.1) + (rand::random::<f64>() - rand::random::<f64>()) * 0.1,
+            (yaw_cos * pitch_cos).mul_add(0.3, angle.sin() * spread),
+        );
+
+        if let Some(stat) = Stat::item(StatType::Dropped, item_stack.item_id) {
+            self.award_stat(stat, item_stack.item_count.into()).await;
+        }
+        self.award_stat(Stat::custom("drop"), 1).await;
+        let item_entity = Arc::new(ItemEntity::new(
+            server.new_entity_id(),
+            self.world(),
+            item_stack,
+        ));
+        item_entity.set_motion(position, velocity);
+        item_entity.set_pickup_delay(THROWN_ITEM_PICKUP_DELAY);
+        self.world().add_item_entity(item_entity).await;
     }
 
     /// Updates the current abilities the Player has
@@ -461,14 +997,37 @@ impl Player {
         client_cmd_suggestions::send_c_commands_packet(self, command_dispatcher).await;
     }
 
+    /// Grants or revokes a permission node for the player, `None` goes back to what their level
+    /// grants. Resends the commands so the client only suggests the ones they may run
+    pub async fn set_permission(
+        self: &Arc<Self>,
+        node: &str,
+        value: Option<bool>,
+        command_dispatcher: &RwLock<CommandDispatcher>,
+    ) {
+        {
+            let mut grants = self.permission_grants.lock().await;
+            match value {
+                Some(value) => grants.set(node, value),
+                None => grants.unset(node),
+            }
+        }
+        client_cmd_suggestions::send_c_commands_packet(self, command_dispatcher).await;
+    }
+
     /// Sends the world time to just the player.
     pub async fn send_time(&self, world: &World) {
+        let daylight_cycle = world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::DoDaylightCycle);
         let l_world = world.level_time.lock().await;
         self.client
             .send_packet(&CUpdateTime::new(
                 l_world.world_age,
                 l_world.time_of_day,
-                true,
+                daylight_cycle,
             ))
             .await;
     }
@@ -499,6 +1058,17 @@ impl Player {
                 &[],
             ))
             .await;
+        entity
+            .broadcast_to_trackers(&CTeleportEntity::new(
+                entity.entity_id.into(),
+                position,
+                Vector3::new(0.0, 0.0, 0.0),
+                yaw,
+                pitch,
+                &[],
+                entity.on_ground.load(std::sync::atomic::Ordering::Relaxed),
+            ))
+            .await;
     }
 
     pub fn block_interaction_range(&self) -> f64 {
@@ -509,6 +1079,21 @@ impl Player {
         }
     }
 
+    /// Uses up one of the items in the main hand, players in creative keep their items
+    pub async fn consume_held_item(&self) {
+        if self.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let mut inventory = self.inventory().lock().await;
+        let held = inventory.held_item_mut();
+        if let Some(item_stack) = held {
+            item_stack.item_count -= 1;
+            if item_stack.item_count == 0 {
+                *held = None;
+            }
+        }
+    }
+
     pub fn can_interact_with_block_at(&self, pos: &WorldPosition, additional_range: f64) -> bool {
         let d = self.block_interaction_range() + additional_range;
         let box_pos = BoundingBox::from_block(pos);
@@ -549,100 +1134,1067 @@ impl Player {
     }
 
     pub async fn set_health(&self, health: f32, food: i32, food_saturation: f32) {
-        self.living_entity.set_health(health).await;
-        self.food.store(food, std::sync::atomic::Ordering::Relaxed);
-        self.food_saturation.store(food_saturation);
-        self.client
-            .send_packet(&CSetHealth::new(health, food.into(), food_saturation))
-            .await;
+        self.living_entity.set_health(health);
+        *self.food_data.lock().await = FoodData {
+            food_level: food,
+            saturation: food_saturation,
+            ..FoodData::default()
+        };
+        self.send_food().await;
     }
 
-    pub async fn kill(&self) {
-        self.living_entity.kill().await;
-
+    /// Tells the player their health, food level and saturation
+    pub async fn send_food(&self) {
+        let food_data = *self.food_data.lock().await;
         self.client
-            .send_packet(&CCombatDeath::new(
-                self.entity_id().into(),
-                TextComponent::text("noob"),
+            .send_packet(&CSetHealth::new(
+                self.living_entity.health.load(),
+                food_data.food_level.into(),
+                food_data.saturation,
             ))
             .await;
     }
 
-    pub async fn set_gamemode(&self, gamemode: GameMode) {
-        // We could send the same gamemode without problems. But why waste bandwidth ?
-        assert_ne!(
-            self.gamemode.load(),
-            gamemode,
-            "Setting the same gamemode as already is"
-        );
-        self.gamemode.store(gamemode);
-        {
-            // use another scope so we instantly unlock abilities
-            let mut abilities = self.abilities.lock().await;
-            match gamemode {
-                GameMode::Undefined | GameMode::Survival | GameMode::Adventure => {
-                    abilities.flying = false;
-                    abilities.allow_flying = false;
-                    abilities.creative = false;
-                    abilities.invulnerable = false;
-                }
-                GameMode::Creative => {
-                    abilities.allow_flying = true;
-                    abilities.creative = true;
-                    abilities.invulnerable = true;
-                }
-                GameMode::Spectator => {
-                    abilities.flying = true;
-                    abilities.allow_flying = true;
-                    abilities.creative = false;
-                    abilities.invulnerable = true;
-                }
-            }
-        }
-        self.send_abilities_update().await;
-        self.living_entity
+    /// Sprinting and jumping make the player hungry, like vanilla's `checkMovementStatistics`
+    /// and `jumpFromGround`
+    pub async fn exhaust_from_movement(
+        &self,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        was_on_ground: bool,
+        on_ground: bool,
+    ) {
+        let sprinting = self
+            .living_entity
             .entity
-            .world
-            .broadcast_packet_all(&CPlayerInfoUpdate::new(
-                0x04,
-                &[pumpkin_protocol::client::play::Player {
-                    uuid: self.gameprofile.id,
-                    actions: vec![PlayerAction::UpdateGameMode((gamemode as i32).into())],
-                }],
-            ))
-            .await;
-        #[allow(clippy::cast_precision_loss)]
-        self.client
-            .send_packet(&CGameEvent::new(
-                GameEvent::ChangeGameMode,
-                gamemode as i32 as f32,
-            ))
-            .await;
+            .sprinting
+            .load(std::sync::atomic::Ordering::Relaxed);
+        let mut exhaustion = 0.0;
+        if sprinting && on_ground {
+            let distance = (to.x - from.x).hypot(to.z - from.z);
+            exhaustion += EXHAUSTION_SPRINT_PER_METER * distance as f32;
+        }
+        // Players tell when they stand on the ground, leaving it upwards is a jump
+        if was_on_ground && !on_ground && to.y > from.y {
+            exhaustion += if sprinting {
+                EXHAUSTION_SPRINT_JUMP
+            } else {
+                EXHAUSTION_JUMP
+            };
+        }
+        if exhaustion > 0.0 {
+            self.add_exhaustion(exhaustion).await;
+        }
     }
 
-    /// Send skin layers and used hand to all players
-    pub async fn update_client_information(&self) {
-        let config = self.config.lock().await;
-        let world = self.world();
-        world
-            .broadcast_packet_all(&CSetEntityMetadata::new(
-                self.entity_id().into(),
-                Metadata::new(17, 0.into(), config.skin_parts),
-            ))
-            .await;
-        world
-            .broadcast_packet_all(&CSetEntityMetadata::new(
-                self.entity_id().into(),
-                Metadata::new(18, 0.into(), config.main_hand as u8),
-            ))
-            .await;
+    /// Makes the player hungrier, players in creative never get hungry
+    pub async fn add_exhaustion(&self, exhaustion: f32) {
+        if self.abilities.lock().await.invulnerable {
+            return;
+        }
+        self.food_data.lock().await.add_exhaustion(exhaustion);
     }
 
-    pub async fn send_system_message<'a>(&self, text: &TextComponent<'a>) {
+    /// Turns exhaustion into hunger and heals or starves the player, like vanilla's
+    /// `FoodData.tick`
+    async fn tick_food(&self) {
+        let health = self.living_entity.health.load();
+        if health <= 0.0 {
+            return;
+        }
+        let world = self.world();
+        let natural_regeneration = world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::NaturalRegeneration);
+        let hunger = self
+            .living_entity
+            .effects
+            .lock()
+            .await
+            .get(StatusEffect::Hunger)
+            .map(|instance| f32::from(instance.amplifier) + 1.0);
+        if let Some(level) = hunger {
+            self.add_exhaustion(EXHAUSTION_HUNGER_EFFECT * level).await;
+        }
+        let (tick, changed) = {
+            let mut food_data = self.food_data.lock().await;
+            let before = (food_data.food_level, food_data.saturation);
+            let tick = food_data.tick(
+                world.difficulty.load(),
+                natural_regeneration,
+                health < MAX_HEALTH,
+                health,
+            );
+            (tick, before != (food_data.food_level, food_data.saturation))
+        };
+        match tick {
+            FoodTick::Heal(amount) => self.living_entity.heal(amount),
+            FoodTick::Starve => {
+                self.hurt(&DamageSource::new(DamageType::Starve), 1.0).await;
+            }
+            FoodTick::Nothing => {}
+        }
+        if changed {
+            self.send_food().await;
+        }
+    }
+
+    /// Hurts the player with what is around it: blocks like fire and cactus it is inside of, lava,
+    /// burning, drowning under water and the void below the world
+    async fn tick_environment(&self) {
+        if self.living_entity.health.load() <= 0.0 {
+            return;
+        }
+        let entity = &self.living_entity.entity;
+        let world = self.world();
+        let pos = entity.pos.load();
+        let last_pos = self.living_entity.last_pos.load();
+        let bounding_box = entity.bounding_box.load();
+
+        if is_below_world(pos.y) {
+            self.hurt(&DamageSource::new(DamageType::OutOfWorld), VOID_DAMAGE)
+                .await;
+        }
+
+        let moving = (pos.x - last_pos.x).abs() >= 0.003 || (pos.z - last_pos.z).abs() >= 0.003;
+        for state_id in world.level.blocks_inside(&bounding_box) {
+            let Some((damage_type, amount)) = block_contact_damage(state_id, moving) else {
+                continue;
+            };
+            if damage_type == DamageType::InFire {
+                self.living_entity.set_on_fire_for(FIRE_IGNITE_TICKS);
+            }
+            self.hurt(&DamageSource::new(damage_type), amount).await;
+        }
+        if world
+            .level
+            .fluid_contact(&bounding_box, Fluid::Lava)
+            .is_some()
+        {
+            self.living_entity.set_on_fire_for(LAVA_IGNITE_TICKS);
+            self.hurt(&DamageSource::new(DamageType::Lava), LAVA_DAMAGE)
+                .await;
+        }
+        let in_water = world
+            .level
+            .fluid_contact(&bounding_box, Fluid::Water)
+            .is_some();
+        if self.living_entity.tick_fire(in_water) {
+            self.hurt(&DamageSource::new(DamageType::OnFire), 1.0).await;
+        }
+
+        // Players in creative never run out of air
+        let eyes_in_water = world.level.is_in_fluid(entity.eye_position(), Fluid::Water)
+            && !self.abilities.lock().await.invulnerable;
+        let respiration = self.inventory().lock().await.armor_mut()[0]
+            .as_ref()
+            .map_or(0, |helmet| helmet.enchantment_level("respiration"));
+        if self
+            .living_entity
+            .tick_air(eyes_in_water, respiration)
+            .await
+        {
+            self.hurt(&DamageSource::new(DamageType::Drown), DROWNING_DAMAGE)
+                .await;
+        }
+    }
+
+    /// Gliding into a wall hurts, like vanilla's `handleFallFlyingCollisions`. Players only lose
+    /// a lot of speed in a single move when they hit something
+    pub async fn check_fly_into_wall(&self, from: Vector3<f64>, to: Vector3<f64>) {
+        let speed = (to.x - from.x).hypot(to.z - from.z);
+        let last_speed = self.glide_speed.swap(speed);
+        if !self
+            .living_entity
+            .entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return;
+        }
+        let damage = fly_into_wall_damage(last_speed, speed);
+        if damage > 0.0 {
+            self.hurt(&DamageSource::new(DamageType::FlyIntoWall), damage)
+                .await;
+        }
+    }
+
+    /// Adds experience levels, negative amounts take levels away. Players losing all their levels
+    /// lose their points too
+    pub async fn add_experience_levels(&self, levels: i32) {
+        self.change_experience_levels(levels);
+        self.send_experience().await;
+    }
+
+    fn change_experience_levels(&self, levels: i32) {
+        let level = self
+            .experience_level
+            .load(std::sync::atomic::Ordering::Relaxed)
+            .saturating_add(levels);
+        if level < 0 {
+            self.experience_progress.store(0.0);
+            self.total_experience
+                .store(0, std::sync::atomic::Ordering::Relaxed);
+        }
+        self.experience_level
+            .store(level.max(0), std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Adds experience points, the player levels up once the progress bar is full. Negative
+    /// amounts empty the bar and take levels away, like vanilla's `Player.giveExperiencePoints`
+    #[allow(clippy::cast_precision_loss)]
+    pub async fn add_experience_points(&self, points: i32) {
+        let needed = |player: &Self| {
+            experience_to_next_level(
+                player
+                    .experience_level
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            ) as f32
+        };
+        let mut progress = self.experience_progress.load() + points as f32 / needed(self);
+        self.total_experience
+            .fetch_update(
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+                |total| Some(total.saturating_add(points).max(0)),
+            )
+            .ok();
+        while progress < 0.0 {
+            let missing = progress * needed(self);
+            let had_levels = self
+                .experience_level
+                .load(std::sync::atomic::Ordering::Relaxed)
+                > 0;
+            self.change_experience_levels(-1);
+            progress = if had_levels {
+                1.0 + missing / needed(self)
+            } else {
+                0.0
+            };
+        }
+        while progress >= 1.0 {
+            progress = (progress - 1.0) * needed(self);
+            self.change_experience_levels(1);
+            progress /= needed(self);
+        }
+        self.experience_progress.store(progress);
+        self.send_experience().await;
+    }
+
+    /// Sets the experience level, keeping the progress towards the next one
+    pub async fn set_experience_levels(&self, level: i32) {
+        let current = self
+            .experience_level
+            .load(std::sync::atomic::Ordering::Relaxed);
+        self.add_experience_levels(level - current).await;
+    }
+
+    /// Fills the progress bar with the points, returns false if the points fill the bar of the
+    /// current level or more
+    #[allow(clippy::cast_precision_loss)]
+    pub async fn set_experience_points(&self, points: i32) -> bool {
+        let needed = experience_to_next_level(
+            self.experience_level
+                .load(std::sync::atomic::Ordering::Relaxed),
+        );
+        if points >= needed {
+            return false;
+        }
+        self.experience_progress
+            .store(points.max(0) as f32 / needed as f32);
+        self.send_experience().await;
+        true
+    }
+
+    /// The points in the progress bar towards the next level
+    #[allow(clippy::cast_precision_loss)]
+    pub fn experience_points(&self) -> i32 {
+        let needed = experience_to_next_level(
+            self.experience_level
+                .load(std::sync::atomic::Ordering::Relaxed),
+        );
+        (self.experience_progress.load() * needed as f32).floor() as i32
+    }
+
+    /// Picks up the experience orb, players only pick up an orb every other tick. Returns whether
+    /// the orb was picked up
+    pub async fn pickup_experience_orb(&self, orb: &ExperienceOrbEntity) -> bool {
+        if self.gamemode.load() == GameMode::Spectator
+            || self.living_entity.health.load() <= 0.0
+            || orb.is_removed()
+        {
+            return false;
+        }
+        if self
+            .experience_pickup_delay
+            .compare_exchange(
+                0,
+                EXPERIENCE_PICKUP_DELAY,
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return false;
+        }
+        let world = self.world();
+        orb.entity
+            .broadcast_to_trackers(&CTakeItemEntity::new(
+                orb.entity.entity_id.into(),
+                self.entity_id().into(),
+                VarInt(1),
+            ))
+            .await;
+        world
+            .play_sound(
+                sound!("entity.experience_orb.pickup"),
+                SoundCategory::Players,
+                &self.living_entity.entity.pos.load(),
+            )
+            .await;
+        orb.discard().await;
+        let points = self.repair_with_mending(orb.value).await;
+        if points > 0 {
+            self.add_experience_points(points).await;
+        }
+        true
+    }
+
+    /// Damaged items with Mending the player holds or wears use up the experience points to
+    /// repair themselves, like vanilla's `ExperienceOrb.repairPlayerItems`. Returns the points
+    /// left over
+    async fn repair_with_mending(&self, points: i32) -> i32 {
+        let mut points = points;
+        {
+            let mut inventory = self.inventory().lock().await;
+            let mut repairable: Vec<_> = inventory
+                .equipment_mut()
+                .filter_map(Option::as_mut)
+                .filter(|item| {
+                    item.enchantment_level("mending") > 0
+                        && item.components.damage.is_some_and(|damage| damage > 0)
+                })
+                .collect();
+            if repairable.is_empty() {
+                return points;
+            }
+            let mut rng = rand::thread_rng();
+            // Items which are still damaged used up all points
+            while points > 0 && !repairable.is_empty() {
+                let item = repairable.swap_remove(rng.gen_range(0..repairable.len()));
+                points = item.repair_with_experience(points);
+            }
+        }
+        self.set_container_content(None).await;
+        points
+    }
+
+    /// Tells the client the level and progress to show, e.g. after respawning
+    pub async fn send_experience(&self) {
+        self.client
+            .send_packet(&CSetExperience::new(
+                self.experience_progress.load(),
+                self.experience_level
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    .into(),
+                self.total_experience
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    .into(),
+            ))
+            .await;
+    }
+
+    /// Kills the player even in creative, like `/kill`
+    pub async fn kill(&self) {
+        self.hurt(&DamageSource::new(DamageType::GenericKill), f32::MAX)
+            .await;
+    }
+
+    /// Hurts the player unless they block it with a shield, armor protects from the damage and
+    /// loses durability, like vanilla's `Player.hurt`. Returns whether the player was hurt
+    pub async fn hurt(&self, source: &DamageSource, amount: f32) -> bool {
+        let damage_type = source.damage_type;
+        if !damage_type.bypasses_invulnerability() && self.abilities.lock().await.invulnerable {
+            return false;
+        }
+        if amount > 0.0 && self.blocks_with_shield(source) {
+            self.block_with_shield(amount).await;
+            return false;
+        }
+        let Some(amount) = self.living_entity.check_hurt(source, amount).await else {
+            return false;
+        };
+        let protection = self.hurt_armor(damage_type, amount).await;
+        if amount > 0.0 {
+            self.add_exhaustion(damage_type.exhaustion()).await;
+        }
+        self.living_entity
+            .actually_hurt(source, amount, protection)
+            .await;
+        true
+    }
+
+    /// Whether the player holds up a shield facing the damage, like vanilla's
+    /// `isDamageSourceBlocked`
+    fn blocks_with_shield(&self, source: &DamageSource) -> bool {
+        if source.damage_type.bypasses_shield() {
+            return false;
+        }
+        let Some(item_in_use) = self.item_in_use.load() else {
+            return false;
+        };
+        if item_in_use.ticks < SHIELD_BLOCK_DELAY
+            || get_item_name_by_id(item_in_use.item_id) != Some("shield")
+        {
+            return false;
+        }
+        let Some(source_position) = source.source_position() else {
+            return false;
+        };
+        let entity = &self.living_entity.entity;
+        let (yaw, pitch) = (
+            f64::from(entity.yaw.load()).to_radians(),
+            f64::from(entity.pitch.load()).to_radians(),
+        );
+        let view_direction = Vector3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+        shield_blocks(view_direction, entity.pos.load(), source_position)
+    }
+
+    /// The raised shield takes the hit, strong hits wear it down
+    async fn block_with_shield(&self, amount: f32) {
+        self.living_entity
+            .entity
+            .broadcast_to_trackers_and_self(&CEntityStatus::new(
+                self.entity_id(),
+                SHIELD_BLOCK_STATUS,
+            ))
+            .await;
+        if amount < SHIELD_DAMAGE_THRESHOLD || self.gamemode.load() == GameMode::Creative {
+            return;
+        }
+        let Some(item_in_use) = self.item_in_use.load() else {
+            return;
+        };
+        let broke = {
+            let mut inventory = self.inventory().lock().await;
+            let shield = inventory.hand_item_mut(item_in_use.offhand);
+            let broke = shield
+                .as_mut()
+                .is_some_and(|shield| shield.damage_item(1 + amount.floor() as i32));
+            if broke {
+                *shield = None;
+            }
+            broke
+        };
+        if broke {
+            self.stop_using_item();
+            self.living_entity
+                .entity
+                .broadcast_to_trackers_and_self(&CEntityStatus::new(
+                    self.entity_id(),
+                    SHIELD_BREAK_STATUS,
+                ))
+                .await;
+        }
+        self.set_container_content(None).await;
+    }
+
+    /// How well the worn armor protects from the damage, the armor loses durability unless the
+    /// damage goes through it
+    async fn hurt_armor(&self, damage_type: DamageType, amount: f32) -> Protection {
+        let mut inventory = self.inventory().lock().await;
+        let armor = inventory.armor_mut();
+        let protection = Protection::of_armor(damage_type, armor.iter().flatten());
+        if damage_type.bypasses_armor()
+            || amount <= 0.0
+            || self.gamemode.load() == GameMode::Creative
+        {
+            return protection;
+        }
+        let durability_damage = armor_durability_damage(amount);
+        let mut broken = Vec::new();
+        for (piece, slot) in armor.iter_mut().zip(ARMOR_SLOTS) {
+            if piece
+                .as_mut()
+                .is_some_and(|piece| piece.damage_item(durability_damage))
+            {
+                *piece = None;
+                broken.push(slot);
+            }
+        }
+        drop(inventory);
+        self.set_container_content(None).await;
+        for slot in broken {
+            self.broadcast_item_break(slot).await;
+        }
+        protection
+    }
+
+    /// Shows the player how they died and tells everyone else unless the world hides death
+    /// messages, like vanilla's `ServerPlayer.die`
+    async fn announce_death(&self) {
+        let source = self
+            .living_entity
+            .last_damage_source
+            .lock()
+            .await
+            .clone()
+            .unwrap_or(DamageSource::new(DamageType::Generic));
+        let world = self.world();
+        let show = world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::ShowDeathMessages);
+        let message = if show {
+            source.death_message(TextComponent::text_string(self.gameprofile.name.clone()))
+        } else {
+            TextComponent::text("")
+        };
+        self.client
+            .send_packet(&CCombatDeath::new(self.entity_id().into(), message.clone()))
+            .await;
+        if show {
+            world
+                .broadcast_packet_all(&CSystemChatMessage::new(&message, false))
+                .await;
+        }
+    }
+
+    /// Counts the death for `deathCount` objectives and the statistics, and the kill for the
+    /// player who killed them, who also triggers `player_killed_entity`, like vanilla's
+    /// `ServerPlayer.die`
+    async fn award_death_scores(&self, server: &Server) {
+        let attacker = self
+            .living_entity
+            .last_damage_source
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|source| source.attacker.as_ref().map(|attacker| attacker.entity_id));
+        let world = self.world();
+        let (killer, killed_by) = match attacker {
+            Some(attacker) => match world.get_player_by_entityid(attacker).await {
+                Some(killer) => (Some(killer), Some(EntityType::Player)),
+                None => (
+                    None,
+                    world
+                        .get_mob(attacker)
+                        .await
+                        .map(|mob| mob.entity().entity_type.clone()),
+                ),
+            },
+            None => (None, None),
+        };
+        self.award_stat(Stat::custom("deaths"), 1).await;
+        self.reset_stat(Stat::custom("time_since_death")).await;
+        self.reset_stat(Stat::custom("time_since_rest")).await;
+        if let Some(killed_by) = killed_by {
+            self.award_stat(Stat::entity(StatType::KilledBy, &killed_by), 1)
+                .await;
+        }
+        let mut scoreboard = server.scoreboard.lock().await;
+        scoreboard
+            .add_to_criterion(
+                server,
+                &self.gameprofile.name,
+                ObjectiveCriterion::DeathCount,
+                1,
+            )
+            .await;
+        if let Some(killer) = killer.filter(|killer| killer.entity_id() != self.entity_id()) {
+            for criterion in [
+                ObjectiveCriterion::PlayerKillCount,
+                ObjectiveCriterion::TotalKillCount,
+            ] {
+                scoreboard
+                    .add_to_criterion(server, &killer.gameprofile.name, criterion, 1)
+                    .await;
+            }
+            drop(scoreboard);
+            killer.award_stat(Stat::custom("player_kills"), 1).await;
+            killer
+                .award_stat(Stat::entity(StatType::Killed, &EntityType::Player), 1)
+                .await;
+            killer.trigger_killed_entity("player").await;
+        }
+    }
+
+    /// Puts the health into `health` objectives when it changed, like vanilla's
+    /// `ServerPlayer.doTick`
+    async fn update_health_score(&self, server: &Server) {
+        let living_entity = &self.living_entity;
+        let health = (living_entity.health.load() + living_entity.absorption.load()).ceil() as i32;
+        if self.last_health_score.swap(Some(health)) != Some(health) {
+            server
+                .scoreboard
+                .lock()
+                .await
+                .set_criterion(
+                    server,
+                    &self.gameprofile.name,
+                    ObjectiveCriterion::Health,
+                    health,
+                )
+                .await;
+        }
+    }
+
+    /// Everything that happens when the player dies, like vanilla's `ServerPlayer.die`: everyone
+    /// is told how they died, they drop their items and experience and lose their effects
+    async fn die(&self, server: &Server) {
+        self.announce_death().await;
+        self.award_death_scores(server).await;
+        self.stop_using_item();
+        self.drop_inventory(server).await;
+        self.drop_experience(server).await;
+        self.clear_effects().await;
+        self.living_entity.extinguish();
+    }
+
+    /// Brings the dead player back at their bed or respawn anchor, or at the spawn of the
+    /// overworld when it is gone, like vanilla's `PlayerList.respawn`. Players in hardcore come
+    /// back as spectators
+    pub async fn respawn(self: &Arc<Self>, server: &Server) {
+        let respawn_point = self.respawn_point.lock().await.clone();
+        let mut respawn = None;
+        if let Some(respawn_point) = respawn_point {
+            let size = self.living_entity.entity.bounding_box_size.load();
+            if let Some(world) = server.get_world(&respawn_point.dimension.to_string()) {
+                respawn = world
+                    .respawn_block_position(respawn_point.position, &size)
+                    .await
+                    .map(|(position, yaw)| (world, position, yaw));
+            }
+            if respawn.is_none() {
+                *self.respawn_point.lock().await = None;
+                self.client
+                    .send_packet(&CGameEvent::new(GameEvent::NoRespawnBlockAvailable, 0.0))
+                    .await;
+            }
+        }
+        let (world, position, yaw) = if let Some(respawn) = respawn {
+            respawn
+        } else {
+            let world = server.worlds[0].clone();
+            let position = world.spawn_position().await;
+            (world, position, 0.0)
+        };
+        world.respawn_player(self, position, yaw).await;
+        if BASIC_CONFIG.hardcore && self.gamemode.load() != GameMode::Spectator {
+            self.set_gamemode(GameMode::Spectator).await;
+        }
+    }
+
+    /// Gives the respawning player full health and hunger again and takes what is left from
+    /// their death, like the fresh player vanilla creates in `PlayerList.respawn`
+    pub async fn reset_after_death(&self) {
+        self.set_health(MAX_HEALTH, MAX_FOOD_LEVEL, 5.0).await;
+        let living_entity = &self.living_entity;
+        if living_entity.absorption.load() > 0.0 {
+            living_entity.set_absorption(0.0);
+        }
+        living_entity.set_air_supply(MAX_AIR_SUPPLY);
+        living_entity.fall_distance.store(0.0);
+        living_entity.extinguish();
+        self.clear_effects().await;
+        *living_entity.last_damage_source.lock().await = None;
+        self.glide_speed.store(0.0);
+        self.dead.store(false, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Drops every item of the dead player where they died, unless the world keeps inventories
+    pub async fn drop_inventory(&self, server: &Server) {
+        let world = self.world();
+        if world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::KeepInventory)
+        {
+            return;
+        }
+        // Items with Curse of Vanishing disappear instead of dropping
+        let items: Vec<_> = self
+            .inventory()
+            .lock()
+            .await
+            .slots_mut()
+            .into_iter()
+            .filter_map(Option::take)
+            .filter(|item| item.enchantment_level("vanishing_curse") == 0)
+            .collect();
+        let pos = self.living_entity.entity.pos.load();
+        for item in items {
+            world
+                .drop_item_stack(server.new_entity_id(), pos, item)
+                .await;
+        }
+        self.set_container_content(None).await;
+    }
+
+    /// Drops orbs worth 7 points per level of the dead player, at most 100 points. The player
+    /// loses all experience unless the world keeps inventories
+    pub async fn drop_experience(&self, server: &Server) {
+        let world = self.world();
+        if world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::KeepInventory)
+        {
+            return;
+        }
+        let level = self
+            .experience_level
+            .load(std::sync::atomic::Ordering::Relaxed);
+        if self.gamemode.load() != GameMode::Spectator {
+            let points = level.saturating_mul(7).min(100);
+            world
+                .award_experience(server, self.living_entity.entity.pos.load(), points)
+                .await;
+        }
+        self.experience_level
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.experience_progress.store(0.0);
+        self.total_experience
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.send_experience().await;
+    }
+
+    pub async fn set_gamemode(&self, gamemode: GameMode) {
+        // We could send the same gamemode without problems. But why waste bandwidth ?
+        assert_ne!(
+            self.gamemode.load(),
+            gamemode,
+            "Setting the same gamemode as already is"
+        );
+        self.gamemode.store(gamemode);
+        {
+            // use another scope so we instantly unlock abilities
+            let mut abilities = self.abilities.lock().await;
+            match gamemode {
+                GameMode::Undefined | GameMode::Survival | GameMode::Adventure => {
+                    abilities.flying = false;
+                    abilities.allow_flying = false;
+                    abilities.creative = false;
+                    abilities.invulnerable = false;
+                }
+                GameMode::Creative => {
+                    abilities.allow_flying = true;
+                    abilities.creative = true;
+                    abilities.invulnerable = true;
+                }
+                GameMode::Spectator => {
+                    abilities.flying = true;
+                    abilities.allow_flying = true;
+                    abilities.creative = false;
+                    abilities.invulnerable = true;
+                }
+            }
+        }
+        self.send_abilities_update().await;
+        self.living_entity
+            .entity
+            .world()
+            .broadcast_packet_all(&CPlayerInfoUpdate::new(
+                0x04,
+                &[pumpkin_protocol::client::play::Player {
+                    uuid: self.gameprofile.id,
+                    actions: vec![PlayerAction::UpdateGameMode((gamemode as i32).into())],
+                }],
+            ))
+            .await;
+        #[allow(clippy::cast_precision_loss)]
+        self.client
+            .send_packet(&CGameEvent::new(
+                GameEvent::ChangeGameMode,
+                gamemode as i32 as f32,
+            ))
+            .await;
+    }
+
+    /// Shows the skin layers and used hand of the player to all players
+    pub async fn update_client_information(&self) {
+        let config = self.config.lock().await;
+        let data = &self.living_entity.entity.data;
+        data.set(&SKIN_PARTS, config.skin_parts as i8);
+        data.set(&MAIN_HAND, config.main_hand as i8);
+    }
+
+    pub async fn send_system_message<'a>(&self, text: &TextComponent<'a>) {
         self.client
             .send_packet(&CSystemChatMessage::new(text, false))
             .await;
     }
+
+    /// Shows the message above the player's hotbar
+    pub async fn send_action_bar_message(&self, text: &TextComponent<'_>) {
+        self.client
+            .send_packet(&CSystemChatMessage::new(text, true))
+            .await;
+    }
+
+    /// Sets where the player respawns, players are told when their respawn point moves
+    pub async fn set_respawn_point(&self, respawn_point: RespawnPoint) {
+        let mut current = self.respawn_point.lock().await;
+        if current.as_ref() != Some(&respawn_point) {
+            self.send_system_message(&TextComponent::text("Respawn point set"))
+                .await;
+        }
+        *current = Some(respawn_point);
+    }
+
+    /// Gives the player the effect, see `LivingEntity::add_effect`
+    pub async fn add_effect(&self, instance: EffectInstance) -> bool {
+        if !self.living_entity.add_effect(instance).await {
+            return false;
+        }
+        if changes_movement_speed(instance.effect) {
+            self.send_movement_speed().await;
+        }
+        true
+    }
+
+    /// Takes the effect from the player, returns whether they had it
+    pub async fn remove_effect(&self, effect: StatusEffect) -> bool {
+        if !self.living_entity.remove_effect(effect).await {
+            return false;
+        }
+        if changes_movement_speed(effect) {
+            self.send_movement_speed().await;
+        }
+        true
+    }
+
+    /// Takes every effect from the player, returns the effects they had
+    pub async fn clear_effects(&self) -> Vec<EffectInstance> {
+        let removed = self.living_entity.clear_effects().await;
+        if removed
+            .iter()
+            .any(|instance| changes_movement_speed(instance.effect))
+        {
+            self.send_movement_speed().await;
+        }
+        removed
+    }
+
+    /// Tells the player how fast their speed and slowness effects make them walk, the client
+    /// moves the player itself
+    async fn send_movement_speed(&self) {
+        let multiplier = self
+            .living_entity
+            .effects
+            .lock()
+            .await
+            .movement_speed_multiplier();
+        let modifiers = if (multiplier - 1.0).abs() < f64::EPSILON {
+            Vec::new()
+        } else {
+            vec![AttributeModifier {
+                id: "minecraft:effect.speed".to_string(),
+                amount: multiplier - 1.0,
+                // Multiplies the total like vanilla's effect modifiers
+                operation: 2,
+            }]
+        };
+        let attributes = [AttributeProperty {
+            attribute_id: MOVEMENT_SPEED_ATTRIBUTE.into(),
+            base: BASE_MOVEMENT_SPEED,
+            modifiers,
+        }];
+        self.client
+            .send_packet(&CUpdateAttributes::new(
+                self.entity_id().into(),
+                &attributes,
+            ))
+            .await;
+    }
+
+    /// Eats or drinks the used item, like vanilla's `Consumable.onConsume`. Food feeds the
+    /// player and the consume effects apply, outside of creative the item is used up and leaves
+    /// its remainder behind, like the bowl of a stew
+    async fn finish_consuming(&self, server: &Server, item_in_use: ItemInUse) {
+        self.stop_using_item();
+        let Some(item) = get_item_by_id(item_in_use.item_id) else {
+            return;
+        };
+        let Some(consumable) = &item.components.consumable else {
+            return;
+        };
+        let remainder = item
+            .components
+            .use_remainder
+            .as_ref()
+            .and_then(|remainder| {
+                get_item(&remainder.id).map(|item| ItemStack::new(remainder.count, item.id))
+            });
+        let leftover = {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.hand_item_mut(item_in_use.offhand);
+            let Some(stack) = held
+                .as_mut()
+                .filter(|held| held.item_id == item_in_use.item_id)
+            else {
+                return;
+            };
+            if self.gamemode.load() == GameMode::Creative {
+                None
+            } else {
+                stack.item_count -= 1;
+                if stack.item_count == 0 {
+                    *held = remainder;
+                    None
+                } else {
+                    remainder.and_then(|remainder| inventory.insert_stack(remainder))
+                }
+            }
+        };
+        if let Some(leftover) = leftover {
+            self.drop_item(server, leftover).await;
+        }
+        self.set_container_content(None).await;
+        self.client
+            .send_packet(&CEntityStatus::new(
+                self.entity_id(),
+                USE_ITEM_COMPLETE_STATUS,
+            ))
+            .await;
+
+        let world = self.world();
+        let pos = self.living_entity.entity.pos.load();
+        let sound = match consumable.animation {
+            UseAnimation::Drink => sound!("entity.generic.drink"),
+            UseAnimation::Eat | UseAnimation::Other => sound!("entity.generic.eat"),
+        };
+        world.play_sound(sound, SoundCategory::Players, &pos).await;
+        if let Some(food) = &item.components.food {
+            self.food_data
+                .lock()
+                .await
+                .eat(food.nutrition, food.saturation);
+            self.send_food().await;
+            world
+                .play_sound(sound!("entity.player.burp"), SoundCategory::Players, &pos)
+                .await;
+        }
+        self.apply_consume_effects(&consumable.on_consume_effects)
+            .await;
+    }
+
+    async fn apply_consume_effects(&self, consume_effects: &[ConsumeEffect]) {
+        for consume_effect in consume_effects {
+            match consume_effect {
+                ConsumeEffect::ApplyEffects {
+                    effects,
+                    probability,
+                } => {
+                    if rand::random::<f32>() >= *probability {
+                        continue;
+                    }
+                    for effect in effects {
+                        if let Some(status_effect) = StatusEffect::from_name(&effect.id) {
+                            self.add_effect(EffectInstance::new(
+                                status_effect,
+                                effect.duration,
+                                effect.amplifier,
+                            ))
+                            .await;
+                        }
+                    }
+                }
+                ConsumeEffect::RemoveEffects { effects } => {
+                    for status_effect in effects.iter().filter_map(StatusEffect::from_name) {
+                        self.remove_effect(status_effect).await;
+                    }
+                }
+                ConsumeEffect::ClearAllEffects => {
+                    self.clear_effects().await;
+                }
+                ConsumeEffect::Other => {}
+            }
+        }
+    }
+
+    /// Starts using the held item, other players see the player draw their bow or raise their
+    /// trident
+    pub fn start_using_item(&self, offhand: bool, item_id: u16) {
+        self.item_in_use.store(Some(ItemInUse {
+            offhand,
+            item_id,
+            ticks: 0,
+        }));
+        let flags = if offhand {
+            USING_ITEM_FLAG | OFFHAND_FLAG
+        } else {
+            USING_ITEM_FLAG
+        };
+        self.living_entity.entity.data.set(&LIVING_FLAGS, flags);
+    }
+
+    /// Stops using the item, returns what was used and for how long
+    pub fn stop_using_item(&self) -> Option<ItemInUse> {
+        let item_in_use = self.item_in_use.take()?;
+        self.living_entity.entity.data.set(&LIVING_FLAGS, 0);
+        Some(item_in_use)
+    }
+
+    /// Whether the player lies in a bed
+    pub fn is_sleeping(&self) -> bool {
+        self.sleeping_position.load().is_some()
+    }
+
+    /// Lays the player into the bed with its head at the position, like vanilla's
+    /// `LivingEntity.startSleeping`. Sleeping resets the time since the player rested
+    pub async fn start_sleeping(&self, bed: WorldPosition) {
+        self.living_entity.set_pos(Vector3::new(
+            f64::from(bed.0.x) + 0.5,
+            f64::from(bed.0.y) + 0.6875,
+            f64::from(bed.0.z) + 0.5,
+        ));
+        self.sleeping_position.store(Some(bed));
+        self.sleep_ticks
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        self.reset_stat(Stat::custom("time_since_rest")).await;
+        self.award_stat(Stat::custom("sleep_in_bed"), 1).await;
+        let entity = &self.living_entity.entity;
+        entity.set_pose(EntityPose::Sleeping);
+        entity.data.set(&SLEEPING_POSITION, Some(bed));
+    }
+
+    /// Gets the player out of their bed and stands them up on it, like vanilla's
+    /// `LivingEntity.stopSleeping`
+    pub async fn wake_up(&self) {
+        let Some(bed) = self.sleeping_position.take() else {
+            return;
+        };
+        let world = self.world();
+        world.set_bed_occupied(bed, false).await;
+        self.sleep_ticks
+            .store(0, std::sync::atomic::Ordering::Relaxed);
+        let entity = &self.living_entity.entity;
+        entity
+            .broadcast_to_trackers_and_self(&CEntityAnimation::new(
+                self.entity_id().into(),
+                Animation::LeaveBed as u8,
+            ))
+            .await;
+        entity.set_pose(EntityPose::Standing);
+        entity.data.set(&SLEEPING_POSITION, None);
+        let position = Vector3::new(
+            f64::from(bed.0.x) + 0.5,
+            f64::from(bed.0.y) + 0.5625,
+            f64::from(bed.0.z) + 0.5,
+        );
+        self.request_teleport(position, entity.yaw.load(), entity.pitch.load())
+            .await;
+    }
 }
 
 impl Player {
@@ -700,14 +2252,15 @@ impl Player {
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(SClientCommand::read(bytebuf)?)
+                self.handle_client_status(server, SClientCommand::read(bytebuf)?)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
                 // TODO
             }
             SInteract::PACKET_ID => {
-                self.handle_interact(SInteract::read(bytebuf)?).await;
+                self.handle_interact(server, SInteract::read(bytebuf)?)
+                    .await;
             }
             SKeepAlive::PACKET_ID => {
                 self.handle_keep_alive(SKeepAlive::read(bytebuf)?).await;
@@ -757,7 +2310,7 @@ impl Player {
                     .await;
             }
             SSetCreativeSlot::PACKET_ID => {
-                self.handle_set_creative_slot(SSetCreativeSlot::read(bytebuf)?)
+                self.handle_set_creative_slot(server, SSetCreativeSlot::read(bytebuf)?)
                     .await?;
             }
             SSwingArm::PACKET_ID => {
@@ -767,7 +2320,10 @@ impl Player {
                 self.handle_use_item_on(SUseItemOn::read(bytebuf)?, server)
                     .await?;
             }
-            SUseItem::PACKET_ID => self.handle_use_item(&SUseItem::read(bytebuf)?),
+            SUseItem::PACKET_ID => {
+                self.handle_use_item(&SUseItem::read(bytebuf)?, server)
+                    .await;
+            }
             SCommandSuggestion::PACKET_ID => {
                 self.handle_command_suggestion(SCommandSuggestion::read(bytebuf)?, server)
                     .await;
@@ -779,6 +2335,34 @@ impl Player {
                 self.handle_close_container(server, SCloseContainer::read(bytebuf)?)
                     .await;
             }
+            SBundleItemSelected::PACKET_ID => {
+                self.handle_bundle_item_selected(server, SBundleItemSelected::read(bytebuf)?)
+                    .await;
+            }
+            SRenameItem::PACKET_ID => {
+                self.handle_rename_item(server, SRenameItem::read(bytebuf)?)
+                    .await;
+            }
+            SSelectTrade::PACKET_ID => {
+                self.handle_select_trade(server, SSelectTrade::read(bytebuf)?)
+                    .await;
+            }
+            SContainerButtonClick::PACKET_ID => {
+                self.handle_container_button_click(server, SContainerButtonClick::read(bytebuf)?)
+                    .await;
+            }
+            SRecipeBookChangeSettings::PACKET_ID => {
+                self.handle_recipe_book_change_settings(SRecipeBookChangeSettings::read(bytebuf)?)
+                    .await;
+            }
+            SRecipeBookSeenRecipe::PACKET_ID => {
+                self.handle_recipe_book_seen_recipe(SRecipeBookSeenRecipe::read(bytebuf)?)
+                    .await;
+            }
+            SPlaceRecipe::PACKET_ID => {
+                self.handle_place_recipe(server, SPlaceRecipe::read(bytebuf)?)
+                    .await;
+            }
             _ => {
                 log::warn!("Failed to handle player packet id {}", packet.id.0);
                 // TODO: We give an error if all play packets are implemented
@@ -789,6 +2373,49 @@ impl Player {
     }
 }
 
+#[async_trait]
+impl TrackedEntity for Player {
+    fn tracked_entity(&self) -> &Entity {
+        &self.living_entity.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        client
+            .send_packet(&CSpawnEntity::new(
+                entity.entity_id.into(),
+                self.gameprofile.id,
+                (EntityType::Player as i32).into(),
+                pos.x,
+                pos.y,
+                pos.z,
+                entity.pitch.load(),
+                entity.yaw.load(),
+                entity.head_yaw.load(),
+                0.into(),
+                0.0,
+                0.0,
+                0.0,
+            ))
+            .await;
+        if let Some(packet) = entity.data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
+
+/// The experience points needed to fill the progress bar at the given level
+const fn experience_to_next_level(level: i32) -> i32 {
+    if level >= 30 {
+        112 + (level - 30) * 9
+    } else if level >= 15 {
+        37 + (level - 15) * 5
+    } else {
+        7 + level * 2
+    }
+}
+
 /// Represents a player's abilities and special powers.
 ///
 /// This struct contains information about the player's current abilities, such as flight, invulnerability, and creative mode.
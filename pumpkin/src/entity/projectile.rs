@@ -0,0 +1,919 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::{
+    math::{
+        boundingbox::{BoundingBox, BoundingBoxSize},
+        position::WorldPosition,
+        vector3::Vector3,
+    },
+    GameMode,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_macros::sound;
+use pumpkin_protocol::{
+    client::play::{
+        CEntityStatus, CEntityVelocity, CGameEvent, CHurtAnimation, CLevelEvent, CSpawnEntity,
+        GameEvent,
+    },
+    codec::var_int::VarInt,
+    SoundCategory,
+};
+use pumpkin_world::{
+    block::fluid::{fluid_state, Fluid},
+    damage::DamageType,
+    item::{item_registry::get_item_name_by_id, ItemStack},
+    physics::{is_below_world, BlockHit},
+    world_info::game_rules::BoolRule,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{
+    damage::{DamageEntity, DamageSource},
+    data::DataField,
+    mob::MobEntity,
+    player::Player,
+    tracker::TrackedEntity,
+    Entity,
+};
+
+/// Damage of arrows flying a block per tick, faster arrows do more
+pub const ARROW_BASE_DAMAGE: f64 = 2.0;
+/// Damage of thrown tridents
+const TRIDENT_DAMAGE: f32 = 8.0;
+/// Damage the player teleported by an ender pearl takes
+const ENDER_PEARL_DAMAGE: f32 = 5.0;
+/// Ticks arrows stay stuck in a block until they despawn, a minute
+const DESPAWN_TIME: u32 = 1200;
+/// Ticks arrows shake after hitting a block, they can't be picked up meanwhile
+const SHAKE_TIME: u32 = 7;
+/// Ticks tridents with Loyalty stay stuck in a block before they fly back
+const LOYALTY_GROUND_TIME: u32 = 4;
+/// Entities are this much larger for projectiles, like in vanilla's `ProjectileUtil`
+const HIT_BOX_MARGIN: f64 = 0.3;
+/// The color of water bottles, the splash of bottles o' enchanting uses it
+const WATER_POTION_COLOR: i32 = -13_083_194;
+/// The critical and no clip flags of arrows and tridents
+const FLAGS: DataField<i8> = DataField::new(8);
+const IN_GROUND: DataField<bool> = DataField::new(10);
+const LOYALTY: DataField<i8> = DataField::new(11);
+/// Whether a trident is enchanted and glints
+const FOIL: DataField<bool> = DataField::new(12);
+const CRITICAL_FLAG: i8 = 0x01;
+const NO_CLIP_FLAG: i8 = 0x02;
+
+/// How far a bow drawn for the ticks shoots, from 0 to 1 once it is fully drawn like vanilla's
+/// `BowItem.getPowerForTime`
+#[must_use]
+pub fn bow_power(ticks: u32) -> f64 {
+    let seconds = f64::from(ticks) / 20.0;
+    (seconds.mul_add(seconds, seconds * 2.0) / 3.0).min(1.0)
+}
+
+/// Whether bows shoot the item, tipped arrows fly like normal ones
+#[must_use]
+pub fn is_arrow(item_stack: &ItemStack) -> bool {
+    matches!(
+        get_item_name_by_id(item_stack.item_id),
+        Some("arrow" | "tipped_arrow")
+    )
+}
+
+/// The kinds of projectiles, they fly and hit things differently
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectileKind {
+    Arrow,
+    Trident,
+    Snowball,
+    Egg,
+    EnderPearl,
+    ExperienceBottle,
+}
+
+impl ProjectileKind {
+    /// The projectile thrown when using the item, `None` for items which are not thrown
+    #[must_use]
+    pub fn thrown_from(item_name: &str) -> Option<Self> {
+        match item_name {
+            "snowball" => Some(Self::Snowball),
+            "egg" => Some(Self::Egg),
+            "ender_pearl" => Some(Self::EnderPearl),
+            "experience_bottle" => Some(Self::ExperienceBottle),
+            _ => None,
+        }
+    }
+
+    const fn entity_type(self) -> EntityType {
+        match self {
+            Self::Arrow => EntityType::Arrow,
+            Self::Trident => EntityType::Trident,
+            Self::Snowball => EntityType::Snowball,
+            Self::Egg => EntityType::Egg,
+            Self::EnderPearl => EntityType::EnderPearl,
+            Self::ExperienceBottle => EntityType::ExperienceBottle,
+        }
+    }
+
+    /// Arrows and tridents stick in the blocks they hit, the rest breaks
+    const fn sticks(self) -> bool {
+        matches!(self, Self::Arrow | Self::Trident)
+    }
+
+    /// Blocks per tick squared the projectile accelerates downwards
+    const fn gravity(self) -> f64 {
+        match self {
+            Self::Arrow | Self::Trident => 0.05,
+            Self::ExperienceBottle => 0.07,
+            _ => 0.03,
+        }
+    }
+
+    /// Velocity kept every tick under water, tridents are made for it
+    const fn water_drag(self) -> f64 {
+        match self {
+            Self::Arrow => 0.6,
+            Self::Trident => 0.99,
+            _ => 0.8,
+        }
+    }
+}
+
+/// Who can pick up an arrow or trident, like vanilla's `AbstractArrow.Pickup`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pickup {
+    Disallowed,
+    Allowed,
+    /// Arrows shot in creative or with Infinity only disappear when picked up
+    CreativeOnly,
+}
+
+/// An entity a projectile hits
+enum Target<'a> {
+    Player(&'a Arc<Player>),
+    Mob(&'a Arc<MobEntity>),
+}
+
+impl Target<'_> {
+    fn entity(&self) -> &Entity {
+        match self {
+            Self::Player(player) => &player.living_entity.entity,
+            Self::Mob(mob) => mob.entity(),
+        }
+    }
+}
+
+/// An arrow, trident or thrown item flying through the world
+pub struct ProjectileEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    pub kind: ProjectileKind,
+    /// The entity which shot or threw the projectile
+    owner: Option<EntityId>,
+    /// What players get back when picking the projectile up, like the trident thrown
+    item_stack: Option<ItemStack>,
+    pickup: Pickup,
+    /// Damage of arrows flying a block per tick, Power adds to it
+    base_damage: AtomicCell<f64>,
+    /// Extra knockback from Punch
+    knockback: AtomicCell<f64>,
+    /// Whether the arrow was shot with a fully drawn bow, it leaves a trail of crit particles
+    critical: AtomicBool,
+    /// Whether the projectile flew out of its owner, it doesn't hit its owner before
+    left_owner: AtomicBool,
+    /// The block the projectile is stuck in and its state, it falls once the block changes
+    in_ground: AtomicCell<Option<(WorldPosition, u16)>>,
+    /// Ticks the projectile is stuck in a block
+    ground_time: AtomicU32,
+    /// Ticks until the arrow stops shaking after hitting a block
+    shake: AtomicU32,
+    /// Whether the trident hit an entity, it only hits one
+    dealt_damage: AtomicBool,
+    /// Whether the trident flies back to its owner
+    returning: AtomicBool,
+    /// Whether the projectile hit something, was picked up or despawned
+    removed: AtomicBool,
+}
+
+impl ProjectileEntity {
+    /// A projectile coming out of the eyes of its owner, it flies once it is shot
+    pub fn new(
+        entity_id: EntityId,
+        world: Arc<World>,
+        kind: ProjectileKind,
+        owner: Option<&Entity>,
+        item_stack: Option<ItemStack>,
+        pickup: Pickup,
+    ) -> Self {
+        let size = if kind.sticks() { 0.5 } else { 0.25 };
+        let bounding_box_size = BoundingBoxSize {
+            width: size,
+            height: size,
+        };
+        let entity = Entity::new(
+            entity_id,
+            world,
+            kind.entity_type(),
+            0.0,
+            AtomicCell::new(BoundingBox::new_default(&bounding_box_size)),
+            AtomicCell::new(bounding_box_size),
+        );
+        if let Some(owner) = owner {
+            let pos = owner.pos.load();
+            entity.set_pos(Vector3::new(
+                pos.x,
+                pos.y + f64::from(owner.standing_eye_height) - 0.1,
+                pos.z,
+            ));
+        }
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            kind,
+            owner: owner.map(|owner| owner.entity_id),
+            item_stack,
+            pickup,
+            base_damage: AtomicCell::new(ARROW_BASE_DAMAGE),
+            knockback: AtomicCell::new(0.0),
+            critical: AtomicBool::new(false),
+            left_owner: AtomicBool::new(owner.is_none()),
+            in_ground: AtomicCell::new(None),
+            ground_time: AtomicU32::new(0),
+            shake: AtomicU32::new(0),
+            dealt_damage: AtomicBool::new(false),
+            returning: AtomicBool::new(false),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    pub const fn owner(&self) -> Option<EntityId> {
+        self.owner
+    }
+
+    pub const fn item_stack(&self) -> Option<&ItemStack> {
+        self.item_stack.as_ref()
+    }
+
+    pub const fn pickup(&self) -> Pickup {
+        self.pickup
+    }
+
+    pub fn set_critical(&self, critical: bool) {
+        self.critical.store(critical, Ordering::Relaxed);
+    }
+
+    pub fn set_base_damage(&self, damage: f64) {
+        self.base_damage.store(damage);
+    }
+
+    pub fn set_knockback(&self, knockback: f64) {
+        self.knockback.store(knockback);
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Relaxed)
+    }
+
+    /// Whether the trident flies back to its owner, only its owner can catch it then
+    pub fn is_returning(&self) -> bool {
+        self.returning.load(Ordering::Relaxed)
+    }
+
+    /// Arrows and tridents can be picked up once they stopped shaking in a block, returning
+    /// tridents can be caught
+    pub fn can_be_picked_up(&self) -> bool {
+        self.kind.sticks()
+            && !self.is_removed()
+            && (self.in_ground.load().is_some() || self.is_returning())
+            && self.shake.load(Ordering::Relaxed) == 0
+    }
+
+    /// The level of Loyalty of the trident, tridents with Loyalty fly back to their owner
+    fn loyalty(&self) -> i32 {
+        self.item_stack
+            .as_ref()
+            .filter(|_| self.kind == ProjectileKind::Trident)
+            .map_or(0, |item_stack| item_stack.enchantment_level("loyalty"))
+    }
+
+    /// Removes the projectile from the world for good, e.g. once it hit something
+    pub async fn discard(&self) {
+        if !self.removed.swap(true, Ordering::Relaxed) {
+            self.entity.world().remove_projectile(self).await;
+        }
+    }
+
+    /// Launches the projectile where the shooter looks, like vanilla's
+    /// `Projectile.shootFromRotation`. Inaccuracy spreads projectiles a little
+    pub fn shoot_from_rotation(
+        &self,
+        shooter: &Entity,
+        pitch_offset: f32,
+        power: f64,
+        inaccuracy: f64,
+    ) {
+        let (yaw_sin, yaw_cos) = f64::from(shooter.yaw.load()).to_radians().sin_cos();
+        let pitch = shooter.pitch.load();
+        let pitch_cos = f64::from(pitch).to_radians().cos();
+        let direction = Vector3::new(
+            -yaw_sin * pitch_cos,
+            -f64::from(pitch + pitch_offset).to_radians().sin(),
+            yaw_cos * pitch_cos,
+        );
+        self.shoot(direction, power, inaccuracy);
+    }
+
+    /// Launches the projectile in the direction, like vanilla's `Projectile.shoot`
+    pub fn shoot(&self, direction: Vector3<f64>, power: f64, inaccuracy: f64) {
+        let spread = 0.017_227_5 * inaccuracy;
+        let deviation = {
+            let mut rng = thread_rng();
+            let mut triangle = || (rng.gen::<f64>() - rng.gen::<f64>()) * spread;
+            Vector3::new(triangle(), triangle(), triangle())
+        };
+        let velocity = (direction.normalize() + deviation) * power;
+        self.entity.velocity.store(velocity);
+        self.face(velocity);
+    }
+
+    /// Turns the projectile into the direction it flies
+    fn face(&self, velocity: Vector3<f64>) {
+        let horizontal = velocity.x.hypot(velocity.z);
+        self.entity.set_rotation(
+            velocity.x.atan2(velocity.z).to_degrees() as f32,
+            velocity.y.atan2(horizontal).to_degrees() as f32,
+        );
+    }
+
+    /// Moves the projectile and lets it hit the entities and blocks in its way, arrows stuck in a
+    /// block despawn after a while. Returns false once the projectile was removed
+    pub async fn tick(
+        &self,
+        server: &Server,
+        players: &[Arc<Player>],
+        mobs: &[Arc<MobEntity>],
+    ) -> bool {
+        if self.is_removed() {
+            return false;
+        }
+        let world = self.entity.world();
+        let pos = self.entity.pos.load();
+        if !world.level.is_loaded(pos) {
+            return true;
+        }
+        // Fails once the arrow stopped shaking, which leaves it at zero
+        let _ = self
+            .shake
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |shake| {
+                shake.checked_sub(1)
+            });
+        let owner = self
+            .owner
+            .and_then(|owner| players.iter().find(|player| player.entity_id() == owner));
+        // Skeletons shoot arrows too
+        let owner_entity = owner.map(|owner| &owner.living_entity.entity).or_else(|| {
+            let owner = self.owner?;
+            mobs.iter()
+                .map(|mob| mob.entity())
+                .find(|mob| mob.entity_id == owner)
+        });
+        self.check_left_owner(owner_entity);
+
+        if let Some((block, state_id)) = self.in_ground.load() {
+            if world.get_block_state_id(block).await.ok() == Some(state_id) {
+                let ground_time = self.ground_time.fetch_add(1, Ordering::Relaxed) + 1;
+                if self.kind == ProjectileKind::Arrow && ground_time >= DESPAWN_TIME {
+                    self.discard().await;
+                    return false;
+                }
+                if self.loyalty() == 0 || ground_time <= LOYALTY_GROUND_TIME {
+                    return true;
+                }
+            } else {
+                // The block is gone, the arrow falls down
+                self.in_ground.store(None);
+                self.ground_time.store(0, Ordering::Relaxed);
+                self.update_data();
+            }
+        }
+
+        let loyalty = self.loyalty();
+        if loyalty > 0
+            && self.owner.is_some()
+            && (self.is_returning()
+                || self.dealt_damage.load(Ordering::Relaxed)
+                || self.in_ground.load().is_some())
+        {
+            return self.tick_return(server, owner, loyalty).await;
+        }
+
+        let to = pos + self.entity.velocity.load();
+        let block_hit = world.level.clip_blocks(pos, to);
+        let end = block_hit.map_or(to, |hit| hit.location);
+        if let Some(target) = self.find_target(pos, end, players, mobs) {
+            self.hit_entity(server, &target, owner).await;
+        } else if let Some(hit) = block_hit {
+            self.hit_block(server, hit, owner).await;
+        }
+        if self.is_removed() {
+            return false;
+        }
+        if self.in_ground.load().is_some() {
+            return true;
+        }
+
+        let velocity = self.entity.velocity.load();
+        let in_water = world
+            .level
+            .blocks_inside(&self.entity.bounding_box.load())
+            .into_iter()
+            .filter_map(fluid_state)
+            .any(|fluid| fluid.fluid == Fluid::Water);
+        let drag = if in_water {
+            self.kind.water_drag()
+        } else {
+            0.99
+        };
+        self.entity.set_pos(pos + velocity);
+        self.face(velocity);
+        let mut velocity = velocity * drag;
+        velocity.y -= self.kind.gravity();
+        self.entity.velocity.store(velocity);
+        if is_below_world(self.entity.pos.load().y) {
+            self.discard().await;
+            return false;
+        }
+        self.entity.sync_movement().await;
+        true
+    }
+
+    /// Projectiles only hit their owner once they flew out of them, so players don't shoot
+    /// themselves
+    fn check_left_owner(&self, owner: Option<&Entity>) {
+        if self.left_owner.load(Ordering::Relaxed) {
+            return;
+        }
+        let inside = owner.is_some_and(|owner| {
+            owner
+                .bounding_box
+                .load()
+                .intersects(&self.entity.bounding_box.load().inflate(1.0))
+        });
+        if !inside {
+            self.left_owner.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// The closest living entity on the line the projectile flies along, like vanilla's
+    /// `ProjectileUtil.getEntityHitResult`. Spectators can't be hit
+    fn find_target<'a>(
+        &self,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        players: &'a [Arc<Player>],
+        mobs: &'a [Arc<MobEntity>],
+    ) -> Option<Target<'a>> {
+        // Tridents fall down after hitting an entity
+        if self.dealt_damage.load(Ordering::Relaxed) {
+            return None;
+        }
+        let left_owner = self.left_owner.load(Ordering::Relaxed);
+        players
+            .iter()
+            .filter(|player| {
+                player.gamemode.load() != GameMode::Spectator
+                    && player.living_entity.health.load() > 0.0
+            })
+            .map(Target::Player)
+            .chain(
+                mobs.iter()
+                    .filter(|mob| mob.living_entity.health.load() > 0.0)
+                    .map(Target::Mob),
+            )
+            .filter(|target| left_owner || Some(target.entity().entity_id) != self.owner)
+            .filter_map(|target| {
+                let hit_box = target.entity().bounding_box.load().inflate(HIT_BOX_MARGIN);
+                hit_box.clip(from, to).map(|distance| (distance, target))
+            })
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, target)| target)
+    }
+
+    /// Hurts the entity hit, arrows stay in it and thrown projectiles break. Arrows bounce off
+    /// entities which can't be hurt right now
+    async fn hit_entity(&self, server: &Server, target: &Target<'_>, owner: Option<&Arc<Player>>) {
+        let world = self.entity.world();
+        let velocity = self.entity.velocity.load();
+        let pos = self.entity.pos.load();
+        match self.kind {
+            ProjectileKind::Arrow => {
+                let mut damage = (velocity.length() * self.base_damage.load())
+                    .clamp(0.0, f64::from(i32::MAX))
+                    .ceil() as i32;
+                if self.critical.load(Ordering::Relaxed) {
+                    let bonus = thread_rng().gen_range(0..damage / 2 + 2);
+                    damage = damage.saturating_add(bonus);
+                }
+                if self
+                    .hurt(
+                        server,
+                        target,
+                        f64::from(damage) as f32,
+                        DamageType::Arrow,
+                        owner,
+                    )
+                    .await
+                {
+                    // Arrow
+                    world
+                        .play_sound(sound!("entity.arrow.hit"), SoundCategory::Neutral, &pos)
+                        .await;
+                    if let (Some(owner), Target::Player(player)) = (owner, target) {
+                        if owner.entity_id() != player.entity_id() {
+                            owner
+                                .client
+                                .send_packet(&CGameEvent::new(GameEvent::ArrowHitPlayer, 0.0))
+                                .await;
+                        }
+                    }
+                    self.discard().await;
+                    return;
+                }
+                let velocity = velocity * -0.1;
+                if velocity.length_squared() < 1.0E-7 {
+                    self.discard().await;
+                    return;
+                }
+                self.entity.set_velocity(velocity);
+                self.face(velocity);
+            }
+            ProjectileKind::Trident => {
+                self.dealt_damage.store(true, Ordering::Relaxed);
+                self.hurt(server, target, TRIDENT_DAMAGE, DamageType::Trident, owner)
+                    .await;
+                self.entity
+                    .set_velocity(velocity.multiply(-0.01, -0.1, -0.01));
+                world
+                    .play_sound(sound!("item.trident.hit"), SoundCategory::Players, &pos)
+                    .await;
+            }
+            _ => {
+                // Snowballs only hurt blazes, the rest still knocks back
+                let damage = if self.kind == ProjectileKind::Snowball
+                    && matches!(target.entity().entity_type, EntityType::Blaze)
+                {
+                    3.0
+                } else {
+                    0.0
+                };
+                self.hurt(server, target, damage, DamageType::Thrown, owner)
+                    .await;
+                self.impact(server, owner).await;
+            }
+        }
+    }
+
+    /// Hurts the entity and pushes it in the direction the projectile flies, like vanilla's
+    /// `LivingEntity.hurt` for projectiles. Returns whether the entity was hurt, players don't hurt
+    /// their teammates without friendly fire
+    async fn hurt(
+        &self,
+        server: &Server,
+        target: &Target<'_>,
+        damage: f32,
+        damage_type: DamageType,
+        owner: Option<&Arc<Player>>,
+    ) -> bool {
+        let source = DamageSource::projectile(
+            damage_type,
+            DamageEntity::entity(&self.entity),
+            owner.map(|owner| DamageEntity::player(owner)),
+        );
+        if let (Some(owner), Target::Player(player)) = (owner, target) {
+            if !server
+                .scoreboard
+                .lock()
+                .await
+                .can_harm(&owner.gameprofile.name, &player.gameprofile.name)
+            {
+                return false;
+            }
+        }
+        // Projectiles without damage still hit entities which are not invulnerable after a hit
+        let hurt = match target {
+            Target::Player(player) => player.hurt(&source, damage).await,
+            Target::Mob(mob) => mob.hurt(&source, damage).await,
+        };
+        if !hurt {
+            return false;
+        }
+        if let Target::Mob(mob) = target {
+            mob.hurt_by(owner.map(|owner| owner.entity_id()));
+            if owner.is_some() {
+                mob.hurt_by_player();
+            }
+        }
+
+        let victim = target.entity();
+        let entity_id = VarInt(victim.entity_id);
+        victim
+            .broadcast_to_trackers_and_self(&CHurtAnimation::new(
+                &entity_id,
+                self.entity.yaw.load(),
+            ))
+            .await;
+        let velocity = self.entity.velocity.load();
+        // Players move themselves, they only get their new velocity
+        let saved_velocity = victim.velocity.load();
+        victim.knockback(0.4, -velocity.x, -velocity.z);
+        let knockback = self.knockback.load();
+        if knockback > 0.0 {
+            let horizontal = Vector3::new(velocity.x, 0.0, velocity.z);
+            if horizontal.length_squared() > 0.0 {
+                let push = horizontal.normalize() * (knockback * 0.6);
+                victim
+                    .velocity
+                    .store(victim.velocity.load() + Vector3::new(push.x, 0.1, push.z));
+            }
+        }
+        match target {
+            Target::Player(player) => {
+                let knocked = victim.velocity.load();
+                victim.velocity.store(saved_velocity);
+                player
+                    .client
+                    .send_packet(&CEntityVelocity::new(
+                        &entity_id, knocked.x, knocked.y, knocked.z,
+                    ))
+                    .await;
+            }
+            Target::Mob(_) => victim.set_velocity(victim.velocity.load()),
+        }
+        true
+    }
+
+    /// Sticks arrows and tridents into the block they hit, thrown projectiles break
+    async fn hit_block(&self, server: &Server, hit: BlockHit, owner: Option<&Arc<Player>>) {
+        if !self.kind.sticks() {
+            self.impact(server, owner).await;
+            return;
+        }
+        let world = self.entity.world();
+        let Ok(state_id) = world.get_block_state_id(hit.position).await else {
+            return;
+        };
+        let velocity = self.entity.velocity.load();
+        // Just outside of the block, so the arrow is drawn sticking out of it
+        let position = hit.location.sub(&(velocity.normalize() * 0.05));
+        self.in_ground.store(Some((hit.position, state_id)));
+        self.ground_time.store(0, Ordering::Relaxed);
+        self.shake.store(SHAKE_TIME, Ordering::Relaxed);
+        self.critical.store(false, Ordering::Relaxed);
+        self.entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+        self.entity
+            .teleport(position, self.entity.yaw.load(), self.entity.pitch.load())
+            .await;
+        self.update_data();
+        let sound = if self.kind == ProjectileKind::Trident {
+            sound!("item.trident.hit_ground")
+        } else {
+            sound!("entity.arrow.hit")
+        };
+        world
+            .play_sound(sound, SoundCategory::Neutral, &position)
+            .await;
+    }
+
+    /// Breaks a thrown projectile where it hit something, like the `onHit` of vanilla's
+    /// snowballs, eggs, ender pearls and bottles o' enchanting
+    async fn impact(&self, server: &Server, owner: Option<&Arc<Player>>) {
+        let world = self.entity.world();
+        let pos = self.entity.pos.load();
+        match self.kind {
+            ProjectileKind::Snowball | ProjectileKind::Egg => {
+                // Item particles
+                self.entity
+                    .broadcast_to_trackers(&CEntityStatus::new(self.entity.entity_id, 3))
+                    .await;
+                if self.kind == ProjectileKind::Egg {
+                    self.hatch(server, &world).await;
+                }
+            }
+            ProjectileKind::EnderPearl => {
+                if let Some(owner) = owner {
+                    self.teleport_owner(server, &world, owner).await;
+                }
+            }
+            ProjectileKind::ExperienceBottle => {
+                world
+                    .broadcast_packet_all(&CLevelEvent::new(
+                        2002,
+                        self.entity.block_pos.load(),
+                        WATER_POTION_COLOR,
+                        false,
+                    ))
+                    .await;
+                let points = {
+                    let mut rng = thread_rng();
+                    3 + rng.gen_range(0..5) + rng.gen_range(0..5)
+                };
+                world.award_experience(server, pos, points).await;
+            }
+            ProjectileKind::Arrow | ProjectileKind::Trident => {}
+        }
+        self.discard().await;
+    }
+
+    /// Eggs sometimes hatch a chicken and rarely four, like vanilla's `ThrownEgg.onHit`
+    async fn hatch(&self, server: &Server, world: &Arc<World>) {
+        let chickens = {
+            let mut rng = thread_rng();
+            match (rng.gen_range(0..8), rng.gen_range(0..32)) {
+                (0, 0) => 4,
+                (0, _) => 1,
+                _ => 0,
+            }
+        };
+        // TODO: Hatch baby chickens once mobs can grow up
+        for _ in 0..chickens {
+            let chicken = MobEntity::new(
+                server.new_entity_id(),
+                world.clone(),
+                EntityType::Chicken,
+                self.entity.pos.load(),
+                self.entity.yaw.load(),
+            );
+            world.add_mob(Arc::new(chicken)).await;
+        }
+    }
+
+    /// Teleports the player who threw the ender pearl to where it landed and hurts them a bit.
+    /// Sometimes an endermite comes along, like vanilla's `ThrownEnderpearl.onHit`
+    async fn teleport_owner(&self, server: &Server, world: &Arc<World>, owner: &Player) {
+        if owner.living_entity.health.load() <= 0.0 || owner.is_sleeping() {
+            return;
+        }
+        let pos = self.entity.pos.load();
+        let entity = &owner.living_entity.entity;
+        if rand::random::<f32>() < 0.05
+            && world
+                .game_rules
+                .lock()
+                .await
+                .get_bool(BoolRule::DoMobSpawning)
+        {
+            let endermite = MobEntity::new(
+                server.new_entity_id(),
+                world.clone(),
+                EntityType::Endermite,
+                entity.pos.load(),
+                entity.yaw.load(),
+            );
+            world.add_mob(Arc::new(endermite)).await;
+        }
+        owner
+            .request_teleport(pos, entity.yaw.load(), entity.pitch.load())
+            .await;
+        owner.living_entity.fall_distance.store(0.0);
+        owner
+            .hurt(
+                &DamageSource::new(DamageType::EnderPearl),
+                ENDER_PEARL_DAMAGE,
+            )
+            .await;
+        world
+            .play_sound(
+                sound!("entity.player.teleport"),
+                SoundCategory::Players,
+                &pos,
+            )
+            .await;
+    }
+
+    /// Flies the trident back to its owner through blocks, like vanilla's `ThrownTrident.tick`
+    /// with Loyalty. Tridents whose owner died or left drop as an item
+    async fn tick_return(
+        &self,
+        server: &Server,
+        owner: Option<&Arc<Player>>,
+        loyalty: i32,
+    ) -> bool {
+        let world = self.entity.world();
+        let pos = self.entity.pos.load();
+        let owner = owner.filter(|owner| {
+            owner.living_entity.health.load() > 0.0 && owner.gamemode.load() != GameMode::Spectator
+        });
+        let Some(owner) = owner else {
+            if let Some(item_stack) = self
+                .item_stack
+                .clone()
+                .filter(|_| self.pickup == Pickup::Allowed)
+            {
+                world
+                    .drop_item_stack(server.new_entity_id(), pos, item_stack)
+                    .await;
+            }
+            self.discard().await;
+            return false;
+        };
+        if !self.returning.swap(true, Ordering::Relaxed) {
+            self.in_ground.store(None);
+            self.update_data();
+            world
+                .play_sound(sound!("item.trident.return"), SoundCategory::Neutral, &pos)
+                .await;
+        }
+        let owner_entity = &owner.living_entity.entity;
+        let target = owner_entity.pos.load()
+            + Vector3::new(0.0, f64::from(owner_entity.standing_eye_height), 0.0);
+        let distance = target.sub(&pos);
+        let loyalty = f64::from(loyalty);
+        let pos = pos + Vector3::new(0.0, distance.y * 0.015 * loyalty, 0.0);
+        let velocity = self.entity.velocity.load() * 0.95 + distance.normalize() * (0.05 * loyalty);
+        self.entity.set_velocity(velocity);
+        self.entity.set_pos(pos + velocity);
+        self.face(velocity);
+        self.entity.sync_movement().await;
+        true
+    }
+
+    /// Shows whether the arrow or trident is critical, flies back or is stuck in a block
+    fn update_data(&self) {
+        if !self.kind.sticks() {
+            return;
+        }
+        let mut flags = 0;
+        if self.critical.load(Ordering::Relaxed) {
+            flags |= CRITICAL_FLAG;
+        }
+        if self.is_returning() {
+            flags |= NO_CLIP_FLAG;
+        }
+        self.entity.data.set(&FLAGS, flags);
+        self.entity
+            .data
+            .set(&IN_GROUND, self.in_ground.load().is_some());
+    }
+
+    /// Shows the Loyalty and glint of tridents
+    fn set_trident_data(&self) {
+        if self.kind != ProjectileKind::Trident {
+            return;
+        }
+        let enchanted = self
+            .item_stack
+            .as_ref()
+            .is_some_and(|item_stack| !item_stack.components.enchantments.is_empty());
+        self.entity.data.set(&LOYALTY, self.loyalty() as i8);
+        self.entity.data.set(&FOIL, enchanted);
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let entity = &self.entity;
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        CSpawnEntity::new(
+            entity.entity_id.into(),
+            self.uuid,
+            (self.kind.entity_type() as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            entity.pitch.load(),
+            entity.yaw.load(),
+            entity.yaw.load(),
+            // Players know who shot the projectile
+            self.owner.unwrap_or(0).into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// Makes the projectile visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.update_data();
+        self.set_trident_data();
+        self.entity.world().start_tracking(self).await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for ProjectileEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity.data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
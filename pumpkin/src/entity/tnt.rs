@@ -0,0 +1,151 @@
+use std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use pumpkin_core::math::{
+    boundingbox::{BoundingBox, BoundingBoxSize},
+    position::WorldPosition,
+    vector3::Vector3,
+};
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_protocol::client::play::CSpawnEntity;
+use pumpkin_world::{block::explosion::TNT_POWER, damage::DamageType};
+use rand::{thread_rng, Rng};
+
+use crate::{net::Client, server::Server, world::World};
+
+use super::{data::DataField, tracker::TrackedEntity, Entity};
+
+/// Ticks until TNT lit by a player or redstone explodes
+pub const DEFAULT_FUSE: u32 = 80;
+/// The fuse metadata, players count it down themselves to flash the TNT
+const FUSE: DataField<i32> = DataField::new(8);
+const SIZE: BoundingBoxSize = BoundingBoxSize {
+    width: 0.98,
+    height: 0.98,
+};
+
+/// The fuse of TNT lit by another explosion, shorter and random so chains spread out
+#[must_use]
+pub fn chained_fuse() -> u32 {
+    thread_rng().gen_range(DEFAULT_FUSE / 8..DEFAULT_FUSE / 8 + DEFAULT_FUSE / 4)
+}
+
+/// Lit TNT, it falls down like a block and explodes once its fuse runs out
+pub struct TntEntity {
+    /// The underlying entity
+    pub entity: Entity,
+    pub uuid: uuid::Uuid,
+    /// Ticks left until the explosion
+    fuse: AtomicU32,
+    /// Set once the TNT exploded, the entity is removed afterwards
+    removed: AtomicBool,
+}
+
+impl TntEntity {
+    /// Primed TNT at the center of the block it was, with a small push in a random direction
+    pub fn new(entity_id: EntityId, world: Arc<World>, location: WorldPosition, fuse: u32) -> Self {
+        let entity = Entity::new(
+            entity_id,
+            world,
+            EntityType::Tnt,
+            0.15,
+            AtomicCell::new(BoundingBox::new_default(&SIZE)),
+            AtomicCell::new(SIZE),
+        );
+        entity.set_pos(Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y),
+            f64::from(location.0.z) + 0.5,
+        ));
+        let angle = thread_rng().gen::<f64>() * std::f64::consts::TAU;
+        entity
+            .velocity
+            .store(Vector3::new(-angle.sin() * 0.02, 0.2, -angle.cos() * 0.02));
+        entity.data.set(&FUSE, fuse as i32);
+        Self {
+            entity,
+            uuid: uuid::Uuid::new_v4(),
+            fuse: AtomicU32::new(fuse),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed.load(Ordering::Relaxed)
+    }
+
+    fn spawn_packet(&self) -> CSpawnEntity {
+        let pos = self.entity.pos.load();
+        let velocity = self.entity.velocity.load();
+        CSpawnEntity::new(
+            self.entity.entity_id.into(),
+            self.uuid,
+            (EntityType::Tnt as i32).into(),
+            pos.x,
+            pos.y,
+            pos.z,
+            0.0,
+            0.0,
+            0.0,
+            0.into(),
+            velocity.x as f32,
+            velocity.y as f32,
+            velocity.z as f32,
+        )
+    }
+
+    /// Makes the TNT visible to the players within its tracking range
+    pub async fn broadcast_spawn(&self) {
+        self.entity.world().start_tracking(self).await;
+    }
+
+    /// Pushes the TNT away from an explosion
+    pub fn knock_back(&self, knockback: Vector3<f64>) {
+        self.entity.add_velocity(knockback);
+    }
+
+    /// Moves the TNT like vanilla's `PrimedTnt.tick`, and explodes it once the fuse ran out. TNT
+    /// in chunks which are not loaded holds still
+    pub async fn tick(&self, server: &Server) {
+        if self.is_removed() {
+            return;
+        }
+        let fuse = self.fuse.load(Ordering::Relaxed).saturating_sub(1);
+        self.fuse.store(fuse, Ordering::Relaxed);
+        if fuse == 0 {
+            self.explode(server).await;
+            return;
+        }
+        self.entity.tick_movement().await;
+    }
+
+    /// Explodes a bit above the bottom of the TNT, so it breaks the block it lies on
+    async fn explode(&self, server: &Server) {
+        self.removed.store(true, Ordering::Relaxed);
+        let world = &self.entity.world();
+        world.remove_primed_tnt(self).await;
+        let pos = self.entity.pos.load();
+        let center = Vector3::new(pos.x, pos.y + SIZE.height * 0.0625, pos.z);
+        world
+            .explode(center, TNT_POWER, false, DamageType::Explosion, server)
+            .await;
+    }
+}
+
+#[async_trait]
+impl TrackedEntity for TntEntity {
+    fn tracked_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    async fn spawn_for(&self, client: &Client) {
+        client.send_packet(&self.spawn_packet()).await;
+        if let Some(packet) = self.entity.data_packet() {
+            client.send_packet(&packet).await;
+        }
+    }
+}
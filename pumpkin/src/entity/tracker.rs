@@ -0,0 +1,140 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use async_trait::async_trait;
+use pumpkin_entity::{entity_type::EntityType, EntityId};
+
+use crate::net::Client;
+
+use super::Entity;
+
+/// Chunks within which players see the entity type, like the client tracking ranges of vanilla's
+/// `EntityType`s. Players never see entities beyond their view distance
+#[must_use]
+pub const fn tracking_range(entity_type: &EntityType) -> i32 {
+    match entity_type {
+        EntityType::Player => 32,
+        EntityType::LightningBolt => 16,
+        EntityType::Arrow
+        | EntityType::SpectralArrow
+        | EntityType::Trident
+        | EntityType::Snowball
+        | EntityType::Egg
+        | EntityType::EnderPearl
+        | EntityType::ExperienceBottle
+        | EntityType::Potion => 4,
+        EntityType::Item | EntityType::ExperienceOrb => 6,
+        EntityType::Zombie
+        | EntityType::Husk
+        | EntityType::Drowned
+        | EntityType::ZombieVillager
+        | EntityType::Skeleton
+        | EntityType::Stray
+        | EntityType::Bogged
+        | EntityType::Creeper
+        | EntityType::Spider
+        | EntityType::CaveSpider
+        | EntityType::Enderman
+        | EntityType::Witch
+        | EntityType::Slime => 8,
+        _ => 10,
+    }
+}
+
+/// An entity players see once they come close enough
+#[async_trait]
+pub trait TrackedEntity: Send + Sync {
+    fn tracked_entity(&self) -> &Entity;
+
+    /// Makes the entity visible to a single client, with its metadata and equipment
+    async fn spawn_for(&self, client: &Client);
+}
+
+/// The players who see an entity, like vanilla's `ChunkMap.TrackedEntity`. Players far away from
+/// the entity get its movement less often
+#[derive(Default)]
+pub struct EntityTracker {
+    /// The entity ids of the players, and whether they are close to the entity
+    seen_by: Mutex<HashMap<EntityId, bool>>,
+}
+
+impl EntityTracker {
+    /// Starts tracking the entity for the player, `false` if the player already sees it
+    pub fn start(&self, player: EntityId) -> bool {
+        self.seen_by
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(player, true)
+            .is_none()
+    }
+
+    /// Stops tracking the entity for the player, `false` if the player didn't see it
+    pub fn stop(&self, player: EntityId) -> bool {
+        self.seen_by
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&player)
+            .is_some()
+    }
+
+    /// Whether the player sees the entity
+    pub fn is_seen_by(&self, player: EntityId) -> bool {
+        self.seen_by
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(&player)
+    }
+
+    /// Remembers whether the player is close to the entity, returns whether it was before.
+    /// `None` if the player doesn't see the entity
+    pub fn set_close(&self, player: EntityId, close: bool) -> Option<bool> {
+        self.seen_by
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(&player)
+            .map(|was_close| std::mem::replace(was_close, close))
+    }
+
+    /// Forgets every player, e.g. when the entity is removed. Returns the players who saw it
+    pub fn clear(&self) -> Vec<EntityId> {
+        self.seen_by
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .drain()
+            .map(|(player, _)| player)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_entity::entity_type::EntityType;
+
+    use super::{tracking_range, EntityTracker};
+
+    #[test]
+    fn players_are_seen_from_further_away() {
+        assert_eq!(tracking_range(&EntityType::Player), 32);
+        assert_eq!(tracking_range(&EntityType::Zombie), 8);
+        assert_eq!(tracking_range(&EntityType::Arrow), 4);
+        assert_eq!(tracking_range(&EntityType::Cow), 10);
+    }
+
+    #[test]
+    fn trackers_remember_who_sees_the_entity() {
+        let tracker = EntityTracker::default();
+        assert!(tracker.start(1));
+        assert!(!tracker.start(1));
+        assert!(tracker.is_seen_by(1));
+        assert_eq!(tracker.set_close(1, false), Some(true));
+        assert_eq!(tracker.set_close(2, false), None);
+
+        assert!(tracker.start(2));
+        assert!(tracker.stop(2));
+        assert!(!tracker.stop(2));
+        assert_eq!(tracker.clear(), vec![1]);
+        assert!(!tracker.is_seen_by(1));
+    }
+}
@@ -23,17 +23,21 @@ impl<ErrorType: PumpkinError + 'static> From<ErrorType> for Box<dyn PumpkinError
 impl PumpkinError for InventoryError {
     fn is_kick(&self) -> bool {
         use InventoryError::{
-            ClosedContainerInteract, InvalidPacket, InvalidSlot, LockError,
+            ClosedContainerInteract, InvalidItem, InvalidPacket, InvalidSlot, LockError,
             MultiplePlayersDragging, OutOfOrderDragging, PermissionError,
         };
         match self {
-            InvalidSlot | ClosedContainerInteract(..) | InvalidPacket | PermissionError => true,
+            InvalidSlot
+            | ClosedContainerInteract(..)
+            | InvalidPacket
+            | PermissionError
+            | InvalidItem => true,
             LockError | OutOfOrderDragging | MultiplePlayersDragging => false,
         }
     }
     fn severity(&self) -> log::Level {
         use InventoryError::{
-            ClosedContainerInteract, InvalidPacket, InvalidSlot, LockError,
+            ClosedContainerInteract, InvalidItem, InvalidPacket, InvalidSlot, LockError,
             MultiplePlayersDragging, OutOfOrderDragging, PermissionError,
         };
         match self {
@@ -41,7 +45,8 @@ impl PumpkinError for InventoryError {
             | InvalidSlot
             | ClosedContainerInteract(..)
             | InvalidPacket
-            | PermissionError => log::Level::Error,
+            | PermissionError
+            | InvalidItem => log::Level::Error,
             OutOfOrderDragging => log::Level::Info,
             MultiplePlayersDragging => log::Level::Warn,
         }
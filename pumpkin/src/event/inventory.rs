@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use pumpkin_inventory::container_click::Click;
+use pumpkin_inventory::Container;
+
+use crate::entity::player::Player;
+
+use super::Cancellable;
+
+/// Listens to players using containers. The container is already locked while a listener runs,
+/// so it has to be accessed through the event
+#[async_trait]
+pub trait InventoryListener: Send + Sync {
+    async fn on_inventory_open(&self, _event: &mut InventoryOpenEvent<'_>) {}
+
+    async fn on_inventory_click(&self, _event: &mut InventoryClickEvent<'_>) {}
+
+    async fn on_inventory_close(&self, _event: &mut InventoryCloseEvent<'_>) {}
+}
+
+/// Called before a container is shown to a player, cancelling it keeps the container closed
+pub struct InventoryOpenEvent<'a> {
+    pub player: &'a Player,
+    /// The contents can still be changed before the player sees them
+    pub container: Option<&'a mut Box<dyn Container>>,
+    cancelled: bool,
+}
+
+impl<'a> InventoryOpenEvent<'a> {
+    pub fn new(player: &'a Player, container: Option<&'a mut Box<dyn Container>>) -> Self {
+        Self {
+            player,
+            container,
+            cancelled: false,
+        }
+    }
+}
+
+impl Cancellable for InventoryOpenEvent<'_> {
+    fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = cancelled;
+    }
+}
+
+/// Called before a click in a window is handled, cancelling it leaves every slot as it was.
+/// The container is `None` if the player clicked in their own inventory
+pub struct InventoryClickEvent<'a> {
+    pub player: &'a Player,
+    pub container: Option<&'a mut Box<dyn Container>>,
+    pub click: &'a Click,
+    cancelled: bool,
+}
+
+impl<'a> InventoryClickEvent<'a> {
+    pub fn new(
+        player: &'a Player,
+        container: Option<&'a mut Box<dyn Container>>,
+        click: &'a Click,
+    ) -> Self {
+        Self {
+            player,
+            container,
+            click,
+            cancelled: false,
+        }
+    }
+}
+
+impl Cancellable for InventoryClickEvent<'_> {
+    fn cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn set_cancelled(&mut self, cancelled: bool) {
+        self.cancelled = cancelled;
+    }
+}
+
+/// Called when a player closes a window, the client already closed it so this can't be cancelled.
+/// The container is `None` if the player closed their own inventory
+pub struct InventoryCloseEvent<'a> {
+    pub player: &'a Player,
+    pub container: Option<&'a mut Box<dyn Container>>,
+}
+
+impl<'a> InventoryCloseEvent<'a> {
+    pub fn new(player: &'a Player, container: Option<&'a mut Box<dyn Container>>) -> Self {
+        Self { player, container }
+    }
+}
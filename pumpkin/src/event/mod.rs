@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use inventory::{InventoryClickEvent, InventoryCloseEvent, InventoryListener, InventoryOpenEvent};
+use tokio::sync::RwLock;
+
+pub mod inventory;
+
+/// Events which plugins can cancel to stop the server from handling them
+pub trait Cancellable {
+    fn cancelled(&self) -> bool;
+
+    fn set_cancelled(&mut self, cancelled: bool);
+}
+
+/// Calls the listeners plugins registered when something happens on the server.
+/// Listeners are called in the order they were registered
+#[derive(Default)]
+pub struct EventManager {
+    inventory_listeners: RwLock<Vec<Arc<dyn InventoryListener>>>,
+}
+
+impl EventManager {
+    pub async fn register_inventory_listener(&self, listener: Arc<dyn InventoryListener>) {
+        self.inventory_listeners.write().await.push(listener);
+    }
+
+    pub async fn fire_inventory_open(&self, event: &mut InventoryOpenEvent<'_>) {
+        for listener in self.inventory_listeners.read().await.iter() {
+            listener.on_inventory_open(event).await;
+        }
+    }
+
+    pub async fn fire_inventory_click(&self, event: &mut InventoryClickEvent<'_>) {
+        for listener in self.inventory_listeners.read().await.iter() {
+            listener.on_inventory_click(event).await;
+        }
+    }
+
+    pub async fn fire_inventory_close(&self, event: &mut InventoryCloseEvent<'_>) {
+        for listener in self.inventory_listeners.read().await.iter() {
+            listener.on_inventory_close(event).await;
+        }
+    }
+}
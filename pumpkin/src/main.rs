@@ -58,6 +58,7 @@ pub mod command;
 pub mod data;
 pub mod entity;
 pub mod error;
+pub mod event;
 pub mod net;
 pub mod server;
 pub mod world;
@@ -164,6 +165,15 @@ async fn main() {
     let rcon = ADVANCED_CONFIG.rcon.clone();
 
     let server = Arc::new(Server::new());
+    if std::env::args().any(|arg| arg == "--force-upgrade") {
+        for world in &server.worlds {
+            let level = world.level.clone();
+            tokio::task::spawn_blocking(move || level.force_upgrade())
+                .await
+                .expect("Failed to upgrade the world");
+        }
+    }
+    server.resume_pregeneration().await;
     let mut ticker = Ticker::new(BASIC_CONFIG.tps);
 
     log::info!("Started Server took {}ms", time.elapsed().as_millis());
@@ -196,6 +206,13 @@ async fn main() {
         })
     };
 
+    {
+        let server = server.clone();
+        tokio::spawn(async move {
+            server.autosave_chunks().await;
+        })
+    };
+
     let mut master_client_id: u16 = 0;
     loop {
         // Asynchronously wait for an inbound socket.
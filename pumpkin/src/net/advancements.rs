@@ -0,0 +1,380 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::entity::player::Player;
+use pumpkin_core::text::{color::NamedColor, TextComponent};
+use pumpkin_protocol::client::play::{
+    AdvancementDisplayEntry, AdvancementEntry, AdvancementProgressEntry, CSystemChatMessage,
+    CUpdateAdvancements, ADVANCEMENT_HAS_BACKGROUND, ADVANCEMENT_HIDDEN, ADVANCEMENT_SHOW_TOAST,
+};
+use pumpkin_protocol::codec::slot::Slot;
+use pumpkin_registry::{get_advancement, Advancement, AdvancementFrame, Criterion, ADVANCEMENTS};
+use pumpkin_world::advancement_data::AdvancementProgressData;
+use pumpkin_world::item::item_registry::{get_item, get_item_name_by_id};
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::world_info::game_rules::BoolRule;
+
+/// Where the advancements are shown in their tab: the depth in the tree and the row. A parent
+/// is shown in the row of its first child
+static POSITIONS: LazyLock<HashMap<&'static str, (f32, f32)>> = LazyLock::new(|| {
+    fn place(
+        id: &'static str,
+        depth: f32,
+        next_row: &mut f32,
+        positions: &mut HashMap<&'static str, (f32, f32)>,
+    ) {
+        let mut row = None;
+        for (child, advancement) in ADVANCEMENTS.iter() {
+            if advancement.display.is_some() && advancement.parent.as_deref() == Some(id) {
+                place(child, depth + 1.0, next_row, positions);
+                row.get_or_insert(positions[child.as_str()].1);
+            }
+        }
+        let row = row.unwrap_or_else(|| {
+            *next_row += 1.0;
+            *next_row - 1.0
+        });
+        positions.insert(id, (depth, row));
+    }
+
+    let mut positions = HashMap::new();
+    for (id, advancement) in ADVANCEMENTS.iter() {
+        if advancement.parent.is_none() && advancement.display.is_some() {
+            place(id, 0.0, &mut 0.0, &mut positions);
+        }
+    }
+    positions
+});
+
+/// The criteria the player has done, like vanilla's `PlayerAdvancements`
+#[derive(Default)]
+pub struct PlayerAdvancements {
+    progress: AdvancementProgressData,
+    /// The items in the inventory when `inventory_changed` was last checked
+    inventory_items: BTreeSet<u16>,
+}
+
+impl PlayerAdvancements {
+    #[must_use]
+    pub fn is_done(&self, id: &str) -> bool {
+        get_advancement(id).is_some_and(|advancement| {
+            let criteria = self.progress.get(id);
+            advancement.is_done(|criterion| criteria.is_some_and(|c| c.contains_key(criterion)))
+        })
+    }
+
+    #[must_use]
+    pub fn has_criterion(&self, id: &str, criterion: &str) -> bool {
+        self.progress
+            .get(id)
+            .is_some_and(|criteria| criteria.contains_key(criterion))
+    }
+
+    fn progress_entry<'a>(
+        &self,
+        id: &'a str,
+        advancement: &'a Advancement,
+    ) -> AdvancementProgressEntry<'a> {
+        let criteria = self.progress.get(id);
+        AdvancementProgressEntry {
+            id,
+            criteria: advancement
+                .criteria
+                .keys()
+                .map(|criterion| {
+                    let date = criteria
+                        .and_then(|criteria| criteria.get(criterion))
+                        .copied();
+                    (criterion.as_str(), date)
+                })
+                .collect(),
+        }
+    }
+}
+
+fn advancement_entry<'a>(id: &'a str, advancement: &'a Advancement) -> AdvancementEntry<'a> {
+    let display = advancement.display.as_ref().map(|display| {
+        let mut flags = 0;
+        if display.background.is_some() {
+            flags |= ADVANCEMENT_HAS_BACKGROUND;
+        }
+        if display.show_toast {
+            flags |= ADVANCEMENT_SHOW_TOAST;
+        }
+        if display.hidden {
+            flags |= ADVANCEMENT_HIDDEN;
+        }
+        let icon = get_item(&display.icon.id).map_or(Slot::empty(), |item| {
+            Slot::from(&ItemStack::new(display.icon.count, item.id))
+        });
+        let (x, y) = POSITIONS.get(id).copied().unwrap_or_default();
+        AdvancementDisplayEntry {
+            title: display.title.clone(),
+            description: display.description.clone(),
+            icon,
+            frame: (display.frame as i32).into(),
+            flags,
+            background: display.background.as_deref(),
+            x,
+            y,
+        }
+    });
+    AdvancementEntry {
+        id,
+        parent: advancement.parent.as_deref(),
+        display,
+        requirements: advancement.requirements(),
+        sends_telemetry: false,
+    }
+}
+
+/// The title in brackets colored by the frame, or the id if the advancement isn't shown, like
+/// vanilla's `DisplayInfo.name`
+#[must_use]
+pub fn advancement_name(id: &str, advancement: &Advancement) -> TextComponent<'static> {
+    advancement.display.as_ref().map_or_else(
+        || TextComponent::text_string(id.to_string()),
+        |display| {
+            let color = match display.frame {
+                AdvancementFrame::Challenge => NamedColor::DarkPurple,
+                AdvancementFrame::Task | AdvancementFrame::Goal => NamedColor::Green,
+            };
+            TextComponent::translate("chat.square_brackets", vec![display.title.clone()])
+                .color_named(color)
+        },
+    )
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as i64)
+}
+
+impl Player {
+    /// Loads the progress of the player from the world, if they have joined it before
+    pub async fn load_advancements(&self) {
+        let uuid = self.gameprofile.id.to_string();
+        match self.world().level.player_advancements.load(&uuid) {
+            Ok(progress) => self.advancements.lock().await.progress = progress,
+            Err(error) => log::error!(
+                "Failed to load advancements of {}: {error}",
+                self.gameprofile.name
+            ),
+        }
+    }
+
+    pub async fn save_advancements(&self) {
+        let advancements = self.advancements.lock().await;
+        let uuid = self.gameprofile.id.to_string();
+        if let Err(error) =
+            self.world()
+                .level
+                .player_advancements
+                .save(&uuid, &advancements.progress, |id| advancements.is_done(id))
+        {
+            log::error!(
+                "Failed to save advancements of {}: {error}",
+                self.gameprofile.name
+            );
+        }
+    }
+
+    /// Sends every advancement with the progress of the player, used when joining
+    pub async fn init_advancements(&self) {
+        let advancements = self.advancements.lock().await;
+        let added: Vec<_> = ADVANCEMENTS
+            .iter()
+            .map(|(id, advancement)| advancement_entry(id, advancement))
+            .collect();
+        let progress: Vec<_> = ADVANCEMENTS
+            .iter()
+            .map(|(id, advancement)| advancements.progress_entry(id, advancement))
+            .collect();
+        self.client
+            .send_packet(&CUpdateAdvancements::new(true, &added, &[], &progress))
+            .await;
+    }
+
+    /// Awards the criterion of the advancement, returns false if it was already done. Completing
+    /// the advancement gives its rewards and announces it, like vanilla's `PlayerAdvancements.award`
+    pub async fn award_criterion(&self, id: &str, criterion: &str) -> bool {
+        let Some((id, advancement)) = ADVANCEMENTS
+            .get_key_value(id)
+            .filter(|(_, advancement)| advancement.criteria.contains_key(criterion))
+        else {
+            return false;
+        };
+        let mut advancements = self.advancements.lock().await;
+        let was_done = advancements.is_done(id);
+        let criteria = advancements.progress.entry(id.clone()).or_default();
+        if criteria.contains_key(criterion) {
+            return false;
+        }
+        criteria.insert(criterion.to_string(), now_millis());
+        let progress = [advancements.progress_entry(id, advancement)];
+        let done = !was_done && advancements.is_done(id);
+        drop(advancements);
+
+        self.client
+            .send_packet(&CUpdateAdvancements::new(false, &[], &[], &progress))
+            .await;
+        if done {
+            self.complete_advancement(id, advancement).await;
+        }
+        true
+    }
+
+    /// Revokes the criterion of the advancement, returns false if it wasn't done
+    pub async fn revoke_criterion(&self, id: &str, criterion: &str) -> bool {
+        let Some((id, advancement)) = ADVANCEMENTS.get_key_value(id) else {
+            return false;
+        };
+        let mut advancements = self.advancements.lock().await;
+        let removed = advancements
+            .progress
+            .get_mut(id)
+            .is_some_and(|criteria| criteria.remove(criterion).is_some());
+        if !removed {
+            return false;
+        }
+        let progress = [advancements.progress_entry(id, advancement)];
+        drop(advancements);
+        self.client
+            .send_packet(&CUpdateAdvancements::new(false, &[], &[], &progress))
+            .await;
+        true
+    }
+
+    /// Awards every criterion of the advancement, returns false if it was already done
+    pub async fn grant_advancement(&self, id: &str) -> bool {
+        let Some(advancement) = get_advancement(id) else {
+            return false;
+        };
+        let mut changed = false;
+        for criterion in advancement.criteria.keys() {
+            changed |= self.award_criterion(id, criterion).await;
+        }
+        changed
+    }
+
+    /// Revokes every criterion of the advancement, returns false if none was done
+    pub async fn revoke_advancement(&self, id: &str) -> bool {
+        let Some(advancement) = get_advancement(id) else {
+            return false;
+        };
+        let mut changed = false;
+        for criterion in advancement.criteria.keys() {
+            changed |= self.revoke_criterion(id, criterion).await;
+        }
+        changed
+    }
+
+    async fn complete_advancement(&self, id: &str, advancement: &Advancement) {
+        if advancement.rewards.experience > 0 {
+            self.add_experience_points(advancement.rewards.experience)
+                .await;
+        }
+        let Some(display) = &advancement.display else {
+            return;
+        };
+        let world = self.world();
+        let announce = display.announce_to_chat
+            && world
+                .game_rules
+                .lock()
+                .await
+                .get_bool(BoolRule::AnnounceAdvancements);
+        if !announce {
+            return;
+        }
+        let message = TextComponent::translate(
+            format!("chat.type.advancement.{}", display.frame.name()),
+            vec![
+                TextComponent::text_string(self.gameprofile.name.clone()),
+                advancement_name(id, advancement),
+            ],
+        );
+        world
+            .broadcast_packet_all(&CSystemChatMessage::new(&message, false))
+            .await;
+    }
+
+    /// Awards every criterion with the trigger whose conditions match, like vanilla's
+    /// `SimpleCriterionTrigger.trigger`
+    async fn trigger(&self, trigger: &str, matches: impl Fn(&Criterion) -> bool) {
+        let mut awarded = Vec::new();
+        {
+            let advancements = self.advancements.lock().await;
+            for (id, advancement) in ADVANCEMENTS.iter() {
+                for (name, criterion) in &advancement.criteria {
+                    if criterion.trigger.strip_prefix("minecraft:") == Some(trigger)
+                        && !advancements.has_criterion(id, name)
+                        && matches(criterion)
+                    {
+                        awarded.push((id, name));
+                    }
+                }
+            }
+        }
+        for (id, criterion) in awarded {
+            self.award_criterion(id, criterion).await;
+        }
+    }
+
+    /// Fires `inventory_changed` when the player got items they didn't have before
+    pub async fn trigger_inventory_changed(&self) {
+        let items: BTreeSet<u16> = self
+            .inventory()
+            .lock()
+            .await
+            .stored_items()
+            .map(|item| item.item_id)
+            .collect();
+        {
+            let mut advancements = self.advancements.lock().await;
+            let gained = !items.is_subset(&advancements.inventory_items);
+            advancements.inventory_items.clone_from(&items);
+            if !gained {
+                return;
+            }
+        }
+        let names: Vec<_> = items
+            .iter()
+            .filter_map(|item| get_item_name_by_id(*item))
+            .collect();
+        self.trigger("inventory_changed", |criterion| {
+            criterion
+                .conditions
+                .items
+                .iter()
+                .all(|predicate| names.iter().any(|name| predicate.matches(name)))
+        })
+        .await;
+    }
+
+    /// Fires `placed_block` for the block the player placed
+    pub async fn trigger_placed_block(&self, block: &str) {
+        self.trigger("placed_block", |criterion| {
+            criterion
+                .conditions
+                .location
+                .iter()
+                .all(|condition| condition.matches(block))
+        })
+        .await;
+    }
+
+    /// Fires `player_killed_entity` for the entity the player killed
+    pub async fn trigger_killed_entity(&self, entity_type: &str) {
+        self.trigger("player_killed_entity", |criterion| {
+            criterion
+                .conditions
+                .entity
+                .iter()
+                .all(|condition| condition.matches(entity_type))
+        })
+        .await;
+    }
+}
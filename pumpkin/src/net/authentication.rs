@@ -30,6 +30,14 @@ pub struct Texture {
 
 const MOJANG_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
 const MOJANG_PREVENT_PROXY_AUTHENTICATION_URL: &str = "https://sessionserver.mojang.com/session/minecraft/hasJoined?username={username}&serverId={server_hash}";
+const MOJANG_PROFILE_LOOKUP_URL: &str = "https://api.mojang.com/users/profiles/minecraft/{username}";
+
+/// The response of a profile lookup by name, it has no properties
+#[derive(Deserialize)]
+struct ProfileLookup {
+    id: Uuid,
+    name: String,
+}
 
 /// Sends a GET request to Mojang's authentication servers to verify a client's Minecraft account.
 ///
@@ -87,6 +95,31 @@ pub async fn authenticate(
     Ok(profile)
 }
 
+/// Looks up the profile of the account with the name, for players who aren't online, like
+/// vanilla's `GameProfileRepository.findProfilesByNames`
+pub async fn lookup_profile(
+    username: &str,
+    auth_client: &reqwest::Client,
+) -> Result<GameProfile, AuthError> {
+    let response = auth_client
+        .get(MOJANG_PROFILE_LOOKUP_URL.replace("{username}", username))
+        .send()
+        .await
+        .map_err(|_| AuthError::FailedResponse)?;
+    match response.status() {
+        StatusCode::OK => {}
+        StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Err(AuthError::UnverifiedUsername)?,
+        other => Err(AuthError::UnknownStatusCode(other))?,
+    }
+    let profile: ProfileLookup = response.json().await.map_err(|_| AuthError::FailedParse)?;
+    Ok(GameProfile {
+        id: profile.id,
+        name: profile.name,
+        properties: Vec::new(),
+        profile_actions: None,
+    })
+}
+
 pub fn validate_textures(property: &Property, config: &TextureConfig) -> Result<(), TextureError> {
     let from64 = general_purpose::STANDARD
         .decode(&property.value)
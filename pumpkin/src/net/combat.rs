@@ -7,7 +7,8 @@ use pumpkin_protocol::{
     codec::var_int::VarInt,
     SoundCategory,
 };
-use pumpkin_world::item::ItemStack;
+use pumpkin_registry::{is_in_tag, TagCategory};
+use pumpkin_world::item::{item_registry::get_item_name_by_id, ItemStack};
 
 use crate::{
     entity::{player::Player, Entity},
@@ -116,6 +117,23 @@ pub async fn spawn_sweep_particle(attacker_entity: &Entity, world: &World, pos:
         .await;
 }
 
+/// The durability a weapon loses for each hit, like vanilla's `Item.postHurtEnemy`. Swords,
+/// maces and tridents lose one point and tools lose two
+#[must_use]
+pub fn attack_durability_damage(weapon: &ItemStack) -> i32 {
+    let Some(name) = get_item_name_by_id(weapon.item_id) else {
+        return 0;
+    };
+    let in_tag = |tag| is_in_tag(TagCategory::Item, name, tag);
+    if in_tag("swords") || matches!(name, "mace" | "trident") {
+        1
+    } else if in_tag("axes") || in_tag("pickaxes") || in_tag("shovels") || in_tag("hoes") {
+        2
+    } else {
+        0
+    }
+}
+
 pub async fn player_attack_sound(pos: &Vector3<f64>, world: &World, attack_type: AttackType) {
     match attack_type {
         AttackType::Knockback => {
@@ -165,3 +183,23 @@ pub async fn player_attack_sound(pos: &Vector3<f64>, world: &World, attack_type:
         }
     };
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::{item_registry::get_item, ItemStack};
+
+    use super::attack_durability_damage;
+
+    fn weapon(item: &str) -> ItemStack {
+        ItemStack::new(1, get_item(item).unwrap().id)
+    }
+
+    #[test]
+    fn tools_wear_out_faster_than_weapons() {
+        assert_eq!(attack_durability_damage(&weapon("minecraft:iron_sword")), 1);
+        assert_eq!(attack_durability_damage(&weapon("minecraft:trident")), 1);
+        assert_eq!(attack_durability_damage(&weapon("minecraft:iron_axe")), 2);
+        assert_eq!(attack_durability_damage(&weapon("minecraft:wooden_hoe")), 2);
+        assert_eq!(attack_durability_damage(&weapon("minecraft:stick")), 0);
+    }
+}
@@ -1,34 +1,66 @@
+use crate::block::use_anvil;
+use crate::entity::horse::HorseEntity;
+use crate::entity::item::ItemEntity;
 use crate::entity::player::Player;
+use crate::entity::projectile::{Pickup, ProjectileEntity};
+use crate::event::inventory::{InventoryClickEvent, InventoryCloseEvent, InventoryOpenEvent};
+use crate::event::Cancellable;
+use crate::net::stats::Stat;
 use crate::server::Server;
+use pumpkin_core::math::boundingbox::BoundingBox;
+use pumpkin_core::math::vector3::Vector3;
 use pumpkin_core::text::TextComponent;
 use pumpkin_core::GameMode;
 use pumpkin_inventory::container_click::{
-    Click, ClickType, KeyClick, MouseClick, MouseDragState, MouseDragType,
+    Click, ClickType, DropType, KeyClick, MouseClick, MouseDragState, MouseDragType,
 };
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::window_property::{WindowProperty, WindowPropertyTrait};
-use pumpkin_inventory::{container_click, InventoryError, OptionallyCombinedContainer};
-use pumpkin_inventory::{Container, WindowType};
+use pumpkin_inventory::{
+    container_click, BundleClick, InventoryError, OptionallyCombinedContainer,
+};
+use pumpkin_inventory::{
+    Container, MerchantContainer, MerchantOffer, OpenContainer, WindowType, TOO_EXPENSIVE_COST,
+};
+use pumpkin_macros::sound;
 use pumpkin_protocol::client::play::{
-    CCloseContainer, COpenScreen, CSetContainerContent, CSetContainerProperty, CSetContainerSlot,
+    CCloseContainer, CHorseScreenOpen, CMerchantOffers, COpenScreen, CSetContainerContent,
+    CSetContainerProperty, CSetContainerSlot, CTakeItemEntity, MerchantTrade, TradeItem,
 };
 use pumpkin_protocol::codec::slot::Slot;
 use pumpkin_protocol::codec::var_int::VarInt;
-use pumpkin_protocol::server::play::SClickContainer;
+use pumpkin_protocol::server::play::{
+    SBundleItemSelected, SClickContainer, SContainerButtonClick, SRenameItem, SSelectTrade,
+};
+use pumpkin_protocol::SoundCategory;
 use pumpkin_world::item::item_registry::Item;
 use pumpkin_world::item::ItemStack;
+use pumpkin_world::stats::StatType;
+use rand::Rng;
 use std::sync::Arc;
 
+/// Players further away from a container block than this can't use it anymore
+const MAX_CONTAINER_DISTANCE: f64 = 6.0;
+
 impl Player {
-    pub async fn open_container(&self, server: &Server, window_type: WindowType) {
-        let mut inventory = self.inventory().lock().await;
-        inventory.state_id = 0;
-        inventory.total_opened_containers += 1;
+    /// Shows the player's open container, returns false if a plugin cancelled opening it
+    pub async fn open_container(&self, server: &Server, window_type: WindowType) -> bool {
         let mut container = self.get_open_container(server).await;
         let mut container = match container.as_mut() {
             Some(container) => Some(container.lock().await),
             None => None,
         };
+        let mut event = InventoryOpenEvent::new(self, container.as_deref_mut());
+        server.event_manager.fire_inventory_open(&mut event).await;
+        if event.cancelled() {
+            drop(container);
+            self.abandon_open_container(server).await;
+            return false;
+        }
+
+        let mut inventory = self.inventory().lock().await;
+        inventory.state_id = 0;
+        inventory.total_opened_containers += 1;
         let window_title = container.as_ref().map_or_else(
             || inventory.window_name(),
             |container| container.window_name(),
@@ -44,6 +76,91 @@ impl Player {
             .await;
         drop(inventory);
         self.set_container_content(container.as_deref_mut()).await;
+        true
+    }
+
+    /// Opens a container made by a plugin, e.g. a custom GUI.
+    /// It only exists while it is open, items in its combinable slots are given back to the player on closing
+    pub async fn open_custom_container(
+        &self,
+        server: &Server,
+        container: Box<dyn Container>,
+    ) -> bool {
+        let window_type = *container.window_type();
+        let container_id = server.new_container_id();
+        server.open_containers.write().await.insert(
+            container_id.into(),
+            OpenContainer::new_without_block(self.entity_id(), container),
+        );
+        self.open_container.store(Some(container_id.into()));
+        self.open_container(server, window_type).await
+    }
+
+    /// Forgets the container the player was about to open, e.g. when a plugin cancelled opening it
+    async fn abandon_open_container(&self, server: &Server) {
+        let Some(id) = self.open_container.take() else {
+            return;
+        };
+        let mut open_containers = server.open_containers.write().await;
+        if let Some(container) = open_containers.get_mut(&id) {
+            container.remove_player(self.entity_id());
+            // Blocks keep their containers, the others only exist while they are open.
+            // Entities like horses and ender chests keep the items themselves
+            if (container.get_location().is_none() || container.get_entity().is_some())
+                && container.get_number_of_players() == 0
+            {
+                open_containers.remove(&id);
+            }
+        }
+    }
+
+    /// Opens the inventory of a horse, the container stays with the horse after it is closed
+    pub async fn open_horse_inventory(&self, server: &Server, horse: &HorseEntity) {
+        let entity_id = horse.entity().entity_id;
+        let mut open_containers = server.open_containers.write().await;
+        // Players looking into the same horse share the container
+        let existing = open_containers
+            .iter_mut()
+            .find(|(_, container)| container.get_entity() == Some(entity_id));
+        let container_id = if let Some((id, container)) = existing {
+            container.add_player(self.entity_id());
+            *id
+        } else {
+            let id = server.new_container_id().into();
+            open_containers.insert(
+                id,
+                OpenContainer::new_for_entity(self.entity_id(), entity_id, horse.inventory.clone()),
+            );
+            id
+        };
+        drop(open_containers);
+        self.open_container.store(Some(container_id));
+
+        let mut container = horse.inventory.lock().await;
+        let mut event = InventoryOpenEvent::new(self, Some(&mut *container));
+        server.event_manager.fire_inventory_open(&mut event).await;
+        if event.cancelled() {
+            drop(container);
+            self.abandon_open_container(server).await;
+            return;
+        }
+        drop(container);
+
+        let columns = horse.inventory_columns().await;
+        let mut inventory = self.inventory().lock().await;
+        inventory.state_id = 0;
+        inventory.total_opened_containers += 1;
+        let window_id = inventory.total_opened_containers;
+        drop(inventory);
+        self.client
+            .send_packet(&CHorseScreenOpen::new(
+                window_id.into(),
+                VarInt(columns.into()),
+                entity_id,
+            ))
+            .await;
+        let mut container = horse.inventory.lock().await;
+        self.set_container_content(Some(&mut *container)).await;
     }
 
     pub async fn set_container_content(&self, container: Option<&mut Box<dyn Container>>) {
@@ -56,6 +173,10 @@ impl Player {
             0
         };
 
+        let properties = container
+            .as_ref()
+            .map(|container| container.window_properties())
+            .unwrap_or_default();
         let container = OptionallyCombinedContainer::new(&mut inventory, container);
 
         let slots: Vec<Slot> = container
@@ -64,11 +185,7 @@ impl Player {
             .map(Slot::from)
             .collect();
 
-        let carried_item = self
-            .carried_item
-            .load()
-            .as_ref()
-            .map_or_else(Slot::empty, std::convert::Into::into);
+        let carried_item = Slot::from(&*self.carried_item.lock().await);
 
         inventory.state_id += 1;
         let packet = CSetContainerContent::new(
@@ -78,6 +195,61 @@ impl Player {
             &carried_item,
         );
         self.client.send_packet(&packet).await;
+        drop(inventory);
+        self.set_container_properties(&properties).await;
+    }
+
+    /// Whether the player may still use the open container. The block backing it must not be broken
+    /// and the player has to stay close to it, containers without a block are always valid
+    pub async fn is_open_container_valid(&self, server: &Server) -> bool {
+        let Some(id) = self.open_container.load() else {
+            return true;
+        };
+        let Some((locations, block)) =
+            server
+                .open_containers
+                .read()
+                .await
+                .get(&id)
+                .map(|container| {
+                    (
+                        [container.get_location(), container.get_second_location()],
+                        container.get_block(),
+                    )
+                })
+        else {
+            return true;
+        };
+        let (Some(location), Some(block)) = (locations[0], block) else {
+            return true;
+        };
+        let pos = self.living_entity.entity.pos.load();
+        let eye_pos = Vector3::new(
+            pos.x,
+            pos.y + f64::from(self.living_entity.entity.standing_eye_height),
+            pos.z,
+        );
+        if BoundingBox::from_block(&location).squared_magnitude(eye_pos)
+            > MAX_CONTAINER_DISTANCE * MAX_CONTAINER_DISTANCE
+        {
+            return false;
+        }
+        // Breaking either half of a large chest invalidates it
+        let world = self.world();
+        for location in locations.into_iter().flatten() {
+            let current = world.get_block(location).await;
+            if !current.is_ok_and(|current| current.id == block.id) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Closes the window on the client and removes the player from the container,
+    /// used when the container became invalid
+    pub async fn force_close_container(&self, server: &Server) {
+        self.close_container().await;
+        self.leave_container(server).await;
     }
 
     /// The official Minecraft client is weird, and will always just close *any* window that is opened when this gets sent
@@ -106,11 +278,26 @@ impl Player {
             .await;
     }
 
+    /// Sends already resolved `(property, value)` pairs of the open container
+    pub async fn set_container_properties(&self, properties: &[(i16, i16)]) {
+        let window_id = self.inventory().lock().await.total_opened_containers;
+        for (id, value) in properties {
+            self.client
+                .send_packet(&CSetContainerProperty::new(window_id.into(), *id, *value))
+                .await;
+        }
+    }
+
+    #[expect(clippy::too_many_lines)]
     pub async fn handle_click_container(
         &self,
         server: &Arc<Server>,
         packet: SClickContainer,
     ) -> Result<(), InventoryError> {
+        if !self.is_open_container_valid(server).await {
+            self.force_close_container(server).await;
+            return Ok(());
+        }
         let opened_container = self.get_open_container(server).await;
         let mut opened_container = match opened_container.as_ref() {
             Some(container) => Some(container.lock().await),
@@ -136,15 +323,31 @@ impl Player {
         }
 
         let click = Click::new(packet.mode, packet.button, packet.slot)?;
-        let (crafted_item, crafted_item_slot) = {
+        if self
+            .cancel_click(server, opened_container.as_deref_mut(), &click)
+            .await
+        {
+            return Ok(());
+        }
+        if opened_container.is_none() && self.takes_bound_armor(&click).await {
+            // The client already moved the item, so it has to be put back
+            self.set_container_content(None).await;
+            return Ok(());
+        }
+        let (crafted_item, crafted_item_slot, experience_cost, experience_reward) = {
             let mut inventory = self.inventory().lock().await;
             let combined =
                 OptionallyCombinedContainer::new(&mut inventory, opened_container.as_deref_mut());
             (
                 combined.crafted_item_slot(),
                 combined.crafting_output_slot(),
+                combined.experience_cost(),
+                combined.experience_reward(),
             )
         };
+        let is_anvil = opened_container
+            .as_ref()
+            .is_some_and(|container| container.window_type() == &WindowType::Anvil);
         let crafted_is_picked = crafted_item.is_some()
             && match click.slot {
                 container_click::Slot::Normal(slot) => {
@@ -152,10 +355,17 @@ impl Player {
                 }
                 container_click::Slot::OutsideInventory => false,
             };
+        if crafted_is_picked && !self.can_pay_experience(experience_cost) {
+            // The client already moved the item, so it has to be put back
+            self.set_container_content(opened_container.as_deref_mut())
+                .await;
+            return Ok(());
+        }
         let mut update_whole_container = false;
 
         let click_slot = click.slot;
         self.match_click_behaviour(
+            server,
             opened_container.as_deref_mut(),
             click,
             drag_handler,
@@ -164,22 +374,61 @@ impl Player {
         )
         .await?;
         // Checks for if crafted item has been taken
+        let mut crafted_item_taken = false;
+        let mut dropped_remainders = Vec::new();
         {
             let mut inventory = self.inventory().lock().await;
             let mut combined =
                 OptionallyCombinedContainer::new(&mut inventory, opened_container.as_deref_mut());
             if combined.crafted_item_slot().is_none() && crafted_item.is_some() {
                 combined.recipe_used();
+                crafted_item_taken = true;
             }
+            let remainders = combined.take_crafting_remainders();
 
             // TODO: `combined.craft` uses rayon! It should be called from `rayon::spawn` and its
             // result passed to the tokio runtime via a channel!
-            if combined.craft() {
+            let changed = combined.craft();
+            // Remainders which fit nowhere in the inventory are dropped
+            let gave_remainders = !remainders.is_empty();
+            dropped_remainders.extend(
+                remainders
+                    .into_iter()
+                    .filter_map(|remainder| inventory.insert_stack(remainder)),
+            );
+            if changed || gave_remainders {
                 drop(inventory);
                 self.set_container_content(opened_container.as_deref_mut())
                     .await;
             }
         }
+        for remainder in dropped_remainders {
+            self.drop_item(server, remainder).await;
+        }
+
+        if crafted_item_taken && experience_cost > 0 && self.gamemode.load() != GameMode::Creative {
+            self.add_experience_levels(-experience_cost).await;
+        }
+        let mut experience = 0;
+        if crafted_item_taken && !experience_reward.is_empty() {
+            experience += rand::thread_rng().gen_range(experience_reward);
+        }
+        if let (Some(container), container_click::Slot::Normal(slot)) =
+            (opened_container.as_deref_mut(), click_slot)
+        {
+            // Fractions of a point are awarded by chance, like vanilla's furnaces do
+            let stored = container.take_experience(slot);
+            experience += stored as i32;
+            if rand::random::<f32>() < stored.fract() {
+                experience += 1;
+            }
+        }
+        if experience > 0 {
+            let position = self.living_entity.entity.pos.load();
+            self.world()
+                .award_experience(server, position, experience)
+                .await;
+        }
 
         if let Some(mut opened_container) = opened_container {
             if update_whole_container {
@@ -197,11 +446,222 @@ impl Player {
                 }
             }
         }
+        if crafted_item_taken && is_anvil {
+            use_anvil(self, server).await;
+        }
         Ok(())
     }
 
+    /// Lets plugins and containers like virtual menus cancel the click,
+    /// the client already made the change so it is undone then. Returns whether the click was cancelled
+    async fn cancel_click(
+        &self,
+        server: &Server,
+        mut container: Option<&mut Box<dyn Container>>,
+        click: &Click,
+    ) -> bool {
+        let mut event = InventoryClickEvent::new(self, container.as_deref_mut(), click);
+        server.event_manager.fire_inventory_click(&mut event).await;
+        let intercepted = event.cancelled()
+            || container
+                .as_mut()
+                .is_some_and(|container| container.intercept_click(self.entity_id(), click));
+        if !intercepted {
+            return false;
+        }
+        self.set_container_content(container).await;
+        true
+    }
+
+    /// Armor with Curse of Binding stays on, only players in creative can take it off.
+    /// Armor slots are only part of the player's own inventory window
+    async fn takes_bound_armor(&self, click: &Click) -> bool {
+        let container_click::Slot::Normal(slot) = click.slot else {
+            return false;
+        };
+        // Drags and double clicks never take items out of armor slots
+        !matches!(
+            click.click_type,
+            ClickType::MouseDrag { .. } | ClickType::DoubleClick
+        ) && self.gamemode.load() != GameMode::Creative
+            && self.inventory().lock().await.is_bound_armor(slot)
+    }
+
+    /// Tells plugins the player closed their window
+    pub async fn fire_close_event(&self, server: &Server) {
+        let container = self.get_open_container(server).await;
+        let mut container = match container.as_ref() {
+            Some(container) => Some(container.lock().await),
+            None => None,
+        };
+        let mut event = InventoryCloseEvent::new(self, container.as_deref_mut());
+        server.event_manager.fire_inventory_close(&mut event).await;
+    }
+
+    /// Whether the player can pay the experience levels for taking a crafting output
+    fn can_pay_experience(&self, cost: i32) -> bool {
+        self.gamemode.load() == GameMode::Creative
+            || (cost < TOO_EXPENSIVE_COST
+                && self
+                    .experience_level
+                    .load(std::sync::atomic::Ordering::Relaxed)
+                    >= cost)
+    }
+
+    pub async fn handle_rename_item(&self, server: &Server, packet: SRenameItem) {
+        let Some(container) = self.get_open_container(server).await else {
+            return;
+        };
+        let mut container = container.lock().await;
+        container.rename_item(&packet.item_name);
+        self.set_container_content(Some(&mut container)).await;
+    }
+
+    pub async fn handle_select_trade(&self, server: &Server, packet: SSelectTrade) {
+        let Some(container) = self.get_open_container(server).await else {
+            return;
+        };
+        let Ok(index) = usize::try_from(packet.selected_slot.0) else {
+            return;
+        };
+        let mut container = container.lock().await;
+        {
+            let mut inventory = self.inventory().lock().await;
+            let mut combined =
+                OptionallyCombinedContainer::new(&mut inventory, Some(&mut container));
+            if let Err(error) = combined.select_trade(index) {
+                log::debug!("Failed to select trade {index}: {error}");
+            }
+        }
+        self.set_container_content(Some(&mut container)).await;
+    }
+
+    /// Remembers which item of the bundle the player hovers, it is the one taken out next
+    pub async fn handle_bundle_item_selected(&self, server: &Server, packet: SBundleItemSelected) {
+        let Ok(slot) = usize::try_from(packet.slot_id.0) else {
+            return;
+        };
+        let selected = usize::try_from(packet.selected_item_index.0).ok();
+        let container = self.get_open_container(server).await;
+        let mut container = match container.as_ref() {
+            Some(container) => Some(container.lock().await),
+            None => None,
+        };
+        let mut inventory = self.inventory().lock().await;
+        let mut combined =
+            OptionallyCombinedContainer::new(&mut inventory, container.as_deref_mut());
+        if let Some(Some(bundle)) = combined.all_slots().get_mut(slot) {
+            if bundle.is_bundle() {
+                bundle.select_bundle_item(selected);
+            }
+        }
+    }
+
+    pub async fn handle_container_button_click(
+        &self,
+        server: &Server,
+        packet: SContainerButtonClick,
+    ) {
+        if self.gamemode.load() == GameMode::Spectator {
+            return;
+        }
+        if !self.is_open_container_valid(server).await {
+            self.force_close_container(server).await;
+            return;
+        }
+        let Some(container) = self.get_open_container(server).await else {
+            return;
+        };
+        let mut container = container.lock().await;
+        if packet.window_id.0 != self.inventory().lock().await.total_opened_containers {
+            return;
+        }
+        if !container.handle_button_click(packet.button_id.0) {
+            log::debug!("Unknown container button {}", packet.button_id.0);
+        }
+        self.set_container_content(Some(&mut container)).await;
+    }
+
+    /// Opens the trading screen with the given offers, used by villagers and wandering traders
+    pub async fn open_merchant(
+        &self,
+        server: &Server,
+        offers: Vec<MerchantOffer>,
+        villager_level: i32,
+        experience: i32,
+        is_regular_villager: bool,
+    ) {
+        let trades: Vec<_> = offers
+            .iter()
+            .map(|offer| MerchantTrade {
+                input_1: trade_item(&offer.adjusted_cost_a()),
+                output: Slot::from(&offer.result),
+                input_2: offer.cost_b.as_ref().map(trade_item),
+                disabled: offer.is_out_of_stock(),
+                uses: offer.uses,
+                max_uses: offer.max_uses,
+                experience: offer.experience,
+                special_price: offer.special_price,
+                price_multiplier: offer.price_multiplier,
+                demand: offer.demand,
+            })
+            .collect();
+
+        let container_id = server.new_container_id();
+        let container = MerchantContainer::new(offers, experience);
+        server.open_containers.write().await.insert(
+            container_id.into(),
+            OpenContainer::new_without_block(self.entity_id(), Box::new(container)),
+        );
+        self.open_container.store(Some(container_id.into()));
+        if !self.open_container(server, WindowType::Merchant).await {
+            return;
+        }
+
+        let window_id = self.inventory().lock().await.total_opened_containers;
+        self.client
+            .send_packet(&CMerchantOffers::new(
+                window_id.into(),
+                &trades,
+                villager_level.into(),
+                experience.into(),
+                is_regular_villager,
+                true,
+            ))
+            .await;
+    }
+
+    /// Gives the items in the input slots of a container back, what doesn't fit is dropped.
+    /// Used by containers which don't keep their items, like anvils
+    pub async fn return_container_inputs(&self, server: &Server, container: &OpenContainer) {
+        let items: Vec<_> = {
+            let container = container.get_container();
+            let mut container = container.lock().await;
+            container
+                .all_combinable_slots_mut()
+                .into_iter()
+                .filter_map(Option::take)
+                .collect()
+        };
+        if items.is_empty() {
+            return;
+        }
+        let leftovers: Vec<_> = {
+            let mut inventory = self.inventory().lock().await;
+            items
+                .into_iter()
+                .filter_map(|item| inventory.insert_stack(item))
+                .collect()
+        };
+        for item in leftovers {
+            self.drop_item(server, item).await;
+        }
+        self.set_container_content(None).await;
+    }
+
     async fn match_click_behaviour(
         &self,
+        server: &Server,
         opened_container: Option<&mut Box<dyn Container>>,
         click: Click,
         drag_handler: &DragHandler,
@@ -219,11 +679,14 @@ impl Player {
                 .await
             }
             ClickType::ShiftClick => {
-                self.shift_mouse_click(opened_container, click.slot, using_crafting_slot)
-                    .await
+                // Quick moves can change any amount of slots
+                *update_whole_container = true;
+                self.shift_mouse_click(opened_container, click.slot).await
             }
             ClickType::KeyClick(key_click) => match click.slot {
                 container_click::Slot::Normal(slot) => {
+                    // The hotbar is not part of the container
+                    *update_whole_container = true;
                     self.number_button_pressed(
                         opened_container,
                         key_click,
@@ -243,8 +706,8 @@ impl Player {
             }
             ClickType::DoubleClick => {
                 *update_whole_container = true;
-                if let container_click::Slot::Normal(slot) = click.slot {
-                    self.double_click(opened_container, slot).await
+                if let container_click::Slot::Normal(_) = click.slot {
+                    self.double_click(opened_container).await
                 } else {
                     Err(InventoryError::InvalidPacket)
                 }
@@ -256,10 +719,20 @@ impl Player {
                 self.mouse_drag(drag_handler, opened_container, drag_state)
                     .await
             }
-            ClickType::DropType(_drop_type) => {
-                log::debug!("todo");
-                Ok(())
-            }
+            ClickType::DropType(drop_type) => match click.slot {
+                container_click::Slot::Normal(slot) => {
+                    self.drop_from_slot(
+                        server,
+                        opened_container,
+                        slot,
+                        &drop_type,
+                        using_crafting_slot,
+                    )
+                    .await
+                }
+                // Pressing the drop key outside of the window does nothing
+                container_click::Slot::OutsideInventory => Ok(()),
+            },
         }
     }
 
@@ -274,65 +747,48 @@ impl Player {
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
         match slot {
             container_click::Slot::Normal(slot) => {
-                let mut carried_item = self.carried_item.load();
-                let res = container.handle_item_change(
-                    &mut carried_item,
-                    slot,
-                    mouse_click,
-                    taking_crafted,
-                );
-                self.carried_item.store(carried_item);
-                res
+                let mut carried_item = self.carried_item.lock().await;
+                if !taking_crafted {
+                    if let Some(bundle_click) =
+                        container.bundle_click(&mut carried_item, slot, &mouse_click)?
+                    {
+                        drop(carried_item);
+                        drop(inventory);
+                        self.play_bundle_sound(bundle_click).await;
+                        return Ok(());
+                    }
+                }
+                container.handle_item_change(&mut carried_item, slot, mouse_click, taking_crafted)
             }
             container_click::Slot::OutsideInventory => Ok(()),
         }
     }
 
+    async fn play_bundle_sound(&self, bundle_click: BundleClick) {
+        let sound_id = match bundle_click {
+            BundleClick::Inserted => sound!("item.bundle.insert"),
+            BundleClick::InsertFailed => sound!("item.bundle.insert_fail"),
+            BundleClick::RemovedOne => sound!("item.bundle.remove_one"),
+            BundleClick::Unchanged => return,
+        };
+        let pos = self.living_entity.entity.pos.load();
+        self.world()
+            .play_sound(sound_id, SoundCategory::Players, &pos)
+            .await;
+    }
+
     async fn shift_mouse_click(
         &self,
         opened_container: Option<&mut Box<dyn Container>>,
         slot: container_click::Slot,
-        taking_crafted: bool,
     ) -> Result<(), InventoryError> {
         let mut inventory = self.inventory().lock().await;
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
 
         match slot {
-            container_click::Slot::Normal(slot) => {
-                let all_slots = container.all_slots();
-                if let Some(item_in_pressed_slot) = all_slots[slot].to_owned() {
-                    let slots = all_slots.into_iter().enumerate();
-                    // Hotbar
-                    let find_condition = |(slot_number, slot): (usize, &mut Option<ItemStack>)| {
-                        // TODO: Check for max item count here
-                        match slot {
-                            Some(item) => (item.item_id == item_in_pressed_slot.item_id
-                                && item.item_count != 64)
-                                .then_some(slot_number),
-                            None => Some(slot_number),
-                        }
-                    };
-
-                    let slots = if slot > 35 {
-                        slots.skip(9).find_map(find_condition)
-                    } else {
-                        slots.skip(36).rev().find_map(find_condition)
-                    };
-                    if let Some(slot) = slots {
-                        let mut item_slot = container.all_slots()[slot].map(|i| i);
-                        container.handle_item_change(
-                            &mut item_slot,
-                            slot,
-                            MouseClick::Left,
-                            taking_crafted,
-                        )?;
-                        *container.all_slots()[slot] = item_slot;
-                    }
-                }
-            }
-            container_click::Slot::OutsideInventory => (),
-        };
-        Ok(())
+            container_click::Slot::Normal(slot) => container.quick_move(slot),
+            container_click::Slot::OutsideInventory => Ok(()),
+        }
     }
 
     async fn number_button_pressed(
@@ -342,22 +798,9 @@ impl Player {
         slot: usize,
         taking_crafted: bool,
     ) -> Result<(), InventoryError> {
-        let changing_slot = match key_click {
-            KeyClick::Slot(slot) => slot,
-            KeyClick::Offhand => 45,
-        };
         let mut inventory = self.inventory().lock().await;
-        let mut changing_item_slot = inventory.get_slot(changing_slot as usize)?.to_owned();
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
-
-        container.handle_item_change(
-            &mut changing_item_slot,
-            slot,
-            MouseClick::Left,
-            taking_crafted,
-        )?;
-        *inventory.get_slot(changing_slot as usize)? = changing_item_slot;
-        Ok(())
+        container.swap_with_key(slot, &key_click, taking_crafted)
     }
 
     async fn creative_pick_item(
@@ -371,46 +814,42 @@ impl Player {
         let mut inventory = self.inventory().lock().await;
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
         if let Some(Some(item)) = container.all_slots().get_mut(slot) {
-            self.carried_item.store(Some(item.to_owned()));
+            *self.carried_item.lock().await = Some(item.clone());
         }
         Ok(())
     }
 
-    async fn double_click(
+    async fn drop_from_slot(
         &self,
+        server: &Server,
         opened_container: Option<&mut Box<dyn Container>>,
         slot: usize,
+        drop_type: &DropType,
+        taking_crafted: bool,
     ) -> Result<(), InventoryError> {
-        let mut inventory = self.inventory().lock().await;
-        let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
-        let mut slots = container.all_slots();
-
-        let Some(item) = slots.get_mut(slot) else {
-            return Ok(());
-        };
-        let Some(mut carried_item) = **item else {
+        // Vanilla only allows dropping from slots while the cursor is empty
+        if self.carried_item.lock().await.is_some() {
             return Ok(());
+        }
+        let dropped = {
+            let mut inventory = self.inventory().lock().await;
+            let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
+            container.take_for_drop(slot, drop_type, taking_crafted)?
         };
-        **item = None;
-
-        for slot in slots.iter_mut().filter_map(|slot| slot.as_mut()) {
-            if slot.item_id == carried_item.item_id {
-                // TODO: Check for max stack size
-                if slot.item_count + carried_item.item_count <= 64 {
-                    slot.item_count = 0;
-                    carried_item.item_count = 64;
-                } else {
-                    let to_remove = slot.item_count - (64 - carried_item.item_count);
-                    slot.item_count -= to_remove;
-                    carried_item.item_count += to_remove;
-                }
-
-                if carried_item.item_count == 64 {
-                    break;
-                }
-            }
+        if let Some(item_stack) = dropped {
+            self.drop_item(server, item_stack).await;
         }
-        self.carried_item.store(Some(carried_item));
+        Ok(())
+    }
+
+    async fn double_click(
+        &self,
+        opened_container: Option<&mut Box<dyn Container>>,
+    ) -> Result<(), InventoryError> {
+        let mut inventory = self.inventory().lock().await;
+        let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
+        let mut carried_item = self.carried_item.lock().await;
+        container.collect_to_cursor(&mut carried_item);
         Ok(())
     }
 
@@ -443,12 +882,10 @@ impl Player {
                 let mut inventory = self.inventory().lock().await;
                 let mut container =
                     OptionallyCombinedContainer::new(&mut inventory, opened_container);
-                let mut carried_item = self.carried_item.load();
-                let res = drag_handler
+                let mut carried_item = self.carried_item.lock().await;
+                drag_handler
                     .apply_drag(&mut carried_item, &mut container, &container_id, player_id)
-                    .await;
-                self.carried_item.store(carried_item);
-                res
+                    .await
             }
         }
     }
@@ -472,7 +909,7 @@ impl Player {
         let players = self
             .living_entity
             .entity
-            .world
+            .world()
             .current_players
             .lock()
             .await
@@ -536,73 +973,118 @@ impl Player {
         }
     }
 
-    async fn pickup_items(&self, item: &Item, mut amount: u32) {
-        let max_stack = item.components.max_stack_size;
-        let mut inventory = self.inventory().lock().await;
-        let slots = inventory.slots_with_hotbar_first();
+    /// Add items to inventory if there's space, else drop them to the ground.
+    ///
+    /// This method automatically syncs changes with the client.
+    pub async fn give_items(&self, server: &Server, item: &Item, amount: u32) {
+        self.give_stack(server, &ItemStack::new(1, item.id), amount)
+            .await;
+    }
 
-        let matching_slots = slots.filter_map(|slot| {
-            if let Some(item_slot) = slot.as_mut() {
-                (item_slot.item_id == item.id && item_slot.item_count < max_stack).then(|| {
-                    let item_count = item_slot.item_count;
-                    (item_slot, item_count)
-                })
-            } else {
-                None
+    /// Gives the amount of copies of the stack, keeping its components, see `give_items`
+    pub async fn give_stack(&self, server: &Server, stack: &ItemStack, amount: u32) {
+        let max_stack = u32::from(stack.max_stack_size());
+        let leftovers: Vec<_> = {
+            let mut inventory = self.inventory().lock().await;
+            let mut leftovers = vec![];
+            let mut remaining = amount;
+            while remaining > 0 {
+                let count = remaining.min(max_stack);
+                remaining -= count;
+                leftovers.extend(inventory.insert_stack(stack.with_count(count as u8)));
             }
-        });
+            leftovers
+        };
+        for item_stack in leftovers {
+            self.drop_item(server, item_stack).await;
+        }
+        self.set_container_content(None).await;
+    }
 
-        for (slot, item_count) in matching_slots {
-            if amount == 0 {
-                return;
-            }
-            let amount_to_add = max_stack - item_count;
-            if let Some(amount_left) = amount.checked_sub(u32::from(amount_to_add)) {
-                amount = amount_left;
-                *slot = ItemStack {
-                    item_id: item.id,
-                    item_count: item.components.max_stack_size,
-                };
-            } else {
-                *slot = ItemStack {
-                    item_id: item.id,
-                    item_count: max_stack - (amount_to_add - amount as u8),
-                };
-                return;
-            }
+    /// Picks up as many of the items as fit into the inventory, the rest stays on the ground.
+    /// Returns whether all items were picked up and the item entity is gone
+    pub async fn pickup_item_entity(&self, item_entity: &ItemEntity) -> bool {
+        if self.gamemode.load() == GameMode::Spectator || self.living_entity.health.load() <= 0.0 {
+            return false;
+        }
+        let mut item_stack = item_entity.item_stack.lock().await;
+        let item_id = item_stack.item_id;
+        let picked_up = {
+            let mut inventory = self.inventory().lock().await;
+            let leftover = inventory.insert_stack(item_stack.clone());
+            let picked_up =
+                item_stack.item_count - leftover.map_or(0, |leftover| leftover.item_count);
+            item_stack.item_count -= picked_up;
+            picked_up
+        };
+        let all_picked_up = item_stack.item_count == 0;
+        drop(item_stack);
+        if picked_up == 0 {
+            return false;
+        }
+        if let Some(stat) = Stat::item(StatType::PickedUp, item_id) {
+            self.award_stat(stat, picked_up.into()).await;
         }
 
-        let empty_slots = inventory
-            .slots_with_hotbar_first()
-            .filter(|slot| slot.is_none());
-        for slot in empty_slots {
-            if amount == 0 {
-                return;
-            }
-            if let Some(remaining_amount) = amount.checked_sub(u32::from(max_stack)) {
-                amount = remaining_amount;
-                *slot = Some(ItemStack {
-                    item_id: item.id,
-                    item_count: max_stack,
-                });
-            } else {
-                *slot = Some(ItemStack {
-                    item_id: item.id,
-                    item_count: amount as u8,
-                });
-                return;
-            }
+        item_entity
+            .entity
+            .broadcast_to_trackers(&CTakeItemEntity::new(
+                item_entity.entity.entity_id.into(),
+                self.entity_id().into(),
+                VarInt(picked_up.into()),
+            ))
+            .await;
+        if all_picked_up {
+            item_entity.discard().await;
+        } else {
+            item_entity.update_item_stack().await;
         }
-        log::warn!(
-            "{amount} items were discarded because dropping them to the ground is not implemented"
-        );
+        self.set_container_content(None).await;
+        all_picked_up
     }
 
-    /// Add items to inventory if there's space, else drop them to the ground.
-    ///
-    /// This method automatically syncs changes with the client.
-    pub async fn give_items(&self, item: &Item, amount: u32) {
-        self.pickup_items(item, amount).await;
+    /// Picks up the arrow or trident lying on the ground, like vanilla's `AbstractArrow.tryPickup`.
+    /// Returning tridents fly into the inventory of their owner. Returns whether it was picked up
+    pub async fn pickup_projectile(&self, projectile: &ProjectileEntity) -> bool {
+        if self.gamemode.load() == GameMode::Spectator || self.living_entity.health.load() <= 0.0 {
+            return false;
+        }
+        // Arrows and tridents are single items, they fit into the inventory whole or not at all
+        let insert = move || async move {
+            let Some(item_stack) = projectile.item_stack() else {
+                return false;
+            };
+            let mut inventory = self.inventory().lock().await;
+            inventory.insert_stack(item_stack.clone()).is_none()
+        };
+        let picked_up = match projectile.pickup() {
+            Pickup::Disallowed => false,
+            Pickup::Allowed => insert().await,
+            Pickup::CreativeOnly => self.gamemode.load() == GameMode::Creative,
+        } || (projectile.is_returning()
+            && projectile.owner() == Some(self.entity_id())
+            && insert().await);
+        if !picked_up {
+            return false;
+        }
+
+        projectile
+            .entity
+            .broadcast_to_trackers(&CTakeItemEntity::new(
+                projectile.entity.entity_id.into(),
+                self.entity_id().into(),
+                VarInt(1),
+            ))
+            .await;
+        projectile.discard().await;
         self.set_container_content(None).await;
+        true
+    }
+}
+
+fn trade_item(item: &ItemStack) -> TradeItem {
+    TradeItem {
+        item_id: VarInt(i32::from(item.item_id)),
+        item_count: VarInt(i32::from(item.item_count)),
     }
 }
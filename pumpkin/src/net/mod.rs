@@ -44,6 +44,7 @@ use tokio::sync::Mutex;
 
 use thiserror::Error;
 use uuid::Uuid;
+pub mod advancements;
 mod authentication;
 pub mod combat;
 mod container;
@@ -52,6 +53,11 @@ mod packet;
 mod proxy;
 pub mod query;
 pub mod rcon;
+mod recipe_book;
+pub mod stats;
+pub mod tags;
+
+pub use authentication::lookup_profile;
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct GameProfile {
@@ -452,7 +458,7 @@ impl Client {
                     .await;
             }
             SLoginPluginResponse::PACKET_ID => {
-                self.handle_plugin_response(SLoginPluginResponse::read(bytebuf)?)
+                self.handle_plugin_response(server, SLoginPluginResponse::read(bytebuf)?)
                     .await;
             }
             SLoginAcknowledged::PACKET_ID => {
@@ -8,7 +8,7 @@ use crate::{
 use core::str;
 use num_traits::FromPrimitive;
 use pumpkin_protocol::{
-    client::config::{CFinishConfig, CRegistryData},
+    client::config::{CFinishConfig, CRegistryData, CUpdateTags},
     codec::var_int::VarInt,
     server::config::{
         SClientInformationConfig, SConfigCookieResponse, SKnownPacks, SPluginMessage,
@@ -83,6 +83,8 @@ impl Client {
             ))
             .await;
         }
+        self.send_packet(&CUpdateTags::new(&server.cached_tags))
+            .await;
 
         // We are done with configuring
         log::debug!("finished config");
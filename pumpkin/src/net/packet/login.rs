@@ -14,6 +14,10 @@ use pumpkin_protocol::{
 use uuid::Uuid;
 
 use crate::{
+    data::{
+        banlist_serializer::ban_message, banned_ip_data::BANNED_IP_LIST,
+        banned_player_data::BANNED_PLAYER_LIST,
+    },
     net::{
         authentication::{self, AuthError},
         offline_uuid,
@@ -119,7 +123,7 @@ impl Client {
                 {
                     Ok((_ip, profile)) => {
                         // self.address.lock() = ip;
-                        self.finish_login(&profile).await;
+                        self.finish_login(server, &profile).await;
                         *gameprofile = Some(profile);
                     }
                     Err(error) => self.kick(&error.to_string()).await,
@@ -149,7 +153,7 @@ impl Client {
                 if ADVANCED_CONFIG.packet_compression.enabled {
                     self.enable_compression().await;
                 }
-                self.finish_login(&profile).await;
+                self.finish_login(server, &profile).await;
             }
 
             *gameprofile = Some(profile);
@@ -208,7 +212,7 @@ impl Client {
         if ADVANCED_CONFIG.packet_compression.enabled {
             self.enable_compression().await;
         }
-        self.finish_login(profile).await;
+        self.finish_login(server, profile).await;
     }
 
     async fn enable_compression(&self) {
@@ -218,7 +222,35 @@ impl Client {
         self.set_compression(Some(compression)).await;
     }
 
-    async fn finish_login(&self, profile: &GameProfile) {
+    /// Why the player may not join, like vanilla's `PlayerList.canPlayerLogin`
+    async fn login_denied_reason(&self, server: &Server, profile: &GameProfile) -> Option<String> {
+        let moderation = &ADVANCED_CONFIG.moderation;
+        if let Some(entry) = BANNED_PLAYER_LIST.write().await.get_entry(profile) {
+            return Some(ban_message(
+                &moderation.banned_message,
+                &entry.reason,
+                entry.expires,
+            ));
+        }
+        if !server.is_whitelisted(profile).await {
+            return Some(moderation.not_whitelisted_message.clone());
+        }
+        let ip = self.address.lock().await.ip();
+        if let Some(entry) = BANNED_IP_LIST.write().await.get_entry(ip) {
+            return Some(ban_message(
+                &moderation.ip_banned_message,
+                &entry.reason,
+                entry.expires,
+            ));
+        }
+        None
+    }
+
+    async fn finish_login(&self, server: &Server, profile: &GameProfile) {
+        if let Some(reason) = self.login_denied_reason(server, profile).await {
+            self.kick(&reason).await;
+            return;
+        }
         let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
         self.send_packet(&packet).await;
     }
@@ -279,7 +311,11 @@ impl Client {
         packet.payload_length.unwrap_or(VarInt::from(0)).0
     );
     }
-    pub async fn handle_plugin_response(&self, plugin_response: SLoginPluginResponse) {
+    pub async fn handle_plugin_response(
+        &self,
+        server: &Server,
+        plugin_response: SLoginPluginResponse,
+    ) {
         log::debug!("Handling plugin");
         let velocity_config = &ADVANCED_CONFIG.proxy.velocity;
         if velocity_config.enabled {
@@ -290,9 +326,11 @@ impl Client {
                 plugin_response,
             ) {
                 Ok((profile, new_address)) => {
-                    self.finish_login(&profile).await;
-                    *self.gameprofile.lock().await = Some(profile);
+                    // Bans of the address apply to the one the proxy forwarded
                     *address = new_address;
+                    drop(address);
+                    self.finish_login(server, &profile).await;
+                    *self.gameprofile.lock().await = Some(profile);
                 }
                 Err(error) => self.kick(&error.to_string()).await,
             }
@@ -2,10 +2,18 @@ use std::num::NonZeroU8;
 use std::sync::Arc;
 
 use crate::block::block_manager::BlockActionResult;
+use crate::net::stats::Stat;
 use crate::net::PlayerConfig;
 use crate::{
     command::CommandSender,
-    entity::player::{ChatMode, Hand, Player},
+    entity::{
+        armor_stand::ArmorStandEntity,
+        item_frame::ItemFrameEntity,
+        player::{ChatMode, Hand, ItemInUse, Player},
+        projectile::{
+            bow_power, is_arrow, Pickup, ProjectileEntity, ProjectileKind, ARROW_BASE_DAMAGE,
+        },
+    },
     error::PumpkinError,
     server::Server,
     world::player_chunker,
@@ -18,12 +26,15 @@ use pumpkin_core::{
     text::TextComponent,
     GameMode,
 };
+use pumpkin_entity::EntityId;
 use pumpkin_inventory::player::PlayerInventory;
 use pumpkin_inventory::InventoryError;
+use pumpkin_macros::sound;
 use pumpkin_protocol::client::play::{CSetContainerSlot, CSetHeldItem};
 use pumpkin_protocol::codec::slot::Slot;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::server::play::SCookieResponse as SPCookieResponse;
+use pumpkin_protocol::SoundCategory;
 use pumpkin_protocol::{
     client::play::CCommandSuggestions,
     server::play::{SCloseContainer, SCommandSuggestion, SKeepAlive, SSetPlayerGround, SUseItem},
@@ -40,9 +51,16 @@ use pumpkin_protocol::{
         SPlayerRotation, SSetCreativeSlot, SSetHeldItem, SSwingArm, SUseItemOn, Status,
     },
 };
-use pumpkin_world::block::{block_registry::get_block_by_item, BlockFace};
-use pumpkin_world::item::item_registry::get_item_by_id;
+use pumpkin_world::block::{
+    block_registry::{get_block_and_state_by_state_id, get_block_by_item},
+    fluid::{fluid_state, Fluid, FluidState},
+    BlockFace,
+};
+use pumpkin_world::item::item_registry::{
+    get_item, get_item_by_id, get_item_name_by_id, EquipmentSlot,
+};
 use pumpkin_world::item::ItemStack;
+use pumpkin_world::stats::StatType;
 use thiserror::Error;
 
 fn modulus(a: f32, b: f32) -> f32 {
@@ -54,6 +72,7 @@ pub enum BlockPlacingError {
     BlockOutOfReach,
     InvalidBlockFace,
     BlockOutOfWorld,
+    BlockOutsideWorldBorder,
     InventoryInvalid,
 }
 
@@ -66,23 +85,24 @@ impl std::fmt::Display for BlockPlacingError {
 impl PumpkinError for BlockPlacingError {
     fn is_kick(&self) -> bool {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld => false,
+            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::BlockOutsideWorldBorder => false,
             Self::InvalidBlockFace | Self::InventoryInvalid => true,
         }
     }
 
     fn severity(&self) -> log::Level {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::InvalidBlockFace => {
-                log::Level::Warn
-            }
+            Self::BlockOutOfReach
+            | Self::BlockOutOfWorld
+            | Self::BlockOutsideWorldBorder
+            | Self::InvalidBlockFace => log::Level::Warn,
             Self::InventoryInvalid => log::Level::Error,
         }
     }
 
     fn client_kick_reason(&self) -> Option<String> {
         match self {
-            Self::BlockOutOfReach | Self::BlockOutOfWorld => None,
+            Self::BlockOutOfReach | Self::BlockOutOfWorld | Self::BlockOutsideWorldBorder => None,
             Self::InvalidBlockFace => Some("Invalid block face".into()),
             Self::InventoryInvalid => Some("Held item invalid".into()),
         }
@@ -137,13 +157,17 @@ impl Player {
         let pos = entity.pos.load();
         let last_pos = self.living_entity.last_pos.load();
 
-        entity
+        let was_on_ground = entity
             .on_ground
-            .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
+            .swap(packet.ground, std::sync::atomic::Ordering::Relaxed);
+        self.exhaust_from_movement(last_pos, pos, was_on_ground, packet.ground)
+            .await;
+        self.award_movement_stats(last_pos, pos, was_on_ground, packet.ground)
+            .await;
+        self.check_fly_into_wall(last_pos, pos).await;
 
         let entity_id = entity.entity_id;
         let Vector3 { x, y, z } = pos;
-        let world = &entity.world;
 
         // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
         // let velocity = self.velocity;
@@ -158,19 +182,16 @@ impl Player {
         //     return;
         // }
         // send new position to all other players
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CUpdateEntityPos::new(
-                    entity_id.into(),
-                    Vector3::new(
-                        x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
-                        y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
-                        z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
-                    ),
-                    packet.ground,
+        entity
+            .broadcast_to_trackers(&CUpdateEntityPos::new(
+                entity_id.into(),
+                Vector3::new(
+                    x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                    y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                    z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
                 ),
-            )
+                packet.ground,
+            ))
             .await;
         player_chunker::update_position(self).await;
     }
@@ -198,9 +219,14 @@ impl Player {
         let pos = entity.pos.load();
         let last_pos = self.living_entity.last_pos.load();
 
-        entity
+        let was_on_ground = entity
             .on_ground
-            .store(packet.ground, std::sync::atomic::Ordering::Relaxed);
+            .swap(packet.ground, std::sync::atomic::Ordering::Relaxed);
+        self.exhaust_from_movement(last_pos, pos, was_on_ground, packet.ground)
+            .await;
+        self.award_movement_stats(last_pos, pos, was_on_ground, packet.ground)
+            .await;
+        self.check_fly_into_wall(last_pos, pos).await;
 
         entity.set_rotation(
             wrap_degrees(packet.yaw) % 360.0,
@@ -213,7 +239,6 @@ impl Player {
         let yaw = modulus(entity.yaw.load() * 256.0 / 360.0, 256.0);
         let pitch = modulus(entity.pitch.load() * 256.0 / 360.0, 256.0);
         // let head_yaw = (entity.head_yaw * 256.0 / 360.0).floor();
-        let world = &entity.world;
 
         // let delta = Vector3::new(x - lastx, y - lasty, z - lastz);
         // let velocity = self.velocity;
@@ -229,27 +254,21 @@ impl Player {
         // }
         // send new position to all other players
 
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CUpdateEntityPosRot::new(
-                    entity_id.into(),
-                    Vector3::new(
-                        x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
-                        y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
-                        z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
-                    ),
-                    yaw as u8,
-                    pitch as u8,
-                    packet.ground,
+        entity
+            .broadcast_to_trackers(&CUpdateEntityPosRot::new(
+                entity_id.into(),
+                Vector3::new(
+                    x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                    y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                    z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
                 ),
-            )
+                yaw as u8,
+                pitch as u8,
+                packet.ground,
+            ))
             .await;
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CHeadRot::new(entity_id.into(), yaw as u8),
-            )
+        entity
+            .broadcast_to_trackers(&CHeadRot::new(entity_id.into(), yaw as u8))
             .await;
         player_chunker::update_position(self).await;
     }
@@ -273,16 +292,11 @@ impl Player {
         let pitch = modulus(entity.pitch.load() * 256.0 / 360.0, 256.0);
         // let head_yaw = modulus(entity.head_yaw * 256.0 / 360.0, 256.0);
 
-        let world = &entity.world;
         let packet =
             CUpdateEntityRot::new(entity_id.into(), yaw as u8, pitch as u8, rotation.ground);
-        world
-            .broadcast_packet_except(&[self.gameprofile.id], &packet)
-            .await;
+        entity.broadcast_to_trackers(&packet).await;
         let packet = CHeadRot::new(entity_id.into(), yaw as u8);
-        world
-            .broadcast_packet_except(&[self.gameprofile.id], &packet)
-            .await;
+        entity.broadcast_to_trackers(&packet).await;
     }
 
     pub async fn handle_chat_command(
@@ -337,7 +351,8 @@ impl Player {
             return;
         }
 
-        let Ok(block) = self.world().get_block(pick_item.pos).await else {
+        let world = self.world();
+        let Ok(block) = world.get_block(pick_item.pos).await else {
             return;
         };
 
@@ -419,26 +434,31 @@ impl Player {
             match action {
                 pumpkin_protocol::server::play::Action::StartSneaking => {
                     if !entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(true).await;
+                        entity.set_sneaking(true);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StopSneaking => {
                     if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(false).await;
+                        entity.set_sneaking(false);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StartSprinting => {
                     if !entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(true).await;
+                        entity.set_sprinting(true);
                     }
                 }
                 pumpkin_protocol::server::play::Action::StopSprinting => {
                     if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(false).await;
+                        entity.set_sprinting(false);
+                    }
+                }
+                pumpkin_protocol::server::play::Action::LeaveBed => {
+                    if self.is_sleeping() {
+                        self.wake_up().await;
+                        self.world().announce_sleeping().await;
                     }
                 }
-                pumpkin_protocol::server::play::Action::LeaveBed
-                | pumpkin_protocol::server::play::Action::StartHorseJump
+                pumpkin_protocol::server::play::Action::StartHorseJump
                 | pumpkin_protocol::server::play::Action::StopHorseJump
                 | pumpkin_protocol::server::play::Action::OpenVehicleInventory => {
                     log::debug!("todo");
@@ -450,7 +470,7 @@ impl Player {
                         .load(std::sync::atomic::Ordering::Relaxed)
                         != fall_flying
                     {
-                        entity.set_fall_flying(fall_flying).await;
+                        entity.set_fall_flying(fall_flying);
                     }
                 } // TODO
             }
@@ -480,12 +500,9 @@ impl Player {
         };
 
         let id = self.entity_id();
-        let world = self.world();
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CEntityAnimation::new(id.into(), animation as u8),
-            )
+        self.living_entity
+            .entity
+            .broadcast_to_trackers(&CEntityAnimation::new(id.into(), animation as u8))
             .await;
     }
 
@@ -506,7 +523,7 @@ impl Player {
         log::info!("<chat>{}: {}", gameprofile.name, message);
 
         let entity = &self.living_entity.entity;
-        let world = &entity.world;
+        let world = &entity.world();
         world
             .broadcast_packet_all(&CPlayerChatMessage::new(
                 gameprofile.id,
@@ -607,19 +624,20 @@ impl Player {
         }
     }
 
-    pub async fn handle_client_status(self: &Arc<Self>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        self: &Arc<Self>,
+        server: &Server,
+        client_status: SClientCommand,
+    ) {
         match client_status.action_id.0 {
             0 => {
-                if self.living_entity.health.load() > 0.0 {
+                // Only players who were told they died may respawn
+                if !self.dead.load(std::sync::atomic::Ordering::Relaxed) {
                     return;
                 }
-                self.world().respawn_player(self, false).await;
-                // TODO: hardcore set spectator
-            }
-            1 => {
-                // request stats
-                log::debug!("todo");
+                self.respawn(server).await;
             }
+            1 => self.send_stats().await,
             _ => {
                 self.kick(TextComponent::text("Invalid client status"))
                     .await;
@@ -627,27 +645,37 @@ impl Player {
         };
     }
 
-    pub async fn handle_interact(&self, interact: SInteract) {
+    pub async fn handle_interact(&self, server: &Server, interact: SInteract) {
         let sneaking = interact.sneaking;
         let entity = &self.living_entity.entity;
         if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) != sneaking {
-            entity.set_sneaking(sneaking).await;
+            entity.set_sneaking(sneaking);
         }
         let Some(action) = ActionType::from_i32(interact.typ.0) else {
             self.kick(TextComponent::text("Invalid action type")).await;
             return;
         };
+        let offhand = interact.hand.is_some_and(|hand| hand.0 == 1);
 
         match action {
             ActionType::Attack => {
                 let entity_id = interact.entity_id;
+                if self.attack_decoration_entity(server, entity_id.0).await {
+                    return;
+                }
+                if let Some(mob) = entity.world().get_mob(entity_id.0).await {
+                    if self.gamemode.load() != GameMode::Spectator {
+                        self.attack_mob(&mob).await;
+                    }
+                    return;
+                }
                 let config = &ADVANCED_CONFIG.pvp;
                 // TODO: do validation and stuff
                 if !config.enabled {
                     return;
                 }
 
-                let world = &entity.world;
+                let world = &entity.world();
                 let victim = world.get_player_by_entityid(entity_id.0).await;
                 let Some(victim) = victim else {
                     self.kick(TextComponent::text("Interacted with invalid entity id"))
@@ -665,90 +693,95 @@ impl Player {
                         .await;
                     return;
                 }
-                self.attack(&victim).await;
-            }
-            ActionType::Interact | ActionType::InteractAt => {
-                log::debug!("todo");
+                self.attack(server, &victim).await;
             }
-        }
-    }
-
-    pub async fn handle_player_action(&self, player_action: SPlayerAction, server: &Server) {
-        match Status::from_i32(player_action.status.0) {
-            Some(status) => match status {
-                Status::StartedDigging => {
-                    if !self.can_interact_with_block_at(&player_action.location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            self.gameprofile.name,
-                            player_action.location
-                        );
+            ActionType::Interact => {
+                // TODO: Only tamed horses should open, and riding players should too
+                if sneaking {
+                    if let Some(horse) = entity.world().get_horse_entity(interact.entity_id.0).await
+                    {
+                        self.open_horse_inventory(server, &horse).await;
                         return;
                     }
-                    // TODO: do validation
-                    // TODO: Config
-                    if self.gamemode.load() == GameMode::Creative {
-                        let location = player_action.location;
-                        // Block break & block break sound
-                        let entity = &self.living_entity.entity;
-                        let world = &entity.world;
-                        let block = world.get_block(location).await;
-
-                        world.break_block(location, Some(self)).await;
-
-                        if let Ok(block) = block {
-                            server
-                                .block_manager
-                                .on_broken(block, self, location, server)
-                                .await;
+                }
+                if self.gamemode.load() != GameMode::Spectator {
+                    if let Some(item_frame) =
+                        entity.world().get_item_frame(interact.entity_id.0).await
+                    {
+                        item_frame.interact(self, offhand).await;
+                        return;
+                    }
+                    if let Some(mob) = entity.world().get_mob(interact.entity_id.0).await {
+                        if mob.use_name_tag(self, offhand).await || mob.feed(self, offhand).await {
+                            return;
                         }
                     }
                 }
-                Status::CancelledDigging => {
-                    if !self.can_interact_with_block_at(&player_action.location, 1.0) {
-                        log::warn!(
-                            "Player {0} tried to interact with block out of reach at {1}",
-                            self.gameprofile.name,
-                            player_action.location
-                        );
+                log::debug!("todo");
+            }
+            ActionType::InteractAt => {
+                // Armor stands care about where they were clicked, so they use this instead of interact
+                if self.gamemode.load() != GameMode::Spectator {
+                    if let (Some(armor_stand), Some(target)) = (
+                        entity.world().get_armor_stand(interact.entity_id.0).await,
+                        interact.target_position,
+                    ) {
+                        armor_stand.interact_at(self, offhand, target.y).await;
                         return;
                     }
-                    self.current_block_destroy_stage
-                        .store(0, std::sync::atomic::Ordering::Relaxed);
                 }
-                Status::FinishedDigging => {
-                    // TODO: do validation
+                log::debug!("todo");
+            }
+        }
+    }
+
+    pub async fn handle_player_action(&self, player_action: SPlayerAction, server: &Server) {
+        match Status::from_i32(player_action.status.0) {
+            Some(status) => match status {
+                Status::StartedDigging | Status::CancelledDigging | Status::FinishedDigging => {
                     let location = player_action.location;
                     if !self.can_interact_with_block_at(&location, 1.0) {
                         log::warn!(
                             "Player {0} tried to interact with block out of reach at {1}",
                             self.gameprofile.name,
-                            player_action.location
+                            location
                         );
                         return;
                     }
-                    // Block break & block break sound
-                    let entity = &self.living_entity.entity;
-                    let world = &entity.world;
-                    let block = world.get_block(location).await;
-
-                    world.break_block(location, Some(self)).await;
-
-                    if let Ok(block) = block {
-                        server
-                            .block_manager
-                            .on_broken(block, self, location, server)
-                            .await;
+                    match status {
+                        Status::StartedDigging => self.start_digging(server, location).await,
+                        Status::CancelledDigging => self.cancel_digging().await,
+                        _ => self.finish_digging(server, location).await,
                     }
-                    // TODO: Send this every tick
                     self.client
                         .send_packet(&CAcknowledgeBlockChange::new(player_action.sequence))
                         .await;
                 }
-                Status::DropItemStack
-                | Status::DropItem
-                | Status::ShootArrowOrFinishEating
-                | Status::SwapItem => {
+                Status::DropItemStack | Status::DropItem => {
+                    if self.gamemode.load() == GameMode::Spectator {
+                        return;
+                    }
+                    let whole_stack = matches!(status, Status::DropItemStack);
+                    let dropped = {
+                        let mut inventory = self.inventory().lock().await;
+                        let held = inventory.held_item_mut();
+                        if whole_stack {
+                            held.take()
+                        } else {
+                            let single = held.as_ref().map(|item| item.with_count(1));
+                            if let Some(item) = held.as_mut() {
+                                item.item_count -= 1;
+                            }
+                            held.take_if(|item| item.item_count == 0);
+                            single
+                        }
+                    };
+                    if let Some(item) = dropped {
+                        self.drop_item(server, item).await;
+                    }
+                }
+                Status::ShootArrowOrFinishEating => self.release_using_item(server).await,
+                Status::SwapItem => {
                     log::debug!("todo");
                 }
             },
@@ -789,6 +822,7 @@ impl Player {
             .await;
     }
 
+    #[expect(clippy::too_many_lines)]
     pub async fn handle_use_item_on(
         &self,
         use_item_on: SUseItemOn,
@@ -804,11 +838,11 @@ impl Player {
         if let Some(face) = BlockFace::from_i32(use_item_on.face.0) {
             let inventory = self.inventory().lock().await;
             let entity = &self.living_entity.entity;
-            let world = &entity.world;
+            let world = &entity.world();
             let item_slot = inventory.held_item();
 
             if let Some(item_stack) = item_slot {
-                let item_stack = *item_stack;
+                let item_stack = item_stack.clone();
                 drop(inventory);
 
                 if let Some(item) = get_item_by_id(item_stack.item_id) {
@@ -867,6 +901,12 @@ impl Player {
                             .await;
                         return Err(BlockPlacingError::BlockOutOfWorld.into());
                     }
+                    if !world.worldborder.lock().await.contains_block(&world_pos) {
+                        self.client
+                            .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
+                            .await;
+                        return Err(BlockPlacingError::BlockOutsideWorldBorder.into());
+                    }
 
                     let block_bounding_box = BoundingBox::from_block(&world_pos);
                     let mut intersects = false;
@@ -882,13 +922,35 @@ impl Player {
                             .await;
                         server
                             .block_manager
-                            .on_placed(block, self, world_pos, server)
+                            .on_placed(block, self, world_pos, &item_stack, server)
                             .await;
+                        world.update_neighbors(world_pos, server).await;
+                        self.trigger_placed_block(&block.name).await;
+                        if let Some(stat) = Stat::item(StatType::Used, item_stack.item_id) {
+                            self.award_stat(stat, 1).await;
+                        }
                     }
 
                     self.client
                         .send_packet(&CAcknowledgeBlockChange::new(use_item_on.sequence))
                         .await;
+                } else {
+                    self.use_igniter(
+                        server,
+                        &item_stack,
+                        location,
+                        face,
+                        use_item_on.sequence.clone(),
+                    )
+                    .await;
+                    self.place_decoration_entity(
+                        server,
+                        &item_stack,
+                        location,
+                        face,
+                        use_item_on.sequence,
+                    )
+                    .await;
                 }
             } else {
                 drop(inventory);
@@ -907,8 +969,621 @@ impl Player {
         }
     }
 
-    pub fn handle_use_item(&self, _use_item: &SUseItem) {
-        // TODO: handle packet correctly
+    /// The blocks the player looks through, starting at the eyes
+    fn blocks_in_sight(&self, yaw: f32, pitch: f32, range: f64) -> Vec<WorldPosition> {
+        let entity = &self.living_entity.entity;
+        let pos = entity.pos.load();
+        let eyes = Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z);
+        let (yaw, pitch) = (f64::from(yaw).to_radians(), f64::from(pitch).to_radians());
+        let direction = Vector3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        );
+
+        // Small steps don't skip the corners of blocks in practice
+        let steps = (range / 0.05) as u32;
+        let mut blocks: Vec<WorldPosition> = Vec::new();
+        for step in 0..=steps {
+            let point = eyes + direction * (f64::from(step) * 0.05);
+            let block = WorldPosition(Vector3::new(
+                point.x.floor() as i32,
+                point.y.floor() as i32,
+                point.z.floor() as i32,
+            ));
+            if blocks.last() != Some(&block) {
+                blocks.push(block);
+            }
+        }
+        blocks
+    }
+
+    /// Fills empty buckets from the fluid source looked at and empties filled ones in front of the
+    /// block looked at. Returns whether the held item was a bucket
+    async fn use_bucket(&self, server: &Server, offhand: bool, yaw: f32, pitch: f32) -> bool {
+        let held = self.inventory().lock().await.hand_item_mut(offhand).clone();
+        let Some(name) = held.and_then(|held| get_item_name_by_id(held.item_id)) else {
+            return false;
+        };
+        let carried = match name {
+            "bucket" => None,
+            "water_bucket" => Some(Fluid::Water),
+            "lava_bucket" => Some(Fluid::Lava),
+            _ => return false,
+        };
+        let creative = self.gamemode.load() == GameMode::Creative;
+        let range = if creative { 5.0 } else { 4.5 };
+        let Some((position, fluid)) = self.bucket_target(carried, yaw, pitch, range).await else {
+            return true;
+        };
+        if carried.is_some() {
+            self.empty_bucket(server, offhand, position, fluid, creative)
+                .await;
+        } else {
+            self.fill_bucket(server, offhand, position, fluid, creative)
+                .await;
+        }
+        true
+    }
+
+    /// Throws a snowball, egg, ender pearl or bottle o' enchanting where the player looks, like
+    /// vanilla's `SnowballItem.use`
+    async fn use_throwable(&self, server: &Server, offhand: bool) -> bool {
+        let kind = {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.hand_item_mut(offhand);
+            let Some(kind) = held
+                .as_ref()
+                .and_then(|held| get_item_name_by_id(held.item_id))
+                .and_then(ProjectileKind::thrown_from)
+            else {
+                return false;
+            };
+            if self.gamemode.load() != GameMode::Creative {
+                if let Some(item) = held.as_mut() {
+                    item.item_count -= 1;
+                }
+                held.take_if(|item| item.item_count == 0);
+            }
+            kind
+        };
+        self.set_container_content(None).await;
+        let world = self.world();
+        let entity = &self.living_entity.entity;
+        let projectile = ProjectileEntity::new(
+            server.new_entity_id(),
+            world.clone(),
+            kind,
+            Some(entity),
+            None,
+            Pickup::Disallowed,
+        );
+        // Bottles are tossed upwards
+        let (pitch_offset, power) = if kind == ProjectileKind::ExperienceBottle {
+            (-20.0, 0.7)
+        } else {
+            (0.0, 1.5)
+        };
+        projectile.shoot_from_rotation(entity, pitch_offset, power, 1.0);
+        world.add_projectile(Arc::new(projectile)).await;
+        let sound = match kind {
+            ProjectileKind::Snowball => sound!("entity.snowball.throw"),
+            ProjectileKind::Egg => sound!("entity.egg.throw"),
+            ProjectileKind::EnderPearl => sound!("entity.ender_pearl.throw"),
+            _ => sound!("entity.experience_bottle.throw"),
+        };
+        world
+            .play_sound(sound, SoundCategory::Neutral, &entity.pos.load())
+            .await;
+        true
+    }
+
+    /// Starts drawing a bow or raising a trident, bows need arrows unless the player is in
+    /// creative. Tridents about to break can't be thrown
+    async fn use_bow_or_trident(&self, offhand: bool) -> bool {
+        let item_id = {
+            let mut inventory = self.inventory().lock().await;
+            let Some(held) = inventory.hand_item_mut(offhand).clone() else {
+                return false;
+            };
+            match get_item_name_by_id(held.item_id) {
+                Some("bow") => {
+                    if self.gamemode.load() != GameMode::Creative
+                        && inventory.find_item_mut(is_arrow).is_none()
+                    {
+                        return false;
+                    }
+                }
+                Some("trident") => {
+                    let max_damage =
+                        get_item_by_id(held.item_id).and_then(|item| item.components.max_damage);
+                    if max_damage.is_some_and(|max_damage| {
+                        held.components.damage.unwrap_or(0) >= i32::from(max_damage) - 1
+                    }) {
+                        return false;
+                    }
+                }
+                _ => return false,
+            }
+            held.item_id
+        };
+        self.start_using_item(offhand, item_id);
+        true
+    }
+
+    /// Starts eating or drinking the held item, the player finishes while ticking. Food is only
+    /// eaten by hungry players unless it can always be eaten
+    async fn start_consuming(&self, offhand: bool) -> bool {
+        let Some(held) = self.inventory().lock().await.hand_item_mut(offhand).clone() else {
+            return false;
+        };
+        let Some(item) = get_item_by_id(held.item_id) else {
+            return false;
+        };
+        if item.components.consumable.is_none() {
+            return false;
+        }
+        if let Some(food) = &item.components.food {
+            let hungry = food.can_always_eat
+                || self.abilities.lock().await.invulnerable
+                || self.food_data.lock().await.needs_food();
+            if !hungry {
+                return true;
+            }
+        }
+        self.start_using_item(offhand, held.item_id);
+        true
+    }
+
+    /// Raises a shield, it blocks damage until the player lowers it again
+    async fn raise_shield(&self, offhand: bool) -> bool {
+        let held = self.inventory().lock().await.hand_item_mut(offhand).clone();
+        let Some(held) = held.filter(|held| get_item_name_by_id(held.item_id) == Some("shield"))
+        else {
+            return false;
+        };
+        self.start_using_item(offhand, held.item_id);
+        true
+    }
+
+    /// Lets go of the item being used, bows shoot an arrow and tridents are thrown
+    async fn release_using_item(&self, server: &Server) {
+        let Some(item_in_use) = self.stop_using_item() else {
+            return;
+        };
+        match get_item_name_by_id(item_in_use.item_id) {
+            Some("bow") => self.release_bow(server, item_in_use).await,
+            Some("trident") => self.release_trident(server, item_in_use).await,
+            _ => {}
+        }
+    }
+
+    /// Shoots an arrow from the bow, like vanilla's `BowItem.releaseUsing`. Fully drawn bows shoot
+    /// critical arrows, Infinity spares plain arrows
+    async fn release_bow(&self, server: &Server, item_in_use: ItemInUse) {
+        let power = bow_power(item_in_use.ticks);
+        if power < 0.1 {
+            return;
+        }
+        let creative = self.gamemode.load() == GameMode::Creative;
+        let (bow, arrow, infinite, broke) = {
+            let mut inventory = self.inventory().lock().await;
+            let Some(bow) = inventory
+                .hand_item_mut(item_in_use.offhand)
+                .clone()
+                .filter(|held| held.item_id == item_in_use.item_id)
+            else {
+                return;
+            };
+            let has_infinity = bow.enchantment_level("infinity") > 0;
+            let (arrow, infinite) = match inventory.find_item_mut(is_arrow) {
+                Some(slot) => {
+                    let Some(stack) = slot.as_mut() else {
+                        return;
+                    };
+                    let mut arrow = stack.clone();
+                    arrow.item_count = 1;
+                    let infinite = creative
+                        || (has_infinity && get_item_name_by_id(stack.item_id) == Some("arrow"));
+                    if !infinite {
+                        stack.item_count -= 1;
+                        slot.take_if(|item| item.item_count == 0);
+                    }
+                    (arrow, infinite)
+                }
+                None if creative => {
+                    let Some(item) = get_item("arrow") else {
+                        return;
+                    };
+                    (ItemStack::new(1, item.id), true)
+                }
+                None => return,
+            };
+            let held = inventory.hand_item_mut(item_in_use.offhand);
+            let broke = !creative && held.as_mut().is_some_and(|bow| bow.damage_item(1));
+            if broke {
+                *held = None;
+            }
+            (bow, arrow, infinite, broke)
+        };
+        self.set_container_content(None).await;
+
+        let world = self.world();
+        let entity = &self.living_entity.entity;
+        let pickup = if infinite {
+            Pickup::CreativeOnly
+        } else {
+            Pickup::Allowed
+        };
+        let projectile = ProjectileEntity::new(
+            server.new_entity_id(),
+            world.clone(),
+            ProjectileKind::Arrow,
+            Some(entity),
+            Some(arrow),
+            pickup,
+        );
+        projectile.shoot_from_rotation(entity, 0.0, power * 3.0, 1.0);
+        projectile.set_critical(power >= 1.0);
+        let power_level = bow.enchantment_level("power");
+        if power_level > 0 {
+            projectile
+                .set_base_damage(ARROW_BASE_DAMAGE + f64::from(power_level).mul_add(0.5, 0.5));
+        }
+        let punch = bow.enchantment_level("punch");
+        if punch > 0 {
+            projectile.set_knockback(f64::from(punch));
+        }
+        world.add_projectile(Arc::new(projectile)).await;
+        let position = entity.pos.load();
+        world
+            .play_sound(
+                sound!("entity.arrow.shoot"),
+                SoundCategory::Players,
+                &position,
+            )
+            .await;
+        if broke {
+            let slot = if item_in_use.offhand {
+                EquipmentSlot::OffHand
+            } else {
+                EquipmentSlot::MainHand
+            };
+            self.broadcast_item_break(slot).await;
+        }
+    }
+
+    /// Throws the trident once it was raised for half a second, like vanilla's
+    /// `TridentItem.releaseUsing`. Tridents thrown in creative stay in the inventory
+    async fn release_trident(&self, server: &Server, item_in_use: ItemInUse) {
+        if item_in_use.ticks < 10 {
+            return;
+        }
+        let creative = self.gamemode.load() == GameMode::Creative;
+        let trident = {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.hand_item_mut(item_in_use.offhand);
+            let Some(trident) = held
+                .as_mut()
+                .filter(|held| held.item_id == item_in_use.item_id)
+            else {
+                return;
+            };
+            // TODO: Riptide launches the player instead
+            if trident.enchantment_level("riptide") > 0 {
+                return;
+            }
+            if !creative {
+                // Tridents about to break can't be raised, so this doesn't break it
+                trident.damage_item(1);
+            }
+            let trident = trident.clone();
+            if !creative {
+                *held = None;
+            }
+            trident
+        };
+        self.set_container_content(None).await;
+
+        let world = self.world();
+        let entity = &self.living_entity.entity;
+        let pickup = if creative {
+            Pickup::CreativeOnly
+        } else {
+            Pickup::Allowed
+        };
+        let projectile = ProjectileEntity::new(
+            server.new_entity_id(),
+            world.clone(),
+            ProjectileKind::Trident,
+            Some(entity),
+            Some(trident),
+            pickup,
+        );
+        projectile.shoot_from_rotation(entity, 0.0, 2.5, 1.0);
+        world.add_projectile(Arc::new(projectile)).await;
+        world
+            .play_sound(
+                sound!("item.trident.throw"),
+                SoundCategory::Players,
+                &entity.pos.load(),
+            )
+            .await;
+    }
+
+    /// Where a bucket is used. Empty buckets stop at the first source, filled ones in front of the
+    /// first block which is not a fluid
+    async fn bucket_target(
+        &self,
+        carried: Option<Fluid>,
+        yaw: f32,
+        pitch: f32,
+        range: f64,
+    ) -> Option<(WorldPosition, Fluid)> {
+        let world = &self.world();
+        let mut previous = None;
+        for position in self.blocks_in_sight(yaw, pitch, range) {
+            let state_id = world.get_block_state_id(position).await.ok()?;
+            let (block, state) = get_block_and_state_by_state_id(state_id)?;
+            let fluid = Fluid::from_name(&block.name);
+            match carried {
+                None => {
+                    if let Some(fluid) = fluid {
+                        if fluid_state(state_id).is_some_and(|fluid| fluid.source) {
+                            return Some((position, fluid));
+                        }
+                    } else if !state.air {
+                        return None;
+                    }
+                }
+                Some(carried) => {
+                    if !state.air && fluid.is_none() {
+                        let target = if state.replaceable {
+                            Some(position)
+                        } else {
+                            previous
+                        };
+                        return target.map(|target| (target, carried));
+                    }
+                    previous = Some(position);
+                }
+            }
+        }
+        None
+    }
+
+    async fn fill_bucket(
+        &self,
+        server: &Server,
+        offhand: bool,
+        position: WorldPosition,
+        fluid: Fluid,
+        creative: bool,
+    ) {
+        let world = &self.world();
+        world.set_block_state(position, 0).await;
+        world.update_neighbors(position, server).await;
+        let (sound, filled) = match fluid {
+            Fluid::Water => (sound!("item.bucket.fill"), "water_bucket"),
+            Fluid::Lava => (sound!("item.bucket.fill_lava"), "lava_bucket"),
+        };
+        world.play_block_sound(sound, position).await;
+        let Some(filled) = get_item(filled) else {
+            return;
+        };
+        if creative {
+            // Creative players keep the empty bucket
+            let has_filled = self
+                .inventory()
+                .lock()
+                .await
+                .get_slot_with_item(filled.id)
+                .is_some();
+            if !has_filled {
+                self.give_items(server, filled, 1).await;
+            }
+            return;
+        }
+        let mut inventory = self.inventory().lock().await;
+        let held = inventory.hand_item_mut(offhand);
+        let stacked = held.as_ref().is_some_and(|held| held.item_count > 1);
+        if let Some(held) = held.as_mut().filter(|_| stacked) {
+            held.item_count -= 1;
+        } else {
+            *held = Some(ItemStack::new(1, filled.id));
+        }
+        drop(inventory);
+        if stacked {
+            self.give_items(server, filled, 1).await;
+        } else {
+            self.set_container_content(None).await;
+        }
+    }
+
+    /// Places a source of the fluid, water evaporates in ultra warm dimensions
+    async fn empty_bucket(
+        &self,
+        server: &Server,
+        offhand: bool,
+        position: WorldPosition,
+        fluid: Fluid,
+        creative: bool,
+    ) {
+        let world = &self.world();
+        if fluid == Fluid::Water && world.is_ultra_warm() {
+            world
+                .play_block_sound(sound!("block.fire.extinguish"), position)
+                .await;
+        } else {
+            world
+                .set_block_state(position, FluidState::source(fluid).state_id())
+                .await;
+            world.update_neighbors(position, server).await;
+            let sound = match fluid {
+                Fluid::Water => sound!("item.bucket.empty"),
+                Fluid::Lava => sound!("item.bucket.empty_lava"),
+            };
+            world.play_block_sound(sound, position).await;
+        }
+        if !creative {
+            if let Some(bucket) = get_item("bucket") {
+                *self.inventory().lock().await.hand_item_mut(offhand) =
+                    Some(ItemStack::new(1, bucket.id));
+                self.set_container_content(None).await;
+            }
+        }
+    }
+
+    /// Pops the item out of item frames and breaks armor stands. Returns whether the entity was one of them
+    async fn attack_decoration_entity(&self, server: &Server, entity_id: EntityId) -> bool {
+        if self.gamemode.load() == GameMode::Spectator {
+            return false;
+        }
+        let world = &self.world();
+        if let Some(item_frame) = world.get_item_frame(entity_id).await {
+            item_frame.attack(self, server).await;
+            return true;
+        }
+        if let Some(armor_stand) = world.get_armor_stand(entity_id).await {
+            armor_stand.attack(self, server).await;
+            return true;
+        }
+        false
+    }
+
+    /// Hangs item frames on the clicked face and puts armor stands in front of it, other items are ignored
+    /// Flint and steel and fire charges set the block in front of the clicked face on fire, which
+    /// lights nether portals in obsidian frames
+    async fn use_igniter(
+        &self,
+        server: &Server,
+        item_stack: &ItemStack,
+        location: WorldPosition,
+        face: BlockFace,
+        sequence: VarInt,
+    ) {
+        let name = get_item_name_by_id(item_stack.item_id);
+        let sound = match name {
+            Some("flint_and_steel") => sound!("item.flintandsteel.use"),
+            Some("fire_charge") => sound!("item.firecharge.use"),
+            _ => return,
+        };
+        self.client
+            .send_packet(&CAcknowledgeBlockChange::new(sequence))
+            .await;
+        let world = &self.world();
+        let target = WorldPosition(location.0 + face.to_offset());
+        if !world
+            .get_block_state(target)
+            .await
+            .is_ok_and(|state| state.air)
+            || !world.worldborder.lock().await.contains_block(&target)
+        {
+            return;
+        }
+        world.play_block_sound(sound, target).await;
+        world.light_fire(target, server).await;
+        if name == Some("fire_charge") {
+            self.consume_held_item().await;
+        } else {
+            self.damage_hand_item(false, 1).await;
+        }
+    }
+
+    async fn place_decoration_entity(
+        &self,
+        server: &Server,
+        item_stack: &ItemStack,
+        location: WorldPosition,
+        face: BlockFace,
+        sequence: VarInt,
+    ) {
+        let Some(name) = get_item_name_by_id(item_stack.item_id) else {
+            return;
+        };
+        if !matches!(name, "item_frame" | "glow_item_frame" | "armor_stand") {
+            return;
+        }
+        self.client
+            .send_packet(&CAcknowledgeBlockChange::new(sequence))
+            .await;
+        let world = &self.world();
+        let target = WorldPosition(location.0 + face.to_offset());
+        if !world
+            .get_block_state(target)
+            .await
+            .is_ok_and(|state| state.replaceable)
+        {
+            return;
+        }
+
+        if name == "armor_stand" {
+            // Armor stands face the player, snapped to 45 degrees
+            let yaw = wrap_degrees(self.living_entity.entity.yaw.load() - 180.0);
+            let yaw = ((yaw + 22.5) / 45.0).floor() * 45.0;
+            let position = Vector3::new(
+                f64::from(target.0.x) + 0.5,
+                f64::from(target.0.y),
+                f64::from(target.0.z) + 0.5,
+            );
+            let armor_stand =
+                ArmorStandEntity::new(server.new_entity_id(), world.clone(), position, yaw);
+            world.add_armor_stand(Arc::new(armor_stand)).await;
+        } else {
+            let item_frame = ItemFrameEntity::new(
+                server.new_entity_id(),
+                world.clone(),
+                name == "glow_item_frame",
+                location,
+                face,
+            );
+            world.add_item_frame(Arc::new(item_frame)).await;
+        }
+
+        if self.gamemode.load() != GameMode::Creative {
+            let mut inventory = self.inventory().lock().await;
+            let held = inventory.held_item_mut();
+            if let Some(item_stack) = held {
+                item_stack.item_count -= 1;
+                if item_stack.item_count == 0 {
+                    *held = None;
+                }
+            }
+        }
+    }
+
+    pub async fn handle_use_item(&self, use_item: &SUseItem, server: &Server) {
+        // Using armor puts it on, swapping it with the worn piece
+        let offhand = use_item.hand.0 == 1;
+        let can_remove_bound = self.gamemode.load() == GameMode::Creative;
+        if self
+            .inventory()
+            .lock()
+            .await
+            .equip_from_hand(offhand, can_remove_bound)
+        {
+            self.set_container_content(None).await;
+            return;
+        }
+        if self
+            .use_bucket(server, offhand, use_item.yaw, use_item.pitch)
+            .await
+        {
+            return;
+        }
+        if self.use_throwable(server, offhand).await {
+            return;
+        }
+        if self.use_bow_or_trident(offhand).await {
+            return;
+        }
+        if self.start_consuming(offhand).await {
+            return;
+        }
+        if self.raise_shield(offhand).await {
+            return;
+        }
+        // TODO: handle other items
         log::error!("An item was used(SUseItem), but the packet is not implemented yet");
     }
 
@@ -919,24 +1594,43 @@ impl Player {
             return;
         }
         self.inventory().lock().await.set_selected(slot as usize);
+        self.stop_using_item();
     }
 
     pub async fn handle_set_creative_slot(
         &self,
+        server: &Server,
         packet: SSetCreativeSlot,
     ) -> Result<(), InventoryError> {
         if self.gamemode.load() != GameMode::Creative {
             return Err(InventoryError::PermissionError);
         }
-        let valid_slot = packet.slot >= 0 && packet.slot <= 45;
-        if valid_slot {
-            self.inventory().lock().await.set_slot(
-                packet.slot as usize,
-                packet.clicked_item.to_item(),
-                true,
-            )?;
-        };
-        // TODO: The Item was dropped per drag and drop,
+        let is_empty = packet.clicked_item.is_empty();
+        let item = packet.clicked_item.to_item();
+        let valid_item = item.as_ref().is_some_and(|item| {
+            get_item_by_id(item.item_id).is_some()
+                && (1..=item.max_stack_size()).contains(&item.item_count)
+        });
+        if !is_empty && !valid_item {
+            return Err(InventoryError::InvalidItem);
+        }
+
+        match packet.slot {
+            // The crafting output can't be set directly
+            1..=45 => {
+                self.inventory()
+                    .lock()
+                    .await
+                    .set_slot(packet.slot as usize, item, true)?;
+            }
+            // The item was dragged out of the creative inventory
+            ..=-1 => {
+                if let Some(item) = item {
+                    self.drop_item(server, item).await;
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 
@@ -951,9 +1645,14 @@ impl Player {
         //     return;
         // };
         // window_id 0 represents both 9x1 Generic AND inventory here
-        let mut inventory = self.inventory().lock().await;
+        self.leave_container(server).await;
+    }
 
-        inventory.state_id = 0;
+    /// Removes the player from the open container after its window was closed
+    pub async fn leave_container(&self, server: &Server) {
+        // Don't hold the lock, closing a container can give items back to the player
+        self.inventory().lock().await.state_id = 0;
+        self.fire_close_event(server).await;
         let open_container = self.open_container.load();
         if let Some(id) = open_container {
             let mut open_containers = server.open_containers.write().await;
@@ -969,6 +1668,22 @@ impl Player {
                 }
                 // Remove the player from the container
                 container.remove_player(self.entity_id());
+                // Containers without a block or entity, like villager trades, only exist while they are open
+                if container.get_location().is_none() && container.get_entity().is_none() {
+                    self.return_container_inputs(server, container).await;
+                    if container.get_number_of_players() == 0 {
+                        open_containers.remove(&id);
+                    }
+                } else if container.get_entity().is_some() && container.get_number_of_players() == 0
+                {
+                    // The entity keeps its inventory
+                    open_containers.remove(&id);
+                } else if container.get_number_of_players() == 0
+                    && container.save_block_entity(&self.world().level).await
+                {
+                    // The block entity keeps working, it is shown again once the block is opened
+                    open_containers.remove(&id);
+                }
             }
             self.open_container.store(None);
         }
@@ -992,12 +1707,12 @@ impl Player {
         let dispatcher = server.command_dispatcher.read().await;
         let suggestions = dispatcher.find_suggestions(&mut src, server, cmd).await;
 
-        let response = CCommandSuggestions::new(
-            packet.id,
-            (last_word_start + 2).into(),
-            (cmd.len() - last_word_start - 1).into(),
-            suggestions,
-        );
+        // Suggestions replace the last word. The client counts UTF-16 code units, the `/` and the
+        // whitespace before the word are one each
+        let start = cmd[..last_word_start].encode_utf16().count() + 2;
+        let length = cmd[last_word_start..].encode_utf16().count() - 1;
+        let response =
+            CCommandSuggestions::new(packet.id, start.into(), length.into(), suggestions);
 
         self.client.send_packet(&response).await;
     }
@@ -0,0 +1,308 @@
+use crate::entity::player::Player;
+use crate::server::Server;
+use num_traits::FromPrimitive;
+use pumpkin_core::GameMode;
+use pumpkin_inventory::recipe_book::{RecipeBookSettings, RecipeBookType};
+use pumpkin_inventory::{stonecutter_recipes, OptionallyCombinedContainer};
+use pumpkin_protocol::client::play::{
+    CPlaceGhostRecipe, CRecipeBookAdd, CRecipeBookSettings, CUpdateRecipes, PropertySet,
+    RecipeBookEntry, RecipeDisplay, RecipeDisplayEntry, SlotDisplay, StonecutterRecipe,
+};
+use pumpkin_protocol::codec::identifier::Identifier;
+use pumpkin_protocol::codec::slot::Slot;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::server::play::{
+    SPlaceRecipe, SRecipeBookChangeSettings, SRecipeBookSeenRecipe,
+};
+use pumpkin_registry::{
+    get_tag_values, IngredientSlot, IngredientType, Recipe, RecipeResult, RecipeType, TagCategory,
+    RECIPES,
+};
+use pumpkin_world::item::item_registry::get_item;
+use pumpkin_world::item::ItemStack;
+use std::collections::BTreeSet;
+
+impl Player {
+    /// Sends the recipe book settings and all known recipes, used when joining
+    pub async fn init_recipe_book(&self) {
+        let mut recipe_book = self.recipe_book.lock().await;
+        // TODO: Unlock recipes through advancements, until then every recipe the book can show is known
+        recipe_book.unlock(
+            RECIPES
+                .iter()
+                .enumerate()
+                .filter(|(_, recipe)| recipe_display(recipe).is_some())
+                .map(|(id, _)| id),
+        );
+        self.client
+            .send_packet(&CRecipeBookSettings::new(recipe_book.all_settings()))
+            .await;
+        let entries: Vec<_> = recipe_book
+            .known()
+            .filter_map(|id| recipe_book_entry(id, false, recipe_book.is_highlighted(id)))
+            .collect();
+        self.client
+            .send_packet(&CRecipeBookAdd::new(&entries, true))
+            .await;
+    }
+
+    /// Sends the recipes the client shows in the stonecutter and the items smithing tables and furnaces accept
+    pub async fn send_recipes(&self) {
+        let smithing_input = |slot: usize| {
+            RECIPES
+                .iter()
+                .filter(|recipe| matches!(recipe.recipe_type, RecipeType::Smithing(_)))
+                .filter_map(move |recipe| recipe.pattern().first()?[0][slot].as_ref())
+        };
+        let cooking_input = |recipe_type: RecipeType| {
+            RECIPES
+                .iter()
+                .filter(move |recipe| recipe.recipe_type == recipe_type)
+                .filter_map(|recipe| recipe.ingredients().next())
+        };
+        let property_sets = [
+            property_set("smithing_template", smithing_input(0)),
+            property_set("smithing_base", smithing_input(1)),
+            property_set("smithing_addition", smithing_input(2)),
+            property_set("furnace_input", cooking_input(RecipeType::Smelting)),
+            property_set("blast_furnace_input", cooking_input(RecipeType::Blasting)),
+            property_set("smoker_input", cooking_input(RecipeType::Smoking)),
+            property_set("campfire_input", cooking_input(RecipeType::CampfireCooking)),
+        ];
+        // The stonecutter buttons are indices into this list, so it has to match `stonecutter_results`
+        let stonecutter_recipes: Vec<_> = stonecutter_recipes()
+            .filter_map(|recipe| {
+                Some(StonecutterRecipe {
+                    ingredient: ingredient_items(recipe.ingredients().next()?)
+                        .into_iter()
+                        .map(VarInt)
+                        .collect(),
+                    result: result_display(recipe)?,
+                })
+            })
+            .collect();
+        self.client
+            .send_packet(&CUpdateRecipes::new(&property_sets, &stonecutter_recipes))
+            .await;
+    }
+
+    /// Unlocks the recipes, the client shows a toast for the ones which are new
+    pub async fn unlock_recipes(&self, recipes: impl IntoIterator<Item = usize>) {
+        let unlocked = self.recipe_book.lock().await.unlock(recipes);
+        let entries: Vec<_> = unlocked
+            .into_iter()
+            .filter_map(|id| recipe_book_entry(id, true, true))
+            .collect();
+        if !entries.is_empty() {
+            self.client
+                .send_packet(&CRecipeBookAdd::new(&entries, false))
+                .await;
+        }
+    }
+
+    pub async fn handle_recipe_book_change_settings(&self, packet: SRecipeBookChangeSettings) {
+        let Some(book) = RecipeBookType::from_i32(packet.book_type.0) else {
+            log::debug!("Unknown recipe book type {}", packet.book_type.0);
+            return;
+        };
+        self.recipe_book.lock().await.set_settings(
+            book,
+            RecipeBookSettings {
+                open: packet.book_open,
+                filtering: packet.filter_active,
+            },
+        );
+    }
+
+    pub async fn handle_recipe_book_seen_recipe(&self, packet: SRecipeBookSeenRecipe) {
+        if let Ok(recipe) = usize::try_from(packet.recipe_id.0) {
+            self.recipe_book.lock().await.remove_highlight(recipe);
+        }
+    }
+
+    pub async fn handle_place_recipe(&self, server: &Server, packet: SPlaceRecipe) {
+        if self.gamemode.load() == GameMode::Spectator {
+            return;
+        }
+        let Ok(recipe_id) = usize::try_from(packet.recipe_id.0) else {
+            return;
+        };
+        let Some(recipe) = RECIPES.get(recipe_id) else {
+            return;
+        };
+        if !self.recipe_book.lock().await.is_known(recipe_id) {
+            return;
+        }
+
+        let opened_container = self.get_open_container(server).await;
+        let mut opened_container = match opened_container.as_ref() {
+            Some(container) => Some(container.lock().await),
+            None => None,
+        };
+        let placed = {
+            let mut inventory = self.inventory().lock().await;
+            let window_id = if opened_container.is_some() {
+                inventory.total_opened_containers
+            } else {
+                0
+            };
+            if packet.window_id.0 != window_id {
+                return;
+            }
+            let mut combined =
+                OptionallyCombinedContainer::new(&mut inventory, opened_container.as_deref_mut());
+            combined.place_recipe(recipe, packet.make_all)
+        };
+        match placed {
+            Ok(true) => {}
+            // Show the player what they are missing
+            Ok(false) => {
+                if let Some(display) = recipe_display(recipe) {
+                    self.client
+                        .send_packet(&CPlaceGhostRecipe::new(packet.window_id, &display))
+                        .await;
+                }
+            }
+            Err(error) => log::debug!("Failed to place recipe {recipe_id}: {error}"),
+        }
+        self.set_container_content(opened_container.as_deref_mut())
+            .await;
+    }
+}
+
+fn recipe_book_entry(
+    id: usize,
+    show_notification: bool,
+    highlight: bool,
+) -> Option<RecipeBookEntry> {
+    let recipe = RECIPES.get(id)?;
+    let crafting_requirements = recipe
+        .ingredients()
+        .map(|ingredient| {
+            ingredient_items(ingredient)
+                .into_iter()
+                .map(VarInt)
+                .collect()
+        })
+        .collect();
+    Some(RecipeBookEntry {
+        display: RecipeDisplayEntry {
+            id: VarInt(id as i32),
+            display: recipe_display(recipe)?,
+            group: None,
+            category: VarInt(recipe_book_category(recipe)),
+            crafting_requirements: Some(crafting_requirements),
+        },
+        show_notification,
+        highlight,
+    })
+}
+
+/// How the recipe book shows the recipe, only crafting recipes are supported
+fn recipe_display(recipe: &Recipe) -> Option<RecipeDisplay> {
+    if !recipe.implemented() {
+        return None;
+    }
+    let result = result_display(recipe)?;
+    let crafting_station =
+        SlotDisplay::Item(VarInt(get_item("minecraft:crafting_table")?.id.into()));
+    match recipe.shape() {
+        Some((width, height)) => {
+            let ingredients = recipe
+                .pattern()
+                .first()?
+                .iter()
+                .take(height)
+                .flat_map(|row| row.iter().take(width))
+                .map(|ingredient| {
+                    ingredient
+                        .as_ref()
+                        .map_or(SlotDisplay::Empty, ingredient_display)
+                })
+                .collect();
+            Some(RecipeDisplay::CraftingShaped {
+                width: VarInt(width as i32),
+                height: VarInt(height as i32),
+                ingredients,
+                result,
+                crafting_station,
+            })
+        }
+        None => Some(RecipeDisplay::CraftingShapeless {
+            ingredients: recipe.ingredients().map(ingredient_display).collect(),
+            result,
+            crafting_station,
+        }),
+    }
+}
+
+fn result_display(recipe: &Recipe) -> Option<SlotDisplay> {
+    match recipe.result() {
+        RecipeResult::Single { id, .. } => Some(SlotDisplay::Item(VarInt(get_item(id)?.id.into()))),
+        RecipeResult::Many { id, count, .. } => Some(SlotDisplay::ItemStack(Box::new(Slot::from(
+            &ItemStack::new(*count, get_item(id)?.id),
+        )))),
+        RecipeResult::Special => None,
+    }
+}
+
+/// A set of all items the ingredients accept, the client uses these to check which items fit into a slot
+fn property_set<'a>(
+    id: &str,
+    ingredients: impl Iterator<Item = &'a IngredientSlot>,
+) -> PropertySet {
+    let items: BTreeSet<i32> = ingredients.flat_map(ingredient_items).collect();
+    PropertySet {
+        id: Identifier::vanilla(id),
+        items: items.into_iter().map(VarInt).collect(),
+    }
+}
+
+fn ingredient_display(ingredient: &IngredientSlot) -> SlotDisplay {
+    let mut items = ingredient_items(ingredient);
+    if items.len() == 1 {
+        SlotDisplay::Item(VarInt(items.remove(0)))
+    } else {
+        SlotDisplay::Composite(
+            items
+                .into_iter()
+                .map(|item| SlotDisplay::Item(VarInt(item)))
+                .collect(),
+        )
+    }
+}
+
+/// The ids of all items the ingredient accepts, tags are resolved because the client doesn't know them
+fn ingredient_items(ingredient: &IngredientSlot) -> Vec<i32> {
+    let mut items = Vec::new();
+    match ingredient {
+        IngredientSlot::Single(ingredient) => add_ingredient_items(ingredient, &mut items),
+        IngredientSlot::Many(ingredients) => {
+            for ingredient in ingredients {
+                add_ingredient_items(ingredient, &mut items);
+            }
+        }
+    }
+    items
+}
+
+fn add_ingredient_items(ingredient: &IngredientType, items: &mut Vec<i32>) {
+    match ingredient {
+        IngredientType::Item(item) => items.extend(get_item(item).map(|item| i32::from(item.id))),
+        IngredientType::Tag(tag) => {
+            for value in get_tag_values(TagCategory::Item, tag).into_iter().flatten() {
+                add_ingredient_items(&value.to_ingredient_type(), items);
+            }
+        }
+    }
+}
+
+/// The id of the recipe's category in the client's recipe book category registry
+fn recipe_book_category(recipe: &Recipe) -> i32 {
+    match recipe.category() {
+        Some("building") => 0,
+        Some("redstone") => 1,
+        Some("equipment") => 2,
+        _ => 3,
+    }
+}
@@ -0,0 +1,329 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::scoreboard::ObjectiveCriterion;
+use pumpkin_core::math::vector3::Vector3;
+use pumpkin_entity::{entity_type::EntityType, pose::EntityPose};
+use pumpkin_protocol::client::play::{AwardedStat, CAwardStats};
+use pumpkin_world::block::block_registry::BLOCK_ID_BY_REGISTRY_ID;
+use pumpkin_world::block::fluid::Fluid;
+use pumpkin_world::item::item_registry::get_item_name_by_id;
+use pumpkin_world::item::ITEMS;
+use pumpkin_world::stats::{custom_stat_id, StatType, StatsData, CUSTOM_STATS};
+
+/// A statistic, what its type counts is kept by its id without the `minecraft:` namespace, like
+/// vanilla's `Stat`
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Stat {
+    pub stat_type: StatType,
+    pub value: &'static str,
+}
+
+impl Stat {
+    /// The statistic if the registry of the stat type knows the value, e.g. a block for `mined`.
+    /// The `minecraft:` namespace is optional
+    pub fn new(stat_type: StatType, value: &str) -> Option<Self> {
+        let value = value.strip_prefix("minecraft:").unwrap_or(value);
+        let value = match stat_type {
+            StatType::Mined => BLOCK_ID_BY_REGISTRY_ID
+                .get_key_value(value)
+                .map(|(name, _)| name.as_str()),
+            StatType::Killed | StatType::KilledBy => {
+                EntityType::from_name(value).map(|entity_type| entity_type.name())
+            }
+            StatType::Custom => custom_stat_id(value).map(|id| CUSTOM_STATS[id]),
+            StatType::Crafted
+            | StatType::Used
+            | StatType::Broken
+            | StatType::PickedUp
+            | StatType::Dropped => ITEMS.get_key_value(value).map(|(name, _)| name.as_str()),
+        }?;
+        Some(Self { stat_type, value })
+    }
+
+    /// The statistic of a stat type counting items, like `picked_up`
+    #[must_use]
+    pub fn item(stat_type: StatType, item_id: u16) -> Option<Self> {
+        get_item_name_by_id(item_id).map(|value| Self { stat_type, value })
+    }
+
+    /// The statistic of a stat type counting entities, like `killed`
+    #[must_use]
+    pub const fn entity(stat_type: StatType, entity_type: &EntityType) -> Self {
+        Self {
+            stat_type,
+            value: entity_type.name(),
+        }
+    }
+
+    /// One of the `CUSTOM_STATS`, like `jump`
+    #[must_use]
+    pub const fn custom(name: &'static str) -> Self {
+        Self {
+            stat_type: StatType::Custom,
+            value: name,
+        }
+    }
+
+    /// The id of the value in the registry of the stat type, which the client knows it by
+    fn registry_id(self) -> Option<i32> {
+        match self.stat_type {
+            StatType::Mined => BLOCK_ID_BY_REGISTRY_ID
+                .get(self.value)
+                .map(|id| i32::from(*id)),
+            StatType::Killed | StatType::KilledBy => {
+                EntityType::from_name(self.value).map(|entity_type| entity_type as i32)
+            }
+            StatType::Custom => custom_stat_id(self.value).and_then(|id| i32::try_from(id).ok()),
+            StatType::Crafted
+            | StatType::Used
+            | StatType::Broken
+            | StatType::PickedUp
+            | StatType::Dropped => ITEMS.get(self.value).map(|item| i32::from(item.id)),
+        }
+    }
+
+    /// The name of the scoreboard criterion following the statistic, e.g.
+    /// `minecraft.mined:minecraft.stone`
+    #[must_use]
+    pub fn criterion_name(self) -> String {
+        format!(
+            "minecraft.{}:minecraft.{}",
+            self.stat_type.name(),
+            self.value
+        )
+    }
+
+    /// The statistic of a scoreboard criterion, the `minecraft.` namespaces are optional
+    #[must_use]
+    pub fn from_criterion_name(name: &str) -> Option<Self> {
+        let (stat_type, value) = name.split_once(':')?;
+        let stat_type =
+            StatType::from_name(stat_type.strip_prefix("minecraft.").unwrap_or(stat_type))?;
+        Self::new(stat_type, value.strip_prefix("minecraft.").unwrap_or(value))
+    }
+}
+
+/// The statistics of a player, like vanilla's `ServerStatsCounter`
+#[derive(Default)]
+pub struct PlayerStats {
+    values: BTreeMap<Stat, i32>,
+    /// What was awarded since the scoreboard was last updated
+    pending: BTreeMap<Stat, i32>,
+    /// The statistics reset since the scoreboard was last updated
+    reset: BTreeSet<Stat>,
+}
+
+impl PlayerStats {
+    fn read_data(data: StatsData) -> Self {
+        let mut values = BTreeMap::new();
+        for (stat_type, stats) in data {
+            let Some(stat_type) = StatType::from_name(&stat_type) else {
+                log::warn!("Skipping unknown statistic type {stat_type}");
+                continue;
+            };
+            for (value, amount) in stats {
+                match Stat::new(stat_type, &value) {
+                    Some(stat) => {
+                        values.insert(stat, amount);
+                    }
+                    None => log::warn!("Skipping unknown statistic {value}"),
+                }
+            }
+        }
+        Self {
+            values,
+            ..Default::default()
+        }
+    }
+
+    fn write_data(&self) -> StatsData {
+        let mut data = StatsData::new();
+        for (stat, value) in &self.values {
+            data.entry(format!("minecraft:{}", stat.stat_type.name()))
+                .or_default()
+                .insert(format!("minecraft:{}", stat.value), *value);
+        }
+        data
+    }
+}
+
+/// Centimeters between the positions, how vanilla counts the distance statistics
+fn centimeters(distance: f64) -> i32 {
+    (distance * 100.0).round() as i32
+}
+
+impl Player {
+    /// Loads the statistics of the player from the world, if they have joined it before
+    pub async fn load_stats(&self) {
+        let uuid = self.gameprofile.id.to_string();
+        match self.world().level.player_stats.load(&uuid) {
+            Ok(data) => *self.stats.lock().await = PlayerStats::read_data(data),
+            Err(error) => log::error!(
+                "Failed to load statistics of {}: {error}",
+                self.gameprofile.name
+            ),
+        }
+    }
+
+    pub async fn save_stats(&self) {
+        let data = self.stats.lock().await.write_data();
+        let uuid = self.gameprofile.id.to_string();
+        if let Err(error) = self.world().level.player_stats.save(&uuid, data) {
+            log::error!(
+                "Failed to save statistics of {}: {error}",
+                self.gameprofile.name
+            );
+        }
+    }
+
+    /// Sends every statistic the player has, the client asks for them when opening the
+    /// statistics screen
+    pub async fn send_stats(&self) {
+        let stats: Vec<_> = self
+            .stats
+            .lock()
+            .await
+            .values
+            .iter()
+            .filter_map(|(stat, value)| {
+                Some(AwardedStat {
+                    category_id: (stat.stat_type as i32).into(),
+                    stat_id: stat.registry_id()?.into(),
+                    value: (*value).into(),
+                })
+            })
+            .collect();
+        self.client.send_packet(&CAwardStats::new(&stats)).await;
+    }
+
+    /// Adds to the statistic, the scores of objectives following it catch up on the next tick
+    pub async fn award_stat(&self, stat: Stat, amount: i32) {
+        if amount <= 0 {
+            return;
+        }
+        let mut stats = self.stats.lock().await;
+        let value = stats.values.entry(stat).or_default();
+        *value = value.saturating_add(amount);
+        let pending = stats.pending.entry(stat).or_default();
+        *pending = pending.saturating_add(amount);
+    }
+
+    /// Sets the statistic back to zero, like `time_since_death` when dying. The scores following
+    /// it are reset on the next tick
+    pub async fn reset_stat(&self, stat: Stat) {
+        let mut stats = self.stats.lock().await;
+        stats.values.remove(&stat);
+        stats.pending.remove(&stat);
+        stats.reset.insert(stat);
+    }
+
+    /// Brings the objectives following the statistics up to date with what changed since the
+    /// last tick
+    pub async fn update_stat_scores(&self, server: &Server) {
+        let (reset, pending) = {
+            let mut stats = self.stats.lock().await;
+            (
+                std::mem::take(&mut stats.reset),
+                std::mem::take(&mut stats.pending),
+            )
+        };
+        if reset.is_empty() && pending.is_empty() {
+            return;
+        }
+        let mut scoreboard = server.scoreboard.lock().await;
+        for stat in reset {
+            scoreboard
+                .set_criterion(
+                    server,
+                    &self.gameprofile.name,
+                    ObjectiveCriterion::Stat(stat),
+                    0,
+                )
+                .await;
+        }
+        for (stat, amount) in pending {
+            scoreboard
+                .add_to_criterion(
+                    server,
+                    &self.gameprofile.name,
+                    ObjectiveCriterion::Stat(stat),
+                    amount,
+                )
+                .await;
+        }
+    }
+
+    /// The statistics counting time, like vanilla's `Player.tick`
+    pub async fn tick_stats(&self) {
+        self.award_stat(Stat::custom("play_time"), 1).await;
+        self.award_stat(Stat::custom("total_world_time"), 1).await;
+        if self.living_entity.health.load() > 0.0 {
+            self.award_stat(Stat::custom("time_since_death"), 1).await;
+        }
+        let sneaking = self
+            .living_entity
+            .entity
+            .sneaking
+            .load(std::sync::atomic::Ordering::Relaxed);
+        if sneaking {
+            self.award_stat(Stat::custom("sneak_time"), 1).await;
+        }
+        if !self.is_sleeping() {
+            self.award_stat(Stat::custom("time_since_rest"), 1).await;
+        }
+    }
+
+    /// Counts the distance the player moved by how they moved and their jumps, like vanilla's
+    /// `checkMovementStatistics`
+    pub async fn award_movement_stats(
+        &self,
+        from: Vector3<f64>,
+        to: Vector3<f64>,
+        was_on_ground: bool,
+        on_ground: bool,
+    ) {
+        let entity = &self.living_entity.entity;
+        let (dx, dy, dz) = (to.x - from.x, to.y - from.y, to.z - from.z);
+        let distance = centimeters(dx.mul_add(dx, dy.mul_add(dy, dz * dz)).sqrt());
+        let horizontal = centimeters(dx.hypot(dz));
+        let world = self.world();
+        let bounding_box = entity.bounding_box.load();
+        let (stat, amount) = if matches!(entity.pose.load(), EntityPose::Swimming) {
+            ("swim_one_cm", distance)
+        } else if world.level.is_in_fluid(entity.eye_position(), Fluid::Water) {
+            ("walk_under_water_one_cm", distance)
+        } else if world
+            .level
+            .fluid_contact(&bounding_box, Fluid::Water)
+            .is_some()
+        {
+            ("walk_on_water_one_cm", horizontal)
+        } else if on_ground {
+            let stat = if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
+                "sprint_one_cm"
+            } else if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
+                "crouch_one_cm"
+            } else {
+                "walk_one_cm"
+            };
+            (stat, horizontal)
+        } else if entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            ("aviate_one_cm", distance)
+        } else if dy < 0.0 {
+            ("fall_one_cm", centimeters(-dy))
+        } else {
+            ("fly_one_cm", horizontal)
+        };
+        self.award_stat(Stat::custom(stat), amount).await;
+
+        // Players tell when they stand on the ground, leaving it upwards is a jump
+        if was_on_ground && !on_ground && dy > 0.0 {
+            self.award_stat(Stat::custom("jump"), 1).await;
+        }
+    }
+}
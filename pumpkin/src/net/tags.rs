@@ -0,0 +1,64 @@
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_protocol::{
+    client::config::{RegistryTags, TagEntries},
+    codec::{identifier::Identifier, var_int::VarInt},
+};
+use pumpkin_registry::{get_tag_values, get_tags, synced_registry_entry_id, TagCategory, TagType};
+use pumpkin_world::{block::block_registry::get_block, item::item_registry::get_item};
+
+/// The fluids in the order of vanilla's fluid registry
+const FLUIDS: [&str; 5] = ["empty", "flowing_water", "water", "flowing_lava", "lava"];
+
+/// The id players know the entry of the registry by
+fn entry_id(tag_category: TagCategory, name: &str) -> Option<i32> {
+    match tag_category {
+        TagCategory::Item => get_item(name).map(|item| i32::from(item.id)),
+        TagCategory::Block => get_block(name).map(|block| i32::from(block.id)),
+        TagCategory::Fluid => FLUIDS
+            .iter()
+            .position(|fluid| *fluid == name)
+            .map(|index| index as i32),
+        TagCategory::Entity => EntityType::from_name(name).map(|entity| entity as i32),
+        _ => synced_registry_entry_id(tag_category, name),
+    }
+}
+
+/// The ids of the entries of a tag, players get tags within tags resolved
+fn tag_entries(tag_category: TagCategory, values: &[TagType]) -> Vec<VarInt> {
+    values
+        .iter()
+        .flat_map(|value| match value {
+            TagType::Item(name) => entry_id(tag_category, name)
+                .map(VarInt)
+                .into_iter()
+                .collect(),
+            TagType::Tag(tag) => get_tag_values(tag_category, tag)
+                .map(|values| tag_entries(tag_category, values))
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// The tags players get when they join, like vanilla's `TagNetworkSerialization`. Tags of
+/// registries players don't know the ids of are left out
+#[must_use]
+pub fn registry_tags() -> Vec<RegistryTags> {
+    TagCategory::ALL
+        .into_iter()
+        .filter_map(|tag_category| {
+            let tags: Vec<_> = get_tags(tag_category)
+                .map(|(tag, values)| TagEntries {
+                    tag_id: Identifier::vanilla(tag),
+                    entries: tag_entries(tag_category, values),
+                })
+                .collect();
+            // Registries without known ids don't have any entries
+            tags.iter()
+                .any(|tag| !tag.entries.is_empty())
+                .then(|| RegistryTags {
+                    registry_id: Identifier::vanilla(tag_category.registry()),
+                    tags,
+                })
+        })
+        .collect()
+}
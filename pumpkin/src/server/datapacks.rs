@@ -0,0 +1,95 @@
+use std::sync::atomic::Ordering;
+
+use crate::command::{dispatcher::CommandError, CommandSender, ExecutionContext};
+
+use super::Server;
+
+/// How deep functions can run functions. Vanilla counts the commands instead, but functions run
+/// recursively here and would overflow the stack first
+const MAX_FUNCTION_DEPTH: usize = 256;
+
+impl Server {
+    /// Loads the datapacks enabled in the overworld again, packs added to its `datapacks` folder
+    /// are enabled. The `#minecraft:load` functions run on the next tick
+    pub async fn reload_datapacks(&self) {
+        let world = &self.worlds[0];
+        let enabled = {
+            let mut data_packs = world.data_packs.lock().await;
+            data_packs.refresh(&world.level.datapacks.available());
+            data_packs.enabled.clone()
+        };
+        let functions = world.level.datapacks.load(&enabled);
+        *self.functions.write().await = functions;
+        self.load_functions.store(true, Ordering::Relaxed);
+    }
+
+    /// Runs the commands of the function one after another in the context of the sender, like
+    /// vanilla the commands give no feedback. Returns how many commands ran
+    pub(crate) async fn run_function(
+        &self,
+        sender: &CommandSender<'_>,
+        id: &str,
+    ) -> Result<usize, CommandError> {
+        let Some(commands) = self
+            .functions
+            .read()
+            .await
+            .function(id)
+            .map(<[String]>::to_vec)
+        else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown function {id}"
+            )));
+        };
+        let mut context = ExecutionContext::of(sender, self).await;
+        if context.function_depth >= MAX_FUNCTION_DEPTH {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Function {id} runs too many functions inside each other"
+            )));
+        }
+        context.function_depth += 1;
+        let dispatcher = self.command_dispatcher.read().await;
+        for command in &commands {
+            let mut sender = CommandSender::Execute(Box::new(context.clone()));
+            dispatcher.handle_command(&mut sender, self, command).await;
+        }
+        Ok(commands.len())
+    }
+
+    /// Runs every function of the tag, returns how many functions and commands ran
+    pub(crate) async fn run_function_tag(
+        &self,
+        sender: &CommandSender<'_>,
+        id: &str,
+    ) -> Result<(usize, usize), CommandError> {
+        let Some(functions) = self.functions.read().await.tag(id).map(<[String]>::to_vec) else {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unknown function tag #{id}"
+            )));
+        };
+        let mut commands = 0;
+        for function in &functions {
+            commands += self.run_function(sender, function).await?;
+        }
+        Ok((functions.len(), commands))
+    }
+
+    /// Runs the `#minecraft:load` functions after the datapacks loaded and the `#minecraft:tick`
+    /// functions every tick, like vanilla's `ServerFunctionManager`
+    pub(super) async fn tick_functions(&self) {
+        if self.load_functions.swap(false, Ordering::Relaxed) {
+            self.run_functions_of("minecraft:load").await;
+        }
+        self.run_functions_of("minecraft:tick").await;
+    }
+
+    /// Runs the functions of the tag as the server at the world spawn, if the tag exists
+    async fn run_functions_of(&self, tag: &str) {
+        if self.functions.read().await.tag(tag).is_none() {
+            return;
+        }
+        if let Err(error) = self.run_function_tag(&CommandSender::Console, tag).await {
+            log::warn!("Failed to run the functions of #{tag}: {error:?}");
+        }
+    }
+}
@@ -1,20 +1,29 @@
 use connection_cache::{CachedBranding, CachedStatus};
 use key_store::KeyStore;
-use pumpkin_config::BASIC_CONFIG;
+use pumpkin_config::{CustomWorldDimension, ADVANCED_CONFIG, BASIC_CONFIG};
 use pumpkin_core::math::position::WorldPosition;
 use pumpkin_core::math::vector2::Vector2;
+use pumpkin_core::text::TextComponent;
 use pumpkin_core::GameMode;
 use pumpkin_entity::EntityId;
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_protocol::client::login::CEncryptionRequest;
-use pumpkin_protocol::{client::config::CPluginMessage, ClientPacket};
+use pumpkin_protocol::{
+    client::config::{CPluginMessage, RegistryTags},
+    codec::identifier::Identifier,
+    ClientPacket,
+};
 use pumpkin_registry::{DimensionType, Registry};
 use pumpkin_world::block::block_registry::Block;
+use pumpkin_world::datapack::FunctionLibrary;
 use pumpkin_world::dimension::Dimension;
+use pumpkin_world::level::Level;
+use pumpkin_world::GeneratorOptions;
 use rand::prelude::SliceRandom;
-use std::collections::HashMap;
-use std::sync::atomic::AtomicU32;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::{
     sync::{
         atomic::{AtomicI32, Ordering},
@@ -22,25 +31,42 @@ use std::{
     },
     time::Duration,
 };
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
 
 use crate::block::block_manager::BlockManager;
 use crate::block::default_block_manager;
-use crate::net::EncryptionError;
+use crate::data::op_data::OPERATOR_CONFIG;
+use crate::data::whitelist_data::WHITELIST_CONFIG;
+use crate::event::EventManager;
+use crate::net::{EncryptionError, GameProfile};
+use crate::world::command_storage::CommandStorage;
 use crate::world::custom_bossbar::CustomBossbars;
+use crate::world::pregen::Pregeneration;
+use crate::world::scoreboard::{Scoreboard, SCOREBOARD_DATA};
 use crate::{
     command::{default_dispatcher, dispatcher::CommandDispatcher},
     entity::player::Player,
-    net::Client,
+    net::{tags::registry_tags, Client},
     world::World,
 };
 
 mod connection_cache;
+mod datapacks;
 mod key_store;
 pub mod ticker;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.4";
 
+/// A container with its viewers and the position of its block
+type ContainerHandle = (
+    Arc<Mutex<Box<dyn Container>>>,
+    Vec<i32>,
+    Option<WorldPosition>,
+);
+
+/// An open container of a block, locked with its viewers
+type LockedContainer = (OwnedMutexGuard<Box<dyn Container>>, Vec<i32>, WorldPosition);
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -53,12 +79,14 @@ pub struct Server {
     pub command_dispatcher: RwLock<CommandDispatcher>,
     /// Saves and calls blocks blocks
     pub block_manager: Arc<BlockManager>,
-    /// Manages multiple worlds within the server.
+    /// Calls the event listeners plugins registered
+    pub event_manager: EventManager,
+    /// Manages multiple worlds within the server, the first one is the overworld players join.
     pub worlds: Vec<Arc<World>>,
-    // All the dimensions that exists on the server,
-    pub dimensions: Vec<DimensionType>,
     /// Caches game registries for efficient access.
     pub cached_registry: Vec<Registry>,
+    /// The tags players get when they join
+    pub cached_tags: Vec<RegistryTags>,
     /// Tracks open containers used for item interactions.
     // TODO: should have per player open_containers
     pub open_containers: RwLock<HashMap<u64, OpenContainer>>,
@@ -71,6 +99,16 @@ pub struct Server {
     pub auth_client: Option<reqwest::Client>,
     /// The server's custom bossbars
     pub bossbars: Mutex<CustomBossbars>,
+    /// The objectives and scores of all worlds, saved with the overworld
+    pub scoreboard: Mutex<Scoreboard>,
+    /// The compounds `/data` keeps in storages, saved with the overworld
+    pub command_storage: Mutex<CommandStorage>,
+    /// The functions of the datapacks enabled in the overworld
+    pub functions: RwLock<FunctionLibrary>,
+    /// Whether the `#minecraft:load` functions still have to run since the datapacks loaded
+    load_functions: AtomicBool,
+    /// Whether only players on the whitelist and operators may join, `/whitelist` turns it on and off
+    pub white_list: AtomicBool,
 }
 
 impl Server {
@@ -87,7 +125,7 @@ impl Server {
         // First register default command, after that plugins can put in their own
         let command_dispatcher = RwLock::new(default_dispatcher());
 
-        let world = World::load(
+        let mut world = World::load(
             Dimension::OverWorld.into_level(
                 // TODO: load form config
                 "./world".parse().unwrap(),
@@ -102,27 +140,88 @@ impl Server {
             }
         }
 
+        let scoreboard = match world.level.saved_data.load(SCOREBOARD_DATA) {
+            Ok(nbt) => nbt
+                .map(|nbt| Scoreboard::from_nbt(&nbt))
+                .unwrap_or_default(),
+            Err(err) => {
+                log::error!("Failed to load the scoreboard: {err}");
+                Scoreboard::new()
+            }
+        };
+
+        let command_storage = CommandStorage::load(&world.level.saved_data);
+
+        let data_packs = world.data_packs.get_mut();
+        data_packs.refresh(&world.level.datapacks.available());
+        let functions = world.level.datapacks.load(&data_packs.enabled);
+
+        let mut worlds = vec![Arc::new(world)];
+        if BASIC_CONFIG.allow_nether {
+            worlds.push(Arc::new(World::load(
+                Dimension::Nether.into_level("./world".parse().unwrap()),
+                DimensionType::TheNether,
+            )));
+        }
+        if BASIC_CONFIG.allow_end {
+            worlds.push(Arc::new(World::load(
+                Dimension::End.into_level("./world".parse().unwrap()),
+                DimensionType::TheEnd,
+            )));
+        }
+        // Custom worlds are saved like the dimensions of datapacks
+        for custom_world in &BASIC_CONFIG.custom_worlds {
+            let (dimension, dimension_type) = match custom_world.dimension {
+                CustomWorldDimension::Overworld => (Dimension::OverWorld, DimensionType::Overworld),
+                CustomWorldDimension::TheNether => (Dimension::Nether, DimensionType::TheNether),
+                CustomWorldDimension::TheEnd => (Dimension::End, DimensionType::TheEnd),
+            };
+            let seed = if custom_world.seed.trim().is_empty() {
+                &BASIC_CONFIG.seed
+            } else {
+                &custom_world.seed
+            };
+            let generator = GeneratorOptions {
+                seed: seed.clone(),
+                level_type: custom_world.level_type,
+                generator_settings: custom_world.generator_settings.clone(),
+            };
+            worlds.push(Arc::new(World::load_named(
+                Level::with_generator(
+                    Path::new("./world/dimensions/pumpkin").join(&custom_world.name),
+                    dimension,
+                    &generator,
+                ),
+                dimension_type,
+                Identifier {
+                    namespace: "pumpkin".to_string(),
+                    path: custom_world.name.clone(),
+                },
+            )));
+        }
+
         Self {
             cached_registry: Registry::get_synced(),
+            cached_tags: registry_tags(),
             open_containers: RwLock::new(HashMap::new()),
             drag_handler: DragHandler::new(),
             // 0 is invalid
             entity_id: 2.into(),
             container_id: 0.into(),
-            worlds: vec![Arc::new(world)],
-            dimensions: vec![
-                DimensionType::Overworld,
-                DimensionType::OverworldCaves,
-                DimensionType::TheNether,
-                DimensionType::TheEnd,
-            ],
+            worlds,
             command_dispatcher,
             block_manager: default_block_manager(),
+            event_manager: EventManager::default(),
             auth_client,
             key_store: KeyStore::new(),
             server_listing: Mutex::new(CachedStatus::new()),
             server_branding: CachedBranding::new(),
             bossbars: Mutex::new(CustomBossbars::new()),
+            scoreboard: Mutex::new(scoreboard),
+            command_storage: Mutex::new(command_storage),
+            functions: RwLock::new(functions),
+            load_functions: AtomicBool::new(true),
+            white_list: AtomicBool::new(BASIC_CONFIG.white_list),
         }
     }
 
@@ -176,6 +275,14 @@ impl Server {
         (player, world.clone())
     }
 
+    /// The world with the identifier, the namespace defaults to `minecraft`
+    pub fn get_world(&self, key: &str) -> Option<Arc<World>> {
+        self.worlds
+            .iter()
+            .find(|world| is_world_key(&world.key, key))
+            .cloned()
+    }
+
     pub async fn remove_player(&self) {
         // TODO: Config if we want decrease online
         self.server_listing.lock().await.remove_player();
@@ -185,6 +292,51 @@ impl Server {
         for world in &self.worlds {
             world.save().await;
         }
+        let scoreboard = self.scoreboard.lock().await.to_nbt();
+        if let Err(err) = self.worlds[0]
+            .level
+            .saved_data
+            .save(SCOREBOARD_DATA, scoreboard)
+        {
+            log::error!("Failed to save the scoreboard: {err}");
+        }
+        if let Err(err) = self
+            .command_storage
+            .lock()
+            .await
+            .save(&self.worlds[0].level.saved_data)
+        {
+            log::error!("Failed to save the command storage: {err}");
+        }
+    }
+
+    /// Periodically writes the chunks which changed to disk, so they survive crashes
+    /// and unwatched chunks don't pile up in memory
+    pub async fn autosave_chunks(&self) {
+        let interval = Duration::from_secs(ADVANCED_CONFIG.chunk.autosave_interval.max(1));
+        let mut interval = tokio::time::interval(interval);
+        // The first tick completes immediately, there is nothing to save yet
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            for world in &self.worlds {
+                world.flush_chunks().await;
+            }
+        }
+    }
+
+    /// Continues the pregenerations which were still running when the server stopped
+    pub async fn resume_pregeneration(&self) {
+        for world in &self.worlds {
+            if let Some(task) = world.level.pregen_task() {
+                log::info!(
+                    "Resuming pregeneration at {}/{} chunks",
+                    task.done,
+                    task.chunk_count()
+                );
+                Pregeneration::start(world.clone(), task, None).await;
+            }
+        }
     }
 
     pub async fn try_get_container(
@@ -294,6 +446,35 @@ impl Server {
         players
     }
 
+    /// Whether the player may join with the whitelist as it is, operators always may
+    pub async fn is_whitelisted(&self, profile: &GameProfile) -> bool {
+        !self.white_list.load(Ordering::Relaxed)
+            || OPERATOR_CONFIG
+                .read()
+                .await
+                .ops
+                .iter()
+                .any(|op| op.uuid == profile.id)
+            || WHITELIST_CONFIG.read().await.is_whitelisted(profile)
+    }
+
+    /// Kicks the players who aren't whitelisted if the whitelist is enforced, like vanilla's
+    /// `MinecraftServer.kickUnlistedPlayers`
+    pub async fn kick_unlisted_players(&self) {
+        if !BASIC_CONFIG.enforce_whitelist {
+            return;
+        }
+        for player in self.get_all_players().await {
+            if !self.is_whitelisted(&player.gameprofile).await {
+                player
+                    .kick(TextComponent::text_string(
+                        ADVANCED_CONFIG.moderation.not_whitelisted_message.clone(),
+                    ))
+                    .await;
+            }
+        }
+    }
+
     /// Returns a random player from any of the worlds or None if all worlds are empty.
     pub async fn get_random_player(&self) -> Option<Arc<Player>> {
         let players = self.get_all_players().await;
@@ -386,8 +567,191 @@ impl Server {
     }
 
     async fn tick(&self) {
+        self.tick_functions().await;
+        // Clicks wait until the block entities ticked and the containers showing them copied
+        // their progress, otherwise a click in between would be overwritten
+        let open_blocks = self.open_container_blocks().await;
+        let mut containers = self.lock_block_containers().await;
+        self.save_container_block_entities(&containers).await;
+        for world in &self.worlds {
+            world.tick(self, &open_blocks).await;
+        }
+        self.tick_containers(&mut containers).await;
+        drop(containers);
+        self.close_invalid_containers().await;
+    }
+
+    /// Closes containers for players who walked away from them or whose container block was broken
+    async fn close_invalid_containers(&self) {
         for world in &self.worlds {
-            world.tick().await;
+            let players: Vec<_> = world
+                .current_players
+                .lock()
+                .await
+                .values()
+                .cloned()
+                .collect();
+            for player in players {
+                if !player.is_open_container_valid(self).await {
+                    player.force_close_container(self).await;
+                }
+            }
         }
     }
+
+    /// The open containers with their viewers and the position of their block. Cloned so the
+    /// lock isn't held while they tick, clicking a container locks in the opposite order
+    async fn open_container_handles(&self) -> Vec<ContainerHandle> {
+        self.open_containers
+            .read()
+            .await
+            .values()
+            .map(|container| {
+                (
+                    container.get_container(),
+                    container.all_player_ids(),
+                    container.get_location(),
+                )
+            })
+            .collect()
+    }
+
+    /// The blocks of the open containers, with both halves of large chests
+    async fn open_container_blocks(&self) -> HashSet<WorldPosition> {
+        self.open_containers
+            .read()
+            .await
+            .values()
+            .flat_map(|container| [container.get_location(), container.get_second_location()])
+            .flatten()
+            .collect()
+    }
+
+    /// Locks the open containers of blocks for the tick, the others are ticked one by one
+    async fn lock_block_containers(&self) -> Vec<LockedContainer> {
+        let mut containers = Vec::new();
+        for (container, player_ids, location) in self.open_container_handles().await {
+            if let Some(location) = location {
+                containers.push((container.lock_owned().await, player_ids, location));
+            }
+        }
+        containers
+    }
+
+    /// The world the players viewing a container are in
+    async fn viewer_world(&self, player_ids: &[i32]) -> Option<&Arc<World>> {
+        for world in &self.worlds {
+            for player_id in player_ids {
+                if world.get_player_by_entityid(*player_id).await.is_some() {
+                    return Some(world);
+                }
+            }
+        }
+        None
+    }
+
+    /// Copies what players put into containers of block entities which work on their own, like
+    /// furnaces, into the block entities before the world ticks them
+    async fn save_container_block_entities(&self, containers: &[LockedContainer]) {
+        for (container, player_ids, location) in containers {
+            if !container.block_entity_changed() {
+                continue;
+            }
+            let Some(world) = self.viewer_world(player_ids).await else {
+                continue;
+            };
+            world
+                .level
+                .update_block_entity(*location, |block_entity| {
+                    container.save_block_entity(block_entity)
+                })
+                .await;
+        }
+    }
+
+    /// Ticks containers which change on their own (e.g. plugin menus), copies the progress of
+    /// block entities into the containers showing them and syncs both with their viewers
+    async fn tick_containers(&self, block_containers: &mut [LockedContainer]) {
+        for (container, player_ids, location) in block_containers {
+            self.tick_container(container, player_ids, Some(*location))
+                .await;
+        }
+        for (container, player_ids, location) in self.open_container_handles().await {
+            if location.is_none() {
+                let mut container = container.lock().await;
+                self.tick_container(&mut container, &player_ids, None).await;
+            }
+        }
+    }
+
+    /// Ticks one container, the containers of blocks are locked for the whole tick
+    async fn tick_container(
+        &self,
+        container: &mut Box<dyn Container>,
+        player_ids: &[i32],
+        location: Option<WorldPosition>,
+    ) {
+        let world = self.viewer_world(player_ids).await;
+        let old_properties = container.window_properties();
+        let mut slots_changed = container.tick();
+        if let (Some(world), Some(location)) = (world, location) {
+            slots_changed |= world
+                .level
+                .read_block_entity(location, |block_entity| {
+                    container.load_block_entity(block_entity)
+                })
+                .await
+                .unwrap_or(false);
+        }
+        let changed_properties: Vec<_> = container
+            .window_properties()
+            .into_iter()
+            .filter(|property| !old_properties.contains(property))
+            .collect();
+        if !slots_changed && changed_properties.is_empty() {
+            return;
+        }
+
+        for player_id in player_ids {
+            for world in &self.worlds {
+                let Some(player) = world.get_player_by_entityid(*player_id).await else {
+                    continue;
+                };
+                if slots_changed {
+                    player.set_container_content(Some(container)).await;
+                }
+                player.set_container_properties(&changed_properties).await;
+            }
+        }
+    }
+}
+
+/// Whether the key names the world, the namespace defaults to `minecraft`
+fn is_world_key(world: &Identifier, key: &str) -> bool {
+    let (namespace, path) = key.split_once(':').unwrap_or(("minecraft", key));
+    world.namespace == namespace && world.path == path
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::codec::identifier::Identifier;
+
+    use super::is_world_key;
+
+    #[test]
+    fn world_keys_default_to_minecraft() {
+        let nether = Identifier::vanilla("the_nether");
+        assert!(is_world_key(&nether, "the_nether"));
+        assert!(is_world_key(&nether, "minecraft:the_nether"));
+        assert!(!is_world_key(&nether, "pumpkin:the_nether"));
+        assert!(!is_world_key(&nether, "overworld"));
+
+        let custom = Identifier {
+            namespace: "pumpkin".to_string(),
+            path: "mining".to_string(),
+        };
+        assert!(is_world_key(&custom, "pumpkin:mining"));
+        assert!(!is_world_key(&custom, "mining"));
+        assert!(!is_world_key(&custom, "pumpkin:"));
+    }
 }
@@ -1,6 +1,9 @@
+use std::sync::{atomic::Ordering, Arc};
+
 use crate::entity::player::Player;
 use pumpkin_core::text::TextComponent;
 use pumpkin_protocol::client::play::{BosseventAction, CBossEvent};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 #[derive(Clone, PartialEq)]
@@ -57,6 +60,123 @@ impl Bossbar {
     }
 }
 
+/// A bossbar and the players who see it, every change is sent to them right away. Lets plugins
+/// and features show a bar without tracking the players themselves, like vanilla's
+/// `ServerBossEvent`
+pub struct BossbarHandle {
+    bossbar: Mutex<Bossbar>,
+    viewers: Mutex<Vec<Arc<Player>>>,
+}
+
+impl BossbarHandle {
+    #[must_use]
+    pub fn new(bossbar: Bossbar) -> Self {
+        Self {
+            bossbar: Mutex::new(bossbar),
+            viewers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The bossbar as it is shown right now
+    pub async fn bossbar(&self) -> Bossbar {
+        self.bossbar.lock().await.clone()
+    }
+
+    /// The players who see the bossbar, players who left are forgotten
+    pub async fn viewers(&self) -> Vec<Arc<Player>> {
+        let mut viewers = self.viewers.lock().await;
+        viewers.retain(|player| !player.client.closed.load(Ordering::Relaxed));
+        viewers.clone()
+    }
+
+    /// Shows the bossbar to the player, returns false if the player already sees it
+    pub async fn add_viewer(&self, player: Arc<Player>) -> bool {
+        // The bossbar is always locked before the viewers
+        let bossbar = self.bossbar.lock().await;
+        let mut viewers = self.viewers.lock().await;
+        if viewers.iter().any(|viewer| Arc::ptr_eq(viewer, &player)) {
+            return false;
+        }
+        player.send_bossbar(&bossbar).await;
+        viewers.push(player);
+        true
+    }
+
+    /// Hides the bossbar from the player, returns false if the player didn't see it
+    pub async fn remove_viewer(&self, player: &Player) -> bool {
+        let uuid = self.bossbar.lock().await.uuid;
+        let mut viewers = self.viewers.lock().await;
+        let Some(index) = viewers
+            .iter()
+            .position(|viewer| viewer.entity_id() == player.entity_id())
+        else {
+            return false;
+        };
+        let player = viewers.remove(index);
+        player.remove_bossbar(uuid).await;
+        true
+    }
+
+    pub async fn remove_all_viewers(&self) {
+        let uuid = self.bossbar.lock().await.uuid;
+        for player in self.viewers.lock().await.drain(..) {
+            player.remove_bossbar(uuid).await;
+        }
+    }
+
+    pub async fn set_title(&self, title: String) {
+        let mut bossbar = self.bossbar.lock().await;
+        if bossbar.title == title {
+            return;
+        }
+        bossbar.title = title;
+        for player in self.viewers().await {
+            player
+                .update_bossbar_title(bossbar.uuid, bossbar.title.clone())
+                .await;
+        }
+    }
+
+    /// Sets how full the bar is, between 0 and 1
+    pub async fn set_health(&self, health: f32) {
+        let mut bossbar = self.bossbar.lock().await;
+        bossbar.health = health.clamp(0.0, 1.0);
+        for player in self.viewers().await {
+            player
+                .update_bossbar_health(bossbar.uuid, bossbar.health)
+                .await;
+        }
+    }
+
+    pub async fn set_style(&self, color: BossbarColor, division: BossbarDivisions) {
+        let mut bossbar = self.bossbar.lock().await;
+        if bossbar.color == color && bossbar.division == division {
+            return;
+        }
+        bossbar.color = color;
+        bossbar.division = division;
+        for player in self.viewers().await {
+            player
+                .update_bossbar_style(
+                    bossbar.uuid,
+                    bossbar.color.clone(),
+                    bossbar.division.clone(),
+                )
+                .await;
+        }
+    }
+
+    pub async fn set_flags(&self, flags: BossbarFlags) {
+        let mut bossbar = self.bossbar.lock().await;
+        bossbar.flags = flags;
+        for player in self.viewers().await {
+            player
+                .update_bossbar_flags(bossbar.uuid, bossbar.flags.clone())
+                .await;
+        }
+    }
+}
+
 /// Extension of the player to send the manage the bossbar
 impl Player {
     pub async fn send_bossbar(&self, bossbar: &Bossbar) {
@@ -116,3 +236,25 @@ impl Player {
         self.client.send_packet(&packet).await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Bossbar, BossbarColor, BossbarDivisions, BossbarHandle};
+
+    #[tokio::test]
+    async fn handles_keep_the_bossbar_up_to_date() {
+        let handle = BossbarHandle::new(Bossbar::new("Raid".to_string()));
+        assert!(handle.viewers().await.is_empty());
+
+        handle.set_health(1.5).await;
+        handle.set_title("Raid - Victory".to_string()).await;
+        handle
+            .set_style(BossbarColor::Red, BossbarDivisions::Notches10)
+            .await;
+        let bossbar = handle.bossbar().await;
+        assert!((bossbar.health - 1.0).abs() < f32::EPSILON);
+        assert_eq!(bossbar.title, "Raid - Victory");
+        assert!(bossbar.color == BossbarColor::Red);
+        assert!(bossbar.division == BossbarDivisions::Notches10);
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_inventory::cooking_result;
+use pumpkin_registry::RecipeType;
+use pumpkin_world::item::ItemStack;
+
+use crate::server::Server;
+
+use super::World;
+
+impl World {
+    /// Puts a single item of the stack on the campfire at the position, if the item can be cooked
+    /// on campfires. Returns false if it can't or the campfire is full
+    pub async fn place_campfire_food(&self, position: WorldPosition, stack: &ItemStack) -> bool {
+        let Some((_, cooking_time, _)) = cooking_result(RecipeType::CampfireCooking, stack) else {
+            return false;
+        };
+        let Some(data) = self
+            .level
+            .place_campfire_food(position, stack, i32::from(cooking_time))
+            .await
+        else {
+            return false;
+        };
+        self.update_block_entity(position, data).await;
+        true
+    }
+
+    /// Drops what the items a campfire finished cooking cook into, like vanilla's
+    /// `CampfireBlockEntity.cookTick`
+    pub(super) async fn drop_campfire_food(
+        self: &Arc<Self>,
+        server: &Server,
+        position: WorldPosition,
+        cooked: Vec<ItemStack>,
+    ) {
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        for item in cooked {
+            if let Some((result, _, _)) = cooking_result(RecipeType::CampfireCooking, &item) {
+                self.drop_item_stack(server.new_entity_id(), center, result)
+                    .await;
+            }
+        }
+    }
+}
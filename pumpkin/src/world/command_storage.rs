@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_world::saved_data::{SavedDataError, SavedDataStorage};
+
+/// The saved data files of the storages are named after their namespace with this prefix
+const COMMAND_STORAGE_PREFIX: &str = "command_storage_";
+
+/// The compounds `/data` keeps in storages, like vanilla's `CommandStorage`. Storages are saved
+/// with the overworld, one file per namespace
+#[derive(Default)]
+pub struct CommandStorage {
+    /// Keyed by the namespaced id of the storage
+    storages: HashMap<String, NbtCompound>,
+}
+
+/// The id with the `minecraft` namespace if it has none
+fn namespaced(id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("minecraft:{id}")
+    }
+}
+
+impl CommandStorage {
+    /// The compound in the storage, empty if nothing was put into it
+    #[must_use]
+    pub fn get(&self, id: &str) -> NbtCompound {
+        self.storages
+            .get(&namespaced(id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replaces the compound in the storage, empty compounds remove the storage
+    pub fn set(&mut self, id: &str, nbt: NbtCompound) {
+        if nbt.child_tags.is_empty() {
+            self.storages.remove(&namespaced(id));
+        } else {
+            self.storages.insert(namespaced(id), nbt);
+        }
+    }
+
+    /// The ids of the storages with something in them
+    #[must_use]
+    pub fn ids(&self) -> Vec<String> {
+        let mut ids: Vec<_> = self.storages.keys().cloned().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Reads every `command_storage_<namespace>.dat` of the saved data
+    #[must_use]
+    pub fn load(saved_data: &SavedDataStorage) -> Self {
+        let mut storage = Self::default();
+        for namespace in saved_data.names_with_prefix(COMMAND_STORAGE_PREFIX) {
+            let nbt = match saved_data.load(&format!("{COMMAND_STORAGE_PREFIX}{namespace}")) {
+                Ok(Some(nbt)) => nbt,
+                Ok(None) => continue,
+                Err(err) => {
+                    log::error!("Failed to load the command storage of {namespace}: {err}");
+                    continue;
+                }
+            };
+            let Some(contents) = nbt.get_compound("contents") else {
+                continue;
+            };
+            for (path, data) in &contents.child_tags {
+                if let NbtTag::Compound(data) = data {
+                    storage
+                        .storages
+                        .insert(format!("{namespace}:{path}"), data.clone());
+                }
+            }
+        }
+        storage
+    }
+
+    /// Writes the storages of every namespace into its own file
+    pub fn save(&self, saved_data: &SavedDataStorage) -> Result<(), SavedDataError> {
+        let mut namespaces: HashMap<&str, NbtCompound> = HashMap::new();
+        for (id, data) in &self.storages {
+            let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+            namespaces
+                .entry(namespace)
+                .or_default()
+                .put(path.to_string(), NbtTag::Compound(data.clone()));
+        }
+        // Namespaces whose storages were all emptied are saved empty, so they don't come back
+        for namespace in saved_data.names_with_prefix(COMMAND_STORAGE_PREFIX) {
+            if !namespaces.keys().any(|saved| *saved == namespace) {
+                saved_data.save(
+                    &format!("{COMMAND_STORAGE_PREFIX}{namespace}"),
+                    NbtCompound::new(),
+                )?;
+            }
+        }
+        for (namespace, contents) in namespaces {
+            let mut nbt = NbtCompound::new();
+            nbt.put("contents".to_string(), NbtTag::Compound(contents));
+            saved_data.save(&format!("{COMMAND_STORAGE_PREFIX}{namespace}"), nbt)?;
+        }
+        Ok(())
+    }
+}
@@ -59,11 +59,12 @@ impl CustomBossbars {
         }
     }
 
+    /// The visible bossbars the player was added to
     #[must_use]
     pub fn get_player_bars(&self, uuid: &Uuid) -> Option<Vec<&Bossbar>> {
         let mut player_bars: Vec<&Bossbar> = Vec::new();
         for bossbar in &self.custom_bossbars {
-            if bossbar.1.player.contains(uuid) {
+            if bossbar.1.visible && bossbar.1.player.contains(uuid) {
                 player_bars.push(&bossbar.1.bossbar_data);
             }
         }
@@ -416,3 +417,33 @@ impl CustomBossbars {
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use uuid::Uuid;
+
+    use super::CustomBossbars;
+    use crate::world::bossbar::Bossbar;
+
+    #[test]
+    fn hidden_bossbars_are_not_shown_on_join() {
+        let player = Uuid::new_v4();
+        let mut bossbars = CustomBossbars::new();
+        bossbars.create_bossbar(
+            "minecraft:raid".to_string(),
+            Bossbar::new("Raid".to_string()),
+        );
+        let mut bossbar = bossbars.get_bossbar("minecraft:raid").unwrap();
+        bossbar.player.push(player);
+        bossbars.replace_bossbar("minecraft:raid", bossbar.clone());
+        assert_eq!(
+            bossbars.get_player_bars(&player).as_ref().map(Vec::len),
+            Some(1)
+        );
+        assert!(bossbars.get_player_bars(&Uuid::new_v4()).is_none());
+
+        bossbar.visible = false;
+        bossbars.replace_bossbar("minecraft:raid", bossbar);
+        assert!(bossbars.get_player_bars(&player).is_none());
+    }
+}
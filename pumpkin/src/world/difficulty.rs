@@ -0,0 +1,87 @@
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    Difficulty,
+};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_protocol::client::play::CChangeDifficulty;
+use pumpkin_world::difficulty::{moon_brightness, LocalDifficulty};
+
+use crate::{
+    entity::mob::{is_monster, MobEntity},
+    net::Client,
+};
+
+use super::World;
+
+/// Whether the mob disappears on peaceful, like vanilla's `Mob.shouldDespawnInPeaceful`
+const fn despawns_in_peaceful(entity_type: &EntityType) -> bool {
+    is_monster(entity_type)
+        || matches!(
+            entity_type,
+            EntityType::Ghast
+                | EntityType::MagmaCube
+                | EntityType::Phantom
+                | EntityType::Slime
+                | EntityType::ZombifiedPiglin
+        )
+}
+
+impl World {
+    /// Tells the client how hard the world is, it shows the difficulty in its options
+    pub async fn send_difficulty(&self, client: &Client) {
+        client
+            .send_packet(&CChangeDifficulty::new(self.difficulty.load() as u8, false))
+            .await;
+    }
+
+    /// Changes how hard the world is and tells every player in it
+    pub async fn set_difficulty(&self, difficulty: Difficulty) {
+        self.difficulty.store(difficulty);
+        self.broadcast_packet_all(&CChangeDifficulty::new(difficulty as u8, false))
+            .await;
+    }
+
+    /// The difficulty at the position, like vanilla's `Level.getCurrentDifficultyAt`. The moon and
+    /// the time players spent nearby only count in loaded chunks
+    pub async fn local_difficulty(&self, position: WorldPosition) -> LocalDifficulty {
+        let day_time = self.level_time.lock().await.time_of_day;
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let (inhabited_time, moon) = match self.level.get_loaded_chunk(&chunk) {
+            Some(chunk) => (chunk.read().await.inhabited_time, moon_brightness(day_time)),
+            None => (0, 0.0),
+        };
+        LocalDifficulty::new(self.difficulty.load(), day_time, inhabited_time, moon)
+    }
+
+    /// Counts how long players stay in the chunks around them and removes monsters on peaceful
+    pub(super) async fn tick_difficulty(&self) {
+        self.level.tick_inhabited_time();
+        if self.difficulty.load() != Difficulty::Peaceful {
+            return;
+        }
+        let despawned: Vec<_> = self
+            .mobs
+            .lock()
+            .await
+            .values()
+            .filter(|mob| despawns_in_peaceful(&mob.entity().entity_type))
+            .cloned()
+            .collect();
+        for mob in despawned {
+            self.remove_mob(&mob).await;
+        }
+    }
+
+    /// Puts armor and weapons on zombies and skeletons, the harder it is where they spawn the more
+    /// likely they are equipped
+    pub async fn equip_spawned_mob(&self, mob: &MobEntity) {
+        let pos = mob.entity().pos.load();
+        let position = WorldPosition(Vector3::new(
+            pos.x.floor() as i32,
+            pos.y.floor() as i32,
+            pos.z.floor() as i32,
+        ));
+        let local = self.local_difficulty(position).await;
+        mob.populate_equipment(&local).await;
+    }
+}
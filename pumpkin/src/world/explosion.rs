@@ -0,0 +1,172 @@
+use std::sync::Arc;
+
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_macros::{particle, sound};
+use pumpkin_protocol::{client::play::CExplosion, codec::var_int::VarInt};
+use pumpkin_world::{
+    block::{
+        block_registry::{get_block, get_block_by_state_id, get_state_by_state_id},
+        explosion::LARGE_EXPLOSION_POWER,
+    },
+    damage::DamageType,
+    difficulty::scale_damage,
+    loot::{block_drops, LootContext},
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    entity::{damage::DamageSource, tnt::chained_fuse},
+    server::Server,
+};
+
+use super::World;
+
+/// Players further away don't see the explosion
+const EXPLOSION_VIEW_DISTANCE: f64 = 64.0;
+
+impl World {
+    /// Explodes at the center like vanilla's `Explosion.explode` and `finalizeExplosion`. Breaks
+    /// the blocks it reaches and drops some of them, lights TNT in its way, hurts and pushes
+    /// players and pushes lit TNT. Explosions causing fires set some of the air they reach alight
+    pub async fn explode(
+        self: &Arc<Self>,
+        center: Vector3<f64>,
+        power: f32,
+        fire: bool,
+        damage_type: DamageType,
+        server: &Server,
+    ) {
+        let exploded = self.level.exploded_blocks(center, power);
+
+        let mut broken = Vec::new();
+        let mut air = Vec::new();
+        for position in exploded {
+            let Some(state_id) = self.level.loaded_block_state(&position).await else {
+                continue;
+            };
+            if get_state_by_state_id(state_id).is_none_or(|state| state.air) {
+                air.push(position);
+                continue;
+            }
+            let Some(block) = get_block_by_state_id(state_id) else {
+                continue;
+            };
+            if block.name == "tnt" {
+                self.spawn_primed_tnt(position, chained_fuse(), server)
+                    .await;
+                continue;
+            }
+            self.set_block_state(position, 0).await;
+            let block_entity = self.remove_block_entity_nbt(position).await;
+            let context = LootContext {
+                explosion_power: Some(power),
+                block_entity: block_entity.as_ref(),
+                ..Default::default()
+            };
+            let drops = block_drops(&block.name, block.item_id, &context, &mut thread_rng());
+            for drop in drops {
+                self.pop_resource(server, position, drop).await;
+            }
+            broken.push(position);
+        }
+        for position in &broken {
+            self.update_neighbors(*position, server).await;
+        }
+        if fire {
+            self.place_explosion_fire(air.into_iter().chain(broken.iter().copied()), server)
+                .await;
+        }
+
+        for tnt in self.primed_tnt.lock().await.values() {
+            let entity = &tnt.entity;
+            let pos = entity.pos.load();
+            if let Some(hit) =
+                self.level
+                    .explosion_hit(center, power, pos, pos, &entity.bounding_box.load())
+            {
+                tnt.knock_back(hit.knockback);
+            }
+        }
+
+        let particle = if power < LARGE_EXPLOSION_POWER || broken.is_empty() {
+            particle!("explosion")
+        } else {
+            particle!("explosion_emitter")
+        };
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            let entity = &player.living_entity.entity;
+            let pos = entity.pos.load();
+            if pos.sub(&center).length_squared() > EXPLOSION_VIEW_DISTANCE.powi(2) {
+                continue;
+            }
+            let gamemode = player.gamemode.load();
+            let hit = if matches!(gamemode, GameMode::Creative | GameMode::Spectator) {
+                None
+            } else {
+                let eyes =
+                    Vector3::new(pos.x, pos.y + f64::from(entity.standing_eye_height), pos.z);
+                self.level
+                    .explosion_hit(center, power, pos, eyes, &entity.bounding_box.load())
+            };
+            if let Some(hit) = hit {
+                // Explosions hurt players more the harder the world is
+                let damage = scale_damage(self.difficulty.load(), hit.damage);
+                player
+                    .hurt(&DamageSource::at(damage_type, center), damage)
+                    .await;
+            }
+            player
+                .client
+                .send_packet(&CExplosion::new(
+                    center,
+                    hit.map(|hit| hit.knockback),
+                    VarInt(i32::from(particle)),
+                    VarInt(i32::from(sound!("entity.generic.explode"))),
+                ))
+                .await;
+        }
+    }
+
+    /// Sets a third of the air the explosion reached alight, where there is a block below to burn on
+    async fn place_explosion_fire(
+        self: &Arc<Self>,
+        positions: impl Iterator<Item = WorldPosition>,
+        server: &Server,
+    ) {
+        let Some(fire) = get_block("minecraft:fire") else {
+            return;
+        };
+        for position in positions {
+            if thread_rng().gen_range(0..3) != 0 {
+                continue;
+            }
+            let below = WorldPosition(position.0 + Vector3::new(0, -1, 0));
+            let is_air = self
+                .level
+                .loaded_block_state(&position)
+                .await
+                .and_then(get_state_by_state_id)
+                .is_some_and(|state| state.air);
+            let on_solid = self
+                .level
+                .loaded_block_state(&below)
+                .await
+                .and_then(get_state_by_state_id)
+                .is_some_and(|state| !state.collision_shapes.is_empty());
+            if is_air && on_solid {
+                self.set_block_state(position, fire.default_state_id).await;
+                self.update_neighbors(position, server).await;
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use pumpkin_protocol::client::play::CUpdateTime;
+use pumpkin_world::world_info::game_rules::BoolRule;
 
 use super::World;
 
@@ -8,6 +9,8 @@ pub struct LevelTime {
     pub rain_time: i64,
 }
 
+/// Times of day `/time set` knows by name
+#[derive(Clone, Copy)]
 pub enum DayTime {
     Day = 1000,
     Night = 13000,
@@ -31,15 +34,29 @@ impl LevelTime {
         }
     }
 
-    pub fn tick_time(&mut self) {
+    /// The time of day only moves on with `doDaylightCycle`, the age of the world always does
+    pub fn tick_time(&mut self, daylight_cycle: bool) {
         self.world_age += 1;
-        self.time_of_day += 1;
+        if daylight_cycle {
+            self.time_of_day += 1;
+        }
         self.rain_time += 1;
     }
 
+    /// Sends the time to every player in the world, their clients move the time of day along
+    /// between updates while the daylight cycle runs
     pub async fn send_time(&self, world: &World) {
+        let daylight_cycle = world
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::DoDaylightCycle);
         world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
+            .broadcast_packet_all(&CUpdateTime::new(
+                self.world_age,
+                self.time_of_day,
+                daylight_cycle,
+            ))
             .await;
     }
 
@@ -51,6 +68,18 @@ impl LevelTime {
         self.time_of_day = time;
     }
 
+    /// Moves the time on to the next morning, like vanilla does when players slept through the night
+    pub fn skip_to_morning(&mut self) {
+        let time = self.time_of_day + 24000;
+        self.time_of_day = time - time % 24000;
+    }
+
+    /// Whether it is dark enough to sleep, like vanilla's `Level.isDay` in clear weather
+    #[must_use]
+    pub fn is_night(&self) -> bool {
+        (12542..23460).contains(&self.query_daytime())
+    }
+
     #[must_use]
     pub const fn query_daytime(&self) -> i64 {
         self.time_of_day % 24000
@@ -66,3 +95,28 @@ impl LevelTime {
         self.time_of_day / 24000
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::LevelTime;
+
+    #[test]
+    fn the_daylight_cycle_only_stops_the_time_of_day() {
+        let mut time = LevelTime::new();
+        time.tick_time(false);
+        assert_eq!((time.world_age, time.time_of_day), (1, 0));
+        time.tick_time(true);
+        assert_eq!((time.world_age, time.time_of_day), (2, 1));
+    }
+
+    #[test]
+    fn sleeping_skips_to_the_next_morning() {
+        let mut time = LevelTime::new();
+        time.set_time(2 * 24000 + 13000);
+        assert!(time.is_night());
+        time.skip_to_morning();
+        assert_eq!(time.time_of_day, 3 * 24000);
+        assert_eq!(time.query_day(), 3);
+        assert!(!time.is_night());
+    }
+}
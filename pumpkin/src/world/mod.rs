@@ -1,57 +1,123 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use crossbeam::atomic::AtomicCell;
 
 pub mod level_time;
 pub mod player_chunker;
 
 use crate::{
     command::client_cmd_suggestions,
-    entity::{player::Player, Entity},
+    entity::{
+        ai::Surroundings,
+        any_entity::AnyEntity,
+        armor_stand::ArmorStandEntity,
+        experience_orb::{orb_value, ExperienceOrbEntity},
+        falling_block::FallingBlockEntity,
+        horse::HorseEntity,
+        item::{ItemEntity, ITEM_ENTITY_ID},
+        item_frame::ItemFrameEntity,
+        lightning::LightningBoltEntity,
+        mob::MobEntity,
+        player::Player,
+        projectile::ProjectileEntity,
+        tnt::TntEntity,
+        Entity,
+    },
     error::PumpkinError,
+    net::stats::Stat,
     server::Server,
 };
 use level_time::LevelTime;
+use neighbor_updates::{changes_by_section, NeighborUpdates, MAX_NEIGHBOR_UPDATES};
+use pregen::Pregeneration;
 use pumpkin_config::BasicConfiguration;
 use pumpkin_core::math::vector2::Vector2;
-use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_core::math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3};
 use pumpkin_core::text::{color::NamedColor, TextComponent};
-use pumpkin_entity::{entity_type::EntityType, EntityId};
+use pumpkin_core::{Difficulty, GameMode};
+use pumpkin_entity::EntityId;
+use pumpkin_inventory::{BrewingStandRecipes, FurnaceRecipes};
+use pumpkin_macros::sound;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_protocol::{
     client::play::CLevelEvent,
     codec::{identifier::Identifier, var_int::VarInt},
 };
 use pumpkin_protocol::{
-    client::play::{CBlockUpdate, CRespawn, CSoundEffect, CWorldEvent},
+    client::play::{
+        CBlockEntityData, CBlockUpdate, CLightUpdate, CRespawn, CSectionBlocksUpdate, CSoundEffect,
+        CWorldEvent,
+    },
     SoundCategory,
 };
 use pumpkin_protocol::{
     client::play::{
-        CChunkData, CGameEvent, CLogin, CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo,
-        CSetEntityMetadata, CSpawnEntity, GameEvent, Metadata, PlayerAction,
+        CChunkData, CGameEvent, CLogin, CPlayerInfoUpdate, CRemovePlayerInfo, GameEvent,
+        PlayerAction,
     },
     ClientPacket,
 };
 use pumpkin_registry::DimensionType;
-use pumpkin_world::chunk::ChunkData;
+use pumpkin_world::chunk::{
+    light::{affects_light, LightUpdater},
+    ticks::TickPriority,
+    ChunkData,
+};
+use pumpkin_world::datapack::DataPacks;
+use pumpkin_world::item::ItemStack;
 use pumpkin_world::level::Level;
+use pumpkin_world::physics::is_below_world;
+use pumpkin_world::stats::StatType;
+use pumpkin_world::world_info::LevelData;
+use pumpkin_world::{
+    block::fluid::Fluid,
+    loot::{entity_drops, LootContext},
+    world_info::game_rules::{BoolRule, GameRules, IntRule},
+};
 use pumpkin_world::{
-    block::block_registry::{
-        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+    block::{
+        block_entity::{
+            block_entity_data, block_entity_from_nbt, block_entity_type_id,
+            hopper::HopperBlockEntity, new_block_entity,
+        },
+        block_registry::{
+            get_block_and_state_by_state_id, get_block_by_state_id, get_block_entity_type,
+            get_state_by_state_id, Block,
+        },
     },
     coordinates::ChunkRelativeBlockCoordinates,
 };
 use rand::{thread_rng, Rng};
-use scoreboard::Scoreboard;
+use scoreboard::ObjectiveCriterion;
 use thiserror::Error;
 use tokio::sync::{mpsc::Receiver, Mutex};
 use tokio::{
     runtime::Handle,
     sync::{mpsc, RwLock},
 };
+use weather::Weather;
 use worldborder::Worldborder;
 
 pub mod bossbar;
+pub mod campfire;
+pub mod command_storage;
 pub mod custom_bossbar;
+pub mod difficulty;
+pub mod explosion;
+pub mod natural_spawning;
+pub mod neighbor_updates;
+pub mod portal;
+pub mod pregen;
+pub mod respawn;
 pub mod scoreboard;
+pub mod sleep;
+pub mod spawner;
+pub mod team;
+pub mod tracking;
+pub mod weather;
 pub mod worldborder;
 
 #[derive(Debug, Error)]
@@ -94,32 +160,179 @@ pub struct World {
     pub level: Arc<Level>,
     /// A map of active players within the world, keyed by their unique UUID.
     pub current_players: Arc<Mutex<HashMap<uuid::Uuid, Arc<Player>>>>,
-    /// The world's scoreboard, used for tracking scores, objectives, and display information.
-    pub scoreboard: Mutex<Scoreboard>,
+    /// A map of dropped items lying in the world, keyed by their entity id.
+    pub item_entities: Arc<Mutex<HashMap<EntityId, Arc<ItemEntity>>>>,
+    /// A map of experience orbs, keyed by their entity id.
+    pub experience_orbs: Arc<Mutex<HashMap<EntityId, Arc<ExperienceOrbEntity>>>>,
+    /// A map of horses, donkeys, mules and llamas, keyed by their entity id.
+    pub horse_entities: Arc<Mutex<HashMap<EntityId, Arc<HorseEntity>>>>,
+    /// A map of item frames hanging on blocks, keyed by their entity id.
+    pub item_frames: Arc<Mutex<HashMap<EntityId, Arc<ItemFrameEntity>>>>,
+    /// A map of armor stands, keyed by their entity id.
+    pub armor_stands: Arc<Mutex<HashMap<EntityId, Arc<ArmorStandEntity>>>>,
+    /// A map of blocks falling down, keyed by their entity id.
+    pub falling_blocks: Arc<Mutex<HashMap<EntityId, Arc<FallingBlockEntity>>>>,
+    /// A map of lit TNT, keyed by their entity id.
+    pub primed_tnt: Arc<Mutex<HashMap<EntityId, Arc<TntEntity>>>>,
+    /// A map of arrows, tridents and thrown items, keyed by their entity id.
+    pub projectiles: Arc<Mutex<HashMap<EntityId, Arc<ProjectileEntity>>>>,
+    /// A map of mobs, like the ones spawners spawn, keyed by their entity id.
+    pub mobs: Arc<Mutex<HashMap<EntityId, Arc<MobEntity>>>>,
+    /// A map of lightning bolts flashing, keyed by their entity id.
+    pub lightning_bolts: Arc<Mutex<HashMap<EntityId, Arc<LightningBoltEntity>>>>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
     pub worldborder: Mutex<Worldborder>,
     /// The world's time, including counting ticks for weather, time cycles and statistics
     pub level_time: Mutex<LevelTime>,
+    /// The world's rain and thunder
+    pub weather: Mutex<Weather>,
+    /// The game rules of the world, like whether the daylight cycle runs
+    pub game_rules: Mutex<GameRules>,
+    /// The datapacks enabled in the world, only the ones of the overworld are loaded
+    pub data_packs: Mutex<DataPacks>,
+    /// How hard the world is, see `local_difficulty` for the difficulty at a place
+    pub difficulty: AtomicCell<Difficulty>,
     /// The type of dimension the world is in
     pub dimension_type: DimensionType,
+    /// The identifier players know the world by, like `minecraft:the_nether`
+    pub key: Identifier,
+    /// The `/pregen` generating chunks in the background, if there is one
+    pub pregeneration: Mutex<Option<Arc<Pregeneration>>>,
+    /// The chain of neighbor updates currently running, see `update_neighbors`
+    neighbor_updates: Mutex<NeighborUpdates>,
+    /// Chunks sent to players, the items saved in them are added to the world with the next tick
+    entity_chunks: Mutex<Vec<Vector2<i32>>>,
     // TODO: entities
 }
 
 impl World {
+    /// Loads one of the vanilla dimensions, the world is known by the name of its dimension type
     #[must_use]
     pub fn load(level: Level, dimension_type: DimensionType) -> Self {
+        let key = dimension_type.name();
+        Self::load_named(level, dimension_type, key)
+    }
+
+    /// Loads a world with its own identifier, like custom worlds
+    #[must_use]
+    pub fn load_named(level: Level, dimension_type: DimensionType, key: Identifier) -> Self {
+        let game_rules = level.level_info.game_rules.clone();
+        let data_packs = level.level_info.data_packs.clone();
+        let difficulty = AtomicCell::new(level.level_info.difficulty);
         Self {
             level: Arc::new(level),
             current_players: Arc::new(Mutex::new(HashMap::new())),
-            scoreboard: Mutex::new(Scoreboard::new()),
-            worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
+            item_entities: Arc::new(Mutex::new(HashMap::new())),
+            experience_orbs: Arc::new(Mutex::new(HashMap::new())),
+            horse_entities: Arc::new(Mutex::new(HashMap::new())),
+            item_frames: Arc::new(Mutex::new(HashMap::new())),
+            armor_stands: Arc::new(Mutex::new(HashMap::new())),
+            falling_blocks: Arc::new(Mutex::new(HashMap::new())),
+            primed_tnt: Arc::new(Mutex::new(HashMap::new())),
+            projectiles: Arc::new(Mutex::new(HashMap::new())),
+            mobs: Arc::new(Mutex::new(HashMap::new())),
+            lightning_bolts: Arc::new(Mutex::new(HashMap::new())),
+            worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 59_999_968.0, 0, 5, 15)),
             level_time: Mutex::new(LevelTime::new()),
+            weather: Mutex::new(Weather::default()),
+            game_rules: Mutex::new(game_rules),
+            data_packs: Mutex::new(data_packs),
+            difficulty,
             dimension_type,
+            key,
+            pregeneration: Mutex::new(None),
+            neighbor_updates: Mutex::new(NeighborUpdates::default()),
+            entity_chunks: Mutex::new(Vec::new()),
         }
     }
 
     pub async fn save(&self) {
-        self.level.save().await;
+        for player in self.current_players.lock().await.values() {
+            player.save_data().await;
+        }
+        let level_info = LevelData {
+            difficulty: self.difficulty.load(),
+            game_rules: self.game_rules.lock().await.clone(),
+            data_packs: self.data_packs.lock().await.clone(),
+            ..self.level.level_info.clone()
+        };
+        self.level
+            .save(level_info, self.live_entities().await)
+            .await;
+    }
+
+    /// Saves the chunks which changed together with the items in them. Items which moved into
+    /// chunks nobody watches are put into their chunk first, so the chunk can be unloaded
+    pub async fn flush_chunks(&self) {
+        let mut unwatched: Vec<_> = self
+            .item_entities
+            .lock()
+            .await
+            .values()
+            .map(|item_entity| item_entity.entity.chunk_pos.load())
+            .filter(|chunk| !self.level.is_chunk_watched(chunk))
+            .collect();
+        unwatched.sort_unstable_by_key(|chunk| (chunk.x, chunk.z));
+        unwatched.dedup();
+        self.unload_chunk_entities(&unwatched).await;
+        self.level
+            .flush_dirty_chunks(self.live_entities().await)
+            .await;
+    }
+
+    /// The NBT of the items in the world, keyed by the chunk they are in
+    async fn live_entities(&self) -> HashMap<Vector2<i32>, Vec<NbtCompound>> {
+        let item_entities: Vec<_> = self.item_entities.lock().await.values().cloned().collect();
+        let mut entities: HashMap<_, Vec<_>> = HashMap::new();
+        for item_entity in item_entities {
+            let mut nbt = NbtCompound::new();
+            item_entity.write_nbt(&mut nbt).await;
+            entities
+                .entry(item_entity.entity.chunk_pos.load())
+                .or_default()
+                .push(nbt);
+        }
+        entities
+    }
+
+    /// Adds the items saved in the chunks players were sent since the last tick to the world
+    async fn load_chunk_entities(self: &Arc<Self>, server: &Server) {
+        let chunks = std::mem::take(&mut *self.entity_chunks.lock().await);
+        for chunk in chunks {
+            for nbt in self.level.take_entities(&chunk, ITEM_ENTITY_ID).await {
+                let Some(item_entity) =
+                    ItemEntity::from_nbt(server.new_entity_id(), self.clone(), &nbt)
+                else {
+                    log::warn!("Dropped invalid item entity in chunk {chunk:?}");
+                    continue;
+                };
+                self.add_item_entity(Arc::new(item_entity)).await;
+            }
+        }
+    }
+
+    /// Puts the items in the chunks back into their chunk before the chunks are unloaded, they
+    /// are saved with it. Items in chunks which are not loaded stay in the world
+    pub async fn unload_chunk_entities(&self, chunks: &[Vector2<i32>]) {
+        if chunks.is_empty() {
+            return;
+        }
+        let item_entities: Vec<_> = self
+            .item_entities
+            .lock()
+            .await
+            .values()
+            .filter(|item_entity| chunks.contains(&item_entity.entity.chunk_pos.load()))
+            .cloned()
+            .collect();
+        for item_entity in item_entities {
+            let mut nbt = NbtCompound::new();
+            item_entity.write_nbt(&mut nbt).await;
+            let chunk = item_entity.entity.chunk_pos.load();
+            if self.level.store_entities(&chunk, vec![nbt]).await {
+                item_entity.discard().await;
+            }
+        }
     }
 
     /// Broadcasts a packet to all connected players within the world.
@@ -152,6 +365,22 @@ impl World {
         }
     }
 
+    /// Broadcasts a packet to the players within the given distance of the position.
+    ///
+    /// **Note:** This function acquires a lock on the `current_players` map, ensuring thread safety.
+    pub async fn broadcast_packet_near<P>(&self, position: &Vector3<f64>, distance: f64, packet: &P)
+    where
+        P: ClientPacket,
+    {
+        let current_players = self.current_players.lock().await;
+        for player in current_players.values() {
+            let pos = player.living_entity.entity.pos.load();
+            if pos.sub(position).length_squared() <= distance * distance {
+                player.client.send_packet(packet).await;
+            }
+        }
+    }
+
     pub async fn play_sound(
         &self,
         sound_id: u16,
@@ -193,21 +422,560 @@ impl World {
             .await;
     }
 
-    pub async fn tick(&self) {
+    /// Ticks the world, hoppers leave the blocks of the open containers alone
+    pub async fn tick(self: &Arc<Self>, server: &Server, open_containers: &HashSet<WorldPosition>) {
         // world ticks
+        let daylight_cycle = self
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::DoDaylightCycle);
         let mut level_time = self.level_time.lock().await;
-        level_time.tick_time();
+        level_time.tick_time(daylight_cycle);
         if level_time.world_age % 20 == 0 {
             level_time.send_time(self).await;
         }
-        // player ticks
-        let current_players = self.current_players.lock().await;
-        for player in current_players.values() {
-            player.tick().await;
+        drop(level_time);
+        // player ticks, players broadcast to the world while ticking
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            player.tick(server).await;
+        }
+        self.tick_weather(server).await;
+        self.tick_sleeping().await;
+        self.tick_difficulty().await;
+        self.tick_worldborder().await;
+        self.tick_portals(server).await;
+        self.load_chunk_entities(server).await;
+        self.tick_natural_spawning(server).await;
+        self.tick_item_entities().await;
+        self.tick_experience_orbs().await;
+        self.tick_projectiles(server).await;
+        self.tick_mobs(server).await;
+        self.tick_falling_blocks(server).await;
+        self.tick_primed_tnt(server).await;
+        self.tick_scheduled_blocks(server).await;
+        self.tick_random_blocks(server).await;
+        self.tick_block_entities(server, open_containers).await;
+        self.update_entity_tracking().await;
+        self.send_entity_data_changes().await;
+    }
+
+    /// Runs the block and fluid ticks which are due in the chunks near players. Block ticks only
+    /// run if the block was not replaced since the tick was scheduled
+    async fn tick_scheduled_blocks(self: &Arc<Self>, server: &Server) {
+        let (block_ticks, fluid_ticks) = self.level.tick_scheduled();
+        for tick in block_ticks {
+            let Ok(block) = self.get_block(tick.position).await else {
+                continue;
+            };
+            if tick.target != format!("minecraft:{}", block.name) {
+                continue;
+            }
+            server
+                .block_manager
+                .on_scheduled_tick(block, self, tick.position, server)
+                .await;
+        }
+        for tick in fluid_ticks {
+            server
+                .block_manager
+                .on_fluid_tick(&tick.target, self, tick.position, server)
+                .await;
+        }
+    }
+
+    /// Water evaporates and lava flows faster and further in ultra warm dimensions like the nether
+    pub fn is_ultra_warm(&self) -> bool {
+        matches!(self.dimension_type, DimensionType::TheNether)
+    }
+
+    /// Blocks in this world per block in the overworld, a block in the nether is eight in the
+    /// overworld
+    pub fn coordinate_scale(&self) -> f64 {
+        match self.dimension_type {
+            DimensionType::TheNether => 8.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Lets the fluid at the position flow, lava hardening next to water fizzes
+    pub async fn tick_fluid(
+        self: &Arc<Self>,
+        position: WorldPosition,
+        fluid: Fluid,
+        server: &Server,
+    ) {
+        let update = self.level.fluid_tick(position, fluid, self.is_ultra_warm());
+        for (position, state_id) in update.changes {
+            self.set_block_state(position, state_id).await;
+            self.update_neighbors(position, server).await;
+        }
+        for position in update.fizz {
+            self.broadcast_packet_all(&CWorldEvent::new(1501, &position, 0, false))
+                .await;
+        }
+    }
+
+    /// Schedules a tick for the block in `delay` ticks, unless one is already scheduled for it at
+    /// the position. Delays only count down while players are near the chunk
+    pub async fn schedule_block_tick(
+        &self,
+        position: WorldPosition,
+        block: &Block,
+        delay: u32,
+        priority: TickPriority,
+    ) {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        self.modify_chunk(chunk, |chunk| {
+            let target = format!("minecraft:{}", block.name);
+            if chunk
+                .block_ticks
+                .schedule(position, &target, delay, priority)
+            {
+                chunk.dirty = true;
+            }
+        })
+        .await;
+    }
+
+    /// Schedules a tick for the fluid, e.g. `minecraft:flowing_water`, in `delay` ticks, unless
+    /// one is already scheduled for it at the position
+    pub async fn schedule_fluid_tick(&self, position: WorldPosition, fluid: &str, delay: u32) {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        self.modify_chunk(chunk, |chunk| {
+            if chunk
+                .fluid_ticks
+                .schedule(position, fluid, delay, TickPriority::Normal)
+            {
+                chunk.dirty = true;
+            }
+        })
+        .await;
+    }
+
+    /// Grows crops, spreads grass and fire, melts ice and decays leaves in the chunks near players
+    async fn tick_random_blocks(self: &Arc<Self>, server: &Server) {
+        let speed = self
+            .game_rules
+            .lock()
+            .await
+            .get_int(IntRule::RandomTickSpeed);
+        let changes = self
+            .level
+            .random_tick(speed.max(0) as u32, self.is_ultra_warm());
+        for (position, state_id) in changes {
+            self.set_block_state(position, state_id).await;
+            self.update_neighbors(position, server).await;
+        }
+    }
+
+    /// Ticks furnaces, spawners, campfires and other ticking block entities near players
+    async fn tick_block_entities(
+        self: &Arc<Self>,
+        server: &Server,
+        open_containers: &HashSet<WorldPosition>,
+    ) {
+        // Spectators don't activate spawners
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| player.gamemode.load() != GameMode::Spectator)
+            .map(|player| player.living_entity.entity.pos.load())
+            .collect();
+        let results = self.level.tick_block_entities(
+            &players,
+            &FurnaceRecipes,
+            &BrewingStandRecipes,
+            open_containers,
+        );
+        for (position, result) in results {
+            if let Some(state_id) = result.state_id {
+                self.set_block_state(position, state_id).await;
+                self.update_neighbors(position, server).await;
+            }
+            if let Some(attempt) = result.spawn {
+                self.spawn_from_spawner(position, &attempt, server).await;
+            }
+            if let Some(data) = result.block_entity_update {
+                self.update_block_entity(position, data).await;
+            }
+            if !result.cooked.is_empty() {
+                self.drop_campfire_food(server, position, result.cooked)
+                    .await;
+            }
+            if result.collect_items {
+                self.collect_hopper_items(position).await;
+            }
+            if !result.dropped.is_empty() {
+                let center = Vector3::new(
+                    f64::from(position.0.x) + 0.5,
+                    f64::from(position.0.y) + 0.5,
+                    f64::from(position.0.z) + 0.5,
+                );
+                for item in result.dropped {
+                    self.drop_item_stack(server.new_entity_id(), center, item)
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Puts the items lying on the hopper at the position into it, like vanilla's
+    /// `HopperBlockEntity.suckInItems`. Hoppers don't wait for the pickup delay
+    async fn collect_hopper_items(&self, position: WorldPosition) {
+        let item_entities: Vec<_> = self.item_entities.lock().await.values().cloned().collect();
+        for item_entity in item_entities {
+            let pos = item_entity.entity.pos.load();
+            let (x, y, z) = (
+                f64::from(position.0.x),
+                f64::from(position.0.y),
+                f64::from(position.0.z),
+            );
+            // The hopper bowl and the block above it
+            if item_entity.is_removed()
+                || !(x..=x + 1.0).contains(&pos.x)
+                || !(y + 11.0 / 16.0..=y + 2.0).contains(&pos.y)
+                || !(z..=z + 1.0).contains(&pos.z)
+            {
+                continue;
+            }
+            let mut item_stack = item_entity.item_stack.lock().await;
+            let collected = self
+                .level
+                .update_block_entity(position, |block_entity| {
+                    block_entity
+                        .as_any_mut()
+                        .downcast_mut::<HopperBlockEntity>()
+                        .is_some_and(|hopper| hopper.collect(&mut item_stack))
+                })
+                .await;
+            let all_collected = item_stack.item_count == 0;
+            drop(item_stack);
+            if all_collected {
+                item_entity.discard().await;
+            } else if collected {
+                item_entity.update_item_stack().await;
+            }
+            if !all_collected {
+                // The hopper is full
+                return;
+            }
+        }
+    }
+
+    /// Shows players the changed data of the block entity at the position
+    pub async fn update_block_entity(&self, position: WorldPosition, data: NbtCompound) {
+        let Ok(state_id) = self.get_block_state_id(position).await else {
+            return;
+        };
+        let Some(block_entity_type) =
+            get_block_entity_type(state_id).and_then(|id| block_entity_type_id(&id))
+        else {
+            return;
+        };
+        self.broadcast_packet_all(&CBlockEntityData::new(
+            &position,
+            VarInt(block_entity_type),
+            data,
+        ))
+        .await;
+    }
+
+    /// Lets falling blocks fall, they turn back into blocks when they land
+    async fn tick_falling_blocks(&self, server: &Server) {
+        // Landing changes blocks, which may start more blocks falling
+        let falling_blocks: Vec<_> = self.falling_blocks.lock().await.values().cloned().collect();
+        for falling_block in falling_blocks {
+            falling_block.tick(server).await;
+        }
+    }
+
+    /// Lets mobs fall and be pushed around, mobs which fell out of the world or whose death
+    /// animation is over are removed
+    async fn tick_mobs(self: &Arc<Self>, server: &Server) {
+        let mobs: Vec<_> = self.mobs.lock().await.values().cloned().collect();
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let daylight =
+            !self.level_time.lock().await.is_night() && !self.weather.lock().await.is_raining();
+        let surroundings = Surroundings {
+            server,
+            players: &players,
+            mobs: &mobs,
+            daylight,
+        };
+        for mob in &mobs {
+            if mob.tick_timers() {
+                self.remove_dead_mob(server, mob).await;
+                continue;
+            }
+            if mob.living_entity.health.load() <= 0.0 {
+                if mob.just_died() {
+                    self.award_mob_kill(server, mob).await;
+                    self.drop_mob_loot(server, mob).await;
+                }
+                continue;
+            }
+            mob.tick_ai(&surroundings).await;
+            let entity = mob.entity();
+            if let Some(movement) = entity.tick_movement().await {
+                mob.controls.moved(&movement);
+            }
+            if is_below_world(entity.pos.load().y) {
+                self.remove_mob(mob).await;
+            }
+        }
+    }
+
+    /// Counts the mob for the `totalKillCount` objectives and the statistics of the player who
+    /// killed it
+    async fn award_mob_kill(&self, server: &Server, mob: &MobEntity) {
+        let Some(attacker) = mob.last_attacker().filter(|_| mob.killed_by_player()) else {
+            return;
+        };
+        if let Some(killer) = self.get_player_by_entityid(attacker).await {
+            server
+                .scoreboard
+                .lock()
+                .await
+                .add_to_criterion(
+                    server,
+                    &killer.gameprofile.name,
+                    ObjectiveCriterion::TotalKillCount,
+                    1,
+                )
+                .await;
+            let entity_type = &mob.entity().entity_type;
+            killer.award_stat(Stat::custom("mob_kills"), 1).await;
+            killer
+                .award_stat(Stat::entity(StatType::Killed, entity_type), 1)
+                .await;
+            killer.trigger_killed_entity(entity_type.name()).await;
+        }
+    }
+
+    /// Drops the loot of a mob which just died, like vanilla's `dropAllDeathLoot`. Mobs players
+    /// killed may drop their equipment too, babies drop nothing
+    async fn drop_mob_loot(self: &Arc<Self>, server: &Server, mob: &MobEntity) {
+        if mob.age() < 0 || !self.game_rules.lock().await.get_bool(BoolRule::DoMobLoot) {
+            return;
+        }
+        let killed_by_player = mob.killed_by_player();
+        let killer = match mob.last_attacker() {
+            Some(attacker) if killed_by_player => self.get_player_by_entityid(attacker).await,
+            _ => None,
+        };
+        let weapon = match &killer {
+            Some(killer) => killer.inventory().lock().await.held_item().cloned(),
+            None => None,
+        };
+        let entity = mob.entity();
+        let context = LootContext {
+            killer_weapon: weapon.as_ref(),
+            killed_by_player,
+            on_fire: mob.living_entity.is_on_fire(),
+            ..Default::default()
+        };
+        let mut drops = entity_drops(entity.entity_type.name(), &context, &mut thread_rng());
+        if killed_by_player {
+            let looting = weapon
+                .as_ref()
+                .map_or(0, |weapon| weapon.enchantment_level("looting"));
+            drops.extend(mob.take_dropped_equipment(looting).await);
+        }
+        let position = entity.pos.load();
+        for drop in drops {
+            self.drop_item_stack(server.new_entity_id(), position, drop)
+                .await;
         }
     }
 
-    /// Gets the y position of the first non air block from the top down
+    /// Removes a mob whose death animation is over, mobs players killed drop experience
+    async fn remove_dead_mob(self: &Arc<Self>, server: &Server, mob: &MobEntity) {
+        let points = mob.experience_reward().await;
+        if points > 0 && self.game_rules.lock().await.get_bool(BoolRule::DoMobLoot) {
+            self.award_experience(server, mob.entity().pos.load(), points)
+                .await;
+        }
+        self.remove_mob(mob).await;
+    }
+
+    /// Counts down the fuses of lit TNT, which explodes once its fuse ran out
+    async fn tick_primed_tnt(&self, server: &Server) {
+        // Explosions light more TNT, which locks the primed TNT
+        let primed_tnt: Vec<_> = self.primed_tnt.lock().await.values().cloned().collect();
+        for tnt in primed_tnt {
+            tnt.tick(server).await;
+        }
+    }
+
+    /// Tells players about the metadata of the entities which changed during the tick
+    async fn send_entity_data_changes(&self) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            player.living_entity.entity.send_data_changes().await;
+        }
+        // Sending only locks the players, so the other entities don't have to be copied
+        for item_entity in self.item_entities.lock().await.values() {
+            item_entity.entity.send_data_changes().await;
+        }
+        for horse_entity in self.horse_entities.lock().await.values() {
+            horse_entity.entity().send_data_changes().await;
+        }
+        for item_frame in self.item_frames.lock().await.values() {
+            item_frame.entity.send_data_changes().await;
+        }
+        for armor_stand in self.armor_stands.lock().await.values() {
+            armor_stand.entity.send_data_changes().await;
+        }
+        for tnt in self.primed_tnt.lock().await.values() {
+            tnt.entity.send_data_changes().await;
+        }
+        for projectile in self.projectiles.lock().await.values() {
+            projectile.entity.send_data_changes().await;
+        }
+        for mob in self.mobs.lock().await.values() {
+            mob.entity().send_data_changes().await;
+        }
+    }
+
+    /// Moves dropped items, merges the ones lying close together and lets players pick up the
+    /// ones they touch
+    async fn tick_item_entities(&self) {
+        // Picking up items broadcasts packets, which locks the players
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let item_entities: Vec<_> = self.item_entities.lock().await.values().cloned().collect();
+        for item_entity in &item_entities {
+            if !item_entity.tick().await {
+                continue;
+            }
+            if item_entity.should_merge() {
+                Self::merge_item_entity(item_entity, &item_entities).await;
+            }
+            if item_entity.is_removed() || !item_entity.can_be_picked_up() {
+                continue;
+            }
+            let item_box = item_entity.entity.bounding_box.load();
+            for player in &players {
+                if pickup_box(player).intersects(&item_box)
+                    && player.pickup_item_entity(item_entity).await
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Moves experience orbs towards players, players touching an orb pick it up
+    async fn tick_experience_orbs(&self) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let orbs: Vec<_> = self
+            .experience_orbs
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for orb in orbs {
+            if !orb.tick(&players).await {
+                continue;
+            }
+            let orb_box = orb.entity.bounding_box.load();
+            for player in &players {
+                if pickup_box(player).intersects(&orb_box)
+                    && player.pickup_experience_orb(&orb).await
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Flies arrows, tridents and thrown items, players touching an arrow or trident stuck in the
+    /// ground pick it up
+    async fn tick_projectiles(&self, server: &Server) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let mobs: Vec<_> = self.mobs.lock().await.values().cloned().collect();
+        let projectiles: Vec<_> = self.projectiles.lock().await.values().cloned().collect();
+        for projectile in projectiles {
+            if !projectile.tick(server, &players, &mobs).await || !projectile.can_be_picked_up() {
+                continue;
+            }
+            let projectile_box = projectile.entity.bounding_box.load();
+            for player in &players {
+                if pickup_box(player).intersects(&projectile_box)
+                    && player.pickup_projectile(&projectile).await
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Merges the items with the items of the same kind lying next to them
+    async fn merge_item_entity(item_entity: &ItemEntity, item_entities: &[Arc<ItemEntity>]) {
+        let mut merge_box = item_entity.entity.bounding_box.load();
+        merge_box.min_x -= 0.5;
+        merge_box.min_z -= 0.5;
+        merge_box.max_x += 0.5;
+        merge_box.max_z += 0.5;
+        for other in item_entities {
+            if item_entity.is_removed() {
+                return;
+            }
+            if other.entity.entity_id != item_entity.entity.entity_id
+                && merge_box.intersects(&other.entity.bounding_box.load())
+            {
+                item_entity.try_merge(other).await;
+            }
+        }
+    }
+
+    /// Where players spawn, on top of the highest block at the spawn of the world
+    pub async fn spawn_position(&self) -> Vector3<f64> {
+        let (x, z) = (self.level.level_info.spawn_x, self.level.level_info.spawn_z);
+        let top = self.get_top_block(Vector2::new(x, z)).await;
+        Vector3::new(f64::from(x) + 0.5, f64::from(top + 1), f64::from(z) + 0.5)
+    }
+
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
         for y in (-64..=319).rev() {
             let pos = WorldPosition(Vector3::new(position.x, y, position.z));
@@ -222,15 +990,19 @@ impl World {
         319
     }
 
-    #[expect(clippy::too_many_lines)]
     pub async fn spawn_player(
         &self,
         base_config: &BasicConfiguration,
         player: Arc<Player>,
         server: &Server,
     ) {
-        let dimensions: Vec<Identifier> =
-            server.dimensions.iter().map(DimensionType::name).collect();
+        let dimensions: Vec<Identifier> = server
+            .worlds
+            .iter()
+            .map(|world| world.key.clone())
+            .collect();
+
+        player.load_data().await;
 
         // This code follows the vanilla packet order
         let entity_id = player.entity_id();
@@ -241,6 +1013,7 @@ impl World {
             entity_id
         );
 
+        let game_rules = self.game_rules.lock().await.clone();
         // login packet for our new player
         player
             .client
@@ -251,11 +1024,11 @@ impl World {
                 base_config.max_players.into(),
                 base_config.view_distance.get().into(), //  TODO: view distance
                 base_config.simulation_distance.get().into(), // TODO: sim view dinstance
-                false,
-                true,
-                false,
+                game_rules.get_bool(BoolRule::ReducedDebugInfo),
+                !game_rules.get_bool(BoolRule::DoImmediateRespawn),
+                game_rules.get_bool(BoolRule::DoLimitedCrafting),
                 (self.dimension_type as u8).into(),
-                self.dimension_type.name(),
+                self.key.clone(),
                 0, // seed
                 gamemode as u8,
                 base_config.default_gamemode as i8,
@@ -267,24 +1040,69 @@ impl World {
                 false,
             ))
             .await;
+        self.send_difficulty(&player.client).await;
+        player.send_experience().await;
+        player.send_food().await;
         // permissions, i. e. the commands a player may use
         player.send_permission_lvl_update().await;
         client_cmd_suggestions::send_c_commands_packet(&player, &server.command_dispatcher).await;
+        player.send_recipes().await;
+        player.init_recipe_book().await;
+        player.init_advancements().await;
         // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
+        let position = self.spawn_position().await;
         let yaw = 10.0;
         let pitch = 10.0;
 
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
-
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
 
         player.living_entity.last_pos.store(position);
 
+        self.introduce_player(&player).await;
+
+        // Start waiting for level chunks, Sets the "Loading Terrain" screen
+        log::debug!("Sending waiting chunks to {}", player.gameprofile.name);
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
+            .await;
+
+        self.worldborder
+            .lock()
+            .await
+            .init_client(&player.client)
+            .await;
+        self.weather.lock().await.init_client(&player.client).await;
+        server
+            .scoreboard
+            .lock()
+            .await
+            .init_client(&player.client)
+            .await;
+
+        // Sends initial time
+        player.send_time(self).await;
+
+        // Spawn in initial chunks
+        player_chunker::player_join(&player).await;
+
+        // Custom bossbars remember their players by UUID, so they see them again after rejoining
+        if let Some(bossbars) = server
+            .bossbars
+            .lock()
+            .await
+            .get_player_bars(&player.gameprofile.id)
+        {
+            for bossbar in bossbars {
+                player.send_bossbar(bossbar).await;
+            }
+        }
+    }
+
+    /// Shows the player to the players of the world and the players and other entities of the
+    /// world to the player, when it joins the world or travels into it
+    async fn introduce_player(&self, player: &Arc<Player>) {
         let gameprofile = &player.gameprofile;
         // first send info update to our new player, So he can see his Skin
         // also send his info to everyone else
@@ -332,204 +1150,228 @@ impl World {
                 .await;
         };
 
-        let gameprofile = &player.gameprofile;
-
-        log::debug!("Broadcasting player spawn for {}", player.gameprofile.name);
-        // spawn player for every client
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            // TODO: add velo
-            &CSpawnEntity::new(
-                entity_id.into(),
-                gameprofile.id,
-                (EntityType::Player as i32).into(),
-                position.x,
-                position.y,
-                position.z,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                0.0,
-                0.0,
-                0.0,
-            ),
-        )
-        .await;
-        // spawn players for our client
-        let id = player.gameprofile.id;
-        for (_, existing_player) in self
-            .current_players
-            .lock()
-            .await
-            .iter()
-            .filter(|c| c.0 != &id)
-        {
-            let entity = &existing_player.living_entity.entity;
-            let pos = entity.pos.load();
-            let gameprofile = &existing_player.gameprofile;
-            log::debug!("Sending player entities to {}", player.gameprofile.name);
-            player
-                .client
-                .send_packet(&CSpawnEntity::new(
-                    existing_player.entity_id().into(),
-                    gameprofile.id,
-                    (EntityType::Player as i32).into(),
-                    pos.x,
-                    pos.y,
-                    pos.z,
-                    entity.yaw.load(),
-                    entity.pitch.load(),
-                    entity.head_yaw.load(),
-                    0.into(),
-                    0.0,
-                    0.0,
-                    0.0,
-                ))
-                .await;
+        // spawn the player for the players close by and the entities close by for the player
+        self.start_tracking(player.as_ref()).await;
+        self.track_entities_for(player).await;
+        // entity meta data, like the skin parts
+        if let Some(packet) = player.living_entity.entity.data_packet() {
+            player.client.send_packet(&packet).await;
         }
-        // entity meta data
-        // set skin parts
-        if let Some(config) = player.client.config.lock().await.as_ref() {
-            let packet = CSetEntityMetadata::new(
-                entity_id.into(),
-                Metadata::new(17, VarInt(0), config.skin_parts),
-            );
-            log::debug!("Broadcasting skin for {}", player.gameprofile.name);
-            self.broadcast_packet_all(&packet).await;
+    }
+
+    /// Brings the dead player back at the position in this world, like vanilla's
+    /// `PlayerList.respawn`. Players who died in another world move into this one
+    pub async fn respawn_player(
+        self: &Arc<Self>,
+        player: &Arc<Player>,
+        position: Vector3<f64>,
+        yaw: f32,
+    ) {
+        player.wake_up().await;
+        let old_world = player.world();
+        let last_pos = player.living_entity.last_pos.load();
+        let death_dimension = old_world.key.clone();
+        let death_location = WorldPosition(Vector3::new(
+            last_pos.x.round() as i32,
+            last_pos.y.round() as i32,
+            last_pos.z.round() as i32,
+        ));
+        let changes_world = !Arc::ptr_eq(&old_world, self);
+        if changes_world {
+            old_world.remove_traveling_player(player).await;
+            player.unwatch_chunks(&old_world).await;
+            player.living_entity.entity.set_world(self.clone());
+            self.current_players
+                .lock()
+                .await
+                .insert(player.gameprofile.id, player.clone());
         }
 
-        // Start waiting for level chunks, Sets the "Loading Terrain" screen
-        log::debug!("Sending waiting chunks to {}", player.gameprofile.name);
         player
             .client
-            .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
+            .send_packet(&CRespawn::new(
+                (self.dimension_type as u8).into(),
+                self.key.clone(),
+                0, // seed
+                player.gamemode.load() as u8,
+                player.gamemode.load() as i8,
+                false,
+                false,
+                Some((death_dimension, death_location)),
+                0.into(),
+                0.into(),
+                0,
+            ))
             .await;
 
+        log::debug!("Sending player abilities to {}", player.gameprofile.name);
+        player.send_abilities_update().await;
+
+        player.send_permission_lvl_update().await;
+
+        let pitch = 0.0;
+        log::debug!("Sending player teleport to {}", player.gameprofile.name);
+        player.request_teleport(position, yaw, pitch).await;
+
+        player.living_entity.last_pos.store(position);
+        player.reset_after_death().await;
+
         self.worldborder
             .lock()
             .await
             .init_client(&player.client)
             .await;
-
-        // Sends initial time
+        self.weather.lock().await.init_client(&player.client).await;
+        self.send_difficulty(&player.client).await;
+        player.send_experience().await;
         player.send_time(self).await;
 
-        // Spawn in initial chunks
-        player_chunker::player_join(&player).await;
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
+            .await;
 
-        // if let Some(bossbars) = self..lock().await.get_player_bars(&player.gameprofile.id) {
-        //     for bossbar in bossbars {
-        //         player.send_bossbar(bossbar).await;
-        //     }
-        // }
+        if changes_world {
+            self.introduce_player(player).await;
+        } else {
+            // The client forgot every entity with the respawn, the other players see the player
+            // come back
+            self.forget_tracked_entities(player).await;
+            let entity = &player.living_entity.entity;
+            self.stop_tracking(entity).await;
+            self.start_tracking(player.as_ref()).await;
+            self.track_entities_for(player).await;
+            if let Some(packet) = entity.data_packet() {
+                player.client.send_packet(&packet).await;
+            }
+        }
+        player_chunker::player_join(player).await;
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
-        let last_pos = player.living_entity.last_pos.load();
-        let death_dimension = player.world().dimension_type.name();
-        let death_location = WorldPosition(Vector3::new(
-            last_pos.x.round() as i32,
-            last_pos.y.round() as i32,
-            last_pos.z.round() as i32,
-        ));
-
-        let data_kept = u8::from(alive);
+    /// Moves the player from the world it is in into this world, like vanilla's
+    /// `ServerPlayer.teleportTo` into another level. The player keeps its inventory, health and
+    /// other data, players in the same world are just teleported
+    pub async fn transfer_player(
+        self: &Arc<Self>,
+        player: &Arc<Player>,
+        position: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+    ) {
+        let old_world = player.world();
+        if Arc::ptr_eq(&old_world, self) {
+            player.request_teleport(position, yaw, pitch).await;
+            return;
+        }
 
-        // TODO: switch world in player entity to new world
+        old_world.remove_traveling_player(player).await;
+        player.unwatch_chunks(&old_world).await;
+        player.living_entity.entity.set_world(self.clone());
+        self.current_players
+            .lock()
+            .await
+            .insert(player.gameprofile.id, player.clone());
 
+        // Players keep their attributes and entity data when they change the dimension
         player
             .client
             .send_packet(&CRespawn::new(
                 (self.dimension_type as u8).into(),
-                self.dimension_type.name(),
+                self.key.clone(),
                 0, // seed
                 player.gamemode.load() as u8,
                 player.gamemode.load() as i8,
                 false,
                 false,
-                Some((death_dimension, death_location)),
+                None,
                 0.into(),
                 0.into(),
-                data_kept,
+                0x01 | 0x02,
             ))
             .await;
-
-        log::debug!("Sending player abilities to {}", player.gameprofile.name);
         player.send_abilities_update().await;
-
         player.send_permission_lvl_update().await;
 
-        // teleport
-        let mut position = Vector3::new(10.0, 120.0, 10.0);
-        let yaw = 10.0;
-        let pitch = 10.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
-
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
-
         player.living_entity.last_pos.store(position);
 
-        // TODO: difficulty, exp bar, status effect
-
+        self.introduce_player(player).await;
+        player
+            .client
+            .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
+            .await;
         self.worldborder
             .lock()
             .await
             .init_client(&player.client)
             .await;
+        self.weather.lock().await.init_client(&player.client).await;
+        self.send_difficulty(&player.client).await;
+        player.send_experience().await;
+        player.send_food().await;
+        player.send_time(self).await;
+        player_chunker::player_join(player).await;
+    }
 
-        // TODO: world spawn (compass stuff)
-
-        player
-            .client
-            .send_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
-            .await;
-
-        let entity = &player.living_entity.entity;
-        let entity_id = entity.entity_id;
-
-        let skin_parts = player.config.lock().await.skin_parts;
-        let entity_metadata_packet =
-            CSetEntityMetadata::new(entity_id.into(), Metadata::new(17, VarInt(0), &skin_parts));
+    /// Teleports the entity to the position in this world, like vanilla's `Entity.teleportTo`.
+    /// Players, mobs and items in other worlds move into this one, other entities can't change
+    /// their world. Returns whether the entity was teleported
+    pub async fn teleport_entity(
+        self: &Arc<Self>,
+        target: &AnyEntity,
+        position: Vector3<f64>,
+        yaw: f32,
+        pitch: f32,
+    ) -> bool {
+        let position = self.worldborder.lock().await.clamp(position);
+        let entity = target.entity();
+        let old_world = entity.world();
+        match target {
+            AnyEntity::Player(player) => {
+                self.transfer_player(player, position, yaw, pitch).await;
+                return true;
+            }
+            _ if Arc::ptr_eq(&old_world, self) => {
+                entity.teleport(position, yaw, pitch).await;
+                return true;
+            }
+            AnyEntity::Mob(mob) => old_world.remove_mob(mob).await,
+            AnyEntity::Item(item_entity) => old_world.remove_item_entity(item_entity).await,
+            AnyEntity::Other(_) => return false,
+        }
+        entity.set_world(self.clone());
+        entity.set_pos(position);
+        entity.set_rotation(yaw, pitch);
+        match target {
+            AnyEntity::Mob(mob) => self.add_mob(mob.clone()).await,
+            AnyEntity::Item(item_entity) => self.add_item_entity(item_entity.clone()).await,
+            AnyEntity::Player(_) | AnyEntity::Other(_) => {}
+        }
+        true
+    }
 
+    /// Removes a player traveling into another world, unlike `remove_player` nobody is told the
+    /// player left the game
+    async fn remove_traveling_player(&self, player: &Player) {
+        self.current_players
+            .lock()
+            .await
+            .remove(&player.gameprofile.id);
         self.broadcast_packet_except(
             &[player.gameprofile.id],
-            // TODO: add velo
-            &CSpawnEntity::new(
-                entity.entity_id.into(),
-                player.gameprofile.id,
-                (EntityType::Player as i32).into(),
-                position.x,
-                position.y,
-                position.z,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                0.0,
-                0.0,
-                0.0,
-            ),
+            &CRemovePlayerInfo::new(1.into(), &[player.gameprofile.id]),
         )
         .await;
-
-        player_chunker::player_join(player).await;
-        self.broadcast_packet_all(&entity_metadata_packet).await;
-        // update commands
-
-        player.set_health(20.0, 20, 20.0).await;
+        self.remove_entity(&player.living_entity.entity).await;
+        self.forget_tracked_entities(player).await;
     }
 
     /// IMPORTANT: Chunks have to be non-empty
     fn spawn_world_chunks(
-        &self,
+        self: &Arc<Self>,
         player: Arc<Player>,
-        chunks: Vec<Vector2<i32>>,
+        chunks: &[Vector2<i32>],
         center_chunk: Vector2<i32>,
     ) {
         if player
@@ -543,18 +1385,14 @@ impl World {
         #[cfg(debug_assertions)]
         let inst = std::time::Instant::now();
 
-        // Sort such that the first chunks are closest to the center
-        let mut chunks = chunks;
-        chunks.sort_unstable_by_key(|pos| {
-            let rel_x = pos.x - center_chunk.x;
-            let rel_z = pos.z - center_chunk.z;
-            rel_x * rel_x + rel_z * rel_z
-        });
-
-        let mut receiver = self.receive_chunks(chunks);
-        let level = self.level.clone();
+        // The chunks closest to the center are sent first
+        let (sender, mut receiver) = mpsc::channel(chunks.len());
+        self.level
+            .fetch_watched_chunks(chunks, center_chunk, sender, &Handle::current());
+        let world = self.clone();
 
         tokio::spawn(async move {
+            let level = &world.level;
             while let Some(chunk_data) = receiver.recv().await {
                 let chunk_data = chunk_data.read().await;
                 let packet = CChunkData(&chunk_data);
@@ -587,6 +1425,7 @@ impl World {
                 {
                     player.client.send_packet(&packet).await;
                 }
+                world.entity_chunks.lock().await.push(chunk_data.position);
             }
 
             #[cfg(debug_assertions)]
@@ -747,6 +1586,7 @@ impl World {
         )
         .await;
         self.remove_entity(&player.living_entity.entity).await;
+        self.forget_tracked_entities(player).await;
 
         // Send disconnect message / quit message to players in the same world
         // TODO: Config
@@ -759,50 +1599,500 @@ impl World {
         log::info!("{}", disconn_msg_cmp.to_pretty_console());
     }
 
-    pub async fn remove_entity(&self, entity: &Entity) {
-        self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
+    /// Adds the item entity to the world and spawns it for every player
+    pub async fn add_item_entity(&self, item_entity: Arc<ItemEntity>) {
+        item_entity.broadcast_spawn().await;
+        self.item_entities
+            .lock()
+            .await
+            .insert(item_entity.entity.entity_id, item_entity);
+    }
+
+    /// Spawns orbs worth the experience points at the position, large amounts are split into
+    /// several orbs like vanilla's `ExperienceOrb.award`
+    pub async fn award_experience(
+        self: &Arc<Self>,
+        server: &Server,
+        position: Vector3<f64>,
+        mut points: i32,
+    ) {
+        while points > 0 {
+            let value = orb_value(points);
+            points -= value;
+            let velocity = Vector3::new(
+                rand::random::<f64>().mul_add(0.4, -0.2),
+                rand::random::<f64>() * 0.4,
+                rand::random::<f64>().mul_add(0.4, -0.2),
+            );
+            let orb = Arc::new(ExperienceOrbEntity::new(
+                server.new_entity_id(),
+                self.clone(),
+                value,
+            ));
+            orb.entity.set_pos(position);
+            orb.entity.velocity.store(velocity);
+            orb.broadcast_spawn().await;
+            self.experience_orbs
+                .lock()
+                .await
+                .insert(orb.entity.entity_id, orb);
+        }
+    }
+
+    /// Spawns experience orbs worth the points in the middle of a block, like vanilla's
+    /// `Block.popExperience`. Nothing drops when the `doTileDrops` game rule is off
+    pub async fn pop_experience(
+        self: &Arc<Self>,
+        server: &Server,
+        position: WorldPosition,
+        points: i32,
+    ) {
+        if !self.game_rules.lock().await.get_bool(BoolRule::DoTileDrops) {
+            return;
+        }
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        self.award_experience(server, center, points).await;
+    }
+
+    /// Removes the experience orb from the world and despawns it for every player
+    pub async fn remove_experience_orb(&self, orb: &ExperienceOrbEntity) {
+        self.experience_orbs
+            .lock()
+            .await
+            .remove(&orb.entity.entity_id);
+        self.remove_entity(&orb.entity).await;
+    }
+
+    /// Drops the items at the position with a small random motion, like items popping out of an entity
+    pub async fn drop_item_stack(
+        self: &Arc<Self>,
+        entity_id: EntityId,
+        position: Vector3<f64>,
+        item_stack: ItemStack,
+    ) {
+        let velocity = Vector3::new(
+            rand::random::<f64>().mul_add(0.2, -0.1),
+            0.2,
+            rand::random::<f64>().mul_add(0.2, -0.1),
+        );
+        let item_entity = Arc::new(ItemEntity::new(entity_id, self.clone(), item_stack));
+        item_entity.set_motion(position, velocity);
+        self.add_item_entity(item_entity).await;
+    }
+
+    /// Drops the items of a block at a random spot in it, like vanilla's `Block.popResource`.
+    /// Nothing drops when the `doTileDrops` game rule is off
+    pub async fn pop_resource(
+        self: &Arc<Self>,
+        server: &Server,
+        position: WorldPosition,
+        item_stack: ItemStack,
+    ) {
+        if !self.game_rules.lock().await.get_bool(BoolRule::DoTileDrops) {
+            return;
+        }
+        // Items are 0.25 blocks high, half of it keeps them centered
+        let position = {
+            let mut rng = thread_rng();
+            Vector3::new(
+                f64::from(position.0.x) + 0.5 + rng.gen_range(-0.25..0.25),
+                f64::from(position.0.y) + 0.5 + rng.gen_range(-0.25..0.25) - 0.125,
+                f64::from(position.0.z) + 0.5 + rng.gen_range(-0.25..0.25),
+            )
+        };
+        self.drop_item_stack(server.new_entity_id(), position, item_stack)
+            .await;
+    }
+
+    /// Adds the horse to the world and spawns it for every player
+    pub async fn add_horse_entity(&self, horse_entity: Arc<HorseEntity>) {
+        horse_entity.broadcast_spawn().await;
+        self.horse_entities
+            .lock()
+            .await
+            .insert(horse_entity.entity().entity_id, horse_entity);
+    }
+
+    pub async fn get_horse_entity(&self, entity_id: EntityId) -> Option<Arc<HorseEntity>> {
+        self.horse_entities.lock().await.get(&entity_id).cloned()
+    }
+
+    /// Adds the item frame to the world and spawns it for every player
+    pub async fn add_item_frame(&self, item_frame: Arc<ItemFrameEntity>) {
+        item_frame.broadcast_spawn().await;
+        self.item_frames
+            .lock()
+            .await
+            .insert(item_frame.entity.entity_id, item_frame);
+    }
+
+    pub async fn get_item_frame(&self, entity_id: EntityId) -> Option<Arc<ItemFrameEntity>> {
+        self.item_frames.lock().await.get(&entity_id).cloned()
+    }
+
+    /// Removes the item frame from the world and despawns it for every player
+    pub async fn remove_item_frame(&self, item_frame: &ItemFrameEntity) {
+        self.item_frames
+            .lock()
+            .await
+            .remove(&item_frame.entity.entity_id);
+        self.remove_entity(&item_frame.entity).await;
+    }
+
+    /// Adds the armor stand to the world and spawns it for every player
+    pub async fn add_armor_stand(&self, armor_stand: Arc<ArmorStandEntity>) {
+        armor_stand.broadcast_spawn().await;
+        self.armor_stands
+            .lock()
+            .await
+            .insert(armor_stand.entity.entity_id, armor_stand);
+    }
+
+    pub async fn get_armor_stand(&self, entity_id: EntityId) -> Option<Arc<ArmorStandEntity>> {
+        self.armor_stands.lock().await.get(&entity_id).cloned()
+    }
+
+    /// Removes the armor stand from the world and despawns it for every player
+    pub async fn remove_armor_stand(&self, armor_stand: &ArmorStandEntity) {
+        self.armor_stands
+            .lock()
+            .await
+            .remove(&armor_stand.entity.entity_id);
+        self.remove_entity(&armor_stand.entity).await;
+    }
+
+    /// Turns the block at the position into a falling block entity
+    pub async fn spawn_falling_block(
+        self: &Arc<Self>,
+        location: WorldPosition,
+        block_state_id: u16,
+        server: &Server,
+    ) {
+        let falling_block = Arc::new(FallingBlockEntity::new(
+            server.new_entity_id(),
+            self.clone(),
+            location,
+            block_state_id,
+        ));
+        falling_block.broadcast_spawn().await;
+        self.falling_blocks
+            .lock()
+            .await
+            .insert(falling_block.entity.entity_id, falling_block);
+        self.set_block_state(location, 0).await;
+        self.update_neighbors(location, server).await;
+    }
+
+    /// Removes the falling block from the world and despawns it for every player
+    pub async fn remove_falling_block(&self, falling_block: &FallingBlockEntity) {
+        self.falling_blocks
+            .lock()
+            .await
+            .remove(&falling_block.entity.entity_id);
+        self.remove_entity(&falling_block.entity).await;
+    }
+
+    /// Replaces the TNT block at the position with lit TNT, which explodes after the fuse
+    pub async fn spawn_primed_tnt(
+        self: &Arc<Self>,
+        location: WorldPosition,
+        fuse: u32,
+        server: &Server,
+    ) {
+        let tnt = Arc::new(TntEntity::new(
+            server.new_entity_id(),
+            self.clone(),
+            location,
+            fuse,
+        ));
+        tnt.broadcast_spawn().await;
+        self.primed_tnt
+            .lock()
+            .await
+            .insert(tnt.entity.entity_id, tnt);
+        self.set_block_state(location, 0).await;
+        self.update_neighbors(location, server).await;
+        self.play_block_sound(sound!("entity.tnt.primed"), location)
             .await;
     }
 
+    /// Removes the lit TNT from the world and despawns it for every player
+    pub async fn remove_primed_tnt(&self, tnt: &TntEntity) {
+        self.primed_tnt.lock().await.remove(&tnt.entity.entity_id);
+        self.remove_entity(&tnt.entity).await;
+    }
+
+    /// Adds the arrow, trident or thrown item to the world and spawns it for every player
+    pub async fn add_projectile(&self, projectile: Arc<ProjectileEntity>) {
+        projectile.broadcast_spawn().await;
+        self.projectiles
+            .lock()
+            .await
+            .insert(projectile.entity.entity_id, projectile);
+    }
+
+    /// Removes the projectile from the world and despawns it for every player
+    pub async fn remove_projectile(&self, projectile: &ProjectileEntity) {
+        self.projectiles
+            .lock()
+            .await
+            .remove(&projectile.entity.entity_id);
+        self.remove_entity(&projectile.entity).await;
+    }
+
+    /// Adds the mob to the world and spawns it for every player
+    pub async fn add_mob(&self, mob: Arc<MobEntity>) {
+        mob.broadcast_spawn().await;
+        self.mobs.lock().await.insert(mob.entity().entity_id, mob);
+    }
+
+    pub async fn get_mob(&self, entity_id: EntityId) -> Option<Arc<MobEntity>> {
+        self.mobs.lock().await.get(&entity_id).cloned()
+    }
+
+    /// Removes the mob from the world and despawns it for every player
+    pub async fn remove_mob(&self, mob: &MobEntity) {
+        self.mobs.lock().await.remove(&mob.entity().entity_id);
+        self.remove_entity(mob.entity()).await;
+    }
+
+    /// Removes the item entity from the world and despawns it for every player
+    pub async fn remove_item_entity(&self, item_entity: &ItemEntity) {
+        self.item_entities
+            .lock()
+            .await
+            .remove(&item_entity.entity.entity_id);
+        self.remove_entity(&item_entity.entity).await;
+    }
+
+    /// Removes the entity from the world whichever kind it is, like vanilla's `Entity.discard`.
+    /// Players can't be discarded
+    pub async fn discard_entity(&self, entity: &Entity) {
+        let entity_id = entity.entity_id;
+        self.item_entities.lock().await.remove(&entity_id);
+        self.experience_orbs.lock().await.remove(&entity_id);
+        self.horse_entities.lock().await.remove(&entity_id);
+        self.item_frames.lock().await.remove(&entity_id);
+        self.armor_stands.lock().await.remove(&entity_id);
+        self.falling_blocks.lock().await.remove(&entity_id);
+        self.primed_tnt.lock().await.remove(&entity_id);
+        self.projectiles.lock().await.remove(&entity_id);
+        self.mobs.lock().await.remove(&entity_id);
+        self.lightning_bolts.lock().await.remove(&entity_id);
+        self.remove_entity(entity).await;
+    }
+
+    /// Despawns the entity for the players who see it
+    pub async fn remove_entity(&self, entity: &Entity) {
+        self.stop_tracking(entity).await;
+    }
+
     /// Sets a block
     pub async fn set_block_state(&self, position: WorldPosition, block_state_id: u16) -> u16 {
+        let replaced_block_state_id = self.place_block_state(position, block_state_id).await;
+
+        let mut neighbor_updates = self.neighbor_updates.lock().await;
+        if neighbor_updates.running {
+            neighbor_updates.changes.push((position, block_state_id));
+            drop(neighbor_updates);
+        } else {
+            drop(neighbor_updates);
+            self.broadcast_packet_all(&CBlockUpdate::new(
+                &position,
+                i32::from(block_state_id).into(),
+            ))
+            .await;
+        }
+        self.block_state_placed(position, replaced_block_state_id, block_state_id)
+            .await;
+
+        replaced_block_state_id
+    }
+
+    /// Sets many blocks like `set_block_state`, but the players get the changed blocks of each
+    /// chunk section in one packet. Returns the replaced states in the same order
+    pub async fn set_block_states(&self, blocks: &[(WorldPosition, u16)]) -> Vec<u16> {
+        let mut replaced = Vec::with_capacity(blocks.len());
+        for &(position, block_state_id) in blocks {
+            replaced.push(self.place_block_state(position, block_state_id).await);
+        }
+
+        let mut neighbor_updates = self.neighbor_updates.lock().await;
+        if neighbor_updates.running {
+            neighbor_updates.changes.extend_from_slice(blocks);
+            drop(neighbor_updates);
+        } else {
+            drop(neighbor_updates);
+            self.broadcast_block_changes(blocks.to_vec()).await;
+        }
+        for (&(position, block_state_id), &replaced_block_state_id) in blocks.iter().zip(&replaced)
+        {
+            self.block_state_placed(position, replaced_block_state_id, block_state_id)
+                .await;
+        }
+
+        replaced
+    }
+
+    /// Sets the block in its chunk without telling anyone, returns the replaced state
+    async fn place_block_state(&self, position: WorldPosition, block_state_id: u16) -> u16 {
         let (chunk_coordinate, relative_coordinates) = position.chunk_and_chunk_relative_position();
 
         // Since we divide by 16 remnant can never exceed u8
         let relative = ChunkRelativeBlockCoordinates::from(relative_coordinates);
 
-        let chunk = self.receive_chunk(chunk_coordinate).await;
-        let replaced_block_state_id = chunk
-            .write()
-            .await
-            .blocks
-            .set_block(relative, block_state_id);
+        self.modify_chunk(chunk_coordinate, |chunk| {
+            chunk.dirty = true;
+            let replaced = chunk.blocks.set_block(relative, block_state_id);
+            update_block_entity(chunk, position, replaced, block_state_id);
+            replaced
+        })
+        .await
+    }
 
-        self.broadcast_packet_all(&CBlockUpdate::new(
-            &position,
-            i32::from(block_state_id).into(),
-        ))
-        .await;
+    /// Updates the light and lets fluids flow around a block which was changed
+    async fn block_state_placed(
+        &self,
+        position: WorldPosition,
+        replaced_block_state_id: u16,
+        block_state_id: u16,
+    ) {
+        if affects_light(replaced_block_state_id, block_state_id) {
+            self.update_light(position).await;
+        }
+        if replaced_block_state_id != block_state_id {
+            self.level
+                .schedule_fluid_updates(position, self.is_ultra_warm())
+                .await;
+        }
+    }
 
-        replaced_block_state_id
+    /// Sends the changed blocks to every player, a packet for each chunk section
+    async fn broadcast_block_changes(&self, changes: Vec<(WorldPosition, u16)>) {
+        for (section, changes) in changes_by_section(changes) {
+            if let [(position, state_id)] = changes[..] {
+                self.broadcast_packet_all(&CBlockUpdate::new(
+                    &position,
+                    i32::from(state_id).into(),
+                ))
+                .await;
+            } else {
+                self.broadcast_packet_all(&CSectionBlocksUpdate::new(section, changes))
+                    .await;
+            }
+        }
+    }
+
+    /// Notifies the six neighbors of the changed block, like vanilla does after most block changes.
+    /// Blocks reacting to it queue updates of their own, the blocks changed along the way are
+    /// sent together once every update ran. Blocks in chunks which are not loaded aren't notified
+    pub async fn update_neighbors(self: &Arc<Self>, position: WorldPosition, server: &Server) {
+        let mut neighbor_updates = self.neighbor_updates.lock().await;
+        neighbor_updates.queue.push_back(position);
+        if neighbor_updates.running {
+            return;
+        }
+        neighbor_updates.running = true;
+        drop(neighbor_updates);
+
+        let mut updated = 0;
+        let changes = loop {
+            let mut neighbor_updates = self.neighbor_updates.lock().await;
+            let Some(changed) = neighbor_updates.queue.pop_front() else {
+                neighbor_updates.running = false;
+                break std::mem::take(&mut neighbor_updates.changes);
+            };
+            if updated >= MAX_NEIGHBOR_UPDATES {
+                log::warn!("Too many chained neighbor updates, skipping the rest");
+                neighbor_updates.queue.clear();
+                continue;
+            }
+            drop(neighbor_updates);
+
+            for direction in [
+                Vector3::new(-1, 0, 0),
+                Vector3::new(1, 0, 0),
+                Vector3::new(0, -1, 0),
+                Vector3::new(0, 1, 0),
+                Vector3::new(0, 0, -1),
+                Vector3::new(0, 0, 1),
+            ] {
+                let neighbor = WorldPosition(changed.0 + direction);
+                updated += 1;
+                let Some(block) = self
+                    .level
+                    .loaded_block_state(&neighbor)
+                    .await
+                    .and_then(get_block_by_state_id)
+                else {
+                    continue;
+                };
+                server
+                    .block_manager
+                    .on_neighbor_update(block, self, neighbor, changed, server)
+                    .await;
+            }
+        };
+
+        self.broadcast_block_changes(changes).await;
+    }
+
+    /// Recalculates the light around a changed block and sends the changed light to the players.
+    /// Light only spreads into chunks which are loaded
+    async fn update_light(&self, position: WorldPosition) {
+        let (center, _) = position.chunk_and_chunk_relative_position();
+        // Chunks are always locked in the same order, so updates next to each other can't deadlock
+        let mut chunks = Vec::new();
+        for x in -1..=1 {
+            for z in -1..=1 {
+                let chunk_pos = Vector2::new(center.x + x, center.z + z);
+                if let Some(chunk) = self.level.get_loaded_chunk(&chunk_pos) {
+                    chunks.push(chunk);
+                }
+            }
+        }
+        let mut guards = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            guards.push(chunk.write().await);
+        }
+
+        let mut updater = LightUpdater::new(guards.iter_mut().map(|guard| &mut **guard));
+        updater.update_block(position);
+        let changed = updater.into_changed_sections();
+        let packets: Vec<_> = guards
+            .iter()
+            .filter_map(|chunk| {
+                let sections = changed.get(&chunk.position)?;
+                Some(CLightUpdate::new(
+                    chunk.position,
+                    chunk.light.copy_sections(*sections),
+                    *sections,
+                ))
+            })
+            .collect();
+        drop(guards);
+
+        for packet in packets {
+            self.broadcast_packet_all(&packet).await;
+        }
     }
 
     // Stream the chunks (don't collect them and then do stuff with them)
     /// Important: must be called from an async function (or changed to accept a tokio runtime
     /// handle)
-    pub fn receive_chunks(&self, chunks: Vec<Vector2<i32>>) -> Receiver<Arc<RwLock<ChunkData>>> {
+    pub fn receive_chunks(&self, chunks: &[Vector2<i32>]) -> Receiver<Arc<RwLock<ChunkData>>> {
         let (sender, receive) = mpsc::channel(chunks.len());
-        // Put this in another thread so we aren't blocking on it
-        let level = self.level.clone();
-        let rt = Handle::current();
-        rayon::spawn(move || {
-            level.fetch_chunks(&chunks, sender, &rt);
-        });
+        // The chunks are loaded on the level's worker threads, so this doesn't block
+        self.level.fetch_chunks(chunks, sender, &Handle::current());
         receive
     }
 
     pub async fn receive_chunk(&self, chunk_pos: Vector2<i32>) -> Arc<RwLock<ChunkData>> {
-        let mut receiver = self.receive_chunks(vec![chunk_pos]);
+        let mut receiver = self.receive_chunks(&[chunk_pos]);
         let chunk = receiver
             .recv()
             .await
@@ -819,8 +2109,72 @@ impl World {
         chunk
     }
 
-    pub async fn break_block(&self, position: WorldPosition, cause: Option<&Player>) {
+    /// Changes the chunk, which has to be marked as dirty if anything changed.
+    /// Dirty chunks stay in memory until they were saved, even if nobody watches them
+    async fn modify_chunk<T>(
+        &self,
+        chunk_pos: Vector2<i32>,
+        modify: impl FnOnce(&mut ChunkData) -> T,
+    ) -> T {
+        let mut receiver = self.receive_chunks(&[chunk_pos]);
+        let chunk = receiver
+            .recv()
+            .await
+            .expect("Channel closed for unknown reason");
+        let result = modify(&mut *chunk.write().await);
+
+        if !self.level.is_chunk_watched(&chunk_pos) {
+            self.level.clean_chunk(&chunk_pos);
+        }
+        result
+    }
+
+    /// Gets a copy of the block entity NBT at the given position, if there is one
+    pub async fn get_block_entity_nbt(&self, position: WorldPosition) -> Option<NbtCompound> {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        let chunk = self.receive_chunk(chunk).await;
+        let chunk = chunk.read().await;
+        chunk
+            .block_entities
+            .get(&position)
+            .map(|block_entity| block_entity_data(block_entity.as_ref()))
+    }
+
+    /// Replaces the block entity at the position with one of the type the block there has
+    pub async fn set_block_entity_nbt(&self, position: WorldPosition, nbt: NbtCompound) {
+        let (chunk, relative) = position.chunk_and_chunk_relative_position();
+        self.modify_chunk(chunk, |chunk| {
+            let state_id = chunk
+                .blocks
+                .get_block(ChunkRelativeBlockCoordinates::from(relative))
+                .unwrap_or_default();
+            let id = get_block_entity_type(state_id).unwrap_or_default();
+            chunk.dirty = true;
+            chunk
+                .block_entities
+                .insert(position, block_entity_from_nbt(&id, &nbt));
+        })
+        .await;
+    }
+
+    pub async fn remove_block_entity_nbt(&self, position: WorldPosition) -> Option<NbtCompound> {
+        let (chunk, _) = position.chunk_and_chunk_relative_position();
+        self.modify_chunk(chunk, |chunk| {
+            let removed = chunk.block_entities.remove(&position);
+            chunk.dirty |= removed.is_some();
+            removed.map(|block_entity| block_entity_data(block_entity.as_ref()))
+        })
+        .await
+    }
+
+    /// Replaces the block with air, returns the block entity it had
+    pub async fn break_block(
+        &self,
+        position: WorldPosition,
+        cause: Option<&Player>,
+    ) -> Option<NbtCompound> {
         let broken_block_state_id = self.set_block_state(position, 0).await;
+        let block_entity = self.remove_block_entity_nbt(position).await;
 
         let particles_packet =
             CWorldEvent::new(2001, &position, broken_block_state_id.into(), false);
@@ -832,6 +2186,7 @@ impl World {
             }
             None => self.broadcast_packet_all(&particles_packet).await,
         }
+        block_entity
     }
 
     pub async fn get_block_state_id(&self, position: WorldPosition) -> Result<u16, GetBlockError> {
@@ -880,3 +2235,34 @@ impl World {
         get_block_and_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
 }
+
+/// Where players pick up items, experience orbs and arrows, a bit further than their hitbox
+fn pickup_box(player: &Player) -> BoundingBox {
+    let mut pickup_box = player.living_entity.entity.bounding_box.load();
+    pickup_box.min_x -= 1.0;
+    pickup_box.min_y -= 0.5;
+    pickup_box.min_z -= 1.0;
+    pickup_box.max_x += 1.0;
+    pickup_box.max_y += 0.5;
+    pickup_box.max_z += 1.0;
+    pickup_box
+}
+
+/// Removes the block entity of the replaced block when the block entity type changes, and adds
+/// the block entity of the new block if it is one which has its own data, like a furnace
+fn update_block_entity(
+    chunk: &mut ChunkData,
+    position: WorldPosition,
+    replaced_state_id: u16,
+    state_id: u16,
+) {
+    let block_entity_type = get_block_entity_type(state_id);
+    if get_block_entity_type(replaced_state_id) != block_entity_type {
+        chunk.block_entities.remove(&position);
+    }
+    if block_entity_type.is_some() && !chunk.block_entities.contains_key(&position) {
+        if let Some(block_entity) = new_block_entity(state_id) {
+            chunk.block_entities.insert(position, block_entity);
+        }
+    }
+}
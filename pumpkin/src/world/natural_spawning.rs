@@ -0,0 +1,202 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use pumpkin_core::{
+    math::{boundingbox::BoundingBoxSize, get_section_cord, vector2::Vector2, vector3::Vector3},
+    Difficulty, GameMode,
+};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{
+    dimension::Dimension,
+    spawning::{sky_darken, MobCategory, NaturalSpawn, SpawnContext},
+    world_info::game_rules::BoolRule,
+};
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{
+    entity::{
+        horse::HorseEntity,
+        mob::{mob_category, mob_size, MobEntity},
+    },
+    server::Server,
+};
+
+use super::World;
+
+/// Mobs spawn in the chunks this many chunks around players, like vanilla's chunks close enough
+/// for natural spawning
+const SPAWN_CHUNK_RADIUS: i32 = 8;
+/// Animals only spawn once in this many ticks, like vanilla's `spawnPersistent`
+const CREATURE_SPAWN_INTERVAL: i64 = 400;
+/// How dark the sky is for monsters while it thunders, like vanilla's thunder brightness
+const THUNDER_SKY_DARKEN: u8 = 10;
+
+/// The size of the mob with the name, `None` for entity types we don't know
+fn spawn_size(name: &str) -> Option<BoundingBoxSize> {
+    let (width, height) = mob_size(&EntityType::from_name(name)?);
+    Some(BoundingBoxSize { width, height })
+}
+
+impl World {
+    /// Spawns mobs in the loaded chunks around players and despawns the ones far away from them,
+    /// like vanilla's `NaturalSpawner.spawnForChunk` and `Mob.checkDespawn`. Mobs of a category
+    /// stop spawning once the world holds as many as its cap allows
+    pub(super) async fn tick_natural_spawning(self: &Arc<Self>, server: &Server) {
+        // Spectators neither spawn nor keep mobs
+        let players: Vec<Vector3<f64>> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .filter(|player| player.gamemode.load() != GameMode::Spectator)
+            .map(|player| player.living_entity.entity.pos.load())
+            .collect();
+        if players.is_empty() {
+            return;
+        }
+        self.despawn_mobs(&players).await;
+        if !self
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::DoMobSpawning)
+        {
+            return;
+        }
+
+        let world_age = self.level_time.lock().await.world_age;
+        let spawn_monsters = self.difficulty.load() != Difficulty::Peaceful;
+        let categories: Vec<_> = MobCategory::ALL
+            .into_iter()
+            .filter(|category| match category {
+                MobCategory::Monster => spawn_monsters,
+                category if category.is_persistent() => world_age % CREATURE_SPAWN_INTERVAL == 0,
+                _ => true,
+            })
+            .collect();
+        let mut chunks = self.spawnable_chunks(&players);
+        let mut counts = self.count_mobs_by_category().await;
+        let context = SpawnContext {
+            dimension: self.dimension(),
+            seed: self.level.seed.0,
+            sky_darken: self.sky_darken().await,
+            players: &players,
+        };
+
+        chunks.shuffle(&mut thread_rng());
+        let spawnable_chunks = chunks.len();
+        for chunk in chunks {
+            for &category in &categories {
+                let count = counts.entry(category).or_default();
+                if *count >= category.cap(spawnable_chunks) {
+                    continue;
+                }
+                let spawns = self
+                    .level
+                    .natural_spawns(&chunk, category, &context, spawn_size);
+                *count += spawns.len();
+                for spawn in spawns {
+                    self.spawn_natural_mob(server, &spawn).await;
+                }
+            }
+        }
+    }
+
+    /// Removes the mobs no player is close enough to
+    async fn despawn_mobs(&self, players: &[Vector3<f64>]) {
+        let mobs: Vec<_> = self.mobs.lock().await.values().cloned().collect();
+        for mob in mobs {
+            // Dead mobs finish their death animation
+            if mob.living_entity.health.load() > 0.0 && mob.check_despawn(players) {
+                self.remove_mob(&mob).await;
+            }
+        }
+    }
+
+    /// The loaded chunks close enough to a player for mobs to spawn in
+    fn spawnable_chunks(&self, players: &[Vector3<f64>]) -> Vec<Vector2<i32>> {
+        let mut chunks = HashSet::new();
+        for player in players {
+            let center = Vector2::new(
+                get_section_cord(player.x.floor() as i32),
+                get_section_cord(player.z.floor() as i32),
+            );
+            for x in -SPAWN_CHUNK_RADIUS..=SPAWN_CHUNK_RADIUS {
+                for z in -SPAWN_CHUNK_RADIUS..=SPAWN_CHUNK_RADIUS {
+                    chunks.insert(Vector2::new(center.x + x, center.z + z));
+                }
+            }
+        }
+        chunks
+            .into_iter()
+            .filter(|chunk| self.level.get_loaded_chunk(chunk).is_some())
+            .collect()
+    }
+
+    /// How many living mobs of each category are in the world
+    async fn count_mobs_by_category(&self) -> HashMap<MobCategory, usize> {
+        let mut counts = HashMap::new();
+        for mob in self.mobs.lock().await.values() {
+            if mob.living_entity.health.load() <= 0.0 {
+                continue;
+            }
+            if let Some(category) = mob_category(&mob.entity().entity_type) {
+                *counts.entry(category).or_default() += 1;
+            }
+        }
+        *counts.entry(MobCategory::Creature).or_default() += self.horse_entities.lock().await.len();
+        counts
+    }
+
+    /// The dimension of the world, which decides the mobs that spawn and how dark it has to be
+    const fn dimension(&self) -> Dimension {
+        match self.dimension_type {
+            DimensionType::TheNether => Dimension::Nether,
+            DimensionType::TheEnd => Dimension::End,
+            DimensionType::Overworld | DimensionType::OverworldCaves => Dimension::OverWorld,
+        }
+    }
+
+    /// How much the time of day and the weather darken the sky, see `spawning::sky_darken`
+    async fn sky_darken(&self) -> u8 {
+        let time_of_day = self.level_time.lock().await.time_of_day;
+        let weather = self.weather.lock().await;
+        if weather.is_thundering() {
+            return THUNDER_SKY_DARKEN;
+        }
+        sky_darken(
+            time_of_day,
+            weather.rain_level,
+            weather.thunder_level * weather.rain_level,
+        )
+    }
+
+    /// Puts a naturally spawned mob into the world, horses and donkeys get their inventory
+    async fn spawn_natural_mob(self: &Arc<Self>, server: &Server, spawn: &NaturalSpawn) {
+        let Some(entity_type) = EntityType::from_name(spawn.entity) else {
+            return;
+        };
+        let entity_id = server.new_entity_id();
+        let yaw = thread_rng().gen_range(0.0..360.0);
+        if let Some(horse) = HorseEntity::new(entity_id, self.clone(), entity_type.clone()) {
+            let entity = horse.entity();
+            entity.set_pos(spawn.position);
+            entity.set_rotation(yaw, 0.0);
+            entity.head_yaw.store(yaw);
+            self.add_horse_entity(Arc::new(horse)).await;
+            return;
+        }
+        let mob = Arc::new(MobEntity::new(
+            entity_id,
+            self.clone(),
+            entity_type,
+            spawn.position,
+            yaw,
+        ));
+        self.equip_spawned_mob(&mob).await;
+        self.add_mob(mob).await;
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::{HashMap, VecDeque};
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+/// Vanilla stops chains of neighbor updates after this many, so contraptions updating each other
+/// forever can't hang the server
+pub const MAX_NEIGHBOR_UPDATES: usize = 1_000_000;
+
+/// The blocks whose neighbors still have to be notified of a change, worked through by whoever
+/// started the chain of updates
+#[derive(Default)]
+pub struct NeighborUpdates {
+    pub queue: VecDeque<WorldPosition>,
+    /// Whether a task is working through the queue, blocks changed meanwhile are sent at the end
+    pub running: bool,
+    /// The blocks changed by the current chain of updates and their new states
+    pub changes: Vec<(WorldPosition, u16)>,
+}
+
+/// Groups block changes by the chunk section they are in, later changes of a block replace
+/// earlier ones
+#[must_use]
+pub fn changes_by_section(
+    changes: Vec<(WorldPosition, u16)>,
+) -> HashMap<Vector3<i32>, Vec<(WorldPosition, u16)>> {
+    let latest: HashMap<_, _> = changes.into_iter().collect();
+    let mut sections: HashMap<_, Vec<_>> = HashMap::new();
+    for (position, state_id) in latest {
+        let section = Vector3::new(
+            position.0.x.div_euclid(16),
+            position.0.y.div_euclid(16),
+            position.0.z.div_euclid(16),
+        );
+        sections
+            .entry(section)
+            .or_default()
+            .push((position, state_id));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use super::changes_by_section;
+
+    #[test]
+    fn changes_are_grouped_by_section() {
+        let block = WorldPosition(Vector3::new(-1, 64, 15));
+        let sections = changes_by_section(vec![
+            (block, 1),
+            (WorldPosition(Vector3::new(0, 64, 15)), 2),
+            (block, 3),
+        ]);
+        assert_eq!(sections.len(), 2);
+        // Only the latest state of a block is sent
+        assert_eq!(sections[&Vector3::new(-1, 4, 0)], vec![(block, 3)]);
+        assert_eq!(sections[&Vector3::new(0, 4, 0)].len(), 1);
+    }
+}
@@ -6,9 +6,9 @@ use pumpkin_core::{
     GameMode,
 };
 use pumpkin_protocol::client::play::{CCenterChunk, CUnloadChunk};
-use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
+use pumpkin_world::{cylindrical_chunk_iterator::Cylindrical, damage::DamageType};
 
-use crate::entity::player::Player;
+use crate::entity::{damage::DamageSource, player::Player};
 
 pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
     player.config.lock().await.view_distance.clamp(
@@ -41,10 +41,15 @@ pub async fn player_join(player: &Arc<Player>) {
 
 pub async fn update_position(player: &Arc<Player>) {
     if !player.abilities.lock().await.flying {
-        player
+        if let Some(damage) = player
             .living_entity
             .update_fall_distance(player.gamemode.load() == GameMode::Creative)
-            .await;
+            .await
+        {
+            player
+                .hurt(&DamageSource::new(DamageType::Fall), damage)
+                .await;
+        }
     }
 
     let entity = &player.living_entity.entity;
@@ -80,17 +85,19 @@ pub async fn update_position(player: &Arc<Player>) {
         // Make sure the watched section and the chunk watcher updates are async atomic. We want to
         // ensure what we unload when the player disconnects is correct
         entity
-            .world
+            .world()
             .level
             .mark_chunks_as_newly_watched(&loading_chunks);
         let chunks_to_clean = entity
-            .world
+            .world()
             .level
             .mark_chunks_as_not_watched(&unloading_chunks);
         player.watched_section.store(new_cylindrical);
 
         if !chunks_to_clean.is_empty() {
-            entity.world.level.clean_chunks(&chunks_to_clean);
+            let world = entity.world();
+            world.unload_chunk_entities(&chunks_to_clean).await;
+            world.level.clean_chunks(&chunks_to_clean);
 
             // This can take a little if we are sending a bunch of packets, queue it up :p
             let client = player.client.clone();
@@ -109,8 +116,8 @@ pub async fn update_position(player: &Arc<Player>) {
 
         if !loading_chunks.is_empty() {
             entity
-                .world
-                .spawn_world_chunks(player.clone(), loading_chunks, new_chunk_center);
+                .world()
+                .spawn_world_chunks(player.clone(), &loading_chunks, new_chunk_center);
         }
     }
 }
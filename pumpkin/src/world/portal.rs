@@ -0,0 +1,271 @@
+use std::sync::Arc;
+
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
+use pumpkin_protocol::client::play::CWorldEvent;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{
+    block::{
+        block_registry::get_block,
+        portal::{
+            build_nether_portal, end_platform, end_portal_blocks, NetherPortalShape, PortalAxis,
+            PortalKind, END_SPAWN,
+        },
+    },
+    WORLD_LOWEST_Y, WORLD_MAX_Y,
+};
+use tokio::runtime::Handle;
+
+use crate::{entity::Entity, server::Server};
+
+use super::World;
+
+/// Ticks players in survival stand in a nether portal before it takes them, like vanilla's
+/// `playersNetherPortalDefaultDelay`
+const PLAYER_PORTAL_WAIT: i32 = 80;
+
+/// Ticks players have to be out of portals after traveling before portals take them again
+const PLAYER_PORTAL_COOLDOWN: i32 = 10;
+
+/// Ticks other entities have to be out of portals after traveling before portals take them again
+const ENTITY_PORTAL_COOLDOWN: i32 = 300;
+
+/// How far from where an entity comes out of a nether portal an existing portal is used, in
+/// blocks of the destination
+const fn portal_search_radius(destination: DimensionType) -> i32 {
+    match destination {
+        DimensionType::TheNether => 16,
+        _ => 128,
+    }
+}
+
+impl World {
+    /// Sets the block on fire, fire in an empty obsidian frame lights a nether portal instead,
+    /// like vanilla's `BaseFireBlock.onPlace`. Portals can't be lit in the end
+    pub async fn light_fire(self: &Arc<Self>, position: WorldPosition, server: &Server) {
+        if !matches!(self.dimension_type, DimensionType::TheEnd) {
+            if let Some(shape) = self.level.nether_portal_shape(position) {
+                let portal = shape.portal_state();
+                for block in shape.blocks() {
+                    self.set_block_state(block, portal).await;
+                }
+                return;
+            }
+        }
+        let Some(fire) = get_block("minecraft:fire") else {
+            return;
+        };
+        self.set_block_state(position, fire.default_state_id).await;
+        self.update_neighbors(position, server).await;
+    }
+
+    /// Fills the end portal frame around the center with end portal blocks
+    pub async fn open_end_portal(&self, center: WorldPosition) {
+        for (position, state) in end_portal_blocks(center) {
+            self.set_block_state(position, state).await;
+        }
+        // The sound every player hears
+        self.broadcast_packet_all(&CWorldEvent::new(1038, &center, 0, true))
+            .await;
+    }
+
+    /// Takes the players, items and mobs in portals to the world the portals lead to
+    pub(super) async fn tick_portals(&self, server: &Server) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            let wait = if player.gamemode.load() == GameMode::Creative {
+                1
+            } else {
+                PLAYER_PORTAL_WAIT
+            };
+            let entity = &player.living_entity.entity;
+            let Some((world, position)) = self
+                .tick_portal(entity, wait, PLAYER_PORTAL_COOLDOWN, server)
+                .await
+            else {
+                continue;
+            };
+            world
+                .transfer_player(&player, position, entity.yaw.load(), entity.pitch.load())
+                .await;
+            let block = WorldPosition(Vector3::new(
+                position.x.floor() as i32,
+                position.y.floor() as i32,
+                position.z.floor() as i32,
+            ));
+            player
+                .client
+                .send_packet(&CWorldEvent::new(1032, &block, 0, false))
+                .await;
+        }
+
+        let item_entities: Vec<_> = self.item_entities.lock().await.values().cloned().collect();
+        for item_entity in item_entities {
+            let entity = &item_entity.entity;
+            let Some((world, position)) = self
+                .tick_portal(entity, 0, ENTITY_PORTAL_COOLDOWN, server)
+                .await
+            else {
+                continue;
+            };
+            self.remove_item_entity(&item_entity).await;
+            entity.set_world(world.clone());
+            entity.set_pos(position);
+            world.add_item_entity(item_entity).await;
+        }
+
+        let mobs: Vec<_> = self.mobs.lock().await.values().cloned().collect();
+        for mob in mobs {
+            let entity = mob.entity();
+            let Some((world, position)) = self
+                .tick_portal(entity, 0, ENTITY_PORTAL_COOLDOWN, server)
+                .await
+            else {
+                continue;
+            };
+            self.remove_mob(&mob).await;
+            entity.set_world(world.clone());
+            entity.set_pos(position);
+            world.add_mob(mob).await;
+        }
+    }
+
+    /// Counts how long the entity is in a portal. Returns where the portal takes it once it
+    /// waited `wait` ticks, end portals take entities right away
+    async fn tick_portal(
+        &self,
+        entity: &Entity,
+        wait: i32,
+        cooldown: i32,
+        server: &Server,
+    ) -> Option<(Arc<Self>, Vector3<f64>)> {
+        entity
+            .portal_cooldown
+            .store((entity.portal_cooldown.load() - 1).max(0));
+        let Some(portal) = self.level.portal_in(&entity.bounding_box.load()) else {
+            // The time in the portal wears off when leaving it
+            entity
+                .portal_time
+                .store((entity.portal_time.load() - 4).max(0));
+            return None;
+        };
+        if entity.portal_cooldown.load() > 0 {
+            entity.portal_cooldown.store(cooldown);
+            return None;
+        }
+        if matches!(portal, PortalKind::Nether(_)) {
+            let time = entity.portal_time.load() + 1;
+            entity.portal_time.store(time);
+            if time < wait {
+                return None;
+            }
+        }
+        entity.portal_time.store(0);
+        entity.portal_cooldown.store(cooldown);
+
+        let destination = match portal {
+            PortalKind::Nether(axis) => self.nether_destination(entity, axis, server).await,
+            PortalKind::End => self.end_destination(server).await,
+        };
+        if destination.is_none() {
+            log::debug!(
+                "The portal in {} leads to a world which is not enabled",
+                self.key
+            );
+        }
+        destination
+    }
+
+    /// The world a nether portal in this world leads to
+    fn nether_portal_world(&self, server: &Server) -> Option<Arc<Self>> {
+        if matches!(self.dimension_type, DimensionType::TheNether) {
+            server.get_world("overworld")
+        } else {
+            server.get_world("the_nether")
+        }
+    }
+
+    /// Where the entity comes out of a nether portal, like vanilla's `PortalForcer`. The closest
+    /// portal near the scaled position is used, a new one is built when there is none
+    async fn nether_destination(
+        &self,
+        entity: &Entity,
+        axis: PortalAxis,
+        server: &Server,
+    ) -> Option<(Arc<Self>, Vector3<f64>)> {
+        let world = self.nether_portal_world(server)?;
+        let scale = self.coordinate_scale() / world.coordinate_scale();
+        let position = entity.pos.load().multiply(scale, 1.0, scale);
+        let position = world.worldborder.lock().await.clamp(position);
+        let (min_y, max_y) = world.portal_height_range();
+        let target = WorldPosition(Vector3::new(
+            position.x.floor() as i32,
+            (position.y.floor() as i32).clamp(min_y, max_y),
+            position.z.floor() as i32,
+        ));
+
+        let rt = Handle::current();
+        let radius = portal_search_radius(world.dimension_type);
+        if let Some(portal) = world.level.find_nether_portal(target, radius, &rt).await {
+            return Some((world, portal.exit()));
+        }
+
+        let site = world
+            .level
+            .find_portal_site(target, axis, min_y, max_y, &rt)
+            .await;
+        // Without room for a portal it is built in the air, on a platform
+        let (site, platform) = site.map_or_else(
+            || {
+                let y = target.0.y.clamp(70.min(max_y), max_y - 10);
+                (WorldPosition(Vector3::new(target.0.x, y, target.0.z)), true)
+            },
+            |site| (site, false),
+        );
+        for (position, state) in build_nether_portal(site, axis, platform) {
+            world.set_block_state(position, state).await;
+        }
+        let portal = NetherPortalShape {
+            bottom_left: site,
+            axis,
+            width: 2,
+            height: 3,
+        };
+        Some((world, portal.exit()))
+    }
+
+    /// Where an end portal takes entities, onto the platform in the end or back to the spawn
+    async fn end_destination(&self, server: &Server) -> Option<(Arc<Self>, Vector3<f64>)> {
+        if matches!(self.dimension_type, DimensionType::TheEnd) {
+            let world = server.get_world("overworld")?;
+            let position = world.spawn_position().await;
+            return Some((world, position));
+        }
+        let world = server.get_world("the_end")?;
+        for (position, state) in end_platform() {
+            world.set_block_state(position, state).await;
+        }
+        let position = Vector3::new(
+            f64::from(END_SPAWN.0.x) + 0.5,
+            f64::from(END_SPAWN.0.y),
+            f64::from(END_SPAWN.0.z) + 0.5,
+        );
+        Some((world, position))
+    }
+
+    /// The lowest and highest blocks new nether portals are built on, below the nether's roof
+    fn portal_height_range(&self) -> (i32, i32) {
+        match self.dimension_type {
+            DimensionType::TheNether => (1, 118),
+            _ => (i32::from(WORLD_LOWEST_Y) + 1, i32::from(WORLD_MAX_Y) - 6),
+        }
+    }
+}
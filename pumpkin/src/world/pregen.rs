@@ -0,0 +1,158 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use pumpkin_config::ADVANCED_CONFIG;
+use pumpkin_core::text::{color::NamedColor, TextComponent};
+use pumpkin_world::pregen::PregenTask;
+use tokio::runtime::Handle;
+
+use crate::entity::player::Player;
+
+use super::{
+    bossbar::{Bossbar, BossbarColor, BossbarHandle},
+    World,
+};
+
+/// A running `/pregen`, generating the chunks of a `PregenTask` batch by batch in the background.
+/// The player who started it sees the progress in a bossbar, the console in the log
+pub struct Pregeneration {
+    /// Updated after every batch
+    task: std::sync::Mutex<PregenTask>,
+    cancelled: AtomicBool,
+    requester: Option<Arc<Player>>,
+    bossbar: BossbarHandle,
+}
+
+impl Pregeneration {
+    /// Starts generating the chunks of the task in the world, returns `None` if the world already has a running pregeneration
+    pub async fn start(
+        world: Arc<World>,
+        task: PregenTask,
+        requester: Option<Arc<Player>>,
+    ) -> Option<Arc<Self>> {
+        let mut running = world.pregeneration.lock().await;
+        if running.is_some() {
+            return None;
+        }
+        let mut bossbar = Bossbar::new(Self::title(&task));
+        bossbar.health = task.progress();
+        bossbar.color = BossbarColor::Green;
+        let pregeneration = Arc::new(Self {
+            task: std::sync::Mutex::new(task),
+            cancelled: AtomicBool::new(false),
+            requester,
+            bossbar: BossbarHandle::new(bossbar),
+        });
+        *running = Some(pregeneration.clone());
+        drop(running);
+        world.level.save_pregen_task(&task);
+
+        log::info!(
+            "Pregenerating {} chunks around chunk {}, {}",
+            task.chunk_count() - task.done,
+            task.center_x,
+            task.center_z
+        );
+        let running = pregeneration.clone();
+        tokio::spawn(async move { running.run(world).await });
+        Some(pregeneration)
+    }
+
+    /// The progress as of the last finished batch
+    pub fn task(&self) -> PregenTask {
+        *self.task.lock().unwrap()
+    }
+
+    /// Stops after the current batch, the chunks generated so far stay saved
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    fn title(task: &PregenTask) -> String {
+        format!(
+            "Pregenerating chunks {}/{}",
+            task.done.min(task.chunk_count()),
+            task.chunk_count()
+        )
+    }
+
+    async fn run(self: Arc<Self>, world: Arc<World>) {
+        let config = ADVANCED_CONFIG.chunk.pregen;
+        let batch_size = config.batch_size.max(1);
+        let rt = Handle::current();
+        let mut task = self.task();
+        if let Some(player) = &self.requester {
+            self.bossbar.add_viewer(player.clone()).await;
+        }
+
+        // Reported every 10%
+        let mut reported = (task.progress() * 10.0) as u32;
+        while !task.is_finished() {
+            if self.cancelled.load(Ordering::Relaxed) {
+                break;
+            }
+            let started = Instant::now();
+            let generated = world
+                .level
+                .pregenerate_batch(&mut task, batch_size, &rt)
+                .await;
+            *self.task.lock().unwrap() = task;
+
+            self.bossbar.set_health(task.progress()).await;
+            self.bossbar.set_title(Self::title(&task)).await;
+            let tenths = (task.progress() * 10.0) as u32;
+            if tenths > reported && !task.is_finished() {
+                reported = tenths;
+                self.report(&format!("Pregeneration at {}%", tenths * 10))
+                    .await;
+            }
+
+            // Leaves the workers to the players between batches
+            if config.chunks_per_second > 0 {
+                let generated = u32::try_from(generated).unwrap_or(u32::MAX);
+                let time = Duration::from_secs(1) * generated / config.chunks_per_second;
+                tokio::time::sleep(time.saturating_sub(started.elapsed())).await;
+            }
+        }
+
+        self.bossbar.remove_all_viewers().await;
+        if task.is_finished() {
+            self.report(&format!(
+                "Pregeneration finished, generated {} chunks",
+                task.chunk_count()
+            ))
+            .await;
+        } else {
+            world.level.remove_pregen_task();
+            self.report(&format!(
+                "Pregeneration cancelled after {} chunks",
+                task.done
+            ))
+            .await;
+        }
+
+        let mut running = world.pregeneration.lock().await;
+        if running
+            .as_ref()
+            .is_some_and(|running| Arc::ptr_eq(running, &self))
+        {
+            *running = None;
+        }
+    }
+
+    async fn report(&self, message: &str) {
+        log::info!("{message}");
+        if let Some(player) = &self.requester {
+            player
+                .send_system_message(
+                    &TextComponent::text_string(message.to_string()).color_named(NamedColor::Gray),
+                )
+                .await;
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use pumpkin_core::math::{
+    boundingbox::BoundingBoxSize, get_section_cord, position::WorldPosition, vector2::Vector2,
+    vector3::Vector3,
+};
+use pumpkin_macros::sound;
+use pumpkin_registry::DimensionType;
+use pumpkin_world::block::block_registry::{get_block_by_state_id, Block};
+
+use super::{sleep::is_bed, World};
+
+/// Respawn anchors hold this many charges of glowstone
+pub const MAX_ANCHOR_CHARGES: u8 = 4;
+
+/// The charges of the respawn anchor, `None` for other blocks
+#[must_use]
+pub fn anchor_charges(block: &Block, state_id: u16) -> Option<u8> {
+    if block.name != "respawn_anchor" {
+        return None;
+    }
+    block
+        .state_properties(state_id)
+        .into_iter()
+        .find_map(|(property, value)| (property == "charges").then_some(value))
+        .and_then(|charges| charges.parse().ok())
+}
+
+/// The yaw looking from the position at the bottom center of the block, players respawn
+/// facing their bed or respawn anchor
+fn yaw_facing(position: Vector3<f64>, block: WorldPosition) -> f32 {
+    let x = f64::from(block.0.x) + 0.5 - position.x;
+    let z = f64::from(block.0.z) + 0.5 - position.z;
+    (z.atan2(x).to_degrees() - 90.0) as f32
+}
+
+impl World {
+    /// Changes how many charges the respawn anchor at the position holds
+    pub async fn set_anchor_charges(&self, position: WorldPosition, charges: u8) {
+        let Ok(state_id) = self.get_block_state_id(position).await else {
+            return;
+        };
+        let Some(block) = get_block_by_state_id(state_id) else {
+            return;
+        };
+        if anchor_charges(block, state_id).is_none() {
+            return;
+        }
+        let mut properties: HashMap<String, String> = block
+            .state_properties(state_id)
+            .into_iter()
+            .map(|(property, value)| (property.to_string(), value.to_string()))
+            .collect();
+        properties.insert("charges".to_string(), charges.to_string());
+        self.set_block_state(position, block.state_with_properties(&properties))
+            .await;
+    }
+
+    /// Where and facing which way a player respawns at the bed or respawn anchor in this world,
+    /// like vanilla's `ServerPlayer.findRespawnAndUseSpawnBlock`. `None` if the block is gone,
+    /// doesn't work in this dimension, or has no room around it. Respawn anchors use up a charge
+    pub async fn respawn_block_position(
+        &self,
+        position: WorldPosition,
+        size: &BoundingBoxSize,
+    ) -> Option<(Vector3<f64>, f32)> {
+        let state_id = self.get_block_state_id(position).await.ok()?;
+        let block = get_block_by_state_id(state_id)?;
+        let charges = if is_bed(block) {
+            None
+        } else if matches!(self.dimension_type, DimensionType::TheNether) {
+            Some(anchor_charges(block, state_id).filter(|charges| *charges > 0)?)
+        } else {
+            return None;
+        };
+
+        let stand_up = self.loaded_stand_up_position(position, size).await?;
+        if let Some(charges) = charges {
+            self.set_anchor_charges(position, charges - 1).await;
+            self.play_block_sound(sound!("block.respawn_anchor.deplete"), position)
+                .await;
+        }
+        Some((stand_up, yaw_facing(stand_up, position)))
+    }
+
+    /// Loads the chunks around the respawn block to find where the player stands up, the ones
+    /// nobody watches are unloaded again
+    async fn loaded_stand_up_position(
+        &self,
+        position: WorldPosition,
+        size: &BoundingBoxSize,
+    ) -> Option<Vector3<f64>> {
+        let mut chunks: Vec<Vector2<i32>> = Vec::new();
+        for (x, z) in [(-1, -1), (-1, 1), (1, -1), (1, 1)] {
+            let chunk = Vector2::new(
+                get_section_cord(position.0.x + x),
+                get_section_cord(position.0.z + z),
+            );
+            if !chunks.contains(&chunk) {
+                chunks.push(chunk);
+            }
+        }
+        let mut receiver = self.receive_chunks(&chunks);
+        for _ in &chunks {
+            receiver.recv().await;
+        }
+        let stand_up = self.level.stand_up_position(&position, size);
+        for chunk in &chunks {
+            if !self.level.is_chunk_watched(chunk) {
+                self.level.clean_chunk(chunk);
+            }
+        }
+        stand_up
+    }
+}
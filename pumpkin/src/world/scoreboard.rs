@@ -1,128 +1,685 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
 use pumpkin_core::text::TextComponent;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
-    client::play::{CDisplayObjective, CUpdateObjectives, CUpdateScore, RenderType},
+    client::play::{
+        CDisplayObjective, CResetScore, CSetPlayerTeam, CUpdateObjectives, CUpdateScore,
+        DisplaySlot, Mode, RenderType, TeamMethod,
+    },
     codec::var_int::VarInt,
-    NumberFormat,
 };
+use thiserror::Error;
 
-use super::World;
+use super::team::Team;
+use crate::{
+    net::{stats::Stat, Client},
+    server::Server,
+};
+
+/// The name the scoreboard is saved as in the `data` folder of the overworld
+pub const SCOREBOARD_DATA: &str = "scoreboard";
+
+/// The display slots by the names commands and the saved scoreboard know them by
+pub const DISPLAY_SLOTS: [(DisplaySlot, &str); 19] = [
+    (DisplaySlot::List, "list"),
+    (DisplaySlot::Sidebar, "sidebar"),
+    (DisplaySlot::BelowName, "below_name"),
+    (DisplaySlot::TeamBlack, "sidebar.team.black"),
+    (DisplaySlot::TeamDarkBlue, "sidebar.team.dark_blue"),
+    (DisplaySlot::TeamDarkGreen, "sidebar.team.dark_green"),
+    (DisplaySlot::TeanDarkAqua, "sidebar.team.dark_aqua"),
+    (DisplaySlot::TeamDarkRed, "sidebar.team.dark_red"),
+    (DisplaySlot::TeamDarkPurple, "sidebar.team.dark_purple"),
+    (DisplaySlot::TeamGold, "sidebar.team.gold"),
+    (DisplaySlot::TeamGray, "sidebar.team.gray"),
+    (DisplaySlot::TeamDarkGray, "sidebar.team.dark_gray"),
+    (DisplaySlot::TeamBlue, "sidebar.team.blue"),
+    (DisplaySlot::TeamGreen, "sidebar.team.green"),
+    (DisplaySlot::TeamAqua, "sidebar.team.aqua"),
+    (DisplaySlot::TeamRed, "sidebar.team.red"),
+    (DisplaySlot::TeamLightPurple, "sidebar.team.light_purple"),
+    (DisplaySlot::TeamYellow, "sidebar.team.yellow"),
+    (DisplaySlot::TeamWhite, "sidebar.team.white"),
+];
+
+#[must_use]
+pub fn display_slot_by_name(name: &str) -> Option<DisplaySlot> {
+    DISPLAY_SLOTS
+        .iter()
+        .find(|(_, slot_name)| *slot_name == name)
+        .map(|(slot, _)| *slot)
+}
+
+#[must_use]
+pub fn display_slot_name(slot: DisplaySlot) -> &'static str {
+    DISPLAY_SLOTS
+        .iter()
+        .find(|(other, _)| *other == slot)
+        .map_or("list", |(_, name)| *name)
+}
+
+#[derive(Debug, Error)]
+pub enum ScoreboardError {
+    #[error("An objective already exists by that name")]
+    ObjectiveExists,
+    #[error("Unknown scoreboard objective '{0}'")]
+    UnknownObjective(String),
+    #[error("A team already exists by that name")]
+    TeamExists,
+    #[error("Unknown team '{0}'")]
+    UnknownTeam(String),
+}
 
+/// What the scores of an objective count, like vanilla's `ObjectiveCriteria`. Only dummy
+/// objectives are changed by commands alone
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveCriterion {
+    Dummy,
+    DeathCount,
+    PlayerKillCount,
+    TotalKillCount,
+    Health,
+    /// Follows a statistic of the player, like `minecraft.mined:minecraft.stone`
+    Stat(Stat),
+}
+
+impl ObjectiveCriterion {
+    pub const ALL: [Self; 5] = [
+        Self::Dummy,
+        Self::DeathCount,
+        Self::PlayerKillCount,
+        Self::TotalKillCount,
+        Self::Health,
+    ];
+
+    #[must_use]
+    pub fn name(self) -> Cow<'static, str> {
+        match self {
+            Self::Dummy => "dummy".into(),
+            Self::DeathCount => "deathCount".into(),
+            Self::PlayerKillCount => "playerKillCount".into(),
+            Self::TotalKillCount => "totalKillCount".into(),
+            Self::Health => "health".into(),
+            Self::Stat(stat) => stat.criterion_name().into(),
+        }
+    }
+
+    /// The criterion with the name, statistics are named by their type and value
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|criterion| criterion.name() == name)
+            .or_else(|| Stat::from_criterion_name(name).map(Self::Stat))
+    }
+
+    /// Whether the scores only follow the game, commands can't change them
+    #[must_use]
+    pub const fn is_read_only(self) -> bool {
+        matches!(self, Self::Health)
+    }
+
+    #[must_use]
+    pub const fn default_render_type(self) -> RenderType {
+        match self {
+            Self::Health => RenderType::Hearts,
+            _ => RenderType::Integer,
+        }
+    }
+}
+
+pub struct ScoreboardObjective {
+    pub name: String,
+    pub criterion: ObjectiveCriterion,
+    pub display_name: String,
+    pub render_type: RenderType,
+}
+
+impl ScoreboardObjective {
+    #[must_use]
+    pub fn new(name: String, criterion: ObjectiveCriterion, display_name: String) -> Self {
+        Self {
+            name,
+            criterion,
+            display_name,
+            render_type: criterion.default_render_type(),
+        }
+    }
+
+    fn packet(&self, mode: Mode) -> CUpdateObjectives<'_> {
+        CUpdateObjectives::new(
+            &self.name,
+            mode,
+            TextComponent::text(&self.display_name),
+            self.render_type,
+            None,
+        )
+    }
+}
+
+/// The objectives, scores and teams of the server, like vanilla's `ServerScoreboard`. Every
+/// change is sent to all players
 #[derive(Default)]
 pub struct Scoreboard {
-    objectives: HashMap<String, ScoreboardObjective<'static>>,
-    //  teams: HashMap<String, Team>,
+    objectives: HashMap<String, ScoreboardObjective>,
+    /// The scores of every score holder by objective, players hold scores by their name
+    scores: HashMap<String, HashMap<String, i32>>,
+    display_slots: HashMap<DisplaySlot, String>,
+    teams: HashMap<String, Team>,
+    /// The team of every score holder on one
+    team_of: HashMap<String, String>,
 }
 
 impl Scoreboard {
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            objectives: HashMap::new(),
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn objective(&self, name: &str) -> Option<&ScoreboardObjective> {
+        self.objectives.get(name)
+    }
+
+    /// The objectives sorted by their name
+    #[must_use]
+    pub fn objectives(&self) -> Vec<&ScoreboardObjective> {
+        let mut objectives: Vec<_> = self.objectives.values().collect();
+        objectives.sort_by(|a, b| a.name.cmp(&b.name));
+        objectives
+    }
+
+    pub async fn add_objective(
+        &mut self,
+        server: &Server,
+        objective: ScoreboardObjective,
+    ) -> Result<(), ScoreboardError> {
+        if self.objectives.contains_key(&objective.name) {
+            return Err(ScoreboardError::ObjectiveExists);
         }
+        server
+            .broadcast_packet_all(&objective.packet(Mode::Add))
+            .await;
+        self.objectives.insert(objective.name.clone(), objective);
+        Ok(())
     }
 
-    pub async fn add_objective<'a>(&mut self, world: &World, objective: ScoreboardObjective<'a>) {
-        if self.objectives.contains_key(objective.name) {
-            // Maybe make this an error ?
-            log::warn!(
-                "Tried to create Objective which does already exist, {}",
-                &objective.name
-            );
-            return;
+    /// Removes the objective together with its scores, it is taken off the display slots
+    pub async fn remove_objective(
+        &mut self,
+        server: &Server,
+        name: &str,
+    ) -> Result<(), ScoreboardError> {
+        let objective = self
+            .objectives
+            .remove(name)
+            .ok_or_else(|| ScoreboardError::UnknownObjective(name.to_string()))?;
+        self.display_slots.retain(|_, shown| shown != name);
+        for scores in self.scores.values_mut() {
+            scores.remove(name);
         }
-        world
-            .broadcast_packet_all(&CUpdateObjectives::new(
-                objective.name,
-                pumpkin_protocol::client::play::Mode::Add,
-                objective.display_name,
-                objective.render_type,
-                objective.number_format,
-            ))
+        self.scores.retain(|_, scores| !scores.is_empty());
+        server
+            .broadcast_packet_all(&objective.packet(Mode::Remove))
             .await;
-        world
-            .broadcast_packet_all(&CDisplayObjective::new(
-                pumpkin_protocol::client::play::DisplaySlot::Sidebar,
-                objective.name,
+        Ok(())
+    }
+
+    /// Changes how the objective is shown, `None` keeps it as it is
+    pub async fn modify_objective(
+        &mut self,
+        server: &Server,
+        name: &str,
+        display_name: Option<String>,
+        render_type: Option<RenderType>,
+    ) -> Result<(), ScoreboardError> {
+        let objective = self
+            .objectives
+            .get_mut(name)
+            .ok_or_else(|| ScoreboardError::UnknownObjective(name.to_string()))?;
+        if let Some(display_name) = display_name {
+            objective.display_name = display_name;
+        }
+        if let Some(render_type) = render_type {
+            objective.render_type = render_type;
+        }
+        server
+            .broadcast_packet_all(&objective.packet(Mode::Update))
+            .await;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn displayed_objective(&self, slot: DisplaySlot) -> Option<&str> {
+        self.display_slots.get(&slot).map(String::as_str)
+    }
+
+    /// Shows the objective in the slot, `None` clears the slot
+    pub async fn set_display_slot(
+        &mut self,
+        server: &Server,
+        slot: DisplaySlot,
+        objective: Option<&str>,
+    ) -> Result<(), ScoreboardError> {
+        match objective {
+            Some(name) => {
+                if !self.objectives.contains_key(name) {
+                    return Err(ScoreboardError::UnknownObjective(name.to_string()));
+                }
+                self.display_slots.insert(slot, name.to_string());
+            }
+            None => {
+                self.display_slots.remove(&slot);
+            }
+        }
+        server
+            .broadcast_packet_all(&CDisplayObjective::new(slot, objective.unwrap_or("")))
+            .await;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn score(&self, holder: &str, objective: &str) -> Option<i32> {
+        self.scores.get(holder)?.get(objective).copied()
+    }
+
+    /// The scores of the holder sorted by objective
+    #[must_use]
+    pub fn scores_of(&self, holder: &str) -> Vec<(&str, i32)> {
+        let mut scores: Vec<_> = self
+            .scores
+            .get(holder)
+            .into_iter()
+            .flatten()
+            .map(|(objective, score)| (objective.as_str(), *score))
+            .collect();
+        scores.sort_unstable();
+        scores
+    }
+
+    /// Everyone who has a score, sorted
+    #[must_use]
+    pub fn holders(&self) -> Vec<&str> {
+        let mut holders: Vec<_> = self.scores.keys().map(String::as_str).collect();
+        holders.sort_unstable();
+        holders
+    }
+
+    pub async fn set_score(
+        &mut self,
+        server: &Server,
+        holder: &str,
+        objective: &str,
+        value: i32,
+    ) -> Result<(), ScoreboardError> {
+        if !self.objectives.contains_key(objective) {
+            return Err(ScoreboardError::UnknownObjective(objective.to_string()));
+        }
+        self.scores
+            .entry(holder.to_string())
+            .or_default()
+            .insert(objective.to_string(), value);
+        server
+            .broadcast_packet_all(&CUpdateScore::new(
+                holder,
+                objective,
+                VarInt(value),
+                None,
+                None,
             ))
             .await;
+        Ok(())
     }
 
-    pub async fn update_score<'a>(&self, world: &World, score: ScoreboardScore<'a>) {
-        if self.objectives.contains_key(score.objective_name) {
-            log::warn!(
-                "Tried to place a score into a Objective which does not exist, {}",
-                &score.objective_name
-            );
+    /// Removes the score of the holder, `None` removes all of its scores
+    pub async fn reset_score(&mut self, server: &Server, holder: &str, objective: Option<&str>) {
+        let Some(scores) = self.scores.get_mut(holder) else {
             return;
+        };
+        match objective {
+            Some(objective) => {
+                scores.remove(objective);
+                if scores.is_empty() {
+                    self.scores.remove(holder);
+                }
+            }
+            None => {
+                self.scores.remove(holder);
+            }
         }
-        world
-            .broadcast_packet_all(&CUpdateScore::new(
-                score.entity_name,
-                score.objective_name,
-                score.value,
-                score.display_name,
-                score.number_format,
+        server
+            .broadcast_packet_all(&CResetScore::new(
+                holder.to_string(),
+                objective.map(ToString::to_string),
             ))
             .await;
     }
 
-    // pub fn add_team(&mut self, name: String) {
-    //     if self.teams.contains_key(&name) {
-    //         // Maybe make this an error ?
-    //         log::warn!("Tried to create Team which does already exist, {}", name);
-    //     }
-    // }
-}
+    fn objectives_with(&self, criterion: ObjectiveCriterion) -> Vec<String> {
+        self.objectives
+            .values()
+            .filter(|objective| objective.criterion == criterion)
+            .map(|objective| objective.name.clone())
+            .collect()
+    }
 
-pub struct ScoreboardObjective<'a> {
-    name: &'a str,
-    display_name: TextComponent<'a>,
-    render_type: RenderType,
-    number_format: Option<NumberFormat<'a>>,
-}
+    /// Adds to the scores of all objectives counting the criterion, like a death to `deathCount`
+    pub async fn add_to_criterion(
+        &mut self,
+        server: &Server,
+        holder: &str,
+        criterion: ObjectiveCriterion,
+        amount: i32,
+    ) {
+        for objective in self.objectives_with(criterion) {
+            let score = self.score(holder, &objective).unwrap_or(0);
+            // The objective exists, it was just looked up
+            let _ = self
+                .set_score(server, holder, &objective, score.saturating_add(amount))
+                .await;
+        }
+    }
+
+    /// Sets the scores of all objectives following the criterion, like the health of a player
+    pub async fn set_criterion(
+        &mut self,
+        server: &Server,
+        holder: &str,
+        criterion: ObjectiveCriterion,
+        value: i32,
+    ) {
+        for objective in self.objectives_with(criterion) {
+            let _ = self.set_score(server, holder, &objective, value).await;
+        }
+    }
 
-impl<'a> ScoreboardObjective<'a> {
     #[must_use]
-    pub const fn new(
-        name: &'a str,
-        display_name: TextComponent<'a>,
-        render_type: RenderType,
-        number_format: Option<NumberFormat<'a>>,
-    ) -> Self {
-        Self {
-            name,
-            display_name,
-            render_type,
-            number_format,
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    /// The teams sorted by their name
+    #[must_use]
+    pub fn teams(&self) -> Vec<&Team> {
+        let mut teams: Vec<_> = self.teams.values().collect();
+        teams.sort_by(|a, b| a.name.cmp(&b.name));
+        teams
+    }
+
+    /// The team the score holder is on
+    #[must_use]
+    pub fn team_of(&self, holder: &str) -> Option<&Team> {
+        self.teams.get(self.team_of.get(holder)?)
+    }
+
+    pub async fn add_team(&mut self, server: &Server, team: Team) -> Result<(), ScoreboardError> {
+        if self.teams.contains_key(&team.name) {
+            return Err(ScoreboardError::TeamExists);
         }
+        server.broadcast_packet_all(&team.create_packet()).await;
+        self.teams.insert(team.name.clone(), team);
+        Ok(())
     }
-}
 
-pub struct ScoreboardScore<'a> {
-    entity_name: &'a str,
-    objective_name: &'a str,
-    value: VarInt,
-    display_name: Option<TextComponent<'a>>,
-    number_format: Option<NumberFormat<'a>>,
-}
+    /// Removes the team, its members are left without a team
+    pub async fn remove_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+    ) -> Result<(), ScoreboardError> {
+        let team = self
+            .teams
+            .remove(name)
+            .ok_or_else(|| ScoreboardError::UnknownTeam(name.to_string()))?;
+        for member in &team.members {
+            self.team_of.remove(member);
+        }
+        server
+            .broadcast_packet_all(&CSetPlayerTeam::new(&team.name, TeamMethod::Remove))
+            .await;
+        Ok(())
+    }
 
-impl<'a> ScoreboardScore<'a> {
+    /// Changes the options of the team and sends them to the players
+    pub async fn modify_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        modify: impl FnOnce(&mut Team),
+    ) -> Result<(), ScoreboardError> {
+        let team = self
+            .teams
+            .get_mut(name)
+            .ok_or_else(|| ScoreboardError::UnknownTeam(name.to_string()))?;
+        modify(team);
+        server.broadcast_packet_all(&team.update_packet()).await;
+        Ok(())
+    }
+
+    /// Puts the score holders on the team, they leave the teams they were on before
+    pub async fn join_team(
+        &mut self,
+        server: &Server,
+        name: &str,
+        holders: &[String],
+    ) -> Result<(), ScoreboardError> {
+        if !self.teams.contains_key(name) {
+            return Err(ScoreboardError::UnknownTeam(name.to_string()));
+        }
+        self.leave_team(server, holders).await;
+        if let Some(team) = self.teams.get_mut(name) {
+            team.members.extend(holders.iter().cloned());
+            for holder in holders {
+                self.team_of.insert(holder.clone(), name.to_string());
+            }
+            server
+                .broadcast_packet_all(&CSetPlayerTeam::new(name, TeamMethod::AddEntities(holders)))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Takes the score holders off their teams, returns how many were on one
+    pub async fn leave_team(&mut self, server: &Server, holders: &[String]) -> usize {
+        let mut left: HashMap<String, Vec<String>> = HashMap::new();
+        for holder in holders {
+            if let Some(team) = self.team_of.remove(holder) {
+                left.entry(team).or_default().push(holder.clone());
+            }
+        }
+        let mut count = 0;
+        for (name, members) in left {
+            count += members.len();
+            if let Some(team) = self.teams.get_mut(&name) {
+                team.members.retain(|member| !members.contains(member));
+            }
+            server
+                .broadcast_packet_all(&CSetPlayerTeam::new(
+                    &name,
+                    TeamMethod::RemoveEntities(&members),
+                ))
+                .await;
+        }
+        count
+    }
+
+    /// Whether the attacker may hurt the victim, members of a team without friendly fire can't
+    /// hurt each other
     #[must_use]
-    pub const fn new(
-        entity_name: &'a str,
-        objective_name: &'a str,
-        value: VarInt,
-        display_name: Option<TextComponent<'a>>,
-        number_format: Option<NumberFormat<'a>>,
-    ) -> Self {
-        Self {
-            entity_name,
-            objective_name,
-            value,
-            display_name,
-            number_format,
+    pub fn can_harm(&self, attacker: &str, victim: &str) -> bool {
+        match (self.team_of.get(attacker), self.team_of.get(victim)) {
+            (Some(attacker_team), Some(victim_team)) if attacker_team == victim_team => self
+                .teams
+                .get(attacker_team)
+                .is_none_or(|team| team.allow_friendly_fire),
+            _ => true,
+        }
+    }
+
+    /// Sends the objectives, scores, display slots and teams to a player who joined
+    pub async fn init_client(&self, client: &Client) {
+        for team in self.teams.values() {
+            client.send_packet(&team.create_packet()).await;
+        }
+        for objective in self.objectives.values() {
+            client.send_packet(&objective.packet(Mode::Add)).await;
+        }
+        for (holder, scores) in &self.scores {
+            for (objective, score) in scores {
+                client
+                    .send_packet(&CUpdateScore::new(
+                        holder,
+                        objective,
+                        VarInt(*score),
+                        None,
+                        None,
+                    ))
+                    .await;
+            }
         }
+        for (slot, objective) in &self.display_slots {
+            client
+                .send_packet(&CDisplayObjective::new(*slot, objective))
+                .await;
+        }
+    }
+
+    /// Reads the scoreboard like vanilla saves it in `scoreboard.dat`, unknown criteria count as
+    /// dummy
+    #[must_use]
+    pub fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut scoreboard = Self::new();
+        let compounds = |name: &str| {
+            nbt.get_list(name)
+                .into_iter()
+                .flatten()
+                .filter_map(NbtTag::extract_compound)
+        };
+        for objective in compounds("Objectives") {
+            let Some(name) = objective.get_string("Name") else {
+                continue;
+            };
+            let criterion = objective
+                .get_string("CriteriaName")
+                .and_then(|criterion| ObjectiveCriterion::from_name(criterion))
+                .unwrap_or(ObjectiveCriterion::Dummy);
+            let display_name = objective
+                .get_string("DisplayName")
+                .map_or_else(|| name.clone(), |json| text_from_json(json));
+            let mut loaded = ScoreboardObjective::new(name.clone(), criterion, display_name);
+            if objective.get_string("RenderType").map(String::as_str) == Some("hearts") {
+                loaded.render_type = RenderType::Hearts;
+            }
+            scoreboard.objectives.insert(name.clone(), loaded);
+        }
+        for score in compounds("PlayerScores") {
+            let (Some(holder), Some(objective), Some(value)) = (
+                score.get_string("Name"),
+                score.get_string("Objective"),
+                score.get_int("Score"),
+            ) else {
+                continue;
+            };
+            if scoreboard.objectives.contains_key(objective) {
+                scoreboard
+                    .scores
+                    .entry(holder.clone())
+                    .or_default()
+                    .insert(objective.clone(), value);
+            }
+        }
+        if let Some(slots) = nbt.get_compound("DisplaySlots") {
+            for (slot, name) in DISPLAY_SLOTS {
+                if let Some(objective) = slots.get_string(name) {
+                    if scoreboard.objectives.contains_key(objective) {
+                        scoreboard.display_slots.insert(slot, objective.clone());
+                    }
+                }
+            }
+        }
+        for team in compounds("Teams").filter_map(Team::from_nbt) {
+            for member in &team.members {
+                scoreboard.team_of.insert(member.clone(), team.name.clone());
+            }
+            scoreboard.teams.insert(team.name.clone(), team);
+        }
+        scoreboard
+    }
+
+    #[must_use]
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        let objectives = self
+            .objectives()
+            .into_iter()
+            .map(|objective| {
+                let mut compound = NbtCompound::new();
+                compound.put("Name".to_string(), NbtTag::String(objective.name.clone()));
+                compound.put(
+                    "CriteriaName".to_string(),
+                    NbtTag::String(objective.criterion.name().to_string()),
+                );
+                compound.put(
+                    "DisplayName".to_string(),
+                    NbtTag::String(
+                        serde_json::json!({ "text": objective.display_name }).to_string(),
+                    ),
+                );
+                let render_type = match objective.render_type {
+                    RenderType::Integer => "integer",
+                    RenderType::Hearts => "hearts",
+                };
+                compound.put(
+                    "RenderType".to_string(),
+                    NbtTag::String(render_type.to_string()),
+                );
+                NbtTag::Compound(compound)
+            })
+            .collect();
+        nbt.put("Objectives".to_string(), NbtTag::List(objectives));
+        let mut scores = Vec::new();
+        for holder in self.holders() {
+            for (objective, score) in self.scores_of(holder) {
+                let mut compound = NbtCompound::new();
+                compound.put("Name".to_string(), NbtTag::String(holder.to_string()));
+                compound.put(
+                    "Objective".to_string(),
+                    NbtTag::String(objective.to_string()),
+                );
+                compound.put("Score".to_string(), NbtTag::Int(score));
+                compound.put("Locked".to_string(), NbtTag::Byte(1));
+                scores.push(NbtTag::Compound(compound));
+            }
+        }
+        nbt.put("PlayerScores".to_string(), NbtTag::List(scores));
+        let mut slots = NbtCompound::new();
+        for (slot, objective) in &self.display_slots {
+            slots.put(
+                display_slot_name(*slot).to_string(),
+                NbtTag::String(objective.clone()),
+            );
+        }
+        nbt.put("DisplaySlots".to_string(), NbtTag::Compound(slots));
+        let teams = self
+            .teams()
+            .into_iter()
+            .map(|team| NbtTag::Compound(team.to_nbt()))
+            .collect();
+        nbt.put("Teams".to_string(), NbtTag::List(teams));
+        nbt
+    }
+}
+
+/// The text of a saved text component, other text than plain text is lost
+pub(crate) fn text_from_json(json: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(value) => value
+            .get("text")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        Err(_) => json.to_string(),
     }
 }
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, sync::Arc};
+
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    text::TextComponent,
+    GameMode,
+};
+use pumpkin_world::{
+    block::{
+        block_registry::{get_block_by_state_id, Block},
+        BlockFace,
+    },
+    world_info::game_rules::{BoolRule, IntRule},
+};
+
+use crate::entity::{
+    mob::is_monster,
+    player::{Player, DEEP_SLEEP_TICKS},
+};
+
+use super::World;
+
+/// How far monsters keep players in a bed awake, horizontally and vertically
+const MONSTER_RANGE: (f64, f64) = (8.0, 5.0);
+
+/// Whether the block is one of the beds
+#[must_use]
+pub fn is_bed(block: &Block) -> bool {
+    block.name.ends_with("_bed")
+}
+
+fn property<'a>(block: &'a Block, state_id: u16, name: &str) -> Option<&'a str> {
+    block
+        .state_properties(state_id)
+        .into_iter()
+        .find_map(|(property, value)| (property == name).then_some(value))
+}
+
+/// The head and the foot of the bed with one of its halves at the position, beds face from their
+/// foot towards their head
+pub fn bed_halves(
+    block: &Block,
+    state_id: u16,
+    position: WorldPosition,
+) -> Option<(WorldPosition, WorldPosition)> {
+    let facing = property(block, state_id, "facing").and_then(BlockFace::from_name)?;
+    if property(block, state_id, "part") == Some("foot") {
+        Some((WorldPosition(position.0 + facing.to_offset()), position))
+    } else {
+        Some((position, WorldPosition(position.0.sub(&facing.to_offset()))))
+    }
+}
+
+/// How many of the players have to sleep to skip the night, like vanilla's
+/// `SleepStatus.sleepersNeeded`
+fn sleepers_needed(active: usize, percentage: i32) -> usize {
+    #[allow(clippy::cast_precision_loss)]
+    let needed = (active as f64 * f64::from(percentage) / 100.0).ceil() as usize;
+    needed.max(1)
+}
+
+/// The players who could sleep and the players sleeping, spectators never count
+fn count_sleeping(players: &[Arc<Player>]) -> (usize, usize) {
+    let active = players
+        .iter()
+        .filter(|player| player.gamemode.load() != GameMode::Spectator)
+        .count();
+    let sleeping = players.iter().filter(|player| player.is_sleeping()).count();
+    (active, sleeping)
+}
+
+impl World {
+    /// Marks both halves of the bed with its head at the position as occupied or free
+    pub async fn set_bed_occupied(&self, head: WorldPosition, occupied: bool) {
+        let Ok(state_id) = self.get_block_state_id(head).await else {
+            return;
+        };
+        let Some(block) = get_block_by_state_id(state_id).filter(|block| is_bed(block)) else {
+            return;
+        };
+        let Some((head, foot)) = bed_halves(block, state_id, head) else {
+            return;
+        };
+        for half in [head, foot] {
+            let Ok(state_id) = self.get_block_state_id(half).await else {
+                continue;
+            };
+            if get_block_by_state_id(state_id).is_none_or(|other| other.id != block.id) {
+                continue;
+            }
+            let mut properties: HashMap<String, String> = block
+                .state_properties(state_id)
+                .into_iter()
+                .map(|(property, value)| (property.to_string(), value.to_string()))
+                .collect();
+            properties.insert("occupied".to_string(), occupied.to_string());
+            self.set_block_state(half, block.state_with_properties(&properties))
+                .await;
+        }
+    }
+
+    /// Whether monsters near the bed keep players from sleeping in it
+    pub async fn monsters_near(&self, bed: WorldPosition) -> bool {
+        let center = Vector3::new(
+            f64::from(bed.0.x) + 0.5,
+            f64::from(bed.0.y),
+            f64::from(bed.0.z) + 0.5,
+        );
+        let (horizontal, vertical) = MONSTER_RANGE;
+        self.mobs.lock().await.values().any(|mob| {
+            let entity = mob.entity();
+            let pos = entity.pos.load();
+            is_monster(&entity.entity_type)
+                && (pos.x - center.x).abs() <= horizontal
+                && (pos.y - center.y).abs() <= vertical
+                && (pos.z - center.z).abs() <= horizontal
+        })
+    }
+
+    /// Players can sleep at night and during thunderstorms, like when vanilla's `Level.isDay` is
+    /// false
+    pub async fn can_sleep(&self) -> bool {
+        self.level_time.lock().await.is_night() || self.weather.lock().await.is_thundering()
+    }
+
+    /// Tells every player how many players sleep and how many have to, like vanilla's
+    /// `ServerLevel.announceSleepStatus`
+    pub async fn announce_sleeping(&self) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let percentage = self
+            .game_rules
+            .lock()
+            .await
+            .get_int(IntRule::PlayersSleepingPercentage);
+        let (active, sleeping) = count_sleeping(&players);
+        let needed = sleepers_needed(active, percentage);
+        let message = if sleeping >= needed {
+            "Sleeping through this night".to_string()
+        } else {
+            format!("{sleeping}/{needed} players sleeping")
+        };
+        let message = TextComponent::text(&message);
+        for player in &players {
+            player.send_action_bar_message(&message).await;
+        }
+    }
+
+    /// Wakes players when the night is over or their bed is gone, and skips to the next morning
+    /// once enough players slept long enough, like vanilla's `ServerLevel.tick`
+    pub(super) async fn tick_sleeping(&self) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        if !players.iter().any(|player| player.is_sleeping()) {
+            return;
+        }
+
+        let can_sleep = self.can_sleep().await;
+        let mut woken = false;
+        for player in &players {
+            let Some(bed) = player.sleeping_position.load() else {
+                continue;
+            };
+            let in_bed = self.get_block(bed).await.is_ok_and(is_bed);
+            if !can_sleep || !in_bed {
+                player.wake_up().await;
+                woken = true;
+            }
+        }
+        if woken {
+            self.announce_sleeping().await;
+            return;
+        }
+
+        let (daylight_cycle, weather_cycle, percentage) = {
+            let game_rules = self.game_rules.lock().await;
+            (
+                game_rules.get_bool(BoolRule::DoDaylightCycle),
+                game_rules.get_bool(BoolRule::DoWeatherCycle),
+                game_rules.get_int(IntRule::PlayersSleepingPercentage),
+            )
+        };
+        let (active, sleeping) = count_sleeping(&players);
+        let needed = sleepers_needed(active, percentage);
+        let slept = players
+            .iter()
+            .filter(|player| {
+                player.is_sleeping()
+                    && player
+                        .sleep_ticks
+                        .load(std::sync::atomic::Ordering::Relaxed)
+                        >= DEEP_SLEEP_TICKS
+            })
+            .count();
+        if sleeping < needed || slept < needed {
+            return;
+        }
+        if daylight_cycle {
+            let mut level_time = self.level_time.lock().await;
+            level_time.skip_to_morning();
+            level_time.send_time(self).await;
+        }
+        if weather_cycle {
+            // The rain fades out by itself
+            let mut weather = self.weather.lock().await;
+            if weather.is_raining() {
+                weather.reset();
+            }
+        }
+        for player in &players {
+            player.wake_up().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+    use pumpkin_world::block::{block_registry::get_block, block_state::BlockStateInput};
+
+    use super::{bed_halves, sleepers_needed};
+
+    #[test]
+    fn at_least_one_player_has_to_sleep() {
+        assert_eq!(sleepers_needed(3, 100), 3);
+        assert_eq!(sleepers_needed(3, 50), 2);
+        assert_eq!(sleepers_needed(3, 0), 1);
+        assert_eq!(sleepers_needed(0, 100), 1);
+    }
+
+    #[test]
+    fn beds_face_from_the_foot_to_the_head() {
+        let bed = get_block("minecraft:red_bed").unwrap();
+        let state = |input| BlockStateInput::parse(input).unwrap().state_id;
+        let foot = WorldPosition(Vector3::new(0, 64, 0));
+        let head = WorldPosition(Vector3::new(1, 64, 0));
+        assert_eq!(
+            bed_halves(bed, state("minecraft:red_bed[facing=east,part=foot]"), foot),
+            Some((head, foot))
+        );
+        assert_eq!(
+            bed_halves(bed, state("minecraft:red_bed[facing=east,part=head]"), head),
+            Some((head, foot))
+        );
+    }
+}
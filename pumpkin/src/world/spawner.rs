@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+use pumpkin_entity::entity_type::EntityType;
+use pumpkin_protocol::{
+    client::play::{CBlockAction, CBlockEntityData, CWorldEvent},
+    codec::var_int::VarInt,
+};
+use pumpkin_world::block::{
+    block_entity::{block_entity_type_id, spawner::SpawnAttempt},
+    block_registry::get_block,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    entity::mob::{mob_size, MobEntity},
+    server::Server,
+};
+
+use super::World;
+
+impl World {
+    /// Spawns the mobs of a spawner whose delay ran out, like vanilla's `BaseSpawner.serverTick`.
+    /// Mobs only spawn where they fit, and not while too many mobs of the type are around
+    pub(super) async fn spawn_from_spawner(
+        self: &Arc<Self>,
+        position: WorldPosition,
+        attempt: &SpawnAttempt,
+        server: &Server,
+    ) {
+        let Some(entity_type) = EntityType::from_name(&attempt.entity_id) else {
+            // Spawners of unknown mobs wait and try the next mob of their potentials
+            self.reset_spawner(position).await;
+            return;
+        };
+        let (width, height) = mob_size(&entity_type);
+
+        let mut nearby = self
+            .count_mobs_near(&entity_type, position, attempt.spawn_range)
+            .await;
+        let mut spawned = false;
+        for spawn_position in &attempt.positions {
+            if nearby >= attempt.max_nearby_entities {
+                break;
+            }
+            if !self
+                .level
+                .spawner_allows(attempt, *spawn_position, width, height)
+            {
+                continue;
+            }
+            let yaw = thread_rng().gen_range(0.0..360.0);
+            let mob = Arc::new(MobEntity::new(
+                server.new_entity_id(),
+                self.clone(),
+                entity_type.clone(),
+                *spawn_position,
+                yaw,
+            ));
+            self.equip_spawned_mob(&mob).await;
+            self.add_mob(mob).await;
+            // Flames and smoke around the spawner
+            self.broadcast_packet_all(&CWorldEvent::new(2004, &position, 0, false))
+                .await;
+            nearby += 1;
+            spawned = true;
+        }
+
+        if spawned || nearby >= attempt.max_nearby_entities {
+            self.reset_spawner(position).await;
+        }
+    }
+
+    /// Mobs of the type in the area a spawner spawns in
+    async fn count_mobs_near(
+        &self,
+        entity_type: &EntityType,
+        position: WorldPosition,
+        range: i32,
+    ) -> i32 {
+        let center = Vector3::new(
+            f64::from(position.0.x) + 0.5,
+            f64::from(position.0.y) + 0.5,
+            f64::from(position.0.z) + 0.5,
+        );
+        let reach = f64::from(range) + 0.5;
+        let mobs = self.mobs.lock().await;
+        mobs.values()
+            .filter(|mob| mob.entity().entity_type.clone() as i32 == entity_type.clone() as i32)
+            .filter(|mob| {
+                let pos = mob.entity().pos.load();
+                (pos.x - center.x).abs() <= reach
+                    && (pos.y - center.y).abs() <= reach
+                    && (pos.z - center.z).abs() <= reach
+            })
+            .count() as i32
+    }
+
+    /// Waits for the next spawn of the spawner and shows players the mob it spawns next
+    async fn reset_spawner(&self, position: WorldPosition) {
+        let Some(data) = self.level.reset_spawner(position).await else {
+            return;
+        };
+        if let Some(block_entity_type) = block_entity_type_id("minecraft:mob_spawner") {
+            self.broadcast_packet_all(&CBlockEntityData::new(
+                &position,
+                VarInt(block_entity_type),
+                data,
+            ))
+            .await;
+        }
+        // Makes the spinning mob start over
+        if let Some(spawner) = get_block("minecraft:spawner") {
+            self.broadcast_packet_all(&CBlockAction::new(
+                &position,
+                1,
+                0,
+                VarInt(spawner.id.into()),
+            ))
+            .await;
+        }
+    }
+}
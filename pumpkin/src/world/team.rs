@@ -0,0 +1,304 @@
+use pumpkin_core::text::{color::NamedColor, TextComponent};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{
+    client::play::{
+        CSetPlayerTeam, TeamInfo, TeamMethod, FRIENDLY_FIRE, SEE_FRIENDLY_INVISIBLES,
+        TEAM_COLOR_RESET,
+    },
+    codec::var_int::VarInt,
+};
+
+use super::scoreboard::text_from_json;
+
+/// The team colors by the names commands and the saved scoreboard know them by
+pub const TEAM_COLORS: [(NamedColor, &str); 16] = [
+    (NamedColor::Black, "black"),
+    (NamedColor::DarkBlue, "dark_blue"),
+    (NamedColor::DarkGreen, "dark_green"),
+    (NamedColor::DarkAqua, "dark_aqua"),
+    (NamedColor::DarkRed, "dark_red"),
+    (NamedColor::DarkPurple, "dark_purple"),
+    (NamedColor::Gold, "gold"),
+    (NamedColor::Gray, "gray"),
+    (NamedColor::DarkGray, "dark_gray"),
+    (NamedColor::Blue, "blue"),
+    (NamedColor::Green, "green"),
+    (NamedColor::Aqua, "aqua"),
+    (NamedColor::Red, "red"),
+    (NamedColor::LightPurple, "light_purple"),
+    (NamedColor::Yellow, "yellow"),
+    (NamedColor::White, "white"),
+];
+
+#[must_use]
+pub fn team_color_name(color: NamedColor) -> &'static str {
+    TEAM_COLORS
+        .iter()
+        .find(|(other, _)| *other == color)
+        .map_or("reset", |(_, name)| *name)
+}
+
+/// Who sees the name tags of the members, like vanilla's `Team.Visibility`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NameTagVisibility {
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+impl NameTagVisibility {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::HideForOtherTeams,
+        Self::HideForOwnTeam,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::HideForOtherTeams => "hideForOtherTeams",
+            Self::HideForOwnTeam => "hideForOwnTeam",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+/// Who pushes the members around, like vanilla's `Team.CollisionRule`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CollisionRule {
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+impl CollisionRule {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::PushOtherTeams,
+        Self::PushOwnTeam,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::PushOtherTeams => "pushOtherTeams",
+            Self::PushOwnTeam => "pushOwnTeam",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+/// A team of score holders, like vanilla's `PlayerTeam`. The client colors the names of the
+/// members and applies the name tag and collision rules itself
+pub struct Team {
+    pub name: String,
+    pub display_name: String,
+    pub color: Option<NamedColor>,
+    pub prefix: String,
+    pub suffix: String,
+    pub allow_friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: NameTagVisibility,
+    pub collision_rule: CollisionRule,
+    /// The score holders on the team, players by their name
+    pub(super) members: Vec<String>,
+}
+
+impl Team {
+    #[must_use]
+    pub fn new(name: String, display_name: String) -> Self {
+        Self {
+            name,
+            display_name,
+            color: None,
+            prefix: String::new(),
+            suffix: String::new(),
+            allow_friendly_fire: true,
+            see_friendly_invisibles: true,
+            name_tag_visibility: NameTagVisibility::Always,
+            collision_rule: CollisionRule::Always,
+            members: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn members(&self) -> &[String] {
+        &self.members
+    }
+
+    /// The display name in the color of the team
+    #[must_use]
+    pub fn formatted_name(&self) -> TextComponent<'static> {
+        let name = TextComponent::text_string(self.display_name.clone());
+        match self.color {
+            Some(color) => name.color_named(color),
+            None => name,
+        }
+    }
+
+    fn info(&self) -> TeamInfo<'_> {
+        let mut friendly_flags = 0;
+        if self.allow_friendly_fire {
+            friendly_flags |= FRIENDLY_FIRE;
+        }
+        if self.see_friendly_invisibles {
+            friendly_flags |= SEE_FRIENDLY_INVISIBLES;
+        }
+        TeamInfo {
+            display_name: TextComponent::text(&self.display_name),
+            friendly_flags,
+            name_tag_visibility: self.name_tag_visibility.name(),
+            collision_rule: self.collision_rule.name(),
+            color: VarInt(self.color.map_or(TEAM_COLOR_RESET, |color| color as i32)),
+            prefix: TextComponent::text(&self.prefix),
+            suffix: TextComponent::text(&self.suffix),
+        }
+    }
+
+    pub(super) fn create_packet(&self) -> CSetPlayerTeam<'_> {
+        CSetPlayerTeam::new(&self.name, TeamMethod::Create(self.info(), &self.members))
+    }
+
+    pub(super) fn update_packet(&self) -> CSetPlayerTeam<'_> {
+        CSetPlayerTeam::new(&self.name, TeamMethod::Update(self.info()))
+    }
+
+    /// Reads a team like vanilla saves it in the `Teams` of `scoreboard.dat`
+    pub(super) fn from_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let name = nbt.get_string("Name")?;
+        let display_name = nbt
+            .get_string("DisplayName")
+            .map_or_else(|| name.clone(), |json| text_from_json(json));
+        let mut team = Self::new(name.clone(), display_name);
+        team.color = nbt.get_string("TeamColor").and_then(|color| {
+            TEAM_COLORS
+                .iter()
+                .find(|(_, name)| *name == color.as_str())
+                .map(|(color, _)| *color)
+        });
+        team.prefix = nbt
+            .get_string("MemberNamePrefix")
+            .map(|json| text_from_json(json))
+            .unwrap_or_default();
+        team.suffix = nbt
+            .get_string("MemberNameSuffix")
+            .map(|json| text_from_json(json))
+            .unwrap_or_default();
+        team.allow_friendly_fire = nbt.get_bool("AllowFriendlyFire").unwrap_or(true);
+        team.see_friendly_invisibles = nbt.get_bool("SeeFriendlyInvisibles").unwrap_or(true);
+        if let Some(rule) = nbt
+            .get_string("NameTagVisibility")
+            .and_then(|rule| NameTagVisibility::from_name(rule))
+        {
+            team.name_tag_visibility = rule;
+        }
+        if let Some(rule) = nbt
+            .get_string("CollisionRule")
+            .and_then(|rule| CollisionRule::from_name(rule))
+        {
+            team.collision_rule = rule;
+        }
+        team.members = nbt
+            .get_list("Players")
+            .into_iter()
+            .flatten()
+            .filter_map(|player| match player {
+                NbtTag::String(player) => Some(player.clone()),
+                _ => None,
+            })
+            .collect();
+        Some(team)
+    }
+
+    pub(super) fn to_nbt(&self) -> NbtCompound {
+        let json = |text: &str| NbtTag::String(serde_json::json!({ "text": text }).to_string());
+        let mut nbt = NbtCompound::new();
+        nbt.put("Name".to_string(), NbtTag::String(self.name.clone()));
+        nbt.put("DisplayName".to_string(), json(&self.display_name));
+        if let Some(color) = self.color {
+            nbt.put(
+                "TeamColor".to_string(),
+                NbtTag::String(team_color_name(color).to_string()),
+            );
+        }
+        nbt.put("MemberNamePrefix".to_string(), json(&self.prefix));
+        nbt.put("MemberNameSuffix".to_string(), json(&self.suffix));
+        nbt.put(
+            "AllowFriendlyFire".to_string(),
+            NbtTag::Byte(i8::from(self.allow_friendly_fire)),
+        );
+        nbt.put(
+            "SeeFriendlyInvisibles".to_string(),
+            NbtTag::Byte(i8::from(self.see_friendly_invisibles)),
+        );
+        nbt.put(
+            "NameTagVisibility".to_string(),
+            NbtTag::String(self.name_tag_visibility.name().to_string()),
+        );
+        nbt.put(
+            "CollisionRule".to_string(),
+            NbtTag::String(self.collision_rule.name().to_string()),
+        );
+        nbt.put(
+            "Players".to_string(),
+            NbtTag::List(self.members.iter().cloned().map(NbtTag::String).collect()),
+        );
+        nbt
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::text::color::NamedColor;
+
+    use super::{team_color_name, CollisionRule, NameTagVisibility, Team};
+
+    #[test]
+    fn teams_are_saved_like_vanilla() {
+        let mut team = Team::new("red".to_string(), "Red Team".to_string());
+        team.color = Some(NamedColor::Red);
+        team.prefix = "[R] ".to_string();
+        team.allow_friendly_fire = false;
+        team.name_tag_visibility = NameTagVisibility::HideForOtherTeams;
+        team.collision_rule = CollisionRule::PushOwnTeam;
+        team.members = vec!["Steve".to_string()];
+
+        let loaded = Team::from_nbt(&team.to_nbt()).unwrap();
+        assert_eq!(loaded.name, "red");
+        assert_eq!(loaded.display_name, "Red Team");
+        assert_eq!(loaded.color, Some(NamedColor::Red));
+        assert_eq!(loaded.prefix, "[R] ");
+        assert!(!loaded.allow_friendly_fire);
+        assert!(loaded.see_friendly_invisibles);
+        assert!(loaded.name_tag_visibility == NameTagVisibility::HideForOtherTeams);
+        assert!(loaded.collision_rule == CollisionRule::PushOwnTeam);
+        assert_eq!(loaded.members(), ["Steve"]);
+    }
+
+    #[test]
+    fn rules_are_known_by_their_vanilla_names() {
+        assert!(
+            NameTagVisibility::from_name("hideForOwnTeam")
+                == Some(NameTagVisibility::HideForOwnTeam)
+        );
+        assert!(CollisionRule::from_name("pushOtherTeams") == Some(CollisionRule::PushOtherTeams));
+        assert!(CollisionRule::from_name("push_other_teams").is_none());
+        assert_eq!(team_color_name(NamedColor::LightPurple), "light_purple");
+    }
+}
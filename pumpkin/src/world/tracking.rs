@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use pumpkin_protocol::client::play::CRemoveEntities;
+
+use crate::entity::{
+    any_entity::AnyEntity,
+    player::Player,
+    tracker::{tracking_range, TrackedEntity},
+    Entity,
+};
+
+use super::{player_chunker::get_view_distance, World};
+
+impl World {
+    /// The players together with how far they see in blocks
+    async fn players_with_view_distance(&self) -> Vec<(Arc<Player>, i32)> {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let mut viewers = Vec::with_capacity(players.len());
+        for player in players {
+            let view_distance = i32::from(get_view_distance(&player).await.get()) * 16;
+            viewers.push((player, view_distance));
+        }
+        viewers
+    }
+
+    /// Every entity of the world
+    pub async fn all_entities(&self) -> Vec<AnyEntity> {
+        let mut entities = Vec::new();
+        for player in self.current_players.lock().await.values() {
+            entities.push(AnyEntity::Player(player.clone()));
+        }
+        for item_entity in self.item_entities.lock().await.values() {
+            entities.push(AnyEntity::Item(item_entity.clone()));
+        }
+        for orb in self.experience_orbs.lock().await.values() {
+            entities.push(AnyEntity::Other(orb.clone()));
+        }
+        for horse_entity in self.horse_entities.lock().await.values() {
+            entities.push(AnyEntity::Other(horse_entity.clone()));
+        }
+        for item_frame in self.item_frames.lock().await.values() {
+            entities.push(AnyEntity::Other(item_frame.clone()));
+        }
+        for armor_stand in self.armor_stands.lock().await.values() {
+            entities.push(AnyEntity::Other(armor_stand.clone()));
+        }
+        for falling_block in self.falling_blocks.lock().await.values() {
+            entities.push(AnyEntity::Other(falling_block.clone()));
+        }
+        for tnt in self.primed_tnt.lock().await.values() {
+            entities.push(AnyEntity::Other(tnt.clone()));
+        }
+        for projectile in self.projectiles.lock().await.values() {
+            entities.push(AnyEntity::Other(projectile.clone()));
+        }
+        for mob in self.mobs.lock().await.values() {
+            entities.push(AnyEntity::Mob(mob.clone()));
+        }
+        for bolt in self.lightning_bolts.lock().await.values() {
+            entities.push(AnyEntity::Other(bolt.clone()));
+        }
+        entities
+    }
+
+    /// Every entity of the world players can see
+    async fn tracked_entities(&self) -> Vec<Arc<dyn TrackedEntity>> {
+        self.all_entities()
+            .await
+            .into_iter()
+            .map(AnyEntity::into_tracked)
+            .collect()
+    }
+
+    /// Spawns the entities which came within the tracking range of players for them and despawns
+    /// the ones which left it, like vanilla's `ChunkMap.tick`
+    pub(super) async fn update_entity_tracking(&self) {
+        let viewers = self.players_with_view_distance().await;
+        for tracked in self.tracked_entities().await {
+            Self::update_tracking(tracked.as_ref(), &viewers).await;
+        }
+    }
+
+    /// Shows a new entity to the players within its tracking range right away, instead of at the
+    /// end of the tick
+    pub async fn start_tracking(&self, tracked: &dyn TrackedEntity) {
+        let viewers = self.players_with_view_distance().await;
+        Self::update_tracking(tracked, &viewers).await;
+    }
+
+    /// Like vanilla's `TrackedEntity.updatePlayer`, players only see entities horizontally within
+    /// the tracking range of the entity type and their view distance
+    async fn update_tracking(tracked: &dyn TrackedEntity, viewers: &[(Arc<Player>, i32)]) {
+        let entity = tracked.tracked_entity();
+        let position = entity.pos.load();
+        let range = tracking_range(&entity.entity_type) * 16;
+        for (player, view_distance) in viewers {
+            let player_id = player.entity_id();
+            if player_id == entity.entity_id {
+                continue;
+            }
+            let range = f64::from(range.min(*view_distance));
+            let offset = player.living_entity.entity.pos.load().sub(&position);
+            if offset.x * offset.x + offset.z * offset.z <= range * range {
+                if entity.tracker.start(player_id) {
+                    tracked.spawn_for(&player.client).await;
+                }
+            } else if entity.tracker.stop(player_id) {
+                player
+                    .client
+                    .send_packet(&CRemoveEntities::new(&[entity.entity_id.into()]))
+                    .await;
+            }
+        }
+    }
+
+    /// Shows the player the entities within their tracking ranges, when it joins the world or
+    /// respawns
+    pub async fn track_entities_for(&self, player: &Arc<Player>) {
+        let view_distance = i32::from(get_view_distance(player).await.get()) * 16;
+        let viewers = [(player.clone(), view_distance)];
+        for tracked in self.tracked_entities().await {
+            Self::update_tracking(tracked.as_ref(), &viewers).await;
+        }
+    }
+
+    /// Despawns the entity for every player who sees it
+    pub async fn stop_tracking(&self, entity: &Entity) {
+        let seen_by = entity.tracker.clear();
+        if seen_by.is_empty() {
+            return;
+        }
+        let entity_ids = [entity.entity_id.into()];
+        let packet = CRemoveEntities::new(&entity_ids);
+        for player in self.current_players.lock().await.values() {
+            if seen_by.contains(&player.entity_id()) {
+                player.client.send_packet(&packet).await;
+            }
+        }
+    }
+
+    /// Forgets which entities the player saw, after its client dropped them on a respawn or when
+    /// the player left the world
+    pub async fn forget_tracked_entities(&self, player: &Player) {
+        let player_id = player.entity_id();
+        for tracked in self.tracked_entities().await {
+            tracked.tracked_entity().tracker.stop(player_id);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::{ops::RangeInclusive, sync::Arc};
+
+use pumpkin_core::{
+    math::{boundingbox::BoundingBox, position::WorldPosition, vector3::Vector3},
+    Difficulty, GameMode,
+};
+use pumpkin_protocol::client::play::{CGameEvent, GameEvent};
+use pumpkin_registry::DimensionType;
+use pumpkin_world::{
+    block::block_registry::get_block, damage::DamageType, world_info::game_rules::BoolRule,
+};
+use rand::{thread_rng, Rng};
+
+use crate::{
+    entity::{damage::DamageSource, lightning::LightningBoltEntity},
+    net::Client,
+    server::Server,
+};
+
+use super::World;
+
+/// Ticks the weather stays clear after `/weather clear` without a duration
+pub const CLEAR_DELAY: RangeInclusive<i32> = 12_000..=180_000;
+/// Ticks until it starts raining
+const RAIN_DELAY: RangeInclusive<i32> = 12_000..=180_000;
+/// Ticks it rains
+pub const RAIN_DURATION: RangeInclusive<i32> = 12_000..=24_000;
+/// Ticks until a thunderstorm starts, thunder only rumbles while it rains
+const THUNDER_DELAY: RangeInclusive<i32> = 12_000..=180_000;
+/// Ticks a thunderstorm lasts
+pub const THUNDER_DURATION: RangeInclusive<i32> = 3_600..=15_600;
+
+/// How much rain and thunder fade in or out every tick
+const LEVEL_CHANGE: f32 = 0.01;
+
+/// During thunderstorms every chunk near players is struck with a chance of one in this each tick
+const LIGHTNING_CHANCE: u32 = 100_000;
+
+/// The damage lightning deals
+const LIGHTNING_DAMAGE: f32 = 5.0;
+
+/// The weather of a world, like vanilla's weather fields of `ServerLevelData`
+#[derive(Default)]
+pub struct Weather {
+    /// Ticks the weather stays clear, set by `/weather clear`
+    pub clear_time: i32,
+    /// Ticks until it starts or stops raining
+    pub rain_time: i32,
+    /// Ticks until a thunderstorm starts or stops
+    pub thunder_time: i32,
+    pub raining: bool,
+    pub thundering: bool,
+    /// How strong the rain is, between 0 and 1. Rain fades in and out
+    pub rain_level: f32,
+    /// How strong the thunderstorm is, between 0 and 1
+    pub thunder_level: f32,
+}
+
+impl Weather {
+    /// Whether it rains noticeably, rain starts a while after the weather turned rainy
+    #[must_use]
+    pub fn is_raining(&self) -> bool {
+        self.rain_level > 0.2
+    }
+
+    #[must_use]
+    pub fn is_thundering(&self) -> bool {
+        self.thunder_level * self.rain_level > 0.9
+    }
+
+    /// Counts down until the rain or thunder starts or stops and fades them in or out, like
+    /// vanilla's `ServerLevel.advanceWeatherCycle`. Returns whether the rain level and the thunder
+    /// level changed
+    pub fn tick(&mut self, weather_cycle: bool, rng: &mut impl Rng) -> (bool, bool) {
+        if weather_cycle {
+            if self.clear_time > 0 {
+                self.clear_time -= 1;
+                // Rain and thunder start right after the clear weather
+                self.thunder_time = i32::from(!self.thundering);
+                self.rain_time = i32::from(!self.raining);
+                self.thundering = false;
+                self.raining = false;
+            } else {
+                if self.thunder_time > 0 {
+                    self.thunder_time -= 1;
+                    if self.thunder_time == 0 {
+                        self.thundering = !self.thundering;
+                    }
+                } else if self.thundering {
+                    self.thunder_time = rng.gen_range(THUNDER_DURATION);
+                } else {
+                    self.thunder_time = rng.gen_range(THUNDER_DELAY);
+                }
+                if self.rain_time > 0 {
+                    self.rain_time -= 1;
+                    if self.rain_time == 0 {
+                        self.raining = !self.raining;
+                    }
+                } else if self.raining {
+                    self.rain_time = rng.gen_range(RAIN_DURATION);
+                } else {
+                    self.rain_time = rng.gen_range(RAIN_DELAY);
+                }
+            }
+        }
+        let fade = |level: f32, on: bool| {
+            if on && level < 1.0 {
+                Some((level + LEVEL_CHANGE).min(1.0))
+            } else if !on && level > 0.0 {
+                Some((level - LEVEL_CHANGE).max(0.0))
+            } else {
+                None
+            }
+        };
+        let thunder_level = fade(self.thunder_level, self.thundering);
+        let rain_level = fade(self.rain_level, self.raining);
+        self.thunder_level = thunder_level.unwrap_or(self.thunder_level);
+        self.rain_level = rain_level.unwrap_or(self.rain_level);
+        (rain_level.is_some(), thunder_level.is_some())
+    }
+
+    /// Sets the weather for a while, like vanilla's `ServerLevel.setWeatherParameters`
+    pub fn set(&mut self, clear_time: i32, weather_time: i32, raining: bool, thundering: bool) {
+        self.clear_time = clear_time;
+        self.rain_time = weather_time;
+        self.thunder_time = weather_time;
+        self.raining = raining;
+        self.thundering = thundering;
+    }
+
+    /// Stops the rain and thunder, like after players slept through the night
+    pub fn reset(&mut self) {
+        self.rain_time = 0;
+        self.raining = false;
+        self.thunder_time = 0;
+        self.thundering = false;
+    }
+
+    /// Shows the rain to a player joining the world
+    pub async fn init_client(&self, client: &Client) {
+        if !self.is_raining() {
+            return;
+        }
+        client
+            .send_packet(&CGameEvent::new(GameEvent::BeginRaining, 0.0))
+            .await;
+        client
+            .send_packet(&CGameEvent::new(
+                GameEvent::RainLevelChange,
+                self.rain_level,
+            ))
+            .await;
+        client
+            .send_packet(&CGameEvent::new(
+                GameEvent::ThunderLevelChange,
+                self.thunder_level,
+            ))
+            .await;
+    }
+}
+
+impl World {
+    /// Only dimensions with a sky have weather
+    pub fn has_weather(&self) -> bool {
+        matches!(
+            self.dimension_type,
+            DimensionType::Overworld | DimensionType::OverworldCaves
+        )
+    }
+
+    /// Moves the weather along and tells players when the rain changes. Lightning strikes near
+    /// players during thunderstorms
+    pub(super) async fn tick_weather(self: &Arc<Self>, server: &Server) {
+        self.tick_lightning_bolts().await;
+        if !self.has_weather() {
+            return;
+        }
+        let weather_cycle = self
+            .game_rules
+            .lock()
+            .await
+            .get_bool(BoolRule::DoWeatherCycle);
+        let mut weather = self.weather.lock().await;
+        let was_raining = weather.is_raining();
+        let (rain_changed, thunder_changed) = weather.tick(weather_cycle, &mut thread_rng());
+
+        let mut events = Vec::new();
+        let started_or_stopped = was_raining != weather.is_raining();
+        if started_or_stopped {
+            events.push(if was_raining {
+                (GameEvent::EndRaining, 0.0)
+            } else {
+                (GameEvent::BeginRaining, 0.0)
+            });
+        }
+        if started_or_stopped || rain_changed {
+            events.push((GameEvent::RainLevelChange, weather.rain_level));
+        }
+        if started_or_stopped || thunder_changed {
+            events.push((GameEvent::ThunderLevelChange, weather.thunder_level));
+        }
+        let thundering = weather.is_raining() && weather.is_thundering();
+        drop(weather);
+
+        for (event, value) in events {
+            self.broadcast_packet_all(&CGameEvent::new(event, value))
+                .await;
+        }
+        if thundering {
+            for strike in self.level.lightning_strikes(LIGHTNING_CHANCE) {
+                self.strike_lightning(strike, server).await;
+            }
+        }
+    }
+
+    /// Strikes lightning at the position like vanilla's `LightningBolt`. It sets blocks alight,
+    /// hurts the players it hits and charges creepers
+    pub async fn strike_lightning(self: &Arc<Self>, position: WorldPosition, server: &Server) {
+        let bolt = Arc::new(LightningBoltEntity::new(
+            server.new_entity_id(),
+            self.clone(),
+            position,
+        ));
+        bolt.broadcast_spawn().await;
+        self.lightning_bolts
+            .lock()
+            .await
+            .insert(bolt.entity.entity_id, bolt.clone());
+
+        if matches!(
+            self.difficulty.load(),
+            Difficulty::Normal | Difficulty::Hard
+        ) {
+            if let Some(fire) = get_block("minecraft:fire") {
+                for block in self.level.lightning_fire(position) {
+                    self.set_block_state(block, fire.default_state_id).await;
+                    self.update_neighbors(block, server).await;
+                }
+            }
+        }
+
+        let pos = bolt.entity.pos.load();
+        let reach = BoundingBox::new(
+            Vector3::new(pos.x - 3.0, pos.y - 3.0, pos.z - 3.0),
+            Vector3::new(pos.x + 3.0, pos.y + 9.0, pos.z + 3.0),
+        );
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for player in players {
+            if matches!(
+                player.gamemode.load(),
+                GameMode::Creative | GameMode::Spectator
+            ) || !player
+                .living_entity
+                .entity
+                .bounding_box
+                .load()
+                .intersects(&reach)
+            {
+                continue;
+            }
+            player
+                .hurt(
+                    &DamageSource::new(DamageType::LightningBolt),
+                    LIGHTNING_DAMAGE,
+                )
+                .await;
+        }
+        let mobs: Vec<_> = self.mobs.lock().await.values().cloned().collect();
+        for mob in mobs {
+            if mob.entity().bounding_box.load().intersects(&reach) {
+                mob.thunder_hit();
+            }
+        }
+    }
+
+    async fn tick_lightning_bolts(&self) {
+        let bolts: Vec<_> = self
+            .lightning_bolts
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for bolt in bolts {
+            bolt.tick().await;
+        }
+    }
+
+    /// Removes the lightning bolt from the world and despawns it for every player
+    pub async fn remove_lightning_bolt(&self, bolt: &LightningBoltEntity) {
+        self.lightning_bolts
+            .lock()
+            .await
+            .remove(&bolt.entity.entity_id);
+        self.remove_entity(&bolt.entity).await;
+    }
+}
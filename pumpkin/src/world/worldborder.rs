@@ -1,22 +1,39 @@
+use pumpkin_core::{
+    math::{position::WorldPosition, vector3::Vector3},
+    GameMode,
+};
 use pumpkin_protocol::client::play::{
     CInitializeWorldBorder, CSetBorderCenter, CSetBorderLerpSize, CSetBorderSize,
     CSetBorderWarningDelay, CSetBorderWarningDistance,
 };
+use pumpkin_world::damage::DamageType;
 
-use crate::net::Client;
+use crate::{entity::damage::DamageSource, net::Client};
 
 use super::World;
 
+/// Milliseconds a tick moves a growing or shrinking border along
+const MILLIS_PER_TICK: i64 = 50;
+
+/// Positions further out than this are never inside of the border
+const MAX_DISTANCE: f64 = 29_999_984.0;
+
 pub struct Worldborder {
     pub center_x: f64,
     pub center_z: f64,
+    /// The diameter the border grows or shrinks from
     pub old_diameter: f64,
+    /// The diameter the border grows or shrinks to, the current diameter when it doesn't move
     pub new_diameter: f64,
+    /// Milliseconds until the border reaches the new diameter
     pub speed: i64,
+    /// Milliseconds the border takes to move from the old to the new diameter
+    pub lerp_time: i64,
     pub portal_teleport_boundary: i32,
     pub warning_blocks: i32,
     pub warning_time: i32,
     pub damage_per_block: f32,
+    /// Blocks players can be outside of the border without taking damage
     pub buffer: f32,
 }
 
@@ -36,11 +53,97 @@ impl Worldborder {
             old_diameter: diameter,
             new_diameter: diameter,
             speed,
+            lerp_time: speed,
             portal_teleport_boundary: 29_999_984,
             warning_blocks,
             warning_time,
-            damage_per_block: 0.0,
-            buffer: 0.0,
+            damage_per_block: 0.2,
+            buffer: 5.0,
+        }
+    }
+
+    /// The diameter right now, moving borders are between their old and new diameter
+    #[must_use]
+    pub fn diameter(&self) -> f64 {
+        if self.speed <= 0 || self.lerp_time <= 0 {
+            return self.new_diameter;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let progress = 1.0 - self.speed as f64 / self.lerp_time as f64;
+        self.old_diameter + (self.new_diameter - self.old_diameter) * progress
+    }
+
+    fn bounds(&self) -> (f64, f64, f64, f64) {
+        let radius = self.diameter() / 2.0;
+        (
+            (self.center_x - radius).max(-MAX_DISTANCE),
+            (self.center_z - radius).max(-MAX_DISTANCE),
+            (self.center_x + radius).min(MAX_DISTANCE),
+            (self.center_z + radius).min(MAX_DISTANCE),
+        )
+    }
+
+    /// Whether the position is inside of the border
+    #[must_use]
+    pub fn contains(&self, x: f64, z: f64) -> bool {
+        let (min_x, min_z, max_x, max_z) = self.bounds();
+        x >= min_x && x < max_x && z >= min_z && z < max_z
+    }
+
+    /// Whether any part of the block is inside of the border, players can't build in blocks
+    /// which are not
+    #[must_use]
+    pub fn contains_block(&self, position: &WorldPosition) -> bool {
+        let (min_x, min_z, max_x, max_z) = self.bounds();
+        let (x, z) = (f64::from(position.0.x), f64::from(position.0.z));
+        x + 1.0 > min_x && x < max_x && z + 1.0 > min_z && z < max_z
+    }
+
+    /// Distance to the closest edge of the border, negative outside of it
+    #[must_use]
+    pub fn distance_inside(&self, x: f64, z: f64) -> f64 {
+        let (min_x, min_z, max_x, max_z) = self.bounds();
+        (x - min_x).min(max_x - x).min(z - min_z).min(max_z - z)
+    }
+
+    /// Moves positions outside of the border to its closest edge
+    #[must_use]
+    pub fn clamp(&self, position: Vector3<f64>) -> Vector3<f64> {
+        let (min_x, min_z, max_x, max_z) = self.bounds();
+        Vector3::new(
+            position.x.clamp(min_x, (max_x - 1.0E-5).max(min_x)),
+            position.y,
+            position.z.clamp(min_z, (max_z - 1.0E-5).max(min_z)),
+        )
+    }
+
+    /// The damage players take at the position, they take damage when they are further outside
+    /// of the border than the buffer
+    #[must_use]
+    pub fn damage_at(&self, x: f64, z: f64) -> Option<f32> {
+        if self.contains(x, z) || self.damage_per_block <= 0.0 {
+            return None;
+        }
+        let distance = self.distance_inside(x, z) + f64::from(self.buffer);
+        if distance >= 0.0 {
+            return None;
+        }
+        Some(
+            (-distance * f64::from(self.damage_per_block))
+                .floor()
+                .max(1.0) as f32,
+        )
+    }
+
+    /// Moves a growing or shrinking border along, players move their border themselves
+    pub fn tick(&mut self) {
+        if self.speed <= 0 {
+            return;
+        }
+        self.speed -= MILLIS_PER_TICK;
+        if self.speed <= 0 {
+            self.speed = 0;
+            self.old_diameter = self.new_diameter;
         }
     }
 
@@ -49,7 +152,7 @@ impl Worldborder {
             .send_packet(&CInitializeWorldBorder::new(
                 self.center_x,
                 self.center_z,
-                self.old_diameter,
+                self.diameter(),
                 self.new_diameter,
                 self.speed.into(),
                 self.portal_teleport_boundary.into(),
@@ -68,25 +171,29 @@ impl Worldborder {
             .await;
     }
 
+    /// Moves the border to the diameter over `speed` milliseconds, or right away without a speed
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
+        // Moving borders move on from where they are right now
+        self.old_diameter = self.diameter();
         self.new_diameter = diameter;
 
-        match speed {
-            Some(speed) => {
-                world
-                    .broadcast_packet_all(&CSetBorderLerpSize::new(
-                        self.old_diameter,
-                        self.new_diameter,
-                        speed.into(),
-                    ))
-                    .await;
-            }
-            None => {
-                world
-                    .broadcast_packet_all(&CSetBorderSize::new(self.new_diameter))
-                    .await;
-            }
+        if let Some(speed) = speed.filter(|speed| *speed > 0) {
+            self.speed = speed;
+            self.lerp_time = speed;
+            world
+                .broadcast_packet_all(&CSetBorderLerpSize::new(
+                    self.old_diameter,
+                    self.new_diameter,
+                    speed.into(),
+                ))
+                .await;
+        } else {
+            self.old_diameter = diameter;
+            self.speed = 0;
+            self.lerp_time = 0;
+            world
+                .broadcast_packet_all(&CSetBorderSize::new(self.new_diameter))
+                .await;
         }
     }
 
@@ -111,3 +218,79 @@ impl Worldborder {
             .await;
     }
 }
+
+impl World {
+    /// Moves the border along and hurts players outside of it, like vanilla's
+    /// `LivingEntity.baseTick`
+    pub(super) async fn tick_worldborder(&self) {
+        let players: Vec<_> = self
+            .current_players
+            .lock()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        let damaged: Vec<_> = {
+            let mut border = self.worldborder.lock().await;
+            border.tick();
+            players
+                .into_iter()
+                .filter(|player| {
+                    !matches!(
+                        player.gamemode.load(),
+                        GameMode::Creative | GameMode::Spectator
+                    )
+                })
+                .filter_map(|player| {
+                    let pos = player.living_entity.entity.pos.load();
+                    Some((border.damage_at(pos.x, pos.z)?, player))
+                })
+                .collect()
+        };
+        for (damage, player) in damaged {
+            player
+                .hurt(&DamageSource::new(DamageType::OutsideBorder), damage)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_core::math::{position::WorldPosition, vector3::Vector3};
+
+    use super::Worldborder;
+
+    #[test]
+    fn moving_borders_reach_their_new_diameter() {
+        let mut border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        border.new_diameter = 200.0;
+        border.speed = 1000;
+        border.lerp_time = 1000;
+        assert!((border.diameter() - 100.0).abs() < 1.0E-9);
+        (0..10).for_each(|_| border.tick());
+        assert!((border.diameter() - 150.0).abs() < 1.0E-9);
+        (0..10).for_each(|_| border.tick());
+        assert_eq!(border.speed, 0);
+        assert!((border.diameter() - 200.0).abs() < 1.0E-9);
+    }
+
+    #[test]
+    fn players_outside_of_the_buffer_take_damage() {
+        let border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        assert_eq!(border.damage_at(0.0, 0.0), None);
+        assert_eq!(border.damage_at(52.0, 0.0), None);
+        assert_eq!(border.damage_at(60.0, 0.0), Some(1.0));
+        assert_eq!(border.damage_at(0.0, -70.0), Some(3.0));
+    }
+
+    #[test]
+    fn blocks_on_the_edge_are_outside() {
+        let border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        assert!(border.contains_block(&WorldPosition(Vector3::new(49, 64, -50))));
+        assert!(!border.contains_block(&WorldPosition(Vector3::new(50, 64, 0))));
+        assert!(!border.contains_block(&WorldPosition(Vector3::new(-51, 64, 0))));
+        let clamped = border.clamp(Vector3::new(80.0, 70.0, -80.0));
+        assert!((clamped.z + 50.0).abs() < f64::EPSILON);
+    }
+}